@@ -16,6 +16,7 @@ use crate::domain::auth::{
         login_minecraft_with_xbox, read_minecraft_profile,
     },
 };
+use crate::infrastructure::http::tls;
 
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -294,7 +295,7 @@ pub async fn complete_microsoft_auth(
         return Err("El código de autorización de Microsoft está vacío.".to_string());
     }
 
-    let client = reqwest::Client::new();
+    let client = tls::default_async_client();
     let microsoft_tokens = exchange_authorization_code(&client, &code, &code_verifier).await?;
     finalize_microsoft_tokens(&client, microsoft_tokens).await
 }
@@ -316,7 +317,7 @@ pub async fn complete_microsoft_device_auth(
 pub async fn refresh_microsoft_auth(
     microsoft_refresh_token: String,
 ) -> Result<MicrosoftAuthResult, String> {
-    let client = reqwest::Client::new();
+    let client = tls::default_async_client();
     let refreshed = refresh_microsoft_access_token(&client, &microsoft_refresh_token).await?;
     finalize_microsoft_tokens(&client, refreshed).await
 }
@@ -0,0 +1,363 @@
+// Mantenimiento en lote sobre varias instancias.
+
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Mutex,
+};
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::{
+    app::{
+        instance_backup::backup_instance,
+        instance_cleanup::cleanup_instance,
+        instance_service::{compute_instance_health, force_close_instance, is_instance_running},
+        launcher_service::list_instances_readonly,
+        trusted_root::resolve_trusted_instance_root,
+    },
+    domain::models::instance::InstanceSummary,
+    shared::{
+        clock::app_clock,
+        tasks::{TaskHandle, TaskProbe},
+    },
+};
+
+/// Hilos de la verificación rápida; el resto de operaciones es secuencial.
+const VERIFY_PARALLELISM: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchOperation {
+    CloseAllRunning,
+    BackupAll,
+    VerifyAll,
+    CleanupAll,
+}
+
+impl BatchOperation {
+    fn key(self) -> &'static str {
+        match self {
+            Self::CloseAllRunning => "close_all_running",
+            Self::BackupAll => "backup_all",
+            Self::VerifyAll => "verify_all",
+            Self::CleanupAll => "cleanup_all",
+        }
+    }
+
+    /// Escribe en la carpeta de juego: no puede correr con el juego abierto.
+    fn requires_exclusive(self) -> bool {
+        matches!(self, Self::BackupAll | Self::CleanupAll)
+    }
+
+    fn parallelism(self) -> usize {
+        if self == Self::VerifyAll {
+            VERIFY_PARALLELISM
+        } else {
+            1
+        }
+    }
+}
+
+/// Sin filtro se procesan todas. `tags` exige todas las etiquetas indicadas.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct BatchInstanceFilter {
+    pub group: Option<String>,
+    pub tags: Vec<String>,
+}
+
+impl BatchInstanceFilter {
+    fn matches(&self, instance: &InstanceSummary) -> bool {
+        let group_mismatch = self
+            .group
+            .as_deref()
+            .map(str::trim)
+            .filter(|group| !group.is_empty())
+            .is_some_and(|group| !instance.group.trim().eq_ignore_ascii_case(group));
+        !group_mismatch
+            && self.tags.iter().all(|tag| {
+                let tag = tag.trim().to_lowercase();
+                tag.is_empty() || instance.tags.contains(&tag)
+            })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchInstanceResult {
+    pub instance_root: String,
+    pub name: String,
+    /// `success`, `failed` o `skipped`.
+    pub status: String,
+    /// Motivo estable para la UI: `running`, `not_running`, `backup_disabled`,
+    /// `needs_recovery`, `cancelled` o el código del error.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchOperationReport {
+    pub operation: BatchOperation,
+    pub succeeded: usize,
+    pub failed: usize,
+    pub skipped: usize,
+    pub results: Vec<BatchInstanceResult>,
+}
+
+fn outcome(
+    instance: &InstanceSummary,
+    status: &str,
+    code: Option<&str>,
+    message: Option<String>,
+) -> BatchInstanceResult {
+    BatchInstanceResult {
+        instance_root: instance.instance_root.clone(),
+        name: instance.name.clone(),
+        status: status.to_string(),
+        code: code.map(str::to_string),
+        message,
+    }
+}
+
+/// Motivo para no tocar la instancia antes de ejecutar la operación.
+fn skip_reason(
+    operation: BatchOperation,
+    instance: &InstanceSummary,
+    running: bool,
+) -> Option<&'static str> {
+    if instance.state.is_some() {
+        return Some("needs_recovery");
+    }
+    match operation {
+        BatchOperation::CloseAllRunning if !running => Some("not_running"),
+        _ if running && operation.requires_exclusive() => Some("running"),
+        _ => None,
+    }
+}
+
+fn run_one(
+    app: &AppHandle,
+    operation: BatchOperation,
+    instance: &InstanceSummary,
+) -> BatchInstanceResult {
+    let root = instance.instance_root.as_str();
+    if let Some(reason) = skip_reason(operation, instance, is_instance_running(root)) {
+        return outcome(instance, "skipped", Some(reason), None);
+    }
+    match operation {
+        BatchOperation::CloseAllRunning => {
+            match force_close_instance(app.clone(), root.to_string()) {
+                Ok(message) => outcome(instance, "success", None, Some(message)),
+                Err(err) => outcome(instance, "failed", Some("close_failed"), Some(err)),
+            }
+        }
+        BatchOperation::BackupAll => match backup_instance(app, root) {
+            Ok(Some(report)) => outcome(instance, "success", None, Some(report.archive_path)),
+            Ok(None) => outcome(instance, "skipped", Some("backup_disabled"), None),
+            Err(err) => outcome(instance, "failed", Some("backup_failed"), Some(err)),
+        },
+        BatchOperation::VerifyAll => {
+            let health = compute_instance_health(root, app_clock(app).clock.as_ref());
+            match health
+                .findings
+                .iter()
+                .find(|finding| finding.severity == "error")
+            {
+                Some(finding) => outcome(
+                    instance,
+                    "failed",
+                    Some(&finding.code),
+                    Some(finding.message.clone()),
+                ),
+                None => outcome(instance, "success", None, Some(health.status)),
+            }
+        }
+        BatchOperation::CleanupAll => match resolve_trusted_instance_root(app, root)
+            .and_then(|validated| cleanup_instance(app, &validated))
+        {
+            Ok(report) => outcome(
+                instance,
+                "success",
+                None,
+                Some(format!(
+                    "{} archivos, {} KB liberados",
+                    report.removed.len(),
+                    report.bytes_freed / 1024
+                )),
+            ),
+            Err(err) => outcome(instance, "failed", Some("cleanup_failed"), Some(err)),
+        },
+    }
+}
+
+fn run_batch(
+    app: &AppHandle,
+    operation: BatchOperation,
+    instances: &[InstanceSummary],
+    task: &TaskProbe,
+) -> Vec<Option<BatchInstanceResult>> {
+    let total = instances.len() as u64;
+    let next = AtomicUsize::new(0);
+    let done = AtomicUsize::new(0);
+    let results = Mutex::new(vec![None; instances.len()]);
+    let worker = || loop {
+        // Punto seguro: entre instancias; la que está en curso termina.
+        if task.is_cancelled() {
+            break;
+        }
+        let index = next.fetch_add(1, Ordering::SeqCst);
+        let Some(instance) = instances.get(index) else {
+            break;
+        };
+        let result = run_one(app, operation, instance);
+        let completed = done.fetch_add(1, Ordering::SeqCst) as u64 + 1;
+        task.progress_with_message(completed, Some(total), "items", Some(instance.name.clone()));
+        task.emit_event("batch_operation_instance", &result);
+        results
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())[index] = Some(result);
+    };
+    std::thread::scope(|scope| {
+        for _ in 1..operation.parallelism().min(instances.len()) {
+            scope.spawn(worker);
+        }
+        worker();
+    });
+    results
+        .into_inner()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn summarize(
+    operation: BatchOperation,
+    instances: &[InstanceSummary],
+    results: Vec<Option<BatchInstanceResult>>,
+) -> BatchOperationReport {
+    let results = instances
+        .iter()
+        .zip(results)
+        .map(|(instance, result)| {
+            result.unwrap_or_else(|| outcome(instance, "skipped", Some("cancelled"), None))
+        })
+        .collect::<Vec<_>>();
+    let count = |status: &str| {
+        results
+            .iter()
+            .filter(|result| result.status == status)
+            .count()
+    };
+    BatchOperationReport {
+        operation,
+        succeeded: count("success"),
+        failed: count("failed"),
+        skipped: count("skipped"),
+        results,
+    }
+}
+
+/// Aplica `operation` a las instancias que cumplen `instance_filter`; un fallo queda en su
+/// resultado y el lote sigue.
+#[tauri::command]
+pub async fn run_batch_operation(
+    app: AppHandle,
+    operation: BatchOperation,
+    instance_filter: Option<BatchInstanceFilter>,
+) -> Result<BatchOperationReport, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let filter = instance_filter.unwrap_or_default();
+        let instances = list_instances_readonly(&app)?
+            .into_iter()
+            .filter(|instance| filter.matches(instance))
+            .collect::<Vec<_>>();
+        let task = TaskHandle::begin(
+            &app,
+            "batch_operation",
+            format!("{} ({} instancias)", operation.key(), instances.len()),
+        );
+        let results = run_batch(&app, operation, &instances, &task.probe());
+        let report = summarize(operation, &instances, results);
+        log::info!(
+            "✔ Lote {}: {} correctas, {} fallidas, {} omitidas",
+            operation.key(),
+            report.succeeded,
+            report.failed,
+            report.skipped
+        );
+        let result = Ok(report);
+        task.finish(&result);
+        result
+    })
+    .await
+    .map_err(|err| format!("Falló la tarea de operación en lote: {err}"))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn instance(name: &str, group: &str, tags: &[&str]) -> InstanceSummary {
+        InstanceSummary {
+            id: name.to_string(),
+            name: name.to_string(),
+            group: group.to_string(),
+            instance_root: format!("/instancias/{name}"),
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            health: None,
+            state: None,
+        }
+    }
+
+    #[test]
+    fn filters_by_group_and_all_requested_tags() {
+        let survival = instance("Survival", "Vanilla", &["modded", "amigos"]);
+        let creative = instance("Creativo", "Vanilla", &["amigos"]);
+        let filter = BatchInstanceFilter {
+            group: Some(" vanilla ".to_string()),
+            tags: vec!["Modded".to_string(), "amigos".to_string()],
+        };
+        assert!(filter.matches(&survival));
+        assert!(!filter.matches(&creative));
+        assert!(BatchInstanceFilter::default().matches(&creative));
+    }
+
+    #[test]
+    fn skips_running_instances_only_for_exclusive_operations() {
+        let ready = instance("Survival", "Vanilla", &[]);
+        assert_eq!(
+            skip_reason(BatchOperation::BackupAll, &ready, true),
+            Some("running")
+        );
+        assert_eq!(
+            skip_reason(BatchOperation::CleanupAll, &ready, true),
+            Some("running")
+        );
+        assert_eq!(skip_reason(BatchOperation::VerifyAll, &ready, true), None);
+        assert_eq!(
+            skip_reason(BatchOperation::CloseAllRunning, &ready, false),
+            Some("not_running")
+        );
+        let recovering = InstanceSummary {
+            state: Some("NEEDS_RECOVERY".to_string()),
+            ..instance("Roto", "Vanilla", &[])
+        };
+        assert_eq!(
+            skip_reason(BatchOperation::VerifyAll, &recovering, false),
+            Some("needs_recovery")
+        );
+
+        let report = summarize(
+            BatchOperation::BackupAll,
+            &[instance("Survival", "Vanilla", &[]), recovering],
+            vec![
+                Some(outcome(&ready, "failed", Some("backup_failed"), None)),
+                None,
+            ],
+        );
+        assert_eq!((report.failed, report.skipped), (1, 1));
+        assert_eq!(report.results[1].code.as_deref(), Some("cancelled"));
+    }
+}
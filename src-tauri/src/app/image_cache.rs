@@ -0,0 +1,394 @@
+use std::{
+    collections::HashMap,
+    fs,
+    io::Read,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, OnceLock},
+    time::SystemTime,
+};
+
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::infrastructure::{
+    checksum::sha1::sha1_hex,
+    downloader::client::build_http_client,
+    filesystem::{config::load_launcher_config, paths::resolve_launcher_root},
+};
+
+const IMAGE_CACHE_DIR: &str = "cache/images";
+/// Límite por defecto de la caché de imágenes (`image_cache_max_mb` en launcher_config.json).
+pub const DEFAULT_IMAGE_CACHE_MAX_MB: u64 = 200;
+const DEFAULT_MAX_IMAGE_BYTES: u64 = 10 * 1024 * 1024;
+const ALLOWED_CONTENT_TYPES: [(&str, &str); 4] = [
+    ("image/png", "png"),
+    ("image/jpeg", "jpg"),
+    ("image/jpg", "jpg"),
+    ("image/webp", "webp"),
+];
+
+/// Una descarga en curso por URL: las peticiones concurrentes esperan a la primera y
+/// después leen el archivo ya cacheado.
+static IN_FLIGHT: OnceLock<Mutex<HashMap<String, Arc<Mutex<()>>>>> = OnceLock::new();
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CachedImage {
+    pub path: String,
+    pub width: u32,
+    pub height: u32,
+    pub size_bytes: u64,
+    pub from_cache: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClearImageCacheResult {
+    pub removed_files: usize,
+    pub freed_bytes: u64,
+}
+
+pub(crate) fn image_cache_root(launcher_root: &Path) -> PathBuf {
+    launcher_root.join(IMAGE_CACHE_DIR)
+}
+
+pub(crate) fn configured_cap_bytes(app: &AppHandle) -> u64 {
+    load_launcher_config(app)
+        .ok()
+        .and_then(|config| config.image_cache_max_mb)
+        .filter(|mb| *mb > 0)
+        .unwrap_or(DEFAULT_IMAGE_CACHE_MAX_MB)
+        * 1024
+        * 1024
+}
+
+fn extension_for_content_type(content_type: &str) -> Option<&'static str> {
+    let mime = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    ALLOWED_CONTENT_TYPES
+        .iter()
+        .find(|(allowed, _)| *allowed == mime)
+        .map(|(_, ext)| *ext)
+}
+
+/// Lee ancho y alto de la cabecera de un PNG, JPEG o WebP sin decodificar la imagen.
+pub fn image_dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+    let be32 = |at: usize| -> Option<u32> {
+        Some(u32::from_be_bytes(bytes.get(at..at + 4)?.try_into().ok()?))
+    };
+    let le16 = |at: usize| -> Option<u32> {
+        Some(u16::from_le_bytes(bytes.get(at..at + 2)?.try_into().ok()?) as u32)
+    };
+    let le24 = |at: usize| -> Option<u32> {
+        let raw = bytes.get(at..at + 3)?;
+        Some(raw[0] as u32 | (raw[1] as u32) << 8 | (raw[2] as u32) << 16)
+    };
+
+    if bytes.starts_with(b"\x89PNG\r\n\x1a\n") && bytes.get(12..16) == Some(b"IHDR") {
+        return Some((be32(16)?, be32(20)?));
+    }
+
+    if bytes.starts_with(&[0xFF, 0xD8]) {
+        let mut at = 2;
+        while at + 9 < bytes.len() {
+            if bytes[at] != 0xFF {
+                return None;
+            }
+            let marker = bytes[at + 1];
+            let length = u16::from_be_bytes([bytes[at + 2], bytes[at + 3]]) as usize;
+            // SOF0..SOF15 salvo DHT (C4), JPG (C8) y DAC (CC).
+            if (0xC0..=0xCF).contains(&marker) && !matches!(marker, 0xC4 | 0xC8 | 0xCC) {
+                let height = u16::from_be_bytes([bytes[at + 5], bytes[at + 6]]) as u32;
+                let width = u16::from_be_bytes([bytes[at + 7], bytes[at + 8]]) as u32;
+                return Some((width, height));
+            }
+            at += 2 + length;
+        }
+        return None;
+    }
+
+    if bytes.starts_with(b"RIFF") && bytes.get(8..12) == Some(b"WEBP") {
+        return match bytes.get(12..16)? {
+            b"VP8 " => Some((le16(26)? & 0x3FFF, le16(28)? & 0x3FFF)),
+            b"VP8L" => {
+                let bits = u32::from_le_bytes(bytes.get(21..25)?.try_into().ok()?);
+                Some(((bits & 0x3FFF) + 1, ((bits >> 14) & 0x3FFF) + 1))
+            }
+            b"VP8X" => Some((le24(24)? + 1, le24(27)? + 1)),
+            _ => None,
+        };
+    }
+
+    None
+}
+
+fn read_header(path: &Path) -> Option<(u32, u32)> {
+    let mut header = vec![0u8; 64 * 1024];
+    let mut file = fs::File::open(path).ok()?;
+    let read = file.read(&mut header).ok()?;
+    image_dimensions(&header[..read])
+}
+
+fn find_cached(cache_root: &Path, key: &str) -> Option<PathBuf> {
+    ["png", "jpg", "webp"]
+        .iter()
+        .map(|ext| cache_root.join(format!("{key}.{ext}")))
+        .find(|path| path.is_file())
+}
+
+/// Marca el archivo como usado recientemente (la expulsión LRU ordena por mtime).
+fn touch(path: &Path) {
+    if let Ok(file) = fs::File::options().append(true).open(path) {
+        let _ = file.set_modified(SystemTime::now());
+    }
+}
+
+/// Elimina las imágenes usadas hace más tiempo hasta dejar la caché por debajo de `cap_bytes`.
+/// `keep` nunca se expulsa: es la imagen recién guardada cuya ruta se va a devolver.
+pub fn evict_lru(cache_root: &Path, cap_bytes: u64, keep: Option<&Path>) -> ClearImageCacheResult {
+    let mut files = fs::read_dir(cache_root)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let meta = entry.metadata().ok()?;
+            meta.is_file().then(|| {
+                (
+                    entry.path(),
+                    meta.len(),
+                    meta.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+                )
+            })
+        })
+        .collect::<Vec<_>>();
+    files.sort_by_key(|(_, _, modified)| *modified);
+
+    let mut total = files.iter().map(|(_, size, _)| size).sum::<u64>();
+    let mut result = ClearImageCacheResult {
+        removed_files: 0,
+        freed_bytes: 0,
+    };
+    for (path, size, _) in files {
+        if total <= cap_bytes {
+            break;
+        }
+        if keep == Some(path.as_path()) {
+            continue;
+        }
+        if fs::remove_file(&path).is_ok() {
+            total = total.saturating_sub(size);
+            result.removed_files += 1;
+            result.freed_bytes += size;
+        }
+    }
+    result
+}
+
+fn download_image(url: &str, max_bytes: u64) -> Result<(Vec<u8>, &'static str), String> {
+    let client = build_http_client()?;
+    let response = client
+        .get(url)
+        .send()
+        .map_err(|err| format!("No se pudo descargar la imagen {url}: {err}"))?;
+    if !response.status().is_success() {
+        return Err(format!(
+            "La imagen {url} respondió HTTP {}.",
+            response.status()
+        ));
+    }
+
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default()
+        .to_string();
+    let ext = extension_for_content_type(&content_type).ok_or_else(|| {
+        format!("Tipo de contenido no permitido para imágenes: '{content_type}' (solo png, jpeg o webp).")
+    })?;
+    if response.content_length().is_some_and(|len| len > max_bytes) {
+        return Err(format!(
+            "La imagen {url} supera el límite de {max_bytes} bytes."
+        ));
+    }
+
+    let mut bytes = Vec::new();
+    response
+        .take(max_bytes + 1)
+        .read_to_end(&mut bytes)
+        .map_err(|err| format!("No se pudo leer la imagen {url}: {err}"))?;
+    if bytes.len() as u64 > max_bytes {
+        return Err(format!(
+            "La imagen {url} supera el límite de {max_bytes} bytes."
+        ));
+    }
+    Ok((bytes, ext))
+}
+
+fn load_or_download(
+    cache_root: &Path,
+    key: &str,
+    url: &str,
+    max_bytes: u64,
+    cap_bytes: u64,
+) -> Result<CachedImage, String> {
+    if let Some(path) = find_cached(cache_root, key) {
+        if let Some((width, height)) = read_header(&path) {
+            touch(&path);
+            return Ok(CachedImage {
+                size_bytes: fs::metadata(&path).map(|meta| meta.len()).unwrap_or(0),
+                path: path.display().to_string(),
+                width,
+                height,
+                from_cache: true,
+            });
+        }
+        let _ = fs::remove_file(&path);
+    }
+
+    let (bytes, ext) = download_image(url, max_bytes)?;
+    let (width, height) = image_dimensions(&bytes)
+        .ok_or_else(|| format!("La imagen {url} no tiene una cabecera png/jpeg/webp válida."))?;
+
+    fs::create_dir_all(cache_root).map_err(|err| {
+        format!(
+            "No se pudo crear la caché de imágenes {}: {err}",
+            cache_root.display()
+        )
+    })?;
+    let path = cache_root.join(format!("{key}.{ext}"));
+    let tmp = cache_root.join(format!("{key}.{ext}.part"));
+    fs::write(&tmp, &bytes)
+        .and_then(|_| fs::rename(&tmp, &path))
+        .map_err(|err| format!("No se pudo guardar la imagen {}: {err}", path.display()))?;
+    evict_lru(cache_root, cap_bytes, Some(&path));
+
+    Ok(CachedImage {
+        path: path.display().to_string(),
+        width,
+        height,
+        size_bytes: bytes.len() as u64,
+        from_cache: false,
+    })
+}
+
+/// Devuelve la imagen desde `cache/images/<sha1-url>.<ext>` o la descarga y la guarda.
+pub fn fetch_image_cached(
+    cache_root: &Path,
+    url: &str,
+    max_bytes: u64,
+    cap_bytes: u64,
+) -> Result<CachedImage, String> {
+    let key = sha1_hex(url.as_bytes());
+    let flight = {
+        let mut in_flight = IN_FLIGHT
+            .get_or_init(|| Mutex::new(HashMap::new()))
+            .lock()
+            .map_err(|_| "No se pudo bloquear la caché de imágenes.".to_string())?;
+        in_flight.entry(key.clone()).or_default().clone()
+    };
+    let _guard = flight
+        .lock()
+        .map_err(|_| "No se pudo bloquear la descarga de la imagen.".to_string())?;
+
+    let result = load_or_download(cache_root, &key, url, max_bytes, cap_bytes);
+
+    if let Ok(mut in_flight) = IN_FLIGHT.get_or_init(|| Mutex::new(HashMap::new())).lock() {
+        if Arc::strong_count(&flight) <= 2 {
+            in_flight.remove(&key);
+        }
+    }
+    result
+}
+
+/// Descarga una imagen (icono o captura de un modpack) fuera del webview y devuelve su
+/// ruta local con las dimensiones.
+#[tauri::command]
+pub async fn fetch_remote_image(
+    app: AppHandle,
+    url: String,
+    max_bytes: Option<u64>,
+) -> Result<CachedImage, String> {
+    if !url.starts_with("https://") && !url.starts_with("http://") {
+        return Err(format!("URL de imagen no válida: {url}"));
+    }
+    let cache_root = image_cache_root(&resolve_launcher_root(&app)?);
+    let cap_bytes = configured_cap_bytes(&app);
+    let max_bytes = max_bytes
+        .filter(|value| *value > 0)
+        .unwrap_or(DEFAULT_MAX_IMAGE_BYTES);
+    tauri::async_runtime::spawn_blocking(move || {
+        fetch_image_cached(&cache_root, &url, max_bytes, cap_bytes)
+    })
+    .await
+    .map_err(|err| format!("Falló la tarea de descarga de imagen: {err}"))?
+}
+
+#[tauri::command]
+pub fn clear_image_cache(app: AppHandle) -> Result<ClearImageCacheResult, String> {
+    let cache_root = image_cache_root(&resolve_launcher_root(&app)?);
+    let result = evict_lru(&cache_root, 0, None);
+    log::info!(
+        "🔹 Caché de imágenes limpiada: {} archivos, {} bytes",
+        result.removed_files,
+        result.freed_bytes
+    );
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_dimensions_from_png_jpeg_and_webp_headers() {
+        let mut png = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+        png.extend_from_slice(&640u32.to_be_bytes());
+        png.extend_from_slice(&360u32.to_be_bytes());
+        assert_eq!(image_dimensions(&png), Some((640, 360)));
+
+        let jpeg = [
+            0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x04, 0x00, 0x00, 0xFF, 0xC0, 0x00, 0x11, 0x08, 0x00,
+            0x78, 0x00, 0xA0, 0x03,
+        ];
+        assert_eq!(image_dimensions(&jpeg), Some((160, 120)));
+
+        let mut webp = b"RIFF\0\0\0\0WEBPVP8X\x0a\0\0\0\0\0\0\0".to_vec();
+        webp.extend_from_slice(&[0x3F, 0x01, 0x00, 0xEF, 0x00, 0x00]);
+        assert_eq!(image_dimensions(&webp), Some((320, 240)));
+
+        assert_eq!(image_dimensions(b"<html>"), None);
+    }
+
+    #[test]
+    fn eviction_removes_least_recently_used_files_first() {
+        let root =
+            std::env::temp_dir().join(format!("interface-image-cache-{}", std::process::id()));
+        fs::create_dir_all(&root).expect("cache dir");
+        for (name, age_secs) in [("old.png", 300), ("mid.png", 200), ("new.png", 100)] {
+            let path = root.join(name);
+            fs::write(&path, vec![0u8; 100]).expect("image");
+            let file = fs::File::options().append(true).open(&path).expect("open");
+            file.set_modified(SystemTime::now() - std::time::Duration::from_secs(age_secs))
+                .expect("mtime");
+        }
+
+        let result = evict_lru(&root, 200, None);
+
+        assert_eq!(result.removed_files, 1);
+        assert!(!root.join("old.png").exists());
+        assert!(root.join("mid.png").exists() && root.join("new.png").exists());
+
+        // Una imagen mayor que el tope no se expulsa a sí misma al guardarse.
+        let huge = root.join("huge.png");
+        fs::write(&huge, vec![0u8; 500]).expect("huge");
+        evict_lru(&root, 200, Some(&huge));
+        assert!(huge.exists());
+        assert!(!root.join("mid.png").exists() && !root.join("new.png").exists());
+        let _ = fs::remove_dir_all(root);
+    }
+}
@@ -0,0 +1,287 @@
+use std::{
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use serde::Serialize;
+use tauri::AppHandle;
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipWriter};
+
+use crate::{
+    app::{
+        instance_service::{is_instance_running, read_instance_metadata, update_instance_metadata},
+        redirect_launch::redirect_game_dir,
+        trusted_root::resolve_trusted_instance_root,
+    },
+    domain::models::instance::{BackupSettings, InstanceMetadata},
+    infrastructure::filesystem::paths::resolve_launcher_root,
+    shared::{clock::app_clock, result::AppResult},
+};
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupReport {
+    pub archive_path: String,
+    pub files: u64,
+    pub bytes: u64,
+    /// Copias antiguas borradas por `keep_last`.
+    pub pruned: Vec<String>,
+}
+
+/// Carpeta de juego que se copia; en las REDIRECT es la del launcher de origen, que solo
+/// se lee.
+fn backup_source_dir(instance_root: &Path, metadata: &InstanceMetadata) -> Option<PathBuf> {
+    if metadata.state.eq_ignore_ascii_case("redirect") {
+        return redirect_game_dir(instance_root);
+    }
+    let game_dir = instance_root.join("minecraft");
+    game_dir.is_dir().then_some(game_dir)
+}
+
+fn add_path(
+    zip: &mut ZipWriter<fs::File>,
+    game_dir: &Path,
+    path: &Path,
+    options: SimpleFileOptions,
+    report: &mut BackupReport,
+) -> Result<(), String> {
+    let Ok(meta) = fs::symlink_metadata(path) else {
+        return Ok(());
+    };
+    let relative = path
+        .strip_prefix(game_dir)
+        .map_err(|err| format!("Ruta relativa inválida: {err}"))?
+        .to_string_lossy()
+        .replace('\\', "/");
+    if meta.is_dir() {
+        zip.add_directory(format!("{relative}/"), options)
+            .map_err(|err| format!("No se pudo agregar carpeta a la copia: {err}"))?;
+        let entries = fs::read_dir(path)
+            .map_err(|err| format!("No se pudo leer directorio {}: {err}", path.display()))?;
+        for entry in entries.flatten() {
+            add_path(zip, game_dir, &entry.path(), options, report)?;
+        }
+        return Ok(());
+    }
+    // `session.lock` está bloqueado mientras el mundo está abierto y no sirve al restaurar.
+    if !meta.is_file() || path.file_name().is_some_and(|name| name == "session.lock") {
+        return Ok(());
+    }
+    let mut file = fs::File::open(path)
+        .map_err(|err| format!("No se pudo leer archivo {}: {err}", path.display()))?;
+    zip.start_file(relative, options)
+        .map_err(|err| format!("No se pudo agregar archivo a la copia: {err}"))?;
+    let copied = io::copy(&mut file, zip)
+        .map_err(|err| format!("No se pudo copiar {}: {err}", path.display()))?;
+    report.files += 1;
+    report.bytes = report.bytes.saturating_add(copied);
+    Ok(())
+}
+
+/// Borra las copias `.zip` más antiguas de `backup_dir` hasta dejar `keep_last`.
+fn prune_backups(backup_dir: &Path, keep_last: u32) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(backup_dir) else {
+        return Vec::new();
+    };
+    let mut archives = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "zip"))
+        .collect::<Vec<_>>();
+    // Los nombres llevan la fecha (`AAAAMMDD-HHMMSS`), así que el orden es cronológico.
+    archives.sort();
+    let excess = archives.len().saturating_sub(keep_last.max(1) as usize);
+    archives
+        .into_iter()
+        .take(excess)
+        .filter_map(|path| match fs::remove_file(&path) {
+            Ok(()) => Some(path.display().to_string()),
+            Err(err) => {
+                log::warn!("⚠ No se pudo borrar la copia {}: {err}", path.display());
+                None
+            }
+        })
+        .collect()
+}
+
+/// Escribe `<backup_dir>/<stamp>.zip` con las rutas de `settings` que existan en `game_dir`.
+pub(crate) fn write_backup(
+    game_dir: &Path,
+    settings: &BackupSettings,
+    backup_dir: &Path,
+    stamp: &str,
+) -> Result<BackupReport, String> {
+    fs::create_dir_all(backup_dir).map_err(|err| {
+        format!(
+            "No se pudo crear la carpeta de copias {}: {err}",
+            backup_dir.display()
+        )
+    })?;
+    let archive = backup_dir.join(format!("{stamp}.zip"));
+    let partial = backup_dir.join(format!("{stamp}.zip.part"));
+    let mut report = BackupReport {
+        archive_path: archive.display().to_string(),
+        files: 0,
+        bytes: 0,
+        pruned: Vec::new(),
+    };
+    let output = fs::File::create(&partial)
+        .map_err(|err| format!("No se pudo crear la copia {}: {err}", partial.display()))?;
+    let mut zip = ZipWriter::new(output);
+    let options = SimpleFileOptions::default()
+        .compression_method(CompressionMethod::Deflated)
+        .unix_permissions(0o644);
+    let written = settings
+        .paths
+        .iter()
+        .map(|relative| relative.trim().trim_matches(['/', '\\']))
+        .filter(|relative| {
+            !relative.is_empty() && !relative.split(['/', '\\']).any(|part| part == "..")
+        })
+        .try_for_each(|relative| {
+            add_path(
+                &mut zip,
+                game_dir,
+                &game_dir.join(relative),
+                options,
+                &mut report,
+            )
+        })
+        .and_then(|()| {
+            zip.finish()
+                .map_err(|err| format!("No se pudo finalizar la copia: {err}"))?
+                .flush()
+                .map_err(|err| format!("No se pudo escribir la copia: {err}"))
+        });
+    if let Err(err) = written.and_then(|()| {
+        fs::rename(&partial, &archive)
+            .map_err(|err| format!("No se pudo guardar la copia {}: {err}", archive.display()))
+    }) {
+        let _ = fs::remove_file(&partial);
+        return Err(err);
+    }
+    report.pruned = prune_backups(backup_dir, settings.keep_last);
+    Ok(report)
+}
+
+fn backup_dir_for(app: &AppHandle, instance_root: &Path) -> AppResult<PathBuf> {
+    let folder = instance_root
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .ok_or_else(|| format!("Ruta de instancia inválida: {}", instance_root.display()))?;
+    Ok(resolve_launcher_root(app)?.join("backups").join(folder))
+}
+
+/// Copia de seguridad según los ajustes de la instancia. `Ok(None)` si están desactivadas.
+pub(crate) fn backup_instance(
+    app: &AppHandle,
+    instance_root: &str,
+) -> AppResult<Option<BackupReport>> {
+    let metadata = read_instance_metadata(instance_root.to_string())?;
+    if !metadata.backup.enabled {
+        return Ok(None);
+    }
+    let root = Path::new(instance_root);
+    let game_dir = backup_source_dir(root, &metadata)
+        .ok_or_else(|| format!("No se encontró la carpeta de juego de {instance_root}."))?;
+    let stamp = app_clock(app)
+        .clock
+        .now()
+        .format("%Y%m%d-%H%M%S")
+        .to_string();
+    let report = write_backup(
+        &game_dir,
+        &metadata.backup,
+        &backup_dir_for(app, root)?,
+        &stamp,
+    )?;
+    log::info!(
+        "✔ Copia de seguridad de {instance_root}: {} archivos, {} KB en {}",
+        report.files,
+        report.bytes / 1024,
+        report.archive_path
+    );
+    Ok(Some(report))
+}
+
+#[tauri::command]
+pub fn set_instance_backup_settings(
+    app: AppHandle,
+    instance_root: String,
+    backup: BackupSettings,
+) -> Result<BackupSettings, String> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    if is_instance_running(instance_root.as_str()) {
+        return Err(
+            "No se pueden cambiar las copias mientras la instancia está en ejecución.".to_string(),
+        );
+    }
+    let metadata = update_instance_metadata(instance_root.as_str(), |metadata| {
+        metadata.backup = backup;
+        Ok::<(), String>(())
+    })?;
+    Ok(metadata.backup)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::test_support::test_temp_dir;
+
+    fn test_dir(label: &str) -> PathBuf {
+        let dir = test_temp_dir(&format!("interface-backup-{label}"));
+        fs::create_dir_all(dir.join("game/saves/Mundo")).expect("saves");
+        fs::create_dir_all(dir.join("game/mods")).expect("mods");
+        fs::write(dir.join("game/saves/Mundo/level.dat"), "nivel").expect("level");
+        fs::write(dir.join("game/saves/Mundo/session.lock"), "lock").expect("lock");
+        fs::write(dir.join("game/options.txt"), "fov:70").expect("options");
+        fs::write(dir.join("game/mods/mod.jar"), "jar").expect("mod");
+        dir
+    }
+
+    #[test]
+    fn archives_only_configured_paths_without_session_lock() {
+        let dir = test_dir("paths");
+        let report = write_backup(
+            &dir.join("game"),
+            &BackupSettings::default(),
+            &dir.join("backups"),
+            "20240101-120000",
+        )
+        .expect("backup");
+
+        let mut archive =
+            zip::ZipArchive::new(fs::File::open(&report.archive_path).expect("archive"))
+                .expect("zip");
+        let mut names = (0..archive.len())
+            .map(|index| archive.by_index(index).expect("entry").name().to_string())
+            .filter(|name| !name.ends_with('/'))
+            .collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(names, vec!["options.txt", "saves/Mundo/level.dat"]);
+        assert_eq!(report.files, 2);
+        assert!(!dir.join("backups/20240101-120000.zip.part").exists());
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn keeps_only_the_newest_backups() {
+        let dir = test_dir("prune");
+        let settings = BackupSettings {
+            keep_last: 2,
+            ..BackupSettings::default()
+        };
+        for stamp in ["20240101-000000", "20240102-000000", "20240103-000000"] {
+            write_backup(&dir.join("game"), &settings, &dir.join("backups"), stamp)
+                .expect("backup");
+        }
+
+        assert!(!dir.join("backups/20240101-000000.zip").exists());
+        assert!(dir.join("backups/20240102-000000.zip").exists());
+        assert!(dir.join("backups/20240103-000000.zip").exists());
+
+        let _ = fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,349 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::{
+    app::{
+        instance_service::{is_instance_running, read_instance_metadata, update_instance_metadata},
+        launcher_service::list_instances_readonly,
+        redirect_launch::redirect_cache_entry_dir,
+        trusted_root::{resolve_trusted_instance_root, ValidatedInstanceRoot},
+    },
+    domain::models::instance::{InstanceMetadata, RetentionSettings},
+    infrastructure::filesystem::{config::load_launcher_config, disk_space::directory_size},
+    services::loader_files::{read_loader_files_manifest, stale_loader_paths},
+    shared::{clock::app_clock, result::AppResult},
+};
+
+/// Logs que pertenecen a la sesión en curso o a la última; nunca se borran.
+const PROTECTED_LOGS: [&str; 2] = ["latest.log", "debug.log"];
+/// Al arrancar solo se limpian las instancias que llevan este tiempo sin lanzarse; las
+/// demás ya se limpiaron al cerrarse.
+const STARTUP_IDLE_DAYS: i64 = 7;
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CleanupReport {
+    /// Rutas relativas a la carpeta de juego limpiada.
+    pub removed: Vec<String>,
+    pub bytes_freed: u64,
+}
+
+fn cleanup_enabled(app: &AppHandle) -> bool {
+    load_launcher_config(app)
+        .ok()
+        .and_then(|config| config.instance_cleanup_enabled)
+        .unwrap_or(true)
+}
+
+fn parse_timestamp(value: Option<&str>) -> Option<DateTime<Utc>> {
+    value
+        .and_then(|raw| DateTime::parse_from_rfc3339(raw).ok())
+        .map(|parsed| parsed.with_timezone(&Utc))
+}
+
+/// Borra los archivos de `dir` (recursivo, sin seguir enlaces) anteriores a `cutoff`.
+fn remove_older_than(
+    game_dir: &Path,
+    dir: &Path,
+    cutoff: DateTime<Utc>,
+    report: &mut CleanupReport,
+) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(meta) = fs::symlink_metadata(&path) else {
+            continue;
+        };
+        if meta.is_dir() {
+            remove_older_than(game_dir, &path, cutoff, report);
+            continue;
+        }
+        if !meta.is_file() {
+            continue;
+        }
+        let name = entry.file_name().to_string_lossy().to_string();
+        if PROTECTED_LOGS.contains(&name.as_str()) {
+            continue;
+        }
+        let Ok(modified) = meta.modified() else {
+            continue;
+        };
+        if DateTime::<Utc>::from(modified) >= cutoff {
+            continue;
+        }
+        match fs::remove_file(&path) {
+            Ok(()) => {
+                report.bytes_freed = report.bytes_freed.saturating_add(meta.len());
+                report.removed.push(
+                    path.strip_prefix(game_dir)
+                        .unwrap_or(&path)
+                        .to_string_lossy()
+                        .replace('\\', "/"),
+                );
+            }
+            Err(err) => log::warn!("⚠ No se pudo borrar {}: {err}", path.display()),
+        }
+    }
+}
+
+/// Aplica la retención a `logs/`, `crash-reports/` y `screenshots/` de una carpeta de juego.
+/// Nada posterior a `session_start` se toca; un valor de 0 días desactiva esa categoría.
+pub(crate) fn cleanup_game_dir(
+    game_dir: &Path,
+    retention: &RetentionSettings,
+    now: DateTime<Utc>,
+    session_start: Option<DateTime<Utc>>,
+) -> CleanupReport {
+    let mut report = CleanupReport::default();
+    let categories = [
+        ("logs", Some(retention.log_retention_days)),
+        ("crash-reports", Some(retention.crash_report_retention_days)),
+        ("screenshots", retention.screenshot_retention),
+    ];
+    for (folder, days) in categories {
+        let Some(days) = days.filter(|days| *days > 0) else {
+            continue;
+        };
+        let mut cutoff = now - Duration::days(i64::from(days));
+        if let Some(session_start) = session_start {
+            cutoff = cutoff.min(session_start);
+        }
+        remove_older_than(game_dir, &game_dir.join(folder), cutoff, &mut report);
+    }
+    report
+}
+
+/// Carpetas que el launcher puede limpiar: la de juego propia o, en las REDIRECT, solo su
+/// copia en redirect-cache; la carpeta del launcher de origen no se toca nunca.
+fn cleanup_targets(
+    app: &AppHandle,
+    instance_root: &Path,
+    metadata: &InstanceMetadata,
+) -> Vec<PathBuf> {
+    if metadata.state.eq_ignore_ascii_case("redirect") {
+        return redirect_cache_entry_dir(app, &metadata.internal_uuid)
+            .ok()
+            .filter(|dir| dir.is_dir())
+            .into_iter()
+            .collect();
+    }
+    vec![instance_root.join("minecraft")]
+}
+
+pub(crate) fn cleanup_instance(
+    app: &AppHandle,
+    instance_root: &ValidatedInstanceRoot,
+) -> AppResult<CleanupReport> {
+    instance_root.revalidate()?;
+    let metadata = read_instance_metadata(instance_root.to_string())?;
+    let now = app_clock(app).clock.now();
+    let session_start = parse_timestamp(metadata.last_used.as_deref());
+    let mut report = CleanupReport::default();
+    for target in cleanup_targets(app, instance_root.path(), &metadata) {
+        let partial = cleanup_game_dir(&target, &metadata.retention, now, session_start);
+        report.removed.extend(partial.removed);
+        report.bytes_freed = report.bytes_freed.saturating_add(partial.bytes_freed);
+    }
+    if !report.removed.is_empty() {
+        log::info!(
+            "🔹 Limpieza de {instance_root}: {} archivos, {} KB liberados",
+            report.removed.len(),
+            report.bytes_freed / 1024
+        );
+    }
+    Ok(report)
+}
+
+/// Limpieza tras cerrar el juego; se llama desde el hilo que vigila el proceso.
+pub fn cleanup_after_exit(app: &AppHandle, instance_root: &str) {
+    if !cleanup_enabled(app) {
+        return;
+    }
+    if let Err(err) = resolve_trusted_instance_root(app, instance_root)
+        .and_then(|validated| cleanup_instance(app, &validated))
+    {
+        log::warn!("⚠ Limpieza tras el cierre de {instance_root} falló: {err}");
+    }
+}
+
+/// Limpia al arrancar las instancias que no se han lanzado en los últimos días.
+pub fn cleanup_idle_instances_on_startup(app: &AppHandle) {
+    if !cleanup_enabled(app) {
+        return;
+    }
+    let Ok(instances) = list_instances_readonly(app) else {
+        return;
+    };
+    let idle_since = app_clock(app).clock.now() - Duration::days(STARTUP_IDLE_DAYS);
+    for instance in instances {
+        if is_instance_running(&instance.instance_root) {
+            continue;
+        }
+        let Ok(metadata) = read_instance_metadata(instance.instance_root.clone()) else {
+            continue;
+        };
+        let recently_used = parse_timestamp(metadata.last_used.as_deref())
+            .is_some_and(|last_used| last_used > idle_since);
+        if recently_used {
+            continue;
+        }
+        if let Err(err) = resolve_trusted_instance_root(app, &instance.instance_root)
+            .and_then(|validated| cleanup_instance(app, &validated))
+        {
+            log::warn!(
+                "⚠ Limpieza al inicio de {} falló: {err}",
+                instance.instance_root
+            );
+        }
+    }
+}
+
+/// Limpieza manual; ignora el ajuste global porque la pide el usuario.
+#[tauri::command]
+pub fn run_instance_cleanup_now(
+    app: AppHandle,
+    instance_root: String,
+) -> Result<CleanupReport, String> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    if is_instance_running(instance_root.as_str()) {
+        return Err("No se puede limpiar la instancia mientras está en ejecución.".to_string());
+    }
+    cleanup_instance(&app, &instance_root)
+}
+
+/// Borra los perfiles y builds del loader que no son los de la última instalación
+/// registrada en `.loader-files.json`.
+#[tauri::command]
+pub fn clean_stale_loader_files(
+    app: AppHandle,
+    instance_root: String,
+) -> Result<CleanupReport, String> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    if is_instance_running(instance_root.as_str()) {
+        return Err("No se puede limpiar la instancia mientras está en ejecución.".to_string());
+    }
+    let metadata = read_instance_metadata(instance_root.to_string())?;
+    if metadata.state.eq_ignore_ascii_case("redirect") {
+        return Err(
+            "Las instancias REDIRECT usan los archivos del launcher de origen; no se limpian."
+                .to_string(),
+        );
+    }
+    let game_dir = instance_root.path().join("minecraft");
+    let manifest = read_loader_files_manifest(&game_dir)
+        .filter(|manifest| manifest.matches(&metadata.loader, ""))
+        .ok_or_else(|| {
+            format!(
+                "No hay registro de la instalación de {} en esta instancia; reinstala el loader antes de limpiar.",
+                metadata.loader
+            )
+        })?;
+
+    instance_root.revalidate()?;
+    let mut report = CleanupReport::default();
+    for path in stale_loader_paths(&game_dir, &manifest) {
+        let size = directory_size(&path);
+        match fs::remove_dir_all(&path) {
+            Ok(()) => {
+                report.bytes_freed = report.bytes_freed.saturating_add(size);
+                report.removed.push(
+                    path.strip_prefix(&game_dir)
+                        .unwrap_or(&path)
+                        .to_string_lossy()
+                        .replace('\\', "/"),
+                );
+            }
+            Err(err) => log::warn!("⚠ No se pudo borrar {}: {err}", path.display()),
+        }
+    }
+    log::info!(
+        "🔹 Restos del loader en {instance_root}: {} carpetas, {} KB liberados (activo: {})",
+        report.removed.len(),
+        report.bytes_freed / 1024,
+        manifest.version_id
+    );
+    Ok(report)
+}
+
+#[tauri::command]
+pub fn set_instance_retention(
+    app: AppHandle,
+    instance_root: String,
+    retention: RetentionSettings,
+) -> Result<RetentionSettings, String> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    let metadata = update_instance_metadata(instance_root.as_str(), |metadata| {
+        metadata.retention = retention;
+        Ok::<(), String>(())
+    })?;
+    Ok(metadata.retention)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn game_dir(label: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("interface-cleanup-{label}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        for folder in ["logs", "crash-reports", "screenshots"] {
+            fs::create_dir_all(dir.join(folder)).expect("folder");
+        }
+        fs::write(dir.join("logs/latest.log"), "actual").expect("latest");
+        fs::write(dir.join("logs/2024-01-01-1.log.gz"), "viejo").expect("log");
+        fs::write(dir.join("crash-reports/crash-2024.txt"), "crash").expect("crash");
+        fs::write(dir.join("screenshots/2024-01-01.png"), "png").expect("png");
+        dir
+    }
+
+    #[test]
+    fn removes_only_categories_past_their_retention() {
+        let dir = game_dir("retention");
+        let now = Utc::now() + Duration::days(40);
+
+        let report = cleanup_game_dir(&dir, &RetentionSettings::default(), now, None);
+        assert_eq!(report.removed, vec!["logs/2024-01-01-1.log.gz"]);
+        assert_eq!(report.bytes_freed, 5);
+        assert!(dir.join("logs/latest.log").exists());
+        assert!(dir.join("crash-reports/crash-2024.txt").exists());
+        assert!(dir.join("screenshots/2024-01-01.png").exists());
+
+        let aggressive = RetentionSettings {
+            log_retention_days: 1,
+            crash_report_retention_days: 1,
+            screenshot_retention: Some(1),
+        };
+        let report = cleanup_game_dir(&dir, &aggressive, now, None);
+        assert_eq!(report.removed.len(), 2);
+        assert!(dir.join("logs/latest.log").exists());
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn never_touches_files_newer_than_the_last_session_start() {
+        let dir = game_dir("session");
+        let now = Utc::now() + Duration::days(400);
+        let session_start = Utc::now() - Duration::hours(1);
+        let aggressive = RetentionSettings {
+            log_retention_days: 1,
+            crash_report_retention_days: 1,
+            screenshot_retention: Some(1),
+        };
+
+        let report = cleanup_game_dir(&dir, &aggressive, now, Some(session_start));
+        assert!(report.removed.is_empty());
+        assert!(dir.join("logs/2024-01-01-1.log.gz").exists());
+
+        let _ = fs::remove_dir_all(dir);
+    }
+}
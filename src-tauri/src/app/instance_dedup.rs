@@ -0,0 +1,724 @@
+// Deduplicación de contenido entre instancias con hardlinks.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::{
+    app::{
+        instance_service::{effective_mods_dir, is_instance_running, read_instance_metadata},
+        launcher_service::list_instances_readonly,
+        maintenance::begin_maintenance,
+    },
+    infrastructure::{
+        checksum::sha1::compute_file_sha1,
+        filesystem::{file_ops::write_file_replacing, paths::resolve_launcher_root},
+    },
+};
+
+const OBJECTS_DIR: &str = "objects";
+/// Índice por instancia: ruta relativa del enlace → sha1 del objeto compartido.
+const DEDUP_INDEX_FILE: &str = ".dedup-links.json";
+const DEDUP_SECTIONS: [&str; 3] = ["mods", "resourcepacks", "shaderpacks"];
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DedupIndex {
+    #[serde(default)]
+    links: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstanceDedupReport {
+    pub instance_root: String,
+    pub files: usize,
+    pub bytes_reclaimable: u64,
+}
+
+/// Objeto cuyo contenido ya no coincide con su hash: afecta a todos sus enlaces.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CorruptedObject {
+    pub sha1: String,
+    pub affected: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DedupReport {
+    pub dry_run: bool,
+    pub duplicate_groups: usize,
+    pub files_linked: usize,
+    pub bytes_reclaimable: u64,
+    pub bytes_reclaimed: u64,
+    pub instances: Vec<InstanceDedupReport>,
+    /// Archivos en otro volumen o en un sistema sin hardlinks; solo se informan.
+    pub link_unsupported: Vec<String>,
+    pub skipped_running: Vec<String>,
+    pub corrupted_objects: Vec<CorruptedObject>,
+    pub objects_pruned: usize,
+}
+
+#[derive(Debug, Clone)]
+struct DedupInstance {
+    instance_root: PathBuf,
+    /// Fuera del alcance solo puede aportar la copia canónica; no se modifica.
+    in_scope: bool,
+    /// Con `mods_dir_override` la carpeta de mods ya es compartida.
+    skip_mods: bool,
+}
+
+#[derive(Debug, Clone)]
+struct Candidate {
+    instance: usize,
+    relative: String,
+    path: PathBuf,
+    size: u64,
+}
+
+fn index_path(instance_root: &Path) -> PathBuf {
+    instance_root.join(DEDUP_INDEX_FILE)
+}
+
+fn read_index(instance_root: &Path) -> DedupIndex {
+    fs::read_to_string(index_path(instance_root))
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn write_index(instance_root: &Path, index: &DedupIndex) {
+    let path = index_path(instance_root);
+    if index.links.is_empty() {
+        let _ = fs::remove_file(path);
+        return;
+    }
+    let result = serde_json::to_vec_pretty(index)
+        .map_err(|err| err.to_string())
+        .and_then(|bytes| write_file_replacing(&path, &bytes, true));
+    if let Err(err) = result {
+        log::warn!("⚠ No se pudo guardar {}: {err}", path.display());
+    }
+}
+
+fn relative_to(instance_root: &Path, path: &Path) -> Option<String> {
+    path.strip_prefix(instance_root)
+        .ok()
+        .map(|relative| relative.to_string_lossy().replace('\\', "/"))
+}
+
+#[cfg(unix)]
+fn same_file(a: &Path, b: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    match (fs::metadata(a), fs::metadata(b)) {
+        (Ok(a), Ok(b)) => a.dev() == b.dev() && a.ino() == b.ino(),
+        _ => false,
+    }
+}
+
+/// Sin API estable para el id de archivo: un hardlink comparte tamaño y fecha de modificación.
+#[cfg(not(unix))]
+fn same_file(a: &Path, b: &Path) -> bool {
+    match (fs::metadata(a), fs::metadata(b)) {
+        (Ok(a), Ok(b)) => a.len() == b.len() && a.modified().ok() == b.modified().ok(),
+        _ => false,
+    }
+}
+
+fn existing_ancestor(path: &Path) -> Option<&Path> {
+    path.ancestors().find(|ancestor| ancestor.exists())
+}
+
+#[cfg(unix)]
+fn same_volume(a: &Path, b: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    let dev = |path: &Path| {
+        existing_ancestor(path)
+            .and_then(|existing| fs::metadata(existing).ok())
+            .map(|meta| meta.dev())
+    };
+    matches!((dev(a), dev(b)), (Some(a), Some(b)) if a == b)
+}
+
+#[cfg(not(unix))]
+fn same_volume(a: &Path, b: &Path) -> bool {
+    let prefix = |path: &Path| {
+        existing_ancestor(path)
+            .and_then(|existing| fs::canonicalize(existing).ok())
+            .and_then(|canonical| {
+                canonical
+                    .components()
+                    .next()
+                    .map(|c| c.as_os_str().to_owned())
+            })
+    };
+    matches!((prefix(a), prefix(b)), (Some(a), Some(b)) if a == b)
+}
+
+#[cfg(unix)]
+fn link_count(path: &Path) -> u64 {
+    use std::os::unix::fs::MetadataExt;
+    fs::metadata(path).map(|meta| meta.nlink()).unwrap_or(1)
+}
+
+/// Sustituye `target` por un enlace a `object` creado con nombre temporal.
+fn link_over(object: &Path, target: &Path) -> Result<(), String> {
+    let file_name = target
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let temp = target.with_file_name(format!(".{file_name}.dedup-tmp"));
+    let _ = fs::remove_file(&temp);
+    fs::hard_link(object, &temp)
+        .map_err(|err| format!("No se pudo enlazar {}: {err}", target.display()))?;
+    fs::rename(&temp, target).map_err(|err| {
+        let _ = fs::remove_file(&temp);
+        format!("No se pudo reemplazar {}: {err}", target.display())
+    })
+}
+
+fn collect_candidates(index: usize, instance: &DedupInstance, out: &mut Vec<Candidate>) {
+    for section in DEDUP_SECTIONS {
+        if section == "mods" && instance.skip_mods {
+            continue;
+        }
+        let dir = instance.instance_root.join("minecraft").join(section);
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Ok(meta) = fs::symlink_metadata(&path) else {
+                continue;
+            };
+            let temp = entry.file_name().to_string_lossy().ends_with(".dedup-tmp");
+            if !meta.is_file() || meta.len() == 0 || temp {
+                continue;
+            }
+            let Some(relative) = relative_to(&instance.instance_root, &path) else {
+                continue;
+            };
+            out.push(Candidate {
+                instance: index,
+                relative,
+                path,
+                size: meta.len(),
+            });
+        }
+    }
+}
+
+fn existing_objects(objects_dir: &Path) -> HashMap<String, u64> {
+    let Ok(entries) = fs::read_dir(objects_dir) else {
+        return HashMap::new();
+    };
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let is_hash = name.len() == 40 && name.chars().all(|ch| ch.is_ascii_hexdigit());
+            let meta = entry.metadata().ok().filter(|meta| meta.is_file())?;
+            is_hash.then_some((name, meta.len()))
+        })
+        .collect()
+}
+
+#[cfg(unix)]
+fn prune_unused_objects(objects_dir: &Path) -> usize {
+    existing_objects(objects_dir)
+        .into_keys()
+        .map(|sha1| objects_dir.join(sha1))
+        .filter(|object| link_count(object) <= 1 && fs::remove_file(object).is_ok())
+        .count()
+}
+
+/// En Windows no hay forma estable de contar enlaces; los objetos se conservan.
+#[cfg(not(unix))]
+fn prune_unused_objects(_objects_dir: &Path) -> usize {
+    0
+}
+
+fn deduplicate(objects_dir: &Path, instances: &[DedupInstance], dry_run: bool) -> DedupReport {
+    let mut report = DedupReport {
+        dry_run,
+        ..DedupReport::default()
+    };
+    let mut candidates = Vec::new();
+    for (index, instance) in instances.iter().enumerate() {
+        collect_candidates(index, instance, &mut candidates);
+    }
+    let objects = existing_objects(objects_dir);
+
+    // Solo se hashean los archivos cuyo tamaño aparece más de una vez.
+    let mut sizes: HashMap<u64, usize> = HashMap::new();
+    for size in candidates
+        .iter()
+        .map(|candidate| candidate.size)
+        .chain(objects.values().copied())
+    {
+        *sizes.entry(size).or_default() += 1;
+    }
+    let mut groups: BTreeMap<(String, u64), Vec<usize>> = BTreeMap::new();
+    for (position, candidate) in candidates.iter().enumerate() {
+        if sizes.get(&candidate.size).copied().unwrap_or(0) < 2 {
+            continue;
+        }
+        match compute_file_sha1(&candidate.path) {
+            Ok(sha1) => groups
+                .entry((sha1, candidate.size))
+                .or_default()
+                .push(position),
+            Err(err) => log::warn!("⚠ {err}"),
+        }
+    }
+
+    let linkable_volume = |path: &Path| same_volume(path, objects_dir);
+    let mut per_instance: BTreeMap<usize, InstanceDedupReport> = BTreeMap::new();
+    let mut new_links: HashMap<usize, Vec<(String, String)>> = HashMap::new();
+    for ((sha1, size), members) in groups {
+        let object = objects_dir.join(&sha1);
+        let object_exists = objects.get(&sha1) == Some(&size);
+        if object_exists {
+            if compute_file_sha1(&object).ok().as_deref() != Some(sha1.as_str()) {
+                log::warn!("⚠ Objeto deduplicado corrupto: {}", object.display());
+                // El índice incluye también los enlaces renombrados o desactivados.
+                let affected = instances
+                    .iter()
+                    .flat_map(|instance| {
+                        dedup_links(&instance.instance_root)
+                            .into_iter()
+                            .filter(|(_, linked)| *linked == sha1)
+                            .map(|(relative, _)| {
+                                instance.instance_root.join(relative).display().to_string()
+                            })
+                    })
+                    .collect();
+                report
+                    .corrupted_objects
+                    .push(CorruptedObject { sha1, affected });
+                continue;
+            }
+        } else if members.len() < 2 {
+            continue;
+        }
+        report.duplicate_groups += 1;
+
+        // Sin objeto previo, la primera copia pasa a serlo y no libera espacio.
+        let (canonical, rest) = if object_exists {
+            (None, members.as_slice())
+        } else {
+            (Some(members[0]), &members[1..])
+        };
+        let mut object_ready = object_exists;
+        if let Some(canonical) = canonical {
+            let source = &candidates[canonical];
+            if !linkable_volume(&source.path) {
+                report
+                    .link_unsupported
+                    .push(source.path.display().to_string());
+            } else if !dry_run {
+                let created = fs::create_dir_all(objects_dir)
+                    .and_then(|_| fs::hard_link(&source.path, &object));
+                match created {
+                    Ok(()) => {
+                        object_ready = true;
+                        new_links
+                            .entry(source.instance)
+                            .or_default()
+                            .push((source.relative.clone(), sha1.clone()));
+                    }
+                    Err(err) => {
+                        log::warn!("⚠ No se pudo crear {}: {err}", object.display());
+                        report
+                            .link_unsupported
+                            .push(source.path.display().to_string());
+                    }
+                }
+            }
+        }
+        let canonical_path = canonical.map(|position| candidates[position].path.clone());
+        for member in rest {
+            let candidate = &candidates[*member];
+            let reference = canonical_path.as_deref().unwrap_or(&object);
+            if same_file(&candidate.path, &object) || same_file(&candidate.path, reference) {
+                new_links
+                    .entry(candidate.instance)
+                    .or_default()
+                    .push((candidate.relative.clone(), sha1.clone()));
+                continue;
+            }
+            if !instances[candidate.instance].in_scope {
+                continue;
+            }
+            let entry =
+                per_instance
+                    .entry(candidate.instance)
+                    .or_insert_with(|| InstanceDedupReport {
+                        instance_root: instances[candidate.instance]
+                            .instance_root
+                            .display()
+                            .to_string(),
+                        ..InstanceDedupReport::default()
+                    });
+            entry.files += 1;
+            entry.bytes_reclaimable += candidate.size;
+            report.bytes_reclaimable += candidate.size;
+            if !linkable_volume(&candidate.path) {
+                report
+                    .link_unsupported
+                    .push(candidate.path.display().to_string());
+                continue;
+            }
+            if dry_run || !object_ready {
+                continue;
+            }
+            match link_over(&object, &candidate.path) {
+                Ok(()) => {
+                    report.files_linked += 1;
+                    report.bytes_reclaimed += candidate.size;
+                    new_links
+                        .entry(candidate.instance)
+                        .or_default()
+                        .push((candidate.relative.clone(), sha1.clone()));
+                }
+                Err(err) => {
+                    log::warn!("⚠ {err}");
+                    report
+                        .link_unsupported
+                        .push(candidate.path.display().to_string());
+                }
+            }
+        }
+    }
+    report.instances = per_instance.into_values().collect();
+
+    if !dry_run {
+        for (position, instance) in instances.iter().enumerate() {
+            let mut index = read_index(&instance.instance_root);
+            index
+                .links
+                .retain(|relative, _| instance.instance_root.join(relative).is_file());
+            for (relative, sha1) in new_links.remove(&position).unwrap_or_default() {
+                index.links.insert(relative, sha1);
+            }
+            write_index(&instance.instance_root, &index);
+        }
+        report.objects_pruned = prune_unused_objects(objects_dir);
+    }
+    report
+}
+
+/// Hashes de los archivos deduplicados de la instancia (ruta relativa → sha1).
+fn dedup_links(instance_root: &Path) -> BTreeMap<String, String> {
+    read_index(instance_root).links
+}
+
+/// Revisa los enlaces de la instancia contra el índice e informa qué otras instancias
+/// comparten cada enlace dañado.
+pub(crate) fn verify_dedup_links(instance_root: &Path) -> Vec<CorruptedObject> {
+    let mut index = read_index(instance_root);
+    let siblings: Vec<PathBuf> = instance_root
+        .parent()
+        .and_then(|parent| fs::read_dir(parent).ok())
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| path != instance_root)
+                .collect()
+        })
+        .unwrap_or_default();
+    let mut damaged = Vec::new();
+    let mut stale = Vec::new();
+    for (relative, sha1) in &index.links {
+        let path = instance_root.join(relative);
+        if !path.is_file() {
+            stale.push(relative.clone());
+            continue;
+        }
+        if compute_file_sha1(&path).ok().as_deref() == Some(sha1.as_str()) {
+            continue;
+        }
+        let affected: Vec<String> = siblings
+            .iter()
+            .flat_map(|sibling| {
+                dedup_links(sibling)
+                    .into_keys()
+                    .map(|linked| sibling.join(linked))
+                    .filter(|linked| same_file(linked, &path))
+                    .map(|linked| linked.display().to_string())
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        if affected.is_empty() {
+            // Se reemplazó el archivo sin pasar por el objeto compartido: ya no es un enlace.
+            stale.push(relative.clone());
+            continue;
+        }
+        log::warn!(
+            "⚠ Enlace deduplicado con contenido distinto al objeto {sha1}: {}",
+            path.display()
+        );
+        damaged.push(CorruptedObject {
+            sha1: sha1.clone(),
+            affected,
+        });
+    }
+    if !stale.is_empty() {
+        for relative in stale {
+            index.links.remove(&relative);
+        }
+        write_index(instance_root, &index);
+    }
+    damaged
+}
+
+/// Mantiene el índice al renombrar un enlace (activar/desactivar).
+pub(crate) fn note_link_renamed(instance_root: &Path, from: &Path, to: &Path) {
+    let (Some(from), Some(to)) = (
+        relative_to(instance_root, from),
+        relative_to(instance_root, to),
+    ) else {
+        return;
+    };
+    let mut index = read_index(instance_root);
+    if let Some(sha1) = index.links.remove(&from) {
+        index.links.insert(to, sha1);
+        write_index(instance_root, &index);
+    }
+}
+
+/// Quita del índice un enlace borrado; el objeto compartido no se toca.
+pub(crate) fn note_link_removed(instance_root: &Path, path: &Path) {
+    let Some(relative) = relative_to(instance_root, path) else {
+        return;
+    };
+    let mut index = read_index(instance_root);
+    if index.links.remove(&relative).is_some() {
+        write_index(instance_root, &index);
+    }
+}
+
+/// Borra el enlace antes de sobrescribir un archivo deduplicado.
+pub(crate) fn detach_shared_link(instance_root: &Path, path: &Path) {
+    let indexed = relative_to(instance_root, path)
+        .is_some_and(|relative| read_index(instance_root).links.contains_key(&relative));
+    #[cfg(unix)]
+    let indexed = indexed || (path.is_file() && link_count(path) > 1);
+    if indexed {
+        let _ = fs::remove_file(path);
+        note_link_removed(instance_root, path);
+    }
+}
+
+/// Como [`detach_shared_link`], buscando la instancia dueña desde la ruta del archivo.
+pub(crate) fn detach_link_at(path: &Path) {
+    let owner = path
+        .ancestors()
+        .skip(1)
+        .find(|dir| index_path(dir).is_file())
+        .or_else(|| path.parent());
+    if let Some(owner) = owner {
+        detach_shared_link(owner, path);
+    }
+}
+
+fn deduplicate_instance_files_impl(
+    app: &AppHandle,
+    scope: &str,
+    dry_run: bool,
+) -> Result<DedupReport, String> {
+    // Reemplazar archivos por enlaces con un juego abierto lo corrompería: se bloquea el
+    // lanzamiento de cualquier instancia hasta terminar.
+    let _maintenance = if dry_run {
+        None
+    } else {
+        Some(begin_maintenance("deduplicate_instance_files")?)
+    };
+    let launcher_root = resolve_launcher_root(app)?;
+    let all = scope.is_empty() || scope.eq_ignore_ascii_case("all");
+    let summaries = list_instances_readonly(app)?;
+    if !all
+        && !summaries
+            .iter()
+            .any(|summary| summary.instance_root == scope)
+    {
+        return Err(format!("No existe la instancia: {scope}"));
+    }
+
+    let mut instances = Vec::new();
+    let mut skipped_running = Vec::new();
+    for summary in summaries {
+        let Ok(metadata) = read_instance_metadata(summary.instance_root.clone()) else {
+            continue;
+        };
+        // La carpeta de juego de una REDIRECT pertenece al launcher de origen.
+        if metadata.state.eq_ignore_ascii_case("redirect") {
+            continue;
+        }
+        if is_instance_running(&summary.instance_root) {
+            skipped_running.push(summary.instance_root);
+            continue;
+        }
+        let root = PathBuf::from(&summary.instance_root);
+        instances.push(DedupInstance {
+            skip_mods: effective_mods_dir(&root) != root.join("minecraft").join("mods"),
+            in_scope: all || summary.instance_root == scope,
+            instance_root: root,
+        });
+    }
+
+    let mut report = deduplicate(&launcher_root.join(OBJECTS_DIR), &instances, dry_run);
+    report.skipped_running = skipped_running;
+    log::info!(
+        "🔹 Deduplicación{}: {} grupos, {} archivos enlazados, {} KB recuperables",
+        if dry_run { " (simulación)" } else { "" },
+        report.duplicate_groups,
+        report.files_linked,
+        report.bytes_reclaimable / 1024
+    );
+    Ok(report)
+}
+
+/// `scope` es `all` o la ruta de una instancia; con `dry_run` solo se calcula el ahorro.
+#[tauri::command]
+pub async fn deduplicate_instance_files(
+    app: AppHandle,
+    scope: String,
+    dry_run: bool,
+) -> Result<DedupReport, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        deduplicate_instance_files_impl(&app, scope.trim(), dry_run)
+    })
+    .await
+    .map_err(|err| format!("Falló la tarea de deduplicación: {err}"))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture(label: &str) -> (PathBuf, Vec<DedupInstance>) {
+        let root =
+            std::env::temp_dir().join(format!("interface-dedup-{label}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let instances = ["a", "b"]
+            .iter()
+            .map(|name| {
+                let instance_root = root.join("instances").join(name);
+                let mods = instance_root.join("minecraft").join("mods");
+                fs::create_dir_all(&mods).expect("mods");
+                fs::write(mods.join("sodium.jar"), b"contenido compartido").expect("jar");
+                fs::write(mods.join(format!("solo-{name}.jar")), name.repeat(7)).expect("jar");
+                DedupInstance {
+                    instance_root,
+                    in_scope: true,
+                    skip_mods: false,
+                }
+            })
+            .collect();
+        (root, instances)
+    }
+
+    #[test]
+    fn dry_run_reports_savings_and_real_run_links_to_one_object() {
+        let (root, instances) = fixture("link");
+        let objects = root.join(OBJECTS_DIR);
+
+        let preview = deduplicate(&objects, &instances, true);
+        assert_eq!(preview.duplicate_groups, 1);
+        assert_eq!(preview.bytes_reclaimable, 20);
+        assert_eq!(preview.files_linked, 0);
+        assert!(!objects.exists());
+
+        let report = deduplicate(&objects, &instances, false);
+        assert_eq!(report.files_linked, 1);
+        assert_eq!(report.bytes_reclaimed, 20);
+        let a = instances[0].instance_root.join("minecraft/mods/sodium.jar");
+        let b = instances[1].instance_root.join("minecraft/mods/sodium.jar");
+        assert!(same_file(&a, &b));
+        let sha1 = compute_file_sha1(&a).expect("sha1");
+        assert!(same_file(&a, &objects.join(&sha1)));
+        assert_eq!(
+            dedup_links(&instances[1].instance_root).get("minecraft/mods/sodium.jar"),
+            Some(&sha1)
+        );
+
+        let again = deduplicate(&objects, &instances, true);
+        assert_eq!(again.bytes_reclaimable, 0);
+
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn disabling_and_deleting_a_link_keeps_the_other_instances_intact() {
+        let (root, instances) = fixture("semantics");
+        let objects = root.join(OBJECTS_DIR);
+        deduplicate(&objects, &instances, false);
+        let a_root = &instances[0].instance_root;
+        let a = a_root.join("minecraft/mods/sodium.jar");
+        let b = instances[1].instance_root.join("minecraft/mods/sodium.jar");
+        let sha1 = compute_file_sha1(&a).expect("sha1");
+
+        let disabled = a.with_file_name("sodium.jar.disabled");
+        fs::rename(&a, &disabled).expect("rename");
+        note_link_renamed(a_root, &a, &disabled);
+        assert!(dedup_links(a_root).contains_key("minecraft/mods/sodium.jar.disabled"));
+        assert_eq!(fs::read(&b).expect("b"), b"contenido compartido");
+
+        fs::remove_file(&disabled).expect("delete");
+        note_link_removed(a_root, &disabled);
+        assert_eq!(prune_unused_objects(&objects), 0);
+        assert!(objects.join(&sha1).exists());
+        assert_eq!(fs::read(&b).expect("b"), b"contenido compartido");
+
+        detach_shared_link(&instances[1].instance_root, &b);
+        assert!(!b.exists());
+        assert_eq!(prune_unused_objects(&objects), 1);
+
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn rewriting_a_link_in_one_instance_leaves_the_other_untouched() {
+        let (root, instances) = fixture("rewrite");
+        deduplicate(&root.join(OBJECTS_DIR), &instances, false);
+        let a_root = &instances[0].instance_root;
+        let a = a_root.join("minecraft/mods/sodium.jar");
+        let b = instances[1].instance_root.join("minecraft/mods/sodium.jar");
+
+        // Lo mismo que hace una reparación o actualización al volver a descargar el mod.
+        detach_link_at(&a);
+        write_file_replacing(&a, b"version reparada", true).expect("write");
+
+        assert_eq!(fs::read(&a).expect("a"), b"version reparada");
+        assert_eq!(fs::read(&b).expect("b"), b"contenido compartido");
+        assert!(!dedup_links(a_root).contains_key("minecraft/mods/sodium.jar"));
+        assert!(verify_dedup_links(&instances[1].instance_root).is_empty());
+
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn verify_reports_every_instance_sharing_a_damaged_link() {
+        let (root, instances) = fixture("verify");
+        deduplicate(&root.join(OBJECTS_DIR), &instances, false);
+        let a = instances[0].instance_root.join("minecraft/mods/sodium.jar");
+        let b = instances[1].instance_root.join("minecraft/mods/sodium.jar");
+
+        fs::write(&a, b"escrito encima del enlace").expect("write");
+
+        let damaged = verify_dedup_links(&instances[0].instance_root);
+        assert_eq!(damaged.len(), 1);
+        assert_eq!(damaged[0].affected, vec![b.display().to_string()]);
+
+        let _ = fs::remove_dir_all(root);
+    }
+}
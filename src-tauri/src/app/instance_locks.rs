@@ -0,0 +1,202 @@
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::{
+    app::{
+        event_journal::record_instance_event,
+        instance_service::{read_instance_metadata, update_instance_metadata},
+        trusted_root::resolve_trusted_instance_root,
+    },
+    domain::models::instance::InstanceMetadata,
+};
+
+/// Aspectos de la instancia que un autor de modpack puede bloquear.
+pub const LOCKABLE_FIELDS: [&str; 10] = [
+    "minecraft_version",
+    "loader",
+    "loader_version",
+    "java_args",
+    "ram_mb",
+    "mods",
+    "mods_dir_override",
+    "resourcepacks",
+    "shaderpacks",
+    "saves",
+];
+
+/// Rechazo por campo bloqueado; incluye la nota de `locked_by` para mostrarla al usuario.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct LockedFieldError {
+    /// Siempre `LOCKED_FIELD`.
+    pub code: &'static str,
+    pub field: String,
+    pub locked_by: String,
+    pub message: String,
+}
+
+/// Error de los comandos que editan la instancia: el bloqueo se serializa como objeto
+/// estructurado y el resto sigue siendo el texto de siempre.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum InstanceEditError {
+    Locked(LockedFieldError),
+    Other(String),
+}
+
+impl From<String> for InstanceEditError {
+    fn from(err: String) -> Self {
+        InstanceEditError::Other(err)
+    }
+}
+
+impl std::fmt::Display for InstanceEditError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InstanceEditError::Locked(locked) => write!(f, "{}", locked.message),
+            InstanceEditError::Other(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+fn locked_field_error(field: &str, locked_by: &str) -> LockedFieldError {
+    let note = if locked_by.trim().is_empty() {
+        String::new()
+    } else {
+        format!(" Nota del autor: {}", locked_by.trim())
+    };
+    LockedFieldError {
+        code: "LOCKED_FIELD",
+        field: field.to_string(),
+        locked_by: locked_by.to_string(),
+        message: format!("El campo '{field}' está bloqueado en esta instancia.{note}"),
+    }
+}
+
+/// Comprueba el bloqueo de `field` en la metadata ya leída. Con `override_lock` deja
+/// pasar la modificación y la registra en el journal de eventos de la instancia.
+pub fn check_metadata_lock(
+    instance_root: &str,
+    metadata: &InstanceMetadata,
+    field: &str,
+    action: &str,
+    override_lock: bool,
+) -> Result<(), InstanceEditError> {
+    if !metadata.locked_fields.iter().any(|locked| locked == field) {
+        return Ok(());
+    }
+    if !override_lock {
+        return Err(InstanceEditError::Locked(locked_field_error(
+            field,
+            &metadata.locked_by,
+        )));
+    }
+    log::warn!("⚠ Bloqueo de '{field}' ignorado en {instance_root} ({action})");
+    record_instance_event(
+        instance_root,
+        "instance_lock_overridden",
+        serde_json::json!({
+            "instanceRoot": instance_root,
+            "field": field,
+            "action": action,
+            "lockedBy": metadata.locked_by,
+        }),
+    );
+    Ok(())
+}
+
+/// Igual que [`check_metadata_lock`] leyendo la metadata de disco. Si no se puede leer no
+/// hay bloqueo que aplicar y el comando sigue con su propia validación.
+pub fn ensure_unlocked(
+    instance_root: &str,
+    field: &str,
+    action: &str,
+    override_lock: bool,
+) -> Result<(), InstanceEditError> {
+    match read_instance_metadata(instance_root.to_string()) {
+        Ok(metadata) => check_metadata_lock(instance_root, &metadata, field, action, override_lock),
+        Err(_) => Ok(()),
+    }
+}
+
+/// Normaliza la lista de campos a bloquear y rechaza los desconocidos.
+pub fn normalize_locked_fields(fields: &[String]) -> Result<Vec<String>, String> {
+    let mut normalized = Vec::new();
+    for field in fields {
+        let field = field.trim().to_ascii_lowercase();
+        if field.is_empty() {
+            continue;
+        }
+        if !LOCKABLE_FIELDS.contains(&field.as_str()) {
+            return Err(format!(
+                "Campo no bloqueable: '{field}'. Permitidos: {}",
+                LOCKABLE_FIELDS.join(", ")
+            ));
+        }
+        if !normalized.contains(&field) {
+            normalized.push(field);
+        }
+    }
+    Ok(normalized)
+}
+
+/// Define los campos bloqueados de la instancia y la nota que se muestra al rechazar una
+/// edición. Una lista vacía quita todos los bloqueos.
+#[tauri::command]
+pub fn set_instance_locks(
+    app: AppHandle,
+    instance_root: String,
+    fields: Vec<String>,
+    note: String,
+) -> Result<InstanceMetadata, String> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    let metadata = update_instance_metadata(instance_root.as_str(), |metadata| {
+        metadata.locked_fields = normalize_locked_fields(&fields)?;
+        metadata.locked_by = if metadata.locked_fields.is_empty() {
+            String::new()
+        } else {
+            note.trim().to_string()
+        };
+        Ok::<(), String>(())
+    })?;
+    log::info!(
+        "🔹 Bloqueos de {instance_root}: [{}]",
+        metadata.locked_fields.join(", ")
+    );
+    Ok(metadata)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_and_rejects_unknown_fields() {
+        let fields = vec![
+            " Mods ".to_string(),
+            "java_args".to_string(),
+            "mods".to_string(),
+        ];
+        assert_eq!(
+            normalize_locked_fields(&fields),
+            Ok(vec!["mods".to_string(), "java_args".to_string()])
+        );
+        assert!(normalize_locked_fields(&["name".to_string()]).is_err());
+    }
+
+    #[test]
+    fn locked_error_serializes_with_code_and_note() {
+        let err = InstanceEditError::Locked(locked_field_error("mods", "Pack oficial de Foo"));
+        let value = serde_json::to_value(&err).expect("json");
+        assert_eq!(value["code"], "LOCKED_FIELD");
+        assert_eq!(value["field"], "mods");
+        assert_eq!(value["lockedBy"], "Pack oficial de Foo");
+        assert!(value["message"]
+            .as_str()
+            .is_some_and(|message| message.contains("Pack oficial de Foo")));
+
+        let other =
+            serde_json::to_value(InstanceEditError::from("fallo".to_string())).expect("json");
+        assert_eq!(other, serde_json::json!("fallo"));
+    }
+}
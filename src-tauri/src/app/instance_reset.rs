@@ -0,0 +1,489 @@
+use std::{collections::HashMap, fs, path::Path};
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tauri::AppHandle;
+
+use crate::{
+    app::{
+        event_journal::record_instance_event,
+        instance_locks::{ensure_unlocked, InstanceEditError},
+        instance_service::{
+            copy_dir_recursive, is_instance_running, shared_mods_dir, VERIFICATION_MARKER_FILE,
+        },
+        instance_upgrade::snapshot_instance,
+        trusted_root::resolve_trusted_instance_root,
+    },
+    infrastructure::checksum::sha1::compute_file_sha1,
+    shared::result::AppResult,
+};
+
+/// Lista de mods con la que se importó la instancia, para poder volver a ella.
+pub const IMPORT_MANIFEST_FILE: &str = ".import-manifest.json";
+/// Copias (hardlinks si el sistema lo permite) de los mods originales del import.
+const IMPORT_ORIGINALS_DIR: &str = ".import-original";
+
+/// Qué conservar al restablecer. Por defecto se guardan mundos, capturas y servidores.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ResetKeepOptions {
+    pub saves: bool,
+    pub screenshots: bool,
+    pub servers_dat: bool,
+    pub options_txt: bool,
+    pub resourcepacks: bool,
+}
+
+impl Default for ResetKeepOptions {
+    fn default() -> Self {
+        Self {
+            saves: true,
+            screenshots: true,
+            servers_dat: true,
+            options_txt: false,
+            resourcepacks: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResetReport {
+    pub snapshot_path: String,
+    pub removed: Vec<String>,
+    pub kept: Vec<String>,
+    /// Mods devueltos desde el manifest de importación.
+    pub restored_mods: Vec<String>,
+    /// Mods del manifest cuya copia original ya no existe o no coincide con su SHA1.
+    pub missing_mods: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+struct ImportedModFile {
+    file_name: String,
+    sha1: String,
+    size: u64,
+    /// `env.client` del pack Modrinth (`required`/`optional`); ausente fuera de un pack.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    env: Option<String>,
+}
+
+/// Archivo opcional del pack (`env.client: optional`) que no se instaló; la interfaz
+/// puede ofrecerlo e instalarlo después con `install_optional_pack_files`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct OptionalPackFile {
+    /// Posición en `files` de `modrinth.index.json`.
+    pub index: usize,
+    pub path: String,
+    pub sha1: Option<String>,
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ImportManifest {
+    created_at: String,
+    mods: Vec<ImportedModFile>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    optional_files: Vec<OptionalPackFile>,
+}
+
+fn is_mod_file(name: &str) -> bool {
+    let lower = name.to_ascii_lowercase();
+    [".jar", ".jar.disabled", ".zip", ".litemod"]
+        .iter()
+        .any(|extension| lower.ends_with(extension))
+}
+
+fn link_or_copy(source: &Path, target: &Path) -> AppResult<()> {
+    if fs::hard_link(source, target).is_ok() {
+        return Ok(());
+    }
+    fs::copy(source, target)
+        .map(|_| ())
+        .map_err(|err| format!("No se pudo copiar {}: {err}", source.display()))
+}
+
+/// Guarda el conjunto de mods con el que se importó la instancia. Se llama justo después
+/// del import; sin este manifest el restablecimiento deja `mods/` vacío.
+pub(crate) fn record_import_manifest(instance_root: &Path, mods_dir: &Path) -> AppResult<usize> {
+    write_import_manifest(instance_root, mods_dir, |_| Some(None), Vec::new())
+}
+
+/// Igual que `record_import_manifest` pero solo con los mods del pack (sin los que añadió
+/// el usuario), cada uno con su `env.client`, y los opcionales pendientes de instalar.
+pub(crate) fn record_pack_import_manifest(
+    instance_root: &Path,
+    mods_dir: &Path,
+    pack_mods: &HashMap<String, Option<String>>,
+    optional_files: Vec<OptionalPackFile>,
+) -> AppResult<usize> {
+    write_import_manifest(
+        instance_root,
+        mods_dir,
+        |name| pack_mods.get(name).cloned(),
+        optional_files,
+    )
+}
+
+/// Mods del manifest con su `env.client`, para reescribirlo conservando la clasificación.
+pub(crate) fn imported_mod_envs(instance_root: &Path) -> HashMap<String, Option<String>> {
+    read_import_manifest(instance_root)
+        .map(|manifest| {
+            manifest
+                .mods
+                .into_iter()
+                .map(|entry| (entry.file_name, entry.env))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Archivos opcionales del pack que el usuario aún no ha instalado.
+pub(crate) fn pending_optional_files(instance_root: &Path) -> Vec<OptionalPackFile> {
+    read_import_manifest(instance_root)
+        .map(|manifest| manifest.optional_files)
+        .unwrap_or_default()
+}
+
+/// Mods (nombre y SHA1) del manifest de importación.
+pub(crate) fn imported_mods(instance_root: &Path) -> Option<Vec<(String, String)>> {
+    read_import_manifest(instance_root).map(|manifest| {
+        manifest
+            .mods
+            .into_iter()
+            .map(|entry| (entry.file_name, entry.sha1))
+            .collect()
+    })
+}
+
+/// `env_of` devuelve `None` para los mods que no van en el manifest y `Some(env)` para
+/// los que sí.
+fn write_import_manifest(
+    instance_root: &Path,
+    mods_dir: &Path,
+    env_of: impl Fn(&str) -> Option<Option<String>>,
+    optional_files: Vec<OptionalPackFile>,
+) -> AppResult<usize> {
+    let originals = instance_root.join(IMPORT_ORIGINALS_DIR).join("mods");
+    if originals.exists() {
+        remove_path(&originals)?;
+    }
+    fs::create_dir_all(&originals)
+        .map_err(|err| format!("No se pudo crear {}: {err}", originals.display()))?;
+    let mut mods = Vec::new();
+    for entry in fs::read_dir(mods_dir).into_iter().flatten().flatten() {
+        let path = entry.path();
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        if !path.is_file() || !is_mod_file(&file_name) {
+            continue;
+        }
+        let Some(env) = env_of(&file_name) else {
+            continue;
+        };
+        link_or_copy(&path, &originals.join(&file_name))?;
+        mods.push(ImportedModFile {
+            sha1: compute_file_sha1(&path)?,
+            size: entry.metadata().map(|meta| meta.len()).unwrap_or(0),
+            file_name,
+            env,
+        });
+    }
+    mods.sort_by(|a, b| a.file_name.cmp(&b.file_name));
+    let manifest = ImportManifest {
+        created_at: chrono::Utc::now().to_rfc3339(),
+        mods,
+        optional_files,
+    };
+    let path = instance_root.join(IMPORT_MANIFEST_FILE);
+    fs::write(
+        &path,
+        serde_json::to_string_pretty(&manifest).map_err(|err| err.to_string())?,
+    )
+    .map_err(|err| format!("No se pudo guardar {}: {err}", path.display()))?;
+    Ok(manifest.mods.len())
+}
+
+fn read_import_manifest(instance_root: &Path) -> Option<ImportManifest> {
+    let raw = fs::read_to_string(instance_root.join(IMPORT_MANIFEST_FILE)).ok()?;
+    serde_json::from_str(&raw).ok()
+}
+
+fn remove_path(path: &Path) -> AppResult<()> {
+    let result = if path.is_dir() {
+        fs::remove_dir_all(path)
+    } else {
+        fs::remove_file(path)
+    };
+    result.map_err(|err| format!("No se pudo eliminar {}: {err}", path.display()))
+}
+
+/// Borra `relative` dentro de `root` si existe y lo anota en el informe.
+fn wipe(root: &Path, relative: &str, report: &mut ResetReport) -> AppResult<()> {
+    let path = root.join(relative);
+    if path.exists() {
+        remove_path(&path)?;
+        report.removed.push(relative.to_string());
+    }
+    Ok(())
+}
+
+fn restore_import_mods(
+    instance_root: &Path,
+    manifest: &ImportManifest,
+    mods_dir: &Path,
+    report: &mut ResetReport,
+) -> AppResult<()> {
+    let originals = instance_root.join(IMPORT_ORIGINALS_DIR).join("mods");
+    for entry in &manifest.mods {
+        let source = originals.join(&entry.file_name);
+        let intact = source.is_file()
+            && compute_file_sha1(&source)
+                .map(|sha1| sha1.eq_ignore_ascii_case(&entry.sha1))
+                .unwrap_or(false);
+        if !intact {
+            report.missing_mods.push(entry.file_name.clone());
+            continue;
+        }
+        link_or_copy(&source, &mods_dir.join(&entry.file_name))?;
+        report.restored_mods.push(entry.file_name.clone());
+    }
+    Ok(())
+}
+
+/// Restablece el game dir conservando lo que indique `keep`. Con `reset_mods = false` los
+/// mods no se tocan.
+fn reset_game_dir(
+    instance_root: &Path,
+    minecraft_root: &Path,
+    reset_mods: bool,
+    keep: &ResetKeepOptions,
+) -> AppResult<ResetReport> {
+    let mut report = ResetReport::default();
+
+    for (relative, keep_it) in [
+        ("saves", keep.saves),
+        ("screenshots", keep.screenshots),
+        ("servers.dat", keep.servers_dat),
+        ("servers.dat_old", keep.servers_dat),
+        ("options.txt", keep.options_txt),
+        ("resourcepacks", keep.resourcepacks),
+    ] {
+        if keep_it {
+            if minecraft_root.join(relative).exists() {
+                report.kept.push(relative.to_string());
+            }
+        } else {
+            wipe(minecraft_root, relative, &mut report)?;
+        }
+    }
+
+    for relative in ["config", "natives", "logs", "crash-reports"] {
+        wipe(minecraft_root, relative, &mut report)?;
+    }
+    for entry in fs::read_dir(minecraft_root).into_iter().flatten().flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name.starts_with("hs_err_pid") && name.ends_with(".log") {
+            wipe(minecraft_root, &name, &mut report)?;
+        }
+    }
+    fs::create_dir_all(minecraft_root.join("config"))
+        .map_err(|err| format!("No se pudo recrear config/: {err}"))?;
+
+    if reset_mods {
+        let mods_dir = minecraft_root.join("mods");
+        if mods_dir.exists() {
+            remove_path(&mods_dir)?;
+            report.removed.push("mods".to_string());
+        }
+        fs::create_dir_all(&mods_dir)
+            .map_err(|err| format!("No se pudo recrear {}: {err}", mods_dir.display()))?;
+        if let Some(manifest) = read_import_manifest(instance_root) {
+            restore_import_mods(instance_root, &manifest, &mods_dir, &mut report)?;
+        }
+    } else {
+        report.kept.push("mods".to_string());
+    }
+
+    wipe(instance_root, VERIFICATION_MARKER_FILE, &mut report)?;
+    Ok(report)
+}
+
+/// Deja la instancia como recién creada conservando mundos y lo que pida `keep`. Antes
+/// toma un snapshot de metadata, mods y config; falla si la instancia está en ejecución.
+#[tauri::command]
+pub fn reset_instance(
+    app: AppHandle,
+    instance_root: String,
+    keep: Option<ResetKeepOptions>,
+    override_lock: Option<bool>,
+) -> Result<ResetReport, InstanceEditError> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    if is_instance_running(instance_root.as_str()) {
+        return Err(
+            "La instancia está en ejecución; ciérrala antes de restablecerla."
+                .to_string()
+                .into(),
+        );
+    }
+    let root = instance_root.path().to_path_buf();
+    let keep = keep.unwrap_or_default();
+    // Con manifest de importación los mods vuelven al pack original, así que un bloqueo de
+    // `mods` (típico de modpacks) no impide el restablecimiento.
+    if !root.join(IMPORT_MANIFEST_FILE).is_file() {
+        ensure_unlocked(
+            instance_root.as_str(),
+            "mods",
+            "restablecer la instancia",
+            override_lock.unwrap_or(false),
+        )?;
+    }
+
+    let minecraft_root = root.join("minecraft");
+    // La carpeta compartida es de varias instancias: restablecer esta no puede vaciarla.
+    let shared_mods = shared_mods_dir(&root);
+    if let Some(shared) = &shared_mods {
+        log::warn!(
+            "⚠ {instance_root} usa la carpeta de mods compartida {}; se conserva al restablecer",
+            shared.display()
+        );
+    }
+    let snapshot = snapshot_instance(&root)?;
+    let config_dir = minecraft_root.join("config");
+    if config_dir.is_dir() {
+        copy_dir_recursive(&config_dir, &snapshot.join("config"))?;
+    }
+
+    instance_root.revalidate()?;
+    let mut report = reset_game_dir(&root, &minecraft_root, shared_mods.is_none(), &keep)?;
+    report.snapshot_path = snapshot.display().to_string();
+    record_instance_event(
+        instance_root.as_str(),
+        "instance_reset",
+        json!({
+            "removed": report.removed,
+            "kept": report.kept,
+            "restoredMods": report.restored_mods.len(),
+            "missingMods": report.missing_mods,
+        }),
+    );
+    log::info!(
+        "✔ Instancia restablecida {instance_root}: {} elementos eliminados, {} conservados",
+        report.removed.len(),
+        report.kept.len()
+    );
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn synthetic_instance(prefix: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("{prefix}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let mc = root.join("minecraft");
+        for dir in [
+            "saves/World",
+            "screenshots",
+            "resourcepacks",
+            "config/create",
+            "mods",
+            "natives",
+            "logs",
+            "crash-reports",
+        ] {
+            fs::create_dir_all(mc.join(dir)).expect("dir");
+        }
+        fs::write(mc.join("saves/World/level.dat"), "world").expect("level");
+        fs::write(mc.join("servers.dat"), "servers").expect("servers");
+        fs::write(mc.join("options.txt"), "fov:0.0").expect("options");
+        fs::write(mc.join("config/create/client.toml"), "x=1").expect("config");
+        fs::write(mc.join("mods/create.jar"), "create").expect("mod");
+        fs::write(mc.join("hs_err_pid123.log"), "crash").expect("hs_err");
+        fs::write(root.join(VERIFICATION_MARKER_FILE), "{}").expect("marker");
+        root
+    }
+
+    #[test]
+    fn default_reset_keeps_worlds_and_wipes_the_rest() {
+        let root = synthetic_instance("interface-reset-default");
+        let mc = root.join("minecraft");
+
+        let report = reset_game_dir(&root, &mc, true, &ResetKeepOptions::default()).expect("reset");
+
+        assert!(mc.join("saves/World/level.dat").is_file());
+        assert!(mc.join("servers.dat").is_file());
+        assert!(!mc.join("options.txt").exists());
+        assert!(!mc.join("resourcepacks").exists());
+        assert!(mc.join("config").is_dir());
+        assert!(!mc.join("config/create").exists());
+        assert!(mc.join("mods").is_dir());
+        assert!(!mc.join("mods/create.jar").exists());
+        assert!(!mc.join("hs_err_pid123.log").exists());
+        assert!(!root.join(VERIFICATION_MARKER_FILE).exists());
+        assert_eq!(report.kept, vec!["saves", "screenshots", "servers.dat"]);
+        for removed in ["options.txt", "resourcepacks", "config", "natives", "mods"] {
+            assert!(
+                report.removed.iter().any(|item| item == removed),
+                "{removed}"
+            );
+        }
+
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn keep_flags_are_honored_and_imported_mods_come_back() {
+        let root = synthetic_instance("interface-reset-keep");
+        let mc = root.join("minecraft");
+        let mods = mc.join("mods");
+        assert_eq!(record_import_manifest(&root, &mods).expect("manifest"), 1);
+        fs::write(mods.join("added-later.jar"), "extra").expect("extra mod");
+
+        let keep = ResetKeepOptions {
+            saves: false,
+            screenshots: true,
+            servers_dat: false,
+            options_txt: true,
+            resourcepacks: true,
+        };
+        let report = reset_game_dir(&root, &mc, true, &keep).expect("reset");
+
+        assert!(!mc.join("saves").exists());
+        assert!(!mc.join("servers.dat").exists());
+        assert!(mc.join("options.txt").is_file());
+        assert!(mc.join("resourcepacks").is_dir());
+        assert_eq!(
+            fs::read_to_string(mods.join("create.jar")).expect("mod"),
+            "create"
+        );
+        assert!(!mods.join("added-later.jar").exists());
+        assert_eq!(report.restored_mods, vec!["create.jar"]);
+        assert!(report.missing_mods.is_empty());
+        assert_eq!(
+            report.kept,
+            vec!["screenshots", "options.txt", "resourcepacks"]
+        );
+
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn shared_mods_folder_is_left_alone() {
+        let root = synthetic_instance("interface-reset-shared-mods");
+        let mc = root.join("minecraft");
+
+        let report =
+            reset_game_dir(&root, &mc, false, &ResetKeepOptions::default()).expect("reset");
+
+        assert!(mc.join("mods/create.jar").is_file());
+        assert!(report.kept.iter().any(|item| item == "mods"));
+        assert!(!report.removed.iter().any(|item| item == "mods"));
+
+        let _ = fs::remove_dir_all(root);
+    }
+}
@@ -0,0 +1,596 @@
+// Búsqueda de contenido entre instancias.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    io::Read,
+    path::{Path, PathBuf},
+    sync::{Mutex, OnceLock},
+};
+
+use flate2::read::GzDecoder;
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::{
+    app::{
+        instance_service::{effective_mods_dir, read_instance_metadata},
+        launch_lock::mtime_millis,
+        mod_duplicates::read_mod_descriptor,
+        settings_service::resolve_instances_root,
+    },
+    domain::models::instance::InstanceMetadata,
+    infrastructure::filesystem::file_ops::write_file_replacing,
+};
+
+const CONTENT_INDEX_FILE: &str = ".content-index.json";
+const CONTENT_INDEX_VERSION: u32 = 1;
+/// Archivos de `config/` que se indexan como máximo por instancia.
+const MAX_CONFIG_FILES: usize = 4000;
+/// `level.dat` descomprimido más grande que se examina.
+const MAX_LEVEL_DAT_BYTES: u64 = 16 * 1024 * 1024;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct IndexedMod {
+    pub file_name: String,
+    pub size: u64,
+    pub modified_ms: u64,
+    pub mod_id: Option<String>,
+    pub display_name: Option<String>,
+    pub version: Option<String>,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct IndexedWorld {
+    pub folder: String,
+    pub level_name: Option<String>,
+    pub level_dat_modified_ms: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ContentIndex {
+    pub version: u32,
+    pub mods: Vec<IndexedMod>,
+    pub worlds: Vec<IndexedWorld>,
+    /// Rutas relativas a `minecraft/config`, con `/` como separador.
+    pub config_files: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct SearchScope {
+    mods: bool,
+    configs: bool,
+    worlds: bool,
+    names: bool,
+}
+
+impl SearchScope {
+    /// Vacío equivale a buscar en todo.
+    fn parse(scope: &[String]) -> Result<Self, String> {
+        if scope.is_empty() {
+            return Ok(Self {
+                mods: true,
+                configs: true,
+                worlds: true,
+                names: true,
+            });
+        }
+        let mut parsed = Self {
+            mods: false,
+            configs: false,
+            worlds: false,
+            names: false,
+        };
+        for item in scope {
+            match item.trim().to_ascii_lowercase().as_str() {
+                "mods" => parsed.mods = true,
+                "configs" => parsed.configs = true,
+                "worlds" => parsed.worlds = true,
+                "names" => parsed.names = true,
+                other => return Err(format!("Ámbito de búsqueda desconocido: {other}")),
+            }
+        }
+        Ok(parsed)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchHit {
+    /// `mod`, `config`, `world`, `name`, `tag` o `group`.
+    pub kind: &'static str,
+    pub label: String,
+    pub detail: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstanceSearchResult {
+    pub instance_root: String,
+    pub name: String,
+    pub hits: Vec<SearchHit>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstanceSearchResponse {
+    pub results: Vec<InstanceSearchResult>,
+    /// Instancias sin índice todavía; se indexan en segundo plano y aparecerán en la
+    /// siguiente búsqueda.
+    pub unindexed: Vec<String>,
+}
+
+fn index_cache() -> &'static Mutex<HashMap<String, ContentIndex>> {
+    static CACHE: OnceLock<Mutex<HashMap<String, ContentIndex>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn refreshing() -> &'static Mutex<HashSet<String>> {
+    static REFRESHING: OnceLock<Mutex<HashSet<String>>> = OnceLock::new();
+    REFRESHING.get_or_init(|| Mutex::new(HashSet::new()))
+}
+
+fn cache_key(instance_root: &Path) -> String {
+    instance_root.display().to_string()
+}
+
+fn read_index_file(instance_root: &Path) -> Option<ContentIndex> {
+    let raw = fs::read_to_string(instance_root.join(CONTENT_INDEX_FILE)).ok()?;
+    serde_json::from_str::<ContentIndex>(&raw)
+        .ok()
+        .filter(|index| index.version == CONTENT_INDEX_VERSION)
+}
+
+/// Índice en memoria o, si no, el guardado en disco.
+fn cached_index(instance_root: &Path) -> Option<ContentIndex> {
+    let key = cache_key(instance_root);
+    if let Some(index) = index_cache()
+        .lock()
+        .ok()
+        .and_then(|cache| cache.get(&key).cloned())
+    {
+        return Some(index);
+    }
+    let index = read_index_file(instance_root)?;
+    if let Ok(mut cache) = index_cache().lock() {
+        cache.insert(key, index.clone());
+    }
+    Some(index)
+}
+
+/// `LevelName` del `Data` de un `level.dat` (NBT comprimido con gzip). Se busca la etiqueta
+/// de texto por su cabecera en lugar de recorrer todo el árbol NBT.
+fn read_level_name(level_dat: &Path) -> Option<String> {
+    let file = fs::File::open(level_dat).ok()?;
+    let mut bytes = Vec::new();
+    GzDecoder::new(file)
+        .take(MAX_LEVEL_DAT_BYTES)
+        .read_to_end(&mut bytes)
+        .ok()?;
+    const TAG: &[u8] = b"\x08\x00\x09LevelName";
+    let start = bytes.windows(TAG.len()).position(|window| window == TAG)? + TAG.len();
+    let len = u16::from_be_bytes([*bytes.get(start)?, *bytes.get(start + 1)?]) as usize;
+    let name = bytes.get(start + 2..start + 2 + len)?;
+    Some(String::from_utf8_lossy(name).trim().to_string()).filter(|name| !name.is_empty())
+}
+
+fn index_mods(mods_dir: &Path, previous: &[IndexedMod]) -> Vec<IndexedMod> {
+    let Ok(entries) = fs::read_dir(mods_dir) else {
+        return Vec::new();
+    };
+    let mut mods = entries
+        .flatten()
+        .filter_map(|entry| {
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            let lower = file_name.to_ascii_lowercase();
+            let enabled = lower.ends_with(".jar");
+            if !enabled && !lower.ends_with(".jar.disabled") {
+                return None;
+            }
+            let meta = entry.metadata().ok().filter(|meta| meta.is_file())?;
+            let modified_ms = mtime_millis(&entry.path()).unwrap_or_default();
+            if let Some(known) = previous.iter().find(|known| {
+                known.file_name == file_name
+                    && known.size == meta.len()
+                    && known.modified_ms == modified_ms
+            }) {
+                return Some(known.clone());
+            }
+            let descriptor = read_mod_descriptor(&entry.path());
+            Some(IndexedMod {
+                size: meta.len(),
+                modified_ms,
+                mod_id: descriptor.as_ref().map(|d| d.mod_id.clone()),
+                display_name: descriptor.as_ref().and_then(|d| d.name.clone()),
+                version: descriptor.and_then(|d| d.version),
+                enabled,
+                file_name,
+            })
+        })
+        .collect::<Vec<_>>();
+    mods.sort_by(|a, b| a.file_name.cmp(&b.file_name));
+    mods
+}
+
+fn index_worlds(saves_dir: &Path, previous: &[IndexedWorld]) -> Vec<IndexedWorld> {
+    let Ok(entries) = fs::read_dir(saves_dir) else {
+        return Vec::new();
+    };
+    let mut worlds = entries
+        .flatten()
+        .filter_map(|entry| {
+            let level_dat = entry.path().join("level.dat");
+            if !level_dat.is_file() {
+                return None;
+            }
+            let folder = entry.file_name().to_string_lossy().to_string();
+            let modified_ms = mtime_millis(&level_dat).unwrap_or_default();
+            if let Some(known) = previous
+                .iter()
+                .find(|known| known.folder == folder && known.level_dat_modified_ms == modified_ms)
+            {
+                return Some(known.clone());
+            }
+            Some(IndexedWorld {
+                level_name: read_level_name(&level_dat),
+                level_dat_modified_ms: modified_ms,
+                folder,
+            })
+        })
+        .collect::<Vec<_>>();
+    worlds.sort_by(|a, b| a.folder.cmp(&b.folder));
+    worlds
+}
+
+fn index_config_files(config_dir: &Path) -> Vec<String> {
+    let mut files = Vec::new();
+    let mut pending = vec![config_dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                pending.push(path);
+            } else if let Ok(relative) = path.strip_prefix(config_dir) {
+                files.push(relative.to_string_lossy().replace('\\', "/"));
+                if files.len() >= MAX_CONFIG_FILES {
+                    files.sort();
+                    return files;
+                }
+            }
+        }
+    }
+    files.sort();
+    files
+}
+
+/// Reconstruye el índice de una instancia. Con `incremental` se reaprovechan los mods y
+/// mundos cuyo archivo no cambió desde la pasada anterior.
+pub(crate) fn refresh_content_index(
+    instance_root: &Path,
+    incremental: bool,
+) -> Result<ContentIndex, String> {
+    let previous = incremental
+        .then(|| cached_index(instance_root))
+        .flatten()
+        .unwrap_or_default();
+    let game_dir = instance_root.join("minecraft");
+    let index = ContentIndex {
+        version: CONTENT_INDEX_VERSION,
+        mods: index_mods(&effective_mods_dir(instance_root), &previous.mods),
+        worlds: index_worlds(&game_dir.join("saves"), &previous.worlds),
+        config_files: index_config_files(&game_dir.join("config")),
+    };
+    let raw = serde_json::to_string(&index)
+        .map_err(|err| format!("No se pudo serializar el índice de contenido: {err}"))?;
+    let path = instance_root.join(CONTENT_INDEX_FILE);
+    write_file_replacing(&path, raw.as_bytes(), true)
+        .map_err(|err| format!("No se pudo guardar {}: {err}", path.display()))?;
+    if let Ok(mut cache) = index_cache().lock() {
+        cache.insert(cache_key(instance_root), index.clone());
+    }
+    Ok(index)
+}
+
+/// Actualiza el índice de la instancia en segundo plano (tras gestionar mods o cerrar el
+/// juego). Si ya hay una actualización en curso para la instancia, no se lanza otra.
+pub(crate) fn schedule_content_index_refresh(instance_root: &Path) {
+    let key = cache_key(instance_root);
+    let Ok(mut running) = refreshing().lock() else {
+        return;
+    };
+    if !running.insert(key.clone()) {
+        return;
+    }
+    drop(running);
+    let instance_root = instance_root.to_path_buf();
+    std::thread::spawn(move || {
+        if let Err(err) = refresh_content_index(&instance_root, true) {
+            log::warn!("⚠ {err}");
+        }
+        if let Ok(mut running) = refreshing().lock() {
+            running.remove(&key);
+        }
+    });
+}
+
+fn matches_terms(haystack: &str, terms: &[String]) -> bool {
+    let haystack = haystack.to_lowercase();
+    terms.iter().all(|term| haystack.contains(term.as_str()))
+}
+
+fn search_instance(
+    metadata: &InstanceMetadata,
+    index: Option<&ContentIndex>,
+    terms: &[String],
+    scope: SearchScope,
+) -> Vec<SearchHit> {
+    let mut hits = Vec::new();
+    if scope.names {
+        if matches_terms(&metadata.name, terms) {
+            hits.push(SearchHit {
+                kind: "name",
+                label: metadata.name.clone(),
+                detail: None,
+            });
+        }
+        if !metadata.group.trim().is_empty() && matches_terms(&metadata.group, terms) {
+            hits.push(SearchHit {
+                kind: "group",
+                label: metadata.group.clone(),
+                detail: None,
+            });
+        }
+        for tag in metadata.tags.iter().filter(|tag| matches_terms(tag, terms)) {
+            hits.push(SearchHit {
+                kind: "tag",
+                label: tag.clone(),
+                detail: None,
+            });
+        }
+    }
+    let Some(index) = index else {
+        return hits;
+    };
+    if scope.mods {
+        for entry in &index.mods {
+            let haystack = [
+                entry.mod_id.as_deref(),
+                entry.display_name.as_deref(),
+                entry.version.as_deref(),
+                Some(entry.file_name.as_str()),
+            ]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join(" ");
+            if matches_terms(&haystack, terms) {
+                hits.push(SearchHit {
+                    kind: "mod",
+                    label: entry
+                        .display_name
+                        .clone()
+                        .or_else(|| entry.mod_id.clone())
+                        .unwrap_or_else(|| entry.file_name.clone()),
+                    detail: Some(format!(
+                        "{}{}{}",
+                        entry.file_name,
+                        entry
+                            .version
+                            .as_deref()
+                            .map(|version| format!(" · {version}"))
+                            .unwrap_or_default(),
+                        if entry.enabled { "" } else { " · desactivado" }
+                    )),
+                });
+            }
+        }
+    }
+    if scope.worlds {
+        for world in &index.worlds {
+            let haystack = format!(
+                "{} {}",
+                world.folder,
+                world.level_name.as_deref().unwrap_or_default()
+            );
+            if matches_terms(&haystack, terms) {
+                hits.push(SearchHit {
+                    kind: "world",
+                    label: world
+                        .level_name
+                        .clone()
+                        .unwrap_or_else(|| world.folder.clone()),
+                    detail: Some(format!("saves/{}", world.folder)),
+                });
+            }
+        }
+    }
+    if scope.configs {
+        for file in index
+            .config_files
+            .iter()
+            .filter(|file| matches_terms(file, terms))
+        {
+            hits.push(SearchHit {
+                kind: "config",
+                label: file.clone(),
+                detail: None,
+            });
+        }
+    }
+    hits
+}
+
+fn instance_roots(app: &AppHandle) -> Result<Vec<PathBuf>, String> {
+    let instances_root = resolve_instances_root(app)?;
+    let Ok(entries) = fs::read_dir(&instances_root) else {
+        return Ok(Vec::new());
+    };
+    let mut roots = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.join(".instance.json").is_file())
+        .collect::<Vec<_>>();
+    roots.sort();
+    Ok(roots)
+}
+
+/// Busca `query` en las instancias. `scope` combina `mods`, `configs`, `worlds` y `names`;
+/// vacío busca en todo. Todas las palabras de la consulta deben aparecer en el elemento.
+#[tauri::command]
+pub fn search_instances(
+    app: AppHandle,
+    query: String,
+    scope: Vec<String>,
+) -> Result<InstanceSearchResponse, String> {
+    let scope = SearchScope::parse(&scope)?;
+    let terms = query
+        .split_whitespace()
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>();
+    let mut response = InstanceSearchResponse {
+        results: Vec::new(),
+        unindexed: Vec::new(),
+    };
+    if terms.is_empty() {
+        return Ok(response);
+    }
+    for root in instance_roots(&app)? {
+        let Ok(metadata) = read_instance_metadata(root.display().to_string()) else {
+            continue;
+        };
+        let index = cached_index(&root);
+        if index.is_none() {
+            response.unindexed.push(root.display().to_string());
+            schedule_content_index_refresh(&root);
+        }
+        let hits = search_instance(&metadata, index.as_ref(), &terms, scope);
+        if !hits.is_empty() {
+            response.results.push(InstanceSearchResult {
+                instance_root: root.display().to_string(),
+                name: metadata.name,
+                hits,
+            });
+        }
+    }
+    Ok(response)
+}
+
+/// Reconstruye desde cero el índice de todas las instancias (recuperación de un índice
+/// dañado o desfasado). Devuelve cuántas se indexaron.
+#[tauri::command]
+pub fn rebuild_instance_search_index(app: AppHandle) -> Result<usize, String> {
+    if let Ok(mut cache) = index_cache().lock() {
+        cache.clear();
+    }
+    let mut indexed = 0;
+    for root in instance_roots(&app)? {
+        match refresh_content_index(&root, false) {
+            Ok(_) => indexed += 1,
+            Err(err) => log::warn!("⚠ {err}"),
+        }
+    }
+    log::info!("✔ Índice de búsqueda reconstruido: {indexed} instancias");
+    Ok(indexed)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use flate2::{write::GzEncoder, Compression};
+
+    use super::*;
+    use crate::shared::test_support::test_temp_dir;
+
+    fn write_level_dat(world: &Path, level_name: &str) {
+        fs::create_dir_all(world).expect("world");
+        let mut nbt = b"\x0a\x00\x00\x0a\x00\x04Data\x03\x00\x07version\x00\x00\x4a\xbd".to_vec();
+        nbt.extend_from_slice(b"\x08\x00\x09LevelName");
+        nbt.extend_from_slice(&(level_name.len() as u16).to_be_bytes());
+        nbt.extend_from_slice(level_name.as_bytes());
+        nbt.extend_from_slice(b"\x00\x00");
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&nbt).expect("gzip");
+        fs::write(world.join("level.dat"), encoder.finish().expect("gzip")).expect("level.dat");
+    }
+
+    fn write_fabric_mod(path: &Path, id: &str, name: &str, version: &str) {
+        let mut zip = zip::ZipWriter::new(fs::File::create(path).expect("jar"));
+        zip.start_file("fabric.mod.json", zip::write::SimpleFileOptions::default())
+            .expect("entry");
+        zip.write_all(
+            serde_json::json!({ "id": id, "name": name, "version": version })
+                .to_string()
+                .as_bytes(),
+        )
+        .expect("descriptor");
+        zip.finish().expect("zip");
+    }
+
+    #[test]
+    fn indexes_mods_worlds_and_configs_and_matches_every_term() {
+        let root = test_temp_dir("instance-search");
+        let game_dir = root.join("minecraft");
+        fs::create_dir_all(game_dir.join("mods")).expect("mods");
+        fs::create_dir_all(game_dir.join("config/create")).expect("config");
+        write_fabric_mod(
+            &game_dir.join("mods/create-fabric.jar"),
+            "create",
+            "Create",
+            "0.5.1-f",
+        );
+        write_fabric_mod(
+            &game_dir.join("mods/sodium.jar.disabled"),
+            "sodium",
+            "Sodium",
+            "0.5.8",
+        );
+        fs::write(game_dir.join("config/create/server.toml"), "").expect("config");
+        write_level_dat(&game_dir.join("saves/Mundo 1"), "Skyblock");
+
+        let index = refresh_content_index(&root, false).expect("index");
+        assert_eq!(index.mods.len(), 2);
+        assert_eq!(index.worlds[0].level_name.as_deref(), Some("Skyblock"));
+        assert_eq!(index.config_files, vec!["create/server.toml".to_string()]);
+        assert_eq!(read_index_file(&root), Some(index.clone()));
+
+        let metadata: InstanceMetadata = serde_json::from_value(serde_json::json!({
+            "name": "Tecnología",
+            "minecraftVersion": "1.20.1",
+            "tags": ["create"],
+        }))
+        .expect("metadata");
+        let all = SearchScope::parse(&[]).expect("scope");
+        let terms = |query: &str| {
+            query
+                .split_whitespace()
+                .map(str::to_lowercase)
+                .collect::<Vec<_>>()
+        };
+
+        let hits = search_instance(&metadata, Some(&index), &terms("create 0.5.1"), all);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].kind, "mod");
+        assert_eq!(hits[0].label, "Create");
+
+        let hits = search_instance(&metadata, Some(&index), &terms("skyblock"), all);
+        assert_eq!(hits[0].kind, "world");
+        assert_eq!(hits[0].detail.as_deref(), Some("saves/Mundo 1"));
+
+        let mods_only = SearchScope::parse(&["mods".to_string()]).expect("scope");
+        let hits = search_instance(&metadata, Some(&index), &terms("create"), mods_only);
+        assert!(hits.iter().all(|hit| hit.kind == "mod"));
+        let hits = search_instance(&metadata, Some(&index), &terms("create"), all);
+        let kinds = hits.iter().map(|hit| hit.kind).collect::<Vec<_>>();
+        assert_eq!(kinds, vec!["tag", "mod", "config"]);
+        assert!(SearchScope::parse(&["jars".to_string()]).is_err());
+        let _ = fs::remove_dir_all(root);
+    }
+}
@@ -4,9 +4,9 @@ use std::{
     hash::{Hash, Hasher},
     io::{BufRead, BufReader},
     path::{Path, PathBuf},
-    process::{Command, Stdio},
+    process::{Child, Command, Stdio},
     sync::{
-        atomic::{AtomicBool, Ordering},
+        atomic::{AtomicBool, AtomicUsize, Ordering},
         Arc, Mutex, OnceLock,
     },
     thread,
@@ -27,6 +27,7 @@ use tauri::{AppHandle, Emitter, Manager};
 use zip::ZipArchive;
 
 use crate::domain::auth::{
+    endpoints::minecraft_profile_url,
     microsoft::refresh_microsoft_access_token,
     xbox::{
         authenticate_with_xbox_live, authorize_xsts, has_minecraft_license,
@@ -34,21 +35,131 @@ use crate::domain::auth::{
     },
 };
 
+use crate::app::event_journal::emit_journaled;
 use crate::services::discord_presence;
 
 use crate::{
+    app::console_filters::{ConsoleDecision, ConsoleFilter},
+    app::crash_index::record_session_crashes,
+    app::game_dir_guard::{ensure_game_dir_free, LaunchError},
+    app::hook_approval::check_hook_approval,
+    app::instance_cleanup::cleanup_after_exit,
+    app::instance_locks::{check_metadata_lock, InstanceEditError},
+    app::instance_search::schedule_content_index_refresh,
+    app::launch_changes::{emit_launch_failure, what_changed_since},
+    app::launch_lock::{
+        mark_launch_successful, record_launch_lock, LaunchLockInputs, LockedAssetIndex,
+    },
+    app::launch_prewarm::{
+        file_stamp, launch_inputs_fingerprint, take_prewarmed_launch, LaunchPrewarmSummary,
+        PrewarmedJars, PrewarmedLaunch,
+    },
+    app::launch_watchdog::{
+        configured_phase_budget, current_watchdog, download_bytes_cancellable, run_with_watchdog,
+        LaunchPhaseTiming, LaunchPreparationStatus, LaunchWatchdog,
+    },
+    app::launcher_snapshot::{index_instance_metadata, mark_launcher_snapshot_dirty},
+    app::maintenance::maintenance_in_progress,
+    app::metadata_writer::{flush_metadata_blocking, metadata_writer, JavaPathUpdate},
+    app::mod_duplicates::{describe_duplicate_groups, find_duplicate_groups},
+    app::op_journal::{needs_recovery, JournalEntry, OperationJournal, OP_DOWNLOAD_LIBRARIES},
+    app::playtime::{record_play_session, PlaySession, SessionAccount},
+    app::power_events::{current_power_state, SuspendAwareDeadline},
+    app::quarantine::quarantine_file,
+    app::runtime_metrics::start_runtime_metrics,
+    app::runtime_output::{
+        OutputFlush, OutputThrottle, SessionLog, OUTPUT_FLUSH_INTERVAL, OUTPUT_MAX_LINES_PER_SEC,
+    },
+    app::service_status::{ensure_minecraft_services_available, observe_minecraft_services},
+    app::settings_service::resolve_instances_root,
+    app::startup_profile::{record_startup_profile, StartupProfiler},
+    app::strict_mode::{
+        enforce_strict_mode, StrictViolation, StrictViolations, ASSET_OBJECTS_UNRESOLVED,
+        CLIENT_EXTRA_MISSING, DUPLICATE_MODS, JAVA_ARGS_NORMALIZED, LIBRARY_OVERRIDE_APPLIED,
+        MERGED_JSON_SUMMARY, NATIVES,
+    },
+    app::token_maintenance::{freshest_session, persist_launch_session, record_profile_rename},
+    app::trusted_root::{
+        resolve_trusted_instance_root, resolve_trusted_launcher_data_root, ValidatedInstanceRoot,
+    },
+    app::webhooks::notify_instance_lifecycle,
+    app::window_tweaks::spawn_window_tweaks,
     domain::{
+        instance::interop::known_external_launcher_roots,
+        java::{
+            java_args::{merge_memory_args, normalize_java_args},
+            java_detector::parse_java_major,
+            java_requirement::determine_required_java,
+            jvm_tuning::{default_jvm_flags, JvmTuningInput},
+        },
         minecraft::{
             argument_resolver::{
                 replace_launch_variables, resolve_launch_arguments, unresolved_variables_in_args,
                 LaunchContext,
             },
-            rule_engine::{RuleContext, RuleFeatures},
+            asset::missing_asset_bytes,
+            game_flags::{
+                optional_game_args, telemetry_option, upsert_option_line,
+                validate_optional_game_flags, OptionalGameFlags,
+            },
+            gpu_compat::{gpu_compat_warnings, lwjgl_version_from_version_json},
+            launch_paths::{
+                args_file_path_entries, java_path_list_separator, prepare_java_path_environment,
+                split_args_file_line,
+            },
+            library::{
+                find_library_override, replace_library_version, validate_library_override,
+                LibraryOverride, LibraryOverrideAction,
+            },
+            log4j_mitigation::{
+                has_log4j_override, log4j_jvm_args, log4j_mitigation_for_version, Log4jMitigation,
+                LEGACY_LOG4J_CONFIG_FILE, LEGACY_LOG4J_CONFIG_SHA1, LEGACY_LOG4J_CONFIG_URL,
+            },
+            logging_config::{
+                client_logging_config, decide_logging, logging_jvm_argument, Log4jXmlAssembler,
+                LoggingDecision,
+            },
+            mods_dir::{apply_mods_dir_injection, mods_dir_injection, ModsDirInjection},
+            rule_engine::{
+                reset_unknown_feature_log, trace_version_rules, RuleContext, RuleFeatures,
+            },
+        },
+        models::instance::{
+            default_state, InstanceHealth, InstanceHealthFinding, InstanceMetadata,
+            LaunchAuthSession,
         },
-        models::instance::{InstanceMetadata, LaunchAuthSession},
         models::java::JavaRuntime,
     },
-    services::java_installer::ensure_embedded_java,
+    infrastructure::checksum::{sha1::compute_file_sha1, verification_cache::VerificationCache},
+    infrastructure::downloader::{client::build_http_client, queue::download_with_retry},
+    infrastructure::filesystem::{
+        capabilities::{capability_warnings, probe_filesystem_capabilities},
+        config::load_launcher_config,
+        disk_space::preflight_disk_space,
+        file_ops::write_file_replacing,
+        paths::{configured_launcher_root, is_path_within_root},
+        text_encoding::read_text_repairing,
+        verified_copy::{copy_dir_with, verified_copy_enabled, CopyProgress, VerifiedCopier},
+    },
+    infrastructure::http::{
+        asset_mirrors::{asset_base_urls, AssetSources, FetchError},
+        rate_limit, tls,
+    },
+    platform::{
+        gpu::{detect_gpu_adapters, detect_gpu_info},
+        gpu_preference::{
+            apply_gpu_preference, clear_gpu_preference, wants_dedicated, AppliedGpuPreference,
+            GpuPreference,
+        },
+        linux::current_os,
+        processes::is_process_alive,
+    },
+    services::{
+        java_installer::{ensure_java_build_with, list_java_builds},
+        loader_files::{read_loader_files_manifest, LoaderFilesManifest},
+    },
+    shared::clock::{app_clock, Clock},
+    shared::tasks::{current_task, TaskHandle, TaskProbe},
 };
 
 #[cfg(windows)]
@@ -83,6 +194,18 @@ pub struct LaunchValidationResult {
     pub main_class: String,
     pub logs: Vec<String>,
     pub refreshed_auth_session: LaunchAuthSession,
+    /// Duración de cada fase de la preparación (línea de tiempo del lanzamiento).
+    pub phase_timings: Vec<LaunchPhaseTiming>,
+    /// Overrides de librerías aplicados al resolver el classpath.
+    pub applied_library_overrides: Vec<String>,
+    /// Flags añadidos por `auto_jvm_tuning` (ya incluidos en `jvm_args`).
+    pub auto_jvm_args: Vec<String>,
+    /// Argumento de la configuración de logging del version.json (ya incluido en
+    /// `jvm_args`), si se aplicó.
+    pub logging_argument: Option<String>,
+    pub strict_mode: bool,
+    /// Avisos tolerados que el modo estricto convierte en error.
+    pub strict_violations: Vec<StrictViolation>,
 }
 
 #[derive(Debug, Serialize)]
@@ -102,15 +225,316 @@ struct RuntimeOutputEvent {
     line: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     parsed: Option<RuntimeLogLine>,
+    /// Repetición de una línea agrupada por un filtro de consola (2, 3...); la interfaz la
+    /// suma a la primera aparición en lugar de mostrarla.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    repeat_count: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RuntimeOutputBatchEvent<'a> {
+    instance_root: &'a str,
+    lines: &'a [RuntimeOutputEvent],
+}
+
+/// Margen para que los lectores vacíen las tuberías tras la salida del proceso.
+const OUTPUT_DRAIN_GRACE: Duration = Duration::from_secs(2);
+
+/// Destino compartido de la salida del proceso: log de sesión, cola de stderr y lotes
+/// limitados en ritmo hacia la interfaz.
+#[derive(Clone)]
+struct RuntimeOutputSink {
+    app: AppHandle,
+    instance_root: String,
+    throttle: Arc<Mutex<OutputThrottle<RuntimeOutputEvent>>>,
+    session_log: Arc<SessionLog>,
+    tail: Arc<Mutex<VecDeque<String>>>,
+    /// Emitir también `instance_runtime_output` por línea (compatibilidad).
+    legacy_events: bool,
+    /// Filtros de consola de la instancia, compilados al lanzar.
+    filter: Arc<Mutex<ConsoleFilter>>,
+    stop: Arc<AtomicBool>,
+}
+
+impl RuntimeOutputSink {
+    fn push_line(&self, stream: &str, line: String) {
+        self.session_log.write_line(stream, &line);
+        if let Ok(mut tail) = self.tail.lock() {
+            tail.push_back(format!("[{stream}] {line}"));
+            if tail.len() > 200 {
+                tail.pop_front();
+            }
+        }
+        let mut parsed = None;
+        let mut repeat_count = None;
+        if let Ok(mut filter) = self.filter.lock() {
+            if !filter.is_empty() {
+                parsed = parse_log_line(&line);
+                let decision = filter.classify(
+                    &line,
+                    parsed.as_ref().map(|parsed| parsed.source.as_str()),
+                    parsed.as_ref().map(|parsed| parsed.message.as_str()),
+                    Instant::now(),
+                );
+                match decision {
+                    // Ya está en el log de sesión; solo no llega a la interfaz.
+                    ConsoleDecision::Hide => return,
+                    ConsoleDecision::Repeat { count } => repeat_count = Some(count),
+                    ConsoleDecision::Emit => {}
+                }
+            }
+        }
+        let event = RuntimeOutputEvent {
+            instance_root: self.instance_root.clone(),
+            stream: stream.to_string(),
+            line,
+            parsed,
+            repeat_count,
+        };
+        let flush = self
+            .throttle
+            .lock()
+            .ok()
+            .and_then(|mut throttle| throttle.push(event, Instant::now()));
+        if let Some(flush) = flush {
+            self.emit(flush);
+        }
+    }
+
+    fn flush_due(&self) {
+        let flush = self
+            .throttle
+            .lock()
+            .ok()
+            .and_then(|mut throttle| throttle.tick(Instant::now()));
+        if let Some(flush) = flush {
+            self.emit(flush);
+        }
+    }
+
+    fn flush_all(&self) {
+        let flush = self
+            .throttle
+            .lock()
+            .ok()
+            .and_then(|mut throttle| throttle.drain(Instant::now()));
+        if let Some(flush) = flush {
+            self.emit(flush);
+        }
+    }
+
+    fn emit(&self, flush: OutputFlush<RuntimeOutputEvent>) {
+        let mut lines = flush.lines;
+        for event in lines.iter_mut().filter(|event| event.parsed.is_none()) {
+            event.parsed = parse_log_line(&event.line);
+        }
+        if flush.suppressed > 0 {
+            lines.push(RuntimeOutputEvent {
+                instance_root: self.instance_root.clone(),
+                stream: "system".to_string(),
+                line: format!(
+                    "… {} líneas suprimidas por exceso de salida (completas en {})",
+                    flush.suppressed,
+                    self.session_log.path().display()
+                ),
+                parsed: None,
+                repeat_count: None,
+            });
+        }
+        let _ = self.app.emit(
+            "instance_runtime_output_batch",
+            RuntimeOutputBatchEvent {
+                instance_root: &self.instance_root,
+                lines: &lines,
+            },
+        );
+        if self.legacy_events {
+            for event in &lines {
+                let _ = self.app.emit("instance_runtime_output", event);
+            }
+        }
+    }
+}
+
+fn spawn_output_reader<R: std::io::Read + Send + 'static>(
+    pipe: R,
+    stream: &'static str,
+    output: RuntimeOutputSink,
+) -> thread::JoinHandle<()> {
+    thread::spawn(move || {
+        let reader = BufReader::new(pipe);
+        // El juego escribe su log por stdout; el perfil es de este proceso y este hilo.
+        let mut profiler = (stream == "stdout").then(|| StartupProfiler::new(Instant::now()));
+        // Con la configuración de logging del version.json la consola sale en XMLLayout.
+        let mut assembler = Log4jXmlAssembler::default();
+        let mut emit = |line: String| {
+            if line.trim().is_empty() {
+                return;
+            }
+            if let Some(profile) = profiler
+                .as_mut()
+                .and_then(|profiler| profiler.observe(&line, Instant::now()))
+            {
+                record_startup_profile(&output.app, &output.instance_root, profile);
+            }
+            output.push_line(stream, line);
+        };
+        for line in reader.lines().map_while(Result::ok) {
+            if output.stop.load(Ordering::Relaxed) {
+                break;
+            }
+            assembler.push(line).into_iter().for_each(&mut emit);
+        }
+        assembler.finish().into_iter().for_each(emit);
+    })
+}
+
+/// Espera a que terminen los hilos hasta `grace`; devuelve si terminaron todos.
+fn wait_for_threads(handles: &[thread::JoinHandle<()>], grace: Duration) -> bool {
+    let started = Instant::now();
+    while handles.iter().any(|handle| !handle.is_finished()) {
+        if started.elapsed() >= grace {
+            return false;
+        }
+        thread::sleep(Duration::from_millis(25));
+    }
+    true
+}
+
+fn log4j_mitigation_enabled(app: &AppHandle) -> bool {
+    load_launcher_config(app)
+        .ok()
+        .and_then(|config| config.log4j_mitigation)
+        .unwrap_or(true)
+}
+
+/// Descarga una sola vez el XML de log4j de Mojang en `config/log4j/` del launcher.
+fn ensure_legacy_log4j_config(launcher_root: &Path) -> Result<PathBuf, String> {
+    let target = launcher_root
+        .join("config")
+        .join("log4j")
+        .join(LEGACY_LOG4J_CONFIG_FILE);
+    let client = build_http_client()?;
+    download_with_retry(
+        &client,
+        LEGACY_LOG4J_CONFIG_URL,
+        &target,
+        LEGACY_LOG4J_CONFIG_SHA1,
+        false,
+    )?;
+    Ok(target)
+}
+
+/// Añade la mitigación de log4j que corresponde a la versión, salvo que el usuario o el
+/// version.json ya configuren log4j.
+fn apply_log4j_mitigation(
+    app: &AppHandle,
+    launcher_root: &Path,
+    minecraft_version: &str,
+    jvm_args: &mut Vec<String>,
+    logs: &mut Vec<String>,
+) {
+    let Some(mitigation) = log4j_mitigation_for_version(minecraft_version) else {
+        return;
+    };
+    if !log4j_mitigation_enabled(app) {
+        logs.push(format!(
+            "⚠ mitigación de log4j desactivada en la configuración ({minecraft_version} es vulnerable)"
+        ));
+        return;
+    }
+    if has_log4j_override(jvm_args) {
+        logs.push(
+            "🔹 log4j ya configurado en los argumentos JVM; no se inyecta mitigación".to_string(),
+        );
+        return;
+    }
+    let config_path = match mitigation {
+        Log4jMitigation::NoLookupsProperty => None,
+        Log4jMitigation::LegacyConfigFile => match ensure_legacy_log4j_config(launcher_root) {
+            Ok(path) => Some(path.display().to_string()),
+            Err(err) => {
+                logs.push(format!(
+                    "⚠ No se pudo descargar la configuración de log4j de Mojang: {err}. El juego se inicia sin mitigación."
+                ));
+                None
+            }
+        },
+    };
+    let args = log4j_jvm_args(mitigation, config_path.as_deref());
+    for arg in &args {
+        logs.push(format!("✔ mitigación de log4j inyectada: {arg}"));
+    }
+    jvm_args.extend(args);
+}
+
+/// Descarga el XML de log4j del version.json y añade su argumento si no hay ya un
+/// `configurationFile`. Devuelve el argumento añadido.
+fn apply_client_logging_config(
+    app: &AppHandle,
+    launcher_root: &Path,
+    version_json: &Value,
+    jvm_args: &mut Vec<String>,
+    logs: &mut Vec<String>,
+) -> Option<String> {
+    let config = client_logging_config(version_json)?;
+    let enabled = load_launcher_config(app)
+        .ok()
+        .and_then(|config| config.version_logging_config)
+        .unwrap_or(true);
+    match decide_logging(enabled, jvm_args) {
+        LoggingDecision::Apply => {}
+        LoggingDecision::Disabled => {
+            logs.push(format!(
+                "🔹 configuración de logging de la versión ({}) desactivada en la configuración",
+                config.id
+            ));
+            return None;
+        }
+        LoggingDecision::AlreadyConfigured(existing) => {
+            logs.push(format!(
+                "🔹 logging de la versión ({}) omitido: ya se usa {existing}",
+                config.id
+            ));
+            return None;
+        }
+    }
+    let target = launcher_root
+        .join("assets")
+        .join("log_configs")
+        .join(&config.id);
+    let downloaded = build_http_client()
+        .and_then(|client| download_with_retry(&client, &config.url, &target, &config.sha1, false));
+    if let Err(err) = downloaded {
+        logs.push(format!(
+            "⚠ No se pudo descargar la configuración de logging {}: {err}. Se usa la de log4j por defecto.",
+            config.id
+        ));
+        return None;
+    }
+    let argument = logging_jvm_argument(&config, &target.display().to_string());
+    logs.push(format!(
+        "✔ configuración de logging de la versión: {argument}"
+    ));
+    jvm_args.push(argument.clone());
+    Some(argument)
+}
+
+fn legacy_runtime_output_enabled(app: &AppHandle) -> bool {
+    load_launcher_config(app)
+        .ok()
+        .and_then(|config| config.legacy_runtime_output_events)
+        .unwrap_or(true)
 }
 
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
-struct RuntimeLogLine {
+pub(crate) struct RuntimeLogLine {
     time: String,
-    source: String,
+    pub(crate) source: String,
     level: String,
-    message: String,
+    pub(crate) message: String,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -120,6 +544,22 @@ pub struct RuntimeStatus {
     pub pid: Option<u32>,
     pub exit_code: Option<i32>,
     pub stderr_tail: Vec<String>,
+    /// Preparando el lanzamiento (aún sin proceso).
+    pub preparing: bool,
+    /// Descargas de la preparación en pausa.
+    pub paused: bool,
+    /// Se pidió al juego que guarde y salga; aún no terminó.
+    pub closing: bool,
+    /// La carpeta de la instancia se borró o movió fuera del launcher con el juego abierto.
+    pub folder_missing: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunningInstanceSnapshot {
+    pub instance_root: String,
+    pub pid: Option<u32>,
+    pub uptime_secs: u64,
 }
 
 #[derive(Debug, Clone, Serialize, serde::Deserialize)]
@@ -129,12 +569,74 @@ struct ShortcutRedirect {
     source_launcher: String,
 }
 
+#[derive(Debug, Clone, Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct VerificationMarker {
+    verified_at: String,
+    version_id: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstanceVersionEntry {
+    pub id: String,
+    pub effective: bool,
+    pub json_valid: bool,
+    pub inherits_from: Option<String>,
+    pub size_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PruneInstanceVersionsResult {
+    pub removed: Vec<String>,
+    pub freed_bytes: u64,
+}
+
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct InstanceCardStats {
     pub size_mb: u64,
     pub mods_count: u32,
     pub last_used: Option<String>,
+    /// Errores de lectura encontrados al recorrer la instancia.
+    pub warnings: Vec<String>,
+    /// `true` si alguna carpeta no se pudo leer y los totales se quedan cortos.
+    pub partial: bool,
+}
+
+/// La carpeta de la instancia (o el origen de la redirección) no se puede leer.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct InstanceUnreadableError {
+    /// `INSTANCE_UNREADABLE`, `SOURCE_MISSING` o `SOURCE_DISCONNECTED`.
+    pub code: &'static str,
+    pub path: String,
+    pub message: String,
+}
+
+/// Error de las estadísticas de la tarjeta: la carpeta ilegible va estructurada y el
+/// resto sigue siendo texto.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum CardStatsError {
+    Unreadable(InstanceUnreadableError),
+    Other(String),
+}
+
+impl From<String> for CardStatsError {
+    fn from(err: String) -> Self {
+        CardStatsError::Other(err)
+    }
+}
+
+impl std::fmt::Display for CardStatsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CardStatsError::Unreadable(unreadable) => write!(f, "{}", unreadable.message),
+            CardStatsError::Other(err) => write!(f, "{err}"),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -143,23 +645,39 @@ struct RuntimeState {
     running: bool,
     exit_code: Option<i32>,
     stderr_tail: VecDeque<String>,
-    started_at: Instant,
+    /// Inicio de la sesión en milisegundos Unix según el `Clock` inyectado.
+    started_at_ms: u64,
+    /// Watchdog de la preparación en curso (solo mientras se prepara el lanzamiento).
+    preparation: Option<LaunchWatchdog>,
+    /// Java con el que se lanzó el proceso; `jcmd` vive en la misma carpeta `bin`.
+    java_path: Option<String>,
+    /// Tiempo que el equipo estuvo suspendido durante la sesión; no cuenta como juego.
+    suspended_duration_ms: u64,
+    /// Cierre cooperativo pedido y todavía en su margen de espera.
+    closing: bool,
+    /// La carpeta de la instancia desapareció del disco con el juego abierto.
+    folder_missing: bool,
 }
 
 #[derive(Debug, Clone)]
 struct VerifiedLaunchAuth {
     profile_id: String,
     profile_name: String,
+    /// Nombre que traía la sesión si cambió en Mojang.
+    previous_profile_name: Option<String>,
     minecraft_access_token: String,
     minecraft_access_token_expires_at: Option<u64>,
+    /// El de la sesión o el que Microsoft devolvió al refrescar (rota en cada refresh).
+    microsoft_refresh_token: Option<String>,
     premium_verified: bool,
 }
 
 static RUNTIME_REGISTRY: OnceLock<Mutex<HashMap<String, RuntimeState>>> = OnceLock::new();
-const OFFICIAL_ASSETS_RESOURCES_URL: &str = "https://resources.download.minecraft.net";
+pub(crate) const VERIFICATION_MARKER_FILE: &str = ".verification.json";
+const VERIFICATION_STALE_AFTER_DAYS: i64 = 30;
 static STRUCTURED_LOG_REGEX: OnceLock<Regex> = OnceLock::new();
 
-fn parse_log_line(raw: &str) -> Option<RuntimeLogLine> {
+pub(crate) fn parse_log_line(raw: &str) -> Option<RuntimeLogLine> {
     let regex = STRUCTURED_LOG_REGEX.get_or_init(|| {
         Regex::new(r"\[(\d{2}:\d{2}:\d{2})\]\s+\[(.*?)\]\s+\[(.*?)\]\s+(.*)")
             .expect("Regex de logs de runtime inválida")
@@ -178,6 +696,21 @@ fn runtime_registry() -> &'static Mutex<HashMap<String, RuntimeState>> {
     RUNTIME_REGISTRY.get_or_init(|| Mutex::new(HashMap::new()))
 }
 
+/// Ejecuta `action` solo si no hay instancias en ejecución, con el registro bloqueado para
+/// que ningún lanzamiento se registre mientras tanto.
+pub(crate) fn with_no_running_instances<T>(
+    running_message: &str,
+    action: impl FnOnce() -> T,
+) -> Result<T, String> {
+    let registry = runtime_registry()
+        .lock()
+        .map_err(|_| "No se pudo bloquear el registro de runtime.".to_string())?;
+    if registry.values().any(|state| state.running) {
+        return Err(running_message.to_string());
+    }
+    Ok(action())
+}
+
 pub fn has_running_instances() -> Result<bool, String> {
     let registry = runtime_registry()
         .lock()
@@ -185,18 +718,46 @@ pub fn has_running_instances() -> Result<bool, String> {
     Ok(registry.values().any(|state| state.running))
 }
 
+pub fn running_instances_snapshot(
+    clock: &dyn Clock,
+) -> Result<Vec<RunningInstanceSnapshot>, String> {
+    let now_ms = clock.now_millis();
+    let registry = runtime_registry()
+        .lock()
+        .map_err(|_| "No se pudo bloquear el registro de runtime.".to_string())?;
+    let mut running = registry
+        .iter()
+        .filter(|(_, state)| state.running)
+        .map(|(instance_root, state)| RunningInstanceSnapshot {
+            instance_root: instance_root.clone(),
+            pid: state.pid,
+            uptime_secs: now_ms.saturating_sub(state.started_at_ms) / 1000,
+        })
+        .collect::<Vec<_>>();
+    running.sort_by(|a, b| a.instance_root.cmp(&b.instance_root));
+    Ok(running)
+}
+
 #[tauri::command]
-pub fn get_runtime_status(instance_root: String) -> Result<RuntimeStatus, String> {
+pub fn get_runtime_status(app: AppHandle, instance_root: String) -> Result<RuntimeStatus, String> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
     let registry = runtime_registry()
         .lock()
         .map_err(|_| "No se pudo bloquear el registro de runtime.".to_string())?;
 
-    if let Some(state) = registry.get(&instance_root) {
+    if let Some(state) = registry.get(instance_root.as_str()) {
         return Ok(RuntimeStatus {
             running: state.running,
             pid: state.pid,
             exit_code: state.exit_code,
             stderr_tail: state.stderr_tail.iter().cloned().collect(),
+            preparing: state.preparation.is_some(),
+            paused: state
+                .preparation
+                .as_ref()
+                .is_some_and(LaunchWatchdog::is_paused),
+            closing: state.closing,
+            folder_missing: state.folder_missing,
         });
     }
 
@@ -205,12 +766,21 @@ pub fn get_runtime_status(instance_root: String) -> Result<RuntimeStatus, String
         pid: None,
         exit_code: None,
         stderr_tail: Vec::new(),
+        preparing: false,
+        paused: false,
+        closing: false,
+        folder_missing: false,
     })
 }
 
 #[tauri::command]
-pub fn open_instance_folder(path: String) -> Result<(), String> {
-    let target = Path::new(&path);
+pub fn open_instance_folder(app: AppHandle, path: String) -> Result<(), String> {
+    let validated = resolve_trusted_launcher_data_root(&app, &path)?;
+    open_folder_in_explorer(validated.path())
+}
+
+/// Abre `target` en el explorador del sistema; quien llama ya validó la ruta.
+pub(crate) fn open_folder_in_explorer(target: &Path) -> Result<(), String> {
     if !target.exists() {
         return Err(format!(
             "La carpeta de la instancia no existe: {}",
@@ -250,8 +820,9 @@ pub fn open_instance_folder(path: String) -> Result<(), String> {
 }
 
 #[tauri::command]
-pub fn open_redirect_origin_folder(instance_root: String) -> Result<(), String> {
-    let redirect_path = Path::new(&instance_root).join(".redirect.json");
+pub fn open_redirect_origin_folder(app: AppHandle, instance_root: String) -> Result<(), String> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    let redirect_path = instance_root.path().join(".redirect.json");
     let raw = fs::read_to_string(&redirect_path).map_err(|err| {
         format!(
             "No se pudo leer redirección de atajo en {}: {err}",
@@ -264,10 +835,36 @@ pub fn open_redirect_origin_folder(instance_root: String) -> Result<(), String>
             redirect_path.display()
         )
     })?;
-    open_instance_folder(redirect.source_path)
+    open_folder_in_explorer(Path::new(&redirect.source_path))
+}
+
+/// Copia pesada de una carpeta entera, verificada en destinos extraíbles o de red y con
+/// el progreso en `instance_copy_progress`.
+pub(crate) fn copy_dir_reporting(
+    app: &AppHandle,
+    operation: &str,
+    instance_root: &str,
+    source: &Path,
+    destination: &Path,
+) -> Result<CopyProgress, String> {
+    if !source.exists() {
+        return Err(format!("La carpeta origen no existe: {}", source.display()));
+    }
+    let mut copier = VerifiedCopier::new(verified_copy_enabled(destination, None), |progress| {
+        let _ = app.emit(
+            "instance_copy_progress",
+            serde_json::json!({
+                "operation": operation,
+                "instanceRoot": instance_root,
+                "progress": progress,
+            }),
+        );
+    });
+    copy_dir_with(source, destination, &mut copier)?;
+    copier.finish()
 }
 
-fn copy_dir_recursive(source: &Path, destination: &Path) -> Result<(), String> {
+pub(crate) fn copy_dir_recursive(source: &Path, destination: &Path) -> Result<(), String> {
     if !source.exists() {
         return Err(format!("La carpeta origen no existe: {}", source.display()));
     }
@@ -316,7 +913,7 @@ fn has_game_markers(path: &Path) -> bool {
         || path.join("saves").is_dir()
 }
 
-fn detect_runtime_game_dir(root: &Path) -> Option<PathBuf> {
+pub(crate) fn detect_runtime_game_dir(root: &Path) -> Option<PathBuf> {
     let direct_candidates = [root.join("minecraft"), root.join(".minecraft")];
     if let Some(path) = direct_candidates
         .into_iter()
@@ -369,8 +966,29 @@ fn detect_runtime_game_dir(root: &Path) -> Option<PathBuf> {
     best.map(|(_, path)| path)
 }
 
+/// Copia en caché de la carpeta de origen de un atajo, con su juego en `minecraft/`.
+pub(crate) fn shortcut_runtime_cache_dir(
+    app: &AppHandle,
+    source_path: &str,
+) -> Result<PathBuf, String> {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    source_path.hash(&mut hasher);
+    let cache_bucket = format!("shortcut-{:x}", hasher.finish());
+
+    Ok(import_runtime_cache_root(app)?.join(cache_bucket))
+}
+
+/// Carpeta de las copias de ejecución de atajos importados.
+pub(crate) fn import_runtime_cache_root(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(app
+        .path()
+        .app_cache_dir()
+        .map_err(|err| format!("No se pudo resolver cache dir para atajo: {err}"))?
+        .join("import-runtime-cache"))
+}
+
 fn prepare_runtime_instance_root(app: &AppHandle, instance_root: &str) -> Result<String, String> {
-    let metadata = get_instance_metadata(instance_root.to_string())?;
+    let metadata = read_instance_metadata(instance_root.to_string())?;
     if !metadata.state.eq_ignore_ascii_case("redirect") {
         return Ok(instance_root.to_string());
     }
@@ -389,22 +1007,24 @@ fn prepare_runtime_instance_root(app: &AppHandle, instance_root: &str) -> Result
         )
     })?;
 
-    let mut hasher = std::collections::hash_map::DefaultHasher::new();
-    redirect.source_path.hash(&mut hasher);
-    let cache_bucket = format!("shortcut-{:x}", hasher.finish());
-
-    let cache_root = app
-        .path()
-        .app_cache_dir()
-        .map_err(|err| format!("No se pudo resolver cache dir para atajo: {err}"))?
-        .join("import-runtime-cache")
-        .join(cache_bucket);
+    let cache_root = shortcut_runtime_cache_dir(app, &redirect.source_path)?;
 
     let needs_refresh = !cache_root.exists();
     if needs_refresh {
         fs::create_dir_all(&cache_root)
             .map_err(|err| format!("No se pudo crear cache temporal de atajo: {err}"))?;
-        copy_dir_recursive(Path::new(&redirect.source_path), &cache_root)?;
+        let copied = copy_dir_reporting(
+            app,
+            "materialize",
+            instance_root,
+            Path::new(&redirect.source_path),
+            &cache_root,
+        );
+        if let Err(err) = copied {
+            // Una copia a medias se reutilizaría en el siguiente lanzamiento.
+            let _ = fs::remove_dir_all(&cache_root);
+            return Err(err);
+        }
         let redirect_raw = serde_json::to_string_pretty(&redirect)
             .map_err(|err| format!("No se pudo serializar metadata redirect runtime: {err}"))?;
         fs::write(cache_root.join(".redirect.json"), redirect_raw)
@@ -454,6 +1074,26 @@ fn prepare_runtime_instance_root(app: &AppHandle, instance_root: &str) -> Result
         state: "REDIRECT_RUNTIME_CACHE".to_string(),
         last_used: metadata.last_used,
         internal_uuid: metadata.internal_uuid,
+        filesystem: None,
+        mods_dir_override: metadata.mods_dir_override,
+        adopted: false,
+        tags: metadata.tags,
+        locked_fields: metadata.locked_fields,
+        locked_by: metadata.locked_by,
+        optional_game_flags: metadata.optional_game_flags,
+        java_build_pin: metadata.java_build_pin,
+        source_settings_write: metadata.source_settings_write,
+        retention: metadata.retention,
+        library_overrides: metadata.library_overrides,
+        pack_origin: metadata.pack_origin,
+        auto_jvm_tuning: metadata.auto_jvm_tuning,
+        preferred_gpu: metadata.preferred_gpu,
+        backup: metadata.backup,
+        strict_mode: metadata.strict_mode,
+        skip_duplicate_mod_check: metadata.skip_duplicate_mod_check,
+        window_tweaks: metadata.window_tweaks,
+        launch_hooks: metadata.launch_hooks,
+        console_filters: metadata.console_filters,
     };
     let runtime_metadata_path = cache_root.join(".instance.json");
     let runtime_metadata_raw = serde_json::to_string_pretty(&runtime_metadata)
@@ -461,7 +1101,9 @@ fn prepare_runtime_instance_root(app: &AppHandle, instance_root: &str) -> Result
     fs::write(&runtime_metadata_path, runtime_metadata_raw)
         .map_err(|err| format!("No se pudo guardar metadata runtime de atajo: {err}"))?;
 
-    let _ = app.emit(
+    emit_journaled(
+        app,
+        instance_root,
         "instance_runtime_output",
         RuntimeOutputEvent {
             instance_root: instance_root.to_string(),
@@ -477,6 +1119,7 @@ fn prepare_runtime_instance_root(app: &AppHandle, instance_root: &str) -> Result
                 cache_root.display()
             ),
             parsed: None,
+            repeat_count: None,
         },
     );
 
@@ -509,6 +1152,11 @@ fn launcher_roots_for_source(source_launcher: &str) -> Vec<PathBuf> {
             roots.push(PathBuf::from(&home).join(".minecraft"));
         }
     }
+    for (external, root) in known_external_launcher_roots() {
+        if launcher.contains(&external.display_name().to_ascii_lowercase()) {
+            roots.push(external.shared_runtime_dir(&root));
+        }
+    }
 
     if launcher.contains("prism") {
         roots.sort();
@@ -655,7 +1303,7 @@ fn resolve_forge_module_path_value(
     module_value: &str,
     library_roots: &[PathBuf],
 ) -> Result<String, String> {
-    let separator = if module_value.contains(';') { ';' } else { ':' };
+    let separator = java_path_list_separator(module_value);
     let mut resolved = Vec::new();
     let mut missing = Vec::new();
 
@@ -753,8 +1401,27 @@ struct ForgeArgsResolution {
 }
 
 #[tauri::command]
-pub fn get_instance_metadata(instance_root: String) -> Result<InstanceMetadata, String> {
-    let metadata_path = Path::new(&instance_root).join(".instance.json");
+pub fn get_instance_metadata(
+    app: AppHandle,
+    instance_root: String,
+) -> Result<InstanceMetadata, String> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    read_instance_metadata(instance_root.to_string())
+}
+
+/// Lee `.instance.json` completando en memoria la metadata antigua; solo la migración de
+/// arranque la guarda.
+pub fn read_instance_metadata(instance_root: String) -> Result<InstanceMetadata, String> {
+    let mut metadata = read_stored_instance_metadata(&instance_root)?;
+    upgrade_instance_metadata(Path::new(&instance_root), &mut metadata);
+    Ok(metadata)
+}
+
+/// `.instance.json` tal como está en disco, sin completar.
+pub(crate) fn read_stored_instance_metadata(
+    instance_root: &str,
+) -> Result<InstanceMetadata, String> {
+    let metadata_path = Path::new(instance_root).join(".instance.json");
     let raw = fs::read_to_string(&metadata_path).map_err(|err| {
         format!(
             "No se pudo leer la metadata de la instancia en {}: {}",
@@ -772,139 +1439,1332 @@ pub fn get_instance_metadata(instance_root: String) -> Result<InstanceMetadata,
     })
 }
 
-fn write_instance_metadata(instance_root: &str, metadata: &InstanceMetadata) -> Result<(), String> {
-    let metadata_path = Path::new(instance_root).join(".instance.json");
-    let raw = serde_json::to_string_pretty(metadata)
-        .map_err(|err| format!("No se pudo serializar metadata de instancia: {err}"))?;
-    fs::write(&metadata_path, raw).map_err(|err| {
-        format!(
-            "No se pudo guardar metadata de la instancia en {}: {err}",
-            metadata_path.display()
-        )
-    })
-}
-
-fn touch_instance_last_used(instance_root: &str) -> Result<(), String> {
-    let mut metadata = get_instance_metadata(instance_root.to_string())?;
-    metadata.last_used = Some(chrono::Utc::now().to_rfc3339());
-    write_instance_metadata(instance_root, &metadata)
+/// Guarda con el escritor de metadata lo que las instancias antiguas solo tienen completado
+/// en memoria, y les asigna el `internal_uuid` que les falte.
+pub fn migrate_instance_metadata_on_startup(app: &AppHandle) {
+    let Ok(instances_root) = resolve_instances_root(app) else {
+        return;
+    };
+    let Ok(entries) = fs::read_dir(&instances_root) else {
+        return;
+    };
+    let ids = app_clock(app).ids;
+    for root in entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.join(".instance.json").is_file())
+    {
+        let root = root.display().to_string();
+        match metadata_writer(&root).persist_upgrade_blocking(&root, ids.as_ref()) {
+            Ok(upgraded) if !upgraded.is_empty() => log::info!(
+                "✔ Metadata de {root} actualizada al formato actual: {}.",
+                upgraded.join(", ")
+            ),
+            Ok(_) => {}
+            Err(err) => log::warn!("⚠ No se pudo actualizar la metadata de {root}: {err}"),
+        }
+    }
 }
 
-fn folder_size_bytes(root: &Path) -> u64 {
-    if !root.exists() {
-        return 0;
+/// Completa los valores que las versiones antiguas no escribían y devuelve los campos
+/// completados. El `internal_uuid` lo asigna la migración de arranque.
+pub(crate) fn upgrade_instance_metadata(
+    instance_root: &Path,
+    metadata: &mut InstanceMetadata,
+) -> Vec<&'static str> {
+    let mut upgraded = Vec::new();
+    if metadata.state.trim().is_empty() {
+        metadata.state = default_state();
+        upgraded.push("state");
+    }
+    if metadata.created_at.trim().is_empty() {
+        if let Ok(modified) =
+            fs::metadata(instance_root.join(".instance.json")).and_then(|meta| meta.modified())
+        {
+            metadata.created_at = chrono::DateTime::<chrono::Utc>::from(modified).to_rfc3339();
+            upgraded.push("createdAt");
+        }
     }
-    let mut total = 0u64;
-    let Ok(entries) = fs::read_dir(root) else {
-        return 0;
-    };
-    for entry in entries.flatten() {
-        let path = entry.path();
-        if path.is_dir() {
-            total = total.saturating_add(folder_size_bytes(&path));
-        } else if let Ok(meta) = path.metadata() {
-            total = total.saturating_add(meta.len());
+    // Los atajos apuntan a un game dir ajeno: su versión la resuelve el lanzamiento.
+    let redirect = metadata.state.eq_ignore_ascii_case("REDIRECT");
+    if metadata.version_id.trim().is_empty() && !redirect {
+        let mc_root = instance_root.join("minecraft");
+        if let Ok(version_id) = resolve_effective_version_id(&mc_root, metadata) {
+            let version_json = mc_root
+                .join("versions")
+                .join(&version_id)
+                .join(format!("{version_id}.json"));
+            if version_json.is_file() {
+                metadata.version_id = version_id;
+                upgraded.push("versionId");
+            }
         }
     }
-    total
+    if metadata.required_java_major == 0 {
+        if let Ok(runtime) = determine_required_java(&metadata.minecraft_version, &metadata.loader)
+        {
+            metadata.required_java_major = u32::from(runtime.major());
+            upgraded.push("requiredJavaMajor");
+            if metadata.java_runtime.trim().is_empty() {
+                metadata.java_runtime = runtime.as_dir_name().to_string();
+                upgraded.push("javaRuntime");
+            }
+        }
+    }
+    upgraded
 }
 
-fn count_mod_files(root: &Path) -> u32 {
-    let mods_paths = [
-        root.join("minecraft").join("mods"),
-        root.join(".minecraft").join("mods"),
-        root.join("mods"),
-    ];
-    let Some(mods_dir) = mods_paths.iter().find(|path| path.is_dir()) else {
-        return 0;
+/// Guarda la metadata con el cerrojo de escritura de la instancia. Para leer, modificar y
+/// guardar usa [`update_instance_metadata`], que mantiene el cerrojo durante todo el cambio.
+pub(crate) fn write_instance_metadata(
+    instance_root: &str,
+    metadata: &InstanceMetadata,
+) -> Result<(), String> {
+    let writer = metadata_writer(instance_root);
+    let _guard = writer.lock();
+    write_instance_metadata_unlocked(instance_root, metadata)
+}
+
+/// Relee la metadata, aplica `update` y la guarda sin soltar el cerrojo de la instancia, para
+/// no pisar lo que el escritor del lanzamiento u otro comando guarde a la vez. Devuelve la
+/// metadata guardada.
+pub(crate) fn update_instance_metadata<E: From<String>>(
+    instance_root: &str,
+    update: impl FnOnce(&mut InstanceMetadata) -> Result<(), E>,
+) -> Result<InstanceMetadata, E> {
+    let writer = metadata_writer(instance_root);
+    let _guard = writer.lock();
+    let mut metadata = read_instance_metadata(instance_root.to_string())?;
+    update(&mut metadata)?;
+    write_instance_metadata_unlocked(instance_root, &metadata)?;
+    Ok(metadata)
+}
+
+/// Igual que [`write_instance_metadata`] con el cerrojo ya tomado.
+pub(crate) fn write_instance_metadata_unlocked(
+    instance_root: &str,
+    metadata: &InstanceMetadata,
+) -> Result<(), String> {
+    let metadata_path = Path::new(instance_root).join(".instance.json");
+    let raw = serde_json::to_string_pretty(metadata)
+        .map_err(|err| format!("No se pudo serializar metadata de instancia: {err}"))?;
+    let supports_atomic_rename = metadata
+        .filesystem
+        .as_ref()
+        .map(|capabilities| capabilities.supports_atomic_rename)
+        .unwrap_or(true);
+    write_file_replacing(&metadata_path, raw.as_bytes(), supports_atomic_rename)
+        .map_err(|err| format!("No se pudo guardar metadata de la instancia: {err}"))?;
+    index_instance_metadata(instance_root, metadata);
+    Ok(())
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JavaArgsUpdateResult {
+    pub java_args: Vec<String>,
+    pub changes: Vec<String>,
+}
+
+/// Normaliza y guarda los argumentos de Java de la instancia; devuelve los cambios
+/// aplicados para que la interfaz pueda mostrarlos.
+#[tauri::command]
+pub fn update_instance_java_args(
+    app: AppHandle,
+    instance_root: String,
+    java_args: Vec<String>,
+    override_lock: Option<bool>,
+) -> Result<JavaArgsUpdateResult, InstanceEditError> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    let normalized = normalize_java_args(&java_args)?;
+    update_instance_metadata(instance_root.as_str(), |metadata| {
+        check_metadata_lock(
+            instance_root.as_str(),
+            metadata,
+            "java_args",
+            "update_instance_java_args",
+            override_lock.unwrap_or(false),
+        )?;
+        metadata.java_args = normalized.args.clone();
+        Ok::<(), InstanceEditError>(())
+    })?;
+    for change in &normalized.changes {
+        log::info!("🔹 java_args de {instance_root}: {change}");
+    }
+    Ok(JavaArgsUpdateResult {
+        java_args: normalized.args,
+        changes: normalized.changes,
+    })
+}
+
+/// Activa o desactiva los flags de JVM automáticos. Los bloquea el mismo candado que
+/// `java_args`.
+#[tauri::command]
+pub fn set_instance_auto_jvm_tuning(
+    app: AppHandle,
+    instance_root: String,
+    enabled: bool,
+    override_lock: Option<bool>,
+) -> Result<InstanceMetadata, InstanceEditError> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    let metadata = update_instance_metadata(instance_root.as_str(), |metadata| {
+        check_metadata_lock(
+            instance_root.as_str(),
+            metadata,
+            "java_args",
+            "set_instance_auto_jvm_tuning",
+            override_lock.unwrap_or(false),
+        )?;
+        metadata.auto_jvm_tuning = enabled;
+        Ok::<(), InstanceEditError>(())
+    })?;
+    log::info!("🔹 Ajuste automático de JVM de {instance_root}: {enabled}");
+    Ok(metadata)
+}
+
+/// Guarda la GPU preferida (`integrated`, `dedicated` o el nombre de un adaptador); con
+/// `None` o vacío se quita y decide el sistema.
+#[tauri::command]
+pub fn set_instance_preferred_gpu(
+    app: AppHandle,
+    instance_root: String,
+    preferred_gpu: Option<String>,
+) -> Result<InstanceMetadata, String> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    let preference = preferred_gpu.as_deref().and_then(GpuPreference::parse);
+    if let Some(GpuPreference::Adapter(name)) = &preference {
+        let adapters = detect_gpu_adapters();
+        // Si la detección falló no se puede validar el nombre; se guarda tal cual.
+        if !adapters.is_empty()
+            && wants_dedicated(&GpuPreference::Adapter(name.clone()), &adapters).is_none()
+        {
+            return Err(format!(
+                "No se encontró la GPU '{name}'. Adaptadores detectados: {}.",
+                adapters
+                    .iter()
+                    .map(|gpu| gpu.adapter_name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+    }
+    let metadata = update_instance_metadata(instance_root.as_str(), |metadata| {
+        metadata.preferred_gpu = preference.map(|preference| preference.label());
+        Ok::<(), String>(())
+    })?;
+    log::info!(
+        "🔹 GPU preferida de {instance_root}: {}",
+        metadata
+            .preferred_gpu
+            .as_deref()
+            .unwrap_or("la del sistema")
+    );
+    Ok(metadata)
+}
+
+/// Fija la instancia a una build instalada de Java o, con `None`, vuelve a usar la más
+/// reciente del major requerido.
+#[tauri::command]
+pub fn set_instance_java_build_pin(
+    app: AppHandle,
+    instance_root: String,
+    build: Option<String>,
+) -> Result<InstanceMetadata, String> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    let metadata = update_instance_metadata(instance_root.as_str(), |metadata| {
+        let build = build
+            .map(|build| build.trim().to_string())
+            .filter(|build| !build.is_empty());
+        if let Some(build) = build.as_deref() {
+            let runtime = parse_runtime_from_metadata(metadata).ok_or_else(|| {
+                format!(
+                    "No se pudo determinar el runtime de Java de la instancia '{}'.",
+                    metadata.name
+                )
+            })?;
+            let launcher_root = resolve_launcher_root_for_instance(
+                instance_root.path(),
+                configured_launcher_root().as_deref(),
+                &mut Vec::new(),
+            )?;
+            if !list_java_builds(&launcher_root, runtime)?
+                .iter()
+                .any(|installed| installed.name == build)
+            {
+                return Err(format!(
+                    "La build '{build}' no está instalada para Java {}.",
+                    runtime.major()
+                ));
+            }
+        }
+        metadata.java_build_pin = build;
+        Ok::<(), String>(())
+    })?;
+    log::info!(
+        "🔹 Build de Java de {instance_root}: {}",
+        metadata
+            .java_build_pin
+            .as_deref()
+            .unwrap_or("la más reciente")
+    );
+    Ok(metadata)
+}
+
+/// Guarda los flags de privacidad de la instancia tras comprobar que su versión los
+/// reconoce.
+#[tauri::command]
+pub fn set_instance_optional_game_flags(
+    app: AppHandle,
+    instance_root: String,
+    flags: OptionalGameFlags,
+) -> Result<InstanceMetadata, String> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    let metadata = update_instance_metadata(instance_root.as_str(), |metadata| {
+        validate_optional_game_flags(&flags, &metadata.minecraft_version)?;
+        metadata.optional_game_flags = flags;
+        Ok::<(), String>(())
+    })?;
+    log::info!(
+        "🔹 Flags opcionales de {instance_root}: {:?}",
+        metadata.optional_game_flags
+    );
+    Ok(metadata)
+}
+
+/// Aplica los flags de privacidad: los argumentos oficiales van a `game_args` y la
+/// telemetría se fija en `options.txt`.
+fn apply_optional_game_flags(
+    metadata: &InstanceMetadata,
+    mc_root: &Path,
+    game_args: &mut Vec<String>,
+    logs: &mut Vec<String>,
+) -> Result<(), String> {
+    let flags = &metadata.optional_game_flags;
+    let args = optional_game_args(flags, &metadata.minecraft_version)?;
+    for arg in &args {
+        if !game_args.contains(arg) {
+            logs.push(format!("✔ flag opcional de juego: {arg}"));
+            game_args.push(arg.clone());
+        }
+    }
+    if flags.disable_telemetry {
+        if let Some((key, value)) = telemetry_option(&metadata.minecraft_version) {
+            let options_path = mc_root.join("options.txt");
+            let current = fs::read_to_string(&options_path).unwrap_or_default();
+            let updated = upsert_option_line(&current, key, value);
+            if updated != current {
+                fs::write(&options_path, updated).map_err(|err| {
+                    format!(
+                        "No se pudo escribir {} para desactivar la telemetría: {err}",
+                        options_path.display()
+                    )
+                })?;
+            }
+            logs.push(format!(
+                "✔ telemetría desactivada: options.txt {key}:{value}"
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Comprueba que la carpeta exista y que el loader sepa redirigir sus mods a ella.
+fn validate_mods_dir_override(
+    metadata: &InstanceMetadata,
+    mods_dir: &Path,
+) -> Result<ModsDirInjection, String> {
+    if !mods_dir.is_dir() {
+        return Err(format!(
+            "La carpeta de mods configurada no existe: {}",
+            mods_dir.display()
+        ));
+    }
+    mods_dir_injection(&metadata.loader, &metadata.minecraft_version, mods_dir)
+}
+
+/// La carpeta compartida debe ser absoluta y no puede estar dentro de otra instancia: sus
+/// mods cambiarían con las operaciones de esa instancia.
+fn check_mods_dir_override_location(
+    mods_dir: &Path,
+    instances_root: &Path,
+    instance_root: &Path,
+) -> Result<(), String> {
+    if !mods_dir.is_absolute() {
+        return Err(format!(
+            "La carpeta de mods compartida debe ser una ruta absoluta: {}",
+            mods_dir.display()
+        ));
+    }
+    let canonical = |path: &Path| fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let mods_dir = canonical(mods_dir);
+    let instances_root = canonical(instances_root);
+    let Ok(relative) = mods_dir.strip_prefix(&instances_root) else {
+        return Ok(());
+    };
+    let Some(owner) = relative.components().next() else {
+        return Err(
+            "La carpeta de mods compartida no puede ser la carpeta de instancias.".to_string(),
+        );
+    };
+    if instances_root.join(owner) != canonical(instance_root) {
+        return Err(format!(
+            "La carpeta de mods compartida está dentro de otra instancia: {}",
+            mods_dir.display()
+        ));
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn set_instance_mods_dir_override(
+    app: AppHandle,
+    instance_root: String,
+    mods_dir: Option<String>,
+    override_lock: Option<bool>,
+) -> Result<InstanceMetadata, InstanceEditError> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    let metadata = update_instance_metadata(instance_root.as_str(), |metadata| {
+        check_metadata_lock(
+            instance_root.as_str(),
+            metadata,
+            "mods_dir_override",
+            "set_instance_mods_dir_override",
+            override_lock.unwrap_or(false),
+        )?;
+        metadata.mods_dir_override = mods_dir
+            .map(|path| path.trim().to_string())
+            .filter(|path| !path.is_empty());
+        if let Some(path) = mods_dir_override_path(metadata) {
+            check_mods_dir_override_location(
+                &path,
+                &resolve_instances_root(&app)?,
+                instance_root.path(),
+            )?;
+            validate_mods_dir_override(metadata, &path)?;
+        }
+        Ok::<(), InstanceEditError>(())
+    })?;
+    log::info!(
+        "🔹 mods_dir_override de {instance_root}: {}",
+        metadata.mods_dir_override.as_deref().unwrap_or("(ninguno)")
+    );
+    Ok(metadata)
+}
+
+/// Guarda las exclusiones y cambios de versión de librerías de la instancia. Se rechazan
+/// las reglas que tocarían el jar del cliente o las librerías de arranque del loader.
+#[tauri::command]
+pub fn set_instance_library_overrides(
+    app: AppHandle,
+    instance_root: String,
+    overrides: Vec<LibraryOverride>,
+    override_lock: Option<bool>,
+) -> Result<InstanceMetadata, InstanceEditError> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    let metadata = update_instance_metadata(instance_root.as_str(), |metadata| {
+        check_metadata_lock(
+            instance_root.as_str(),
+            metadata,
+            "library_overrides",
+            "set_instance_library_overrides",
+            override_lock.unwrap_or(false),
+        )?;
+        for rule in &overrides {
+            validate_library_override(rule)?;
+        }
+        metadata.library_overrides = overrides
+            .into_iter()
+            .map(|mut rule| {
+                rule.coordinate = rule.coordinate.trim().to_string();
+                rule
+            })
+            .collect();
+        Ok::<(), InstanceEditError>(())
+    })?;
+    log::info!(
+        "🔹 library_overrides de {instance_root}: {}",
+        metadata
+            .library_overrides
+            .iter()
+            .map(LibraryOverride::describe)
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    Ok(metadata)
+}
+
+/// Marca la instancia como usada ahora y lo guarda junto con lo que hubiera pendiente;
+/// se llama una vez el proceso del juego ya arrancó.
+fn touch_instance_last_used(instance_root: &str, clock: &dyn Clock) {
+    let writer = metadata_writer(instance_root);
+    writer.queue_last_used(clock.now_rfc3339());
+    if let Err(err) = writer.flush_blocking(instance_root) {
+        log::warn!("⚠ No se pudo guardar last_used de {instance_root}: {err}");
+    }
+}
+
+fn folder_size_bytes(root: &Path) -> u64 {
+    folder_size_with_warnings(root, &mut Vec::new())
+}
+
+/// Igual que [`folder_size_bytes`] pero anotando las carpetas y archivos que no se
+/// pudieron leer en lugar de contarlos como vacíos.
+fn folder_size_with_warnings(root: &Path, warnings: &mut Vec<String>) -> u64 {
+    if !root.exists() {
+        return 0;
+    }
+    let mut total = 0u64;
+    let entries = match fs::read_dir(root) {
+        Ok(entries) => entries,
+        Err(err) => {
+            warnings.push(format!("No se pudo leer {}: {err}", root.display()));
+            return 0;
+        }
     };
+    for entry in entries {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(err) => {
+                warnings.push(format!(
+                    "No se pudo leer una entrada de {}: {err}",
+                    root.display()
+                ));
+                continue;
+            }
+        };
+        let path = entry.path();
+        if path.is_dir() {
+            total = total.saturating_add(folder_size_with_warnings(&path, warnings));
+        } else {
+            match path.metadata() {
+                Ok(meta) => total = total.saturating_add(meta.len()),
+                Err(err) => warnings.push(format!("No se pudo leer {}: {err}", path.display())),
+            }
+        }
+    }
+    total
+}
+
+/// `mods_dir_override` normalizado (vacío equivale a no tenerlo).
+fn mods_dir_override_path(metadata: &InstanceMetadata) -> Option<PathBuf> {
+    metadata
+        .mods_dir_override
+        .as_deref()
+        .map(str::trim)
+        .filter(|path| !path.is_empty())
+        .map(PathBuf::from)
+}
+
+/// Carpeta de mods real de la instancia: `mods_dir_override` si está definido y, si no,
+/// `minecraft/mods`. Los listados, estadísticas y el comprobador de compatibilidad usan esta.
+pub fn effective_mods_dir(instance_root: &Path) -> PathBuf {
+    shared_mods_dir(instance_root).unwrap_or_else(|| instance_local_mods_dir(instance_root))
+}
+
+/// `minecraft/mods` de la propia instancia, aunque tenga una carpeta compartida.
+pub(crate) fn instance_local_mods_dir(instance_root: &Path) -> PathBuf {
+    instance_root.join("minecraft").join("mods")
+}
+
+/// Carpeta de mods compartida (`mods_dir_override`) si la instancia la usa. Las operaciones
+/// destructivas no deben tocarla: otras instancias pueden depender de ella.
+pub(crate) fn shared_mods_dir(instance_root: &Path) -> Option<PathBuf> {
+    read_instance_metadata(instance_root.display().to_string())
+        .ok()
+        .and_then(|metadata| mods_dir_override_path(&metadata))
+}
+
+fn count_mod_files(
+    root: &Path,
+    mods_dir_override: Option<PathBuf>,
+    warnings: &mut Vec<String>,
+) -> u32 {
+    let mods_paths = [
+        root.join("minecraft").join("mods"),
+        root.join(".minecraft").join("mods"),
+        root.join("mods"),
+    ];
+    let Some(mods_dir) = mods_dir_override
+        .iter()
+        .chain(mods_paths.iter())
+        .find(|path| path.is_dir())
+    else {
+        return 0;
+    };
+
+    let entries = match fs::read_dir(mods_dir) {
+        Ok(entries) => entries,
+        Err(err) => {
+            warnings.push(format!(
+                "No se pudo leer la carpeta de mods {}: {err}",
+                mods_dir.display()
+            ));
+            return 0;
+        }
+    };
+
+    entries
+        .flatten()
+        .filter_map(|entry| entry.metadata().ok())
+        .filter(|meta| meta.is_file())
+        .count() as u32
+}
+
+/// Origen en una unidad extraíble o de red: letra de unidad ausente en Windows o punto de
+/// montaje típico de USB/NAS en Unix.
+fn looks_like_disconnected_drive(path: &Path) -> bool {
+    if let Some(std::path::Component::Prefix(prefix)) = path.components().next() {
+        let mut drive_root = prefix.as_os_str().to_os_string();
+        drive_root.push("\\");
+        return !Path::new(&drive_root).exists();
+    }
+    let text = path.to_string_lossy();
+    ["/media/", "/mnt/", "/run/media/", "/Volumes/"]
+        .iter()
+        .any(|mount| text.starts_with(mount))
+}
+
+fn unreadable_source_error(source: &Path) -> InstanceUnreadableError {
+    if looks_like_disconnected_drive(source) {
+        InstanceUnreadableError {
+            code: "SOURCE_DISCONNECTED",
+            path: source.display().to_string(),
+            message: format!(
+                "El origen de la instancia está en una unidad desconectada: {}. Conecta la unidad y vuelve a intentarlo.",
+                source.display()
+            ),
+        }
+    } else {
+        InstanceUnreadableError {
+            code: "SOURCE_MISSING",
+            path: source.display().to_string(),
+            message: format!(
+                "El origen de la instancia ya no existe: {}",
+                source.display()
+            ),
+        }
+    }
+}
+
+/// Tamaño y número de mods de `effective_root`. Falla solo si la carpeta raíz no se puede
+/// leer; los errores por debajo se devuelven como avisos.
+fn compute_card_stats(
+    effective_root: &Path,
+    mods_dir_override: Option<PathBuf>,
+    last_used: Option<String>,
+) -> Result<InstanceCardStats, CardStatsError> {
+    if let Err(err) = fs::read_dir(effective_root) {
+        return Err(CardStatsError::Unreadable(InstanceUnreadableError {
+            code: "INSTANCE_UNREADABLE",
+            path: effective_root.display().to_string(),
+            message: format!(
+                "No se pudo leer la carpeta de la instancia {}: {err}",
+                effective_root.display()
+            ),
+        }));
+    }
+    let mut warnings = Vec::new();
+    let size_mb = (folder_size_with_warnings(effective_root, &mut warnings) / (1024 * 1024)).max(1);
+    let mods_count = count_mod_files(effective_root, mods_dir_override, &mut warnings);
+    Ok(InstanceCardStats {
+        size_mb,
+        mods_count,
+        last_used,
+        partial: !warnings.is_empty(),
+        warnings,
+    })
+}
+
+pub(crate) fn is_instance_running(instance_root: &str) -> bool {
+    runtime_registry()
+        .lock()
+        .map(|registry| {
+            registry
+                .get(instance_root)
+                .map(|state| state.running)
+                .unwrap_or(false)
+        })
+        .unwrap_or(false)
+}
+
+fn read_version_inherits_from(mc_root: &Path, version_id: &str) -> Option<String> {
+    load_single_version_json(mc_root, version_id)
+        .ok()?
+        .get("inheritsFrom")
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(ToOwned::to_owned)
+}
+
+/// Devuelve la versión efectiva seguida de toda su cadena `inheritsFrom`.
+fn effective_version_chain(mc_root: &Path, effective_id: &str) -> Vec<String> {
+    let mut chain = vec![effective_id.to_string()];
+    let mut current = effective_id.to_string();
+    while let Some(parent) = read_version_inherits_from(mc_root, &current) {
+        if chain.contains(&parent) {
+            break;
+        }
+        chain.push(parent.clone());
+        current = parent;
+    }
+    chain
+}
+
+#[tauri::command]
+pub fn list_instance_versions(
+    app: AppHandle,
+    instance_root: String,
+) -> Result<Vec<InstanceVersionEntry>, String> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    let metadata = read_instance_metadata(instance_root.to_string())?;
+    let mc_root = instance_root.path().join("minecraft");
+    if !mc_root.join("versions").is_dir() {
+        return Ok(Vec::new());
+    }
+    let effective_id = resolve_effective_version_id(&mc_root, &metadata)?;
+    list_versions(&mc_root, &effective_id)
+}
+
+fn list_versions(mc_root: &Path, effective_id: &str) -> Result<Vec<InstanceVersionEntry>, String> {
+    let versions_dir = mc_root.join("versions");
+    let mut versions = Vec::new();
+    for entry in fs::read_dir(&versions_dir)
+        .map_err(|err| format!("No se pudo leer versions {}: {err}", versions_dir.display()))?
+        .flatten()
+    {
+        let path = entry.path();
+        if !path.is_dir() {
+            continue;
+        }
+        let id = entry.file_name().to_string_lossy().to_string();
+        let parsed = load_single_version_json(mc_root, &id).ok();
+        versions.push(InstanceVersionEntry {
+            effective: id == effective_id,
+            json_valid: parsed.is_some(),
+            inherits_from: parsed
+                .as_ref()
+                .and_then(|json| json.get("inheritsFrom"))
+                .and_then(Value::as_str)
+                .map(ToOwned::to_owned),
+            size_bytes: folder_size_bytes(&path),
+            id,
+        });
+    }
+
+    versions.sort_by_key(|entry| entry.id.to_lowercase());
+    Ok(versions)
+}
+
+#[tauri::command]
+pub fn prune_instance_versions(
+    app: AppHandle,
+    instance_root: String,
+    keep: Vec<String>,
+) -> Result<PruneInstanceVersionsResult, String> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    let metadata = read_instance_metadata(instance_root.to_string())?;
+    prune_versions(instance_root.as_str(), &metadata, &keep)
+}
+
+fn prune_versions(
+    instance_root: &str,
+    metadata: &InstanceMetadata,
+    keep: &[String],
+) -> Result<PruneInstanceVersionsResult, String> {
+    if is_instance_running(instance_root) {
+        return Err(
+            "No se pueden eliminar versiones mientras la instancia está en ejecución.".to_string(),
+        );
+    }
+    if metadata.state.eq_ignore_ascii_case("redirect") {
+        return Err(
+            "Las versiones de un atajo pertenecen al launcher de origen y no se pueden podar desde aquí."
+                .to_string(),
+        );
+    }
+
+    let mc_root = Path::new(instance_root).join("minecraft");
+    let versions_dir = mc_root.join("versions");
+    if !versions_dir.is_dir() {
+        return Ok(PruneInstanceVersionsResult {
+            removed: Vec::new(),
+            freed_bytes: 0,
+        });
+    }
+
+    let effective_id = resolve_effective_version_id(&mc_root, metadata)?;
+    let missing_from_keep = effective_version_chain(&mc_root, &effective_id)
+        .into_iter()
+        .filter(|id| !keep.contains(id))
+        .collect::<Vec<_>>();
+    if !missing_from_keep.is_empty() {
+        return Err(format!(
+            "La versión efectiva '{effective_id}' y su cadena inheritsFrom deben conservarse. Faltan en la lista: {}",
+            missing_from_keep.join(", ")
+        ));
+    }
+
+    let mut removed = Vec::new();
+    let mut freed_bytes = 0u64;
+    for entry in fs::read_dir(&versions_dir)
+        .map_err(|err| format!("No se pudo leer versions {}: {err}", versions_dir.display()))?
+        .flatten()
+    {
+        let path = entry.path();
+        let id = entry.file_name().to_string_lossy().to_string();
+        if !path.is_dir() || keep.contains(&id) {
+            continue;
+        }
+        let size = folder_size_bytes(&path);
+        fs::remove_dir_all(&path)
+            .map_err(|err| format!("No se pudo eliminar versión {}: {err}", path.display()))?;
+        freed_bytes = freed_bytes.saturating_add(size);
+        removed.push(id);
+    }
+
+    removed.sort();
+    Ok(PruneInstanceVersionsResult {
+        removed,
+        freed_bytes,
+    })
+}
+
+#[tauri::command]
+pub fn get_instance_card_stats(
+    app: AppHandle,
+    instance_root: String,
+) -> Result<InstanceCardStats, CardStatsError> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    let root_path = instance_root.path().to_path_buf();
+    let metadata = read_instance_metadata(instance_root.to_string())?;
+
+    let effective_root = if metadata.state.eq_ignore_ascii_case("redirect") {
+        let redirect_path = root_path.join(".redirect.json");
+        let raw = fs::read_to_string(&redirect_path).map_err(|err| {
+            format!(
+                "No se pudo leer redirección en {}: {err}",
+                redirect_path.display()
+            )
+        })?;
+        let redirect: ShortcutRedirect = serde_json::from_str(&raw).map_err(|err| {
+            format!(
+                "No se pudo parsear redirección en {}: {err}",
+                redirect_path.display()
+            )
+        })?;
+        let source = PathBuf::from(redirect.source_path);
+        if !source.exists() {
+            return Err(CardStatsError::Unreadable(unreadable_source_error(&source)));
+        }
+        source
+    } else {
+        root_path
+    };
+
+    compute_card_stats(
+        &effective_root,
+        mods_dir_override_path(&metadata),
+        metadata.last_used.clone(),
+    )
+}
+
+fn health_finding(code: &str, severity: &str, message: String) -> InstanceHealthFinding {
+    InstanceHealthFinding {
+        code: code.to_string(),
+        severity: severity.to_string(),
+        message,
+    }
+}
+
+fn newest_crash_report_time(minecraft_root: &Path) -> Option<std::time::SystemTime> {
+    fs::read_dir(minecraft_root.join("crash-reports"))
+        .ok()?
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().is_file())
+        .filter_map(|entry| entry.metadata().ok()?.modified().ok())
+        .max()
+}
+
+fn read_verification_marker(instance_path: &Path) -> Option<VerificationMarker> {
+    let raw = fs::read_to_string(instance_path.join(VERIFICATION_MARKER_FILE)).ok()?;
+    serde_json::from_str(&raw).ok()
+}
+
+/// Guarda la fecha de la última validación completa de lanzamiento; `get_instance_health`
+/// la usa para avisar cuando la instancia lleva mucho tiempo sin verificarse.
+fn write_verification_marker(instance_path: &Path, version_id: &str, clock: &dyn Clock) {
+    let marker = VerificationMarker {
+        verified_at: clock.now_rfc3339(),
+        version_id: version_id.to_string(),
+    };
+    if let Ok(raw) = serde_json::to_string_pretty(&marker) {
+        let _ = fs::write(instance_path.join(VERIFICATION_MARKER_FILE), raw);
+    }
+}
+
+fn overall_health_status(findings: &[InstanceHealthFinding]) -> &'static str {
+    if findings.iter().any(|finding| finding.severity == "error") {
+        "error"
+    } else if findings.is_empty() {
+        "ok"
+    } else {
+        "warning"
+    }
+}
+
+/// Calcula la salud de la instancia solo con señales baratas: sin red ni hashing.
+pub fn compute_instance_health(instance_root: &str, clock: &dyn Clock) -> InstanceHealth {
+    let instance_path = Path::new(instance_root);
+    let minecraft_root = instance_path.join("minecraft");
+    let mut findings = Vec::new();
+
+    let runtime = runtime_registry()
+        .lock()
+        .ok()
+        .and_then(|registry| registry.get(instance_root).cloned());
+    if let Some(state) = runtime.as_ref().filter(|state| !state.running) {
+        if let Some(code) = state.exit_code.filter(|code| *code != 0) {
+            findings.push(health_finding(
+                "last_exit_failed",
+                "warning",
+                format!("La última ejecución terminó con código {code}."),
+            ));
+        }
+    }
+
+    let metadata = match read_instance_metadata(instance_root.to_string()) {
+        Ok(metadata) => metadata,
+        Err(err) => {
+            findings.push(health_finding("metadata_invalid", "error", err));
+            return InstanceHealth {
+                status: overall_health_status(&findings).to_string(),
+                findings,
+            };
+        }
+    };
+
+    let last_used = metadata
+        .last_used
+        .as_deref()
+        .and_then(|raw| chrono::DateTime::parse_from_rfc3339(raw).ok())
+        .map(|value| value.with_timezone(&chrono::Utc));
+    if let Some(crashed_at) = newest_crash_report_time(&minecraft_root) {
+        let crashed_at = chrono::DateTime::<chrono::Utc>::from(crashed_at);
+        if last_used.map(|used| crashed_at >= used).unwrap_or(true) {
+            findings.push(health_finding(
+                "recent_crash",
+                "warning",
+                "Existe un crash report posterior al último lanzamiento.".to_string(),
+            ));
+        }
+    }
+
+    if metadata.state.eq_ignore_ascii_case("redirect") {
+        let source = fs::read_to_string(instance_path.join(".redirect.json"))
+            .ok()
+            .and_then(|raw| serde_json::from_str::<ShortcutRedirect>(&raw).ok());
+        match source {
+            Some(redirect) if Path::new(&redirect.source_path).exists() => {}
+            Some(redirect) => findings.push(health_finding(
+                "redirect_source_missing",
+                "error",
+                format!(
+                    "La instancia de origen ya no existe: {}",
+                    redirect.source_path
+                ),
+            )),
+            None => findings.push(health_finding(
+                "redirect_invalid",
+                "error",
+                "No se pudo leer .redirect.json del acceso directo.".to_string(),
+            )),
+        }
+    } else {
+        let version_id = if metadata.version_id.trim().is_empty() {
+            metadata.minecraft_version.clone()
+        } else {
+            metadata.version_id.clone()
+        };
+        let version_json = minecraft_root
+            .join("versions")
+            .join(&version_id)
+            .join(format!("{version_id}.json"));
+        if !version_json.is_file() {
+            findings.push(health_finding(
+                "version_missing",
+                "error",
+                format!("Falta la versión efectiva {version_id} en versions/."),
+            ));
+        }
+
+        let mut root_logs = Vec::new();
+        let java_present = Path::new(&metadata.java_path).is_file()
+            || parse_runtime_from_metadata(&metadata)
+                .zip(
+                    resolve_launcher_root_for_instance(
+                        instance_path,
+                        configured_launcher_root().as_deref(),
+                        &mut root_logs,
+                    )
+                    .ok(),
+                )
+                .map(|(runtime, launcher_root)| {
+                    list_java_builds(&launcher_root, runtime)
+                        .map(|builds| match metadata.java_build_pin.as_deref() {
+                            Some(pin) => builds.iter().any(|build| build.name == pin),
+                            None => !builds.is_empty(),
+                        })
+                        .unwrap_or(false)
+                })
+                .unwrap_or(false);
+        if !java_present {
+            findings.push(health_finding(
+                "java_missing",
+                "warning",
+                format!(
+                    "No hay runtime de Java {} instalado; se descargará al iniciar.",
+                    metadata.required_java_major
+                ),
+            ));
+        }
+    }
 
-    let Ok(entries) = fs::read_dir(mods_dir) else {
-        return 0;
-    };
+    if let Some(marker) = read_verification_marker(instance_path) {
+        let stale = chrono::DateTime::parse_from_rfc3339(&marker.verified_at)
+            .map(|verified| {
+                clock
+                    .now()
+                    .signed_duration_since(verified.with_timezone(&chrono::Utc))
+                    > chrono::Duration::days(VERIFICATION_STALE_AFTER_DAYS)
+            })
+            .unwrap_or(true);
+        if stale {
+            findings.push(health_finding(
+                "verification_stale",
+                "warning",
+                format!(
+                    "La última verificación completa tiene más de {VERIFICATION_STALE_AFTER_DAYS} días."
+                ),
+            ));
+        }
+    }
 
-    entries
-        .flatten()
-        .filter_map(|entry| entry.metadata().ok())
-        .filter(|meta| meta.is_file())
-        .count() as u32
+    InstanceHealth {
+        status: overall_health_status(&findings).to_string(),
+        findings,
+    }
 }
 
 #[tauri::command]
-pub fn get_instance_card_stats(instance_root: String) -> Result<InstanceCardStats, String> {
-    let root_path = PathBuf::from(instance_root.clone());
-    let metadata = get_instance_metadata(instance_root)?;
+pub fn get_instance_health(
+    app: AppHandle,
+    instance_root: String,
+) -> Result<InstanceHealth, String> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    Ok(compute_instance_health(
+        instance_root.as_str(),
+        app_clock(&app).clock.as_ref(),
+    ))
+}
 
-    let effective_root = if metadata.state.eq_ignore_ascii_case("redirect") {
-        let redirect_path = root_path.join(".redirect.json");
-        let raw = fs::read_to_string(&redirect_path).map_err(|err| {
-            format!(
-                "No se pudo leer redirección en {}: {err}",
-                redirect_path.display()
-            )
-        })?;
-        let redirect: ShortcutRedirect = serde_json::from_str(&raw).map_err(|err| {
-            format!(
-                "No se pudo parsear redirección en {}: {err}",
-                redirect_path.display()
-            )
-        })?;
-        PathBuf::from(redirect.source_path)
-    } else {
-        root_path
-    };
+/// Partes de solo lectura de `validate_and_prepare_launch` para `prewarm_instance`: no
+/// descarga, no autentica y no escribe la metadata.
+pub(crate) fn build_launch_prewarm(
+    root: &ValidatedInstanceRoot,
+    task: &TaskProbe,
+    trace_rules: bool,
+) -> Result<PrewarmedLaunch, String> {
+    let started = Instant::now();
+    let instance_root = root.as_str();
+    let instance_path = root.path();
+    if !instance_path.exists() {
+        return Err("La instancia no existe en disco.".to_string());
+    }
+    if needs_recovery(instance_path) {
+        return Err("La instancia tiene una operación interrumpida; no se prevalida.".to_string());
+    }
+    let metadata = read_instance_metadata(instance_root.to_string())?;
+    if metadata.state.eq_ignore_ascii_case("redirect") {
+        return Err("Las instancias REDIRECT no se prevalidan.".to_string());
+    }
+    let fingerprint = launch_inputs_fingerprint(instance_path, &metadata);
+    let launcher_root = resolve_launcher_root_for_instance(
+        instance_path,
+        configured_launcher_root().as_deref(),
+        &mut Vec::new(),
+    )?;
 
-    let size_mb = (folder_size_bytes(&effective_root) / (1024 * 1024)).max(1);
-    let mods_count = count_mod_files(&effective_root);
+    task.progress(0, Some(4), "pasos");
+    let java_path = PathBuf::from(&metadata.java_path);
+    let java_stamp = file_stamp(&java_path);
+    let java_version_text = java_stamp
+        .and_then(|_| Command::new(&java_path).arg("-version").output().ok())
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stderr).to_string());
+    task.check_cancelled()?;
 
-    Ok(InstanceCardStats {
-        size_mb,
-        mods_count,
-        last_used: metadata.last_used,
+    task.progress(1, Some(4), "pasos");
+    let mc_root = instance_path.join("minecraft");
+    let version_id = resolve_effective_version_id(&mc_root, &metadata)?;
+    let version_json = load_merged_version_json(&mc_root, &version_id)?;
+    task.check_cancelled()?;
+
+    task.progress(2, Some(4), "pasos");
+    let rule_context = RuleContext::current();
+    let resolved_libraries = resolve_libraries(
+        &launcher_root.join("libraries"),
+        &version_json,
+        &rule_context,
+        &metadata.library_overrides,
+    );
+    let rule_trace = trace_rules.then(|| trace_version_rules(&version_json, &rule_context));
+    let missing_libraries = resolved_libraries.missing_classpath_entries.len()
+        + resolved_libraries.missing_native_entries.len();
+    let executable_version_id = version_json
+        .get("id")
+        .and_then(Value::as_str)
+        .unwrap_or(&version_id)
+        .to_string();
+    let client_jar = [
+        mc_root
+            .join("versions")
+            .join(&executable_version_id)
+            .join(format!("{executable_version_id}.jar")),
+        mc_root
+            .join("versions")
+            .join(&metadata.minecraft_version)
+            .join(format!("{}.jar", metadata.minecraft_version)),
+    ]
+    .into_iter()
+    .find(|jar| jar.exists());
+    let main_class = version_json
+        .get("mainClass")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .trim()
+        .to_string();
+
+    task.progress(3, Some(4), "pasos");
+    let jars = match client_jar {
+        Some(client_jar) if missing_libraries == 0 && !main_class.is_empty() => {
+            let (jars_to_validate, main_class_search) =
+                launch_jar_sets(&metadata.loader, &resolved_libraries, &client_jar);
+            let inspection =
+                inspect_launch_jars(&jars_to_validate, &main_class_search, &main_class);
+            let stamps = jars_to_validate
+                .iter()
+                .chain(main_class_search.iter())
+                .map(|jar| (jar.clone(), file_stamp(jar)))
+                .collect();
+            Some(PrewarmedJars {
+                main_class,
+                stamps,
+                inspection,
+            })
+        }
+        _ => None,
+    };
+    let asset_index_present = version_json
+        .get("assetIndex")
+        .and_then(|index| index.get("id"))
+        .and_then(Value::as_str)
+        .is_some_and(|id| {
+            launcher_root
+                .join("assets")
+                .join("indexes")
+                .join(format!("{id}.json"))
+                .is_file()
+        });
+    task.check_cancelled()?;
+    if launch_inputs_fingerprint(instance_path, &metadata) != fingerprint {
+        return Err("La instancia cambió durante la prevalidación.".to_string());
+    }
+    task.progress(4, Some(4), "pasos");
+
+    let summary = LaunchPrewarmSummary {
+        instance_root: instance_root.to_string(),
+        ready: java_version_text.is_some() && jars.is_some() && asset_index_present,
+        java_checked: java_version_text.is_some(),
+        libraries: resolved_libraries.classpath_entries.len(),
+        missing_libraries,
+        native_jars: resolved_libraries.native_jars.len(),
+        asset_index_present,
+        elapsed_ms: started.elapsed().as_millis() as u64,
+        rule_trace,
+    };
+    Ok(PrewarmedLaunch {
+        fingerprint,
+        java_path,
+        java_stamp,
+        java_version_text,
+        version_id,
+        version_json,
+        jars,
+        summary,
+        created_at: Instant::now(),
     })
 }
 
 #[tauri::command]
 pub fn validate_and_prepare_launch(
+    app: AppHandle,
     instance_root: String,
     auth_session: LaunchAuthSession,
-) -> Result<LaunchValidationResult, String> {
-    let instance_path = Path::new(&instance_root);
+    persist_refreshed_session: Option<bool>,
+) -> Result<LaunchValidationResult, LaunchError> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    prepare_strict_launch(app, &instance_root, auth_session, persist_refreshed_session)
+}
+
+fn prepare_strict_launch(
+    app: AppHandle,
+    instance_root: &ValidatedInstanceRoot,
+    auth_session: LaunchAuthSession,
+    persist_refreshed_session: Option<bool>,
+) -> Result<LaunchValidationResult, LaunchError> {
+    let prepared = prepare_launch(app, instance_root, auth_session, persist_refreshed_session)?;
+    enforce_strict_mode(prepared.strict_mode, &prepared.strict_violations)?;
+    Ok(prepared)
+}
+
+/// Prepara el lanzamiento sin aplicar el modo estricto; los avisos quedan en
+/// `strict_violations`.
+pub(crate) fn prepare_launch(
+    app: AppHandle,
+    instance_root: &ValidatedInstanceRoot,
+    auth_session: LaunchAuthSession,
+    persist_refreshed_session: Option<bool>,
+) -> Result<LaunchValidationResult, LaunchError> {
+    let prepared = validate_launch(app, instance_root, auth_session, persist_refreshed_session);
+    flush_metadata_blocking(instance_root.as_str(), "fin de la validación");
+    prepared
+}
+
+fn validate_launch(
+    app: AppHandle,
+    instance_root: &ValidatedInstanceRoot,
+    auth_session: LaunchAuthSession,
+    persist_refreshed_session: Option<bool>,
+) -> Result<LaunchValidationResult, LaunchError> {
+    let clock = app_clock(&app).clock;
+    let instance_path = instance_root.path();
     if !instance_path.exists() {
-        return Err("La instancia no existe en disco.".to_string());
+        return Err("La instancia no existe en disco.".to_string().into());
+    }
+    if needs_recovery(instance_path) {
+        return Err(
+            "La instancia tiene una operación interrumpida (p. ej. un corte de luz durante una descarga). Recupérala antes de iniciarla."
+                .to_string().into(),
+        );
     }
 
     let mut logs = vec!["🔹 1. Validaciones iniciales".to_string()];
+    let mut violations = StrictViolations::default();
+    reset_unknown_feature_log();
+    let watchdog = current_watchdog();
+    watchdog.enter_phase("metadata")?;
 
-    let mut metadata = get_instance_metadata(instance_root.clone())?;
+    let mut metadata = read_instance_metadata(instance_root.to_string())?;
     logs.push("✔ .instance.json leído correctamente".to_string());
+    let prewarmed = take_prewarmed_launch(&app, instance_root.as_str());
 
-    let launcher_root = resolve_launcher_root_from_instance_path(instance_path)?;
+    if metadata.filesystem.is_none() {
+        let capabilities = probe_filesystem_capabilities(instance_path);
+        metadata_writer(instance_root.as_str()).queue_filesystem(capabilities.clone());
+        metadata.filesystem = Some(capabilities);
+    }
+    if let Some(filesystem) = metadata.filesystem.as_ref() {
+        logs.extend(capability_warnings(filesystem));
+    }
+
+    let launcher_root = resolve_launcher_root_for_instance(
+        instance_path,
+        configured_launcher_root().as_deref(),
+        &mut logs,
+    )?;
     let launcher_libraries_root = launcher_root.join("libraries");
     logs.push(format!(
         "✔ libraries root del launcher: {}",
         launcher_libraries_root.display()
     ));
 
-    let verified_auth = validate_official_minecraft_auth(&auth_session, &mut logs)?;
+    watchdog.enter_phase("auth")?;
+    watchdog.set_sub_operation("verificando perfil de Minecraft");
+    // El mantenimiento en segundo plano puede tener ya un token más reciente que el del frontend.
+    let auth_session = freshest_session(&launcher_root, &auth_session);
+    let verified_auth = validate_official_minecraft_auth(&auth_session, clock.as_ref(), &mut logs)?;
+    let mut refreshed_auth_session = LaunchAuthSession {
+        profile_id: verified_auth.profile_id.clone(),
+        profile_name: verified_auth.profile_name.clone(),
+        minecraft_access_token: verified_auth.minecraft_access_token.clone(),
+        minecraft_access_token_expires_at: verified_auth.minecraft_access_token_expires_at,
+        microsoft_refresh_token: verified_auth.microsoft_refresh_token.clone(),
+        premium_verified: verified_auth.premium_verified,
+        persisted: false,
+    };
+    // Se guarda antes de devolver nada: si el frontend se cierra ahora, el token renovado
+    // (y el refresh token rotado) no se pierde.
+    refreshed_auth_session.persisted = persist_launch_session(
+        &launcher_root,
+        &refreshed_auth_session,
+        persist_refreshed_session.unwrap_or(false),
+    )
+    .unwrap_or_else(|err| {
+        logs.push(format!("⚠ No se pudo guardar la sesión renovada: {err}"));
+        false
+    });
+    if let Some(previous_name) = verified_auth.previous_profile_name.as_deref() {
+        record_profile_rename(
+            &launcher_root,
+            &verified_auth.profile_id,
+            &verified_auth.profile_name,
+        );
+        let _ = app.emit(
+            "account_profile_updated",
+            serde_json::json!({
+                "profileId": verified_auth.profile_id,
+                "profileName": verified_auth.profile_name,
+                "previousName": previous_name,
+            }),
+        );
+    }
 
-    let embedded_java = ensure_instance_embedded_java(instance_path, &metadata, &mut logs)?;
+    watchdog.enter_phase("java")?;
+    let embedded_java = ensure_instance_embedded_java(instance_root, &metadata, &mut logs)?;
     let java_path = PathBuf::from(&embedded_java);
 
-    let java_output = Command::new(&java_path)
-        .arg("-version")
-        .output()
-        .map_err(|err| format!("No se pudo validar versión de Java: {err}"))?;
-    let java_version_text = String::from_utf8_lossy(&java_output.stderr).to_string();
-    if !java_output.status.success() {
-        return Err(format!("java -version falló: {}", java_version_text.trim()));
-    }
-    logs.push(format!(
-        "✔ java -version detectado: {}",
-        first_line(&java_version_text)
-    ));
+    let java_version_text = match prewarmed
+        .as_ref()
+        .and_then(|plan| plan.java_version_for(&java_path))
+    {
+        Some(text) => {
+            logs.push(format!(
+                "✔ java -version (prevalidado): {}",
+                first_line(text)
+            ));
+            text.to_string()
+        }
+        None => {
+            let java_output = Command::new(&java_path)
+                .arg("-version")
+                .output()
+                .map_err(|err| format!("No se pudo validar versión de Java: {err}"))?;
+            watchdog.check()?;
+            let java_version_text = String::from_utf8_lossy(&java_output.stderr).to_string();
+            if !java_output.status.success() {
+                return Err(format!("java -version falló: {}", java_version_text.trim()).into());
+            }
+            logs.push(format!(
+                "✔ java -version detectado: {}",
+                first_line(&java_version_text)
+            ));
+            java_version_text
+        }
+    };
 
     let mc_root = instance_path.join("minecraft");
+    watchdog.enter_phase("loader")?;
     ensure_loader_ready_for_launch(
         instance_path,
         &mc_root,
@@ -917,7 +2777,24 @@ pub fn validate_and_prepare_launch(
     let loader_lower = metadata.loader.trim().to_ascii_lowercase();
     let is_forge = loader_lower == "forge";
     logs.push(format!("VERSION JSON efectivo: {selected_version_id}"));
-    let version_json = load_merged_version_json(&mc_root, &selected_version_id)?;
+    // El loader puede haber cambiado la metadata o los version.json: la huella lo detecta.
+    let had_prewarm = prewarmed.is_some();
+    let prewarmed = prewarmed.filter(|plan| {
+        plan.version_id == selected_version_id
+            && plan.fingerprint == launch_inputs_fingerprint(instance_path, &metadata)
+    });
+    let version_json = match prewarmed.as_ref() {
+        Some(plan) => {
+            logs.push("✔ usando la preparación prevalidada de la instancia".to_string());
+            plan.version_json.clone()
+        }
+        None => {
+            if had_prewarm {
+                logs.push("⚠ la prevalidación quedó obsoleta; se prepara de nuevo".to_string());
+            }
+            load_merged_version_json(&mc_root, &selected_version_id)?
+        }
+    };
     let forge_generation = if is_forge {
         let detected = detect_forge_generation(&mc_root, &selected_version_id, &version_json);
         logs.push(format!("Forge generación detectada: {:?}", detected));
@@ -925,7 +2802,10 @@ pub fn validate_and_prepare_launch(
     } else {
         ForgeGeneration::Legacy
     };
-    log_merged_json_summary(&version_json, &mut logs);
+    watchdog.check()?;
+    for warning in log_merged_json_summary(&version_json, &mut logs) {
+        violations.note(MERGED_JSON_SUMMARY, warning);
+    }
     validate_merged_has_auth_args(&version_json)?;
 
     let executable_version_id = version_json
@@ -958,7 +2838,8 @@ pub fn validate_and_prepare_launch(
             "No se encontró JAR ejecutable.\n\nBuscado loader jar: {}\n\nBuscado vanilla jar: {}",
             loader_jar.display(),
             vanilla_jar.display()
-        ));
+        )
+        .into());
     };
 
     logs.push(format!("✔ jar ejecutable: {}", client_jar.display()));
@@ -970,7 +2851,9 @@ pub fn validate_and_prepare_launch(
         .trim()
         .to_string();
     if resolved_main_class.is_empty() {
-        return Err("mainClass faltante en version.json efectivo.".to_string());
+        return Err("mainClass faltante en version.json efectivo."
+            .to_string()
+            .into());
     }
 
     let executable_version_json = mc_root
@@ -983,34 +2866,84 @@ pub fn validate_and_prepare_launch(
         executable_version_json.display()
     ));
 
+    watchdog.enter_phase("libraries")?;
     let rule_context = RuleContext::current();
-    let resolved_libraries =
-        resolve_libraries(&launcher_libraries_root, &version_json, &rule_context);
+    let resolved_libraries = resolve_libraries(
+        &launcher_libraries_root,
+        &version_json,
+        &rule_context,
+        &metadata.library_overrides,
+    );
+    for applied in &resolved_libraries.applied_overrides {
+        logs.push(format!("✔ override de librería: {applied}"));
+        violations.note(
+            LIBRARY_OVERRIDE_APPLIED,
+            format!("override de librería aplicado: {applied}"),
+        );
+    }
 
     if !resolved_libraries.missing_classpath_entries.is_empty() {
         logs.push(format!(
             "⚠ librerías faltantes detectadas ({}). Iniciando descarga automática...",
             resolved_libraries.missing_classpath_entries.len()
         ));
-        let downloaded = ensure_missing_libraries(&resolved_libraries.missing_classpath_entries)?;
+        let journal = OperationJournal::begin(
+            instance_path,
+            OP_DOWNLOAD_LIBRARIES,
+            &metadata.name,
+            resolved_libraries
+                .missing_classpath_entries
+                .iter()
+                .map(|entry| {
+                    JournalEntry::download(Path::new(&entry.path), &entry.url, &entry.sha1)
+                })
+                .collect(),
+        )?;
+        let downloaded =
+            ensure_missing_libraries(&resolved_libraries.missing_classpath_entries, &watchdog);
+        // Un error aquí lo gestiona el propio proceso; el diario solo debe sobrevivir a un
+        // cierre abrupto.
+        journal.complete();
+        let downloaded = downloaded?;
         logs.push(format!(
             "✔ librerías recuperadas automáticamente: {downloaded}/{}",
             resolved_libraries.missing_classpath_entries.len()
         ));
     }
 
+    let mut legacy_natives_dir = None;
     if !resolved_libraries.missing_native_entries.is_empty() {
-        return Err(format!(
-            "Faltan nativos requeridos para el OS actual ({}). Ejemplo: {}",
-            resolved_libraries.missing_native_entries.len(),
-            resolved_libraries
-                .missing_native_entries
-                .iter()
-                .take(3)
-                .cloned()
-                .collect::<Vec<_>>()
-                .join(" | ")
-        ));
+        let candidates = legacy_natives_candidates(
+            &mc_root,
+            &[executable_version_id.as_str(), selected_version_id.as_str()],
+        );
+        // Solo natives de clasificador: otras librerías incompletas no se cubren con la
+        // carpeta legacy.
+        let only_classifiers = resolved_libraries.missing_native_libraries.len()
+            == resolved_libraries.missing_native_entries.len();
+        legacy_natives_dir = only_classifiers
+            .then(|| {
+                find_legacy_natives_dir(&candidates, &resolved_libraries.missing_native_libraries)
+            })
+            .flatten();
+        if legacy_natives_dir.is_none() {
+            return Err(format!(
+                "Faltan nativos requeridos para el OS actual ({}). Ejemplo: {}\n\nTambién se buscaron natives preextraídos (formato antiguo) en: {}",
+                resolved_libraries.missing_native_entries.len(),
+                resolved_libraries
+                    .missing_native_entries
+                    .iter()
+                    .take(3)
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .join(" | "),
+                candidates
+                    .iter()
+                    .map(|dir| dir.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ).into());
+        }
     }
 
     logs.push(format!(
@@ -1018,36 +2951,103 @@ pub fn validate_and_prepare_launch(
         resolved_libraries.classpath_entries.len()
     ));
 
+    let lwjgl_version = lwjgl_version_from_version_json(&version_json);
+    let gpu = detect_gpu_info();
+    let gpu_warnings = gpu_compat_warnings(&gpu, current_os(), lwjgl_version.as_deref());
+    for warning in &gpu_warnings {
+        logs.push(format!(
+            "⚠ GPU {} ({}): {} Sugerencia: {}",
+            gpu.adapter_name, gpu.driver_version, warning.message, warning.suggestion
+        ));
+    }
+    if !gpu_warnings.is_empty() {
+        emit_journaled(
+            &app,
+            instance_root.as_str(),
+            "instance_gpu_warnings",
+            serde_json::json!({
+                "instanceRoot": instance_root.clone(),
+                "gpu": gpu,
+                "lwjglVersion": lwjgl_version,
+                "warnings": gpu_warnings,
+            }),
+        );
+    }
+
+    watchdog.enter_phase("jars")?;
     let loader = metadata.loader.trim().to_ascii_lowercase();
-    if loader == "vanilla" || loader.is_empty() {
-        ensure_main_class_present_in_jar(&client_jar, &resolved_main_class).map_err(|err| {
-            format!("{err}. (instancia vanilla, mainClass debe estar en client.jar)")
-        })?;
+    let is_vanilla = loader == "vanilla" || loader.is_empty();
+    if !is_vanilla && !metadata.skip_duplicate_mod_check {
+        let duplicate_mods = find_duplicate_groups(&effective_mods_dir(instance_path));
+        if !duplicate_mods.is_empty() {
+            for line in describe_duplicate_groups(&duplicate_mods) {
+                logs.push(format!("⚠ {line}"));
+                violations.note(DUPLICATE_MODS, line);
+            }
+            emit_journaled(
+                &app,
+                instance_root.as_str(),
+                "instance_duplicate_mods",
+                serde_json::json!({
+                    "instanceRoot": instance_root.clone(),
+                    "groups": duplicate_mods,
+                }),
+            );
+        }
+    }
+    let mut launch_classpath_entries = resolved_libraries.classpath_entries.clone();
+    launch_classpath_entries.push(client_jar.display().to_string());
+    let (jars_to_validate, main_class_search) =
+        launch_jar_sets(&metadata.loader, &resolved_libraries, &client_jar);
+    let inspected_jars = jars_to_validate
+        .iter()
+        .chain(main_class_search.iter())
+        .cloned()
+        .collect::<Vec<_>>();
+    let jar_inspection = match prewarmed
+        .and_then(|plan| plan.jars)
+        .filter(|jars| jars.still_valid(&resolved_main_class, &inspected_jars))
+    {
+        Some(jars) => {
+            logs.push("✔ inspección de jars reutilizada de la prevalidación".to_string());
+            jars.inspection
+        }
+        None => inspect_launch_jars(&jars_to_validate, &main_class_search, &resolved_main_class),
+    };
+    logs.push(format!(
+        "✔ inspección de jars: {} abiertos una vez (máx. {} a la vez), mainClass buscada en {}, {} reintentos, {} ms",
+        jar_inspection.archives_opened,
+        jar_inspection.peak_open_handles,
+        jar_inspection.main_class_probes,
+        jar_inspection.open_retries,
+        jar_inspection.elapsed_ms
+    ));
+    watchdog.add_phase_counter("jarOpenRetries", jar_inspection.open_retries as u64);
+    let jars_with_natives = jar_inspection.jars_with_natives();
+    if jars_with_natives > 0 {
+        logs.push(format!(
+            "jars del classpath con nativos embebidos: {jars_with_natives}"
+        ));
+    }
+
+    if is_vanilla {
+        ensure_main_class_present_in_jar(
+            &client_jar,
+            jar_inspection.check(&client_jar),
+            &resolved_main_class,
+        )
+        .map_err(|err| format!("{err}. (instancia vanilla, mainClass debe estar en client.jar)"))?;
         logs.push(format!(
             "✔ mainClass {resolved_main_class} verificada en client.jar"
         ));
     } else {
-        let class_entry = format!("{}.class", resolved_main_class.replace('.', "/"));
-
         // First try to find the class inside a classpath JAR (works for Fabric, Quilt, legacy Forge).
-        let found_in_classpath = resolved_libraries
-            .classpath_entries
-            .iter()
-            .find(|jar_path| {
-                std::fs::File::open(jar_path)
-                    .ok()
-                    .and_then(|file| zip::ZipArchive::new(file).ok())
-                    .and_then(|mut archive| archive.by_name(&class_entry).ok().map(|_| true))
-                    .unwrap_or(false)
-            });
+        let found_in_classpath = jar_inspection.main_class_jar.as_ref();
 
         if let Some(jar_path) = found_in_classpath {
             logs.push(format!(
                 "✔ mainClass {resolved_main_class} verificada en library: {}",
-                Path::new(jar_path)
-                    .file_name()
-                    .unwrap_or_default()
-                    .to_string_lossy()
+                jar_path.file_name().unwrap_or_default().to_string_lossy()
             ));
         } else {
             // Modern Forge (≥1.36 approx) loads BootstrapLauncher via the JPMS module path
@@ -1108,7 +3108,8 @@ Primeros 5: {}",
                     "La mainClass '{resolved_main_class}' no se encontró \
 en ningún JAR del classpath del loader '{}'.\n{}",
                     metadata.loader, diagnostic
-                ));
+                )
+                .into());
             }
         }
     }
@@ -1151,7 +3152,7 @@ en ningún JAR del classpath del loader '{}'.\n{}",
         return Err(format!(
             "Regla de validación incumplida: loader={} pero mainClass quedó en vanilla ({resolved_main_class}).",
             metadata.loader
-        ));
+        ).into());
     }
     if let Some(expected_main_class) = expected_main_class_for_loader(&loader_lower, &version_json)
     {
@@ -1159,7 +3160,7 @@ en ningún JAR del classpath del loader '{}'.\n{}",
             return Err(format!(
                 "Regla de validación incumplida: loader={} requiere mainClass={} pero se obtuvo {}.",
                 metadata.loader, expected_main_class, resolved_main_class
-            ));
+            ).into());
         }
     }
     // Newer NeoForge (21.x+) uses net.neoforged.* instead of cpw.mods.bootstraplauncher
@@ -1177,14 +3178,17 @@ en ningún JAR del classpath del loader '{}'.\n{}",
         && !has_neoforged_modern
     {
         return Err(
-            "Forge moderno requiere bootstraplauncher en classpath o module-path.".to_string(),
+            "Forge moderno requiere bootstraplauncher en classpath o module-path."
+                .to_string()
+                .into(),
         );
     }
     if loader_lower == "neoforge" && !has_bootstrap && !has_neoforged_modern {
         return Err(format!(
             "Regla de validación incumplida: loader={} requiere bootstraplauncher en classpath.",
             metadata.loader
-        ));
+        )
+        .into());
     }
     if loader_lower != "vanilla" {
         let effective_version_json = mc_root
@@ -1211,54 +3215,77 @@ en ningún JAR del classpath del loader '{}'.\n{}",
             return Err(format!(
                 "Regla de validación incumplida: loader={} requiere inheritsFrom en version.json efectivo.",
                 metadata.loader
-            ));
+            ).into());
         }
     }
 
-    let mut jars_to_validate = resolved_libraries
-        .classpath_entries
+    watchdog.enter_phase("natives")?;
+    let natives_dir = mc_root.join("natives");
+    let classpath_entries = finalize_classpath_and_natives(
+        &launch_classpath_entries,
+        &resolved_libraries.native_jars,
+        &natives_dir,
+        Some(&jar_inspection),
+        &mut logs,
+    )?;
+    drop(jar_inspection);
+    for skipped in launch_classpath_entries
         .iter()
-        .map(PathBuf::from)
-        .collect::<Vec<_>>();
-    jars_to_validate.push(client_jar.clone());
-    jars_to_validate.extend(
-        resolved_libraries
-            .native_jars
-            .iter()
-            .map(|native| PathBuf::from(&native.path))
-            .filter(|path| path.exists()),
-    );
-    validate_jars_as_zip(&jars_to_validate)?;
-    logs.push(format!(
-        "✔ jars validados como zip: {}",
-        jars_to_validate.len()
-    ));
-
-    logs.push(format!(
-        "native_jars detectados: {}",
-        resolved_libraries.native_jars.len()
-    ));
-    for native in &resolved_libraries.native_jars {
-        let file_name = Path::new(&native.path)
-            .file_name()
-            .and_then(|name| name.to_str())
-            .unwrap_or("unknown");
-        logs.push(format!("  - {file_name}"));
+        .filter(|entry| !classpath_entries.contains(entry))
+    {
+        violations.note(
+            NATIVES,
+            format!("jar de natives corrupto omitido del classpath: {skipped}"),
+        );
+    }
+    if let Some(legacy_dir) = &legacy_natives_dir {
+        violations.note(
+            NATIVES,
+            format!(
+                "faltan natives de clasificador; se usan los preextraídos de {}",
+                legacy_dir.display()
+            ),
+        );
+        let copied = copy_legacy_natives(legacy_dir, &natives_dir)?;
+        log::info!(
+            "🔹 Natives en formato antiguo: {copied} archivos copiados desde {}",
+            legacy_dir.display()
+        );
+        logs.push(format!(
+            "✔ natives preextraídos (formato antiguo) copiados desde {}: {copied}",
+            legacy_dir.display()
+        ));
     }
 
-    let natives_dir = mc_root.join("natives");
-    prepare_natives_dir(&natives_dir)?;
-    extract_natives(&resolved_libraries.native_jars, &natives_dir, &mut logs)?;
-    log_natives_dir_contents(&natives_dir, &mut logs);
-    logs.push(format!(
-        "✔ natives extraídos: {} archivos fuente en {}",
-        resolved_libraries.native_jars.len(),
-        natives_dir.display()
-    ));
-
+    watchdog.enter_phase("assets")?;
     let launcher_assets_root = launcher_root.join("assets");
-    let (resolved_assets_index_name, resolved_assets_root) =
-        ensure_assets_ready(&version_json, &launcher_assets_root, &mut logs)?;
+    // Cancelar deja los objetos ya escritos; el siguiente lanzamiento solo baja los que faltan.
+    let assets_task = TaskHandle::begin(
+        &app,
+        "assets_download",
+        format!("Assets de {}", metadata.minecraft_version),
+    );
+    let mut unresolved_assets = Vec::new();
+    let assets = ensure_assets_ready(
+        &version_json,
+        &launcher_assets_root,
+        &mut logs,
+        &watchdog,
+        &assets_task,
+        &mut unresolved_assets,
+    );
+    assets_task.finish(&assets);
+    let (resolved_assets_index_name, resolved_assets_root) = assets?;
+    if !unresolved_assets.is_empty() {
+        violations.note(
+            ASSET_OBJECTS_UNRESOLVED,
+            format!(
+                "{} objeto(s) del asset index sin hash válido: {}",
+                unresolved_assets.len(),
+                unresolved_assets.join(", ")
+            ),
+        );
+    }
 
     let client_extra = mc_root
         .join("versions")
@@ -1269,11 +3296,16 @@ en ningún JAR del classpath del loader '{}'.\n{}",
             "⚠ client-extra.jar no encontrado: {}. NeoForge puede fallar al cargar recursos de MC.",
             client_extra.display()
         ));
+        violations.note(
+            CLIENT_EXTRA_MISSING,
+            format!("client-extra.jar no encontrado: {}", client_extra.display()),
+        );
     }
 
     fs::create_dir_all(mc_root.join("mods"))
         .map_err(|err| format!("No se pudo crear mods/: {err}"))?;
 
+    watchdog.enter_phase("arguments")?;
     logs.push("🔹 2. Preparación de ejecución".to_string());
 
     let sep = if cfg!(target_os = "windows") {
@@ -1281,12 +3313,11 @@ en ningún JAR del classpath del loader '{}'.\n{}",
     } else {
         ":"
     };
-    let mut classpath_entries = resolved_libraries.classpath_entries.clone();
-    classpath_entries.push(client_jar.display().to_string());
-    verify_no_duplicate_classpath_entries(&classpath_entries, &mut logs)?;
     let classpath = classpath_entries.join(sep);
     if classpath.trim().is_empty() {
-        return Err("Classpath vacío luego del ensamblado final.".to_string());
+        return Err("Classpath vacío luego del ensamblado final."
+            .to_string()
+            .into());
     }
     logs.push(format!(
         "✔ classpath construido ({} entradas)",
@@ -1347,11 +3378,7 @@ en ningún JAR del classpath del loader '{}'.\n{}",
     };
 
     let launch_rules = RuleContext {
-        features: RuleFeatures {
-            is_demo_user: false,
-            has_custom_resolution: false,
-            is_quick_play: false,
-        },
+        features: launch_context.apply_quick_play_features(RuleFeatures::default()),
         ..RuleContext::current()
     };
 
@@ -1383,7 +3410,7 @@ en ningún JAR del classpath del loader '{}'.\n{}",
                 return Err(format!(
                     "Forge moderno detectado pero no se encontró win_args.txt/unix_args.txt en versions/{}/. El instalador de Forge debe haber fallado o la instancia debe recrearse.",
                     selected_version_id
-                ));
+                ).into());
             }
         }
     } else {
@@ -1395,24 +3422,57 @@ en ningún JAR del classpath del loader '{}'.\n{}",
     let forge_library_directory = forge_args_resolution.library_directory.clone();
     let forge_extra_jvm_args = forge_args_resolution.args;
 
-    let memory_args = vec![
-        format!("-Xms{}M", metadata.ram_mb.max(512) / 2),
-        format!("-Xmx{}M", metadata.ram_mb.max(512)),
-    ];
+    let user_java_args = normalize_java_args(&metadata.java_args)?;
+    for change in &user_java_args.changes {
+        logs.push(format!("⚠ java_args: {change}"));
+        violations.note(JAVA_ARGS_NORMALIZED, change.clone());
+    }
+    let memory_args = merge_memory_args(
+        &[
+            format!("-Xms{}M", metadata.ram_mb.max(512) / 2),
+            format!("-Xmx{}M", metadata.ram_mb.max(512)),
+        ],
+        &user_java_args.args,
+    );
+    let tuning_args = if metadata.auto_jvm_tuning {
+        auto_jvm_tuning_args(
+            &metadata,
+            parse_java_major(&java_version_text),
+            &user_java_args.args,
+            &mut logs,
+        )
+    } else {
+        Vec::new()
+    };
     let mut jvm_args: Vec<String> = Vec::new();
     jvm_args.extend(memory_args.clone());
+    jvm_args.extend(tuning_args.iter().cloned());
 
     if is_forge && forge_generation == ForgeGeneration::Modern {
         jvm_args.extend(forge_extra_jvm_args.clone());
     }
 
     jvm_args.extend(
-        metadata
-            .java_args
+        user_java_args
+            .args
             .iter()
             .map(|arg| replace_launch_variables(arg, &launch_context)),
     );
-    jvm_args.append(&mut resolved.jvm);
+    jvm_args.append(&mut resolved.jvm);
+    apply_log4j_mitigation(
+        &app,
+        &launcher_root,
+        &metadata.minecraft_version,
+        &mut jvm_args,
+        &mut logs,
+    );
+    let logging_argument = apply_client_logging_config(
+        &app,
+        &launcher_root,
+        &version_json,
+        &mut jvm_args,
+        &mut logs,
+    );
 
     // Modern Forge (1.17+) needs system properties so its bootstrap can
     // locate libraries and know which JARs to skip mod-scanning.
@@ -1434,6 +3494,16 @@ en ningún JAR del classpath del loader '{}'.\n{}",
         );
     }
 
+    if let Some(mods_dir) = mods_dir_override_path(&metadata) {
+        let injection = validate_mods_dir_override(&metadata, &mods_dir)?;
+        apply_mods_dir_injection(&mut jvm_args, &mut resolved.game, &injection);
+        logs.push(format!(
+            "✔ carpeta de mods redirigida a {}",
+            mods_dir.display()
+        ));
+    }
+    apply_optional_game_flags(&metadata, &mc_root, &mut resolved.game, &mut logs)?;
+
     logs.push(format!(
         "DEBUG auth - profile_name: '{}'",
         verified_auth.profile_name
@@ -1531,7 +3601,7 @@ en ningún JAR del classpath del loader '{}'.\n{}",
                     "java_home inválido tras corrección: {}\nlib/modules no existe.\nRuntime embebido: {}",
                     home_str,
                     correct_java_home.display()
-                ));
+                ).into());
             }
             logs.push(format!("✔ java.home verificado en: {}", home_str));
             break;
@@ -1540,15 +3610,20 @@ en ningún JAR del classpath del loader '{}'.\n{}",
     // ── Fin corrección java.home ────────────────────────────────────────────
 
     logs.push(format!(
-        "jvm_args orden final: [memory({})] [forge_file({})] [user({})] [version_json({})] [cp({})]",
+        "jvm_args orden final: [memory({})] [auto_tuning({})] [forge_file({})] [user({})] [version_json({})] [cp({})]",
         memory_args.len(),
+        tuning_args.len(),
         if is_forge && forge_generation == ForgeGeneration::Modern {
             forge_extra_jvm_args.len()
         } else {
             0
         },
-        metadata.java_args.len(),
-        jvm_args.len().saturating_sub(memory_args.len()).saturating_sub(metadata.java_args.len()),
+        user_java_args.args.len(),
+        jvm_args
+            .len()
+            .saturating_sub(memory_args.len())
+            .saturating_sub(tuning_args.len())
+            .saturating_sub(user_java_args.args.len()),
         if contains_classpath_switch(&jvm_args) { 2 } else { 0 }
     ));
 
@@ -1561,7 +3636,8 @@ en ningún JAR del classpath del loader '{}'.\n{}",
         return Err(format!(
             "Hay variables sin resolver en argumentos JVM/Game: {}",
             unresolved_vars.join(", ")
-        ));
+        )
+        .into());
     }
 
     logs.push("✔ argumentos JVM y GAME resueltos".to_string());
@@ -1587,14 +3663,14 @@ en ningún JAR del classpath del loader '{}'.\n{}",
     logs.push("✔ Manejo de cierre normal/error y persistencia de log completo".to_string());
 
     if !verified_auth.premium_verified {
-        return Err("Cuenta sin licencia premium verificada. Lanzamiento bloqueado.".to_string());
+        return Err(
+            "Cuenta sin licencia premium verificada. Lanzamiento bloqueado."
+                .to_string()
+                .into(),
+        );
     }
 
-    validate_required_online_launch_flags(&resolved.game, &launch_context).map_err(|err| {
-        format!(
-            "Argumentos críticos de sesión incompletos o inválidos. {err}. Lanzamiento bloqueado para evitar Demo."
-        )
-    })?;
+    ensure_online_launch_flags(&resolved.game, &launch_context)?;
 
     let username = find_arg_value(&resolved.game, "--username").unwrap_or_default();
     let uuid = find_arg_value(&resolved.game, "--uuid").unwrap_or_default();
@@ -1614,7 +3690,9 @@ en ningún JAR del classpath del loader '{}'.\n{}",
 
     if resolved.game.iter().any(|arg| arg == "--demo") {
         return Err(
-            "Se detectó --demo en los argumentos de juego. Lanzamiento bloqueado.".to_string(),
+            "Se detectó --demo en los argumentos de juego. Lanzamiento bloqueado."
+                .to_string()
+                .into(),
         );
     }
 
@@ -1622,7 +3700,8 @@ en ningún JAR del classpath del loader '{}'.\n{}",
         return Err(format!(
             "--username no coincide con el perfil oficial validado. esperado={} recibido={}",
             verified_auth.profile_name, username
-        ));
+        )
+        .into());
     }
 
     if uuid != sanitize_uuid(&verified_auth.profile_id) {
@@ -1630,13 +3709,15 @@ en ningún JAR del classpath del loader '{}'.\n{}",
             "--uuid no coincide byte a byte con profile.id validado. esperado={} recibido={}",
             sanitize_uuid(&verified_auth.profile_id),
             uuid
-        ));
+        )
+        .into());
     }
 
     if access_token != verified_auth.minecraft_access_token {
         return Err(
             "--accessToken no coincide con el token activo validado; lanzamiento bloqueado."
-                .to_string(),
+                .to_string()
+                .into(),
         );
     }
 
@@ -1647,6 +3728,33 @@ en ningún JAR del classpath del loader '{}'.\n{}",
         .collect::<Vec<_>>()
         .join(" ");
     logs.push(format!("COMANDO FINAL JAVA: {command_preview}"));
+    write_verification_marker(instance_path, &metadata.version_id, clock.as_ref());
+
+    let phase_timings = watchdog.timeline();
+    logs.push(format!(
+        "⏱ fases: {}",
+        phase_timings
+            .iter()
+            .map(|timing| {
+                let counters = timing
+                    .counters
+                    .iter()
+                    .map(|(name, value)| format!("{name}={value}"))
+                    .collect::<Vec<_>>();
+                if counters.is_empty() {
+                    format!("{}={}ms", timing.phase, timing.elapsed_ms)
+                } else {
+                    format!(
+                        "{}={}ms ({})",
+                        timing.phase,
+                        timing.elapsed_ms,
+                        counters.join(", ")
+                    )
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    ));
 
     Ok(LaunchValidationResult {
         java_path: embedded_java,
@@ -1656,14 +3764,13 @@ en ningún JAR del classpath del loader '{}'.\n{}",
         game_args: resolved.game,
         main_class: resolved.main_class,
         logs,
-        refreshed_auth_session: LaunchAuthSession {
-            profile_id: verified_auth.profile_id,
-            profile_name: verified_auth.profile_name,
-            minecraft_access_token: verified_auth.minecraft_access_token,
-            minecraft_access_token_expires_at: verified_auth.minecraft_access_token_expires_at,
-            microsoft_refresh_token: auth_session.microsoft_refresh_token,
-            premium_verified: verified_auth.premium_verified,
-        },
+        refreshed_auth_session,
+        phase_timings,
+        applied_library_overrides: resolved_libraries.applied_overrides,
+        auto_jvm_args: tuning_args,
+        logging_argument,
+        strict_mode: metadata.strict_mode,
+        strict_violations: violations.into_vec(),
     })
 }
 
@@ -1672,280 +3779,467 @@ pub async fn start_instance(
     app: AppHandle,
     instance_root: String,
     auth_session: LaunchAuthSession,
-) -> Result<StartInstanceResult, String> {
-    let metadata = get_instance_metadata(instance_root.clone())?;
+    force: Option<bool>,
+    persist_refreshed_session: Option<bool>,
+) -> Result<StartInstanceResult, LaunchError> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    let metadata = read_instance_metadata(instance_root.to_string())?;
+    // Antes de cualquier preparación: los comandos externos solo corren aprobados.
+    let hook_approvals = load_launcher_config(&app)
+        .map(|config| config.hook_approvals)
+        .unwrap_or_default();
+    check_hook_approval(&hook_approvals, &metadata)?;
     discord_presence::set_instance_presence(&metadata);
-    let _ = touch_instance_last_used(&instance_root);
+    let clock = app_clock(&app).clock;
     if metadata.state.eq_ignore_ascii_case("redirect") {
-        register_runtime_start(instance_root.clone())?;
+        // El launcher de origen puede tener abierta la misma carpeta; las instancias propias
+        // son exclusivas de este launcher y no se comprueban.
+        if let Some(game_dir) = crate::app::redirect_launch::redirect_game_dir(instance_root.path())
+        {
+            ensure_game_dir_free(instance_root.as_str(), &game_dir, force.unwrap_or(false))?;
+        }
+        register_runtime_start(instance_root.to_string(), clock.as_ref())?;
+        let app_for_webhooks = app.clone();
         let result = crate::app::redirect_launch::launch_redirect_instance(
             app,
-            instance_root.clone(),
+            instance_root.to_string(),
             auth_session,
+            persist_refreshed_session.unwrap_or(false),
         )
         .await;
         match result {
             Ok(started) => {
-                register_runtime_pid(&instance_root, started.pid);
+                register_runtime_pid(instance_root.as_str(), started.pid, &started.java_path);
+                touch_instance_last_used(instance_root.as_str(), clock.as_ref());
+                spawn_window_tweaks(
+                    &app_for_webhooks,
+                    instance_root.as_str(),
+                    &metadata,
+                    started.pid,
+                );
+                notify_instance_lifecycle(
+                    &app_for_webhooks,
+                    instance_root.as_str(),
+                    "start",
+                    None,
+                    Some(started.refreshed_auth_session.profile_name.clone()),
+                );
                 return Ok(started);
             }
             Err(err) => {
                 if let Ok(mut registry) = runtime_registry().lock() {
-                    registry.remove(&instance_root);
+                    registry.remove(instance_root.as_str());
                 }
+                mark_launcher_snapshot_dirty();
                 discord_presence::set_launcher_presence();
-                return Err(err);
+                return Err(err.into());
             }
         }
     }
 
-    register_runtime_start(instance_root.clone())?;
+    register_runtime_start(instance_root.to_string(), clock.as_ref())?;
 
-    let runtime_instance_root = match prepare_runtime_instance_root(&app, &instance_root) {
+    // La copia de ejecución de un atajo vive en la caché del launcher, no bajo instancias.
+    let runtime_instance_root = match prepare_runtime_instance_root(&app, instance_root.as_str())
+        .and_then(|runtime| resolve_trusted_launcher_data_root(&app, &runtime))
+    {
         Ok(value) => value,
         Err(err) => {
             if let Ok(mut registry) = runtime_registry().lock() {
-                registry.remove(&instance_root);
+                registry.remove(instance_root.as_str());
             }
+            mark_launcher_snapshot_dirty();
             discord_presence::set_launcher_presence();
-            return Err(err);
+            return Err(err.into());
         }
     };
 
     let instance_root_for_prepare = runtime_instance_root.clone();
-    let prepared = match tauri::async_runtime::spawn_blocking(move || {
-        validate_and_prepare_launch(instance_root_for_prepare, auth_session)
-    })
-    .await
-    .map_err(|err| format!("Falló la tarea de validación/lanzamiento: {err}"))?
-    {
+    let watchdog = LaunchWatchdog::new(configured_phase_budget(&app));
+    set_preparation_watchdog(instance_root.as_str(), Some(watchdog.clone()));
+    let watchdog_for_prepare = watchdog.clone();
+    let app_for_prepare = app.clone();
+    let preparation = tauri::async_runtime::spawn_blocking(move || {
+        run_with_watchdog(watchdog_for_prepare, || {
+            prepare_strict_launch(
+                app_for_prepare,
+                &instance_root_for_prepare,
+                auth_session,
+                persist_refreshed_session,
+            )
+        })
+    });
+    // Si una fase supera su presupuesto se responde de inmediato; el hilo bloqueado
+    // termina por su cuenta al ver la cancelación en la siguiente comprobación.
+    let prepared = tokio::select! {
+        joined = preparation => joined
+            .map_err(|err| format!("Falló la tarea de validación/lanzamiento: {err}").into())
+            .and_then(|result| result),
+        timeout = watchdog.expired() => Err(timeout.into()),
+    };
+    set_preparation_watchdog(instance_root.as_str(), None);
+    let prepared = match prepared {
         Ok(value) => value,
         Err(err) => {
+            emit_launch_failure(&app, instance_root.as_str(), &err);
             if let Ok(mut registry) = runtime_registry().lock() {
-                registry.remove(&instance_root);
+                registry.remove(instance_root.as_str());
             }
+            mark_launcher_snapshot_dirty();
             discord_presence::set_launcher_presence();
             return Err(err);
         }
     };
 
     let java_launch_path = resolve_java_launch_path(Path::new(&prepared.java_path));
-    let mut command = Command::new(&java_launch_path);
-    let mut effective_jvm_args = prepared.jvm_args.clone();
-
-    if cfg!(target_os = "windows") {
-        if let Some(classpath) = strip_classpath_from_jvm_args(&mut effective_jvm_args) {
-            command.env("CLASSPATH", classpath);
-        }
-    }
-
-    let launch_jvm_args = effective_jvm_args.clone();
-
-    command
-        .args(&effective_jvm_args)
-        .arg(&prepared.main_class)
-        .args(&prepared.game_args)
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .stdin(Stdio::null())
-        .current_dir(Path::new(&runtime_instance_root).join("minecraft"));
-
-    #[cfg(unix)]
-    {
-        command.process_group(0);
-    }
+    let (mut command, launch_jvm_args) = build_java_command(
+        &java_launch_path,
+        &prepared.jvm_args,
+        &prepared.main_class,
+        &prepared.game_args,
+        &runtime_instance_root.path().join("minecraft"),
+        cfg!(target_os = "windows"),
+    );
 
-    #[cfg(windows)]
-    {
-        command.creation_flags(CREATE_NO_WINDOW);
-    }
+    let gpu_preference = apply_gpu_preference(
+        &mut command,
+        &java_launch_path,
+        metadata.preferred_gpu.as_deref(),
+    );
 
-    let mut child = match command
+    let child = match command
         .spawn()
         .map_err(|err| format!("No se pudo iniciar java para la instancia: {err}"))
     {
         Ok(child) => child,
         Err(err) => {
             if let Ok(mut registry) = runtime_registry().lock() {
-                registry.remove(&instance_root);
+                registry.remove(instance_root.as_str());
             }
+            mark_launcher_snapshot_dirty();
             discord_presence::set_launcher_presence();
-            return Err(err);
+            return Err(err.into());
         }
     };
 
     let pid = child.id();
-    register_runtime_pid(&instance_root, pid);
+    register_runtime_pid(instance_root.as_str(), pid, &prepared.java_path);
+    touch_instance_last_used(instance_root.as_str(), clock.as_ref());
+    spawn_window_tweaks(&app, instance_root.as_str(), &metadata, pid);
+    start_runtime_metrics(
+        instance_root.as_str(),
+        pid,
+        gpu_preference
+            .as_ref()
+            .map(|applied| applied.log_line.clone()),
+    );
+    spawn_launch_lock_recorder(
+        instance_root.to_string(),
+        runtime_instance_root.to_string(),
+        &prepared,
+        clock.now_rfc3339(),
+    );
+    notify_instance_lifecycle(
+        &app,
+        instance_root.as_str(),
+        "start",
+        None,
+        Some(prepared.refreshed_auth_session.profile_name.clone()),
+    );
+
+    let clear_gpu_on_exit = gpu_preference_cleanup(&app, gpu_preference.as_ref());
+    monitor_child(
+        app,
+        instance_root.to_string(),
+        child,
+        SessionAccount {
+            profile_id: prepared.refreshed_auth_session.profile_id.clone(),
+            profile_name: prepared.refreshed_auth_session.profile_name.clone(),
+        },
+        runtime_instance_root.path().join("minecraft"),
+        clear_gpu_on_exit,
+    );
+
+    let java_path = prepared.java_path.clone();
+
+    Ok(StartInstanceResult {
+        pid,
+        java_path,
+        logs: [
+            "Comando de lanzamiento ejecutado con argumentos validados.".to_string(),
+            format!(
+                "Comando final ejecutado: {}",
+                std::iter::once(prepared.java_path)
+                    .chain(launch_jvm_args.iter().cloned())
+                    .chain(std::iter::once(prepared.main_class.clone()))
+                    .chain(prepared.game_args.iter().cloned())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            ),
+            "Salida estándar y de error conectadas para monitoreo; exit_code persistido al finalizar.".to_string(),
+        ]
+        .into_iter()
+        .chain(gpu_preference.map(|applied| applied.log_line))
+        .collect(),
+        refreshed_auth_session: prepared.refreshed_auth_session,
+    })
+}
+
+/// Acción de salida que quita la preferencia de GPU si `clear_gpu_preference_on_exit`.
+pub(crate) fn gpu_preference_cleanup(
+    app: &AppHandle,
+    applied: Option<&AppliedGpuPreference>,
+) -> impl FnOnce(Option<i32>) + Send + 'static {
+    let executable = applied
+        .and_then(|applied| applied.registry_executable.clone())
+        .filter(|_| {
+            load_launcher_config(app)
+                .map(|config| config.clear_gpu_preference_on_exit)
+                .unwrap_or(false)
+        });
+    move |_| {
+        if let Some(executable) = executable {
+            clear_gpu_preference(&executable);
+        }
+    }
+}
 
+/// Supervisa un proceso del juego ya lanzado; `on_exit` recibe el exit code al terminar.
+pub(crate) fn monitor_child(
+    app: AppHandle,
+    instance_root: String,
+    mut child: Child,
+    account: SessionAccount,
+    game_dir: PathBuf,
+    on_exit: impl FnOnce(Option<i32>) + Send + 'static,
+) {
+    let pid = child.id();
     let stdout = child.stdout.take();
     let stderr = child.stderr.take();
-    let instance_root_for_thread = instance_root.clone();
-    let expected_username = prepared.refreshed_auth_session.profile_name.clone();
-
-    let app_for_thread = app.clone();
+    let session_started = SystemTime::now();
+    let clock = app_clock(&app).clock;
 
     thread::spawn(move || {
         let stop_log_monitor = Arc::new(AtomicBool::new(false));
         let monitor_stop_signal = Arc::clone(&stop_log_monitor);
-        let monitor_instance = instance_root_for_thread.clone();
-        let monitor_username = expected_username.clone();
-        let monitor_app = app_for_thread.clone();
+        let monitor_instance = instance_root.clone();
+        let monitor_username = account.profile_name.clone();
+        let monitor_app = app.clone();
+        let monitor_log = game_dir.join("logs").join("latest.log");
         let monitor_handle = thread::spawn(move || {
             monitor_latest_log_for_auth(
                 monitor_app,
                 monitor_instance,
+                monitor_log,
                 monitor_username,
                 pid,
                 monitor_stop_signal,
             );
         });
         let stderr_tail = Arc::new(Mutex::new(VecDeque::<String>::new()));
+        let output = RuntimeOutputSink {
+            app: app.clone(),
+            instance_root: instance_root.clone(),
+            throttle: Arc::new(Mutex::new(OutputThrottle::new(
+                Instant::now(),
+                OUTPUT_MAX_LINES_PER_SEC,
+            ))),
+            session_log: Arc::new(SessionLog::create(Path::new(&instance_root))),
+            tail: Arc::clone(&stderr_tail),
+            legacy_events: legacy_runtime_output_enabled(&app),
+            filter: Arc::new(Mutex::new(ConsoleFilter::compile(
+                &read_instance_metadata(instance_root.clone())
+                    .map(|metadata| metadata.console_filters)
+                    .unwrap_or_default(),
+            ))),
+            stop: Arc::new(AtomicBool::new(false)),
+        };
         let mut stream_threads = Vec::new();
-
         if let Some(stdout_pipe) = stdout {
-            let instance_for_stdout = instance_root_for_thread.clone();
-            let app_for_stdout = app_for_thread.clone();
-            let tail_for_stdout = Arc::clone(&stderr_tail);
-            stream_threads.push(thread::spawn(move || {
-                let reader = BufReader::new(stdout_pipe);
-                for line in reader.lines().map_while(Result::ok) {
-                    if line.trim().is_empty() {
-                        continue;
-                    }
-                    log::info!("[MC-STDOUT][{}] {}", instance_for_stdout, line);
-                    let _ = app_for_stdout.emit(
-                        "instance_runtime_output",
-                        RuntimeOutputEvent {
-                            instance_root: instance_for_stdout.clone(),
-                            stream: "stdout".to_string(),
-                            line: line.clone(),
-                            parsed: parse_log_line(&line),
-                        },
-                    );
-                    if let Ok(mut tail) = tail_for_stdout.lock() {
-                        tail.push_back(format!("[stdout] {line}"));
-                        if tail.len() > 200 {
-                            tail.pop_front();
-                        }
-                    }
-                }
-            }));
+            stream_threads.push(spawn_output_reader(stdout_pipe, "stdout", output.clone()));
         }
-
         if let Some(stderr_pipe) = stderr {
-            let instance_for_stderr = instance_root_for_thread.clone();
-            let app_for_stderr = app_for_thread.clone();
-            let tail_for_stderr = Arc::clone(&stderr_tail);
-            stream_threads.push(thread::spawn(move || {
-                let reader = BufReader::new(stderr_pipe);
-                for line in reader.lines().map_while(Result::ok) {
-                    if line.trim().is_empty() {
-                        continue;
-                    }
-                    log::warn!("[MC-STDERR][{}] {}", instance_for_stderr, line);
-                    let _ = app_for_stderr.emit(
-                        "instance_runtime_output",
-                        RuntimeOutputEvent {
-                            instance_root: instance_for_stderr.clone(),
-                            stream: "stderr".to_string(),
-                            line: line.clone(),
-                            parsed: parse_log_line(&line),
-                        },
-                    );
-                    if let Ok(mut tail) = tail_for_stderr.lock() {
-                        tail.push_back(format!("[stderr] {line}"));
-                        if tail.len() > 200 {
-                            tail.pop_front();
-                        }
-                    }
-                }
-            }));
-        }
-
-        for handle in stream_threads {
-            let _ = handle.join();
+            stream_threads.push(spawn_output_reader(stderr_pipe, "stderr", output.clone()));
         }
+        let flusher_output = output.clone();
+        let flusher_handle = thread::spawn(move || {
+            while !flusher_output.stop.load(Ordering::Relaxed) {
+                thread::sleep(OUTPUT_FLUSH_INTERVAL);
+                flusher_output.flush_due();
+            }
+        });
 
-        let exit_code = child.wait().ok().and_then(|status| status.code());
+        let exit = wait_and_record_exit(
+            &mut child,
+            &instance_root,
+            &stream_threads,
+            &stderr_tail,
+            clock.as_ref(),
+        );
+        output.stop.store(true, Ordering::Relaxed);
+        let _ = flusher_handle.join();
+        output.flush_all();
+        output.session_log.close();
+        log::info!(
+            "🔹 Salida de la instancia {} guardada en {}",
+            instance_root,
+            output.session_log.path().display()
+        );
         stop_log_monitor.store(true, Ordering::Relaxed);
         let _ = monitor_handle.join();
-        let final_tail = stderr_tail
-            .lock()
-            .map(|tail| tail.clone())
-            .unwrap_or_else(|_| VecDeque::new());
 
-        let _ = app_for_thread.emit(
+        emit_journaled(
+            &app,
+            &instance_root,
             "instance_runtime_output",
             RuntimeOutputEvent {
-                instance_root: instance_root_for_thread.clone(),
+                instance_root: instance_root.clone(),
                 stream: "system".to_string(),
-                line: if exit_code == Some(0) {
+                line: if exit.exit_code == Some(0) {
                     "Instance closed normally".to_string()
                 } else {
                     format!(
                         "Instance crashed (exit_code={})",
-                        exit_code
+                        exit.exit_code
                             .map(|value| value.to_string())
                             .unwrap_or_else(|| "desconocido".to_string())
                     )
                 },
                 parsed: None,
+                repeat_count: None,
             },
         );
-
-        let runtime_tail: VecDeque<String> = final_tail
-            .into_iter()
-            .rev()
-            .take(50)
-            .collect::<Vec<_>>()
-            .into_iter()
-            .rev()
-            .collect();
-
-        let _ = app_for_thread.emit(
-            "instance_runtime_exit",
-            serde_json::json!({
-                "instanceRoot": instance_root_for_thread.clone(),
-                "exitCode": exit_code,
-                "pid": pid,
-            }),
+        let mut exit_payload = runtime_exit_payload(&instance_root, &exit, &account);
+        if exit.exit_code == Some(0) {
+            mark_launch_successful(Path::new(&instance_root), &game_dir);
+        } else if let Value::Object(map) = &mut exit_payload {
+            let changes = what_changed_since(Path::new(&instance_root));
+            map.insert(
+                "changes".to_string(),
+                serde_json::to_value(changes).unwrap_or(Value::Null),
+            );
+        }
+        emit_journaled(&app, &instance_root, "instance_runtime_exit", exit_payload);
+        // Los mundos y la configuración cambian al jugar.
+        schedule_content_index_refresh(Path::new(&instance_root));
+        notify_instance_lifecycle(
+            &app,
+            &instance_root,
+            if exit.exit_code == Some(0) {
+                "exit"
+            } else {
+                "crash"
+            },
+            exit.exit_code,
+            Some(account.profile_name.clone()),
         );
-
-        if let Ok(mut registry) = runtime_registry().lock() {
-            registry.insert(
-                instance_root_for_thread,
-                RuntimeState {
-                    pid: Some(pid),
-                    running: false,
-                    exit_code,
-                    stderr_tail: runtime_tail,
-                    started_at: Instant::now(),
-                },
+        record_play_session(
+            &app,
+            &instance_root,
+            PlaySession {
+                started_at: chrono::DateTime::<chrono::Utc>::from(session_started).to_rfc3339(),
+                ended_at: clock.now_rfc3339(),
+                duration_ms: exit.session_ms,
+                exit_code: exit.exit_code,
+                profile_id: account.profile_id.clone(),
+                profile_name: account.profile_name.clone(),
+            },
+        );
+        if exit.exit_code != Some(0) {
+            record_session_crashes(
+                &app,
+                &instance_root,
+                &game_dir,
+                exit.exit_code,
+                session_started,
             );
         }
-
+        flush_metadata_blocking(&instance_root, "salida del juego");
+        cleanup_after_exit(&app, &instance_root);
         discord_presence::set_launcher_presence();
+        on_exit(exit.exit_code);
     });
+}
 
-    let java_path = prepared.java_path.clone();
+/// Fin de una sesión de juego ya registrado en el registro de runtime.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ChildExit {
+    pub pid: u32,
+    pub exit_code: Option<i32>,
+    /// Duración de la sesión (tiempo de juego).
+    pub session_ms: u64,
+}
 
-    Ok(StartInstanceResult {
+/// Espera al proceso y a sus lectores de salida y marca la sesión como terminada con el
+/// exit code real y las últimas líneas de la salida.
+fn wait_and_record_exit(
+    child: &mut Child,
+    instance_root: &str,
+    stream_threads: &[thread::JoinHandle<()>],
+    stderr_tail: &Mutex<VecDeque<String>>,
+    clock: &dyn Clock,
+) -> ChildExit {
+    let pid = child.id();
+    let waited = child.wait();
+    // Justo al reanudar el equipo las tuberías pueden tardar en vaciarse: se les da el
+    // margen completo antes de cerrarlas, y si la espera falló se comprueba que el proceso
+    // haya terminado de verdad antes de informar la salida.
+    let now = clock.now_millis();
+    let resume_grace = current_power_state(now).resume_grace_remaining(now);
+    if let Some(grace) = resume_grace {
+        log::info!(
+            "🔹 El proceso {pid} terminó justo tras reanudar el equipo; se esperan {} s antes de informar la salida.",
+            grace.as_secs()
+        );
+        if waited.is_err() {
+            thread::sleep(grace);
+            while is_process_alive(pid) {
+                thread::sleep(Duration::from_secs(1));
+            }
+        }
+    }
+    let exit_code = waited.ok().and_then(|status| status.code());
+    let drain_grace =
+        resume_grace.map_or(OUTPUT_DRAIN_GRACE, |grace| grace.max(OUTPUT_DRAIN_GRACE));
+    if !wait_for_threads(stream_threads, drain_grace) {
+        // Algún proceso hijo heredó las tuberías y las mantiene abiertas: se termina su
+        // grupo para cerrarlas. Si aun así siguen bloqueados, los lectores se abandonan.
+        #[cfg(unix)]
+        {
+            let _ = Command::new("kill")
+                .args(["-KILL", &format!("-{pid}")])
+                .status();
+            wait_for_threads(stream_threads, drain_grace);
+        }
+    }
+    let final_tail = stderr_tail
+        .lock()
+        .map(|tail| tail.clone())
+        .unwrap_or_else(|_| VecDeque::new());
+    let runtime_tail: VecDeque<String> = final_tail
+        .into_iter()
+        .rev()
+        .take(50)
+        .collect::<Vec<_>>()
+        .into_iter()
+        .rev()
+        .collect();
+    let session_ms = record_runtime_exit(instance_root, pid, exit_code, runtime_tail, clock);
+    ChildExit {
         pid,
-        java_path,
-        logs: vec![
-            "Comando de lanzamiento ejecutado con argumentos validados.".to_string(),
-            format!(
-                "Comando final ejecutado: {}",
-                std::iter::once(prepared.java_path)
-                    .chain(launch_jvm_args.iter().cloned())
-                    .chain(std::iter::once(prepared.main_class.clone()))
-                    .chain(prepared.game_args.iter().cloned())
-                    .collect::<Vec<_>>()
-                    .join(" ")
-            ),
-            "Salida estándar y de error conectadas para monitoreo; exit_code persistido al finalizar.".to_string(),
-        ],
-        refreshed_auth_session: prepared.refreshed_auth_session,
+        exit_code,
+        session_ms,
+    }
+}
+
+fn runtime_exit_payload(instance_root: &str, exit: &ChildExit, account: &SessionAccount) -> Value {
+    serde_json::json!({
+        "instanceRoot": instance_root,
+        "exitCode": exit.exit_code,
+        "pid": exit.pid,
+        "sessionMs": exit.session_ms,
+        "profileId": account.profile_id,
+        "profileName": account.profile_name,
     })
 }
 
@@ -1957,14 +4251,7 @@ fn first_line(text: &str) -> String {
         .to_string()
 }
 
-fn now_unix_millis() -> Option<u64> {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .ok()
-        .map(|duration| duration.as_millis() as u64)
-}
-
-fn terminate_process(pid: u32) {
+pub(crate) fn terminate_process(pid: u32) {
     #[cfg(target_os = "windows")]
     {
         let _ = Command::new("taskkill")
@@ -1987,14 +4274,21 @@ fn terminate_process(pid: u32) {
     }
 }
 
-pub fn register_runtime_start(instance_root: String) -> Result<(), String> {
+pub fn register_runtime_start(instance_root: String, clock: &dyn Clock) -> Result<(), LaunchError> {
     let mut registry = runtime_registry()
         .lock()
         .map_err(|_| "No se pudo bloquear el registro de runtime.".to_string())?;
+    // Con el registro bloqueado: un mantenimiento no puede empezar entre esta comprobación
+    // y el alta de la instancia.
+    if let Some(maintenance) = maintenance_in_progress() {
+        return Err(LaunchError::MaintenanceInProgress(maintenance));
+    }
     if let Some(state) = registry.get(&instance_root) {
         if state.running {
             return Err(
-                "La instancia ya está ejecutándose; no se permite doble ejecución.".to_string(),
+                "La instancia ya está ejecutándose; no se permite doble ejecución."
+                    .to_string()
+                    .into(),
             );
         }
     }
@@ -2005,56 +4299,227 @@ pub fn register_runtime_start(instance_root: String) -> Result<(), String> {
             running: true,
             exit_code: None,
             stderr_tail: VecDeque::new(),
-            started_at: Instant::now(),
+            started_at_ms: clock.now_millis(),
+            preparation: None,
+            java_path: None,
+            suspended_duration_ms: 0,
+            closing: false,
+            folder_missing: false,
         },
     );
+    drop(registry);
+    mark_launcher_snapshot_dirty();
     Ok(())
 }
 
-pub fn register_runtime_pid(instance_root: &str, pid: u32) {
+fn set_preparation_watchdog(instance_root: &str, watchdog: Option<LaunchWatchdog>) {
+    if let Ok(mut registry) = runtime_registry().lock() {
+        if let Some(state) = registry.get_mut(instance_root) {
+            state.preparation = watchdog;
+        }
+    }
+}
+
+/// Fase actual de la preparación del lanzamiento y el tiempo que lleva en ella.
+#[tauri::command]
+pub fn get_launch_preparation_status(
+    app: AppHandle,
+    instance_root: String,
+) -> Result<LaunchPreparationStatus, String> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    let registry = runtime_registry()
+        .lock()
+        .map_err(|_| "No se pudo bloquear el registro de runtime.".to_string())?;
+    Ok(registry
+        .get(instance_root.as_str())
+        .and_then(|state| state.preparation.as_ref())
+        .map(LaunchWatchdog::status)
+        .unwrap_or_else(LaunchPreparationStatus::idle))
+}
+
+fn preparation_watchdog(instance_root: &str) -> Result<LaunchWatchdog, String> {
+    let registry = runtime_registry()
+        .lock()
+        .map_err(|_| "No se pudo bloquear el registro de runtime.".to_string())?;
+    registry
+        .get(instance_root)
+        .and_then(|state| state.preparation.clone())
+        .ok_or_else(|| "La instancia no está preparando un lanzamiento.".to_string())
+}
+
+/// Pausa las descargas de la preparación: las que están en curso terminan y no se
+/// empiezan nuevas hasta `resume_launch_preparation`.
+#[tauri::command]
+pub fn pause_launch_preparation(
+    app: AppHandle,
+    instance_root: String,
+) -> Result<LaunchPreparationStatus, String> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    let watchdog = preparation_watchdog(instance_root.as_str())?;
+    watchdog.pause()?;
+    Ok(watchdog.status())
+}
+
+#[tauri::command]
+pub fn resume_launch_preparation(
+    app: AppHandle,
+    instance_root: String,
+) -> Result<LaunchPreparationStatus, String> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    let watchdog = preparation_watchdog(instance_root.as_str())?;
+    watchdog.resume()?;
+    Ok(watchdog.status())
+}
+
+pub fn register_runtime_pid(instance_root: &str, pid: u32, java_path: &str) {
     if let Ok(mut registry) = runtime_registry().lock() {
         if let Some(state) = registry.get_mut(instance_root) {
             state.pid = Some(pid);
+            state.java_path = Some(java_path.to_string());
+        }
+    }
+    mark_launcher_snapshot_dirty();
+}
+
+/// PID y ejecutable de Java de la instancia si está en ejecución.
+pub(crate) fn running_java_process(instance_root: &str) -> Option<(u32, String)> {
+    let registry = runtime_registry().lock().ok()?;
+    let state = registry.get(instance_root).filter(|state| state.running)?;
+    Some((state.pid?, state.java_path.clone()?))
+}
+
+/// Marca la sesión como terminada y devuelve su duración en milisegundos (tiempo de juego).
+pub fn register_runtime_exit(
+    instance_root: &str,
+    pid: u32,
+    exit_code: Option<i32>,
+    clock: &dyn Clock,
+) -> u64 {
+    record_runtime_exit(instance_root, pid, exit_code, VecDeque::new(), clock)
+}
+
+fn record_runtime_exit(
+    instance_root: &str,
+    pid: u32,
+    exit_code: Option<i32>,
+    stderr_tail: VecDeque<String>,
+    clock: &dyn Clock,
+) -> u64 {
+    let now_ms = clock.now_millis();
+    let Ok(mut registry) = runtime_registry().lock() else {
+        return 0;
+    };
+    let (started_at_ms, suspended_duration_ms) = registry
+        .get(instance_root)
+        .map(|state| (state.started_at_ms, state.suspended_duration_ms))
+        .unwrap_or((now_ms, 0));
+    registry.insert(
+        instance_root.to_string(),
+        RuntimeState {
+            pid: Some(pid),
+            running: false,
+            exit_code,
+            stderr_tail,
+            started_at_ms,
+            preparation: None,
+            java_path: None,
+            suspended_duration_ms,
+            closing: false,
+            folder_missing: false,
+        },
+    );
+    drop(registry);
+    mark_launcher_snapshot_dirty();
+    now_ms
+        .saturating_sub(started_at_ms)
+        .saturating_sub(suspended_duration_ms)
+}
+
+/// Suma a la sesión la parte de la suspensión `[since_ms, until_ms]` posterior a su inicio.
+fn add_suspension(state: &mut RuntimeState, since_ms: u64, until_ms: u64) {
+    let overlap = until_ms.saturating_sub(since_ms.max(state.started_at_ms));
+    state.suspended_duration_ms += overlap;
+}
+
+/// Descuenta una suspensión del equipo del tiempo de juego de las instancias en ejecución.
+pub(crate) fn record_runtime_suspension(since_ms: u64, until_ms: u64) {
+    if let Ok(mut registry) = runtime_registry().lock() {
+        for state in registry.values_mut().filter(|state| state.running) {
+            add_suspension(state, since_ms, until_ms);
         }
     }
 }
 
-pub fn register_runtime_exit(instance_root: &str, pid: u32, exit_code: Option<i32>) {
-    if let Ok(mut registry) = runtime_registry().lock() {
-        registry.insert(
-            instance_root.to_string(),
-            RuntimeState {
-                pid: Some(pid),
-                running: false,
-                exit_code,
-                stderr_tail: VecDeque::new(),
-                started_at: Instant::now(),
-            },
-        );
-    }
+/// Aplica `update` a la sesión en ejecución de la instancia y devuelve su PID.
+fn with_running_session(
+    instance_root: &str,
+    update: impl FnOnce(&mut RuntimeState),
+) -> Result<u32, String> {
+    let mut registry = runtime_registry()
+        .lock()
+        .map_err(|_| "No se pudo bloquear el registro de runtime.".to_string())?;
+    let Some(state) = registry.get_mut(instance_root) else {
+        return Err("No existe estado de ejecución para esta instancia.".to_string());
+    };
+    if !state.running {
+        return Err("La instancia no está en ejecución.".to_string());
+    }
+    let Some(pid) = state.pid else {
+        return Err("La instancia está iniciando y aún no tiene PID asignado.".to_string());
+    };
+    update(state);
+    drop(registry);
+    mark_launcher_snapshot_dirty();
+    Ok(pid)
+}
+
+/// Marca la sesión en ejecución como huérfana de carpeta. Devuelve `false` si no había una
+/// sesión activa para esa ruta.
+pub(crate) fn mark_runtime_folder_missing(instance_root: &str) -> bool {
+    let Ok(mut registry) = runtime_registry().lock() else {
+        return false;
+    };
+    let Some(state) = registry
+        .get_mut(instance_root)
+        .filter(|state| state.running)
+    else {
+        return false;
+    };
+    state.folder_missing = true;
+    drop(registry);
+    mark_launcher_snapshot_dirty();
+    true
+}
+
+/// Marca la sesión como "cerrando" (cierre cooperativo en curso) y devuelve su PID.
+pub(crate) fn begin_runtime_close(instance_root: &str) -> Result<u32, String> {
+    with_running_session(instance_root, |state| state.closing = true)
 }
 
-#[tauri::command]
-pub fn force_close_instance(instance_root: String) -> Result<String, String> {
-    let pid = {
-        let mut registry = runtime_registry()
-            .lock()
-            .map_err(|_| "No se pudo bloquear el registro de runtime.".to_string())?;
-        let Some(state) = registry.get_mut(&instance_root) else {
-            return Err("No existe estado de ejecución para esta instancia.".to_string());
-        };
-        if !state.running {
-            return Err("La instancia no está en ejecución.".to_string());
-        }
-        let Some(pid) = state.pid else {
-            return Err("La instancia está iniciando y aún no tiene PID asignado.".to_string());
-        };
+/// Si la sesión de `pid` sigue en ejecución; el monitor de salida la cierra al terminar.
+pub(crate) fn is_runtime_session_running(instance_root: &str, pid: u32) -> bool {
+    runtime_registry().lock().is_ok_and(|registry| {
+        registry
+            .get(instance_root)
+            .is_some_and(|state| state.running && state.pid == Some(pid))
+    })
+}
+
+/// Marca la sesión como terminada a la fuerza y mata el proceso con su árbol.
+pub(crate) fn force_terminate_runtime(instance_root: &str) -> Result<u32, String> {
+    let pid = with_running_session(instance_root, |state| {
         state.running = false;
+        state.closing = false;
         state.exit_code = Some(-9);
-        pid
-    };
-
+    })?;
     terminate_process(pid);
+    Ok(pid)
+}
+
+#[tauri::command]
+pub fn force_close_instance(app: AppHandle, instance_root: String) -> Result<String, String> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    let pid = force_terminate_runtime(instance_root.as_str())?;
     Ok(format!(
         "Se forzó el cierre completo del proceso (PID {pid})."
     ))
@@ -2063,26 +4528,36 @@ pub fn force_close_instance(instance_root: String) -> Result<String, String> {
 fn monitor_latest_log_for_auth(
     app: AppHandle,
     instance_root: String,
+    latest_log_path: PathBuf,
     expected_username: String,
     pid: u32,
     stop_signal: Arc<AtomicBool>,
 ) {
-    let latest_log_path = Path::new(&instance_root)
-        .join("minecraft")
-        .join("logs")
-        .join("latest.log");
-
-    let started = Instant::now();
-    while !stop_signal.load(Ordering::Relaxed) && started.elapsed() < Duration::from_secs(180) {
+    let clock = app_clock(&app).clock;
+    // El plazo no corre mientras el equipo está suspendido.
+    let started = clock.now_millis();
+    let deadline = SuspendAwareDeadline::start(
+        &current_power_state(started),
+        started,
+        Duration::from_secs(180),
+    );
+    let expired = || {
+        let now = clock.now_millis();
+        deadline.expired(&current_power_state(now), now)
+    };
+    while !stop_signal.load(Ordering::Relaxed) && !expired() {
         if let Ok(content) = fs::read_to_string(&latest_log_path) {
             if content.contains("Setting user: Demo") {
-                let _ = app.emit(
+                emit_journaled(
+                    &app,
+                    &instance_root,
                     "instance_runtime_output",
                     RuntimeOutputEvent {
                         instance_root: instance_root.clone(),
                         stream: "system".to_string(),
                         line: "ERROR AUTH: latest.log reportó 'Setting user: Demo'. Se aborta el proceso por autenticación inválida.".to_string(),
                         parsed: None,
+                        repeat_count: None,
                     },
                 );
                 terminate_process(pid);
@@ -2090,7 +4565,9 @@ fn monitor_latest_log_for_auth(
             }
 
             if content.contains(&expected_username) {
-                let _ = app.emit(
+                emit_journaled(
+                    &app,
+                    &instance_root,
                     "instance_runtime_output",
                     RuntimeOutputEvent {
                         instance_root: instance_root.clone(),
@@ -2099,6 +4576,7 @@ fn monitor_latest_log_for_auth(
                             "OK AUTH: latest.log contiene el username oficial validado ({expected_username})."
                         ),
                         parsed: None,
+                        repeat_count: None,
                     },
                 );
                 break;
@@ -2110,11 +4588,16 @@ fn monitor_latest_log_for_auth(
 }
 
 fn ensure_instance_embedded_java(
-    instance_path: &Path,
+    instance_root: &ValidatedInstanceRoot,
     metadata: &InstanceMetadata,
     logs: &mut Vec<String>,
 ) -> Result<String, String> {
-    let launcher_root = resolve_launcher_root_from_instance_path(instance_path)?;
+    let instance_path = instance_root.path();
+    let launcher_root = resolve_launcher_root_for_instance(
+        instance_path,
+        configured_launcher_root().as_deref(),
+        logs,
+    )?;
 
     let runtime = parse_runtime_from_metadata(metadata).ok_or_else(|| {
         format!(
@@ -2123,7 +4606,16 @@ fn ensure_instance_embedded_java(
         )
     })?;
 
-    let java_exec = ensure_embedded_java(launcher_root, runtime, logs)?;
+    // La instalación del runtime descarga cientos de MB: el watchdog debe poder cortarla.
+    let watchdog = current_watchdog();
+    watchdog.set_sub_operation(format!("instalando runtime Java {}", runtime.major()));
+    let java_exec = ensure_java_build_with(
+        &launcher_root,
+        runtime,
+        metadata.java_build_pin.as_deref(),
+        logs,
+        &|| watchdog.check(),
+    )?;
     logs.push(format!(
         "✔ runtime embebido garantizado para Java {}: {}",
         runtime.major(),
@@ -2131,16 +4623,33 @@ fn ensure_instance_embedded_java(
     ));
 
     if Path::new(&metadata.java_path) != java_exec {
-        persist_instance_java_path(instance_path, metadata, &java_exec, logs)?;
+        queue_instance_java_path(instance_root.as_str(), metadata, &java_exec, logs);
     }
 
     Ok(java_exec.display().to_string())
 }
 
-fn resolve_launcher_root_from_instance_path(instance_path: &Path) -> Result<&Path, String> {
+/// Raíz compartida (runtime/libraries/assets) para una instancia, aunque viva fuera de ella.
+pub(crate) fn resolve_launcher_root_for_instance(
+    instance_path: &Path,
+    configured_root: Option<&Path>,
+    logs: &mut Vec<String>,
+) -> Result<PathBuf, String> {
+    if let Some(root) = configured_root {
+        if !is_path_within_root(instance_path, root) {
+            logs.push(format!(
+                "🔹 Instancia externa a la raíz del launcher ({}); runtime, libraries y assets compartidos se resuelven desde {}",
+                instance_path.display(),
+                root.display()
+            ));
+        }
+        return Ok(root.to_path_buf());
+    }
+
     instance_path
         .parent()
         .and_then(Path::parent)
+        .map(Path::to_path_buf)
         .ok_or_else(|| {
             format!(
                 "No se pudo resolver launcher_root desde instancia {}",
@@ -2151,27 +4660,35 @@ fn resolve_launcher_root_from_instance_path(instance_path: &Path) -> Result<&Pat
 
 fn validate_official_minecraft_auth(
     auth_session: &LaunchAuthSession,
+    clock: &dyn Clock,
     logs: &mut Vec<String>,
-) -> Result<VerifiedLaunchAuth, String> {
+) -> Result<VerifiedLaunchAuth, LaunchError> {
     if !auth_session.premium_verified {
-        return Err("La cuenta no posee licencia oficial de Minecraft.".to_string());
+        return Err("La cuenta no posee licencia oficial de Minecraft."
+            .to_string()
+            .into());
     }
 
     if auth_session.minecraft_access_token.trim().is_empty() {
         return Err(
             "No hay access token de Minecraft válido; no se permite iniciar en modo Demo."
-                .to_string(),
+                .to_string()
+                .into(),
         );
     }
 
     if auth_session.profile_name.trim().is_empty() || auth_session.profile_id.trim().is_empty() {
         return Err(
             "No hay perfil oficial de Minecraft (name/uuid); no se permite iniciar en modo Demo."
-                .to_string(),
+                .to_string()
+                .into(),
         );
     }
 
-    let client = reqwest::blocking::Client::builder()
+    // Durante una caída de los servicios no se vuelve a esperar a los timeouts.
+    ensure_minecraft_services_available(clock)?;
+
+    let client = tls::with_custom_roots(reqwest::blocking::Client::builder())
         .timeout(Duration::from_secs(20))
         .build()
         .map_err(|err| {
@@ -2179,9 +4696,10 @@ fn validate_official_minecraft_auth(
         })?;
     let mut active_minecraft_token = auth_session.minecraft_access_token.clone();
     let mut active_minecraft_expires_at = auth_session.minecraft_access_token_expires_at;
+    let mut active_refresh_token = auth_session.microsoft_refresh_token.clone();
 
     let mut needs_refresh = false;
-    if let (Some(expires_at), Some(now)) = (active_minecraft_expires_at, now_unix_millis()) {
+    if let (Some(expires_at), Some(now)) = (active_minecraft_expires_at, Some(clock.now_millis())) {
         if expires_at <= now.saturating_add(60_000) {
             logs.push(
                 "⚠ access_token próximo a expirar; refrescando de forma preventiva (MSA→XBL→XSTS→Minecraft).".to_string(),
@@ -2192,20 +4710,21 @@ fn validate_official_minecraft_auth(
 
     logs.push("CHECK obligatorio: validando perfil oficial vía /minecraft/profile".to_string());
 
+    let profile_url = minecraft_profile_url();
     let mut profile_response = if needs_refresh {
         None
     } else {
-        Some(
+        let response = rate_limit::send_blocking(&profile_url, || {
             client
-                .get("https://api.minecraftservices.com/minecraft/profile")
+                .get(&profile_url)
                 .header(
                     "Authorization",
                     format!("Bearer {}", active_minecraft_token),
                 )
                 .header("Accept", "application/json")
-                .send()
-                .map_err(|err| format!("No se pudo consultar perfil de Minecraft: {err}"))?,
-        )
+        });
+        observe_minecraft_services(&response, clock);
+        Some(response.map_err(|err| err.with_context("No se pudo consultar perfil de Minecraft"))?)
     };
 
     if profile_response
@@ -2229,32 +4748,40 @@ fn validate_official_minecraft_auth(
             .map_err(|err| format!("No se pudo crear runtime para refresh de token: {err}"))?;
 
         let refreshed = runtime.block_on(async {
-            let client = reqwest::Client::new();
+            let client = tls::default_async_client();
             let ms = refresh_microsoft_access_token(&client, &refresh_token).await?;
             let xbox = authenticate_with_xbox_live(&client, &ms.access_token).await?;
             let xsts = authorize_xsts(&client, &xbox.token).await?;
             let mc = login_minecraft_with_xbox(&client, &xsts.uhs, &xsts.token).await?;
             let expires_at = mc.expires_in.and_then(|expires_in| {
-                now_unix_millis().map(|now| now.saturating_add(expires_in.saturating_mul(1000)))
+                Some(clock.now_millis())
+                    .map(|now| now.saturating_add(expires_in.saturating_mul(1000)))
             });
-            Ok::<(String, Option<u64>), String>((mc.access_token, expires_at))
+            Ok::<(String, Option<u64>, Option<String>), LaunchError>((
+                mc.access_token,
+                expires_at,
+                ms.refresh_token,
+            ))
         })?;
 
         active_minecraft_token = refreshed.0;
         active_minecraft_expires_at = refreshed.1;
-        profile_response = Some(
+        if refreshed.2.is_some() {
+            active_refresh_token = refreshed.2;
+        }
+        let response = rate_limit::send_blocking(&profile_url, || {
             client
-                .get("https://api.minecraftservices.com/minecraft/profile")
+                .get(&profile_url)
                 .header(
                     "Authorization",
                     format!("Bearer {}", active_minecraft_token),
                 )
                 .header("Accept", "application/json")
-                .send()
-                .map_err(|err| {
-                    format!("No se pudo consultar perfil de Minecraft tras refresh: {err}")
-                })?,
-        );
+        });
+        observe_minecraft_services(&response, clock);
+        profile_response = Some(response.map_err(|err| {
+            err.with_context("No se pudo consultar perfil de Minecraft tras refresh")
+        })?);
     }
 
     let profile_response = profile_response.ok_or_else(|| {
@@ -2270,22 +4797,78 @@ fn validate_official_minecraft_auth(
         let body = profile_response.text().unwrap_or_default();
         return Err(format!(
             "La API de perfil de Minecraft devolvió error HTTP: {profile_status}. Body completo: {body}. Lanzamiento bloqueado."
-        ));
+        ).into());
     }
 
     let profile = profile_response
         .json::<serde_json::Value>()
         .map_err(|err| format!("No se pudo leer perfil de Minecraft: {err}"))?;
 
+    let VerifiedProfile {
+        profile_id,
+        profile_name,
+        previous_profile_name,
+    } = verify_profile_matches_session(&profile, auth_session, logs)?;
+
+    logs.push("CHECK obligatorio: validando licencia vía /entitlements/mcstore".to_string());
+
+    let runtime = tokio::runtime::Runtime::new()
+        .map_err(|err| format!("No se pudo crear runtime para validar entitlements: {err}"))?;
+    let has_license = runtime.block_on(async {
+        has_minecraft_license(&tls::default_async_client(), &active_minecraft_token).await
+    })?;
+
+    if !has_license {
+        return Err(
+            "Cuenta sin licencia premium verificada. Lanzamiento bloqueado."
+                .to_string()
+                .into(),
+        );
+    }
+
+    logs.push("✔ Licencia oficial verificada en entitlements/mcstore (sin Demo).".to_string());
+    logs.push(format!(
+        "✔ Perfil oficial verificado: {} ({})",
+        profile_name, profile_id
+    ));
+
+    Ok(VerifiedLaunchAuth {
+        profile_id,
+        profile_name,
+        previous_profile_name,
+        minecraft_access_token: active_minecraft_token,
+        minecraft_access_token_expires_at: active_minecraft_expires_at,
+        microsoft_refresh_token: active_refresh_token,
+        premium_verified: true,
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct VerifiedProfile {
+    profile_id: String,
+    profile_name: String,
+    /// Nombre de la sesión si el jugador se lo cambió en Mojang después de iniciar sesión.
+    previous_profile_name: Option<String>,
+}
+
+/// Compara la respuesta de `/minecraft/profile` con la sesión. Solo un UUID distinto bloquea
+/// el lanzamiento: si cambió el nombre se usa el que devuelve la API.
+fn verify_profile_matches_session(
+    profile: &Value,
+    auth_session: &LaunchAuthSession,
+    logs: &mut Vec<String>,
+) -> Result<VerifiedProfile, String> {
     let profile_id = profile
         .get("id")
-        .and_then(serde_json::Value::as_str)
+        .and_then(Value::as_str)
         .unwrap_or_default()
+        .trim()
         .to_string();
     let profile_name = profile
         .get("name")
-        .and_then(serde_json::Value::as_str)
+        .and_then(Value::as_str)
         .unwrap_or_default()
+        .trim()
         .to_string();
 
     if profile_id.is_empty() || profile_name.is_empty() {
@@ -2301,34 +4884,22 @@ fn validate_official_minecraft_auth(
         );
     }
 
-    if profile_id != auth_session.profile_id || profile_name != auth_session.profile_name {
-        return Err("El perfil de Minecraft no coincide con la sesión actual; token inválido o vencido. Se bloquea para evitar modo Demo.".to_string());
-    }
-
-    logs.push("CHECK obligatorio: validando licencia vía /entitlements/mcstore".to_string());
-
-    let runtime = tokio::runtime::Runtime::new()
-        .map_err(|err| format!("No se pudo crear runtime para validar entitlements: {err}"))?;
-    let has_license = runtime.block_on(async {
-        has_minecraft_license(&reqwest::Client::new(), &active_minecraft_token).await
-    })?;
-
-    if !has_license {
-        return Err("Cuenta sin licencia premium verificada. Lanzamiento bloqueado.".to_string());
+    if !profile_id.eq_ignore_ascii_case(&sanitize_uuid(auth_session.profile_id.trim())) {
+        return Err("El perfil de Minecraft pertenece a otra cuenta (UUID distinto al de la sesión); token inválido o de otra cuenta. Se bloquea para evitar modo Demo.".to_string());
     }
 
-    logs.push("✔ Licencia oficial verificada en entitlements/mcstore (sin Demo).".to_string());
-    logs.push(format!(
-        "✔ Perfil oficial verificado: {} ({})",
-        profile_name, profile_id
-    ));
+    let session_name = auth_session.profile_name.trim();
+    let previous_profile_name = (profile_name != session_name).then(|| {
+        logs.push(format!(
+            "⚠ El jugador cambió su nombre de Minecraft: '{session_name}' -> '{profile_name}'. Se usa el nombre actual."
+        ));
+        session_name.to_string()
+    });
 
-    Ok(VerifiedLaunchAuth {
+    Ok(VerifiedProfile {
         profile_id,
         profile_name,
-        minecraft_access_token: active_minecraft_token,
-        minecraft_access_token_expires_at: active_minecraft_expires_at,
-        premium_verified: true,
+        previous_profile_name,
     })
 }
 
@@ -2397,11 +4968,25 @@ fn validate_merged_has_auth_args(merged: &Value) -> Result<(), String> {
     Ok(())
 }
 
-fn log_merged_json_summary(merged: &serde_json::Value, logs: &mut Vec<String>) {
+/// Datos clave del version.json ya mergeado (los mismos que se registran al lanzar).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergedJsonSummary {
+    pub main_class: Option<String>,
+    /// `modern`, `legacy` o `none`.
+    pub args_format: String,
+    pub game_args_count: usize,
+    pub jvm_args_count: usize,
+    pub libraries_count: usize,
+    pub asset_index: Option<String>,
+    pub has_auth_player_name: bool,
+}
+
+pub(crate) fn merged_json_summary(merged: &serde_json::Value) -> MergedJsonSummary {
     let main_class = merged
         .get("mainClass")
         .and_then(|v| v.as_str())
-        .unwrap_or("(ausente)");
+        .map(ToString::to_string);
 
     let has_modern_args = merged.get("arguments").is_some();
     let has_legacy_args = merged.get("minecraftArguments").is_some();
@@ -2420,13 +5005,13 @@ fn log_merged_json_summary(merged: &serde_json::Value, logs: &mut Vec<String>) {
         .map(|a| a.len())
         .unwrap_or(0);
 
-    let libs_count = merged
+    let libraries_count = merged
         .get("libraries")
         .and_then(|v| v.as_array())
         .map(|a| a.len())
         .unwrap_or(0);
 
-    let has_username = if has_modern_args {
+    let has_auth_player_name = if has_modern_args {
         merged
             .get("arguments")
             .and_then(|a| a.get("game"))
@@ -2451,40 +5036,79 @@ fn log_merged_json_summary(merged: &serde_json::Value, logs: &mut Vec<String>) {
         .get("assetIndex")
         .and_then(|v| v.get("id"))
         .and_then(|v| v.as_str())
-        .unwrap_or("(ausente)");
+        .map(ToString::to_string);
+
+    let args_format = if has_modern_args {
+        "modern"
+    } else if has_legacy_args {
+        "legacy"
+    } else {
+        "none"
+    };
+
+    MergedJsonSummary {
+        main_class,
+        args_format: args_format.to_string(),
+        game_args_count,
+        jvm_args_count,
+        libraries_count,
+        asset_index,
+        has_auth_player_name,
+    }
+}
+
+/// Registra el resumen y devuelve sus avisos (sin el de auth_player_name, que ya es fatal).
+fn log_merged_json_summary(merged: &serde_json::Value, logs: &mut Vec<String>) -> Vec<String> {
+    let summary = merged_json_summary(merged);
+    let mut warnings = Vec::new();
 
     logs.push("── Resumen version.json mergeado ──────────────".to_string());
-    logs.push(format!("  mainClass:          {}", main_class));
+    logs.push(format!(
+        "  mainClass:          {}",
+        summary.main_class.as_deref().unwrap_or("(ausente)")
+    ));
     logs.push(format!(
         "  formato args:       {}",
-        if has_modern_args {
-            "moderno (arguments)"
-        } else if has_legacy_args {
-            "legacy (minecraftArguments)"
-        } else {
-            "NINGUNO — ERROR"
+        match summary.args_format.as_str() {
+            "modern" => "moderno (arguments)",
+            "legacy" => "legacy (minecraftArguments)",
+            _ => "NINGUNO — ERROR",
         }
     ));
-    logs.push(format!("  game args count:    {}", game_args_count));
-    logs.push(format!("  jvm args count:     {}", jvm_args_count));
-    logs.push(format!("  libraries count:    {}", libs_count));
-    logs.push(format!("  assetIndex id:      {}", asset_index));
-    logs.push(format!("  tiene auth_player_name: {}", has_username));
+    logs.push(format!("  game args count:    {}", summary.game_args_count));
+    logs.push(format!("  jvm args count:     {}", summary.jvm_args_count));
+    logs.push(format!("  libraries count:    {}", summary.libraries_count));
+    logs.push(format!(
+        "  assetIndex id:      {}",
+        summary.asset_index.as_deref().unwrap_or("(ausente)")
+    ));
+    logs.push(format!(
+        "  tiene auth_player_name: {}",
+        summary.has_auth_player_name
+    ));
     logs.push("────────────────────────────────────────────────".to_string());
 
-    if !has_username {
+    if !summary.has_auth_player_name {
         logs.push(
             "  ⚠ ADVERTENCIA: auth_player_name no encontrado en game args tras el merge. El launch fallará."
                 .to_string(),
         );
     }
 
-    if game_args_count == 0 && !has_legacy_args {
+    if summary.game_args_count == 0 && merged.get("minecraftArguments").is_none() {
         logs.push(
             "  ⚠ ADVERTENCIA: game_args_count es 0 y no hay minecraftArguments. El version.json mergeado está vacío de argumentos de juego."
                 .to_string(),
         );
+        warnings.push(
+            "version.json mergeado sin argumentos de juego (game_args_count 0 y sin minecraftArguments)"
+                .to_string(),
+        );
     }
+    if summary.main_class.is_none() {
+        warnings.push("version.json mergeado sin mainClass".to_string());
+    }
+    warnings
 }
 
 #[derive(Debug, PartialEq, Clone, Copy)]
@@ -2554,7 +5178,10 @@ fn resolve_real_forge_library_dir(
     for line in args_file_raw.lines() {
         let line = line.trim();
 
-        if let Some(val) = line.strip_prefix("-DlibraryDirectory=") {
+        if let Some(val) = split_args_file_line(line)
+            .first()
+            .and_then(|arg| arg.strip_prefix("-DlibraryDirectory="))
+        {
             let p = PathBuf::from(val.trim());
             if p.is_dir() {
                 logs.push(format!(
@@ -2565,13 +5192,8 @@ fn resolve_real_forge_library_dir(
             }
         }
 
-        let separators = if cfg!(target_os = "windows") {
-            &[';'][..]
-        } else {
-            &[':'][..]
-        };
-        for segment in line.split(separators).flat_map(|s| s.split_whitespace()) {
-            let segment = segment.trim();
+        for segment in args_file_path_entries(line) {
+            let segment = segment.as_str();
             if segment.len() < 5 {
                 continue;
             }
@@ -2849,9 +5471,19 @@ fn load_forge_args_file(
     if !path.exists() {
         return Ok(None);
     }
+    if let Some(manifest) = read_loader_files_manifest(mc_root) {
+        let relative = format!("versions/{version_id}/{filename}");
+        if !manifest.contains(&relative) {
+            let warning = format!(
+                "⚠ {relative} no está entre los archivos que generó el instalador de {} {} ({}); puede ser de una instalación anterior.",
+                manifest.loader, manifest.loader_version, manifest.version_id
+            );
+            log::warn!("{warning}");
+            logs.push(warning);
+        }
+    }
 
-    let raw_content = fs::read_to_string(&path)
-        .map_err(|e| format!("No se pudo leer {}: {e}", path.display()))?;
+    let raw_content = read_text_repairing(&path, instance_owns_game_dir(mc_root))?;
 
     let real_lib_dir = resolve_real_forge_library_dir(mc_root, source_path, &raw_content, logs);
     let mut ctx_for_forge = launch_context.clone();
@@ -2876,18 +5508,13 @@ fn load_forge_args_file(
             continue;
         }
 
-        if line.starts_with("--") || line.starts_with('-') {
-            if let Some(space_pos) = line.find(' ') {
-                let flag = &line[..space_pos];
-                let value = line[space_pos + 1..].trim();
-                args.push(flag.to_string());
-                args.push(replace_launch_variables(value, &ctx_for_forge));
-            } else {
-                args.push(replace_launch_variables(line, &ctx_for_forge));
-            }
-        } else {
-            args.push(replace_launch_variables(line, &ctx_for_forge));
-        }
+        // Las comillas se quitan antes de sustituir: una ruta con espacios en
+        // ${library_directory} queda como un único argumento, sin comillas añadidas.
+        args.extend(
+            split_args_file_line(line)
+                .iter()
+                .map(|token| replace_launch_variables(token, &ctx_for_forge)),
+        );
     }
 
     if let Some(module_idx) = args.iter().position(|arg| arg == "--module-path") {
@@ -2947,6 +5574,19 @@ fn load_forge_args_file(
     }))
 }
 
+/// Verifica los argumentos de sesión con el mismo mensaje de error para instancias
+/// propias y redirigidas.
+pub(crate) fn ensure_online_launch_flags(
+    game_args: &[String],
+    launch_context: &LaunchContext,
+) -> Result<(), String> {
+    validate_required_online_launch_flags(game_args, launch_context).map_err(|err| {
+        format!(
+            "Argumentos críticos de sesión incompletos o inválidos. {err}. Lanzamiento bloqueado para evitar Demo."
+        )
+    })
+}
+
 fn validate_required_online_launch_flags(
     game_args: &[String],
     launch_context: &LaunchContext,
@@ -3043,6 +5683,47 @@ fn contains_classpath_switch(jvm_args: &[String]) -> bool {
         .any(|window| matches!(window, [flag, _value] if flag == "-cp" || flag == "-classpath"))
 }
 
+/// Construye el comando java final; con `classpath_via_env` el `-cp` va en `CLASSPATH`.
+fn build_java_command(
+    java_launch_path: &Path,
+    jvm_args: &[String],
+    main_class: &str,
+    game_args: &[String],
+    game_dir: &Path,
+    classpath_via_env: bool,
+) -> (Command, Vec<String>) {
+    let mut command = Command::new(java_launch_path);
+    let mut effective_jvm_args = jvm_args.to_vec();
+
+    if classpath_via_env {
+        if let Some(classpath) = strip_classpath_from_jvm_args(&mut effective_jvm_args) {
+            command.env("CLASSPATH", classpath);
+        }
+    }
+
+    command
+        .args(&effective_jvm_args)
+        .arg(main_class)
+        .args(game_args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .stdin(Stdio::null())
+        .current_dir(game_dir);
+
+    #[cfg(unix)]
+    {
+        command.process_group(0);
+    }
+
+    #[cfg(windows)]
+    {
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    prepare_java_path_environment(&mut command);
+    (command, effective_jvm_args)
+}
+
 fn strip_classpath_from_jvm_args(jvm_args: &mut Vec<String>) -> Option<String> {
     let mut index = 0usize;
     while index < jvm_args.len() {
@@ -3083,6 +5764,26 @@ struct MissingLibraryEntry {
 #[derive(Debug, Clone)]
 struct NativeJarEntry {
     path: String,
+    url: Option<String>,
+    sha1: Option<String>,
+}
+
+impl NativeJarEntry {
+    fn from_download(path: String, download: Option<&Value>) -> Self {
+        let field = |key: &str| {
+            download
+                .and_then(|v| v.get(key))
+                .and_then(Value::as_str)
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(ToOwned::to_owned)
+        };
+        Self {
+            url: field("url"),
+            sha1: field("sha1"),
+            path,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -3091,22 +5792,33 @@ struct ResolvedLibraries {
     missing_classpath_entries: Vec<MissingLibraryEntry>,
     native_jars: Vec<NativeJarEntry>,
     missing_native_entries: Vec<String>,
+    /// Artefactos (`lwjgl-platform`, `jinput-platform`...) cuyo jar de natives falta.
+    missing_native_libraries: Vec<String>,
+    /// Overrides de `library_overrides` aplicados (`librería: acción`).
+    applied_overrides: Vec<String>,
 }
 
-fn ensure_missing_libraries(entries: &[MissingLibraryEntry]) -> Result<usize, String> {
+fn ensure_missing_libraries(
+    entries: &[MissingLibraryEntry],
+    watchdog: &LaunchWatchdog,
+) -> Result<usize, String> {
     if entries.is_empty() {
         return Ok(0);
     }
 
-    let client = reqwest::blocking::Client::builder()
+    let client = tls::with_custom_roots(reqwest::blocking::Client::builder())
         .timeout(Duration::from_secs(45))
         .build()
         .map_err(|err| {
             format!("No se pudo crear cliente HTTP para descargar librerías faltantes: {err}")
         })?;
 
+    let _downloads = watchdog.pausable_downloads();
     let mut downloaded = 0_usize;
     for entry in entries {
+        // Punto de pausa: lo ya descargado queda en `libraries` y no se vuelve a verificar.
+        watchdog
+            .wait_while_paused(|| current_task().map_or(Ok(()), |task| task.check_cancelled()))?;
         let target = PathBuf::from(&entry.path);
         if let Some(parent) = target.parent() {
             fs::create_dir_all(parent).map_err(|err| {
@@ -3117,23 +5829,8 @@ fn ensure_missing_libraries(entries: &[MissingLibraryEntry]) -> Result<usize, St
             })?;
         }
 
-        let bytes = client
-            .get(&entry.url)
-            .send()
-            .and_then(|response| response.error_for_status())
-            .map_err(|err| {
-                format!(
-                    "No se pudo descargar librería faltante {}: {err}",
-                    entry.url
-                )
-            })?
-            .bytes()
-            .map_err(|err| {
-                format!(
-                    "No se pudo leer bytes de librería faltante {}: {err}",
-                    entry.url
-                )
-            })?;
+        let bytes = download_bytes_cancellable(&client, &entry.url, watchdog)
+            .map_err(|err| format!("Librería faltante {}: {err}", target.display()))?;
 
         let computed_sha1 = {
             let mut hasher = Sha1::new();
@@ -3166,7 +5863,10 @@ fn ensure_assets_ready(
     version_json: &Value,
     launcher_assets_root: &Path,
     logs: &mut Vec<String>,
-) -> Result<(String, PathBuf), String> {
+    watchdog: &LaunchWatchdog,
+    task: &TaskProbe,
+    unresolved: &mut Vec<String>,
+) -> Result<(String, PathBuf), LaunchError> {
     fs::create_dir_all(launcher_assets_root.join("indexes")).map_err(|err| {
         format!(
             "No se pudo crear assets/indexes global {}: {err}",
@@ -3203,7 +5903,7 @@ fn ensure_assets_ready(
             "⚠ Falta asset index '{}' → se descargará automáticamente desde {}",
             asset_index_id, asset_index_url
         ));
-        let payload = download_text_from_url(&asset_index_url)?;
+        let payload = download_text_from_url(&asset_index_url, watchdog)?;
         let _: Value = serde_json::from_str(&payload)
             .map_err(|err| format!("El asset index descargado es inválido: {err}"))?;
         fs::write(&index_path, payload.as_bytes()).map_err(|err| {
@@ -3221,11 +5921,20 @@ fn ensure_assets_ready(
             index_path.display()
         )
     })?;
-    let downloaded_assets = ensure_assets_objects_present(&index_json_value, launcher_assets_root)?;
+    let (downloaded_assets, served_by) = ensure_assets_objects_present(
+        &index_json_value,
+        launcher_assets_root,
+        watchdog,
+        task,
+        unresolved,
+    )?;
     logs.push(format!(
         "✔ assets listos: índice '{}' y {} objetos descargados/reparados.",
         asset_index_id, downloaded_assets
     ));
+    if let Some(served_by) = served_by {
+        logs.push(format!("🔹 objetos de assets servidos por: {served_by}"));
+    }
 
     Ok((asset_index_id, launcher_assets_root.to_path_buf()))
 }
@@ -3267,43 +5976,54 @@ fn is_valid_json_file(path: &Path) -> bool {
     serde_json::from_str::<Value>(&raw).is_ok()
 }
 
-fn download_text_from_url(url: &str) -> Result<String, String> {
-    let client = reqwest::blocking::Client::builder()
+fn download_text_from_url(url: &str, watchdog: &LaunchWatchdog) -> Result<String, String> {
+    let client = tls::with_custom_roots(reqwest::blocking::Client::builder())
         .timeout(Duration::from_secs(45))
         .build()
         .map_err(|err| format!("No se pudo crear cliente HTTP para assets: {err}"))?;
 
-    client
-        .get(url)
-        .send()
-        .and_then(|response| response.error_for_status())
-        .map_err(|err| format!("No se pudo descargar {url}: {err}"))?
-        .text()
-        .map_err(|err| format!("No se pudo leer respuesta de {url}: {err}"))
+    let bytes = download_bytes_cancellable(&client, url, watchdog)?;
+    String::from_utf8(bytes).map_err(|err| format!("Respuesta no UTF-8 de {url}: {err}"))
 }
 
 fn ensure_assets_objects_present(
     index_json: &Value,
     launcher_assets_root: &Path,
-) -> Result<usize, String> {
+    watchdog: &LaunchWatchdog,
+    task: &TaskProbe,
+    unresolved: &mut Vec<String>,
+) -> Result<(usize, Option<String>), LaunchError> {
     let objects = index_json
         .get("objects")
         .and_then(Value::as_object)
         .ok_or_else(|| "assets index no contiene 'objects'.".to_string())?;
+    let objects_root = launcher_assets_root.join("objects");
+    preflight_disk_space(
+        &objects_root,
+        missing_asset_bytes(index_json, &objects_root),
+    )?;
 
-    let client = reqwest::blocking::Client::builder()
+    let client = tls::with_custom_roots(reqwest::blocking::Client::builder())
         .timeout(Duration::from_secs(45))
         .build()
         .map_err(|err| format!("No se pudo crear cliente HTTP para objetos de assets: {err}"))?;
 
+    let _downloads = watchdog.pausable_downloads();
+    let mut sources = AssetSources::new(asset_base_urls());
     let mut downloaded = 0_usize;
-    for obj in objects.values() {
+    let total = objects.len() as u64;
+    for (position, (name, obj)) in objects.iter().enumerate() {
+        // Punto seguro de pausa y cancelación: cada objeto se escribe entero antes de pasar
+        // al siguiente.
+        watchdog.wait_while_paused(|| task.check_cancelled())?;
+        task.progress(position as u64, Some(total), "items");
         let hash = obj
             .get("hash")
             .and_then(Value::as_str)
             .unwrap_or_default()
             .trim();
         if hash.len() < 2 {
+            unresolved.push(name.clone());
             continue;
         }
         let size = obj.get("size").and_then(Value::as_u64).unwrap_or(0);
@@ -3327,29 +6047,50 @@ fn ensure_assets_objects_present(
             })?;
         }
 
-        let url = format!("{OFFICIAL_ASSETS_RESOURCES_URL}/{prefix}/{hash}");
-        let bytes = client
-            .get(&url)
-            .send()
-            .and_then(|response| response.error_for_status())
-            .map_err(|err| format!("No se pudo descargar asset {hash}: {err}"))?
-            .bytes()
-            .map_err(|err| format!("No se pudo leer bytes de asset {hash}: {err}"))?;
+        let (bytes, _) = sources.fetch(hash, |url| {
+            download_bytes_cancellable(&client, url, watchdog).map_err(|err| {
+                match watchdog.check() {
+                    Err(cancelled) => FetchError::Abort(cancelled),
+                    Ok(()) => FetchError::Retryable(err),
+                }
+            })
+        })?;
 
         fs::write(&target, &bytes)
             .map_err(|err| format!("No se pudo guardar asset {}: {err}", target.display()))?;
         downloaded += 1;
     }
+    task.progress(total, Some(total), "items");
 
-    Ok(downloaded)
+    Ok((downloaded, sources.summary()))
+}
+
+/// Avisa si la versión elegida no es la que generó la última instalación del loader.
+fn warn_if_outside_loader_manifest(
+    manifest: Option<&LoaderFilesManifest>,
+    metadata: &InstanceMetadata,
+    version_id: &str,
+) {
+    let Some(manifest) = manifest.filter(|manifest| manifest.matches(&metadata.loader, "")) else {
+        return;
+    };
+    if manifest.version_id != version_id {
+        log::warn!(
+            "⚠ La versión efectiva '{version_id}' no es la que generó el último instalador de {} ('{}'). Puede mezclar archivos de builds distintos; ejecuta clean_stale_loader_files o reinstala el loader.",
+            manifest.loader,
+            manifest.version_id
+        );
+    }
 }
 
-fn resolve_effective_version_id(
+pub(crate) fn resolve_effective_version_id(
     mc_root: &Path,
     metadata: &InstanceMetadata,
 ) -> Result<String, String> {
+    let manifest = read_loader_files_manifest(mc_root);
     let explicit_version_id = metadata.version_id.trim();
     if !explicit_version_id.is_empty() {
+        warn_if_outside_loader_manifest(manifest.as_ref(), metadata, explicit_version_id);
         return Ok(explicit_version_id.to_string());
     }
 
@@ -3362,6 +6103,16 @@ fn resolve_effective_version_id(
     }
 
     let versions_dir = mc_root.join("versions");
+    if let Some(manifest) = manifest
+        .as_ref()
+        .filter(|manifest| manifest.matches(&loader, &loader_version))
+    {
+        let id = &manifest.version_id;
+        if versions_dir.join(id).join(format!("{id}.json")).is_file() {
+            return Ok(id.clone());
+        }
+    }
+
     let mut candidates = Vec::new();
     if versions_dir.exists() {
         for entry in fs::read_dir(&versions_dir)
@@ -3423,20 +6174,52 @@ fn resolve_effective_version_id(
     }
 
     candidates.sort_by(|a, b| a.cmp(b));
-    Ok(candidates
+    if let Some((top_score, picked)) = candidates.last() {
+        let tied = candidates
+            .iter()
+            .filter(|(score, _)| score == top_score)
+            .map(|(_, id)| id.as_str())
+            .collect::<Vec<_>>();
+        if tied.len() > 1 {
+            log::info!(
+                "🔹 Varias versiones candidatas con la misma puntuación ({top_score}): {}. Se eligió '{picked}' (orden alfabético mayor). Fija version_id en la metadata o limpia versions/ para evitar ambigüedad.",
+                tied.join(", ")
+            );
+        }
+    }
+    let picked = candidates
         .pop()
         .map(|(_, id)| id)
-        .unwrap_or_else(|| base.to_string()))
+        .unwrap_or_else(|| base.to_string());
+    warn_if_outside_loader_manifest(manifest.as_ref(), metadata, &picked);
+    Ok(picked)
+}
+
+/// El game dir pertenece a una instancia propia (no a un atajo ni a otro launcher), así
+/// que se pueden reescribir sus archivos al normalizarlos.
+fn instance_owns_game_dir(mc_root: &Path) -> bool {
+    mc_root.parent().is_some_and(|instance_root| {
+        instance_root.join(".instance.json").is_file()
+            && !instance_root.join(".redirect.json").exists()
+    })
 }
 
 fn load_single_version_json(mc_root: &Path, version_id: &str) -> Result<serde_json::Value, String> {
+    read_single_version_json(mc_root, version_id, instance_owns_game_dir(mc_root))
+}
+
+fn read_single_version_json(
+    mc_root: &Path,
+    version_id: &str,
+    repair_encoding: bool,
+) -> Result<serde_json::Value, String> {
     let path = mc_root
         .join("versions")
         .join(version_id)
         .join(format!("{version_id}.json"));
 
-    let raw = std::fs::read_to_string(&path)
-        .map_err(|e| format!("No se pudo leer version.json '{}': {}", path.display(), e))?;
+    let raw = read_text_repairing(&path, repair_encoding)
+        .map_err(|e| format!("No se pudo leer version.json: {e}"))?;
 
     serde_json::from_str(&raw).map_err(|e| {
         format!(
@@ -3458,15 +6241,64 @@ fn extract_maven_key(lib: &Value) -> Option<String> {
     }
 }
 
+/// Origen de cada clave de primer nivel y librerías descartadas en un merge hijo/padre.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeProvenance {
+    /// `child`, `parent` o `merged` (listas combinadas de ambos).
+    pub key_origins: std::collections::BTreeMap<String, String>,
+    pub deduplicated_libraries: Vec<DeduplicatedLibrary>,
+}
+
+/// Librería descartada por compartir clave Maven con otra ya incluida.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeduplicatedLibrary {
+    pub key: String,
+    pub dropped_name: String,
+    pub dropped_from: String,
+    pub kept_name: String,
+    pub kept_from: String,
+}
+
+#[cfg(test)]
 fn merge_version_jsons(parent: serde_json::Value, child: serde_json::Value) -> serde_json::Value {
+    merge_version_jsons_with_provenance(parent, child).0
+}
+
+/// El merge del lanzamiento, registrando además de dónde sale cada parte del resultado.
+fn merge_version_jsons_with_provenance(
+    parent: serde_json::Value,
+    child: serde_json::Value,
+) -> (serde_json::Value, MergeProvenance) {
     use serde_json::{Map, Value};
 
     let mut result: Map<String, Value> = parent.as_object().cloned().unwrap_or_default();
+    let mut provenance = MergeProvenance {
+        key_origins: result
+            .keys()
+            .map(|key| (key.clone(), "parent".to_string()))
+            .collect(),
+        deduplicated_libraries: Vec::new(),
+    };
 
     let child_obj: Map<String, Value> = match child.as_object() {
         Some(o) => o.clone(),
-        None => return Value::Object(result),
+        None => return (Value::Object(result), provenance),
+    };
+
+    let library_name = |lib: &Value| {
+        lib.get("name")
+            .and_then(Value::as_str)
+            .unwrap_or("(sin nombre)")
+            .to_string()
     };
+    let mut mark = |key: &str, origin: &str| {
+        provenance
+            .key_origins
+            .insert(key.to_string(), origin.to_string());
+    };
+    let mut deduplicated = Vec::new();
 
     for (key, child_val) in child_obj {
         match key.as_str() {
@@ -3480,33 +6312,43 @@ fn merge_version_jsons(parent: serde_json::Value, child: serde_json::Value) -> s
                 let child_libs = child_val.as_array().cloned().unwrap_or_default();
 
                 let mut deduped = Vec::with_capacity(child_libs.len() + parent_libs.len());
-                let mut seen_keys = std::collections::HashSet::new();
+                let mut seen_keys = HashMap::new();
                 let mut fallback_idx = 0usize;
 
-                for lib in &child_libs {
-                    let key = extract_maven_key(lib).unwrap_or_else(|| {
-                        let key = format!("__unknown_{fallback_idx}");
-                        fallback_idx += 1;
-                        key
-                    });
-
-                    if seen_keys.insert(key) {
-                        deduped.push(lib.clone());
-                    }
-                }
-
-                for lib in &parent_libs {
-                    let key = extract_maven_key(lib).unwrap_or_else(|| {
-                        let key = format!("__unknown_{fallback_idx}");
-                        fallback_idx += 1;
-                        key
-                    });
-
-                    if seen_keys.insert(key) {
-                        deduped.push(lib.clone());
+                for (origin, libs) in [("child", &child_libs), ("parent", &parent_libs)] {
+                    for lib in libs {
+                        let key = extract_maven_key(lib).unwrap_or_else(|| {
+                            let key = format!("__unknown_{fallback_idx}");
+                            fallback_idx += 1;
+                            key
+                        });
+
+                        match seen_keys.get(&key) {
+                            None => {
+                                seen_keys.insert(key, (library_name(lib), origin));
+                                deduped.push(lib.clone());
+                            }
+                            Some((kept_name, kept_from)) => {
+                                deduplicated.push(DeduplicatedLibrary {
+                                    dropped_name: library_name(lib),
+                                    dropped_from: origin.to_string(),
+                                    kept_name: kept_name.clone(),
+                                    kept_from: kept_from.to_string(),
+                                    key,
+                                });
+                            }
+                        }
                     }
                 }
 
+                mark(
+                    "libraries",
+                    if parent_libs.is_empty() {
+                        "child"
+                    } else {
+                        "merged"
+                    },
+                );
                 result.insert("libraries".to_string(), Value::Array(deduped));
             }
             "arguments" => {
@@ -3559,10 +6401,19 @@ fn merge_version_jsons(parent: serde_json::Value, child: serde_json::Value) -> s
                     merged_arguments.insert("jvm".to_string(), Value::Array(merged_jvm));
                 }
 
+                mark(
+                    "arguments",
+                    if parent_arguments.is_empty() {
+                        "child"
+                    } else {
+                        "merged"
+                    },
+                );
                 result.insert("arguments".to_string(), Value::Object(merged_arguments));
             }
             "assetIndex" | "assets" | "downloads" => {
                 if !result.contains_key(&key) {
+                    mark(&key, "child");
                     result.insert(key, child_val);
                 }
             }
@@ -3578,56 +6429,89 @@ fn merge_version_jsons(parent: serde_json::Value, child: serde_json::Value) -> s
                     .unwrap_or(0);
 
                 if child_major > parent_major {
+                    mark("javaVersion", "child");
                     result.insert("javaVersion".to_string(), child_val);
                 }
             }
             "minecraftArguments" => {
+                mark(&key, "child");
                 result.insert(key, child_val);
             }
             _ => {
+                mark(&key, "child");
                 result.insert(key, child_val);
             }
         }
     }
 
-    Value::Object(result)
+    provenance.deduplicated_libraries = deduplicated;
+    (Value::Object(result), provenance)
+}
+
+pub fn load_merged_version_json(
+    mc_root: &Path,
+    version_id: &str,
+) -> Result<serde_json::Value, String> {
+    load_version_json_chain(mc_root, version_id, instance_owns_game_dir(mc_root))
+        .map(|(merged, _)| merged)
+}
+
+/// Como `load_merged_version_json`, de solo lectura y con el origen de cada parte del merge.
+pub(crate) fn inspect_merged_version_json(
+    mc_root: &Path,
+    version_id: &str,
+) -> Result<(serde_json::Value, Option<MergeProvenance>), String> {
+    load_version_json_chain(mc_root, version_id, false)
 }
 
-pub fn load_merged_version_json(
+fn load_version_json_chain(
     mc_root: &Path,
     version_id: &str,
-) -> Result<serde_json::Value, String> {
-    let child = load_single_version_json(mc_root, version_id)?;
+    repair_encoding: bool,
+) -> Result<(serde_json::Value, Option<MergeProvenance>), String> {
+    let child = read_single_version_json(mc_root, version_id, repair_encoding)?;
 
     let parent_id = match child.get("inheritsFrom").and_then(|v| v.as_str()) {
         Some(id) => id.to_string(),
         None => {
-            return Ok(child);
+            return Ok((child, None));
         }
     };
 
-    let parent = load_merged_version_json(mc_root, &parent_id).map_err(|e| {
-        format!(
-            "No se pudo cargar parent '{}' requerido por '{}': {}",
-            parent_id, version_id, e
-        )
-    })?;
+    let (parent, _) =
+        load_version_json_chain(mc_root, &parent_id, repair_encoding).map_err(|e| {
+            format!(
+                "No se pudo cargar parent '{}' requerido por '{}': {}",
+                parent_id, version_id, e
+            )
+        })?;
 
-    Ok(merge_version_jsons(parent, child))
+    let (merged, provenance) = merge_version_jsons_with_provenance(parent, child);
+    Ok((merged, Some(provenance)))
 }
 
-fn ensure_main_class_present_in_jar(jar_path: &Path, main_class: &str) -> Result<(), String> {
-    let file = fs::File::open(jar_path)
-        .map_err(|err| format!("No se pudo abrir jar {}: {err}", jar_path.display()))?;
-    let mut archive = ZipArchive::new(file)
-        .map_err(|err| format!("Jar inválido {}: {err}", jar_path.display()))?;
+/// Traduce la inspección del jar a los mismos errores que daba abrirlo por separado.
+fn ensure_main_class_present_in_jar(
+    jar_path: &Path,
+    check: Option<&JarCheck>,
+    main_class: &str,
+) -> Result<(), String> {
     let class_entry = format!("{}.class", main_class.replace('.', "/"));
-    archive.by_name(&class_entry).map(|_| ()).map_err(|_| {
-        format!(
+    let check = check
+        .cloned()
+        .unwrap_or_else(|| inspect_jar(jar_path, Some(&class_entry), &JarOpenStats::default()));
+    match check {
+        JarCheck::Unreadable(err) => Err(format!(
+            "No se pudo abrir jar {}: {err}",
+            jar_path.display()
+        )),
+        JarCheck::InvalidZip(err) => Err(format!("Jar inválido {}: {err}", jar_path.display())),
+        check if check.has_main_class() => Ok(()),
+        _ => Err(format!(
             "La clase principal {main_class} no existe en {}",
             jar_path.display()
-        )
-    })
+        )),
+    }
 }
 
 /// Recursively scans `dir` for any `.jar` file whose path (lowercased) contains `keyword`.
@@ -3726,7 +6610,7 @@ fn forge_inject_system_properties(
     }
 }
 
-fn build_maven_library_path(libraries_root: &Path, library: &Value) -> Option<String> {
+pub(crate) fn build_maven_library_path(libraries_root: &Path, library: &Value) -> Option<String> {
     let name = library.get("name")?.as_str()?;
     let mut parts = name.split(':');
     let group = parts.next()?;
@@ -3762,15 +6646,19 @@ fn build_maven_library_path(libraries_root: &Path, library: &Value) -> Option<St
     )
 }
 
+/// Evalúa las reglas y aplica los `overrides` de librerías de la instancia.
 fn resolve_libraries(
     libraries_root: &Path,
     version_json: &Value,
     rule_context: &RuleContext,
+    overrides: &[LibraryOverride],
 ) -> ResolvedLibraries {
+    let mut applied_overrides = Vec::new();
     let mut classpath_entries = Vec::new();
     let mut missing_classpath_entries = Vec::new();
     let mut native_jars = Vec::new();
     let mut missing_native_entries = Vec::new();
+    let mut missing_native_libraries = Vec::new();
 
     let os_key = if cfg!(target_os = "windows") {
         "windows"
@@ -3795,6 +6683,36 @@ fn resolve_libraries(
             continue;
         }
 
+        let lib_name = lib
+            .get("name")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string();
+        let mut overridden = false;
+        let lib = match find_library_override(overrides, &lib_name) {
+            Some(rule) => match &rule.action {
+                LibraryOverrideAction::Exclude => {
+                    log::info!("🔹 Librería excluida por override: {lib_name}");
+                    applied_overrides.push(format!("{lib_name}: {}", rule.describe()));
+                    continue;
+                }
+                LibraryOverrideAction::ReplaceVersion(version) => {
+                    match replace_library_version(&lib, version) {
+                        Some(replaced) => {
+                            log::info!(
+                                "🔹 Librería {lib_name} reemplazada por la versión {version}"
+                            );
+                            applied_overrides.push(format!("{lib_name}: {}", rule.describe()));
+                            overridden = true;
+                            replaced
+                        }
+                        None => lib,
+                    }
+                }
+            },
+            None => lib,
+        };
+
         let artifact_path = lib
             .get("downloads")
             .and_then(|v| v.get("artifact"))
@@ -3817,7 +6735,8 @@ fn resolve_libraries(
                     || (is_native_jar_path(&path) && should_extract_for_platform(&filename));
 
                 if needs_extraction {
-                    native_jars.push(NativeJarEntry { path });
+                    let artifact = lib.get("downloads").and_then(|v| v.get("artifact"));
+                    native_jars.push(NativeJarEntry::from_download(path, artifact));
                 }
             } else {
                 let artifact = lib.get("downloads").and_then(|v| v.get("artifact"));
@@ -3832,8 +6751,12 @@ fn resolve_libraries(
                     .unwrap_or_default()
                     .to_string();
 
-                if !url.is_empty() && !sha1.is_empty() {
+                // Una versión reemplazada no tiene SHA1 publicado; se descarga sin verificar.
+                if !url.is_empty() && (!sha1.is_empty() || overridden) {
                     missing_classpath_entries.push(MissingLibraryEntry { path, url, sha1 });
+                } else if lib.get("natives").is_some() && artifact.is_none() {
+                    // Librería solo de natives (`lwjgl-platform`): no tiene jar principal y
+                    // el clasificador se comprueba más abajo.
                 } else {
                     missing_native_entries.push(format!(
                         "metadata incompleta para descargar librería faltante: {}",
@@ -3850,14 +6773,21 @@ fn resolve_libraries(
 
         if let Some(classifier) = native_classifier {
             let native_key = classifier.replace("${arch}", std::env::consts::ARCH);
-            let native_path = lib
+            let native_download = lib
                 .get("downloads")
                 .and_then(|v| v.get("classifiers"))
-                .and_then(|v| v.get(&native_key))
+                .and_then(|v| v.get(&native_key));
+            let native_path = native_download
                 .and_then(|v| v.get("path"))
                 .and_then(Value::as_str)
                 .map(|p| libraries_root.join(p).display().to_string());
 
+            if !native_path
+                .as_deref()
+                .is_some_and(|path| Path::new(path).exists())
+            {
+                missing_native_libraries.extend(library_artifact_name(&lib));
+            }
             match native_path {
                 Some(path) if Path::new(&path).exists() => {
                     classpath_entries.push(path.clone());
@@ -3867,7 +6797,7 @@ fn resolve_libraries(
                         .unwrap_or("")
                         .to_string();
                     if should_extract_for_platform(&filename) {
-                        native_jars.push(NativeJarEntry { path });
+                        native_jars.push(NativeJarEntry::from_download(path, native_download));
                     }
                 }
                 Some(path) => missing_native_entries.push(path),
@@ -3896,7 +6826,122 @@ fn resolve_libraries(
         missing_classpath_entries,
         native_jars,
         missing_native_entries,
+        missing_native_libraries,
+        applied_overrides,
+    }
+}
+
+/// Jars a validar como zip y jars donde buscar la mainClass: en vanilla solo el client.jar,
+/// con loader todo el classpath de librerías.
+fn launch_jar_sets(
+    loader: &str,
+    resolved_libraries: &ResolvedLibraries,
+    client_jar: &Path,
+) -> (Vec<PathBuf>, Vec<PathBuf>) {
+    let mut classpath_entries = resolved_libraries.classpath_entries.clone();
+    classpath_entries.push(client_jar.display().to_string());
+    let jars_to_validate =
+        jars_to_validate_as_zip(&classpath_entries, &resolved_libraries.native_jars);
+    let loader = loader.trim();
+    let main_class_search = if loader.is_empty() || loader.eq_ignore_ascii_case("vanilla") {
+        vec![client_jar.to_path_buf()]
+    } else {
+        resolved_libraries
+            .classpath_entries
+            .iter()
+            .map(PathBuf::from)
+            .collect()
+    };
+    (jars_to_validate, main_class_search)
+}
+
+/// Jars del classpath que se validan como zip: todos menos los de natives, que se validan
+/// (y reparan si es posible) dentro de `extract_natives`.
+fn jars_to_validate_as_zip(
+    classpath_entries: &[String],
+    native_jars: &[NativeJarEntry],
+) -> Vec<PathBuf> {
+    classpath_entries
+        .iter()
+        .map(PathBuf::from)
+        .filter(|jar| {
+            !native_jars
+                .iter()
+                .any(|native| Path::new(&native.path) == jar.as_path())
+        })
+        .collect()
+}
+
+/// Valida los jars, extrae los natives y devuelve las entradas definitivas del classpath.
+fn finalize_classpath_and_natives(
+    classpath_entries: &[String],
+    native_jars: &[NativeJarEntry],
+    natives_dir: &Path,
+    inspection: Option<&JarInspection>,
+    logs: &mut Vec<String>,
+) -> Result<Vec<String>, String> {
+    let jars_to_validate = jars_to_validate_as_zip(classpath_entries, native_jars);
+    let mut not_inspected = Vec::new();
+    for jar in &jars_to_validate {
+        match inspection.and_then(|inspection| inspection.check(jar)) {
+            Some(check) => check.clone().into_zip_result(jar)?,
+            None => not_inspected.push(jar.clone()),
+        }
+    }
+    validate_jars_as_zip(&not_inspected)?;
+    logs.push(format!(
+        "✔ jars validados como zip: {}",
+        jars_to_validate.len()
+    ));
+
+    logs.push(format!("native_jars detectados: {}", native_jars.len()));
+    for native in native_jars {
+        let file_name = Path::new(&native.path)
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("unknown");
+        logs.push(format!("  - {file_name}"));
+    }
+
+    prepare_natives_dir(natives_dir)?;
+    let skipped_native_jars = extract_natives(native_jars, natives_dir, logs)?;
+    log_natives_dir_contents(natives_dir, logs);
+    logs.push(format!(
+        "✔ natives extraídos: {} archivos fuente en {}",
+        native_jars.len(),
+        natives_dir.display()
+    ));
+
+    let mut final_entries = classpath_entries.to_vec();
+    final_entries.retain(|path| !skipped_native_jars.contains(path));
+    verify_no_duplicate_classpath_entries(&final_entries, logs)?;
+    Ok(final_entries)
+}
+
+/// Variante para instancias redirigidas: natives del launcher de origen, extraídos aparte.
+pub(crate) fn finalize_redirect_classpath(
+    classpath_entries: &[String],
+    libraries_dirs: &[PathBuf],
+    version_json: &Value,
+    natives_dir: &Path,
+    logs: &mut Vec<String>,
+) -> Result<Vec<String>, String> {
+    let rule_context = RuleContext::current();
+    let mut native_jars: Vec<NativeJarEntry> = Vec::new();
+    for libraries_dir in libraries_dirs {
+        for native in resolve_libraries(libraries_dir, version_json, &rule_context, &[]).native_jars
+        {
+            let file_name = Path::new(&native.path).file_name().map(ToOwned::to_owned);
+            if native_jars
+                .iter()
+                .any(|known| Path::new(&known.path).file_name().map(ToOwned::to_owned) == file_name)
+            {
+                continue;
+            }
+            native_jars.push(native);
+        }
     }
+    finalize_classpath_and_natives(classpath_entries, &native_jars, natives_dir, None, logs)
 }
 
 fn verify_no_duplicate_classpath_entries(
@@ -3942,23 +6987,555 @@ fn verify_no_duplicate_classpath_entries(
         unique_dupes.insert(filename);
     }
 
-    Err(format!(
-        "Classpath contiene {} entradas duplicadas que causarán \\n         'Duplicate key' en BootstrapLauncher de NeoForge/Forge.\n\n\
-         JARs duplicados: {}\n\n\
-         Causa: merge_version_jsons() no deduplicó libraries correctamente.",
-        duplicates.len(),
-        unique_dupes.into_iter().collect::<Vec<_>>().join(", ")
-    ))
-}
+    Err(format!(
+        "Classpath contiene {} entradas duplicadas que causarán \\n         'Duplicate key' en BootstrapLauncher de NeoForge/Forge.\n\n\
+         JARs duplicados: {}\n\n\
+         Causa: merge_version_jsons() no deduplicó libraries correctamente.",
+        duplicates.len(),
+        unique_dupes.into_iter().collect::<Vec<_>>().join(", ")
+    ))
+}
+
+/// Valida los jars con el pool de inspección y devuelve el primer error en el orden dado.
+fn validate_jars_as_zip(jars: &[PathBuf]) -> Result<(), String> {
+    let stats = JarOpenStats::default();
+    for (jar, check) in jars.iter().zip(inspect_jars_pooled(jars, &stats)) {
+        check.into_zip_result(jar)?;
+    }
+    Ok(())
+}
+
+/// Hilos que inspeccionan jars en paralelo; también es el tope de jars abiertos a la vez.
+const JAR_INSPECTION_WORKERS: usize = 4;
+/// Esperas antes de cada reintento tras una violación de uso compartido (~500 ms en total
+/// con el jitter).
+const SHARING_VIOLATION_BACKOFF_MS: [u64; 3] = [50, 100, 200];
+const SHARING_VIOLATION_JITTER_MS: u32 = 40;
+
+/// Contadores compartidos por los hilos de inspección.
+#[derive(Debug, Default)]
+struct JarOpenStats {
+    retries: AtomicUsize,
+    open_handles: AtomicUsize,
+    peak_open_handles: AtomicUsize,
+}
+
+impl JarOpenStats {
+    fn track_open(&self) -> OpenJarHandle<'_> {
+        let open = self.open_handles.fetch_add(1, Ordering::SeqCst) + 1;
+        self.peak_open_handles.fetch_max(open, Ordering::SeqCst);
+        OpenJarHandle { stats: self }
+    }
+}
+
+struct OpenJarHandle<'a> {
+    stats: &'a JarOpenStats,
+}
+
+impl Drop for OpenJarHandle<'_> {
+    fn drop(&mut self) {
+        self.stats.open_handles.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// `ERROR_SHARING_VIOLATION` (32) y `ERROR_LOCK_VIOLATION` (33): el antivirus tiene el jar
+/// abierto mientras lo escanea y suele soltarlo enseguida.
+pub(crate) fn is_sharing_violation(err: &std::io::Error) -> bool {
+    cfg!(windows) && matches!(err.raw_os_error(), Some(32 | 33))
+}
+
+/// Reintenta `open` con backoff y jitter mientras falle por un error transitorio; el
+/// último error se devuelve tal cual.
+pub(crate) fn retry_transient_open<T>(
+    retries: &AtomicUsize,
+    is_transient: impl Fn(&std::io::Error) -> bool,
+    mut open: impl FnMut() -> std::io::Result<T>,
+) -> std::io::Result<T> {
+    let mut attempt = 0;
+    loop {
+        match open() {
+            Err(err) if attempt < SHARING_VIOLATION_BACKOFF_MS.len() && is_transient(&err) => {
+                let jitter = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|now| now.subsec_nanos() % SHARING_VIOLATION_JITTER_MS)
+                    .unwrap_or(0);
+                thread::sleep(Duration::from_millis(
+                    SHARING_VIOLATION_BACKOFF_MS[attempt] + u64::from(jitter),
+                ));
+                retries.fetch_add(1, Ordering::SeqCst);
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Inspecciona `jars` (solo como zip) con `JAR_INSPECTION_WORKERS` hilos y devuelve los
+/// resultados en el mismo orden.
+fn inspect_jars_pooled(jars: &[PathBuf], stats: &JarOpenStats) -> Vec<JarCheck> {
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new(vec![None; jars.len()]);
+    thread::scope(|scope| {
+        for _ in 0..JAR_INSPECTION_WORKERS.min(jars.len()) {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::SeqCst);
+                let Some(jar) = jars.get(index) else {
+                    break;
+                };
+                let check = inspect_jar(jar, None, stats);
+                results
+                    .lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner())[index] = Some(check);
+            });
+        }
+    });
+    results
+        .into_inner()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .into_iter()
+        .map(|check| {
+            check.unwrap_or_else(|| JarCheck::Unreadable("no se llegó a inspeccionar".to_string()))
+        })
+        .collect()
+}
+
+/// Resultado de abrir un jar una sola vez durante la preparación del lanzamiento.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum JarCheck {
+    /// No se pudo abrir el archivo (error de E/S).
+    Unreadable(String),
+    /// El archivo existe pero no es un zip válido.
+    InvalidZip(String),
+    Valid {
+        /// `None` si no se buscó la mainClass en este jar.
+        has_main_class: Option<bool>,
+        /// Contiene bibliotecas nativas (.dll/.so/.dylib/.jnilib) en la raíz.
+        has_natives: bool,
+    },
+}
+
+impl JarCheck {
+    fn into_zip_result(self, jar: &Path) -> Result<(), String> {
+        match self {
+            JarCheck::Unreadable(err) => {
+                Err(format!("No se pudo abrir jar {}: {err}", jar.display()))
+            }
+            JarCheck::InvalidZip(err) => {
+                Err(format!("Jar inválido/corrupto {}: {err}", jar.display()))
+            }
+            JarCheck::Valid { .. } => Ok(()),
+        }
+    }
+
+    fn has_main_class(&self) -> bool {
+        matches!(
+            self,
+            JarCheck::Valid {
+                has_main_class: Some(true),
+                ..
+            }
+        )
+    }
+}
+
+/// Abre el jar una vez y responde a la vez si es un zip válido, si contiene la clase
+/// indicada y si trae nativos. El archivo se cierra al volver.
+fn inspect_jar(jar: &Path, class_entry: Option<&str>, stats: &JarOpenStats) -> JarCheck {
+    let file =
+        match retry_transient_open(&stats.retries, is_sharing_violation, || fs::File::open(jar)) {
+            Ok(file) => file,
+            Err(err) => return JarCheck::Unreadable(err.to_string()),
+        };
+    let _handle = stats.track_open();
+    let archive = match ZipArchive::new(file) {
+        Ok(archive) => archive,
+        Err(err) => return JarCheck::InvalidZip(err.to_string()),
+    };
+    let has_natives = archive.file_names().any(|name| {
+        let lower = name.to_ascii_lowercase();
+        !lower.contains('/')
+            && [".dll", ".so", ".dylib", ".jnilib"]
+                .iter()
+                .any(|ext| lower.ends_with(ext))
+    });
+    JarCheck::Valid {
+        has_main_class: class_entry.map(|entry| archive.index_for_name(entry).is_some()),
+        has_natives,
+    }
+}
+
+/// Artefactos donde suele vivir la mainClass de cada loader.
+const MAIN_CLASS_JAR_HINTS: [(&str, &str); 8] = [
+    ("org.quiltmc.loader", "quilt-loader"),
+    ("net.fabricmc.loader", "fabric-loader"),
+    ("cpw.mods.bootstraplauncher", "bootstraplauncher"),
+    ("net.neoforged.fancymodloader", "fancymodloader"),
+    ("net.neoforged", "neoforge"),
+    ("net.minecraftforge.bootstrap", "forge-bootstrap"),
+    ("cpw.mods.modlauncher", "modlauncher"),
+    ("net.minecraft.launchwrapper", "launchwrapper"),
+];
+
+/// Probabilidad relativa de que `jar` contenga `main_class`.
+fn main_class_jar_score(jar: &Path, main_class: &str) -> u32 {
+    let path = jar
+        .to_string_lossy()
+        .replace('\\', "/")
+        .to_ascii_lowercase();
+    let file_name = path.rsplit('/').next().unwrap_or_default();
+    let main_class = main_class.to_ascii_lowercase();
+    let hinted = MAIN_CLASS_JAR_HINTS
+        .iter()
+        .find(|(package, _)| main_class.starts_with(package))
+        .is_some_and(|(_, artifact)| file_name.starts_with(artifact));
+    let segments = main_class.split('.').collect::<Vec<_>>();
+    let package_depth = (1..segments.len().min(4))
+        .rev()
+        .find(|depth| path.contains(&format!("/{}/", segments[..*depth].join("/"))))
+        .unwrap_or(0) as u32;
+    u32::from(hinted) * 100 + package_depth
+}
+
+/// Pasada única sobre los jars del lanzamiento.
+#[derive(Debug, Default)]
+pub(crate) struct JarInspection {
+    checks: HashMap<PathBuf, JarCheck>,
+    main_class_jar: Option<PathBuf>,
+    archives_opened: usize,
+    main_class_probes: usize,
+    /// Reintentos por violaciones de uso compartido (antivirus).
+    open_retries: usize,
+    peak_open_handles: usize,
+    elapsed_ms: u64,
+}
+
+impl JarInspection {
+    fn check(&self, jar: &Path) -> Option<&JarCheck> {
+        self.checks.get(jar)
+    }
+
+    fn jars_with_natives(&self) -> usize {
+        self.checks
+            .values()
+            .filter(|check| {
+                matches!(
+                    check,
+                    JarCheck::Valid {
+                        has_natives: true,
+                        ..
+                    }
+                )
+            })
+            .count()
+    }
+}
+
+/// Abre cada jar una sola vez: busca la mainClass en los candidatos y valida el resto como
+/// zip en paralelo.
+fn inspect_launch_jars(
+    validate: &[PathBuf],
+    main_class_search: &[PathBuf],
+    main_class: &str,
+) -> JarInspection {
+    let started = Instant::now();
+    let class_entry = format!("{}.class", main_class.replace('.', "/"));
+    let mut search = main_class_search.to_vec();
+    search.sort_by_key(|jar| std::cmp::Reverse(main_class_jar_score(jar, main_class)));
+
+    let stats = JarOpenStats::default();
+    let mut inspection = JarInspection::default();
+    for jar in &search {
+        if inspection.main_class_jar.is_some() {
+            break;
+        }
+        if inspection.checks.contains_key(jar) {
+            continue;
+        }
+        let check = inspect_jar(jar, Some(class_entry.as_str()), &stats);
+        inspection.main_class_probes += 1;
+        if check.has_main_class() {
+            inspection.main_class_jar = Some(jar.clone());
+        }
+        inspection.checks.insert(jar.clone(), check);
+    }
+
+    let mut remaining = Vec::new();
+    for jar in search.iter().chain(validate.iter()) {
+        if !inspection.checks.contains_key(jar) && !remaining.contains(jar) {
+            remaining.push(jar.clone());
+        }
+    }
+    let checks = inspect_jars_pooled(&remaining, &stats);
+    inspection.checks.extend(remaining.into_iter().zip(checks));
+
+    inspection.archives_opened = inspection.checks.len();
+    inspection.open_retries = stats.retries.load(Ordering::SeqCst);
+    inspection.peak_open_handles = stats.peak_open_handles.load(Ordering::SeqCst);
+    inspection.elapsed_ms = started.elapsed().as_millis() as u64;
+    inspection
+}
+
+/// Jar con el SHA1 y la URL que publica Mojang en el version.json.
+#[derive(Debug, Clone)]
+struct PublishedJar {
+    path: PathBuf,
+    url: Option<String>,
+    sha1: Option<String>,
+}
+
+impl PublishedJar {
+    fn from_download(path: PathBuf, download: Option<&Value>) -> Self {
+        let native = NativeJarEntry::from_download(String::new(), download);
+        Self {
+            path,
+            url: native.url,
+            sha1: native.sha1,
+        }
+    }
+}
+
+/// Jars del version.json efectivo (client jar vanilla, artifacts y natives de la plataforma)
+/// junto con su SHA1 publicado.
+fn collect_published_jars(
+    libraries_root: &Path,
+    mc_root: &Path,
+    minecraft_version: &str,
+    version_json: &Value,
+) -> Vec<PublishedJar> {
+    let mut jars = Vec::new();
+    if let Some(client) = version_json.get("downloads").and_then(|v| v.get("client")) {
+        let client_jar = mc_root
+            .join("versions")
+            .join(minecraft_version)
+            .join(format!("{minecraft_version}.jar"));
+        jars.push(PublishedJar::from_download(client_jar, Some(client)));
+    }
+
+    let os_key = if cfg!(target_os = "windows") {
+        "windows"
+    } else if cfg!(target_os = "linux") {
+        "linux"
+    } else {
+        "osx"
+    };
+    let rule_context = RuleContext::current();
+    for lib in version_json
+        .get("libraries")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        let rules = lib
+            .get("rules")
+            .and_then(Value::as_array)
+            .cloned()
+            .unwrap_or_default();
+        if !crate::domain::minecraft::rule_engine::evaluate_rules(&rules, &rule_context) {
+            continue;
+        }
+        let downloads = lib.get("downloads");
+        if let Some(artifact) = downloads.and_then(|v| v.get("artifact")) {
+            if let Some(path) = artifact.get("path").and_then(Value::as_str) {
+                jars.push(PublishedJar::from_download(
+                    libraries_root.join(path),
+                    Some(artifact),
+                ));
+            }
+        }
+        let native_key = lib
+            .get("natives")
+            .and_then(|v| v.get(os_key))
+            .and_then(Value::as_str)
+            .map(|classifier| classifier.replace("${arch}", std::env::consts::ARCH));
+        if let Some(native) = native_key.and_then(|key| {
+            downloads
+                .and_then(|v| v.get("classifiers"))
+                .and_then(|v| v.get(&key))
+        }) {
+            if let Some(path) = native.get("path").and_then(Value::as_str) {
+                jars.push(PublishedJar::from_download(
+                    libraries_root.join(path),
+                    Some(native),
+                ));
+            }
+        }
+    }
+
+    let mut seen = std::collections::HashSet::new();
+    jars.retain(|jar| seen.insert(jar.path.clone()));
+    jars
+}
+
+/// Resultado de la verificación completa por SHA1 que ejecuta la reparación.
+#[derive(Debug, Default)]
+pub(crate) struct JarVerificationReport {
+    pub verified: usize,
+    pub quarantined: Vec<String>,
+    pub redownloaded: usize,
+    pub errors: Vec<String>,
+}
+
+/// Hashea el client jar y las librerías; los corruptos van a cuarentena y se vuelven a
+/// descargar.
+pub(crate) fn verify_and_repair_instance_jars(
+    launcher_root: &Path,
+    instance_path: &Path,
+    mc_root: &Path,
+    metadata: &InstanceMetadata,
+    clock: &dyn Clock,
+    logs: &mut Vec<String>,
+) -> Result<JarVerificationReport, String> {
+    let version_id = resolve_effective_version_id(mc_root, metadata)?;
+    let version_json = load_merged_version_json(mc_root, &version_id)?;
+    let jars = collect_published_jars(
+        &launcher_root.join("libraries"),
+        mc_root,
+        &metadata.minecraft_version,
+        &version_json,
+    );
+
+    let mut report = JarVerificationReport::default();
+    let mut to_download = Vec::new();
+    let mut verification_cache = VerificationCache::load(launcher_root);
+    for jar in jars {
+        if !jar.path.exists() {
+            if let Some(url) = jar.url.clone() {
+                to_download.push(MissingLibraryEntry {
+                    path: jar.path.display().to_string(),
+                    url,
+                    sha1: jar.sha1.clone().unwrap_or_default(),
+                });
+            }
+            continue;
+        }
+
+        let reason = if let Err(err) = validate_jars_as_zip(std::slice::from_ref(&jar.path)) {
+            Some(err)
+        } else if let Some(expected) = jar.sha1.as_deref() {
+            match compute_file_sha1(&jar.path) {
+                Ok(actual) if actual.eq_ignore_ascii_case(expected) => {
+                    verification_cache.record(&jar.path, &actual);
+                    None
+                }
+                Ok(actual) => Some(format!(
+                    "SHA1 distinto al publicado (esperado {expected}, obtenido {actual})"
+                )),
+                Err(err) => Some(format!("No se pudo calcular SHA1: {err}")),
+            }
+        } else {
+            None
+        };
+
+        let Some(reason) = reason else {
+            report.verified += 1;
+            continue;
+        };
+        match quarantine_file(launcher_root, &jar.path, &reason) {
+            Ok(entry) => {
+                logs.push(format!(
+                    "⚠ {} en cuarentena ({reason}) -> {}",
+                    jar.path.display(),
+                    entry.quarantined_path
+                ));
+                report.quarantined.push(jar.path.display().to_string());
+                match jar.url {
+                    Some(url) => to_download.push(MissingLibraryEntry {
+                        path: jar.path.display().to_string(),
+                        url,
+                        sha1: jar.sha1.unwrap_or_default(),
+                    }),
+                    None => report.errors.push(format!(
+                        "{} quedó en cuarentena y no tiene URL para volver a descargarlo.",
+                        jar.path.display()
+                    )),
+                }
+            }
+            Err(err) => report.errors.push(err),
+        }
+    }
+
+    verification_cache.save();
 
-fn validate_jars_as_zip(jars: &[PathBuf]) -> Result<(), String> {
-    for jar in jars {
-        let file = fs::File::open(jar)
-            .map_err(|err| format!("No se pudo abrir jar {}: {err}", jar.display()))?;
-        ZipArchive::new(file)
-            .map_err(|err| format!("Jar inválido/corrupto {}: {err}", jar.display()))?;
+    match ensure_missing_libraries(&to_download, &LaunchWatchdog::detached()) {
+        Ok(downloaded) => report.redownloaded = downloaded,
+        Err(err) => report.errors.push(err),
     }
-    Ok(())
+    logs.push(format!(
+        "✔ Verificación SHA1: {} correctos, {} en cuarentena, {} descargados",
+        report.verified,
+        report.quarantined.len(),
+        report.redownloaded
+    ));
+
+    if report.errors.is_empty() {
+        write_verification_marker(instance_path, &version_id, clock);
+    }
+    Ok(report)
+}
+
+/// Guarda el lockfile del lanzamiento en segundo plano, ya con el proceso en marcha, para
+/// no retrasar la ventana del juego.
+fn spawn_launch_lock_recorder(
+    instance_root: String,
+    runtime_instance_root: String,
+    prepared: &LaunchValidationResult,
+    created_at: String,
+) {
+    let classpath = env::split_paths(&prepared.classpath).collect::<Vec<_>>();
+    let java_path = prepared.java_path.clone();
+    let java_version = prepared.java_version.clone();
+    thread::spawn(move || {
+        let runtime_path = Path::new(&runtime_instance_root);
+        let inputs = (|| -> Result<LaunchLockInputs, String> {
+            let metadata = read_instance_metadata(runtime_instance_root.clone())?;
+            let mc_root = runtime_path.join("minecraft");
+            let version_id = resolve_effective_version_id(&mc_root, &metadata)?;
+            let asset_index = load_merged_version_json(&mc_root, &version_id)
+                .ok()
+                .and_then(|merged| {
+                    let index = merged.get("assetIndex")?;
+                    Some(LockedAssetIndex {
+                        id: index.get("id")?.as_str()?.to_string(),
+                        sha1: index
+                            .get("sha1")
+                            .and_then(Value::as_str)
+                            .unwrap_or_default()
+                            .to_string(),
+                    })
+                });
+            let launcher_root = resolve_launcher_root_for_instance(
+                runtime_path,
+                configured_launcher_root().as_deref(),
+                &mut Vec::new(),
+            )?;
+            Ok(LaunchLockInputs {
+                launcher_root,
+                created_at,
+                version_json_path: mc_root
+                    .join("versions")
+                    .join(&version_id)
+                    .join(format!("{version_id}.json")),
+                version_id,
+                minecraft_version: metadata.minecraft_version,
+                loader: metadata.loader,
+                loader_version: metadata.loader_version,
+                java_path,
+                java_version,
+                classpath,
+                natives_dir: mc_root.join("natives"),
+                asset_index,
+                library_overrides: metadata.library_overrides,
+                mods_dir: effective_mods_dir(runtime_path),
+                game_dir: mc_root,
+                ram_mb: metadata.ram_mb,
+                java_args: metadata.java_args,
+            })
+        })();
+        match inputs {
+            Ok(inputs) => record_launch_lock(Path::new(&instance_root), &inputs),
+            Err(err) => log::warn!("⚠ No se pudo generar el lockfile de lanzamiento: {err}"),
+        }
+    });
 }
 
 fn is_native_jar_path(jar_path: &str) -> bool {
@@ -4015,6 +7592,110 @@ fn should_extract_for_platform(filename: &str) -> bool {
     true
 }
 
+/// Artefacto Maven de una librería (`org.lwjgl.lwjgl:lwjgl-platform:2.9.1` → `lwjgl-platform`).
+fn library_artifact_name(lib: &Value) -> Option<String> {
+    lib.get("name")
+        .and_then(Value::as_str)
+        .and_then(|name| name.split(':').nth(1))
+        .map(str::to_ascii_lowercase)
+}
+
+fn is_platform_native_file(filename: &str) -> bool {
+    let lower = filename.to_ascii_lowercase();
+    if cfg!(target_os = "windows") {
+        lower.ends_with(".dll")
+    } else if cfg!(target_os = "macos") {
+        lower.ends_with(".dylib") || lower.ends_with(".jnilib")
+    } else {
+        lower.ends_with(".so") || lower.contains(".so.")
+    }
+}
+
+/// Fragmentos de nombre de archivo que delatan los natives de un artefacto:
+/// `lwjgl-platform` → `lwjgl`, `jinput-platform` → `jinput`, `lwjgl-glfw` → `lwjgl_glfw`/`glfw`.
+fn expected_native_stems(artifact: &str) -> Vec<String> {
+    let base = artifact
+        .trim_end_matches("-platform")
+        .split("-natives")
+        .next()
+        .unwrap_or(artifact);
+    let mut stems = vec![base.replace('-', "_")];
+    if let Some(module) = base.strip_prefix("lwjgl-") {
+        stems.push(module.to_string());
+    }
+    stems
+}
+
+/// Carpetas donde los launchers antiguos dejaban los natives ya extraídos.
+fn legacy_natives_candidates(mc_root: &Path, version_ids: &[&str]) -> Vec<PathBuf> {
+    let mut candidates = Vec::new();
+    for version_id in version_ids {
+        let version_dir = mc_root.join("versions").join(version_id);
+        for candidate in [
+            version_dir.join(format!("{version_id}-natives")),
+            version_dir.join("natives"),
+        ] {
+            if !candidates.contains(&candidate) {
+                candidates.push(candidate);
+            }
+        }
+    }
+    candidates
+}
+
+/// Primera carpeta legacy con natives de esta plataforma para todos los artefactos faltantes.
+fn find_legacy_natives_dir(
+    candidates: &[PathBuf],
+    missing_artifacts: &[String],
+) -> Option<PathBuf> {
+    candidates
+        .iter()
+        .find(|dir| {
+            let Ok(entries) = fs::read_dir(dir) else {
+                return false;
+            };
+            let files = entries
+                .flatten()
+                .filter(|entry| entry.path().is_file())
+                .map(|entry| entry.file_name().to_string_lossy().to_ascii_lowercase())
+                .filter(|name| is_platform_native_file(name))
+                .collect::<Vec<_>>();
+            !files.is_empty()
+                && missing_artifacts.iter().all(|artifact| {
+                    expected_native_stems(artifact)
+                        .iter()
+                        .any(|stem| files.iter().any(|file| file.contains(stem.as_str())))
+                })
+        })
+        .cloned()
+}
+
+/// Copia los natives preextraídos al directorio de natives del lanzamiento.
+fn copy_legacy_natives(source: &Path, natives_dir: &Path) -> Result<usize, String> {
+    fs::create_dir_all(natives_dir)
+        .map_err(|err| format!("No se pudo crear natives dir: {err}"))?;
+    let mut copied = 0usize;
+    for entry in fs::read_dir(source)
+        .map_err(|err| format!("No se pudo leer {}: {err}", source.display()))?
+        .flatten()
+    {
+        let path = entry.path();
+        if !path.is_file() {
+            continue;
+        }
+        let target = natives_dir.join(entry.file_name());
+        fs::copy(&path, &target).map_err(|err| {
+            format!(
+                "No se pudo copiar native {} -> {}: {err}",
+                path.display(),
+                target.display()
+            )
+        })?;
+        copied += 1;
+    }
+    Ok(copied)
+}
+
 fn prepare_natives_dir(natives_dir: &Path) -> Result<(), String> {
     if natives_dir.exists() {
         for entry in fs::read_dir(natives_dir)
@@ -4046,11 +7727,41 @@ fn prepare_natives_dir(natives_dir: &Path) -> Result<(), String> {
     fs::create_dir_all(natives_dir).map_err(|err| format!("No se pudo crear natives dir: {err}"))
 }
 
+fn open_native_jar(path: &Path) -> Result<ZipArchive<fs::File>, String> {
+    let file = fs::File::open(path)
+        .map_err(|err| format!("No se pudo abrir {}: {err}", path.display()))?;
+    ZipArchive::new(file).map_err(|err| format!("ZIP inválido {}: {err}", path.display()))
+}
+
+/// Intenta re-descargar un jar de natives corrupto usando la url+sha1 del version JSON.
+fn redownload_native_jar(
+    native: &NativeJarEntry,
+    logs: &mut Vec<String>,
+) -> Result<ZipArchive<fs::File>, String> {
+    let (Some(url), Some(sha1)) = (native.url.as_ref(), native.sha1.as_ref()) else {
+        return Err("sin url/sha1 conocidos en el version JSON".to_string());
+    };
+
+    logs.push(format!(
+        "  ↻ Re-descargando jar de natives corrupto desde {url}"
+    ));
+    ensure_missing_libraries(
+        &[MissingLibraryEntry {
+            path: native.path.clone(),
+            url: url.clone(),
+            sha1: sha1.clone(),
+        }],
+        &current_watchdog(),
+    )?;
+    open_native_jar(Path::new(&native.path))
+}
+
+/// Extrae los natives y devuelve las rutas de jars corruptos no esenciales que se omitieron.
 fn extract_natives(
     native_jars: &[NativeJarEntry],
     natives_dir: &Path,
     logs: &mut Vec<String>,
-) -> Result<(), String> {
+) -> Result<Vec<String>, String> {
     if natives_dir.exists() {
         for entry in fs::read_dir(natives_dir)
             .map_err(|err| format!("Error leyendo natives dir: {err}"))?
@@ -4096,6 +7807,7 @@ fn extract_natives(
     }
 
     let mut extracted = 0_u32;
+    let mut skipped = Vec::new();
 
     for native in native_jars {
         let jar_path = Path::new(&native.path);
@@ -4104,10 +7816,40 @@ fn extract_natives(
             continue;
         }
 
-        let file = fs::File::open(jar_path)
-            .map_err(|err| format!("No se pudo abrir {}: {err}", native.path))?;
-        let mut archive =
-            ZipArchive::new(file).map_err(|err| format!("ZIP inválido {}: {err}", native.path))?;
+        let mut archive = match open_native_jar(jar_path) {
+            Ok(archive) => archive,
+            Err(corrupt_err) => {
+                logs.push(format!("  ⚠ {corrupt_err}"));
+                match redownload_native_jar(native, logs) {
+                    Ok(archive) => {
+                        logs.push(format!("  ✓ Jar de natives reparado: {}", native.path));
+                        archive
+                    }
+                    Err(repair_err) => {
+                        let file_name = jar_path
+                            .file_name()
+                            .and_then(|name| name.to_str())
+                            .unwrap_or("");
+                        if should_extract_for_platform(file_name) {
+                            return Err(format!(
+                                "Jar de natives corrupto e irrecuperable: {}\n\
+                                 URL de origen: {}\n\
+                                 Detalle: {corrupt_err}. Reparación fallida: {repair_err}.\n\
+                                 Elimina el archivo y vuelve a intentar.",
+                                native.path,
+                                native.url.as_deref().unwrap_or("desconocida")
+                            ));
+                        }
+                        logs.push(format!(
+                            "  ⚠ Jar de natives corrupto no requerido en esta plataforma, se omite: {} ({repair_err})",
+                            native.path
+                        ));
+                        skipped.push(native.path.clone());
+                        continue;
+                    }
+                }
+            }
+        };
 
         for i in 0..archive.len() {
             let mut entry = archive
@@ -4149,6 +7891,12 @@ fn extract_natives(
     }
 
     logs.push(format!("✔ Total extraídos: {} archivos nativos", extracted));
+    if !skipped.is_empty() {
+        logs.push(format!(
+            "⚠ Jars de natives corruptos omitidos: {}",
+            skipped.len()
+        ));
+    }
 
     #[cfg(target_os = "windows")]
     {
@@ -4170,7 +7918,7 @@ fn extract_natives(
         }
     }
 
-    Ok(())
+    Ok(skipped)
 }
 
 fn list_dir_files(dir: &Path) -> Vec<String> {
@@ -4255,6 +8003,10 @@ fn ensure_loader_ready_for_launch(
         return Ok(());
     }
 
+    let watchdog = current_watchdog();
+    watchdog.set_sub_operation(format!("verificando loader {}", metadata.loader));
+    watchdog.check()?;
+
     let current_version_id = metadata.version_id.trim();
     if current_version_id.is_empty() {
         return Err(format!(
@@ -4283,6 +8035,38 @@ fn ensure_loader_ready_for_launch(
     Ok(())
 }
 
+/// Flags de `auto_jvm_tuning` que faltan en los argumentos del usuario. El conjunto completo
+/// queda en los logs del lanzamiento.
+pub(crate) fn auto_jvm_tuning_args(
+    metadata: &InstanceMetadata,
+    java_major: Option<u32>,
+    user_args: &[String],
+    logs: &mut Vec<String>,
+) -> Vec<String> {
+    let java_major = java_major
+        .or_else(|| parse_runtime_from_metadata(metadata).map(|runtime| runtime.major() as u32))
+        .unwrap_or(8);
+    let ram_mb = metadata.ram_mb.max(512);
+    let flags = default_jvm_flags(
+        &JvmTuningInput {
+            java_major,
+            ram_mb,
+            minecraft_version: &metadata.minecraft_version,
+            windows: cfg!(target_os = "windows"),
+        },
+        user_args,
+    );
+    if flags.is_empty() {
+        logs.push("🔹 auto_jvm_tuning: sin flags que añadir, los define java_args".to_string());
+    } else {
+        logs.push(format!(
+            "🔹 auto_jvm_tuning (Java {java_major}, {ram_mb} MB): {}",
+            flags.join(" ")
+        ));
+    }
+    flags
+}
+
 fn parse_runtime_major(input: &str) -> Option<JavaRuntime> {
     let digits = input
         .chars()
@@ -4335,73 +8119,84 @@ fn guess_runtime_from_minecraft_version(version: &str) -> JavaRuntime {
     }
 }
 
-fn persist_instance_java_path(
-    instance_path: &Path,
+/// Encola el java_path embebido; se guarda al terminar la validación.
+fn queue_instance_java_path(
+    instance_root: &str,
     metadata: &InstanceMetadata,
     java_exec: &Path,
     logs: &mut Vec<String>,
-) -> Result<(), String> {
+) {
     let mut updated = metadata.clone();
     updated.java_path = java_exec.display().to_string();
-    updated.java_runtime = format!(
-        "java{}",
-        parse_runtime_from_metadata(&updated)
-            .map(|r| r.major())
-            .unwrap_or(17)
-    );
-    updated.java_version = format!(
-        "{}.0.x",
-        parse_runtime_from_metadata(&updated)
-            .map(|r| r.major())
-            .unwrap_or(17)
-    );
-
-    let metadata_path = instance_path.join(".instance.json");
-    fs::write(
-        &metadata_path,
-        serde_json::to_string_pretty(&updated)
-            .map_err(|err| format!("No se pudo serializar metadata actualizada: {err}"))?,
-    )
-    .map_err(|err| {
-        format!(
-            "No se pudo persistir metadata actualizada en {}: {err}",
-            metadata_path.display()
-        )
-    })?;
+    let major = parse_runtime_from_metadata(&updated)
+        .map(|r| r.major())
+        .unwrap_or(17);
+    metadata_writer(instance_root).queue_java_path(JavaPathUpdate {
+        java_path: updated.java_path,
+        java_runtime: format!("java{major}"),
+        java_version: format!("{major}.0.x"),
+    });
 
     logs.push(format!(
         "✔ .instance.json actualizado con java_path embebido: {}",
         java_exec.display()
     ));
-
-    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::{
-        build_maven_library_path, contains_classpath_switch, detect_forge_generation,
-        extract_maven_key, load_forge_args_file, merge_version_jsons, parse_runtime_from_metadata,
-        parse_runtime_major, should_extract_for_platform, verify_no_duplicate_classpath_entries,
-        ForgeGeneration,
+        add_suspension, build_java_command, build_maven_library_path,
+        check_mods_dir_override_location, compute_card_stats, compute_instance_health,
+        contains_classpath_switch, copy_legacy_natives, detect_forge_generation,
+        ensure_main_class_present_in_jar, extract_maven_key, extract_natives,
+        finalize_classpath_and_natives, finalize_redirect_classpath, find_legacy_natives_dir,
+        forge_inject_system_properties, inspect_jars_pooled, inspect_launch_jars,
+        inspect_merged_version_json, is_instance_running, legacy_natives_candidates, list_versions,
+        load_forge_args_file, load_single_version_json, merge_version_jsons, merged_json_summary,
+        parse_runtime_from_metadata, parse_runtime_major, prune_versions, read_instance_metadata,
+        register_runtime_exit, register_runtime_pid, register_runtime_start,
+        resolve_effective_version_id, resolve_launcher_root_for_instance, resolve_libraries,
+        retry_transient_open, running_instances_snapshot, runtime_exit_payload, runtime_registry,
+        should_extract_for_platform, strip_classpath_from_jvm_args, unreadable_source_error,
+        upgrade_instance_metadata, validate_jars_as_zip, verify_no_duplicate_classpath_entries,
+        verify_profile_matches_session, wait_and_record_exit, CardStatsError, ForgeGeneration,
+        JarCheck, JarOpenStats, NativeJarEntry, JAR_INSPECTION_WORKERS, VERIFICATION_MARKER_FILE,
     };
+    use crate::app::metadata_writer::metadata_writer;
+    use crate::app::playtime::SessionAccount;
+    use crate::app::redirect_launch::build_classpath_multi;
     use crate::domain::minecraft::argument_resolver::LaunchContext;
-    use crate::domain::models::{instance::InstanceMetadata, java::JavaRuntime};
+    use crate::domain::minecraft::rule_engine::RuleContext;
+    use crate::domain::models::{
+        instance::{InstanceMetadata, LaunchAuthSession},
+        java::JavaRuntime,
+    };
+    use crate::shared::clock::{
+        mock::{MockClock, SequentialIds},
+        Clock,
+    };
+    use crate::shared::test_support::test_temp_dir;
     use serde_json::json;
     use std::{
         fs,
         path::Path,
-        time::{SystemTime, UNIX_EPOCH},
+        sync::atomic::{AtomicUsize, Ordering},
+        time::Duration,
     };
 
-    fn test_temp_dir(prefix: &str) -> std::path::PathBuf {
-        let nonce = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .expect("clock")
-            .as_nanos();
-        let dir = std::env::temp_dir().join(format!("{prefix}-{nonce}"));
-        fs::create_dir_all(&dir).expect("temp dir");
-        dir
+    /// Los tests que arrancan una JVM se omiten sin `java` en el PATH, salvo en CI, donde el
+    /// workflow lo instala y su ausencia es un fallo.
+    fn java_on_path() -> bool {
+        let available = std::process::Command::new("java")
+            .arg("-version")
+            .output()
+            .is_ok();
+        assert!(
+            available || std::env::var_os("CI").is_none(),
+            "este test necesita java en el PATH"
+        );
+        available
     }
 
     fn launch_context_for_tests() -> LaunchContext {
@@ -4474,6 +8269,77 @@ mod tests {
         );
     }
 
+    #[test]
+    fn renamed_profile_is_accepted_with_the_new_name_but_another_uuid_is_not() {
+        let session = LaunchAuthSession {
+            profile_id: "069a79f4-44e9-4726-a5be-fca90e38aaf5".to_string(),
+            profile_name: "NombreViejo".to_string(),
+            minecraft_access_token: "token".to_string(),
+            minecraft_access_token_expires_at: None,
+            microsoft_refresh_token: None,
+            premium_verified: true,
+            persisted: false,
+        };
+        let mut logs = Vec::new();
+        let verified = verify_profile_matches_session(
+            &json!({ "id": "069A79F444E94726A5BEFCA90E38AAF5", "name": "NombreNuevo" }),
+            &session,
+            &mut logs,
+        )
+        .expect("mismo UUID con otro nombre");
+        assert_eq!(verified.profile_name, "NombreNuevo");
+        assert_eq!(verified.profile_id, "069A79F444E94726A5BEFCA90E38AAF5");
+        assert_eq!(
+            verified.previous_profile_name.as_deref(),
+            Some("NombreViejo")
+        );
+        assert!(logs.iter().any(|line| line.contains("NombreNuevo")));
+
+        let unchanged = verify_profile_matches_session(
+            &json!({ "id": "069a79f444e94726a5befca90e38aaf5", "name": "NombreViejo" }),
+            &session,
+            &mut logs,
+        )
+        .expect("mismo perfil");
+        assert_eq!(unchanged.previous_profile_name, None);
+
+        assert!(verify_profile_matches_session(
+            &json!({ "id": "853c80ef3c3749fdaa49938b674adae6", "name": "NombreViejo" }),
+            &session,
+            &mut logs,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn mods_dir_override_must_be_absolute_and_outside_other_instances() {
+        let instances =
+            std::env::temp_dir().join(format!("interface-mods-override-{}", std::process::id()));
+        let own = instances.join("Propia");
+        let other = instances.join("Otra");
+        let shared = std::env::temp_dir().join("interface-mods-override-shared");
+        for dir in [
+            own.join("minecraft/mods"),
+            other.join("minecraft/mods"),
+            shared.clone(),
+        ] {
+            fs::create_dir_all(dir).expect("dir");
+        }
+
+        assert!(check_mods_dir_override_location(&shared, &instances, &own).is_ok());
+        assert!(
+            check_mods_dir_override_location(&own.join("minecraft/mods"), &instances, &own).is_ok()
+        );
+        assert!(
+            check_mods_dir_override_location(&other.join("minecraft/mods"), &instances, &own)
+                .is_err()
+        );
+        assert!(check_mods_dir_override_location(Path::new("mods"), &instances, &own).is_err());
+
+        let _ = fs::remove_dir_all(&instances);
+        let _ = fs::remove_dir_all(&shared);
+    }
+
     #[test]
     fn parse_runtime_major_maps_expected_ranges() {
         assert_eq!(parse_runtime_major("8"), Some(JavaRuntime::Java8));
@@ -4483,20 +8349,21 @@ mod tests {
 
     #[test]
     fn parse_runtime_from_metadata_uses_fallback_fields() {
-        let metadata = InstanceMetadata {
-            name: "Demo".to_string(),
-            group: "Default".to_string(),
-            minecraft_version: "1.20.4".to_string(),
-            loader: "vanilla".to_string(),
-            loader_version: "".to_string(),
-            ram_mb: 2048,
-            java_args: vec![],
-            java_path: "C:/runtime/java17/bin/java.exe".to_string(),
-            java_runtime: "desconocido".to_string(),
-            java_version: "17.0.x".to_string(),
-            last_used: None,
-            internal_uuid: "id".to_string(),
-        };
+        let metadata: InstanceMetadata = serde_json::from_value(json!({
+            "name": "Demo",
+            "group": "Default",
+            "minecraftVersion": "1.20.4",
+            "loader": "vanilla",
+            "loaderVersion": "",
+            "ramMb": 2048,
+            "javaArgs": [],
+            "javaPath": "C:/runtime/java17/bin/java.exe",
+            "javaRuntime": "desconocido",
+            "javaVersion": "17.0.x",
+            "lastUsed": null,
+            "internalUuid": "id",
+        }))
+        .expect("metadata");
 
         assert_eq!(
             parse_runtime_from_metadata(&metadata),
@@ -4542,6 +8409,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn latest_loader_install_wins_over_leftover_builds() {
+        use crate::services::loader_files::{record_loader_files, snapshot_loader_files};
+
+        let mc_root = test_temp_dir("loader-manifest-select").join("minecraft");
+        let metadata: InstanceMetadata = serde_json::from_value(json!({
+            "name": "Demo",
+            "minecraftVersion": "1.20.1",
+            "loader": "forge",
+        }))
+        .expect("metadata");
+
+        // El segundo build ordena antes alfabéticamente: sin manifiesto ganaría el primero.
+        for (version_id, build) in [
+            ("1.20.1-forge-47.2.0", "47.2.0"),
+            ("1.20.1-forge-47.1.0", "47.1.0"),
+        ] {
+            let before = snapshot_loader_files(&mc_root);
+            let version_dir = mc_root.join("versions").join(version_id);
+            fs::create_dir_all(&version_dir).expect("version dir");
+            fs::write(
+                version_dir.join(format!("{version_id}.json")),
+                r#"{"inheritsFrom":"1.20.1"}"#,
+            )
+            .expect("version json");
+            record_loader_files(&mc_root, "forge", build, version_id, &before).expect("manifest");
+            assert_eq!(
+                resolve_effective_version_id(&mc_root, &metadata).expect("version"),
+                version_id
+            );
+        }
+    }
+
     #[test]
     fn forge_args_file_parsing_splits_flag_and_value_correctly() {
         let root = test_temp_dir("forge-args-parse");
@@ -4589,6 +8489,72 @@ mod tests {
         );
     }
 
+    #[test]
+    fn readers_accept_bom_utf16_and_crlf_like_clean_files() {
+        let root = test_temp_dir("encoding-readers");
+        let mc_root = root.join("minecraft");
+        let version_id = "forge-encoding";
+        let version_dir = mc_root.join("versions").join(version_id);
+        fs::create_dir_all(&version_dir).expect("version dir");
+        fs::create_dir_all(mc_root.join("libraries")).expect("libraries dir");
+        fs::write(mc_root.join("libraries/one"), "").expect("module");
+        fs::write(root.join(".instance.json"), "{}").expect("metadata");
+
+        let clean_json = "{\n  \"id\": \"forge-encoding\",\n  \"mainClass\": \"a.B\"\n}\n";
+        let json_path = version_dir.join(format!("{version_id}.json"));
+        let mut utf16 = vec![0xFF, 0xFE];
+        utf16.extend(
+            clean_json
+                .replace('\n', "\r\n")
+                .encode_utf16()
+                .flat_map(u16::to_le_bytes),
+        );
+        fs::write(&json_path, utf16).expect("utf16 json");
+        let parsed = load_single_version_json(&mc_root, version_id).expect("utf16 parse");
+        assert_eq!(
+            parsed,
+            serde_json::from_str::<serde_json::Value>(clean_json).expect("clean")
+        );
+        assert_eq!(
+            fs::read_to_string(&json_path).expect("rewritten"),
+            clean_json
+        );
+
+        let args_path = if cfg!(target_os = "windows") {
+            version_dir.join("win_args.txt")
+        } else {
+            version_dir.join("unix_args.txt")
+        };
+        let clean_args = "--add-modules\nALL-MODULE-PATH\n";
+        fs::write(&args_path, clean_args).expect("clean args");
+        let clean = load_forge_args_file(
+            &mc_root,
+            version_id,
+            &launch_context_for_tests(),
+            &mc_root,
+            &mut Vec::new(),
+        )
+        .expect("ok")
+        .expect("some");
+        let mut bom_args = vec![0xEF, 0xBB, 0xBF];
+        bom_args.extend(clean_args.replace('\n', "\r\n").as_bytes());
+        fs::write(&args_path, bom_args).expect("bom args");
+        let repaired = load_forge_args_file(
+            &mc_root,
+            version_id,
+            &launch_context_for_tests(),
+            &mc_root,
+            &mut Vec::new(),
+        )
+        .expect("ok")
+        .expect("some");
+        assert_eq!(repaired.args, clean.args);
+        assert_eq!(repaired.args[0], "--add-modules");
+        assert_eq!(fs::read_to_string(&args_path).expect("args"), clean_args);
+
+        let _ = fs::remove_dir_all(root);
+    }
+
     #[test]
     fn forge_inject_system_properties_uses_classpath_for_legacy_classpath() {
         let mut jvm_args = Vec::new();
@@ -4679,6 +8645,91 @@ mod tests {
         );
     }
 
+    #[test]
+    fn java_receives_paths_with_spaces_and_non_ascii_intact() {
+        if !java_on_path() {
+            return;
+        }
+        let root = test_temp_dir("Ínstance Tëst Dir");
+        let version_id = "forge-test";
+        let version_dir = root.join("versions").join(version_id);
+        let modules_dir = root.join("libraries/cpw/mods");
+        let natives_dir = root.join("natives");
+        let game_dir = root.join("minecraft");
+        for dir in [&version_dir, &modules_dir, &natives_dir, &game_dir] {
+            fs::create_dir_all(dir).expect("dir");
+        }
+        let client_jar = root.join("libraries/client.jar");
+        fs::write(&client_jar, "").expect("client jar");
+        let args_path = if cfg!(target_os = "windows") {
+            version_dir.join("win_args.txt")
+        } else {
+            version_dir.join("unix_args.txt")
+        };
+        fs::write(
+            &args_path,
+            format!(
+                "-DlibraryDirectory=${{library_directory}}\n--module-path \"${{library_directory}}/cpw/mods\"\n-Dtest.path=\"{}\"\n",
+                root.display()
+            ),
+        )
+        .expect("args file");
+
+        let mut logs = Vec::new();
+        let parsed = load_forge_args_file(
+            &root,
+            version_id,
+            &launch_context_for_tests(),
+            &root,
+            &mut logs,
+        )
+        .expect("ok")
+        .expect("some");
+        assert!(
+            parsed.args.iter().all(|arg| !arg.contains('"')),
+            "no deben quedar comillas del args file: {:?}",
+            parsed.args
+        );
+
+        let mut jvm_args = parsed.args.clone();
+        jvm_args.extend([
+            format!("-Djava.library.path={}", natives_dir.display()),
+            "-XshowSettings:properties".to_string(),
+            "-cp".to_string(),
+            client_jar.display().to_string(),
+        ]);
+        // `-version` como clase principal: la JVM imprime las propiedades y termina.
+        let (mut command, effective_jvm_args) = build_java_command(
+            Path::new("java"),
+            &jvm_args,
+            "-version",
+            &[],
+            &game_dir,
+            true,
+        );
+        assert!(!contains_classpath_switch(&effective_jvm_args));
+        let output = command.output().expect("java en el PATH");
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(output.status.success(), "java falló: {stderr}");
+        let property = |key: &str| {
+            stderr
+                .lines()
+                .find_map(|line| line.trim().strip_prefix(&format!("{key} = ")))
+                .map(|value| value.replace('\\', "/"))
+        };
+        let expected = |path: &Path| Some(path.display().to_string().replace('\\', "/"));
+
+        assert_eq!(property("test.path"), expected(&root));
+        assert_eq!(
+            property("libraryDirectory"),
+            expected(&root.join("libraries"))
+        );
+        assert_eq!(property("jdk.module.path"), expected(&modules_dir));
+        assert_eq!(property("java.library.path"), expected(&natives_dir));
+        assert_eq!(property("java.class.path"), expected(&client_jar));
+        assert_eq!(property("user.dir"), expected(&game_dir));
+    }
+
     #[test]
     fn jvm_args_order_for_modern_forge_has_module_path_before_cp() {
         let mut jvm_args = vec!["-Xms512M".to_string(), "-Xmx2048M".to_string()];
@@ -4767,26 +8818,108 @@ mod tests {
             "jvm debe tener parent(1) + child(2) = mínimo 3, tiene {}",
             jvm_args.len()
         );
-
-        let libs = merged["libraries"]
-            .as_array()
-            .expect("libraries debe existir");
+
+        let libs = merged["libraries"]
+            .as_array()
+            .expect("libraries debe existir");
+        assert_eq!(
+            libs.len(),
+            2,
+            "libraries debe tener 2 (1 parent + 1 child), tiene {}",
+            libs.len()
+        );
+
+        assert_eq!(
+            merged["assetIndex"]["id"].as_str().unwrap_or_default(),
+            "17"
+        );
+
+        assert!(
+            merged.get("inheritsFrom").is_none(),
+            "inheritsFrom no debe estar en el JSON mergeado"
+        );
+    }
+
+    #[test]
+    fn inspection_reports_merge_provenance_without_rewriting() {
+        let root = test_temp_dir("version-inspect");
+        let versions = root.join("versions");
+        let parent = json!({
+            "id": "1.21.1",
+            "mainClass": "net.minecraft.client.main.Main",
+            "arguments": {
+                "game": ["--username", "${auth_player_name}"],
+                "jvm": ["-Djava.library.path=${natives_directory}"]
+            },
+            "libraries": [
+                { "name": "com.mojang:minecraft:1.21.1" },
+                { "name": "org.ow2.asm:asm:9.6" }
+            ],
+            "assetIndex": { "id": "17", "url": "https://..." },
+            "assets": "17"
+        });
+        let child = json!({
+            "id": "neoforge-21.1.219",
+            "inheritsFrom": "1.21.1",
+            "mainClass": "cpw.mods.bootstraplauncher.BootstrapLauncher",
+            "arguments": { "jvm": ["-DignoreList=bootstraplauncher"] },
+            "libraries": [
+                { "name": "cpw.mods:bootstraplauncher:1.1.2" },
+                { "name": "org.ow2.asm:asm:9.7" }
+            ],
+            "assetIndex": { "id": "neo", "url": "https://..." }
+        });
+        for (id, value) in [("1.21.1", &parent), ("neoforge-21.1.219", &child)] {
+            fs::create_dir_all(versions.join(id)).expect("version dir");
+            fs::write(
+                versions.join(id).join(format!("{id}.json")),
+                serde_json::to_string(value).expect("json"),
+            )
+            .expect("write version");
+        }
+        let child_path = versions
+            .join("neoforge-21.1.219")
+            .join("neoforge-21.1.219.json");
+        let child_before = fs::read(&child_path).expect("read child");
+
+        let (merged, provenance) =
+            inspect_merged_version_json(&root, "neoforge-21.1.219").expect("inspect");
+        let provenance = provenance.expect("hereda de 1.21.1");
+        assert_eq!(merged, merge_version_jsons(parent, child));
+        let origin = |key: &str| provenance.key_origins.get(key).map(String::as_str);
+        assert_eq!(origin("mainClass"), Some("child"));
+        assert_eq!(origin("id"), Some("child"));
+        assert_eq!(origin("assetIndex"), Some("parent"));
+        assert_eq!(origin("assets"), Some("parent"));
+        assert_eq!(origin("arguments"), Some("merged"));
+        assert_eq!(origin("libraries"), Some("merged"));
+        assert_eq!(origin("inheritsFrom"), None);
+
+        assert_eq!(provenance.deduplicated_libraries.len(), 1);
+        let dedup = &provenance.deduplicated_libraries[0];
+        assert_eq!(dedup.key, "org.ow2.asm:asm");
+        assert_eq!(
+            (dedup.kept_name.as_str(), dedup.kept_from.as_str()),
+            ("org.ow2.asm:asm:9.7", "child")
+        );
         assert_eq!(
-            libs.len(),
-            2,
-            "libraries debe tener 2 (1 parent + 1 child), tiene {}",
-            libs.len()
+            (dedup.dropped_name.as_str(), dedup.dropped_from.as_str()),
+            ("org.ow2.asm:asm:9.6", "parent")
         );
 
+        let summary = merged_json_summary(&merged);
         assert_eq!(
-            merged["assetIndex"]["id"].as_str().unwrap_or_default(),
-            "17"
+            summary.main_class.as_deref(),
+            Some("cpw.mods.bootstraplauncher.BootstrapLauncher")
         );
+        assert_eq!(summary.args_format, "modern");
+        assert_eq!((summary.game_args_count, summary.jvm_args_count), (2, 2));
+        assert_eq!(summary.libraries_count, 3);
+        assert_eq!(summary.asset_index.as_deref(), Some("17"));
+        assert!(summary.has_auth_player_name);
 
-        assert!(
-            merged.get("inheritsFrom").is_none(),
-            "inheritsFrom no debe estar en el JSON mergeado"
-        );
+        assert_eq!(fs::read(&child_path).expect("reread child"), child_before);
+        let _ = fs::remove_dir_all(root);
     }
 
     #[test]
@@ -4939,6 +9072,64 @@ mod tests {
         );
     }
 
+    #[test]
+    fn legacy_version_natives_folder_replaces_missing_classifier_jars() {
+        let native_file = |stem: &str| {
+            if cfg!(target_os = "windows") {
+                format!("{stem}.dll")
+            } else if cfg!(target_os = "macos") {
+                format!("lib{stem}.dylib")
+            } else {
+                format!("lib{stem}.so")
+            }
+        };
+        let mc_root = test_temp_dir("legacy-natives");
+        let legacy_dir = mc_root.join("versions/1.5.2/1.5.2-natives");
+        fs::create_dir_all(&legacy_dir).expect("legacy natives");
+        fs::write(legacy_dir.join(native_file("lwjgl64")), "lwjgl").expect("lwjgl");
+        fs::write(legacy_dir.join(native_file("jinput-raw_64")), "jinput").expect("jinput");
+        let classifiers = json!({
+            "linux": "natives-linux",
+            "windows": "natives-windows",
+            "osx": "natives-osx"
+        });
+        let version_json = json!({
+            "libraries": [
+                { "name": "org.lwjgl.lwjgl:lwjgl-platform:2.9.0", "natives": classifiers },
+                { "name": "net.java.jinput:jinput-platform:2.0.5", "natives": classifiers }
+            ]
+        });
+
+        let resolved = resolve_libraries(
+            &mc_root.join("libraries"),
+            &version_json,
+            &RuleContext::current(),
+            &[],
+        );
+        assert_eq!(resolved.missing_native_entries.len(), 2);
+        assert_eq!(
+            resolved.missing_native_libraries,
+            vec!["lwjgl-platform", "jinput-platform"]
+        );
+
+        let candidates = legacy_natives_candidates(&mc_root, &["1.5.2", "1.5.2"]);
+        assert_eq!(candidates.len(), 2);
+        let found = find_legacy_natives_dir(&candidates, &resolved.missing_native_libraries);
+        assert_eq!(found.as_deref(), Some(legacy_dir.as_path()));
+
+        let natives_dir = mc_root.join("natives");
+        assert_eq!(
+            copy_legacy_natives(&legacy_dir, &natives_dir).expect("copy"),
+            2
+        );
+        assert!(natives_dir.join(native_file("lwjgl64")).is_file());
+
+        fs::remove_file(legacy_dir.join(native_file("jinput-raw_64"))).expect("remove jinput");
+        assert!(find_legacy_natives_dir(&candidates, &resolved.missing_native_libraries).is_none());
+
+        let _ = fs::remove_dir_all(mc_root);
+    }
+
     #[test]
     fn maven_key_distinguishes_classifier() {
         let principal = json!({ "name": "org.lwjgl:lwjgl:3.3.3" });
@@ -5002,4 +9193,878 @@ mod tests {
 
         assert_eq!(seen.len(), 4);
     }
+
+    fn write_truncated_native_jar(dir: &Path, file_name: &str) -> std::path::PathBuf {
+        use std::io::Write;
+
+        let jar_path = dir.join(file_name);
+        let mut writer = zip::ZipWriter::new(fs::File::create(&jar_path).expect("crear jar"));
+        writer
+            .start_file("liblwjgl.so", zip::write::SimpleFileOptions::default())
+            .expect("entrada zip");
+        writer.write_all(&[7u8; 4096]).expect("escribir entrada");
+        writer.finish().expect("cerrar zip");
+
+        let bytes = fs::read(&jar_path).expect("leer jar");
+        fs::write(&jar_path, &bytes[..bytes.len() / 2]).expect("truncar jar");
+        jar_path
+    }
+
+    #[test]
+    fn extract_natives_skips_corrupt_jar_for_other_platform() {
+        let dir = test_temp_dir("natives-corrupt-other");
+        let other_platform = if cfg!(target_os = "linux") {
+            "lwjgl-3.3.3-natives-windows.jar"
+        } else {
+            "lwjgl-3.3.3-natives-linux.jar"
+        };
+        let jar_path = write_truncated_native_jar(&dir, other_platform);
+        let natives = vec![NativeJarEntry {
+            path: jar_path.display().to_string(),
+            url: None,
+            sha1: None,
+        }];
+
+        let mut logs = Vec::new();
+        let skipped = extract_natives(&natives, &dir.join("natives"), &mut logs)
+            .expect("un jar no requerido no debe abortar el lanzamiento");
+        assert_eq!(skipped, vec![jar_path.display().to_string()]);
+        assert!(logs.iter().any(|line| line.contains("se omite")));
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn extract_natives_fails_with_path_and_url_for_required_corrupt_jar() {
+        let dir = test_temp_dir("natives-corrupt-required");
+        let jar_path = write_truncated_native_jar(&dir, "lwjgl-3.3.3-natives-custom.jar");
+        let natives = vec![NativeJarEntry {
+            path: jar_path.display().to_string(),
+            url: Some("https://libraries.example.invalid/lwjgl-natives.jar".to_string()),
+            sha1: None,
+        }];
+
+        let mut logs = Vec::new();
+        let err = extract_natives(&natives, &dir.join("natives"), &mut logs)
+            .expect_err("un jar requerido corrupto sin sha1 debe fallar");
+        assert!(err.contains(&jar_path.display().to_string()));
+        assert!(err.contains("https://libraries.example.invalid/lwjgl-natives.jar"));
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    fn write_valid_jar(path: &Path) {
+        use std::io::Write;
+
+        fs::create_dir_all(path.parent().expect("padre")).expect("crear carpeta");
+        let mut writer = zip::ZipWriter::new(fs::File::create(path).expect("crear jar"));
+        writer
+            .start_file(
+                "META-INF/MANIFEST.MF",
+                zip::write::SimpleFileOptions::default(),
+            )
+            .expect("entrada zip");
+        writer
+            .write_all(b"Manifest-Version: 1.0\n")
+            .expect("escribir entrada");
+        writer.finish().expect("cerrar zip");
+    }
+
+    fn write_jar_with_entries(path: &Path, entries: &[&str]) {
+        use std::io::Write;
+
+        fs::create_dir_all(path.parent().expect("padre")).expect("crear carpeta");
+        let mut writer = zip::ZipWriter::new(fs::File::create(path).expect("crear jar"));
+        for entry in entries {
+            writer
+                .start_file(*entry, zip::write::SimpleFileOptions::default())
+                .expect("entrada zip");
+            writer.write_all(b"x").expect("escribir entrada");
+        }
+        writer.finish().expect("cerrar zip");
+    }
+
+    #[test]
+    fn jar_inspection_finds_loader_main_class_with_a_single_probe() {
+        let dir = test_temp_dir("jar-inspection-knot");
+        let main_class = "net.fabricmc.loader.impl.launch.knot.KnotClient";
+        let mut jars = (0..6)
+            .map(|index| {
+                let jar = dir.join(format!("com/example/lib{index}/1.0/lib{index}-1.0.jar"));
+                write_jar_with_entries(&jar, &["com/example/Lib.class", "liblib.so"]);
+                jar
+            })
+            .collect::<Vec<_>>();
+        let loader_jar = dir.join("net/fabricmc/fabric-loader/0.15.11/fabric-loader-0.15.11.jar");
+        write_jar_with_entries(
+            &loader_jar,
+            &["net/fabricmc/loader/impl/launch/knot/KnotClient.class"],
+        );
+        jars.push(loader_jar.clone());
+
+        let inspection = inspect_launch_jars(&jars, &jars, main_class);
+        assert_eq!(inspection.main_class_jar.as_ref(), Some(&loader_jar));
+        assert_eq!(inspection.main_class_probes, 1);
+        assert_eq!(inspection.archives_opened, jars.len());
+        assert_eq!(inspection.jars_with_natives(), 6);
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn jar_inspection_keeps_errors_for_missing_class_and_corrupt_jar() {
+        let dir = test_temp_dir("jar-inspection-corrupt");
+        let valid = dir.join("libraries/valid-1.0.jar");
+        write_valid_jar(&valid);
+        let corrupt = dir.join("libraries/corrupt-1.0.jar");
+        fs::write(&corrupt, b"no es un zip").expect("jar corrupto");
+        let jars = vec![valid.clone(), corrupt.clone()];
+
+        let inspection = inspect_launch_jars(&jars, &jars, "net.fabricmc.loader.Missing");
+        assert!(inspection.main_class_jar.is_none());
+        assert_eq!(inspection.archives_opened, 2);
+
+        let err = ensure_main_class_present_in_jar(
+            &valid,
+            inspection.check(&valid),
+            "net.fabricmc.loader.Missing",
+        )
+        .expect_err("clase ausente");
+        assert!(err.starts_with("La clase principal net.fabricmc.loader.Missing no existe"));
+
+        let entries = jars
+            .iter()
+            .map(|jar| jar.display().to_string())
+            .collect::<Vec<_>>();
+        let err = finalize_classpath_and_natives(
+            &entries,
+            &[],
+            &dir.join("natives"),
+            Some(&inspection),
+            &mut Vec::new(),
+        )
+        .expect_err("jar corrupto");
+        assert_eq!(
+            err,
+            validate_jars_as_zip(std::slice::from_ref(&corrupt)).expect_err("mismo error")
+        );
+        assert!(err.starts_with("Jar inválido/corrupto"));
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn pooled_inspection_caps_open_handles_and_retries_transient_opens() {
+        let dir = test_temp_dir("jar-inspection-pool");
+        let jars = (0..24)
+            .map(|index| {
+                let jar = dir.join(format!("libraries/lib{index}-1.0.jar"));
+                write_valid_jar(&jar);
+                jar
+            })
+            .collect::<Vec<_>>();
+        let stats = JarOpenStats::default();
+        let checks = inspect_jars_pooled(&jars, &stats);
+        assert_eq!(checks.len(), jars.len());
+        assert!(checks
+            .iter()
+            .all(|check| matches!(check, JarCheck::Valid { .. })));
+        let peak = stats.peak_open_handles.load(Ordering::SeqCst);
+        assert!((1..=JAR_INSPECTION_WORKERS).contains(&peak));
+        assert_eq!(stats.open_handles.load(Ordering::SeqCst), 0);
+
+        let retries = AtomicUsize::new(0);
+        let mut attempts = 0;
+        let opened = retry_transient_open(
+            &retries,
+            |_| true,
+            || {
+                attempts += 1;
+                if attempts < 3 {
+                    Err(std::io::Error::other("sharing violation"))
+                } else {
+                    Ok(attempts)
+                }
+            },
+        );
+        assert_eq!(opened.expect("tercer intento"), 3);
+        assert_eq!(retries.load(Ordering::SeqCst), 2);
+
+        let retries = AtomicUsize::new(0);
+        let started = std::time::Instant::now();
+        let err = retry_transient_open::<()>(
+            &retries,
+            |_| true,
+            || Err(std::io::Error::other("sigue bloqueado")),
+        )
+        .expect_err("se agotan los reintentos");
+        assert_eq!(err.to_string(), "sigue bloqueado");
+        assert_eq!(retries.load(Ordering::SeqCst), 3);
+        assert!(started.elapsed() < Duration::from_millis(600));
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    /// Ensambla el mismo árbol por la ruta de redirección (classpath multi-directorio) y por
+    /// la ruta propia (`resolve_libraries`) y devuelve ambos resultados. `extra_entries` se
+    /// añade al classpath de las dos, como lo haría un jar que se coló tras el merge.
+    fn finalize_both_paths(
+        libraries_dir: &Path,
+        versions_dir: &Path,
+        version_json: &serde_json::Value,
+        natives_root: &Path,
+        extra_entries: &[String],
+    ) -> (Result<Vec<String>, String>, Result<Vec<String>, String>) {
+        let sep = if cfg!(target_os = "windows") {
+            ";"
+        } else {
+            ":"
+        };
+        let redirect_classpath = build_classpath_multi(
+            version_json,
+            &[libraries_dir.to_path_buf()],
+            versions_dir,
+            "1.20.1",
+        )
+        .expect("classpath de redirección");
+        let redirect_entries = redirect_classpath
+            .split(sep)
+            .map(ToOwned::to_owned)
+            .chain(extra_entries.iter().cloned())
+            .collect::<Vec<_>>();
+        let redirect = finalize_redirect_classpath(
+            &redirect_entries,
+            &[libraries_dir.to_path_buf()],
+            version_json,
+            &natives_root.join("redirect-natives"),
+            &mut Vec::new(),
+        );
+
+        let resolved = resolve_libraries(libraries_dir, version_json, &RuleContext::current(), &[]);
+        let mut owned_entries = resolved.classpath_entries.clone();
+        owned_entries.push(
+            versions_dir
+                .join("1.20.1")
+                .join("1.20.1.jar")
+                .display()
+                .to_string(),
+        );
+        owned_entries.extend(extra_entries.iter().cloned());
+        let owned = finalize_classpath_and_natives(
+            &owned_entries,
+            &resolved.native_jars,
+            &natives_root.join("owned-natives"),
+            None,
+            &mut Vec::new(),
+        );
+        (redirect, owned)
+    }
+
+    #[test]
+    fn redirect_prism_tree_fails_like_owned_instance() {
+        let root = test_temp_dir("redirect-prism-tree");
+        let prism = root.join("PrismLauncher");
+        let libraries_dir = prism.join("libraries");
+        let versions_dir = prism.join("versions");
+        write_valid_jar(&versions_dir.join("1.20.1").join("1.20.1.jar"));
+        write_valid_jar(&libraries_dir.join("com/example/good/1.0/good-1.0.jar"));
+        // Sin natives la extracción aborta antes de llegar a la comprobación de duplicados.
+        let natives_path = "org/lwjgl/lwjgl/3.3.1/lwjgl-3.3.1-natives.jar";
+        write_valid_jar(&libraries_dir.join(natives_path));
+        let broken_jar = libraries_dir.join("com/example/broken/1.0/broken-1.0.jar");
+        fs::create_dir_all(broken_jar.parent().expect("padre")).expect("crear carpeta");
+        fs::write(&broken_jar, b"esto no es un zip").expect("escribir jar corrupto");
+
+        let library = |name: &str, path: &str| json!({ "name": name, "downloads": { "artifact": { "path": path } } });
+        let version_json = json!({
+            "id": "1.20.1",
+            "libraries": [
+                library("com.example:good:1.0", "com/example/good/1.0/good-1.0.jar"),
+                library("com.example:broken:1.0", "com/example/broken/1.0/broken-1.0.jar"),
+                {
+                    "name": "org.lwjgl:lwjgl:3.3.1:natives",
+                    "natives": { "linux": "natives", "windows": "natives", "osx": "natives" },
+                    "downloads": { "artifact": { "path": natives_path } }
+                },
+            ]
+        });
+
+        let (redirect, owned) =
+            finalize_both_paths(&libraries_dir, &versions_dir, &version_json, &root, &[]);
+        let redirect_err = redirect.expect_err("la redirección debe rechazar el jar corrupto");
+        assert!(redirect_err.starts_with("Jar inválido/corrupto"));
+        assert!(redirect_err.contains(&broken_jar.display().to_string()));
+        assert_eq!(Err(redirect_err), owned);
+
+        write_valid_jar(&broken_jar);
+        let good_jar = libraries_dir
+            .join("com/example/good/1.0/good-1.0.jar")
+            .display()
+            .to_string();
+        let (redirect, owned) = finalize_both_paths(
+            &libraries_dir,
+            &versions_dir,
+            &version_json,
+            &root,
+            &[good_jar],
+        );
+        let redirect_err = redirect.expect_err("la redirección debe rechazar duplicados");
+        assert!(redirect_err.contains("good-1.0.jar"));
+        assert_eq!(Err(redirect_err), owned);
+
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn launcher_root_for_instance_on_other_drive_uses_configured_root() {
+        // Simula raíz e instancia en "unidades" distintas con dos árboles temporales independientes.
+        let root_drive = test_temp_dir("launcher-root-drive-a");
+        let instance_drive = test_temp_dir("launcher-root-drive-b");
+        let launcher_root = root_drive.join("InterfaceLauncher");
+        let instance_path = instance_drive.join("games").join("instances").join("pack");
+        fs::create_dir_all(&launcher_root).expect("raíz");
+        fs::create_dir_all(&instance_path).expect("instancia");
+
+        let mut logs = Vec::new();
+        let resolved =
+            resolve_launcher_root_for_instance(&instance_path, Some(&launcher_root), &mut logs)
+                .expect("debe resolver la raíz configurada");
+
+        assert_eq!(resolved, launcher_root);
+        assert_ne!(resolved, instance_drive.join("games"));
+        assert!(logs.iter().any(|line| line.contains("Instancia externa")));
+
+        let _ = fs::remove_dir_all(root_drive);
+        let _ = fs::remove_dir_all(instance_drive);
+    }
+
+    #[test]
+    fn launcher_root_for_managed_instance_is_not_reported_as_external() {
+        let launcher_root = test_temp_dir("launcher-root-managed");
+        let instance_path = launcher_root.join("instances").join("pack");
+        fs::create_dir_all(&instance_path).expect("instancia");
+
+        let mut logs = Vec::new();
+        let resolved =
+            resolve_launcher_root_for_instance(&instance_path, Some(&launcher_root), &mut logs)
+                .expect("debe resolver");
+
+        assert_eq!(resolved, launcher_root);
+        assert!(logs.is_empty());
+
+        let _ = fs::remove_dir_all(launcher_root);
+    }
+
+    #[test]
+    fn launcher_root_for_instance_falls_back_to_legacy_layout_without_configured_root() {
+        let mut logs = Vec::new();
+        let resolved = resolve_launcher_root_for_instance(
+            Path::new("/data/InterfaceLauncher/instances/pack"),
+            None,
+            &mut logs,
+        )
+        .expect("debe resolver");
+
+        assert_eq!(resolved, Path::new("/data/InterfaceLauncher"));
+    }
+
+    #[test]
+    fn instance_health_reports_invalid_metadata_as_error() {
+        let root = test_temp_dir("interface-health-metadata");
+        fs::write(root.join(".instance.json"), "{ roto").expect("metadata");
+
+        let clock = MockClock::at("2024-05-01T12:00:00Z");
+        let health = compute_instance_health(&root.display().to_string(), &clock);
+
+        assert_eq!(health.status, "error");
+        assert_eq!(health.findings[0].code, "metadata_invalid");
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn instance_health_flags_missing_version_and_stale_verification() {
+        let root = test_temp_dir("interface-health-version");
+        let java = root.join("java");
+        fs::write(&java, "").expect("java");
+        let metadata = json!({
+            "name": "Demo",
+            "group": "Default",
+            "minecraftVersion": "1.20.1",
+            "versionId": "1.20.1",
+            "loader": "vanilla",
+            "loaderVersion": "",
+            "ramMb": 2048,
+            "javaArgs": [],
+            "javaPath": java.display().to_string(),
+            "javaRuntime": "java17",
+            "internalUuid": "demo",
+            "lastUsed": null,
+        });
+        fs::write(root.join(".instance.json"), metadata.to_string()).expect("metadata");
+        fs::write(
+            root.join(VERIFICATION_MARKER_FILE),
+            r#"{"verifiedAt":"2024-05-01T12:00:00Z","versionId":"1.20.1"}"#,
+        )
+        .expect("marker");
+
+        let clock = MockClock::at("2024-05-31T12:00:00Z");
+        let fresh = compute_instance_health(&root.display().to_string(), &clock);
+        assert!(fresh
+            .findings
+            .iter()
+            .all(|finding| finding.code != "verification_stale"));
+
+        clock.advance(chrono::Duration::days(1));
+        let health = compute_instance_health(&root.display().to_string(), &clock);
+        let codes = health
+            .findings
+            .iter()
+            .map(|finding| finding.code.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(health.status, "error");
+        assert_eq!(codes, vec!["version_missing", "verification_stale"]);
+        let _ = fs::remove_dir_all(root);
+    }
+
+    /// `versions/` con un Forge efectivo, su vanilla, un Forge viejo y una carpeta rota.
+    fn versions_fixture(label: &str) -> (std::path::PathBuf, InstanceMetadata) {
+        let root = test_temp_dir(label);
+        let versions = root.join("minecraft/versions");
+        for (id, json) in [
+            ("1.20.1", json!({"id": "1.20.1"}).to_string()),
+            (
+                "1.20.1-forge-47.2.0",
+                json!({"id": "1.20.1-forge-47.2.0", "inheritsFrom": "1.20.1"}).to_string(),
+            ),
+            (
+                "1.20.1-forge-47.1.0",
+                json!({"id": "1.20.1-forge-47.1.0", "inheritsFrom": "1.20.1"}).to_string(),
+            ),
+            ("roto", "{ no es json".to_string()),
+        ] {
+            fs::create_dir_all(versions.join(id)).expect("version dir");
+            fs::write(versions.join(id).join(format!("{id}.json")), json).expect("json");
+        }
+        let metadata = serde_json::from_value::<InstanceMetadata>(json!({
+            "name": "Forge",
+            "minecraftVersion": "1.20.1",
+            "versionId": "1.20.1-forge-47.2.0",
+            "loader": "forge",
+            "state": "READY",
+        }))
+        .expect("metadata");
+        (root, metadata)
+    }
+
+    #[test]
+    fn versions_listing_flags_effective_broken_and_parent() {
+        let (root, _) = versions_fixture("versions-list");
+        let versions =
+            list_versions(&root.join("minecraft"), "1.20.1-forge-47.2.0").expect("listado");
+
+        let ids = versions.iter().map(|v| v.id.as_str()).collect::<Vec<_>>();
+        assert_eq!(
+            ids,
+            vec![
+                "1.20.1",
+                "1.20.1-forge-47.1.0",
+                "1.20.1-forge-47.2.0",
+                "roto"
+            ]
+        );
+        let effective = versions.iter().find(|v| v.effective).expect("efectiva");
+        assert_eq!(effective.id, "1.20.1-forge-47.2.0");
+        assert_eq!(effective.inherits_from.as_deref(), Some("1.20.1"));
+        assert!(!versions[3].json_valid);
+        assert!(versions.iter().all(|v| v.size_bytes > 0));
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn pruning_keeps_the_effective_chain_and_refuses_while_running() {
+        let (root, metadata) = versions_fixture("versions-prune");
+        let instance_root = root.display().to_string();
+
+        let refused = prune_versions(
+            &instance_root,
+            &metadata,
+            &["1.20.1-forge-47.2.0".to_string()],
+        )
+        .expect_err("falta el padre");
+        assert!(refused.contains("1.20.1"));
+
+        let keep = ["1.20.1-forge-47.2.0".to_string(), "1.20.1".to_string()];
+        let clock = MockClock::at("2024-05-01T20:00:00Z");
+        register_runtime_start(instance_root.clone(), &clock).expect("start");
+        assert!(prune_versions(&instance_root, &metadata, &keep).is_err());
+        register_runtime_exit(&instance_root, 1, Some(0), &clock);
+
+        let result = prune_versions(&instance_root, &metadata, &keep).expect("poda");
+        assert_eq!(result.removed, vec!["1.20.1-forge-47.1.0", "roto"]);
+        assert!(result.freed_bytes > 0);
+        assert!(root.join("minecraft/versions/1.20.1").is_dir());
+        assert!(!root.join("minecraft/versions/roto").exists());
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn runtime_exit_reports_session_lasted_90_minutes() {
+        let clock = MockClock::at("2024-05-01T20:00:00Z");
+        let instance_root = "test://runtime-session-90-minutes".to_string();
+        register_runtime_start(instance_root.clone(), &clock).expect("start");
+
+        clock.advance(chrono::Duration::minutes(30));
+        let uptime = running_instances_snapshot(&clock)
+            .expect("snapshot")
+            .into_iter()
+            .find(|running| running.instance_root == instance_root)
+            .map(|running| running.uptime_secs);
+        assert_eq!(uptime, Some(30 * 60));
+
+        clock.advance(chrono::Duration::minutes(60));
+        let session_ms = register_runtime_exit(&instance_root, 4242, Some(0), &clock);
+        assert_eq!(session_ms, 90 * 60 * 1000);
+    }
+
+    #[test]
+    fn suspended_time_is_not_counted_as_playtime() {
+        let clock = MockClock::at("2024-05-01T20:00:00Z");
+        let instance_root = "test://runtime-session-suspended".to_string();
+        register_runtime_start(instance_root.clone(), &clock).expect("start");
+        let started_ms = clock.now_millis();
+
+        // El portátil duerme 8 horas a los 20 minutos de juego; la suspensión que empezó
+        // antes de la sesión no cuenta.
+        clock.advance(chrono::Duration::minutes(20));
+        let sleep_ms = clock.now_millis();
+        clock.advance(chrono::Duration::hours(8));
+        {
+            let mut registry = runtime_registry().lock().expect("registry");
+            let state = registry.get_mut(&instance_root).expect("state");
+            add_suspension(state, sleep_ms, clock.now_millis());
+            add_suspension(state, started_ms - 60_000, started_ms + 30_000);
+            assert_eq!(state.suspended_duration_ms, 8 * 60 * 60 * 1000 + 30_000);
+        }
+        clock.advance(chrono::Duration::minutes(10));
+
+        let session_ms = register_runtime_exit(&instance_root, 4242, Some(0), &clock);
+        assert_eq!(session_ms, 30 * 60 * 1000 - 30_000);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn monitored_child_exit_marks_redirect_instance_stopped() {
+        use std::{
+            collections::VecDeque,
+            io::{BufRead, BufReader},
+            process::{Command, Stdio},
+            sync::{Arc, Mutex},
+            thread,
+        };
+
+        fn tail_reader<R: std::io::Read + Send + 'static>(
+            pipe: R,
+            tail: Arc<Mutex<VecDeque<String>>>,
+        ) -> thread::JoinHandle<()> {
+            thread::spawn(move || {
+                for line in BufReader::new(pipe).lines().map_while(Result::ok) {
+                    tail.lock().expect("tail").push_back(line);
+                }
+            })
+        }
+
+        let clock = MockClock::at("2024-05-01T20:00:00Z");
+        let instance_root = "test://redirect-child-exit".to_string();
+        register_runtime_start(instance_root.clone(), &clock).expect("start");
+        let mut child = Command::new("sh")
+            .args([
+                "-c",
+                "echo iniciando; echo 'Exception in thread main' >&2; exit 3",
+            ])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .expect("proceso falso");
+        register_runtime_pid(&instance_root, child.id(), "/java/bin/java");
+        assert!(is_instance_running(instance_root.as_str()));
+
+        let tail = Arc::new(Mutex::new(VecDeque::new()));
+        let readers = vec![
+            tail_reader(child.stdout.take().expect("stdout"), Arc::clone(&tail)),
+            tail_reader(child.stderr.take().expect("stderr"), Arc::clone(&tail)),
+        ];
+        clock.advance(chrono::Duration::minutes(5));
+        let exit = wait_and_record_exit(&mut child, &instance_root, &readers, &tail, &clock);
+
+        assert!(!is_instance_running(instance_root.as_str()));
+        assert_eq!(exit.exit_code, Some(3));
+        assert_eq!(exit.session_ms, 5 * 60 * 1000);
+        let recorded_tail = runtime_registry()
+            .lock()
+            .expect("registry")
+            .get(&instance_root)
+            .map(|state| state.stderr_tail.clone())
+            .unwrap_or_default();
+        assert!(recorded_tail.contains(&"Exception in thread main".to_string()));
+
+        let account = SessionAccount {
+            profile_id: "alex-id".to_string(),
+            profile_name: "Alex".to_string(),
+        };
+        let event = runtime_exit_payload(&instance_root, &exit, &account);
+        assert_eq!(event["instanceRoot"], instance_root.as_str());
+        assert_eq!(event["exitCode"], 3);
+        assert_eq!(event["sessionMs"], 5 * 60 * 1000);
+        assert_eq!(event["profileName"], "Alex");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn card_stats_report_unreadable_subfolders_as_partial() {
+        use std::os::unix::fs::PermissionsExt;
+
+        // root ignora los permisos y la carpeta seguiría siendo legible.
+        // SAFETY: `geteuid` no recibe argumentos ni puede fallar.
+        if unsafe { libc::geteuid() } == 0 {
+            return;
+        }
+        let root = std::env::temp_dir().join(format!(
+            "interface-card-stats-partial-{}",
+            std::process::id()
+        ));
+        let mods = root.join("minecraft").join("mods");
+        let locked = root.join("minecraft").join("saves");
+        fs::create_dir_all(&mods).expect("mods");
+        fs::create_dir_all(&locked).expect("saves");
+        fs::write(mods.join("a.jar"), b"jar").expect("jar");
+        fs::set_permissions(&locked, fs::Permissions::from_mode(0o000)).expect("chmod");
+
+        let stats = compute_card_stats(&root, None, None);
+        fs::set_permissions(&locked, fs::Permissions::from_mode(0o755)).expect("chmod");
+        let stats = stats.expect("stats parciales");
+        assert!(stats.partial);
+        assert_eq!(stats.mods_count, 1);
+        assert!(stats
+            .warnings
+            .iter()
+            .any(|warning| warning.contains("saves")));
+
+        fs::set_permissions(&root, fs::Permissions::from_mode(0o000)).expect("chmod");
+        let unreadable = compute_card_stats(&root, None, None);
+        fs::set_permissions(&root, fs::Permissions::from_mode(0o755)).expect("chmod");
+        assert!(matches!(
+            unreadable,
+            Err(CardStatsError::Unreadable(ref err)) if err.code == "INSTANCE_UNREADABLE"
+        ));
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn missing_redirect_source_on_removable_mount_is_disconnected() {
+        assert_eq!(
+            unreadable_source_error(Path::new("/media/steve/USB/.minecraft")).code,
+            "SOURCE_DISCONNECTED"
+        );
+        assert_eq!(
+            unreadable_source_error(Path::new("/home/steve/borrado/.minecraft")).code,
+            "SOURCE_MISSING"
+        );
+    }
+
+    /// `.instance.json` tal como lo escribían las primeras versiones al crear la instancia.
+    const CREATION_TIME_METADATA: &str = r#"{
+        "name": "Survival 1.12",
+        "group": "Mods",
+        "minecraftVersion": "1.12.2",
+        "loader": "forge",
+        "loaderVersion": "14.23.5.2860",
+        "ramMb": 3072,
+        "javaArgs": ["-XX:+UseG1GC"],
+        "javaPath": "/opt/java8/bin/java",
+        "javaRuntime": "java8",
+        "lastUsed": null,
+        "internalUuid": "6f1d2c1e-0b5e-4a53-9f0e-2d8c3a1b7e10"
+    }"#;
+
+    #[test]
+    fn creation_time_metadata_is_upgraded_once_and_round_trips() {
+        let root = test_temp_dir("metadata-upgrade");
+        let version_id = "1.12.2-forge-14.23.5.2860";
+        let version_dir = root.join("minecraft/versions").join(version_id);
+        fs::create_dir_all(&version_dir).expect("versions");
+        fs::write(
+            version_dir.join(format!("{version_id}.json")),
+            json!({"id": version_id, "inheritsFrom": "1.12.2"}).to_string(),
+        )
+        .expect("version json");
+        fs::write(root.join(".instance.json"), CREATION_TIME_METADATA).expect("metadata");
+
+        let metadata = read_instance_metadata(root.display().to_string()).expect("carga");
+        assert_eq!(metadata.version_id, version_id);
+        assert_eq!(metadata.required_java_major, 8);
+        assert_eq!(metadata.state, "READY");
+        assert!(!metadata.created_at.is_empty());
+        assert_eq!(metadata.ram_mb, 3072);
+        assert_eq!(
+            metadata.internal_uuid,
+            "6f1d2c1e-0b5e-4a53-9f0e-2d8c3a1b7e10"
+        );
+
+        // Leer no escribe: la actualización se guarda una vez en la migración de arranque.
+        assert_eq!(
+            fs::read_to_string(root.join(".instance.json")).expect("sin tocar"),
+            CREATION_TIME_METADATA
+        );
+        let root_str = root.display().to_string();
+        let upgraded = metadata_writer(&root_str)
+            .persist_upgrade_blocking(&root_str, &SequentialIds::default())
+            .expect("migración");
+        assert!(upgraded.contains(&"versionId"));
+        assert!(!upgraded.contains(&"internalUuid"));
+
+        let persisted = fs::read_to_string(root.join(".instance.json")).expect("persistida");
+        let mut reread: InstanceMetadata =
+            serde_json::from_str(&persisted).expect("formato actual");
+        assert!(upgrade_instance_metadata(&root, &mut reread).is_empty());
+        let reserialized = serde_json::to_string_pretty(&reread).expect("serializa");
+        assert_eq!(reserialized, persisted);
+
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn minimal_metadata_gets_defaults_and_a_stable_uuid() {
+        let root = test_temp_dir("metadata-minimal");
+        fs::write(
+            root.join(".instance.json"),
+            r#"{"name": "Vanilla", "minecraftVersion": "1.20.1"}"#,
+        )
+        .expect("metadata");
+
+        let first = read_instance_metadata(root.display().to_string()).expect("carga");
+        assert_eq!(first.loader, "vanilla");
+        assert_eq!(first.ram_mb, 4096);
+        assert_eq!(first.state, "READY");
+        assert_eq!(first.required_java_major, 17);
+        assert_eq!(first.java_runtime, "java17");
+        // Sin versions/ en disco no se inventa un version_id.
+        assert!(first.version_id.is_empty());
+        // El uuid no se inventa en cada lectura; lo asigna la migración con el generador.
+        assert!(first.internal_uuid.is_empty());
+
+        let root_str = root.display().to_string();
+        let writer = metadata_writer(&root_str);
+        let ids = SequentialIds::default();
+        let upgraded = writer
+            .persist_upgrade_blocking(&root_str, &ids)
+            .expect("migración");
+        assert!(upgraded.contains(&"internalUuid"));
+        assert!(writer
+            .persist_upgrade_blocking(&root_str, &ids)
+            .expect("segunda pasada")
+            .is_empty());
+
+        let second = read_instance_metadata(root_str.clone()).expect("relectura");
+        let third = read_instance_metadata(root_str).expect("relectura");
+        assert_eq!(second.internal_uuid, "id-1");
+        assert_eq!(third.internal_uuid, second.internal_uuid);
+        assert_eq!(third.created_at, second.created_at);
+
+        let _ = fs::remove_dir_all(root);
+    }
+
+    /// validate → plan → spawn contra los servicios simulados y una clase main trivial en
+    /// lugar del juego. Necesita `java` (11+, ejecuta el fuente directamente) en el PATH.
+    #[cfg(feature = "dev-auth")]
+    #[test]
+    fn dev_auth_e2e_validates_plans_and_spawns_against_mock_services() {
+        use super::{
+            resolve_launch_arguments, sanitize_uuid, validate_official_minecraft_auth,
+            validate_required_online_launch_flags,
+        };
+        use crate::domain::auth::mock_server::{
+            MockAuthServer, MOCK_MINECRAFT_TOKEN, MOCK_PROFILE_ID, MOCK_PROFILE_NAME,
+            MOCK_REFRESH_TOKEN,
+        };
+
+        let server = MockAuthServer::start();
+        server.install_env();
+        let clock = MockClock::at("2024-05-01T10:00:00Z");
+        let session = LaunchAuthSession {
+            profile_id: "069a79f4-44e9-4726-a5be-fca90e38aaf5".to_string(),
+            profile_name: MOCK_PROFILE_NAME.to_string(),
+            minecraft_access_token: "caducado".to_string(),
+            // Caducado: obliga a recorrer MSA → XBL → XSTS → login de Minecraft.
+            minecraft_access_token_expires_at: Some(0),
+            microsoft_refresh_token: Some(MOCK_REFRESH_TOKEN.to_string()),
+            premium_verified: true,
+            persisted: false,
+        };
+        let mut logs = Vec::new();
+        let verified = validate_official_minecraft_auth(&session, &clock, &mut logs)
+            .expect("auth contra el servidor simulado");
+        assert_eq!(verified.minecraft_access_token, MOCK_MINECRAFT_TOKEN);
+        assert_eq!(verified.profile_id, MOCK_PROFILE_ID);
+        assert!(logs
+            .iter()
+            .any(|line| line.contains("entitlements/mcstore")));
+
+        let dir = test_temp_dir("dev-auth-e2e");
+        let main_source = dir.join("Main.java");
+        fs::write(
+            &main_source,
+            "public class Main { public static void main(String[] args) { System.out.println(\"E2E \" + String.join(\" \", args)); } }\n",
+        )
+        .expect("Main.java");
+        let version_json = json!({
+            "mainClass": main_source.display().to_string(),
+            "arguments": {
+                "jvm": ["-Dfile.encoding=UTF-8"],
+                "game": [
+                    "--username", "${auth_player_name}",
+                    "--uuid", "${auth_uuid}",
+                    "--accessToken", "${auth_access_token}",
+                    "--userType", "${user_type}",
+                    "--versionType", "${version_type}"
+                ]
+            }
+        });
+        let mut context = launch_context_for_tests();
+        context.auth_player_name = verified.profile_name.clone();
+        context.auth_uuid = sanitize_uuid(&verified.profile_id);
+        context.auth_access_token = verified.minecraft_access_token.clone();
+        let resolved = resolve_launch_arguments(&version_json, &context, &RuleContext::current())
+            .expect("plan");
+        validate_required_online_launch_flags(&resolved.game, &context).expect("flags online");
+
+        if !java_on_path() {
+            return;
+        }
+        let (mut command, _) = build_java_command(
+            Path::new("java"),
+            &resolved.jvm,
+            &resolved.main_class,
+            &resolved.game,
+            &dir,
+            false,
+        );
+        let output = command.output().expect("spawn");
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        assert!(
+            stdout.contains(&format!("--username {MOCK_PROFILE_NAME}")),
+            "{stdout}"
+        );
+        assert!(
+            stdout.contains(&format!("--accessToken {MOCK_MINECRAFT_TOKEN}")),
+            "{stdout}"
+        );
+        let _ = fs::remove_dir_all(dir);
+    }
 }
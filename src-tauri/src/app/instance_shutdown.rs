@@ -0,0 +1,192 @@
+// Cierre cooperativo de instancias.
+
+use std::{thread, time::Duration};
+
+use tauri::AppHandle;
+
+use crate::{
+    app::{
+        instance_service::{
+            begin_runtime_close, force_terminate_runtime, is_runtime_session_running,
+        },
+        trusted_root::resolve_trusted_instance_root,
+    },
+    platform::processes::request_process_close,
+};
+
+pub const DEFAULT_CLOSE_GRACE: Duration = Duration::from_secs(30);
+const MAX_CLOSE_GRACE: Duration = Duration::from_secs(300);
+const CLOSE_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Proceso que se puede cerrar por las buenas o por las malas.
+pub(crate) trait ClosableProcess {
+    /// Pide el cierre; `false` si la petición no se pudo entregar.
+    fn request_close(&self) -> bool;
+    fn has_exited(&self) -> bool;
+    fn force_kill(&self);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CloseOutcome {
+    /// Terminó por su cuenta dentro del margen (el mundo se guardó).
+    Exited,
+    /// No respondió (o no se le pudo pedir) y se mató.
+    Forced,
+}
+
+/// Pide el cierre y espera hasta `grace` comprobando cada `poll`; si sigue vivo, lo mata.
+pub(crate) fn close_cooperatively(
+    process: &dyn ClosableProcess,
+    grace: Duration,
+    poll: Duration,
+) -> CloseOutcome {
+    if process.request_close() {
+        let checks = (grace.as_millis() / poll.as_millis().max(1)).max(1);
+        for _ in 0..checks {
+            if process.has_exited() {
+                return CloseOutcome::Exited;
+            }
+            thread::sleep(poll);
+        }
+        if process.has_exited() {
+            return CloseOutcome::Exited;
+        }
+    }
+    process.force_kill();
+    CloseOutcome::Forced
+}
+
+/// Sesión registrada en el runtime; su salida la detecta el monitor de la instancia.
+struct RuntimeProcess {
+    instance_root: String,
+    pid: u32,
+}
+
+impl ClosableProcess for RuntimeProcess {
+    fn request_close(&self) -> bool {
+        request_process_close(self.pid)
+    }
+
+    fn has_exited(&self) -> bool {
+        !is_runtime_session_running(&self.instance_root, self.pid)
+    }
+
+    fn force_kill(&self) {
+        if is_runtime_session_running(&self.instance_root, self.pid) {
+            let _ = force_terminate_runtime(&self.instance_root);
+        }
+    }
+}
+
+fn close_grace(grace_seconds: Option<u64>) -> Duration {
+    grace_seconds
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_CLOSE_GRACE)
+        .min(MAX_CLOSE_GRACE)
+}
+
+/// Pide a Minecraft que guarde y salga; pasados `grace_seconds` (30 s) se fuerza el cierre.
+#[tauri::command]
+pub fn close_instance(
+    app: AppHandle,
+    instance_root: String,
+    grace_seconds: Option<u64>,
+) -> Result<String, String> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    let pid = begin_runtime_close(instance_root.as_str())?;
+    let grace = close_grace(grace_seconds);
+    let process = RuntimeProcess {
+        instance_root: instance_root.as_str().to_string(),
+        pid,
+    };
+    thread::spawn(
+        move || match close_cooperatively(&process, grace, CLOSE_POLL_INTERVAL) {
+            CloseOutcome::Exited => {
+                log::info!("✔ La instancia (PID {pid}) se cerró guardando el mundo.")
+            }
+            CloseOutcome::Forced => log::warn!(
+                "⚠ La instancia (PID {pid}) no se cerró en {} s; se forzó el cierre.",
+                grace.as_secs()
+            ),
+        },
+    );
+    Ok(format!(
+        "Se pidió a Minecraft que guarde y salga (PID {pid}); se forzará en {} s si no responde.",
+        grace.as_secs()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::{Cell, RefCell};
+
+    use super::*;
+
+    struct MockProcess {
+        deliverable: bool,
+        exits_after_checks: Option<u32>,
+        checks: Cell<u32>,
+        calls: RefCell<Vec<&'static str>>,
+    }
+
+    impl MockProcess {
+        fn new(deliverable: bool, exits_after_checks: Option<u32>) -> Self {
+            Self {
+                deliverable,
+                exits_after_checks,
+                checks: Cell::new(0),
+                calls: RefCell::new(Vec::new()),
+            }
+        }
+    }
+
+    impl ClosableProcess for MockProcess {
+        fn request_close(&self) -> bool {
+            self.calls.borrow_mut().push("request");
+            self.deliverable
+        }
+
+        fn has_exited(&self) -> bool {
+            self.checks.set(self.checks.get() + 1);
+            self.exits_after_checks
+                .is_some_and(|limit| self.checks.get() > limit)
+        }
+
+        fn force_kill(&self) {
+            self.calls.borrow_mut().push("kill");
+        }
+    }
+
+    #[test]
+    fn exits_within_grace_without_force_and_escalates_otherwise() {
+        let graceful = MockProcess::new(true, Some(3));
+        assert_eq!(
+            close_cooperatively(&graceful, Duration::from_millis(10), Duration::ZERO),
+            CloseOutcome::Exited
+        );
+        assert_eq!(*graceful.calls.borrow(), vec!["request"]);
+
+        let stuck = MockProcess::new(true, None);
+        assert_eq!(
+            close_cooperatively(&stuck, Duration::from_millis(10), Duration::ZERO),
+            CloseOutcome::Forced
+        );
+        assert_eq!(*stuck.calls.borrow(), vec!["request", "kill"]);
+        assert_eq!(stuck.checks.get(), 11);
+    }
+
+    #[test]
+    fn undeliverable_request_falls_back_to_force_and_grace_is_capped() {
+        let windowless = MockProcess::new(false, Some(0));
+        assert_eq!(
+            close_cooperatively(&windowless, Duration::from_secs(30), Duration::ZERO),
+            CloseOutcome::Forced
+        );
+        assert_eq!(*windowless.calls.borrow(), vec!["request", "kill"]);
+        assert_eq!(windowless.checks.get(), 0);
+
+        assert_eq!(close_grace(None), DEFAULT_CLOSE_GRACE);
+        assert_eq!(close_grace(Some(5)), Duration::from_secs(5));
+        assert_eq!(close_grace(Some(3_600)), MAX_CLOSE_GRACE);
+    }
+}
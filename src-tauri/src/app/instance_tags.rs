@@ -0,0 +1,225 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+use crate::app::{
+    instance_service::update_instance_metadata, launcher_service::list_instances_readonly,
+    trusted_root::resolve_trusted_instance_root,
+};
+
+pub const MAX_TAG_CHARS: usize = 32;
+pub const MAX_TAGS_PER_INSTANCE: usize = 20;
+
+/// Motivo por el que una etiqueta no se puede añadir.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TagError {
+    /// Tras recortar espacios la etiqueta queda vacía.
+    Empty,
+    /// Supera [`MAX_TAG_CHARS`] caracteres.
+    TooLong(String),
+    /// La instancia ya tiene [`MAX_TAGS_PER_INSTANCE`] etiquetas.
+    TooMany,
+    /// No se pudo leer o guardar la metadata de la instancia.
+    Metadata(String),
+}
+
+impl TagError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            TagError::Empty => "empty",
+            TagError::TooLong(_) => "too_long",
+            TagError::TooMany => "too_many",
+            TagError::Metadata(_) => "metadata",
+        }
+    }
+}
+
+impl From<String> for TagError {
+    fn from(err: String) -> Self {
+        TagError::Metadata(err)
+    }
+}
+
+impl std::fmt::Display for TagError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TagError::Empty => write!(f, "La etiqueta no puede estar vacía."),
+            TagError::TooLong(tag) => write!(
+                f,
+                "La etiqueta \"{tag}\" supera el máximo de {MAX_TAG_CHARS} caracteres."
+            ),
+            TagError::TooMany => write!(
+                f,
+                "Una instancia admite como máximo {MAX_TAGS_PER_INSTANCE} etiquetas."
+            ),
+            TagError::Metadata(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl serde::Serialize for TagError {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+        let mut state = serializer.serialize_struct("TagError", 2)?;
+        state.serialize_field("code", self.code())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.end()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TagUsage {
+    pub tag: String,
+    pub count: usize,
+}
+
+/// Recorta y pasa a minúsculas; rechaza etiquetas vacías o de más de [`MAX_TAG_CHARS`].
+pub fn normalize_tag(tag: &str) -> Result<String, TagError> {
+    let normalized = tag.trim().to_lowercase();
+    if normalized.is_empty() {
+        return Err(TagError::Empty);
+    }
+    if normalized.chars().count() > MAX_TAG_CHARS {
+        return Err(TagError::TooLong(normalized));
+    }
+    Ok(normalized)
+}
+
+/// Normaliza una lista leída de disco o de una importación: descarta las inválidas y
+/// duplicadas y respeta el máximo por instancia.
+pub fn sanitize_tags<I, S>(tags: I) -> Vec<String>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut sanitized = Vec::new();
+    for tag in tags {
+        if let Ok(tag) = normalize_tag(tag.as_ref()) {
+            if !sanitized.contains(&tag) && sanitized.len() < MAX_TAGS_PER_INSTANCE {
+                sanitized.push(tag);
+            }
+        }
+    }
+    sanitized
+}
+
+/// Semántica AND: la instancia debe tener todas las etiquetas del filtro.
+pub fn matches_all_tags(instance_tags: &[String], filter_tags: &[String]) -> bool {
+    filter_tags.iter().all(|tag| instance_tags.contains(tag))
+}
+
+fn emit_tags_changed(app: &AppHandle, instance_root: &str, tags: &[String]) {
+    let _ = app.emit(
+        "instances_changed",
+        serde_json::json!({
+            "action": "tags_updated",
+            "instancePath": instance_root,
+            "tags": tags,
+        }),
+    );
+}
+
+#[tauri::command]
+pub fn add_instance_tag(
+    app: AppHandle,
+    instance_root: String,
+    tag: String,
+) -> Result<Vec<String>, TagError> {
+    let instance_root =
+        resolve_trusted_instance_root(&app, &instance_root).map_err(TagError::Metadata)?;
+    let tag = normalize_tag(&tag)?;
+    let metadata = update_instance_metadata(instance_root.as_str(), |metadata| {
+        if metadata.tags.contains(&tag) {
+            return Ok(());
+        }
+        if metadata.tags.len() >= MAX_TAGS_PER_INSTANCE {
+            return Err(TagError::TooMany);
+        }
+        metadata.tags.push(tag);
+        Ok(())
+    })?;
+    emit_tags_changed(&app, instance_root.as_str(), &metadata.tags);
+    Ok(metadata.tags)
+}
+
+#[tauri::command]
+pub fn remove_instance_tag(
+    app: AppHandle,
+    instance_root: String,
+    tag: String,
+) -> Result<Vec<String>, TagError> {
+    let instance_root =
+        resolve_trusted_instance_root(&app, &instance_root).map_err(TagError::Metadata)?;
+    let tag = tag.trim().to_lowercase();
+    let mut removed = false;
+    let metadata = update_instance_metadata(instance_root.as_str(), |metadata| {
+        let before = metadata.tags.len();
+        metadata.tags.retain(|existing| *existing != tag);
+        removed = metadata.tags.len() != before;
+        Ok::<(), TagError>(())
+    })?;
+    if removed {
+        emit_tags_changed(&app, instance_root.as_str(), &metadata.tags);
+    }
+    Ok(metadata.tags)
+}
+
+/// Todas las etiquetas en uso con el número de instancias que las tienen, de más a
+/// menos usada.
+#[tauri::command]
+pub fn list_all_tags(app: AppHandle) -> Result<Vec<TagUsage>, String> {
+    let mut counts = BTreeMap::<String, usize>::new();
+    for instance in list_instances_readonly(&app)? {
+        for tag in instance.tags {
+            *counts.entry(tag).or_default() += 1;
+        }
+    }
+    let mut usage = counts
+        .into_iter()
+        .map(|(tag, count)| TagUsage { tag, count })
+        .collect::<Vec<_>>();
+    usage.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.tag.cmp(&b.tag)));
+    Ok(usage)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_and_validates_tags() {
+        assert_eq!(
+            normalize_tag("  With Shaders "),
+            Ok("with shaders".to_string())
+        );
+        assert_eq!(normalize_tag("   "), Err(TagError::Empty));
+        assert_eq!(
+            normalize_tag(&"x".repeat(33)).map_err(|err| err.code()),
+            Err("too_long")
+        );
+        assert!(normalize_tag(&"ñ".repeat(32)).is_ok());
+
+        let many = (0..25)
+            .map(|index| format!("tag-{index}"))
+            .collect::<Vec<_>>();
+        let sanitized = sanitize_tags(
+            many.iter()
+                .chain(["TAG-1".to_string(), String::new()].iter()),
+        );
+        assert_eq!(sanitized.len(), MAX_TAGS_PER_INSTANCE);
+        assert_eq!(sanitized.iter().filter(|tag| *tag == "tag-1").count(), 1);
+    }
+
+    #[test]
+    fn filter_uses_and_semantics() {
+        let tags = vec!["hardcore".to_string(), "1.21".to_string()];
+        assert!(matches_all_tags(&tags, &[]));
+        assert!(matches_all_tags(&tags, &["1.21".to_string()]));
+        assert!(!matches_all_tags(
+            &tags,
+            &["1.21".to_string(), "with shaders".to_string()]
+        ));
+    }
+}
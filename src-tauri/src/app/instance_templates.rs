@@ -0,0 +1,430 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use reqwest::blocking::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tauri::AppHandle;
+
+use crate::{
+    app::{
+        instance_service::{effective_mods_dir, read_instance_metadata},
+        instance_upgrade::{
+            build_upgrade_client, download_mod_file, list_enabled_mod_jars, modrinth_hash_query,
+            modrinth_loader_names, primary_modrinth_file, MODRINTH_VERSION_FILES_URL,
+        },
+    },
+    infrastructure::{checksum::sha1::compute_file_sha1, filesystem::paths::resolve_launcher_root},
+    shared::result::AppResult,
+};
+
+const TEMPLATES_FILE: &str = "templates.json";
+const MODRINTH_PROJECT_URL: &str = "https://api.modrinth.com/v2/project";
+
+/// Plantilla de creación de instancias: loader, argumentos y mods de Modrinth a instalar.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct InstanceTemplate {
+    pub name: String,
+    #[serde(default)]
+    pub loader: String,
+    #[serde(default)]
+    pub java_args: Vec<String>,
+    #[serde(default)]
+    pub recommended_ram_mb: Option<u32>,
+    /// Slugs o ids de proyectos de Modrinth.
+    #[serde(default)]
+    pub modrinth_projects: Vec<String>,
+    #[serde(default)]
+    pub built_in: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveInstanceTemplateRequest {
+    pub name: String,
+    #[serde(default)]
+    pub loader: Option<String>,
+    #[serde(default)]
+    pub java_args: Option<Vec<String>>,
+    #[serde(default)]
+    pub recommended_ram_mb: Option<u32>,
+    #[serde(default)]
+    pub modrinth_projects: Option<Vec<String>>,
+    /// Si se indica, loader, argumentos, RAM y mods se capturan de esta instancia.
+    #[serde(default)]
+    pub from_instance: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveInstanceTemplateResult {
+    pub template: InstanceTemplate,
+    /// Mods de la instancia de origen que no se pudieron asociar a un proyecto de Modrinth.
+    pub unresolved_mods: Vec<String>,
+}
+
+/// Resultado de instalar un mod de plantilla; los fallos no abortan la creación.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateModOutcome {
+    pub project: String,
+    pub file_name: Option<String>,
+    pub error: Option<String>,
+}
+
+fn built_in_templates() -> Vec<InstanceTemplate> {
+    vec![
+        InstanceTemplate {
+            name: "vanilla+sodium".to_string(),
+            loader: "fabric".to_string(),
+            java_args: Vec::new(),
+            recommended_ram_mb: Some(4096),
+            modrinth_projects: vec!["sodium".to_string()],
+            built_in: true,
+        },
+        InstanceTemplate {
+            name: "fabric dev".to_string(),
+            loader: "fabric".to_string(),
+            java_args: vec![
+                "-XX:+UseG1GC".to_string(),
+                "-Dmixin.debug.export=true".to_string(),
+            ],
+            recommended_ram_mb: Some(6144),
+            modrinth_projects: vec!["fabric-api".to_string(), "modmenu".to_string()],
+            built_in: true,
+        },
+    ]
+}
+
+fn templates_path(app: &AppHandle) -> AppResult<PathBuf> {
+    Ok(resolve_launcher_root(app)?
+        .join("config")
+        .join(TEMPLATES_FILE))
+}
+
+fn load_user_templates(path: &Path) -> AppResult<Vec<InstanceTemplate>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let raw = fs::read_to_string(path)
+        .map_err(|err| format!("No se pudo leer {}: {err}", path.display()))?;
+    let mut templates: Vec<InstanceTemplate> = serde_json::from_str(&raw)
+        .map_err(|err| format!("Plantillas inválidas en {}: {err}", path.display()))?;
+    for template in &mut templates {
+        template.built_in = false;
+    }
+    Ok(templates)
+}
+
+fn write_user_templates(path: &Path, templates: &[InstanceTemplate]) -> AppResult<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|err| format!("No se pudo crear {}: {err}", parent.display()))?;
+    }
+    let raw = serde_json::to_string_pretty(templates)
+        .map_err(|err| format!("No se pudieron serializar las plantillas: {err}"))?;
+    fs::write(path, raw).map_err(|err| format!("No se pudo guardar {}: {err}", path.display()))
+}
+
+fn is_built_in_name(name: &str) -> bool {
+    built_in_templates()
+        .iter()
+        .any(|template| template.name.eq_ignore_ascii_case(name.trim()))
+}
+
+/// Inserta o reemplaza (por nombre, sin distinguir mayúsculas) una plantilla de usuario.
+fn upsert_template(
+    templates: &mut Vec<InstanceTemplate>,
+    template: InstanceTemplate,
+) -> AppResult<()> {
+    if template.name.trim().is_empty() {
+        return Err("El nombre de la plantilla es obligatorio.".to_string());
+    }
+    if is_built_in_name(&template.name) {
+        return Err(format!(
+            "'{}' es una plantilla integrada y no se puede sobrescribir.",
+            template.name
+        ));
+    }
+    templates.retain(|existing| !existing.name.eq_ignore_ascii_case(template.name.trim()));
+    templates.push(template);
+    templates.sort_by_key(|template| template.name.to_lowercase());
+    Ok(())
+}
+
+fn all_templates(user_templates: Vec<InstanceTemplate>) -> Vec<InstanceTemplate> {
+    let mut templates = built_in_templates();
+    templates.extend(user_templates);
+    templates
+}
+
+/// Busca una plantilla (integrada o de usuario) por nombre.
+pub fn find_instance_template(app: &AppHandle, name: &str) -> AppResult<InstanceTemplate> {
+    let user_templates = load_user_templates(&templates_path(app)?)?;
+    all_templates(user_templates)
+        .into_iter()
+        .find(|template| template.name.eq_ignore_ascii_case(name.trim()))
+        .ok_or_else(|| format!("No existe la plantilla '{name}'."))
+}
+
+/// Asocia los mods activos de una instancia a proyectos de Modrinth por SHA1.
+/// Devuelve `(proyectos, mods_sin_resolver)`.
+fn capture_instance_mods(
+    client: &Client,
+    mods_dir: &Path,
+) -> AppResult<(Vec<String>, Vec<String>)> {
+    let mut hashed = Vec::new();
+    for jar in list_enabled_mod_jars(mods_dir)? {
+        let file_name = jar
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        hashed.push((file_name, compute_file_sha1(&jar)?));
+    }
+    if hashed.is_empty() {
+        return Ok((Vec::new(), Vec::new()));
+    }
+
+    let hashes = hashed
+        .iter()
+        .map(|(_, sha1)| sha1.clone())
+        .collect::<Vec<_>>();
+    let lookup = modrinth_hash_query(
+        client,
+        MODRINTH_VERSION_FILES_URL,
+        &json!({ "hashes": hashes, "algorithm": "sha1" }),
+    )
+    .map_err(|err| format!("No se pudieron resolver los mods en Modrinth: {err}"))?;
+
+    let mut projects: Vec<String> = Vec::new();
+    let mut unresolved = Vec::new();
+    for (file_name, sha1) in hashed {
+        match lookup
+            .get(&sha1)
+            .and_then(|version| version.get("project_id"))
+            .and_then(Value::as_str)
+        {
+            Some(project) if !projects.iter().any(|known| known == project) => {
+                projects.push(project.to_string())
+            }
+            Some(_) => {}
+            None => unresolved.push(file_name),
+        }
+    }
+    Ok((projects, unresolved))
+}
+
+/// Descarga la versión más reciente de un proyecto de Modrinth compatible con el
+/// loader y la versión de Minecraft. Devuelve el nombre del archivo instalado.
+fn install_modrinth_project(
+    client: &Client,
+    project: &str,
+    loader: &str,
+    minecraft_version: &str,
+    mods_dir: &Path,
+) -> AppResult<String> {
+    let loaders = serde_json::to_string(&modrinth_loader_names(loader)).unwrap_or_default();
+    let game_versions = serde_json::to_string(&[minecraft_version]).unwrap_or_default();
+    let versions: Vec<Value> = client
+        .get(format!("{MODRINTH_PROJECT_URL}/{project}/version"))
+        .query(&[("loaders", loaders), ("game_versions", game_versions)])
+        .send()
+        .and_then(|res| res.error_for_status())
+        .and_then(|res| res.json())
+        .map_err(|err| format!("No se pudo consultar {project} en Modrinth: {err}"))?;
+
+    let file = versions
+        .first()
+        .and_then(primary_modrinth_file)
+        .ok_or_else(|| {
+            format!("{project} no tiene versiones para {loader} en Minecraft {minecraft_version}.")
+        })?;
+    let url = file
+        .get("url")
+        .and_then(Value::as_str)
+        .ok_or_else(|| format!("{project}: archivo sin url de descarga."))?;
+    let file_name = file
+        .get("filename")
+        .and_then(Value::as_str)
+        .filter(|name| !name.contains(['/', '\\']) && !name.trim().is_empty())
+        .ok_or_else(|| format!("{project}: nombre de archivo inválido."))?;
+    let sha1 = file
+        .get("hashes")
+        .and_then(|hashes| hashes.get("sha1"))
+        .and_then(Value::as_str);
+
+    fs::create_dir_all(mods_dir)
+        .map_err(|err| format!("No se pudo crear {}: {err}", mods_dir.display()))?;
+    download_mod_file(client, url, &mods_dir.join(file_name), sha1)?;
+    Ok(file_name.to_string())
+}
+
+/// Instala los mods de la plantilla en `minecraft_root/mods`. Cada fallo se reporta en
+/// su `TemplateModOutcome` sin interrumpir el resto.
+pub fn install_template_mods(
+    template: &InstanceTemplate,
+    minecraft_root: &Path,
+    loader: &str,
+    minecraft_version: &str,
+    on_progress: &mut dyn FnMut(u64, u64, String),
+) -> Vec<TemplateModOutcome> {
+    let total = template.modrinth_projects.len() as u64;
+    let client = match build_upgrade_client() {
+        Ok(client) => client,
+        Err(err) => {
+            return template
+                .modrinth_projects
+                .iter()
+                .map(|project| TemplateModOutcome {
+                    project: project.clone(),
+                    file_name: None,
+                    error: Some(err.clone()),
+                })
+                .collect();
+        }
+    };
+
+    let mods_dir = minecraft_root.join("mods");
+    let mut outcomes = Vec::with_capacity(template.modrinth_projects.len());
+    for (index, project) in template.modrinth_projects.iter().enumerate() {
+        on_progress(
+            index as u64,
+            total,
+            format!("Instalando mod de plantilla {project}..."),
+        );
+        let outcome = match install_modrinth_project(
+            &client,
+            project,
+            loader,
+            minecraft_version,
+            &mods_dir,
+        ) {
+            Ok(file_name) => TemplateModOutcome {
+                project: project.clone(),
+                file_name: Some(file_name),
+                error: None,
+            },
+            Err(err) => {
+                log::warn!("⚠ Plantilla '{}': {err}", template.name);
+                TemplateModOutcome {
+                    project: project.clone(),
+                    file_name: None,
+                    error: Some(err),
+                }
+            }
+        };
+        outcomes.push(outcome);
+    }
+    on_progress(total, total, "Mods de plantilla procesados.".to_string());
+    outcomes
+}
+
+#[tauri::command]
+pub fn list_instance_templates(app: AppHandle) -> Result<Vec<InstanceTemplate>, String> {
+    Ok(all_templates(load_user_templates(&templates_path(&app)?)?))
+}
+
+#[tauri::command]
+pub async fn save_instance_template(
+    app: AppHandle,
+    template: SaveInstanceTemplateRequest,
+) -> Result<SaveInstanceTemplateResult, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let path = templates_path(&app)?;
+        let mut unresolved_mods = Vec::new();
+        let mut saved = InstanceTemplate {
+            name: template.name.trim().to_string(),
+            loader: template.loader.unwrap_or_default(),
+            java_args: template.java_args.unwrap_or_default(),
+            recommended_ram_mb: template.recommended_ram_mb,
+            modrinth_projects: template.modrinth_projects.unwrap_or_default(),
+            built_in: false,
+        };
+
+        if let Some(instance_root) = template.from_instance {
+            let metadata = read_instance_metadata(instance_root.clone())?;
+            let (projects, unresolved) = capture_instance_mods(
+                &build_upgrade_client()?,
+                &effective_mods_dir(Path::new(&instance_root)),
+            )?;
+            saved.loader = metadata.loader;
+            saved.java_args = metadata.java_args;
+            saved.recommended_ram_mb = Some(metadata.ram_mb);
+            saved.modrinth_projects = projects;
+            unresolved_mods = unresolved;
+        }
+
+        let mut templates = load_user_templates(&path)?;
+        upsert_template(&mut templates, saved.clone())?;
+        write_user_templates(&path, &templates)?;
+        Ok(SaveInstanceTemplateResult {
+            template: saved,
+            unresolved_mods,
+        })
+    })
+    .await
+    .map_err(|err| format!("Falló la tarea de guardado de plantilla: {err}"))?
+}
+
+#[tauri::command]
+pub fn delete_instance_template(app: AppHandle, name: String) -> Result<(), String> {
+    if is_built_in_name(&name) {
+        return Err(format!(
+            "'{name}' es una plantilla integrada y no se puede eliminar."
+        ));
+    }
+    let path = templates_path(&app)?;
+    let mut templates = load_user_templates(&path)?;
+    let before = templates.len();
+    templates.retain(|template| !template.name.eq_ignore_ascii_case(name.trim()));
+    if templates.len() == before {
+        return Err(format!("No existe la plantilla '{name}'."));
+    }
+    write_user_templates(&path, &templates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::test_support::test_temp_dir;
+
+    fn user_template(name: &str) -> InstanceTemplate {
+        InstanceTemplate {
+            name: name.to_string(),
+            loader: "fabric".to_string(),
+            java_args: vec!["-XX:+UseG1GC".to_string()],
+            recommended_ram_mb: Some(4096),
+            modrinth_projects: vec!["sodium".to_string(), "iris".to_string()],
+            built_in: false,
+        }
+    }
+
+    #[test]
+    fn user_templates_round_trip_and_replace_by_name() {
+        let root = test_temp_dir("interface-templates");
+        let path = root.join("config").join(TEMPLATES_FILE);
+
+        let mut templates = load_user_templates(&path).expect("sin archivo");
+        upsert_template(&mut templates, user_template("Shaders")).expect("guardar");
+        let mut renamed = user_template("shaders");
+        renamed.modrinth_projects = vec!["iris".to_string()];
+        upsert_template(&mut templates, renamed.clone()).expect("reemplazar");
+        write_user_templates(&path, &templates).expect("escribir");
+
+        let loaded = load_user_templates(&path).expect("leer");
+        assert_eq!(loaded, vec![renamed]);
+        assert_eq!(all_templates(loaded).len(), built_in_templates().len() + 1);
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn built_in_templates_cannot_be_overwritten() {
+        let mut templates = Vec::new();
+        assert!(upsert_template(&mut templates, user_template("Vanilla+Sodium")).is_err());
+        assert!(upsert_template(&mut templates, user_template("   ")).is_err());
+        assert!(templates.is_empty());
+    }
+}
@@ -0,0 +1,1041 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::{Mutex, OnceLock},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use reqwest::blocking::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tauri::{AppHandle, Emitter};
+
+use crate::{
+    app::instance_dedup::detach_link_at,
+    app::instance_locks::{check_metadata_lock, InstanceEditError},
+    app::instance_service::{
+        copy_dir_recursive, effective_mods_dir, instance_local_mods_dir, is_instance_running,
+        read_instance_metadata, shared_mods_dir,
+    },
+    app::trusted_root::{resolve_trusted_instance_root, ValidatedInstanceRoot},
+    domain::java::java_requirement::determine_required_java,
+    infrastructure::{
+        checksum::sha1::compute_file_sha1,
+        filesystem::{
+            file_ops::write_file_replacing,
+            paths::resolve_launcher_root,
+            verified_copy::{copy_dir_with, verified_copy_enabled, VerifiedCopier},
+        },
+        http::{rate_limit, tls::with_custom_roots},
+    },
+    services::{
+        instance_builder::{
+            build_instance_structure, persist_instance_metadata, InstanceBuildProgress,
+        },
+        java_installer::ensure_embedded_java,
+    },
+    shared::result::AppResult,
+};
+
+const UPGRADE_SNAPSHOTS_DIR: &str = ".upgrade-snapshots";
+const UPGRADE_SNAPSHOT_MANIFEST: &str = "snapshot.json";
+const FABRIC_LOADERS_URL: &str = "https://meta.fabricmc.net/v2/versions/loader";
+const QUILT_LOADERS_URL: &str = "https://meta.quiltmc.org/v3/versions/loader";
+const FORGE_PROMOTIONS_URL: &str =
+    "https://files.minecraftforge.net/net/minecraftforge/forge/promotions_slim.json";
+const NEOFORGE_VERSIONS_URL: &str =
+    "https://maven.neoforged.net/api/maven/versions/releases/net/neoforged/neoforge";
+pub(crate) const MODRINTH_VERSION_FILES_URL: &str = "https://api.modrinth.com/v2/version_files";
+const MODRINTH_VERSION_FILES_UPDATE_URL: &str = "https://api.modrinth.com/v2/version_files/update";
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpgradeModCompatibility {
+    pub file_name: String,
+    pub sha1: String,
+    pub project_id: Option<String>,
+    pub current_version: Option<String>,
+    /// `compatible`, `incompatible` o `unknown` (no se encontró en Modrinth).
+    pub status: String,
+    pub target_version: Option<String>,
+    pub target_file_name: Option<String>,
+    pub target_download_url: Option<String>,
+    pub target_sha1: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstanceUpgradePlan {
+    pub plan_id: String,
+    pub instance_root: String,
+    pub current_minecraft_version: String,
+    pub target_minecraft_version: String,
+    pub loader: String,
+    pub current_loader_version: String,
+    pub target_loader_version: Option<String>,
+    pub loader_available: bool,
+    pub current_java_major: u32,
+    pub required_java_major: Option<u32>,
+    pub java_change: bool,
+    pub mods: Vec<UpgradeModCompatibility>,
+    pub compatible_mods: usize,
+    pub incompatible_mods: usize,
+    pub unknown_mods: usize,
+    pub blockers: Vec<String>,
+    pub warnings: Vec<String>,
+    pub go: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstanceUpgradeResult {
+    pub instance_root: String,
+    pub minecraft_version: String,
+    pub version_id: String,
+    pub loader_version: String,
+    pub updated_mods: Vec<String>,
+    pub disabled_mods: Vec<String>,
+    pub logs: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct InstanceUpgradeProgressEvent {
+    instance_root: String,
+    step: String,
+    message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UpgradeSnapshotManifest {
+    created_at: u64,
+    had_mods_dir: bool,
+    versions_before: Vec<String>,
+}
+
+struct UpgradeSnapshot {
+    path: PathBuf,
+    manifest: UpgradeSnapshotManifest,
+}
+
+static UPGRADE_PLANS: OnceLock<Mutex<HashMap<String, InstanceUpgradePlan>>> = OnceLock::new();
+
+fn upgrade_plans() -> &'static Mutex<HashMap<String, InstanceUpgradePlan>> {
+    UPGRADE_PLANS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+pub(crate) fn build_upgrade_client() -> AppResult<Client> {
+    with_custom_roots(Client::builder())
+        .user_agent("Interface-2/0.1")
+        .timeout(Duration::from_secs(30))
+        .build()
+        .map_err(|err| format!("No se pudo inicializar cliente HTTP: {err}"))
+}
+
+fn now_unix_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|value| value.as_secs())
+        .unwrap_or(0)
+}
+
+fn emit_upgrade_progress(app: &AppHandle, instance_root: &str, step: &str, message: String) {
+    let _ = app.emit(
+        "instance_upgrade_progress",
+        InstanceUpgradeProgressEvent {
+            instance_root: instance_root.to_string(),
+            step: step.to_string(),
+            message,
+        },
+    );
+}
+
+/// Prefijo de versiones de NeoForge para una versión de Minecraft
+/// (`1.21.1` -> `21.1.`, `1.21` -> `21.0.`).
+fn neoforge_version_prefix(minecraft_version: &str) -> Option<String> {
+    let mut parts = minecraft_version.trim().split('.');
+    if parts.next()? != "1" {
+        return None;
+    }
+    let minor = parts.next()?.parse::<u32>().ok()?;
+    let patch = match parts.next() {
+        Some(raw) => raw.parse::<u32>().ok()?,
+        None => 0,
+    };
+    Some(format!("{minor}.{patch}."))
+}
+
+pub(crate) fn modrinth_loader_names(loader: &str) -> Vec<String> {
+    match loader.trim().to_ascii_lowercase().as_str() {
+        "quilt" | "quilit" => vec!["quilt".to_string(), "fabric".to_string()],
+        other => vec![other.to_string()],
+    }
+}
+
+/// Consulta las APIs de metadatos del loader y devuelve el build recomendado
+/// para la versión destino, o `None` si el loader no publica builds para ella.
+fn latest_loader_build(
+    client: &Client,
+    loader: &str,
+    minecraft_version: &str,
+) -> AppResult<Option<String>> {
+    match loader.trim().to_ascii_lowercase().as_str() {
+        "" | "vanilla" => Ok(Some(String::new())),
+        "fabric" | "quilt" | "quilit" => {
+            let base = if loader.eq_ignore_ascii_case("fabric") {
+                FABRIC_LOADERS_URL
+            } else {
+                QUILT_LOADERS_URL
+            };
+            let entries: Value = client
+                .get(format!("{base}/{minecraft_version}"))
+                .send()
+                .and_then(|res| res.error_for_status())
+                .map_err(|err| format!("No se pudo consultar builds de {loader}: {err}"))?
+                .json()
+                .map_err(|err| format!("Respuesta inválida de metadatos de {loader}: {err}"))?;
+            let entries = entries.as_array().cloned().unwrap_or_default();
+            let stable = entries.iter().find(|entry| {
+                entry
+                    .get("loader")
+                    .and_then(|loader| loader.get("stable"))
+                    .and_then(Value::as_bool)
+                    .unwrap_or(false)
+            });
+            Ok(stable
+                .or_else(|| entries.first())
+                .and_then(|entry| entry.get("loader"))
+                .and_then(|loader| loader.get("version"))
+                .and_then(Value::as_str)
+                .map(str::to_string))
+        }
+        "forge" => {
+            let promotions: Value = client
+                .get(FORGE_PROMOTIONS_URL)
+                .send()
+                .and_then(|res| res.error_for_status())
+                .map_err(|err| format!("No se pudo consultar promociones de Forge: {err}"))?
+                .json()
+                .map_err(|err| format!("Respuesta inválida de promociones de Forge: {err}"))?;
+            let promos = promotions.get("promos");
+            Ok(["recommended", "latest"].iter().find_map(|kind| {
+                promos
+                    .and_then(|promos| promos.get(format!("{minecraft_version}-{kind}")))
+                    .and_then(Value::as_str)
+                    .map(str::to_string)
+            }))
+        }
+        "neoforge" => {
+            let Some(prefix) = neoforge_version_prefix(minecraft_version) else {
+                return Ok(None);
+            };
+            let payload: Value = client
+                .get(NEOFORGE_VERSIONS_URL)
+                .send()
+                .and_then(|res| res.error_for_status())
+                .map_err(|err| format!("No se pudo consultar versiones de NeoForge: {err}"))?
+                .json()
+                .map_err(|err| format!("Respuesta inválida de versiones de NeoForge: {err}"))?;
+            let matching = payload
+                .get("versions")
+                .and_then(Value::as_array)
+                .cloned()
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|value| value.as_str().map(str::to_string))
+                .filter(|version| version.starts_with(&prefix))
+                .collect::<Vec<_>>();
+            Ok(matching
+                .iter()
+                .rev()
+                .find(|version| !version.contains("beta"))
+                .or_else(|| matching.last())
+                .cloned())
+        }
+        other => Err(format!("Loader no soportado todavía: {other}")),
+    }
+}
+
+pub(crate) fn list_enabled_mod_jars(mods_dir: &Path) -> AppResult<Vec<PathBuf>> {
+    if !mods_dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut jars = fs::read_dir(mods_dir)
+        .map_err(|err| {
+            format!(
+                "No se pudo leer carpeta de mods {}: {err}",
+                mods_dir.display()
+            )
+        })?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .and_then(|ext| ext.to_str())
+                    .map(|ext| ext.eq_ignore_ascii_case("jar"))
+                    .unwrap_or(false)
+        })
+        .collect::<Vec<_>>();
+    jars.sort();
+    Ok(jars)
+}
+
+/// POST de búsqueda por hashes a Modrinth, respetando su límite de peticiones.
+pub(crate) fn modrinth_hash_query(
+    client: &Client,
+    url: &str,
+    body: &Value,
+) -> AppResult<HashMap<String, Value>> {
+    rate_limit::send_blocking(url, || client.post(url).json(body))
+        .map_err(|err| err.describe("No se pudo contactar con Modrinth"))?
+        .error_for_status()
+        .and_then(|res| res.json::<HashMap<String, Value>>())
+        .map_err(|err| err.to_string())
+}
+
+pub(crate) fn primary_modrinth_file(version: &Value) -> Option<&Value> {
+    let files = version.get("files").and_then(Value::as_array)?;
+    files
+        .iter()
+        .find(|file| {
+            file.get("primary")
+                .and_then(Value::as_bool)
+                .unwrap_or(false)
+        })
+        .or_else(|| files.first())
+}
+
+fn classify_mods(
+    client: &Client,
+    mods_dir: &Path,
+    loader: &str,
+    target_minecraft_version: &str,
+    warnings: &mut Vec<String>,
+) -> AppResult<Vec<UpgradeModCompatibility>> {
+    let jars = list_enabled_mod_jars(mods_dir)?;
+    if jars.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut hashed = Vec::with_capacity(jars.len());
+    for jar in jars {
+        let sha1 = compute_file_sha1(&jar)?;
+        let file_name = jar
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        hashed.push((file_name, sha1));
+    }
+    let hashes = hashed
+        .iter()
+        .map(|(_, sha1)| sha1.clone())
+        .collect::<Vec<_>>();
+
+    let lookup = modrinth_hash_query(
+        client,
+        MODRINTH_VERSION_FILES_URL,
+        &json!({ "hashes": hashes, "algorithm": "sha1" }),
+    );
+    let updates = modrinth_hash_query(
+        client,
+        MODRINTH_VERSION_FILES_UPDATE_URL,
+        &json!({
+            "hashes": hashes,
+            "algorithm": "sha1",
+            "loaders": modrinth_loader_names(loader),
+            "game_versions": [target_minecraft_version],
+        }),
+    );
+
+    let (lookup, updates) = match (lookup, updates) {
+        (Ok(lookup), Ok(updates)) => (lookup, updates),
+        (Err(err), _) | (_, Err(err)) => {
+            warnings.push(format!(
+                "No se pudo consultar compatibilidad de mods en Modrinth: {err}"
+            ));
+            (HashMap::new(), HashMap::new())
+        }
+    };
+
+    Ok(hashed
+        .into_iter()
+        .map(|(file_name, sha1)| {
+            let current = lookup.get(&sha1);
+            let target = updates.get(&sha1);
+            let target_file = target.and_then(primary_modrinth_file);
+            let status = if target.is_some() {
+                "compatible"
+            } else if current.is_some() {
+                "incompatible"
+            } else {
+                "unknown"
+            };
+            UpgradeModCompatibility {
+                file_name,
+                project_id: current
+                    .or(target)
+                    .and_then(|version| version.get("project_id"))
+                    .and_then(Value::as_str)
+                    .map(str::to_string),
+                current_version: current
+                    .and_then(|version| version.get("version_number"))
+                    .and_then(Value::as_str)
+                    .map(str::to_string),
+                status: status.to_string(),
+                target_version: target
+                    .and_then(|version| version.get("version_number"))
+                    .and_then(Value::as_str)
+                    .map(str::to_string),
+                target_file_name: target_file
+                    .and_then(|file| file.get("filename"))
+                    .and_then(Value::as_str)
+                    .map(str::to_string),
+                target_download_url: target_file
+                    .and_then(|file| file.get("url"))
+                    .and_then(Value::as_str)
+                    .map(str::to_string),
+                target_sha1: target_file
+                    .and_then(|file| file.get("hashes"))
+                    .and_then(|hashes| hashes.get("sha1"))
+                    .and_then(Value::as_str)
+                    .map(str::to_string),
+                sha1,
+            }
+        })
+        .collect())
+}
+
+fn build_upgrade_plan(
+    root: &ValidatedInstanceRoot,
+    target_minecraft_version: &str,
+) -> AppResult<InstanceUpgradePlan> {
+    let instance_root = root.as_str();
+    let instance_path = root.path();
+    let metadata = read_instance_metadata(instance_root.to_string())?;
+    let target = target_minecraft_version.trim().to_string();
+    if target.is_empty() {
+        return Err("Debes indicar la versión de Minecraft destino.".to_string());
+    }
+
+    let mut blockers = Vec::new();
+    let mut warnings = Vec::new();
+
+    if instance_path.join(".redirect.json").exists() {
+        blockers.push(
+            "La instancia es un acceso directo; actualiza la instancia de origen.".to_string(),
+        );
+    }
+    if target == metadata.minecraft_version {
+        blockers.push(format!(
+            "La instancia ya usa Minecraft {}.",
+            metadata.minecraft_version
+        ));
+    }
+
+    let required_java_major = match determine_required_java(&target, &metadata.loader) {
+        Ok(runtime) => Some(u32::from(runtime.major())),
+        Err(err) => {
+            blockers.push(format!("No se pudo determinar el Java requerido: {err}"));
+            None
+        }
+    };
+    let java_change = required_java_major
+        .map(|major| major != metadata.required_java_major)
+        .unwrap_or(false);
+    if let (true, Some(major)) = (java_change, required_java_major) {
+        warnings.push(format!(
+            "El runtime cambia de Java {} a Java {major}.",
+            metadata.required_java_major
+        ));
+    }
+
+    let client = build_upgrade_client()?;
+    let target_loader_version = match latest_loader_build(&client, &metadata.loader, &target) {
+        Ok(version) => version,
+        Err(err) => {
+            blockers.push(err);
+            None
+        }
+    };
+    let loader_available = target_loader_version.is_some();
+    if !loader_available && blockers.is_empty() {
+        blockers.push(format!(
+            "El loader {} no tiene builds publicados para Minecraft {target}.",
+            metadata.loader
+        ));
+    }
+
+    let loader_lower = metadata.loader.trim().to_ascii_lowercase();
+    let mods = if loader_lower.is_empty() || loader_lower == "vanilla" {
+        Vec::new()
+    } else {
+        classify_mods(
+            &client,
+            &effective_mods_dir(instance_path),
+            &metadata.loader,
+            &target,
+            &mut warnings,
+        )?
+    };
+    let compatible_mods = mods.iter().filter(|m| m.status == "compatible").count();
+    let incompatible_mods = mods.iter().filter(|m| m.status == "incompatible").count();
+    let unknown_mods = mods.iter().filter(|m| m.status == "unknown").count();
+    if let Some(shared) = shared_mods_dir(instance_path) {
+        warnings.push(format!(
+            "La carpeta de mods es compartida ({}); la actualización no cambiará sus mods.",
+            shared.display()
+        ));
+    }
+    if incompatible_mods > 0 {
+        warnings.push(format!(
+            "{incompatible_mods} mod(s) no tienen versión para {target} y quedarán desactivados."
+        ));
+    }
+    if unknown_mods > 0 {
+        warnings.push(format!(
+            "{unknown_mods} mod(s) no se encontraron en Modrinth; revisa su compatibilidad manualmente."
+        ));
+    }
+
+    Ok(InstanceUpgradePlan {
+        plan_id: uuid::Uuid::new_v4().to_string(),
+        instance_root: instance_root.to_string(),
+        current_minecraft_version: metadata.minecraft_version,
+        target_minecraft_version: target,
+        loader: metadata.loader,
+        current_loader_version: metadata.loader_version,
+        target_loader_version,
+        loader_available,
+        current_java_major: metadata.required_java_major,
+        required_java_major,
+        java_change,
+        mods,
+        compatible_mods,
+        incompatible_mods,
+        unknown_mods,
+        go: blockers.is_empty(),
+        blockers,
+        warnings,
+    })
+}
+
+fn list_version_dirs(minecraft_root: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(minecraft_root.join("versions")) else {
+        return Vec::new();
+    };
+    let mut names = entries
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().is_dir())
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .collect::<Vec<_>>();
+    names.sort();
+    names
+}
+
+/// Crea `.upgrade-snapshots/<segundos>[-n]`; `create_dir` falla si ya existe, así que dos
+/// snapshots en el mismo segundo nunca comparten carpeta.
+fn create_snapshot_dir(instance_root: &Path, created_at: u64) -> AppResult<PathBuf> {
+    let snapshots_root = instance_root.join(UPGRADE_SNAPSHOTS_DIR);
+    fs::create_dir_all(&snapshots_root).map_err(|err| {
+        format!(
+            "No se pudo crear snapshot de actualización {}: {err}",
+            snapshots_root.display()
+        )
+    })?;
+    for attempt in 0u32.. {
+        let name = if attempt == 0 {
+            created_at.to_string()
+        } else {
+            format!("{created_at}-{attempt}")
+        };
+        let path = snapshots_root.join(name);
+        match fs::create_dir(&path) {
+            Ok(()) => return Ok(path),
+            Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => continue,
+            Err(err) => {
+                return Err(format!(
+                    "No se pudo crear snapshot de actualización {}: {err}",
+                    path.display()
+                ))
+            }
+        }
+    }
+    unreachable!("el rango de intentos no tiene fin")
+}
+
+fn create_upgrade_snapshot(instance_root: &Path) -> AppResult<UpgradeSnapshot> {
+    let created_at = now_unix_secs();
+    let path = create_snapshot_dir(instance_root, created_at)?;
+
+    for file_name in [".instance.json", "instance.json"] {
+        let source = instance_root.join(file_name);
+        if source.is_file() {
+            fs::copy(&source, path.join(file_name))
+                .map_err(|err| format!("No se pudo respaldar {}: {err}", source.display()))?;
+        }
+    }
+
+    let minecraft_root = instance_root.join("minecraft");
+    // Solo los mods propios: la carpeta compartida es de varias instancias y no se restaura.
+    let mods_dir = instance_local_mods_dir(instance_root);
+    let had_mods_dir = mods_dir.is_dir();
+    if had_mods_dir {
+        copy_dir_recursive(&mods_dir, &path.join("mods"))?;
+    }
+
+    let manifest = UpgradeSnapshotManifest {
+        created_at,
+        had_mods_dir,
+        versions_before: list_version_dirs(&minecraft_root),
+    };
+    let manifest_path = path.join(UPGRADE_SNAPSHOT_MANIFEST);
+    fs::write(
+        &manifest_path,
+        serde_json::to_string_pretty(&manifest).map_err(|err| err.to_string())?,
+    )
+    .map_err(|err| format!("No se pudo guardar {}: {err}", manifest_path.display()))?;
+
+    Ok(UpgradeSnapshot { path, manifest })
+}
+
+/// Snapshot de metadata y mods reutilizable por otras operaciones destructivas (p. ej. el
+/// restablecimiento). Devuelve la carpeta del snapshot.
+pub(crate) fn snapshot_instance(instance_root: &Path) -> AppResult<PathBuf> {
+    create_upgrade_snapshot(instance_root).map(|snapshot| snapshot.path)
+}
+
+/// Borra un snapshot que ya no hace falta (la operación terminó bien).
+fn discard_upgrade_snapshot(snapshot: &UpgradeSnapshot) {
+    if let Err(err) = fs::remove_dir_all(&snapshot.path) {
+        log::warn!(
+            "⚠ No se pudo borrar el snapshot {}: {err}",
+            snapshot.path.display()
+        );
+    }
+}
+
+/// Restaura un snapshot creado con `snapshot_instance`.
+pub(crate) fn restore_instance_snapshot(
+    instance_root: &Path,
+    snapshot_path: &Path,
+) -> AppResult<()> {
+    let manifest_path = snapshot_path.join(UPGRADE_SNAPSHOT_MANIFEST);
+    let manifest = fs::read_to_string(&manifest_path)
+        .map_err(|err| format!("No se pudo leer {}: {err}", manifest_path.display()))
+        .and_then(|raw| {
+            serde_json::from_str::<UpgradeSnapshotManifest>(&raw)
+                .map_err(|err| format!("Snapshot inválido {}: {err}", manifest_path.display()))
+        })?;
+    restore_upgrade_snapshot(
+        instance_root,
+        &UpgradeSnapshot {
+            path: snapshot_path.to_path_buf(),
+            manifest,
+        },
+    )
+}
+
+fn restore_upgrade_snapshot(instance_root: &Path, snapshot: &UpgradeSnapshot) -> AppResult<()> {
+    for file_name in [".instance.json", "instance.json"] {
+        let backup = snapshot.path.join(file_name);
+        if backup.is_file() {
+            fs::copy(&backup, instance_root.join(file_name))
+                .map_err(|err| format!("No se pudo restaurar {}: {err}", backup.display()))?;
+        }
+    }
+
+    let minecraft_root = instance_root.join("minecraft");
+    let mods_dir = instance_local_mods_dir(instance_root);
+    if mods_dir.exists() {
+        fs::remove_dir_all(&mods_dir).map_err(|err| {
+            format!(
+                "No se pudo limpiar carpeta de mods {}: {err}",
+                mods_dir.display()
+            )
+        })?;
+    }
+    if snapshot.manifest.had_mods_dir {
+        // Restaurar sobre un disco que corrompe datos dejaría la instancia peor que antes.
+        let mut copier = VerifiedCopier::new(verified_copy_enabled(&mods_dir, None), |_| {});
+        copy_dir_with(&snapshot.path.join("mods"), &mods_dir, &mut copier)?;
+        copier.finish()?;
+    }
+
+    for version in list_version_dirs(&minecraft_root) {
+        if snapshot.manifest.versions_before.contains(&version) {
+            continue;
+        }
+        let version_dir = minecraft_root.join("versions").join(&version);
+        fs::remove_dir_all(&version_dir).map_err(|err| {
+            format!(
+                "No se pudo eliminar versión creada durante la actualización {}: {err}",
+                version_dir.display()
+            )
+        })?;
+    }
+    Ok(())
+}
+
+pub(crate) fn download_mod_file(
+    client: &Client,
+    url: &str,
+    target: &Path,
+    sha1: Option<&str>,
+) -> AppResult<()> {
+    let bytes = client
+        .get(url)
+        .send()
+        .and_then(|res| res.error_for_status())
+        .and_then(|res| res.bytes())
+        .map_err(|err| format!("No se pudo descargar {url}: {err}"))?;
+    if let Some(expected) = sha1 {
+        let actual = crate::infrastructure::checksum::sha1::sha1_hex(&bytes);
+        if !actual.eq_ignore_ascii_case(expected) {
+            return Err(format!(
+                "SHA1 inválido para {url} (esperado={expected}, obtenido={actual})."
+            ));
+        }
+    }
+    // El destino puede ser un enlace deduplicado: se separa y se escribe un archivo nuevo
+    // para no cambiar el mod de las demás instancias.
+    detach_link_at(target);
+    write_file_replacing(target, &bytes, true)
+}
+
+/// Sustituye los mods compatibles por su versión destino y desactiva los
+/// incompatibles. Devuelve `(actualizados, desactivados)`.
+fn swap_mod_jars(
+    mods_dir: &Path,
+    mods: &[UpgradeModCompatibility],
+    logs: &mut Vec<String>,
+) -> AppResult<(Vec<String>, Vec<String>)> {
+    let client = build_upgrade_client()?;
+    let mut updated = Vec::new();
+    let mut disabled = Vec::new();
+
+    for entry in mods {
+        let current_path = mods_dir.join(&entry.file_name);
+        match entry.status.as_str() {
+            "compatible" => {
+                if entry
+                    .target_sha1
+                    .as_deref()
+                    .is_some_and(|sha1| sha1.eq_ignore_ascii_case(&entry.sha1))
+                {
+                    continue;
+                }
+                let (Some(url), Some(file_name)) = (
+                    entry.target_download_url.as_deref(),
+                    entry.target_file_name.as_deref(),
+                ) else {
+                    continue;
+                };
+                let target_path = mods_dir.join(file_name);
+                download_mod_file(&client, url, &target_path, entry.target_sha1.as_deref())?;
+                if target_path != current_path && current_path.exists() {
+                    fs::remove_file(&current_path).map_err(|err| {
+                        format!(
+                            "No se pudo eliminar versión anterior {}: {err}",
+                            current_path.display()
+                        )
+                    })?;
+                }
+                logs.push(format!(
+                    "Mod actualizado: {} -> {file_name}",
+                    entry.file_name
+                ));
+                updated.push(file_name.to_string());
+            }
+            "incompatible" => {
+                if !current_path.exists() {
+                    continue;
+                }
+                let disabled_path = mods_dir.join(format!("{}.disabled", entry.file_name));
+                fs::rename(&current_path, &disabled_path).map_err(|err| {
+                    format!(
+                        "No se pudo desactivar mod incompatible {}: {err}",
+                        current_path.display()
+                    )
+                })?;
+                logs.push(format!("Mod incompatible desactivado: {}", entry.file_name));
+                disabled.push(entry.file_name.clone());
+            }
+            _ => {}
+        }
+    }
+
+    Ok((updated, disabled))
+}
+
+fn run_upgrade_steps(
+    app: &AppHandle,
+    plan: &InstanceUpgradePlan,
+    update_mods: bool,
+    logs: &mut Vec<String>,
+) -> AppResult<(String, String, Vec<String>, Vec<String>)> {
+    let instance_path = PathBuf::from(&plan.instance_root);
+    let minecraft_root = instance_path.join("minecraft");
+    let mut metadata = read_instance_metadata(plan.instance_root.clone())?;
+    let target_loader_version = plan
+        .target_loader_version
+        .clone()
+        .filter(|version| !version.is_empty())
+        .unwrap_or_else(|| metadata.loader_version.clone());
+
+    emit_upgrade_progress(
+        app,
+        &plan.instance_root,
+        "preparing_java",
+        "Preparando runtime Java...".to_string(),
+    );
+    let launcher_root = resolve_launcher_root(app)?;
+    let required_java = determine_required_java(&plan.target_minecraft_version, &metadata.loader)?;
+    let java_exec = ensure_embedded_java(&launcher_root, required_java, logs)?;
+
+    let effective_version_id = build_instance_structure(
+        &instance_path,
+        &minecraft_root,
+        &plan.target_minecraft_version,
+        &metadata.loader,
+        &target_loader_version,
+        &java_exec,
+        logs,
+        &mut |progress: InstanceBuildProgress| {
+            emit_upgrade_progress(app, &plan.instance_root, &progress.step, progress.message);
+        },
+    )?;
+
+    metadata.minecraft_version = plan.target_minecraft_version.clone();
+    metadata.version_id = effective_version_id.clone();
+    metadata.loader_version = target_loader_version.clone();
+    metadata.java_path = java_exec.display().to_string();
+    metadata.java_runtime = required_java.as_dir_name().to_string();
+    metadata.java_version = format!("{}.0.x", required_java.major());
+    metadata.required_java_major = u32::from(required_java.major());
+    persist_instance_metadata(&instance_path, &metadata, logs)?;
+
+    let shared_mods = shared_mods_dir(&instance_path);
+    if let (true, Some(shared)) = (update_mods, &shared_mods) {
+        logs.push(format!(
+            "⚠ La carpeta de mods es compartida ({}); sus mods no se actualizan para no afectar a otras instancias.",
+            shared.display()
+        ));
+    }
+    let (updated, disabled) = if update_mods && shared_mods.is_none() {
+        emit_upgrade_progress(
+            app,
+            &plan.instance_root,
+            "updating_mods",
+            "Actualizando mods compatibles...".to_string(),
+        );
+        swap_mod_jars(&instance_local_mods_dir(&instance_path), &plan.mods, logs)?
+    } else {
+        (Vec::new(), Vec::new())
+    };
+
+    Ok((
+        effective_version_id,
+        target_loader_version,
+        updated,
+        disabled,
+    ))
+}
+
+/// Genera un informe de viabilidad para mover la instancia a otra versión de
+/// Minecraft: cambio de Java, build del loader y compatibilidad de cada mod.
+#[tauri::command]
+pub async fn plan_instance_upgrade(
+    app: AppHandle,
+    instance_root: String,
+    target_minecraft_version: String,
+) -> Result<InstanceUpgradePlan, String> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    let plan = tauri::async_runtime::spawn_blocking(move || {
+        build_upgrade_plan(&instance_root, &target_minecraft_version)
+    })
+    .await
+    .map_err(|err| format!("Falló la tarea de planificación de actualización: {err}"))??;
+
+    upgrade_plans()
+        .lock()
+        .map_err(|_| "No se pudo bloquear registro de planes de actualización".to_string())?
+        .insert(plan.plan_id.clone(), plan.clone());
+    Ok(plan)
+}
+
+/// Aplica un plan de `plan_instance_upgrade`; si algún paso falla se restaura el snapshot.
+#[tauri::command]
+pub async fn apply_instance_upgrade(
+    app: AppHandle,
+    instance_root: String,
+    plan_id: String,
+    update_mods: bool,
+    override_lock: Option<bool>,
+) -> Result<InstanceUpgradeResult, InstanceEditError> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    let plan = upgrade_plans()
+        .lock()
+        .map_err(|_| "No se pudo bloquear registro de planes de actualización".to_string())?
+        .get(&plan_id)
+        .cloned()
+        .ok_or_else(|| format!("No existe el plan de actualización {plan_id}."))?;
+    if plan.instance_root != instance_root.as_str() {
+        return Err("El plan de actualización pertenece a otra instancia."
+            .to_string()
+            .into());
+    }
+    if !plan.go {
+        return Err(format!(
+            "El plan de actualización tiene bloqueos: {}",
+            plan.blockers.join(" | ")
+        )
+        .into());
+    }
+    if is_instance_running(instance_root.as_str()) {
+        return Err("No se puede actualizar una instancia en ejecución."
+            .to_string()
+            .into());
+    }
+    let metadata = read_instance_metadata(instance_root.to_string())?;
+    if metadata.minecraft_version != plan.current_minecraft_version {
+        return Err(
+            "La instancia cambió desde que se generó el plan; genera uno nuevo."
+                .to_string()
+                .into(),
+        );
+    }
+    let override_lock = override_lock.unwrap_or(false);
+    let mut locked_aspects = vec!["minecraft_version", "loader_version"];
+    if update_mods {
+        locked_aspects.push("mods");
+    }
+    for field in locked_aspects {
+        check_metadata_lock(
+            instance_root.as_str(),
+            &metadata,
+            field,
+            "apply_instance_upgrade",
+            override_lock,
+        )?;
+    }
+
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        let instance_path = PathBuf::from(&plan.instance_root);
+        let mut logs = Vec::new();
+
+        emit_upgrade_progress(
+            &app,
+            &plan.instance_root,
+            "snapshot",
+            "Creando snapshot de la instancia...".to_string(),
+        );
+        let snapshot = create_upgrade_snapshot(&instance_path)?;
+        logs.push(format!("Snapshot creado en {}", snapshot.path.display()));
+
+        match run_upgrade_steps(&app, &plan, update_mods, &mut logs) {
+            Ok((version_id, loader_version, updated_mods, disabled_mods)) => {
+                log::info!(
+                    "✔ Instancia {} actualizada a Minecraft {}",
+                    plan.instance_root,
+                    plan.target_minecraft_version
+                );
+                discard_upgrade_snapshot(&snapshot);
+                Ok(InstanceUpgradeResult {
+                    instance_root: plan.instance_root.clone(),
+                    minecraft_version: plan.target_minecraft_version.clone(),
+                    version_id,
+                    loader_version,
+                    updated_mods,
+                    disabled_mods,
+                    logs,
+                })
+            }
+            Err(err) => {
+                log::warn!(
+                    "⚠ Falló la actualización de {}: {err}. Restaurando snapshot.",
+                    plan.instance_root
+                );
+                match restore_upgrade_snapshot(&instance_path, &snapshot) {
+                    Ok(()) => Err(format!(
+                        "{err} (se restauró el snapshot {})",
+                        snapshot.path.display()
+                    )),
+                    Err(restore_err) => Err(format!(
+                        "{err} (no se pudo restaurar el snapshot {}: {restore_err})",
+                        snapshot.path.display()
+                    )),
+                }
+            }
+        }
+    })
+    .await
+    .map_err(|err| format!("Falló la tarea de actualización de instancia: {err}"))?;
+
+    if result.is_ok() {
+        if let Ok(mut plans) = upgrade_plans().lock() {
+            plans.remove(&plan_id);
+        }
+    }
+    result.map_err(InstanceEditError::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::test_support::test_temp_dir;
+
+    #[test]
+    fn neoforge_prefix_follows_minecraft_minor_and_patch() {
+        assert_eq!(neoforge_version_prefix("1.21.1").as_deref(), Some("21.1."));
+        assert_eq!(neoforge_version_prefix("1.21").as_deref(), Some("21.0."));
+        assert_eq!(neoforge_version_prefix("24w14a"), None);
+    }
+
+    #[test]
+    fn restore_snapshot_reverts_mods_metadata_and_new_versions() {
+        let root = test_temp_dir("interface-upgrade-snapshot");
+        let mc = root.join("minecraft");
+        fs::create_dir_all(mc.join("mods")).expect("mods");
+        fs::create_dir_all(mc.join("versions").join("1.20.1")).expect("versions");
+        fs::write(root.join(".instance.json"), "old").expect("metadata");
+        fs::write(mc.join("mods").join("a.jar"), "a").expect("mod");
+
+        let snapshot = create_upgrade_snapshot(&root).expect("snapshot");
+
+        fs::write(root.join(".instance.json"), "new").expect("metadata");
+        fs::remove_file(mc.join("mods").join("a.jar")).expect("remove mod");
+        fs::write(mc.join("mods").join("b.jar"), "b").expect("new mod");
+        fs::create_dir_all(mc.join("versions").join("1.21.1")).expect("new version");
+
+        restore_upgrade_snapshot(&root, &snapshot).expect("restore");
+
+        assert_eq!(
+            fs::read_to_string(root.join(".instance.json")).expect("metadata"),
+            "old"
+        );
+        assert!(mc.join("mods").join("a.jar").exists());
+        assert!(!mc.join("mods").join("b.jar").exists());
+        assert!(mc.join("versions").join("1.20.1").exists());
+        assert!(!mc.join("versions").join("1.21.1").exists());
+
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn snapshots_in_the_same_second_do_not_collide_and_are_discarded() {
+        let root = test_temp_dir("interface-upgrade-snapshot-names");
+        let first = create_snapshot_dir(&root, 1_700_000_000).expect("first");
+        let second = create_snapshot_dir(&root, 1_700_000_000).expect("second");
+        assert_ne!(first, second);
+
+        let snapshot = create_upgrade_snapshot(&root).expect("snapshot");
+        discard_upgrade_snapshot(&snapshot);
+        assert!(!snapshot.path.exists());
+
+        let _ = fs::remove_dir_all(root);
+    }
+}
@@ -0,0 +1,268 @@
+// Unicidad de internal_uuid.
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::Serialize;
+use serde_json::Value;
+use tauri::{AppHandle, Emitter};
+
+use crate::{
+    app::{
+        instance_service::{is_instance_running, read_instance_metadata, write_instance_metadata},
+        redirect_launch::{migrate_redirect_cache_entry, redirect_cache_root, redirect_source},
+        settings_service::resolve_instances_root,
+    },
+    domain::models::instance::InstanceMetadata,
+    infrastructure::filesystem::file_ops::write_file_replacing,
+    shared::clock::{app_clock, IdGenerator},
+};
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct UuidReassignment {
+    pub instance_root: String,
+    pub old_uuid: String,
+    pub new_uuid: String,
+    /// Datos asociados que se movieron al uuid nuevo (`redirect-cache`, `state.json`).
+    pub migrated: Vec<String>,
+}
+
+struct ScannedInstance {
+    root: PathBuf,
+    metadata: InstanceMetadata,
+}
+
+impl ScannedInstance {
+    fn created_at_millis(&self) -> Option<i64> {
+        chrono::DateTime::parse_from_rfc3339(self.metadata.created_at.trim())
+            .ok()
+            .map(|created| created.timestamp_millis())
+    }
+
+    fn redirect_source(&self) -> Option<PathBuf> {
+        self.metadata
+            .state
+            .eq_ignore_ascii_case("REDIRECT")
+            .then(|| redirect_source(&self.root).ok().map(|(path, _)| path))
+            .flatten()
+    }
+}
+
+fn scan_instances(instance_roots: &[PathBuf]) -> Vec<ScannedInstance> {
+    instance_roots
+        .iter()
+        .filter(|root| root.join(".instance.json").is_file())
+        .filter_map(|root| {
+            let metadata = read_instance_metadata(root.display().to_string()).ok()?;
+            Some(ScannedInstance {
+                root: root.clone(),
+                metadata,
+            })
+        })
+        .collect()
+}
+
+/// Instancias que reciben un uuid nuevo; conserva el suyo la de `created_at` más antiguo.
+fn plan_reassignments(instances: &[ScannedInstance]) -> Vec<usize> {
+    let mut groups = BTreeMap::<String, Vec<usize>>::new();
+    for (index, instance) in instances.iter().enumerate() {
+        let uuid = instance.metadata.internal_uuid.trim().to_ascii_lowercase();
+        if !uuid.is_empty() {
+            groups.entry(uuid).or_default().push(index);
+        }
+    }
+    let mut reassign = Vec::new();
+    for mut members in groups.into_values().filter(|members| members.len() > 1) {
+        members.sort_by(|a, b| {
+            let (a, b) = (&instances[*a], &instances[*b]);
+            let key = |instance: &ScannedInstance| instance.created_at_millis().unwrap_or(i64::MAX);
+            key(a).cmp(&key(b)).then_with(|| a.root.cmp(&b.root))
+        });
+        reassign.extend(members.into_iter().skip(1));
+    }
+    reassign.sort_unstable();
+    reassign
+}
+
+/// `state.json` de un atajo guarda el mismo id.
+fn migrate_shortcut_state(root: &Path, old_uuid: &str, new_uuid: &str) -> Result<bool, String> {
+    let path = root.join("state.json");
+    let Ok(raw) = fs::read_to_string(&path) else {
+        return Ok(false);
+    };
+    let mut state = serde_json::from_str::<Value>(&raw)
+        .map_err(|err| format!("No se pudo parsear {}: {err}", path.display()))?;
+    if state.get("id").and_then(Value::as_str) != Some(old_uuid) {
+        return Ok(false);
+    }
+    state["id"] = Value::from(new_uuid);
+    let raw = serde_json::to_string_pretty(&state)
+        .map_err(|err| format!("No se pudo serializar {}: {err}", path.display()))?;
+    write_file_replacing(&path, raw.as_bytes(), true)
+        .map_err(|err| format!("No se pudo guardar {}: {err}", path.display()))?;
+    Ok(true)
+}
+
+/// Asigna uuids nuevos a los repetidos entre `instance_roots`; las instancias en ejecución
+/// se dejan para la próxima pasada.
+pub(crate) fn repair_duplicate_uuids(
+    instance_roots: &[PathBuf],
+    redirect_cache_root: Option<&Path>,
+    ids: &dyn IdGenerator,
+) -> Vec<UuidReassignment> {
+    let instances = scan_instances(instance_roots);
+    let mut reassignments = Vec::new();
+    for index in plan_reassignments(&instances) {
+        let instance = &instances[index];
+        let instance_root = instance.root.display().to_string();
+        if is_instance_running(&instance_root) {
+            log::warn!(
+                "⚠ {instance_root} comparte internal_uuid con otra instancia pero está en ejecución; se corregirá en la próxima revisión."
+            );
+            continue;
+        }
+        let old_uuid = instance.metadata.internal_uuid.clone();
+        let new_uuid = ids.new_id();
+        let mut metadata = instance.metadata.clone();
+        metadata.internal_uuid = new_uuid.clone();
+        if let Err(err) = write_instance_metadata(&instance_root, &metadata) {
+            log::warn!("⚠ No se pudo asignar un internal_uuid nuevo a {instance_root}: {err}");
+            continue;
+        }
+
+        let mut migrated = Vec::new();
+        match migrate_shortcut_state(&instance.root, &old_uuid, &new_uuid) {
+            Ok(true) => migrated.push("state.json".to_string()),
+            Ok(false) => {}
+            Err(err) => log::warn!("⚠ {err}"),
+        }
+        if let (Some(source), Some(cache_root)) = (instance.redirect_source(), redirect_cache_root)
+        {
+            let same_source = instances
+                .iter()
+                .filter(|other| {
+                    other.metadata.internal_uuid.eq_ignore_ascii_case(&old_uuid)
+                        && other.redirect_source().as_deref() == Some(source.as_path())
+                })
+                .count();
+            if same_source > 1 {
+                log::info!(
+                    "🔹 La entrada de redirect-cache de {old_uuid} no se puede atribuir a una sola instancia ({same_source} atajos a {}); se deja como está.",
+                    source.display()
+                );
+            } else {
+                match migrate_redirect_cache_entry(cache_root, &old_uuid, &new_uuid, &source) {
+                    Ok(true) => migrated.push("redirect-cache".to_string()),
+                    Ok(false) => {}
+                    Err(err) => log::warn!("⚠ {err}"),
+                }
+            }
+        }
+
+        log::warn!(
+            "⚠ internal_uuid duplicado en {instance_root}: {old_uuid} → {new_uuid}{}",
+            if migrated.is_empty() {
+                String::new()
+            } else {
+                format!(" (migrado: {})", migrated.join(", "))
+            }
+        );
+        reassignments.push(UuidReassignment {
+            instance_root,
+            old_uuid,
+            new_uuid,
+            migrated,
+        });
+    }
+    reassignments
+}
+
+/// Pasada de unicidad sobre la carpeta de instancias; emite `instance_uuid_reassigned`.
+pub fn repair_duplicate_instance_uuids(app: &AppHandle) -> Vec<UuidReassignment> {
+    let Ok(instances_root) = resolve_instances_root(app) else {
+        return Vec::new();
+    };
+    let Ok(entries) = fs::read_dir(&instances_root) else {
+        return Vec::new();
+    };
+    let roots = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect::<Vec<_>>();
+    let cache_root = redirect_cache_root(app).ok();
+    let reassignments =
+        repair_duplicate_uuids(&roots, cache_root.as_deref(), app_clock(app).ids.as_ref());
+    if !reassignments.is_empty() {
+        let _ = app.emit(
+            "instance_uuid_reassigned",
+            serde_json::json!({ "reassignments": reassignments }),
+        );
+        crate::app::launcher_problems::invalidate_launcher_problems(app);
+    }
+    reassignments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::clock::mock::SequentialIds;
+    use crate::shared::test_support::test_temp_dir;
+
+    fn write_instance(root: &Path, created_at: &str) {
+        fs::create_dir_all(root.join("minecraft")).expect("instance dir");
+        fs::write(
+            root.join(".instance.json"),
+            serde_json::json!({
+                "name": "Survival",
+                "minecraftVersion": "1.20.1",
+                "versionId": "1.20.1",
+                "loader": "vanilla",
+                "createdAt": created_at,
+                "state": "READY",
+                "internalUuid": "0b6f3c1e-8a9d-4f51-9c7e-2d4a6b8e1f00",
+            })
+            .to_string(),
+        )
+        .expect("metadata");
+    }
+
+    #[test]
+    fn copied_instance_gets_a_new_uuid_and_both_stay_launchable() {
+        let dir = test_temp_dir("instance-uuid");
+        let original = dir.join("Survival");
+        let copy = dir.join("Survival (copia)");
+        write_instance(&original, "2024-01-10T12:00:00Z");
+        write_instance(&copy, "2024-03-02T08:30:00Z");
+
+        let ids = SequentialIds::default();
+        let roots = vec![copy.clone(), original.clone()];
+        let reassignments = repair_duplicate_uuids(&roots, None, &ids);
+        assert_eq!(
+            reassignments,
+            vec![UuidReassignment {
+                instance_root: copy.display().to_string(),
+                old_uuid: "0b6f3c1e-8a9d-4f51-9c7e-2d4a6b8e1f00".to_string(),
+                new_uuid: "id-1".to_string(),
+                migrated: Vec::new(),
+            }]
+        );
+
+        let kept = read_instance_metadata(original.display().to_string()).expect("original");
+        let renewed = read_instance_metadata(copy.display().to_string()).expect("copy");
+        assert_eq!(kept.internal_uuid, "0b6f3c1e-8a9d-4f51-9c7e-2d4a6b8e1f00");
+        assert_eq!(renewed.internal_uuid, "id-1");
+        for metadata in [&kept, &renewed] {
+            assert_eq!(metadata.state, "READY");
+            assert_eq!(metadata.version_id, "1.20.1");
+        }
+
+        // Una segunda pasada no encuentra nada que corregir.
+        assert!(repair_duplicate_uuids(&roots, None, &ids).is_empty());
+        let _ = fs::remove_dir_all(dir);
+    }
+}
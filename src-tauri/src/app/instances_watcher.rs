@@ -0,0 +1,474 @@
+// Vigilancia de la carpeta de instancias.
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::{Mutex, OnceLock},
+    thread,
+    time::{Duration, Instant},
+};
+
+use serde_json::json;
+use tauri::{AppHandle, Emitter};
+
+use crate::{
+    app::{
+        instance_search::schedule_content_index_refresh,
+        instance_service::{is_instance_running, mark_runtime_folder_missing},
+        launcher_problems::invalidate_launcher_problems,
+        launcher_snapshot::mark_launcher_snapshot_dirty,
+        orphan_adoption::analyze_orphan_folder,
+        settings_service::resolve_instances_root,
+    },
+    domain::models::instance::InstanceMetadata,
+};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+/// Tiempo que una carpeta sin `.instance.json` debe seguir así antes de analizarla como
+/// posible huérfana; cubre copias lentas en las que la metadata llega segundos después.
+const ORPHAN_SETTLE_MS: u64 = 15_000;
+
+/// Instancias cuya carpeta desapareció con el juego abierto (ruta → nombre).
+static MISSING_RUNNING_FOLDERS: OnceLock<Mutex<BTreeMap<String, String>>> = OnceLock::new();
+
+fn missing_running_folders() -> &'static Mutex<BTreeMap<String, String>> {
+    MISSING_RUNNING_FOLDERS.get_or_init(|| Mutex::new(BTreeMap::new()))
+}
+
+/// Sesiones en ejecución que se quedaron sin carpeta, para el panel de problemas.
+pub(crate) fn running_instances_without_folder() -> Vec<(String, String)> {
+    missing_running_folders()
+        .lock()
+        .map(|missing| {
+            missing
+                .iter()
+                .map(|(root, name)| (root.clone(), name.clone()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ObservedMetadata {
+    pub internal_uuid: String,
+    pub name: String,
+}
+
+/// Estado de cada carpeta de primer nivel en un sondeo; `None` si aún no tiene un
+/// `.instance.json` legible.
+pub(crate) type FolderSnapshot = BTreeMap<String, Option<ObservedMetadata>>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum FolderChange {
+    Added {
+        folder: String,
+        metadata: ObservedMetadata,
+    },
+    Removed {
+        folder: String,
+        metadata: ObservedMetadata,
+    },
+    Renamed {
+        from: String,
+        to: String,
+        metadata: ObservedMetadata,
+    },
+    /// Carpeta sin metadata que lleva el margen completo estable; falta analizarla.
+    OrphanCandidate { folder: String },
+}
+
+struct PendingFolder {
+    first_seen_ms: u64,
+    analyzed: bool,
+}
+
+/// Lógica pura de reconciliación: recibe sondeos con su instante y devuelve los cambios
+/// confirmados. El primer sondeo solo establece la línea base.
+pub(crate) struct InstancesReconciler {
+    known: BTreeMap<String, ObservedMetadata>,
+    pending: BTreeMap<String, PendingFolder>,
+    previous: Option<FolderSnapshot>,
+    orphan_settle_ms: u64,
+}
+
+impl InstancesReconciler {
+    pub(crate) fn new(orphan_settle_ms: u64) -> Self {
+        Self {
+            known: BTreeMap::new(),
+            pending: BTreeMap::new(),
+            previous: None,
+            orphan_settle_ms,
+        }
+    }
+
+    pub(crate) fn observe(&mut self, snapshot: FolderSnapshot, now_ms: u64) -> Vec<FolderChange> {
+        let Some(previous) = self.previous.replace(snapshot.clone()) else {
+            for (folder, metadata) in snapshot {
+                match metadata {
+                    Some(metadata) => {
+                        self.known.insert(folder, metadata);
+                    }
+                    None => {
+                        // Las huérfanas que ya estaban al abrir no se anuncian como nuevas.
+                        self.pending.insert(
+                            folder,
+                            PendingFolder {
+                                first_seen_ms: now_ms,
+                                analyzed: true,
+                            },
+                        );
+                    }
+                }
+            }
+            return Vec::new();
+        };
+
+        let mut removed = Vec::new();
+        for (folder, metadata) in &self.known {
+            if !snapshot.contains_key(folder) && !previous.contains_key(folder) {
+                removed.push((folder.clone(), metadata.clone()));
+            }
+        }
+        for (folder, _) in &removed {
+            self.known.remove(folder);
+        }
+        self.pending
+            .retain(|folder, _| snapshot.contains_key(folder) || previous.contains_key(folder));
+
+        let mut added = Vec::new();
+        let mut changes = Vec::new();
+        for (folder, metadata) in &snapshot {
+            let stable = previous.get(folder) == Some(metadata);
+            match metadata {
+                Some(metadata) if stable => {
+                    if self.known.contains_key(folder) {
+                        self.known.insert(folder.clone(), metadata.clone());
+                    } else {
+                        self.pending.remove(folder);
+                        self.known.insert(folder.clone(), metadata.clone());
+                        added.push((folder.clone(), metadata.clone()));
+                    }
+                }
+                None if !self.known.contains_key(folder) => {
+                    let pending = self.pending.entry(folder.clone()).or_insert(PendingFolder {
+                        first_seen_ms: now_ms,
+                        analyzed: false,
+                    });
+                    if stable
+                        && !pending.analyzed
+                        && now_ms.saturating_sub(pending.first_seen_ms) >= self.orphan_settle_ms
+                    {
+                        pending.analyzed = true;
+                        changes.push(FolderChange::OrphanCandidate {
+                            folder: folder.clone(),
+                        });
+                    }
+                }
+                // Metadata de una instancia conocida a medio reescribir: se conserva la anterior.
+                _ => {}
+            }
+        }
+
+        for (from, metadata) in removed {
+            let renamed_to = added.iter().position(|(_, candidate)| {
+                !metadata.internal_uuid.is_empty()
+                    && candidate
+                        .internal_uuid
+                        .eq_ignore_ascii_case(&metadata.internal_uuid)
+            });
+            match renamed_to {
+                Some(index) => {
+                    let (to, metadata) = added.remove(index);
+                    changes.push(FolderChange::Renamed { from, to, metadata });
+                }
+                None => changes.push(FolderChange::Removed {
+                    folder: from,
+                    metadata,
+                }),
+            }
+        }
+        changes.extend(
+            added
+                .into_iter()
+                .map(|(folder, metadata)| FolderChange::Added { folder, metadata }),
+        );
+        changes
+    }
+}
+
+fn scan_instances_dir(instances_root: &Path) -> Result<FolderSnapshot, String> {
+    let entries = fs::read_dir(instances_root).map_err(|err| {
+        format!(
+            "No se pudo leer la carpeta de instancias {}: {err}",
+            instances_root.display()
+        )
+    })?;
+    let mut snapshot = FolderSnapshot::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_dir() {
+            continue;
+        }
+        let Some(folder) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        if folder.starts_with('.') {
+            continue;
+        }
+        let metadata = fs::read_to_string(path.join(".instance.json"))
+            .ok()
+            .and_then(|raw| serde_json::from_str::<InstanceMetadata>(&raw).ok())
+            .map(|metadata| ObservedMetadata {
+                internal_uuid: metadata.internal_uuid,
+                name: metadata.name,
+            });
+        snapshot.insert(folder.to_string(), metadata);
+    }
+    Ok(snapshot)
+}
+
+fn apply_change(app: &AppHandle, instances_root: &Path, change: FolderChange) {
+    let root_of = |folder: &str| instances_root.join(folder).display().to_string();
+    match change {
+        FolderChange::Added { folder, metadata } => {
+            let instance_root = root_of(&folder);
+            log::info!("🔹 Instancia añadida fuera del launcher: {}", instance_root);
+            schedule_content_index_refresh(&instances_root.join(&folder));
+            let _ = app.emit(
+                "instance_added",
+                json!({
+                    "instancePath": instance_root,
+                    "folderName": folder,
+                    "name": metadata.name,
+                    "internalUuid": metadata.internal_uuid,
+                    "orphan": false,
+                }),
+            );
+        }
+        FolderChange::OrphanCandidate { folder } => {
+            let path = instances_root.join(&folder);
+            let Ok(analysis) = analyze_orphan_folder(&path) else {
+                return;
+            };
+            if analysis.game_dir.is_none() && analysis.candidates.is_empty() {
+                return;
+            }
+            log::info!(
+                "🔹 Carpeta huérfana detectada en instancias: {}",
+                path.display()
+            );
+            let _ = app.emit(
+                "instance_added",
+                json!({
+                    "instancePath": path.display().to_string(),
+                    "folderName": folder,
+                    "name": folder,
+                    "orphan": true,
+                    "suggestedVersion": analysis.suggested.map(|candidate| candidate.version_id),
+                    "modLoaderHint": analysis.mod_loader_hint,
+                }),
+            );
+        }
+        FolderChange::Removed { folder, metadata } => {
+            let instance_root = root_of(&folder);
+            let was_running = is_instance_running(&instance_root);
+            if was_running {
+                mark_runtime_folder_missing(&instance_root);
+                if let Ok(mut missing) = missing_running_folders().lock() {
+                    missing.insert(instance_root.clone(), metadata.name.clone());
+                }
+                log::error!(
+                    "❌ La carpeta de '{}' desapareció con el juego en ejecución: {}",
+                    metadata.name,
+                    instance_root
+                );
+            } else {
+                log::info!(
+                    "🔹 Instancia eliminada fuera del launcher: {}",
+                    instance_root
+                );
+            }
+            let _ = app.emit(
+                "instance_removed",
+                json!({
+                    "instancePath": instance_root,
+                    "folderName": folder,
+                    "name": metadata.name,
+                    "internalUuid": metadata.internal_uuid,
+                    "wasRunning": was_running,
+                }),
+            );
+        }
+        FolderChange::Renamed { from, to, metadata } => {
+            let previous_root = root_of(&from);
+            let instance_root = root_of(&to);
+            log::info!(
+                "🔹 Instancia renombrada fuera del launcher: {} → {}",
+                previous_root,
+                instance_root
+            );
+            let was_running = is_instance_running(&previous_root);
+            if was_running {
+                // El proceso sigue escribiendo en la ruta vieja, que ya no existe.
+                mark_runtime_folder_missing(&previous_root);
+                if let Ok(mut missing) = missing_running_folders().lock() {
+                    missing.insert(previous_root.clone(), metadata.name.clone());
+                }
+            }
+            let _ = app.emit(
+                "instance_renamed_externally",
+                json!({
+                    "previousInstancePath": previous_root,
+                    "instancePath": instance_root,
+                    "folderName": to,
+                    "name": metadata.name,
+                    "internalUuid": metadata.internal_uuid,
+                    "wasRunning": was_running,
+                }),
+            );
+        }
+    }
+}
+
+/// Olvida las sesiones sin carpeta que ya terminaron. Devuelve si cambió algo.
+fn forget_finished_missing_folders() -> bool {
+    let Ok(mut missing) = missing_running_folders().lock() else {
+        return false;
+    };
+    let before = missing.len();
+    missing.retain(|instance_root, _| is_instance_running(instance_root));
+    missing.len() != before
+}
+
+/// Arranca el sondeo de la carpeta de instancias mientras el launcher está abierto. Si la
+/// carpeta configurada cambia (migración o ajuste), se toma una línea base nueva.
+pub fn start_instances_watcher(app: AppHandle) {
+    thread::spawn(move || {
+        let started = Instant::now();
+        let mut reconciler = InstancesReconciler::new(ORPHAN_SETTLE_MS);
+        let mut watched: Option<PathBuf> = None;
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            let mut problems_changed = forget_finished_missing_folders();
+            if let Ok(instances_root) = resolve_instances_root(&app) {
+                let instances_root = fs::canonicalize(&instances_root).unwrap_or(instances_root);
+                if watched.as_ref() != Some(&instances_root) {
+                    reconciler = InstancesReconciler::new(ORPHAN_SETTLE_MS);
+                    watched = Some(instances_root.clone());
+                }
+                if let Ok(snapshot) = scan_instances_dir(&instances_root) {
+                    let now_ms = started.elapsed().as_millis() as u64;
+                    let changes = reconciler.observe(snapshot, now_ms);
+                    let instances_changed = changes
+                        .iter()
+                        .any(|change| !matches!(change, FolderChange::OrphanCandidate { .. }));
+                    if instances_changed {
+                        mark_launcher_snapshot_dirty();
+                        problems_changed = true;
+                    }
+                    for change in changes {
+                        apply_change(&app, &instances_root, change);
+                    }
+                }
+            }
+            if problems_changed {
+                invalidate_launcher_problems(&app);
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata(uuid: &str, name: &str) -> Option<ObservedMetadata> {
+        Some(ObservedMetadata {
+            internal_uuid: uuid.to_string(),
+            name: name.to_string(),
+        })
+    }
+
+    fn snapshot(entries: &[(&str, Option<ObservedMetadata>)]) -> FolderSnapshot {
+        entries
+            .iter()
+            .map(|(folder, metadata)| (folder.to_string(), metadata.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn partial_copy_is_announced_only_once_metadata_is_stable() {
+        let mut reconciler = InstancesReconciler::new(15_000);
+        let existing = ("Survival", metadata("uuid-a", "Survival"));
+        assert!(reconciler
+            .observe(snapshot(std::slice::from_ref(&existing)), 0)
+            .is_empty());
+
+        // La carpeta aparece vacía y la metadata llega segundos después, primero a medias.
+        for now in [2_000, 4_000, 6_000] {
+            let changes = reconciler.observe(snapshot(&[existing.clone(), ("Copia", None)]), now);
+            assert!(changes.is_empty(), "{changes:?}");
+        }
+        let copied = ("Copia", metadata("uuid-b", "Copia"));
+        assert!(reconciler
+            .observe(snapshot(&[existing.clone(), copied.clone()]), 8_000)
+            .is_empty());
+        assert_eq!(
+            reconciler.observe(snapshot(&[existing.clone(), copied.clone()]), 10_000),
+            vec![FolderChange::Added {
+                folder: "Copia".to_string(),
+                metadata: copied.1.clone().unwrap(),
+            }]
+        );
+        assert!(reconciler
+            .observe(snapshot(&[existing, copied]), 12_000)
+            .is_empty());
+    }
+
+    #[test]
+    fn rename_is_paired_by_uuid_and_plain_deletion_is_removed() {
+        let mut reconciler = InstancesReconciler::new(15_000);
+        let a = metadata("uuid-a", "Survival");
+        let b = metadata("uuid-b", "Creativo");
+        reconciler.observe(
+            snapshot(&[("Survival", a.clone()), ("Creativo", b.clone())]),
+            0,
+        );
+
+        let after = snapshot(&[("Survival 2", a.clone())]);
+        assert!(reconciler.observe(after.clone(), 2_000).is_empty());
+        assert_eq!(
+            reconciler.observe(after, 4_000),
+            vec![
+                FolderChange::Removed {
+                    folder: "Creativo".to_string(),
+                    metadata: b.unwrap(),
+                },
+                FolderChange::Renamed {
+                    from: "Survival".to_string(),
+                    to: "Survival 2".to_string(),
+                    metadata: a.unwrap(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn folder_without_metadata_becomes_orphan_candidate_after_settling() {
+        let mut reconciler = InstancesReconciler::new(15_000);
+        reconciler.observe(snapshot(&[("Vieja", None)]), 0);
+        let mut candidates = Vec::new();
+        for now in (2_000..=30_000).step_by(2_000) {
+            candidates.extend(
+                reconciler.observe(snapshot(&[("Vieja", None), ("Descomprimida", None)]), now),
+            );
+        }
+        assert_eq!(
+            candidates,
+            vec![FolderChange::OrphanCandidate {
+                folder: "Descomprimida".to_string(),
+            }]
+        );
+    }
+}
@@ -1 +1,141 @@
 // Servicio de orquestación de Java.
+use std::path::PathBuf;
+
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::{
+    app::{launcher_problems::invalidate_launcher_problems, maintenance::begin_maintenance},
+    domain::models::java::JavaRuntime,
+    infrastructure::filesystem::paths::resolve_launcher_root,
+    services::java_installer::{self, InstalledJavaBuild},
+    shared::tasks::{run_with_task, TaskHandle},
+};
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JavaArchiveInstallResult {
+    pub runtime: String,
+    pub java_path: String,
+    pub logs: Vec<String>,
+}
+
+#[tauri::command]
+pub async fn install_java_from_archive(
+    app: AppHandle,
+    runtime: String,
+    archive_path: String,
+    expected_sha256: Option<String>,
+) -> Result<JavaArchiveInstallResult, String> {
+    let java_runtime = JavaRuntime::from_name(&runtime)
+        .ok_or_else(|| format!("Runtime de Java no soportado: {runtime}"))?;
+    let launcher_root = resolve_launcher_root(&app)?;
+
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        // Sustituye un runtime que otra instancia podría estar a punto de usar.
+        let _maintenance = begin_maintenance("runtime_reinstall")?;
+        let mut logs = Vec::new();
+        let java_exec = java_installer::install_java_from_archive(
+            &launcher_root,
+            java_runtime,
+            &PathBuf::from(&archive_path),
+            expected_sha256.as_deref(),
+            &mut logs,
+        )?;
+        Ok(JavaArchiveInstallResult {
+            runtime: java_runtime.as_dir_name().to_string(),
+            java_path: java_exec.display().to_string(),
+            logs,
+        })
+    })
+    .await
+    .map_err(|err| format!("Falló la tarea de instalación de Java: {err}"))?;
+    invalidate_launcher_problems(&app);
+    result
+}
+
+/// Reemplaza las builds del major que no pasan `java -version` por la embebida actual.
+#[tauri::command]
+pub async fn reinstall_embedded_runtime(
+    app: AppHandle,
+    major: u32,
+) -> Result<JavaArchiveInstallResult, String> {
+    let java_runtime = runtime_for_major(major)?;
+    let launcher_root = resolve_launcher_root(&app)?;
+
+    let task = TaskHandle::begin(&app, "java_runtime_download", format!("Java {major}"));
+    let probe = task.probe();
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        run_with_task(probe, || {
+            let _maintenance = begin_maintenance("runtime_reinstall")?;
+            let mut logs = Vec::new();
+            let java_exec =
+                java_installer::ensure_embedded_java(&launcher_root, java_runtime, &mut logs)?;
+            Ok(JavaArchiveInstallResult {
+                runtime: java_runtime.as_dir_name().to_string(),
+                java_path: java_exec.display().to_string(),
+                logs,
+            })
+        })
+    })
+    .await
+    .map_err(|err| format!("Falló la tarea de instalación de Java: {err}"))?;
+    task.finish(&result);
+    invalidate_launcher_problems(&app);
+    result
+}
+
+fn runtime_for_major(major: u32) -> Result<JavaRuntime, String> {
+    JavaRuntime::from_name(&major.to_string())
+        .ok_or_else(|| format!("Runtime de Java no soportado: {major}"))
+}
+
+/// Builds de Java instaladas para un major, de la más reciente a la más antigua.
+#[tauri::command]
+pub fn list_installed_java_builds(
+    app: AppHandle,
+    major: u32,
+) -> Result<Vec<InstalledJavaBuild>, String> {
+    let java_runtime = runtime_for_major(major)?;
+    let launcher_root = resolve_launcher_root(&app)?;
+    java_installer::list_java_builds(&launcher_root, java_runtime)
+}
+
+/// Descarga una release concreta de Temurin (`jdk-17.0.9+9`) junto a las ya instaladas.
+#[tauri::command]
+pub async fn install_specific_java_build(
+    app: AppHandle,
+    major: u32,
+    release_name: String,
+) -> Result<JavaArchiveInstallResult, String> {
+    let java_runtime = runtime_for_major(major)?;
+    let launcher_root = resolve_launcher_root(&app)?;
+
+    let task = TaskHandle::begin(
+        &app,
+        "java_runtime_download",
+        format!("Java {major} ({release_name})"),
+    );
+    let probe = task.probe();
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        run_with_task(probe, || {
+            let mut logs = Vec::new();
+            let java_exec = java_installer::install_specific_java_build(
+                &launcher_root,
+                java_runtime,
+                &release_name,
+                &mut logs,
+            )?;
+            Ok(JavaArchiveInstallResult {
+                runtime: java_runtime.as_dir_name().to_string(),
+                java_path: java_exec.display().to_string(),
+                logs,
+            })
+        })
+    })
+    .await
+    .map_err(|err| format!("Falló la tarea de instalación de Java: {err}"))?;
+    task.finish(&result);
+    invalidate_launcher_problems(&app);
+    result
+}
@@ -0,0 +1,491 @@
+// Desglose de memoria de la JVM con jcmd.
+
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+#[cfg(windows)]
+use std::os::windows::process::CommandExt;
+
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::app::{
+    instance_service::{read_instance_metadata, running_java_process},
+    trusted_root::{resolve_trusted_instance_root, ValidatedInstanceRoot},
+};
+
+#[cfg(windows)]
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+/// Categorías nativas que se devuelven, ordenadas por memoria comprometida.
+const TOP_NATIVE_CATEGORIES: usize = 8;
+
+#[derive(Debug, Clone, Default, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct MemoryUsage {
+    pub used_mb: u64,
+    pub committed_mb: u64,
+    /// Máximo reservable (`-Xmx` para el heap); `None` si el colector no lo informa.
+    pub max_mb: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct NativeMemoryCategory {
+    pub name: String,
+    pub reserved_mb: u64,
+    pub committed_mb: u64,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct InstanceHeapSummary {
+    pub pid: u32,
+    pub heap: MemoryUsage,
+    pub metaspace: Option<MemoryUsage>,
+    /// `false` si la instancia no se lanzó con `-XX:NativeMemoryTracking`.
+    pub native_tracking_enabled: bool,
+    pub native_total: Option<NativeMemoryCategory>,
+    pub native_categories: Vec<NativeMemoryCategory>,
+}
+
+/// `jcmd` no pudo conectarse a la JVM.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct JcmdAttachError {
+    /// `JCMD_NOT_FOUND`, `ATTACH_PERMISSION_DENIED`, `ATTACH_DISABLED`, `ATTACH_TIMEOUT`,
+    /// `PROCESS_NOT_FOUND` o `ATTACH_FAILED`.
+    pub code: &'static str,
+    pub pid: u32,
+    pub message: String,
+}
+
+/// Error del desglose de memoria: los fallos de `jcmd` van estructurados y el resto sigue
+/// siendo texto.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum HeapSummaryError {
+    Attach(JcmdAttachError),
+    Other(String),
+}
+
+impl From<String> for HeapSummaryError {
+    fn from(err: String) -> Self {
+        HeapSummaryError::Other(err)
+    }
+}
+
+impl std::fmt::Display for HeapSummaryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HeapSummaryError::Attach(attach) => write!(f, "{}", attach.message),
+            HeapSummaryError::Other(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn get_instance_heap_summary(
+    app: AppHandle,
+    instance_root: String,
+) -> Result<InstanceHeapSummary, HeapSummaryError> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    tauri::async_runtime::spawn_blocking(move || heap_summary_for(&instance_root))
+        .await
+        .map_err(|err| format!("Falló la tarea de lectura de memoria: {err}"))?
+}
+
+fn heap_summary_for(root: &ValidatedInstanceRoot) -> Result<InstanceHeapSummary, HeapSummaryError> {
+    let instance_root = root.as_str();
+    let (pid, java_path) = running_java_process(instance_root)
+        .ok_or_else(|| "La instancia no está en ejecución.".to_string())?;
+    let metadata = read_instance_metadata(instance_root.to_string())?;
+    let jcmd = jcmd_next_to(Path::new(&java_path)).ok_or_else(|| {
+        HeapSummaryError::Attach(JcmdAttachError {
+            code: "JCMD_NOT_FOUND",
+            pid,
+            message: format!(
+                "No se encontró jcmd junto a {java_path}; el runtime no es un JDK completo."
+            ),
+        })
+    })?;
+
+    let heap_info = run_jcmd(&jcmd, pid, &["GC.heap_info"])?;
+    let (heap, metaspace) = parse_heap_info(&heap_info).ok_or_else(|| {
+        format!("No se pudo interpretar la salida de GC.heap_info del proceso {pid}.")
+    })?;
+
+    let native_tracking_enabled = native_tracking_requested(&metadata.java_args);
+    let (native_total, native_categories) = if native_tracking_enabled {
+        parse_native_memory(&run_jcmd(&jcmd, pid, &["VM.native_memory", "summary"])?)
+    } else {
+        (None, Vec::new())
+    };
+
+    Ok(InstanceHeapSummary {
+        pid,
+        heap,
+        metaspace,
+        native_tracking_enabled: native_tracking_enabled && native_total.is_some(),
+        native_total,
+        native_categories,
+    })
+}
+
+fn jcmd_next_to(java_path: &Path) -> Option<PathBuf> {
+    let name = if cfg!(windows) { "jcmd.exe" } else { "jcmd" };
+    let candidate = java_path.parent()?.join(name);
+    candidate.is_file().then_some(candidate)
+}
+
+fn native_tracking_requested(java_args: &[String]) -> bool {
+    java_args.iter().any(|arg| {
+        arg.strip_prefix("-XX:NativeMemoryTracking=")
+            .is_some_and(|mode| !mode.eq_ignore_ascii_case("off"))
+    })
+}
+
+fn run_jcmd(jcmd: &Path, pid: u32, args: &[&str]) -> Result<String, HeapSummaryError> {
+    let mut command = Command::new(jcmd);
+    command.arg(pid.to_string()).args(args);
+    #[cfg(windows)]
+    {
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+    let output = command
+        .output()
+        .map_err(|err| format!("No se pudo ejecutar {}: {err}", jcmd.display()))?;
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if output.status.success() && !stdout.contains("AttachNotSupportedException") {
+        return Ok(stdout);
+    }
+    let combined = format!("{stdout}\n{stderr}");
+    log::warn!(
+        "⚠ jcmd {} falló para el proceso {pid}: {}",
+        args.join(" "),
+        combined.trim()
+    );
+    Err(HeapSummaryError::Attach(classify_attach_failure(
+        pid, &combined,
+    )))
+}
+
+/// Traduce la salida de un `jcmd` fallido a un código estable en vez de devolver stderr.
+fn classify_attach_failure(pid: u32, output: &str) -> JcmdAttachError {
+    let lower = output.to_ascii_lowercase();
+    let (code, message) = if lower.contains("does not support the attach mechanism")
+        || lower.contains("disableattachmechanism")
+    {
+        (
+            "ATTACH_DISABLED",
+            "La JVM tiene desactivado el mecanismo de attach (-XX:+DisableAttachMechanism).",
+        )
+    } else if lower.contains("permission denied")
+        || lower.contains("operation not permitted")
+        || lower.contains("access is denied")
+        || lower.contains("not secure")
+    {
+        (
+            "ATTACH_PERMISSION_DENIED",
+            "El proceso de Minecraft pertenece a otro usuario o el sistema bloquea el attach.",
+        )
+    } else if lower.contains("no such process") || lower.contains("could not find") {
+        ("PROCESS_NOT_FOUND", "El proceso de Minecraft ya no existe.")
+    } else if lower.contains("doesn't respond")
+        || lower.contains("timed out")
+        || lower.contains("unable to open socket file")
+    {
+        (
+            "ATTACH_TIMEOUT",
+            "La JVM no respondió a jcmd a tiempo; puede estar arrancando o bloqueada.",
+        )
+    } else {
+        ("ATTACH_FAILED", "jcmd no pudo conectarse a la JVM.")
+    };
+    JcmdAttachError {
+        code,
+        pid,
+        message: message.to_string(),
+    }
+}
+
+/// Convierte `102400K`, `300M`, `4G` o `2048KB` a KB.
+fn parse_size_kb(token: &str) -> Option<u64> {
+    let token = token.trim_end_matches('B');
+    let split = token.find(|c: char| !c.is_ascii_digit())?;
+    let value = token[..split].parse::<u64>().ok()?;
+    match &token[split..] {
+        "K" => Some(value),
+        "M" => Some(value * 1024),
+        "G" => Some(value * 1024 * 1024),
+        _ => None,
+    }
+}
+
+/// Pares `etiqueta → KB` de una línea de `GC.heap_info`. Admite `used 100K` (etiqueta antes)
+/// y `100K used` (Shenandoah, valor antes).
+fn labeled_sizes(line: &str) -> Vec<(String, u64)> {
+    let tokens = line
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|token| !token.is_empty())
+        .collect::<Vec<_>>();
+    let value_first = tokens
+        .first()
+        .is_some_and(|token| parse_size_kb(token).is_some());
+    let mut pairs = Vec::new();
+    for (index, token) in tokens.iter().enumerate() {
+        let Some(kb) = parse_size_kb(token) else {
+            continue;
+        };
+        let label = if value_first {
+            tokens[index + 1..]
+                .iter()
+                .take_while(|word| parse_size_kb(word).is_none())
+                .copied()
+                .collect::<Vec<_>>()
+                .join(" ")
+        } else {
+            let words = &tokens[..index];
+            match words {
+                [.., "max", last] => format!("max {last}"),
+                [.., last] => last.to_string(),
+                [] => String::new(),
+            }
+        };
+        pairs.push((label, kb));
+    }
+    pairs
+}
+
+/// Tamaño del rango `[0xinicio, ..., 0xfin)` que G1 (Java 17) y Parallel imprimen como
+/// reserva del heap.
+fn address_range_kb(line: &str) -> Option<u64> {
+    let inner = &line[line.find('[')? + 1..line.rfind(')')?];
+    let bounds = inner
+        .split(',')
+        .filter_map(|part| u64::from_str_radix(part.trim().trim_start_matches("0x"), 16).ok())
+        .collect::<Vec<_>>();
+    let (first, last) = (bounds.first()?, bounds.last()?);
+    Some(last.saturating_sub(*first) / 1024)
+}
+
+fn usage_from(pairs: &[(String, u64)], line: &str) -> Option<MemoryUsage> {
+    let value = |label: &str| {
+        pairs
+            .iter()
+            .find(|(name, _)| name == label)
+            .map(|(_, kb)| *kb)
+    };
+    let used = value("used")?;
+    let committed = value("committed")
+        .or_else(|| value("total"))
+        .or_else(|| value("capacity"))
+        .unwrap_or(used);
+    let max = value("reserved")
+        .or_else(|| value("max capacity"))
+        .or_else(|| value("max"))
+        .or_else(|| address_range_kb(line));
+    Some(MemoryUsage {
+        used_mb: used / 1024,
+        committed_mb: committed / 1024,
+        max_mb: max.map(|kb| kb / 1024),
+    })
+}
+
+/// Heap y metaspace de `jcmd <pid> GC.heap_info`. En Java 17 G1 da `total X, used Y` y la
+/// reserva sale del rango de direcciones; en Java 21 da `total reserved X, committed Y, used Z`.
+fn parse_heap_info(output: &str) -> Option<(MemoryUsage, Option<MemoryUsage>)> {
+    let mut heap: Option<MemoryUsage> = None;
+    let mut metaspace = None;
+    for line in output.lines().skip(1) {
+        let first = line.split_whitespace().next().unwrap_or_default();
+        if matches!(
+            first,
+            "class" | "eden" | "from" | "to" | "object" | "region"
+        ) {
+            continue;
+        }
+        let pairs = labeled_sizes(line);
+        let Some(usage) = usage_from(&pairs, line) else {
+            continue;
+        };
+        if first == "Metaspace" {
+            metaspace = Some(usage);
+            continue;
+        }
+        // Parallel y Serial reparten el heap en generaciones: se suman.
+        heap = Some(match heap {
+            Some(total) => MemoryUsage {
+                used_mb: total.used_mb + usage.used_mb,
+                committed_mb: total.committed_mb + usage.committed_mb,
+                max_mb: total.max_mb.zip(usage.max_mb).map(|(a, b)| a + b),
+            },
+            None => usage,
+        });
+    }
+    heap.map(|heap| (heap, metaspace))
+}
+
+fn nmt_value_kb(line: &str, key: &str) -> Option<u64> {
+    let start = line.find(key)? + key.len();
+    let value = line[start..]
+        .split(|c: char| c == ',' || c == ')' || c.is_whitespace())
+        .next()?;
+    parse_size_kb(value)
+}
+
+/// Total y categorías de `VM.native_memory summary`. Java 21 añade líneas `malloc:`/`mmap:`
+/// bajo el total y `peak=` en las categorías; se ignoran.
+fn parse_native_memory(output: &str) -> (Option<NativeMemoryCategory>, Vec<NativeMemoryCategory>) {
+    let category = |name: &str, line: &str| {
+        Some(NativeMemoryCategory {
+            name: name.to_string(),
+            reserved_mb: nmt_value_kb(line, "reserved=")? / 1024,
+            committed_mb: nmt_value_kb(line, "committed=")? / 1024,
+        })
+    };
+    let mut total = None;
+    let mut categories = Vec::new();
+    for line in output.lines().map(str::trim) {
+        if let Some(rest) = line.strip_prefix("Total:") {
+            total = category("Total", rest);
+        } else if let Some(rest) = line.strip_prefix('-') {
+            let Some((name, values)) = rest.split_once('(') else {
+                continue;
+            };
+            if let Some(entry) = category(name.trim(), values) {
+                categories.push(entry);
+            }
+        }
+    }
+    categories.sort_by_key(|entry| std::cmp::Reverse(entry.committed_mb));
+    categories.truncate(TOP_NATIVE_CATEGORIES);
+    (total, categories)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEAP_INFO_JAVA17: &str = "12345:
+ garbage-first heap   total 2097152K, used 1153433K [0x0000000700000000, 0x0000000800000000)
+  region size 2048K, 312 young (638976K), 12 survivors (24576K)
+ Metaspace       used 183500K, committed 185344K, reserved 1236992K
+  class space    used 27600K, committed 28416K, reserved 1048576K
+";
+
+    const HEAP_INFO_JAVA21: &str = "12345:
+ garbage-first heap   total reserved 4194304K, committed 2097152K, used 1153433K [0x0000000700000000, 0x0000000800000000)
+  region size 2048K, 312 young (638976K), 12 survivors (24576K)
+ Metaspace       used 183500K, committed 185344K, reserved 1245184K
+  class space    used 27600K, committed 28416K, reserved 1048576K
+";
+
+    const NMT_JAVA17: &str = "12345:
+
+Native Memory Tracking:
+
+(Omitting categories weighting less than 1KB)
+
+Total: reserved=6021120KB, committed=2666496KB
+
+-                 Java Heap (reserved=4194304KB, committed=2097152KB)
+                            (mmap: reserved=4194304KB, committed=2097152KB)
+
+-                     Class (reserved=1050624KB, committed=30720KB)
+                            (classes #31000)
+                            (malloc=2048KB #61000)
+
+-                    Thread (reserved=98304KB, committed=10240KB)
+                            (thread #96)
+
+-                      Code (reserved=251904KB, committed=81920KB)
+                            (malloc=10240KB #25000)
+";
+
+    const NMT_JAVA21: &str = "12345:
+
+Native Memory Tracking:
+
+(Omitting categories weighting less than 1KB)
+
+Total: reserved=6021120KB, committed=2666496KB
+       malloc: 307200KB #900000, peak=350000KB #950000
+       mmap:   reserved=5713920KB, committed=2359296KB
+
+-                 Java Heap (reserved=4194304KB, committed=2097152KB)
+                            (mmap: reserved=4194304KB, committed=2097152KB, peak=2097152KB)
+
+-                     Class (reserved=1050624KB, committed=30720KB)
+                            (classes #31000)
+                            (malloc=2048KB tag=Class #61000) (peak=2100KB #61500)
+
+-                        GC (reserved=204800KB, committed=153600KB)
+                            (malloc=51200KB #4000) (peak=60000KB #4100)
+";
+
+    #[test]
+    fn parses_heap_info_from_java_17_and_21() {
+        let (heap17, metaspace17) = parse_heap_info(HEAP_INFO_JAVA17).expect("java 17");
+        assert_eq!(
+            heap17,
+            MemoryUsage {
+                used_mb: 1126,
+                committed_mb: 2048,
+                max_mb: Some(4096),
+            }
+        );
+        assert_eq!(metaspace17.expect("metaspace").used_mb, 179);
+
+        let (heap21, metaspace21) = parse_heap_info(HEAP_INFO_JAVA21).expect("java 21");
+        assert_eq!(heap21, heap17);
+        assert_eq!(metaspace21.expect("metaspace").committed_mb, 181);
+
+        let zgc = "12345:\n ZHeap           used 800M, capacity 2048M, max capacity 4096M\n Metaspace       used 183500K, committed 185344K, reserved 1236992K\n";
+        let (heap, _) = parse_heap_info(zgc).expect("zgc");
+        assert_eq!(
+            (heap.used_mb, heap.committed_mb, heap.max_mb),
+            (800, 2048, Some(4096))
+        );
+    }
+
+    #[test]
+    fn parses_native_memory_and_classifies_attach_errors() {
+        let (total17, categories17) = parse_native_memory(NMT_JAVA17);
+        assert_eq!(total17.as_ref().map(|total| total.committed_mb), Some(2604));
+        assert_eq!(
+            categories17
+                .iter()
+                .map(|entry| entry.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["Java Heap", "Code", "Class", "Thread"]
+        );
+
+        let (total21, categories21) = parse_native_memory(NMT_JAVA21);
+        assert_eq!(total21, total17);
+        assert_eq!(categories21[1].name, "GC");
+        assert_eq!(categories21[1].committed_mb, 150);
+
+        assert!(native_tracking_requested(&[
+            "-XX:NativeMemoryTracking=summary".to_string()
+        ]));
+        assert!(!native_tracking_requested(&[
+            "-XX:NativeMemoryTracking=off".to_string()
+        ]));
+
+        let disabled = classify_attach_failure(
+            12345,
+            "com.sun.tools.attach.AttachNotSupportedException: The VM does not support the attach mechanism",
+        );
+        assert_eq!(disabled.code, "ATTACH_DISABLED");
+        let timeout = classify_attach_failure(
+            12345,
+            "com.sun.tools.attach.AttachNotSupportedException: Unable to open socket file /proc/12345/root/tmp/.java_pid12345: target process 12345 doesn't respond within 10500ms or HotSpot VM not loaded",
+        );
+        assert_eq!(timeout.code, "ATTACH_TIMEOUT");
+        let denied = classify_attach_failure(12345, "java.io.IOException: Permission denied");
+        assert_eq!(denied.code, "ATTACH_PERMISSION_DENIED");
+    }
+}
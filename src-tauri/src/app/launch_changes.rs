@@ -0,0 +1,482 @@
+// Cambios desde el último lanzamiento correcto.
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    thread,
+};
+
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::{
+    app::{
+        event_journal::emit_journaled,
+        game_dir_guard::LaunchError,
+        instance_service::{
+            effective_mods_dir, read_instance_metadata, resolve_effective_version_id,
+            resolve_launcher_root_for_instance,
+        },
+        launch_lock::{
+            changed, hash_mods, mtime_millis, read_lock, snapshot_config_files, ChangedValue,
+            LaunchLock, LockedFile, SUCCESS_LAUNCH_LOCK_FILE,
+        },
+        trusted_root::resolve_trusted_instance_root,
+    },
+    infrastructure::{
+        checksum::verification_cache::VerificationCache,
+        filesystem::paths::configured_launcher_root,
+    },
+};
+
+/// Nombres que se citan en el resumen antes de abreviar con "+N".
+const SUMMARY_NAMES: usize = 3;
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangedEntry {
+    pub name: String,
+    /// mtime del archivo actual, si existe.
+    pub changed_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct MetadataChange {
+    pub field: String,
+    pub previous: String,
+    pub current: String,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TimedChange {
+    pub previous: String,
+    pub current: String,
+    pub changed_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchChanges {
+    /// `false` si la instancia nunca terminó un lanzamiento correcto con lockfile.
+    pub available: bool,
+    pub last_success_at: Option<String>,
+    pub mods_added: Vec<ChangedEntry>,
+    pub mods_removed: Vec<String>,
+    pub mods_modified: Vec<ChangedEntry>,
+    /// Solo nombres: el contenido de la configuración no se compara.
+    pub config_files: Vec<ChangedEntry>,
+    pub metadata: Vec<MetadataChange>,
+    pub java_runtime: Option<TimedChange>,
+    pub version_json: Option<TimedChange>,
+    /// Una línea lista para mostrar junto al error.
+    pub summary: String,
+}
+
+/// Estado actual de la instancia en los mismos términos que el lockfile.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct CurrentState {
+    pub mods: Vec<LockedFile>,
+    pub mod_mtimes: BTreeMap<String, u64>,
+    pub config_files: BTreeMap<String, u64>,
+    pub ram_mb: u32,
+    pub java_args: Vec<String>,
+    pub loader_version: String,
+    pub java_path: String,
+    pub java_mtime: Option<u64>,
+    pub version_id: String,
+    pub version_json_sha1: String,
+    pub version_json_mtime: Option<u64>,
+}
+
+fn millis_to_rfc3339(millis: u64) -> Option<String> {
+    chrono::DateTime::<chrono::Utc>::from_timestamp_millis(millis as i64)
+        .map(|moment| moment.to_rfc3339())
+}
+
+fn timed(change: Option<ChangedValue>, changed_at: Option<u64>) -> Option<TimedChange> {
+    change.map(|change| TimedChange {
+        previous: change.previous,
+        current: change.current,
+        changed_at: changed_at.and_then(millis_to_rfc3339),
+    })
+}
+
+fn capture_current_state(instance_root: &Path) -> Result<CurrentState, String> {
+    let metadata = read_instance_metadata(instance_root.display().to_string())?;
+    let game_dir = instance_root.join("minecraft");
+    let launcher_root = resolve_launcher_root_for_instance(
+        instance_root,
+        configured_launcher_root().as_deref(),
+        &mut Vec::new(),
+    )?;
+    let mut cache = VerificationCache::load(&launcher_root);
+    let mods_dir = effective_mods_dir(instance_root);
+    let mods = hash_mods(&mut cache, &mods_dir);
+    let mod_mtimes = mods
+        .iter()
+        .filter_map(|file| Some((file.path.clone(), mtime_millis(&mods_dir.join(&file.path))?)))
+        .collect();
+    let version_id = resolve_effective_version_id(&game_dir, &metadata).unwrap_or_default();
+    let version_json_path: PathBuf = game_dir
+        .join("versions")
+        .join(&version_id)
+        .join(format!("{version_id}.json"));
+    let version_json_sha1 = cache
+        .sha1(&version_json_path)
+        .map(|(sha1, _)| sha1)
+        .unwrap_or_default();
+    cache.save();
+    Ok(CurrentState {
+        mods,
+        mod_mtimes,
+        config_files: snapshot_config_files(&game_dir),
+        ram_mb: metadata.ram_mb,
+        java_args: metadata.java_args,
+        loader_version: metadata.loader_version,
+        java_mtime: mtime_millis(Path::new(&metadata.java_path)),
+        java_path: metadata.java_path,
+        version_id,
+        version_json_sha1,
+        version_json_mtime: mtime_millis(&version_json_path),
+    })
+}
+
+/// Compara el lockfile del último lanzamiento correcto con el estado actual.
+pub(crate) fn diff_against_lock(lock: &LaunchLock, current: &CurrentState) -> LaunchChanges {
+    let before = lock
+        .mods
+        .iter()
+        .map(|file| (file.path.as_str(), file.sha1.as_str()))
+        .collect::<BTreeMap<_, _>>();
+    let entry = |name: &str| ChangedEntry {
+        name: name.to_string(),
+        changed_at: current
+            .mod_mtimes
+            .get(name)
+            .copied()
+            .and_then(millis_to_rfc3339),
+    };
+    let mut mods_added = Vec::new();
+    let mut mods_modified = Vec::new();
+    for file in &current.mods {
+        match before.get(file.path.as_str()) {
+            None => mods_added.push(entry(&file.path)),
+            Some(sha1) if *sha1 != file.sha1 => mods_modified.push(entry(&file.path)),
+            Some(_) => {}
+        }
+    }
+    let mods_removed = before
+        .keys()
+        .filter(|name| !current.mods.iter().any(|file| file.path == **name))
+        .map(|name| name.to_string())
+        .collect();
+
+    let config_files = current
+        .config_files
+        .iter()
+        .filter(|(name, mtime)| lock.config_files.get(*name) != Some(*mtime))
+        .map(|(name, mtime)| ChangedEntry {
+            name: name.clone(),
+            changed_at: millis_to_rfc3339(*mtime),
+        })
+        .chain(
+            lock.config_files
+                .keys()
+                .filter(|name| !current.config_files.contains_key(*name))
+                .map(|name| ChangedEntry {
+                    name: name.clone(),
+                    changed_at: None,
+                }),
+        )
+        .collect();
+
+    let mut metadata = Vec::new();
+    if let Some(previous) = lock.ram_mb.filter(|ram| *ram != current.ram_mb) {
+        metadata.push(MetadataChange {
+            field: "ram_mb".to_string(),
+            previous: previous.to_string(),
+            current: current.ram_mb.to_string(),
+        });
+    }
+    // Los lockfiles anteriores a este campo no guardaban ni RAM ni argumentos.
+    if lock.ram_mb.is_some() && lock.java_args != current.java_args {
+        metadata.push(MetadataChange {
+            field: "java_args".to_string(),
+            previous: lock.java_args.join(" "),
+            current: current.java_args.join(" "),
+        });
+    }
+    if let Some(change) = changed(&lock.loader_version, &current.loader_version) {
+        metadata.push(MetadataChange {
+            field: "loader_version".to_string(),
+            previous: change.previous,
+            current: change.current,
+        });
+    }
+
+    let version_json = if lock.version_id != current.version_id {
+        changed(&lock.version_id, &current.version_id)
+    } else if current.version_json_sha1.is_empty() {
+        None
+    } else {
+        changed(&lock.version_json_sha1, &current.version_json_sha1)
+    };
+
+    let mut changes = LaunchChanges {
+        available: true,
+        last_success_at: Some(lock.created_at.clone()),
+        mods_added,
+        mods_removed,
+        mods_modified,
+        config_files,
+        metadata,
+        java_runtime: timed(
+            changed(&lock.java_path, &current.java_path),
+            current.java_mtime,
+        ),
+        version_json: timed(version_json, current.version_json_mtime),
+        summary: String::new(),
+    };
+    changes.summary = summarize(&changes);
+    changes
+}
+
+fn names<'a>(items: impl Iterator<Item = &'a str>) -> String {
+    let items = items.collect::<Vec<_>>();
+    let mut text = items
+        .iter()
+        .take(SUMMARY_NAMES)
+        .copied()
+        .collect::<Vec<_>>()
+        .join(", ");
+    if items.len() > SUMMARY_NAMES {
+        text.push_str(&format!(" (+{})", items.len() - SUMMARY_NAMES));
+    }
+    text
+}
+
+fn summarize(changes: &LaunchChanges) -> String {
+    if !changes.available {
+        return "No hay un lanzamiento correcto anterior con el que comparar.".to_string();
+    }
+    let mut parts = Vec::new();
+    if !changes.mods_added.is_empty() {
+        parts.push(format!(
+            "añadido {}",
+            names(changes.mods_added.iter().map(|entry| entry.name.as_str()))
+        ));
+    }
+    if !changes.mods_removed.is_empty() {
+        parts.push(format!(
+            "quitado {}",
+            names(changes.mods_removed.iter().map(String::as_str))
+        ));
+    }
+    if !changes.mods_modified.is_empty() {
+        parts.push(format!(
+            "actualizado {}",
+            names(
+                changes
+                    .mods_modified
+                    .iter()
+                    .map(|entry| entry.name.as_str())
+            )
+        ));
+    }
+    for change in &changes.metadata {
+        parts.push(format!("cambiado {}", change.field));
+    }
+    if changes.java_runtime.is_some() {
+        parts.push("cambiado el runtime de Java".to_string());
+    }
+    if changes.version_json.is_some() {
+        parts.push("cambiado el JSON de versión".to_string());
+    }
+    if !changes.config_files.is_empty() {
+        parts.push(format!(
+            "{} archivo(s) de configuración modificados",
+            changes.config_files.len()
+        ));
+    }
+    if parts.is_empty() {
+        return "Sin cambios desde el último lanzamiento correcto.".to_string();
+    }
+    format!(
+        "Desde tu último lanzamiento correcto: {}.",
+        parts.join(", ")
+    )
+}
+
+/// Cambios de la instancia desde su último lanzamiento correcto; sin lockfile de referencia
+/// devuelve `available: false` en lugar de fallar.
+pub fn what_changed_since(instance_root: &Path) -> LaunchChanges {
+    let unavailable = || {
+        let mut changes = LaunchChanges::default();
+        changes.summary = summarize(&changes);
+        changes
+    };
+    let Ok(lock) = read_lock(&instance_root.join(SUCCESS_LAUNCH_LOCK_FILE)) else {
+        return unavailable();
+    };
+    match capture_current_state(instance_root) {
+        Ok(current) => diff_against_lock(&lock, &current),
+        Err(err) => {
+            log::warn!("⚠ No se pudo comparar con el último lanzamiento correcto: {err}");
+            unavailable()
+        }
+    }
+}
+
+/// Emite `instance_launch_failed` con el error y los cambios desde el último lanzamiento
+/// correcto. Se calcula en otro hilo para no retrasar la respuesta del lanzamiento.
+pub(crate) fn emit_launch_failure(app: &AppHandle, instance_root: &str, error: &LaunchError) {
+    let app = app.clone();
+    let instance_root = instance_root.to_string();
+    let error = error.clone();
+    thread::spawn(move || {
+        let changes = what_changed_since(Path::new(&instance_root));
+        emit_journaled(
+            &app,
+            &instance_root,
+            "instance_launch_failed",
+            serde_json::json!({
+                "instanceRoot": instance_root,
+                "error": error,
+                "changes": changes,
+            }),
+        );
+    });
+}
+
+#[tauri::command]
+pub fn what_changed_since_last_launch(
+    app: AppHandle,
+    instance_root: String,
+) -> Result<LaunchChanges, String> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    Ok(what_changed_since(instance_root.path()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn locked(name: &str, sha1: &str) -> LockedFile {
+        LockedFile {
+            path: name.to_string(),
+            sha1: sha1.to_string(),
+            size: 1,
+        }
+    }
+
+    fn success_lock() -> LaunchLock {
+        LaunchLock {
+            created_at: "2024-05-01T10:00:00+00:00".to_string(),
+            version_id: "1.20.1-forge-47.2.0".to_string(),
+            version_json_sha1: "aaa".to_string(),
+            minecraft_version: "1.20.1".to_string(),
+            loader: "forge".to_string(),
+            loader_version: "47.2.0".to_string(),
+            java_path: "/runtime/java17/17.0.8/bin/java".to_string(),
+            java_version: "openjdk version \"17.0.8\"".to_string(),
+            classpath: Vec::new(),
+            natives: Vec::new(),
+            asset_index: None,
+            library_overrides: Vec::new(),
+            mods: vec![locked("jei.jar", "1"), locked("sodium.jar", "2")],
+            config_files: BTreeMap::from([
+                ("options.txt".to_string(), 100),
+                ("config/jei.toml".to_string(), 200),
+            ]),
+            ram_mb: Some(4096),
+            java_args: vec!["-XX:+UseG1GC".to_string()],
+        }
+    }
+
+    fn unchanged_state(lock: &LaunchLock) -> CurrentState {
+        CurrentState {
+            mods: lock.mods.clone(),
+            mod_mtimes: BTreeMap::new(),
+            config_files: lock.config_files.clone(),
+            ram_mb: 4096,
+            java_args: lock.java_args.clone(),
+            loader_version: lock.loader_version.clone(),
+            java_path: lock.java_path.clone(),
+            java_mtime: None,
+            version_id: lock.version_id.clone(),
+            version_json_sha1: lock.version_json_sha1.clone(),
+            version_json_mtime: None,
+        }
+    }
+
+    #[test]
+    fn reports_mod_metadata_and_config_changes() {
+        let lock = success_lock();
+        let mut current = unchanged_state(&lock);
+        current.mods = vec![locked("jei.jar", "9"), locked("optifine.jar", "3")];
+        current
+            .mod_mtimes
+            .insert("optifine.jar".to_string(), 1_714_561_200_000);
+        current
+            .config_files
+            .insert("config/jei.toml".to_string(), 250);
+        current.java_args.push("-Dfml.debug=true".to_string());
+
+        let changes = diff_against_lock(&lock, &current);
+        assert_eq!(
+            changes.mods_added,
+            vec![ChangedEntry {
+                name: "optifine.jar".to_string(),
+                changed_at: Some("2024-05-01T11:00:00+00:00".to_string()),
+            }]
+        );
+        assert_eq!(changes.mods_removed, vec!["sodium.jar".to_string()]);
+        assert_eq!(changes.mods_modified[0].name, "jei.jar");
+        assert_eq!(changes.config_files[0].name, "config/jei.toml");
+        assert_eq!(changes.metadata[0].field, "java_args");
+        assert!(changes.java_runtime.is_none());
+        assert_eq!(
+            changes.summary,
+            "Desde tu último lanzamiento correcto: añadido optifine.jar, quitado sodium.jar, actualizado jei.jar, cambiado java_args, 1 archivo(s) de configuración modificados."
+        );
+    }
+
+    #[test]
+    fn identical_state_and_legacy_locks_report_nothing() {
+        let mut lock = success_lock();
+        let changes = diff_against_lock(&lock, &unchanged_state(&lock));
+        assert!(changes.available);
+        assert_eq!(
+            changes.summary,
+            "Sin cambios desde el último lanzamiento correcto."
+        );
+
+        // Un lockfile antiguo sin RAM ni argumentos no debe inventar cambios.
+        lock.ram_mb = None;
+        lock.java_args.clear();
+        let mut current = unchanged_state(&lock);
+        current.ram_mb = 6144;
+        current.java_args = vec!["-Xss2m".to_string()];
+        assert!(diff_against_lock(&lock, &current).metadata.is_empty());
+    }
+
+    #[test]
+    fn missing_success_lock_degrades_gracefully() {
+        let root = std::env::temp_dir().join(format!(
+            "launch-changes-{}",
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|elapsed| elapsed.as_nanos())
+                .unwrap_or_default()
+        ));
+        let changes = what_changed_since(&root);
+        assert!(!changes.available);
+        assert!(changes.mods_added.is_empty());
+        assert_eq!(
+            changes.summary,
+            "No hay un lanzamiento correcto anterior con el que comparar."
+        );
+    }
+}
@@ -0,0 +1,491 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    time::Instant,
+};
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::{
+    app::trusted_root::resolve_trusted_instance_root,
+    domain::minecraft::library::LibraryOverride,
+    infrastructure::checksum::{sha1::compute_file_sha1, verification_cache::VerificationCache},
+};
+
+const LAUNCH_LOCK_FILE: &str = ".launch-lock.json";
+const PREVIOUS_LAUNCH_LOCK_FILE: &str = ".launch-lock.previous.json";
+/// Copia del lockfile del último lanzamiento que terminó con código 0.
+pub(crate) const SUCCESS_LAUNCH_LOCK_FILE: &str = ".launch-lock.success.json";
+/// Tope de archivos de configuración que se anotan; packs enormes no deben frenar nada.
+const MAX_CONFIG_FILES: usize = 4000;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct LockedFile {
+    pub path: String,
+    pub sha1: String,
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct LockedAssetIndex {
+    pub id: String,
+    pub sha1: String,
+}
+
+/// Conjunto exacto de dependencias con el que arrancó la instancia.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchLock {
+    pub created_at: String,
+    pub version_id: String,
+    pub version_json_sha1: String,
+    pub minecraft_version: String,
+    pub loader: String,
+    pub loader_version: String,
+    pub java_path: String,
+    pub java_version: String,
+    pub classpath: Vec<LockedFile>,
+    pub natives: Vec<String>,
+    #[serde(default)]
+    pub asset_index: Option<LockedAssetIndex>,
+    /// `library_overrides` de la instancia en el momento del lanzamiento.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub library_overrides: Vec<LibraryOverride>,
+    /// Mods activos (`.jar` de la carpeta de mods efectiva) por nombre de archivo.
+    #[serde(default)]
+    pub mods: Vec<LockedFile>,
+    /// Archivos de configuración relativos a `minecraft/` con su mtime en milisegundos.
+    #[serde(default)]
+    pub config_files: BTreeMap<String, u64>,
+    #[serde(default)]
+    pub ram_mb: Option<u32>,
+    #[serde(default)]
+    pub java_args: Vec<String>,
+}
+
+/// Datos de la preparación necesarios para construir el lockfile.
+#[derive(Debug, Clone)]
+pub struct LaunchLockInputs {
+    pub launcher_root: PathBuf,
+    pub created_at: String,
+    pub version_id: String,
+    pub version_json_path: PathBuf,
+    pub minecraft_version: String,
+    pub loader: String,
+    pub loader_version: String,
+    pub java_path: String,
+    pub java_version: String,
+    pub classpath: Vec<PathBuf>,
+    pub natives_dir: PathBuf,
+    pub asset_index: Option<LockedAssetIndex>,
+    pub library_overrides: Vec<LibraryOverride>,
+    pub mods_dir: PathBuf,
+    pub game_dir: PathBuf,
+    pub ram_mb: u32,
+    pub java_args: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangedValue {
+    pub previous: String,
+    pub current: String,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangedFile {
+    pub path: String,
+    pub previous_sha1: String,
+    pub current_sha1: String,
+}
+
+/// Diferencias entre los dos últimos lanzamientos.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchLockDiff {
+    pub previous_created_at: String,
+    pub current_created_at: String,
+    pub added_libraries: Vec<String>,
+    pub removed_libraries: Vec<String>,
+    pub changed_libraries: Vec<ChangedFile>,
+    pub added_natives: Vec<String>,
+    pub removed_natives: Vec<String>,
+    pub version_id: Option<ChangedValue>,
+    pub version_json: Option<ChangedValue>,
+    pub loader_version: Option<ChangedValue>,
+    pub java_version: Option<ChangedValue>,
+    pub asset_index: Option<ChangedValue>,
+    pub unchanged: bool,
+}
+
+fn list_natives(natives_dir: &Path) -> Vec<String> {
+    let mut natives = fs::read_dir(natives_dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|entry| entry.path().is_file())
+                .map(|entry| entry.file_name().to_string_lossy().to_string())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    natives.sort();
+    natives
+}
+
+/// `.jar` activos de la carpeta de mods, ordenados por nombre.
+pub(crate) fn list_mod_jars(mods_dir: &Path) -> Vec<PathBuf> {
+    let mut jars = fs::read_dir(mods_dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| {
+                    path.is_file()
+                        && path
+                            .extension()
+                            .is_some_and(|ext| ext.eq_ignore_ascii_case("jar"))
+                })
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    jars.sort();
+    jars
+}
+
+/// Hashea los mods con la caché de verificación: solo se leen los que cambiaron de
+/// tamaño o mtime desde la última vez.
+pub(crate) fn hash_mods(cache: &mut VerificationCache, mods_dir: &Path) -> Vec<LockedFile> {
+    list_mod_jars(mods_dir)
+        .iter()
+        .filter_map(|jar| {
+            let name = jar.file_name()?.to_string_lossy().to_string();
+            let (sha1, size) = cache.sha1(jar).ok()?;
+            Some(LockedFile {
+                path: name,
+                sha1,
+                size,
+            })
+        })
+        .collect()
+}
+
+pub(crate) fn mtime_millis(path: &Path) -> Option<u64> {
+    let modified = fs::metadata(path).ok()?.modified().ok()?;
+    modified
+        .duration_since(std::time::UNIX_EPOCH)
+        .ok()
+        .map(|elapsed| elapsed.as_millis() as u64)
+}
+
+/// mtime de `config/**` y de los `options*.txt` de `game_dir`, sin leer su contenido.
+pub(crate) fn snapshot_config_files(game_dir: &Path) -> BTreeMap<String, u64> {
+    let mut files = BTreeMap::new();
+    if let Ok(entries) = fs::read_dir(game_dir) {
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name.starts_with("options") && name.ends_with(".txt") {
+                if let Some(mtime) = mtime_millis(&entry.path()) {
+                    files.insert(name, mtime);
+                }
+            }
+        }
+    }
+    let mut pending = vec![game_dir.join("config")];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                pending.push(path);
+                continue;
+            }
+            if files.len() >= MAX_CONFIG_FILES {
+                return files;
+            }
+            let (Ok(relative), Some(mtime)) = (path.strip_prefix(game_dir), mtime_millis(&path))
+            else {
+                continue;
+            };
+            files.insert(relative.to_string_lossy().replace('\\', "/"), mtime);
+        }
+    }
+    files
+}
+
+/// Hashea el classpath reutilizando la caché de verificación del launcher.
+pub fn build_launch_lock(inputs: &LaunchLockInputs) -> LaunchLock {
+    let mut cache = VerificationCache::load(&inputs.launcher_root);
+    let classpath = inputs
+        .classpath
+        .iter()
+        .map(|entry| {
+            let (sha1, size) = cache
+                .sha1(entry)
+                .unwrap_or_else(|_| ("missing".to_string(), 0));
+            LockedFile {
+                path: entry.display().to_string(),
+                sha1,
+                size,
+            }
+        })
+        .collect();
+    let mods = hash_mods(&mut cache, &inputs.mods_dir);
+    cache.save();
+    LaunchLock {
+        created_at: inputs.created_at.clone(),
+        version_id: inputs.version_id.clone(),
+        version_json_sha1: compute_file_sha1(&inputs.version_json_path).unwrap_or_default(),
+        minecraft_version: inputs.minecraft_version.clone(),
+        loader: inputs.loader.clone(),
+        loader_version: inputs.loader_version.clone(),
+        java_path: inputs.java_path.clone(),
+        java_version: inputs.java_version.clone(),
+        classpath,
+        natives: list_natives(&inputs.natives_dir),
+        asset_index: inputs.asset_index.clone(),
+        library_overrides: inputs.library_overrides.clone(),
+        mods,
+        config_files: snapshot_config_files(&inputs.game_dir),
+        ram_mb: Some(inputs.ram_mb),
+        java_args: inputs.java_args.clone(),
+    }
+}
+
+/// Construye y guarda el lockfile; el anterior pasa a `.launch-lock.previous.json`.
+pub fn record_launch_lock(instance_root: &Path, inputs: &LaunchLockInputs) {
+    let started = Instant::now();
+    let lock = build_launch_lock(inputs);
+    let current = instance_root.join(LAUNCH_LOCK_FILE);
+    if current.is_file() {
+        let _ = fs::rename(&current, instance_root.join(PREVIOUS_LAUNCH_LOCK_FILE));
+    }
+    match serde_json::to_string_pretty(&lock) {
+        Ok(raw) => {
+            if let Err(err) = fs::write(&current, raw) {
+                log::warn!("⚠ No se pudo guardar {}: {err}", current.display());
+                return;
+            }
+        }
+        Err(err) => {
+            log::warn!("⚠ No se pudo serializar el lockfile de lanzamiento: {err}");
+            return;
+        }
+    }
+    log::info!(
+        "🔹 Lockfile de lanzamiento guardado ({} entradas de classpath, {} ms)",
+        lock.classpath.len(),
+        started.elapsed().as_millis()
+    );
+}
+
+/// El juego terminó con código 0: el lockfile actual pasa a ser el último lanzamiento correcto.
+pub fn mark_launch_successful(instance_root: &Path, game_dir: &Path) {
+    let Ok(mut lock) = read_lock(&instance_root.join(LAUNCH_LOCK_FILE)) else {
+        return;
+    };
+    lock.config_files = snapshot_config_files(game_dir);
+    let target = instance_root.join(SUCCESS_LAUNCH_LOCK_FILE);
+    match serde_json::to_string_pretty(&lock) {
+        Ok(raw) => {
+            if let Err(err) = fs::write(&target, raw) {
+                log::warn!("⚠ No se pudo guardar {}: {err}", target.display());
+            }
+        }
+        Err(err) => log::warn!("⚠ No se pudo serializar el lockfile de lanzamiento: {err}"),
+    }
+}
+
+pub(crate) fn read_lock(path: &Path) -> Result<LaunchLock, String> {
+    let raw = fs::read_to_string(path)
+        .map_err(|err| format!("No se pudo leer {}: {err}", path.display()))?;
+    serde_json::from_str(&raw).map_err(|err| format!("Lockfile inválido {}: {err}", path.display()))
+}
+
+pub(crate) fn changed(previous: &str, current: &str) -> Option<ChangedValue> {
+    (previous != current).then(|| ChangedValue {
+        previous: previous.to_string(),
+        current: current.to_string(),
+    })
+}
+
+/// Compara dos lockfiles: librerías añadidas, quitadas o con otro hash, natives,
+/// versión, loader, build de Java e índice de assets.
+pub fn diff_locks(previous: &LaunchLock, current: &LaunchLock) -> LaunchLockDiff {
+    let before = previous
+        .classpath
+        .iter()
+        .map(|file| (file.path.as_str(), file.sha1.as_str()))
+        .collect::<BTreeMap<_, _>>();
+    let after = current
+        .classpath
+        .iter()
+        .map(|file| (file.path.as_str(), file.sha1.as_str()))
+        .collect::<BTreeMap<_, _>>();
+    let added_libraries = after
+        .keys()
+        .filter(|path| !before.contains_key(*path))
+        .map(|path| path.to_string())
+        .collect::<Vec<_>>();
+    let removed_libraries = before
+        .keys()
+        .filter(|path| !after.contains_key(*path))
+        .map(|path| path.to_string())
+        .collect::<Vec<_>>();
+    let changed_libraries = after
+        .iter()
+        .filter_map(|(path, sha1)| {
+            let previous_sha1 = before.get(path)?;
+            (previous_sha1 != sha1).then(|| ChangedFile {
+                path: path.to_string(),
+                previous_sha1: previous_sha1.to_string(),
+                current_sha1: sha1.to_string(),
+            })
+        })
+        .collect::<Vec<_>>();
+    let added_natives = current
+        .natives
+        .iter()
+        .filter(|native| !previous.natives.contains(native))
+        .cloned()
+        .collect::<Vec<_>>();
+    let removed_natives = previous
+        .natives
+        .iter()
+        .filter(|native| !current.natives.contains(native))
+        .cloned()
+        .collect::<Vec<_>>();
+    let asset_label = |lock: &LaunchLock| {
+        lock.asset_index
+            .as_ref()
+            .map(|index| format!("{} ({})", index.id, index.sha1))
+            .unwrap_or_default()
+    };
+
+    let mut diff = LaunchLockDiff {
+        previous_created_at: previous.created_at.clone(),
+        current_created_at: current.created_at.clone(),
+        added_libraries,
+        removed_libraries,
+        changed_libraries,
+        added_natives,
+        removed_natives,
+        version_id: changed(&previous.version_id, &current.version_id),
+        version_json: changed(&previous.version_json_sha1, &current.version_json_sha1),
+        loader_version: changed(&previous.loader_version, &current.loader_version),
+        java_version: changed(&previous.java_version, &current.java_version),
+        asset_index: changed(&asset_label(previous), &asset_label(current)),
+        unchanged: false,
+    };
+    diff.unchanged = diff.added_libraries.is_empty()
+        && diff.removed_libraries.is_empty()
+        && diff.changed_libraries.is_empty()
+        && diff.added_natives.is_empty()
+        && diff.removed_natives.is_empty()
+        && diff.version_id.is_none()
+        && diff.version_json.is_none()
+        && diff.loader_version.is_none()
+        && diff.java_version.is_none()
+        && diff.asset_index.is_none();
+    diff
+}
+
+/// Lockfile del último lanzamiento de la instancia.
+#[tauri::command]
+pub fn get_launch_lock(app: AppHandle, instance_root: String) -> Result<LaunchLock, String> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    let path = instance_root.path().join(LAUNCH_LOCK_FILE);
+    if !path.is_file() {
+        return Err("La instancia todavía no tiene lockfile de lanzamiento.".to_string());
+    }
+    read_lock(&path)
+}
+
+/// Qué cambió entre el penúltimo y el último lanzamiento.
+#[tauri::command]
+pub fn diff_launch_locks(app: AppHandle, instance_root: String) -> Result<LaunchLockDiff, String> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    let root = instance_root.path();
+    let previous_path = root.join(PREVIOUS_LAUNCH_LOCK_FILE);
+    if !previous_path.is_file() {
+        return Err("Hace falta al menos dos lanzamientos para comparar lockfiles.".to_string());
+    }
+    let previous = read_lock(&previous_path)?;
+    let current = read_lock(&root.join(LAUNCH_LOCK_FILE))?;
+    Ok(diff_locks(&previous, &current))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lock(classpath: &[(&str, &str)], java_version: &str) -> LaunchLock {
+        LaunchLock {
+            created_at: "2024-01-01T00:00:00Z".to_string(),
+            version_id: "1.20.1-forge-47.2.0".to_string(),
+            version_json_sha1: "aaa".to_string(),
+            minecraft_version: "1.20.1".to_string(),
+            loader: "forge".to_string(),
+            loader_version: "47.2.0".to_string(),
+            java_path: "/java/bin/java".to_string(),
+            java_version: java_version.to_string(),
+            classpath: classpath
+                .iter()
+                .map(|(path, sha1)| LockedFile {
+                    path: path.to_string(),
+                    sha1: sha1.to_string(),
+                    size: 1,
+                })
+                .collect(),
+            natives: vec!["liblwjgl.so".to_string()],
+            asset_index: Some(LockedAssetIndex {
+                id: "5".to_string(),
+                sha1: "bbb".to_string(),
+            }),
+            library_overrides: Vec::new(),
+            mods: Vec::new(),
+            config_files: BTreeMap::new(),
+            ram_mb: Some(4096),
+            java_args: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn reports_library_and_java_changes() {
+        let previous = lock(&[("a.jar", "1"), ("b.jar", "2")], "17.0.8");
+        let current = lock(&[("a.jar", "9"), ("c.jar", "3")], "17.0.9");
+        let diff = diff_locks(&previous, &current);
+        assert_eq!(diff.added_libraries, vec!["c.jar".to_string()]);
+        assert_eq!(diff.removed_libraries, vec!["b.jar".to_string()]);
+        assert_eq!(
+            diff.changed_libraries,
+            vec![ChangedFile {
+                path: "a.jar".to_string(),
+                previous_sha1: "1".to_string(),
+                current_sha1: "9".to_string(),
+            }]
+        );
+        assert_eq!(
+            diff.java_version,
+            Some(ChangedValue {
+                previous: "17.0.8".to_string(),
+                current: "17.0.9".to_string(),
+            })
+        );
+        assert!(!diff.unchanged);
+    }
+
+    #[test]
+    fn identical_locks_are_unchanged() {
+        let previous = lock(&[("a.jar", "1")], "17.0.8");
+        let diff = diff_locks(&previous, &previous.clone());
+        assert!(diff.unchanged);
+        assert!(diff.version_json.is_none());
+    }
+}
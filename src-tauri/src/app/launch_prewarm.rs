@@ -0,0 +1,289 @@
+// Prevalidación en segundo plano del lanzamiento.
+
+use std::{
+    collections::{hash_map::DefaultHasher, BTreeMap, HashMap},
+    fs,
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+    sync::{Mutex, MutexGuard, OnceLock},
+    time::{Duration, Instant, SystemTime},
+};
+
+use serde::Serialize;
+use serde_json::Value;
+use tauri::AppHandle;
+
+use crate::{
+    app::instance_service::{build_launch_prewarm, JarInspection},
+    app::trusted_root::resolve_trusted_instance_root,
+    domain::{minecraft::rule_engine::RuleTrace, models::instance::InstanceMetadata},
+    shared::tasks::{task_registry, TaskHandle},
+};
+
+/// Un plan más antiguo se descarta aunque la huella coincida.
+const PREWARM_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// Tamaño y fecha de modificación de un archivo.
+pub(crate) type FileStamp = Option<(u64, SystemTime)>;
+
+pub(crate) fn file_stamp(path: &Path) -> FileStamp {
+    let metadata = fs::metadata(path).ok()?;
+    Some((metadata.len(), metadata.modified().ok()?))
+}
+
+/// Inspección de jars reutilizable mientras ningún jar cambie.
+pub(crate) struct PrewarmedJars {
+    pub main_class: String,
+    pub stamps: HashMap<PathBuf, FileStamp>,
+    pub inspection: JarInspection,
+}
+
+impl PrewarmedJars {
+    /// `true` si cubre exactamente estos jars y ninguno cambió desde la prevalidación.
+    pub fn still_valid(&self, main_class: &str, jars: &[PathBuf]) -> bool {
+        self.main_class == main_class
+            && jars.iter().all(|jar| {
+                self.stamps
+                    .get(jar)
+                    .is_some_and(|stamp| *stamp == file_stamp(jar))
+            })
+    }
+}
+
+/// Resultado de la prevalidación de una instancia.
+pub(crate) struct PrewarmedLaunch {
+    pub fingerprint: u64,
+    pub java_path: PathBuf,
+    pub java_stamp: FileStamp,
+    /// Salida de `java -version` (stderr) si terminó bien.
+    pub java_version_text: Option<String>,
+    pub version_id: String,
+    pub version_json: Value,
+    /// `None` si faltan librerías o natives: el lanzamiento los descargará.
+    pub jars: Option<PrewarmedJars>,
+    pub summary: LaunchPrewarmSummary,
+    pub created_at: Instant,
+}
+
+impl PrewarmedLaunch {
+    /// Salida de `java -version` si el ejecutable es el mismo y no cambió.
+    pub fn java_version_for(&self, java_path: &Path) -> Option<&str> {
+        (self.java_path == java_path && self.java_stamp == file_stamp(java_path))
+            .then_some(self.java_version_text.as_deref())
+            .flatten()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchPrewarmSummary {
+    pub instance_root: String,
+    /// Todo lo de solo lectura está listo: Play no tendrá que rehacerlo.
+    pub ready: bool,
+    pub java_checked: bool,
+    pub libraries: usize,
+    pub missing_libraries: usize,
+    pub native_jars: usize,
+    pub asset_index_present: bool,
+    pub elapsed_ms: u64,
+    /// Evaluación de reglas por librería y grupo de argumentos, solo si se pidió.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rule_trace: Option<BTreeMap<String, RuleTrace>>,
+}
+
+struct PrewarmState {
+    plans: HashMap<String, PrewarmedLaunch>,
+    /// Tarea de prevalidación en curso por instancia.
+    running: HashMap<String, String>,
+}
+
+static PREWARM: OnceLock<Mutex<PrewarmState>> = OnceLock::new();
+
+fn prewarm_state() -> MutexGuard<'static, PrewarmState> {
+    PREWARM
+        .get_or_init(|| {
+            Mutex::new(PrewarmState {
+                plans: HashMap::new(),
+                running: HashMap::new(),
+            })
+        })
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Huella de lo que determina el plan: los campos de metadata que afectan al lanzamiento
+/// (no `last_used`, que se actualiza al pulsar Play) y tamaño/fecha de cada version.json.
+pub(crate) fn launch_inputs_fingerprint(instance_path: &Path, metadata: &InstanceMetadata) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    (
+        &metadata.minecraft_version,
+        &metadata.version_id,
+        &metadata.loader,
+        &metadata.loader_version,
+        &metadata.java_runtime,
+        &metadata.java_build_pin,
+    )
+        .hash(&mut hasher);
+    serde_json::to_string(&metadata.library_overrides)
+        .unwrap_or_default()
+        .hash(&mut hasher);
+
+    let mut version_jsons = fs::read_dir(instance_path.join("minecraft").join("versions"))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .flat_map(|entry| fs::read_dir(entry.path()).into_iter().flatten().flatten())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect::<Vec<_>>();
+    version_jsons.sort();
+    for path in version_jsons {
+        (&path, file_stamp(&path)).hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+/// Saca el plan de la instancia (se usa una sola vez) y cancela una prevalidación que
+/// siga en curso para no competir con el lanzamiento.
+pub(crate) fn take_prewarmed_launch(
+    app: &AppHandle,
+    instance_root: &str,
+) -> Option<PrewarmedLaunch> {
+    let mut state = prewarm_state();
+    if let Some(task_id) = state.running.remove(instance_root) {
+        task_registry(app).cancel(&task_id);
+    }
+    state
+        .plans
+        .remove(instance_root)
+        .filter(|plan| plan.created_at.elapsed() < PREWARM_TTL)
+}
+
+/// Prevalida la instancia en segundo plano; se cancela con `cancel_task` o al pulsar Play.
+#[tauri::command]
+pub async fn prewarm_instance(
+    app: AppHandle,
+    instance_root: String,
+    trace_rules: Option<bool>,
+) -> Result<LaunchPrewarmSummary, String> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    tauri::async_runtime::spawn_blocking(move || {
+        let key = instance_root.to_string();
+        let task = TaskHandle::begin(
+            &app,
+            "launch_prewarm",
+            format!("Prevalidando {instance_root}"),
+        );
+        {
+            let mut state = prewarm_state();
+            if let Some(previous) = state
+                .running
+                .insert(key.clone(), task.task_id().to_string())
+            {
+                task_registry(&app).cancel(&previous);
+            }
+            state.plans.remove(&key);
+        }
+        let result =
+            build_launch_prewarm(&instance_root, &task.probe(), trace_rules.unwrap_or(false));
+        let mut state = prewarm_state();
+        if state.running.get(&key).map(String::as_str) == Some(task.task_id()) {
+            state.running.remove(&key);
+        }
+        let summary = match &result {
+            Ok(plan) if !task.is_cancelled() => {
+                let summary = plan.summary.clone();
+                log::info!(
+                    "✔ Prevalidación de {instance_root} en {} ms (lista: {})",
+                    summary.elapsed_ms,
+                    summary.ready
+                );
+                Ok(summary)
+            }
+            Ok(_) => Err(crate::shared::tasks::cancelled_error("launch_prewarm")),
+            Err(err) => Err(err.clone()),
+        };
+        if let (Ok(plan), Ok(_)) = (result, &summary) {
+            state.plans.insert(key, plan);
+        }
+        drop(state);
+        task.finish(&summary);
+        summary
+    })
+    .await
+    .map_err(|err| format!("Falló la tarea de prevalidación: {err}"))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::test_support::test_temp_dir;
+
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = test_temp_dir(&format!("launch-prewarm-{name}"));
+        fs::create_dir_all(dir.join("minecraft/versions/1.20.1")).expect("versions");
+        dir
+    }
+
+    fn metadata() -> InstanceMetadata {
+        serde_json::from_value(serde_json::json!({
+            "name": "Survival",
+            "group": "Sin grupo",
+            "minecraftVersion": "1.20.1",
+            "loader": "vanilla",
+            "loaderVersion": "",
+            "ramMb": 4096,
+            "javaArgs": [],
+            "javaPath": "/java/bin/java",
+            "javaRuntime": "java17",
+            "lastUsed": null,
+            "internalUuid": "uuid",
+        }))
+        .expect("metadata")
+    }
+
+    #[test]
+    fn fingerprint_ignores_last_used_but_tracks_version_jsons() {
+        let dir = test_dir("fingerprint");
+        let version_json = dir.join("minecraft/versions/1.20.1/1.20.1.json");
+        fs::write(&version_json, "{}").expect("json");
+        let mut metadata = metadata();
+        let before = launch_inputs_fingerprint(&dir, &metadata);
+
+        metadata.last_used = Some("2026-01-01T00:00:00Z".to_string());
+        assert_eq!(launch_inputs_fingerprint(&dir, &metadata), before);
+
+        fs::write(&version_json, "{\"id\":\"1.20.1\"}").expect("json");
+        assert_ne!(launch_inputs_fingerprint(&dir, &metadata), before);
+
+        let current = launch_inputs_fingerprint(&dir, &metadata);
+        metadata.loader_version = "0.15.11".to_string();
+        assert_ne!(launch_inputs_fingerprint(&dir, &metadata), current);
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn prewarmed_jars_are_invalidated_by_a_changed_jar() {
+        let dir = test_dir("jars");
+        let jar = dir.join("lib-1.0.jar");
+        fs::write(&jar, b"jar").expect("jar");
+        let jars = PrewarmedJars {
+            main_class: "net.minecraft.client.main.Main".to_string(),
+            stamps: HashMap::from([(jar.clone(), file_stamp(&jar))]),
+            inspection: JarInspection::default(),
+        };
+        assert!(jars.still_valid("net.minecraft.client.main.Main", std::slice::from_ref(&jar)));
+        assert!(!jars.still_valid("other.Main", std::slice::from_ref(&jar)));
+        assert!(!jars.still_valid(
+            "net.minecraft.client.main.Main",
+            &[jar.clone(), dir.join("new.jar")]
+        ));
+
+        fs::write(&jar, b"jar modificado").expect("jar");
+        assert!(!jars.still_valid("net.minecraft.client.main.Main", std::slice::from_ref(&jar)));
+
+        let _ = fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,564 @@
+use std::{
+    cell::RefCell,
+    collections::BTreeMap,
+    io::Read,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::infrastructure::filesystem::config::load_launcher_config;
+
+pub const DEFAULT_PHASE_BUDGET_SECS: u64 = 120;
+const DOWNLOAD_CHUNK_BYTES: usize = 64 * 1024;
+const WATCHDOG_POLL_INTERVAL: Duration = Duration::from_millis(500);
+const PAUSE_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Fase que superó su presupuesto de tiempo durante la preparación del lanzamiento.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchPhaseTimeout {
+    pub phase: String,
+    pub sub_operation: Option<String>,
+    pub elapsed_secs: u64,
+    pub budget_secs: u64,
+}
+
+impl LaunchPhaseTimeout {
+    pub fn message(&self) -> String {
+        format!(
+            "TIMEOUT: la fase '{}' superó {}s sin terminar ({}s). Última operación: {}.",
+            self.phase,
+            self.budget_secs,
+            self.elapsed_secs,
+            self.sub_operation.as_deref().unwrap_or("desconocida")
+        )
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchPreparationStatus {
+    pub preparing: bool,
+    pub phase: Option<String>,
+    pub elapsed_ms: u64,
+    pub sub_operation: Option<String>,
+    pub budget_secs: u64,
+    pub timeout: Option<LaunchPhaseTimeout>,
+    /// La fase actual descarga archivos y admite pausa.
+    pub pausable: bool,
+    /// Pausada: no se empiezan descargas nuevas y el presupuesto de la fase no corre.
+    pub paused: bool,
+}
+
+impl LaunchPreparationStatus {
+    pub fn idle() -> Self {
+        Self {
+            preparing: false,
+            phase: None,
+            elapsed_ms: 0,
+            sub_operation: None,
+            budget_secs: DEFAULT_PHASE_BUDGET_SECS,
+            timeout: None,
+            pausable: false,
+            paused: false,
+        }
+    }
+}
+
+/// Duración de una fase de la preparación, para la línea de tiempo del lanzamiento.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchPhaseTiming {
+    pub phase: String,
+    pub elapsed_ms: u64,
+    /// Contadores de la fase (p. ej. `jarOpenRetries`), para los bundles de soporte.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub counters: BTreeMap<String, u64>,
+}
+
+#[derive(Debug)]
+struct WatchdogState {
+    phase: Option<String>,
+    phase_started_at: Instant,
+    sub_operation: Option<String>,
+    timeout: Option<LaunchPhaseTimeout>,
+    completed_phases: Vec<LaunchPhaseTiming>,
+    phase_counters: BTreeMap<String, u64>,
+    pausable: bool,
+    paused_since: Option<Instant>,
+}
+
+impl WatchdogState {
+    /// Tiempo en la fase actual sin contar la pausa en curso.
+    fn phase_elapsed(&self) -> Duration {
+        self.paused_since
+            .unwrap_or_else(Instant::now)
+            .saturating_duration_since(self.phase_started_at)
+    }
+}
+
+/// Supervisa la preparación del lanzamiento: registra la fase actual y, si una fase
+/// supera el presupuesto, marca la cancelación para que las descargas se detengan.
+#[derive(Debug, Clone)]
+pub struct LaunchWatchdog {
+    state: Arc<Mutex<WatchdogState>>,
+    cancelled: Arc<AtomicBool>,
+    budget: Option<Duration>,
+}
+
+thread_local! {
+    static CURRENT_WATCHDOG: RefCell<Option<LaunchWatchdog>> = const { RefCell::new(None) };
+}
+
+impl LaunchWatchdog {
+    pub fn new(budget: Duration) -> Self {
+        Self::with_budget(Some(budget))
+    }
+
+    /// Watchdog sin presupuesto, para cuando la preparación se invoca directamente.
+    pub fn detached() -> Self {
+        Self::with_budget(None)
+    }
+
+    fn with_budget(budget: Option<Duration>) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(WatchdogState {
+                phase: None,
+                phase_started_at: Instant::now(),
+                sub_operation: None,
+                timeout: None,
+                completed_phases: Vec::new(),
+                phase_counters: BTreeMap::new(),
+                pausable: false,
+                paused_since: None,
+            })),
+            cancelled: Arc::new(AtomicBool::new(false)),
+            budget,
+        }
+    }
+
+    /// Inicia una fase nueva (reinicia su cronómetro). Falla si ya se abortó.
+    pub fn enter_phase(&self, phase: &str) -> Result<(), String> {
+        self.check()?;
+        if let Ok(mut state) = self.state.lock() {
+            if let Some(previous) = state.phase.take() {
+                let elapsed_ms = state.phase_elapsed().as_millis() as u64;
+                let counters = std::mem::take(&mut state.phase_counters);
+                state.completed_phases.push(LaunchPhaseTiming {
+                    phase: previous,
+                    elapsed_ms,
+                    counters,
+                });
+            }
+            state.phase = Some(phase.to_string());
+            state.phase_started_at = Instant::now();
+            state.sub_operation = None;
+        }
+        Ok(())
+    }
+
+    pub fn set_sub_operation(&self, operation: impl Into<String>) {
+        if let Ok(mut state) = self.state.lock() {
+            state.sub_operation = Some(operation.into());
+        }
+    }
+
+    /// Suma `value` al contador `name` de la fase actual.
+    pub fn add_phase_counter(&self, name: &str, value: u64) {
+        if let Ok(mut state) = self.state.lock() {
+            *state.phase_counters.entry(name.to_string()).or_insert(0) += value;
+        }
+    }
+
+    /// Devuelve el error TIMEOUT si la preparación fue abortada.
+    pub fn check(&self) -> Result<(), String> {
+        if !self.cancelled.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+        let timeout = self
+            .state
+            .lock()
+            .ok()
+            .and_then(|state| state.timeout.clone());
+        Err(timeout
+            .map(|timeout| timeout.message())
+            .unwrap_or_else(|| "TIMEOUT: preparación del lanzamiento abortada.".to_string()))
+    }
+
+    /// Comprueba el presupuesto de la fase actual y aborta si se superó.
+    pub fn check_budget(&self) -> Option<LaunchPhaseTimeout> {
+        let budget = self.budget?;
+        let mut state = self.state.lock().ok()?;
+        if let Some(timeout) = state.timeout.clone() {
+            return Some(timeout);
+        }
+        if state.paused_since.is_some() {
+            return None;
+        }
+        let elapsed = state.phase_elapsed();
+        if state.phase.is_none() || elapsed <= budget {
+            return None;
+        }
+        let timeout = LaunchPhaseTimeout {
+            phase: state.phase.clone().unwrap_or_default(),
+            sub_operation: state.sub_operation.clone(),
+            elapsed_secs: elapsed.as_secs(),
+            budget_secs: budget.as_secs(),
+        };
+        state.timeout = Some(timeout.clone());
+        self.cancelled.store(true, Ordering::Relaxed);
+        log::warn!("⚠ {}", timeout.message());
+        Some(timeout)
+    }
+
+    /// Espera hasta que alguna fase supere el presupuesto y devuelve el mensaje de error.
+    pub async fn expired(&self) -> String {
+        loop {
+            tokio::time::sleep(WATCHDOG_POLL_INTERVAL).await;
+            if let Some(timeout) = self.check_budget() {
+                return timeout.message();
+            }
+        }
+    }
+
+    /// Fases terminadas y la actual (hasta ahora), en orden de ejecución.
+    pub fn timeline(&self) -> Vec<LaunchPhaseTiming> {
+        let Ok(state) = self.state.lock() else {
+            return Vec::new();
+        };
+        let mut timeline = state.completed_phases.clone();
+        if let Some(phase) = state.phase.clone() {
+            timeline.push(LaunchPhaseTiming {
+                phase,
+                elapsed_ms: state.phase_elapsed().as_millis() as u64,
+                counters: state.phase_counters.clone(),
+            });
+        }
+        timeline
+    }
+
+    pub fn status(&self) -> LaunchPreparationStatus {
+        let Ok(state) = self.state.lock() else {
+            return LaunchPreparationStatus::idle();
+        };
+        LaunchPreparationStatus {
+            preparing: state.timeout.is_none(),
+            phase: state.phase.clone(),
+            elapsed_ms: state.phase_elapsed().as_millis() as u64,
+            sub_operation: state.sub_operation.clone(),
+            budget_secs: self
+                .budget
+                .map(|budget| budget.as_secs())
+                .unwrap_or(DEFAULT_PHASE_BUDGET_SECS),
+            timeout: state.timeout.clone(),
+            pausable: state.pausable,
+            paused: state.paused_since.is_some(),
+        }
+    }
+
+    /// Marca el tramo de descargas de la fase actual como pausable mientras viva el guard.
+    pub fn pausable_downloads(&self) -> PausableDownloads<'_> {
+        if let Ok(mut state) = self.state.lock() {
+            state.pausable = true;
+        }
+        PausableDownloads { watchdog: self }
+    }
+
+    /// Deja de empezar descargas nuevas; las que están en curso terminan.
+    pub fn pause(&self) -> Result<(), String> {
+        self.check()?;
+        let mut state = self
+            .state
+            .lock()
+            .map_err(|_| "No se pudo bloquear el estado de la preparación.".to_string())?;
+        if !state.pausable {
+            return Err(
+                "Solo se puede pausar la preparación mientras descarga assets o librerías."
+                    .to_string(),
+            );
+        }
+        if state.paused_since.is_none() {
+            state.paused_since = Some(Instant::now());
+            log::info!(
+                "🔹 Descargas del lanzamiento en pausa (fase {}).",
+                state.phase.as_deref().unwrap_or("desconocida")
+            );
+        }
+        Ok(())
+    }
+
+    /// Reanuda desde el siguiente archivo pendiente; el tiempo en pausa no cuenta para el
+    /// presupuesto de la fase.
+    pub fn resume(&self) -> Result<(), String> {
+        let mut state = self
+            .state
+            .lock()
+            .map_err(|_| "No se pudo bloquear el estado de la preparación.".to_string())?;
+        let Some(paused_since) = state.paused_since.take() else {
+            return Err("La preparación del lanzamiento no está en pausa.".to_string());
+        };
+        state.phase_started_at += paused_since.elapsed();
+        log::info!("🔹 Descargas del lanzamiento reanudadas.");
+        Ok(())
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.state
+            .lock()
+            .map(|state| state.paused_since.is_some())
+            .unwrap_or(false)
+    }
+
+    /// Punto de pausa antes de empezar cada descarga: bloquea mientras esté pausada y
+    /// sale con error si entretanto se cancela (`cancelled`) o se aborta la preparación.
+    pub fn wait_while_paused(
+        &self,
+        cancelled: impl Fn() -> Result<(), String>,
+    ) -> Result<(), String> {
+        loop {
+            self.check()?;
+            cancelled()?;
+            if !self.is_paused() {
+                return Ok(());
+            }
+            std::thread::sleep(PAUSE_POLL_INTERVAL);
+        }
+    }
+}
+
+/// Guard de [`LaunchWatchdog::pausable_downloads`]; al soltarse la fase deja de ser
+/// pausable y cualquier pausa pendiente se descarta.
+pub struct PausableDownloads<'a> {
+    watchdog: &'a LaunchWatchdog,
+}
+
+impl Drop for PausableDownloads<'_> {
+    fn drop(&mut self) {
+        if let Ok(mut state) = self.watchdog.state.lock() {
+            state.pausable = false;
+            if let Some(paused_since) = state.paused_since.take() {
+                state.phase_started_at += paused_since.elapsed();
+            }
+        }
+    }
+}
+
+/// Restaura el supervisor anterior del hilo al salir, también si `f` entra en pánico.
+struct WatchdogScope(Option<LaunchWatchdog>);
+
+impl Drop for WatchdogScope {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        CURRENT_WATCHDOG.with(|current| *current.borrow_mut() = previous);
+    }
+}
+
+/// Ejecuta `f` con `watchdog` como supervisor del hilo actual.
+pub fn run_with_watchdog<T>(watchdog: LaunchWatchdog, f: impl FnOnce() -> T) -> T {
+    let previous = CURRENT_WATCHDOG.with(|current| current.borrow_mut().replace(watchdog));
+    let _scope = WatchdogScope(previous);
+    f()
+}
+
+/// Watchdog del hilo actual, o uno sin presupuesto si no hay ninguno activo.
+pub fn current_watchdog() -> LaunchWatchdog {
+    CURRENT_WATCHDOG
+        .with(|current| current.borrow().clone())
+        .unwrap_or_else(LaunchWatchdog::detached)
+}
+
+/// Presupuesto por fase configurado en `launcher_config.json` (`launch_phase_timeout_secs`).
+pub fn configured_phase_budget(app: &AppHandle) -> Duration {
+    let secs = load_launcher_config(app)
+        .ok()
+        .and_then(|config| config.launch_phase_timeout_secs)
+        .filter(|secs| *secs > 0)
+        .unwrap_or(DEFAULT_PHASE_BUDGET_SECS);
+    Duration::from_secs(secs)
+}
+
+/// Descarga `url` por bloques comprobando la cancelación entre cada uno, para que un
+/// TIMEOUT del watchdog detenga la descarga en lugar de dejar el hilo colgado.
+pub fn download_bytes_cancellable(
+    client: &reqwest::blocking::Client,
+    url: &str,
+    watchdog: &LaunchWatchdog,
+) -> Result<Vec<u8>, String> {
+    watchdog.set_sub_operation(format!("descargando {url}"));
+    watchdog.check()?;
+    let mut response = client
+        .get(url)
+        .send()
+        .and_then(|response| response.error_for_status())
+        .map_err(|err| format!("No se pudo descargar {url}: {err}"))?;
+
+    let mut bytes = Vec::new();
+    let mut chunk = vec![0u8; DOWNLOAD_CHUNK_BYTES];
+    loop {
+        watchdog.check()?;
+        let read = response
+            .read(&mut chunk)
+            .map_err(|err| format!("No se pudo leer respuesta de {url}: {err}"))?;
+        if read == 0 {
+            break;
+        }
+        bytes.extend_from_slice(&chunk[..read]);
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn watchdog_aborts_phase_over_budget_with_phase_and_sub_operation() {
+        let watchdog = LaunchWatchdog::new(Duration::from_millis(10));
+        watchdog.enter_phase("assets").expect("fase");
+        watchdog.set_sub_operation("descargando https://example.invalid/index.json");
+
+        std::thread::sleep(Duration::from_millis(30));
+        let timeout = watchdog.check_budget().expect("debe abortar");
+        assert_eq!(timeout.phase, "assets");
+        let err = watchdog.check().expect_err("cancelado");
+        assert!(err.starts_with("TIMEOUT"));
+        assert!(err.contains("https://example.invalid/index.json"));
+        assert!(watchdog.enter_phase("libraries").is_err());
+    }
+
+    #[test]
+    fn detached_watchdog_never_times_out_and_thread_local_is_restored() {
+        let watchdog = LaunchWatchdog::detached();
+        watchdog.enter_phase("java").expect("fase");
+        assert!(watchdog.check_budget().is_none());
+
+        let supervised = LaunchWatchdog::new(Duration::from_secs(5));
+        let phase = run_with_watchdog(supervised, || {
+            current_watchdog().enter_phase("auth").expect("fase");
+            current_watchdog().status().phase
+        });
+        assert_eq!(phase.as_deref(), Some("auth"));
+        assert_eq!(current_watchdog().status().phase, None);
+    }
+
+    #[test]
+    fn panic_inside_supervised_work_still_clears_the_thread_local() {
+        let supervised = LaunchWatchdog::new(Duration::from_secs(5));
+        let outcome = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            run_with_watchdog(supervised, || {
+                current_watchdog().enter_phase("java").expect("fase");
+                panic!("fallo en la preparación");
+            })
+        }));
+        assert!(outcome.is_err());
+        assert!(CURRENT_WATCHDOG.with(|current| current.borrow().is_none()));
+    }
+
+    #[test]
+    fn timeline_records_phases_in_order() {
+        let watchdog = LaunchWatchdog::detached();
+        assert!(watchdog.timeline().is_empty());
+        watchdog.enter_phase("libraries").expect("fase");
+        watchdog.add_phase_counter("downloads", 2);
+        std::thread::sleep(Duration::from_millis(5));
+        watchdog.enter_phase("jars").expect("fase");
+        watchdog.add_phase_counter("jarOpenRetries", 1);
+        watchdog.add_phase_counter("jarOpenRetries", 2);
+
+        let timeline = watchdog.timeline();
+        let phases = timeline
+            .iter()
+            .map(|timing| timing.phase.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(phases, vec!["libraries", "jars"]);
+        assert!(timeline[0].elapsed_ms >= 5);
+        assert_eq!(timeline[0].counters.get("downloads"), Some(&2));
+        assert_eq!(timeline[1].counters.get("jarOpenRetries"), Some(&3));
+    }
+
+    #[test]
+    fn paused_download_queue_resumes_where_it_stopped() {
+        let watchdog = LaunchWatchdog::new(Duration::from_millis(300));
+        watchdog.enter_phase("assets").expect("fase");
+        assert!(
+            watchdog.pause().is_err(),
+            "fuera de las descargas no hay pausa"
+        );
+
+        let completed = Arc::new(Mutex::new(Vec::new()));
+        let (started_tx, started_rx) = std::sync::mpsc::channel();
+        let (gate_tx, gate_rx) = std::sync::mpsc::channel::<()>();
+        let worker = {
+            let watchdog = watchdog.clone();
+            let completed = completed.clone();
+            std::thread::spawn(move || {
+                let _downloads = watchdog.pausable_downloads();
+                for item in 0..5_u32 {
+                    watchdog.wait_while_paused(|| Ok(()))?;
+                    started_tx.send(item).expect("canal");
+                    if item == 1 {
+                        // Descarga en curso cuando llega la pausa: debe terminar igualmente.
+                        gate_rx.recv().expect("canal");
+                    }
+                    completed.lock().expect("lock").push(item);
+                }
+                Ok::<(), String>(())
+            })
+        };
+
+        assert_eq!(started_rx.recv().expect("item 0"), 0);
+        assert_eq!(started_rx.recv().expect("item 1"), 1);
+        watchdog.pause().expect("pausa");
+        gate_tx.send(()).expect("canal");
+        std::thread::sleep(Duration::from_millis(450));
+
+        let status = watchdog.status();
+        assert!(status.paused && status.pausable);
+        assert!(status.timeout.is_none(), "el presupuesto no corre en pausa");
+        assert!(watchdog.check_budget().is_none());
+        assert_eq!(*completed.lock().expect("lock"), vec![0, 1]);
+
+        watchdog.resume().expect("reanudar");
+        assert!(watchdog.resume().is_err());
+        worker.join().expect("hilo").expect("descargas");
+        assert_eq!(*completed.lock().expect("lock"), vec![0, 1, 2, 3, 4]);
+        assert!(!watchdog.status().pausable);
+    }
+
+    #[test]
+    fn cancelling_while_paused_abandons_the_queue() {
+        let watchdog = LaunchWatchdog::detached();
+        watchdog.enter_phase("libraries").expect("fase");
+        let downloads = watchdog.pausable_downloads();
+        watchdog.pause().expect("pausa");
+
+        let cancelled = AtomicBool::new(false);
+        let waiter = std::thread::scope(|scope| {
+            let handle = scope.spawn(|| {
+                watchdog.wait_while_paused(|| {
+                    if cancelled.load(Ordering::Relaxed) {
+                        Err("Operación cancelada.".to_string())
+                    } else {
+                        Ok(())
+                    }
+                })
+            });
+            std::thread::sleep(Duration::from_millis(50));
+            cancelled.store(true, Ordering::Relaxed);
+            handle.join().expect("hilo")
+        });
+        assert_eq!(waiter, Err("Operación cancelada.".to_string()));
+
+        drop(downloads);
+        assert!(
+            !watchdog.is_paused(),
+            "la pausa no sobrevive al tramo de descargas"
+        );
+    }
+}
@@ -0,0 +1,392 @@
+use std::{
+    collections::HashSet,
+    path::Path,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex, MutexGuard, OnceLock,
+    },
+};
+
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use serde_json::{json, Value};
+use tauri::{AppHandle, Emitter};
+
+use crate::{
+    app::{
+        game_dir_guard::held_session_locks,
+        instance_service::{compute_instance_health, is_instance_running, read_instance_metadata},
+        instances_watcher::running_instances_without_folder,
+        launcher_service::list_instances_readonly,
+        launcher_snapshot::mark_launcher_snapshot_dirty,
+        op_journal::{needs_recovery, NEEDS_RECOVERY_STATE},
+        redirect_launch::redirect_cache_inconsistencies,
+    },
+    domain::models::{instance::InstanceSummary, java::JavaRuntime},
+    infrastructure::filesystem::paths::resolve_launcher_root,
+    services::java_installer::{cached_runtime_health, list_java_builds},
+    shared::clock::{app_clock, Clock},
+};
+
+/// La lista se recalcula como mucho cada 5 minutos salvo que algo la invalide.
+const PROBLEMS_TTL_SECONDS: i64 = 300;
+/// Por debajo de esto no se pueden crear instancias (ver `validate_instance_constraints`).
+const DISK_ERROR_BYTES: u64 = 1024 * 1024 * 1024;
+const DISK_WARNING_BYTES: u64 = 5 * 1024 * 1024 * 1024;
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct LauncherProblem {
+    /// Código estable (`runtime_corrupt`, `version_missing`...).
+    pub code: String,
+    /// `error`, `warning` o `info`.
+    pub severity: String,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instance_root: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instance_name: Option<String>,
+    /// Comando que lo soluciona y sus argumentos, si existe uno.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fix_command: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fix_args: Option<Value>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LauncherProblemsReport {
+    pub computed_at: String,
+    pub problems: Vec<LauncherProblem>,
+}
+
+struct CachedProblems {
+    computed_at: DateTime<Utc>,
+    problems: Vec<LauncherProblem>,
+}
+
+static PROBLEMS_CACHE: OnceLock<Mutex<Option<CachedProblems>>> = OnceLock::new();
+static RECOMPUTING: AtomicBool = AtomicBool::new(false);
+
+fn problems_cache() -> MutexGuard<'static, Option<CachedProblems>> {
+    PROBLEMS_CACHE
+        .get_or_init(|| Mutex::new(None))
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn problem(code: &str, severity: &str, message: String) -> LauncherProblem {
+    LauncherProblem {
+        code: code.to_string(),
+        severity: severity.to_string(),
+        message,
+        instance_root: None,
+        instance_name: None,
+        fix_command: None,
+        fix_args: None,
+    }
+}
+
+fn with_fix(mut problem: LauncherProblem, command: &str, args: Value) -> LauncherProblem {
+    problem.fix_command = Some(command.to_string());
+    problem.fix_args = Some(args);
+    problem
+}
+
+/// Comando que resuelve cada hallazgo de `compute_instance_health`, si lo hay.
+fn fix_for_health_code(code: &str) -> Option<&'static str> {
+    match code {
+        "version_missing" | "verification_stale" => Some("repair_instance"),
+        "java_missing" => Some("reinstall_embedded_runtime"),
+        _ => None,
+    }
+}
+
+fn instance_problems(instance: &InstanceSummary, clock: &dyn Clock) -> Vec<LauncherProblem> {
+    let root = &instance.instance_root;
+    let mut problems = Vec::new();
+    if instance.state.as_deref() == Some(NEEDS_RECOVERY_STATE) || needs_recovery(Path::new(root)) {
+        problems.push(with_fix(
+            problem(
+                "interrupted_operation",
+                "error",
+                "Una operación quedó a medias; la instancia necesita recuperarse.".to_string(),
+            ),
+            "recover_interrupted_operation",
+            json!({ "instanceRoot": root }),
+        ));
+    } else {
+        let health = instance
+            .health
+            .clone()
+            .unwrap_or_else(|| compute_instance_health(root, clock));
+        let metadata = read_instance_metadata(root.clone()).ok();
+        for finding in health.findings {
+            let mut entry = problem(&finding.code, &finding.severity, finding.message);
+            if let Some(command) = fix_for_health_code(&finding.code) {
+                let args = match (command, metadata.as_ref()) {
+                    ("reinstall_embedded_runtime", Some(metadata)) => {
+                        json!({ "major": metadata.required_java_major })
+                    }
+                    ("reinstall_embedded_runtime", None) => continue,
+                    _ => json!({ "instanceRoot": root }),
+                };
+                entry = with_fix(entry, command, args);
+            }
+            problems.push(entry);
+        }
+
+        let owns_game_dir = metadata
+            .as_ref()
+            .is_some_and(|metadata| !metadata.state.eq_ignore_ascii_case("redirect"));
+        if owns_game_dir && !is_instance_running(root) {
+            let worlds = held_session_locks(&Path::new(root).join("minecraft"));
+            if !worlds.is_empty() {
+                problems.push(problem(
+                    "session_lock_orphaned",
+                    "warning",
+                    format!(
+                        "Otro proceso retiene el session.lock de: {}. Cierra ese juego antes de abrir estos mundos.",
+                        worlds.join(", ")
+                    ),
+                ));
+            }
+        }
+    }
+    for entry in &mut problems {
+        entry.instance_root = Some(root.clone());
+        entry.instance_name = Some(instance.name.clone());
+    }
+    problems
+}
+
+fn runtime_problems(launcher_root: &Path) -> Vec<LauncherProblem> {
+    let mut problems = Vec::new();
+    for runtime in [JavaRuntime::Java8, JavaRuntime::Java17, JavaRuntime::Java21] {
+        let Ok(builds) = list_java_builds(launcher_root, runtime) else {
+            continue;
+        };
+        for build in builds {
+            if cached_runtime_health(Path::new(&build.java_path)) {
+                continue;
+            }
+            problems.push(with_fix(
+                problem(
+                    "runtime_corrupt",
+                    "error",
+                    format!(
+                        "El runtime de Java {} (build '{}') no arranca con `java -version`.",
+                        runtime.major(),
+                        build.name
+                    ),
+                ),
+                "reinstall_embedded_runtime",
+                json!({ "major": runtime.major() }),
+            ));
+        }
+    }
+    problems
+}
+
+fn disk_space_problem(launcher_root: &Path, available: u64) -> Option<LauncherProblem> {
+    let severity = if available < DISK_ERROR_BYTES {
+        "error"
+    } else if available < DISK_WARNING_BYTES {
+        "warning"
+    } else {
+        return None;
+    };
+    Some(problem(
+        "low_disk_space",
+        severity,
+        format!(
+            "Quedan {} MB libres en {}.",
+            available / (1024 * 1024),
+            launcher_root.display()
+        ),
+    ))
+}
+
+fn severity_rank(severity: &str) -> u8 {
+    match severity {
+        "error" => 0,
+        "warning" => 1,
+        _ => 2,
+    }
+}
+
+/// Una entrada por código e instancia (p. ej. varias builds rotas del mismo major se
+/// arreglan con una sola reinstalación); errores primero.
+fn dedupe_problems(problems: Vec<LauncherProblem>) -> Vec<LauncherProblem> {
+    let mut seen = HashSet::new();
+    let mut unique = problems
+        .into_iter()
+        .filter(|entry| {
+            seen.insert((
+                entry.code.clone(),
+                entry.instance_root.clone(),
+                entry.fix_args.as_ref().map(Value::to_string),
+            ))
+        })
+        .collect::<Vec<_>>();
+    unique.sort_by_key(|entry| severity_rank(&entry.severity));
+    unique
+}
+
+fn compute_launcher_problems(app: &AppHandle) -> Vec<LauncherProblem> {
+    let clock = app_clock(app).clock;
+    let mut problems = Vec::new();
+    if let Ok(instances) = list_instances_readonly(app) {
+        for instance in &instances {
+            problems.extend(instance_problems(instance, clock.as_ref()));
+        }
+    }
+    if let Ok(launcher_root) = resolve_launcher_root(app) {
+        problems.extend(runtime_problems(&launcher_root));
+        if let Ok(available) = fs2::available_space(&launcher_root) {
+            problems.extend(disk_space_problem(&launcher_root, available));
+        }
+    }
+    for (instance_root, instance_name) in running_instances_without_folder() {
+        let mut missing = problem(
+            "running_instance_folder_missing",
+            "error",
+            format!(
+                "La carpeta de '{instance_name}' se borró o movió mientras el juego estaba abierto; el progreso de esta sesión puede perderse. Cierra el juego desde el juego y restaura la carpeta."
+            ),
+        );
+        missing.instance_root = Some(instance_root);
+        missing.instance_name = Some(instance_name);
+        problems.push(missing);
+    }
+    if let Ok(entries) = redirect_cache_inconsistencies(app) {
+        for (entry, reason) in entries {
+            problems.push(with_fix(
+                problem(
+                    "redirect_cache_inconsistent",
+                    "warning",
+                    format!(
+                        "La caché REDIRECT de {} no coincide con el disco ({reason}).",
+                        entry.version_id
+                    ),
+                ),
+                "force_cleanup_redirect_cache",
+                json!({}),
+            ));
+        }
+    }
+    dedupe_problems(problems)
+}
+
+/// Última lista calculada, sin recalcular ni comprobar su antigüedad (para consultas que no
+/// pueden tocar disco); `None` si aún no se ha calculado o se acaba de invalidar.
+pub fn cached_launcher_problems() -> Option<Vec<LauncherProblem>> {
+    problems_cache()
+        .as_ref()
+        .map(|cached| cached.problems.clone())
+}
+
+fn refresh_launcher_problems(app: &AppHandle) -> LauncherProblemsReport {
+    let problems = compute_launcher_problems(app);
+    let computed_at = app_clock(app).clock.now();
+    *problems_cache() = Some(CachedProblems {
+        computed_at,
+        problems: problems.clone(),
+    });
+    mark_launcher_snapshot_dirty();
+    LauncherProblemsReport {
+        computed_at: computed_at.to_rfc3339(),
+        problems,
+    }
+}
+
+/// Descarta la lista cacheada y la recalcula en segundo plano; el resultado se emite como
+/// `launcher_problems_updated` para refrescar el contador de la pantalla principal.
+pub fn invalidate_launcher_problems(app: &AppHandle) {
+    *problems_cache() = None;
+    if RECOMPUTING.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    let app = app.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let report = refresh_launcher_problems(&app);
+        RECOMPUTING.store(false, Ordering::SeqCst);
+        let _ = app.emit("launcher_problems_updated", report);
+    });
+}
+
+#[tauri::command]
+pub async fn get_launcher_problems(app: AppHandle) -> Result<LauncherProblemsReport, String> {
+    let now = app_clock(&app).clock.now();
+    if let Some(cached) = problems_cache()
+        .as_ref()
+        .filter(|cached| now - cached.computed_at < Duration::seconds(PROBLEMS_TTL_SECONDS))
+    {
+        return Ok(LauncherProblemsReport {
+            computed_at: cached.computed_at.to_rfc3339(),
+            problems: cached.problems.clone(),
+        });
+    }
+    tauri::async_runtime::spawn_blocking(move || refresh_launcher_problems(&app))
+        .await
+        .map_err(|err| format!("Falló la tarea de diagnóstico: {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn duplicates_collapse_and_errors_come_first() {
+        let runtime = |build: &str| {
+            with_fix(
+                problem("runtime_corrupt", "error", format!("build {build}")),
+                "reinstall_embedded_runtime",
+                json!({ "major": 17 }),
+            )
+        };
+        let mut crash = problem("recent_crash", "warning", "crash".to_string());
+        crash.instance_root = Some("/instances/a".to_string());
+        let mut other_crash = crash.clone();
+        other_crash.instance_root = Some("/instances/b".to_string());
+
+        let problems = dedupe_problems(vec![
+            crash.clone(),
+            runtime("default"),
+            runtime("jdk-17.0.9+9"),
+            other_crash,
+            crash,
+        ]);
+        let codes = problems
+            .iter()
+            .map(|entry| (entry.code.as_str(), entry.instance_root.as_deref()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            codes,
+            vec![
+                ("runtime_corrupt", None),
+                ("recent_crash", Some("/instances/a")),
+                ("recent_crash", Some("/instances/b")),
+            ]
+        );
+    }
+
+    #[test]
+    fn disk_space_thresholds_and_health_fixes() {
+        let root = Path::new("/launcher");
+        assert_eq!(disk_space_problem(root, DISK_WARNING_BYTES), None);
+        assert_eq!(
+            disk_space_problem(root, DISK_WARNING_BYTES - 1).map(|entry| entry.severity),
+            Some("warning".to_string())
+        );
+        assert_eq!(
+            disk_space_problem(root, 512 * 1024 * 1024).map(|entry| entry.severity),
+            Some("error".to_string())
+        );
+        assert_eq!(
+            fix_for_health_code("version_missing"),
+            Some("repair_instance")
+        );
+        assert_eq!(fix_for_health_code("redirect_source_missing"), None);
+    }
+}
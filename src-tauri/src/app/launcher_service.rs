@@ -1,4 +1,5 @@
 use std::{
+    collections::HashSet,
     fs,
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
@@ -6,16 +7,36 @@ use std::{
 use tauri::{AppHandle, Emitter};
 
 use crate::{
-    app::settings_service::resolve_instances_root,
+    app::{
+        creation_downloads::{run_joined_tracks, VERSION_FILES_TASK_KIND},
+        instance_service::compute_instance_health,
+        instance_tags::{matches_all_tags, sanitize_tags},
+        instance_templates::{find_instance_template, install_template_mods},
+        op_journal::{
+            needs_recovery, read_op_journal, JournalEntry, OperationJournal, NEEDS_RECOVERY_STATE,
+            OP_CREATE_INSTANCE, OP_JOURNAL_FILE,
+        },
+        settings_profiles::{apply_profile_files, find_settings_profile},
+        settings_service::resolve_instances_root,
+        trusted_root::resolve_trusted_instance_root,
+    },
     domain::{
         auth::{
+            endpoints::{minecraft_entitlements_url, minecraft_profile_url},
             microsoft::refresh_microsoft_access_token,
             xbox::{
                 authenticate_with_xbox_live, authorize_xsts, has_minecraft_license,
                 login_minecraft_with_xbox,
             },
         },
-        java::{java_detector::find_compatible_java, java_requirement::determine_required_java},
+        instance::validator::{
+            max_ram_for_system, validate_create_payload, CreationLimits, KnownVersions,
+            ValidationFailedError,
+        },
+        java::{
+            java_args::normalize_java_args, java_detector::find_compatible_java,
+            java_requirement::determine_required_java,
+        },
         models::{
             instance::{
                 CreateInstancePayload, CreateInstanceResult, InstanceMetadata, InstanceSummary,
@@ -24,14 +45,27 @@ use crate::{
             java::JavaRuntime,
         },
     },
-    infrastructure::filesystem::paths::resolve_launcher_root,
+    infrastructure::{
+        filesystem::{
+            capabilities::{capability_warnings, probe_filesystem_capabilities},
+            config::load_launcher_config,
+            paths::{resolve_launcher_root, safe_path_component, NameError},
+        },
+        http::{rate_limit, tls},
+    },
+    platform::memory::total_memory_mb,
     services::{
         instance_builder::{
-            build_instance_structure, persist_instance_metadata, InstanceBuildProgress,
+            cached_manifest_version_ids, download_vanilla_files, install_instance_loader,
+            persist_instance_metadata, InstanceBuildProgress,
         },
         java_installer::ensure_embedded_java,
     },
-    shared::result::AppResult,
+    shared::{
+        clock::app_clock,
+        result::AppResult,
+        tasks::{run_with_task, task_registry, TaskHandle},
+    },
 };
 
 #[derive(Clone, serde::Serialize)]
@@ -46,6 +80,10 @@ struct InstanceCreationProgressEvent {
     completed: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     total: Option<u64>,
+    /// Pista que emite el progreso mientras Java y los archivos de versión se descargan a
+    /// la vez: `java` o `minecraft`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    track: Option<&'static str>,
 }
 
 fn push_creation_log(
@@ -66,6 +104,7 @@ fn push_creation_log(
             message,
             completed: None,
             total: None,
+            track: None,
         },
     );
 }
@@ -87,6 +126,7 @@ fn emit_creation_progress(
             message: message.into(),
             completed: Some(completed),
             total: Some(total),
+            track: None,
         },
     );
 }
@@ -100,8 +140,10 @@ pub struct RemoteUpdateManifest {
 }
 
 #[tauri::command]
-pub async fn fetch_remote_update_manifest(manifest_url: String) -> Result<RemoteUpdateManifest, String> {
-    let client = reqwest::Client::builder()
+pub async fn fetch_remote_update_manifest(
+    manifest_url: String,
+) -> Result<RemoteUpdateManifest, String> {
+    let client = tls::with_custom_roots_async(reqwest::Client::builder())
         .timeout(Duration::from_secs(20))
         .build()
         .map_err(|err| format!("No se pudo construir cliente HTTP: {err}"))?;
@@ -165,54 +207,142 @@ pub async fn fetch_remote_update_manifest(manifest_url: String) -> Result<Remote
 pub async fn create_instance(
     app: AppHandle,
     payload: CreateInstancePayload,
-) -> Result<CreateInstanceResult, String> {
-    tauri::async_runtime::spawn_blocking(move || create_instance_impl(app, payload))
-        .await
-        .map_err(|err| format!("Falló la tarea de creación de instancia: {err}"))?
+) -> Result<CreateInstanceResult, CreateInstanceError> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let limits = creation_limits(&app);
+        let payload = CreateInstancePayload {
+            ram_mb: template_ram_mb(&app, &payload, limits.max_ram_mb)?,
+            ..payload
+        };
+        let validated = validate_create_payload(&payload, &limits)
+            .map_err(CreateInstanceError::ValidationFailed)?;
+        let payload = CreateInstancePayload {
+            loader: validated.loader,
+            ..payload
+        };
+        let mut result = create_instance_impl(app, payload)?;
+        result.warnings.extend(validated.warnings);
+        Ok(result)
+    })
+    .await
+    .map_err(|err| {
+        CreateInstanceError::Other(format!("Falló la tarea de creación de instancia: {err}"))
+    })?
 }
 
-#[tauri::command]
-pub fn list_instances(app: AppHandle) -> Result<Vec<InstanceSummary>, String> {
-    list_instances_impl(app)
+/// RAM del payload o, si no indica ninguna, la recomendada por la plantilla (sin pasar del
+/// máximo del sistema).
+fn template_ram_mb(
+    app: &AppHandle,
+    payload: &CreateInstancePayload,
+    max_ram_mb: u32,
+) -> AppResult<u32> {
+    if payload.ram_mb != 0 {
+        return Ok(payload.ram_mb);
+    }
+    let recommended = match payload.template.as_deref().map(str::trim) {
+        Some(name) if !name.is_empty() => find_instance_template(app, name)?.recommended_ram_mb,
+        _ => None,
+    };
+    Ok(recommended.map_or(0, |ram_mb| ram_mb.min(max_ram_mb)))
 }
 
-#[tauri::command]
-pub fn delete_instance(app: AppHandle, instance_root: String) -> Result<(), String> {
-    let instances_root = resolve_instances_root(&app)?;
-    let target_path = std::path::PathBuf::from(&instance_root);
+/// Error de creación: los fallos de validación van estructurados por campo y el resto
+/// sigue siendo el texto de siempre.
+#[derive(Debug, Clone, serde::Serialize, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum CreateInstanceError {
+    ValidationFailed(ValidationFailedError),
+    Other(String),
+}
 
-    if !target_path.exists() {
-        return Err(format!(
-            "La instancia no existe en disco: {}",
-            target_path.display()
-        ));
+impl From<String> for CreateInstanceError {
+    fn from(err: String) -> Self {
+        CreateInstanceError::Other(err)
     }
+}
 
-    if !target_path.is_dir() {
-        return Err(format!(
-            "La ruta de instancia no es un directorio: {}",
-            target_path.display()
-        ));
+impl std::fmt::Display for CreateInstanceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CreateInstanceError::ValidationFailed(failed) => write!(f, "{}", failed.message),
+            CreateInstanceError::Other(err) => write!(f, "{err}"),
+        }
     }
+}
 
-    let canonical_instances_root = fs::canonicalize(&instances_root).map_err(|err| {
-        format!(
-            "No se pudo resolver la ruta de instancias {}: {}",
-            instances_root.display(),
-            err
-        )
-    })?;
-    let canonical_target = fs::canonicalize(&target_path).map_err(|err| {
-        format!(
-            "No se pudo resolver la ruta de la instancia {}: {}",
-            target_path.display(),
-            err
-        )
-    })?;
+/// Límites del sistema para validar: RAM física y versiones del manifest cacheado.
+fn creation_limits(app: &AppHandle) -> CreationLimits {
+    let known_versions = resolve_launcher_root(app)
+        .ok()
+        .and_then(|root| cached_manifest_version_ids(&root))
+        .map(|(ids, fresh)| KnownVersions { ids, fresh });
+    CreationLimits {
+        max_ram_mb: max_ram_for_system(total_memory_mb()),
+        known_versions,
+    }
+}
+
+/// Devuelve el nombre de carpeta que se usaría para `name` o el motivo estructurado
+/// (`code` + `message`) por el que se rechaza.
+#[tauri::command]
+pub fn validate_instance_name(name: String) -> Result<String, NameError> {
+    safe_path_component(&name)
+}
+
+/// Con `include_health` incluye el `InstanceHealth` de cada instancia; `filter_tags` deja
+/// solo las que tienen todas las etiquetas.
+#[tauri::command]
+pub fn list_instances(
+    app: AppHandle,
+    include_health: Option<bool>,
+    filter_tags: Option<Vec<String>>,
+) -> Result<Vec<InstanceSummary>, String> {
+    let mut instances = list_instances_impl(&app, true)?;
+    if has_duplicate_ids(&instances)
+        && !crate::app::instance_uuid::repair_duplicate_instance_uuids(&app).is_empty()
+    {
+        instances = list_instances_impl(&app, true)?;
+    }
+    let filter_tags = sanitize_tags(filter_tags.unwrap_or_default());
+    instances.retain(|instance| matches_all_tags(&instance.tags, &filter_tags));
+    if include_health.unwrap_or(false) {
+        let clock = app_clock(&app).clock;
+        for instance in &mut instances {
+            instance.health = Some(compute_instance_health(
+                &instance.instance_root,
+                clock.as_ref(),
+            ));
+        }
+    }
+    Ok(instances)
+}
+
+/// Carpetas copiadas a mano comparten `internal_uuid`; solo entonces se hace la pasada de
+/// unicidad completa.
+fn has_duplicate_ids(instances: &[InstanceSummary]) -> bool {
+    let mut seen = HashSet::new();
+    instances
+        .iter()
+        .filter(|instance| {
+            !instance.id.starts_with("recovery:") && !instance.id.starts_with("legacy:")
+        })
+        .any(|instance| !seen.insert(instance.id.to_ascii_lowercase()))
+}
+
+/// Igual que `list_instances` pero sin borrar carpetas incompletas; pensado para consultas de solo lectura.
+pub fn list_instances_readonly(app: &AppHandle) -> AppResult<Vec<InstanceSummary>> {
+    list_instances_impl(app, false)
+}
+
+#[tauri::command]
+pub fn delete_instance(app: AppHandle, instance_root: String) -> Result<(), String> {
+    let validated = resolve_trusted_instance_root(&app, &instance_root)?;
+    let canonical_target = validated.canonical().to_path_buf();
 
-    if !canonical_target.starts_with(&canonical_instances_root) {
+    if !canonical_target.is_dir() {
         return Err(format!(
-            "Ruta inválida para eliminar instancia fuera del directorio permitido: {}",
+            "La ruta de instancia no es un directorio: {}",
             canonical_target.display()
         ));
     }
@@ -229,6 +359,7 @@ pub fn delete_instance(app: AppHandle, instance_root: String) -> Result<(), Stri
         }
     }
 
+    validated.revalidate()?;
     fs::remove_dir_all(&canonical_target).map_err(|err| {
         format!(
             "No se pudo eliminar la instancia {}: {}",
@@ -244,12 +375,16 @@ pub fn delete_instance(app: AppHandle, instance_root: String) -> Result<(), Stri
             "instancePath": canonical_target.display().to_string(),
         }),
     );
+    crate::app::launcher_problems::invalidate_launcher_problems(&app);
 
     Ok(())
 }
 
-fn list_instances_impl(app: AppHandle) -> AppResult<Vec<InstanceSummary>> {
-    let instances_root = resolve_instances_root(&app)?;
+fn list_instances_impl(
+    app: &AppHandle,
+    remove_incomplete: bool,
+) -> AppResult<Vec<InstanceSummary>> {
+    let instances_root = resolve_instances_root(app)?;
 
     if !instances_root.exists() {
         return Ok(Vec::new());
@@ -278,7 +413,36 @@ fn list_instances_impl(app: AppHandle) -> AppResult<Vec<InstanceSummary>> {
 
         let metadata_path = path.join(".instance.json");
         if !metadata_path.exists() {
-            let _ = fs::remove_dir_all(&path);
+            // Creación o importación cortada a mitad: se lista para poder recuperarla.
+            if needs_recovery(&path) {
+                if let Some(journal) = read_op_journal(&path) {
+                    let name = if journal.label.trim().is_empty() {
+                        entry.file_name().to_string_lossy().to_string()
+                    } else {
+                        journal.label
+                    };
+                    instances.push(InstanceSummary {
+                        id: format!("recovery:{}", path.display()),
+                        name,
+                        group: "Sin grupo".to_string(),
+                        instance_root: path.display().to_string(),
+                        tags: Vec::new(),
+                        health: None,
+                        state: Some(NEEDS_RECOVERY_STATE.to_string()),
+                    });
+                    continue;
+                }
+            }
+            if path.join(OP_JOURNAL_FILE).exists() {
+                // Creación o importación todavía en curso.
+                continue;
+            }
+            // Carpetas con datos de juego son huérfanas adoptables, no restos de una creación fallida.
+            if remove_incomplete
+                && crate::app::instance_service::detect_runtime_game_dir(&path).is_none()
+            {
+                let _ = fs::remove_dir_all(&path);
+            }
             continue;
         }
 
@@ -315,11 +479,23 @@ fn list_instances_impl(app: AppHandle) -> AppResult<Vec<InstanceSummary>> {
             .map(ToOwned::to_owned)
             .unwrap_or_else(|| format!("legacy:{}", path.display()));
 
+        let tags = sanitize_tags(
+            metadata_json
+                .get("tags")
+                .and_then(serde_json::Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(serde_json::Value::as_str),
+        );
+
         instances.push(InstanceSummary {
             id,
             name,
             group,
             instance_root: path.display().to_string(),
+            tags,
+            health: None,
+            state: needs_recovery(&path).then(|| NEEDS_RECOVERY_STATE.to_string()),
         });
     }
 
@@ -342,6 +518,25 @@ fn create_instance_impl(
         "Iniciando validación de payload...",
     );
     validate_payload(&payload)?;
+    let template = match payload.template.as_deref().map(str::trim) {
+        Some(name) if !name.is_empty() => {
+            let template = find_instance_template(&app, name)?;
+            if !template.loader.is_empty()
+                && !template.loader.eq_ignore_ascii_case(payload.loader.trim())
+            {
+                return Err(format!(
+                    "La plantilla '{}' requiere el loader {} (seleccionado: {}).",
+                    template.name, template.loader, payload.loader
+                ));
+            }
+            Some(template)
+        }
+        _ => None,
+    };
+    let settings_profile = match payload.apply_settings_profile.as_deref().map(str::trim) {
+        Some(name) if !name.is_empty() => Some(find_settings_profile(&app, name)?),
+        _ => None,
+    };
     push_creation_log(&app, &request_id, &mut logs, "Payload válido.");
 
     let mut auth_logs = Vec::new();
@@ -387,6 +582,7 @@ fn create_instance_impl(
                 message: last,
                 completed: None,
                 total: None,
+                track: None,
             },
         );
     }
@@ -428,28 +624,6 @@ fn create_instance_impl(
         );
     }
 
-    push_creation_log(
-        &app,
-        &request_id,
-        &mut logs,
-        "Preparando runtime Java embebido...",
-    );
-    let java_exec = ensure_embedded_java(&launcher_root, required_java, &mut logs)?;
-    if let Some(last) = logs.last().cloned() {
-        let _ = app.emit(
-            "instance_creation_progress",
-            InstanceCreationProgressEvent {
-                request_id: request_id.clone(),
-                step: None,
-                step_index: None,
-                total_steps: None,
-                message: last,
-                completed: None,
-                total: None,
-            },
-        );
-    }
-
     log_download_steps(&payload, &mut logs, required_java);
     for line in logs
         .iter()
@@ -470,12 +644,12 @@ fn create_instance_impl(
                 message: line,
                 completed: None,
                 total: None,
+                track: None,
             },
         );
     }
 
-    let sanitized_name =
-        crate::infrastructure::filesystem::paths::sanitize_path_segment(&payload.name);
+    let sanitized_name = safe_path_component(&payload.name).map_err(|err| err.to_string())?;
     let instance_root = instances_root.join(&sanitized_name);
     let minecraft_root = instance_root.join("minecraft");
 
@@ -499,6 +673,21 @@ fn create_instance_impl(
         format!("Creada carpeta base: {}", instance_root.display()),
     );
 
+    let filesystem = probe_filesystem_capabilities(&instance_root);
+    let mut filesystem_warnings = capability_warnings(&filesystem);
+    push_creation_log(
+        &app,
+        &request_id,
+        &mut logs,
+        format!(
+            "Sistema de archivos de la instancia: {} ({}).",
+            filesystem.filesystem, filesystem.drive_kind
+        ),
+    );
+    for warning in &filesystem_warnings {
+        push_creation_log(&app, &request_id, &mut logs, warning.clone());
+    }
+
     struct InstanceCleanupGuard {
         path: std::path::PathBuf,
         keep: bool,
@@ -517,46 +706,134 @@ fn create_instance_impl(
         path: instance_root.clone(),
         keep: false,
     };
+    let creation_journal = OperationJournal::begin(
+        &instance_root,
+        OP_CREATE_INSTANCE,
+        &payload.name,
+        vec![JournalEntry::path(&instance_root)],
+    )?;
 
+    let concurrent = load_launcher_config(&app)
+        .ok()
+        .and_then(|config| config.concurrent_creation_downloads)
+        .unwrap_or(true);
     push_creation_log(
         &app,
         &request_id,
         &mut logs,
-        "Construyendo estructura interna de la instancia...",
+        if concurrent {
+            "Descargando runtime Java y archivos de la versión en paralelo..."
+        } else {
+            "Descargando runtime Java y después los archivos de la versión..."
+        },
     );
-    let mut build_logs = Vec::new();
     let mut progress_logs = Vec::new();
-    let effective_version_id = build_instance_structure(
-        &instance_root,
-        &minecraft_root,
-        &payload.minecraft_version,
-        &payload.loader,
-        &payload.loader_version,
-        &java_exec,
-        &mut build_logs,
-        &mut |progress: InstanceBuildProgress| {
-            let line = format!(
-                "{} (paso {}/{}) [{} / {}]",
-                progress.message,
-                progress.step_index,
-                progress.total_steps,
-                progress.completed,
-                progress.total
-            );
-            progress_logs.push(line);
+    let emit_build_progress = |progress: InstanceBuildProgress,
+                               track: Option<&'static str>,
+                               progress_logs: &mut Vec<String>| {
+        let line = format!(
+            "{} (paso {}/{}) [{} / {}]",
+            progress.message,
+            progress.step_index,
+            progress.total_steps,
+            progress.completed,
+            progress.total
+        );
+        progress_logs.push(line);
+        let _ = app.emit(
+            "instance_creation_progress",
+            InstanceCreationProgressEvent {
+                request_id: request_id.clone(),
+                step: Some(progress.step),
+                step_index: Some(progress.step_index),
+                total_steps: Some(progress.total_steps),
+                message: progress.message,
+                completed: Some(progress.completed),
+                total: Some(progress.total),
+                track,
+            },
+        );
+    };
+
+    // Cada pista es una tarea propia (los bytes del JDK salen en `task_progress` de
+    // `java_runtime_download`); cancelar cualquiera de las dos, o que una falle, detiene
+    // la otra. El loader no empieza hasta tener el ejecutable y los archivos vanilla.
+    let java_task = TaskHandle::begin(
+        &app,
+        "java_runtime_download",
+        format!("Java {} para {}", required_java.major(), payload.name),
+    );
+    let files_task = TaskHandle::begin(
+        &app,
+        VERSION_FILES_TASK_KIND,
+        format!(
+            "Minecraft {} para {}",
+            payload.minecraft_version, payload.name
+        ),
+    );
+    let (java_probe, files_probe) = (java_task.probe(), files_task.probe());
+    let registry = task_registry(&app);
+    let mut java_logs = Vec::new();
+    let joined = run_joined_tracks(
+        concurrent,
+        || {
             let _ = app.emit(
                 "instance_creation_progress",
                 InstanceCreationProgressEvent {
                     request_id: request_id.clone(),
-                    step: Some(progress.step),
-                    step_index: Some(progress.step_index),
-                    total_steps: Some(progress.total_steps),
-                    message: progress.message,
-                    completed: Some(progress.completed),
-                    total: Some(progress.total),
+                    step: None,
+                    step_index: None,
+                    total_steps: None,
+                    message: "Preparando runtime Java embebido...".to_string(),
+                    completed: None,
+                    total: None,
+                    track: Some("java"),
                 },
             );
+            run_with_task(java_probe.clone(), || {
+                ensure_embedded_java(&launcher_root, required_java, &mut java_logs)
+            })
         },
+        || {
+            run_with_task(files_probe.clone(), || {
+                download_vanilla_files(
+                    &instance_root,
+                    &minecraft_root,
+                    &payload.minecraft_version,
+                    &mut |progress| {
+                        emit_build_progress(progress, Some("minecraft"), &mut progress_logs)
+                    },
+                )
+            })
+        },
+        || {
+            registry.cancel(java_probe.task_id());
+        },
+        || {
+            registry.cancel(files_probe.task_id());
+        },
+    );
+    java_task.finish(&joined.java.result);
+    files_task.finish(&joined.files.result);
+    logs.extend(java_logs);
+    push_creation_log(&app, &request_id, &mut logs, joined.timeline_line());
+    let (java_exec, normalized_version) = joined.into_results()?;
+
+    push_creation_log(
+        &app,
+        &request_id,
+        &mut logs,
+        "Construyendo estructura interna de la instancia...",
+    );
+    let mut build_logs = Vec::new();
+    let effective_version_id = install_instance_loader(
+        &minecraft_root,
+        &normalized_version,
+        &payload.loader,
+        &payload.loader_version,
+        &java_exec,
+        &mut build_logs,
+        &mut |progress| emit_build_progress(progress, None, &mut progress_logs),
     )?;
     logs.extend(build_logs);
     push_creation_log(
@@ -579,11 +856,23 @@ fn create_instance_impl(
                 message: last,
                 completed: None,
                 total: None,
+                track: None,
             },
         );
     }
 
-    let internal_uuid = uuid::Uuid::new_v4().to_string();
+    let requested_java_args = match template.as_ref() {
+        Some(template) if payload.java_args.is_empty() => template.java_args.clone(),
+        _ => payload.java_args.clone(),
+    };
+    let normalized_java_args = normalize_java_args(&requested_java_args)?;
+    for change in &normalized_java_args.changes {
+        push_creation_log(&app, &request_id, &mut logs, format!("java_args: {change}"));
+    }
+    let java_args = normalized_java_args.args;
+
+    let clock = app_clock(&app);
+    let internal_uuid = clock.ids.new_id();
     let metadata = InstanceMetadata {
         name: payload.name,
         group: payload.group,
@@ -592,15 +881,35 @@ fn create_instance_impl(
         loader: payload.loader,
         loader_version: payload.loader_version,
         ram_mb: payload.ram_mb,
-        java_args: payload.java_args,
+        java_args,
         java_path: java_exec.display().to_string(),
         java_runtime: runtime_name(required_java).to_string(),
         java_version: format!("{}.0.x", required_java.major()),
         required_java_major: u32::from(required_java.major()),
-        created_at: current_timestamp_iso8601(),
+        created_at: clock.clock.now_rfc3339(),
         state: "READY".to_string(),
         last_used: None,
         internal_uuid: internal_uuid.clone(),
+        filesystem: Some(filesystem),
+        mods_dir_override: None,
+        adopted: false,
+        tags: Vec::new(),
+        locked_fields: Vec::new(),
+        locked_by: String::new(),
+        optional_game_flags: Default::default(),
+        java_build_pin: None,
+        source_settings_write: false,
+        retention: Default::default(),
+        library_overrides: Vec::new(),
+        pack_origin: None,
+        auto_jvm_tuning: true,
+        preferred_gpu: None,
+        backup: Default::default(),
+        strict_mode: false,
+        skip_duplicate_mod_check: false,
+        window_tweaks: Default::default(),
+        launch_hooks: Default::default(),
+        console_filters: Vec::new(),
     };
 
     push_creation_log(
@@ -610,6 +919,11 @@ fn create_instance_impl(
         "Guardando metadata final de la instancia...",
     );
     persist_instance_metadata(&instance_root, &metadata, &mut logs)?;
+    crate::app::launcher_snapshot::index_instance_metadata(
+        &instance_root.display().to_string(),
+        &metadata,
+    );
+    creation_journal.complete();
     push_creation_log(
         &app,
         &request_id,
@@ -618,6 +932,67 @@ fn create_instance_impl(
     );
     cleanup_guard.keep = true;
 
+    if let Some((profile, profile_dir)) = settings_profile.as_ref() {
+        match apply_profile_files(profile_dir, &profile.files, &minecraft_root, true) {
+            Ok(report) => push_creation_log(
+                &app,
+                &request_id,
+                &mut logs,
+                format!(
+                    "✔ Perfil de ajustes '{}' aplicado ({} archivos).",
+                    profile.name,
+                    report.applied.len()
+                ),
+            ),
+            Err(err) => {
+                let line = format!(
+                    "⚠ No se pudo aplicar el perfil de ajustes '{}': {err}",
+                    profile.name
+                );
+                push_creation_log(&app, &request_id, &mut logs, line.clone());
+                filesystem_warnings.push(line);
+            }
+        }
+    }
+
+    let mut template_failures = Vec::new();
+    if let Some(template) = template.as_ref() {
+        push_creation_log(
+            &app,
+            &request_id,
+            &mut logs,
+            format!("Aplicando plantilla '{}'...", template.name),
+        );
+        let outcomes = install_template_mods(
+            template,
+            &minecraft_root,
+            &metadata.loader,
+            &metadata.minecraft_version,
+            &mut |completed, total, message| {
+                emit_creation_progress(&app, &request_id, completed, total, message)
+            },
+        );
+        for outcome in outcomes {
+            match (outcome.file_name, outcome.error) {
+                (Some(file_name), None) => push_creation_log(
+                    &app,
+                    &request_id,
+                    &mut logs,
+                    format!("✔ Mod de plantilla instalado: {file_name}"),
+                ),
+                (_, error) => {
+                    let line = format!(
+                        "⚠ No se pudo instalar {}: {}",
+                        outcome.project,
+                        error.unwrap_or_default()
+                    );
+                    push_creation_log(&app, &request_id, &mut logs, line.clone());
+                    template_failures.push(line);
+                }
+            }
+        }
+    }
+
     Ok(CreateInstanceResult {
         id: internal_uuid,
         name: metadata.name,
@@ -626,20 +1001,17 @@ fn create_instance_impl(
         instance_root: instance_root.display().to_string(),
         minecraft_path: minecraft_root.display().to_string(),
         logs,
+        warnings: filesystem_warnings,
+        template_failures,
     })
 }
 
-fn current_timestamp_iso8601() -> String {
-    chrono::Utc::now().to_rfc3339()
-}
-
 fn validate_instance_constraints(
     launcher_root: &std::path::Path,
     instances_root: &std::path::Path,
     payload: &CreateInstancePayload,
 ) -> AppResult<()> {
-    let sanitized_name =
-        crate::infrastructure::filesystem::paths::sanitize_path_segment(&payload.name);
+    let sanitized_name = safe_path_component(&payload.name).map_err(|err| err.to_string())?;
     let instance_root = instances_root.join(&sanitized_name);
 
     if instance_root.exists() {
@@ -668,15 +1040,8 @@ fn validate_instance_constraints(
     Ok(())
 }
 
+/// Lo que no cubre `validate_create_payload`: la sesión usada para crear la instancia.
 fn validate_payload(payload: &CreateInstancePayload) -> AppResult<()> {
-    if payload.name.trim().is_empty() {
-        return Err("El nombre de la instancia es obligatorio.".to_string());
-    }
-
-    if payload.minecraft_version.trim().is_empty() {
-        return Err("La versión de Minecraft es obligatoria.".to_string());
-    }
-
     if payload
         .auth_session
         .minecraft_access_token
@@ -727,7 +1092,7 @@ fn validate_official_minecraft_auth(
             .map_err(|err| format!("No se pudo crear runtime para refresh de token: {err}"))?;
 
         let refreshed = runtime.block_on(async {
-            let client = reqwest::Client::new();
+            let client = tls::default_async_client();
             let ms = refresh_microsoft_access_token(&client, &refresh_token).await?;
             let xbox = authenticate_with_xbox_live(&client, &ms.access_token).await?;
             let xsts = authorize_xsts(&client, &xbox.token).await?;
@@ -743,17 +1108,19 @@ fn validate_official_minecraft_auth(
         logs.push("✔ access_token de Minecraft renovado correctamente.".to_string());
     }
 
-    let client = reqwest::blocking::Client::builder()
+    let client = tls::with_custom_roots(reqwest::blocking::Client::builder())
         .timeout(std::time::Duration::from_secs(20))
         .build()
         .map_err(|err| format!("No se pudo crear cliente HTTP para auth oficial: {err}"))?;
 
-    let mut entitlements_response = client
-        .get("https://api.minecraftservices.com/entitlements/mcstore")
-        .header("Authorization", format!("Bearer {active_minecraft_token}"))
-        .header("Accept", "application/json")
-        .send()
-        .map_err(|err| format!("No se pudo consultar entitlements de Minecraft: {err}"))?;
+    let entitlements_url = minecraft_entitlements_url();
+    let mut entitlements_response = rate_limit::send_blocking(&entitlements_url, || {
+        client
+            .get(&entitlements_url)
+            .header("Authorization", format!("Bearer {active_minecraft_token}"))
+            .header("Accept", "application/json")
+    })
+    .map_err(|err| err.describe("No se pudo consultar entitlements de Minecraft"))?;
 
     if entitlements_response.status().as_u16() == 401 {
         logs.push("⚠ /entitlements devolvió 401; reintentando con refresh oficial...".to_string());
@@ -769,7 +1136,7 @@ fn validate_official_minecraft_auth(
             .map_err(|err| format!("No se pudo crear runtime para refresh de token: {err}"))?;
 
         let refreshed = runtime.block_on(async {
-            let client = reqwest::Client::new();
+            let client = tls::default_async_client();
             let ms = refresh_microsoft_access_token(&client, &refresh_token).await?;
             let xbox = authenticate_with_xbox_live(&client, &ms.access_token).await?;
             let xsts = authorize_xsts(&client, &xbox.token).await?;
@@ -784,14 +1151,15 @@ fn validate_official_minecraft_auth(
         active_minecraft_expires_at = refreshed.1;
         logs.push("✔ refresh completado; reintentando validación de licencia.".to_string());
 
-        entitlements_response = client
-            .get("https://api.minecraftservices.com/entitlements/mcstore")
-            .header("Authorization", format!("Bearer {active_minecraft_token}"))
-            .header("Accept", "application/json")
-            .send()
-            .map_err(|err| {
-                format!("No se pudo consultar entitlements de Minecraft tras refresh: {err}")
-            })?;
+        entitlements_response = rate_limit::send_blocking(&entitlements_url, || {
+            client
+                .get(&entitlements_url)
+                .header("Authorization", format!("Bearer {active_minecraft_token}"))
+                .header("Accept", "application/json")
+        })
+        .map_err(|err| {
+            err.describe("No se pudo consultar entitlements de Minecraft tras refresh")
+        })?;
     }
 
     let entitlements_status = entitlements_response.status();
@@ -816,12 +1184,14 @@ fn validate_official_minecraft_auth(
         return Err("La cuenta no posee licencia oficial de Minecraft.".to_string());
     }
 
-    let profile_response = client
-        .get("https://api.minecraftservices.com/minecraft/profile")
-        .header("Authorization", format!("Bearer {active_minecraft_token}"))
-        .header("Accept", "application/json")
-        .send()
-        .map_err(|err| format!("No se pudo consultar perfil de Minecraft: {err}"))?;
+    let profile_url = minecraft_profile_url();
+    let profile_response = rate_limit::send_blocking(&profile_url, || {
+        client
+            .get(&profile_url)
+            .header("Authorization", format!("Bearer {active_minecraft_token}"))
+            .header("Accept", "application/json")
+    })
+    .map_err(|err| err.describe("No se pudo consultar perfil de Minecraft"))?;
 
     let profile_status = profile_response.status();
     if !profile_status.is_success() {
@@ -854,7 +1224,7 @@ fn validate_official_minecraft_auth(
     let runtime = tokio::runtime::Runtime::new()
         .map_err(|err| format!("No se pudo crear runtime para validar entitlements: {err}"))?;
     let has_license = runtime.block_on(async {
-        has_minecraft_license(&reqwest::Client::new(), &active_minecraft_token).await
+        has_minecraft_license(&tls::default_async_client(), &active_minecraft_token).await
     })?;
 
     if !has_license {
@@ -0,0 +1,383 @@
+// Estado compacto del launcher para la bandeja y la API local.
+
+use std::{
+    collections::HashMap,
+    path::Path,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex, MutexGuard, OnceLock,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+use serde_json::Value;
+use tauri::{AppHandle, Emitter, Listener};
+
+use crate::{
+    app::{
+        instance_service::{read_instance_metadata, running_instances_snapshot},
+        launcher_problems::cached_launcher_problems,
+        launcher_service::list_instances_readonly,
+    },
+    domain::models::instance::InstanceMetadata,
+    shared::{
+        clock::app_clock,
+        tasks::{task_registry, TaskProgress},
+    },
+};
+
+pub const SNAPSHOT_CHANGED_EVENT: &str = "launcher_snapshot_changed";
+const SNAPSHOT_EMIT_INTERVAL: Duration = Duration::from_secs(1);
+const RECENT_INSTANCES: usize = 3;
+/// Eventos cuyo origen cambia la foto y no puede avisar directamente (capa `shared`).
+const WATCHED_EVENTS: [&str; 4] = [
+    "task_started",
+    "task_progress",
+    "task_completed",
+    "task_cancelled",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct IndexedInstance {
+    name: String,
+    last_used: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotRunningInstance {
+    pub instance_root: String,
+    pub name: Option<String>,
+    pub pid: Option<u32>,
+    pub uptime_secs: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotTask {
+    pub task_id: String,
+    pub kind: String,
+    pub label: String,
+    /// 0-100; `None` si la tarea no conoce su total.
+    pub percent: Option<u8>,
+    pub stalled: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProblemCounts {
+    pub error: usize,
+    pub warning: usize,
+    pub info: usize,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RecentInstance {
+    pub instance_root: String,
+    pub name: String,
+    pub last_used: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LauncherSnapshot {
+    pub running_count: usize,
+    pub running: Vec<SnapshotRunningInstance>,
+    pub downloading: bool,
+    pub bytes_per_second: u64,
+    pub tasks: Vec<SnapshotTask>,
+    /// `None` mientras la lista de problemas no se ha calculado todavía.
+    pub problems: Option<ProblemCounts>,
+    pub recent_instances: Vec<RecentInstance>,
+}
+
+static INSTANCE_INDEX: OnceLock<Mutex<HashMap<String, IndexedInstance>>> = OnceLock::new();
+static SNAPSHOT_APP: OnceLock<AppHandle> = OnceLock::new();
+static EMIT_PENDING: AtomicBool = AtomicBool::new(false);
+static LAST_EMIT: OnceLock<Mutex<Option<Instant>>> = OnceLock::new();
+
+fn instance_index() -> MutexGuard<'static, HashMap<String, IndexedInstance>> {
+    INSTANCE_INDEX
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn last_emit() -> MutexGuard<'static, Option<Instant>> {
+    LAST_EMIT
+        .get_or_init(|| Mutex::new(None))
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Actualiza el índice tras escribir la metadata de una instancia.
+pub fn index_instance_metadata(instance_root: &str, metadata: &InstanceMetadata) {
+    let entry = IndexedInstance {
+        name: metadata.name.clone(),
+        last_used: metadata.last_used.clone(),
+    };
+    let changed = instance_index().insert(instance_root.to_string(), entry.clone()) != Some(entry);
+    if changed {
+        mark_launcher_snapshot_dirty();
+    }
+}
+
+pub fn forget_instance(instance_root: &str) {
+    if instance_index().remove(instance_root).is_some() {
+        mark_launcher_snapshot_dirty();
+    }
+}
+
+fn indexed_name(index: &HashMap<String, IndexedInstance>, instance_root: &str) -> Option<String> {
+    index
+        .get(instance_root)
+        .or_else(|| {
+            index
+                .iter()
+                .find(|(root, _)| Path::new(root) == Path::new(instance_root))
+                .map(|(_, entry)| entry)
+        })
+        .map(|entry| entry.name.clone())
+}
+
+fn recent_instances(index: &HashMap<String, IndexedInstance>, limit: usize) -> Vec<RecentInstance> {
+    let mut played = index
+        .iter()
+        .filter_map(|(root, entry)| {
+            Some(RecentInstance {
+                instance_root: root.clone(),
+                name: entry.name.clone(),
+                last_used: entry.last_used.clone()?,
+            })
+        })
+        .collect::<Vec<_>>();
+    // RFC 3339 en UTC: el orden de texto es el cronológico.
+    played.sort_by(|a, b| {
+        b.last_used
+            .cmp(&a.last_used)
+            .then_with(|| a.instance_root.cmp(&b.instance_root))
+    });
+    played.truncate(limit);
+    played
+}
+
+fn task_percent(progress: &TaskProgress) -> Option<u8> {
+    if progress.unit == "percent" {
+        return Some(progress.completed.min(100) as u8);
+    }
+    let total = progress.total.filter(|total| *total > 0)?;
+    Some((progress.completed.min(total) * 100 / total) as u8)
+}
+
+fn count_problems<'a>(severities: impl IntoIterator<Item = &'a str>) -> ProblemCounts {
+    let mut counts = ProblemCounts::default();
+    for severity in severities {
+        match severity {
+            "error" => counts.error += 1,
+            "warning" => counts.warning += 1,
+            _ => counts.info += 1,
+        }
+    }
+    counts
+}
+
+fn build_launcher_snapshot(app: &AppHandle) -> Result<LauncherSnapshot, String> {
+    let running = running_instances_snapshot(app_clock(app).clock.as_ref())?;
+    let downloads = task_registry(app).download_status();
+    let index = instance_index();
+    let running = running
+        .into_iter()
+        .map(|running| SnapshotRunningInstance {
+            name: indexed_name(&index, &running.instance_root),
+            instance_root: running.instance_root,
+            pid: running.pid,
+            uptime_secs: running.uptime_secs,
+        })
+        .collect::<Vec<_>>();
+    let recent_instances = recent_instances(&index, RECENT_INSTANCES);
+    drop(index);
+
+    let tasks = task_registry(app)
+        .list()
+        .into_iter()
+        .map(|task| {
+            let progress = downloads
+                .tasks
+                .iter()
+                .find(|download| download.task_id == task.task_id)
+                .and_then(|download| download.progress.as_ref());
+            SnapshotTask {
+                percent: progress.and_then(task_percent),
+                stalled: progress.is_some_and(|progress| progress.stalled),
+                task_id: task.task_id,
+                kind: task.kind,
+                label: task.label,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    Ok(LauncherSnapshot {
+        running_count: running.len(),
+        running,
+        downloading: !tasks.is_empty(),
+        bytes_per_second: downloads.bytes_per_second,
+        tasks,
+        problems: cached_launcher_problems().map(|problems| {
+            count_problems(problems.iter().map(|problem| problem.severity.as_str()))
+        }),
+        recent_instances,
+    })
+}
+
+#[tauri::command]
+pub fn get_launcher_snapshot(app: AppHandle) -> Result<LauncherSnapshot, String> {
+    build_launcher_snapshot(&app)
+}
+
+/// Espera hasta poder emitir otra vez sin superar un evento por intervalo.
+fn emit_delay(last: Option<Instant>, now: Instant) -> Duration {
+    last.map_or(Duration::ZERO, |last| {
+        SNAPSHOT_EMIT_INTERVAL.saturating_sub(now.saturating_duration_since(last))
+    })
+}
+
+/// Programa un `launcher_snapshot_changed`; los avisos que llegan mientras espera se
+/// agrupan en el mismo evento.
+pub fn mark_launcher_snapshot_dirty() {
+    let Some(app) = SNAPSHOT_APP.get() else {
+        return;
+    };
+    if EMIT_PENDING.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    let app = app.clone();
+    thread::spawn(move || {
+        thread::sleep(emit_delay(*last_emit(), Instant::now()));
+        {
+            // Se actualiza `last_emit` antes de liberar `EMIT_PENDING`: un aviso que llegue justo
+            // después ya calcula su espera desde esta emisión.
+            let mut last = last_emit();
+            *last = Some(Instant::now());
+            EMIT_PENDING.store(false, Ordering::SeqCst);
+        }
+        if let Ok(snapshot) = build_launcher_snapshot(&app) {
+            let _ = app.emit(SNAPSHOT_CHANGED_EVENT, snapshot);
+        }
+    });
+}
+
+fn on_instances_changed(payload: &str) {
+    let Ok(payload) = serde_json::from_str::<Value>(payload) else {
+        return;
+    };
+    let Some(instance_root) = payload.get("instancePath").and_then(Value::as_str) else {
+        return;
+    };
+    if payload.get("action").and_then(Value::as_str) == Some("deleted") {
+        forget_instance(instance_root);
+    } else if let Ok(metadata) = read_instance_metadata(instance_root.to_string()) {
+        index_instance_metadata(instance_root, &metadata);
+    }
+}
+
+/// Carga el índice de instancias en segundo plano y se suscribe a los eventos que cambian
+/// la foto.
+pub fn initialize_launcher_snapshot(app: &AppHandle) {
+    let _ = SNAPSHOT_APP.set(app.clone());
+    for event in WATCHED_EVENTS {
+        app.listen_any(event, |_| mark_launcher_snapshot_dirty());
+    }
+    app.listen_any("instances_changed", |event| {
+        let payload = event.payload().to_string();
+        thread::spawn(move || on_instances_changed(&payload));
+    });
+    let app = app.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        for instance in list_instances_readonly(&app).unwrap_or_default() {
+            if let Ok(metadata) = read_instance_metadata(instance.instance_root.clone()) {
+                index_instance_metadata(&instance.instance_root, &metadata);
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn indexed(name: &str, last_used: Option<&str>) -> IndexedInstance {
+        IndexedInstance {
+            name: name.to_string(),
+            last_used: last_used.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn recent_instances_are_the_three_last_played() {
+        let index = HashMap::from([
+            (
+                "/i/a".to_string(),
+                indexed("A", Some("2026-03-01T10:00:00+00:00")),
+            ),
+            (
+                "/i/b".to_string(),
+                indexed("B", Some("2026-03-04T10:00:00+00:00")),
+            ),
+            ("/i/c".to_string(), indexed("C", None)),
+            (
+                "/i/d".to_string(),
+                indexed("D", Some("2026-03-02T10:00:00+00:00")),
+            ),
+            (
+                "/i/e".to_string(),
+                indexed("E", Some("2026-03-03T10:00:00+00:00")),
+            ),
+        ]);
+        let names = recent_instances(&index, RECENT_INSTANCES)
+            .into_iter()
+            .map(|recent| recent.name)
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["B", "E", "D"]);
+        assert_eq!(indexed_name(&index, "/i/c"), Some("C".to_string()));
+        assert_eq!(indexed_name(&index, "/i/z"), None);
+    }
+
+    #[test]
+    fn percentages_problem_counts_and_debounce() {
+        let progress = |completed, total, unit: &str| TaskProgress {
+            completed,
+            total,
+            unit: unit.to_string(),
+            message: None,
+            bytes_per_second: None,
+            eta_seconds: None,
+            stalled: false,
+        };
+        assert_eq!(task_percent(&progress(50, Some(200), "bytes")), Some(25));
+        assert_eq!(task_percent(&progress(7, None, "items")), None);
+        assert_eq!(task_percent(&progress(130, None, "percent")), Some(100));
+
+        assert_eq!(
+            count_problems(["error", "warning", "warning", "info"]),
+            ProblemCounts {
+                error: 1,
+                warning: 2,
+                info: 1
+            }
+        );
+
+        let now = Instant::now();
+        assert_eq!(emit_delay(None, now), Duration::ZERO);
+        assert_eq!(
+            emit_delay(Some(now), now + Duration::from_millis(400)),
+            Duration::from_millis(600)
+        );
+        assert_eq!(
+            emit_delay(Some(now), now + Duration::from_secs(3)),
+            Duration::ZERO
+        );
+    }
+}
@@ -0,0 +1,441 @@
+// Aviso de actualizaciones del launcher.
+
+use std::{
+    cmp::Ordering,
+    fs,
+    io::{Read, Write},
+    path::{Path, PathBuf},
+};
+
+use chrono::Duration;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tauri::AppHandle;
+
+use crate::{
+    app::mod_duplicates::compare_mod_versions,
+    infrastructure::{
+        cache::cache_manager::{cache_file, load_cached},
+        downloader::client::build_http_client,
+        filesystem::{
+            config::{load_launcher_config, save_launcher_config},
+            paths::{resolve_launcher_root, safe_path_component},
+        },
+        http::rate_limit,
+    },
+    shared::{clock::app_clock, result::AppResult},
+};
+
+pub const DEFAULT_UPDATE_MANIFEST_URL: &str =
+    "https://manzanitaspice.github.io/Interface-2/updates/releases.json";
+const UPDATE_CACHE_DIR: &str = "cache/updates";
+const MANIFEST_MAX_AGE_HOURS: i64 = 6;
+const DOWNLOAD_CHUNK_BYTES: usize = 256 * 1024;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct ReleaseAsset {
+    pub url: String,
+    pub sha256: String,
+    pub size: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct ReleaseEntry {
+    pub version: String,
+    /// `stable` o `beta`.
+    pub channel: String,
+    pub notes_url: Option<String>,
+    pub pub_date: Option<String>,
+    /// Sin el campo la versión llega a todos.
+    pub rollout_percentage: Option<u8>,
+    /// Por plataforma con el formato del updater de Tauri (`windows-x86_64`,
+    /// `darwin-aarch64`, `linux-x86_64`...).
+    pub platforms: std::collections::BTreeMap<String, ReleaseAsset>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct ReleaseManifest {
+    pub releases: Vec<ReleaseEntry>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct LauncherUpdateCheck {
+    pub current_version: String,
+    pub channel: String,
+    pub update_available: bool,
+    pub version: Option<String>,
+    pub notes_url: Option<String>,
+    pub download_url: Option<String>,
+    pub sha256: Option<String>,
+    /// Hay una versión más nueva pero este equipo aún no entra en su despliegue.
+    pub held_back_by_rollout: bool,
+    pub checked_at: Option<String>,
+    /// El manifiesto en caché caducó y no se pudo refrescar.
+    pub stale: bool,
+    pub disabled: bool,
+}
+
+/// Clave de plataforma del updater de Tauri para este build.
+fn platform_key() -> String {
+    let os = match std::env::consts::OS {
+        "macos" => "darwin",
+        other => other,
+    };
+    format!("{os}-{}", std::env::consts::ARCH)
+}
+
+/// Tramo 0–99 estable por instalación y versión: cada versión reparte de nuevo los tramos
+/// para que no sean siempre los mismos equipos los primeros en recibirla.
+fn rollout_bucket(installation_id: &str, version: &str) -> u8 {
+    let digest = Sha256::digest(format!("{installation_id}:{version}").as_bytes());
+    (u32::from_be_bytes([digest[0], digest[1], digest[2], digest[3]]) % 100) as u8
+}
+
+fn channel_accepts(channel: &str, entry_channel: &str) -> bool {
+    let entry_channel = entry_channel.trim().to_ascii_lowercase();
+    match channel {
+        "beta" => entry_channel == "stable" || entry_channel == "beta",
+        _ => entry_channel == "stable",
+    }
+}
+
+/// Elige la versión más alta del canal más nueva que `current_version`, con instalador para
+/// `platform` y cuyo despliegue incluya a esta instalación.
+fn select_update(
+    manifest: &ReleaseManifest,
+    current_version: &str,
+    channel: &str,
+    platform: &str,
+    installation_id: &str,
+) -> LauncherUpdateCheck {
+    let mut candidates = manifest
+        .releases
+        .iter()
+        .filter(|entry| channel_accepts(channel, &entry.channel))
+        .filter(|entry| {
+            compare_mod_versions(&entry.version, current_version) == Some(Ordering::Greater)
+        })
+        .filter(|entry| {
+            entry
+                .platforms
+                .get(platform)
+                .is_some_and(|asset| !asset.url.trim().is_empty())
+        })
+        .collect::<Vec<_>>();
+    candidates
+        .sort_by(|a, b| compare_mod_versions(&b.version, &a.version).unwrap_or(Ordering::Equal));
+
+    let mut check = LauncherUpdateCheck {
+        current_version: current_version.to_string(),
+        channel: channel.to_string(),
+        ..LauncherUpdateCheck::default()
+    };
+    for entry in candidates {
+        let percentage = entry.rollout_percentage.unwrap_or(100).min(100);
+        if rollout_bucket(installation_id, &entry.version) >= percentage {
+            check.held_back_by_rollout = true;
+            continue;
+        }
+        let asset = &entry.platforms[platform];
+        check.update_available = true;
+        check.version = Some(entry.version.clone());
+        check.notes_url = entry.notes_url.clone();
+        check.download_url = Some(asset.url.clone());
+        check.sha256 = Some(asset.sha256.trim().to_ascii_lowercase());
+        break;
+    }
+    check
+}
+
+struct UpdateSettings {
+    enabled: bool,
+    channel: String,
+    source_url: String,
+    installation_id: String,
+}
+
+/// Lee la configuración de actualizaciones y crea el `installation_id` la primera vez.
+fn update_settings(app: &AppHandle) -> AppResult<UpdateSettings> {
+    // Sin `unwrap_or_default`: guardar el id sobre una configuración ilegible la borraría.
+    let mut config = load_launcher_config(app)?;
+    let installation_id = match config.installation_id.clone() {
+        Some(id) if !id.trim().is_empty() => id,
+        _ => {
+            let id = app_clock(app).ids.new_id();
+            config.installation_id = Some(id.clone());
+            save_launcher_config(app, &config)?;
+            id
+        }
+    };
+    let channel = match config.update_channel.as_deref().map(str::trim) {
+        Some(channel) if channel.eq_ignore_ascii_case("beta") => "beta",
+        _ => "stable",
+    };
+    Ok(UpdateSettings {
+        enabled: config.update_checks_enabled.unwrap_or(true),
+        channel: channel.to_string(),
+        source_url: config
+            .update_manifest_url
+            .map(|url| url.trim().to_string())
+            .filter(|url| !url.is_empty())
+            .unwrap_or_else(|| DEFAULT_UPDATE_MANIFEST_URL.to_string()),
+        installation_id,
+    })
+}
+
+fn fetch_manifest(source_url: &str) -> AppResult<ReleaseManifest> {
+    let client = build_http_client()?;
+    let response = rate_limit::send_blocking(source_url, || {
+        client
+            .get(source_url)
+            .header(reqwest::header::ACCEPT, "application/json")
+    })
+    .map_err(|err| err.describe("No se pudo descargar el manifiesto de versiones"))?;
+    if !response.status().is_success() {
+        return Err(format!(
+            "El manifiesto de versiones {source_url} respondió HTTP {}.",
+            response.status()
+        ));
+    }
+    let body = response
+        .text()
+        .map_err(|err| format!("No se pudo leer el manifiesto de versiones: {err}"))?;
+    serde_json::from_str(&body).map_err(|err| format!("Manifiesto de versiones inválido: {err}"))
+}
+
+/// Manifiesto en caché si está vigente; si no, se descarga y, sin conexión, se usa la copia
+/// caducada. Devuelve también la fecha de descarga y si está caducado.
+fn load_manifest(
+    app: &AppHandle,
+    source_url: &str,
+    force_refresh: bool,
+) -> AppResult<(ReleaseManifest, String, bool)> {
+    let path = cache_file(&resolve_launcher_root(app)?, UPDATE_CACHE_DIR, "manifest");
+    let (cached, stale) = load_cached(
+        &path,
+        source_url,
+        app_clock(app).clock.now(),
+        Duration::hours(MANIFEST_MAX_AGE_HOURS),
+        force_refresh,
+        "actualizaciones",
+        || fetch_manifest(source_url),
+    )?;
+    Ok((cached.data, cached.fetched_at, stale))
+}
+
+fn run_update_check(app: &AppHandle, force_refresh: bool) -> AppResult<LauncherUpdateCheck> {
+    let settings = update_settings(app)?;
+    if !settings.enabled {
+        return Ok(LauncherUpdateCheck {
+            current_version: env!("CARGO_PKG_VERSION").to_string(),
+            channel: settings.channel,
+            disabled: true,
+            ..LauncherUpdateCheck::default()
+        });
+    }
+    let (manifest, fetched_at, stale) = load_manifest(app, &settings.source_url, force_refresh)?;
+    let mut check = select_update(
+        &manifest,
+        env!("CARGO_PKG_VERSION"),
+        &settings.channel,
+        &platform_key(),
+        &settings.installation_id,
+    );
+    check.checked_at = Some(fetched_at);
+    check.stale = stale;
+    if let Some(version) = check.version.as_deref() {
+        log::info!(
+            "🔹 Actualización del launcher disponible: {} → {version} ({})",
+            check.current_version,
+            check.channel
+        );
+    }
+    Ok(check)
+}
+
+/// Busca una versión más nueva del launcher en el canal configurado.
+#[tauri::command]
+pub async fn check_launcher_update(
+    app: AppHandle,
+    force_refresh: Option<bool>,
+) -> Result<LauncherUpdateCheck, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        run_update_check(&app, force_refresh.unwrap_or(false))
+    })
+    .await
+    .map_err(|err| format!("Falló la comprobación de actualizaciones: {err}"))?
+}
+
+/// Descarga `url` en `target` calculando el SHA-256 al vuelo; el archivo solo aparece con
+/// su nombre final si el hash coincide.
+fn download_verified(url: &str, expected_sha256: &str, target: &Path) -> AppResult<()> {
+    let client = build_http_client()?;
+    let mut response = client
+        .get(url)
+        .send()
+        .and_then(|response| response.error_for_status())
+        .map_err(|err| format!("No se pudo descargar la actualización {url}: {err}"))?;
+    let partial = target.with_extension("part");
+    let mut output = fs::File::create(&partial)
+        .map_err(|err| format!("No se pudo crear {}: {err}", partial.display()))?;
+    let mut hasher = Sha256::new();
+    let mut chunk = vec![0u8; DOWNLOAD_CHUNK_BYTES];
+    loop {
+        let read = response
+            .read(&mut chunk)
+            .map_err(|err| format!("No se pudo leer la descarga de la actualización: {err}"))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&chunk[..read]);
+        output
+            .write_all(&chunk[..read])
+            .map_err(|err| format!("No se pudo escribir {}: {err}", partial.display()))?;
+    }
+    output
+        .sync_all()
+        .map_err(|err| format!("No se pudo volcar a disco {}: {err}", partial.display()))?;
+    drop(output);
+    let actual = format!("{:x}", hasher.finalize());
+    if actual != expected_sha256 {
+        let _ = fs::remove_file(&partial);
+        return Err(format!(
+            "La actualización descargada no coincide con el manifiesto (SHA-256 {actual}, esperado {expected_sha256}); se descartó."
+        ));
+    }
+    fs::rename(&partial, target)
+        .map_err(|err| format!("No se pudo guardar {}: {err}", target.display()))
+}
+
+fn run_update_download(app: &AppHandle, target_dir: Option<String>) -> AppResult<String> {
+    let check = run_update_check(app, false)?;
+    if check.disabled {
+        return Err("La búsqueda de actualizaciones está desactivada en los ajustes.".to_string());
+    }
+    let (Some(version), Some(url), Some(sha256)) =
+        (check.version, check.download_url, check.sha256)
+    else {
+        return Err("No hay ninguna actualización disponible para este equipo.".to_string());
+    };
+    if !url.starts_with("https://") {
+        return Err(format!("URL de actualización no segura: {url}"));
+    }
+    if sha256.len() != 64 {
+        return Err(format!(
+            "El manifiesto no trae un SHA-256 válido para la versión {version}."
+        ));
+    }
+    let target_dir = match target_dir.map(|dir| dir.trim().to_string()) {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => resolve_launcher_root(app)?
+            .join(UPDATE_CACHE_DIR)
+            .join(&version),
+    };
+    fs::create_dir_all(&target_dir)
+        .map_err(|err| format!("No se pudo crear {}: {err}", target_dir.display()))?;
+    let file_name = url
+        .rsplit('/')
+        .next()
+        .and_then(|name| name.split('?').next())
+        .and_then(|name| safe_path_component(name).ok())
+        .unwrap_or_else(|| format!("Interface-2-{version}"));
+    let target = target_dir.join(file_name);
+    download_verified(&url, &sha256, &target)?;
+    log::info!(
+        "✔ Actualización {version} descargada y verificada en {}",
+        target.display()
+    );
+    Ok(target.display().to_string())
+}
+
+/// Descarga y verifica el instalador de la actualización disponible. Devuelve su ruta para
+/// que la interfaz se lo entregue al sistema; la instalación no se hace aquí.
+#[tauri::command]
+pub async fn download_launcher_update(
+    app: AppHandle,
+    target_dir: Option<String>,
+) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || run_update_download(&app, target_dir))
+        .await
+        .map_err(|err| format!("Falló la descarga de la actualización: {err}"))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest() -> ReleaseManifest {
+        serde_json::from_value(serde_json::json!({
+            "releases": [
+                {
+                    "version": "0.2.0-beta.1",
+                    "channel": "beta",
+                    "platforms": { "linux-x86_64": { "url": "https://example.invalid/beta.AppImage", "sha256": "AA" } }
+                },
+                {
+                    "version": "0.1.50",
+                    "channel": "stable",
+                    "notes_url": "https://example.invalid/notes",
+                    "rollout_percentage": 100,
+                    "platforms": { "linux-x86_64": { "url": "https://example.invalid/0.1.50.AppImage", "sha256": "BB" } }
+                },
+                {
+                    "version": "0.1.48",
+                    "channel": "stable",
+                    "platforms": { "linux-x86_64": { "url": "https://example.invalid/old.AppImage", "sha256": "CC" } }
+                }
+            ]
+        }))
+        .expect("manifest")
+    }
+
+    #[test]
+    fn channel_and_semver_pick_the_newest_applicable_release() {
+        let manifest = manifest();
+        let stable = select_update(&manifest, "0.1.49", "stable", "linux-x86_64", "id-1");
+        assert!(stable.update_available);
+        assert_eq!(stable.version.as_deref(), Some("0.1.50"));
+        assert_eq!(stable.sha256.as_deref(), Some("bb"));
+        assert_eq!(
+            stable.notes_url.as_deref(),
+            Some("https://example.invalid/notes")
+        );
+
+        let beta = select_update(&manifest, "0.1.49", "beta", "linux-x86_64", "id-1");
+        assert_eq!(beta.version.as_deref(), Some("0.2.0-beta.1"));
+
+        let other_platform = select_update(&manifest, "0.1.49", "stable", "windows-x86_64", "id-1");
+        assert!(!other_platform.update_available);
+        let up_to_date = select_update(&manifest, "0.1.50", "stable", "linux-x86_64", "id-1");
+        assert!(!up_to_date.update_available);
+    }
+
+    #[test]
+    fn staged_rollout_is_stable_per_installation() {
+        let mut manifest = manifest();
+        manifest.releases[1].rollout_percentage = Some(30);
+        let ids = (0..200).map(|n| format!("install-{n}")).collect::<Vec<_>>();
+        let included = ids
+            .iter()
+            .filter(|id| {
+                select_update(&manifest, "0.1.49", "stable", "linux-x86_64", id).update_available
+            })
+            .count();
+        assert!((30..=90).contains(&included), "{included}");
+        for id in &ids {
+            let first = select_update(&manifest, "0.1.49", "stable", "linux-x86_64", id);
+            let again = select_update(&manifest, "0.1.49", "stable", "linux-x86_64", id);
+            assert_eq!(first, again);
+            assert_eq!(first.held_back_by_rollout, !first.update_available);
+        }
+
+        manifest.releases[1].rollout_percentage = Some(0);
+        let none = select_update(&manifest, "0.1.49", "stable", "linux-x86_64", "install-1");
+        assert!(!none.update_available);
+        assert!(none.held_back_by_rollout);
+    }
+}
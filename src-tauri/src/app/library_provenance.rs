@@ -0,0 +1,496 @@
+// Origen y licencia de las librerías del classpath.
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::Read,
+    path::{Path, PathBuf},
+    sync::atomic::AtomicUsize,
+};
+
+use serde::Serialize;
+use serde_json::Value;
+use tauri::AppHandle;
+use zip::ZipArchive;
+
+use crate::{
+    app::{
+        instance_service::{
+            build_maven_library_path, inspect_merged_version_json, is_sharing_violation,
+            read_instance_metadata, resolve_effective_version_id, retry_transient_open,
+        },
+        trusted_root::resolve_trusted_instance_root,
+    },
+    domain::minecraft::rule_engine::{evaluate_rules, RuleContext},
+    infrastructure::{
+        checksum::verification_cache::{JarManifestInfo, VerificationCache},
+        filesystem::paths::resolve_launcher_root,
+    },
+};
+
+const UNKNOWN: &str = "unknown";
+/// Nombres de archivo de licencia que se buscan en la raíz y en `META-INF/`.
+const LICENSE_FILE_STEMS: [&str; 3] = ["license", "licence", "copying"];
+/// Bytes de un LICENSE que bastan para reconocer las licencias habituales.
+const LICENSE_TEXT_PREFIX: u64 = 4096;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LibraryProvenance {
+    /// Coordenada maven (`grupo:artefacto:versión[:clasificador]`).
+    pub coordinate: String,
+    pub path: String,
+    pub present: bool,
+    pub url: Option<String>,
+    pub host: String,
+    /// Licencia declarada en el jar o `unknown`.
+    pub license: String,
+    pub license_file: Option<String>,
+    pub vendor: Option<String>,
+    pub sha1: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HostCount {
+    pub host: String,
+    pub libraries: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProvenanceReport {
+    pub version_id: String,
+    pub libraries: Vec<LibraryProvenance>,
+    pub hosts: Vec<HostCount>,
+    pub unknown_licenses: usize,
+    /// Jars abiertos en esta consulta; 0 con la caché de verificación caliente.
+    pub jars_opened: usize,
+    pub export_path: Option<String>,
+}
+
+/// Cabeceras del `MANIFEST.MF` con las claves en minúsculas; une las líneas de continuación.
+fn parse_manifest_headers(raw: &str) -> BTreeMap<String, String> {
+    let mut headers = BTreeMap::new();
+    let mut current: Option<(String, String)> = None;
+    for line in raw.lines() {
+        if let Some(rest) = line.strip_prefix(' ') {
+            if let Some((_, value)) = current.as_mut() {
+                value.push_str(rest);
+            }
+            continue;
+        }
+        if let Some((key, value)) = current.take() {
+            headers.entry(key).or_insert(value);
+        }
+        if let Some((key, value)) = line.split_once(':') {
+            current = Some((key.trim().to_ascii_lowercase(), value.trim().to_string()));
+        }
+    }
+    if let Some((key, value)) = current {
+        headers.entry(key).or_insert(value);
+    }
+    headers
+}
+
+/// Licencia reconocible por el texto de un LICENSE; `None` si no es una de las habituales.
+fn license_from_text(text: &str) -> Option<&'static str> {
+    let text = text.to_ascii_lowercase();
+    let version_3 = text.contains("version 3");
+    if text.contains("apache license") && text.contains("version 2.0") {
+        Some("Apache-2.0")
+    } else if text.contains("mit license")
+        || text.contains("permission is hereby granted, free of charge")
+    {
+        Some("MIT")
+    } else if text.contains("gnu lesser general public license") {
+        Some(if version_3 { "LGPL-3.0" } else { "LGPL-2.1" })
+    } else if text.contains("gnu general public license") {
+        Some(if version_3 { "GPL-3.0" } else { "GPL-2.0" })
+    } else if text.contains("mozilla public license") && text.contains("2.0") {
+        Some("MPL-2.0")
+    } else if text.contains("eclipse public license") {
+        Some("EPL")
+    } else if text.contains("redistribution and use in source and binary forms") {
+        Some("BSD")
+    } else {
+        None
+    }
+}
+
+fn is_license_entry(name: &str) -> bool {
+    let lower = name.to_ascii_lowercase();
+    let file = lower.strip_prefix("meta-inf/").unwrap_or(&lower);
+    !file.contains('/')
+        && LICENSE_FILE_STEMS
+            .iter()
+            .any(|stem| file == *stem || file.starts_with(&format!("{stem}.")))
+}
+
+/// Lee el manifest y, si no declara licencia, el primer LICENSE embebido. Una sola apertura.
+fn read_jar_manifest_info(jar: &Path) -> JarManifestInfo {
+    let retries = AtomicUsize::new(0);
+    let Ok(file) = retry_transient_open(&retries, is_sharing_violation, || fs::File::open(jar))
+    else {
+        return JarManifestInfo::default();
+    };
+    let Ok(mut archive) = ZipArchive::new(file) else {
+        return JarManifestInfo::default();
+    };
+    let mut info = JarManifestInfo::default();
+    if let Ok(mut entry) = archive.by_name("META-INF/MANIFEST.MF") {
+        let mut raw = String::new();
+        if entry.read_to_string(&mut raw).is_ok() {
+            let headers = parse_manifest_headers(&raw);
+            info.license = headers.get("bundle-license").cloned();
+            info.vendor = headers
+                .get("implementation-vendor")
+                .or_else(|| headers.get("bundle-vendor"))
+                .cloned();
+        }
+    }
+    let license_entry = archive
+        .file_names()
+        .filter(|name| is_license_entry(name))
+        .min()
+        .map(str::to_string);
+    if let Some(name) = license_entry {
+        if info.license.is_none() {
+            if let Ok(entry) = archive.by_name(&name) {
+                let mut text = String::new();
+                if entry
+                    .take(LICENSE_TEXT_PREFIX)
+                    .read_to_string(&mut text)
+                    .is_ok()
+                {
+                    info.license = license_from_text(&text).map(str::to_string);
+                }
+            }
+        }
+        info.license_file = Some(name);
+    }
+    info
+}
+
+fn url_host(url: &str) -> Option<String> {
+    reqwest::Url::parse(url)
+        .ok()?
+        .host_str()
+        .map(str::to_ascii_lowercase)
+}
+
+/// Jar del classpath y su URL de descarga: `downloads.artifact` o, en librerías solo con
+/// `name`/`url` (Fabric, Quilt), la ruta maven sobre la URL base del repositorio.
+fn library_location(libraries_root: &Path, library: &Value) -> Option<(PathBuf, Option<String>)> {
+    if let Some(downloads) = library.get("downloads") {
+        let artifact = downloads.get("artifact")?;
+        let path = artifact.get("path").and_then(Value::as_str)?;
+        let url = artifact
+            .get("url")
+            .and_then(Value::as_str)
+            .filter(|url| !url.trim().is_empty())
+            .map(str::to_string);
+        return Some((libraries_root.join(path), url));
+    }
+    if library.get("natives").is_some() {
+        return None;
+    }
+    let path = PathBuf::from(build_maven_library_path(libraries_root, library)?);
+    let url = library.get("url").and_then(Value::as_str).and_then(|base| {
+        let relative = path
+            .strip_prefix(libraries_root)
+            .ok()?
+            .to_string_lossy()
+            .replace('\\', "/");
+        Some(format!("{}/{relative}", base.trim_end_matches('/')))
+    });
+    Some((path, url))
+}
+
+fn build_provenance(
+    libraries_root: &Path,
+    version_json: &Value,
+    cache: &mut VerificationCache,
+    jars_opened: &mut usize,
+) -> Vec<LibraryProvenance> {
+    let rule_context = RuleContext::current();
+    let mut libraries = Vec::new();
+    for library in version_json
+        .get("libraries")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        let rules = library
+            .get("rules")
+            .and_then(Value::as_array)
+            .cloned()
+            .unwrap_or_default();
+        if !evaluate_rules(&rules, &rule_context) {
+            continue;
+        }
+        let Some((path, url)) = library_location(libraries_root, library) else {
+            continue;
+        };
+        let coordinate = library
+            .get("name")
+            .and_then(Value::as_str)
+            .unwrap_or(UNKNOWN)
+            .to_string();
+        let present = path.is_file();
+        let sha1 = present
+            .then(|| cache.sha1(&path).ok().map(|(sha1, _)| sha1))
+            .flatten();
+        let manifest = if present {
+            cache.jar_manifest(&path).unwrap_or_else(|| {
+                *jars_opened += 1;
+                let info = read_jar_manifest_info(&path);
+                cache.record_jar_manifest(&path, info.clone());
+                info
+            })
+        } else {
+            JarManifestInfo::default()
+        };
+        libraries.push(LibraryProvenance {
+            coordinate,
+            path: path.display().to_string(),
+            present,
+            host: url
+                .as_deref()
+                .and_then(url_host)
+                .unwrap_or_else(|| UNKNOWN.to_string()),
+            url,
+            license: manifest.license.unwrap_or_else(|| UNKNOWN.to_string()),
+            license_file: manifest.license_file,
+            vendor: manifest.vendor,
+            sha1,
+        });
+    }
+    libraries
+}
+
+fn host_counts(libraries: &[LibraryProvenance]) -> Vec<HostCount> {
+    let mut counts = BTreeMap::<&str, usize>::new();
+    for library in libraries {
+        *counts.entry(library.host.as_str()).or_default() += 1;
+    }
+    let mut hosts = counts
+        .into_iter()
+        .map(|(host, libraries)| HostCount {
+            host: host.to_string(),
+            libraries,
+        })
+        .collect::<Vec<_>>();
+    hosts.sort_by_key(|host| std::cmp::Reverse(host.libraries));
+    hosts
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn provenance_csv(libraries: &[LibraryProvenance]) -> String {
+    let mut csv = String::from("coordinate,path,host,url,license,vendor,sha1\n");
+    for library in libraries {
+        let row = [
+            library.coordinate.as_str(),
+            library.path.as_str(),
+            library.host.as_str(),
+            library.url.as_deref().unwrap_or_default(),
+            library.license.as_str(),
+            library.vendor.as_deref().unwrap_or_default(),
+            library.sha1.as_deref().unwrap_or_default(),
+        ]
+        .map(csv_field)
+        .join(",");
+        csv.push_str(&row);
+        csv.push('\n');
+    }
+    csv
+}
+
+fn instance_provenance(
+    app: &AppHandle,
+    instance_root: &Path,
+    export: Option<PathBuf>,
+) -> Result<ProvenanceReport, String> {
+    let metadata = read_instance_metadata(instance_root.display().to_string())?;
+    if metadata.state.eq_ignore_ascii_case("redirect") {
+        return Err(
+            "Las instancias redirigidas usan las librerías del launcher de origen.".to_string(),
+        );
+    }
+    let mc_root = instance_root.join("minecraft");
+    let version_id = resolve_effective_version_id(&mc_root, &metadata)?;
+    let (version_json, _) = inspect_merged_version_json(&mc_root, &version_id)?;
+    let launcher_root = resolve_launcher_root(app)?;
+
+    let mut cache = VerificationCache::load(&launcher_root);
+    let mut jars_opened = 0;
+    let libraries = build_provenance(
+        &launcher_root.join("libraries"),
+        &version_json,
+        &mut cache,
+        &mut jars_opened,
+    );
+    cache.save();
+
+    let export_path = match export {
+        Some(path) => {
+            fs::write(&path, provenance_csv(&libraries)).map_err(|err| {
+                format!("No se pudo exportar el informe a {}: {err}", path.display())
+            })?;
+            Some(path.display().to_string())
+        }
+        None => None,
+    };
+    let unknown_licenses = libraries
+        .iter()
+        .filter(|library| library.license == UNKNOWN)
+        .count();
+    log::info!(
+        "🔹 Procedencia de {version_id}: {} librerías, {} sin licencia conocida, {jars_opened} jars abiertos",
+        libraries.len(),
+        unknown_licenses
+    );
+    Ok(ProvenanceReport {
+        version_id,
+        hosts: host_counts(&libraries),
+        libraries,
+        unknown_licenses,
+        jars_opened,
+        export_path,
+    })
+}
+
+#[tauri::command]
+pub async fn get_instance_provenance(
+    app: AppHandle,
+    instance_root: String,
+    export: Option<PathBuf>,
+) -> Result<ProvenanceReport, String> {
+    let root = resolve_trusted_instance_root(&app, &instance_root)?
+        .path()
+        .to_path_buf();
+    tauri::async_runtime::spawn_blocking(move || instance_provenance(&app, &root, export))
+        .await
+        .map_err(|err| format!("Falló la tarea de procedencia de librerías: {err}"))?
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use zip::{write::SimpleFileOptions, ZipWriter};
+
+    use super::*;
+
+    fn write_jar(path: &Path, entries: &[(&str, &str)]) {
+        fs::create_dir_all(path.parent().expect("parent")).expect("dir");
+        let mut zip = ZipWriter::new(fs::File::create(path).expect("jar"));
+        for (name, content) in entries {
+            zip.start_file(*name, SimpleFileOptions::default())
+                .expect("entry");
+            zip.write_all(content.as_bytes()).expect("write");
+        }
+        zip.finish().expect("finish");
+    }
+
+    #[test]
+    fn reads_license_from_manifest_or_license_file_and_never_guesses() {
+        let dir =
+            std::env::temp_dir().join(format!("interface-provenance-jar-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let manifest = dir.join("manifest.jar");
+        write_jar(
+            &manifest,
+            &[(
+                "META-INF/MANIFEST.MF",
+                "Manifest-Version: 1.0\r\nBundle-License: https://www.apache.org/licenses/LICENSE\r\n -2.0.txt\r\nImplementation-Vendor: FasterXML\r\n",
+            )],
+        );
+        let info = read_jar_manifest_info(&manifest);
+        assert_eq!(
+            info.license.as_deref(),
+            Some("https://www.apache.org/licenses/LICENSE-2.0.txt")
+        );
+        assert_eq!(info.vendor.as_deref(), Some("FasterXML"));
+
+        let embedded = dir.join("embedded.jar");
+        write_jar(
+            &embedded,
+            &[(
+                "META-INF/LICENSE.txt",
+                "The MIT License (MIT)\nCopyright...",
+            )],
+        );
+        let info = read_jar_manifest_info(&embedded);
+        assert_eq!(info.license.as_deref(), Some("MIT"));
+        assert_eq!(info.license_file.as_deref(), Some("META-INF/LICENSE.txt"));
+
+        let custom = dir.join("custom.jar");
+        write_jar(&custom, &[("LICENSE", "Todos los derechos reservados.")]);
+        let info = read_jar_manifest_info(&custom);
+        assert_eq!(info.license, None);
+        assert_eq!(info.license_file.as_deref(), Some("LICENSE"));
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn reports_hosts_and_reuses_cached_manifests() {
+        let dir = std::env::temp_dir().join(format!(
+            "interface-provenance-report-{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        let libraries_root = dir.join("libraries");
+        write_jar(
+            &libraries_root.join("org/ow2/asm/asm/9.6/asm-9.6.jar"),
+            &[(
+                "LICENSE.txt",
+                "Redistribution and use in source and binary forms",
+            )],
+        );
+        let version_json = serde_json::json!({
+            "libraries": [
+                {
+                    "name": "org.ow2.asm:asm:9.6",
+                    "url": "https://maven.fabricmc.net/"
+                },
+                {
+                    "name": "com.mojang:brigadier:1.0.18",
+                    "downloads": { "artifact": {
+                        "path": "com/mojang/brigadier/1.0.18/brigadier-1.0.18.jar",
+                        "url": "https://libraries.minecraft.net/com/mojang/brigadier/1.0.18/brigadier-1.0.18.jar"
+                    } }
+                }
+            ]
+        });
+
+        let mut cache = VerificationCache::load(&dir);
+        let mut opened = 0;
+        let report = build_provenance(&libraries_root, &version_json, &mut cache, &mut opened);
+        assert_eq!(opened, 1);
+        assert_eq!(report[0].host, "maven.fabricmc.net");
+        assert_eq!(report[0].license, "BSD");
+        assert!(report[0].sha1.is_some());
+        assert!(!report[1].present);
+        assert_eq!(report[1].license, UNKNOWN);
+        assert_eq!(report[1].host, "libraries.minecraft.net");
+        assert_eq!(host_counts(&report).len(), 2);
+        assert!(provenance_csv(&report).starts_with("coordinate,path,host,url,license"));
+        cache.save();
+
+        let mut warm = VerificationCache::load(&dir);
+        let mut reopened = 0;
+        build_provenance(&libraries_root, &version_json, &mut warm, &mut reopened);
+        assert_eq!(reopened, 0);
+
+        let _ = fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,527 @@
+use std::{
+    collections::VecDeque,
+    fs,
+    io::{BufRead, BufReader},
+    path::Path,
+    sync::{Mutex, OnceLock},
+    time::Duration,
+};
+
+use serde::Serialize;
+use tauri::AppHandle;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+    sync::oneshot,
+};
+
+use crate::{
+    app::{
+        instance_service::{get_runtime_status, running_instances_snapshot},
+        launcher_service::list_instances_readonly,
+        launcher_snapshot::get_launcher_snapshot,
+    },
+    domain::models::instance::InstanceSummary,
+    infrastructure::filesystem::config::{load_launcher_config, save_launcher_config},
+    shared::clock::app_clock,
+};
+
+pub const DEFAULT_LOCAL_API_PORT: u16 = 47631;
+const DEFAULT_TAIL_LINES: usize = 100;
+const MAX_TAIL_LINES: usize = 1000;
+const MAX_REQUEST_BYTES: usize = 8 * 1024;
+const HEADER_READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+static LOCAL_API_TOKEN: OnceLock<String> = OnceLock::new();
+static LOCAL_API_SERVER: OnceLock<Mutex<Option<LocalApiServerHandle>>> = OnceLock::new();
+
+struct LocalApiServerHandle {
+    port: u16,
+    shutdown: oneshot::Sender<()>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalApiSettings {
+    pub enabled: bool,
+    pub port: u16,
+    pub running: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LocalApiRunningInstance {
+    id: Option<String>,
+    name: Option<String>,
+    instance_root: String,
+    pid: Option<u32>,
+    uptime_secs: u64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LocalApiStatus {
+    launcher_version: String,
+    running_instances: Vec<LocalApiRunningInstance>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LocalApiLogTail {
+    id: String,
+    source: String,
+    lines: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct LocalApiRequest {
+    method: String,
+    path: String,
+    query: String,
+    bearer_token: Option<String>,
+}
+
+fn server_slot() -> &'static Mutex<Option<LocalApiServerHandle>> {
+    LOCAL_API_SERVER.get_or_init(|| Mutex::new(None))
+}
+
+fn session_token() -> &'static str {
+    LOCAL_API_TOKEN.get_or_init(|| uuid::Uuid::new_v4().simple().to_string())
+}
+
+fn is_server_running() -> bool {
+    server_slot()
+        .lock()
+        .map(|slot| slot.is_some())
+        .unwrap_or(false)
+}
+
+fn stop_local_api_server() {
+    let Ok(mut slot) = server_slot().lock() else {
+        return;
+    };
+    if let Some(handle) = slot.take() {
+        let _ = handle.shutdown.send(());
+        log::info!("🔹 API local detenida (puerto {}).", handle.port);
+    }
+}
+
+fn start_local_api_server(app: &AppHandle, port: u16) -> Result<(), String> {
+    let mut slot = server_slot()
+        .lock()
+        .map_err(|_| "No se pudo bloquear el estado de la API local.".to_string())?;
+    if let Some(handle) = slot.as_ref() {
+        if handle.port == port {
+            return Ok(());
+        }
+    }
+    if let Some(handle) = slot.take() {
+        let _ = handle.shutdown.send(());
+    }
+
+    // Bind síncrono para devolver el error (puerto ocupado, etc.) al llamador.
+    let listener = std::net::TcpListener::bind(("127.0.0.1", port))
+        .map_err(|err| format!("No se pudo abrir la API local en 127.0.0.1:{port}: {err}"))?;
+    listener
+        .set_nonblocking(true)
+        .map_err(|err| format!("No se pudo configurar la API local: {err}"))?;
+
+    let (shutdown_tx, mut shutdown_rx) = oneshot::channel::<()>();
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let listener = match TcpListener::from_std(listener) {
+            Ok(listener) => listener,
+            Err(err) => {
+                log::warn!("⚠ API local no pudo iniciar el listener: {err}");
+                return;
+            }
+        };
+        loop {
+            tokio::select! {
+                _ = &mut shutdown_rx => break,
+                accepted = listener.accept() => {
+                    let Ok((stream, _)) = accepted else {
+                        continue;
+                    };
+                    let app = app.clone();
+                    tauri::async_runtime::spawn(async move {
+                        let _ = handle_connection(app, stream).await;
+                    });
+                }
+            }
+        }
+    });
+
+    *slot = Some(LocalApiServerHandle {
+        port,
+        shutdown: shutdown_tx,
+    });
+    log::info!("✔ API local escuchando en 127.0.0.1:{port} (solo lectura).");
+    Ok(())
+}
+
+/// Arranca la API local al iniciar el launcher si está habilitada en la configuración.
+pub fn initialize_local_api(app: &AppHandle) {
+    let Ok(config) = load_launcher_config(app) else {
+        return;
+    };
+    if !config.local_api_enabled {
+        return;
+    }
+    let port = config.local_api_port.unwrap_or(DEFAULT_LOCAL_API_PORT);
+    if let Err(err) = start_local_api_server(app, port) {
+        log::warn!("⚠ {err}");
+    }
+}
+
+#[tauri::command]
+pub fn get_local_api_settings(app: AppHandle) -> Result<LocalApiSettings, String> {
+    let config = load_launcher_config(&app).unwrap_or_default();
+    Ok(LocalApiSettings {
+        enabled: config.local_api_enabled,
+        port: config.local_api_port.unwrap_or(DEFAULT_LOCAL_API_PORT),
+        running: is_server_running(),
+    })
+}
+
+#[tauri::command]
+pub fn set_local_api_settings(
+    app: AppHandle,
+    enabled: bool,
+    port: Option<u16>,
+) -> Result<LocalApiSettings, String> {
+    let port = port.unwrap_or(DEFAULT_LOCAL_API_PORT);
+    if port < 1024 {
+        return Err(format!(
+            "Puerto inválido para la API local: {port}. Usa un puerto entre 1024 y 65535."
+        ));
+    }
+
+    if enabled {
+        start_local_api_server(&app, port)?;
+    } else {
+        stop_local_api_server();
+    }
+
+    let mut config = load_launcher_config(&app).unwrap_or_default();
+    config.local_api_enabled = enabled;
+    config.local_api_port = Some(port);
+    save_launcher_config(&app, &config)?;
+
+    Ok(LocalApiSettings {
+        enabled,
+        port,
+        running: is_server_running(),
+    })
+}
+
+#[tauri::command]
+pub fn get_local_api_token() -> String {
+    session_token().to_string()
+}
+
+async fn handle_connection(app: AppHandle, mut stream: TcpStream) -> std::io::Result<()> {
+    // Un cliente que abre la conexión y no termina las cabeceras no debe ocupar la tarea.
+    let head = match tokio::time::timeout(HEADER_READ_TIMEOUT, read_request_head(&mut stream)).await
+    {
+        Ok(head) => head?,
+        Err(_) => {
+            return write_response(
+                &mut stream,
+                408,
+                &error_body("Tiempo de espera agotado leyendo la petición."),
+            )
+            .await;
+        }
+    };
+    let Some(buffer) = head else {
+        return write_response(&mut stream, 431, &error_body("Petición demasiado grande.")).await;
+    };
+
+    let Some(request) = parse_request(&String::from_utf8_lossy(&buffer)) else {
+        return write_response(&mut stream, 400, &error_body("Petición HTTP inválida.")).await;
+    };
+
+    if request.method != "GET" {
+        return write_response(
+            &mut stream,
+            405,
+            &error_body("La API local es de solo lectura (solo GET)."),
+        )
+        .await;
+    }
+    if !token_matches(request.bearer_token.as_deref(), session_token()) {
+        return write_response(&mut stream, 401, &error_body("Token inválido o ausente.")).await;
+    }
+
+    let (status, body) =
+        tauri::async_runtime::spawn_blocking(move || route_request(&app, &request))
+            .await
+            .unwrap_or_else(|err| {
+                (
+                    500,
+                    error_body(&format!("Falló la tarea de la API local: {err}")),
+                )
+            });
+    write_response(&mut stream, status, &body).await
+}
+
+/// Lee hasta el final de las cabeceras; `None` si superan `MAX_REQUEST_BYTES`.
+async fn read_request_head(stream: &mut TcpStream) -> std::io::Result<Option<Vec<u8>>> {
+    let mut buffer = Vec::with_capacity(1024);
+    let mut chunk = [0u8; 1024];
+    loop {
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            break;
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+        if buffer.windows(4).any(|window| window == b"\r\n\r\n") {
+            break;
+        }
+        if buffer.len() > MAX_REQUEST_BYTES {
+            return Ok(None);
+        }
+    }
+    Ok(Some(buffer))
+}
+
+/// Compara el token sin salir en el primer byte distinto, para no filtrarlo por tiempos.
+fn token_matches(received: Option<&str>, expected: &str) -> bool {
+    let Some(received) = received else {
+        return false;
+    };
+    received.len() == expected.len()
+        && received
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0u8, |diff, (left, right)| diff | (left ^ right))
+            == 0
+}
+
+async fn write_response(stream: &mut TcpStream, status: u16, body: &str) -> std::io::Result<()> {
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        408 => "Request Timeout",
+        431 => "Request Header Fields Too Large",
+        _ => "Internal Server Error",
+    };
+    let response = format!(
+        "HTTP/1.1 {status} {reason}\r\nContent-Type: application/json; charset=utf-8\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+fn error_body(message: &str) -> String {
+    serde_json::json!({ "error": message }).to_string()
+}
+
+fn json_body<T: Serialize>(value: &T) -> (u16, String) {
+    match serde_json::to_string(value) {
+        Ok(body) => (200, body),
+        Err(err) => (
+            500,
+            error_body(&format!("No se pudo serializar respuesta: {err}")),
+        ),
+    }
+}
+
+fn parse_request(raw: &str) -> Option<LocalApiRequest> {
+    let mut lines = raw.split("\r\n");
+    let mut request_line = lines.next()?.split_whitespace();
+    let method = request_line.next()?.to_string();
+    let target = request_line.next()?;
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+
+    let bearer_token = lines
+        .take_while(|line| !line.is_empty())
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("authorization"))
+        .and_then(|(_, value)| {
+            let value = value.trim();
+            value
+                .strip_prefix("Bearer ")
+                .or_else(|| value.strip_prefix("bearer "))
+                .map(|token| token.trim().to_string())
+        });
+
+    Some(LocalApiRequest {
+        method,
+        path: path.trim_end_matches('/').to_string(),
+        query: query.to_string(),
+        bearer_token,
+    })
+}
+
+fn parse_tail_lines(query: &str) -> usize {
+    serde_urlencoded::from_str::<Vec<(String, String)>>(query)
+        .ok()
+        .and_then(|pairs| {
+            pairs
+                .into_iter()
+                .find(|(key, _)| key == "lines")
+                .and_then(|(_, value)| value.parse::<usize>().ok())
+        })
+        .unwrap_or(DEFAULT_TAIL_LINES)
+        .clamp(1, MAX_TAIL_LINES)
+}
+
+fn route_request(app: &AppHandle, request: &LocalApiRequest) -> (u16, String) {
+    let segments = request
+        .path
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>();
+
+    match segments.as_slice() {
+        ["status"] => match build_status(app) {
+            Ok(status) => json_body(&status),
+            Err(err) => (500, error_body(&err)),
+        },
+        ["snapshot"] => match get_launcher_snapshot(app.clone()) {
+            Ok(snapshot) => json_body(&snapshot),
+            Err(err) => (500, error_body(&err)),
+        },
+        ["instances"] => match list_instances_readonly(app) {
+            Ok(instances) => json_body(&instances),
+            Err(err) => (500, error_body(&err)),
+        },
+        ["instances", id, "logs", "tail"] => {
+            let id = urlencoding::decode(id)
+                .map(|value| value.into_owned())
+                .unwrap_or_else(|_| id.to_string());
+            let lines = parse_tail_lines(&request.query);
+            match build_log_tail(app, &id, lines) {
+                Ok(Some(tail)) => json_body(&tail),
+                Ok(None) => (404, error_body("Instancia no encontrada.")),
+                Err(err) => (500, error_body(&err)),
+            }
+        }
+        _ => (404, error_body("Ruta no encontrada.")),
+    }
+}
+
+fn build_status(app: &AppHandle) -> Result<LocalApiStatus, String> {
+    let instances = list_instances_readonly(app).unwrap_or_default();
+    let running_instances = running_instances_snapshot(app_clock(app).clock.as_ref())?
+        .into_iter()
+        .map(|running| {
+            let summary = find_summary_by_root(&instances, &running.instance_root);
+            LocalApiRunningInstance {
+                id: summary.map(|item| item.id.clone()),
+                name: summary.map(|item| item.name.clone()),
+                instance_root: running.instance_root,
+                pid: running.pid,
+                uptime_secs: running.uptime_secs,
+            }
+        })
+        .collect();
+
+    Ok(LocalApiStatus {
+        launcher_version: app.package_info().version.to_string(),
+        running_instances,
+    })
+}
+
+fn find_summary_by_root<'a>(
+    instances: &'a [InstanceSummary],
+    instance_root: &str,
+) -> Option<&'a InstanceSummary> {
+    instances
+        .iter()
+        .find(|item| Path::new(&item.instance_root) == Path::new(instance_root))
+}
+
+fn build_log_tail(
+    app: &AppHandle,
+    id: &str,
+    lines: usize,
+) -> Result<Option<LocalApiLogTail>, String> {
+    let instances = list_instances_readonly(app)?;
+    let Some(summary) = instances.iter().find(|item| item.id == id) else {
+        return Ok(None);
+    };
+
+    let latest_log = Path::new(&summary.instance_root)
+        .join("minecraft")
+        .join("logs")
+        .join("latest.log");
+    if latest_log.is_file() {
+        return Ok(Some(LocalApiLogTail {
+            id: summary.id.clone(),
+            source: "latest.log".to_string(),
+            lines: read_tail_lines(&latest_log, lines)?,
+        }));
+    }
+
+    let status = get_runtime_status(app.clone(), summary.instance_root.clone())?;
+    let skip = status.stderr_tail.len().saturating_sub(lines);
+    Ok(Some(LocalApiLogTail {
+        id: summary.id.clone(),
+        source: "runtime".to_string(),
+        lines: status.stderr_tail.into_iter().skip(skip).collect(),
+    }))
+}
+
+fn read_tail_lines(path: &Path, lines: usize) -> Result<Vec<String>, String> {
+    let file = fs::File::open(path)
+        .map_err(|err| format!("No se pudo abrir log {}: {err}", path.display()))?;
+    let mut tail = VecDeque::with_capacity(lines);
+    for line in BufReader::new(file).split(b'\n') {
+        let line = line.map_err(|err| format!("No se pudo leer log {}: {err}", path.display()))?;
+        if tail.len() == lines {
+            tail.pop_front();
+        }
+        tail.push_back(
+            String::from_utf8_lossy(&line)
+                .trim_end_matches('\r')
+                .to_string(),
+        );
+    }
+    Ok(tail.into_iter().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_request_extracts_path_query_and_bearer_token() {
+        let raw = "GET /instances/abc/logs/tail?lines=20 HTTP/1.1\r\nHost: 127.0.0.1\r\nAuthorization: Bearer token123\r\n\r\n";
+        let request = parse_request(raw).expect("la petición debería parsearse");
+        assert_eq!(request.method, "GET");
+        assert_eq!(request.path, "/instances/abc/logs/tail");
+        assert_eq!(request.query, "lines=20");
+        assert_eq!(request.bearer_token.as_deref(), Some("token123"));
+    }
+
+    #[test]
+    fn parse_request_without_authorization_has_no_token() {
+        let raw = "GET /status HTTP/1.1\r\nHost: 127.0.0.1\r\n\r\n";
+        let request = parse_request(raw).expect("la petición debería parsearse");
+        assert_eq!(request.bearer_token, None);
+    }
+
+    #[test]
+    fn token_matches_requires_exact_token() {
+        assert!(token_matches(Some("abc123"), "abc123"));
+        assert!(!token_matches(Some("abc124"), "abc123"));
+        assert!(!token_matches(Some("abc12"), "abc123"));
+        assert!(!token_matches(None, "abc123"));
+    }
+
+    #[test]
+    fn parse_tail_lines_defaults_and_clamps() {
+        assert_eq!(parse_tail_lines(""), DEFAULT_TAIL_LINES);
+        assert_eq!(parse_tail_lines("lines=abc"), DEFAULT_TAIL_LINES);
+        assert_eq!(parse_tail_lines("lines=0"), 1);
+        assert_eq!(parse_tail_lines("lines=50000"), MAX_TAIL_LINES);
+        assert_eq!(parse_tail_lines("lines=25"), 25);
+    }
+}
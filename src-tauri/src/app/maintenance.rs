@@ -0,0 +1,198 @@
+use std::{
+    sync::{Mutex, MutexGuard, OnceLock},
+    thread::{self, ThreadId},
+};
+
+use serde::Serialize;
+
+use crate::app::instance_service::with_no_running_instances;
+
+/// Un mantenimiento de recursos compartidos está en curso; se rechaza el lanzamiento.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct MaintenanceInProgressError {
+    /// Siempre `MAINTENANCE_IN_PROGRESS`.
+    pub code: &'static str,
+    pub operation: String,
+    pub progress_percent: Option<u8>,
+    pub message: String,
+}
+
+/// Estado para el banner de la UI.
+#[derive(Debug, Clone, Default, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct MaintenanceStatus {
+    pub active: bool,
+    pub operation: Option<String>,
+    pub progress_percent: Option<u8>,
+}
+
+#[derive(Debug)]
+struct ActiveMaintenance {
+    operation: String,
+    owner: ThreadId,
+    /// Llamadas anidadas del mismo hilo (p. ej. una relocalización que migra instancias).
+    depth: u32,
+    progress_percent: Option<u8>,
+}
+
+static MAINTENANCE: OnceLock<Mutex<Option<ActiveMaintenance>>> = OnceLock::new();
+
+/// Un pánico dentro de la sección crítica no debe dejar el launcher bloqueado para siempre.
+fn maintenance_state() -> MutexGuard<'static, Option<ActiveMaintenance>> {
+    MAINTENANCE
+        .get_or_init(|| Mutex::new(None))
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Mantiene el bloqueo mientras vive; se libera al salir del ámbito, también por pánico.
+#[must_use = "el mantenimiento termina en cuanto se suelta el guard"]
+#[derive(Debug)]
+pub struct MaintenanceGuard {
+    _private: (),
+}
+
+impl Drop for MaintenanceGuard {
+    fn drop(&mut self) {
+        leave(&mut maintenance_state());
+    }
+}
+
+fn enter(state: &mut Option<ActiveMaintenance>, operation: &str) -> Result<(), String> {
+    match state.as_mut() {
+        Some(active) if active.owner == thread::current().id() => {
+            active.depth += 1;
+            Ok(())
+        }
+        Some(active) => Err(format!(
+            "Ya hay un mantenimiento en curso ({}); espera a que termine.",
+            active.operation
+        )),
+        None => {
+            *state = Some(ActiveMaintenance {
+                operation: operation.to_string(),
+                owner: thread::current().id(),
+                depth: 1,
+                progress_percent: None,
+            });
+            log::info!("🔹 Mantenimiento '{operation}' iniciado; lanzamientos bloqueados");
+            Ok(())
+        }
+    }
+}
+
+fn leave(state: &mut Option<ActiveMaintenance>) {
+    if let Some(active) = state.as_mut() {
+        active.depth = active.depth.saturating_sub(1);
+        if active.depth == 0 {
+            log::info!("✔ Mantenimiento '{}' finalizado", active.operation);
+            *state = None;
+        }
+    }
+}
+
+fn in_progress_error(active: &ActiveMaintenance) -> MaintenanceInProgressError {
+    let message = match active.progress_percent {
+        Some(percent) => format!(
+            "El launcher está en mantenimiento ({}, {percent}%). Inténtalo cuando termine.",
+            active.operation
+        ),
+        None => format!(
+            "El launcher está en mantenimiento ({}). Inténtalo cuando termine.",
+            active.operation
+        ),
+    };
+    MaintenanceInProgressError {
+        code: "MAINTENANCE_IN_PROGRESS",
+        operation: active.operation.clone(),
+        progress_percent: active.progress_percent,
+        message,
+    }
+}
+
+/// Toma el bloqueo de mantenimiento. Falla si hay instancias en ejecución o si otro hilo
+/// ya está manteniendo; desde el mismo hilo se puede anidar.
+pub fn begin_maintenance(operation: &str) -> Result<MaintenanceGuard, String> {
+    if maintenance_state()
+        .as_ref()
+        .is_some_and(|active| active.owner == thread::current().id())
+    {
+        enter(&mut maintenance_state(), operation)?;
+        return Ok(MaintenanceGuard { _private: () });
+    }
+    with_no_running_instances(
+        "Hay instancias en ejecución. Cierra los juegos antes de iniciar el mantenimiento.",
+        || enter(&mut maintenance_state(), operation),
+    )??;
+    Ok(MaintenanceGuard { _private: () })
+}
+
+/// Actualiza el progreso del mantenimiento activo, si lo hay.
+pub fn report_maintenance_progress(completed: usize, total: usize) {
+    if let Some(active) = maintenance_state().as_mut() {
+        let percent = completed.saturating_mul(100) / total.max(1);
+        active.progress_percent = Some(percent.min(100) as u8);
+    }
+}
+
+/// Error para rechazar un lanzamiento mientras dura el mantenimiento.
+pub(crate) fn maintenance_in_progress() -> Option<MaintenanceInProgressError> {
+    maintenance_state().as_ref().map(in_progress_error)
+}
+
+#[tauri::command]
+pub fn get_maintenance_status() -> MaintenanceStatus {
+    maintenance_state()
+        .as_ref()
+        .map(|active| MaintenanceStatus {
+            active: true,
+            operation: Some(active.operation.clone()),
+            progress_percent: active.progress_percent,
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nesting_is_per_thread_and_the_outermost_leave_releases() {
+        let state = Mutex::new(None);
+        enter(&mut state.lock().unwrap(), "relocate_launcher_root").expect("outer");
+        enter(&mut state.lock().unwrap(), "migrate_instances_folder").expect("nested");
+
+        let rejected = thread::scope(|scope| {
+            scope
+                .spawn(|| enter(&mut state.lock().unwrap(), "gc"))
+                .join()
+                .expect("join")
+        });
+        assert!(rejected.unwrap_err().contains("relocate_launcher_root"));
+
+        leave(&mut state.lock().unwrap());
+        assert!(state.lock().unwrap().is_some());
+        leave(&mut state.lock().unwrap());
+        assert!(state.lock().unwrap().is_none());
+    }
+
+    #[test]
+    fn launch_rejection_carries_operation_and_progress() {
+        let mut active = ActiveMaintenance {
+            operation: "runtime_reinstall".to_string(),
+            owner: thread::current().id(),
+            depth: 1,
+            progress_percent: None,
+        };
+        assert_eq!(
+            in_progress_error(&active).message,
+            "El launcher está en mantenimiento (runtime_reinstall). Inténtalo cuando termine."
+        );
+        active.progress_percent = Some(40);
+        let err = in_progress_error(&active);
+        assert_eq!(err.code, "MAINTENANCE_IN_PROGRESS");
+        assert_eq!(err.progress_percent, Some(40));
+        assert!(err.message.contains("40%"));
+    }
+}
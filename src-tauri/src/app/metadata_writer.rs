@@ -0,0 +1,329 @@
+// Escritura agrupada de .instance.json durante el lanzamiento.
+
+use std::{
+    collections::HashMap,
+    path::Path,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex, MutexGuard, OnceLock,
+    },
+};
+
+use crate::{
+    app::instance_service::{
+        read_instance_metadata, read_stored_instance_metadata, upgrade_instance_metadata,
+        write_instance_metadata_unlocked,
+    },
+    domain::models::instance::{FilesystemCapabilities, InstanceMetadata},
+    shared::clock::IdGenerator,
+};
+
+/// Runtime de Java embebido que la validación decidió guardar en la metadata.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct JavaPathUpdate {
+    pub java_path: String,
+    pub java_runtime: String,
+    pub java_version: String,
+}
+
+/// Campos pendientes de guardar; un cambio posterior del mismo campo sustituye al anterior.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct PendingMetadata {
+    filesystem: Option<FilesystemCapabilities>,
+    java: Option<JavaPathUpdate>,
+    last_used: Option<String>,
+}
+
+impl PendingMetadata {
+    fn is_empty(&self) -> bool {
+        self.filesystem.is_none() && self.java.is_none() && self.last_used.is_none()
+    }
+
+    fn apply(&self, metadata: &mut InstanceMetadata) {
+        if let Some(filesystem) = &self.filesystem {
+            metadata.filesystem = Some(filesystem.clone());
+        }
+        if let Some(java) = &self.java {
+            metadata.java_path = java.java_path.clone();
+            metadata.java_runtime = java.java_runtime.clone();
+            metadata.java_version = java.java_version.clone();
+        }
+        if let Some(last_used) = &self.last_used {
+            metadata.last_used = Some(last_used.clone());
+        }
+    }
+
+    /// Devuelve a la cola lo que no se pudo guardar sin pisar cambios encolados después.
+    fn restore(&mut self, failed: PendingMetadata) {
+        self.filesystem = self.filesystem.take().or(failed.filesystem);
+        self.java = self.java.take().or(failed.java);
+        self.last_used = self.last_used.take().or(failed.last_used);
+    }
+}
+
+/// Cola de cambios y cerrojo de escritura de la metadata de una instancia. Toda escritura de
+/// `.instance.json` pasa por el cerrojo, también las de los comandos de ajustes.
+#[derive(Debug, Default)]
+pub(crate) struct InstanceMetadataWriter {
+    pending: Mutex<PendingMetadata>,
+    write_lock: Mutex<()>,
+    writes: AtomicUsize,
+}
+
+static METADATA_WRITERS: OnceLock<Mutex<HashMap<String, Arc<InstanceMetadataWriter>>>> =
+    OnceLock::new();
+
+/// Escritor compartido de la instancia.
+pub(crate) fn metadata_writer(instance_root: &str) -> Arc<InstanceMetadataWriter> {
+    let mut writers = METADATA_WRITERS
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    Arc::clone(writers.entry(instance_root.to_string()).or_default())
+}
+
+impl InstanceMetadataWriter {
+    /// Se mantiene mientras se lee, modifica y guarda la metadata.
+    pub fn lock(&self) -> MutexGuard<'_, ()> {
+        self.write_lock
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    pub fn queue_filesystem(&self, filesystem: FilesystemCapabilities) {
+        self.pending().filesystem = Some(filesystem);
+    }
+
+    pub fn queue_java_path(&self, java: JavaPathUpdate) {
+        self.pending().java = Some(java);
+    }
+
+    pub fn queue_last_used(&self, last_used: String) {
+        self.pending().last_used = Some(last_used);
+    }
+
+    /// Guarda la actualización de la metadata antigua y el `internal_uuid` si falta.
+    pub fn persist_upgrade_blocking(
+        &self,
+        instance_root: &str,
+        ids: &dyn IdGenerator,
+    ) -> Result<Vec<&'static str>, String> {
+        let _guard = self.lock();
+        let mut metadata = read_stored_instance_metadata(instance_root)?;
+        let mut upgraded = upgrade_instance_metadata(Path::new(instance_root), &mut metadata);
+        if metadata.internal_uuid.trim().is_empty() {
+            metadata.internal_uuid = ids.new_id();
+            upgraded.push("internalUuid");
+        }
+        if !upgraded.is_empty() {
+            write_instance_metadata_unlocked(instance_root, &metadata)?;
+            self.writes.fetch_add(1, Ordering::SeqCst);
+        }
+        Ok(upgraded)
+    }
+
+    /// Escrituras reales de `.instance.json` hechas por este escritor.
+    #[cfg(test)]
+    pub fn writes(&self) -> usize {
+        self.writes.load(Ordering::SeqCst)
+    }
+
+    /// Vuelca lo pendiente; `true` si escribió.
+    pub fn flush_blocking(&self, instance_root: &str) -> Result<bool, String> {
+        let _guard = self.lock();
+        self.write_pending(instance_root)
+    }
+
+    fn pending(&self) -> std::sync::MutexGuard<'_, PendingMetadata> {
+        self.pending
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Se llama con el cerrojo tomado; relee el documento para no pisar otros cambios.
+    fn write_pending(&self, instance_root: &str) -> Result<bool, String> {
+        let pending = std::mem::take(&mut *self.pending());
+        if pending.is_empty() {
+            return Ok(false);
+        }
+        let written = read_instance_metadata(instance_root.to_string()).and_then(|mut metadata| {
+            pending.apply(&mut metadata);
+            write_instance_metadata_unlocked(instance_root, &metadata)
+        });
+        match written {
+            Ok(()) => {
+                self.writes.fetch_add(1, Ordering::SeqCst);
+                Ok(true)
+            }
+            Err(err) => {
+                self.pending().restore(pending);
+                Err(err)
+            }
+        }
+    }
+}
+
+/// Vuelca lo pendiente de la instancia; un fallo solo deja un aviso.
+pub(crate) fn flush_metadata_blocking(instance_root: &str, point: &str) {
+    if let Err(err) = metadata_writer(instance_root).flush_blocking(instance_root) {
+        log::warn!("⚠ No se pudo guardar la metadata de {instance_root} ({point}): {err}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::instance_service::update_instance_metadata;
+    use crate::shared::test_support::test_temp_dir;
+    use std::{fs, sync::Barrier, thread};
+
+    type Trigger = Box<dyn FnOnce(&InstanceMetadataWriter) + Send>;
+
+    fn write_fixture(dir: &Path) {
+        let metadata = serde_json::json!({
+            "name": "Survival",
+            "group": "Sin grupo",
+            "minecraftVersion": "1.20.1",
+            "versionId": "1.20.1",
+            "loader": "vanilla",
+            "loaderVersion": "",
+            "ramMb": 4096,
+            "javaArgs": [],
+            "javaPath": "/old/bin/java",
+            "javaRuntime": "java17",
+            "javaVersion": "17.0.x",
+            "requiredJavaMajor": 17,
+            "lastUsed": null,
+            "internalUuid": "uuid",
+            "createdAt": "2026-01-01T00:00:00Z",
+            "state": "READY",
+        });
+        fs::write(
+            dir.join(".instance.json"),
+            serde_json::to_string_pretty(&metadata).expect("json"),
+        )
+        .expect("metadata");
+    }
+
+    fn capabilities() -> FilesystemCapabilities {
+        FilesystemCapabilities {
+            filesystem: "ext4".to_string(),
+            drive_kind: "local".to_string(),
+            is_cloud_synced: false,
+            cloud_provider: None,
+            supports_atomic_rename: true,
+            supports_exec_bit: true,
+            probed_at: "2026-01-01T00:00:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn concurrent_launch_triggers_coalesce_into_a_single_write() {
+        let dir = test_temp_dir("metadata-writer-coalesce");
+        write_fixture(&dir);
+        let root = dir.display().to_string();
+        let barrier = Arc::new(Barrier::new(3));
+
+        let triggers: Vec<Trigger> = vec![
+            Box::new(|writer| writer.queue_filesystem(capabilities())),
+            Box::new(|writer| {
+                writer.queue_java_path(JavaPathUpdate {
+                    java_path: "/runtime/java21/bin/java".to_string(),
+                    java_runtime: "java21".to_string(),
+                    java_version: "21.0.x".to_string(),
+                })
+            }),
+            Box::new(|writer| writer.queue_last_used("2026-10-17T12:00:00Z".to_string())),
+        ];
+        let handles = triggers
+            .into_iter()
+            .map(|trigger| {
+                let root = root.clone();
+                let barrier = Arc::clone(&barrier);
+                thread::spawn(move || {
+                    let writer = metadata_writer(&root);
+                    trigger(&writer);
+                    barrier.wait();
+                    writer.flush_blocking(&root).expect("flush")
+                })
+            })
+            .collect::<Vec<_>>();
+        let wrote = handles
+            .into_iter()
+            .map(|handle| handle.join().expect("thread"))
+            .filter(|wrote| *wrote)
+            .count();
+
+        assert_eq!(wrote, 1);
+        assert_eq!(metadata_writer(&root).writes(), 1);
+        assert!(!dir.join(".instance.json.tmp").exists());
+
+        let saved = read_instance_metadata(root.clone()).expect("metadata");
+        assert_eq!(saved.filesystem, Some(capabilities()));
+        assert_eq!(saved.java_path, "/runtime/java21/bin/java");
+        assert_eq!(saved.java_runtime, "java21");
+        assert_eq!(saved.java_version, "21.0.x");
+        assert_eq!(saved.last_used.as_deref(), Some("2026-10-17T12:00:00Z"));
+        assert_eq!(saved.name, "Survival");
+
+        assert!(!metadata_writer(&root).flush_blocking(&root).expect("flush"));
+        assert_eq!(metadata_writer(&root).writes(), 1);
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn failed_flush_keeps_changes_queued() {
+        let dir = test_temp_dir("metadata-writer-failed");
+        let root = dir.display().to_string();
+        let writer = metadata_writer(&root);
+        writer.queue_last_used("2026-10-17T12:00:00Z".to_string());
+
+        assert!(writer.flush_blocking(&root).is_err());
+        assert_eq!(writer.writes(), 0);
+
+        write_fixture(&dir);
+        assert!(writer.flush_blocking(&root).expect("flush"));
+        let saved = read_instance_metadata(root).expect("metadata");
+        assert_eq!(saved.last_used.as_deref(), Some("2026-10-17T12:00:00Z"));
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn settings_updates_and_launch_flushes_do_not_overwrite_each_other() {
+        let dir = test_temp_dir("metadata-writer-updates");
+        write_fixture(&dir);
+        let root = dir.display().to_string();
+
+        let handles = (0..8)
+            .map(|index| {
+                let root = root.clone();
+                thread::spawn(move || {
+                    if index % 2 == 0 {
+                        update_instance_metadata(&root, |metadata| {
+                            metadata.tags.push(format!("tag-{index}"));
+                            Ok::<(), String>(())
+                        })
+                        .expect("update");
+                    } else {
+                        let writer = metadata_writer(&root);
+                        writer.queue_last_used(format!("2026-10-17T12:00:0{index}Z"));
+                        writer.flush_blocking(&root).expect("flush");
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        for handle in handles {
+            handle.join().expect("thread");
+        }
+
+        let saved = read_instance_metadata(root).expect("metadata");
+        let mut tags = saved.tags.clone();
+        tags.sort();
+        assert_eq!(tags, vec!["tag-0", "tag-2", "tag-4", "tag-6"]);
+        assert!(saved.last_used.is_some());
+
+        let _ = fs::remove_dir_all(dir);
+    }
+}
@@ -1,9 +1,66 @@
 pub mod auth_service;
+pub mod batch_operations;
+pub mod console_filters;
+pub mod creation_downloads;
+pub mod crash_index;
+pub mod event_journal;
+pub mod game_dir_guard;
+pub mod hook_approval;
+pub mod image_cache;
+pub mod instance_backup;
+pub mod instance_cleanup;
+pub mod instance_dedup;
+pub mod instance_locks;
+pub mod instance_reset;
+pub mod instance_search;
 pub mod instance_service;
+pub mod instance_shutdown;
+pub mod instance_tags;
+pub mod instance_templates;
+pub mod instance_upgrade;
+pub mod instance_uuid;
+pub mod instances_watcher;
 pub mod java_service;
+pub mod jvm_memory;
+pub mod launch_changes;
+pub mod launch_lock;
+pub mod launch_prewarm;
+pub mod launch_watchdog;
+pub mod launcher_problems;
 pub mod launcher_service;
+pub mod launcher_snapshot;
+pub mod launcher_update;
+pub mod library_provenance;
+pub mod maintenance;
+pub mod metadata_writer;
+pub mod local_api;
+pub mod mod_duplicates;
+pub mod mod_list_install;
+pub mod news_feed;
+pub mod op_journal;
+pub mod orphan_adoption;
+pub mod pack_update;
+pub mod performance_report;
+pub mod playtime;
+pub mod power_events;
+pub mod quarantine;
 pub mod redirect_launch;
+pub mod runtime_metrics;
+pub mod runtime_output;
+pub mod saves_sync;
+pub mod screenshots;
+pub mod service_status;
+pub mod version_inspect;
 pub mod version_service;
+pub mod webhooks;
+pub mod window_tweaks;
 
+pub mod settings_profiles;
 pub mod settings_service;
 pub mod shortcut_instance;
+pub mod source_instance_settings;
+pub mod startup_profile;
+pub mod strict_mode;
+pub mod tls_diagnostics;
+pub mod token_maintenance;
+pub mod trusted_root;
@@ -0,0 +1,497 @@
+// Mods duplicados por id.
+
+use std::{cmp::Ordering, collections::BTreeMap, fs, io::Read, path::Path, time::UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+use zip::ZipArchive;
+
+use crate::app::{
+    instance_dedup::note_link_renamed,
+    instance_locks::{ensure_unlocked, InstanceEditError},
+    instance_service::{effective_mods_dir, update_instance_metadata},
+    trusted_root::resolve_trusted_instance_root,
+};
+
+/// Id, versión y nombre visible declarados en el descriptor del jar.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ModDescriptor {
+    pub(crate) mod_id: String,
+    pub(crate) version: Option<String>,
+    pub(crate) name: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateModFile {
+    pub file_name: String,
+    pub version: Option<String>,
+    pub modified_at: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateModGroup {
+    pub mod_id: String,
+    pub files: Vec<DuplicateModFile>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DuplicateStrategy {
+    /// La versión más alta (semver; si no se puede comparar, el archivo más reciente).
+    KeepNewestVersion,
+    /// El archivo modificado más recientemente.
+    KeepNewestFile,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateModAction {
+    pub mod_id: String,
+    pub kept: String,
+    /// Archivos renombrados a `.disabled`.
+    pub disabled: Vec<String>,
+}
+
+fn zip_entry_text(archive: &mut ZipArchive<fs::File>, name: &str) -> Option<String> {
+    let mut entry = archive.by_name(name).ok()?;
+    let mut text = String::new();
+    entry.read_to_string(&mut text).ok()?;
+    Some(text)
+}
+
+/// `modId`, `version` y `displayName` de la primera sección `[[mods]]` de un `mods.toml`.
+fn parse_mods_toml(text: &str) -> Option<ModDescriptor> {
+    let mut in_mods = false;
+    let mut mod_id = None;
+    let mut version = None;
+    let mut name = None;
+    for line in text.lines().map(str::trim) {
+        if line.starts_with('[') {
+            if in_mods {
+                break;
+            }
+            in_mods = line == "[[mods]]";
+            continue;
+        }
+        if !in_mods {
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let value = value
+            .split('#')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .trim_matches(|ch| ch == '"' || ch == '\'')
+            .to_string();
+        match key.trim() {
+            "modId" => mod_id = Some(value),
+            "version" => version = Some(value),
+            "displayName" => name = Some(value),
+            _ => {}
+        }
+    }
+    Some(ModDescriptor {
+        mod_id: mod_id.filter(|id| !id.is_empty())?,
+        version,
+        name: name.filter(|name| !name.is_empty()),
+    })
+}
+
+/// `fabric.mod.json` o `quilt.mod.json` (bajo `quilt_loader`, con el nombre en `metadata`).
+fn parse_mod_json(text: &str, quilt: bool) -> Option<ModDescriptor> {
+    let value: serde_json::Value = serde_json::from_str(text).ok()?;
+    let root = if quilt {
+        value.get("quilt_loader")?
+    } else {
+        &value
+    };
+    Some(ModDescriptor {
+        mod_id: root
+            .get("id")?
+            .as_str()
+            .filter(|id| !id.is_empty())?
+            .to_string(),
+        version: root
+            .get("version")
+            .and_then(|v| v.as_str())
+            .map(str::to_string),
+        name: if quilt {
+            root.get("metadata")
+        } else {
+            Some(root)
+        }
+        .and_then(|meta| meta.get("name"))
+        .and_then(|v| v.as_str())
+        .filter(|name| !name.is_empty())
+        .map(str::to_string),
+    })
+}
+
+fn manifest_version(archive: &mut ZipArchive<fs::File>) -> Option<String> {
+    zip_entry_text(archive, "META-INF/MANIFEST.MF")?
+        .lines()
+        .find_map(|line| line.strip_prefix("Implementation-Version:"))
+        .map(|version| version.trim().to_string())
+}
+
+/// Versión del nombre del archivo (`sodium-0.5.8.jar` → `0.5.8`).
+fn file_name_version(file_name: &str) -> Option<String> {
+    let base = file_name.trim_end_matches(".jar");
+    let (_, version) = base.rsplit_once('-')?;
+    version
+        .starts_with(|ch: char| ch.is_ascii_digit() || ch == 'v')
+        .then(|| version.to_string())
+}
+
+pub(crate) fn read_mod_descriptor(jar: &Path) -> Option<ModDescriptor> {
+    let mut archive = ZipArchive::new(fs::File::open(jar).ok()?).ok()?;
+    let mut descriptor = if let Some(text) = zip_entry_text(&mut archive, "quilt.mod.json") {
+        parse_mod_json(&text, true)
+    } else if let Some(text) = zip_entry_text(&mut archive, "fabric.mod.json") {
+        parse_mod_json(&text, false)
+    } else {
+        zip_entry_text(&mut archive, "META-INF/neoforge.mods.toml")
+            .or_else(|| zip_entry_text(&mut archive, "META-INF/mods.toml"))
+            .and_then(|text| parse_mods_toml(&text))
+    }?;
+    // Forge rellena `${file.jarVersion}` desde el manifiesto al cargar.
+    if is_missing_version(descriptor.version.as_deref()) {
+        descriptor.version = manifest_version(&mut archive).or_else(|| {
+            jar.file_name()
+                .and_then(|name| name.to_str())
+                .and_then(file_name_version)
+        });
+    }
+    Some(descriptor)
+}
+
+/// Sin versión o con un marcador sin sustituir (`${file.jarVersion}`).
+fn is_missing_version(version: Option<&str>) -> bool {
+    !version.is_some_and(|version| !version.is_empty() && !version.starts_with("${"))
+}
+
+/// Partes semver de una versión (`v0.5.11+mc1.20.1` → `[0, 5, 11]`, sin prerelease);
+/// `None` si el núcleo no es numérico.
+fn semver_parts(version: &str) -> Option<(Vec<u64>, Option<&str>)> {
+    let version = version.trim().trim_start_matches('v');
+    let version = version.split('+').next().unwrap_or_default();
+    let (core, prerelease) = match version.split_once('-') {
+        Some((core, prerelease)) => (core, Some(prerelease)),
+        None => (version, None),
+    };
+    let numbers = core
+        .split('.')
+        .map(|part| part.parse::<u64>().ok())
+        .collect::<Option<Vec<_>>>()?;
+    Some((numbers, prerelease))
+}
+
+/// Identificadores de prerelease: los numéricos se comparan como números.
+fn compare_prerelease(left: &str, right: &str) -> Ordering {
+    let mut left_parts = left.split('.');
+    let mut right_parts = right.split('.');
+    loop {
+        match (left_parts.next(), right_parts.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(l), Some(r)) => {
+                let ordering = match (l.parse::<u64>(), r.parse::<u64>()) {
+                    (Ok(l), Ok(r)) => l.cmp(&r),
+                    (Ok(_), Err(_)) => Ordering::Less,
+                    (Err(_), Ok(_)) => Ordering::Greater,
+                    (Err(_), Err(_)) => l.cmp(r),
+                };
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+        }
+    }
+}
+
+/// Compara dos versiones semver; `None` si alguna no lo es.
+pub(crate) fn compare_mod_versions(left: &str, right: &str) -> Option<Ordering> {
+    let (mut left_numbers, left_pre) = semver_parts(left)?;
+    let (mut right_numbers, right_pre) = semver_parts(right)?;
+    let width = left_numbers.len().max(right_numbers.len());
+    left_numbers.resize(width, 0);
+    right_numbers.resize(width, 0);
+    Some(
+        left_numbers
+            .cmp(&right_numbers)
+            .then_with(|| match (left_pre, right_pre) {
+                (None, None) => Ordering::Equal,
+                (None, Some(_)) => Ordering::Greater,
+                (Some(_), None) => Ordering::Less,
+                (Some(l), Some(r)) => compare_prerelease(l, r),
+            }),
+    )
+}
+
+fn compare_for_strategy(
+    left: &DuplicateModFile,
+    right: &DuplicateModFile,
+    strategy: DuplicateStrategy,
+) -> Ordering {
+    let by_version = match (strategy, left.version.as_deref(), right.version.as_deref()) {
+        (DuplicateStrategy::KeepNewestVersion, Some(l), Some(r)) => {
+            compare_mod_versions(l, r).unwrap_or(Ordering::Equal)
+        }
+        _ => Ordering::Equal,
+    };
+    by_version
+        .then_with(|| left.modified_at.cmp(&right.modified_at))
+        .then_with(|| left.file_name.cmp(&right.file_name))
+}
+
+/// Índice del archivo que se conserva en un grupo.
+fn pick_winner(group: &DuplicateModGroup, strategy: DuplicateStrategy) -> usize {
+    (0..group.files.len())
+        .max_by(|&l, &r| compare_for_strategy(&group.files[l], &group.files[r], strategy))
+        .unwrap_or_default()
+}
+
+/// Grupos de jars activos de `mods_dir` que declaran el mismo id.
+pub fn find_duplicate_groups(mods_dir: &Path) -> Vec<DuplicateModGroup> {
+    let mut by_id: BTreeMap<String, Vec<DuplicateModFile>> = BTreeMap::new();
+    for entry in fs::read_dir(mods_dir).into_iter().flatten().flatten() {
+        let path = entry.path();
+        let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        if !file_name.to_ascii_lowercase().ends_with(".jar") || !path.is_file() {
+            continue;
+        }
+        let Some(descriptor) = read_mod_descriptor(&path) else {
+            continue;
+        };
+        let modified_at = entry
+            .metadata()
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+            .map(|duration| duration.as_secs());
+        by_id
+            .entry(descriptor.mod_id.to_ascii_lowercase())
+            .or_default()
+            .push(DuplicateModFile {
+                file_name: file_name.to_string(),
+                version: descriptor.version,
+                modified_at,
+            });
+    }
+    by_id
+        .into_iter()
+        .filter(|(_, files)| files.len() > 1)
+        .map(|(mod_id, mut files)| {
+            files.sort_by(|a, b| a.file_name.cmp(&b.file_name));
+            DuplicateModGroup { mod_id, files }
+        })
+        .collect()
+}
+
+/// Resumen de una línea por grupo para logs y avisos.
+pub fn describe_duplicate_groups(groups: &[DuplicateModGroup]) -> Vec<String> {
+    groups
+        .iter()
+        .map(|group| {
+            let files = group
+                .files
+                .iter()
+                .map(|file| file.file_name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!("mod '{}' duplicado: {files}", group.mod_id)
+        })
+        .collect()
+}
+
+/// Desactiva (renombra a `.disabled`) todos los archivos de cada grupo salvo el elegido.
+fn resolve_duplicates_in(
+    instance_root: &Path,
+    mods_dir: &Path,
+    strategy: DuplicateStrategy,
+) -> Result<Vec<DuplicateModAction>, String> {
+    let mut actions = Vec::new();
+    for group in find_duplicate_groups(mods_dir) {
+        let winner = pick_winner(&group, strategy);
+        let mut disabled = Vec::new();
+        for (index, file) in group.files.iter().enumerate() {
+            if index == winner {
+                continue;
+            }
+            let source = mods_dir.join(&file.file_name);
+            let target = mods_dir.join(format!("{}.disabled", file.file_name));
+            fs::rename(&source, &target).map_err(|err| {
+                format!(
+                    "No se pudo desactivar el mod duplicado {}: {err}",
+                    file.file_name
+                )
+            })?;
+            note_link_renamed(instance_root, &source, &target);
+            disabled.push(file.file_name.clone());
+        }
+        log::info!(
+            "✔ Mod '{}': se conserva {} y se desactivan {}",
+            group.mod_id,
+            group.files[winner].file_name,
+            disabled.join(", ")
+        );
+        actions.push(DuplicateModAction {
+            mod_id: group.mod_id,
+            kept: group.files[winner].file_name.clone(),
+            disabled,
+        });
+    }
+    Ok(actions)
+}
+
+#[tauri::command]
+pub fn find_duplicate_mods(
+    app: AppHandle,
+    instance_root: String,
+) -> Result<Vec<DuplicateModGroup>, String> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    Ok(find_duplicate_groups(&effective_mods_dir(
+        instance_root.path(),
+    )))
+}
+
+#[tauri::command]
+pub fn resolve_duplicate_mods(
+    app: AppHandle,
+    instance_root: String,
+    strategy: DuplicateStrategy,
+    override_lock: Option<bool>,
+) -> Result<Vec<DuplicateModAction>, InstanceEditError> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    ensure_unlocked(
+        instance_root.as_str(),
+        "mods",
+        "resolve_duplicate_mods",
+        override_lock.unwrap_or(false),
+    )?;
+    let mods_dir = effective_mods_dir(instance_root.path());
+    Ok(resolve_duplicates_in(
+        instance_root.path(),
+        &mods_dir,
+        strategy,
+    )?)
+}
+
+/// Activa o desactiva la comprobación de duplicados antes de lanzar (activa por defecto).
+#[tauri::command]
+pub fn set_instance_duplicate_mod_check(
+    app: AppHandle,
+    instance_root: String,
+    enabled: bool,
+) -> Result<bool, String> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    update_instance_metadata(instance_root.as_str(), |metadata| {
+        metadata.skip_duplicate_mod_check = !enabled;
+        Ok::<(), String>(())
+    })?;
+    Ok(enabled)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+
+    #[test]
+    fn parses_descriptors_and_compares_versions() {
+        let toml = "modLoader=\"javafml\"\n[[mods]] \nmodId=\"create\" # comentario\nversion=\"${file.jarVersion}\"\n[[dependencies.create]]\nmodId=\"forge\"\n";
+        assert_eq!(
+            parse_mods_toml(toml),
+            Some(ModDescriptor {
+                mod_id: "create".to_string(),
+                version: Some("${file.jarVersion}".to_string()),
+                name: None,
+            })
+        );
+        assert!(is_missing_version(Some("${file.jarVersion}")));
+        let quilt = r#"{"quilt_loader": {"id": "qsl", "version": "6.1.2"}}"#;
+        assert_eq!(parse_mod_json(quilt, true).unwrap().mod_id, "qsl");
+        assert_eq!(
+            file_name_version("sodium-fabric-0.5.8.jar").as_deref(),
+            Some("0.5.8")
+        );
+
+        assert_eq!(
+            compare_mod_versions("0.5.11+mc1.20.1", "0.5.8+mc1.20.1"),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(
+            compare_mod_versions("1.0.0-beta.2", "1.0.0-beta.10"),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            compare_mod_versions("1.0.0", "1.0.0-rc.1"),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(compare_mod_versions("v2.1", "2.1.0"), Some(Ordering::Equal));
+        // Versiones no semver: no se comparan y se decide por fecha.
+        assert_eq!(compare_mod_versions("mc1.20.1-0.5.8", "0.5.11"), None);
+        assert_eq!(compare_mod_versions("build 42", "build 43"), None);
+    }
+
+    fn write_fabric_jar(path: &Path, id: &str, version: &str) {
+        let mut zip = zip::ZipWriter::new(fs::File::create(path).expect("jar"));
+        zip.start_file("fabric.mod.json", zip::write::SimpleFileOptions::default())
+            .expect("entrada");
+        zip.write_all(format!(r#"{{"id":"{id}","version":"{version}"}}"#).as_bytes())
+            .expect("descriptor");
+        zip.finish().expect("cerrar jar");
+    }
+
+    #[test]
+    fn disables_older_duplicates_and_falls_back_to_mtime() {
+        let root = std::env::temp_dir().join(format!("interface-dupmods-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let mods_dir = root.join("minecraft/mods");
+        fs::create_dir_all(&mods_dir).expect("mods");
+        write_fabric_jar(&mods_dir.join("sodium-0.5.11.jar"), "sodium", "0.5.11");
+        write_fabric_jar(&mods_dir.join("sodium-0.5.8.jar"), "sodium", "0.5.8");
+        write_fabric_jar(&mods_dir.join("lithium.jar"), "lithium", "0.11.2");
+        fs::write(mods_dir.join("old-sodium.jar.disabled"), b"x").expect("desactivado");
+
+        let groups = find_duplicate_groups(&mods_dir);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].mod_id, "sodium");
+
+        let actions = resolve_duplicates_in(&root, &mods_dir, DuplicateStrategy::KeepNewestVersion)
+            .expect("resolver");
+        assert_eq!(actions[0].kept, "sodium-0.5.11.jar");
+        assert_eq!(actions[0].disabled, vec!["sodium-0.5.8.jar".to_string()]);
+        assert!(mods_dir.join("sodium-0.5.8.jar.disabled").exists());
+        assert!(find_duplicate_groups(&mods_dir).is_empty());
+
+        let group = DuplicateModGroup {
+            mod_id: "custom".to_string(),
+            files: vec![
+                DuplicateModFile {
+                    file_name: "custom-a.jar".to_string(),
+                    version: Some("build-7".to_string()),
+                    modified_at: Some(200),
+                },
+                DuplicateModFile {
+                    file_name: "custom-b.jar".to_string(),
+                    version: Some("build-9".to_string()),
+                    modified_at: Some(100),
+                },
+            ],
+        };
+        assert_eq!(pick_winner(&group, DuplicateStrategy::KeepNewestVersion), 0);
+        assert_eq!(pick_winner(&group, DuplicateStrategy::KeepNewestFile), 0);
+
+        let _ = fs::remove_dir_all(root);
+    }
+}
@@ -0,0 +1,620 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::Path,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, OnceLock,
+    },
+};
+
+use reqwest::{blocking::Client, StatusCode};
+use serde::Serialize;
+use serde_json::Value;
+use tauri::{AppHandle, Emitter};
+
+use crate::{
+    app::{
+        instance_locks::{ensure_unlocked, InstanceEditError},
+        instance_service::{effective_mods_dir, read_instance_metadata},
+        instance_upgrade::{
+            build_upgrade_client, download_mod_file, list_enabled_mod_jars, modrinth_loader_names,
+            primary_modrinth_file,
+        },
+        trusted_root::{resolve_trusted_instance_root, ValidatedInstanceRoot},
+    },
+    infrastructure::{checksum::sha1::compute_file_sha1, http::rate_limit},
+};
+
+const MODRINTH_API_URL: &str = "https://api.modrinth.com/v2";
+
+static CANCEL_MOD_LIST_INSTALLS: OnceLock<Mutex<HashMap<String, Arc<AtomicBool>>>> =
+    OnceLock::new();
+
+fn cancel_flags() -> &'static Mutex<HashMap<String, Arc<AtomicBool>>> {
+    CANCEL_MOD_LIST_INSTALLS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Entrada de una lista de mods ya interpretada.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModListEntry {
+    /// Slug o ID de proyecto: se instala la versión compatible más reciente.
+    Project(String),
+    /// Versión concreta (`/mod/<slug>/version/<versión>` o `/version/<id>`).
+    Version {
+        project: Option<String>,
+        version: String,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ModListInstallResult {
+    pub entry: String,
+    /// `installed`, `skipped_present`, `no_compatible_version`, `not_found`, `failed` o
+    /// `cancelled`.
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_name: Option<String>,
+    /// Entrada de la lista que arrastró esta dependencia requerida.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dependency_of: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl ModListInstallResult {
+    fn new(entry: &str, status: &str) -> Self {
+        Self {
+            entry: entry.to_string(),
+            status: status.to_string(),
+            project_id: None,
+            version: None,
+            file_name: None,
+            dependency_of: None,
+            error: None,
+        }
+    }
+}
+
+/// Interpreta una entrada: URL de modrinth.com o de la API, slug o ID de proyecto.
+pub fn parse_mod_list_entry(raw: &str) -> Option<ModListEntry> {
+    let entry = raw.split('#').next().unwrap_or_default().trim();
+    if entry.is_empty() {
+        return None;
+    }
+    let Some(rest) = entry
+        .strip_prefix("https://")
+        .or_else(|| entry.strip_prefix("http://"))
+    else {
+        return Some(ModListEntry::Project(entry.to_string()));
+    };
+    let segments = rest
+        .split(['?', '#'])
+        .next()
+        .unwrap_or_default()
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>();
+    match segments.as_slice() {
+        [_, "v2", "version", version, ..] | [_, "version", version, ..] => {
+            Some(ModListEntry::Version {
+                project: None,
+                version: version.to_string(),
+            })
+        }
+        [_, _, project, "version", version, ..] => Some(ModListEntry::Version {
+            project: Some(project.to_string()),
+            version: version.to_string(),
+        }),
+        [_, "v2", "project", project, ..] | [_, _, project, ..] => {
+            Some(ModListEntry::Project(project.to_string()))
+        }
+        _ => None,
+    }
+}
+
+/// Lista de texto plano: una entrada por línea, `#` inicia un comentario.
+pub fn parse_mod_list_text(text: &str) -> Vec<String> {
+    text.lines()
+        .map(|line| line.split('#').next().unwrap_or_default().trim())
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn version_is_compatible(version: &Value, loaders: &[String], minecraft_version: &str) -> bool {
+    let contains = |key: &str, wanted: &dyn Fn(&str) -> bool| {
+        version
+            .get(key)
+            .and_then(Value::as_array)
+            .is_some_and(|values| values.iter().filter_map(Value::as_str).any(wanted))
+    };
+    contains("loaders", &|loader| {
+        loaders
+            .iter()
+            .any(|wanted| wanted.eq_ignore_ascii_case(loader))
+    }) && contains("game_versions", &|game_version| {
+        game_version == minecraft_version
+    })
+}
+
+/// Versión compatible publicada más recientemente.
+pub fn newest_compatible_version<'a>(
+    versions: &'a [Value],
+    loaders: &[String],
+    minecraft_version: &str,
+) -> Option<&'a Value> {
+    versions
+        .iter()
+        .filter(|version| version_is_compatible(version, loaders, minecraft_version))
+        .max_by(|a, b| {
+            let published = |version: &Value| {
+                version
+                    .get("date_published")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+                    .to_string()
+            };
+            published(a).cmp(&published(b))
+        })
+}
+
+/// Dependencias requeridas de una versión: `(project_id, version_id)`.
+pub fn required_dependencies(version: &Value) -> Vec<(Option<String>, Option<String>)> {
+    version
+        .get("dependencies")
+        .and_then(Value::as_array)
+        .map(|dependencies| {
+            dependencies
+                .iter()
+                .filter(|dependency| {
+                    dependency.get("dependency_type").and_then(Value::as_str) == Some("required")
+                })
+                .map(|dependency| {
+                    let field = |key: &str| {
+                        dependency
+                            .get(key)
+                            .and_then(Value::as_str)
+                            .map(str::to_string)
+                    };
+                    (field("project_id"), field("version_id"))
+                })
+                .filter(|(project, version)| project.is_some() || version.is_some())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// GET a la API de Modrinth; `Ok(None)` si el recurso no existe.
+fn modrinth_get(
+    client: &Client,
+    url: &str,
+    query: &[(&str, String)],
+) -> Result<Option<Value>, String> {
+    let response = rate_limit::send_blocking(url, || client.get(url).query(query))
+        .map_err(|err| err.describe(&format!("No se pudo consultar Modrinth ({url})")))?;
+    if response.status() == StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    response
+        .error_for_status()
+        .and_then(|res| res.json())
+        .map(Some)
+        .map_err(|err| format!("Respuesta inválida de Modrinth ({url}): {err}"))
+}
+
+enum Resolution {
+    Found(Value),
+    NotFound,
+    NoCompatibleVersion,
+}
+
+struct ModListContext<'a> {
+    client: Client,
+    loaders: Vec<String>,
+    minecraft_version: String,
+    mods_dir: &'a Path,
+    installed_hashes: HashSet<String>,
+    seen_projects: HashSet<String>,
+}
+
+impl ModListContext<'_> {
+    fn resolve_project(&self, project: &str) -> Result<Resolution, String> {
+        let query = [
+            (
+                "loaders",
+                serde_json::to_string(&self.loaders).unwrap_or_default(),
+            ),
+            (
+                "game_versions",
+                serde_json::to_string(&[&self.minecraft_version]).unwrap_or_default(),
+            ),
+        ];
+        let Some(versions) = modrinth_get(
+            &self.client,
+            &format!("{MODRINTH_API_URL}/project/{project}/version"),
+            &query,
+        )?
+        else {
+            return Ok(Resolution::NotFound);
+        };
+        let versions = versions.as_array().cloned().unwrap_or_default();
+        Ok(
+            match newest_compatible_version(&versions, &self.loaders, &self.minecraft_version) {
+                Some(version) => Resolution::Found(version.clone()),
+                None => Resolution::NoCompatibleVersion,
+            },
+        )
+    }
+
+    fn resolve_version(&self, project: Option<&str>, version: &str) -> Result<Resolution, String> {
+        let url = match project {
+            Some(project) => format!("{MODRINTH_API_URL}/project/{project}/version/{version}"),
+            None => format!("{MODRINTH_API_URL}/version/{version}"),
+        };
+        Ok(match modrinth_get(&self.client, &url, &[])? {
+            None => Resolution::NotFound,
+            Some(version)
+                if version_is_compatible(&version, &self.loaders, &self.minecraft_version) =>
+            {
+                Resolution::Found(version)
+            }
+            Some(_) => Resolution::NoCompatibleVersion,
+        })
+    }
+
+    /// Descarga la versión resuelta salvo que ya esté instalada (mismo SHA1).
+    fn install_version(&mut self, entry: &str, version: &Value) -> ModListInstallResult {
+        let project_id = version
+            .get("project_id")
+            .and_then(Value::as_str)
+            .map(str::to_string);
+        if let Some(project_id) = &project_id {
+            self.seen_projects.insert(project_id.clone());
+        }
+        let mut result = ModListInstallResult::new(entry, "installed");
+        result.project_id = project_id;
+        result.version = version
+            .get("version_number")
+            .and_then(Value::as_str)
+            .map(str::to_string);
+
+        let Some(file) = primary_modrinth_file(version) else {
+            result.status = "no_compatible_version".to_string();
+            return result;
+        };
+        let sha1 = file
+            .get("hashes")
+            .and_then(|hashes| hashes.get("sha1"))
+            .and_then(Value::as_str)
+            .map(str::to_ascii_lowercase);
+        let file_name = file
+            .get("filename")
+            .and_then(Value::as_str)
+            .filter(|name| !name.contains(['/', '\\']) && !name.trim().is_empty());
+        result.file_name = file_name.map(str::to_string);
+
+        if sha1
+            .as_ref()
+            .is_some_and(|sha1| self.installed_hashes.contains(sha1))
+        {
+            result.status = "skipped_present".to_string();
+            return result;
+        }
+        let (Some(url), Some(file_name)) = (file.get("url").and_then(Value::as_str), file_name)
+        else {
+            result.status = "failed".to_string();
+            result.error = Some(format!("{entry}: archivo sin url o nombre válido."));
+            return result;
+        };
+        let downloaded = fs::create_dir_all(self.mods_dir)
+            .map_err(|err| format!("No se pudo crear {}: {err}", self.mods_dir.display()))
+            .and_then(|_| {
+                download_mod_file(
+                    &self.client,
+                    url,
+                    &self.mods_dir.join(file_name),
+                    sha1.as_deref(),
+                )
+            });
+        match downloaded {
+            Ok(()) => {
+                if let Some(sha1) = sha1 {
+                    self.installed_hashes.insert(sha1);
+                }
+            }
+            Err(err) => {
+                result.status = "failed".to_string();
+                result.error = Some(err);
+            }
+        }
+        result
+    }
+
+    fn install_entry(&mut self, entry: &str) -> (ModListInstallResult, Option<Value>) {
+        let resolution = match parse_mod_list_entry(entry) {
+            None => Ok(Resolution::NotFound),
+            Some(ModListEntry::Project(project)) => self.resolve_project(&project),
+            Some(ModListEntry::Version { project, version }) => {
+                self.resolve_version(project.as_deref(), &version)
+            }
+        };
+        match resolution {
+            Ok(Resolution::Found(version)) => {
+                let result = self.install_version(entry, &version);
+                let installed = result.status == "installed" || result.status == "skipped_present";
+                (result, installed.then_some(version))
+            }
+            Ok(Resolution::NotFound) => (ModListInstallResult::new(entry, "not_found"), None),
+            Ok(Resolution::NoCompatibleVersion) => (
+                ModListInstallResult::new(entry, "no_compatible_version"),
+                None,
+            ),
+            Err(err) => {
+                let mut result = ModListInstallResult::new(entry, "failed");
+                result.error = Some(err);
+                (result, None)
+            }
+        }
+    }
+
+    /// Dependencias requeridas de `version`, a un solo nivel. Los proyectos ya vistos
+    /// (de la lista o de otra dependencia) se omiten para no entrar en ciclos.
+    fn install_dependencies(&mut self, parent: &str, version: &Value) -> Vec<ModListInstallResult> {
+        let mut results = Vec::new();
+        for (project_id, version_id) in required_dependencies(version) {
+            if project_id
+                .as_ref()
+                .is_some_and(|project| self.seen_projects.contains(project))
+            {
+                continue;
+            }
+            let label = project_id
+                .clone()
+                .or_else(|| version_id.clone())
+                .unwrap_or_default();
+            let resolution = match (&project_id, &version_id) {
+                (_, Some(version_id)) => self.resolve_version(None, version_id),
+                (Some(project_id), None) => self.resolve_project(project_id),
+                (None, None) => continue,
+            };
+            if let Some(project_id) = &project_id {
+                self.seen_projects.insert(project_id.clone());
+            }
+            let mut result = match resolution {
+                Ok(Resolution::Found(dependency)) => self.install_version(&label, &dependency),
+                Ok(Resolution::NotFound) => ModListInstallResult::new(&label, "not_found"),
+                Ok(Resolution::NoCompatibleVersion) => {
+                    ModListInstallResult::new(&label, "no_compatible_version")
+                }
+                Err(err) => {
+                    let mut result = ModListInstallResult::new(&label, "failed");
+                    result.error = Some(err);
+                    result
+                }
+            };
+            result.dependency_of = Some(parent.to_string());
+            results.push(result);
+        }
+        results
+    }
+}
+
+fn emit_mod_list_progress(
+    app: &AppHandle,
+    instance_root: &str,
+    current: usize,
+    total: usize,
+    result: Option<&ModListInstallResult>,
+) {
+    let _ = app.emit(
+        "mod_list_install_progress",
+        serde_json::json!({
+            "instanceRoot": instance_root,
+            "current": current,
+            "total": total,
+            "result": result,
+        }),
+    );
+}
+
+fn install_mod_list_blocking(
+    app: &AppHandle,
+    root: &ValidatedInstanceRoot,
+    entries: Vec<String>,
+    cancel: &AtomicBool,
+) -> Result<Vec<ModListInstallResult>, String> {
+    let instance_root = root.as_str();
+    let metadata = read_instance_metadata(instance_root.to_string())?;
+    let mods_dir = effective_mods_dir(root.path());
+    let mut installed_hashes = HashSet::new();
+    for jar in list_enabled_mod_jars(&mods_dir)? {
+        if let Ok(sha1) = compute_file_sha1(&jar) {
+            installed_hashes.insert(sha1.to_ascii_lowercase());
+        }
+    }
+    let mut context = ModListContext {
+        client: build_upgrade_client()?,
+        loaders: modrinth_loader_names(&metadata.loader),
+        minecraft_version: metadata.minecraft_version.clone(),
+        mods_dir: &mods_dir,
+        installed_hashes,
+        seen_projects: HashSet::new(),
+    };
+    // Los slugs/IDs de la propia lista cuentan como vistos para no duplicarlos como
+    // dependencia de otra entrada.
+    for entry in &entries {
+        if let Some(ModListEntry::Project(project)) = parse_mod_list_entry(entry) {
+            context.seen_projects.insert(project);
+        }
+    }
+
+    let total = entries.len();
+    let mut results = Vec::with_capacity(total);
+    for (index, entry) in entries.iter().enumerate() {
+        if cancel.load(Ordering::Relaxed) {
+            results.extend(
+                entries[index..]
+                    .iter()
+                    .map(|entry| ModListInstallResult::new(entry, "cancelled")),
+            );
+            log::warn!("⚠ Instalación de lista de mods cancelada en {instance_root}");
+            break;
+        }
+        emit_mod_list_progress(app, instance_root, index, total, None);
+        let (result, version) = context.install_entry(entry);
+        emit_mod_list_progress(app, instance_root, index + 1, total, Some(&result));
+        results.push(result);
+        if let Some(version) = version {
+            for dependency in context.install_dependencies(entry, &version) {
+                emit_mod_list_progress(app, instance_root, index + 1, total, Some(&dependency));
+                results.push(dependency);
+            }
+        }
+    }
+
+    let installed = results
+        .iter()
+        .filter(|result| result.status == "installed")
+        .count();
+    log::info!(
+        "✔ Lista de mods procesada en {instance_root}: {installed} instalados de {} entradas",
+        results.len()
+    );
+    Ok(results)
+}
+
+/// Instala una lista de mods de Modrinth (slugs, IDs o URLs de versión) con la versión
+/// compatible más reciente para el loader y la versión de Minecraft de la instancia.
+#[tauri::command]
+pub async fn install_mods_from_list(
+    app: AppHandle,
+    instance_root: String,
+    entries: Vec<String>,
+    override_lock: Option<bool>,
+) -> Result<Vec<ModListInstallResult>, InstanceEditError> {
+    let root = resolve_trusted_instance_root(&app, &instance_root)?;
+    install_mod_list(app, root, entries, override_lock).await
+}
+
+async fn install_mod_list(
+    app: AppHandle,
+    root: ValidatedInstanceRoot,
+    entries: Vec<String>,
+    override_lock: Option<bool>,
+) -> Result<Vec<ModListInstallResult>, InstanceEditError> {
+    let instance_root = root.to_string();
+    ensure_unlocked(
+        &instance_root,
+        "mods",
+        "install_mods_from_list",
+        override_lock.unwrap_or(false),
+    )?;
+    let cancel = Arc::new(AtomicBool::new(false));
+    cancel_flags()
+        .lock()
+        .map_err(|_| "No se pudo bloquear registro de cancelación".to_string())?
+        .insert(instance_root.clone(), cancel.clone());
+
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        install_mod_list_blocking(&app, &root, entries, &cancel)
+    })
+    .await
+    .map_err(|err| format!("Falló la tarea de instalación de mods: {err}"));
+
+    if let Ok(mut flags) = cancel_flags().lock() {
+        flags.remove(&instance_root);
+    }
+    Ok(result??)
+}
+
+/// Variante de [`install_mods_from_list`] que lee un archivo de texto (una entrada por
+/// línea, `#` para comentarios).
+#[tauri::command]
+pub async fn install_mods_from_list_file(
+    app: AppHandle,
+    instance_root: String,
+    file_path: String,
+    override_lock: Option<bool>,
+) -> Result<Vec<ModListInstallResult>, InstanceEditError> {
+    let root = resolve_trusted_instance_root(&app, &instance_root)?;
+    let text = fs::read_to_string(&file_path)
+        .map_err(|err| format!("No se pudo leer la lista de mods {file_path}: {err}"))?;
+    install_mod_list(app, root, parse_mod_list_text(&text), override_lock).await
+}
+
+#[tauri::command]
+pub fn cancel_mod_list_install(app: AppHandle, instance_root: String) -> Result<(), String> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    if let Ok(flags) = cancel_flags().lock() {
+        if let Some(flag) = flags.get(instance_root.as_str()) {
+            flag.store(true, Ordering::Relaxed);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parses_slugs_urls_and_comments() {
+        assert_eq!(
+            parse_mod_list_entry("sodium"),
+            Some(ModListEntry::Project("sodium".to_string()))
+        );
+        assert_eq!(
+            parse_mod_list_entry("https://modrinth.com/mod/iris/version/1.7.0+1.20.1"),
+            Some(ModListEntry::Version {
+                project: Some("iris".to_string()),
+                version: "1.7.0+1.20.1".to_string()
+            })
+        );
+        assert_eq!(
+            parse_mod_list_entry("https://api.modrinth.com/v2/version/AbCd1234"),
+            Some(ModListEntry::Version {
+                project: None,
+                version: "AbCd1234".to_string()
+            })
+        );
+        assert_eq!(
+            parse_mod_list_entry("https://modrinth.com/mod/lithium"),
+            Some(ModListEntry::Project("lithium".to_string()))
+        );
+        assert_eq!(
+            parse_mod_list_text("# servidor\nsodium\n\n  lithium # rendimiento\n#iris\n"),
+            vec!["sodium".to_string(), "lithium".to_string()]
+        );
+    }
+
+    #[test]
+    fn picks_newest_compatible_version_and_required_dependencies() {
+        let versions = vec![
+            json!({ "id": "old", "loaders": ["fabric"], "game_versions": ["1.20.1"],
+                    "date_published": "2023-06-01T00:00:00Z" }),
+            json!({ "id": "forge", "loaders": ["forge"], "game_versions": ["1.20.1"],
+                    "date_published": "2024-02-01T00:00:00Z" }),
+            json!({ "id": "new", "loaders": ["fabric"], "game_versions": ["1.20.1", "1.20.2"],
+                    "date_published": "2024-01-01T00:00:00Z",
+                    "dependencies": [
+                        { "project_id": "P7dR8mSH", "dependency_type": "required" },
+                        { "project_id": "mOgUt4GM", "dependency_type": "optional" }
+                    ] }),
+        ];
+        let loaders = modrinth_loader_names("quilt");
+        let newest = newest_compatible_version(&versions, &loaders, "1.20.1").expect("versión");
+        assert_eq!(newest["id"], "new");
+        assert!(newest_compatible_version(&versions, &loaders, "1.19.4").is_none());
+        assert_eq!(
+            required_dependencies(newest),
+            vec![(Some("P7dR8mSH".to_string()), None)]
+        );
+    }
+}
@@ -0,0 +1,396 @@
+// Noticias de la pantalla de inicio.
+
+use std::{path::Path, sync::OnceLock};
+
+use chrono::Duration;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::AppHandle;
+
+use crate::{
+    app::image_cache::{configured_cap_bytes, fetch_image_cached, image_cache_root},
+    infrastructure::{
+        cache::cache_manager::{cache_file, load_cached},
+        downloader::client::build_http_client,
+        filesystem::{config::load_launcher_config, paths::resolve_launcher_root},
+        http::rate_limit,
+    },
+    shared::{clock::app_clock, result::AppResult},
+};
+
+/// Releases del launcher (API de GitHub); se puede sustituir con `launcher_news_url`.
+pub const DEFAULT_LAUNCHER_NEWS_URL: &str =
+    "https://api.github.com/repos/ManzanitaSpice/Interface-2/releases";
+/// Noticias del launcher oficial; se puede sustituir con `minecraft_news_url`.
+pub const DEFAULT_MINECRAFT_NEWS_URL: &str = "https://launchercontent.mojang.com/news.json";
+const NEWS_CACHE_DIR: &str = "cache/news";
+const NEWS_MAX_AGE_HOURS: i64 = 6;
+const MAX_ENTRIES: usize = 30;
+const MAX_SUMMARY_CHARS: usize = 400;
+/// Solo se cachean las imágenes de las primeras entradas, las que muestra la portada.
+const MAX_CACHED_IMAGES: usize = 8;
+const MAX_NEWS_IMAGE_BYTES: u64 = 4 * 1024 * 1024;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct NewsEntry {
+    pub id: String,
+    pub title: String,
+    pub date: Option<String>,
+    pub summary: String,
+    pub url: Option<String>,
+    /// Ruta local de la imagen en la caché de imágenes.
+    pub image_url: Option<String>,
+    /// URL original de la imagen.
+    pub remote_image_url: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewsFeed {
+    pub feed: String,
+    pub entries: Vec<NewsEntry>,
+    pub fetched_at: Option<String>,
+    /// La copia en caché caducó y no se pudo refrescar.
+    pub stale: bool,
+    pub disabled: bool,
+}
+
+fn feed_source(app: &AppHandle, feed: &str) -> Result<(bool, String), String> {
+    let config = load_launcher_config(app).unwrap_or_default();
+    let enabled = config.news_enabled.unwrap_or(true);
+    let configured = match feed {
+        "launcher" => config.launcher_news_url,
+        "minecraft" => config.minecraft_news_url,
+        other => return Err(format!("Feed de noticias desconocido: {other}")),
+    };
+    let url = configured
+        .map(|url| url.trim().to_string())
+        .filter(|url| !url.is_empty())
+        .unwrap_or_else(|| {
+            if feed == "launcher" {
+                DEFAULT_LAUNCHER_NEWS_URL.to_string()
+            } else {
+                DEFAULT_MINECRAFT_NEWS_URL.to_string()
+            }
+        });
+    Ok((enabled, url))
+}
+
+fn first_str<'a>(value: &'a Value, keys: &[&str]) -> Option<&'a str> {
+    keys.iter()
+        .filter_map(|key| value.get(*key))
+        .find_map(|field| match field {
+            Value::String(text) if !text.trim().is_empty() => Some(text.trim()),
+            _ => None,
+        })
+}
+
+/// Las imágenes del launcher oficial vienen como rutas relativas al host del feed.
+fn absolute_url(source_url: &str, url: &str) -> Option<String> {
+    if url.starts_with("https://") || url.starts_with("http://") {
+        return Some(url.to_string());
+    }
+    let base = reqwest::Url::parse(source_url).ok()?;
+    base.join(url).ok().map(|joined| joined.to_string())
+}
+
+fn summarize(text: &str) -> String {
+    let plain = html_tags().replace_all(text, " ");
+    let collapsed = plain.split_whitespace().collect::<Vec<_>>().join(" ");
+    if collapsed.chars().count() <= MAX_SUMMARY_CHARS {
+        return collapsed;
+    }
+    let cut: String = collapsed.chars().take(MAX_SUMMARY_CHARS).collect();
+    format!("{}…", cut.trim_end())
+}
+
+fn html_tags() -> &'static Regex {
+    static TAGS: OnceLock<Regex> = OnceLock::new();
+    TAGS.get_or_init(|| Regex::new(r"<[^>]*>").expect("regex de etiquetas"))
+}
+
+fn json_entry(source_url: &str, item: &Value) -> Option<NewsEntry> {
+    let title = first_str(item, &["title", "name", "tag_name"])?.to_string();
+    let url = first_str(item, &["url", "html_url", "readMoreLink", "link"])
+        .and_then(|url| absolute_url(source_url, url));
+    let id = match item.get("id") {
+        Some(Value::String(id)) if !id.trim().is_empty() => id.trim().to_string(),
+        Some(Value::Number(id)) => id.to_string(),
+        _ => first_str(item, &["tag_name"])
+            .map(ToString::to_string)
+            .or_else(|| url.clone())
+            .unwrap_or_else(|| title.clone()),
+    };
+    let image = first_str(item, &["image_url", "imageUrl", "image"]).or_else(|| {
+        ["newsPageImage", "playPageImage", "image"]
+            .iter()
+            .find_map(|key| item.get(*key).and_then(|image| first_str(image, &["url"])))
+    });
+    Some(NewsEntry {
+        id,
+        title,
+        date: first_str(
+            item,
+            &[
+                "date",
+                "published_at",
+                "publishedAt",
+                "created_at",
+                "updated",
+            ],
+        )
+        .map(ToString::to_string),
+        summary: summarize(
+            first_str(item, &["summary", "text", "body", "description"]).unwrap_or(""),
+        ),
+        url,
+        image_url: None,
+        remote_image_url: image.and_then(|image| absolute_url(source_url, image)),
+    })
+}
+
+fn atom_tag<'a>(entry: &'a str, tags: &[&str]) -> Option<&'a str> {
+    tags.iter().find_map(|tag| {
+        let start = entry.find(&format!("<{tag}"))?;
+        let open_end = start + entry[start..].find('>')?;
+        if entry[..=open_end].ends_with("/>") {
+            return None;
+        }
+        let close = open_end + entry[open_end..].find(&format!("</{tag}>"))?;
+        let text = entry[open_end + 1..close].trim();
+        let text = text
+            .strip_prefix("<![CDATA[")
+            .and_then(|inner| inner.strip_suffix("]]>"))
+            .unwrap_or(text);
+        (!text.is_empty()).then_some(text)
+    })
+}
+
+fn xml_unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Subconjunto tolerante de Atom: `<entry>` con `id`, `title`, `updated`/`published`,
+/// `summary`/`content` y el primer `<link href>` (preferentemente `alternate`).
+fn atom_entries(source_url: &str, xml: &str) -> Vec<NewsEntry> {
+    static LINK: OnceLock<Regex> = OnceLock::new();
+    let link = LINK.get_or_init(|| {
+        Regex::new(r#"<link\b([^>]*)href\s*=\s*["']([^"']+)["']([^>]*)>"#).expect("regex de link")
+    });
+    xml.split("<entry")
+        .skip(1)
+        .filter_map(|chunk| {
+            let entry = chunk.split("</entry>").next()?;
+            let title = xml_unescape(atom_tag(entry, &["title"])?);
+            let links: Vec<(String, bool)> = link
+                .captures_iter(entry)
+                .map(|caps| {
+                    let attrs = format!("{}{}", &caps[1], &caps[3]);
+                    (
+                        xml_unescape(&caps[2]),
+                        !attrs.contains("rel=") || attrs.contains("alternate"),
+                    )
+                })
+                .collect();
+            let url = links
+                .iter()
+                .find(|(_, alternate)| *alternate)
+                .or(links.first())
+                .and_then(|(href, _)| absolute_url(source_url, href));
+            let summary = atom_tag(entry, &["summary", "content"])
+                .map(|text| summarize(&xml_unescape(text)))
+                .unwrap_or_default();
+            Some(NewsEntry {
+                id: atom_tag(entry, &["id"])
+                    .map(xml_unescape)
+                    .or_else(|| url.clone())
+                    .unwrap_or_else(|| title.clone()),
+                date: atom_tag(entry, &["updated", "published"]).map(ToString::to_string),
+                title,
+                summary,
+                url,
+                image_url: None,
+                remote_image_url: None,
+            })
+        })
+        .collect()
+}
+
+/// Normaliza la respuesta de un feed: Atom si empieza por `<`, si no JSON (releases de
+/// GitHub, noticias del launcher oficial o una lista genérica con campos parecidos).
+pub fn parse_news(source_url: &str, body: &str) -> Result<Vec<NewsEntry>, String> {
+    let trimmed = body.trim_start_matches('\u{feff}').trim_start();
+    let mut entries = if trimmed.starts_with('<') {
+        atom_entries(source_url, trimmed)
+    } else {
+        let value: Value = serde_json::from_str(trimmed)
+            .map_err(|err| format!("El feed de noticias {source_url} no es JSON válido: {err}"))?;
+        let items = match &value {
+            Value::Array(items) => items.as_slice(),
+            _ => ["entries", "items", "articles"]
+                .iter()
+                .find_map(|key| value.get(*key).and_then(Value::as_array))
+                .map(Vec::as_slice)
+                .unwrap_or_default(),
+        };
+        items
+            .iter()
+            .filter_map(|item| json_entry(source_url, item))
+            .collect()
+    };
+    entries.truncate(MAX_ENTRIES);
+    Ok(entries)
+}
+
+fn fetch_feed(source_url: &str) -> AppResult<Vec<NewsEntry>> {
+    let client = build_http_client()?;
+    let response = rate_limit::send_blocking(source_url, || {
+        client.get(source_url).header(
+            reqwest::header::ACCEPT,
+            "application/json, application/atom+xml",
+        )
+    })
+    .map_err(|err| err.describe("No se pudo descargar el feed de noticias"))?;
+    if !response.status().is_success() {
+        return Err(format!(
+            "El feed de noticias {source_url} respondió HTTP {}.",
+            response.status()
+        ));
+    }
+    let body = response
+        .text()
+        .map_err(|err| format!("No se pudo leer el feed de noticias {source_url}: {err}"))?;
+    parse_news(source_url, &body)
+}
+
+/// Pasa las imágenes de las primeras entradas por la caché de imágenes; si una falla la
+/// entrada se muestra sin imagen.
+fn cache_entry_images(app: &AppHandle, launcher_root: &Path, entries: &mut [NewsEntry]) {
+    let cache_root = image_cache_root(launcher_root);
+    let cap_bytes = configured_cap_bytes(app);
+    for entry in entries.iter_mut().take(MAX_CACHED_IMAGES) {
+        let Some(remote) = entry.remote_image_url.as_deref() else {
+            continue;
+        };
+        match fetch_image_cached(&cache_root, remote, MAX_NEWS_IMAGE_BYTES, cap_bytes) {
+            Ok(image) => entry.image_url = Some(image.path),
+            Err(err) => log::warn!("⚠ Imagen de noticia omitida: {err}"),
+        }
+    }
+}
+
+fn load_news_feed(app: &AppHandle, feed: &str, force_refresh: bool) -> AppResult<NewsFeed> {
+    let (enabled, source_url) = feed_source(app, feed)?;
+    if !enabled {
+        return Ok(NewsFeed {
+            feed: feed.to_string(),
+            entries: Vec::new(),
+            fetched_at: None,
+            stale: false,
+            disabled: true,
+        });
+    }
+    let launcher_root = resolve_launcher_root(app)?;
+    let (cached, stale) = load_cached(
+        &cache_file(&launcher_root, NEWS_CACHE_DIR, feed),
+        &source_url,
+        app_clock(app).clock.now(),
+        Duration::hours(NEWS_MAX_AGE_HOURS),
+        force_refresh,
+        &format!("noticias '{feed}'"),
+        || {
+            let mut entries = fetch_feed(&source_url)?;
+            cache_entry_images(app, &launcher_root, &mut entries);
+            Ok(entries)
+        },
+    )?;
+    Ok(NewsFeed {
+        feed: feed.to_string(),
+        entries: cached.data,
+        fetched_at: Some(cached.fetched_at),
+        stale,
+        disabled: false,
+    })
+}
+
+/// `feed` es `launcher` o `minecraft`. Con `force_refresh` se ignora la vigencia de la
+/// caché, que igualmente se usa si la descarga falla.
+#[tauri::command]
+pub async fn get_news_feed(
+    app: AppHandle,
+    feed: String,
+    force_refresh: Option<bool>,
+) -> Result<NewsFeed, String> {
+    let feed = feed.trim().to_ascii_lowercase();
+    tauri::async_runtime::spawn_blocking(move || {
+        load_news_feed(&app, &feed, force_refresh.unwrap_or(false))
+    })
+    .await
+    .map_err(|err| format!("Falló la tarea de noticias: {err}"))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_github_mojang_and_atom_sources() {
+        let releases = r###"[
+            {"id": 101, "tag_name": "v0.4.0", "name": "", "published_at": "2026-09-01T10:00:00Z",
+             "body": "## Cambios\n- **Nuevo** gestor", "html_url": "https://github.com/x/y/releases/v0.4.0"},
+            {"id": 100, "body": "sin título"}
+        ]"###;
+        let entries = parse_news(DEFAULT_LAUNCHER_NEWS_URL, releases).expect("github");
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].id, "101");
+        assert_eq!(entries[0].title, "v0.4.0");
+        assert_eq!(entries[0].date.as_deref(), Some("2026-09-01T10:00:00Z"));
+        assert_eq!(entries[0].summary, "## Cambios - **Nuevo** gestor");
+        assert_eq!(entries[0].remote_image_url, None);
+
+        let mojang = r#"{"version": 1, "entries": [
+            {"id": "abc", "title": "Minecraft Live", "date": "2026-09-28",
+             "text": "<p>Se viene <b>algo</b></p>",
+             "newsPageImage": {"title": "img", "url": "/images/live.jpg"},
+             "readMoreLink": "https://www.minecraft.net/article/live"},
+            {"title": "Sin nada más"}
+        ]}"#;
+        let entries = parse_news(DEFAULT_MINECRAFT_NEWS_URL, mojang).expect("mojang");
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].summary, "Se viene algo");
+        assert_eq!(
+            entries[0].remote_image_url.as_deref(),
+            Some("https://launchercontent.mojang.com/images/live.jpg")
+        );
+        assert_eq!(entries[1].id, "Sin nada más");
+        assert_eq!(entries[1].date, None);
+        assert_eq!(entries[1].url, None);
+
+        let atom = r#"<?xml version="1.0"?><feed xmlns="http://www.w3.org/2005/Atom">
+            <title>Feed</title>
+            <entry><id>tag:1</id><title>Parche &amp; notas</title><updated>2026-10-01T00:00:00Z</updated>
+              <link rel="self" href="https://e.com/self"/><link rel="alternate" href="https://e.com/1"/>
+              <content type="html"><![CDATA[<p>Hola</p>]]></content></entry>
+            <entry><title>Solo título</title><link href="/2"/></entry>
+        </feed>"#;
+        let entries = parse_news("https://e.com/feed.atom", atom).expect("atom");
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].title, "Parche & notas");
+        assert_eq!(entries[0].url.as_deref(), Some("https://e.com/1"));
+        assert_eq!(entries[0].summary, "Hola");
+        assert_eq!(entries[1].url.as_deref(), Some("https://e.com/2"));
+        assert_eq!(entries[1].id, "https://e.com/2");
+
+        assert!(parse_news(DEFAULT_MINECRAFT_NEWS_URL, "no es json").is_err());
+        assert!(parse_news(DEFAULT_MINECRAFT_NEWS_URL, r#"{"otra": 1}"#)
+            .expect("vacío")
+            .is_empty());
+    }
+}
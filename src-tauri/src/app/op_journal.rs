@@ -0,0 +1,363 @@
+use std::{
+    collections::HashSet,
+    fs,
+    path::{Path, PathBuf},
+    sync::{Mutex, OnceLock},
+};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+use crate::{
+    app::{
+        instance_service::is_instance_running, launcher_service::list_instances_readonly,
+        trusted_root::resolve_trusted_instance_root,
+    },
+    infrastructure::{
+        checksum::sha1::compute_file_sha1,
+        downloader::queue::{build_official_client, download_with_retry},
+        filesystem::file_ops::write_file_replacing,
+    },
+    shared::result::AppResult,
+};
+
+/// Diario de la operación multiarchivo en curso; si sobrevive a un reinicio, la operación
+/// se cortó (apagón, cierre forzado) y la instancia necesita recuperación.
+pub const OP_JOURNAL_FILE: &str = ".op-journal.json";
+pub const OP_CREATE_INSTANCE: &str = "create_instance";
+pub const OP_IMPORT_INSTANCE: &str = "import_instance";
+pub const OP_DOWNLOAD_LIBRARIES: &str = "download_libraries";
+/// Estado con el que el listado marca las instancias con un diario pendiente.
+pub const NEEDS_RECOVERY_STATE: &str = "NEEDS_RECOVERY";
+/// Operaciones que se reanudan (descargas con hash conocido); el resto se deshacen.
+const RESUMABLE_OPERATIONS: [&str; 1] = [OP_DOWNLOAD_LIBRARIES];
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct JournalEntry {
+    pub path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha1: Option<String>,
+}
+
+impl JournalEntry {
+    pub fn path(path: &Path) -> Self {
+        Self {
+            path: path.display().to_string(),
+            url: None,
+            sha1: None,
+        }
+    }
+
+    pub fn download(path: &Path, url: &str, sha1: &str) -> Self {
+        Self {
+            path: path.display().to_string(),
+            url: Some(url.to_string()).filter(|url| !url.trim().is_empty()),
+            sha1: Some(sha1.to_ascii_lowercase()).filter(|sha1| !sha1.trim().is_empty()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct OpJournal {
+    pub operation: String,
+    pub started_at: String,
+    /// Nombre para mostrar la instancia aunque aún no tenga `.instance.json`.
+    #[serde(default)]
+    pub label: String,
+    pub files: Vec<JournalEntry>,
+}
+
+/// Diario abierto. Se borra con [`OperationJournal::complete`]; si el proceso muere antes,
+/// queda en disco para la recuperación.
+#[derive(Debug)]
+pub struct OperationJournal {
+    instance_root: PathBuf,
+    path: PathBuf,
+}
+
+/// Instancias con una operación en curso en este proceso: su diario no es un resto.
+static ACTIVE_JOURNALS: OnceLock<Mutex<HashSet<PathBuf>>> = OnceLock::new();
+
+fn active_journals() -> &'static Mutex<HashSet<PathBuf>> {
+    ACTIVE_JOURNALS.get_or_init(|| Mutex::new(HashSet::new()))
+}
+
+impl OperationJournal {
+    pub fn begin(
+        instance_root: &Path,
+        operation: &str,
+        label: &str,
+        files: Vec<JournalEntry>,
+    ) -> AppResult<Self> {
+        let journal = OpJournal {
+            operation: operation.to_string(),
+            started_at: chrono::Utc::now().to_rfc3339(),
+            label: label.to_string(),
+            files,
+        };
+        let path = instance_root.join(OP_JOURNAL_FILE);
+        let raw = serde_json::to_vec_pretty(&journal)
+            .map_err(|err| format!("No se pudo serializar {OP_JOURNAL_FILE}: {err}"))?;
+        write_file_replacing(&path, &raw, true)?;
+        if let Ok(mut active) = active_journals().lock() {
+            active.insert(instance_root.to_path_buf());
+        }
+        Ok(Self {
+            instance_root: instance_root.to_path_buf(),
+            path,
+        })
+    }
+
+    pub fn complete(self) {
+        if let Err(err) = fs::remove_file(&self.path) {
+            if err.kind() != std::io::ErrorKind::NotFound {
+                log::warn!("⚠ No se pudo borrar {}: {err}", self.path.display());
+            }
+        }
+    }
+}
+
+/// Soltarlo sin `complete` (error a mitad) deja el diario para que se pueda recuperar.
+impl Drop for OperationJournal {
+    fn drop(&mut self) {
+        if let Ok(mut active) = active_journals().lock() {
+            active.remove(&self.instance_root);
+        }
+    }
+}
+
+/// Hay un diario de una operación que ya no está en curso.
+pub fn needs_recovery(instance_root: &Path) -> bool {
+    instance_root.join(OP_JOURNAL_FILE).is_file()
+        && !active_journals()
+            .lock()
+            .map(|active| active.contains(instance_root))
+            .unwrap_or(false)
+}
+
+pub fn read_op_journal(instance_root: &Path) -> Option<OpJournal> {
+    let raw = fs::read_to_string(instance_root.join(OP_JOURNAL_FILE)).ok()?;
+    serde_json::from_str(&raw).ok()
+}
+
+#[derive(Debug, Clone, Default, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RecoveryReport {
+    pub operation: String,
+    /// `resumed` o `rolled_back`.
+    pub action: String,
+    /// Archivos verificados o vueltos a descargar.
+    pub restored: Vec<String>,
+    /// Rutas parciales eliminadas.
+    pub removed: Vec<String>,
+    pub failed: Vec<String>,
+}
+
+fn remove_any(path: &Path) -> std::io::Result<()> {
+    if path.is_dir() {
+        fs::remove_dir_all(path)
+    } else {
+        fs::remove_file(path)
+    }
+}
+
+fn resume_entry(entry: &JournalEntry, report: &mut RecoveryReport) {
+    let path = Path::new(&entry.path);
+    let (Some(url), Some(sha1)) = (entry.url.as_deref(), entry.sha1.as_deref()) else {
+        // Sin hash no se puede saber si está completo: se borra y se volverá a descargar.
+        if path.exists() && remove_any(path).is_ok() {
+            report.removed.push(entry.path.clone());
+        }
+        return;
+    };
+    if compute_file_sha1(path).is_ok_and(|current| current.eq_ignore_ascii_case(sha1)) {
+        report.restored.push(entry.path.clone());
+        return;
+    }
+    let downloaded = build_official_client()
+        .and_then(|client| download_with_retry(&client, url, path, sha1, true));
+    match downloaded {
+        Ok(_) => report.restored.push(entry.path.clone()),
+        Err(err) => {
+            log::warn!("⚠ No se pudo reanudar la descarga de {}: {err}", entry.path);
+            if path.exists() && remove_any(path).is_ok() {
+                report.removed.push(entry.path.clone());
+            }
+            report.failed.push(entry.path.clone());
+        }
+    }
+}
+
+/// Resuelve el diario de `instance_root`: reanuda las descargas o borra lo que dejó a medias.
+/// Al deshacer solo se borran rutas dentro de la instancia.
+pub(crate) fn recover_journal(instance_root: &Path, journal: &OpJournal) -> RecoveryReport {
+    let resumable = RESUMABLE_OPERATIONS.contains(&journal.operation.as_str());
+    let mut report = RecoveryReport {
+        operation: journal.operation.clone(),
+        action: if resumable { "resumed" } else { "rolled_back" }.to_string(),
+        ..RecoveryReport::default()
+    };
+    for entry in &journal.files {
+        if resumable {
+            resume_entry(entry, &mut report);
+            continue;
+        }
+        let path = Path::new(&entry.path);
+        if !path.starts_with(instance_root) {
+            report.failed.push(entry.path.clone());
+            continue;
+        }
+        if !path.exists() {
+            continue;
+        }
+        match remove_any(path) {
+            Ok(()) => report.removed.push(entry.path.clone()),
+            Err(err) => {
+                log::warn!("⚠ No se pudo deshacer {}: {err}", entry.path);
+                report.failed.push(entry.path.clone());
+            }
+        }
+    }
+    let journal_path = instance_root.join(OP_JOURNAL_FILE);
+    if report.failed.is_empty() && journal_path.exists() {
+        let _ = fs::remove_file(journal_path);
+    }
+    report
+}
+
+/// Al arrancar: avisa de las instancias con operaciones interrumpidas.
+pub fn scan_interrupted_operations(app: &AppHandle) {
+    let Ok(instances) = list_instances_readonly(app) else {
+        return;
+    };
+    let interrupted = instances
+        .iter()
+        .filter(|instance| instance.state.as_deref() == Some(NEEDS_RECOVERY_STATE))
+        .map(|instance| instance.instance_root.clone())
+        .collect::<Vec<_>>();
+    if interrupted.is_empty() {
+        return;
+    }
+    log::warn!(
+        "⚠ {} instancia(s) con operaciones interrumpidas: {}",
+        interrupted.len(),
+        interrupted.join(", ")
+    );
+    let _ = app.emit(
+        "interrupted_operations_detected",
+        serde_json::json!({ "instanceRoots": interrupted }),
+    );
+}
+
+#[tauri::command]
+pub fn recover_interrupted_operation(
+    app: AppHandle,
+    instance_root: String,
+) -> Result<RecoveryReport, String> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    if is_instance_running(instance_root.as_str()) {
+        return Err("No se puede recuperar la instancia mientras está en ejecución.".to_string());
+    }
+    let root = instance_root.path();
+    let journal = read_op_journal(root).ok_or_else(|| {
+        format!("La instancia no tiene operaciones interrumpidas: {instance_root}")
+    })?;
+    let report = recover_journal(root, &journal);
+    log::info!(
+        "🔹 Recuperación de '{}' en {instance_root}: {} ({} restaurados, {} eliminados, {} fallidos)",
+        report.operation,
+        report.action,
+        report.restored.len(),
+        report.removed.len(),
+        report.failed.len()
+    );
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_instance(label: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "interface-op-journal-{label}-{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).expect("instance");
+        dir
+    }
+
+    #[test]
+    fn interrupted_import_is_rolled_back_to_nothing() {
+        let root = temp_instance("import");
+        let journal = OperationJournal::begin(
+            &root,
+            OP_IMPORT_INSTANCE,
+            "Pack",
+            vec![JournalEntry::path(&root)],
+        )
+        .expect("begin");
+        fs::create_dir_all(root.join("minecraft/mods")).expect("mods");
+        fs::write(root.join("minecraft/mods/half.jar"), [0x50, 0x4b]).expect("partial");
+        assert!(!needs_recovery(&root), "en curso no es un resto");
+        // La operación se corta sin completar: el diario queda en disco.
+        drop(journal);
+        assert!(needs_recovery(&root));
+
+        let journal = read_op_journal(&root).expect("journal");
+        assert_eq!(journal.label, "Pack");
+        let report = recover_journal(&root, &journal);
+        assert_eq!(report.action, "rolled_back");
+        assert_eq!(report.removed, vec![root.display().to_string()]);
+        assert!(!root.exists());
+
+        let completed = temp_instance("import-ok");
+        OperationJournal::begin(&completed, OP_IMPORT_INSTANCE, "Pack", Vec::new())
+            .expect("begin")
+            .complete();
+        assert!(read_op_journal(&completed).is_none());
+        let _ = fs::remove_dir_all(completed);
+    }
+
+    #[test]
+    fn interrupted_download_keeps_verified_files_and_drops_unhashed_partials() {
+        let root = temp_instance("download");
+        let libraries = root.join("libraries");
+        fs::create_dir_all(&libraries).expect("libraries");
+        let complete = libraries.join("complete.jar");
+        fs::write(&complete, b"contenido").expect("complete");
+        let sha1 = compute_file_sha1(&complete).expect("sha1");
+        let unhashed = libraries.join("truncated.jar");
+        fs::write(&unhashed, [0x50]).expect("truncated");
+
+        let journal = OpJournal {
+            operation: OP_DOWNLOAD_LIBRARIES.to_string(),
+            started_at: String::new(),
+            label: String::new(),
+            files: vec![
+                JournalEntry::download(&complete, "https://libraries.minecraft.net/x.jar", &sha1),
+                JournalEntry::path(&unhashed),
+            ],
+        };
+        fs::write(
+            root.join(OP_JOURNAL_FILE),
+            serde_json::to_vec(&journal).expect("json"),
+        )
+        .expect("journal");
+
+        let report = recover_journal(&root, &journal);
+        assert_eq!(report.action, "resumed");
+        assert_eq!(report.restored, vec![complete.display().to_string()]);
+        assert_eq!(report.removed, vec![unhashed.display().to_string()]);
+        assert!(report.failed.is_empty());
+        assert!(complete.exists() && !unhashed.exists());
+        assert!(!root.join(OP_JOURNAL_FILE).exists());
+
+        let _ = fs::remove_dir_all(root);
+    }
+}
@@ -0,0 +1,515 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{AppHandle, Emitter};
+use zip::ZipArchive;
+
+use crate::{
+    app::{
+        instance_service::detect_runtime_game_dir, op_journal::OP_JOURNAL_FILE,
+        redirect_launch::detect_loader_from_version_id, settings_service::resolve_instances_root,
+    },
+    domain::{java::java_requirement::determine_required_java, models::instance::InstanceMetadata},
+    services::instance_builder::persist_instance_metadata,
+    shared::clock::app_clock,
+};
+
+const MAX_SCANNED_MOD_JARS: usize = 200;
+const DEFAULT_ADOPTED_RAM_MB: u32 = 4096;
+const LOADER_OPTIONS: [&str; 5] = ["vanilla", "fabric", "quilt", "forge", "neoforge"];
+
+/// Versión instalada encontrada en `versions/` de la carpeta huérfana.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct OrphanVersionCandidate {
+    pub version_id: String,
+    pub minecraft_version: String,
+    pub loader: String,
+    pub loader_version: String,
+}
+
+/// Pregunta que la UI debe resolver antes de adoptar. `options` vacío significa respuesta libre.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct OrphanQuestion {
+    pub id: String,
+    pub prompt: String,
+    pub options: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrphanInstanceAnalysis {
+    pub folder_path: String,
+    pub game_dir: Option<String>,
+    pub candidates: Vec<OrphanVersionCandidate>,
+    pub mod_jar_count: usize,
+    pub mod_loader_hint: Option<String>,
+    pub suggested: Option<OrphanVersionCandidate>,
+    pub questions: Vec<OrphanQuestion>,
+}
+
+/// Respuestas a las preguntas de `analyze_orphan_instance` (por `id` de pregunta).
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct OrphanAdoptionAnswers {
+    pub version_id: Option<String>,
+    pub minecraft_version: Option<String>,
+    pub loader: Option<String>,
+    pub loader_version: Option<String>,
+}
+
+fn question(id: &str, prompt: &str, options: Vec<String>) -> OrphanQuestion {
+    OrphanQuestion {
+        id: id.to_string(),
+        prompt: prompt.to_string(),
+        options,
+    }
+}
+
+fn read_version_candidate(version_dir: &Path) -> Option<OrphanVersionCandidate> {
+    let version_id = version_dir.file_name()?.to_str()?.to_string();
+    let json_path = version_dir.join(format!("{version_id}.json"));
+    let jar_path = version_dir.join(format!("{version_id}.jar"));
+    if !json_path.is_file() && !jar_path.is_file() {
+        return None;
+    }
+    let inherits_from = fs::read_to_string(&json_path)
+        .ok()
+        .and_then(|raw| serde_json::from_str::<Value>(&raw).ok())
+        .and_then(|json| {
+            json.get("inheritsFrom")
+                .and_then(Value::as_str)
+                .map(ToOwned::to_owned)
+        });
+    let (loader, loader_version) = detect_loader_from_version_id(&version_id)
+        .unwrap_or_else(|| ("vanilla".to_string(), "-".to_string()));
+    Some(OrphanVersionCandidate {
+        minecraft_version: inherits_from.unwrap_or_else(|| version_id.clone()),
+        version_id,
+        loader,
+        loader_version,
+    })
+}
+
+/// Candidatos de versión, quitando las vanilla que solo están como padre de un loader.
+fn version_candidates(game_dir: &Path) -> Vec<OrphanVersionCandidate> {
+    let mut candidates: Vec<OrphanVersionCandidate> = fs::read_dir(game_dir.join("versions"))
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .filter_map(|path| read_version_candidate(&path))
+        .collect();
+    let parents: Vec<String> = candidates
+        .iter()
+        .filter(|candidate| candidate.loader != "vanilla")
+        .map(|candidate| candidate.minecraft_version.clone())
+        .collect();
+    candidates.retain(|candidate| {
+        candidate.loader != "vanilla" || !parents.contains(&candidate.version_id)
+    });
+    candidates.sort_by(|a, b| a.version_id.cmp(&b.version_id));
+    candidates
+}
+
+fn mod_jar_loader(jar: &Path) -> Option<&'static str> {
+    let mut archive = ZipArchive::new(fs::File::open(jar).ok()?).ok()?;
+    let has = |archive: &mut ZipArchive<fs::File>, name: &str| archive.by_name(name).is_ok();
+    if has(&mut archive, "quilt.mod.json") {
+        Some("quilt")
+    } else if has(&mut archive, "fabric.mod.json") {
+        Some("fabric")
+    } else if has(&mut archive, "META-INF/neoforge.mods.toml") {
+        Some("neoforge")
+    } else if has(&mut archive, "META-INF/mods.toml") || has(&mut archive, "mcmod.info") {
+        Some("forge")
+    } else {
+        None
+    }
+}
+
+/// Cuenta los jars de `mods/` y devuelve el loader mayoritario según sus descriptores.
+fn probe_mods_loader(game_dir: &Path) -> (usize, Option<String>) {
+    let jars: Vec<PathBuf> = fs::read_dir(game_dir.join("mods"))
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("jar"))
+        })
+        .collect();
+    let mut votes: HashMap<&'static str, usize> = HashMap::new();
+    for jar in jars.iter().take(MAX_SCANNED_MOD_JARS) {
+        if let Some(loader) = mod_jar_loader(jar) {
+            *votes.entry(loader).or_default() += 1;
+        }
+    }
+    let hint = votes
+        .into_iter()
+        .max_by_key(|(_, count)| *count)
+        .map(|(loader, _)| loader.to_string());
+    (jars.len(), hint)
+}
+
+/// Inspecciona una carpeta sin `.instance.json` y devuelve lo que se puede deducir y las
+/// preguntas que quedan abiertas cuando hay ambigüedad.
+pub fn analyze_orphan_folder(folder: &Path) -> Result<OrphanInstanceAnalysis, String> {
+    if !folder.is_dir() {
+        return Err(format!("La carpeta no existe: {}", folder.display()));
+    }
+    if folder.join(".instance.json").exists() {
+        return Err(format!(
+            "La carpeta ya es una instancia registrada: {}",
+            folder.display()
+        ));
+    }
+    if folder.join(OP_JOURNAL_FILE).exists() {
+        return Err(format!(
+            "La carpeta es una creación o importación interrumpida; recupérala en lugar de adoptarla: {}",
+            folder.display()
+        ));
+    }
+
+    let game_dir = detect_runtime_game_dir(folder);
+    let (candidates, mod_jar_count, mod_loader_hint) = match game_dir.as_deref() {
+        Some(game_dir) => {
+            let (count, hint) = probe_mods_loader(game_dir);
+            (version_candidates(game_dir), count, hint)
+        }
+        None => (Vec::new(), 0, None),
+    };
+
+    let matching: Vec<&OrphanVersionCandidate> = match mod_loader_hint.as_deref() {
+        Some(hint) if candidates.iter().any(|c| c.loader == hint) => {
+            candidates.iter().filter(|c| c.loader == hint).collect()
+        }
+        _ => candidates.iter().collect(),
+    };
+
+    let mut questions = Vec::new();
+    let suggested = match matching.as_slice() {
+        [single] => Some((*single).clone()),
+        [] => {
+            questions.push(question(
+                "minecraftVersion",
+                "No se encontró ninguna versión instalada. ¿Qué versión de Minecraft usa?",
+                Vec::new(),
+            ));
+            None
+        }
+        many => {
+            questions.push(question(
+                "versionId",
+                "Hay varias versiones instaladas. ¿Cuál usa esta instancia?",
+                many.iter().map(|c| c.version_id.clone()).collect(),
+            ));
+            None
+        }
+    };
+
+    let suggested_loader = suggested.as_ref().map(|c| c.loader.as_str());
+    let loader_unclear = match (suggested_loader, mod_loader_hint.as_deref()) {
+        (Some("vanilla"), Some(_)) => true,
+        (None, None) => mod_jar_count > 0 && candidates.is_empty(),
+        _ => false,
+    };
+    if loader_unclear {
+        questions.push(question(
+            "loader",
+            "Hay mods pero no se pudo confirmar el loader. ¿Cuál usa?",
+            LOADER_OPTIONS.iter().map(ToString::to_string).collect(),
+        ));
+    }
+
+    Ok(OrphanInstanceAnalysis {
+        folder_path: folder.display().to_string(),
+        game_dir: game_dir.map(|path| path.display().to_string()),
+        candidates,
+        mod_jar_count,
+        mod_loader_hint,
+        suggested,
+        questions,
+    })
+}
+
+fn answer_for<'a>(answers: &'a OrphanAdoptionAnswers, id: &str) -> Option<&'a str> {
+    let value = match id {
+        "versionId" => answers.version_id.as_deref(),
+        "minecraftVersion" => answers.minecraft_version.as_deref(),
+        "loader" => answers.loader.as_deref(),
+        _ => None,
+    };
+    value.map(str::trim).filter(|value| !value.is_empty())
+}
+
+/// Combina el análisis con las respuestas; falla si alguna pregunta queda sin responder.
+fn resolve_adoption(
+    analysis: &OrphanInstanceAnalysis,
+    answers: &OrphanAdoptionAnswers,
+) -> Result<OrphanVersionCandidate, String> {
+    let pending: Vec<&str> = analysis
+        .questions
+        .iter()
+        .filter(|q| answer_for(answers, &q.id).is_none())
+        .map(|q| q.id.as_str())
+        .collect();
+    if !pending.is_empty() {
+        return Err(format!(
+            "Faltan respuestas para adoptar la carpeta: {}",
+            pending.join(", ")
+        ));
+    }
+
+    let mut chosen = match answer_for(answers, "versionId") {
+        Some(version_id) => analysis
+            .candidates
+            .iter()
+            .find(|c| c.version_id == version_id)
+            .cloned()
+            .ok_or_else(|| format!("La versión '{version_id}' no está entre las detectadas."))?,
+        None => analysis
+            .suggested
+            .clone()
+            .unwrap_or(OrphanVersionCandidate {
+                version_id: String::new(),
+                minecraft_version: String::new(),
+                loader: "vanilla".to_string(),
+                loader_version: "-".to_string(),
+            }),
+    };
+    if let Some(minecraft_version) = answer_for(answers, "minecraftVersion") {
+        chosen.minecraft_version = minecraft_version.to_string();
+        if chosen.version_id.is_empty() {
+            chosen.version_id = minecraft_version.to_string();
+        }
+    }
+    if let Some(loader) = answer_for(answers, "loader") {
+        chosen.loader = loader.to_ascii_lowercase();
+    }
+    if let Some(loader_version) = answers.loader_version.as_deref().map(str::trim) {
+        if !loader_version.is_empty() {
+            chosen.loader_version = loader_version.to_string();
+        }
+    }
+    if chosen.minecraft_version.is_empty() {
+        return Err("No se pudo determinar la versión de Minecraft.".to_string());
+    }
+    Ok(chosen)
+}
+
+/// Deja los datos del juego en `<instancia>/minecraft`, que es donde los busca el lanzamiento.
+fn normalize_game_dir(folder: &Path, game_dir: Option<&Path>) -> Result<(), String> {
+    let target = folder.join("minecraft");
+    match game_dir {
+        Some(dir) if dir == target => Ok(()),
+        Some(dir) if dir != folder => fs::rename(dir, &target).map_err(|err| {
+            format!(
+                "No se pudo mover {} a {}: {err}",
+                dir.display(),
+                target.display()
+            )
+        }),
+        _ => {
+            fs::create_dir_all(&target)
+                .map_err(|err| format!("No se pudo crear {}: {err}", target.display()))?;
+            for entry in fs::read_dir(folder)
+                .map_err(|err| format!("No se pudo leer {}: {err}", folder.display()))?
+                .filter_map(Result::ok)
+            {
+                let path = entry.path();
+                if path == target {
+                    continue;
+                }
+                fs::rename(&path, target.join(entry.file_name()))
+                    .map_err(|err| format!("No se pudo mover {}: {err}", path.display()))?;
+            }
+            Ok(())
+        }
+    }
+}
+
+fn ensure_inside_instances_root(instances_root: &Path, folder: &Path) -> Result<(), String> {
+    let root = fs::canonicalize(instances_root).map_err(|err| {
+        format!(
+            "No se pudo resolver la ruta de instancias {}: {err}",
+            instances_root.display()
+        )
+    })?;
+    let parent = fs::canonicalize(folder)
+        .ok()
+        .and_then(|path| path.parent().map(Path::to_path_buf));
+    if parent.as_deref() != Some(root.as_path()) {
+        return Err(format!(
+            "Solo se pueden adoptar carpetas dentro de {}; usa importar para otras ubicaciones.",
+            root.display()
+        ));
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn analyze_orphan_instance(folder_path: String) -> Result<OrphanInstanceAnalysis, String> {
+    analyze_orphan_folder(Path::new(&folder_path))
+}
+
+#[tauri::command]
+pub fn adopt_orphan_instance(
+    app: AppHandle,
+    folder_path: String,
+    name: String,
+    group: String,
+    answers: Option<OrphanAdoptionAnswers>,
+) -> Result<InstanceMetadata, String> {
+    let folder = PathBuf::from(&folder_path);
+    ensure_inside_instances_root(&resolve_instances_root(&app)?, &folder)?;
+    let analysis = analyze_orphan_folder(&folder)?;
+    let chosen = resolve_adoption(&analysis, &answers.unwrap_or_default())?;
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("El nombre de la instancia no puede estar vacío.".to_string());
+    }
+
+    normalize_game_dir(&folder, analysis.game_dir.as_deref().map(Path::new))?;
+
+    let required_java = determine_required_java(&chosen.minecraft_version, &chosen.loader).ok();
+    let clock = app_clock(&app);
+    let metadata = InstanceMetadata {
+        name,
+        group: if group.trim().is_empty() {
+            "Sin grupo".to_string()
+        } else {
+            group
+        },
+        minecraft_version: chosen.minecraft_version,
+        version_id: chosen.version_id,
+        loader: chosen.loader,
+        loader_version: chosen.loader_version,
+        ram_mb: DEFAULT_ADOPTED_RAM_MB,
+        java_args: Vec::new(),
+        java_path: String::new(),
+        java_runtime: required_java
+            .map(|runtime| runtime.as_dir_name().to_string())
+            .unwrap_or_default(),
+        java_version: required_java
+            .map(|runtime| format!("{}.0.x", runtime.major()))
+            .unwrap_or_default(),
+        required_java_major: required_java
+            .map(|runtime| u32::from(runtime.major()))
+            .unwrap_or(0),
+        created_at: clock.clock.now_rfc3339(),
+        state: "IMPORTED".to_string(),
+        last_used: None,
+        internal_uuid: clock.ids.new_id(),
+        filesystem: None,
+        mods_dir_override: None,
+        adopted: true,
+        tags: Vec::new(),
+        locked_fields: Vec::new(),
+        locked_by: String::new(),
+        optional_game_flags: Default::default(),
+        java_build_pin: None,
+        source_settings_write: false,
+        retention: Default::default(),
+        library_overrides: Vec::new(),
+        pack_origin: None,
+        auto_jvm_tuning: false,
+        preferred_gpu: None,
+        backup: Default::default(),
+        strict_mode: false,
+        skip_duplicate_mod_check: false,
+        window_tweaks: Default::default(),
+        launch_hooks: Default::default(),
+        console_filters: Vec::new(),
+    };
+
+    let mut logs = Vec::new();
+    persist_instance_metadata(&folder, &metadata, &mut logs)?;
+    log::info!(
+        "✔ Carpeta huérfana adoptada como '{}' ({} {})",
+        metadata.name,
+        metadata.loader,
+        metadata.minecraft_version
+    );
+    let _ = app.emit(
+        "instances_changed",
+        serde_json::json!({
+            "action": "adopted",
+            "instancePath": folder.display().to_string(),
+        }),
+    );
+    Ok(metadata)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::test_support::test_temp_dir;
+
+    fn write_version(game_dir: &Path, id: &str, inherits_from: Option<&str>) {
+        let dir = game_dir.join("versions").join(id);
+        fs::create_dir_all(&dir).expect("version dir");
+        let json = match inherits_from {
+            Some(parent) => serde_json::json!({ "id": id, "inheritsFrom": parent }),
+            None => serde_json::json!({ "id": id }),
+        };
+        fs::write(dir.join(format!("{id}.json")), json.to_string()).expect("json");
+    }
+
+    #[test]
+    fn single_loader_version_is_suggested_without_questions() {
+        let root = test_temp_dir("orphan-single");
+        let game = root.join(".minecraft");
+        write_version(&game, "1.20.1", None);
+        write_version(&game, "fabric-loader-0.15.7-1.20.1", Some("1.20.1"));
+
+        let analysis = analyze_orphan_folder(&root).expect("analysis");
+        assert!(analysis.questions.is_empty());
+        let suggested = analysis.suggested.clone().expect("suggested");
+        assert_eq!(suggested.loader, "fabric");
+        assert_eq!(suggested.minecraft_version, "1.20.1");
+
+        let chosen =
+            resolve_adoption(&analysis, &OrphanAdoptionAnswers::default()).expect("resolve");
+        assert_eq!(chosen.version_id, "fabric-loader-0.15.7-1.20.1");
+        normalize_game_dir(&root, Some(&game)).expect("normalize");
+        assert!(root.join("minecraft").join("versions").is_dir());
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn multiple_versions_become_a_question_and_require_an_answer() {
+        let root = test_temp_dir("orphan-ambiguous");
+        let game = root.join("minecraft");
+        write_version(&game, "1.19.4", None);
+        write_version(&game, "1.20.1", None);
+
+        let analysis = analyze_orphan_folder(&root).expect("analysis");
+        assert!(analysis.suggested.is_none());
+        let question = analysis
+            .questions
+            .iter()
+            .find(|q| q.id == "versionId")
+            .expect("question");
+        assert_eq!(question.options, vec!["1.19.4", "1.20.1"]);
+
+        assert!(resolve_adoption(&analysis, &OrphanAdoptionAnswers::default()).is_err());
+        let answers = OrphanAdoptionAnswers {
+            version_id: Some("1.19.4".to_string()),
+            ..OrphanAdoptionAnswers::default()
+        };
+        let chosen = resolve_adoption(&analysis, &answers).expect("resolve");
+        assert_eq!(chosen.minecraft_version, "1.19.4");
+        assert_eq!(chosen.loader, "vanilla");
+        let _ = fs::remove_dir_all(root);
+    }
+}
@@ -0,0 +1,1411 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    io::{Cursor, Read},
+    path::{Component, Path, PathBuf},
+};
+
+use reqwest::blocking::Client;
+use serde::Serialize;
+use serde_json::{json, Value};
+use tauri::{AppHandle, Emitter};
+use zip::ZipArchive;
+
+use crate::{
+    app::{
+        instance_locks::{check_metadata_lock, InstanceEditError},
+        instance_reset::{
+            imported_mod_envs, imported_mods, pending_optional_files, record_pack_import_manifest,
+            OptionalPackFile, IMPORT_MANIFEST_FILE,
+        },
+        instance_service::{
+            effective_mods_dir, is_instance_running, read_instance_metadata, shared_mods_dir,
+            update_instance_metadata,
+        },
+        instance_upgrade::{
+            build_upgrade_client, download_mod_file, primary_modrinth_file,
+            restore_instance_snapshot, snapshot_instance,
+        },
+        launch_lock::ChangedFile,
+        trusted_root::{resolve_trusted_instance_root, ValidatedInstanceRoot},
+    },
+    domain::models::instance::PackOrigin,
+    infrastructure::{checksum::sha1::compute_file_sha1, http::rate_limit},
+    shared::result::AppResult,
+};
+
+const MODRINTH_API_URL: &str = "https://api.modrinth.com/v2";
+const CURSEFORGE_API_URL: &str = "https://api.curseforge.com/v1";
+/// Archivo del pack descargado durante la actualización; se borra al terminar.
+const PACK_DOWNLOAD_DIR: &str = ".pack-update";
+
+/// Versión publicada de un modpack.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PackVersionInfo {
+    pub version_id: String,
+    pub name: String,
+    pub version_number: String,
+    pub changelog_url: String,
+    pub file_size: u64,
+    #[serde(skip)]
+    download_url: Option<String>,
+    #[serde(skip)]
+    sha1: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackUpdateCheck {
+    pub instance_root: String,
+    pub platform: String,
+    pub project_id: String,
+    pub current_version_id: String,
+    pub current_version_number: String,
+    pub update_available: bool,
+    /// Versión más reciente compatible con la versión de Minecraft de la instancia.
+    pub latest: Option<PackVersionInfo>,
+}
+
+/// Cambios en `mods/` entre el pack importado y la versión destino.
+#[derive(Debug, Clone, Default, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PackModsDiff {
+    pub added_mods: Vec<String>,
+    pub removed_mods: Vec<String>,
+    pub changed_mods: Vec<ChangedFile>,
+    /// Mods que no vienen del pack: no se tocan.
+    pub user_mods: Vec<String>,
+    pub unchanged: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackUpdateReport {
+    pub instance_root: String,
+    pub previous_version: String,
+    pub current_version: String,
+    pub snapshot_path: String,
+    #[serde(flatten)]
+    pub diff: PackModsDiff,
+    /// Mods del pack que el usuario modificó: no se borraron aunque el pack ya no los trae.
+    pub kept_modified: Vec<String>,
+    pub overrides_applied: usize,
+    /// Archivos opcionales del pack que no se instalaron; se eligen con
+    /// `install_optional_pack_files`.
+    pub optional_files: Vec<OptionalPackFile>,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OptionalPackInstallReport {
+    pub instance_root: String,
+    pub installed: Vec<String>,
+    /// Opcionales que siguen sin instalar.
+    pub remaining: Vec<OptionalPackFile>,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PackUpdateProgressEvent<'a> {
+    instance_root: &'a str,
+    stage: &'a str,
+    message: String,
+}
+
+/// Archivo que declara el pack (`mods/...`, `resourcepacks/...`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PackFile {
+    /// Posición en `files` del índice del pack.
+    index: usize,
+    path: String,
+    sha1: Option<String>,
+    url: Option<String>,
+    /// `env.client` de Modrinth (`required`/`optional`); CurseForge no lo declara.
+    client_env: Option<String>,
+}
+
+impl PackFile {
+    fn is_optional(&self) -> bool {
+        self.client_env.as_deref() == Some("optional")
+    }
+
+    fn to_optional(&self) -> OptionalPackFile {
+        OptionalPackFile {
+            index: self.index,
+            path: self.path.clone(),
+            sha1: self.sha1.clone(),
+            url: self.url.clone(),
+        }
+    }
+}
+
+/// Contenido del archivo del pack relevante para actualizar.
+#[derive(Debug, Default)]
+struct PackContents {
+    files: Vec<PackFile>,
+    /// Carpetas del zip que se copian tal cual sobre el game dir.
+    override_prefixes: Vec<String>,
+    /// Jars de `mods/` incluidos dentro de los overrides.
+    override_mods: Vec<String>,
+    minecraft_version: Option<String>,
+    loader_version: Option<String>,
+}
+
+fn emit_progress(app: &AppHandle, instance_root: &str, stage: &str, message: String) {
+    let _ = app.emit(
+        "instance_pack_update_progress",
+        PackUpdateProgressEvent {
+            instance_root,
+            stage,
+            message,
+        },
+    );
+}
+
+fn curseforge_api_key() -> String {
+    std::env::var("CURSEFORGE_API_KEY").unwrap_or_else(|_| {
+        "$2a$10$jK7YyZHdUNTDlcME9Egd6.Zt5RananLQKn/tpIhmRDezd2.wHGU9G".to_string()
+    })
+}
+
+/// Origen del pack según los archivos que dejan la app de Modrinth (`profile.json` con
+/// `linked_data`) y la de CurseForge (`minecraftinstance.json` con `installedModpack`).
+pub(crate) fn pack_origin_from_source(source_root: &Path) -> Option<PackOrigin> {
+    let read = |name: &str| {
+        fs::read_to_string(source_root.join(name))
+            .ok()
+            .and_then(|raw| serde_json::from_str::<Value>(&raw).ok())
+    };
+    let text = |value: Option<&Value>| {
+        match value {
+            Some(Value::String(text)) => Some(text.trim().to_string()),
+            Some(Value::Number(number)) => Some(number.to_string()),
+            _ => None,
+        }
+        .filter(|text| !text.is_empty())
+    };
+
+    if let Some(linked) = read("profile.json").and_then(|json| json.get("linked_data").cloned()) {
+        if let (Some(project_id), Some(version_id)) = (
+            text(linked.get("project_id")),
+            text(linked.get("version_id")),
+        ) {
+            return Some(PackOrigin {
+                platform: "modrinth".to_string(),
+                project_id,
+                version_id,
+                version_number: String::new(),
+            });
+        }
+    }
+
+    let pack = read("minecraftinstance.json")?
+        .get("installedModpack")?
+        .clone();
+    let file = pack.get("installedFile");
+    Some(PackOrigin {
+        platform: "curseforge".to_string(),
+        project_id: text(pack.get("addonID"))?,
+        version_id: text(file.and_then(|file| file.get("id")))?,
+        version_number: text(file.and_then(|file| file.get("displayName"))).unwrap_or_default(),
+    })
+}
+
+fn get_json(client: &Client, url: &str, curseforge: bool) -> AppResult<Value> {
+    rate_limit::send_blocking(url, || {
+        let request = client.get(url).header("Accept", "application/json");
+        if curseforge {
+            request.header("x-api-key", curseforge_api_key())
+        } else {
+            request
+        }
+    })
+    .map_err(|err| err.describe("No se pudo consultar la plataforma del modpack"))?
+    .error_for_status()
+    .and_then(|response| response.json::<Value>())
+    .map_err(|err| format!("Respuesta inválida de {url}: {err}"))
+}
+
+fn modrinth_version_info(project_id: &str, version: &Value) -> Option<PackVersionInfo> {
+    let version_id = version.get("id")?.as_str()?.to_string();
+    let file = primary_modrinth_file(version);
+    Some(PackVersionInfo {
+        changelog_url: format!("https://modrinth.com/modpack/{project_id}/version/{version_id}"),
+        name: version
+            .get("name")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string(),
+        version_number: version
+            .get("version_number")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string(),
+        file_size: file
+            .and_then(|file| file.get("size"))
+            .and_then(Value::as_u64)
+            .unwrap_or(0),
+        download_url: file
+            .and_then(|file| file.get("url"))
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        sha1: file
+            .and_then(|file| file.pointer("/hashes/sha1"))
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        version_id,
+    })
+}
+
+fn curseforge_sha1(file: &Value) -> Option<String> {
+    file.get("hashes")?
+        .as_array()?
+        .iter()
+        .find(|hash| hash.get("algo").and_then(Value::as_u64) == Some(1))
+        .and_then(|hash| hash.get("value"))
+        .and_then(Value::as_str)
+        .map(str::to_string)
+}
+
+fn curseforge_version_info(project_id: &str, file: &Value) -> Option<PackVersionInfo> {
+    let version_id = file.get("id")?.as_u64()?.to_string();
+    let display_name = file
+        .get("displayName")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string();
+    Some(PackVersionInfo {
+        changelog_url: format!(
+            "https://www.curseforge.com/projects/{project_id}/files/{version_id}"
+        ),
+        name: display_name.clone(),
+        version_number: display_name,
+        file_size: file.get("fileLength").and_then(Value::as_u64).unwrap_or(0),
+        download_url: file
+            .get("downloadUrl")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        sha1: curseforge_sha1(file),
+        version_id,
+    })
+}
+
+fn latest_pack_version(
+    client: &Client,
+    origin: &PackOrigin,
+    minecraft_version: &str,
+) -> AppResult<Option<PackVersionInfo>> {
+    match origin.platform.as_str() {
+        "modrinth" => {
+            let url = format!(
+                "{MODRINTH_API_URL}/project/{}/version?game_versions={}",
+                origin.project_id,
+                json!([minecraft_version])
+            );
+            // Modrinth devuelve las versiones de la más reciente a la más antigua.
+            Ok(get_json(client, &url, false)?
+                .as_array()
+                .and_then(|versions| versions.first())
+                .and_then(|version| modrinth_version_info(&origin.project_id, version)))
+        }
+        "curseforge" => {
+            let url = format!(
+                "{CURSEFORGE_API_URL}/mods/{}/files?gameVersion={minecraft_version}&pageSize=50",
+                origin.project_id
+            );
+            Ok(get_json(client, &url, true)?
+                .get("data")
+                .and_then(Value::as_array)
+                .and_then(|files| {
+                    files.iter().max_by_key(|file| {
+                        file.get("fileDate")
+                            .and_then(Value::as_str)
+                            .unwrap_or_default()
+                            .to_string()
+                    })
+                })
+                .and_then(|file| curseforge_version_info(&origin.project_id, file)))
+        }
+        other => Err(format!("Plataforma de modpack desconocida: {other}")),
+    }
+}
+
+fn pack_version(
+    client: &Client,
+    origin: &PackOrigin,
+    version_id: &str,
+) -> AppResult<PackVersionInfo> {
+    let info = match origin.platform.as_str() {
+        "modrinth" => {
+            let version = get_json(
+                client,
+                &format!("{MODRINTH_API_URL}/version/{version_id}"),
+                false,
+            )?;
+            if version.get("project_id").and_then(Value::as_str) != Some(origin.project_id.as_str())
+            {
+                return Err(format!(
+                    "La versión {version_id} no pertenece al modpack {}.",
+                    origin.project_id
+                ));
+            }
+            modrinth_version_info(&origin.project_id, &version)
+        }
+        "curseforge" => {
+            let url = format!(
+                "{CURSEFORGE_API_URL}/mods/{}/files/{version_id}",
+                origin.project_id
+            );
+            get_json(client, &url, true)?
+                .get("data")
+                .and_then(|file| curseforge_version_info(&origin.project_id, file))
+        }
+        other => return Err(format!("Plataforma de modpack desconocida: {other}")),
+    };
+    info.ok_or_else(|| format!("La versión {version_id} del modpack no tiene archivo descargable."))
+}
+
+/// Archivos de `modrinth.index.json` que usa el cliente: los `unsupported` en cliente
+/// (mods de servidor) se descartan.
+fn modrinth_index_files(index: &Value) -> Vec<PackFile> {
+    index
+        .get("files")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .enumerate()
+        .filter_map(|(position, file)| {
+            let client_env = file
+                .pointer("/env/client")
+                .and_then(Value::as_str)
+                .map(str::to_ascii_lowercase);
+            if client_env.as_deref() == Some("unsupported") {
+                return None;
+            }
+            Some(PackFile {
+                index: position,
+                client_env,
+                path: file.get("path")?.as_str()?.replace('\\', "/"),
+                sha1: file
+                    .pointer("/hashes/sha1")
+                    .and_then(Value::as_str)
+                    .map(str::to_string),
+                url: file
+                    .get("downloads")
+                    .and_then(Value::as_array)
+                    .and_then(|downloads| downloads.first())
+                    .and_then(Value::as_str)
+                    .map(str::to_string),
+            })
+        })
+        .collect()
+}
+
+/// Resuelve los `files` del manifest de CurseForge (proyecto + archivo) a nombre y URL.
+fn curseforge_manifest_files(client: &Client, manifest: &Value) -> AppResult<Vec<PackFile>> {
+    let file_ids = manifest
+        .get("files")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|file| file.get("fileID").and_then(Value::as_u64))
+        .collect::<Vec<_>>();
+    if file_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    let url = format!("{CURSEFORGE_API_URL}/mods/files");
+    let body = json!({ "fileIds": file_ids });
+    let response = rate_limit::send_blocking(&url, || {
+        client
+            .post(&url)
+            .header("x-api-key", curseforge_api_key())
+            .json(&body)
+    })
+    .map_err(|err| err.describe("No se pudo contactar con CurseForge"))?
+    .error_for_status()
+    .and_then(|response| response.json::<Value>())
+    .map_err(|err| format!("Respuesta inválida de CurseForge (files): {err}"))?;
+    Ok(response
+        .get("data")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .enumerate()
+        .filter_map(|(position, file)| {
+            Some(PackFile {
+                index: position,
+                client_env: None,
+                path: format!("mods/{}", file.get("fileName")?.as_str()?),
+                sha1: curseforge_sha1(file),
+                url: file
+                    .get("downloadUrl")
+                    .and_then(Value::as_str)
+                    .map(str::to_string),
+            })
+        })
+        .collect())
+}
+
+fn read_zip_json(archive: &mut ZipArchive<Cursor<Vec<u8>>>, name: &str) -> Option<Value> {
+    let mut entry = archive.by_name(name).ok()?;
+    let mut raw = String::new();
+    entry.read_to_string(&mut raw).ok()?;
+    serde_json::from_str(&raw).ok()
+}
+
+fn read_pack_contents(
+    client: &Client,
+    archive: &mut ZipArchive<Cursor<Vec<u8>>>,
+) -> AppResult<PackContents> {
+    let mut contents = if let Some(index) = read_zip_json(archive, "modrinth.index.json") {
+        let dependencies = index.get("dependencies");
+        PackContents {
+            files: modrinth_index_files(&index),
+            override_prefixes: vec!["overrides/".to_string(), "client-overrides/".to_string()],
+            minecraft_version: dependencies
+                .and_then(|deps| deps.get("minecraft"))
+                .and_then(Value::as_str)
+                .map(str::to_string),
+            loader_version: dependencies
+                .and_then(Value::as_object)
+                .and_then(|deps| {
+                    deps.iter()
+                        .find(|(name, _)| name.as_str() != "minecraft")
+                        .and_then(|(_, version)| version.as_str())
+                })
+                .map(str::to_string),
+            ..PackContents::default()
+        }
+    } else if let Some(manifest) = read_zip_json(archive, "manifest.json") {
+        let overrides = manifest
+            .get("overrides")
+            .and_then(Value::as_str)
+            .unwrap_or("overrides");
+        PackContents {
+            files: curseforge_manifest_files(client, &manifest)?,
+            override_prefixes: vec![format!("{}/", overrides.trim_end_matches('/'))],
+            minecraft_version: manifest
+                .pointer("/minecraft/version")
+                .and_then(Value::as_str)
+                .map(str::to_string),
+            loader_version: manifest
+                .pointer("/minecraft/modLoaders/0/id")
+                .and_then(Value::as_str)
+                .and_then(|id| id.split_once('-'))
+                .map(|(_, version)| version.to_string()),
+            ..PackContents::default()
+        }
+    } else {
+        return Err(
+            "El archivo descargado no es un modpack de Modrinth ni de CurseForge.".to_string(),
+        );
+    };
+
+    for index in 0..archive.len() {
+        let Ok(entry) = archive.by_index(index) else {
+            continue;
+        };
+        let name = entry.name().replace('\\', "/");
+        let relative = contents
+            .override_prefixes
+            .iter()
+            .find_map(|prefix| name.strip_prefix(prefix.as_str()));
+        if let Some(file_name) = relative.and_then(|relative| relative.strip_prefix("mods/")) {
+            if !file_name.is_empty() && !file_name.contains('/') {
+                contents.override_mods.push(file_name.to_string());
+            }
+        }
+    }
+    Ok(contents)
+}
+
+/// Ruta relativa del pack sin `..` ni componentes absolutos.
+fn safe_relative_path(path: &str) -> Option<PathBuf> {
+    let path = Path::new(path);
+    path.components()
+        .all(|component| matches!(component, Component::Normal(_)))
+        .then(|| path.to_path_buf())
+        .filter(|path| path.components().next().is_some())
+}
+
+/// Destino de un archivo del pack: `mods/` va a la carpeta de mods efectiva de la instancia.
+fn pack_target(minecraft_root: &Path, mods_dir: &Path, relative: &Path) -> PathBuf {
+    match relative.strip_prefix("mods") {
+        Ok(rest) => mods_dir.join(rest),
+        Err(_) => minecraft_root.join(relative),
+    }
+}
+
+fn pack_mod_name(path: &str) -> Option<&str> {
+    path.strip_prefix("mods/")
+        .filter(|name| !name.contains('/'))
+}
+
+/// Separa los archivos a instalar de los opcionales que se ofrecen. Un opcional que el
+/// usuario ya eligió (`chosen`) se sigue instalando y actualizando como los obligatorios.
+fn select_pack_files(
+    files: &[PackFile],
+    chosen: impl Fn(&PackFile) -> bool,
+) -> (Vec<PackFile>, Vec<OptionalPackFile>) {
+    let mut install = Vec::new();
+    let mut optional = Vec::new();
+    for file in files {
+        if !file.is_optional() || chosen(file) {
+            install.push(file.clone());
+        } else {
+            optional.push(file.to_optional());
+        }
+    }
+    (install, optional)
+}
+
+/// Compara los mods del manifest de importación con los del pack destino. Los archivos de
+/// `mods/` que no aparecen en ninguno de los dos son del usuario.
+fn diff_pack_mods(
+    previous: &[(String, String)],
+    target: &[PackFile],
+    override_mods: &[String],
+    installed: &[String],
+) -> PackModsDiff {
+    let before = previous
+        .iter()
+        .map(|(name, sha1)| (name.as_str(), sha1.as_str()))
+        .collect::<HashMap<_, _>>();
+    let mut after = target
+        .iter()
+        .filter_map(|file| Some((pack_mod_name(&file.path)?, file.sha1.as_deref())))
+        .collect::<HashMap<_, _>>();
+    for name in override_mods {
+        after.entry(name.as_str()).or_insert(None);
+    }
+
+    let mut diff = PackModsDiff {
+        added_mods: after
+            .keys()
+            .filter(|name| !before.contains_key(*name))
+            .map(|name| name.to_string())
+            .collect(),
+        removed_mods: before
+            .keys()
+            .filter(|name| !after.contains_key(*name))
+            .map(|name| name.to_string())
+            .collect(),
+        changed_mods: after
+            .iter()
+            .filter_map(|(name, sha1)| {
+                let previous_sha1 = before.get(name)?;
+                let current_sha1 = (*sha1)?;
+                (!previous_sha1.eq_ignore_ascii_case(current_sha1)).then(|| ChangedFile {
+                    path: name.to_string(),
+                    previous_sha1: previous_sha1.to_string(),
+                    current_sha1: current_sha1.to_string(),
+                })
+            })
+            .collect(),
+        user_mods: installed
+            .iter()
+            .filter(|name| {
+                let base = name.strip_suffix(".disabled").unwrap_or(name);
+                !before.contains_key(base) && !after.contains_key(base)
+            })
+            .cloned()
+            .collect(),
+        unchanged: false,
+    };
+    diff.added_mods.sort();
+    diff.removed_mods.sort();
+    diff.changed_mods.sort_by(|a, b| a.path.cmp(&b.path));
+    diff.user_mods.sort();
+    diff.unchanged =
+        diff.added_mods.is_empty() && diff.removed_mods.is_empty() && diff.changed_mods.is_empty();
+    diff
+}
+
+fn installed_mod_files(mods_dir: &Path) -> Vec<String> {
+    fs::read_dir(mods_dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| entry.path().is_file())
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .collect()
+}
+
+/// Archivos fuera de la carpeta de mods que la actualización sobrescribe, con su copia previa
+/// dentro del snapshot para poder deshacerla.
+struct PackFileBackup {
+    dir: PathBuf,
+    saved: Vec<(PathBuf, Option<PathBuf>)>,
+}
+
+impl PackFileBackup {
+    fn new(snapshot: &Path) -> Self {
+        Self {
+            dir: snapshot.join("pack-files"),
+            saved: Vec::new(),
+        }
+    }
+
+    /// Guarda el estado de `path` antes de escribirlo por primera vez (o que no existía).
+    fn save(&mut self, path: &Path) -> AppResult<()> {
+        if self.saved.iter().any(|(saved, _)| saved == path) {
+            return Ok(());
+        }
+        let backup = if path.is_file() {
+            fs::create_dir_all(&self.dir)
+                .map_err(|err| format!("No se pudo crear {}: {err}", self.dir.display()))?;
+            let backup = self.dir.join(self.saved.len().to_string());
+            fs::copy(path, &backup)
+                .map_err(|err| format!("No se pudo respaldar {}: {err}", path.display()))?;
+            Some(backup)
+        } else {
+            None
+        };
+        self.saved.push((path.to_path_buf(), backup));
+        Ok(())
+    }
+
+    fn restore(&self) -> AppResult<()> {
+        for (path, backup) in self.saved.iter().rev() {
+            match backup {
+                Some(backup) => {
+                    if let Some(parent) = path.parent() {
+                        fs::create_dir_all(parent).map_err(|err| {
+                            format!("No se pudo crear {}: {err}", parent.display())
+                        })?;
+                    }
+                    fs::copy(backup, path)
+                        .map_err(|err| format!("No se pudo restaurar {}: {err}", path.display()))?;
+                }
+                None if path.exists() => fs::remove_file(path)
+                    .map_err(|err| format!("No se pudo eliminar {}: {err}", path.display()))?,
+                None => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Copia los overrides del pack sobre el game dir. Devuelve cuántos archivos escribió.
+fn extract_overrides(
+    archive: &mut ZipArchive<Cursor<Vec<u8>>>,
+    prefixes: &[String],
+    minecraft_root: &Path,
+    mods_dir: &Path,
+    backup: &mut PackFileBackup,
+) -> AppResult<usize> {
+    let mut written = 0;
+    for index in 0..archive.len() {
+        let mut entry = archive
+            .by_index(index)
+            .map_err(|err| format!("Entrada inválida en el modpack: {err}"))?;
+        if entry.is_dir() {
+            continue;
+        }
+        let name = entry.name().replace('\\', "/");
+        let Some(relative) = prefixes
+            .iter()
+            .find_map(|prefix| name.strip_prefix(prefix.as_str()))
+            .and_then(safe_relative_path)
+        else {
+            continue;
+        };
+        let target = pack_target(minecraft_root, mods_dir, &relative);
+        backup.save(&target)?;
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|err| format!("No se pudo crear {}: {err}", parent.display()))?;
+        }
+        let mut file = fs::File::create(&target)
+            .map_err(|err| format!("No se pudo escribir {}: {err}", target.display()))?;
+        std::io::copy(&mut entry, &mut file)
+            .map_err(|err| format!("No se pudo escribir {}: {err}", target.display()))?;
+        written += 1;
+    }
+    Ok(written)
+}
+
+/// Busca una versión más reciente del modpack de origen para la misma versión de Minecraft.
+#[tauri::command]
+pub async fn check_pack_update(
+    app: AppHandle,
+    instance_root: String,
+) -> Result<PackUpdateCheck, String> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    tauri::async_runtime::spawn_blocking(move || {
+        let metadata = read_instance_metadata(instance_root.to_string())?;
+        let origin = metadata
+            .pack_origin
+            .ok_or_else(|| "La instancia no se importó desde un modpack conocido.".to_string())?;
+        let client = build_upgrade_client()?;
+        let latest = latest_pack_version(&client, &origin, &metadata.minecraft_version)?;
+        Ok(PackUpdateCheck {
+            instance_root: instance_root.to_string(),
+            update_available: latest
+                .as_ref()
+                .is_some_and(|latest| latest.version_id != origin.version_id),
+            platform: origin.platform,
+            project_id: origin.project_id,
+            current_version_id: origin.version_id,
+            current_version_number: origin.version_number,
+            latest,
+        })
+    })
+    .await
+    .map_err(|err| format!("Falló la tarea de búsqueda de actualizaciones del pack: {err}"))?
+}
+
+fn apply_pack_update(
+    app: &AppHandle,
+    validated: &ValidatedInstanceRoot,
+    origin: &PackOrigin,
+    target: &PackVersionInfo,
+    backup: &mut PackFileBackup,
+    warnings: &mut Vec<String>,
+) -> AppResult<(PackModsDiff, Vec<String>, usize, Vec<OptionalPackFile>)> {
+    let instance_root = validated.as_str();
+    let root = validated.path();
+    let minecraft_root = root.join("minecraft");
+    // El snapshot solo cubre los mods propios; una carpeta compartida no se podría revertir.
+    if let Some(shared) = shared_mods_dir(root) {
+        return Err(format!(
+            "La instancia usa una carpeta de mods compartida ({}); quítala antes de actualizar el pack.",
+            shared.display()
+        ));
+    }
+    let mods_dir = effective_mods_dir(root);
+    let client = build_upgrade_client()?;
+
+    emit_progress(
+        app,
+        instance_root,
+        "downloading_pack",
+        format!("Descargando {}...", target.name),
+    );
+    let url = target.download_url.as_deref().ok_or_else(|| {
+        "La plataforma no permite descargar esta versión del pack fuera de su app.".to_string()
+    })?;
+    let download_dir = root.join(PACK_DOWNLOAD_DIR);
+    fs::create_dir_all(&download_dir)
+        .map_err(|err| format!("No se pudo crear {}: {err}", download_dir.display()))?;
+    let archive_path = download_dir.join(format!("{}.zip", target.version_id));
+    download_mod_file(&client, url, &archive_path, target.sha1.as_deref())?;
+    let bytes = fs::read(&archive_path)
+        .map_err(|err| format!("No se pudo leer {}: {err}", archive_path.display()))?;
+    let _ = fs::remove_dir_all(&download_dir);
+    let mut archive = ZipArchive::new(Cursor::new(bytes))
+        .map_err(|err| format!("El modpack descargado no es un zip válido: {err}"))?;
+    let contents = read_pack_contents(&client, &mut archive)?;
+
+    emit_progress(
+        app,
+        instance_root,
+        "diff",
+        "Comparando con el pack importado...".to_string(),
+    );
+    let metadata = read_instance_metadata(instance_root.to_string())?;
+    if let Some(version) = contents.minecraft_version.as_deref() {
+        if version != metadata.minecraft_version {
+            return Err(format!(
+                "La versión {} del pack es para Minecraft {version} y la instancia usa {}.",
+                target.version_number, metadata.minecraft_version
+            ));
+        }
+    }
+    if let Some(version) = contents.loader_version.as_deref() {
+        if version != metadata.loader_version {
+            warnings.push(format!(
+                "El pack usa {} {version}; la instancia sigue con {}. Cámbialo en la configuración de la instancia si el pack lo necesita.",
+                metadata.loader, metadata.loader_version
+            ));
+        }
+    }
+    let previous = imported_mods(root).unwrap_or_else(|| {
+        warnings.push(
+            "No hay manifest de importación: ningún mod se considera del pack y no se borra nada."
+                .to_string(),
+        );
+        Vec::new()
+    });
+    let previous_names = previous
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect::<HashSet<_>>();
+    let (files, optional_files) =
+        select_pack_files(&contents.files, |file| match pack_mod_name(&file.path) {
+            Some(name) => previous_names.contains(name),
+            None => safe_relative_path(&file.path).is_some_and(|relative| {
+                pack_target(&minecraft_root, &mods_dir, &relative).is_file()
+            }),
+        });
+    if !optional_files.is_empty() {
+        log::info!(
+            "🔹 {} archivo(s) opcionales del pack sin instalar en {instance_root}",
+            optional_files.len()
+        );
+    }
+    let diff = diff_pack_mods(
+        &previous,
+        &files,
+        &contents.override_mods,
+        &installed_mod_files(&mods_dir),
+    );
+
+    emit_progress(
+        app,
+        instance_root,
+        "applying_mods",
+        format!(
+            "Aplicando cambios: {} nuevos, {} actualizados, {} quitados...",
+            diff.added_mods.len(),
+            diff.changed_mods.len(),
+            diff.removed_mods.len()
+        ),
+    );
+    fs::create_dir_all(&mods_dir)
+        .map_err(|err| format!("No se pudo crear {}: {err}", mods_dir.display()))?;
+    let mut kept_modified = Vec::new();
+    let previous_sha1 = previous.iter().cloned().collect::<HashMap<_, _>>();
+    for name in &diff.removed_mods {
+        let path = mods_dir.join(name);
+        if !path.is_file() {
+            continue;
+        }
+        let untouched = compute_file_sha1(&path).is_ok_and(|sha1| {
+            previous_sha1
+                .get(name)
+                .is_some_and(|old| old.eq_ignore_ascii_case(&sha1))
+        });
+        if untouched {
+            fs::remove_file(&path)
+                .map_err(|err| format!("No se pudo eliminar {}: {err}", path.display()))?;
+        } else {
+            kept_modified.push(name.clone());
+        }
+    }
+    for file in &files {
+        let Some(relative) = safe_relative_path(&file.path) else {
+            warnings.push(format!("Ruta insegura ignorada en el pack: {}", file.path));
+            continue;
+        };
+        let target_path = pack_target(&minecraft_root, &mods_dir, &relative);
+        let up_to_date = match file.sha1.as_deref() {
+            Some(sha1) => compute_file_sha1(&target_path)
+                .is_ok_and(|current| current.eq_ignore_ascii_case(sha1)),
+            None => target_path.is_file(),
+        };
+        if up_to_date {
+            continue;
+        }
+        let Some(url) = file.url.as_deref() else {
+            warnings.push(format!(
+                "{} no tiene descarga directa; descárgalo a mano.",
+                file.path
+            ));
+            continue;
+        };
+        // Los mods ya están en el snapshot; el resto (configs, resourcepacks...) no.
+        if pack_mod_name(&file.path).is_none() {
+            backup.save(&target_path)?;
+        }
+        if let Some(parent) = target_path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|err| format!("No se pudo crear {}: {err}", parent.display()))?;
+        }
+        download_mod_file(&client, url, &target_path, file.sha1.as_deref())?;
+    }
+
+    emit_progress(
+        app,
+        instance_root,
+        "overrides",
+        "Copiando overrides del pack...".to_string(),
+    );
+    let overrides_applied = extract_overrides(
+        &mut archive,
+        &contents.override_prefixes,
+        &minecraft_root,
+        &mods_dir,
+        backup,
+    )?;
+
+    emit_progress(
+        app,
+        instance_root,
+        "finalizing",
+        "Guardando la versión del pack...".to_string(),
+    );
+    let pack_mods = contents
+        .override_mods
+        .iter()
+        .map(|name| (name.clone(), None))
+        .chain(files.iter().filter_map(|file| {
+            Some((
+                pack_mod_name(&file.path)?.to_string(),
+                file.client_env.clone(),
+            ))
+        }))
+        .collect::<HashMap<_, _>>();
+    backup.save(&root.join(IMPORT_MANIFEST_FILE))?;
+    record_pack_import_manifest(root, &mods_dir, &pack_mods, optional_files.clone())?;
+    update_instance_metadata(instance_root, |metadata| {
+        metadata.pack_origin = Some(PackOrigin {
+            version_id: target.version_id.clone(),
+            version_number: target.version_number.clone(),
+            ..origin.clone()
+        });
+        Ok::<(), String>(())
+    })?;
+    Ok((diff, kept_modified, overrides_applied, optional_files))
+}
+
+/// Actualiza la instancia a otra versión de su modpack de origen. Los mods que añadió el
+/// usuario se quedan; antes de tocar nada se toma un snapshot que se restaura si algo falla.
+#[tauri::command]
+pub async fn update_pack(
+    app: AppHandle,
+    instance_root: String,
+    target_version_id: String,
+    override_lock: Option<bool>,
+) -> Result<PackUpdateReport, InstanceEditError> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    if is_instance_running(instance_root.as_str()) {
+        return Err(
+            "No se puede actualizar el pack de una instancia en ejecución."
+                .to_string()
+                .into(),
+        );
+    }
+    let metadata = read_instance_metadata(instance_root.to_string())?;
+    check_metadata_lock(
+        instance_root.as_str(),
+        &metadata,
+        "mods",
+        "update_pack",
+        override_lock.unwrap_or(false),
+    )?;
+    let origin = metadata
+        .pack_origin
+        .ok_or_else(|| "La instancia no se importó desde un modpack conocido.".to_string())?;
+
+    let report = tauri::async_runtime::spawn_blocking(move || {
+        emit_progress(
+            &app,
+            instance_root.as_str(),
+            "resolving",
+            "Consultando la versión del pack...".to_string(),
+        );
+        let target = pack_version(&build_upgrade_client()?, &origin, &target_version_id)?;
+
+        emit_progress(
+            &app,
+            instance_root.as_str(),
+            "snapshot",
+            "Creando snapshot de la instancia...".to_string(),
+        );
+        let root = instance_root.path();
+        let snapshot = snapshot_instance(root)?;
+        let mut backup = PackFileBackup::new(&snapshot);
+
+        let mut warnings = Vec::new();
+        match apply_pack_update(
+            &app,
+            &instance_root,
+            &origin,
+            &target,
+            &mut backup,
+            &mut warnings,
+        ) {
+            Ok((diff, kept_modified, overrides_applied, optional_files)) => {
+                log::info!(
+                    "✔ Pack de {instance_root} actualizado: {} -> {}",
+                    origin.version_id,
+                    target.version_id
+                );
+                Ok(PackUpdateReport {
+                    instance_root: instance_root.to_string(),
+                    previous_version: if origin.version_number.is_empty() {
+                        origin.version_id.clone()
+                    } else {
+                        origin.version_number.clone()
+                    },
+                    current_version: target.version_number.clone(),
+                    snapshot_path: snapshot.display().to_string(),
+                    diff,
+                    kept_modified,
+                    overrides_applied,
+                    optional_files,
+                    warnings,
+                })
+            }
+            Err(err) => {
+                log::warn!("⚠ Falló la actualización del pack de {instance_root}: {err}. Restaurando snapshot.");
+                let restored =
+                    restore_instance_snapshot(root, &snapshot).and_then(|()| backup.restore());
+                match restored {
+                    Ok(()) => Err(format!(
+                        "{err} (se restauró el snapshot {})",
+                        snapshot.display()
+                    )),
+                    Err(restore_err) => Err(format!(
+                        "{err} (no se pudo restaurar el snapshot {}: {restore_err})",
+                        snapshot.display()
+                    )),
+                }
+            }
+        }
+    })
+    .await
+    .map_err(|err| format!("Falló la tarea de actualización del pack: {err}"))?;
+    report.map_err(InstanceEditError::from)
+}
+
+/// Instala los opcionales elegidos (por su `index`) y los pasa del manifest de pendientes
+/// al de mods del pack con `env: optional`.
+fn install_optional_files(
+    instance_root: &Path,
+    file_indices: &[usize],
+    mut download: impl FnMut(&str, &Path, Option<&str>) -> AppResult<()>,
+) -> AppResult<OptionalPackInstallReport> {
+    let minecraft_root = instance_root.join("minecraft");
+    let mods_dir = effective_mods_dir(instance_root);
+    let pending = pending_optional_files(instance_root);
+    let mut report = OptionalPackInstallReport {
+        instance_root: instance_root.display().to_string(),
+        installed: Vec::new(),
+        remaining: Vec::new(),
+        warnings: Vec::new(),
+    };
+    for index in file_indices {
+        if !pending.iter().any(|file| file.index == *index) {
+            report.warnings.push(format!(
+                "El archivo {index} no es un opcional pendiente del pack."
+            ));
+        }
+    }
+    let mut pack_mods = imported_mod_envs(instance_root);
+    for file in pending {
+        if !file_indices.contains(&file.index) {
+            report.remaining.push(file);
+            continue;
+        }
+        let (Some(relative), Some(url)) = (safe_relative_path(&file.path), file.url.as_deref())
+        else {
+            report.warnings.push(format!(
+                "{} no tiene ruta segura o descarga directa; descárgalo a mano.",
+                file.path
+            ));
+            report.remaining.push(file);
+            continue;
+        };
+        let target = pack_target(&minecraft_root, &mods_dir, &relative);
+        // Un fallo deja el archivo pendiente y no impide registrar los que sí se instalaron.
+        let installed = match target.parent() {
+            Some(parent) => fs::create_dir_all(parent)
+                .map_err(|err| format!("No se pudo crear {}: {err}", parent.display())),
+            None => Ok(()),
+        }
+        .and_then(|()| download(url, &target, file.sha1.as_deref()));
+        if let Err(err) = installed {
+            report
+                .warnings
+                .push(format!("No se pudo instalar {}: {err}", file.path));
+            report.remaining.push(file);
+            continue;
+        }
+        if let Some(name) = pack_mod_name(&file.path) {
+            pack_mods.insert(name.to_string(), Some("optional".to_string()));
+        }
+        report.installed.push(file.path);
+    }
+    record_pack_import_manifest(
+        instance_root,
+        &mods_dir,
+        &pack_mods,
+        report.remaining.clone(),
+    )?;
+    Ok(report)
+}
+
+/// Instala archivos opcionales del pack que se ofrecieron al importarlo o actualizarlo.
+#[tauri::command]
+pub async fn install_optional_pack_files(
+    app: AppHandle,
+    instance_root: String,
+    file_indices: Vec<usize>,
+) -> Result<OptionalPackInstallReport, InstanceEditError> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    if is_instance_running(instance_root.as_str()) {
+        return Err("No se pueden instalar mods en una instancia en ejecución."
+            .to_string()
+            .into());
+    }
+    let metadata = read_instance_metadata(instance_root.to_string())?;
+    check_metadata_lock(
+        instance_root.as_str(),
+        &metadata,
+        "mods",
+        "install_optional_pack_files",
+        false,
+    )?;
+    let report = tauri::async_runtime::spawn_blocking(move || {
+        let client = build_upgrade_client()?;
+        install_optional_files(instance_root.path(), &file_indices, |url, target, sha1| {
+            download_mod_file(&client, url, target, sha1)
+        })
+    })
+    .await
+    .map_err(|err| format!("Falló la instalación de opcionales del pack: {err}"))?;
+    report.map_err(InstanceEditError::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pack_file(path: &str, sha1: &str) -> PackFile {
+        PackFile {
+            index: 0,
+            path: path.to_string(),
+            sha1: Some(sha1.to_string()),
+            url: Some(format!("https://cdn.modrinth.com/{path}")),
+            client_env: None,
+        }
+    }
+
+    #[test]
+    fn diff_reports_pack_changes_and_leaves_user_mods_alone() {
+        let previous = vec![
+            ("create-0.5.1.jar".to_string(), "aaa".to_string()),
+            ("jei-15.2.jar".to_string(), "bbb".to_string()),
+            ("old-lib.jar".to_string(), "ccc".to_string()),
+        ];
+        let target = vec![
+            pack_file("mods/create-0.5.1.jar", "AAA"),
+            pack_file("mods/jei-15.2.jar", "ddd"),
+            pack_file("mods/sodium-0.5.jar", "eee"),
+            pack_file("resourcepacks/faithful.zip", "fff"),
+        ];
+        let installed = vec![
+            "create-0.5.1.jar".to_string(),
+            "jei-15.2.jar".to_string(),
+            "old-lib.jar".to_string(),
+            "minimap-user.jar".to_string(),
+            "bundled.jar".to_string(),
+        ];
+        let diff = diff_pack_mods(&previous, &target, &["bundled.jar".to_string()], &installed);
+
+        assert_eq!(diff.added_mods, vec!["bundled.jar", "sodium-0.5.jar"]);
+        assert_eq!(diff.removed_mods, vec!["old-lib.jar"]);
+        assert_eq!(
+            diff.changed_mods,
+            vec![ChangedFile {
+                path: "jei-15.2.jar".to_string(),
+                previous_sha1: "bbb".to_string(),
+                current_sha1: "ddd".to_string(),
+            }]
+        );
+        assert_eq!(diff.user_mods, vec!["minimap-user.jar"]);
+        assert!(!diff.unchanged);
+
+        assert_eq!(safe_relative_path("../mods/evil.jar"), None);
+        assert_eq!(
+            safe_relative_path("config/pack.toml"),
+            Some(PathBuf::from("config/pack.toml"))
+        );
+    }
+
+    #[test]
+    fn mrpack_env_selects_required_and_offers_optional_files_later() {
+        use std::io::Write;
+
+        let index = json!({
+            "formatVersion": 1,
+            "dependencies": { "minecraft": "1.20.1", "fabric-loader": "0.15.7" },
+            "files": [
+                {
+                    "path": "mods/sodium.jar",
+                    "hashes": { "sha1": "aaa" },
+                    "env": { "client": "required", "server": "unsupported" },
+                    "downloads": ["https://cdn.modrinth.com/sodium.jar"]
+                },
+                {
+                    "path": "mods/server-utils.jar",
+                    "hashes": { "sha1": "bbb" },
+                    "env": { "client": "unsupported", "server": "required" },
+                    "downloads": ["https://cdn.modrinth.com/server-utils.jar"]
+                },
+                {
+                    "path": "mods/zoomify.jar",
+                    "env": { "client": "optional", "server": "unsupported" },
+                    "downloads": ["https://cdn.modrinth.com/zoomify.jar"]
+                }
+            ]
+        });
+        let mut buffer = Cursor::new(Vec::new());
+        {
+            let mut writer = zip::ZipWriter::new(&mut buffer);
+            writer
+                .start_file(
+                    "modrinth.index.json",
+                    zip::write::SimpleFileOptions::default(),
+                )
+                .expect("índice");
+            writer
+                .write_all(index.to_string().as_bytes())
+                .expect("índice");
+            writer.finish().expect("mrpack");
+        }
+        let mut archive = ZipArchive::new(Cursor::new(buffer.into_inner())).expect("zip");
+        let contents = read_pack_contents(&Client::new(), &mut archive).expect("contenido");
+        assert_eq!(contents.minecraft_version.as_deref(), Some("1.20.1"));
+
+        let (install, optional) = select_pack_files(&contents.files, |_| false);
+        let installed = install
+            .iter()
+            .map(|file| file.path.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(installed, vec!["mods/sodium.jar"]);
+        assert_eq!(
+            optional,
+            vec![OptionalPackFile {
+                index: 2,
+                path: "mods/zoomify.jar".to_string(),
+                sha1: None,
+                url: Some("https://cdn.modrinth.com/zoomify.jar".to_string()),
+            }]
+        );
+        // Un opcional elegido antes se sigue instalando.
+        let (install, optional) =
+            select_pack_files(&contents.files, |file| file.path == "mods/zoomify.jar");
+        assert_eq!(install.len(), 2);
+        assert!(optional.is_empty());
+
+        let root = std::env::temp_dir().join(format!("pack-optional-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let mods_dir = root.join("minecraft").join("mods");
+        fs::create_dir_all(&mods_dir).expect("mods");
+        fs::write(mods_dir.join("sodium.jar"), "sodium").expect("sodium");
+        let pack_mods = HashMap::from([("sodium.jar".to_string(), Some("required".to_string()))]);
+        let (_, optional) = select_pack_files(&contents.files, |_| false);
+        record_pack_import_manifest(&root, &mods_dir, &pack_mods, optional).expect("manifest");
+        assert_eq!(pending_optional_files(&root).len(), 1);
+
+        let report =
+            install_optional_files(&root, &[2], |_, _, _| Err("conexión rechazada".to_string()))
+                .expect("un fallo de descarga no aborta");
+        assert!(report.installed.is_empty());
+        assert_eq!(report.remaining.len(), 1);
+        assert!(report.warnings[0].contains("conexión rechazada"));
+        assert_eq!(pending_optional_files(&root).len(), 1);
+
+        let mut downloaded = Vec::new();
+        let report = install_optional_files(&root, &[2, 7], |url, target, _| {
+            downloaded.push(url.to_string());
+            fs::write(target, "zoomify").map_err(|err| err.to_string())
+        })
+        .expect("opcionales");
+        assert_eq!(downloaded, vec!["https://cdn.modrinth.com/zoomify.jar"]);
+        assert_eq!(report.installed, vec!["mods/zoomify.jar"]);
+        assert!(report.remaining.is_empty());
+        assert_eq!(report.warnings.len(), 1);
+        assert!(mods_dir.join("zoomify.jar").is_file());
+        assert!(pending_optional_files(&root).is_empty());
+        let envs = imported_mod_envs(&root);
+        assert_eq!(envs["sodium.jar"].as_deref(), Some("required"));
+        assert_eq!(envs["zoomify.jar"].as_deref(), Some("optional"));
+
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn origin_is_read_from_modrinth_and_curseforge_app_instances() {
+        let dir = std::env::temp_dir().join(format!("pack-origin-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).expect("dir");
+
+        fs::write(
+            dir.join("minecraftinstance.json"),
+            r#"{"installedModpack":{"addonID":925200,"installedFile":{"id":5123456,"displayName":"ATM9 0.2.44"}}}"#,
+        )
+        .expect("curseforge");
+        assert_eq!(
+            pack_origin_from_source(&dir),
+            Some(PackOrigin {
+                platform: "curseforge".to_string(),
+                project_id: "925200".to_string(),
+                version_id: "5123456".to_string(),
+                version_number: "ATM9 0.2.44".to_string(),
+            })
+        );
+
+        fs::write(
+            dir.join("profile.json"),
+            r#"{"game_version":"1.20.1","linked_data":{"project_id":"1KVo5zza","version_id":"Xy12AbCd","locked":true}}"#,
+        )
+        .expect("modrinth");
+        let origin = pack_origin_from_source(&dir).expect("origen");
+        assert_eq!(origin.platform, "modrinth");
+        assert_eq!(origin.version_id, "Xy12AbCd");
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn failed_update_restores_overridden_files_and_import_manifest() {
+        use std::io::Write;
+
+        let root = std::env::temp_dir().join(format!("pack-rollback-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let minecraft_root = root.join("minecraft");
+        let mods_dir = minecraft_root.join("mods");
+        fs::create_dir_all(minecraft_root.join("config")).expect("config");
+        fs::write(minecraft_root.join("config/create.toml"), "viejo").expect("config");
+        fs::write(minecraft_root.join("options.txt"), "fov:90").expect("options");
+        fs::write(root.join(IMPORT_MANIFEST_FILE), "manifest viejo").expect("manifest");
+
+        let mut buffer = Cursor::new(Vec::new());
+        {
+            let mut writer = zip::ZipWriter::new(&mut buffer);
+            for (name, body) in [
+                ("overrides/config/create.toml", "nuevo"),
+                ("overrides/options.txt", "fov:70"),
+                ("overrides/resourcepacks/pack.zip", "pack"),
+            ] {
+                writer
+                    .start_file(name, zip::write::SimpleFileOptions::default())
+                    .expect("entrada");
+                writer.write_all(body.as_bytes()).expect("entrada");
+            }
+            writer.finish().expect("zip");
+        }
+        let mut archive = ZipArchive::new(Cursor::new(buffer.into_inner())).expect("zip");
+        let snapshot = root.join("snapshot");
+        let mut backup = PackFileBackup::new(&snapshot);
+        let written = extract_overrides(
+            &mut archive,
+            &["overrides/".to_string()],
+            &minecraft_root,
+            &mods_dir,
+            &mut backup,
+        )
+        .expect("overrides");
+        assert_eq!(written, 3);
+        backup
+            .save(&root.join(IMPORT_MANIFEST_FILE))
+            .expect("respaldo");
+        fs::write(root.join(IMPORT_MANIFEST_FILE), "manifest nuevo").expect("manifest");
+
+        backup.restore().expect("restaurar");
+        let read = |path: PathBuf| fs::read_to_string(path).expect("leer");
+        assert_eq!(read(minecraft_root.join("config/create.toml")), "viejo");
+        assert_eq!(read(minecraft_root.join("options.txt")), "fov:90");
+        assert_eq!(read(root.join(IMPORT_MANIFEST_FILE)), "manifest viejo");
+        assert!(!minecraft_root.join("resourcepacks/pack.zip").exists());
+
+        let _ = fs::remove_dir_all(root);
+    }
+}
@@ -0,0 +1,700 @@
+// Informe de rendimiento de la última sesión.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+use regex::Regex;
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::{
+    app::{
+        instance_service::read_instance_metadata,
+        runtime_metrics::{read_session_metrics, SessionMetrics},
+        startup_profile::{latest_startup_profile, StartupProfile},
+        trusted_root::resolve_trusted_instance_root,
+    },
+    platform::memory::total_memory_mb,
+};
+
+const MB: f64 = 1024.0 * 1024.0;
+/// Pausas de GC a partir de las cuales se notan tirones.
+const LONG_PAUSE_MS: f64 = 200.0;
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    High,
+    Medium,
+    Low,
+}
+
+impl Severity {
+    fn label(self) -> &'static str {
+        match self {
+            Severity::High => "ALTA",
+            Severity::Medium => "MEDIA",
+            Severity::Low => "BAJA",
+        }
+    }
+}
+
+/// Estadísticas del log de GC de la última sesión.
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct GcStats {
+    pub pause_count: usize,
+    pub avg_pause_ms: f64,
+    pub max_pause_ms: f64,
+    /// Pausas de más de `LONG_PAUSE_MS`.
+    pub long_pauses: usize,
+    pub full_gc_count: usize,
+    /// Heap ocupado justo antes de la recolección más llena.
+    pub peak_heap_used_mb: f64,
+    /// Tamaño máximo del heap que reportó el GC.
+    pub heap_capacity_mb: f64,
+    /// MB asignados por segundo entre la primera y la última recolección.
+    pub allocation_rate_mb_s: Option<f64>,
+}
+
+/// Una recolección del log.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct GcEvent {
+    uptime_secs: Option<f64>,
+    before_mb: f64,
+    after_mb: f64,
+    capacity_mb: f64,
+    pause_ms: f64,
+    full: bool,
+}
+
+struct GcPatterns {
+    /// JDK 9+: `[12.345s][info][gc] GC(3) Pause Young (Normal) (G1 Evacuation Pause) 512M->128M(2048M) 3.456ms`.
+    unified: Regex,
+    uptime: Regex,
+    /// JDK 8: `12.345: [GC (Allocation Failure)  123456K->23456K(987654K), 0.0123456 secs]`;
+    /// con `-XX:+PrintGCDetails` los totales van después del detalle de cada generación.
+    legacy: Regex,
+}
+
+static GC_PATTERNS: OnceLock<GcPatterns> = OnceLock::new();
+
+fn gc_patterns() -> &'static GcPatterns {
+    GC_PATTERNS.get_or_init(|| GcPatterns {
+        unified: Regex::new(
+            r"GC\(\d+\) Pause (\w+)[^\n]*? (\d+(?:\.\d+)?)([KMG])->(\d+(?:\.\d+)?)([KMG])\((\d+(?:\.\d+)?)([KMG])\) (\d+(?:\.\d+)?)ms",
+        )
+        .expect("Regex de GC unificado inválida"),
+        uptime: Regex::new(r"\[(\d+(?:\.\d+)?)s\]").expect("Regex de uptime de GC inválida"),
+        legacy: Regex::new(
+            r"(?:(\d+\.\d+): )?\[(Full GC|GC).*?(\d+)K->(\d+)K\((\d+)K\), (\d+\.\d+) secs\]",
+        )
+        .expect("Regex de GC de Java 8 inválida"),
+    })
+}
+
+fn to_mb(value: &str, unit: &str) -> f64 {
+    let value = value.parse::<f64>().unwrap_or(0.0);
+    match unit {
+        "K" => value / 1024.0,
+        "G" => value * 1024.0,
+        _ => value,
+    }
+}
+
+fn parse_gc_events(log: &str) -> Vec<GcEvent> {
+    let patterns = gc_patterns();
+    log.lines()
+        .filter_map(|line| {
+            if let Some(captures) = patterns.unified.captures(line) {
+                return Some(GcEvent {
+                    uptime_secs: patterns
+                        .uptime
+                        .captures(line)
+                        .and_then(|uptime| uptime[1].parse().ok()),
+                    before_mb: to_mb(&captures[2], &captures[3]),
+                    after_mb: to_mb(&captures[4], &captures[5]),
+                    capacity_mb: to_mb(&captures[6], &captures[7]),
+                    pause_ms: captures[8].parse().unwrap_or(0.0),
+                    full: &captures[1] == "Full",
+                });
+            }
+            let captures = patterns.legacy.captures(line)?;
+            Some(GcEvent {
+                uptime_secs: captures
+                    .get(1)
+                    .and_then(|value| value.as_str().parse().ok()),
+                before_mb: to_mb(&captures[3], "K"),
+                after_mb: to_mb(&captures[4], "K"),
+                capacity_mb: to_mb(&captures[5], "K"),
+                pause_ms: captures[6].parse::<f64>().unwrap_or(0.0) * 1000.0,
+                full: &captures[2] == "Full GC",
+            })
+        })
+        .collect()
+}
+
+/// Estadísticas de un log de GC; `None` si no contiene ninguna pausa reconocible.
+pub(crate) fn parse_gc_log(log: &str) -> Option<GcStats> {
+    let events = parse_gc_events(log);
+    if events.is_empty() {
+        return None;
+    }
+    let pauses = events.iter().map(|event| event.pause_ms);
+    let allocated = events
+        .windows(2)
+        .map(|pair| (pair[1].before_mb - pair[0].after_mb).max(0.0))
+        .sum::<f64>();
+    let elapsed = match (
+        events.first().and_then(|event| event.uptime_secs),
+        events.last().and_then(|event| event.uptime_secs),
+    ) {
+        (Some(first), Some(last)) if last > first => Some(last - first),
+        _ => None,
+    };
+    Some(GcStats {
+        pause_count: events.len(),
+        avg_pause_ms: pauses.clone().sum::<f64>() / events.len() as f64,
+        max_pause_ms: pauses.fold(0.0, f64::max),
+        long_pauses: events
+            .iter()
+            .filter(|event| event.pause_ms > LONG_PAUSE_MS)
+            .count(),
+        full_gc_count: events.iter().filter(|event| event.full).count(),
+        peak_heap_used_mb: events
+            .iter()
+            .map(|event| event.before_mb)
+            .fold(0.0, f64::max),
+        heap_capacity_mb: events
+            .iter()
+            .map(|event| event.capacity_mb)
+            .fold(0.0, f64::max),
+        allocation_rate_mb_s: elapsed.map(|secs| allocated / secs),
+    })
+}
+
+/// Archivo del log de GC según los flags de la sesión, relativo a la carpeta de juego.
+fn gc_log_path(game_dir: &Path, jvm_flags: &[String]) -> Option<PathBuf> {
+    let configured = jvm_flags.iter().find_map(|flag| {
+        if let Some(path) = flag.strip_prefix("-Xloggc:") {
+            return Some(path.to_string());
+        }
+        let options = flag.strip_prefix("-Xlog:gc")?;
+        let file = options.split("file=").nth(1)?;
+        Some(file.split(':').next()?.trim_matches('"').to_string())
+    });
+    match configured {
+        // Con `%p`/`%t` el nombre cambia en cada sesión: no se puede adivinar.
+        Some(path) if !path.contains('%') => {
+            let path = PathBuf::from(path);
+            Some(if path.is_absolute() {
+                path
+            } else {
+                game_dir.join(path)
+            })
+        }
+        Some(_) => None,
+        None => ["logs/gc.log", "gc.log"]
+            .iter()
+            .map(|candidate| game_dir.join(candidate))
+            .find(|candidate| candidate.is_file()),
+    }
+}
+
+/// Datos que evalúan las reglas.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ReportInputs {
+    pub ram_mb: u32,
+    pub system_memory_mb: Option<u64>,
+    pub auto_jvm_tuning: bool,
+    pub preferred_gpu: Option<String>,
+    pub metrics: SessionMetrics,
+    pub gc: Option<GcStats>,
+    pub startup: Option<StartupProfile>,
+}
+
+impl ReportInputs {
+    /// Heap disponible: el que reportó el GC o, sin log, la RAM asignada.
+    fn heap_mb(&self) -> f64 {
+        self.gc
+            .as_ref()
+            .map(|gc| gc.heap_capacity_mb)
+            .filter(|capacity| *capacity > 0.0)
+            .unwrap_or(f64::from(self.ram_mb))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PerformanceFinding {
+    pub id: &'static str,
+    pub severity: Severity,
+    pub message: String,
+}
+
+struct Rule {
+    id: &'static str,
+    severity: Severity,
+    check: fn(&ReportInputs) -> Option<String>,
+}
+
+/// Reglas en orden de prioridad dentro de cada gravedad.
+const RULES: &[Rule] = &[
+    Rule {
+        id: "heap_pressure",
+        severity: Severity::High,
+        check: |inputs| {
+            let gc = inputs.gc.as_ref()?;
+            let used = gc.peak_heap_used_mb / inputs.heap_mb();
+            (used >= 0.9).then(|| {
+                format!(
+                    "El heap llega al {:.0}% en el pico; sube ram_mb (ahora {} MB).",
+                    used * 100.0,
+                    inputs.ram_mb
+                )
+            })
+        },
+    },
+    Rule {
+        id: "long_gc_pauses",
+        severity: Severity::High,
+        check: |inputs| {
+            let gc = inputs.gc.as_ref()?;
+            (gc.max_pause_ms > LONG_PAUSE_MS).then(|| {
+                let advice = if inputs.auto_jvm_tuning {
+                    "revisa los mods que más memoria usan o sube ram_mb"
+                } else {
+                    "prueba el ajuste automático de JVM (preset G1)"
+                };
+                format!(
+                    "Las pausas de GC llegan a {:.0} ms ({} de más de {LONG_PAUSE_MS:.0} ms); {advice}.",
+                    gc.max_pause_ms, gc.long_pauses
+                )
+            })
+        },
+    },
+    Rule {
+        id: "ram_exceeds_system",
+        severity: Severity::High,
+        check: |inputs| {
+            let total = inputs.system_memory_mb?;
+            (u64::from(inputs.ram_mb) * 4 > total * 3).then(|| {
+                format!(
+                    "La instancia tiene asignados {} MB de {total} MB del equipo; el sistema acabará usando swap. Baja ram_mb.",
+                    inputs.ram_mb
+                )
+            })
+        },
+    },
+    Rule {
+        id: "full_gcs",
+        severity: Severity::Medium,
+        check: |inputs| {
+            let gc = inputs.gc.as_ref()?;
+            (gc.full_gc_count > 0).then(|| {
+                format!(
+                    "Hubo {} recolecciones completas (Full GC), que congelan el juego; suele indicar falta de heap.",
+                    gc.full_gc_count
+                )
+            })
+        },
+    },
+    Rule {
+        id: "cpu_saturated",
+        severity: Severity::Medium,
+        check: |inputs| {
+            let metrics = &inputs.metrics;
+            let capacity = metrics.logical_cpus.max(1) as f64 * 100.0;
+            (metrics.avg_cpu_percent >= capacity * 0.85).then(|| {
+                format!(
+                    "El juego usa de media el {:.0}% de la CPU del equipo; cierra otros programas o baja la distancia de renderizado.",
+                    metrics.avg_cpu_percent * 100.0 / capacity
+                )
+            })
+        },
+    },
+    Rule {
+        id: "startup_dominated_by_mod",
+        severity: Severity::Medium,
+        check: |inputs| {
+            let startup = inputs.startup.as_ref()?;
+            let slowest = startup.top_mods.first()?;
+            let share = slowest.millis as f64 / (startup.total_secs * 1000.0);
+            (startup.total_secs > 0.0 && share >= 0.3).then(|| {
+                format!(
+                    "El arranque está dominado por {} ({:.1} s, {:.0}% del total).",
+                    slowest.mod_id,
+                    slowest.millis as f64 / 1000.0,
+                    share * 100.0
+                )
+            })
+        },
+    },
+    Rule {
+        id: "gpu_preference_not_applied",
+        severity: Severity::Medium,
+        check: |inputs| {
+            let preferred = inputs.preferred_gpu.as_deref()?;
+            let applied = inputs
+                .metrics
+                .gpu_preference
+                .as_deref()
+                .is_some_and(|line| !line.starts_with('⚠'));
+            (!applied).then(|| {
+                format!("La GPU preferida ('{preferred}') no se aplicó en la última sesión; puede estar usando la integrada.")
+            })
+        },
+    },
+    Rule {
+        id: "heap_over_allocated",
+        severity: Severity::Low,
+        check: |inputs| {
+            let gc = inputs.gc.as_ref()?;
+            let used = gc.peak_heap_used_mb / inputs.heap_mb();
+            (inputs.ram_mb >= 6144 && used < 0.35).then(|| {
+                format!(
+                    "El heap nunca pasó del {:.0}% de {} MB; con ~{} MB bastaría y el GC recorrería menos memoria.",
+                    used * 100.0,
+                    inputs.ram_mb,
+                    ((gc.peak_heap_used_mb * 1.5 / 512.0).ceil() * 512.0).max(2048.0) as u32
+                )
+            })
+        },
+    },
+    Rule {
+        id: "slow_startup",
+        severity: Severity::Low,
+        check: |inputs| {
+            let startup = inputs.startup.as_ref()?;
+            (startup.total_secs > 120.0).then(|| {
+                format!(
+                    "El juego tardó {:.0} s en llegar al menú; revisa los mods más lentos del perfil de arranque.",
+                    startup.total_secs
+                )
+            })
+        },
+    },
+    Rule {
+        id: "no_gc_log",
+        severity: Severity::Low,
+        check: |inputs| {
+            inputs.gc.is_none().then(|| {
+                "Sin log de GC no se pueden analizar las pausas ni el heap; añade -Xlog:gc*:file=logs/gc.log a los argumentos de Java y juega otra sesión.".to_string()
+            })
+        },
+    },
+];
+
+/// Evalúa todas las reglas y ordena los hallazgos por gravedad.
+pub(crate) fn evaluate_rules(inputs: &ReportInputs) -> Vec<PerformanceFinding> {
+    let mut findings = RULES
+        .iter()
+        .filter_map(|rule| {
+            (rule.check)(inputs).map(|message| PerformanceFinding {
+                id: rule.id,
+                severity: rule.severity,
+                message,
+            })
+        })
+        .collect::<Vec<_>>();
+    findings.sort_by_key(|finding| finding.severity);
+    findings
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PerformanceReport {
+    pub instance_name: String,
+    pub session_recorded_at: String,
+    pub ram_mb: u32,
+    pub metrics: SessionMetrics,
+    pub gc: Option<GcStats>,
+    pub startup: Option<StartupProfile>,
+    pub findings: Vec<PerformanceFinding>,
+    /// Resumen en texto para pegar en Discord.
+    pub summary: String,
+}
+
+pub(crate) fn render_summary(
+    instance_name: &str,
+    inputs: &ReportInputs,
+    findings: &[PerformanceFinding],
+) -> String {
+    let metrics = &inputs.metrics;
+    let mut lines = vec![
+        format!("**Informe de rendimiento: {instance_name}**"),
+        format!(
+            "Sesión de {} min · RAM asignada {} MB · RSS medio/pico {:.0}/{:.0} MB · CPU media/pico {:.0}%/{:.0}% ({} núcleos)",
+            metrics.duration_secs / 60,
+            inputs.ram_mb,
+            metrics.avg_rss_bytes as f64 / MB,
+            metrics.peak_rss_bytes as f64 / MB,
+            metrics.avg_cpu_percent,
+            metrics.peak_cpu_percent,
+            metrics.logical_cpus
+        ),
+    ];
+    match &inputs.gc {
+        Some(gc) => lines.push(format!(
+            "GC: {} pausas, media {:.1} ms, máx {:.0} ms, {} Full GC, heap pico {:.0}/{:.0} MB{}",
+            gc.pause_count,
+            gc.avg_pause_ms,
+            gc.max_pause_ms,
+            gc.full_gc_count,
+            gc.peak_heap_used_mb,
+            inputs.heap_mb(),
+            gc.allocation_rate_mb_s
+                .map(|rate| format!(", {rate:.0} MB/s asignados"))
+                .unwrap_or_default()
+        )),
+        None => lines.push("GC: sin log".to_string()),
+    }
+    if let Some(startup) = &inputs.startup {
+        let slowest = startup
+            .top_mods
+            .iter()
+            .take(3)
+            .map(|entry| format!("{} ({:.1} s)", entry.mod_id, entry.millis as f64 / 1000.0))
+            .collect::<Vec<_>>();
+        lines.push(format!(
+            "Arranque: {:.0} s{}",
+            startup.total_secs,
+            if slowest.is_empty() {
+                String::new()
+            } else {
+                format!("; más lentos: {}", slowest.join(", "))
+            }
+        ));
+    }
+    lines.push(format!(
+        "GPU: {}",
+        metrics
+            .gpu_preference
+            .as_deref()
+            .or(inputs.preferred_gpu.as_deref())
+            .unwrap_or("la del sistema")
+    ));
+    if !metrics.jvm_flags.is_empty() {
+        lines.push(format!("Flags JVM: `{}`", metrics.jvm_flags.join(" ")));
+    }
+    if findings.is_empty() {
+        lines.push("Sin problemas detectados.".to_string());
+    } else {
+        lines.push("Hallazgos:".to_string());
+        lines.extend(findings.iter().enumerate().map(|(index, finding)| {
+            format!(
+                "{}. [{}] {}",
+                index + 1,
+                finding.severity.label(),
+                finding.message
+            )
+        }));
+    }
+    lines.join("\n")
+}
+
+/// Analiza la última sesión de la instancia. Necesita al menos una sesión con métricas.
+#[tauri::command]
+pub fn generate_performance_report(
+    app: AppHandle,
+    instance_root: String,
+) -> Result<PerformanceReport, String> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    let root = instance_root.path();
+    let metadata = read_instance_metadata(instance_root.to_string())?;
+    let metrics = read_session_metrics(root)?.ok_or_else(|| {
+        "Juega al menos una sesión con esta instancia para generar el informe de rendimiento."
+            .to_string()
+    })?;
+    let gc = gc_log_path(&root.join("minecraft"), &metrics.jvm_flags)
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|log| parse_gc_log(&log));
+    let inputs = ReportInputs {
+        ram_mb: metadata.ram_mb,
+        system_memory_mb: total_memory_mb(),
+        auto_jvm_tuning: metadata.auto_jvm_tuning,
+        preferred_gpu: metadata.preferred_gpu.clone(),
+        metrics,
+        gc,
+        startup: latest_startup_profile(root),
+    };
+    let findings = evaluate_rules(&inputs);
+    let summary = render_summary(&metadata.name, &inputs, &findings);
+    Ok(PerformanceReport {
+        instance_name: metadata.name,
+        session_recorded_at: inputs.metrics.recorded_at.clone(),
+        ram_mb: inputs.ram_mb,
+        metrics: inputs.metrics,
+        gc: inputs.gc,
+        startup: inputs.startup,
+        findings,
+        summary,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::startup_profile::ModLoadTime;
+
+    const G1_HEALTHY: &str = "\
+[0.010s][info][gc,init] Heap Max Capacity: 4G
+[10.000s][info][gc] GC(0) Pause Young (Normal) (G1 Evacuation Pause) 400M->100M(4096M) 8.000ms
+[20.000s][info][gc] GC(1) Pause Young (Normal) (G1 Evacuation Pause) 600M->150M(4096M) 12.000ms
+[30.000s][info][gc] GC(2) Pause Young (Normal) (G1 Evacuation Pause) 650M->160M(4096M) 10.000ms";
+
+    const G1_STARVED: &str = "\
+[5.000s][info][gc] GC(0) Pause Young (Normal) (G1 Evacuation Pause) 1900M->1700M(2048M) 150.000ms
+[6.000s][info][gc] GC(1) Pause Full (G1 Compaction Pause) 2000M->1800M(2048M) 850.000ms
+[7.000s][info][gc] GC(2) Pause Young (Normal) (G1 Evacuation Pause) 1980M->1850M(2048M) 320.000ms";
+
+    const JAVA8_LOG: &str = "\
+3.210: [GC (Allocation Failure)  524288K->65536K(2097152K), 0.0120000 secs]
+9.870: [Full GC (Ergonomics)  1048576K->262144K(2097152K), 0.4500000 secs]";
+
+    fn metrics(avg_cpu: f64, cpus: usize) -> SessionMetrics {
+        SessionMetrics {
+            duration_secs: 1800,
+            sample_count: 360,
+            avg_rss_bytes: 3 * 1024 * 1024 * 1024,
+            peak_rss_bytes: 4 * 1024 * 1024 * 1024,
+            avg_cpu_percent: avg_cpu,
+            peak_cpu_percent: avg_cpu * 1.5,
+            logical_cpus: cpus,
+            jvm_flags: vec!["-Xmx4G".to_string(), "-XX:+UseG1GC".to_string()],
+            ..SessionMetrics::default()
+        }
+    }
+
+    fn inputs(ram_mb: u32, gc: Option<&str>) -> ReportInputs {
+        ReportInputs {
+            ram_mb,
+            system_memory_mb: Some(16_384),
+            metrics: metrics(150.0, 8),
+            gc: gc.and_then(parse_gc_log),
+            ..ReportInputs::default()
+        }
+    }
+
+    fn ids(findings: &[PerformanceFinding]) -> Vec<&'static str> {
+        findings.iter().map(|finding| finding.id).collect()
+    }
+
+    #[test]
+    fn parses_unified_and_java8_gc_logs() {
+        let healthy = parse_gc_log(G1_HEALTHY).expect("G1");
+        assert_eq!(healthy.pause_count, 3);
+        assert_eq!(healthy.max_pause_ms, 12.0);
+        assert_eq!(healthy.avg_pause_ms, 10.0);
+        assert_eq!(healthy.heap_capacity_mb, 4096.0);
+        assert_eq!(healthy.peak_heap_used_mb, 650.0);
+        // (600 - 100) + (650 - 150) MB en 20 s.
+        assert_eq!(healthy.allocation_rate_mb_s, Some(50.0));
+
+        let java8 = parse_gc_log(JAVA8_LOG).expect("Java 8");
+        assert_eq!(java8.pause_count, 2);
+        assert_eq!(java8.full_gc_count, 1);
+        assert_eq!(java8.max_pause_ms, 450.0);
+        assert_eq!(java8.heap_capacity_mb, 2048.0);
+
+        let detailed = parse_gc_log(
+            "1.5: [GC (Allocation Failure) [PSYoungGen: 1024K->512K(2048K)] 4096K->1024K(8192K), 0.0500000 secs]",
+        )
+        .expect("PrintGCDetails");
+        assert_eq!(detailed.peak_heap_used_mb, 4.0);
+        assert_eq!(detailed.heap_capacity_mb, 8.0);
+
+        assert_eq!(parse_gc_log("[0.01s][info][gc,init] Version: 21"), None);
+    }
+
+    #[test]
+    fn healthy_session_only_reports_nothing_serious() {
+        let findings = evaluate_rules(&inputs(4096, Some(G1_HEALTHY)));
+        assert!(findings.is_empty(), "{findings:?}");
+    }
+
+    #[test]
+    fn starved_heap_is_ranked_first_with_gc_advice() {
+        let findings = evaluate_rules(&inputs(2048, Some(G1_STARVED)));
+        assert_eq!(
+            ids(&findings),
+            vec!["heap_pressure", "long_gc_pauses", "full_gcs"]
+        );
+        assert!(findings[0].message.contains("sube ram_mb"));
+        assert!(findings[1].message.contains("preset G1"));
+
+        let mut tuned = inputs(2048, Some(G1_STARVED));
+        tuned.auto_jvm_tuning = true;
+        assert!(!evaluate_rules(&tuned)[1].message.contains("preset G1"));
+    }
+
+    #[test]
+    fn flags_over_allocation_system_memory_and_cpu() {
+        let over = evaluate_rules(&inputs(8192, Some(G1_HEALTHY)));
+        assert_eq!(ids(&over), vec!["heap_over_allocated"]);
+
+        let mut greedy = inputs(14_336, Some(G1_HEALTHY));
+        greedy.metrics = metrics(380.0, 4);
+        assert_eq!(
+            ids(&evaluate_rules(&greedy)),
+            vec!["ram_exceeds_system", "cpu_saturated", "heap_over_allocated"]
+        );
+    }
+
+    #[test]
+    fn startup_gpu_and_missing_gc_log() {
+        let mut slow = inputs(4096, None);
+        slow.startup = Some(StartupProfile {
+            recorded_at: "2026-10-17T12:00:00Z".to_string(),
+            total_secs: 150.0,
+            mods_timed: 40,
+            top_mods: vec![ModLoadTime {
+                mod_id: "create".to_string(),
+                millis: 60_000,
+            }],
+        });
+        slow.preferred_gpu = Some("dedicated".to_string());
+        slow.metrics.gpu_preference = Some("⚠ No se encontró el adaptador 'dedicated'".to_string());
+
+        let findings = evaluate_rules(&slow);
+        assert_eq!(
+            ids(&findings),
+            vec![
+                "startup_dominated_by_mod",
+                "gpu_preference_not_applied",
+                "slow_startup",
+                "no_gc_log"
+            ]
+        );
+        assert!(findings[0].message.contains("create"));
+
+        let summary = render_summary("Survival", &slow, &findings);
+        assert!(summary.starts_with("**Informe de rendimiento: Survival**"));
+        assert!(summary.contains("GC: sin log"));
+        assert!(summary.contains("Flags JVM: `-Xmx4G -XX:+UseG1GC`"));
+        assert!(summary.contains("1. [MEDIA] El arranque está dominado por create"));
+    }
+
+    #[test]
+    fn finds_the_configured_gc_log() {
+        let game_dir = Path::new("/games/survival/minecraft");
+        assert_eq!(
+            gc_log_path(
+                game_dir,
+                &["-Xlog:gc*:file=logs/gc.log:time,uptime".to_string()]
+            ),
+            Some(game_dir.join("logs/gc.log"))
+        );
+        assert_eq!(
+            gc_log_path(game_dir, &["-Xloggc:/tmp/gc.log".to_string()]),
+            Some(PathBuf::from("/tmp/gc.log"))
+        );
+        assert_eq!(
+            gc_log_path(game_dir, &["-Xlog:gc:file=gc-%p.log".to_string()]),
+            None
+        );
+    }
+}
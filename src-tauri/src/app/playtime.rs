@@ -0,0 +1,324 @@
+// Tiempo de juego por sesión y por cuenta.
+
+use std::{
+    cmp::Reverse,
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::{
+    app::{settings_service::resolve_instances_root, trusted_root::resolve_trusted_instance_root},
+    infrastructure::filesystem::{file_ops::write_file_replacing, paths::resolve_launcher_root},
+    shared::result::AppResult,
+};
+
+const PLAYTIME_FILE: &str = ".playtime.json";
+const ACCOUNT_INDEX_FILE: &str = "account-playtime.json";
+pub const UNKNOWN_ACCOUNT: &str = "unknown";
+
+/// Serializa las actualizaciones del índice: dos instancias pueden cerrarse a la vez.
+static ACCOUNT_INDEX_LOCK: Mutex<()> = Mutex::new(());
+
+fn unknown_account() -> String {
+    UNKNOWN_ACCOUNT.to_string()
+}
+
+/// Cuenta con la que se lanzó una sesión.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionAccount {
+    pub profile_id: String,
+    pub profile_name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaySession {
+    pub started_at: String,
+    pub ended_at: String,
+    pub duration_ms: u64,
+    #[serde(default)]
+    pub exit_code: Option<i32>,
+    #[serde(default = "unknown_account")]
+    pub profile_id: String,
+    #[serde(default = "unknown_account")]
+    pub profile_name: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PlaytimeLog {
+    #[serde(default)]
+    sessions: Vec<PlaySession>,
+}
+
+/// Tiempo acumulado de una cuenta; `by_instance` solo se rellena en el índice global.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountPlaytime {
+    pub profile_id: String,
+    /// Nombre de la sesión más reciente.
+    pub profile_name: String,
+    pub total_ms: u64,
+    pub session_count: u32,
+    pub last_played: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub by_instance: BTreeMap<String, u64>,
+}
+
+impl AccountPlaytime {
+    fn add(&mut self, session: &PlaySession) {
+        self.total_ms += session.duration_ms;
+        self.session_count += 1;
+        if !self
+            .last_played
+            .as_deref()
+            .is_some_and(|last| session.ended_at.as_str() < last)
+        {
+            self.profile_name = session.profile_name.clone();
+            self.last_played = Some(session.ended_at.clone());
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AccountIndex {
+    #[serde(default)]
+    accounts: BTreeMap<String, AccountPlaytime>,
+}
+
+impl AccountIndex {
+    fn add(&mut self, instance_root: &str, session: &PlaySession) {
+        let account = self
+            .accounts
+            .entry(session.profile_id.clone())
+            .or_insert_with(|| AccountPlaytime {
+                profile_id: session.profile_id.clone(),
+                ..AccountPlaytime::default()
+            });
+        account.add(session);
+        *account
+            .by_instance
+            .entry(instance_root.to_string())
+            .or_default() += session.duration_ms;
+    }
+}
+
+fn read_json<T: Default + for<'de> Deserialize<'de>>(path: &Path) -> AppResult<T> {
+    if !path.exists() {
+        return Ok(T::default());
+    }
+    let raw = fs::read_to_string(path)
+        .map_err(|err| format!("No se pudo leer {}: {err}", path.display()))?;
+    serde_json::from_str(&raw)
+        .map_err(|err| format!("No se pudo parsear {}: {err}", path.display()))
+}
+
+fn write_json<T: Serialize>(path: &Path, value: &T) -> AppResult<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|err| format!("No se pudo crear {}: {err}", parent.display()))?;
+    }
+    let raw = serde_json::to_string_pretty(value)
+        .map_err(|err| format!("No se pudo serializar {}: {err}", path.display()))?;
+    write_file_replacing(path, raw.as_bytes(), true)
+}
+
+fn account_index_path(app: &AppHandle) -> AppResult<PathBuf> {
+    Ok(resolve_launcher_root(app)?
+        .join("config")
+        .join(ACCOUNT_INDEX_FILE))
+}
+
+fn read_sessions(instance_root: &Path) -> AppResult<Vec<PlaySession>> {
+    Ok(read_json::<PlaytimeLog>(&instance_root.join(PLAYTIME_FILE))?.sessions)
+}
+
+/// Añade la sesión al historial de la instancia y al índice por cuenta.
+fn append_session(instance_root: &Path, index_path: &Path, session: PlaySession) -> AppResult<()> {
+    let log_path = instance_root.join(PLAYTIME_FILE);
+    let mut log = read_json::<PlaytimeLog>(&log_path)?;
+    log.sessions.push(session.clone());
+    write_json(&log_path, &log)?;
+
+    let _guard = ACCOUNT_INDEX_LOCK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let mut index = read_json::<AccountIndex>(index_path)?;
+    index.add(&instance_root.display().to_string(), &session);
+    write_json(index_path, &index)
+}
+
+/// Guarda una sesión terminada; un fallo solo se registra, no afecta a la salida del juego.
+pub(crate) fn record_play_session(app: &AppHandle, instance_root: &str, session: PlaySession) {
+    let recorded = account_index_path(app)
+        .and_then(|index_path| append_session(Path::new(instance_root), &index_path, session));
+    if let Err(err) = recorded {
+        log::warn!("⚠ No se pudo guardar el tiempo de juego de {instance_root}: {err}");
+    }
+}
+
+/// Suma las sesiones por cuenta, de más a menos tiempo.
+fn playtime_by_account(sessions: &[PlaySession]) -> Vec<AccountPlaytime> {
+    let mut accounts = BTreeMap::<String, AccountPlaytime>::new();
+    for session in sessions {
+        accounts
+            .entry(session.profile_id.clone())
+            .or_insert_with(|| AccountPlaytime {
+                profile_id: session.profile_id.clone(),
+                ..AccountPlaytime::default()
+            })
+            .add(session);
+    }
+    let mut accounts = accounts.into_values().collect::<Vec<_>>();
+    accounts.sort_by_key(|account| Reverse(account.total_ms));
+    accounts
+}
+
+/// Reconstruye el índice global desde los historiales de las instancias; solo se usa si el
+/// índice no existe (primera consulta o borrado a mano).
+fn rebuild_account_index(instances_root: &Path, index_path: &Path) -> AppResult<AccountIndex> {
+    let mut index = AccountIndex::default();
+    if let Ok(entries) = fs::read_dir(instances_root) {
+        for entry in entries.flatten() {
+            let root = entry.path();
+            if !root.join(PLAYTIME_FILE).is_file() {
+                continue;
+            }
+            let root_key = root.display().to_string();
+            for session in read_sessions(&root)? {
+                index.add(&root_key, &session);
+            }
+        }
+    }
+    write_json(index_path, &index)?;
+    Ok(index)
+}
+
+/// Tiempo de juego de la instancia agrupado por la cuenta que lanzó cada sesión.
+#[tauri::command]
+pub fn get_playtime_by_account(
+    app: AppHandle,
+    instance_root: String,
+) -> Result<Vec<AccountPlaytime>, String> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    Ok(playtime_by_account(&read_sessions(instance_root.path())?))
+}
+
+/// Tiempo de juego de una cuenta en todas las instancias, desglosado por instancia.
+#[tauri::command]
+pub fn get_account_playtime(app: AppHandle, profile_id: String) -> Result<AccountPlaytime, String> {
+    let index_path = account_index_path(&app)?;
+    let index = {
+        let _guard = ACCOUNT_INDEX_LOCK
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if index_path.exists() {
+            read_json::<AccountIndex>(&index_path)?
+        } else {
+            rebuild_account_index(&resolve_instances_root(&app)?, &index_path)?
+        }
+    };
+    Ok(index
+        .accounts
+        .get(&profile_id)
+        .cloned()
+        .unwrap_or(AccountPlaytime {
+            profile_id,
+            ..AccountPlaytime::default()
+        }))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::shared::test_support::test_temp_dir;
+
+    fn session(profile: &str, ended_at: &str, minutes: u64) -> PlaySession {
+        PlaySession {
+            started_at: "2026-10-01T10:00:00Z".to_string(),
+            ended_at: ended_at.to_string(),
+            duration_ms: minutes * 60_000,
+            exit_code: Some(0),
+            profile_id: format!("{profile}-id"),
+            profile_name: profile.to_string(),
+        }
+    }
+
+    #[test]
+    fn sessions_without_account_aggregate_as_unknown() {
+        let raw = r#"{"sessions":[
+            {"startedAt":"2026-01-01T10:00:00Z","endedAt":"2026-01-01T11:00:00Z","durationMs":3600000},
+            {"startedAt":"2026-01-02T10:00:00Z","endedAt":"2026-01-02T10:30:00Z","durationMs":1800000,
+             "profileId":"alex-id","profileName":"Alex"}
+        ]}"#;
+        let log = serde_json::from_str::<PlaytimeLog>(raw).expect("log antiguo");
+        assert_eq!(log.sessions[0].profile_id, UNKNOWN_ACCOUNT);
+        assert_eq!(log.sessions[0].profile_name, UNKNOWN_ACCOUNT);
+
+        let accounts = playtime_by_account(&log.sessions);
+        assert_eq!(accounts.len(), 2);
+        assert_eq!(accounts[0].profile_id, UNKNOWN_ACCOUNT);
+        assert_eq!(accounts[0].total_ms, 3_600_000);
+        assert_eq!(accounts[1].profile_name, "Alex");
+        assert_eq!(accounts[1].session_count, 1);
+    }
+
+    #[test]
+    fn account_index_is_updated_with_each_session_and_matches_a_rebuild() {
+        let root = test_temp_dir("interface-playtime");
+        let survival = root.join("instances").join("Survival");
+        let creative = root.join("instances").join("Creative");
+        fs::create_dir_all(&survival).expect("survival");
+        fs::create_dir_all(&creative).expect("creative");
+        let index_path = root.join("config").join(ACCOUNT_INDEX_FILE);
+
+        append_session(
+            &survival,
+            &index_path,
+            session("Alex", "2026-10-01T11:00:00Z", 60),
+        )
+        .expect("sesión");
+        append_session(
+            &creative,
+            &index_path,
+            session("Alex", "2026-10-02T11:00:00Z", 15),
+        )
+        .expect("sesión");
+        append_session(
+            &survival,
+            &index_path,
+            session("Sam", "2026-10-03T11:00:00Z", 30),
+        )
+        .expect("sesión");
+
+        let index = read_json::<AccountIndex>(&index_path).expect("índice");
+        let alex = &index.accounts["Alex-id"];
+        assert_eq!(alex.total_ms, 75 * 60_000);
+        assert_eq!(alex.session_count, 2);
+        assert_eq!(alex.last_played.as_deref(), Some("2026-10-02T11:00:00Z"));
+        assert_eq!(
+            alex.by_instance[&survival.display().to_string()],
+            60 * 60_000
+        );
+        assert_eq!(
+            alex.by_instance[&creative.display().to_string()],
+            15 * 60_000
+        );
+        assert_eq!(index.accounts["Sam-id"].total_ms, 30 * 60_000);
+        assert_eq!(read_sessions(&survival).expect("historial").len(), 2);
+
+        fs::remove_file(&index_path).expect("borrar índice");
+        let rebuilt =
+            rebuild_account_index(&root.join("instances"), &index_path).expect("reconstruir");
+        assert_eq!(rebuilt.accounts, index.accounts);
+
+        let _ = fs::remove_dir_all(root);
+    }
+}
@@ -0,0 +1,420 @@
+// Suspensión y reanudación del equipo.
+
+use std::{
+    sync::{Arc, Mutex, OnceLock},
+    thread,
+    time::Duration,
+};
+
+use tauri::AppHandle;
+
+use crate::{
+    app::instance_service::record_runtime_suspension,
+    shared::clock::{app_clock, AppClock, Clock},
+};
+
+/// Tras reanudar, margen antes de informar la salida del juego o de cerrar sus tuberías.
+pub const RESUME_GRACE: Duration = Duration::from_secs(10);
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(2);
+/// Salto de reloj entre latidos (además del intervalo) que se interpreta como suspensión.
+const HEARTBEAT_GAP: Duration = Duration::from_secs(8);
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PowerState {
+    sleeping_since_ms: Option<u64>,
+    /// Suspensión acumulada desde que arrancó el launcher, sin contar la actual.
+    total_suspended_ms: u64,
+    last_resume_ms: Option<u64>,
+    /// Última vez que el launcher comprobó el reloj estando despierto.
+    last_seen_ms: Option<u64>,
+}
+
+impl PowerState {
+    pub fn on_sleep(&mut self, now_ms: u64) {
+        self.sleeping_since_ms.get_or_insert(now_ms);
+    }
+
+    /// Cierra la suspensión en curso y devuelve su intervalo `(inicio, fin)`; un resume sin
+    /// sleep previo (evento repetido o ya detectado por el reloj) no hace nada.
+    pub fn on_resume(&mut self, now_ms: u64) -> Option<(u64, u64)> {
+        self.last_seen_ms = Some(now_ms);
+        let since = self.sleeping_since_ms.take()?;
+        self.total_suspended_ms += now_ms.saturating_sub(since);
+        self.last_resume_ms = Some(now_ms);
+        Some((since, now_ms))
+    }
+
+    /// Anota que el launcher está despierto en `now_ms`. Si desde la comprobación anterior
+    /// pasó mucho más que un latido, el proceso estuvo congelado: se cierra esa suspensión.
+    pub fn observe_clock(&mut self, now_ms: u64) -> Option<(u64, u64)> {
+        let previous = self.last_seen_ms.replace(now_ms)?;
+        let (since, until) = heartbeat_gap(previous, now_ms)?;
+        self.on_sleep(since);
+        self.on_resume(until)
+    }
+
+    /// Suspensión total hasta `now_ms`, incluida la que esté en curso.
+    pub fn suspended_total_ms(&self, now_ms: u64) -> u64 {
+        self.total_suspended_ms
+            + self
+                .sleeping_since_ms
+                .map_or(0, |since| now_ms.saturating_sub(since))
+    }
+
+    /// Lo que queda del margen posterior a la reanudación (todo el margen si está durmiendo).
+    pub fn resume_grace_remaining(&self, now_ms: u64) -> Option<Duration> {
+        if self.sleeping_since_ms.is_some() {
+            return Some(RESUME_GRACE);
+        }
+        let elapsed = now_ms.saturating_sub(self.last_resume_ms?);
+        RESUME_GRACE
+            .checked_sub(Duration::from_millis(elapsed))
+            .filter(|remaining| !remaining.is_zero())
+    }
+}
+
+/// Plazo que no avanza mientras el equipo duerme.
+#[derive(Debug, Clone, Copy)]
+pub struct SuspendAwareDeadline {
+    started_ms: u64,
+    suspended_at_start_ms: u64,
+    budget: Duration,
+}
+
+impl SuspendAwareDeadline {
+    pub fn start(state: &PowerState, now_ms: u64, budget: Duration) -> Self {
+        Self {
+            started_ms: now_ms,
+            suspended_at_start_ms: state.suspended_total_ms(now_ms),
+            budget,
+        }
+    }
+
+    pub fn expired(&self, state: &PowerState, now_ms: u64) -> bool {
+        let suspended = state
+            .suspended_total_ms(now_ms)
+            .saturating_sub(self.suspended_at_start_ms);
+        let active_ms = now_ms
+            .saturating_sub(self.started_ms)
+            .saturating_sub(suspended);
+        Duration::from_millis(active_ms) >= self.budget
+    }
+}
+
+static POWER_STATE: OnceLock<Mutex<PowerState>> = OnceLock::new();
+/// Reloj de la app para los avisos que llegan por callbacks del sistema.
+static LISTENER_CLOCK: OnceLock<Arc<dyn Clock>> = OnceLock::new();
+
+fn power_state() -> &'static Mutex<PowerState> {
+    POWER_STATE.get_or_init(|| Mutex::new(PowerState::default()))
+}
+
+#[cfg_attr(not(any(windows, target_os = "macos")), allow(dead_code))]
+fn listener_now() -> u64 {
+    match LISTENER_CLOCK.get() {
+        Some(clock) => clock.now_millis(),
+        None => AppClock::default().clock.now_millis(),
+    }
+}
+
+/// Copia del estado global en `now_ms`, ya descontando una suspensión recién detectada.
+pub fn current_power_state(now_ms: u64) -> PowerState {
+    let Ok(mut state) = power_state().lock() else {
+        return PowerState::default();
+    };
+    let interval = state.observe_clock(now_ms);
+    let snapshot = state.clone();
+    drop(state);
+    if let Some((since, until)) = interval {
+        record_runtime_suspension(since, until);
+        log::info!(
+            "🔹 El reloj saltó {} s: el equipo estuvo suspendido.",
+            until.saturating_sub(since) / 1000
+        );
+    }
+    snapshot
+}
+
+pub fn handle_system_sleep(now_ms: u64) {
+    if let Ok(mut state) = power_state().lock() {
+        state.on_sleep(now_ms);
+    }
+    log::info!("🔹 El equipo entra en suspensión; se pausan plazos y tiempo de juego.");
+}
+
+pub fn handle_system_resume(now_ms: u64) {
+    let interval = power_state()
+        .lock()
+        .ok()
+        .and_then(|mut state| state.on_resume(now_ms));
+    if let Some((since, until)) = interval {
+        record_runtime_suspension(since, until);
+        log::info!(
+            "🔹 Equipo reanudado tras {} s de suspensión.",
+            until.saturating_sub(since) / 1000
+        );
+    }
+}
+
+/// Intervalo de suspensión si entre dos latidos pasó mucho más tiempo que el previsto.
+fn heartbeat_gap(previous_ms: u64, now_ms: u64) -> Option<(u64, u64)> {
+    let expected = (HEARTBEAT_INTERVAL + HEARTBEAT_GAP).as_millis() as u64;
+    (now_ms.saturating_sub(previous_ms) > expected).then_some((previous_ms, now_ms))
+}
+
+/// Sleep (`true`) o resume (`false`) según el argumento de `PrepareForSleep`.
+#[cfg(target_os = "linux")]
+fn handle_prepare_for_sleep(sleeping: bool, now_ms: u64) {
+    if sleeping {
+        handle_system_sleep(now_ms);
+    } else {
+        handle_system_resume(now_ms);
+    }
+}
+
+/// Escucha `PrepareForSleep` de logind en el bus del sistema hasta que se cierra la conexión.
+#[cfg(target_os = "linux")]
+fn listen_logind(clock: &dyn Clock) -> zbus::Result<()> {
+    let connection = zbus::blocking::Connection::system()?;
+    let proxy = zbus::blocking::Proxy::new(
+        &connection,
+        "org.freedesktop.login1",
+        "/org/freedesktop/login1",
+        "org.freedesktop.login1.Manager",
+    )?;
+    for signal in proxy.receive_signal("PrepareForSleep")? {
+        match signal.body().deserialize::<bool>() {
+            Ok(sleeping) => handle_prepare_for_sleep(sleeping, clock.now_millis()),
+            Err(err) => log::warn!("⚠ Señal PrepareForSleep de logind ilegible: {err}"),
+        }
+    }
+    Ok(())
+}
+
+/// Registra el callback de suspensión de Windows (los mismos avisos que `WM_POWERBROADCAST`
+/// sin necesitar una ventana); devuelve `false` si el sistema lo rechaza.
+#[cfg(windows)]
+fn register_power_broadcast() -> bool {
+    use std::ffi::c_void;
+
+    use windows_sys::Win32::{
+        System::Power::{
+            PowerRegisterSuspendResumeNotification, DEVICE_NOTIFY_SUBSCRIBE_PARAMETERS,
+        },
+        UI::WindowsAndMessaging::{
+            DEVICE_NOTIFY_CALLBACK, PBT_APMRESUMEAUTOMATIC, PBT_APMRESUMESUSPEND, PBT_APMSUSPEND,
+        },
+    };
+
+    unsafe extern "system" fn on_power_broadcast(
+        _context: *const c_void,
+        event: u32,
+        _setting: *const c_void,
+    ) -> u32 {
+        match event {
+            PBT_APMSUSPEND => handle_system_sleep(listener_now()),
+            PBT_APMRESUMEAUTOMATIC | PBT_APMRESUMESUSPEND => handle_system_resume(listener_now()),
+            _ => {}
+        }
+        0
+    }
+
+    // Windows conserva el puntero mientras dure el registro, que es toda la vida del proceso.
+    let parameters = Box::leak(Box::new(DEVICE_NOTIFY_SUBSCRIBE_PARAMETERS {
+        Callback: Some(on_power_broadcast),
+        Context: std::ptr::null_mut(),
+    }));
+    let mut registration = std::ptr::null_mut();
+    // SAFETY: `parameters` es estático y `registration` apunta a memoria propia.
+    let status = unsafe {
+        PowerRegisterSuspendResumeNotification(
+            DEVICE_NOTIFY_CALLBACK,
+            (parameters as *mut DEVICE_NOTIFY_SUBSCRIBE_PARAMETERS).cast(),
+            &mut registration,
+        )
+    };
+    status == 0
+}
+
+/// Avisos de energía de IOKit; el hilo queda dentro de su run loop.
+#[cfg(target_os = "macos")]
+mod iokit {
+    use std::{
+        ffi::c_void,
+        sync::atomic::{AtomicU32, Ordering},
+    };
+
+    use super::{handle_system_resume, handle_system_sleep, listener_now};
+
+    const MESSAGE_CAN_SYSTEM_SLEEP: u32 = 0xE000_0270;
+    const MESSAGE_SYSTEM_WILL_SLEEP: u32 = 0xE000_0280;
+    const MESSAGE_SYSTEM_HAS_POWERED_ON: u32 = 0xE000_0300;
+
+    static ROOT_PORT: AtomicU32 = AtomicU32::new(0);
+
+    type PowerCallback = extern "C" fn(*mut c_void, u32, u32, *mut c_void);
+
+    #[link(name = "IOKit", kind = "framework")]
+    extern "C" {
+        fn IORegisterForSystemPower(
+            refcon: *mut c_void,
+            notify_port: *mut *mut c_void,
+            callback: PowerCallback,
+            notifier: *mut u32,
+        ) -> u32;
+        fn IONotificationPortGetRunLoopSource(notify_port: *mut c_void) -> *mut c_void;
+        fn IOAllowPowerChange(kernel_port: u32, notification_id: isize) -> i32;
+    }
+
+    #[link(name = "CoreFoundation", kind = "framework")]
+    extern "C" {
+        static kCFRunLoopCommonModes: *const c_void;
+        fn CFRunLoopGetCurrent() -> *mut c_void;
+        fn CFRunLoopAddSource(run_loop: *mut c_void, source: *mut c_void, mode: *const c_void);
+        fn CFRunLoopRun();
+    }
+
+    extern "C" fn on_power_message(
+        _refcon: *mut c_void,
+        _service: u32,
+        message: u32,
+        argument: *mut c_void,
+    ) {
+        match message {
+            MESSAGE_SYSTEM_WILL_SLEEP => handle_system_sleep(listener_now()),
+            MESSAGE_CAN_SYSTEM_SLEEP => {}
+            MESSAGE_SYSTEM_HAS_POWERED_ON => {
+                handle_system_resume(listener_now());
+                return;
+            }
+            _ => return,
+        }
+        // Sin esta respuesta macOS espera 30 s antes de dormir.
+        // SAFETY: el puerto viene de `IORegisterForSystemPower` y el id del propio mensaje.
+        unsafe {
+            IOAllowPowerChange(ROOT_PORT.load(Ordering::SeqCst), argument as isize);
+        }
+    }
+
+    /// Bloquea el hilo actual atendiendo avisos; devuelve `false` si no se pudo registrar.
+    pub fn listen() -> bool {
+        let mut notify_port = std::ptr::null_mut();
+        let mut notifier = 0;
+        // SAFETY: los punteros de salida apuntan a variables locales válidas.
+        let root_port = unsafe {
+            IORegisterForSystemPower(
+                std::ptr::null_mut(),
+                &mut notify_port,
+                on_power_message,
+                &mut notifier,
+            )
+        };
+        if root_port == 0 {
+            return false;
+        }
+        ROOT_PORT.store(root_port, Ordering::SeqCst);
+        // SAFETY: `notify_port` es válido tras un registro correcto y el run loop es el del
+        // hilo actual, que no sale de `CFRunLoopRun`.
+        unsafe {
+            CFRunLoopAddSource(
+                CFRunLoopGetCurrent(),
+                IONotificationPortGetRunLoopSource(notify_port),
+                kCFRunLoopCommonModes,
+            );
+            CFRunLoopRun();
+        }
+        true
+    }
+}
+
+/// Arranca la escucha de avisos del sistema y el latido que detecta suspensiones perdidas.
+pub fn start_power_listener(app: AppHandle) {
+    let clock = app_clock(&app).clock;
+    let _ = LISTENER_CLOCK.set(clock.clone());
+
+    #[cfg(target_os = "linux")]
+    {
+        let clock = clock.clone();
+        thread::spawn(move || {
+            if let Err(err) = listen_logind(clock.as_ref()) {
+                log::warn!(
+                    "⚠ Sin avisos de suspensión de logind ({err}); se detecta por el reloj."
+                );
+            }
+        });
+    }
+    #[cfg(windows)]
+    if !register_power_broadcast() {
+        log::warn!("⚠ Windows rechazó el aviso de suspensión; se detecta por el reloj.");
+    }
+    #[cfg(target_os = "macos")]
+    thread::spawn(|| {
+        if !iokit::listen() {
+            log::warn!("⚠ IOKit rechazó el aviso de suspensión; se detecta por el reloj.");
+        }
+    });
+
+    thread::spawn(move || loop {
+        thread::sleep(HEARTBEAT_INTERVAL);
+        current_power_state(clock.now_millis());
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MINUTE: u64 = 60 * 1000;
+
+    #[test]
+    fn auth_deadline_pauses_while_sleeping() {
+        let mut state = PowerState::default();
+        let deadline = SuspendAwareDeadline::start(&state, 0, Duration::from_secs(180));
+
+        state.on_sleep(MINUTE);
+        // Dos horas dormido: el plazo sigue con 2 minutos por delante.
+        assert!(!deadline.expired(&state, 120 * MINUTE));
+        assert_eq!(state.on_resume(121 * MINUTE), Some((MINUTE, 121 * MINUTE)));
+        assert_eq!(state.on_resume(122 * MINUTE), None);
+        assert!(!deadline.expired(&state, 122 * MINUTE));
+        assert!(deadline.expired(&state, 123 * MINUTE));
+    }
+
+    #[test]
+    fn resume_grace_and_clock_jump_detection() {
+        let mut state = PowerState::default();
+        assert_eq!(state.resume_grace_remaining(0), None);
+        state.on_sleep(10_000);
+        assert_eq!(state.resume_grace_remaining(20_000), Some(RESUME_GRACE));
+        state.on_resume(50_000);
+        assert_eq!(
+            state.resume_grace_remaining(54_000),
+            Some(Duration::from_secs(6))
+        );
+        assert_eq!(state.resume_grace_remaining(60_000), None);
+        assert_eq!(state.suspended_total_ms(60_000), 40_000);
+
+        // Al despertar, la primera consulta ve el salto antes que el latido o el aviso.
+        let mut state = PowerState::default();
+        assert_eq!(state.observe_clock(0), None);
+        let deadline = SuspendAwareDeadline::start(&state, 0, Duration::from_secs(180));
+        assert_eq!(state.observe_clock(2_000), None);
+        assert_eq!(
+            state.observe_clock(120 * MINUTE),
+            Some((2_000, 120 * MINUTE))
+        );
+        assert!(!deadline.expired(&state, 120 * MINUTE));
+        assert_eq!(
+            state.resume_grace_remaining(120 * MINUTE),
+            Some(RESUME_GRACE)
+        );
+        // El aviso de resume que llega después no vuelve a contar la suspensión.
+        assert_eq!(state.on_resume(120 * MINUTE + 1_000), None);
+        assert_eq!(state.suspended_total_ms(121 * MINUTE), 120 * MINUTE - 2_000);
+
+        // Con el aviso del sistema primero, el latido siguiente no ve salto.
+        state.on_sleep(121 * MINUTE);
+        state.on_resume(180 * MINUTE);
+        assert_eq!(state.observe_clock(180 * MINUTE + 2_000), None);
+    }
+}
@@ -0,0 +1,235 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::infrastructure::filesystem::paths::resolve_launcher_root;
+
+const QUARANTINE_DIR: &str = "quarantine";
+const QUARANTINE_MANIFEST: &str = "quarantine.json";
+/// Tamaño máximo de la cuarentena; al superarlo se eliminan primero los archivos más antiguos.
+pub const QUARANTINE_CAP_BYTES: u64 = 1024 * 1024 * 1024;
+
+/// Archivo apartado por la verificación (zip corrupto o SHA1 distinto al publicado).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct QuarantinedFile {
+    pub original_path: String,
+    pub quarantined_path: String,
+    pub reason: String,
+    pub size_bytes: u64,
+    pub quarantined_at: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PurgeQuarantineResult {
+    pub removed_files: usize,
+    pub freed_bytes: u64,
+}
+
+fn quarantine_root(launcher_root: &Path) -> PathBuf {
+    launcher_root.join(QUARANTINE_DIR)
+}
+
+fn read_manifest(launcher_root: &Path) -> Vec<QuarantinedFile> {
+    fs::read_to_string(quarantine_root(launcher_root).join(QUARANTINE_MANIFEST))
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn write_manifest(launcher_root: &Path, entries: &[QuarantinedFile]) -> Result<(), String> {
+    let path = quarantine_root(launcher_root).join(QUARANTINE_MANIFEST);
+    let raw = serde_json::to_string_pretty(entries)
+        .map_err(|err| format!("No se pudo serializar manifiesto de cuarentena: {err}"))?;
+    fs::write(&path, raw).map_err(|err| {
+        format!(
+            "No se pudo guardar manifiesto de cuarentena {}: {err}",
+            path.display()
+        )
+    })
+}
+
+fn unique_destination(dir: &Path, file_name: &str) -> PathBuf {
+    let candidate = dir.join(file_name);
+    if !candidate.exists() {
+        return candidate;
+    }
+    (1..)
+        .map(|n| dir.join(format!("{n}-{file_name}")))
+        .find(|path| !path.exists())
+        .unwrap_or(candidate)
+}
+
+/// Mueve `path` a `launcher_root/quarantine/<fecha>/` en lugar de borrarlo y registra el
+/// motivo en el manifiesto. Después aplica el límite de tamaño de la cuarentena.
+pub fn quarantine_file(
+    launcher_root: &Path,
+    path: &Path,
+    reason: &str,
+) -> Result<QuarantinedFile, String> {
+    let date_dir =
+        quarantine_root(launcher_root).join(chrono::Utc::now().format("%Y-%m-%d").to_string());
+    fs::create_dir_all(&date_dir).map_err(|err| {
+        format!(
+            "No se pudo crear carpeta de cuarentena {}: {err}",
+            date_dir.display()
+        )
+    })?;
+
+    let file_name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("archivo");
+    let destination = unique_destination(&date_dir, file_name);
+    let size_bytes = fs::metadata(path).map(|meta| meta.len()).unwrap_or(0);
+
+    if fs::rename(path, &destination).is_err() {
+        fs::copy(path, &destination)
+            .and_then(|_| fs::remove_file(path))
+            .map_err(|err| format!("No se pudo mover {} a cuarentena: {err}", path.display()))?;
+    }
+
+    let entry = QuarantinedFile {
+        original_path: path.display().to_string(),
+        quarantined_path: destination.display().to_string(),
+        reason: reason.to_string(),
+        size_bytes,
+        quarantined_at: chrono::Utc::now().to_rfc3339(),
+    };
+    let mut entries = read_manifest(launcher_root);
+    entries.push(entry.clone());
+    write_manifest(launcher_root, &entries)?;
+    log::warn!(
+        "⚠ Archivo en cuarentena: {} ({reason})",
+        entry.original_path
+    );
+
+    prune_quarantine(launcher_root, QUARANTINE_CAP_BYTES)?;
+    Ok(entry)
+}
+
+/// Elimina los archivos más antiguos hasta que la cuarentena quede por debajo de `cap_bytes`.
+/// Devuelve las entradas eliminadas.
+pub fn prune_quarantine(
+    launcher_root: &Path,
+    cap_bytes: u64,
+) -> Result<Vec<QuarantinedFile>, String> {
+    let mut entries: Vec<QuarantinedFile> = read_manifest(launcher_root)
+        .into_iter()
+        .filter(|entry| Path::new(&entry.quarantined_path).exists())
+        .collect();
+    entries.sort_by(|a, b| a.quarantined_at.cmp(&b.quarantined_at));
+
+    let mut total: u64 = entries.iter().map(|entry| entry.size_bytes).sum();
+    let mut removed = Vec::new();
+    while total > cap_bytes && !entries.is_empty() {
+        let oldest = entries.remove(0);
+        let _ = fs::remove_file(&oldest.quarantined_path);
+        total = total.saturating_sub(oldest.size_bytes);
+        removed.push(oldest);
+    }
+
+    remove_empty_date_dirs(launcher_root);
+    if quarantine_root(launcher_root).is_dir() {
+        write_manifest(launcher_root, &entries)?;
+    }
+    Ok(removed)
+}
+
+fn remove_empty_date_dirs(launcher_root: &Path) {
+    let Ok(dirs) = fs::read_dir(quarantine_root(launcher_root)) else {
+        return;
+    };
+    for dir in dirs.filter_map(Result::ok).map(|entry| entry.path()) {
+        if dir.is_dir()
+            && fs::read_dir(&dir)
+                .map(|mut entries| entries.next().is_none())
+                .unwrap_or(false)
+        {
+            let _ = fs::remove_dir(&dir);
+        }
+    }
+}
+
+pub fn list_quarantine(launcher_root: &Path) -> Vec<QuarantinedFile> {
+    let mut entries: Vec<QuarantinedFile> = read_manifest(launcher_root)
+        .into_iter()
+        .filter(|entry| Path::new(&entry.quarantined_path).exists())
+        .collect();
+    entries.sort_by(|a, b| b.quarantined_at.cmp(&a.quarantined_at));
+    entries
+}
+
+#[tauri::command]
+pub fn list_quarantined_files(app: AppHandle) -> Result<Vec<QuarantinedFile>, String> {
+    let launcher_root = resolve_launcher_root(&app)?;
+    Ok(list_quarantine(&launcher_root))
+}
+
+#[tauri::command]
+pub fn purge_quarantine(app: AppHandle) -> Result<PurgeQuarantineResult, String> {
+    let launcher_root = resolve_launcher_root(&app)?;
+    let removed = prune_quarantine(&launcher_root, 0)?;
+    log::info!("🔹 Cuarentena vaciada: {} archivo(s)", removed.len());
+    Ok(PurgeQuarantineResult {
+        removed_files: removed.len(),
+        freed_bytes: removed.iter().map(|entry| entry.size_bytes).sum(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::test_support::test_temp_dir;
+
+    #[test]
+    fn quarantine_moves_file_and_records_reason() {
+        let root = test_temp_dir("quarantine-move");
+        let jar = root.join("libraries").join("lib.jar");
+        fs::create_dir_all(jar.parent().unwrap()).unwrap();
+        fs::write(&jar, b"not a zip").unwrap();
+
+        let entry = quarantine_file(&root, &jar, "zip inválido").expect("cuarentena");
+        assert!(!jar.exists());
+        assert!(Path::new(&entry.quarantined_path).exists());
+        assert!(entry.quarantined_path.contains(QUARANTINE_DIR));
+
+        fs::write(&jar, b"again").unwrap();
+        let second = quarantine_file(&root, &jar, "sha1").expect("cuarentena");
+        assert_ne!(second.quarantined_path, entry.quarantined_path);
+
+        let listed = list_quarantine(&root);
+        assert_eq!(listed.len(), 2);
+        assert!(listed.iter().any(|item| item.reason == "zip inválido"));
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn prune_removes_oldest_first_until_under_cap() {
+        let root = test_temp_dir("quarantine-prune");
+        for name in ["a.jar", "b.jar", "c.jar"] {
+            let path = root.join(name);
+            fs::write(&path, vec![0u8; 10]).unwrap();
+            quarantine_file(&root, &path, "test").expect("cuarentena");
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+
+        let removed = prune_quarantine(&root, 15).expect("prune");
+        assert_eq!(removed.len(), 2);
+        assert!(removed[0].original_path.ends_with("a.jar"));
+        assert!(removed[1].original_path.ends_with("b.jar"));
+        let remaining = list_quarantine(&root);
+        assert_eq!(remaining.len(), 1);
+        assert!(remaining[0].original_path.ends_with("c.jar"));
+
+        let all = prune_quarantine(&root, 0).expect("purge");
+        assert_eq!(all.len(), 1);
+        assert!(list_quarantine(&root).is_empty());
+        let _ = fs::remove_dir_all(root);
+    }
+}
@@ -1,11 +1,9 @@
 use std::{
     collections::HashMap,
     fs,
-    io::{BufRead, BufReader},
     path::{Path, PathBuf},
     process::{Command, Stdio},
     sync::{Mutex, OnceLock},
-    thread,
     time::{SystemTime, UNIX_EPOCH},
 };
 
@@ -23,11 +21,18 @@ use tokio::{io::AsyncWriteExt, time::sleep};
 
 use crate::{
     app::{
-        instance_service::{get_instance_metadata, StartInstanceResult},
+        instance_service::{
+            ensure_online_launch_flags, finalize_redirect_classpath, gpu_preference_cleanup,
+            read_instance_metadata, update_instance_metadata, StartInstanceResult,
+        },
+        playtime::SessionAccount,
+        runtime_metrics::start_runtime_metrics,
         shortcut_instance::{
             resolve_external_game_dir_with_relink, select_embedded_java, validate_classpath_exists,
             ShortcutState,
         },
+        strict_mode::{enforce_strict_mode, StrictViolations, REDIRECT_GAME_DIR_DATA},
+        trusted_root::resolve_trusted_instance_root,
     },
     commands::import::resolve_effective_version_id,
     domain::{
@@ -36,9 +41,17 @@ use crate::{
             authenticate_with_xbox_live, authorize_xsts, login_minecraft_with_xbox,
             read_minecraft_profile,
         },
+        instance::interop::{
+            known_external_launcher_roots, read_external_manifest, shared_runtime_roots,
+        },
+        java::java_args::{merge_memory_args, normalize_java_args},
         minecraft::{
             argument_resolver::{resolve_launch_arguments, LaunchContext},
-            rule_engine::{evaluate_rules, RuleContext, RuleFeatures},
+            launch_paths::{
+                args_file_path_entries, java_path_list_separator, prepare_java_path_environment,
+                split_args_file_line,
+            },
+            rule_engine::{evaluate_rules, reset_unknown_feature_log, RuleContext, RuleFeatures},
         },
         models::{
             instance::{InstanceMetadata, LaunchAuthSession},
@@ -48,7 +61,15 @@ use crate::{
     infrastructure::downloader::queue::{
         ensure_official_binary_url, explain_network_error, official_retries, official_timeout,
     },
-    services::{instance_builder::build_instance_structure, java_installer::ensure_embedded_java},
+    infrastructure::filesystem::text_encoding::read_text_normalized,
+    infrastructure::http::tls,
+    platform::gpu_preference::apply_gpu_preference,
+    services::{
+        instance_builder::build_instance_structure,
+        java_installer::{ensure_embedded_java, ensure_java_build},
+    },
+    shared::clock::{app_clock, Clock},
+    shared::tasks::{TaskHandle, TaskProbe},
 };
 
 const DEFAULT_CACHE_EXPIRY_DAYS: u32 = 7;
@@ -155,14 +176,6 @@ fn build_version_id_candidates(version_id: &str, hints: &RedirectVersionHints) -
     candidates
 }
 
-#[derive(Debug, Clone, Serialize)]
-#[serde(rename_all = "camelCase")]
-struct RuntimeOutputEvent {
-    instance_root: String,
-    stream: String,
-    line: String,
-}
-
 #[derive(Debug, Clone)]
 struct CachedRedirectContext {
     ctx: RedirectLaunchContext,
@@ -207,6 +220,11 @@ pub struct RedirectCacheEntry {
     pub jar_cached: bool,
     pub libraries_cached: bool,
     pub assets_cached: bool,
+    #[serde(default)]
+    pub pinned: bool,
+    /// Última copia de mundos de la caché al launcher de origen (`sync_saves_to_source`).
+    #[serde(default)]
+    pub last_saves_sync_at: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -223,6 +241,18 @@ pub struct CacheCleanupResult {
     pub bytes_freed: u64,
     pub entries_remaining: usize,
     pub total_size_bytes: u64,
+    pub dry_run: bool,
+    pub removed: Vec<RemovedCacheEntry>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemovedCacheEntry {
+    pub instance_uuid: String,
+    pub version_id: String,
+    pub size_bytes: u64,
+    /// expired | source_missing | incomplete | cache_dir_missing | over_size_limit | over_entry_limit
+    pub reason: String,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -246,6 +276,7 @@ pub struct RedirectCacheEntryInfo {
     pub expires_in_days: i64,
     pub size_mb: u64,
     pub complete: bool,
+    pub pinned: bool,
 }
 
 #[derive(Debug, Serialize)]
@@ -271,13 +302,6 @@ fn now_millis() -> u128 {
         .unwrap_or(0)
 }
 
-fn now_unix_millis() -> Option<u64> {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .ok()
-        .map(|duration| duration.as_millis() as u64)
-}
-
 fn is_valid_mc_version(version: &str) -> bool {
     let parts: Vec<&str> = version.trim().split('.').collect();
     parts.len() >= 2
@@ -291,7 +315,7 @@ fn is_valid_mc_version(version: &str) -> bool {
 }
 
 fn read_json(path: &Path) -> Option<Value> {
-    let raw = fs::read_to_string(path).ok()?;
+    let raw = read_text_normalized(path).ok()?.text;
     serde_json::from_str::<Value>(&raw).ok()
 }
 
@@ -368,7 +392,7 @@ fn parse_loader_version_id(version_id: &str) -> Option<(String, String, String)>
     None
 }
 
-fn detect_loader_from_version_id(version_id: &str) -> Option<(String, String)> {
+pub(crate) fn detect_loader_from_version_id(version_id: &str) -> Option<(String, String)> {
     parse_loader_version_id(version_id).map(|(loader, loader_version, _)| (loader, loader_version))
 }
 
@@ -474,32 +498,24 @@ fn read_instance_manifest_strict(source_root: &Path) -> (String, String, String)
         }
     }
 
-    (String::new(), String::new(), String::new())
-}
+    if let Some(manifest) = read_external_manifest(source_root) {
+        return (
+            manifest.minecraft_version,
+            manifest.loader,
+            manifest.loader_version.unwrap_or_default(),
+        );
+    }
 
-fn write_instance_metadata(
-    instance_path: &Path,
-    metadata: &InstanceMetadata,
-) -> Result<(), String> {
-    let metadata_path = instance_path.join(".instance.json");
-    let raw = serde_json::to_string_pretty(metadata).map_err(|err| {
-        format!(
-            "No se pudo serializar metadata de {}: {err}",
-            instance_path.display()
-        )
-    })?;
-    fs::write(&metadata_path, raw)
-        .map_err(|err| format!("No se pudo guardar {}: {err}", metadata_path.display()))
+    (String::new(), String::new(), String::new())
 }
 
 async fn refresh_microsoft_token_if_needed(
     auth_session: LaunchAuthSession,
+    clock: &dyn Clock,
 ) -> Result<LaunchAuthSession, String> {
     let mut needs_refresh = auth_session.minecraft_access_token.trim().is_empty();
-    if let (Some(expires_at), Some(now)) = (
-        auth_session.minecraft_access_token_expires_at,
-        now_unix_millis(),
-    ) {
+    let now = clock.now_millis();
+    if let Some(expires_at) = auth_session.minecraft_access_token_expires_at {
         if expires_at <= now.saturating_add(60_000) {
             needs_refresh = true;
         }
@@ -516,14 +532,16 @@ async fn refresh_microsoft_token_if_needed(
             "No hay refresh token de Microsoft para renovar credenciales REDIRECT.".to_string()
         })?;
 
-    let client = reqwest::Client::new();
+    let client = tls::default_async_client();
     let ms = refresh_microsoft_access_token(&client, &refresh_token).await?;
     let xbox = authenticate_with_xbox_live(&client, &ms.access_token).await?;
     let xsts = authorize_xsts(&client, &xbox.token).await?;
     let minecraft = login_minecraft_with_xbox(&client, &xsts.uhs, &xsts.token).await?;
     let profile = read_minecraft_profile(&client, &minecraft.access_token).await?;
-    let expires_at = minecraft.expires_in.and_then(|expires_in| {
-        now_unix_millis().map(|now| now.saturating_add(expires_in.saturating_mul(1000)))
+    let expires_at = minecraft.expires_in.map(|expires_in| {
+        clock
+            .now_millis()
+            .saturating_add(expires_in.saturating_mul(1000))
     });
 
     Ok(LaunchAuthSession {
@@ -533,20 +551,17 @@ async fn refresh_microsoft_token_if_needed(
         minecraft_access_token_expires_at: expires_at,
         microsoft_refresh_token: ms.refresh_token.or(auth_session.microsoft_refresh_token),
         premium_verified: auth_session.premium_verified,
+        persisted: false,
     })
 }
 
-fn now_rfc3339() -> String {
-    chrono::Utc::now().to_rfc3339()
-}
-
 fn parse_rfc3339(raw: &str) -> Option<chrono::DateTime<chrono::Utc>> {
     chrono::DateTime::parse_from_rfc3339(raw)
         .ok()
         .map(|d| d.with_timezone(&chrono::Utc))
 }
 
-fn redirect_cache_root(app: &AppHandle) -> Result<PathBuf, String> {
+pub(crate) fn redirect_cache_root(app: &AppHandle) -> Result<PathBuf, String> {
     Ok(app
         .path()
         .app_cache_dir()
@@ -578,6 +593,55 @@ fn entry_cache_dir(cache_root: &Path, instance_uuid: &str) -> PathBuf {
     cache_root.join(instance_uuid)
 }
 
+/// Pasa la entrada de redirect-cache de `old_uuid` a `new_uuid` (carpeta e índice) si
+/// pertenece a `source_path`. Devuelve `false` si no había entrada atribuible.
+pub(crate) fn migrate_redirect_cache_entry(
+    cache_root: &Path,
+    old_uuid: &str,
+    new_uuid: &str,
+    source_path: &Path,
+) -> Result<bool, String> {
+    let mut index = load_redirect_cache_index(cache_root);
+    let Some(entry) = index
+        .entries
+        .iter_mut()
+        .find(|entry| entry.instance_uuid == old_uuid)
+    else {
+        return Ok(false);
+    };
+    if Path::new(&entry.source_path) != source_path {
+        return Ok(false);
+    }
+    let old_dir = entry_cache_dir(cache_root, old_uuid);
+    let new_dir = entry_cache_dir(cache_root, new_uuid);
+    if new_dir.exists() {
+        return Err(format!(
+            "Ya existe una entrada de redirect-cache en {}",
+            new_dir.display()
+        ));
+    }
+    if old_dir.exists() {
+        fs::rename(&old_dir, &new_dir).map_err(|err| {
+            format!(
+                "No se pudo mover {} a {}: {err}",
+                old_dir.display(),
+                new_dir.display()
+            )
+        })?;
+    }
+    entry.instance_uuid = new_uuid.to_string();
+    save_redirect_cache_index(cache_root, &index)?;
+    Ok(true)
+}
+
+/// Copia propia de una instancia REDIRECT en redirect-cache (nunca la carpeta de origen).
+pub(crate) fn redirect_cache_entry_dir(
+    app: &AppHandle,
+    instance_uuid: &str,
+) -> Result<PathBuf, String> {
+    Ok(entry_cache_dir(&redirect_cache_root(app)?, instance_uuid))
+}
+
 fn folder_size_bytes(root: &Path) -> u64 {
     let mut total = 0_u64;
     let mut stack = vec![root.to_path_buf()];
@@ -627,73 +691,174 @@ pub fn clear_redirect_cache_for_instance(
     Ok(())
 }
 
-fn entry_expired(entry: &RedirectCacheEntry) -> bool {
+fn entry_expired(entry: &RedirectCacheEntry, now: chrono::DateTime<chrono::Utc>) -> bool {
     let Some(last_used) = parse_rfc3339(&entry.last_used_at) else {
         return true;
     };
-    let age = chrono::Utc::now() - last_used;
+    let age = now - last_used;
     age.num_days() > entry.expires_after_days as i64
 }
 
-fn run_redirect_cache_cleanup(
+fn invalid_cache_entry_reason(
     cache_root: &Path,
-    index: &mut RedirectCacheIndex,
-) -> CacheCleanupResult {
-    let before_size = index.total_size_bytes;
-    let before_count = index.entries.len();
+    entry: &RedirectCacheEntry,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Option<&'static str> {
+    if !Path::new(&entry.source_path).exists() {
+        Some("source_missing")
+    } else if !entry.complete {
+        Some("incomplete")
+    } else if !entry_cache_dir(cache_root, &entry.instance_uuid).exists() {
+        Some("cache_dir_missing")
+    } else if entry_expired(entry, now) {
+        Some("expired")
+    } else {
+        None
+    }
+}
 
-    let expired_or_invalid: Vec<String> = index
+/// Entradas del índice de redirect-cache que no cuadran con el disco (origen o carpeta
+/// desaparecidos, copia a medias), sin contar las simplemente caducadas.
+pub(crate) fn redirect_cache_inconsistencies(
+    app: &AppHandle,
+) -> Result<Vec<(RedirectCacheEntry, &'static str)>, String> {
+    let cache_root = redirect_cache_root(app)?;
+    let now = app_clock(app).clock.now();
+    Ok(load_redirect_cache_index(&cache_root)
         .entries
-        .iter()
-        .filter(|entry| {
-            entry_expired(entry)
-                || !Path::new(&entry.source_path).exists()
-                || !entry.complete
-                || !entry_cache_dir(cache_root, &entry.instance_uuid).exists()
+        .into_iter()
+        .filter_map(|entry| {
+            invalid_cache_entry_reason(&cache_root, &entry, now)
+                .filter(|reason| *reason != "expired")
+                .map(|reason| (entry, reason))
         })
-        .map(|entry| entry.instance_uuid.clone())
-        .collect();
+        .collect())
+}
 
-    for instance_uuid in expired_or_invalid {
-        remove_cache_entry(cache_root, index, &instance_uuid);
+/// Decide qué entradas se eliminarían. Las entradas fijadas (`pinned`) siguen caducando e
+/// invalidándose como las demás; solo quedan fuera del recorte por tamaño y número (LRU).
+fn plan_redirect_cache_cleanup(
+    cache_root: &Path,
+    index: &RedirectCacheIndex,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Vec<RemovedCacheEntry> {
+    let mut removed = Vec::new();
+    let mut remaining = Vec::new();
+    for entry in &index.entries {
+        match invalid_cache_entry_reason(cache_root, entry, now) {
+            Some(reason) => removed.push(RemovedCacheEntry {
+                instance_uuid: entry.instance_uuid.clone(),
+                version_id: entry.version_id.clone(),
+                size_bytes: entry.size_bytes,
+                reason: reason.to_string(),
+            }),
+            None => remaining.push(entry),
+        }
     }
 
-    index.entries.sort_by_key(|entry| {
+    remaining.sort_by_key(|entry| {
         parse_rfc3339(&entry.last_used_at)
             .map(|d| d.timestamp())
             .unwrap_or(i64::MIN)
     });
 
     let max_bytes = MAX_CACHE_SIZE_MB * 1024 * 1024;
-    recalc_cache_totals(index);
-    while index.total_size_bytes > max_bytes || index.entries.len() > MAX_CACHE_ENTRIES {
-        let Some(oldest) = index.entries.first().cloned() else {
+    let mut total_bytes = remaining.iter().map(|entry| entry.size_bytes).sum::<u64>();
+    let mut count = remaining.len();
+    for entry in remaining.iter().filter(|entry| !entry.pinned) {
+        let reason = if total_bytes > max_bytes {
+            "over_size_limit"
+        } else if count > MAX_CACHE_ENTRIES {
+            "over_entry_limit"
+        } else {
             break;
         };
-        remove_cache_entry(cache_root, index, &oldest.instance_uuid);
-        recalc_cache_totals(index);
+        removed.push(RemovedCacheEntry {
+            instance_uuid: entry.instance_uuid.clone(),
+            version_id: entry.version_id.clone(),
+            size_bytes: entry.size_bytes,
+            reason: reason.to_string(),
+        });
+        total_bytes = total_bytes.saturating_sub(entry.size_bytes);
+        count -= 1;
+    }
+
+    removed
+}
+
+fn run_redirect_cache_cleanup(
+    cache_root: &Path,
+    index: &mut RedirectCacheIndex,
+    dry_run: bool,
+    clock: &dyn Clock,
+) -> CacheCleanupResult {
+    recalc_cache_totals(index);
+    let before_size = index.total_size_bytes;
+    let removed = plan_redirect_cache_cleanup(cache_root, index, clock.now());
+
+    if dry_run {
+        let bytes_freed = removed.iter().map(|entry| entry.size_bytes).sum::<u64>();
+        return CacheCleanupResult {
+            entries_removed: removed.len(),
+            bytes_freed,
+            entries_remaining: index.entries.len().saturating_sub(removed.len()),
+            total_size_bytes: before_size.saturating_sub(bytes_freed),
+            dry_run,
+            removed,
+        };
+    }
+
+    for entry in &removed {
+        remove_cache_entry(cache_root, index, &entry.instance_uuid);
     }
 
-    index.last_cleanup_at = now_rfc3339();
+    index.last_cleanup_at = clock.now_rfc3339();
     recalc_cache_totals(index);
 
     CacheCleanupResult {
-        entries_removed: before_count.saturating_sub(index.entries.len()),
+        entries_removed: removed.len(),
         bytes_freed: before_size.saturating_sub(index.total_size_bytes),
         entries_remaining: index.entries.len(),
         total_size_bytes: index.total_size_bytes,
+        dry_run,
+        removed,
     }
 }
 
-pub fn cleanup_redirect_cache_on_startup(app: &AppHandle) -> Result<(), String> {
+fn run_automatic_redirect_cache_cleanup(app: &AppHandle, trigger: &str) -> Result<(), String> {
     let cache_root = redirect_cache_root(app)?;
     let mut index = load_redirect_cache_index(&cache_root);
-    run_redirect_cache_cleanup(&cache_root, &mut index);
-    save_redirect_cache_index(&cache_root, &index)
+    let result = run_redirect_cache_cleanup(
+        &cache_root,
+        &mut index,
+        false,
+        app_clock(app).clock.as_ref(),
+    );
+    save_redirect_cache_index(&cache_root, &index)?;
+
+    if !result.removed.is_empty() {
+        log::info!(
+            "[REDIRECT] Limpieza automática de caché ({trigger}): {} entradas, {} MB liberados",
+            result.entries_removed,
+            result.bytes_freed / (1024 * 1024)
+        );
+        let _ = app.emit(
+            "redirect_cache_cleaned",
+            json!({
+                "trigger": trigger,
+                "result": result,
+            }),
+        );
+    }
+    Ok(())
+}
+
+pub fn cleanup_redirect_cache_on_startup(app: &AppHandle) -> Result<(), String> {
+    run_automatic_redirect_cache_cleanup(app, "startup")
 }
 
 pub fn cleanup_redirect_cache_after_launch(app: &AppHandle) -> Result<(), String> {
-    cleanup_redirect_cache_on_startup(app)
+    run_automatic_redirect_cache_cleanup(app, "after_launch")
 }
 
 fn read_redirect_file(instance_root: &Path) -> Result<ShortcutRedirect, String> {
@@ -704,6 +869,32 @@ fn read_redirect_file(instance_root: &Path) -> Result<ShortcutRedirect, String>
         .map_err(|err| format!("No se pudo parsear {}: {err}", path.display()))
 }
 
+/// Carpeta y launcher de origen de una instancia redirigida.
+pub(crate) fn redirect_source(instance_root: &Path) -> Result<(PathBuf, String), String> {
+    let redirect = read_redirect_file(instance_root)?;
+    Ok((
+        PathBuf::from(redirect.source_path),
+        redirect.source_launcher,
+    ))
+}
+
+/// Game dir que usará una instancia redirigida: el del atajo READY o el detectado en la
+/// carpeta de origen. `None` si no existe en disco.
+pub(crate) fn redirect_game_dir(instance_root: &Path) -> Option<PathBuf> {
+    let shortcut_dir = fs::read_to_string(instance_root.join("state.json"))
+        .ok()
+        .and_then(|raw| serde_json::from_str::<ShortcutState>(&raw).ok())
+        .filter(|state| state.status.eq_ignore_ascii_case("READY"))
+        .map(|state| PathBuf::from(state.external_game_dir));
+    let game_dir = match shortcut_dir {
+        Some(dir) => dir,
+        None => resolve_redirect_game_dir(Path::new(
+            &read_redirect_file(instance_root).ok()?.source_path,
+        )),
+    };
+    game_dir.is_dir().then_some(game_dir)
+}
+
 fn system_minecraft_root() -> Option<PathBuf> {
     #[cfg(target_os = "windows")]
     {
@@ -766,6 +957,10 @@ fn known_launcher_roots() -> Vec<PathBuf> {
         }
     }
 
+    for (launcher, root) in known_external_launcher_roots() {
+        roots.push(launcher.shared_runtime_dir(&root));
+    }
+
     roots
 }
 
@@ -786,10 +981,20 @@ fn launcher_roots_for_source(source_launcher: &str) -> Vec<PathBuf> {
                 || (launcher.contains("prism") && path.contains("prism"))
                 || (launcher.contains("modrinth") && path.contains("modrinth"))
                 || (launcher.contains("multimc") && path.contains("multimc"))
+                || (launcher.contains("gdlauncher") && path.contains("gdlauncher"))
+                || (launcher.contains("atlauncher") && path.contains("atlauncher"))
         })
         .collect()
 }
 
+/// Raíces del launcher de origen: primero las deducidas de la propia instancia (GDLauncher
+/// y ATLauncher pueden estar instalados fuera de su ruta por defecto) y luego las conocidas.
+fn launcher_roots_for_instance(source_path: &Path, source_launcher: &str) -> Vec<PathBuf> {
+    let mut roots = shared_runtime_roots(source_path);
+    roots.extend(launcher_roots_for_source(source_launcher));
+    unique_paths(roots)
+}
+
 fn unique_paths(paths: Vec<PathBuf>) -> Vec<PathBuf> {
     let mut out = Vec::new();
     for p in paths {
@@ -801,7 +1006,7 @@ fn unique_paths(paths: Vec<PathBuf>) -> Vec<PathBuf> {
 }
 
 fn read_and_validate_version_json(path: &Path) -> Option<Value> {
-    let raw = fs::read_to_string(path).ok()?;
+    let raw = read_text_normalized(path).ok()?.text;
     let json: Value = serde_json::from_str(&raw).ok()?;
 
     let has_main_class = json.get("mainClass").and_then(Value::as_str).is_some();
@@ -846,7 +1051,7 @@ fn find_versions_matching_prefix(versions_dir: &Path, prefix: &str) -> Vec<PathB
 
 fn detect_source_instance_hints(source_path: &Path) -> (Option<String>, Option<String>) {
     let prism_manifest = source_path.join("minecraftinstance.json");
-    if let Ok(raw) = fs::read_to_string(&prism_manifest) {
+    if let Ok(raw) = read_text_normalized(&prism_manifest).map(|normalized| normalized.text) {
         if let Ok(json) = serde_json::from_str::<Value>(&raw) {
             let mc = json
                 .get("components")
@@ -888,7 +1093,7 @@ fn detect_source_instance_hints(source_path: &Path) -> (Option<String>, Option<S
     }
 
     let modrinth_manifest = source_path.join("profile.json");
-    if let Ok(raw) = fs::read_to_string(&modrinth_manifest) {
+    if let Ok(raw) = read_text_normalized(&modrinth_manifest).map(|normalized| normalized.text) {
         if let Ok(json) = serde_json::from_str::<Value>(&raw) {
             let loader = json
                 .get("loader")
@@ -1077,7 +1282,7 @@ fn resolve_official_version_json(
         }
     }
 
-    for launcher_root in launcher_roots_for_source(source_launcher) {
+    for launcher_root in launcher_roots_for_instance(source_path, source_launcher) {
         for version_id in version_ids {
             if version_id.ends_with('-') {
                 let versions_dir = launcher_root.join("versions");
@@ -1457,7 +1662,7 @@ fn minecraft_jar_candidates(
         }
     }
 
-    for launcher_root in launcher_roots_for_source(source_launcher) {
+    for launcher_root in launcher_roots_for_instance(source_path, source_launcher) {
         for version_id in version_ids {
             candidates.push(
                 launcher_root
@@ -1484,7 +1689,7 @@ fn libraries_dir_candidates(source_path: &Path, source_launcher: &str) -> Vec<Pa
         candidates.push(system_root.join("libraries"));
     }
 
-    for launcher_root in launcher_roots_for_source(source_launcher) {
+    for launcher_root in launcher_roots_for_instance(source_path, source_launcher) {
         candidates.push(launcher_root.join("libraries"));
     }
 
@@ -1504,7 +1709,7 @@ fn assets_dir_candidates(source_path: &Path, source_launcher: &str) -> Vec<PathB
         candidates.push(system_root.join("assets"));
     }
 
-    for launcher_root in launcher_roots_for_source(source_launcher) {
+    for launcher_root in launcher_roots_for_instance(source_path, source_launcher) {
         candidates.push(launcher_root.join("assets"));
     }
 
@@ -1837,7 +2042,7 @@ fn fix_redirect_cache_library_paths_in_jvm_args(
         let Some(module_value) = jvm_args.get(idx + 1).cloned() else {
             continue;
         };
-        let separator = if module_value.contains(';') { ';' } else { ':' };
+        let separator = java_path_list_separator(&module_value);
         let mut missing = Vec::new();
         let fixed_segments: Vec<String> = module_value
             .split(separator)
@@ -1958,7 +2163,10 @@ fn find_real_forge_libraries_dir(
 
             for line in raw.lines() {
                 let line = line.trim();
-                if let Some(val) = line.strip_prefix("-DlibraryDirectory=") {
+                if let Some(val) = split_args_file_line(line)
+                    .first()
+                    .and_then(|arg| arg.strip_prefix("-DlibraryDirectory="))
+                {
                     if !val.contains("${") {
                         let p = PathBuf::from(val);
                         if p.is_dir() {
@@ -1971,13 +2179,8 @@ fn find_real_forge_libraries_dir(
                     }
                 }
 
-                let sep = if cfg!(target_os = "windows") {
-                    ';'
-                } else {
-                    ':'
-                };
-                for segment in line.split(sep) {
-                    let segment = segment.trim();
+                for segment in args_file_path_entries(line) {
+                    let segment = segment.as_str();
                     if segment.len() < 5 || segment.contains("${") {
                         continue;
                     }
@@ -2793,7 +2996,7 @@ fn locate_forge_generated_jar(
     }
 
     search_roots.extend(
-        launcher_roots_for_source(source_launcher)
+        launcher_roots_for_instance(source_path, source_launcher)
             .into_iter()
             .map(|root| root.join("libraries")),
     );
@@ -3114,7 +3317,7 @@ fn collect_runtime_libraries_dirs(
         dirs.push(system_root.join("libraries"));
     }
 
-    for launcher_root in launcher_roots_for_source(source_launcher) {
+    for launcher_root in launcher_roots_for_instance(source_path, source_launcher) {
         dirs.push(launcher_root.join("libraries"));
     }
 
@@ -3703,7 +3906,7 @@ fn link_or_copy(existing: &Path, target: &Path) -> Result<(), String> {
 }
 
 fn build_async_official_client() -> Result<reqwest::Client, String> {
-    reqwest::Client::builder()
+    tls::with_custom_roots_async(reqwest::Client::builder())
         .timeout(official_timeout())
         .connect_timeout(std::time::Duration::from_secs(30))
         .tcp_keepalive(std::time::Duration::from_secs(60))
@@ -4096,6 +4299,7 @@ async fn download_redirect_runtime(
     version_id: &str,
     source_launcher: &str,
     hints: &RedirectVersionHints,
+    task: &TaskProbe,
 ) -> Result<RedirectCacheEntry, String> {
     let enriched_hints =
         if let Some((loader, loader_ver, mc_ver)) = parse_loader_version_id(version_id) {
@@ -4184,6 +4388,8 @@ async fn download_redirect_runtime(
     fs::create_dir_all(&assets_indexes_dir)
         .map_err(|err| format!("No se pudo crear assets/indexes cache: {err}"))?;
 
+    task.check_cancelled()?;
+    task.progress(10, Some(100), "percent");
     emit_redirect_cache_status(
         app,
         json!({
@@ -4318,6 +4524,8 @@ async fn download_redirect_runtime(
         .await
         .map_err(|err| format!("No se pudo guardar version json mergeado: {err}"))?;
 
+    task.check_cancelled()?;
+    task.progress(30, Some(100), "percent");
     emit_redirect_cache_status(
         app,
         json!({
@@ -4362,6 +4570,8 @@ async fn download_redirect_runtime(
         return Err("version json no contiene downloads.client".to_string());
     }
 
+    task.check_cancelled()?;
+    task.progress(60, Some(100), "percent");
     emit_redirect_cache_status(
         app,
         json!({
@@ -4385,6 +4595,7 @@ async fn download_redirect_runtime(
     let mut missing_critical: Vec<String> = Vec::new();
 
     for lib in &libraries_to_sync {
+        task.check_cancelled()?;
         if let Some(rules) = lib.get("rules").and_then(Value::as_array) {
             if !evaluate_rules(rules, &rule_context) {
                 continue;
@@ -4553,13 +4764,15 @@ Ruta donde deberían estar: {}/libraries/",
             source_launcher,
             source_launcher,
             source_launcher,
-            launcher_roots_for_source(source_launcher)
+            launcher_roots_for_instance(source_path, source_launcher)
                 .first()
                 .map(|path| path.display().to_string())
                 .unwrap_or_else(|| "tu launcher".to_string())
         ));
     }
 
+    task.check_cancelled()?;
+    task.progress(85, Some(100), "percent");
     emit_redirect_cache_status(
         app,
         json!({
@@ -4601,6 +4814,8 @@ Ruta donde deberían estar: {}/libraries/",
                 .await
                 .map_err(|err| format!("No se pudo guardar {}: {err}", asset_path.display()))?;
 
+            task.check_cancelled()?;
+            task.progress(92, Some(100), "percent");
             emit_redirect_cache_status(
                 app,
                 json!({
@@ -4623,12 +4838,13 @@ Ruta donde deberían estar: {}/libraries/",
                 source_path,
                 source_launcher,
                 &entry_dir.join("assets"),
+                task,
             )
             .await?;
         }
     }
 
-    let created_at = now_rfc3339();
+    let created_at = app_clock(app).clock.now_rfc3339();
     Ok(RedirectCacheEntry {
         instance_uuid: instance_uuid.to_string(),
         version_id: version_id.to_string(),
@@ -4643,6 +4859,8 @@ Ruta donde deberían estar: {}/libraries/",
         jar_cached: jar_path.exists(),
         libraries_cached: libs_dir.exists(),
         assets_cached: assets_indexes_dir.exists(),
+        pinned: false,
+        last_saves_sync_at: None,
     })
 }
 
@@ -4652,6 +4870,7 @@ async fn download_assets_objects_for_redirect(
     source_path: &Path,
     source_launcher: &str,
     cache_assets_dir: &Path,
+    task: &TaskProbe,
 ) -> Result<(), String> {
     let objects = assets_index
         .get("objects")
@@ -4671,7 +4890,7 @@ async fn download_assets_objects_for_redirect(
             roots.push(system_root.join("assets").join("objects"));
         }
         roots.extend(
-            launcher_roots_for_source(source_launcher)
+            launcher_roots_for_instance(source_path, source_launcher)
                 .into_iter()
                 .map(|root| root.join("assets").join("objects")),
         );
@@ -4679,6 +4898,7 @@ async fn download_assets_objects_for_redirect(
     };
 
     for obj in objects.values() {
+        task.check_cancelled()?;
         let Some(hash) = obj.get("hash").and_then(Value::as_str) else {
             continue;
         };
@@ -4770,12 +4990,13 @@ async fn ensure_redirect_cache_context(
 ) -> Result<RedirectLaunchContext, String> {
     let cache_root = redirect_cache_root(app)?;
     let mut index = load_redirect_cache_index(&cache_root);
+    let clock = app_clock(app).clock;
 
     if let Some(entry) = index.entries.iter().find(|entry| {
         entry.instance_uuid == instance_uuid
             && entry.version_id == version_id
             && entry.complete
-            && !entry_expired(entry)
+            && !entry_expired(entry, clock.now())
     }) {
         let cache_libs = entry_cache_dir(&cache_root, instance_uuid).join("libraries");
         let loader_lower = hints.loader.trim().to_ascii_lowercase();
@@ -4829,14 +5050,18 @@ async fn ensure_redirect_cache_context(
         }
     }
 
+    let was_pinned = index
+        .entries
+        .iter()
+        .any(|entry| entry.instance_uuid == instance_uuid && entry.pinned);
     remove_cache_entry(&cache_root, &mut index, instance_uuid);
     index.entries.push(RedirectCacheEntry {
         instance_uuid: instance_uuid.to_string(),
         version_id: version_id.to_string(),
         source_path: source_path.display().to_string(),
         source_launcher: source_launcher.to_string(),
-        created_at: now_rfc3339(),
-        last_used_at: now_rfc3339(),
+        created_at: clock.now_rfc3339(),
+        last_used_at: clock.now_rfc3339(),
         expires_after_days: DEFAULT_CACHE_EXPIRY_DAYS,
         size_bytes: 0,
         complete: false,
@@ -4844,9 +5069,17 @@ async fn ensure_redirect_cache_context(
         jar_cached: false,
         libraries_cached: false,
         assets_cached: false,
+        pinned: was_pinned,
+        last_saves_sync_at: None,
     });
     save_redirect_cache_index(&cache_root, &index)?;
 
+    // Cancelar deja la entrada marcada como incompleta; el siguiente uso la regenera.
+    let copy_task = TaskHandle::begin(
+        app,
+        "redirect_cache_copy",
+        format!("Caché REDIRECT de {version_id}"),
+    );
     let downloaded = download_redirect_runtime(
         app,
         source_path,
@@ -4854,8 +5087,12 @@ async fn ensure_redirect_cache_context(
         version_id,
         source_launcher,
         hints,
+        &copy_task,
     )
-    .await?;
+    .await;
+    copy_task.finish(&downloaded);
+    let mut downloaded = downloaded?;
+    downloaded.pinned = was_pinned;
 
     index
         .entries
@@ -4902,6 +5139,27 @@ pub async fn prewarm_redirect_runtime(
     Ok(())
 }
 
+/// Anota en la entrada de redirect-cache la hora de la última sincronización de mundos
+/// hacia el origen. `false` si la instancia no tiene entrada en la caché.
+pub(crate) fn record_saves_sync(
+    app: &AppHandle,
+    instance_uuid: &str,
+    synced_at: &str,
+) -> Result<bool, String> {
+    let cache_root = redirect_cache_root(app)?;
+    let mut index = load_redirect_cache_index(&cache_root);
+    let Some(entry) = index
+        .entries
+        .iter_mut()
+        .find(|entry| entry.instance_uuid == instance_uuid)
+    else {
+        return Ok(false);
+    };
+    entry.last_saves_sync_at = Some(synced_at.to_string());
+    save_redirect_cache_index(&cache_root, &index)?;
+    Ok(true)
+}
+
 fn touch_cache_entry_last_used(app: &AppHandle, instance_uuid: &str) {
     if let Ok(cache_root) = redirect_cache_root(app) {
         let mut index = load_redirect_cache_index(&cache_root);
@@ -4910,7 +5168,7 @@ fn touch_cache_entry_last_used(app: &AppHandle, instance_uuid: &str) {
             .iter_mut()
             .find(|entry| entry.instance_uuid == instance_uuid)
         {
-            entry.last_used_at = now_rfc3339();
+            entry.last_used_at = app_clock(app).clock.now_rfc3339();
         }
         recalc_cache_totals(&mut index);
         let _ = save_redirect_cache_index(&cache_root, &index);
@@ -4921,17 +5179,51 @@ fn touch_cache_entry_last_used(app: &AppHandle, instance_uuid: &str) {
 pub fn force_cleanup_redirect_cache(app: AppHandle) -> Result<CacheCleanupResult, String> {
     let cache_root = redirect_cache_root(&app)?;
     let mut index = load_redirect_cache_index(&cache_root);
-    let result = run_redirect_cache_cleanup(&cache_root, &mut index);
+    let result = run_redirect_cache_cleanup(
+        &cache_root,
+        &mut index,
+        false,
+        app_clock(&app).clock.as_ref(),
+    );
     save_redirect_cache_index(&cache_root, &index)?;
     Ok(result)
 }
 
+#[tauri::command]
+pub fn preview_redirect_cache_cleanup(app: AppHandle) -> Result<CacheCleanupResult, String> {
+    let cache_root = redirect_cache_root(&app)?;
+    let mut index = load_redirect_cache_index(&cache_root);
+    Ok(run_redirect_cache_cleanup(
+        &cache_root,
+        &mut index,
+        true,
+        app_clock(&app).clock.as_ref(),
+    ))
+}
+
+#[tauri::command]
+pub fn set_redirect_cache_pinned(
+    app: AppHandle,
+    instance_uuid: String,
+    pinned: bool,
+) -> Result<(), String> {
+    let cache_root = redirect_cache_root(&app)?;
+    let mut index = load_redirect_cache_index(&cache_root);
+    let entry = index
+        .entries
+        .iter_mut()
+        .find(|entry| entry.instance_uuid == instance_uuid)
+        .ok_or_else(|| format!("No existe entrada de caché REDIRECT para {instance_uuid}."))?;
+    entry.pinned = pinned;
+    save_redirect_cache_index(&cache_root, &index)
+}
+
 #[tauri::command]
 pub fn get_redirect_cache_info(app: AppHandle) -> Result<RedirectCacheInfo, String> {
     let cache_root = redirect_cache_root(&app)?;
     let mut index = load_redirect_cache_index(&cache_root);
     recalc_cache_totals(&mut index);
-    let now = chrono::Utc::now();
+    let now = app_clock(&app).clock.now();
     let entries = index
         .entries
         .iter()
@@ -4947,6 +5239,7 @@ pub fn get_redirect_cache_info(app: AppHandle) -> Result<RedirectCacheInfo, Stri
                 expires_in_days,
                 size_mb: entry.size_bytes / (1024 * 1024),
                 complete: entry.complete,
+                pinned: entry.pinned,
             }
         })
         .collect::<Vec<_>>();
@@ -4963,13 +5256,15 @@ pub fn get_redirect_cache_info(app: AppHandle) -> Result<RedirectCacheInfo, Stri
 
 #[tauri::command]
 pub fn validate_redirect_instance(
+    app: AppHandle,
     instance_path: String,
 ) -> Result<RedirectValidationResult, String> {
+    let instance_path = resolve_trusted_instance_root(&app, &instance_path)?;
     let mut warnings = Vec::new();
     let mut errors = Vec::new();
-    let instance_root = PathBuf::from(&instance_path);
+    let instance_root = instance_path.path().to_path_buf();
 
-    let metadata = get_instance_metadata(instance_path.clone())?;
+    let metadata = read_instance_metadata(instance_path.to_string())?;
     let redirect = read_redirect_file(&instance_root)?;
     let source = PathBuf::from(&redirect.source_path);
     let source_exists = source.exists();
@@ -5050,11 +5345,30 @@ pub async fn launch_redirect_instance(
     app: AppHandle,
     instance_root: String,
     auth_session: LaunchAuthSession,
+    persist_refreshed_session: bool,
 ) -> Result<StartInstanceResult, String> {
-    let auth_session = refresh_microsoft_token_if_needed(auth_session)
-        .await
-        .map_err(|e| format!("No se pudo refrescar el token de autenticación: {e}"))?;
-    let metadata = get_instance_metadata(instance_root.clone())?;
+    reset_unknown_feature_log();
+    let mut auth_session =
+        refresh_microsoft_token_if_needed(auth_session, app_clock(&app).clock.as_ref())
+            .await
+            .map_err(|e| format!("No se pudo refrescar el token de autenticación: {e}"))?;
+    auth_session.persisted = match crate::infrastructure::filesystem::paths::resolve_launcher_root(
+        &app,
+    )
+    .and_then(|launcher_root| {
+        crate::app::token_maintenance::persist_launch_session(
+            &launcher_root,
+            &auth_session,
+            persist_refreshed_session,
+        )
+    }) {
+        Ok(persisted) => persisted,
+        Err(err) => {
+            log::warn!("[REDIRECT] ⚠ No se pudo guardar la sesión renovada: {err}");
+            false
+        }
+    };
+    let metadata = read_instance_metadata(instance_root.to_string())?;
     let instance_path = PathBuf::from(&instance_root);
     let redirect = read_redirect_file(&instance_path)?;
 
@@ -5070,7 +5384,7 @@ pub async fn launch_redirect_instance(
                 source_path = crate::app::shortcut_instance::normalize_external_root(&relinked);
                 state.external_game_dir = relinked.display().to_string();
                 state.external_root_dir = source_path.display().to_string();
-                state.updated_at = chrono::Utc::now().to_rfc3339();
+                state.updated_at = app_clock(&app).clock.now_rfc3339();
                 let _ = crate::app::shortcut_instance::save_shortcut_state(&instance_path, &state);
                 log::info!(
                     "[SHORTCUT] relink OK root={} game={}",
@@ -5160,6 +5474,7 @@ pub async fn launch_redirect_instance(
                     .stderr(Stdio::piped())
                     .stdin(Stdio::null())
                     .current_dir(Path::new(&relinked_game_dir));
+                prepare_java_path_environment(&mut command);
                 #[cfg(unix)]
                 {
                     command.process_group(0);
@@ -5168,55 +5483,40 @@ pub async fn launch_redirect_instance(
                 {
                     command.creation_flags(CREATE_NO_WINDOW);
                 }
-                let mut child = command
+                let gpu_preference = apply_gpu_preference(
+                    &mut command,
+                    &java_launch_path,
+                    metadata.preferred_gpu.as_deref(),
+                );
+                let child = command
                     .spawn()
                     .map_err(|err| format!("No se pudo iniciar shortcut READY: {err}"))?;
                 let pid = child.id();
-                let stdout = child.stdout.take();
-                let stderr = child.stderr.take();
-                let app_for_thread = app.clone();
-                let instance_root_for_thread = instance_root.clone();
-                thread::spawn(move || {
-                    if let Some(stdout_pipe) = stdout {
-                        let app_for_stdout = app_for_thread.clone();
-                        let instance_for_stdout = instance_root_for_thread.clone();
-                        thread::spawn(move || {
-                            let reader = BufReader::new(stdout_pipe);
-                            for line in reader.lines().map_while(Result::ok) {
-                                let _ = app_for_stdout.emit(
-                                    "instance_runtime_output",
-                                    RuntimeOutputEvent {
-                                        instance_root: instance_for_stdout.clone(),
-                                        stream: "stdout".to_string(),
-                                        line,
-                                    },
-                                );
-                            }
-                        });
-                    }
-                    if let Some(stderr_pipe) = stderr {
-                        let app_for_stderr = app_for_thread.clone();
-                        let instance_for_stderr = instance_root_for_thread.clone();
-                        thread::spawn(move || {
-                            let reader = BufReader::new(stderr_pipe);
-                            for line in reader.lines().map_while(Result::ok) {
-                                let _ = app_for_stderr.emit(
-                                    "instance_runtime_output",
-                                    RuntimeOutputEvent {
-                                        instance_root: instance_for_stderr.clone(),
-                                        stream: "stderr".to_string(),
-                                        line,
-                                    },
-                                );
-                            }
-                        });
-                    }
-                    let _ = child.wait();
-                });
+                start_runtime_metrics(
+                    &instance_root,
+                    pid,
+                    gpu_preference
+                        .as_ref()
+                        .map(|applied| applied.log_line.clone()),
+                );
+                crate::app::instance_service::monitor_child(
+                    app.clone(),
+                    instance_root.clone(),
+                    child,
+                    SessionAccount {
+                        profile_id: auth_session.profile_id.clone(),
+                        profile_name: auth_session.profile_name.clone(),
+                    },
+                    PathBuf::from(&relinked_game_dir),
+                    gpu_preference_cleanup(&app, gpu_preference.as_ref()),
+                );
                 return Ok(StartInstanceResult {
                     pid: pid as u32,
                     java_path: launch_plan.java_path.clone(),
-                    logs: Vec::new(),
+                    logs: gpu_preference
+                        .map(|applied| applied.log_line)
+                        .into_iter()
+                        .collect(),
                     refreshed_auth_session: auth_session.clone(),
                 });
             }
@@ -5355,7 +5655,6 @@ pub async fn launch_redirect_instance(
             top_missing.join(" | ")
         ));
     }
-    let classpath_entry_count = classpath.split(classpath_separator).count();
     let natives_dir = instance_path.join("natives");
     let _ = fs::remove_dir_all(&natives_dir);
 
@@ -5370,23 +5669,45 @@ pub async fn launch_redirect_instance(
             "error": Value::Null
         }),
     );
-    let redirect_cache_dir = instance_path.join("runtime-temp");
-    prepare_redirect_natives(
-        &app,
+    let classpath_entries: Vec<String> = classpath
+        .split(classpath_separator)
+        .filter(|e| !e.trim().is_empty())
+        .map(ToOwned::to_owned)
+        .collect();
+    let finalized = finalize_redirect_classpath(
+        &classpath_entries,
+        &libraries_dirs,
         &ctx.version_json,
-        &metadata.version_id,
-        &ctx.libraries_dir,
-        &redirect_cache_dir,
         &natives_dir,
-        &redirect.source_launcher,
-    )
-    .await?;
+        &mut logs,
+    );
+    for line in logs.drain(..) {
+        log::info!("[REDIRECT] {line}");
+    }
+    let classpath_entries = finalized?;
+    if !has_any_file(&natives_dir) {
+        // Versiones antiguas cuyo launcher de origen no conserva los jars de natives:
+        // se recurre a la descarga desde el manifest de Mojang.
+        let redirect_cache_dir = instance_path.join("runtime-temp");
+        prepare_redirect_natives(
+            &app,
+            &ctx.version_json,
+            &metadata.version_id,
+            &ctx.libraries_dir,
+            &redirect_cache_dir,
+            &natives_dir,
+            &redirect.source_launcher,
+        )
+        .await?;
+    }
     if !has_any_file(&natives_dir) {
         return Err(format!(
             "No se encontraron ni pudieron descargarse los natives para {} en {}. Verifica que la versión esté instalada en el launcher de origen.",
             metadata.version_id, redirect.source_launcher
         ));
     }
+    let classpath = classpath_entries.join(classpath_separator);
+    let classpath_entry_count = classpath_entries.len();
 
     let asset_index = ctx
         .version_json
@@ -5429,18 +5750,33 @@ pub async fn launch_redirect_instance(
         &ctx.version_json,
         &launch_context,
         &RuleContext {
-            os_name: RuleContext::current().os_name,
-            arch: std::env::consts::ARCH.to_string(),
-            features: RuleFeatures::default(),
+            features: launch_context.apply_quick_play_features(RuleFeatures::default()),
+            ..RuleContext::current()
         },
     )?;
-
-    let mut jvm_args = vec![
-        format!("-Xmx{}M", metadata.ram_mb.max(512)),
-        "-Xms512M".to_string(),
-    ];
+    ensure_online_launch_flags(&resolved.game, &launch_context)?;
+
+    let user_java_args = normalize_java_args(&metadata.java_args)?;
+    for change in &user_java_args.changes {
+        log::warn!("⚠ java_args: {change}");
+    }
+    let mut jvm_args = merge_memory_args(
+        &[
+            format!("-Xmx{}M", metadata.ram_mb.max(512)),
+            "-Xms512M".to_string(),
+        ],
+        &user_java_args.args,
+    );
+    if metadata.auto_jvm_tuning {
+        jvm_args.extend(crate::app::instance_service::auto_jvm_tuning_args(
+            &metadata,
+            Some(runtime.major() as u32),
+            &user_java_args.args,
+            &mut logs,
+        ));
+    }
     jvm_args.extend(resolved.jvm);
-    jvm_args.extend(metadata.java_args.clone());
+    jvm_args.extend(user_java_args.args);
 
     let mc_root_cache = ctx
         .libraries_dir
@@ -5585,9 +5921,12 @@ pub async fn launch_redirect_instance(
         }),
     );
 
+    let mut violations = StrictViolations::default();
     for warning in verify_game_dir_has_instance_data(&ctx.game_dir) {
         log::warn!("[REDIRECT] Advertencia game_dir: {warning}");
+        violations.note(REDIRECT_GAME_DIR_DATA, warning);
     }
+    enforce_strict_mode(metadata.strict_mode, &violations.into_vec())?;
 
     log::info!("[REDIRECT] === DIAGNÓSTICO DE LOADER ===");
     log::info!("[REDIRECT] loader:          {}", metadata.loader);
@@ -5650,6 +5989,7 @@ pub async fn launch_redirect_instance(
         .stderr(Stdio::piped())
         .stdin(Stdio::null())
         .current_dir(&ctx.game_dir);
+    prepare_java_path_environment(&mut command);
 
     #[cfg(windows)]
     {
@@ -5676,7 +6016,13 @@ pub async fn launch_redirect_instance(
         command.process_group(0);
     }
 
-    let mut child = command.spawn().map_err(|err| {
+    let gpu_preference = apply_gpu_preference(
+        &mut command,
+        &java_launch_path,
+        metadata.preferred_gpu.as_deref(),
+    );
+
+    let child = command.spawn().map_err(|err| {
         let message = format!("No se pudo iniciar el proceso REDIRECT: {err}");
         let _ = app.emit(
             "redirect_launch_status",
@@ -5693,8 +6039,6 @@ pub async fn launch_redirect_instance(
     })?;
 
     let pid = child.id();
-    let stdout = child.stdout.take();
-    let stderr = child.stderr.take();
     let _ = app.emit(
         "redirect_launch_status",
         json!({
@@ -5707,89 +6051,46 @@ pub async fn launch_redirect_instance(
         }),
     );
 
-    let app_for_thread = app.clone();
+    start_runtime_metrics(
+        &instance_root,
+        pid,
+        gpu_preference
+            .as_ref()
+            .map(|applied| applied.log_line.clone()),
+    );
+    // Registro de salida, eventos, crashes y presencia: lo mismo que el lanzamiento normal.
+    let app_for_exit = app.clone();
     let instance_uuid = metadata.internal_uuid.clone();
     let source_launcher = redirect.source_launcher.clone();
-    let instance_root_for_thread = instance_root.clone();
-    let registry_instance_root = instance_root.clone();
-    thread::spawn(move || {
-        let mut stream_threads = Vec::new();
-
-        if let Some(stdout_pipe) = stdout {
-            let app_for_stdout = app_for_thread.clone();
-            let instance_for_stdout = instance_root_for_thread.clone();
-            stream_threads.push(thread::spawn(move || {
-                let reader = BufReader::new(stdout_pipe);
-                for line in reader.lines().map_while(Result::ok) {
-                    if line.trim().is_empty() {
-                        continue;
-                    }
-                    let _ = app_for_stdout.emit(
-                        "instance_runtime_output",
-                        RuntimeOutputEvent {
-                            instance_root: instance_for_stdout.clone(),
-                            stream: "stdout".to_string(),
-                            line,
-                        },
-                    );
-                }
-            }));
-        }
-
-        if let Some(stderr_pipe) = stderr {
-            let app_for_stderr = app_for_thread.clone();
-            let instance_for_stderr = instance_root_for_thread.clone();
-            stream_threads.push(thread::spawn(move || {
-                let reader = BufReader::new(stderr_pipe);
-                for line in reader.lines().map_while(Result::ok) {
-                    if line.trim().is_empty() {
-                        continue;
-                    }
-                    let _ = app_for_stderr.emit(
-                        "instance_runtime_output",
-                        RuntimeOutputEvent {
-                            instance_root: instance_for_stderr.clone(),
-                            stream: "stderr".to_string(),
-                            line,
-                        },
-                    );
-                }
-            }));
-        }
-
-        for handle in stream_threads {
-            let _ = handle.join();
-        }
-
-        let exit_code = child.wait().ok().and_then(|status| status.code());
-        let _ = app_for_thread.emit(
-            "redirect_launch_status",
-            json!({
-                "stage":"closed",
-                "message":"Instancia REDIRECT finalizada.",
-                "instance_uuid": instance_uuid.clone(),
-                "source_launcher": source_launcher.clone(),
-                "exit_code": exit_code,
-                "error": Value::Null,
-            }),
-        );
-        let _ = app_for_thread.emit(
-            "instance_runtime_exit",
-            serde_json::json!({
-                "instanceRoot": instance_root_for_thread,
-                "exitCode": exit_code,
-                "pid": pid,
-            }),
-        );
-        crate::app::instance_service::register_runtime_exit(
-            &registry_instance_root,
-            pid,
-            exit_code,
-        );
-        let _ = fs::remove_dir_all(&natives_dir);
-        touch_cache_entry_last_used(&app_for_thread, &instance_uuid);
-        let _ = cleanup_redirect_cache_after_launch(&app_for_thread);
-    });
+    let clear_gpu_on_exit = gpu_preference_cleanup(&app, gpu_preference.as_ref());
+    crate::app::instance_service::monitor_child(
+        app.clone(),
+        instance_root.clone(),
+        child,
+        SessionAccount {
+            profile_id: auth_session.profile_id.clone(),
+            profile_name: auth_session.profile_name.clone(),
+        },
+        ctx.game_dir.clone(),
+        move |exit_code| {
+            let _ = app_for_exit.emit(
+                "redirect_launch_status",
+                json!({
+                    "stage":"closed",
+                    "message":"Instancia REDIRECT finalizada.",
+                    "instance_uuid": instance_uuid.clone(),
+                    "source_launcher": source_launcher,
+                    "exit_code": exit_code,
+                    "error": Value::Null,
+                }),
+            );
+            let _ = fs::remove_dir_all(&natives_dir);
+            clear_gpu_on_exit(exit_code);
+            touch_cache_entry_last_used(&app_for_exit, &instance_uuid);
+            let _ = cleanup_redirect_cache_after_launch(&app_for_exit);
+        },
+    );
+    logs.extend(gpu_preference.map(|applied| applied.log_line));
 
     Ok(StartInstanceResult {
         pid,
@@ -5825,8 +6126,9 @@ pub async fn repair_instance(
     app: AppHandle,
     instance_root: String,
 ) -> Result<RepairInstanceResult, String> {
-    let instance_path = PathBuf::from(&instance_root);
-    let mut metadata = get_instance_metadata(instance_root.clone())?;
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    let instance_path = instance_root.path().to_path_buf();
+    let mut metadata = read_instance_metadata(instance_root.to_string())?;
     let mut changes_made = Vec::new();
     let mut errors = Vec::new();
 
@@ -5934,7 +6236,13 @@ pub async fn repair_instance(
                 "message": "Reinstalando runtime/loader de la instancia..."
             }),
         );
-        match ensure_embedded_java(launcher_root, required_java, &mut logs).and_then(|java_exec| {
+        match ensure_java_build(
+            launcher_root,
+            required_java,
+            metadata.java_build_pin.as_deref(),
+            &mut logs,
+        )
+        .and_then(|java_exec| {
             build_instance_structure(
                 &instance_path,
                 &minecraft_root,
@@ -5960,10 +6268,60 @@ pub async fn repair_instance(
             }
             Err(err) => errors.push(format!("No se pudo reconstruir runtime: {err}")),
         }
+
+        let _ = app.emit(
+            "repair_instance_progress",
+            json!({
+                "instanceRoot": instance_root,
+                "stage": "verify_jars",
+                "message": "Verificando SHA1 de client jar y librerías..."
+            }),
+        );
+        match crate::app::instance_service::verify_and_repair_instance_jars(
+            launcher_root,
+            &instance_path,
+            &minecraft_root,
+            &metadata,
+            app_clock(&app).clock.as_ref(),
+            &mut logs,
+        ) {
+            Ok(report) => {
+                if !report.quarantined.is_empty() {
+                    changes_made.push(format!(
+                        "{} jar(s) corruptos movidos a cuarentena: {}",
+                        report.quarantined.len(),
+                        report.quarantined.join(", ")
+                    ));
+                }
+                if report.redownloaded > 0 {
+                    changes_made.push(format!(
+                        "{} jar(s) descargados de nuevo",
+                        report.redownloaded
+                    ));
+                }
+                errors.extend(report.errors);
+            }
+            Err(err) => errors.push(format!("No se pudo verificar jars por SHA1: {err}")),
+        }
+
+        for damaged in crate::app::instance_dedup::verify_dedup_links(&instance_path) {
+            errors.push(format!(
+                "Archivo deduplicado dañado (objeto {}); también afecta a: {}",
+                damaged.sha1,
+                damaged.affected.join(", ")
+            ));
+        }
     }
 
     if errors.is_empty() || !changes_made.is_empty() {
-        write_instance_metadata(&instance_path, &metadata)?;
+        // La reparación espera descargas: solo se guardan los campos que repara, sobre la
+        // metadata actual, para no pisar lo que se haya guardado mientras tanto.
+        update_instance_metadata(instance_root.as_str(), |current| {
+            current.loader_version = metadata.loader_version.clone();
+            current.version_id = metadata.version_id.clone();
+            current.java_path = metadata.java_path.clone();
+            Ok::<(), String>(())
+        })?;
     }
 
     Ok(RepairInstanceResult {
@@ -5992,7 +6350,7 @@ pub async fn repair_all_instances(app: AppHandle) -> Result<Vec<RepairInstanceRe
             continue;
         }
         let instance_root = path.display().to_string();
-        let metadata = match get_instance_metadata(instance_root.clone()) {
+        let metadata = match read_instance_metadata(instance_root.to_string()) {
             Ok(metadata) => metadata,
             Err(_) => continue,
         };
@@ -6023,3 +6381,158 @@ pub async fn repair_all_instances(app: AppHandle) -> Result<Vec<RepairInstanceRe
 
     Ok(results)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::clock::mock::MockClock;
+
+    fn cache_entry(
+        instance_uuid: &str,
+        source_path: &Path,
+        last_used_at: &str,
+    ) -> RedirectCacheEntry {
+        RedirectCacheEntry {
+            instance_uuid: instance_uuid.to_string(),
+            version_id: "1.20.1".to_string(),
+            source_path: source_path.display().to_string(),
+            source_launcher: "prism".to_string(),
+            created_at: last_used_at.to_string(),
+            last_used_at: last_used_at.to_string(),
+            expires_after_days: DEFAULT_CACHE_EXPIRY_DAYS,
+            size_bytes: 1024,
+            complete: true,
+            version_json_cached: true,
+            jar_cached: true,
+            libraries_cached: true,
+            assets_cached: true,
+            pinned: false,
+            last_saves_sync_at: None,
+        }
+    }
+
+    #[test]
+    fn manifest_probes_read_bom_and_utf16_files_written_by_other_tools() {
+        let source =
+            std::env::temp_dir().join(format!("interface-redirect-probe-{}", std::process::id()));
+        fs::create_dir_all(&source).expect("source");
+        let profile = "{\r\n  \"loader\": \"Fabric\",\r\n  \"game_version\": \"1.20.1\"\r\n}";
+        let mut bom = vec![0xEF, 0xBB, 0xBF];
+        bom.extend(profile.as_bytes());
+        fs::write(source.join("profile.json"), bom).expect("profile");
+        assert_eq!(
+            detect_source_instance_hints(&source),
+            (Some("fabric".to_string()), Some("1.20.1".to_string()))
+        );
+
+        let version = source.join("1.20.1.json");
+        let clean = r#"{"id":"1.20.1","mainClass":"net.minecraft.client.main.Main"}"#;
+        fs::write(
+            &version,
+            clean
+                .encode_utf16()
+                .flat_map(u16::to_le_bytes)
+                .collect::<Vec<_>>(),
+        )
+        .expect("utf16 version");
+        assert_eq!(
+            read_and_validate_version_json(&version),
+            serde_json::from_str::<Value>(clean).ok()
+        );
+        assert_eq!(
+            read_json(&version),
+            serde_json::from_str::<Value>(clean).ok()
+        );
+
+        let _ = fs::remove_dir_all(source);
+    }
+
+    #[test]
+    fn entry_expires_after_7_days() {
+        let clock = MockClock::at("2024-05-01T10:00:00Z");
+        let entry = cache_entry("demo", Path::new("/"), &clock.now_rfc3339());
+
+        clock.advance(chrono::Duration::days(7));
+        assert!(!entry_expired(&entry, clock.now()));
+
+        clock.advance(chrono::Duration::days(1));
+        assert!(entry_expired(&entry, clock.now()));
+    }
+
+    #[test]
+    fn cleanup_plan_expires_pinned_entries_like_any_other() {
+        let cache_root =
+            std::env::temp_dir().join(format!("interface-redirect-cache-{}", std::process::id()));
+        for uuid in ["old", "pinned", "recent"] {
+            fs::create_dir_all(entry_cache_dir(&cache_root, uuid)).expect("cache dir");
+        }
+        let clock = MockClock::at("2024-05-20T10:00:00Z");
+        let mut pinned = cache_entry("pinned", &cache_root, "2024-05-01T10:00:00Z");
+        pinned.pinned = true;
+        let index = RedirectCacheIndex {
+            entries: vec![
+                cache_entry("old", &cache_root, "2024-05-01T10:00:00Z"),
+                pinned,
+                cache_entry("recent", &cache_root, "2024-05-18T10:00:00Z"),
+            ],
+            ..RedirectCacheIndex::default()
+        };
+
+        let removed = plan_redirect_cache_cleanup(&cache_root, &index, clock.now());
+
+        let removed = removed
+            .iter()
+            .map(|entry| (entry.instance_uuid.as_str(), entry.reason.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(removed, vec![("old", "expired"), ("pinned", "expired")]);
+        let _ = fs::remove_dir_all(cache_root);
+    }
+
+    #[test]
+    fn lru_trim_skips_pinned_entries_and_dry_run_keeps_everything() {
+        let cache_root = std::env::temp_dir().join(format!(
+            "interface-redirect-cache-lru-{}",
+            std::process::id()
+        ));
+        let clock = MockClock::at("2024-05-20T10:00:00Z");
+        let mut entries = Vec::new();
+        for day in 1..=MAX_CACHE_ENTRIES + 2 {
+            let uuid = format!("entry-{day:02}");
+            fs::create_dir_all(entry_cache_dir(&cache_root, &uuid)).expect("cache dir");
+            let mut entry = cache_entry(&uuid, &cache_root, &format!("2024-05-{day:02}T10:00:00Z"));
+            entry.expires_after_days = 30;
+            entry.pinned = day == 1;
+            entries.push(entry);
+        }
+        let mut index = RedirectCacheIndex {
+            entries,
+            ..RedirectCacheIndex::default()
+        };
+
+        let preview = run_redirect_cache_cleanup(&cache_root, &mut index, true, &clock);
+        let planned = preview
+            .removed
+            .iter()
+            .map(|entry| (entry.instance_uuid.as_str(), entry.reason.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            planned,
+            vec![
+                ("entry-02", "over_entry_limit"),
+                ("entry-03", "over_entry_limit")
+            ]
+        );
+        assert_eq!(index.entries.len(), MAX_CACHE_ENTRIES + 2);
+        assert!(entry_cache_dir(&cache_root, "entry-02").exists());
+
+        let result = run_redirect_cache_cleanup(&cache_root, &mut index, false, &clock);
+        assert_eq!(result.entries_removed, 2);
+        assert_eq!(index.entries.len(), MAX_CACHE_ENTRIES);
+        assert!(index
+            .entries
+            .iter()
+            .any(|entry| entry.instance_uuid == "entry-01"));
+        assert!(!entry_cache_dir(&cache_root, "entry-02").exists());
+        let _ = fs::remove_dir_all(cache_root);
+    }
+}
@@ -0,0 +1,270 @@
+// Métricas del proceso del juego.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    fs,
+    path::Path,
+    sync::{Arc, Mutex, OnceLock},
+    thread,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::{
+    app::trusted_root::resolve_trusted_instance_root,
+    infrastructure::filesystem::file_ops::write_file_replacing,
+    platform::processes::{list_java_processes, process_usage, ProcessUsage},
+};
+
+const METRICS_FILE: &str = ".runtime-metrics.json";
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(5);
+/// Una hora de muestras; en sesiones más largas se conservan las más recientes.
+const RING_CAPACITY: usize = 720;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MetricSample {
+    /// Milisegundos desde el inicio de la sesión.
+    pub at_ms: u64,
+    pub rss_bytes: u64,
+    /// Porcentaje de un núcleo (puede superar 100 en varios núcleos); `None` en la primera
+    /// lectura, que no tiene con qué compararse.
+    pub cpu_percent: Option<f64>,
+}
+
+/// Buffer circular de muestras de una sesión.
+#[derive(Debug, Clone)]
+pub(crate) struct MetricsRing {
+    samples: VecDeque<MetricSample>,
+    capacity: usize,
+    previous: Option<(u64, ProcessUsage)>,
+}
+
+impl MetricsRing {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            samples: VecDeque::with_capacity(capacity),
+            capacity,
+            previous: None,
+        }
+    }
+
+    /// Añade una lectura; el uso de CPU sale de la diferencia con la lectura anterior.
+    pub fn record(&mut self, at_ms: u64, usage: ProcessUsage) {
+        let cpu_percent = match self.previous {
+            Some((previous_ms, previous)) if at_ms > previous_ms => Some(
+                usage.cpu_time_ms.saturating_sub(previous.cpu_time_ms) as f64 * 100.0
+                    / (at_ms - previous_ms) as f64,
+            ),
+            _ => None,
+        };
+        self.previous = Some((at_ms, usage));
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(MetricSample {
+            at_ms,
+            rss_bytes: usage.rss_bytes,
+            cpu_percent,
+        });
+    }
+
+    pub fn samples(&self) -> Vec<MetricSample> {
+        self.samples.iter().copied().collect()
+    }
+}
+
+/// Resumen de la última sesión, guardado en `.runtime-metrics.json`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionMetrics {
+    pub recorded_at: String,
+    pub duration_secs: u64,
+    pub sample_count: usize,
+    pub avg_rss_bytes: u64,
+    pub peak_rss_bytes: u64,
+    pub avg_cpu_percent: f64,
+    pub peak_cpu_percent: f64,
+    /// Núcleos lógicos del equipo, para interpretar el porcentaje de CPU.
+    pub logical_cpus: usize,
+    /// Flags `-X`/`-XX` con los que arrancó el proceso.
+    #[serde(default)]
+    pub jvm_flags: Vec<String>,
+    /// Línea de log de la preferencia de GPU aplicada al lanzar, si había una.
+    #[serde(default)]
+    pub gpu_preference: Option<String>,
+    #[serde(default)]
+    pub samples: Vec<MetricSample>,
+}
+
+pub(crate) fn summarize_samples(samples: &[MetricSample]) -> SessionMetrics {
+    let cpu = samples
+        .iter()
+        .filter_map(|sample| sample.cpu_percent)
+        .collect::<Vec<_>>();
+    SessionMetrics {
+        sample_count: samples.len(),
+        avg_rss_bytes: if samples.is_empty() {
+            0
+        } else {
+            samples.iter().map(|sample| sample.rss_bytes).sum::<u64>() / samples.len() as u64
+        },
+        peak_rss_bytes: samples
+            .iter()
+            .map(|sample| sample.rss_bytes)
+            .max()
+            .unwrap_or(0),
+        avg_cpu_percent: if cpu.is_empty() {
+            0.0
+        } else {
+            cpu.iter().sum::<f64>() / cpu.len() as f64
+        },
+        peak_cpu_percent: cpu.iter().copied().fold(0.0, f64::max),
+        ..SessionMetrics::default()
+    }
+}
+
+/// Flags de memoria y GC de una línea de comandos de Java.
+pub(crate) fn jvm_flags_from_command_line(command_line: &str) -> Vec<String> {
+    command_line
+        .split_whitespace()
+        .filter(|arg| arg.starts_with("-X") || arg.starts_with("-verbose:gc"))
+        .map(str::to_string)
+        .collect()
+}
+
+static LIVE_METRICS: OnceLock<Mutex<HashMap<String, Arc<Mutex<MetricsRing>>>>> = OnceLock::new();
+
+fn live_metrics() -> std::sync::MutexGuard<'static, HashMap<String, Arc<Mutex<MetricsRing>>>> {
+    LIVE_METRICS
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Muestrea `pid` hasta que termine y guarda el resumen de la sesión en la instancia.
+pub(crate) fn start_runtime_metrics(instance_root: &str, pid: u32, gpu_preference: Option<String>) {
+    let ring = Arc::new(Mutex::new(MetricsRing::new(RING_CAPACITY)));
+    live_metrics().insert(instance_root.to_string(), Arc::clone(&ring));
+    let instance_root = instance_root.to_string();
+    thread::spawn(move || {
+        let started = Instant::now();
+        let jvm_flags = list_java_processes()
+            .into_iter()
+            .find(|process| process.pid == pid)
+            .map(|process| jvm_flags_from_command_line(&process.command_line))
+            .unwrap_or_default();
+        while let Some(usage) = process_usage(pid) {
+            if let Ok(mut ring) = ring.lock() {
+                ring.record(started.elapsed().as_millis() as u64, usage);
+            }
+            thread::sleep(SAMPLE_INTERVAL);
+        }
+        {
+            // Si ya hay otra sesión registrada (relanzamiento rápido), no se toca.
+            let mut live = live_metrics();
+            if live
+                .get(&instance_root)
+                .is_some_and(|current| Arc::ptr_eq(current, &ring))
+            {
+                live.remove(&instance_root);
+            }
+        }
+        let samples = ring.lock().map(|ring| ring.samples()).unwrap_or_default();
+        let metrics = SessionMetrics {
+            recorded_at: chrono::Utc::now().to_rfc3339(),
+            duration_secs: started.elapsed().as_secs(),
+            logical_cpus: thread::available_parallelism().map_or(1, |cpus| cpus.get()),
+            jvm_flags,
+            gpu_preference,
+            samples: samples.clone(),
+            ..summarize_samples(&samples)
+        };
+        if let Err(err) = write_session_metrics(Path::new(&instance_root), &metrics) {
+            log::warn!("⚠ No se pudieron guardar las métricas de {instance_root}: {err}");
+        }
+    });
+}
+
+fn write_session_metrics(instance_root: &Path, metrics: &SessionMetrics) -> Result<(), String> {
+    let raw = serde_json::to_string_pretty(metrics)
+        .map_err(|err| format!("No se pudieron serializar las métricas: {err}"))?;
+    write_file_replacing(&instance_root.join(METRICS_FILE), raw.as_bytes(), true)
+}
+
+/// Métricas de la última sesión terminada; `None` si la instancia no se ha jugado aún.
+pub(crate) fn read_session_metrics(instance_root: &Path) -> Result<Option<SessionMetrics>, String> {
+    let path = instance_root.join(METRICS_FILE);
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let raw = fs::read_to_string(&path)
+        .map_err(|err| format!("No se pudo leer {}: {err}", path.display()))?;
+    serde_json::from_str(&raw)
+        .map(Some)
+        .map_err(|err| format!("Métricas inválidas en {}: {err}", path.display()))
+}
+
+/// Muestras de la sesión en curso o, si la instancia no está en ejecución, de la última.
+#[tauri::command]
+pub fn get_runtime_metrics(
+    app: AppHandle,
+    instance_root: String,
+) -> Result<Vec<MetricSample>, String> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    let live = live_metrics().get(instance_root.as_str()).cloned();
+    if let Some(ring) = live {
+        return Ok(ring.lock().map(|ring| ring.samples()).unwrap_or_default());
+    }
+    Ok(read_session_metrics(instance_root.path())?
+        .map(|metrics| metrics.samples)
+        .unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usage(rss_mb: u64, cpu_time_ms: u64) -> ProcessUsage {
+        ProcessUsage {
+            rss_bytes: rss_mb * 1024 * 1024,
+            cpu_time_ms,
+        }
+    }
+
+    #[test]
+    fn ring_keeps_the_latest_samples_and_derives_cpu_from_deltas() {
+        let mut ring = MetricsRing::new(3);
+        ring.record(0, usage(1000, 0));
+        ring.record(5_000, usage(1500, 5_000));
+        ring.record(10_000, usage(2000, 15_000));
+        ring.record(15_000, usage(1800, 17_500));
+
+        let samples = ring.samples();
+        assert_eq!(samples.len(), 3);
+        assert_eq!(samples[0].at_ms, 5_000);
+        assert_eq!(samples[0].cpu_percent, Some(100.0));
+        assert_eq!(samples[1].cpu_percent, Some(200.0));
+        assert_eq!(samples[2].cpu_percent, Some(50.0));
+
+        let summary = summarize_samples(&samples);
+        assert_eq!(summary.peak_rss_bytes, 2000 * 1024 * 1024);
+        assert_eq!(summary.peak_cpu_percent, 200.0);
+        assert!((summary.avg_cpu_percent - 350.0 / 3.0).abs() < 1e-9);
+
+        let mut first = MetricsRing::new(3);
+        first.record(0, usage(1000, 0));
+        assert_eq!(first.samples()[0].cpu_percent, None);
+        assert_eq!(summarize_samples(&first.samples()).avg_cpu_percent, 0.0);
+    }
+
+    #[test]
+    fn extracts_memory_and_gc_flags_from_the_command_line() {
+        let flags = jvm_flags_from_command_line(
+            "/java/bin/java -Xms2G -Xmx4G -XX:+UseG1GC -Djava.library.path=/n -cp a.jar net.minecraft.client.main.Main",
+        );
+        assert_eq!(flags, vec!["-Xms2G", "-Xmx4G", "-XX:+UseG1GC"]);
+    }
+}
@@ -0,0 +1,198 @@
+use std::{
+    fs::{self, File},
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// Intervalo máximo entre lotes de `instance_runtime_output_batch`.
+pub const OUTPUT_FLUSH_INTERVAL: Duration = Duration::from_millis(100);
+/// Líneas por lote; al llegar se vacía sin esperar al intervalo.
+pub const OUTPUT_BATCH_LINES: usize = 50;
+/// Por encima de este ritmo las líneas no se emiten a la interfaz, solo se cuentan.
+pub const OUTPUT_MAX_LINES_PER_SEC: usize = 1000;
+
+const SESSION_LOG_FILE: &str = "logs/runtime-session.log";
+
+/// Contenido a emitir: las líneas pendientes y cuántas se suprimieron desde el último lote.
+#[derive(Debug, PartialEq, Eq)]
+pub struct OutputFlush<T> {
+    pub lines: Vec<T>,
+    pub suppressed: usize,
+}
+
+/// Agrupa las líneas del juego en lotes y limita el ritmo de emisión para que un pack que
+/// escribe miles de líneas por segundo no congele el webview.
+#[derive(Debug)]
+pub struct OutputThrottle<T> {
+    pending: Vec<T>,
+    suppressed: usize,
+    window_started: Instant,
+    window_lines: usize,
+    last_flush: Instant,
+    max_lines_per_sec: usize,
+}
+
+impl<T> OutputThrottle<T> {
+    pub fn new(now: Instant, max_lines_per_sec: usize) -> Self {
+        Self {
+            pending: Vec::new(),
+            suppressed: 0,
+            window_started: now,
+            window_lines: 0,
+            last_flush: now,
+            max_lines_per_sec,
+        }
+    }
+
+    /// Añade una línea; devuelve un lote cuando se alcanza el tamaño o el intervalo.
+    pub fn push(&mut self, line: T, now: Instant) -> Option<OutputFlush<T>> {
+        if now.duration_since(self.window_started) >= Duration::from_secs(1) {
+            self.window_started = now;
+            self.window_lines = 0;
+        }
+        self.window_lines += 1;
+        if self.window_lines > self.max_lines_per_sec {
+            self.suppressed += 1;
+        } else {
+            self.pending.push(line);
+        }
+        if self.pending.len() >= OUTPUT_BATCH_LINES {
+            return Some(self.take(now));
+        }
+        self.tick(now)
+    }
+
+    /// Vacía lo pendiente si pasó el intervalo desde el último lote.
+    pub fn tick(&mut self, now: Instant) -> Option<OutputFlush<T>> {
+        if now.duration_since(self.last_flush) >= OUTPUT_FLUSH_INTERVAL {
+            self.drain(now)
+        } else {
+            None
+        }
+    }
+
+    /// Vacía todo lo pendiente (al cerrar el proceso).
+    pub fn drain(&mut self, now: Instant) -> Option<OutputFlush<T>> {
+        (!self.pending.is_empty() || self.suppressed > 0).then(|| self.take(now))
+    }
+
+    fn take(&mut self, now: Instant) -> OutputFlush<T> {
+        self.last_flush = now;
+        OutputFlush {
+            lines: std::mem::take(&mut self.pending),
+            suppressed: std::mem::take(&mut self.suppressed),
+        }
+    }
+}
+
+/// Ruta del log de la última sesión de la instancia.
+pub fn session_log_path(instance_root: &Path) -> PathBuf {
+    instance_root.join(SESSION_LOG_FILE)
+}
+
+/// Log de la sesión en `<instancia>/logs/runtime-session.log` con toda la salida del
+/// proceso, incluidas las líneas que no se emitieron por exceso de ritmo.
+pub struct SessionLog {
+    path: PathBuf,
+    writer: Mutex<Option<BufWriter<File>>>,
+}
+
+impl SessionLog {
+    pub fn create(instance_root: &Path) -> Self {
+        let path = session_log_path(instance_root);
+        let writer = path
+            .parent()
+            .map(fs::create_dir_all)
+            .and_then(Result::ok)
+            .and_then(|_| File::create(&path).ok())
+            .map(BufWriter::new);
+        if writer.is_none() {
+            log::warn!(
+                "⚠ No se pudo crear el log de sesión {}; la salida del juego no se guardará",
+                path.display()
+            );
+        }
+        Self {
+            path,
+            writer: Mutex::new(writer),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn write_line(&self, stream: &str, line: &str) {
+        if let Ok(mut writer) = self.writer.lock() {
+            if let Some(writer) = writer.as_mut() {
+                let _ = writeln!(writer, "[{stream}] {line}");
+            }
+        }
+    }
+
+    pub fn close(&self) {
+        if let Ok(mut writer) = self.writer.lock() {
+            if let Some(mut writer) = writer.take() {
+                let _ = writer.flush();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn batches_by_size_and_interval() {
+        let start = Instant::now();
+        let mut throttle = OutputThrottle::new(start, OUTPUT_MAX_LINES_PER_SEC);
+
+        for index in 0..OUTPUT_BATCH_LINES - 1 {
+            assert!(throttle.push(index, start).is_none());
+        }
+        let batch = throttle.push(99, start).expect("lote por tamaño");
+        assert_eq!(batch.lines.len(), OUTPUT_BATCH_LINES);
+
+        assert!(throttle
+            .push(1, start + Duration::from_millis(10))
+            .is_none());
+        let batch = throttle
+            .tick(start + OUTPUT_FLUSH_INTERVAL)
+            .expect("lote por intervalo");
+        assert_eq!(
+            batch,
+            OutputFlush {
+                lines: vec![1],
+                suppressed: 0
+            }
+        );
+        assert!(throttle.tick(start + Duration::from_secs(5)).is_none());
+    }
+
+    #[test]
+    fn suppresses_lines_above_rate_and_reports_count() {
+        let start = Instant::now();
+        let mut throttle = OutputThrottle::new(start, 10);
+        let mut emitted = 0;
+        let mut suppressed = 0;
+        for index in 0..25 {
+            if let Some(batch) = throttle.push(index, start) {
+                emitted += batch.lines.len();
+                suppressed += batch.suppressed;
+            }
+        }
+        let rest = throttle.drain(start).expect("pendiente");
+        emitted += rest.lines.len();
+        suppressed += rest.suppressed;
+        assert_eq!((emitted, suppressed), (10, 15));
+
+        // En la siguiente ventana de un segundo vuelve a emitir.
+        let batch = throttle
+            .push(100, start + Duration::from_secs(1))
+            .expect("lote por intervalo");
+        assert_eq!(batch.lines, vec![100]);
+    }
+}
@@ -0,0 +1,355 @@
+// Copia de mundos de la caché de un atajo al launcher de origen.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::{
+    app::{
+        game_dir_guard::held_session_locks,
+        instance_service::{read_instance_metadata, shortcut_runtime_cache_dir},
+        redirect_launch::{record_saves_sync, redirect_game_dir, redirect_source},
+        trusted_root::resolve_trusted_instance_root,
+    },
+    shared::{clock::app_clock, tasks::TaskHandle},
+};
+
+/// Mundos a partir de este tamaño avisan con `saves_sync_large_world` antes de copiarse.
+const LARGE_WORLD_BYTES: u64 = 256 * 1024 * 1024;
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct WorldSyncItem {
+    pub world: String,
+    /// `only_in_cache` o `newer_in_cache`.
+    pub reason: &'static str,
+    /// Fecha de modificación del `level.dat` en la caché (RFC 3339).
+    pub cache_modified: Option<String>,
+    /// Fecha del `level.dat` del origen; `None` si el mundo no existe allí.
+    pub source_modified: Option<String>,
+    pub size_bytes: u64,
+    pub copied: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SavesSyncReport {
+    pub dry_run: bool,
+    pub cache_saves_dir: String,
+    pub source_saves_dir: String,
+    /// Mundos que se copian (o se copiarían) al origen; cada uno se confirma por separado.
+    pub worlds: Vec<WorldSyncItem>,
+    /// Mundos presentes en ambos lados sin cambios que copiar.
+    pub up_to_date: Vec<String>,
+    /// Mundos abiertos (`session.lock` retenido) en cualquiera de los dos lados.
+    pub in_use: Vec<String>,
+    pub synced_at: Option<String>,
+}
+
+fn rfc3339(time: SystemTime) -> Option<String> {
+    let secs = time.duration_since(SystemTime::UNIX_EPOCH).ok()?.as_secs();
+    chrono::DateTime::from_timestamp(secs as i64, 0).map(|date| date.to_rfc3339())
+}
+
+fn level_dat_modified(world: &Path) -> Option<SystemTime> {
+    fs::metadata(world.join("level.dat"))
+        .and_then(|meta| meta.modified())
+        .ok()
+}
+
+/// Carpetas de `saves/` que contienen un `level.dat`, ordenadas por nombre.
+fn world_dirs(saves: &Path) -> Vec<(String, PathBuf)> {
+    let Ok(entries) = fs::read_dir(saves) else {
+        return Vec::new();
+    };
+    let mut worlds = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.join("level.dat").is_file())
+        .filter_map(|path| {
+            let name = path.file_name()?.to_string_lossy().to_string();
+            Some((name, path))
+        })
+        .collect::<Vec<_>>();
+    worlds.sort();
+    worlds
+}
+
+fn world_size(path: &Path) -> u64 {
+    let Ok(meta) = fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if !meta.is_dir() {
+        return meta.len();
+    }
+    fs::read_dir(path)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| world_size(&entry.path()))
+                .sum()
+        })
+        .unwrap_or(0)
+}
+
+/// Compara los mundos de ambos lados; un `level.dat` idéntico cuenta como sin cambios.
+pub(crate) fn plan_saves_sync(
+    cache_saves: &Path,
+    source_saves: &Path,
+) -> (Vec<WorldSyncItem>, Vec<String>) {
+    let mut pending = Vec::new();
+    let mut up_to_date = Vec::new();
+    for (world, cache_world) in world_dirs(cache_saves) {
+        let source_world = source_saves.join(&world);
+        let cache_modified = level_dat_modified(&cache_world);
+        let source_modified = level_dat_modified(&source_world);
+        let reason = match (cache_modified, source_modified) {
+            (_, None) => "only_in_cache",
+            (Some(cache), Some(source)) if cache > source => {
+                let unchanged = fs::read(cache_world.join("level.dat")).ok()
+                    == fs::read(source_world.join("level.dat")).ok();
+                if unchanged {
+                    up_to_date.push(world);
+                    continue;
+                }
+                "newer_in_cache"
+            }
+            _ => {
+                up_to_date.push(world);
+                continue;
+            }
+        };
+        pending.push(WorldSyncItem {
+            size_bytes: world_size(&cache_world),
+            cache_modified: cache_modified.and_then(rfc3339),
+            source_modified: source_modified.and_then(rfc3339),
+            world,
+            reason,
+            copied: false,
+        });
+    }
+    (pending, up_to_date)
+}
+
+/// Copia `source` sobre `target` sin borrar nada en el destino y omitiendo `session.lock`.
+pub(crate) fn copy_world(
+    source: &Path,
+    target: &Path,
+    on_file: &mut dyn FnMut(u64),
+) -> Result<u64, String> {
+    fs::create_dir_all(target)
+        .map_err(|err| format!("No se pudo crear {}: {err}", target.display()))?;
+    let entries = fs::read_dir(source)
+        .map_err(|err| format!("No se pudo leer {}: {err}", source.display()))?;
+    let mut copied = 0_u64;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = entry.file_name();
+        let Ok(meta) = fs::symlink_metadata(&path) else {
+            continue;
+        };
+        let destination = target.join(&name);
+        if meta.is_dir() {
+            copied += copy_world(&path, &destination, on_file)?;
+        } else if meta.is_file() && name != "session.lock" {
+            let temp = target.join(format!(".{}.sync-tmp", name.to_string_lossy()));
+            fs::copy(&path, &temp).map_err(|err| {
+                format!(
+                    "No se pudo copiar {} -> {}: {err}",
+                    path.display(),
+                    temp.display()
+                )
+            })?;
+            fs::rename(&temp, &destination).map_err(|err| {
+                let _ = fs::remove_file(&temp);
+                format!("No se pudo reemplazar {}: {err}", destination.display())
+            })?;
+            copied += meta.len();
+            on_file(meta.len());
+        }
+    }
+    Ok(copied)
+}
+
+fn sync_saves_blocking(
+    app: &AppHandle,
+    instance_root: &Path,
+    dry_run: bool,
+) -> Result<SavesSyncReport, String> {
+    let metadata = read_instance_metadata(instance_root.display().to_string())?;
+    if !metadata.state.eq_ignore_ascii_case("redirect") {
+        return Err(format!(
+            "'{}' no es un atajo: solo las instancias REDIRECT sincronizan mundos con el origen.",
+            metadata.name
+        ));
+    }
+    let (source_path, _) = redirect_source(instance_root)?;
+    let source_game_dir = redirect_game_dir(instance_root).ok_or_else(|| {
+        format!(
+            "No se encontró la carpeta de juego del origen en {}.",
+            source_path.display()
+        )
+    })?;
+    let cache_game_dir =
+        shortcut_runtime_cache_dir(app, &source_path.display().to_string())?.join("minecraft");
+    let cache_saves = cache_game_dir.join("saves");
+    let source_saves = source_game_dir.join("saves");
+
+    let mut in_use = held_session_locks(&cache_game_dir);
+    in_use.extend(held_session_locks(&source_game_dir));
+    in_use.sort();
+    in_use.dedup();
+    let (mut worlds, up_to_date) = plan_saves_sync(&cache_saves, &source_saves);
+    let mut report = SavesSyncReport {
+        dry_run,
+        cache_saves_dir: cache_saves.display().to_string(),
+        source_saves_dir: source_saves.display().to_string(),
+        worlds: Vec::new(),
+        up_to_date,
+        in_use,
+        synced_at: None,
+    };
+    if dry_run || worlds.is_empty() {
+        report.worlds = worlds;
+        return Ok(report);
+    }
+    if !report.in_use.is_empty() {
+        return Err(format!(
+            "Hay mundos abiertos ({}). Cierra el juego y el launcher de origen antes de sincronizar.",
+            report.in_use.join(", ")
+        ));
+    }
+
+    let task = TaskHandle::begin(
+        app,
+        "saves_sync",
+        format!("Sincronizando mundos de {}", metadata.name),
+    );
+    let total = worlds.iter().map(|world| world.size_bytes).sum::<u64>();
+    let mut done = 0_u64;
+    let result = (|| -> Result<(), String> {
+        for world in worlds.iter_mut() {
+            task.check_cancelled()?;
+            if world.size_bytes >= LARGE_WORLD_BYTES {
+                task.emit_event("saves_sync_large_world", &*world);
+            }
+            let name = world.world.clone();
+            copy_world(
+                &cache_saves.join(&world.world),
+                &source_saves.join(&world.world),
+                &mut |bytes| {
+                    done += bytes;
+                    task.progress_with_message(done, Some(total), "bytes", Some(name.clone()));
+                },
+            )?;
+            world.copied = true;
+            log::info!(
+                "✔ Mundo '{}' copiado al origen ({})",
+                world.world,
+                world.reason
+            );
+        }
+        Ok(())
+    })();
+    task.finish(&result);
+    report.worlds = worlds;
+    result?;
+
+    let synced_at = app_clock(app).clock.now_rfc3339();
+    if !record_saves_sync(app, &metadata.internal_uuid, &synced_at)? {
+        log::warn!(
+            "⚠ {} no tiene entrada en redirect-cache; no se guardó la fecha de sincronización",
+            metadata.internal_uuid
+        );
+    }
+    report.synced_at = Some(synced_at);
+    Ok(report)
+}
+
+/// Copia al launcher de origen los mundos creados o jugados desde la caché de un atajo.
+#[tauri::command]
+pub async fn sync_saves_to_source(
+    app: AppHandle,
+    instance_root: String,
+    dry_run: bool,
+) -> Result<SavesSyncReport, String> {
+    let root = resolve_trusted_instance_root(&app, &instance_root)?;
+    tauri::async_runtime::spawn_blocking(move || sync_saves_blocking(&app, root.path(), dry_run))
+        .await
+        .map_err(|err| format!("Falló la tarea de sincronización de mundos: {err}"))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn world(saves: &Path, name: &str, level: &[u8], modified: SystemTime) -> PathBuf {
+        let dir = saves.join(name);
+        fs::create_dir_all(dir.join("region")).expect("world");
+        fs::write(dir.join("level.dat"), level).expect("level.dat");
+        fs::write(dir.join("region/r.0.0.mca"), level).expect("region");
+        fs::File::options()
+            .write(true)
+            .open(dir.join("level.dat"))
+            .and_then(|file| file.set_modified(modified))
+            .expect("mtime");
+        dir
+    }
+
+    #[test]
+    fn plans_only_new_or_changed_worlds() {
+        let root = std::env::temp_dir().join(format!("saves-sync-{}", uuid::Uuid::new_v4()));
+        let (cache, source) = (root.join("cache/saves"), root.join("source/saves"));
+        let old = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let new = old + Duration::from_secs(3_600);
+
+        world(&cache, "Nuevo", b"a", new);
+        world(&cache, "Jugado", b"cambiado", new);
+        world(&source, "Jugado", b"original", old);
+        world(&cache, "Copiado", b"igual", new);
+        world(&source, "Copiado", b"igual", old);
+        world(&cache, "Antiguo", b"viejo", old);
+        world(&source, "Antiguo", b"reciente", new);
+        fs::create_dir_all(cache.join("sin-level-dat")).expect("dir");
+
+        let (pending, up_to_date) = plan_saves_sync(&cache, &source);
+        let planned: Vec<(&str, &str)> = pending
+            .iter()
+            .map(|item| (item.world.as_str(), item.reason))
+            .collect();
+        assert_eq!(
+            planned,
+            vec![("Jugado", "newer_in_cache"), ("Nuevo", "only_in_cache")]
+        );
+        assert_eq!(pending[1].size_bytes, 2);
+        assert!(pending[1].source_modified.is_none());
+        assert_eq!(up_to_date, vec!["Antiguo", "Copiado"]);
+
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn copy_never_deletes_source_files_and_skips_session_lock() {
+        let root = std::env::temp_dir().join(format!("saves-copy-{}", uuid::Uuid::new_v4()));
+        let now = SystemTime::now();
+        let cache_world = world(&root.join("cache"), "Mundo", b"nuevo", now);
+        fs::write(cache_world.join("session.lock"), b"lock").expect("lock");
+        let source_world = world(&root.join("source"), "Mundo", b"viejo", now);
+        fs::write(source_world.join("region/r.1.1.mca"), b"solo-origen").expect("extra");
+
+        let mut files = 0;
+        let copied = copy_world(&cache_world, &source_world, &mut |_| files += 1).expect("copy");
+        assert_eq!((copied, files), (10, 2));
+        assert_eq!(fs::read(source_world.join("level.dat")).unwrap(), b"nuevo");
+        assert!(source_world.join("region/r.1.1.mca").is_file());
+        assert!(!source_world.join("session.lock").exists());
+
+        let _ = fs::remove_dir_all(root);
+    }
+}
@@ -0,0 +1,480 @@
+// Capturas de pantalla de una instancia.
+
+use std::{
+    fs,
+    io::{BufReader, Cursor},
+    path::{Path, PathBuf},
+    time::UNIX_EPOCH,
+};
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use chrono::NaiveDateTime;
+use image::{ImageFormat, RgbaImage};
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::app::{
+    instance_service::{open_folder_in_explorer, read_instance_metadata},
+    redirect_launch::redirect_game_dir,
+    trusted_root::resolve_trusted_instance_root,
+};
+
+const THUMBNAIL_CACHE_DIR: &str = ".cache/screenshots";
+const DEFAULT_PAGE_SIZE: u32 = 24;
+const MAX_PAGE_SIZE: u32 = 200;
+const MIN_THUMBNAIL_DIMENSION: u32 = 16;
+const MAX_THUMBNAIL_DIMENSION: u32 = 1024;
+/// Tope de memoria para el decodificador de respaldo (PNG entrelazados).
+const FALLBACK_DECODE_LIMIT_BYTES: u64 = 256 * 1024 * 1024;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreenshotEntry {
+    pub file_name: String,
+    /// Fecha del nombre vanilla (`2024-01-31_12.34.56.png`, hora local) o, si no sigue ese
+    /// formato, la fecha de modificación.
+    pub taken_at: String,
+    pub size_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreenshotPage {
+    pub entries: Vec<ScreenshotEntry>,
+    pub page: u32,
+    pub page_size: u32,
+    pub total: usize,
+    pub has_more: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreenshotThumbnail {
+    pub file_name: String,
+    pub data_url: String,
+    pub width: u32,
+    pub height: u32,
+    pub from_cache: bool,
+}
+
+/// Carpeta de capturas del game dir efectivo: la del origen en las REDIRECT.
+fn screenshots_dir(instance_root: &Path) -> Result<PathBuf, String> {
+    let metadata = read_instance_metadata(instance_root.display().to_string())?;
+    let game_dir = if metadata.state.eq_ignore_ascii_case("redirect") {
+        redirect_game_dir(instance_root).ok_or_else(|| {
+            "No se encontró la carpeta de juego de la instancia redirigida.".to_string()
+        })?
+    } else {
+        instance_root.join("minecraft")
+    };
+    Ok(game_dir.join("screenshots"))
+}
+
+fn is_screenshot_name(name: &str) -> bool {
+    name.to_ascii_lowercase().ends_with(".png")
+}
+
+/// Resuelve `file_name` dentro de `dir` rechazando separadores, `..` y enlaces que salgan.
+fn resolve_screenshot(dir: &Path, file_name: &str) -> Result<PathBuf, String> {
+    if file_name.is_empty()
+        || file_name.contains(['/', '\\', '\0'])
+        || file_name.starts_with('.')
+        || !is_screenshot_name(file_name)
+    {
+        return Err(format!("Nombre de captura no válido: {file_name}"));
+    }
+    let target = dir.join(file_name);
+    let canonical_dir = fs::canonicalize(dir)
+        .map_err(|err| format!("No se pudo abrir {}: {err}", dir.display()))?;
+    let canonical = fs::canonicalize(&target)
+        .map_err(|err| format!("No existe la captura {}: {err}", target.display()))?;
+    if canonical.parent() != Some(canonical_dir.as_path()) || !canonical.is_file() {
+        return Err(format!(
+            "La captura {file_name} no está dentro de la carpeta de capturas."
+        ));
+    }
+    Ok(target)
+}
+
+/// `2024-01-31_12.34.56.png` o `2024-01-31_12.34.56_2.png` (varias en el mismo segundo).
+fn parse_vanilla_timestamp(file_name: &str) -> Option<NaiveDateTime> {
+    let stem = file_name.get(..19)?;
+    NaiveDateTime::parse_from_str(stem, "%Y-%m-%d_%H.%M.%S").ok()
+}
+
+fn modified_secs(metadata: &fs::Metadata) -> u64 {
+    metadata
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
+
+fn collect_screenshots(dir: &Path) -> Vec<ScreenshotEntry> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut found: Vec<(NaiveDateTime, ScreenshotEntry)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            let metadata = entry.metadata().ok()?;
+            if !metadata.is_file() || !is_screenshot_name(&file_name) {
+                return None;
+            }
+            let taken_at = parse_vanilla_timestamp(&file_name).or_else(|| {
+                chrono::DateTime::from_timestamp(modified_secs(&metadata) as i64, 0)
+                    .map(|utc| utc.with_timezone(&chrono::Local).naive_local())
+            })?;
+            Some((
+                taken_at,
+                ScreenshotEntry {
+                    taken_at: taken_at.format("%Y-%m-%dT%H:%M:%S").to_string(),
+                    file_name,
+                    size_bytes: metadata.len(),
+                },
+            ))
+        })
+        .collect();
+    found.sort_by(|a, b| {
+        b.0.cmp(&a.0)
+            .then_with(|| b.1.file_name.cmp(&a.1.file_name))
+    });
+    found.into_iter().map(|(_, entry)| entry).collect()
+}
+
+fn thumbnail_size(width: u32, height: u32, max_dimension: u32) -> (u32, u32) {
+    let longest = width.max(height).max(1);
+    if longest <= max_dimension {
+        return (width.max(1), height.max(1));
+    }
+    let scale = |side: u32| ((side as u64 * max_dimension as u64) / longest as u64).max(1) as u32;
+    (scale(width), scale(height))
+}
+
+/// Reduce un PNG promediando bloques fila a fila; solo se guarda una fila decodificada y las
+/// sumas de la fila de destino en curso.
+fn downscale_png_streaming(path: &Path, max_dimension: u32) -> Result<RgbaImage, String> {
+    let file = fs::File::open(path)
+        .map_err(|err| format!("No se pudo abrir {}: {err}", path.display()))?;
+    let mut decoder = png::Decoder::new(BufReader::new(file));
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder
+        .read_info()
+        .map_err(|err| format!("PNG no válido {}: {err}", path.display()))?;
+    let info = reader.info();
+    let (width, height) = (info.width, info.height);
+    if info.interlaced {
+        return downscale_with_image_crate(path, max_dimension);
+    }
+    let channels = match reader.output_color_type().0 {
+        png::ColorType::Grayscale => 1,
+        png::ColorType::GrayscaleAlpha => 2,
+        png::ColorType::Rgb => 3,
+        png::ColorType::Rgba => 4,
+        png::ColorType::Indexed => {
+            return Err(format!("PNG indexado sin expandir: {}", path.display()))
+        }
+    };
+
+    let (out_width, out_height) = thumbnail_size(width, height, max_dimension);
+    let mut output = RgbaImage::new(out_width, out_height);
+    let mut sums = vec![0u64; out_width as usize * 4];
+    let mut counts = vec![0u64; out_width as usize];
+    let mut current_row = 0u32;
+    let mut source_y = 0u32;
+
+    let flush = |output: &mut RgbaImage, row: u32, sums: &mut [u64], counts: &mut [u64]| {
+        for x in 0..out_width as usize {
+            let count = counts[x].max(1);
+            let pixel = [0, 1, 2, 3].map(|c| (sums[x * 4 + c] / count) as u8);
+            output.put_pixel(x as u32, row, image::Rgba(pixel));
+        }
+        sums.fill(0);
+        counts.fill(0);
+    };
+
+    while let Some(row) = reader
+        .next_row()
+        .map_err(|err| format!("No se pudo decodificar {}: {err}", path.display()))?
+    {
+        let target_row = ((source_y as u64 * out_height as u64) / height.max(1) as u64) as u32;
+        if target_row != current_row {
+            flush(&mut output, current_row, &mut sums, &mut counts);
+            current_row = target_row.min(out_height - 1);
+        }
+        for (x, pixel) in row.data().chunks_exact(channels).enumerate() {
+            let target_x = (x as u64 * out_width as u64 / width.max(1) as u64) as usize;
+            let rgba = match channels {
+                1 => [pixel[0], pixel[0], pixel[0], 255],
+                2 => [pixel[0], pixel[0], pixel[0], pixel[1]],
+                3 => [pixel[0], pixel[1], pixel[2], 255],
+                _ => [pixel[0], pixel[1], pixel[2], pixel[3]],
+            };
+            for (c, value) in rgba.iter().enumerate() {
+                sums[target_x * 4 + c] += *value as u64;
+            }
+            counts[target_x] += 1;
+        }
+        source_y += 1;
+    }
+    flush(&mut output, current_row, &mut sums, &mut counts);
+    Ok(output)
+}
+
+/// Los PNG entrelazados no se pueden leer fila a fila; se decodifican con un tope de memoria.
+fn downscale_with_image_crate(path: &Path, max_dimension: u32) -> Result<RgbaImage, String> {
+    let mut reader = image::ImageReader::open(path)
+        .map_err(|err| format!("No se pudo abrir {}: {err}", path.display()))?;
+    let mut limits = image::Limits::default();
+    limits.max_alloc = Some(FALLBACK_DECODE_LIMIT_BYTES);
+    reader.limits(limits);
+    let decoded = reader
+        .decode()
+        .map_err(|err| format!("No se pudo decodificar {}: {err}", path.display()))?;
+    Ok(decoded.thumbnail(max_dimension, max_dimension).to_rgba8())
+}
+
+fn thumbnail_cache_name(file_name: &str, max_dimension: u32, metadata: &fs::Metadata) -> String {
+    let stem = file_name
+        .rsplit_once('.')
+        .map_or(file_name, |(stem, _)| stem);
+    format!(
+        "{stem}@{max_dimension}-{}-{}.png",
+        modified_secs(metadata),
+        metadata.len()
+    )
+}
+
+fn thumbnail_data(file_name: &str, bytes: &[u8], from_cache: bool) -> ScreenshotThumbnail {
+    let (width, height) = crate::app::image_cache::image_dimensions(bytes).unwrap_or((0, 0));
+    ScreenshotThumbnail {
+        file_name: file_name.to_string(),
+        data_url: format!("data:image/png;base64,{}", STANDARD.encode(bytes)),
+        width,
+        height,
+        from_cache,
+    }
+}
+
+/// Miniatura cacheada en `cache_dir`; si no existe se genera y se guarda.
+fn load_or_create_thumbnail(
+    source: &Path,
+    file_name: &str,
+    cache_dir: &Path,
+    max_dimension: u32,
+) -> Result<ScreenshotThumbnail, String> {
+    let metadata = fs::metadata(source)
+        .map_err(|err| format!("No se pudo leer {}: {err}", source.display()))?;
+    let cached_path = cache_dir.join(thumbnail_cache_name(file_name, max_dimension, &metadata));
+    if let Ok(bytes) = fs::read(&cached_path) {
+        return Ok(thumbnail_data(file_name, &bytes, true));
+    }
+
+    let thumbnail = downscale_png_streaming(source, max_dimension)?;
+    let mut bytes = Vec::new();
+    thumbnail
+        .write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png)
+        .map_err(|err| format!("No se pudo codificar la miniatura: {err}"))?;
+
+    // Las miniaturas de versiones anteriores de la captura ya no sirven.
+    remove_cached_thumbnails(cache_dir, file_name);
+    fs::create_dir_all(cache_dir)
+        .map_err(|err| format!("No se pudo crear {}: {err}", cache_dir.display()))?;
+    if let Err(err) = fs::write(&cached_path, &bytes) {
+        log::warn!(
+            "⚠ No se pudo guardar la miniatura {}: {err}",
+            cached_path.display()
+        );
+    }
+    Ok(thumbnail_data(file_name, &bytes, false))
+}
+
+fn remove_cached_thumbnails(cache_dir: &Path, file_name: &str) {
+    let stem = file_name
+        .rsplit_once('.')
+        .map_or(file_name, |(stem, _)| stem);
+    let prefix = format!("{stem}@");
+    let Ok(entries) = fs::read_dir(cache_dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        if entry.file_name().to_string_lossy().starts_with(&prefix) {
+            let _ = fs::remove_file(entry.path());
+        }
+    }
+}
+
+/// Capturas de la instancia, de la más reciente a la más antigua. `page` empieza en 1.
+#[tauri::command]
+pub fn list_instance_screenshots(
+    app: AppHandle,
+    instance_root: String,
+    page: Option<u32>,
+    page_size: Option<u32>,
+) -> Result<ScreenshotPage, String> {
+    let root = resolve_trusted_instance_root(&app, &instance_root)?;
+    let entries = collect_screenshots(&screenshots_dir(root.path())?);
+    let page = page.unwrap_or(1).max(1);
+    let page_size = page_size
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+        .clamp(1, MAX_PAGE_SIZE);
+    let total = entries.len();
+    let offset = (page - 1) as usize * page_size as usize;
+    let entries: Vec<ScreenshotEntry> = entries
+        .into_iter()
+        .skip(offset)
+        .take(page_size as usize)
+        .collect();
+    Ok(ScreenshotPage {
+        has_more: offset + entries.len() < total,
+        entries,
+        page,
+        page_size,
+        total,
+    })
+}
+
+#[tauri::command]
+pub async fn get_screenshot_thumbnail(
+    app: AppHandle,
+    instance_root: String,
+    file_name: String,
+    max_dimension: Option<u32>,
+) -> Result<ScreenshotThumbnail, String> {
+    let root = resolve_trusted_instance_root(&app, &instance_root)?;
+    let max_dimension = max_dimension
+        .unwrap_or(320)
+        .clamp(MIN_THUMBNAIL_DIMENSION, MAX_THUMBNAIL_DIMENSION);
+    tauri::async_runtime::spawn_blocking(move || {
+        let source = resolve_screenshot(&screenshots_dir(root.path())?, &file_name)?;
+        load_or_create_thumbnail(
+            &source,
+            &file_name,
+            &root.path().join(THUMBNAIL_CACHE_DIR),
+            max_dimension,
+        )
+    })
+    .await
+    .map_err(|err| format!("Falló la tarea de miniatura: {err}"))?
+}
+
+#[tauri::command]
+pub fn delete_screenshot(
+    app: AppHandle,
+    instance_root: String,
+    file_name: String,
+) -> Result<(), String> {
+    let root = resolve_trusted_instance_root(&app, &instance_root)?;
+    let target = resolve_screenshot(&screenshots_dir(root.path())?, &file_name)?;
+    fs::remove_file(&target)
+        .map_err(|err| format!("No se pudo eliminar {}: {err}", target.display()))?;
+    remove_cached_thumbnails(&root.path().join(THUMBNAIL_CACHE_DIR), &file_name);
+    log::info!("✔ Captura eliminada: {}", target.display());
+    Ok(())
+}
+
+/// Abre la carpeta de capturas; con `file_name` comprueba antes que la captura exista.
+#[tauri::command]
+pub fn reveal_screenshot(
+    app: AppHandle,
+    instance_root: String,
+    file_name: Option<String>,
+) -> Result<(), String> {
+    let root = resolve_trusted_instance_root(&app, &instance_root)?;
+    let dir = screenshots_dir(root.path())?;
+    if let Some(file_name) = file_name.as_deref() {
+        resolve_screenshot(&dir, file_name)?;
+    }
+    open_folder_in_explorer(&dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sandbox(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "screenshots-{name}-{}-{}",
+            std::process::id(),
+            std::time::SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_nanos())
+                .unwrap_or(0)
+        ));
+        fs::create_dir_all(&dir).expect("sandbox");
+        dir
+    }
+
+    #[test]
+    fn lists_newest_first_and_rejects_traversal() {
+        let dir = sandbox("list");
+        for name in [
+            "2024-01-31_12.34.56.png",
+            "2024-02-01_08.00.00.png",
+            "2024-02-01_08.00.00_2.png",
+            "notas.txt",
+        ] {
+            fs::write(dir.join(name), b"png").expect("write");
+        }
+        let names: Vec<String> = collect_screenshots(&dir)
+            .into_iter()
+            .map(|entry| entry.file_name)
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "2024-02-01_08.00.00_2.png",
+                "2024-02-01_08.00.00.png",
+                "2024-01-31_12.34.56.png",
+            ]
+        );
+        assert_eq!(collect_screenshots(&dir)[2].taken_at, "2024-01-31T12:34:56");
+
+        for bad in [
+            "../options.txt",
+            "..\\x.png",
+            "",
+            ".oculta.png",
+            "notas.txt",
+        ] {
+            assert!(resolve_screenshot(&dir, bad).is_err(), "{bad}");
+        }
+        assert!(resolve_screenshot(&dir, "2024-01-31_12.34.56.png").is_ok());
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn thumbnail_is_downscaled_and_cached() {
+        let dir = sandbox("thumb");
+        let source = dir.join("2024-01-31_12.34.56.png");
+        RgbaImage::from_fn(400, 200, |x, _| {
+            if x < 200 {
+                image::Rgba([255, 0, 0, 255])
+            } else {
+                image::Rgba([0, 0, 255, 255])
+            }
+        })
+        .save(&source)
+        .expect("png");
+        let cache = dir.join(THUMBNAIL_CACHE_DIR);
+
+        let first = load_or_create_thumbnail(&source, "2024-01-31_12.34.56.png", &cache, 100)
+            .expect("thumbnail");
+        assert!(!first.from_cache);
+        assert_eq!((first.width, first.height), (100, 50));
+        let second = load_or_create_thumbnail(&source, "2024-01-31_12.34.56.png", &cache, 100)
+            .expect("thumbnail");
+        assert!(second.from_cache);
+        assert_eq!(first.data_url, second.data_url);
+
+        let decoded = downscale_png_streaming(&source, 100).expect("downscale");
+        assert_eq!(decoded.get_pixel(10, 25).0, [255, 0, 0, 255]);
+        assert_eq!(decoded.get_pixel(90, 25).0, [0, 0, 255, 255]);
+
+        remove_cached_thumbnails(&cache, "2024-01-31_12.34.56.png");
+        assert_eq!(fs::read_dir(&cache).expect("cache").count(), 0);
+        let _ = fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,312 @@
+// Estado de los servicios de Minecraft.
+
+use std::{
+    sync::{Mutex, OnceLock},
+    time::Duration,
+};
+
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::{
+    infrastructure::http::{rate_limit::LimitedRequestError, tls},
+    shared::clock::{app_clock, Clock},
+};
+
+const FAILURE_THRESHOLD: u32 = 3;
+const FAILURE_WINDOW: Duration = Duration::from_secs(5 * 60);
+const COOLDOWN: Duration = Duration::from_secs(2 * 60);
+const MINECRAFT_SERVICES: &str = "minecraftServices";
+
+/// Los servicios de Minecraft no responden y el circuito está abierto. Se serializa para
+/// que la UI muestre la cuenta atrás.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceUnavailableError {
+    /// Siempre `SERVICE_UNAVAILABLE`.
+    pub code: &'static str,
+    pub service: String,
+    pub retry_after_seconds: u64,
+    pub last_error: Option<String>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    Closed,
+    Open,
+    /// Enfriamiento cumplido: la próxima petición decide si se cierra o se reabre.
+    HalfOpen,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceStatus {
+    pub service: String,
+    pub state: CircuitState,
+    pub consecutive_failures: u32,
+    pub cooldown_remaining_seconds: u64,
+    pub last_error: Option<String>,
+    pub last_failure_at: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct CircuitBreaker {
+    threshold: u32,
+    window_ms: u64,
+    cooldown_ms: u64,
+    consecutive_failures: u32,
+    first_failure_ms: Option<u64>,
+    open_until_ms: Option<u64>,
+    last_error: Option<String>,
+    last_failure_at: Option<String>,
+}
+
+impl CircuitBreaker {
+    pub fn new(threshold: u32, window: Duration, cooldown: Duration) -> Self {
+        Self {
+            threshold: threshold.max(1),
+            window_ms: window.as_millis() as u64,
+            cooldown_ms: cooldown.as_millis() as u64,
+            consecutive_failures: 0,
+            first_failure_ms: None,
+            open_until_ms: None,
+            last_error: None,
+            last_failure_at: None,
+        }
+    }
+
+    pub fn state(&self, clock: &dyn Clock) -> CircuitState {
+        match self.open_until_ms {
+            None => CircuitState::Closed,
+            Some(until) if until > clock.now_millis() => CircuitState::Open,
+            Some(_) => CircuitState::HalfOpen,
+        }
+    }
+
+    /// `Err` con el tiempo que queda de enfriamiento si no se debe intentar la petición.
+    pub fn check(&self, clock: &dyn Clock) -> Result<(), Duration> {
+        match self.open_until_ms {
+            Some(until) if until > clock.now_millis() => {
+                Err(Duration::from_millis(until - clock.now_millis()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// El servicio respondió: se cierra el circuito.
+    pub fn record_success(&mut self) {
+        self.consecutive_failures = 0;
+        self.first_failure_ms = None;
+        self.open_until_ms = None;
+    }
+
+    pub fn record_failure(&mut self, clock: &dyn Clock, error: String) {
+        let now = clock.now_millis();
+        self.last_error = Some(error);
+        self.last_failure_at = Some(clock.now_rfc3339());
+        if self.open_until_ms.is_some() {
+            // Falló la petición de prueba (o una que ya estaba en vuelo): otro enfriamiento.
+            self.consecutive_failures += 1;
+            self.open_until_ms = Some(now + self.cooldown_ms);
+            return;
+        }
+        match self.first_failure_ms {
+            Some(first) if now.saturating_sub(first) <= self.window_ms => {
+                self.consecutive_failures += 1;
+            }
+            _ => {
+                self.consecutive_failures = 1;
+                self.first_failure_ms = Some(now);
+            }
+        }
+        if self.consecutive_failures >= self.threshold {
+            self.open_until_ms = Some(now + self.cooldown_ms);
+        }
+    }
+
+    pub fn status(&self, service: &str, clock: &dyn Clock) -> ServiceStatus {
+        ServiceStatus {
+            service: service.to_string(),
+            state: self.state(clock),
+            consecutive_failures: self.consecutive_failures,
+            cooldown_remaining_seconds: self
+                .check(clock)
+                .err()
+                .map_or(0, |remaining| remaining.as_secs_f64().ceil() as u64),
+            last_error: self.last_error.clone(),
+            last_failure_at: self.last_failure_at.clone(),
+        }
+    }
+}
+
+static MINECRAFT_SERVICES_CIRCUIT: OnceLock<Mutex<CircuitBreaker>> = OnceLock::new();
+
+fn minecraft_services_circuit() -> std::sync::MutexGuard<'static, CircuitBreaker> {
+    MINECRAFT_SERVICES_CIRCUIT
+        .get_or_init(|| {
+            Mutex::new(CircuitBreaker::new(
+                FAILURE_THRESHOLD,
+                FAILURE_WINDOW,
+                COOLDOWN,
+            ))
+        })
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Falla en el acto si el circuito de los servicios de Minecraft está abierto.
+pub(crate) fn ensure_minecraft_services_available(
+    clock: &dyn Clock,
+) -> Result<(), ServiceUnavailableError> {
+    let circuit = minecraft_services_circuit();
+    circuit.check(clock).map_err(|remaining| {
+        let retry_after_seconds = remaining.as_secs_f64().ceil().max(1.0) as u64;
+        ServiceUnavailableError {
+            code: "SERVICE_UNAVAILABLE",
+            service: MINECRAFT_SERVICES.to_string(),
+            retry_after_seconds,
+            last_error: circuit.last_error.clone(),
+            message: format!(
+                "Los servicios de Minecraft no responden ({} fallos seguidos). Reintenta en {retry_after_seconds} s.",
+                circuit.consecutive_failures
+            ),
+        }
+    })
+}
+
+/// Qué dice una petición sobre el estado del servicio.
+enum Observation {
+    Reachable,
+    Outage(String),
+    /// Fallo que no es del servicio (límite de peticiones local, inspección TLS, otros).
+    Unrelated,
+}
+
+fn observe(result: &Result<reqwest::blocking::Response, LimitedRequestError>) -> Observation {
+    match result {
+        Ok(response) if response.status().is_server_error() => {
+            Observation::Outage(format!("HTTP {}", response.status().as_u16()))
+        }
+        Ok(_) => Observation::Reachable,
+        Err(LimitedRequestError::Request(err))
+            if (err.is_timeout() || err.is_connect()) && tls::interception_error(err).is_none() =>
+        {
+            Observation::Outage(err.to_string())
+        }
+        Err(_) => Observation::Unrelated,
+    }
+}
+
+/// Registra en el circuito el resultado de una petición a los servicios de Minecraft.
+pub(crate) fn observe_minecraft_services(
+    result: &Result<reqwest::blocking::Response, LimitedRequestError>,
+    clock: &dyn Clock,
+) {
+    let mut circuit = minecraft_services_circuit();
+    match observe(result) {
+        Observation::Outage(reason) => {
+            circuit.record_failure(clock, reason);
+            if circuit.state(clock) == CircuitState::Open {
+                log::warn!(
+                    "⚠ Servicios de Minecraft sin respuesta: {} fallos seguidos; lanzamientos en pausa {} s.",
+                    circuit.consecutive_failures,
+                    COOLDOWN.as_secs()
+                );
+            }
+        }
+        Observation::Reachable => circuit.record_success(),
+        Observation::Unrelated => {}
+    }
+}
+
+/// Estado de los servicios externos vigilados por el launcher.
+#[tauri::command]
+pub fn get_service_status(app: AppHandle) -> Vec<ServiceStatus> {
+    let clock = app_clock(&app).clock;
+    vec![minecraft_services_circuit().status(MINECRAFT_SERVICES, clock.as_ref())]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::clock::mock::MockClock;
+
+    fn breaker() -> CircuitBreaker {
+        CircuitBreaker::new(3, Duration::from_secs(300), Duration::from_secs(120))
+    }
+
+    #[test]
+    fn opens_after_consecutive_failures_and_recovers_on_success() {
+        let clock = MockClock::at("2024-05-01T10:00:00Z");
+        let mut circuit = breaker();
+        for _ in 0..2 {
+            circuit.record_failure(&clock, "timeout".to_string());
+            clock.advance(chrono::Duration::seconds(25));
+        }
+        assert_eq!(circuit.state(&clock), CircuitState::Closed);
+        assert!(circuit.check(&clock).is_ok());
+
+        circuit.record_failure(&clock, "timeout".to_string());
+        assert_eq!(circuit.state(&clock), CircuitState::Open);
+        assert_eq!(circuit.check(&clock), Err(Duration::from_secs(120)));
+
+        clock.advance(chrono::Duration::seconds(90));
+        let status = circuit.status("minecraftServices", &clock);
+        assert_eq!(status.cooldown_remaining_seconds, 30);
+        assert_eq!(status.consecutive_failures, 3);
+
+        clock.advance(chrono::Duration::seconds(30));
+        assert_eq!(circuit.state(&clock), CircuitState::HalfOpen);
+        assert!(circuit.check(&clock).is_ok());
+
+        circuit.record_success();
+        assert_eq!(circuit.state(&clock), CircuitState::Closed);
+        assert_eq!(
+            circuit
+                .status("minecraftServices", &clock)
+                .consecutive_failures,
+            0
+        );
+    }
+
+    #[test]
+    fn failed_probe_reopens_immediately() {
+        let clock = MockClock::at("2024-05-01T10:00:00Z");
+        let mut circuit = breaker();
+        for _ in 0..3 {
+            circuit.record_failure(&clock, "timeout".to_string());
+        }
+        clock.advance(chrono::Duration::seconds(121));
+        assert_eq!(circuit.state(&clock), CircuitState::HalfOpen);
+
+        circuit.record_failure(&clock, "HTTP 503".to_string());
+        assert_eq!(circuit.state(&clock), CircuitState::Open);
+        assert_eq!(circuit.check(&clock), Err(Duration::from_secs(120)));
+        assert_eq!(
+            circuit
+                .status("minecraftServices", &clock)
+                .last_error
+                .as_deref(),
+            Some("HTTP 503")
+        );
+    }
+
+    #[test]
+    fn failures_outside_the_window_start_a_new_count() {
+        let clock = MockClock::at("2024-05-01T10:00:00Z");
+        let mut circuit = breaker();
+        circuit.record_failure(&clock, "timeout".to_string());
+        circuit.record_failure(&clock, "timeout".to_string());
+        clock.advance(chrono::Duration::minutes(6));
+        circuit.record_failure(&clock, "timeout".to_string());
+        assert_eq!(circuit.state(&clock), CircuitState::Closed);
+        assert_eq!(
+            circuit
+                .status("minecraftServices", &clock)
+                .consecutive_failures,
+            1
+        );
+    }
+}
@@ -0,0 +1,404 @@
+// Perfiles de ajustes del jugador.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::AppHandle;
+
+use crate::{
+    app::{
+        instance_service::{is_instance_running, read_instance_metadata},
+        trusted_root::resolve_trusted_instance_root,
+    },
+    infrastructure::filesystem::{
+        file_ops::write_file_replacing,
+        paths::{resolve_launcher_root, safe_path_component},
+    },
+    shared::result::AppResult,
+};
+
+const PROFILES_DIR: &str = "settings-profiles";
+const PROFILE_MANIFEST: &str = "profile.json";
+
+/// Archivos del game dir que forman un perfil: opciones de vanilla, OptiFine y shaders y
+/// la configuración de cliente de Sodium e Iris.
+const PROFILE_FILES: &[&str] = &[
+    "options.txt",
+    "optionsof.txt",
+    "optionsshaders.txt",
+    "config/sodium-options.json",
+    "config/iris.properties",
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsProfile {
+    pub name: String,
+    pub created_at: String,
+    /// Nombre de la instancia de la que se capturó.
+    #[serde(default)]
+    pub source_instance: Option<String>,
+    pub files: Vec<String>,
+    /// Se guardó sin quitar servidores ni tokens.
+    #[serde(default)]
+    pub include_private: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplySettingsProfileReport {
+    pub applied: Vec<String>,
+    /// Ya existían en la instancia y no se pidió sobrescribir.
+    pub skipped: Vec<String>,
+}
+
+fn profiles_root(app: &AppHandle) -> AppResult<PathBuf> {
+    Ok(resolve_launcher_root(app)?
+        .join("config")
+        .join(PROFILES_DIR))
+}
+
+fn profile_dir(profiles_root: &Path, name: &str) -> AppResult<PathBuf> {
+    let component = safe_path_component(name.trim()).map_err(|err| err.to_string())?;
+    Ok(profiles_root.join(component))
+}
+
+/// Clave privada: direcciones de servidor, tokens y contraseñas.
+fn is_private_key(key: &str) -> bool {
+    let key = key.trim().trim_matches('"').to_ascii_lowercase();
+    key == "lastserver"
+        || ["token", "password", "secret", "session"]
+            .iter()
+            .any(|needle| key.contains(needle))
+        || ["serveraddress", "server_address", "serverip", "server_ip"]
+            .iter()
+            .any(|needle| key.contains(needle))
+}
+
+fn scrub_json(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            map.retain(|key, _| !is_private_key(key));
+            map.values_mut().for_each(scrub_json);
+        }
+        Value::Array(items) => items.iter_mut().for_each(scrub_json),
+        _ => {}
+    }
+}
+
+/// Contenido de `relative` sin datos privados. Los archivos de texto se filtran por línea
+/// (`clave:valor` o `clave=valor`) y los JSON por clave.
+pub(crate) fn scrub_private(relative: &str, content: &[u8]) -> Vec<u8> {
+    if relative.ends_with(".json") {
+        if let Ok(mut value) = serde_json::from_slice::<Value>(content) {
+            scrub_json(&mut value);
+            if let Ok(raw) = serde_json::to_vec_pretty(&value) {
+                return raw;
+            }
+        }
+        return content.to_vec();
+    }
+    let text = String::from_utf8_lossy(content);
+    let mut scrubbed = text
+        .lines()
+        .filter(|line| {
+            let key = line.split([':', '=']).next().unwrap_or_default();
+            !is_private_key(key)
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    if text.ends_with('\n') {
+        scrubbed.push('\n');
+    }
+    scrubbed.into_bytes()
+}
+
+/// Copia los archivos del perfil que existan en `game_dir` a `target`.
+pub(crate) fn capture_profile_files(
+    game_dir: &Path,
+    target: &Path,
+    include_private: bool,
+) -> AppResult<Vec<String>> {
+    let mut captured = Vec::new();
+    for relative in PROFILE_FILES {
+        let source = game_dir.join(relative);
+        if !source.is_file() {
+            continue;
+        }
+        let content = fs::read(&source)
+            .map_err(|err| format!("No se pudo leer {}: {err}", source.display()))?;
+        let content = if include_private {
+            content
+        } else {
+            scrub_private(relative, &content)
+        };
+        let destination = target.join(relative);
+        if let Some(parent) = destination.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|err| format!("No se pudo crear {}: {err}", parent.display()))?;
+        }
+        write_file_replacing(&destination, &content, true)?;
+        captured.push(relative.to_string());
+    }
+    Ok(captured)
+}
+
+/// Copia los archivos del perfil al game dir. Sin `overwrite` solo se copian los que no
+/// existen en el destino.
+pub(crate) fn apply_profile_files(
+    profile_dir: &Path,
+    files: &[String],
+    game_dir: &Path,
+    overwrite: bool,
+) -> AppResult<ApplySettingsProfileReport> {
+    let mut report = ApplySettingsProfileReport::default();
+    for relative in files {
+        // El manifiesto podría estar editado a mano: solo se aceptan archivos conocidos.
+        if !PROFILE_FILES.contains(&relative.as_str()) {
+            continue;
+        }
+        let source = profile_dir.join(relative);
+        if !source.is_file() {
+            continue;
+        }
+        let destination = game_dir.join(relative);
+        if destination.exists() && !overwrite {
+            report.skipped.push(relative.clone());
+            continue;
+        }
+        let content = fs::read(&source)
+            .map_err(|err| format!("No se pudo leer {}: {err}", source.display()))?;
+        if let Some(parent) = destination.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|err| format!("No se pudo crear {}: {err}", parent.display()))?;
+        }
+        write_file_replacing(&destination, &content, true)?;
+        report.applied.push(relative.clone());
+    }
+    Ok(report)
+}
+
+fn read_manifest(dir: &Path) -> AppResult<SettingsProfile> {
+    let path = dir.join(PROFILE_MANIFEST);
+    let raw = fs::read_to_string(&path)
+        .map_err(|err| format!("No se pudo leer {}: {err}", path.display()))?;
+    serde_json::from_str(&raw)
+        .map_err(|err| format!("Perfil inválido en {}: {err}", path.display()))
+}
+
+/// Perfil guardado con ese nombre y su carpeta.
+pub(crate) fn find_settings_profile(
+    app: &AppHandle,
+    name: &str,
+) -> AppResult<(SettingsProfile, PathBuf)> {
+    let dir = profile_dir(&profiles_root(app)?, name)?;
+    if !dir.join(PROFILE_MANIFEST).is_file() {
+        return Err(format!("No existe el perfil de ajustes '{}'.", name.trim()));
+    }
+    Ok((read_manifest(&dir)?, dir))
+}
+
+/// Captura los ajustes de una instancia en un perfil; si ya existe uno con ese nombre se
+/// reemplaza.
+#[tauri::command]
+pub fn save_settings_profile(
+    app: AppHandle,
+    name: String,
+    from_instance_root: String,
+    include_private: Option<bool>,
+) -> Result<SettingsProfile, String> {
+    let instance_root = resolve_trusted_instance_root(&app, &from_instance_root)?;
+    let metadata = read_instance_metadata(instance_root.to_string())?;
+    let include_private = include_private.unwrap_or(false);
+    let root = profiles_root(&app)?;
+    let dir = profile_dir(&root, &name)?;
+
+    // Se captura en una carpeta aparte y se cambia al final, para no dejar el perfil
+    // anterior a medias si algo falla.
+    let staging = root.join(format!(
+        ".{}.tmp",
+        dir.file_name().unwrap_or_default().to_string_lossy()
+    ));
+    if staging.exists() {
+        fs::remove_dir_all(&staging)
+            .map_err(|err| format!("No se pudo limpiar {}: {err}", staging.display()))?;
+    }
+    let files = capture_profile_files(
+        &instance_root.path().join("minecraft"),
+        &staging,
+        include_private,
+    )?;
+    if files.is_empty() {
+        let _ = fs::remove_dir_all(&staging);
+        return Err(format!(
+            "La instancia '{}' no tiene options.txt ni otros ajustes que guardar.",
+            metadata.name
+        ));
+    }
+    let profile = SettingsProfile {
+        name: name.trim().to_string(),
+        created_at: chrono::Utc::now().to_rfc3339(),
+        source_instance: Some(metadata.name),
+        files,
+        include_private,
+    };
+    let raw = serde_json::to_string_pretty(&profile)
+        .map_err(|err| format!("No se pudo serializar el perfil: {err}"))?;
+    write_file_replacing(&staging.join(PROFILE_MANIFEST), raw.as_bytes(), true)?;
+    if dir.exists() {
+        fs::remove_dir_all(&dir)
+            .map_err(|err| format!("No se pudo reemplazar {}: {err}", dir.display()))?;
+    }
+    fs::rename(&staging, &dir)
+        .map_err(|err| format!("No se pudo guardar el perfil en {}: {err}", dir.display()))?;
+    log::info!(
+        "🔹 Perfil de ajustes '{}' guardado ({} archivos).",
+        profile.name,
+        profile.files.len()
+    );
+    Ok(profile)
+}
+
+#[tauri::command]
+pub fn list_settings_profiles(app: AppHandle) -> Result<Vec<SettingsProfile>, String> {
+    let root = profiles_root(&app)?;
+    let mut profiles = fs::read_dir(&root)
+        .into_iter()
+        .flatten()
+        .flatten()
+        // Las carpetas `.<nombre>.tmp` son capturas a medias.
+        .filter(|entry| !entry.file_name().to_string_lossy().starts_with('.'))
+        .filter(|entry| entry.path().join(PROFILE_MANIFEST).is_file())
+        .filter_map(|entry| match read_manifest(&entry.path()) {
+            Ok(profile) => Some(profile),
+            Err(err) => {
+                log::warn!("⚠ {err}");
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+    profiles.sort_by_key(|profile| profile.name.to_lowercase());
+    Ok(profiles)
+}
+
+#[tauri::command]
+pub fn apply_settings_profile(
+    app: AppHandle,
+    instance_root: String,
+    name: String,
+    overwrite: bool,
+) -> Result<ApplySettingsProfileReport, String> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    if is_instance_running(instance_root.as_str()) {
+        return Err(
+            "La instancia está en ejecución; ciérrala antes de aplicar un perfil de ajustes."
+                .to_string(),
+        );
+    }
+    let metadata = read_instance_metadata(instance_root.to_string())?;
+    if metadata.state.eq_ignore_ascii_case("redirect") {
+        return Err(
+            "Las instancias REDIRECT usan los ajustes del launcher de origen; no se modifican."
+                .to_string(),
+        );
+    }
+    let (profile, dir) = find_settings_profile(&app, &name)?;
+    let report = apply_profile_files(
+        &dir,
+        &profile.files,
+        &instance_root.path().join("minecraft"),
+        overwrite,
+    )?;
+    log::info!(
+        "🔹 Perfil de ajustes '{}' aplicado a {}: {} copiados, {} conservados.",
+        profile.name,
+        metadata.name,
+        report.applied.len(),
+        report.skipped.len()
+    );
+    Ok(report)
+}
+
+#[tauri::command]
+pub fn delete_settings_profile(app: AppHandle, name: String) -> Result<(), String> {
+    let (_, dir) = find_settings_profile(&app, &name)?;
+    fs::remove_dir_all(&dir).map_err(|err| format!("No se pudo eliminar {}: {err}", dir.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::test_support::test_temp_dir;
+
+    #[test]
+    fn scrubs_servers_and_tokens_from_captured_files() {
+        let options =
+            b"fov:0.25\nlastServer:mc.example.net:25565\nkey_key.jump:key.keyboard.space\n";
+        assert_eq!(
+            String::from_utf8(scrub_private("options.txt", options)).expect("utf8"),
+            "fov:0.25\nkey_key.jump:key.keyboard.space\n"
+        );
+
+        let json =
+            br#"{"quality":{"weather":"FAST"},"auth":{"accessToken":"abc","serverAddress":"x"}}"#;
+        let scrubbed: Value =
+            serde_json::from_slice(&scrub_private("config/sodium-options.json", json))
+                .expect("json");
+        assert_eq!(
+            scrubbed,
+            serde_json::json!({"quality": {"weather": "FAST"}, "auth": {}})
+        );
+    }
+
+    #[test]
+    fn capture_and_merge_apply() {
+        let root = test_temp_dir("interface-settings-profiles");
+        let source = root.join("source");
+        fs::create_dir_all(source.join("config")).expect("config");
+        fs::write(source.join("options.txt"), "fov:0.5\nlastServer:play.net\n").expect("options");
+        fs::write(
+            source.join("config/iris.properties"),
+            "shaderPack=BSL.zip\n",
+        )
+        .expect("iris");
+        fs::write(source.join("servers.dat"), "no se captura").expect("servers");
+
+        let profile = root.join("profile");
+        let files = capture_profile_files(&source, &profile, false).expect("capture");
+        assert_eq!(files, vec!["options.txt", "config/iris.properties"]);
+        assert!(!profile.join("servers.dat").exists());
+        assert_eq!(
+            fs::read_to_string(profile.join("options.txt")).expect("options"),
+            "fov:0.5\n"
+        );
+
+        let target = root.join("target");
+        fs::create_dir_all(&target).expect("target");
+        fs::write(target.join("options.txt"), "fov:1.0\n").expect("existing");
+        let merged = apply_profile_files(&profile, &files, &target, false).expect("merge");
+        assert_eq!(merged.applied, vec!["config/iris.properties"]);
+        assert_eq!(merged.skipped, vec!["options.txt"]);
+        assert_eq!(
+            fs::read_to_string(target.join("options.txt")).expect("options"),
+            "fov:1.0\n"
+        );
+
+        let overwritten = apply_profile_files(&profile, &files, &target, true).expect("overwrite");
+        assert_eq!(overwritten.applied.len(), 2);
+        assert_eq!(
+            fs::read_to_string(target.join("options.txt")).expect("options"),
+            "fov:0.5\n"
+        );
+
+        let tampered = vec!["../escape.txt".to_string()];
+        assert!(apply_profile_files(&profile, &tampered, &target, true)
+            .expect("ignored")
+            .applied
+            .is_empty());
+        let _ = fs::remove_dir_all(root);
+    }
+}
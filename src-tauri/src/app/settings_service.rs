@@ -6,9 +6,12 @@ use std::{
 
 use tauri::AppHandle;
 
-use crate::infrastructure::filesystem::{
-    config::{load_launcher_config, save_launcher_config},
-    paths::{folder_routes_settings_file, resolve_launcher_root},
+use crate::{
+    app::maintenance::begin_maintenance,
+    infrastructure::filesystem::{
+        config::{load_launcher_config, save_launcher_config},
+        paths::{folder_routes_settings_file, resolve_launcher_root, write_launcher_root_pointer},
+    },
 };
 
 #[derive(serde::Serialize)]
@@ -256,6 +259,7 @@ pub fn save_folder_routes(app: AppHandle, routes: serde_json::Value) -> Result<(
         .filter(|value| !value.is_empty());
 
     let mut config = load_launcher_config(&app).unwrap_or_default();
+    write_launcher_root_pointer(&app, launcher_route.as_deref().map(Path::new))?;
     config.launcher_root_override = launcher_route;
     config.instances_dir_override = instances_route;
     save_launcher_config(&app, &config)?;
@@ -342,6 +346,7 @@ pub fn migrate_instances_folder(
     source_path: String,
     target_path: String,
 ) -> Result<FolderRouteMigrationResult, String> {
+    let _maintenance = begin_maintenance("migrate_instances_folder")?;
     let source = PathBuf::from(normalize_path(&source_path));
     let target = PathBuf::from(normalize_path(&target_path));
 
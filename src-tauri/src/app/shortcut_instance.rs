@@ -15,6 +15,7 @@ use tauri::AppHandle;
 use crate::{
     app::redirect_launch::{build_classpath_multi, prepare_redirect_natives},
     domain::{
+        instance::interop::read_external_manifest,
         java::java_requirement::determine_required_java,
         minecraft::{
             argument_resolver::{resolve_launch_arguments, LaunchContext},
@@ -172,10 +173,8 @@ pub fn create_shortcut_instance(
     let instances_root = crate::app::settings_service::resolve_instances_root(app)?;
     fs::create_dir_all(&instances_root)
         .map_err(|e| format!("No se pudo crear instances root: {e}"))?;
-    let mut sanitized = crate::infrastructure::filesystem::paths::sanitize_path_segment(&req.name);
-    if sanitized.trim().is_empty() {
-        sanitized = "instancia-atajo".to_string();
-    }
+    let sanitized = crate::infrastructure::filesystem::paths::safe_path_component(&req.name)
+        .map_err(|err| err.to_string())?;
     let mut instance_root = instances_root.join(&sanitized);
     let mut i = 1;
     while instance_root.exists() {
@@ -331,12 +330,36 @@ pub fn create_shortcut_instance(
         state: "REDIRECT".to_string(),
         last_used: None,
         internal_uuid: state.id.clone(),
+        filesystem: None,
+        mods_dir_override: None,
+        adopted: false,
+        tags: Vec::new(),
+        locked_fields: Vec::new(),
+        locked_by: String::new(),
+        optional_game_flags: Default::default(),
+        java_build_pin: None,
+        source_settings_write: false,
+        retention: Default::default(),
+        library_overrides: Vec::new(),
+        pack_origin: None,
+        auto_jvm_tuning: false,
+        preferred_gpu: None,
+        backup: Default::default(),
+        strict_mode: false,
+        skip_duplicate_mod_check: false,
+        window_tweaks: Default::default(),
+        launch_hooks: Default::default(),
+        console_filters: Vec::new(),
     };
     fs::write(
         instance_root.join(".instance.json"),
         serde_json::to_vec_pretty(&metadata).map_err(|e| e.to_string())?,
     )
     .map_err(|e| e.to_string())?;
+    crate::app::launcher_snapshot::index_instance_metadata(
+        &instance_root.display().to_string(),
+        &metadata,
+    );
     fs::write(instance_root.join(".redirect.json"), serde_json::to_vec_pretty(&serde_json::json!({"sourcePath": external_root_dir.display().to_string(), "sourceLauncher": req.source_launcher})).map_err(|e| e.to_string())?).map_err(|e| e.to_string())?;
 
     state.status = "READY".to_string();
@@ -986,7 +1009,15 @@ pub fn build_java_args(
 fn read_instance_manifest_strict(source_root: &Path) -> (String, String, String) {
     let manifest_path = source_root.join("minecraftinstance.json");
     if !manifest_path.exists() {
-        return (String::new(), String::new(), String::new());
+        return read_external_manifest(source_root)
+            .map(|manifest| {
+                (
+                    manifest.minecraft_version,
+                    manifest.loader,
+                    manifest.loader_version.unwrap_or_default(),
+                )
+            })
+            .unwrap_or_default();
     }
     let raw = match fs::read_to_string(&manifest_path) {
         Ok(v) => v,
@@ -0,0 +1,408 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::AppHandle;
+
+use crate::{
+    app::{
+        game_dir_guard::find_game_dir_conflict,
+        instance_service::{is_instance_running, read_instance_metadata, update_instance_metadata},
+        redirect_launch::{redirect_game_dir, redirect_source},
+        trusted_root::resolve_trusted_instance_root,
+    },
+    domain::models::instance::InstanceMetadata,
+    infrastructure::filesystem::file_ops::write_file_replacing,
+};
+
+const INSTANCE_CFG: &str = "instance.cfg";
+const MMC_PACK: &str = "mmc-pack.json";
+const GENERAL_SECTION: &str = "General";
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceComponent {
+    pub uid: String,
+    pub version: String,
+}
+
+/// Ajustes de la instancia original de Prism/MultiMC que el atajo puede reflejar.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceInstanceSettings {
+    pub source_launcher: String,
+    pub cfg_path: String,
+    pub max_mem_mb: Option<u32>,
+    pub min_mem_mb: Option<u32>,
+    pub jvm_args: Option<String>,
+    pub override_memory: bool,
+    pub override_java_args: bool,
+    pub components: Vec<SourceComponent>,
+    pub write_enabled: bool,
+}
+
+/// Cambios a escribir en `instance.cfg`; los campos `None` no se tocan.
+#[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SourceSettingsPatch {
+    pub max_mem_mb: Option<u32>,
+    pub min_mem_mb: Option<u32>,
+    pub jvm_args: Option<String>,
+}
+
+/// `instance.cfg` editado línea a línea: conserva orden, comentarios, claves
+/// desconocidas y el fin de línea original.
+#[derive(Debug, Clone)]
+struct InstanceCfg {
+    lines: Vec<String>,
+    newline: &'static str,
+    trailing_newline: bool,
+}
+
+fn section_name(line: &str) -> Option<&str> {
+    let trimmed = line.trim();
+    trimmed
+        .strip_prefix('[')
+        .and_then(|rest| rest.strip_suffix(']'))
+        .map(str::trim)
+}
+
+fn split_key(line: &str) -> Option<(&str, &str)> {
+    let trimmed = line.trim_start();
+    if trimmed.starts_with('#') || trimmed.starts_with(';') {
+        return None;
+    }
+    let (key, value) = line.split_once('=')?;
+    Some((key.trim(), value))
+}
+
+impl InstanceCfg {
+    fn parse(raw: &str) -> Self {
+        let newline = if raw.contains("\r\n") { "\r\n" } else { "\n" };
+        let trailing_newline = raw.ends_with('\n');
+        let body = raw.strip_suffix(newline).unwrap_or(raw);
+        let lines = if raw.is_empty() {
+            Vec::new()
+        } else {
+            body.split(newline).map(str::to_string).collect()
+        };
+        Self {
+            lines,
+            newline,
+            trailing_newline,
+        }
+    }
+
+    fn render(&self) -> String {
+        let mut rendered = self.lines.join(self.newline);
+        if self.trailing_newline || self.lines.is_empty() {
+            rendered.push_str(self.newline);
+        }
+        rendered
+    }
+
+    /// Rango de líneas de la sección general: `[General]` en Prism o todo lo que va
+    /// antes de la primera sección en MultiMC (que no usa cabecera).
+    fn general_range(&self) -> (usize, usize) {
+        let header = self
+            .lines
+            .iter()
+            .position(|line| section_name(line) == Some(GENERAL_SECTION));
+        let start = header.map_or(0, |index| index + 1);
+        let end = self.lines[start..]
+            .iter()
+            .position(|line| section_name(line).is_some())
+            .map_or(self.lines.len(), |offset| start + offset);
+        (start, end)
+    }
+
+    fn get(&self, key: &str) -> Option<&str> {
+        let (start, end) = self.general_range();
+        self.lines[start..end]
+            .iter()
+            .filter_map(|line| split_key(line))
+            .find(|(found, _)| *found == key)
+            .map(|(_, value)| value)
+    }
+
+    fn set(&mut self, key: &str, value: &str) {
+        let (start, end) = self.general_range();
+        if let Some(index) = (start..end)
+            .find(|index| split_key(&self.lines[*index]).is_some_and(|(found, _)| found == key))
+        {
+            self.lines[index] = format!("{key}={value}");
+            return;
+        }
+        let insert_at = (start..end)
+            .rev()
+            .find(|index| !self.lines[*index].trim().is_empty())
+            .map_or(start, |index| index + 1);
+        self.lines.insert(insert_at, format!("{key}={value}"));
+    }
+}
+
+fn parse_bool(value: Option<&str>) -> bool {
+    value.is_some_and(|value| value.trim().eq_ignore_ascii_case("true"))
+}
+
+/// Valor INI de Qt: las cadenas con espacios o comas van entre comillas.
+fn unquote(value: &str) -> String {
+    let trimmed = value.trim();
+    trimmed
+        .strip_prefix('"')
+        .and_then(|rest| rest.strip_suffix('"'))
+        .map(|inner| inner.replace("\\\"", "\"").replace("\\\\", "\\"))
+        .unwrap_or_else(|| trimmed.to_string())
+}
+
+fn quote(value: &str) -> String {
+    if value.contains([' ', ',', ';', '"', '\\', '=']) {
+        format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn apply_patch(cfg: &mut InstanceCfg, patch: &SourceSettingsPatch) {
+    if patch.max_mem_mb.is_some() || patch.min_mem_mb.is_some() {
+        cfg.set("OverrideMemory", "true");
+        if let Some(max) = patch.max_mem_mb {
+            cfg.set("MaxMemAlloc", &max.to_string());
+        }
+        if let Some(min) = patch.min_mem_mb {
+            cfg.set("MinMemAlloc", &min.to_string());
+        }
+    }
+    if let Some(jvm_args) = patch.jvm_args.as_deref() {
+        cfg.set("OverrideJavaArgs", "true");
+        cfg.set("JvmArgs", &quote(jvm_args.trim()));
+    }
+}
+
+fn read_components(instance_dir: &Path) -> Vec<SourceComponent> {
+    let Some(pack) = fs::read_to_string(instance_dir.join(MMC_PACK))
+        .ok()
+        .and_then(|raw| serde_json::from_str::<Value>(&raw).ok())
+    else {
+        return Vec::new();
+    };
+    pack.get("components")
+        .and_then(Value::as_array)
+        .map(|components| {
+            components
+                .iter()
+                .filter_map(|component| {
+                    Some(SourceComponent {
+                        uid: component.get("uid")?.as_str()?.to_string(),
+                        version: component
+                            .get("version")
+                            .or_else(|| component.get("cachedVersion"))
+                            .and_then(Value::as_str)
+                            .unwrap_or_default()
+                            .to_string(),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Carpeta de la instancia de Prism/MultiMC (la que contiene `instance.cfg`); el origen
+/// puede apuntar a ella o a su `.minecraft`.
+fn source_instance_dir(instance_root: &Path) -> Result<(PathBuf, String), String> {
+    let (source_path, source_launcher) = redirect_source(instance_root)?;
+    let launcher = source_launcher.to_ascii_lowercase();
+    if !launcher.contains("prism") && !launcher.contains("multimc") {
+        return Err(format!(
+            "La instancia de origen es de '{source_launcher}'; solo se pueden sincronizar ajustes con Prism o MultiMC."
+        ));
+    }
+    let candidates = [
+        Some(source_path.clone()),
+        source_path.parent().map(Path::to_path_buf),
+    ];
+    candidates
+        .into_iter()
+        .flatten()
+        .find(|dir| dir.join(INSTANCE_CFG).is_file())
+        .map(|dir| (dir, source_launcher))
+        .ok_or_else(|| {
+            format!(
+                "No se encontró {INSTANCE_CFG} junto a la instancia de origen {}.",
+                source_path.display()
+            )
+        })
+}
+
+fn load_cfg(cfg_path: &Path) -> Result<InstanceCfg, String> {
+    let raw = fs::read(cfg_path)
+        .map_err(|err| format!("No se pudo leer {}: {err}", cfg_path.display()))?;
+    let raw =
+        String::from_utf8(raw).map_err(|_| format!("{} no está en UTF-8.", cfg_path.display()))?;
+    Ok(InstanceCfg::parse(&raw))
+}
+
+fn settings_from_cfg(
+    cfg: &InstanceCfg,
+    instance_dir: &Path,
+    source_launcher: String,
+    metadata: &InstanceMetadata,
+) -> SourceInstanceSettings {
+    SourceInstanceSettings {
+        source_launcher,
+        cfg_path: instance_dir.join(INSTANCE_CFG).display().to_string(),
+        max_mem_mb: cfg.get("MaxMemAlloc").and_then(|v| v.trim().parse().ok()),
+        min_mem_mb: cfg.get("MinMemAlloc").and_then(|v| v.trim().parse().ok()),
+        jvm_args: cfg
+            .get("JvmArgs")
+            .map(unquote)
+            .filter(|args| !args.is_empty()),
+        override_memory: parse_bool(cfg.get("OverrideMemory")),
+        override_java_args: parse_bool(cfg.get("OverrideJavaArgs")),
+        components: read_components(instance_dir),
+        write_enabled: metadata.source_settings_write,
+    }
+}
+
+/// Lee RAM, argumentos de Java y componentes de la instancia de Prism/MultiMC a la que
+/// apunta un atajo.
+#[tauri::command]
+pub fn read_source_instance_settings(
+    app: AppHandle,
+    instance_root: String,
+) -> Result<SourceInstanceSettings, String> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    let metadata = read_instance_metadata(instance_root.to_string())?;
+    let (instance_dir, source_launcher) = source_instance_dir(instance_root.path())?;
+    let cfg = load_cfg(&instance_dir.join(INSTANCE_CFG))?;
+    Ok(settings_from_cfg(
+        &cfg,
+        &instance_dir,
+        source_launcher,
+        &metadata,
+    ))
+}
+
+/// Escribe RAM y argumentos de Java en el `instance.cfg` de origen. Requiere el opt-in
+/// de la instancia y que ningún launcher la tenga abierta.
+#[tauri::command]
+pub fn write_source_instance_settings(
+    app: AppHandle,
+    instance_root: String,
+    patch: SourceSettingsPatch,
+) -> Result<SourceInstanceSettings, String> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    let metadata = read_instance_metadata(instance_root.to_string())?;
+    if !metadata.source_settings_write {
+        return Err(
+            "La escritura en la instancia de origen está desactivada para este atajo.".to_string(),
+        );
+    }
+    if let (Some(max), Some(min)) = (patch.max_mem_mb, patch.min_mem_mb) {
+        if min > max {
+            return Err(format!(
+                "La RAM mínima ({min} MB) no puede superar la máxima ({max} MB)."
+            ));
+        }
+    }
+    if is_instance_running(instance_root.as_str()) {
+        return Err(
+            "La instancia está en ejecución; ciérrala antes de cambiar sus ajustes.".to_string(),
+        );
+    }
+    let root = instance_root.path();
+    if let Some(conflict) = redirect_game_dir(root).and_then(|dir| find_game_dir_conflict(&dir)) {
+        return Err(conflict.message);
+    }
+
+    let (instance_dir, source_launcher) = source_instance_dir(root)?;
+    let cfg_path = instance_dir.join(INSTANCE_CFG);
+    let mut cfg = load_cfg(&cfg_path)?;
+    apply_patch(&mut cfg, &patch);
+    write_file_replacing(&cfg_path, cfg.render().as_bytes(), true)?;
+    log::info!(
+        "✔ Ajustes escritos en {} ({source_launcher})",
+        cfg_path.display()
+    );
+    Ok(settings_from_cfg(
+        &cfg,
+        &instance_dir,
+        source_launcher,
+        &metadata,
+    ))
+}
+
+/// Activa o desactiva la escritura de ajustes en la instancia de origen del atajo.
+#[tauri::command]
+pub fn set_source_settings_write_enabled(
+    app: AppHandle,
+    instance_root: String,
+    enabled: bool,
+) -> Result<InstanceMetadata, String> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    if enabled {
+        source_instance_dir(instance_root.path())?;
+    }
+    let metadata = update_instance_metadata(instance_root.as_str(), |metadata| {
+        metadata.source_settings_write = enabled;
+        Ok::<(), String>(())
+    })?;
+    Ok(metadata)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PRISM_CFG: &str = "[General]\r\nConfigVersion=1.2\r\nExportAuthor=\r\nInstanceType=OneSix\r\nJavaPath=/usr/lib/jvm/java-17/bin/java\r\nJvmArgs=\"-XX:+UseG1GC -Dfml.ignorePatchDiscrepancies=true\"\r\nMaxMemAlloc=4096\r\nMinMemAlloc=512\r\nOverrideJavaArgs=true\r\nOverrideMemory=true\r\niconKey=default\r\nlastLaunchTime=1700000000000\r\nname=Create 1.20.1\r\nnotes=\r\ntotalTimePlayed=3600\r\n";
+
+    const MULTIMC_CFG: &str = "InstanceType=OneSix\niconKey=flame\nname=Vanilla 1.8.9\nnotes=\n";
+
+    #[test]
+    fn prism_cfg_round_trips_and_patches_only_target_lines() {
+        let cfg = InstanceCfg::parse(PRISM_CFG);
+        assert_eq!(cfg.render(), PRISM_CFG);
+        assert_eq!(cfg.get("MaxMemAlloc"), Some("4096"));
+        assert_eq!(
+            cfg.get("JvmArgs").map(unquote).as_deref(),
+            Some("-XX:+UseG1GC -Dfml.ignorePatchDiscrepancies=true")
+        );
+
+        let mut patched = cfg.clone();
+        apply_patch(
+            &mut patched,
+            &SourceSettingsPatch {
+                max_mem_mb: Some(6144),
+                ..SourceSettingsPatch::default()
+            },
+        );
+        let rendered = patched.render();
+        assert_eq!(
+            rendered,
+            PRISM_CFG.replace("MaxMemAlloc=4096", "MaxMemAlloc=6144")
+        );
+    }
+
+    #[test]
+    fn headerless_multimc_cfg_gains_missing_keys_at_end_of_section() {
+        let mut cfg = InstanceCfg::parse(MULTIMC_CFG);
+        assert_eq!(cfg.render(), MULTIMC_CFG);
+        apply_patch(
+            &mut cfg,
+            &SourceSettingsPatch {
+                max_mem_mb: Some(2048),
+                min_mem_mb: None,
+                jvm_args: Some("-XX:+UseG1GC -Xss1M".to_string()),
+            },
+        );
+        assert_eq!(
+            cfg.render(),
+            "InstanceType=OneSix\niconKey=flame\nname=Vanilla 1.8.9\nnotes=\nOverrideMemory=true\nMaxMemAlloc=2048\nOverrideJavaArgs=true\nJvmArgs=\"-XX:+UseG1GC -Xss1M\"\n"
+        );
+        assert!(parse_bool(cfg.get("OverrideMemory")));
+        assert_eq!(cfg.get("MinMemAlloc"), None);
+    }
+}
@@ -0,0 +1,309 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::Path,
+    sync::OnceLock,
+    time::{Duration, Instant},
+};
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::app::{event_journal::emit_journaled, trusted_root::resolve_trusted_instance_root};
+
+const STARTUP_PROFILE_FILE: &str = ".startup-profile.json";
+/// Mods más lentos que se guardan en el perfil.
+const TOP_OFFENDERS: usize = 10;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ModLoadTime {
+    pub mod_id: String,
+    pub millis: u64,
+}
+
+/// Tiempo de arranque hasta la pantalla de título y mods que más tardaron en cargar.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct StartupProfile {
+    pub recorded_at: String,
+    pub total_secs: f64,
+    /// Mods con al menos una medición en el log.
+    pub mods_timed: usize,
+    pub top_mods: Vec<ModLoadTime>,
+}
+
+/// Contenido de `.startup-profile.json`: el último arranque y el anterior para comparar.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct StartupProfileHistory {
+    pub latest: Option<StartupProfile>,
+    pub previous: Option<StartupProfile>,
+}
+
+struct ProfilePatterns {
+    /// Forge: `Loading mod instance <id> of type ...` al empezar a construir el mod.
+    forge_loading: Regex,
+    /// Forge: `Loaded mod instance <id> of type ...` al terminar.
+    forge_loaded: Regex,
+    /// Fabric con `fabric.log.level=debug`: tiempo de cada entrypoint por mod.
+    fabric_entrypoint: Regex,
+    /// Resumen de la pantalla de carga: `Mod <id> took 1234ms`.
+    summary: Regex,
+    /// El juego llegó a la pantalla de título.
+    ready: Regex,
+}
+
+static PATTERNS: OnceLock<ProfilePatterns> = OnceLock::new();
+
+fn patterns() -> &'static ProfilePatterns {
+    PATTERNS.get_or_init(|| ProfilePatterns {
+        forge_loading: Regex::new(r"Loading mod instance ([a-z0-9_.\-]+) of type")
+            .expect("Regex de carga de mods de Forge inválida"),
+        forge_loaded: Regex::new(r"Loaded mod instance ([a-z0-9_.\-]+) of type")
+            .expect("Regex de carga de mods de Forge inválida"),
+        fabric_entrypoint: Regex::new(
+            r"(?i)entrypoint '?[\w:\-]+'? (?:for|of) mod '?([a-z0-9_.\-]+)'? took (\d+(?:\.\d+)?) ?(ms|s)\b",
+        )
+        .expect("Regex de entrypoints de Fabric inválida"),
+        summary: Regex::new(r"(?i)\bmod '?([a-z0-9_.\-]+)'? took (\d+(?:\.\d+)?) ?(ms|s)\b")
+            .expect("Regex de resumen de carga inválida"),
+        ready: Regex::new(r"Sound engine started").expect("Regex de pantalla de título inválida"),
+    })
+}
+
+fn to_millis(value: &str, unit: &str) -> Option<u64> {
+    let value = value.parse::<f64>().ok()?;
+    let millis = if unit.eq_ignore_ascii_case("s") {
+        value * 1000.0
+    } else {
+        value
+    };
+    Some(millis.round() as u64)
+}
+
+/// Acumula tiempos por mod mientras arranca el juego. Cada hilo lector de la salida tiene
+/// el suyo, así que las instancias simultáneas no se mezclan.
+pub(crate) struct StartupProfiler {
+    started: Instant,
+    constructing: HashMap<String, Instant>,
+    timings: HashMap<String, u64>,
+    finished: bool,
+}
+
+impl StartupProfiler {
+    pub fn new(started: Instant) -> Self {
+        Self {
+            started,
+            constructing: HashMap::new(),
+            timings: HashMap::new(),
+            finished: false,
+        }
+    }
+
+    fn add(&mut self, mod_id: &str, millis: u64) {
+        *self.timings.entry(mod_id.to_string()).or_default() += millis;
+    }
+
+    /// Procesa una línea; devuelve el perfil la primera vez que se llega a la pantalla de
+    /// título. Después ignora el resto de la salida.
+    pub fn observe(&mut self, line: &str, now: Instant) -> Option<StartupProfile> {
+        if self.finished {
+            return None;
+        }
+        let patterns = patterns();
+        if let Some(caps) = patterns.forge_loading.captures(line) {
+            self.constructing.insert(caps[1].to_string(), now);
+        } else if let Some(caps) = patterns.forge_loaded.captures(line) {
+            if let Some(started) = self.constructing.remove(&caps[1]) {
+                let millis = now.saturating_duration_since(started).as_millis() as u64;
+                self.add(&caps[1], millis);
+            }
+        } else if let Some(caps) = patterns
+            .fabric_entrypoint
+            .captures(line)
+            .or_else(|| patterns.summary.captures(line))
+        {
+            if let Some(millis) = to_millis(&caps[2], &caps[3]) {
+                self.add(&caps[1], millis);
+            }
+        } else if patterns.ready.is_match(line) {
+            self.finished = true;
+            return Some(self.profile(now.saturating_duration_since(self.started)));
+        }
+        None
+    }
+
+    fn profile(&self, total: Duration) -> StartupProfile {
+        let mut top_mods = self
+            .timings
+            .iter()
+            .map(|(mod_id, millis)| ModLoadTime {
+                mod_id: mod_id.clone(),
+                millis: *millis,
+            })
+            .collect::<Vec<_>>();
+        top_mods.sort_by(|a, b| b.millis.cmp(&a.millis).then(a.mod_id.cmp(&b.mod_id)));
+        top_mods.truncate(TOP_OFFENDERS);
+        StartupProfile {
+            recorded_at: chrono::Utc::now().to_rfc3339(),
+            total_secs: (total.as_secs_f64() * 10.0).round() / 10.0,
+            mods_timed: self.timings.len(),
+            top_mods,
+        }
+    }
+}
+
+fn read_history(instance_root: &Path) -> Result<StartupProfileHistory, String> {
+    let path = instance_root.join(STARTUP_PROFILE_FILE);
+    let raw = fs::read_to_string(&path)
+        .map_err(|err| format!("No se pudo leer {}: {err}", path.display()))?;
+    serde_json::from_str(&raw)
+        .map_err(|err| format!("Perfil de arranque inválido {}: {err}", path.display()))
+}
+
+/// Perfil del último arranque medido, si lo hay.
+pub(crate) fn latest_startup_profile(instance_root: &Path) -> Option<StartupProfile> {
+    read_history(instance_root).ok()?.latest
+}
+
+/// Guarda el perfil como el último (el anterior pasa a `previous`).
+fn store_profile(
+    instance_root: &Path,
+    profile: StartupProfile,
+) -> Result<StartupProfileHistory, String> {
+    let history = StartupProfileHistory {
+        previous: read_history(instance_root)
+            .ok()
+            .and_then(|history| history.latest),
+        latest: Some(profile),
+    };
+    let path = instance_root.join(STARTUP_PROFILE_FILE);
+    let raw = serde_json::to_string_pretty(&history)
+        .map_err(|err| format!("No se pudo serializar el perfil de arranque: {err}"))?;
+    fs::write(&path, raw).map_err(|err| format!("No se pudo guardar {}: {err}", path.display()))?;
+    Ok(history)
+}
+
+/// Persiste el perfil y emite `instance_startup_profile`.
+pub(crate) fn record_startup_profile(
+    app: &AppHandle,
+    instance_root: &str,
+    profile: StartupProfile,
+) {
+    log::info!(
+        "🔹 Arranque de {instance_root} en {:.1} s ({} mods medidos)",
+        profile.total_secs,
+        profile.mods_timed
+    );
+    match store_profile(Path::new(instance_root), profile) {
+        Ok(history) => emit_journaled(
+            app,
+            instance_root,
+            "instance_startup_profile",
+            serde_json::json!({
+                "instanceRoot": instance_root,
+                "latest": history.latest,
+                "previous": history.previous,
+            }),
+        ),
+        Err(err) => log::warn!("⚠ {err}"),
+    }
+}
+
+/// Último perfil de arranque de la instancia y el anterior.
+#[tauri::command]
+pub fn get_startup_profile(
+    app: AppHandle,
+    instance_root: String,
+) -> Result<StartupProfileHistory, String> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    let root = instance_root.path();
+    if !root.join(STARTUP_PROFILE_FILE).is_file() {
+        return Err("La instancia todavía no tiene perfil de arranque.".to_string());
+    }
+    read_history(root)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FORGE_1_20_LOG: &str = "\
+[12:00:01] [main/INFO] [cp.mo.mo.Launcher/MODLAUNCHER]: ModLauncher running: args [--launchTarget, forgeclient]
+[12:00:05] [modloading-worker-0/DEBUG] [ne.mi.fm.ja.FMLModContainer/LOADING]: Loading mod instance create of type com.simibubi.create.Create
+[12:00:05] [modloading-worker-1/DEBUG] [ne.mi.fm.ja.FMLModContainer/LOADING]: Loading mod instance jei of type mezz.jei.forge.JustEnoughItems
+[12:00:06] [modloading-worker-1/DEBUG] [ne.mi.fm.ja.FMLModContainer/LOADING]: Loaded mod instance jei of type mezz.jei.forge.JustEnoughItems
+[12:00:09] [modloading-worker-0/DEBUG] [ne.mi.fm.ja.FMLModContainer/LOADING]: Loaded mod instance create of type com.simibubi.create.Create
+[12:00:20] [Render thread/INFO] [mojang/Library]: OpenAL initialized on device OpenAL Soft
+[12:00:20] [Render thread/INFO] [minecraft/SoundEngine]: Sound engine started
+[12:00:30] [Render thread/INFO] [minecraft/SoundEngine]: Sound engine started";
+
+    const FABRIC_LOG: &str = "\
+[12:00:01] [main/INFO] (FabricLoader/GameProvider) Loading Minecraft 1.20.1 with Fabric Loader 0.15.11
+[12:00:02] [main/DEBUG] (FabricLoader/Entrypoint) Entrypoint 'main' for mod 'sodium' took 120ms
+[12:00:03] [main/DEBUG] (FabricLoader/Entrypoint) Entrypoint 'client' for mod 'sodium' took 80ms
+[12:00:04] [main/DEBUG] (FabricLoader/Entrypoint) Entrypoint 'main' for mod 'fabric-api' took 1.5s
+[12:00:05] [Render thread/INFO] (Minecraft) Backend library: LWJGL version 3.3.1 build 7
+[12:00:09] [Render thread/INFO] (Minecraft) Sound engine started";
+
+    fn run(log: &str) -> Vec<StartupProfile> {
+        let started = Instant::now();
+        let mut profiler = StartupProfiler::new(started);
+        log.lines()
+            .enumerate()
+            .filter_map(|(index, line)| {
+                profiler.observe(line, started + Duration::from_secs(index as u64))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn profiles_forge_construction_and_fabric_entrypoints_once() {
+        let forge = run(FORGE_1_20_LOG);
+        assert_eq!(forge.len(), 1);
+        assert_eq!(forge[0].total_secs, 6.0);
+        assert_eq!(forge[0].mods_timed, 2);
+        assert_eq!(
+            forge[0].top_mods,
+            vec![
+                ModLoadTime {
+                    mod_id: "create".to_string(),
+                    millis: 3000
+                },
+                ModLoadTime {
+                    mod_id: "jei".to_string(),
+                    millis: 1000
+                },
+            ]
+        );
+
+        let fabric = run(FABRIC_LOG);
+        assert_eq!(fabric.len(), 1);
+        assert_eq!(fabric[0].top_mods[0].mod_id, "fabric-api");
+        assert_eq!(fabric[0].top_mods[0].millis, 1500);
+        assert_eq!(fabric[0].top_mods[1].millis, 200);
+    }
+
+    #[test]
+    fn stored_profile_keeps_previous_for_comparison() {
+        let dir = std::env::temp_dir().join(format!("startup-profile-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).expect("dir");
+        let profile = |total_secs| StartupProfile {
+            recorded_at: "2026-01-01T00:00:00Z".to_string(),
+            total_secs,
+            mods_timed: 0,
+            top_mods: Vec::new(),
+        };
+
+        store_profile(&dir, profile(90.0)).expect("primero");
+        let history = store_profile(&dir, profile(45.0)).expect("segundo");
+        assert_eq!(history.latest, Some(profile(45.0)));
+        assert_eq!(history.previous, Some(profile(90.0)));
+        assert_eq!(read_history(&dir).expect("perfil"), history);
+
+        let _ = fs::remove_dir_all(dir);
+    }
+}
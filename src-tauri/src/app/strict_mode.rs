@@ -0,0 +1,144 @@
+// Modo estricto para autores de modpacks.
+
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::{
+    app::{
+        game_dir_guard::LaunchError,
+        instance_service::{prepare_launch, update_instance_metadata},
+        trusted_root::resolve_trusted_instance_root,
+    },
+    domain::models::instance::LaunchAuthSession,
+};
+
+/// Prefijo del error de un lanzamiento rechazado por el modo estricto.
+pub const STRICT_MODE_PREFIX: &str = "STRICT";
+
+pub const CLIENT_EXTRA_MISSING: &str = "client_extra_missing";
+pub const ASSET_OBJECTS_UNRESOLVED: &str = "asset_objects_unresolved";
+pub const NATIVES: &str = "natives";
+pub const REDIRECT_GAME_DIR_DATA: &str = "redirect_game_dir_data";
+pub const JAVA_ARGS_NORMALIZED: &str = "java_args_normalized";
+pub const LIBRARY_OVERRIDE_APPLIED: &str = "library_override_applied";
+pub const MERGED_JSON_SUMMARY: &str = "merged_json_summary";
+pub const DUPLICATE_MODS: &str = "duplicate_mods";
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct StrictViolation {
+    pub code: String,
+    pub message: String,
+}
+
+/// Avisos reunidos durante la preparación; con el modo estricto cada uno es un error.
+#[derive(Debug, Default)]
+pub struct StrictViolations(Vec<StrictViolation>);
+
+impl StrictViolations {
+    pub fn note(&mut self, code: &str, message: impl Into<String>) {
+        self.0.push(StrictViolation {
+            code: code.to_string(),
+            message: message.into(),
+        });
+    }
+
+    pub fn into_vec(self) -> Vec<StrictViolation> {
+        self.0
+    }
+}
+
+/// Error con todas las condiciones incumplidas, una por línea.
+pub fn strict_mode_error(violations: &[StrictViolation]) -> String {
+    let mut message = format!(
+        "{STRICT_MODE_PREFIX}: modo estricto activo; {} aviso(s) tratados como error:",
+        violations.len()
+    );
+    for violation in violations {
+        message.push_str(&format!("\n- [{}] {}", violation.code, violation.message));
+    }
+    message
+}
+
+/// `Err` con todas las violaciones si el modo estricto está activo y hay alguna.
+pub fn enforce_strict_mode(
+    strict_mode: bool,
+    violations: &[StrictViolation],
+) -> Result<(), String> {
+    if strict_mode && !violations.is_empty() {
+        Err(strict_mode_error(violations))
+    } else {
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StrictCheckReport {
+    pub strict_mode: bool,
+    /// `false` si el lanzamiento se rechazaría con este modo.
+    pub passed: bool,
+    pub violations: Vec<StrictViolation>,
+}
+
+#[tauri::command]
+pub fn set_instance_strict_mode(
+    app: AppHandle,
+    instance_root: String,
+    enabled: bool,
+) -> Result<bool, String> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    let metadata = update_instance_metadata(instance_root.as_str(), |metadata| {
+        metadata.strict_mode = enabled;
+        Ok::<(), String>(())
+    })?;
+    Ok(metadata.strict_mode)
+}
+
+/// Simulación: prepara el lanzamiento sin iniciar el juego y devuelve las violaciones.
+/// `strict_mode` sustituye al ajuste de la instancia (p. ej. para forzarlo en una CI).
+#[tauri::command]
+pub fn check_strict_launch(
+    app: AppHandle,
+    instance_root: String,
+    auth_session: LaunchAuthSession,
+    strict_mode: Option<bool>,
+) -> Result<StrictCheckReport, LaunchError> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    let prepared = prepare_launch(app, &instance_root, auth_session, Some(false))?;
+    let strict_mode = strict_mode.unwrap_or(prepared.strict_mode);
+    Ok(StrictCheckReport {
+        strict_mode,
+        passed: enforce_strict_mode(strict_mode, &prepared.strict_violations).is_ok(),
+        violations: prepared.strict_violations,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_lists_every_violation() {
+        let mut violations = StrictViolations::default();
+        violations.note(CLIENT_EXTRA_MISSING, "client-extra.jar no encontrado");
+        violations.note(JAVA_ARGS_NORMALIZED, "-Xmx duplicado eliminado");
+        let violations = violations.into_vec();
+
+        let err = enforce_strict_mode(true, &violations).unwrap_err();
+        assert!(err.starts_with(STRICT_MODE_PREFIX));
+        assert!(err.contains("2 aviso(s)"));
+        assert!(err.contains("[client_extra_missing] client-extra.jar no encontrado"));
+        assert!(err.contains("[java_args_normalized] -Xmx duplicado eliminado"));
+    }
+
+    #[test]
+    fn lenient_mode_or_clean_launch_passes() {
+        let violations = vec![StrictViolation {
+            code: NATIVES.to_string(),
+            message: "natives omitidos".to_string(),
+        }];
+        assert!(enforce_strict_mode(false, &violations).is_ok());
+        assert!(enforce_strict_mode(true, &[]).is_ok());
+    }
+}
@@ -0,0 +1,104 @@
+// Diagnóstico de conexiones HTTPS.
+
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::infrastructure::{
+    filesystem::config::load_launcher_config,
+    http::tls::{add_roots, detected_proxy, interception_error, load_configured_certificates},
+};
+
+const PROBE_TIMEOUT: Duration = Duration::from_secs(15);
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TlsProbe {
+    /// El handshake se completó; cualquier respuesta HTTP cuenta como éxito.
+    pub ok: bool,
+    pub http_status: Option<u16>,
+    /// `TLS_INTERCEPTION_SUSPECTED` cuando el fallo apunta a un proxy que re-firma.
+    pub code: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TlsTestReport {
+    pub url: String,
+    pub proxy: Option<String>,
+    pub custom_ca_count: usize,
+    /// Archivos de `custom_ca_certificates` que no se pudieron cargar.
+    pub custom_ca_errors: Vec<String>,
+    pub without_custom_ca: TlsProbe,
+    /// `None` si no hay certificados adicionales configurados.
+    pub with_custom_ca: Option<TlsProbe>,
+}
+
+fn probe(url: &str, certificates: &[reqwest::Certificate]) -> TlsProbe {
+    let client = match add_roots(reqwest::blocking::Client::builder(), certificates)
+        .user_agent("InterfaceLauncher/0.1")
+        .timeout(PROBE_TIMEOUT)
+        .build()
+    {
+        Ok(client) => client,
+        Err(err) => {
+            return TlsProbe {
+                ok: false,
+                http_status: None,
+                code: None,
+                error: Some(format!("No se pudo crear cliente HTTP: {err}")),
+            }
+        }
+    };
+    match client.head(url).send() {
+        Ok(response) => TlsProbe {
+            ok: true,
+            http_status: Some(response.status().as_u16()),
+            code: None,
+            error: None,
+        },
+        Err(err) => match interception_error(&err) {
+            Some(interception) => TlsProbe {
+                ok: false,
+                http_status: None,
+                code: Some(interception.code.to_string()),
+                error: Some(interception.message),
+            },
+            None => TlsProbe {
+                ok: false,
+                http_status: None,
+                code: None,
+                error: Some(format!("Falló la conexión con {url}: {err}")),
+            },
+        },
+    }
+}
+
+#[tauri::command]
+pub async fn test_tls(app: AppHandle, url: String) -> Result<TlsTestReport, String> {
+    let url = url.trim().to_string();
+    let parsed =
+        reqwest::Url::parse(&url).map_err(|err| format!("URL inválida: {url}. Error: {err}"))?;
+    if parsed.scheme() != "https" {
+        return Err(format!("La prueba TLS necesita una URL https: {url}"));
+    }
+    let config = load_launcher_config(&app)?;
+    tauri::async_runtime::spawn_blocking(move || {
+        let (certificates, custom_ca_errors) =
+            load_configured_certificates(&config.custom_ca_certificates);
+        let without_custom_ca = probe(&url, &[]);
+        let with_custom_ca = (!certificates.is_empty()).then(|| probe(&url, &certificates));
+        Ok(TlsTestReport {
+            proxy: detected_proxy(),
+            custom_ca_count: certificates.len(),
+            custom_ca_errors,
+            without_custom_ca,
+            with_custom_ca,
+            url,
+        })
+    })
+    .await
+    .map_err(|err| format!("Falló la tarea de prueba TLS: {err}"))?
+}
@@ -0,0 +1,632 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::{Mutex, OnceLock},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+use crate::{
+    app::auth_service::refresh_microsoft_auth,
+    domain::models::instance::LaunchAuthSession,
+    infrastructure::{
+        filesystem::{config::load_launcher_config, paths::resolve_launcher_root},
+        storage::secret_store::{delete_secret, load_secret, store_secret},
+    },
+};
+
+const ACCOUNTS_FILE: &str = "config/accounts.json";
+const CHECK_INTERVAL: Duration = Duration::from_secs(30 * 60);
+/// Se refresca de forma preventiva cuando quedan menos de 20 minutos de validez.
+const REFRESH_WINDOW_MS: u64 = 20 * 60 * 1000;
+/// Nunca más de un refresco cada 10 minutos por cuenta (se duplica tras cada fallo).
+const MIN_REFRESH_INTERVAL_MS: u64 = 10 * 60 * 1000;
+const MAX_CONSECUTIVE_FAILURES: u32 = 3;
+
+pub const HEALTH_OK: &str = "ok";
+pub const HEALTH_NEEDS_REAUTH: &str = "needs_reauth";
+
+/// Sesión de cuenta guardada en `config/accounts.json` para el mantenimiento en segundo plano.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoredAccountSession {
+    pub session: LaunchAuthSession,
+    #[serde(default)]
+    pub active: bool,
+    #[serde(default = "default_health")]
+    pub health: String,
+    #[serde(default)]
+    pub consecutive_failures: u32,
+    #[serde(default)]
+    pub last_refresh_attempt_at: Option<u64>,
+    #[serde(default)]
+    pub last_error: Option<String>,
+}
+
+fn default_health() -> String {
+    HEALTH_OK.to_string()
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountTokenHealth {
+    pub profile_id: String,
+    pub profile_name: String,
+    pub active: bool,
+    pub health: String,
+    pub expires_at: Option<u64>,
+    pub consecutive_failures: u32,
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RefreshDecision {
+    Skip,
+    Refresh,
+}
+
+fn now_unix_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+fn store_lock() -> &'static Mutex<()> {
+    static LOCK: Mutex<()> = Mutex::new(());
+    &LOCK
+}
+
+/// Refresh tokens que el llavero rechazó; se conservan en memoria hasta el siguiente guardado.
+fn unsaved_refresh_tokens() -> &'static Mutex<HashMap<String, String>> {
+    static TOKENS: OnceLock<Mutex<HashMap<String, String>>> = OnceLock::new();
+    TOKENS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn accounts_path(launcher_root: &Path) -> PathBuf {
+    launcher_root.join(ACCOUNTS_FILE)
+}
+
+/// Clave del refresh token de la cuenta en el llavero del sistema.
+fn refresh_token_key(profile_id: &str) -> String {
+    format!("microsoft-refresh-token:{profile_id}")
+}
+
+/// Cuentas tal como están en `accounts.json`, sin los refresh tokens del llavero.
+fn read_stored_accounts(launcher_root: &Path) -> Vec<StoredAccountSession> {
+    // El archivo se crea como `[]`; entradas con otro formato se ignoran.
+    fs::read_to_string(accounts_path(launcher_root))
+        .ok()
+        .and_then(|raw| serde_json::from_str::<Vec<serde_json::Value>>(&raw).ok())
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|value| serde_json::from_value(value).ok())
+        .collect()
+}
+
+fn read_accounts(launcher_root: &Path) -> Vec<StoredAccountSession> {
+    let mut accounts = read_stored_accounts(launcher_root);
+    for account in &mut accounts {
+        // Un token todavía en el archivo es de una versión anterior; se mueve al escribir.
+        if account.session.microsoft_refresh_token.is_some() {
+            continue;
+        }
+        let unsaved = unsaved_refresh_tokens()
+            .lock()
+            .ok()
+            .and_then(|tokens| tokens.get(&account.session.profile_id).cloned());
+        if unsaved.is_some() {
+            account.session.microsoft_refresh_token = unsaved;
+            continue;
+        }
+        match load_secret(&refresh_token_key(&account.session.profile_id)) {
+            Ok(token) => account.session.microsoft_refresh_token = token,
+            Err(err) => log::warn!("⚠ {err}"),
+        }
+    }
+    accounts
+}
+
+/// Guarda las cuentas; los refresh tokens van al llavero y nunca en claro al archivo. Si el
+/// llavero rechaza un token, se conserva en memoria (y el valor que ya tuviera el archivo) y
+/// se devuelve el error.
+fn write_accounts(launcher_root: &Path, accounts: &[StoredAccountSession]) -> Result<(), String> {
+    let path = accounts_path(launcher_root);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|err| format!("No se pudo crear {}: {err}", parent.display()))?;
+    }
+    let previous = read_stored_accounts(launcher_root);
+    let mut unsaved = unsaved_refresh_tokens()
+        .lock()
+        .map_err(|err| err.to_string())?;
+    let mut failures = Vec::new();
+    let mut stored = accounts.to_vec();
+    for account in &mut stored {
+        let profile_id = account.session.profile_id.clone();
+        let Some(token) = account.session.microsoft_refresh_token.take() else {
+            continue;
+        };
+        match store_secret(&refresh_token_key(&profile_id), &token) {
+            Ok(()) => {
+                unsaved.remove(&profile_id);
+            }
+            Err(err) => {
+                account.session.microsoft_refresh_token = previous
+                    .iter()
+                    .find(|old| old.session.profile_id == profile_id)
+                    .and_then(|old| old.session.microsoft_refresh_token.clone());
+                unsaved.insert(profile_id, token);
+                failures.push(format!("{}: {err}", account.session.profile_name));
+            }
+        }
+    }
+    drop(unsaved);
+    let raw = serde_json::to_string_pretty(&stored)
+        .map_err(|err| format!("No se pudieron serializar las cuentas: {err}"))?;
+    fs::write(&path, raw).map_err(|err| format!("No se pudo guardar {}: {err}", path.display()))?;
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "El refresh token no se guardó en el llavero y se conserva solo mientras el launcher siga abierto ({})",
+            failures.join("; ")
+        ))
+    }
+}
+
+/// Mueve al llavero los refresh tokens que versiones anteriores guardaban en claro.
+fn migrate_plaintext_refresh_tokens(launcher_root: &Path) {
+    let plaintext = read_stored_accounts(launcher_root)
+        .iter()
+        .any(|account| account.session.microsoft_refresh_token.is_some());
+    if plaintext {
+        if let Err(err) = update_accounts(launcher_root, |_| ()) {
+            log::warn!("⚠ No se pudieron migrar los refresh tokens al llavero: {err}");
+        }
+    }
+}
+
+fn update_accounts<T>(
+    launcher_root: &Path,
+    f: impl FnOnce(&mut Vec<StoredAccountSession>) -> T,
+) -> Result<T, String> {
+    let _guard = store_lock().lock().map_err(|err| err.to_string())?;
+    let mut accounts = read_accounts(launcher_root);
+    let result = f(&mut accounts);
+    write_accounts(launcher_root, &accounts)?;
+    Ok(result)
+}
+
+fn refresh_decision(account: &StoredAccountSession, now: u64) -> RefreshDecision {
+    if account.health == HEALTH_NEEDS_REAUTH || account.session.microsoft_refresh_token.is_none() {
+        return RefreshDecision::Skip;
+    }
+    let Some(expires_at) = account.session.minecraft_access_token_expires_at else {
+        return RefreshDecision::Skip;
+    };
+    if expires_at > now.saturating_add(REFRESH_WINDOW_MS) {
+        return RefreshDecision::Skip;
+    }
+    let min_interval =
+        MIN_REFRESH_INTERVAL_MS.saturating_mul(1 << account.consecutive_failures.min(4));
+    if account
+        .last_refresh_attempt_at
+        .is_some_and(|last| now.saturating_sub(last) < min_interval)
+    {
+        return RefreshDecision::Skip;
+    }
+    RefreshDecision::Refresh
+}
+
+/// Registra la sesión que el usuario acaba de iniciar o seleccionar. Cualquier interacción
+/// del usuario reinicia los fallos y quita el estado `needs_reauth`.
+pub fn upsert_account_session(
+    launcher_root: &Path,
+    session: LaunchAuthSession,
+    active: bool,
+) -> Result<(), String> {
+    update_accounts(launcher_root, |accounts| {
+        if active {
+            for account in accounts.iter_mut() {
+                account.active = false;
+            }
+        }
+        let stored = StoredAccountSession {
+            session: session.clone(),
+            active,
+            health: default_health(),
+            consecutive_failures: 0,
+            last_refresh_attempt_at: None,
+            last_error: None,
+        };
+        match accounts
+            .iter_mut()
+            .find(|account| account.session.profile_id == session.profile_id)
+        {
+            Some(existing) => *existing = stored,
+            None => accounts.push(stored),
+        }
+    })
+}
+
+/// Guarda la sesión que devuelve un lanzamiento; devuelve si quedó guardada.
+pub fn persist_launch_session(
+    launcher_root: &Path,
+    session: &LaunchAuthSession,
+    insert_missing: bool,
+) -> Result<bool, String> {
+    let mut session = session.clone();
+    session.persisted = false;
+    update_accounts(launcher_root, |accounts| {
+        match accounts
+            .iter_mut()
+            .find(|account| account.session.profile_id == session.profile_id)
+        {
+            Some(existing) => {
+                if session.microsoft_refresh_token.is_none() {
+                    session.microsoft_refresh_token =
+                        existing.session.microsoft_refresh_token.take();
+                }
+                existing.session = session;
+                existing.health = default_health();
+                existing.consecutive_failures = 0;
+                existing.last_error = None;
+                true
+            }
+            None if insert_missing => {
+                accounts.push(StoredAccountSession {
+                    session,
+                    active: false,
+                    health: default_health(),
+                    consecutive_failures: 0,
+                    last_refresh_attempt_at: None,
+                    last_error: None,
+                });
+                true
+            }
+            None => false,
+        }
+    })
+}
+
+/// Guarda el nombre nuevo de la cuenta cuando el jugador lo cambió en Mojang durante una
+/// sesión ya iniciada.
+pub fn record_profile_rename(launcher_root: &Path, profile_id: &str, profile_name: &str) {
+    let _ = update_accounts(launcher_root, |accounts| {
+        if let Some(existing) = accounts
+            .iter_mut()
+            .find(|account| account.session.profile_id == profile_id)
+        {
+            existing.session.profile_name = profile_name.to_string();
+        }
+    });
+}
+
+/// Devuelve la sesión guardada si es más reciente que la que envió el frontend, para que el
+/// lanzamiento use el token ya refrescado en segundo plano y se salte la cadena de refresh.
+pub fn freshest_session(launcher_root: &Path, session: &LaunchAuthSession) -> LaunchAuthSession {
+    let _guard = store_lock().lock();
+    read_accounts(launcher_root)
+        .into_iter()
+        .find(|account| account.session.profile_id == session.profile_id)
+        .filter(|account| {
+            account
+                .session
+                .minecraft_access_token_expires_at
+                .unwrap_or(0)
+                > session.minecraft_access_token_expires_at.unwrap_or(0)
+        })
+        .map(|account| LaunchAuthSession {
+            microsoft_refresh_token: account
+                .session
+                .microsoft_refresh_token
+                .or_else(|| session.microsoft_refresh_token.clone()),
+            ..account.session
+        })
+        .unwrap_or_else(|| session.clone())
+}
+
+async fn refresh_account(app: &AppHandle, launcher_root: &Path, profile_id: &str) {
+    let now = now_unix_millis();
+    let refresh_token = update_accounts(launcher_root, |accounts| {
+        let account = accounts
+            .iter_mut()
+            .find(|account| account.session.profile_id == profile_id)?;
+        account.last_refresh_attempt_at = Some(now);
+        account.session.microsoft_refresh_token.clone()
+    });
+    let Ok(Some(refresh_token)) = refresh_token else {
+        return;
+    };
+
+    let result = refresh_microsoft_auth(refresh_token).await;
+    let refreshed = update_accounts(launcher_root, |accounts| {
+        let account = accounts
+            .iter_mut()
+            .find(|account| account.session.profile_id == profile_id)?;
+        match &result {
+            Ok(auth) => {
+                account.session.minecraft_access_token = auth.minecraft_access_token.clone();
+                account.session.minecraft_access_token_expires_at =
+                    auth.minecraft_access_token_expires_at;
+                if auth.microsoft_refresh_token.is_some() {
+                    account.session.microsoft_refresh_token = auth.microsoft_refresh_token.clone();
+                }
+                account.session.profile_name = auth.profile.name.clone();
+                account.consecutive_failures = 0;
+                account.last_error = None;
+                Some(account.session.clone())
+            }
+            Err(err) => {
+                account.consecutive_failures += 1;
+                account.last_error = Some(err.clone());
+                if account.consecutive_failures >= MAX_CONSECUTIVE_FAILURES {
+                    account.health = HEALTH_NEEDS_REAUTH.to_string();
+                }
+                None
+            }
+        }
+    });
+
+    match (refreshed, result) {
+        (Ok(Some(session)), _) => {
+            log::info!(
+                "✔ Token de {} refrescado en segundo plano",
+                session.profile_name
+            );
+            let _ = app.emit("account_session_refreshed", &session);
+        }
+        (_, Err(err)) => log::warn!("⚠ Refresco en segundo plano de {profile_id} falló: {err}"),
+        (Err(err), Ok(_)) => log::warn!("⚠ {err}"),
+        _ => {}
+    }
+}
+
+/// Revisa las cuentas guardadas y refresca las que estén por expirar. Por defecto solo la
+/// cuenta activa; `refresh_all_accounts_in_background` incluye todas.
+pub async fn maintain_account_tokens(app: &AppHandle) {
+    let Ok(launcher_root) = resolve_launcher_root(app) else {
+        return;
+    };
+    let all_accounts = load_launcher_config(app)
+        .map(|config| config.refresh_all_accounts_in_background)
+        .unwrap_or(false);
+    let now = now_unix_millis();
+    let due: Vec<String> = {
+        let _guard = store_lock().lock();
+        read_accounts(&launcher_root)
+            .into_iter()
+            .filter(|account| all_accounts || account.active)
+            .filter(|account| refresh_decision(account, now) == RefreshDecision::Refresh)
+            .map(|account| account.session.profile_id)
+            .collect()
+    };
+    for profile_id in due {
+        refresh_account(app, &launcher_root, &profile_id).await;
+    }
+}
+
+/// Arranca la tarea que revisa la expiración de tokens cada 30 minutos mientras el
+/// launcher está abierto.
+pub fn start_token_maintenance(app: AppHandle) {
+    if let Ok(launcher_root) = resolve_launcher_root(&app) {
+        migrate_plaintext_refresh_tokens(&launcher_root);
+    }
+    tauri::async_runtime::spawn(async move {
+        loop {
+            maintain_account_tokens(&app).await;
+            tokio::time::sleep(CHECK_INTERVAL).await;
+        }
+    });
+}
+
+#[tauri::command]
+pub fn register_account_session(
+    app: AppHandle,
+    session: LaunchAuthSession,
+    active: bool,
+) -> Result<(), String> {
+    let launcher_root = resolve_launcher_root(&app)?;
+    upsert_account_session(&launcher_root, session, active)
+}
+
+#[tauri::command]
+pub fn remove_account_session(app: AppHandle, profile_id: String) -> Result<(), String> {
+    let launcher_root = resolve_launcher_root(&app)?;
+    update_accounts(&launcher_root, |accounts| {
+        accounts.retain(|account| account.session.profile_id != profile_id)
+    })?;
+    delete_secret(&refresh_token_key(&profile_id))
+}
+
+#[tauri::command]
+pub fn get_account_token_health(app: AppHandle) -> Result<Vec<AccountTokenHealth>, String> {
+    let launcher_root = resolve_launcher_root(&app)?;
+    Ok(read_accounts(&launcher_root)
+        .into_iter()
+        .map(|account| AccountTokenHealth {
+            profile_id: account.session.profile_id,
+            profile_name: account.session.profile_name,
+            active: account.active,
+            health: account.health,
+            expires_at: account.session.minecraft_access_token_expires_at,
+            consecutive_failures: account.consecutive_failures,
+            last_error: account.last_error,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::storage::secret_store::set_test_keyring_unavailable;
+
+    fn account(expires_in_ms: u64, now: u64) -> StoredAccountSession {
+        StoredAccountSession {
+            session: LaunchAuthSession {
+                profile_id: "abc".to_string(),
+                profile_name: "Steve".to_string(),
+                minecraft_access_token: "token".to_string(),
+                minecraft_access_token_expires_at: Some(now + expires_in_ms),
+                microsoft_refresh_token: Some("refresh".to_string()),
+                premium_verified: true,
+                persisted: false,
+            },
+            active: true,
+            health: default_health(),
+            consecutive_failures: 0,
+            last_refresh_attempt_at: None,
+            last_error: None,
+        }
+    }
+
+    #[test]
+    fn refreshes_only_inside_window_and_respects_rate_limit() {
+        let now = 10_000_000_000;
+        assert_eq!(
+            refresh_decision(&account(60 * 60 * 1000, now), now),
+            RefreshDecision::Skip
+        );
+
+        let mut due = account(5 * 60 * 1000, now);
+        assert_eq!(refresh_decision(&due, now), RefreshDecision::Refresh);
+
+        due.last_refresh_attempt_at = Some(now - 5 * 60 * 1000);
+        assert_eq!(refresh_decision(&due, now), RefreshDecision::Skip);
+        due.last_refresh_attempt_at = Some(now - 11 * 60 * 1000);
+        assert_eq!(refresh_decision(&due, now), RefreshDecision::Refresh);
+
+        due.consecutive_failures = 1;
+        assert_eq!(refresh_decision(&due, now), RefreshDecision::Skip);
+
+        due.health = HEALTH_NEEDS_REAUTH.to_string();
+        due.last_refresh_attempt_at = None;
+        assert_eq!(refresh_decision(&due, now), RefreshDecision::Skip);
+    }
+
+    #[test]
+    fn freshest_session_prefers_stored_token_and_user_login_clears_reauth() {
+        let root = std::env::temp_dir().join(format!("token-maintenance-{}", now_unix_millis()));
+        let now = now_unix_millis();
+        let stale = account(1000, now).session;
+        let mut fresh = account(60 * 60 * 1000, now);
+        fresh.session.minecraft_access_token = "fresh".to_string();
+        fresh.health = HEALTH_NEEDS_REAUTH.to_string();
+        write_accounts(&root, &[fresh]).expect("write");
+
+        assert_eq!(
+            freshest_session(&root, &stale).minecraft_access_token,
+            "fresh"
+        );
+
+        upsert_account_session(&root, stale.clone(), true).expect("upsert");
+        let stored = read_accounts(&root);
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].health, HEALTH_OK);
+        assert_eq!(
+            freshest_session(&root, &stale).minecraft_access_token,
+            "token"
+        );
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn launch_session_is_stored_even_if_result_is_dropped() {
+        let root = std::env::temp_dir().join(format!("token-persist-{}", now_unix_millis()));
+        let now = now_unix_millis();
+        let old = account(1000, now).session;
+        upsert_account_session(&root, old.clone(), true).expect("upsert");
+
+        let mut refreshed = old.clone();
+        refreshed.minecraft_access_token = "renovado".to_string();
+        refreshed.microsoft_refresh_token = Some("rotado".to_string());
+        let _ = persist_launch_session(&root, &refreshed, false);
+
+        let stored = read_accounts(&root);
+        assert_eq!(stored[0].session.minecraft_access_token, "renovado");
+        assert_eq!(
+            stored[0].session.microsoft_refresh_token.as_deref(),
+            Some("rotado")
+        );
+        assert!(stored[0].active);
+
+        let mut unknown = old;
+        unknown.profile_id = "otro".to_string();
+        assert!(!persist_launch_session(&root, &unknown, false).expect("persist"));
+        assert_eq!(read_accounts(&root).len(), 1);
+        assert!(persist_launch_session(&root, &unknown, true).expect("persist"));
+        assert_eq!(read_accounts(&root).len(), 2);
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn refresh_tokens_live_in_the_secret_store_and_legacy_files_are_migrated() {
+        let root = std::env::temp_dir().join(format!("token-secret-{}", now_unix_millis()));
+        let now = now_unix_millis();
+        let mut session = account(1000, now).session;
+        session.microsoft_refresh_token = Some("refresh-secreto".to_string());
+        upsert_account_session(&root, session, true).expect("upsert");
+
+        let raw = fs::read_to_string(accounts_path(&root)).expect("accounts");
+        assert!(!raw.contains("refresh-secreto"));
+        assert_eq!(
+            read_accounts(&root)[0]
+                .session
+                .microsoft_refresh_token
+                .as_deref(),
+            Some("refresh-secreto")
+        );
+
+        let mut legacy = account(1000, now);
+        legacy.session.profile_id = "legado".to_string();
+        legacy.session.microsoft_refresh_token = Some("token-en-claro".to_string());
+        fs::write(
+            accounts_path(&root),
+            serde_json::to_string(&[legacy]).expect("json"),
+        )
+        .expect("legacy");
+        migrate_plaintext_refresh_tokens(&root);
+
+        let raw = fs::read_to_string(accounts_path(&root)).expect("accounts");
+        assert!(!raw.contains("token-en-claro"));
+        assert_eq!(
+            load_secret(&refresh_token_key("legado")).expect("llavero"),
+            Some("token-en-claro".to_string())
+        );
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn refresh_token_is_kept_when_the_secret_store_rejects_it() {
+        let root = std::env::temp_dir().join(format!("token-unsaved-{}", now_unix_millis()));
+        let now = now_unix_millis();
+        let mut session = account(1000, now).session;
+        session.profile_id = "sin-llavero".to_string();
+        session.microsoft_refresh_token = Some("refresh-pendiente".to_string());
+
+        set_test_keyring_unavailable(true);
+        let err = upsert_account_session(&root, session, true).expect_err("llavero caído");
+        assert!(err.contains("llavero"));
+        let raw = fs::read_to_string(accounts_path(&root)).expect("accounts");
+        assert!(!raw.contains("refresh-pendiente"));
+        assert_eq!(
+            read_accounts(&root)[0]
+                .session
+                .microsoft_refresh_token
+                .as_deref(),
+            Some("refresh-pendiente")
+        );
+
+        set_test_keyring_unavailable(false);
+        update_accounts(&root, |_| ()).expect("reintento");
+        assert_eq!(
+            load_secret(&refresh_token_key("sin-llavero")).expect("llavero"),
+            Some("refresh-pendiente".to_string())
+        );
+        let _ = fs::remove_dir_all(root);
+    }
+}
@@ -0,0 +1,335 @@
+// Validación de las rutas que el frontend envía a los comandos.
+
+use std::{
+    fs,
+    path::{Component, Path, PathBuf},
+};
+
+use tauri::AppHandle;
+
+use crate::{
+    app::{
+        instance_service::import_runtime_cache_root, redirect_launch::redirect_cache_root,
+        settings_service::resolve_instances_root,
+    },
+    infrastructure::filesystem::paths::resolve_launcher_root,
+};
+
+/// Ruta de instancia comprobada contra los directorios permitidos.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidatedInstanceRoot {
+    raw: PathBuf,
+    resolved: PathBuf,
+    canonical: PathBuf,
+}
+
+impl ValidatedInstanceRoot {
+    /// Ruta resuelta sin el prefijo `\\?\` de Windows, que rompe herramientas externas.
+    pub fn path(&self) -> &Path {
+        &self.resolved
+    }
+
+    pub fn canonical(&self) -> &Path {
+        &self.canonical
+    }
+
+    /// Vuelve a resolver la ruta justo antes de un borrado: si ya no apunta al mismo sitio
+    /// que se validó (se cambió por un enlace), se aborta.
+    pub fn revalidate(&self) -> Result<(), String> {
+        match fs::canonicalize(&self.raw) {
+            Ok(current) if current == self.canonical => Ok(()),
+            Ok(current) => Err(format!(
+                "La ruta de instancia {} cambió tras validarse y ahora apunta a {}.",
+                self.raw.display(),
+                current.display()
+            )),
+            Err(err) => Err(format!(
+                "No se pudo volver a resolver la instancia {}: {err}",
+                self.raw.display()
+            )),
+        }
+    }
+
+    /// Ruta tal como llegó, para claves de registro, eventos y logs.
+    pub fn as_str(&self) -> &str {
+        self.raw.to_str().unwrap_or_default()
+    }
+}
+
+impl serde::Serialize for ValidatedInstanceRoot {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl std::fmt::Display for ValidatedInstanceRoot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Valida `raw` contra la carpeta de instancias configurada y la predeterminada del launcher.
+pub fn resolve_trusted_instance_root(
+    app: &AppHandle,
+    raw: &str,
+) -> Result<ValidatedInstanceRoot, String> {
+    let mut allowed = vec![resolve_instances_root(app)?];
+    if let Ok(launcher_root) = resolve_launcher_root(app) {
+        allowed.push(launcher_root.join("instances"));
+    }
+    validate_instance_root(raw, &allowed)
+}
+
+/// Como `resolve_trusted_instance_root`, admitiendo también las copias de instancias del
+/// launcher (redirect-cache, atajos, copias de seguridad).
+pub fn resolve_trusted_launcher_data_root(
+    app: &AppHandle,
+    raw: &str,
+) -> Result<ValidatedInstanceRoot, String> {
+    let mut allowed = vec![resolve_instances_root(app)?];
+    if let Ok(launcher_root) = resolve_launcher_root(app) {
+        allowed.push(launcher_root.join("instances"));
+        allowed.push(launcher_root.join("backups"));
+    }
+    allowed.extend(redirect_cache_root(app));
+    allowed.extend(import_runtime_cache_root(app));
+    validate_instance_root(raw, &allowed)
+}
+
+/// La ruta debe quedar estrictamente dentro de algún `allowed_roots` tras resolver enlaces.
+pub fn validate_instance_root(
+    raw: &str,
+    allowed_roots: &[PathBuf],
+) -> Result<ValidatedInstanceRoot, String> {
+    let candidate = check_raw_path(raw)?;
+    let canonical = fs::canonicalize(&candidate).map_err(|err| {
+        format!(
+            "No se pudo resolver la ruta de la instancia {}: {err}",
+            candidate.display()
+        )
+    })?;
+
+    let mut lexically_inside = false;
+    for root in allowed_roots {
+        if candidate.starts_with(root) && candidate != *root {
+            lexically_inside = true;
+        }
+        let Ok(canonical_root) = fs::canonicalize(root) else {
+            continue;
+        };
+        if canonical.starts_with(&canonical_root) && canonical != canonical_root {
+            return Ok(ValidatedInstanceRoot {
+                raw: candidate,
+                resolved: strip_verbatim_prefix(&canonical),
+                canonical,
+            });
+        }
+    }
+
+    if lexically_inside {
+        return Err(format!(
+            "La ruta de instancia {} sale del directorio de instancias a través de un enlace simbólico o junction ({}).",
+            candidate.display(),
+            canonical.display()
+        ));
+    }
+    Err(format!(
+        "Ruta de instancia fuera del directorio permitido: {}",
+        canonical.display()
+    ))
+}
+
+/// Valida una carpeta externa elegida por el usuario (origen de un atajo o de una importación).
+pub fn resolve_external_source_dir(raw: &str) -> Result<PathBuf, String> {
+    let candidate = check_raw_path(raw)?;
+    let canonical = fs::canonicalize(&candidate).map_err(|err| {
+        format!(
+            "No se pudo resolver la carpeta externa {}: {err}",
+            candidate.display()
+        )
+    })?;
+    if !canonical.is_dir() {
+        return Err(format!(
+            "La ruta externa no es una carpeta válida: {}",
+            candidate.display()
+        ));
+    }
+    if canonical.parent().is_none() {
+        return Err(format!(
+            "No se permite usar la raíz de un disco como carpeta externa: {}",
+            candidate.display()
+        ));
+    }
+    if let Some(protected) = protected_system_dirs()
+        .into_iter()
+        .find(|dir| canonical.starts_with(dir))
+    {
+        return Err(format!(
+            "La carpeta externa {} está dentro de una carpeta del sistema ({}).",
+            candidate.display(),
+            protected.display()
+        ));
+    }
+    if home_dir().is_some_and(|home| canonical == home) {
+        return Err(format!(
+            "No se permite usar el directorio personal completo como carpeta externa: {}",
+            candidate.display()
+        ));
+    }
+    Ok(candidate)
+}
+
+/// `\\?\C:\x` → `C:\x`; las rutas UNC verbatim se dejan como están.
+fn strip_verbatim_prefix(canonical: &Path) -> PathBuf {
+    let text = canonical.to_string_lossy();
+    match text.strip_prefix(r"\\?\") {
+        Some(rest) if rest.as_bytes().get(1) == Some(&b':') => PathBuf::from(rest),
+        _ => canonical.to_path_buf(),
+    }
+}
+
+/// Comprobaciones léxicas comunes: vacía, prefijos de dispositivo, relativa o con `..`.
+fn check_raw_path(raw: &str) -> Result<PathBuf, String> {
+    let trimmed = raw.trim();
+    if trimmed.is_empty() {
+        return Err("La ruta recibida está vacía.".to_string());
+    }
+    let normalized = trimmed.replace('/', "\\");
+    if normalized.starts_with("\\\\?\\") || normalized.starts_with("\\\\.\\") {
+        return Err(format!(
+            "No se aceptan rutas con prefijo de dispositivo: {trimmed}"
+        ));
+    }
+    let candidate = PathBuf::from(trimmed);
+    if !candidate.is_absolute() {
+        return Err(format!("La ruta debe ser absoluta: {trimmed}"));
+    }
+    if candidate
+        .components()
+        .any(|component| matches!(component, Component::ParentDir))
+    {
+        return Err(format!("La ruta no puede contener '..': {trimmed}"));
+    }
+    Ok(candidate)
+}
+
+fn protected_system_dirs() -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = if cfg!(windows) {
+        ["SystemRoot", "windir"]
+            .iter()
+            .filter_map(|key| std::env::var_os(key).map(PathBuf::from))
+            .collect()
+    } else {
+        [
+            "/etc", "/usr", "/bin", "/sbin", "/boot", "/proc", "/sys", "/dev", "/System",
+        ]
+        .iter()
+        .map(PathBuf::from)
+        .collect()
+    };
+    dirs.retain(|dir| dir.exists());
+    dirs.into_iter()
+        .filter_map(|dir| fs::canonicalize(dir).ok())
+        .collect()
+}
+
+fn home_dir() -> Option<PathBuf> {
+    let key = if cfg!(windows) { "USERPROFILE" } else { "HOME" };
+    let home = std::env::var_os(key).map(PathBuf::from)?;
+    fs::canonicalize(home).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sandbox(name: &str) -> (PathBuf, PathBuf, PathBuf) {
+        let base = std::env::temp_dir().join(format!("trusted-root-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&base);
+        let instances = base.join("instances");
+        let outside = base.join("documentos");
+        fs::create_dir_all(instances.join("Survival")).expect("instancia");
+        fs::create_dir_all(&outside).expect("carpeta externa");
+        (base, instances, outside)
+    }
+
+    #[test]
+    fn accepts_instances_and_rejects_lexical_tricks() {
+        let (base, instances, outside) = sandbox("lexical");
+        let roots = vec![instances.clone()];
+
+        let valid =
+            validate_instance_root(&instances.join("Survival").display().to_string(), &roots)
+                .expect("instancia válida");
+        assert_eq!(
+            valid.path(),
+            fs::canonicalize(instances.join("Survival")).expect("canónica")
+        );
+        assert!(valid.revalidate().is_ok());
+
+        for raw in [
+            String::new(),
+            r"\\?\C:\Windows".to_string(),
+            r"\\.\PhysicalDrive0".to_string(),
+            "instances/Survival".to_string(),
+            instances
+                .join("..")
+                .join("documentos")
+                .display()
+                .to_string(),
+            instances.display().to_string(),
+            outside.display().to_string(),
+        ] {
+            assert!(validate_instance_root(&raw, &roots).is_err(), "{raw}");
+        }
+
+        let _ = fs::remove_dir_all(base);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn rejects_symlink_escaping_instances_dir() {
+        let (base, instances, outside) = sandbox("symlink");
+        let link = instances.join("Trampa");
+        std::os::unix::fs::symlink(&outside, &link).expect("symlink");
+
+        let error = validate_instance_root(
+            &link.display().to_string(),
+            std::slice::from_ref(&instances),
+        )
+        .expect_err("el enlace sale del directorio");
+        assert!(error.contains("enlace simbólico"), "{error}");
+        assert!(resolve_external_source_dir(&outside.display().to_string()).is_ok());
+        assert!(resolve_external_source_dir("/").is_err());
+
+        let swapped = instances.join("Cambiante");
+        fs::create_dir_all(&swapped).expect("instancia");
+        let validated = validate_instance_root(&swapped.display().to_string(), &[instances])
+            .expect("instancia válida");
+        fs::remove_dir(&swapped).expect("quitar instancia");
+        std::os::unix::fs::symlink(&outside, &swapped).expect("symlink");
+        assert!(validated.revalidate().is_err());
+
+        let _ = fs::remove_dir_all(base);
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn rejects_junction_escaping_instances_dir() {
+        let (base, instances, outside) = sandbox("junction");
+        let link = instances.join("Trampa");
+        let status = std::process::Command::new("cmd")
+            .args(["/C", "mklink", "/J"])
+            .arg(&link)
+            .arg(&outside)
+            .status()
+            .expect("mklink");
+        assert!(status.success());
+
+        let error = validate_instance_root(&link.display().to_string(), &[instances.clone()])
+            .expect_err("la junction sale del directorio");
+        assert!(error.contains("junction"), "{error}");
+
+        let _ = fs::remove_dir_all(base);
+    }
+}
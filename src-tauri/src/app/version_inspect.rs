@@ -0,0 +1,132 @@
+// Inspección del version.json efectivo.
+
+use std::path::Path;
+
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::app::{
+    instance_service::{
+        inspect_merged_version_json, merged_json_summary, read_instance_metadata,
+        resolve_effective_version_id, DeduplicatedLibrary, MergedJsonSummary,
+    },
+    trusted_root::resolve_trusted_instance_root,
+};
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EffectiveVersionJson {
+    pub version_id: String,
+    pub pretty_json: String,
+    pub summary: MergedJsonSummary,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionJsonDiff {
+    pub version_id: String,
+    /// `inheritsFrom` del JSON hijo en disco; `None` si no hereda de otra versión.
+    pub parent_id: Option<String>,
+    pub child_keys: Vec<String>,
+    pub parent_keys: Vec<String>,
+    /// Claves combinadas de ambos lados (`arguments`, `libraries`).
+    pub merged_keys: Vec<String>,
+    pub deduplicated_libraries: Vec<DeduplicatedLibrary>,
+}
+
+struct Inspection {
+    version_id: String,
+    merged: serde_json::Value,
+    diff: VersionJsonDiff,
+}
+
+fn inspect_instance_version(instance_root: &Path) -> Result<Inspection, String> {
+    let metadata = read_instance_metadata(instance_root.display().to_string())?;
+    if metadata.state.eq_ignore_ascii_case("redirect") {
+        return Err(
+            "Las instancias redirigidas usan el version.json del launcher de origen.".to_string(),
+        );
+    }
+    let mc_root = instance_root.join("minecraft");
+    let version_id = resolve_effective_version_id(&mc_root, &metadata)?;
+    let (merged, provenance) = inspect_merged_version_json(&mc_root, &version_id)?;
+
+    let mut diff = VersionJsonDiff {
+        version_id: version_id.clone(),
+        parent_id: None,
+        child_keys: Vec::new(),
+        parent_keys: Vec::new(),
+        merged_keys: Vec::new(),
+        deduplicated_libraries: Vec::new(),
+    };
+    match provenance {
+        Some(provenance) => {
+            for (key, origin) in provenance.key_origins {
+                match origin.as_str() {
+                    "child" => diff.child_keys.push(key),
+                    "parent" => diff.parent_keys.push(key),
+                    _ => diff.merged_keys.push(key),
+                }
+            }
+            diff.deduplicated_libraries = provenance.deduplicated_libraries;
+            let child_path = mc_root
+                .join("versions")
+                .join(&version_id)
+                .join(format!("{version_id}.json"));
+            diff.parent_id = std::fs::read_to_string(&child_path)
+                .ok()
+                .and_then(|raw| serde_json::from_str::<serde_json::Value>(&raw).ok())
+                .and_then(|child| {
+                    child
+                        .get("inheritsFrom")
+                        .and_then(|v| v.as_str())
+                        .map(ToString::to_string)
+                });
+        }
+        None => {
+            diff.child_keys = merged
+                .as_object()
+                .map(|object| object.keys().cloned().collect())
+                .unwrap_or_default();
+        }
+    }
+
+    Ok(Inspection {
+        version_id,
+        merged,
+        diff,
+    })
+}
+
+#[tauri::command]
+pub async fn get_effective_version_json(
+    app: AppHandle,
+    instance_root: String,
+) -> Result<EffectiveVersionJson, String> {
+    let root = resolve_trusted_instance_root(&app, &instance_root)?;
+    tauri::async_runtime::spawn_blocking(move || {
+        let inspection = inspect_instance_version(root.path())?;
+        let pretty_json = serde_json::to_string_pretty(&inspection.merged)
+            .map_err(|err| format!("No se pudo serializar el version.json efectivo: {err}"))?;
+        Ok(EffectiveVersionJson {
+            summary: merged_json_summary(&inspection.merged),
+            version_id: inspection.version_id,
+            pretty_json,
+        })
+    })
+    .await
+    .map_err(|err| format!("Falló la tarea de inspección del version.json: {err}"))?
+}
+
+#[tauri::command]
+pub async fn diff_version_json(
+    app: AppHandle,
+    instance_root: String,
+) -> Result<VersionJsonDiff, String> {
+    let root = resolve_trusted_instance_root(&app, &instance_root)?;
+    tauri::async_runtime::spawn_blocking(move || {
+        inspect_instance_version(root.path()).map(|inspection| inspection.diff)
+    })
+    .await
+    .map_err(|err| format!("Falló la tarea de inspección del version.json: {err}"))?
+}
@@ -0,0 +1,329 @@
+use std::{
+    collections::HashMap,
+    sync::{Mutex, OnceLock},
+    thread,
+    time::{Duration, Instant},
+};
+
+use reqwest::blocking::Client;
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::{
+    app::instance_service::read_instance_metadata,
+    infrastructure::{
+        filesystem::config::{load_launcher_config, save_launcher_config, WebhookConfig},
+        http::tls::with_custom_roots,
+    },
+};
+
+pub const WEBHOOK_EVENTS: [&str; 3] = ["start", "exit", "crash"];
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(5);
+/// Eventos iguales de la misma instancia dentro de esta ventana se descartan (crash loops).
+const WEBHOOK_DEDUP_WINDOW: Duration = Duration::from_secs(10);
+
+/// Cuerpo enviado a los webhooks. No lleva tokens ni rutas locales; `content` permite que
+/// un webhook de Discord lo muestre sin adaptador.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookPayload {
+    pub content: String,
+    pub launcher_version: String,
+    pub instance_name: String,
+    pub instance_uuid: String,
+    pub event: String,
+    pub exit_code: Option<i32>,
+    pub timestamp: String,
+    pub player_name: Option<String>,
+}
+
+static LAST_SENT: OnceLock<Mutex<HashMap<(String, String), Instant>>> = OnceLock::new();
+
+fn last_sent() -> &'static Mutex<HashMap<(String, String), Instant>> {
+    LAST_SENT.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Decide si un evento se envía o se descarta por repetirse dentro de la ventana.
+fn should_send(
+    history: &mut HashMap<(String, String), Instant>,
+    instance_root: &str,
+    event: &str,
+    now: Instant,
+) -> bool {
+    let key = (instance_root.to_string(), event.to_string());
+    if let Some(previous) = history.get(&key) {
+        if now.duration_since(*previous) < WEBHOOK_DEDUP_WINDOW {
+            return false;
+        }
+    }
+    history.insert(key, now);
+    true
+}
+
+fn webhook_matches(webhook: &WebhookConfig, event: &str, instance_uuid: &str) -> bool {
+    !webhook.url.trim().is_empty()
+        && webhook.events.iter().any(|wanted| wanted == event)
+        && !webhook
+            .instance_filter
+            .as_deref()
+            .map(str::trim)
+            .filter(|filter| !filter.is_empty())
+            .is_some_and(|filter| !filter.eq_ignore_ascii_case(instance_uuid))
+}
+
+/// Solo esquema y host: la ruta de un webhook de Discord contiene su token.
+fn redacted_url(url: &str) -> String {
+    reqwest::Url::parse(url)
+        .ok()
+        .and_then(|parsed| {
+            parsed
+                .host_str()
+                .map(|host| format!("{}://{host}/…", parsed.scheme()))
+        })
+        .unwrap_or_else(|| "<url inválida>".to_string())
+}
+
+fn event_summary(
+    event: &str,
+    instance_name: &str,
+    player_name: Option<&str>,
+    exit_code: Option<i32>,
+) -> String {
+    let player = player_name.unwrap_or("Alguien");
+    match event {
+        "start" => format!("▶ {player} inició {instance_name}"),
+        "crash" => format!(
+            "💥 {instance_name} se cerró con error (código {})",
+            exit_code
+                .map(|code| code.to_string())
+                .unwrap_or_else(|| "desconocido".to_string())
+        ),
+        _ => format!("⏹ {player} cerró {instance_name}"),
+    }
+}
+
+fn build_webhook_client() -> Result<Client, String> {
+    with_custom_roots(Client::builder())
+        .user_agent("InterfaceLauncher/0.1")
+        .timeout(WEBHOOK_TIMEOUT)
+        .build()
+        .map_err(|err| format!("No se pudo crear cliente HTTP para webhooks: {err}"))
+}
+
+/// POST con un único reintento.
+fn post_webhook(client: &Client, url: &str, payload: &WebhookPayload) -> Result<u16, String> {
+    let mut last_error = String::new();
+    for attempt in 1..=2 {
+        match client
+            .post(url)
+            .json(payload)
+            .send()
+            .and_then(|response| response.error_for_status())
+        {
+            Ok(response) => return Ok(response.status().as_u16()),
+            Err(err) => {
+                last_error = err.without_url().to_string();
+                if attempt == 1 {
+                    thread::sleep(Duration::from_millis(500));
+                }
+            }
+        }
+    }
+    Err(last_error)
+}
+
+/// Notifica un evento de ciclo de vida (`start`, `exit` o `crash`) a los webhooks
+/// configurados. Se envía en segundo plano y los fallos solo se registran.
+pub fn notify_instance_lifecycle(
+    app: &AppHandle,
+    instance_root: &str,
+    event: &str,
+    exit_code: Option<i32>,
+    player_name: Option<String>,
+) {
+    let webhooks = load_launcher_config(app)
+        .map(|config| config.webhooks)
+        .unwrap_or_default();
+    if webhooks.is_empty() {
+        return;
+    }
+    let Ok(metadata) = read_instance_metadata(instance_root.to_string()) else {
+        return;
+    };
+    let targets = webhooks
+        .into_iter()
+        .filter(|webhook| webhook_matches(webhook, event, &metadata.internal_uuid))
+        .map(|webhook| webhook.url)
+        .collect::<Vec<_>>();
+    if targets.is_empty() {
+        return;
+    }
+    let allowed = last_sent()
+        .lock()
+        .map(|mut history| should_send(&mut history, instance_root, event, Instant::now()))
+        .unwrap_or(true);
+    if !allowed {
+        log::info!("🔹 Webhook '{event}' de {instance_root} omitido: repetido en menos de 10 s");
+        return;
+    }
+
+    let payload = WebhookPayload {
+        content: event_summary(event, &metadata.name, player_name.as_deref(), exit_code),
+        launcher_version: app.package_info().version.to_string(),
+        instance_name: metadata.name,
+        instance_uuid: metadata.internal_uuid,
+        event: event.to_string(),
+        exit_code,
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        player_name,
+    };
+    thread::spawn(move || {
+        let client = match build_webhook_client() {
+            Ok(client) => client,
+            Err(err) => {
+                log::warn!("⚠ {err}");
+                return;
+            }
+        };
+        for url in targets {
+            if let Err(err) = post_webhook(&client, &url, &payload) {
+                log::warn!(
+                    "⚠ Webhook '{}' a {} falló: {err}",
+                    payload.event,
+                    redacted_url(&url)
+                );
+            }
+        }
+    });
+}
+
+fn normalize_webhooks(webhooks: Vec<WebhookConfig>) -> Result<Vec<WebhookConfig>, String> {
+    webhooks
+        .into_iter()
+        .filter(|webhook| !webhook.url.trim().is_empty())
+        .map(|mut webhook| {
+            webhook.url = webhook.url.trim().to_string();
+            let parsed = reqwest::Url::parse(&webhook.url)
+                .map_err(|err| format!("URL de webhook inválida: {err}"))?;
+            if !matches!(parsed.scheme(), "http" | "https") {
+                return Err(format!(
+                    "El webhook debe usar http o https: {}",
+                    redacted_url(&webhook.url)
+                ));
+            }
+            webhook.events = webhook
+                .events
+                .iter()
+                .map(|event| event.trim().to_ascii_lowercase())
+                .collect();
+            if let Some(unknown) = webhook
+                .events
+                .iter()
+                .find(|event| !WEBHOOK_EVENTS.contains(&event.as_str()))
+            {
+                return Err(format!(
+                    "Evento de webhook desconocido: '{unknown}'. Permitidos: {}",
+                    WEBHOOK_EVENTS.join(", ")
+                ));
+            }
+            webhook.events.sort();
+            webhook.events.dedup();
+            Ok(webhook)
+        })
+        .collect()
+}
+
+#[tauri::command]
+pub fn get_webhooks(app: AppHandle) -> Result<Vec<WebhookConfig>, String> {
+    Ok(load_launcher_config(&app).unwrap_or_default().webhooks)
+}
+
+#[tauri::command]
+pub fn set_webhooks(
+    app: AppHandle,
+    webhooks: Vec<WebhookConfig>,
+) -> Result<Vec<WebhookConfig>, String> {
+    let webhooks = normalize_webhooks(webhooks)?;
+    let mut config = load_launcher_config(&app).unwrap_or_default();
+    config.webhooks = webhooks.clone();
+    save_launcher_config(&app, &config)?;
+    Ok(webhooks)
+}
+
+/// Envía un payload de ejemplo para comprobar la configuración; devuelve el código HTTP.
+#[tauri::command]
+pub async fn test_webhook(app: AppHandle, url: String) -> Result<u16, String> {
+    let payload = WebhookPayload {
+        content: "✔ Webhook de Interface Launcher configurado correctamente".to_string(),
+        launcher_version: app.package_info().version.to_string(),
+        instance_name: "Instancia de prueba".to_string(),
+        instance_uuid: "00000000-0000-0000-0000-000000000000".to_string(),
+        event: "test".to_string(),
+        exit_code: None,
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        player_name: Some("Steve".to_string()),
+    };
+    tauri::async_runtime::spawn_blocking(move || {
+        let client = build_webhook_client()?;
+        post_webhook(&client, url.trim(), &payload)
+            .map_err(|err| format!("El webhook {} no respondió: {err}", redacted_url(&url)))
+    })
+    .await
+    .map_err(|err| format!("Falló la tarea de prueba del webhook: {err}"))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identical_events_are_rate_limited_per_instance() {
+        let mut history = HashMap::new();
+        let start = Instant::now();
+        assert!(should_send(&mut history, "/a", "crash", start));
+        assert!(!should_send(
+            &mut history,
+            "/a",
+            "crash",
+            start + Duration::from_secs(3)
+        ));
+        assert!(should_send(
+            &mut history,
+            "/b",
+            "crash",
+            start + Duration::from_secs(3)
+        ));
+        assert!(should_send(
+            &mut history,
+            "/a",
+            "exit",
+            start + Duration::from_secs(3)
+        ));
+        assert!(should_send(
+            &mut history,
+            "/a",
+            "crash",
+            start + Duration::from_secs(11)
+        ));
+    }
+
+    #[test]
+    fn filters_by_event_and_instance_and_redacts_url() {
+        let webhook = WebhookConfig {
+            url: "https://discord.com/api/webhooks/123/secreto".to_string(),
+            events: vec!["crash".to_string()],
+            instance_filter: Some("ABC".to_string()),
+        };
+        assert!(webhook_matches(&webhook, "crash", "abc"));
+        assert!(!webhook_matches(&webhook, "start", "abc"));
+        assert!(!webhook_matches(&webhook, "crash", "otra"));
+        assert_eq!(redacted_url(&webhook.url), "https://discord.com/…");
+
+        assert!(normalize_webhooks(vec![WebhookConfig {
+            url: "https://example.com/hook".to_string(),
+            events: vec!["reboot".to_string()],
+            instance_filter: None,
+        }])
+        .is_err());
+    }
+}
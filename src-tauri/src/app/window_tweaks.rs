@@ -0,0 +1,252 @@
+// Ajustes de ventana por instancia.
+
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+use tauri::AppHandle;
+
+use crate::{
+    app::{
+        event_journal::emit_journaled, instance_service::update_instance_metadata,
+        trusted_root::resolve_trusted_instance_root,
+    },
+    domain::models::instance::{InstanceMetadata, WindowTweaks},
+    infrastructure::filesystem::config::load_launcher_config,
+    platform::{
+        game_window::{apply_window_tweaks, find_process_window, WindowId, WindowTweakError},
+        processes::is_process_alive,
+    },
+};
+
+/// Tiempo máximo que se espera a que aparezca la primera ventana (Forge grande en un disco
+/// lento tarda varios minutos).
+const WINDOW_APPEAR_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+const WINDOW_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Acceso a las ventanas del juego; separado para poder probar la vigilancia.
+pub(crate) trait GameWindows {
+    fn current(&self) -> Result<Option<WindowId>, WindowTweakError>;
+    fn apply(&self, window: WindowId) -> Result<(), WindowTweakError>;
+    fn process_alive(&self) -> bool;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum WatchOutcome {
+    /// El proceso terminó; `applied` cuenta las ventanas ajustadas.
+    Exited {
+        applied: usize,
+    },
+    /// No apareció ninguna ventana dentro del plazo.
+    NoWindow,
+    Unsupported(String),
+}
+
+/// Aplica los ajustes a cada ventana nueva del proceso hasta que termine. Un fallo al
+/// aplicar se registra y se reintenta con la siguiente ventana que aparezca.
+pub(crate) fn watch_game_window(
+    windows: &dyn GameWindows,
+    appear_timeout: Duration,
+    poll: Duration,
+) -> WatchOutcome {
+    let started = Instant::now();
+    let mut last: Option<WindowId> = None;
+    let mut applied = 0;
+    while windows.process_alive() {
+        match windows.current() {
+            Err(WindowTweakError::Unsupported(reason)) => return WatchOutcome::Unsupported(reason),
+            Err(WindowTweakError::Failed(err)) => {
+                log::warn!("⚠ No se pudo buscar la ventana del juego: {err}");
+            }
+            Ok(None) if last.is_none() && started.elapsed() >= appear_timeout => {
+                return WatchOutcome::NoWindow;
+            }
+            Ok(Some(window)) if last != Some(window) => {
+                last = Some(window);
+                match windows.apply(window) {
+                    Ok(()) => applied += 1,
+                    Err(WindowTweakError::Unsupported(reason)) => {
+                        return WatchOutcome::Unsupported(reason)
+                    }
+                    Err(WindowTweakError::Failed(err)) => {
+                        log::warn!("⚠ No se pudieron aplicar los ajustes de ventana: {err}");
+                    }
+                }
+            }
+            Ok(_) => {}
+        }
+        thread::sleep(poll);
+    }
+    WatchOutcome::Exited { applied }
+}
+
+struct ProcessWindows {
+    pid: u32,
+    tweaks: WindowTweaks,
+}
+
+impl GameWindows for ProcessWindows {
+    fn current(&self) -> Result<Option<WindowId>, WindowTweakError> {
+        find_process_window(self.pid)
+    }
+
+    fn apply(&self, window: WindowId) -> Result<(), WindowTweakError> {
+        apply_window_tweaks(window, self.tweaks)
+    }
+
+    fn process_alive(&self) -> bool {
+        is_process_alive(self.pid)
+    }
+}
+
+fn emit_system_line(app: &AppHandle, instance_root: &str, line: String) {
+    emit_journaled(
+        app,
+        instance_root,
+        "instance_runtime_output",
+        serde_json::json!({
+            "instanceRoot": instance_root,
+            "stream": "system",
+            "line": line,
+        }),
+    );
+}
+
+/// Empieza a vigilar la ventana del proceso recién lanzado si la instancia tiene algún
+/// ajuste activo y el launcher no los tiene desactivados.
+pub(crate) fn spawn_window_tweaks(
+    app: &AppHandle,
+    instance_root: &str,
+    metadata: &InstanceMetadata,
+    pid: u32,
+) {
+    let tweaks = metadata.window_tweaks;
+    if tweaks.is_default() {
+        return;
+    }
+    if load_launcher_config(app).is_ok_and(|config| config.disable_window_tweaks) {
+        log::info!("🔹 Ajustes de ventana de {instance_root} omitidos por la configuración.");
+        return;
+    }
+    let app = app.clone();
+    let instance_root = instance_root.to_string();
+    thread::spawn(move || {
+        let windows = ProcessWindows { pid, tweaks };
+        match watch_game_window(&windows, WINDOW_APPEAR_TIMEOUT, WINDOW_POLL_INTERVAL) {
+            WatchOutcome::Exited { applied } => log::info!(
+                "🔹 Ajustes de ventana de {instance_root} aplicados a {applied} ventana(s)."
+            ),
+            WatchOutcome::NoWindow => emit_system_line(
+                &app,
+                &instance_root,
+                format!(
+                    "Ajustes de ventana: el juego no mostró ninguna ventana en {} s.",
+                    WINDOW_APPEAR_TIMEOUT.as_secs()
+                ),
+            ),
+            WatchOutcome::Unsupported(reason) => emit_system_line(
+                &app,
+                &instance_root,
+                format!("Ajustes de ventana no aplicados: {reason}."),
+            ),
+        }
+    });
+}
+
+/// Guarda los ajustes de ventana de la instancia; se aplican desde el siguiente
+/// lanzamiento.
+#[tauri::command]
+pub fn set_instance_window_tweaks(
+    app: AppHandle,
+    instance_root: String,
+    tweaks: WindowTweaks,
+) -> Result<InstanceMetadata, String> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    let metadata = update_instance_metadata(instance_root.as_str(), |metadata| {
+        metadata.window_tweaks = tweaks;
+        Ok::<(), String>(())
+    })?;
+    log::info!(
+        "🔹 Ajustes de ventana de {instance_root}: sin borde={}, siempre encima={}",
+        tweaks.borderless,
+        tweaks.always_on_top
+    );
+    Ok(metadata)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::{Cell, RefCell};
+
+    use super::*;
+
+    /// Devuelve una ventana por comprobación y termina al agotar la secuencia.
+    struct MockWindows {
+        sequence: Vec<Result<Option<WindowId>, WindowTweakError>>,
+        step: Cell<usize>,
+        applied: RefCell<Vec<WindowId>>,
+    }
+
+    impl MockWindows {
+        fn new(sequence: Vec<Result<Option<WindowId>, WindowTweakError>>) -> Self {
+            Self {
+                sequence,
+                step: Cell::new(0),
+                applied: RefCell::new(Vec::new()),
+            }
+        }
+    }
+
+    impl GameWindows for MockWindows {
+        fn current(&self) -> Result<Option<WindowId>, WindowTweakError> {
+            let step = self.step.get();
+            self.step.set(step + 1);
+            self.sequence[step].clone()
+        }
+
+        fn apply(&self, window: WindowId) -> Result<(), WindowTweakError> {
+            self.applied.borrow_mut().push(window);
+            Ok(())
+        }
+
+        fn process_alive(&self) -> bool {
+            self.step.get() < self.sequence.len()
+        }
+    }
+
+    #[test]
+    fn reapplies_only_when_the_window_is_recreated() {
+        let windows = MockWindows::new(vec![
+            Ok(None),
+            Ok(Some(7)),
+            Ok(Some(7)),
+            Err(WindowTweakError::Failed("transitorio".to_string())),
+            Ok(Some(9)),
+            Ok(Some(9)),
+        ]);
+        assert_eq!(
+            watch_game_window(&windows, Duration::from_secs(60), Duration::ZERO),
+            WatchOutcome::Exited { applied: 2 }
+        );
+        assert_eq!(*windows.applied.borrow(), vec![7, 9]);
+    }
+
+    #[test]
+    fn stops_on_unsupported_sessions_and_missing_windows() {
+        let wayland = MockWindows::new(vec![Err(WindowTweakError::Unsupported(
+            "Wayland".to_string(),
+        ))]);
+        assert_eq!(
+            watch_game_window(&wayland, Duration::from_secs(60), Duration::ZERO),
+            WatchOutcome::Unsupported("Wayland".to_string())
+        );
+
+        let windowless = MockWindows::new(vec![Ok(None), Ok(None)]);
+        assert_eq!(
+            watch_game_window(&windowless, Duration::ZERO, Duration::ZERO),
+            WatchOutcome::NoWindow
+        );
+        assert!(windowless.applied.borrow().is_empty());
+    }
+}
@@ -2,6 +2,8 @@ use reqwest::blocking::{Client, RequestBuilder};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use crate::infrastructure::http::tls::with_custom_roots;
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CatalogSearchRequest {
@@ -94,7 +96,7 @@ pub struct CatalogDetailResponse {
 
 #[tauri::command]
 pub fn search_catalogs(request: CatalogSearchRequest) -> Result<CatalogSearchResponse, String> {
-    let client = Client::builder()
+    let client = with_custom_roots(Client::builder())
         .user_agent("Interface-2/0.1")
         .timeout(std::time::Duration::from_secs(12))
         .build()
@@ -148,7 +150,7 @@ pub fn search_catalogs(request: CatalogSearchRequest) -> Result<CatalogSearchRes
 
 #[tauri::command]
 pub fn get_catalog_detail(request: CatalogDetailRequest) -> Result<CatalogDetailResponse, String> {
-    let client = Client::builder()
+    let client = with_custom_roots(Client::builder())
         .user_agent("Interface-2/0.1")
         .timeout(std::time::Duration::from_secs(15))
         .build()
@@ -1,7 +1,13 @@
 use serde::{Deserialize, Serialize};
-use std::{fs, io::Write, path::{Path, PathBuf}};
+use std::{fs, io::Write, path::Path};
+use tauri::AppHandle;
 use zip::{write::SimpleFileOptions, CompressionMethod, ZipWriter};
 
+use crate::{
+    app::trusted_root::resolve_trusted_instance_root,
+    infrastructure::filesystem::paths::safe_path_component,
+};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ExportResult {
@@ -16,15 +22,6 @@ pub struct InstanceExportRequest {
     pub export_format: String,
 }
 
-fn slugify(name: &str) -> String {
-    let cleaned = name
-        .trim()
-        .chars()
-        .map(|ch| if ch.is_ascii_alphanumeric() || ch == '-' || ch == '_' { ch } else { '-' })
-        .collect::<String>();
-    cleaned.trim_matches('-').to_string()
-}
-
 fn add_dir_recursively(
     zip: &mut ZipWriter<std::fs::File>,
     base: &Path,
@@ -59,14 +56,17 @@ fn add_dir_recursively(
 }
 
 #[tauri::command]
-pub fn export_instance_package(request: InstanceExportRequest) -> Result<ExportResult, String> {
-    let instance_root = PathBuf::from(&request.instance_root);
-    if !instance_root.exists() {
-        return Err("La instancia no existe en disco".into());
-    }
+pub fn export_instance_package(
+    app: AppHandle,
+    request: InstanceExportRequest,
+) -> Result<ExportResult, String> {
+    let validated = resolve_trusted_instance_root(&app, &request.instance_root)?;
+    let instance_root = validated.path();
 
     let extension = if request.export_format == "mrpack" { "mrpack" } else { "zip" };
-    let suggested = format!("{}-{}.{}", slugify(&request.instance_name), request.export_format.to_lowercase(), extension);
+    let stem = safe_path_component(&format!("{}-{}", request.instance_name, request.export_format))
+        .map_err(|err| err.to_string())?;
+    let suggested = format!("{stem}.{extension}");
 
     let file = rfd::FileDialog::new()
         .set_title("Exportar instancia")
@@ -85,11 +85,24 @@ pub fn export_instance_package(request: InstanceExportRequest) -> Result<ExportR
         .compression_method(CompressionMethod::Deflated)
         .unix_permissions(0o644);
 
+    let instance_json = fs::read_to_string(instance_root.join(".instance.json"))
+        .ok()
+        .and_then(|raw| serde_json::from_str::<serde_json::Value>(&raw).ok());
+    let manifest_field = |key: &str, default: serde_json::Value| {
+        instance_json
+            .as_ref()
+            .and_then(|json| json.get(key).cloned())
+            .unwrap_or(default)
+    };
+
     let export_manifest = serde_json::json!({
         "name": request.instance_name,
         "format": request.export_format,
         "exportedBy": "Interface Launcher",
         "version": 1,
+        "tags": manifest_field("tags", serde_json::json!([])),
+        "lockedFields": manifest_field("lockedFields", serde_json::json!([])),
+        "lockedBy": manifest_field("lockedBy", serde_json::json!("")),
     });
 
     zip.start_file("interface-export.json", options)
@@ -97,7 +110,7 @@ pub fn export_instance_package(request: InstanceExportRequest) -> Result<ExportR
     zip.write_all(export_manifest.to_string().as_bytes())
         .map_err(|err| format!("No se pudo escribir manifest: {err}"))?;
 
-    add_dir_recursively(&mut zip, &instance_root, &instance_root, options)?;
+    add_dir_recursively(&mut zip, instance_root, instance_root, options)?;
 
     zip.finish()
         .map_err(|err| format!("No se pudo finalizar el archivo: {err}"))?;
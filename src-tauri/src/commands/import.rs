@@ -16,10 +16,19 @@ use tauri::{AppHandle, Emitter};
 use uuid::Uuid;
 
 use crate::{
+    app::instance_locks::normalize_locked_fields,
+    app::instance_reset::record_import_manifest,
+    app::instance_tags::sanitize_tags,
+    app::op_journal::{JournalEntry, OperationJournal, OP_IMPORT_INSTANCE},
+    app::pack_update::pack_origin_from_source,
+    app::trusted_root::resolve_external_source_dir,
+    domain::instance::interop::{known_external_launcher_roots, read_external_manifest},
     domain::java::java_requirement::determine_required_java,
     domain::models::instance::InstanceMetadata,
     domain::models::java::JavaRuntime,
-    infrastructure::filesystem::paths::sanitize_path_segment,
+    infrastructure::filesystem::disk_space::{directory_size, preflight_disk_space},
+    infrastructure::filesystem::paths::safe_path_component,
+    infrastructure::filesystem::verified_copy::{verified_copy_enabled, VerifiedCopier},
     services::{instance_builder::build_instance_structure, java_installer::ensure_embedded_java},
 };
 
@@ -58,6 +67,9 @@ pub struct ImportRequest {
     copy_resourcepacks: bool,
     copy_screenshots: bool,
     copy_logs: bool,
+    /// Releer y comparar cada archivo copiado; por defecto solo en unidades extraíbles o
+    /// de red.
+    verified_copy: Option<bool>,
 }
 
 #[derive(serde::Deserialize)]
@@ -164,6 +176,8 @@ struct DetectionMeta {
     loader_version: Option<String>,
     format: Option<String>,
     importable: bool,
+    /// Mods listados por el manifiesto, si la carpeta `mods/` no se puede contar.
+    mods_count: Option<u32>,
 }
 
 fn runtime_name(runtime: JavaRuntime) -> &'static str {
@@ -253,7 +267,9 @@ fn normalize_import_layout(instance_root: &Path, source_root: &Path) -> Result<P
 
     if let Some(source_mc) = detect_source_minecraft_dir(source_root) {
         let mut copied = 0usize;
-        copy_dir_recursive_limited(&source_mc, &minecraft_root, &mut copied, None)?;
+        let mut copier = VerifiedCopier::new(verified_copy_enabled(instance_root, None), |_| {});
+        copy_dir_recursive_limited(&source_mc, &minecraft_root, &mut copied, None, &mut copier)?;
+        copier.finish()?;
     }
 
     Ok(minecraft_root)
@@ -264,6 +280,7 @@ fn copy_dir_recursive_limited(
     dst: &Path,
     copied: &mut usize,
     max_files: Option<usize>,
+    copier: &mut VerifiedCopier<'_>,
 ) -> Result<(), String> {
     if max_files.is_some_and(|max| *copied >= max) || !src.exists() {
         return Ok(());
@@ -293,20 +310,22 @@ fn copy_dir_recursive_limited(
                 .and_then(|value| value.to_str())
                 .map(|value| value.to_ascii_lowercase())
                 .unwrap_or_default();
-            if ["cache", "temp", "tmp", "natives"].contains(&dir_name.as_str()) {
+            // `versions/<id>/natives` son los natives preextraídos de launchers antiguos.
+            let legacy_version_natives = dir_name == "natives"
+                && src
+                    .parent()
+                    .and_then(|parent| parent.file_name())
+                    .is_some_and(|name| name.eq_ignore_ascii_case("versions"));
+            if ["cache", "temp", "tmp", "natives"].contains(&dir_name.as_str())
+                && !legacy_version_natives
+            {
                 continue;
             }
-            copy_dir_recursive_limited(&path, &target, copied, max_files)?;
+            copy_dir_recursive_limited(&path, &target, copied, max_files, copier)?;
             continue;
         }
 
-        fs::copy(&path, &target).map_err(|err| {
-            format!(
-                "No se pudo copiar {} -> {}: {err}",
-                path.display(),
-                target.display()
-            )
-        })?;
+        copier.copy_file(&path, &target)?;
         *copied += 1;
     }
 
@@ -531,6 +550,10 @@ fn known_paths() -> Vec<(String, PathBuf)> {
         }
     }
 
+    for (launcher, root) in known_external_launcher_roots() {
+        out.push((launcher.display_name().to_string(), root.join("instances")));
+    }
+
     out
 }
 
@@ -548,9 +571,7 @@ fn has_instance_markers(path: &Path) -> bool {
             "profile.json" => read_json(&marker)
                 .and_then(|json| json.get("game_version").cloned())
                 .is_some(),
-            "config.json" => read_json(&marker)
-                .and_then(|json| json.get("mcVersion").cloned())
-                .is_some(),
+            "config.json" => read_external_manifest(path).is_some(),
             _ => true,
         }
     })
@@ -1004,6 +1025,46 @@ fn read_json(path: &Path) -> Option<serde_json::Value> {
     serde_json::from_str(&raw).ok()
 }
 
+/// Etiquetas de una instancia exportada por este launcher (`.instance.json` o el manifest
+/// `interface-export.json`).
+fn imported_tags(source_root: &Path) -> Vec<String> {
+    [".instance.json", "interface-export.json"]
+        .iter()
+        .filter_map(|file_name| read_json(&source_root.join(file_name)))
+        .find_map(|json| json.get("tags")?.as_array().cloned())
+        .map(|tags| sanitize_tags(tags.iter().filter_map(Value::as_str)))
+        .unwrap_or_default()
+}
+
+/// Bloqueos del pack original; se conservan al importar para que el pack siga protegido.
+fn imported_locks(source_root: &Path) -> (Vec<String>, String) {
+    let Some(json) = [".instance.json", "interface-export.json"]
+        .iter()
+        .filter_map(|file_name| read_json(&source_root.join(file_name)))
+        .find(|json| json.get("lockedFields").is_some())
+    else {
+        return (Vec::new(), String::new());
+    };
+    let fields = json
+        .get("lockedFields")
+        .and_then(Value::as_array)
+        .map(|fields| {
+            fields
+                .iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        })
+        .and_then(|fields| normalize_locked_fields(&fields).ok())
+        .unwrap_or_default();
+    let locked_by = json
+        .get("lockedBy")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string();
+    (fields, locked_by)
+}
+
 fn normalize_loader(loader: &str) -> String {
     let normalized = loader.trim().to_ascii_lowercase();
     match normalized.as_str() {
@@ -1441,6 +1502,14 @@ fn read_instance_manifest_strict(source_root: &Path) -> (String, String, String)
         }
     }
 
+    if let Some(manifest) = read_external_manifest(source_root) {
+        return (
+            manifest.minecraft_version,
+            manifest.loader,
+            manifest.loader_version.unwrap_or_default(),
+        );
+    }
+
     (String::new(), String::new(), String::new())
 }
 
@@ -1619,52 +1688,18 @@ fn detect_from_manifest(path: &Path) -> DetectionMeta {
         return meta;
     }
 
-    let atlauncher_manifest = path.join("instance.json");
-    if let Some(json) = read_json(&atlauncher_manifest) {
-        meta.minecraft_version = json
-            .get("mcVersion")
-            .and_then(Value::as_str)
-            .filter(|value| is_valid_mc_version(value))
-            .map(ToOwned::to_owned);
-        if meta.minecraft_version.is_none() {
-            return DetectionMeta::default();
-        }
+    if let Some(manifest) = read_external_manifest(path)
+        .filter(|manifest| is_valid_mc_version(&manifest.minecraft_version))
+    {
         meta.importable = true;
-        meta.format = Some("atlauncher".to_string());
-        meta.loader = json
-            .get("loader")
-            .and_then(Value::as_str)
-            .map(|loader| loader.to_ascii_lowercase());
-        meta.loader_version = json
-            .get("loaderVersion")
-            .and_then(Value::as_str)
-            .map(ToOwned::to_owned);
+        meta.format = Some(manifest.launcher.display_name().to_ascii_lowercase());
+        meta.minecraft_version = Some(manifest.minecraft_version);
+        meta.loader = Some(manifest.loader);
+        meta.loader_version = manifest.loader_version;
+        meta.mods_count = (!manifest.mods.is_empty()).then_some(manifest.mods.len() as u32);
         return meta;
     }
 
-    let gdlauncher_manifest = path.join("config.json");
-    if let Some(json) = read_json(&gdlauncher_manifest) {
-        let mc = json
-            .get("mcVersion")
-            .and_then(Value::as_str)
-            .filter(|value| is_valid_mc_version(value))
-            .map(ToOwned::to_owned);
-        if mc.is_some() {
-            meta.importable = true;
-            meta.format = Some("gdlauncher".to_string());
-            meta.minecraft_version = mc;
-            meta.loader = json
-                .get("modLoader")
-                .and_then(Value::as_str)
-                .map(|loader| loader.to_ascii_lowercase());
-            meta.loader_version = json
-                .get("loaderVersion")
-                .and_then(Value::as_str)
-                .map(ToOwned::to_owned);
-            return meta;
-        }
-    }
-
     let pack_manifest = path.join("pack.json");
     if let Some(json) = read_json(&pack_manifest) {
         meta.importable = true;
@@ -1843,7 +1878,8 @@ fn detect_dir(path: &Path, launcher: &str) -> Option<DetectedInstance> {
                     .is_some_and(|ext| ext.eq_ignore_ascii_case("jar"))
             })
             .count() as u32
-    });
+    })
+    .or(meta.mods_count);
 
     let size_mb = {
         let size_bytes = dir_size(path);
@@ -2317,23 +2353,36 @@ pub fn execute_import(app: AppHandle, requests: Vec<ImportRequest>) -> Result<()
         .map_err(|err| format!("No se pudo preparar el directorio de instancias: {err}"))?;
 
     for (index, req) in requests.iter().enumerate() {
-        let source_root = PathBuf::from(&req.source_path);
-        if !source_root.exists() || !source_root.is_dir() {
-            let _ = app.emit(
-                "import_instance_completed",
-                serde_json::json!({
-                    "success": false,
-                    "instanceId": req.detected_instance_id,
-                    "error": format!("Ruta inválida: {}", source_root.display())
-                }),
-            );
-            continue;
-        }
+        let source_root = match resolve_external_source_dir(&req.source_path) {
+            Ok(path) => path,
+            Err(error) => {
+                let _ = app.emit(
+                    "import_instance_completed",
+                    serde_json::json!({
+                        "success": false,
+                        "instanceId": req.detected_instance_id,
+                        "error": format!("Ruta inválida: {error}")
+                    }),
+                );
+                continue;
+            }
+        };
 
-        let mut sanitized_name = sanitize_path_segment(&req.target_name);
-        if sanitized_name.trim().is_empty() {
-            sanitized_name = format!("imported-{}", index + 1);
-        }
+        let sanitized_name = match safe_path_component(&req.target_name) {
+            Ok(name) => name,
+            Err(err) => {
+                let _ = app.emit(
+                    "import_instance_completed",
+                    serde_json::json!({
+                        "success": false,
+                        "instanceId": req.detected_instance_id,
+                        "error": err.to_string(),
+                        "errorCode": err.code(),
+                    }),
+                );
+                continue;
+            }
+        };
 
         let mut instance_root = instances_root.join(&sanitized_name);
         if instance_root.exists() {
@@ -2355,6 +2404,33 @@ pub fn execute_import(app: AppHandle, requests: Vec<ImportRequest>) -> Result<()
             }),
         );
 
+        // Una carpeta importada se copia entera: se exige su tamaño libre antes de crearla.
+        match preflight_disk_space(&instances_root, directory_size(&source_root)) {
+            Ok(check) => {
+                if let Some(check) = check.filter(|check| check.tight) {
+                    let _ = app.emit(
+                        "disk_space_warning",
+                        serde_json::json!({
+                            "instanceId": req.detected_instance_id,
+                            "payload": check,
+                        }),
+                    );
+                }
+            }
+            Err(disk_space) => {
+                let _ = app.emit(
+                    "import_instance_completed",
+                    serde_json::json!({
+                        "success": false,
+                        "instanceId": req.detected_instance_id,
+                        "error": disk_space.message,
+                        "diskSpace": disk_space,
+                    }),
+                );
+                continue;
+            }
+        }
+
         let result = (|| -> Result<(), String> {
             fs::create_dir_all(&instance_root).map_err(|err| {
                 format!(
@@ -2362,9 +2438,34 @@ pub fn execute_import(app: AppHandle, requests: Vec<ImportRequest>) -> Result<()
                     instance_root.display()
                 )
             })?;
+            // Si la copia se corta, el diario permite deshacerla desde el listado.
+            let journal = OperationJournal::begin(
+                &instance_root,
+                OP_IMPORT_INSTANCE,
+                &req.target_name,
+                vec![JournalEntry::path(&instance_root)],
+            )?;
 
             let mut copied_files = 0usize;
-            copy_dir_recursive_limited(&source_root, &instance_root, &mut copied_files, None)?;
+            let verify = verified_copy_enabled(&instance_root, req.verified_copy);
+            let mut copier = VerifiedCopier::new(verify, |progress| {
+                let _ = app.emit(
+                    "instance_copy_progress",
+                    serde_json::json!({
+                        "operation": "import",
+                        "instanceId": req.detected_instance_id,
+                        "progress": progress,
+                    }),
+                );
+            });
+            copy_dir_recursive_limited(
+                &source_root,
+                &instance_root,
+                &mut copied_files,
+                None,
+                &mut copier,
+            )?;
+            copier.finish()?;
 
             let effective_version_id = resolve_effective_version_id(
                 &source_root,
@@ -2375,6 +2476,7 @@ pub fn execute_import(app: AppHandle, requests: Vec<ImportRequest>) -> Result<()
             );
 
             let internal_uuid = uuid::Uuid::new_v4().to_string();
+            let (locked_fields, locked_by) = imported_locks(&source_root);
             let mut metadata = InstanceMetadata {
                 name: req.target_name.clone(),
                 group: req.target_group.clone(),
@@ -2392,15 +2494,42 @@ pub fn execute_import(app: AppHandle, requests: Vec<ImportRequest>) -> Result<()
                 state: "IMPORTED".to_string(),
                 last_used: None,
                 internal_uuid,
+                filesystem: None,
+                mods_dir_override: None,
+                adopted: false,
+                tags: imported_tags(&source_root),
+                locked_fields,
+                locked_by,
+                optional_game_flags: Default::default(),
+                java_build_pin: None,
+                source_settings_write: false,
+                retention: Default::default(),
+                library_overrides: Vec::new(),
+                pack_origin: pack_origin_from_source(&source_root),
+                auto_jvm_tuning: true,
+                preferred_gpu: None,
+                backup: Default::default(),
+                strict_mode: false,
+                skip_duplicate_mod_check: false,
+                window_tweaks: Default::default(),
+                launch_hooks: Default::default(),
+                console_filters: Vec::new(),
             };
 
             finalize_import_runtime(&app, &instance_root, &source_root, &mut metadata)?;
+            if let Err(err) = record_import_manifest(
+                &instance_root,
+                &instance_root.join("minecraft").join("mods"),
+            ) {
+                log::warn!("⚠ No se pudo guardar el manifest de importación: {err}");
+            }
 
             let metadata_path = instance_root.join(".instance.json");
             let metadata_raw = serde_json::to_string_pretty(&metadata)
                 .map_err(|err| format!("No se pudo serializar metadata: {err}"))?;
             fs::write(&metadata_path, metadata_raw)
                 .map_err(|err| format!("No se pudo guardar metadata: {err}"))?;
+            journal.complete();
 
             Ok(())
         })();
@@ -2437,11 +2566,11 @@ pub fn execute_import_action(
     request: ImportActionRequest,
 ) -> Result<ImportActionResult, String> {
     let action = request.action.trim().to_ascii_lowercase();
-    let requested_source = PathBuf::from(&request.source_path);
+    let requested_source = resolve_external_source_dir(&request.source_path)?;
     let source_root = crate::app::shortcut_instance::normalize_external_root(&requested_source);
 
     if action == "abrir_carpeta" {
-        crate::app::instance_service::open_instance_folder(request.source_path.clone())?;
+        crate::app::instance_service::open_folder_in_explorer(&requested_source)?;
         return Ok(ImportActionResult {
             success: true,
             target_name: request.target_name,
@@ -2575,6 +2704,7 @@ pub fn execute_import_action(
         copy_resourcepacks: true,
         copy_screenshots: true,
         copy_logs: true,
+        verified_copy: None,
     };
 
     execute_import(app.clone(), vec![import_request])?;
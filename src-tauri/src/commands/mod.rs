@@ -5,5 +5,6 @@ pub mod import;
 pub mod settings;
 pub mod visual_meta;
 pub mod skin_processor;
+pub mod tasks;
 pub mod validator;
 pub mod mods;
@@ -1,5 +1,19 @@
 use serde::Serialize;
-use std::{fs, path::PathBuf, time::UNIX_EPOCH};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::UNIX_EPOCH,
+};
+
+use tauri::AppHandle;
+
+use crate::app::{
+    instance_dedup::{detach_shared_link, note_link_removed, note_link_renamed},
+    instance_locks::{ensure_unlocked, InstanceEditError},
+    instance_search::schedule_content_index_refresh,
+    instance_service::effective_mods_dir,
+    trusted_root::{resolve_trusted_instance_root, ValidatedInstanceRoot},
+};
 
 fn section_folder(section: Option<&str>) -> &'static str {
     match section
@@ -15,6 +29,14 @@ fn section_folder(section: Option<&str>) -> &'static str {
     }
 }
 
+/// Carpeta de la sección; para `mods` respeta `mods_dir_override` de la instancia.
+fn section_dir(instance_root: &Path, section: Option<&str>) -> PathBuf {
+    match section_folder(section) {
+        "mods" => effective_mods_dir(instance_root),
+        folder => instance_root.join("minecraft").join(folder),
+    }
+}
+
 fn section_allows_disable(section: Option<&str>) -> bool {
     section_folder(section) != "saves"
 }
@@ -48,12 +70,19 @@ pub struct InstanceModEntry {
 
 #[tauri::command]
 pub fn list_instance_mods(
+    app: AppHandle,
     instance_root: String,
     section: Option<String>,
 ) -> Result<Vec<InstanceModEntry>, String> {
-    let mods_dir = PathBuf::from(instance_root)
-        .join("minecraft")
-        .join(section_folder(section.as_deref()));
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    list_section_entries(&instance_root, section)
+}
+
+fn list_section_entries(
+    instance_root: &ValidatedInstanceRoot,
+    section: Option<String>,
+) -> Result<Vec<InstanceModEntry>, String> {
+    let mods_dir = section_dir(instance_root.path(), section.as_deref());
     if !mods_dir.exists() {
         return Ok(Vec::new());
     }
@@ -107,23 +136,38 @@ pub fn list_instance_mods(
 
 #[tauri::command]
 pub fn set_instance_mod_enabled(
+    app: AppHandle,
     instance_root: String,
     file_name: String,
     enabled: bool,
     section: Option<String>,
-) -> Result<(), String> {
+    override_lock: Option<bool>,
+) -> Result<(), InstanceEditError> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    set_mod_enabled(&instance_root, file_name, enabled, section, override_lock)
+        .inspect(|_| schedule_content_index_refresh(instance_root.path()))
+}
+
+fn set_mod_enabled(
+    instance_root: &ValidatedInstanceRoot,
+    file_name: String,
+    enabled: bool,
+    section: Option<String>,
+    override_lock: Option<bool>,
+) -> Result<(), InstanceEditError> {
     if !section_allows_disable(section.as_deref()) {
         return Ok(());
     }
-    let mods_dir = PathBuf::from(instance_root)
-        .join("minecraft")
-        .join(section_folder(section.as_deref()));
+    ensure_unlocked(
+        instance_root.as_str(),
+        section_folder(section.as_deref()),
+        "set_instance_mod_enabled",
+        override_lock.unwrap_or(false),
+    )?;
+    let mods_dir = section_dir(instance_root.path(), section.as_deref());
     let source_path = mods_dir.join(&file_name);
     if !source_path.exists() {
-        return Err(format!(
-            "No existe el mod seleccionado: {}",
-            source_path.display()
-        ));
+        return Err(format!("No existe el mod seleccionado: {}", source_path.display()).into());
     }
 
     let lower = file_name.to_lowercase();
@@ -137,8 +181,9 @@ pub fn set_instance_mod_enabled(
             file_name.trim_end_matches(".disabled").to_string()
         };
         let target_path = mods_dir.join(next_name);
-        fs::rename(&source_path, target_path)
+        fs::rename(&source_path, &target_path)
             .map_err(|err| format!("No se pudo activar mod: {err}"))?;
+        note_link_renamed(instance_root.path(), &source_path, &target_path);
         return Ok(());
     }
 
@@ -147,22 +192,49 @@ pub fn set_instance_mod_enabled(
     }
 
     let target_path = mods_dir.join(format!("{file_name}.disabled"));
-    fs::rename(&source_path, target_path)
+    fs::rename(&source_path, &target_path)
         .map_err(|err| format!("No se pudo desactivar mod: {err}"))?;
+    note_link_renamed(instance_root.path(), &source_path, &target_path);
     Ok(())
 }
 
 #[tauri::command]
 pub fn replace_instance_mod_file(
+    app: AppHandle,
     instance_root: String,
     current_file_name: String,
     download_url: String,
     new_file_name: String,
     section: Option<String>,
-) -> Result<(), String> {
-    let mods_dir = PathBuf::from(instance_root)
-        .join("minecraft")
-        .join(section_folder(section.as_deref()));
+    override_lock: Option<bool>,
+) -> Result<(), InstanceEditError> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    replace_mod_file(
+        &instance_root,
+        current_file_name,
+        download_url,
+        new_file_name,
+        section,
+        override_lock,
+    )
+    .inspect(|_| schedule_content_index_refresh(instance_root.path()))
+}
+
+fn replace_mod_file(
+    instance_root: &ValidatedInstanceRoot,
+    current_file_name: String,
+    download_url: String,
+    new_file_name: String,
+    section: Option<String>,
+    override_lock: Option<bool>,
+) -> Result<(), InstanceEditError> {
+    ensure_unlocked(
+        instance_root.as_str(),
+        section_folder(section.as_deref()),
+        "replace_instance_mod_file",
+        override_lock.unwrap_or(false),
+    )?;
+    let mods_dir = section_dir(instance_root.path(), section.as_deref());
     fs::create_dir_all(&mods_dir)
         .map_err(|err| format!("No se pudo preparar carpeta de mods: {err}"))?;
 
@@ -173,12 +245,13 @@ pub fn replace_instance_mod_file(
         .map_err(|err| format!("No se pudo leer descarga de versión: {err}"))?;
 
     let new_target = mods_dir.join(&new_file_name);
+    detach_shared_link(instance_root.path(), &new_target);
     fs::write(&new_target, &bytes)
         .map_err(|err| format!("No se pudo guardar la nueva versión: {err}"))?;
 
     let old_target = mods_dir.join(&current_file_name);
-    if old_target.exists() {
-        let _ = fs::remove_file(old_target);
+    if old_target.exists() && fs::remove_file(&old_target).is_ok() {
+        note_link_removed(instance_root.path(), &old_target);
     }
 
     Ok(())
@@ -186,15 +259,22 @@ pub fn replace_instance_mod_file(
 
 #[tauri::command]
 pub fn install_catalog_mod_file(
+    app: AppHandle,
     instance_root: String,
     download_url: String,
     file_name: String,
     replace_existing: bool,
     section: Option<String>,
-) -> Result<(), String> {
-    let mods_dir = PathBuf::from(instance_root)
-        .join("minecraft")
-        .join(section_folder(section.as_deref()));
+    override_lock: Option<bool>,
+) -> Result<(), InstanceEditError> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    ensure_unlocked(
+        instance_root.as_str(),
+        section_folder(section.as_deref()),
+        "install_catalog_mod_file",
+        override_lock.unwrap_or(false),
+    )?;
+    let mods_dir = section_dir(instance_root.path(), section.as_deref());
     fs::create_dir_all(&mods_dir)
         .map_err(|err| format!("No se pudo preparar carpeta de mods: {err}"))?;
 
@@ -236,9 +316,53 @@ pub fn install_catalog_mod_file(
         return Ok(());
     }
 
+    detach_shared_link(instance_root.path(), &target_path);
     fs::write(&target_path, &bytes)
         .map_err(|err| format!("No se pudo guardar mod descargado: {err}"))?;
+    schedule_content_index_refresh(instance_root.path());
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn delete_instance_mod(
+    app: AppHandle,
+    instance_root: String,
+    file_name: String,
+    section: Option<String>,
+    override_lock: Option<bool>,
+) -> Result<(), InstanceEditError> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    delete_mod_file(&instance_root, file_name, section, override_lock)
+        .inspect(|_| schedule_content_index_refresh(instance_root.path()))
+}
 
+fn delete_mod_file(
+    instance_root: &ValidatedInstanceRoot,
+    file_name: String,
+    section: Option<String>,
+    override_lock: Option<bool>,
+) -> Result<(), InstanceEditError> {
+    ensure_unlocked(
+        instance_root.as_str(),
+        section_folder(section.as_deref()),
+        "delete_instance_mod",
+        override_lock.unwrap_or(false),
+    )?;
+    if file_name.contains(['/', '\\']) || file_name == ".." {
+        return Err(format!("Nombre de archivo no válido: {file_name}").into());
+    }
+    let target = section_dir(instance_root.path(), section.as_deref()).join(&file_name);
+    if !target.exists() {
+        return Err(format!("No existe el mod seleccionado: {}", target.display()).into());
+    }
+    if target.is_dir() {
+        fs::remove_dir_all(&target)
+    } else {
+        fs::remove_file(&target)
+    }
+    .map_err(|err| format!("No se pudo eliminar {}: {err}", target.display()))?;
+    note_link_removed(instance_root.path(), &target);
     Ok(())
 }
 
@@ -269,3 +393,87 @@ fn detect_provider(file_name: &str) -> String {
     }
     "Local".to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::trusted_root::validate_instance_root;
+
+    fn locked_instance(name: &str) -> PathBuf {
+        let root =
+            std::env::temp_dir().join(format!("interface-mods-lock-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("minecraft").join("mods")).expect("mods dir");
+        fs::write(
+            root.join("minecraft").join("mods").join("sodium-0.5.8.jar"),
+            b"jar",
+        )
+        .expect("mod");
+        let metadata = serde_json::json!({
+            "name": "Pack",
+            "group": "",
+            "minecraftVersion": "1.20.1",
+            "loader": "fabric",
+            "loaderVersion": "0.15.11",
+            "ramMb": 4096,
+            "javaArgs": [],
+            "javaPath": "",
+            "javaRuntime": "embedded",
+            "lastUsed": null,
+            "internalUuid": "test",
+            "lockedFields": ["mods"],
+            "lockedBy": "Pack publicado: no modificar"
+        });
+        fs::write(root.join(".instance.json"), metadata.to_string()).expect("metadata");
+        root
+    }
+
+    fn validated(root: &Path) -> ValidatedInstanceRoot {
+        let parent = root.parent().expect("parent").to_path_buf();
+        validate_instance_root(&root.display().to_string(), &[parent]).expect("validada")
+    }
+
+    #[test]
+    fn locked_mods_folder_blocks_changes_but_not_listing() {
+        let root = locked_instance("block");
+        let instance_root = validated(&root);
+
+        let deleted = delete_mod_file(&instance_root, "sodium-0.5.8.jar".to_string(), None, None);
+        assert!(matches!(
+            deleted,
+            Err(InstanceEditError::Locked(ref err)) if err.field == "mods"
+                && err.locked_by == "Pack publicado: no modificar"
+        ));
+
+        // El bloqueo se comprueba antes de cualquier descarga.
+        let updated = replace_mod_file(
+            &instance_root,
+            "sodium-0.5.8.jar".to_string(),
+            "http://127.0.0.1:9/sodium.jar".to_string(),
+            "sodium-0.5.9.jar".to_string(),
+            None,
+            None,
+        );
+        assert!(matches!(updated, Err(InstanceEditError::Locked(_))));
+
+        let listed = list_section_entries(&instance_root, None).expect("listado");
+        assert_eq!(listed.len(), 1);
+        assert!(root.join("minecraft/mods/sodium-0.5.8.jar").exists());
+
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn override_lock_allows_delete() {
+        let root = locked_instance("override");
+        delete_mod_file(
+            &validated(&root),
+            "sodium-0.5.8.jar".to_string(),
+            None,
+            Some(true),
+        )
+        .expect("borrado con override");
+        assert!(!root.join("minecraft/mods/sodium-0.5.8.jar").exists());
+        let _ = fs::remove_dir_all(root);
+    }
+}
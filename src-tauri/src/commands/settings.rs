@@ -8,12 +8,21 @@ use tauri::{AppHandle, Emitter};
 
 use crate::{
     app::{
-        instance_service::has_running_instances, launcher_service::list_instances,
+        launcher_service::list_instances,
+        maintenance::{begin_maintenance, report_maintenance_progress},
         settings_service::resolve_instances_root,
     },
-    infrastructure::filesystem::{
-        config::{load_launcher_config, save_launcher_config, LauncherConfig},
-        paths::resolve_launcher_root,
+    infrastructure::{
+        filesystem::{
+            config::{
+                launcher_config_path, load_launcher_config, save_launcher_config, LauncherConfig,
+            },
+            paths::{
+                folder_routes_settings_file, launcher_root_pointer_path, resolve_launcher_root,
+                write_launcher_root_pointer,
+            },
+        },
+        http::rate_limit::{rate_limiter_status, RateLimiterHostStatus},
     },
 };
 
@@ -85,24 +94,43 @@ fn copy_recursive_with_progress(
     app: &AppHandle,
     from: &Path,
     to: &Path,
+    keep: &[PathBuf],
     completed: &mut usize,
     total: usize,
     step: &str,
 ) -> Result<(), String> {
+    copy_tree(from, to, keep, &mut |file| {
+        *completed += 1;
+        report_maintenance_progress(*completed, total);
+        let _ = app.emit(
+            "migration_progress",
+            MigrationProgressEvent {
+                step: step.to_string(),
+                completed: *completed,
+                total,
+                message: format!("Copiando {}", file.display()),
+            },
+        );
+    })
+}
+
+/// Copia `from` en `to` saltando las rutas de `keep`; `on_file` se llama tras cada archivo.
+fn copy_tree(
+    from: &Path,
+    to: &Path,
+    keep: &[PathBuf],
+    on_file: &mut dyn FnMut(&Path),
+) -> Result<(), String> {
+    if keep.iter().any(|kept| kept == from) {
+        return Ok(());
+    }
     if from.is_dir() {
         fs::create_dir_all(to).map_err(|e| format!("No se pudo crear {}: {e}", to.display()))?;
         for entry in
             fs::read_dir(from).map_err(|e| format!("No se pudo leer {}: {e}", from.display()))?
         {
             let entry = entry.map_err(|e| format!("No se pudo leer entrada: {e}"))?;
-            copy_recursive_with_progress(
-                app,
-                &entry.path(),
-                &to.join(entry.file_name()),
-                completed,
-                total,
-                step,
-            )?;
+            copy_tree(&entry.path(), &to.join(entry.file_name()), keep, on_file)?;
         }
     } else {
         if let Some(parent) = to.parent() {
@@ -116,16 +144,59 @@ fn copy_recursive_with_progress(
                 to.display()
             )
         })?;
-        *completed += 1;
-        let _ = app.emit(
-            "migration_progress",
-            MigrationProgressEvent {
-                step: step.to_string(),
-                completed: *completed,
-                total,
-                message: format!("Copiando {}", from.display()),
-            },
-        );
+        on_file(from);
+    }
+    Ok(())
+}
+
+/// Comprueba que cada archivo copiado existe en el destino con el mismo tamaño.
+fn verify_copied_tree(from: &Path, to: &Path, keep: &[PathBuf]) -> Result<(), String> {
+    if keep.iter().any(|kept| kept == from) {
+        return Ok(());
+    }
+    if from.is_dir() {
+        for entry in
+            fs::read_dir(from).map_err(|e| format!("No se pudo leer {}: {e}", from.display()))?
+        {
+            let entry = entry.map_err(|e| format!("No se pudo leer entrada: {e}"))?;
+            verify_copied_tree(&entry.path(), &to.join(entry.file_name()), keep)?;
+        }
+        return Ok(());
+    }
+    let expected = from
+        .metadata()
+        .map_err(|e| format!("No se pudo leer metadata de {}: {e}", from.display()))?
+        .len();
+    match to.metadata() {
+        Ok(meta) if meta.len() == expected => Ok(()),
+        _ => Err(format!(
+            "La copia de {} en {} no coincide con el original.",
+            from.display(),
+            to.display()
+        )),
+    }
+}
+
+/// Borra lo ya copiado conservando las rutas de `keep` y las carpetas que las contienen.
+fn remove_tree_except(path: &Path, keep: &[PathBuf]) -> Result<(), String> {
+    if keep.iter().any(|kept| kept == path) {
+        return Ok(());
+    }
+    if !path.is_dir() {
+        return fs::remove_file(path)
+            .map_err(|e| format!("No se pudo eliminar {}: {e}", path.display()));
+    }
+    for entry in
+        fs::read_dir(path).map_err(|e| format!("No se pudo leer {}: {e}", path.display()))?
+    {
+        let entry = entry.map_err(|e| format!("No se pudo leer entrada: {e}"))?;
+        remove_tree_except(&entry.path(), keep)?;
+    }
+    let empty = fs::read_dir(path)
+        .map(|mut entries| entries.next().is_none())
+        .unwrap_or(false);
+    if empty {
+        fs::remove_dir(path).map_err(|e| format!("No se pudo eliminar {}: {e}", path.display()))?;
     }
     Ok(())
 }
@@ -149,6 +220,12 @@ fn list_files_count(path: &Path) -> Result<usize, String> {
     Ok(total)
 }
 
+/// Estado del limitador de peticiones por host, para depuración.
+#[tauri::command]
+pub fn get_rate_limiter_status() -> Vec<RateLimiterHostStatus> {
+    rate_limiter_status()
+}
+
 #[tauri::command]
 pub fn get_launcher_folders(app: AppHandle) -> Result<LauncherFolders, String> {
     let launcher_root = resolve_launcher_root(&app)?;
@@ -163,7 +240,7 @@ pub fn get_launcher_folders(app: AppHandle) -> Result<LauncherFolders, String> {
 
 #[tauri::command]
 pub fn get_instances_count(app: AppHandle) -> Result<u32, String> {
-    Ok(list_instances(app)?.len() as u32)
+    Ok(list_instances(app, None, None)?.len() as u32)
 }
 
 #[tauri::command]
@@ -172,9 +249,7 @@ pub fn migrate_launcher_root(
     new_path: String,
     migrate_files: bool,
 ) -> Result<(), String> {
-    if has_running_instances()? {
-        return Err("Hay instancias en ejecución. Cierra los juegos antes de migrar.".to_string());
-    }
+    let _maintenance = begin_maintenance("migrate_launcher_root")?;
 
     let old_root = resolve_launcher_root(&app)?;
     let new_root = PathBuf::from(new_path.trim());
@@ -204,6 +279,7 @@ pub fn migrate_launcher_root(
             &app,
             &old_root,
             &new_root,
+            &[],
             &mut completed,
             total.max(1),
             "migrating_launcher_root",
@@ -213,19 +289,103 @@ pub fn migrate_launcher_root(
     let mut config = load_launcher_config(&app).unwrap_or_else(|_| LauncherConfig::default());
     config.launcher_root_override = Some(new_root.display().to_string());
     save_launcher_config(&app, &config)?;
+    write_launcher_root_pointer(&app, Some(&new_root))?;
 
     Ok(())
 }
 
+#[tauri::command]
+pub fn relocate_launcher_root(
+    app: AppHandle,
+    new_path: String,
+    move_data: bool,
+) -> Result<LauncherFolders, String> {
+    let _maintenance = begin_maintenance("relocate_launcher_root")?;
+
+    let old_root = resolve_launcher_root(&app)?;
+    let new_root = PathBuf::from(new_path.trim());
+    ensure_valid_destination(&old_root, &new_root)?;
+    // En Windows y macOS la carpeta de configuración es la propia raíz: la configuración se
+    // lee antes de mover nada y sus archivos ni se copian ni se borran.
+    let mut config = load_launcher_config(&app)?;
+    let keep = [
+        launcher_root_pointer_path(&app)?,
+        launcher_config_path(&app)?,
+        folder_routes_settings_file(&app)?,
+    ];
+
+    let mut moved = Vec::new();
+    if move_data && old_root.exists() {
+        let required = dir_size(&old_root)?.saturating_add(500 * 1024 * 1024);
+        let free = available_space(&new_root)
+            .or_else(|_| available_space(new_root.parent().unwrap_or(&new_root)))
+            .map_err(|e| format!("No se pudo verificar espacio disponible: {e}"))?;
+        if free < required {
+            return Err("No hay suficiente espacio libre para migrar el launcher.".to_string());
+        }
+
+        moved = fs::read_dir(&old_root)
+            .map_err(|e| format!("No se pudo leer {}: {e}", old_root.display()))?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("No se pudo leer entrada: {e}"))?;
+        moved.retain(|path| !keep.contains(path));
+
+        let total = list_files_count(&old_root)?.max(1);
+        let mut completed = 0usize;
+        for path in &moved {
+            let target = new_root.join(path.file_name().unwrap_or_default());
+            copy_recursive_with_progress(
+                &app,
+                path,
+                &target,
+                &keep,
+                &mut completed,
+                total,
+                "relocating_launcher_root",
+            )?;
+            verify_copied_tree(path, &target, &keep)?;
+        }
+    }
+
+    if move_data {
+        if let Some(instances_dir) = config.instances_dir_override.clone() {
+            if let Ok(relative) = Path::new(instances_dir.trim()).strip_prefix(&old_root) {
+                config.instances_dir_override = Some(new_root.join(relative).display().to_string());
+            }
+        }
+    }
+    config.launcher_root_override = Some(new_root.display().to_string());
+    save_launcher_config(&app, &config)?;
+    write_launcher_root_pointer(&app, Some(&new_root))?;
+
+    // Los datos viejos solo se borran cuando la nueva raíz ya está copiada y registrada.
+    for path in &moved {
+        if let Err(err) = remove_tree_except(path, &keep) {
+            log::warn!("⚠ {err}");
+        }
+    }
+
+    let _ = app.emit(
+        "migration_progress",
+        MigrationProgressEvent {
+            step: "launcher_root_relocated".to_string(),
+            completed: 1,
+            total: 1,
+            message: format!("Raíz del launcher: {}", new_root.display()),
+        },
+    );
+
+    get_launcher_folders(app)
+}
+
 #[tauri::command]
 pub fn change_instances_folder(
     app: AppHandle,
     new_path: String,
     migrate_files: bool,
 ) -> Result<(), String> {
-    if has_running_instances()? {
-        return Err("Hay instancias en ejecución. Cierra los juegos antes de migrar.".to_string());
-    }
+    let _maintenance = begin_maintenance("change_instances_folder")?;
 
     let current = resolve_instances_root(&app)?;
     let target = PathBuf::from(new_path.trim());
@@ -250,6 +410,7 @@ pub fn change_instances_folder(
                     &app,
                     &from,
                     &to,
+                    &[],
                     &mut completed,
                     list_files_count(&from)?.max(1),
                     "moving_instance",
@@ -279,3 +440,72 @@ pub fn change_instances_folder(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn relocation_keeps_config_files_living_under_the_old_root() {
+        let base = std::env::temp_dir().join(format!("interface-relocate-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&base);
+        let old_root = base.join("InterfaceLauncher");
+        let new_root = base.join("nueva");
+        fs::create_dir_all(old_root.join("config")).expect("config");
+        fs::create_dir_all(old_root.join("instances/demo")).expect("instances");
+        fs::write(
+            old_root.join("launcher_config.json"),
+            b"{\"theme\":\"dark\"}",
+        )
+        .expect("cfg");
+        fs::write(old_root.join("launcher_root.json"), b"{}").expect("pointer");
+        fs::write(old_root.join("config/folder_routes.json"), b"[]").expect("routes");
+        fs::write(old_root.join("config/accounts.json"), b"[1]").expect("accounts");
+        fs::write(old_root.join("instances/demo/.instance.json"), b"{}").expect("meta");
+        let keep = [
+            old_root.join("launcher_root.json"),
+            old_root.join("launcher_config.json"),
+            old_root.join("config/folder_routes.json"),
+        ];
+
+        let moved = ["config", "instances"].map(|name| old_root.join(name));
+        for path in &moved {
+            let target = new_root.join(path.file_name().expect("name"));
+            copy_tree(path, &target, &keep, &mut |_| {}).expect("copy");
+            verify_copied_tree(path, &target, &keep).expect("verify");
+        }
+        for path in &moved {
+            remove_tree_except(path, &keep).expect("remove");
+        }
+
+        assert_eq!(
+            fs::read(old_root.join("launcher_config.json")).expect("cfg"),
+            b"{\"theme\":\"dark\"}"
+        );
+        assert!(old_root.join("config/folder_routes.json").is_file());
+        assert!(!old_root.join("config/accounts.json").exists());
+        assert!(!old_root.join("instances").exists());
+        assert_eq!(
+            fs::read(new_root.join("config/accounts.json")).expect("accounts"),
+            b"[1]"
+        );
+        assert!(new_root.join("instances/demo/.instance.json").is_file());
+        assert!(!new_root.join("config/folder_routes.json").exists());
+
+        let _ = fs::remove_dir_all(base);
+    }
+
+    #[test]
+    fn verification_rejects_a_truncated_copy() {
+        let base = std::env::temp_dir().join(format!("interface-verify-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&base);
+        fs::create_dir_all(base.join("a")).expect("a");
+        fs::create_dir_all(base.join("b")).expect("b");
+        fs::write(base.join("a/mod.jar"), b"1234").expect("a");
+        fs::write(base.join("b/mod.jar"), b"12").expect("b");
+
+        assert!(verify_copied_tree(&base.join("a"), &base.join("b"), &[]).is_err());
+
+        let _ = fs::remove_dir_all(base);
+    }
+}
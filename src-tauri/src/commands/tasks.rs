@@ -0,0 +1,25 @@
+use tauri::AppHandle;
+
+use crate::shared::tasks::{task_registry, ActiveTask, DownloadStatus};
+
+#[tauri::command]
+pub fn list_active_tasks(app: AppHandle) -> Vec<ActiveTask> {
+    task_registry(&app).list()
+}
+
+/// Velocidad global y ETA de cada tarea activa, para el indicador de la barra de estado.
+#[tauri::command]
+pub fn get_download_status(app: AppHandle) -> DownloadStatus {
+    task_registry(&app).download_status()
+}
+
+/// Pide cancelar la tarea; se detiene en su siguiente punto seguro.
+#[tauri::command]
+pub fn cancel_task(app: AppHandle, task_id: String) -> Result<(), String> {
+    if task_registry(&app).cancel(&task_id) {
+        log::info!("🔹 Cancelación solicitada para la tarea {task_id}");
+        Ok(())
+    } else {
+        Err(format!("No hay ninguna tarea activa con id {task_id}"))
+    }
+}
@@ -1,6 +1,9 @@
 use base64::{engine::general_purpose::STANDARD, Engine as _};
 use serde::{Deserialize, Serialize};
 use std::{fs, path::{Path, PathBuf}, time::{SystemTime, UNIX_EPOCH}};
+use tauri::AppHandle;
+
+use crate::app::trusted_root::resolve_trusted_instance_root;
 
 const VISUAL_META_FILE: &str = ".interface-visual.json";
 
@@ -16,11 +19,13 @@ pub struct InstanceVisualMeta {
 
 #[tauri::command]
 pub fn save_instance_visual_media(
+    app: AppHandle,
     instance_root: String,
     file_name: String,
     bytes: Vec<u8>,
     previous_media_path: Option<String>,
 ) -> Result<String, String> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
     if bytes.is_empty() {
         return Err("El archivo visual está vacío.".to_string());
     }
@@ -29,7 +34,7 @@ pub fn save_instance_visual_media(
         .extension()
         .and_then(|value| value.to_str())
         .unwrap_or("bin");
-    let media_dir = PathBuf::from(&instance_root).join(".interface-media");
+    let media_dir = instance_root.path().to_path_buf().join(".interface-media");
     fs::create_dir_all(&media_dir).map_err(|err| format!("No se pudo preparar carpeta media: {err}"))?;
 
     let stamp = SystemTime::now()
@@ -81,15 +86,24 @@ pub fn read_visual_media_as_data_url(media_path: String, media_mime: Option<Stri
 }
 
 #[tauri::command]
-pub fn save_instance_visual_meta(instance_root: String, meta: InstanceVisualMeta) -> Result<(), String> {
-    let path = PathBuf::from(instance_root).join(VISUAL_META_FILE);
+pub fn save_instance_visual_meta(
+    app: AppHandle,
+    instance_root: String,
+    meta: InstanceVisualMeta,
+) -> Result<(), String> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    let path = instance_root.path().join(VISUAL_META_FILE);
     let payload = serde_json::to_string_pretty(&meta).map_err(|err| format!("No se pudo serializar visual meta: {err}"))?;
     fs::write(path, payload).map_err(|err| format!("No se pudo guardar metadata visual: {err}"))
 }
 
 #[tauri::command]
-pub fn load_instance_visual_meta(instance_root: String) -> Result<Option<InstanceVisualMeta>, String> {
-    let path = PathBuf::from(instance_root).join(VISUAL_META_FILE);
+pub fn load_instance_visual_meta(
+    app: AppHandle,
+    instance_root: String,
+) -> Result<Option<InstanceVisualMeta>, String> {
+    let instance_root = resolve_trusted_instance_root(&app, &instance_root)?;
+    let path = instance_root.path().join(VISUAL_META_FILE);
     if !path.exists() {
         return Ok(None);
     }
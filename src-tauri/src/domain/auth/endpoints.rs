@@ -0,0 +1,103 @@
+// URLs de los servicios de autenticación.
+
+const MINECRAFT_SERVICES_BASE: &str = "https://api.minecraftservices.com";
+const XBOX_USER_AUTH_BASE: &str = "https://user.auth.xboxlive.com";
+const XSTS_BASE: &str = "https://xsts.auth.xboxlive.com";
+const MICROSOFT_LOGIN_BASE: &str = "https://login.microsoftonline.com";
+
+#[derive(Debug, Clone, Copy)]
+enum AuthService {
+    MinecraftServices,
+    XboxUserAuth,
+    Xsts,
+    MicrosoftLogin,
+}
+
+impl AuthService {
+    fn official_base(self) -> &'static str {
+        match self {
+            Self::MinecraftServices => MINECRAFT_SERVICES_BASE,
+            Self::XboxUserAuth => XBOX_USER_AUTH_BASE,
+            Self::Xsts => XSTS_BASE,
+            Self::MicrosoftLogin => MICROSOFT_LOGIN_BASE,
+        }
+    }
+}
+
+#[cfg(not(feature = "dev-auth"))]
+fn base_url(service: AuthService) -> &'static str {
+    service.official_base()
+}
+
+#[cfg(feature = "dev-auth")]
+fn base_url(service: AuthService) -> String {
+    static WARNED: std::sync::Once = std::sync::Once::new();
+    let variable = match service {
+        AuthService::MinecraftServices => "DEV_AUTH_MINECRAFT_SERVICES_URL",
+        AuthService::XboxUserAuth => "DEV_AUTH_XBOX_USER_URL",
+        AuthService::Xsts => "DEV_AUTH_XSTS_URL",
+        AuthService::MicrosoftLogin => "DEV_AUTH_MICROSOFT_LOGIN_URL",
+    };
+    match std::env::var(variable) {
+        Ok(url) if !url.trim().is_empty() => {
+            WARNED.call_once(|| {
+                log::warn!(
+                    "⚠ Modo desarrollador de autenticación activo: los servicios de Microsoft/Xbox/Minecraft se sustituyen por las URLs de DEV_AUTH_*."
+                );
+            });
+            url.trim().trim_end_matches('/').to_string()
+        }
+        _ => service.official_base().to_string(),
+    }
+}
+
+/// `https://api.minecraftservices.com{path}`.
+pub fn minecraft_services_url(path: &str) -> String {
+    format!("{}{path}", base_url(AuthService::MinecraftServices))
+}
+
+pub fn minecraft_profile_url() -> String {
+    minecraft_services_url("/minecraft/profile")
+}
+
+pub fn minecraft_entitlements_url() -> String {
+    minecraft_services_url("/entitlements/mcstore")
+}
+
+pub fn xbox_user_auth_url() -> String {
+    format!("{}/user/authenticate", base_url(AuthService::XboxUserAuth))
+}
+
+pub fn xsts_authorize_url() -> String {
+    format!("{}/xsts/authorize", base_url(AuthService::Xsts))
+}
+
+/// Endpoint de tokens OAuth (canje del código y refresh). La autorización en el navegador
+/// sigue yendo siempre a Microsoft.
+pub fn microsoft_token_url() -> String {
+    format!(
+        "{}/consumers/oauth2/v2.0/token",
+        base_url(AuthService::MicrosoftLogin)
+    )
+}
+
+#[cfg(all(test, not(feature = "dev-auth")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_build_always_uses_official_hosts() {
+        assert_eq!(
+            minecraft_profile_url(),
+            "https://api.minecraftservices.com/minecraft/profile"
+        );
+        assert_eq!(
+            xsts_authorize_url(),
+            "https://xsts.auth.xboxlive.com/xsts/authorize"
+        );
+        assert_eq!(
+            microsoft_token_url(),
+            "https://login.microsoftonline.com/consumers/oauth2/v2.0/token"
+        );
+    }
+}
@@ -3,7 +3,7 @@ use serde::Deserialize;
 use sha2::{Digest, Sha256};
 use uuid::Uuid;
 
-use crate::domain::auth::tokens::MicrosoftTokenResponse;
+use crate::domain::auth::{endpoints::microsoft_token_url, tokens::MicrosoftTokenResponse};
 
 pub const MICROSOFT_CLIENT_ID: &str = "7ce1b3e8-48d7-4a9d-9329-7e11f988df39";
 pub const MICROSOFT_SCOPES: &str = "XboxLive.signin offline_access";
@@ -14,8 +14,6 @@ pub const MICROSOFT_REDIRECT_URI: &str = "https://login.live.com/oauth20_desktop
 const AUTHORIZE_ENDPOINT: &str =
     "https://login.microsoftonline.com/consumers/oauth2/v2.0/authorize";
 
-const TOKEN_ENDPOINT: &str = "https://login.microsoftonline.com/consumers/oauth2/v2.0/token";
-
 /* =========================================================
    PKCE
 ========================================================= */
@@ -107,7 +105,7 @@ pub async fn refresh_microsoft_access_token(
     let params = build_refresh_token_params(refresh_token);
 
     let response = client
-        .post(TOKEN_ENDPOINT)
+        .post(microsoft_token_url())
         .form(&params)
         .send()
         .await
@@ -144,7 +142,7 @@ pub async fn exchange_authorization_code(
     let params = build_token_params(code, verifier)?;
 
     let response = client
-        .post(TOKEN_ENDPOINT)
+        .post(microsoft_token_url())
         .form(&params)
         .send()
         .await
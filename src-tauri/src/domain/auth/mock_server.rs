@@ -0,0 +1,132 @@
+// Servidor simulado de autenticación para los tests de dev-auth.
+
+use std::{net::SocketAddr, sync::mpsc, thread};
+
+use axum::{
+    http::{HeaderMap, StatusCode},
+    routing::{get, post},
+    Json, Router,
+};
+use serde_json::{json, Value};
+
+pub const MOCK_PROFILE_ID: &str = "069a79f444e94726a5befca90e38aaf5";
+pub const MOCK_PROFILE_NAME: &str = "DevPlayer";
+pub const MOCK_REFRESH_TOKEN: &str = "mock-msa-refresh";
+pub const MOCK_MINECRAFT_TOKEN: &str = "mock-minecraft-token";
+
+pub struct MockAuthServer {
+    pub base_url: String,
+}
+
+impl MockAuthServer {
+    /// Arranca el servidor en un puerto libre de `127.0.0.1` en su propio hilo.
+    pub fn start() -> Self {
+        let (ready, address) = mpsc::channel::<SocketAddr>();
+        thread::spawn(move || {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .expect("runtime del mock");
+            runtime.block_on(async move {
+                let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+                    .await
+                    .expect("bind del mock");
+                ready
+                    .send(listener.local_addr().expect("dirección del mock"))
+                    .expect("aviso de arranque");
+                axum::serve(listener, router()).await.expect("mock auth");
+            });
+        });
+        let address = address.recv().expect("mock arrancado");
+        Self {
+            base_url: format!("http://{address}"),
+        }
+    }
+
+    /// Apunta todos los servicios de autenticación a este servidor.
+    pub fn install_env(&self) {
+        for variable in [
+            "DEV_AUTH_MINECRAFT_SERVICES_URL",
+            "DEV_AUTH_XBOX_USER_URL",
+            "DEV_AUTH_XSTS_URL",
+            "DEV_AUTH_MICROSOFT_LOGIN_URL",
+        ] {
+            std::env::set_var(variable, &self.base_url);
+        }
+    }
+}
+
+fn router() -> Router {
+    Router::new()
+        .route(
+            "/consumers/oauth2/v2.0/token",
+            post(|body: String| async move {
+                if !body.contains(&format!("refresh_token={MOCK_REFRESH_TOKEN}")) {
+                    return (
+                        StatusCode::BAD_REQUEST,
+                        Json(json!({ "error": "invalid_grant" })),
+                    );
+                }
+                (
+                    StatusCode::OK,
+                    Json(json!({
+                        "access_token": "mock-msa-access",
+                        "refresh_token": MOCK_REFRESH_TOKEN,
+                        "expires_in": 3600,
+                    })),
+                )
+            }),
+        )
+        .route(
+            "/user/authenticate",
+            post(|| async { Json(xbox_token("mock-xbl-token")) }),
+        )
+        .route(
+            "/xsts/authorize",
+            post(|| async { Json(xbox_token("mock-xsts-token")) }),
+        )
+        .route(
+            "/authentication/login_with_xbox",
+            post(|| async {
+                Json(json!({
+                    "access_token": MOCK_MINECRAFT_TOKEN,
+                    "expires_in": 86400,
+                }))
+            }),
+        )
+        .route(
+            "/minecraft/profile",
+            get(|headers: HeaderMap| async move {
+                authorized(&headers, json!({ "id": MOCK_PROFILE_ID, "name": MOCK_PROFILE_NAME }))
+            }),
+        )
+        .route(
+            "/entitlements/mcstore",
+            get(|headers: HeaderMap| async move {
+                authorized(
+                    &headers,
+                    json!({ "items": [{ "name": "product_minecraft" }, { "name": "game_minecraft" }] }),
+                )
+            }),
+        )
+}
+
+fn xbox_token(token: &str) -> Value {
+    json!({ "Token": token, "DisplayClaims": { "xui": [{ "uhs": "mock-uhs" }] } })
+}
+
+fn authorized(headers: &HeaderMap, body: Value) -> (StatusCode, Json<Value>) {
+    let expected = format!("Bearer {MOCK_MINECRAFT_TOKEN}");
+    let valid = headers
+        .get("authorization")
+        .and_then(|value| value.to_str().ok())
+        == Some(expected.as_str());
+    if valid {
+        (StatusCode::OK, Json(body))
+    } else {
+        (
+            StatusCode::UNAUTHORIZED,
+            Json(json!({ "error": "UNAUTHORIZED" })),
+        )
+    }
+}
@@ -1,4 +1,7 @@
+pub mod endpoints;
 pub mod microsoft;
 pub mod profile;
 pub mod tokens;
 pub mod xbox;
+#[cfg(all(test, feature = "dev-auth"))]
+pub mod mock_server;
@@ -1,18 +1,18 @@
 use serde::Serialize;
 use serde_json::json;
 
-use crate::domain::auth::{
-    profile::MinecraftProfile,
-    tokens::{MinecraftLoginResponse, XboxAuthResponse},
+use crate::{
+    domain::auth::{
+        endpoints::{
+            minecraft_entitlements_url, minecraft_profile_url, minecraft_services_url,
+            xbox_user_auth_url, xsts_authorize_url,
+        },
+        profile::MinecraftProfile,
+        tokens::{MinecraftLoginResponse, XboxAuthResponse},
+    },
+    infrastructure::http::rate_limit::{self, ApiRequestError},
 };
 
-const XBOX_AUTH_URL: &str = "https://user.auth.xboxlive.com/user/authenticate";
-const XSTS_AUTH_URL: &str = "https://xsts.auth.xboxlive.com/xsts/authorize";
-const MINECRAFT_LOGIN_URL: &str =
-    "https://api.minecraftservices.com/authentication/login_with_xbox";
-const MINECRAFT_PROFILE_URL: &str = "https://api.minecraftservices.com/minecraft/profile";
-const MINECRAFT_ENTITLEMENTS_URL: &str = "https://api.minecraftservices.com/entitlements/mcstore";
-
 #[derive(Debug, Serialize)]
 struct XstsProperties<'a> {
     #[serde(rename = "SandboxId")]
@@ -81,7 +81,7 @@ pub async fn authenticate_with_xbox_live(
     });
 
     let response = client
-        .post(XBOX_AUTH_URL)
+        .post(xbox_user_auth_url())
         .header("Accept", "application/json")
         .json(&payload)
         .send()
@@ -121,7 +121,7 @@ pub async fn authorize_xsts(
     let payload = build_xsts_request(xbox_token);
 
     let response = client
-        .post(XSTS_AUTH_URL)
+        .post(xsts_authorize_url())
         .header("Accept", "application/json")
         .json(&payload)
         .send()
@@ -158,7 +158,7 @@ pub async fn login_minecraft_with_xbox(
     client: &reqwest::Client,
     uhs: &str,
     xsts_token: &str,
-) -> Result<MinecraftLoginResponse, String> {
+) -> Result<MinecraftLoginResponse, ApiRequestError> {
     #[derive(Debug, Serialize)]
     struct MinecraftLoginRequest {
         #[serde(rename = "identityToken")]
@@ -169,32 +169,33 @@ pub async fn login_minecraft_with_xbox(
         identity_token: build_minecraft_identity_token(uhs, xsts_token),
     };
 
-    let response = client
-        .post(MINECRAFT_LOGIN_URL)
-        .header("Accept", "application/json")
-        .json(&payload)
-        .send()
-        .await
-        .map_err(|err| format!("No se pudo autenticar en Minecraft Services: {err}"))?;
+    let url = minecraft_services_url("/authentication/login_with_xbox");
+    let response = rate_limit::send(&url, || {
+        client
+            .post(&url)
+            .header("Accept", "application/json")
+            .json(&payload)
+    })
+    .await
+    .map_err(|err| err.with_context("No se pudo autenticar en Minecraft Services"))?;
 
     let status = response.status();
     if !status.is_success() {
         let body = response.text().await.unwrap_or_default();
         if status.as_u16() == 403 {
-            return Err(format!(
-                "Minecraft Services 403 Forbidden. Body completo: {body}"
-            ));
+            return Err(format!("Minecraft Services 403 Forbidden. Body completo: {body}").into());
         }
 
         return Err(format!(
             "Minecraft Services devolvió error HTTP: {status}. Body completo: {body}"
-        ));
+        )
+        .into());
     }
 
     response
         .json::<MinecraftLoginResponse>()
         .await
-        .map_err(|err| format!("No se pudo leer access token de Minecraft: {err}"))
+        .map_err(|err| format!("No se pudo leer access token de Minecraft: {err}").into())
 }
 
 #[derive(Debug, serde::Deserialize)]
@@ -207,13 +208,15 @@ pub async fn has_minecraft_license(
     client: &reqwest::Client,
     minecraft_access_token: &str,
 ) -> Result<bool, String> {
-    let response = client
-        .get(MINECRAFT_ENTITLEMENTS_URL)
-        .header("Authorization", format!("Bearer {minecraft_access_token}"))
-        .header("Accept", "application/json")
-        .send()
-        .await
-        .map_err(|err| format!("No se pudo consultar entitlements de Minecraft: {err}"))?;
+    let url = minecraft_entitlements_url();
+    let response = rate_limit::send(&url, || {
+        client
+            .get(&url)
+            .header("Authorization", format!("Bearer {minecraft_access_token}"))
+            .header("Accept", "application/json")
+    })
+    .await
+    .map_err(|err| err.describe("No se pudo consultar entitlements de Minecraft"))?;
 
     let status = response.status();
     if !status.is_success() {
@@ -242,13 +245,15 @@ pub async fn read_minecraft_profile(
     client: &reqwest::Client,
     minecraft_access_token: &str,
 ) -> Result<MinecraftProfile, String> {
-    let response = client
-        .get(MINECRAFT_PROFILE_URL)
-        .header("Authorization", format!("Bearer {minecraft_access_token}"))
-        .header("Accept", "application/json")
-        .send()
-        .await
-        .map_err(|err| format!("No se pudo consultar perfil de Minecraft: {err}"))?;
+    let url = minecraft_profile_url();
+    let response = rate_limit::send(&url, || {
+        client
+            .get(&url)
+            .header("Authorization", format!("Bearer {minecraft_access_token}"))
+            .header("Accept", "application/json")
+    })
+    .await
+    .map_err(|err| err.describe("No se pudo consultar perfil de Minecraft"))?;
 
     let status = response.status();
     if !status.is_success() {
@@ -290,12 +295,10 @@ mod tests {
 
     #[test]
     fn entitlements_unauthorized_hint_describes_common_causes() {
-        let message = build_entitlements_unauthorized_hint("abc123");
+        let message = build_entitlements_unauthorized_hint();
 
         assert!(message.contains("HTTP 401"));
         assert!(message.contains("/entitlements/mcstore"));
         assert!(message.contains("login_with_xbox"));
-        assert!(message.contains("6 caracteres"));
-        assert!(message.contains("abc123"));
     }
 }
@@ -0,0 +1,436 @@
+// Lectura de instancias de GDLauncher y ATLauncher.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum ExternalLauncher {
+    #[serde(rename = "GDLauncher")]
+    GdLauncher,
+    #[serde(rename = "ATLauncher")]
+    AtLauncher,
+}
+
+impl ExternalLauncher {
+    pub fn display_name(self) -> &'static str {
+        match self {
+            Self::GdLauncher => "GDLauncher",
+            Self::AtLauncher => "ATLauncher",
+        }
+    }
+
+    /// Carpeta con los `libraries/`, `assets/` y `versions/` compartidos por las instancias.
+    pub fn shared_runtime_dir(self, launcher_root: &Path) -> PathBuf {
+        match self {
+            Self::GdLauncher => launcher_root.join("datastore"),
+            Self::AtLauncher => launcher_root.to_path_buf(),
+        }
+    }
+
+    /// Raíces por defecto del launcher en este sistema (existan o no).
+    pub fn default_roots(self) -> Vec<PathBuf> {
+        let dir_name = match self {
+            Self::GdLauncher => "gdlauncher_next",
+            Self::AtLauncher => "ATLauncher",
+        };
+        let mut roots = Vec::new();
+
+        #[cfg(target_os = "windows")]
+        {
+            if let Some(app_data) = std::env::var_os("APPDATA").map(PathBuf::from) {
+                roots.push(app_data.join(dir_name));
+            }
+        }
+
+        #[cfg(target_os = "macos")]
+        {
+            if let Some(home) = std::env::var_os("HOME").map(PathBuf::from) {
+                roots.push(home.join("Library/Application Support").join(dir_name));
+            }
+        }
+
+        #[cfg(all(unix, not(target_os = "macos")))]
+        {
+            if let Some(home) = std::env::var_os("HOME").map(PathBuf::from) {
+                match self {
+                    Self::GdLauncher => roots.push(home.join(".config").join(dir_name)),
+                    Self::AtLauncher => {
+                        roots.push(home.join(".local/share/atlauncher"));
+                        roots.push(home.join(dir_name));
+                    }
+                }
+            }
+        }
+
+        roots
+    }
+}
+
+/// Raíces por defecto de ambos launchers, en el orden en que se prueban.
+pub fn known_external_launcher_roots() -> Vec<(ExternalLauncher, PathBuf)> {
+    [ExternalLauncher::GdLauncher, ExternalLauncher::AtLauncher]
+        .into_iter()
+        .flat_map(|launcher| {
+            launcher
+                .default_roots()
+                .into_iter()
+                .map(move |root| (launcher, root))
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExternalMod {
+    pub name: String,
+    pub file_name: String,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExternalInstanceManifest {
+    pub launcher: ExternalLauncher,
+    pub name: String,
+    pub minecraft_version: String,
+    /// En minúsculas: `vanilla`, `forge`, `neoforge`, `fabric` o `quilt`.
+    pub loader: String,
+    /// Sin el prefijo `<mc>-` que usan ambos launchers para Forge; `None` en vanilla.
+    pub loader_version: Option<String>,
+    pub mods: Vec<ExternalMod>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GdConfig {
+    loader: Option<GdLoader>,
+    /// Formato plano de versiones antiguas.
+    mc_version: Option<String>,
+    mod_loader: Option<String>,
+    loader_version: Option<String>,
+    #[serde(default)]
+    mods: Vec<GdMod>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GdLoader {
+    loader_type: Option<String>,
+    loader_version: Option<String>,
+    mc_version: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GdMod {
+    file_name: Option<String>,
+    display_name: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AtInstance {
+    /// Versión de Minecraft: el instance.json es también el version.json de la instancia.
+    id: Option<String>,
+    launcher: Option<AtLauncherSection>,
+    /// Formato plano de versiones antiguas.
+    mc_version: Option<String>,
+    loader: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AtLauncherSection {
+    name: Option<String>,
+    loader_version: Option<AtLoaderVersion>,
+    #[serde(default)]
+    mods: Vec<AtMod>,
+}
+
+#[derive(Deserialize)]
+struct AtLoaderVersion {
+    #[serde(rename = "type")]
+    kind: Option<String>,
+    version: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct AtMod {
+    name: Option<String>,
+    file: Option<String>,
+    #[serde(rename = "type")]
+    kind: Option<String>,
+    #[serde(default)]
+    disabled: bool,
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+fn normalize_loader(loader: Option<String>) -> String {
+    match non_empty(loader)
+        .map(|loader| loader.to_ascii_lowercase())
+        .as_deref()
+    {
+        None | Some("vanilla") | Some("minecraft") => "vanilla".to_string(),
+        Some("neoforged") => "neoforge".to_string(),
+        Some(other) => other.to_string(),
+    }
+}
+
+fn normalize_loader_version(
+    loader: &str,
+    version: Option<String>,
+    minecraft_version: &str,
+) -> Option<String> {
+    if loader == "vanilla" {
+        return None;
+    }
+    let version = non_empty(version)?;
+    let stripped = version
+        .strip_prefix(minecraft_version)
+        .and_then(|rest| rest.strip_prefix('-'))
+        .unwrap_or(&version);
+    Some(stripped.to_string())
+}
+
+/// Interpreta un `config.json` de GDLauncher; `dir_name` es el nombre de la instancia.
+pub fn parse_gdlauncher_config(raw: &str, dir_name: &str) -> Option<ExternalInstanceManifest> {
+    let config: GdConfig = serde_json::from_str(raw).ok()?;
+    let (loader_type, loader_version, mc_version) = match config.loader {
+        Some(loader) => (loader.loader_type, loader.loader_version, loader.mc_version),
+        None => (config.mod_loader, config.loader_version, config.mc_version),
+    };
+    let minecraft_version = non_empty(mc_version)?;
+    let loader = normalize_loader(loader_type);
+    let loader_version = normalize_loader_version(&loader, loader_version, &minecraft_version);
+    let mods = config
+        .mods
+        .into_iter()
+        .filter_map(|entry| {
+            let file_name = non_empty(entry.file_name)?;
+            Some(ExternalMod {
+                name: non_empty(entry.display_name).unwrap_or_else(|| file_name.clone()),
+                enabled: !file_name.ends_with(".disabled"),
+                file_name,
+            })
+        })
+        .collect();
+    Some(ExternalInstanceManifest {
+        launcher: ExternalLauncher::GdLauncher,
+        name: dir_name.to_string(),
+        minecraft_version,
+        loader,
+        loader_version,
+        mods,
+    })
+}
+
+/// Interpreta un `instance.json` de ATLauncher; `dir_name` se usa si no trae nombre.
+pub fn parse_atlauncher_instance(raw: &str, dir_name: &str) -> Option<ExternalInstanceManifest> {
+    let instance: AtInstance = serde_json::from_str(raw).ok()?;
+    let Some(section) = instance.launcher else {
+        // Sin la sección `launcher` solo se acepta el formato plano con `mcVersion`,
+        // para no confundir un version.json cualquiera con una instancia.
+        let minecraft_version = non_empty(instance.mc_version)?;
+        return Some(ExternalInstanceManifest {
+            launcher: ExternalLauncher::AtLauncher,
+            name: dir_name.to_string(),
+            minecraft_version,
+            loader: normalize_loader(instance.loader),
+            loader_version: None,
+            mods: Vec::new(),
+        });
+    };
+    let minecraft_version = non_empty(instance.id.or(instance.mc_version))?;
+    let (loader_type, loader_version) = section
+        .loader_version
+        .map(|loader| (loader.kind, loader.version))
+        .unwrap_or_default();
+    let loader = normalize_loader(loader_type);
+    let loader_version = normalize_loader_version(&loader, loader_version, &minecraft_version);
+    let mods = section
+        .mods
+        .into_iter()
+        .filter(|entry| {
+            !entry
+                .kind
+                .as_deref()
+                .is_some_and(|kind| !matches!(kind, "mods" | "coremods"))
+        })
+        .filter_map(|entry| {
+            let file_name = non_empty(entry.file)?;
+            Some(ExternalMod {
+                name: non_empty(entry.name).unwrap_or_else(|| file_name.clone()),
+                enabled: !entry.disabled,
+                file_name,
+            })
+        })
+        .collect();
+    Some(ExternalInstanceManifest {
+        launcher: ExternalLauncher::AtLauncher,
+        name: non_empty(section.name).unwrap_or_else(|| dir_name.to_string()),
+        minecraft_version,
+        loader,
+        loader_version,
+        mods,
+    })
+}
+
+/// Lee el manifiesto de GDLauncher o ATLauncher de `instance_dir`, si lo hay.
+pub fn read_external_manifest(instance_dir: &Path) -> Option<ExternalInstanceManifest> {
+    let dir_name = instance_dir
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let read = |file: &str| fs::read_to_string(instance_dir.join(file)).ok();
+    read("instance.json")
+        .and_then(|raw| parse_atlauncher_instance(&raw, &dir_name))
+        .or_else(|| read("config.json").and_then(|raw| parse_gdlauncher_config(&raw, &dir_name)))
+}
+
+/// Carpetas compartidas del launcher de origen para una instancia en
+/// `<raíz>/instances/<nombre>`, aunque el launcher esté instalado en otra ubicación.
+pub fn shared_runtime_roots(instance_dir: &Path) -> Vec<PathBuf> {
+    let Some(instances_dir) = instance_dir.parent() else {
+        return Vec::new();
+    };
+    let is_instances_dir = instances_dir
+        .file_name()
+        .is_some_and(|name| name.eq_ignore_ascii_case("instances"));
+    let Some(launcher_root) = instances_dir.parent().filter(|_| is_instances_dir) else {
+        return Vec::new();
+    };
+    read_external_manifest(instance_dir)
+        .map(|manifest| vec![manifest.launcher.shared_runtime_dir(launcher_root)])
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GDLAUNCHER_CONFIG: &str = r#"{
+        "loader": {
+            "loaderType": "forge",
+            "loaderVersion": "1.16.5-36.2.39",
+            "mcVersion": "1.16.5",
+            "fileID": 3497520,
+            "projectID": 391382,
+            "source": "curseforge"
+        },
+        "timePlayed": 5400,
+        "lastPlayed": 1700000000000,
+        "mods": [
+            { "fileName": "jei-1.16.5-7.7.1.jar", "displayName": "Just Enough Items", "projectID": 238222 },
+            { "fileName": "journeymap-1.16.5.jar.disabled", "projectID": 32274 }
+        ]
+    }"#;
+
+    const ATLAUNCHER_INSTANCE: &str = r#"{
+        "id": "1.20.1",
+        "type": "release",
+        "mainClass": "net.fabricmc.loader.impl.launch.knot.KnotClient",
+        "libraries": [],
+        "launcher": {
+            "name": "Fabulously Optimized",
+            "pack": "Fabulously Optimized",
+            "version": "5.4.1",
+            "loaderVersion": { "version": "0.14.22", "type": "Fabric" },
+            "mods": [
+                { "name": "Sodium", "file": "sodium-fabric-0.5.3.jar", "type": "mods", "disabled": false },
+                { "name": "Iris", "file": "iris-1.6.10.jar", "type": "mods", "disabled": true },
+                { "name": "Complementary", "file": "complementary.zip", "type": "shaderpack" }
+            ]
+        }
+    }"#;
+
+    #[test]
+    fn parses_gdlauncher_config_and_strips_forge_prefix() {
+        let manifest = parse_gdlauncher_config(GDLAUNCHER_CONFIG, "RLCraft").expect("manifest");
+        assert_eq!(manifest.launcher, ExternalLauncher::GdLauncher);
+        assert_eq!(manifest.name, "RLCraft");
+        assert_eq!(manifest.minecraft_version, "1.16.5");
+        assert_eq!(manifest.loader, "forge");
+        assert_eq!(manifest.loader_version.as_deref(), Some("36.2.39"));
+        assert_eq!(
+            manifest.mods,
+            vec![
+                ExternalMod {
+                    name: "Just Enough Items".to_string(),
+                    file_name: "jei-1.16.5-7.7.1.jar".to_string(),
+                    enabled: true,
+                },
+                ExternalMod {
+                    name: "journeymap-1.16.5.jar.disabled".to_string(),
+                    file_name: "journeymap-1.16.5.jar.disabled".to_string(),
+                    enabled: false,
+                },
+            ]
+        );
+
+        let legacy = parse_gdlauncher_config(
+            r#"{ "mcVersion": "1.12.2", "modLoader": "Vanilla", "loaderVersion": "x" }"#,
+            "Old",
+        )
+        .expect("legacy");
+        assert_eq!(
+            (legacy.loader.as_str(), legacy.loader_version),
+            ("vanilla", None)
+        );
+        assert!(parse_gdlauncher_config(r#"{ "javaArgs": "-Xmx2G" }"#, "Other").is_none());
+    }
+
+    #[test]
+    fn parses_atlauncher_instance_and_resolves_shared_root() {
+        let root = std::env::temp_dir().join(format!("atlauncher-{}", uuid::Uuid::new_v4()));
+        let instance_dir = root.join("instances").join("FabulouslyOptimized");
+        fs::create_dir_all(&instance_dir).expect("instance dir");
+        fs::write(instance_dir.join("instance.json"), ATLAUNCHER_INSTANCE).expect("write");
+
+        let manifest = read_external_manifest(&instance_dir).expect("manifest");
+        assert_eq!(manifest.launcher, ExternalLauncher::AtLauncher);
+        assert_eq!(manifest.name, "Fabulously Optimized");
+        assert_eq!(manifest.minecraft_version, "1.20.1");
+        assert_eq!(manifest.loader, "fabric");
+        assert_eq!(manifest.loader_version.as_deref(), Some("0.14.22"));
+        let files: Vec<(&str, bool)> = manifest
+            .mods
+            .iter()
+            .map(|entry| (entry.file_name.as_str(), entry.enabled))
+            .collect();
+        assert_eq!(
+            files,
+            vec![
+                ("sodium-fabric-0.5.3.jar", true),
+                ("iris-1.6.10.jar", false)
+            ]
+        );
+        assert_eq!(shared_runtime_roots(&instance_dir), vec![root.clone()]);
+
+        let gd_dir = root.join("instances").join("Pack");
+        fs::create_dir_all(&gd_dir).expect("gd dir");
+        fs::write(gd_dir.join("config.json"), GDLAUNCHER_CONFIG).expect("write");
+        assert_eq!(shared_runtime_roots(&gd_dir), vec![root.join("datastore")]);
+
+        // Un version.json suelto no es una instancia de ATLauncher.
+        fs::write(
+            gd_dir.join("instance.json"),
+            r#"{ "id": "1.20.1", "mainClass": "net.minecraft.client.main.Main" }"#,
+        )
+        .expect("write");
+        assert_eq!(
+            read_external_manifest(&gd_dir).map(|manifest| manifest.launcher),
+            Some(ExternalLauncher::GdLauncher)
+        );
+
+        let _ = fs::remove_dir_all(root);
+    }
+}
@@ -1,5 +1,6 @@
 pub mod creator;
 pub mod instance;
+pub mod interop;
 pub mod metadata;
 pub mod structure;
 pub mod validator;
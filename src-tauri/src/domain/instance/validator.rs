@@ -1 +1,387 @@
 // Módulo instance: validator.
+
+use std::{collections::HashSet, sync::OnceLock};
+
+use regex::Regex;
+use serde::Serialize;
+
+use crate::{
+    domain::{java::java_args::normalize_java_args, models::instance::CreateInstancePayload},
+    infrastructure::filesystem::paths::safe_path_component,
+};
+
+pub const MIN_RAM_MB: u32 = 512;
+/// Tope de RAM cuando no se puede leer la memoria del sistema.
+pub const FALLBACK_MAX_RAM_MB: u32 = 32 * 1024;
+pub const MAX_JAVA_ARGS: usize = 64;
+pub const MAX_JAVA_ARG_CHARS: usize = 512;
+pub const SUPPORTED_LOADERS: [&str; 5] = ["vanilla", "fabric", "quilt", "forge", "neoforge"];
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldProblem {
+    /// Campo del payload en camelCase (`ramMb`, `javaArgs[3]`...).
+    pub field: String,
+    pub code: &'static str,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationFailedError {
+    /// Siempre `VALIDATION_FAILED`.
+    pub code: &'static str,
+    pub message: String,
+    pub problems: Vec<FieldProblem>,
+}
+
+/// Versiones del manifest cacheado; `fresh` indica que no ha caducado todavía.
+#[derive(Debug, Clone, Default)]
+pub struct KnownVersions {
+    pub ids: HashSet<String>,
+    pub fresh: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreationLimits {
+    pub max_ram_mb: u32,
+    pub known_versions: Option<KnownVersions>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidatedPayload {
+    /// Loader en minúsculas, con `quilit` corregido y vacío como `vanilla`.
+    pub loader: String,
+    pub warnings: Vec<String>,
+}
+
+/// RAM máxima asignable según la memoria física total (en MB).
+pub fn max_ram_for_system(total_memory_mb: Option<u64>) -> u32 {
+    total_memory_mb
+        .map(|total| u32::try_from(total).unwrap_or(u32::MAX).max(MIN_RAM_MB))
+        .unwrap_or(FALLBACK_MAX_RAM_MB)
+}
+
+pub fn normalize_loader(loader: &str) -> String {
+    match loader.trim().to_ascii_lowercase().as_str() {
+        "" => "vanilla".to_string(),
+        "quilit" => "quilt".to_string(),
+        other => other.to_string(),
+    }
+}
+
+fn semver_like() -> &'static Regex {
+    static SEMVER: OnceLock<Regex> = OnceLock::new();
+    SEMVER.get_or_init(|| {
+        Regex::new(r"^\d+\.\d+\.\d+(?:[-+.][0-9A-Za-z.+-]+)?$").expect("Regex semver inválida")
+    })
+}
+
+/// `47.2.0`, `14.23.5.2859` o con prefijo de Minecraft (`1.20.1-47.2.0`).
+fn forge_like() -> &'static Regex {
+    static FORGE: OnceLock<Regex> = OnceLock::new();
+    FORGE.get_or_init(|| {
+        Regex::new(r"^(?:\d+\.\d+(?:\.\d+)?-)?\d+\.\d+\.\d+(?:\.\d+)?(?:-[0-9A-Za-z.]+)?$")
+            .expect("Regex de versión de Forge inválida")
+    })
+}
+
+fn problem(field: impl Into<String>, code: &'static str, message: String) -> FieldProblem {
+    FieldProblem {
+        field: field.into(),
+        code,
+        message,
+    }
+}
+
+fn validate_group(group: &str, problems: &mut Vec<FieldProblem>) {
+    let group = group.trim();
+    if group.is_empty() {
+        return;
+    }
+    if group.contains(['/', '\\']) || group.contains("..") {
+        problems.push(problem(
+            "group",
+            "invalid_chars",
+            "El grupo no puede contener separadores de ruta ni '..'.".to_string(),
+        ));
+    } else if let Err(err) = safe_path_component(group) {
+        problems.push(problem("group", err.code(), err.to_string()));
+    }
+}
+
+fn validate_loader_version(loader: &str, version: &str, problems: &mut Vec<FieldProblem>) {
+    let version = version.trim();
+    if loader == "vanilla" {
+        return;
+    }
+    if version.is_empty() {
+        problems.push(problem(
+            "loaderVersion",
+            "required",
+            format!("La versión del loader es obligatoria para {loader}."),
+        ));
+        return;
+    }
+    let valid = match loader {
+        "forge" => forge_like().is_match(version),
+        _ => semver_like().is_match(version),
+    };
+    if !valid {
+        problems.push(problem(
+            "loaderVersion",
+            "invalid_format",
+            format!("La versión '{version}' no tiene un formato válido para {loader}."),
+        ));
+    }
+}
+
+fn validate_java_args(java_args: &[String], problems: &mut Vec<FieldProblem>) {
+    if java_args.len() > MAX_JAVA_ARGS {
+        problems.push(problem(
+            "javaArgs",
+            "too_many",
+            format!(
+                "Se admiten como máximo {MAX_JAVA_ARGS} argumentos de Java ({} recibidos).",
+                java_args.len()
+            ),
+        ));
+    }
+    let mut all_short = true;
+    for (index, arg) in java_args.iter().enumerate() {
+        if arg.chars().count() > MAX_JAVA_ARG_CHARS {
+            all_short = false;
+            problems.push(problem(
+                format!("javaArgs[{index}]"),
+                "too_long",
+                format!(
+                    "Cada argumento de Java admite como máximo {MAX_JAVA_ARG_CHARS} caracteres."
+                ),
+            ));
+        }
+    }
+    if all_short {
+        if let Err(err) = normalize_java_args(java_args) {
+            problems.push(problem("javaArgs", "invalid", err));
+        }
+    }
+}
+
+/// Valida el payload completo. Devuelve el loader normalizado y los avisos no bloqueantes,
+/// o todos los problemas encontrados como `VALIDATION_FAILED`.
+pub fn validate_create_payload(
+    payload: &CreateInstancePayload,
+    limits: &CreationLimits,
+) -> Result<ValidatedPayload, ValidationFailedError> {
+    let mut problems = Vec::new();
+    let mut warnings = Vec::new();
+
+    if payload.name.trim().is_empty() {
+        problems.push(problem(
+            "name",
+            "required",
+            "El nombre de la instancia es obligatorio.".to_string(),
+        ));
+    } else if let Err(err) = safe_path_component(&payload.name) {
+        problems.push(problem("name", err.code(), err.to_string()));
+    }
+
+    validate_group(&payload.group, &mut problems);
+
+    let minecraft_version = payload.minecraft_version.trim();
+    if minecraft_version.is_empty() {
+        problems.push(problem(
+            "minecraftVersion",
+            "required",
+            "La versión de Minecraft es obligatoria.".to_string(),
+        ));
+    } else {
+        match &limits.known_versions {
+            Some(known) if known.ids.contains(minecraft_version) => {}
+            Some(known) if known.fresh => problems.push(problem(
+                "minecraftVersion",
+                "unknown_version",
+                format!("La versión {minecraft_version} no existe en el manifest oficial."),
+            )),
+            _ => warnings.push(format!(
+                "No se pudo comprobar la versión {minecraft_version} contra el manifest oficial; se verificará al descargarla."
+            )),
+        }
+    }
+
+    let loader = normalize_loader(&payload.loader);
+    if SUPPORTED_LOADERS.contains(&loader.as_str()) {
+        validate_loader_version(&loader, &payload.loader_version, &mut problems);
+    } else {
+        problems.push(problem(
+            "loader",
+            "unsupported",
+            format!(
+                "Loader no soportado: {}. Usa uno de: {}.",
+                payload.loader.trim(),
+                SUPPORTED_LOADERS.join(", ")
+            ),
+        ));
+    }
+
+    if payload.ram_mb < MIN_RAM_MB || payload.ram_mb > limits.max_ram_mb {
+        problems.push(problem(
+            "ramMb",
+            "out_of_range",
+            format!(
+                "La RAM debe estar entre {MIN_RAM_MB} y {} MB ({} MB recibidos).",
+                limits.max_ram_mb, payload.ram_mb
+            ),
+        ));
+    }
+
+    validate_java_args(&payload.java_args, &mut problems);
+
+    if problems.is_empty() {
+        Ok(ValidatedPayload { loader, warnings })
+    } else {
+        Err(ValidationFailedError {
+            code: "VALIDATION_FAILED",
+            message: format!(
+                "La instancia no se puede crear: {} campo(s) no válido(s).",
+                problems.len()
+            ),
+            problems,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn payload() -> CreateInstancePayload {
+        serde_json::from_value(serde_json::json!({
+            "name": "Survival",
+            "group": "Sin grupo",
+            "minecraftVersion": "1.20.1",
+            "loader": "Quilit",
+            "loaderVersion": "0.26.4-beta.5",
+            "requiredJavaMajor": null,
+            "ramMb": 4096,
+            "javaArgs": ["-XX:+UseG1GC"],
+            "authSession": {
+                "profileId": "id",
+                "profileName": "Steve",
+                "minecraftAccessToken": "token",
+            },
+        }))
+        .expect("payload")
+    }
+
+    fn limits(fresh: bool) -> CreationLimits {
+        CreationLimits {
+            max_ram_mb: 8192,
+            known_versions: Some(KnownVersions {
+                ids: HashSet::from(["1.20.1".to_string()]),
+                fresh,
+            }),
+        }
+    }
+
+    fn codes(err: &ValidationFailedError) -> Vec<(&str, &str)> {
+        err.problems
+            .iter()
+            .map(|problem| (problem.field.as_str(), problem.code))
+            .collect()
+    }
+
+    #[test]
+    fn valid_payload_normalizes_loader_and_each_field_is_rejected() {
+        let ok = validate_create_payload(&payload(), &limits(true)).expect("valid");
+        assert_eq!(ok.loader, "quilt");
+        assert!(ok.warnings.is_empty());
+
+        let reject = |edit: &dyn Fn(&mut CreateInstancePayload)| {
+            let mut payload = payload();
+            edit(&mut payload);
+            let err = validate_create_payload(&payload, &limits(true)).unwrap_err();
+            err.problems
+                .into_iter()
+                .map(|problem| (problem.field, problem.code))
+                .collect::<Vec<_>>()
+        };
+        let one = |field: &str, code: &'static str| vec![(field.to_string(), code)];
+
+        assert_eq!(reject(&|p| p.ram_mb = 256), one("ramMb", "out_of_range"));
+        assert_eq!(reject(&|p| p.ram_mb = 16384), one("ramMb", "out_of_range"));
+        assert_eq!(
+            reject(&|p| p.java_args = vec!["-Xss1M".to_string(); MAX_JAVA_ARGS + 1]),
+            one("javaArgs", "too_many")
+        );
+        assert_eq!(
+            reject(&|p| p.java_args = vec!["-Dx=ok".to_string(), "x".repeat(600)]),
+            one("javaArgs[1]", "too_long")
+        );
+        assert_eq!(
+            reject(&|p| p.group = "../fuera".to_string()),
+            one("group", "invalid_chars")
+        );
+        assert_eq!(
+            reject(&|p| p.group = "NUL".to_string()),
+            one("group", "reserved")
+        );
+        assert_eq!(
+            reject(&|p| p.loader = "liteloader".to_string()),
+            one("loader", "unsupported")
+        );
+        assert_eq!(
+            reject(&|p| p.loader_version = "latest".to_string()),
+            one("loaderVersion", "invalid_format")
+        );
+        assert_eq!(
+            reject(&|p| {
+                p.loader = "forge".to_string();
+                p.loader_version = "47.2".to_string();
+            }),
+            one("loaderVersion", "invalid_format")
+        );
+        assert_eq!(
+            reject(&|p| p.minecraft_version = "1.99.9".to_string()),
+            one("minecraftVersion", "unknown_version")
+        );
+
+        let mut forge = payload();
+        forge.loader = "forge".to_string();
+        forge.loader_version = "1.20.1-47.2.0".to_string();
+        assert!(validate_create_payload(&forge, &limits(true)).is_ok());
+
+        let mut unknown = payload();
+        unknown.minecraft_version = "24w14a".to_string();
+        let stale = validate_create_payload(&unknown, &limits(false)).expect("stale cache");
+        assert_eq!(stale.warnings.len(), 1);
+    }
+
+    #[test]
+    fn all_problems_are_reported_together() {
+        let mut payload = payload();
+        payload.name = "  ".to_string();
+        payload.group = "a/b".to_string();
+        payload.loader = "fabric".to_string();
+        payload.loader_version = String::new();
+        payload.ram_mb = 0;
+
+        let err = validate_create_payload(&payload, &limits(true)).unwrap_err();
+        assert_eq!(err.code, "VALIDATION_FAILED");
+        assert_eq!(
+            codes(&err),
+            vec![
+                ("name", "required"),
+                ("group", "invalid_chars"),
+                ("loaderVersion", "required"),
+                ("ramMb", "out_of_range"),
+            ]
+        );
+        let json = serde_json::to_value(&err).unwrap();
+        assert_eq!(json["problems"][3]["field"], "ramMb");
+        assert!(json["message"].as_str().unwrap().contains('4'));
+
+        assert_eq!(max_ram_for_system(Some(16 * 1024)), 16 * 1024);
+        assert_eq!(max_ram_for_system(None), FALLBACK_MAX_RAM_MB);
+    }
+}
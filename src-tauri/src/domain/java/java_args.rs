@@ -0,0 +1,207 @@
+/// Resultado de normalizar los argumentos de Java escritos por el usuario.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NormalizedJavaArgs {
+    pub args: Vec<String>,
+    /// Descripción legible de cada cambio aplicado, para mostrarlo en los logs.
+    pub changes: Vec<String>,
+}
+
+const MEMORY_FLAG_PREFIXES: [&str; 2] = ["-Xmx", "-Xms"];
+
+/// Divide una línea de argumentos respetando comillas simples y dobles.
+/// Las comillas se eliminan; una comilla sin cerrar se cierra al final de la línea. Si unas
+/// comillas envuelven varios flags (`"-XX:+UseG1GC -Xmn128M"`), se separan.
+pub fn split_java_arg_line(line: &str) -> Vec<String> {
+    if let Some(inner) = strip_wrapping_quotes(line.trim()) {
+        let tokens = split_unquoted(inner);
+        if tokens.len() > 1 && tokens.iter().all(|token| token.starts_with('-')) {
+            return tokens;
+        }
+    }
+    split_unquoted(line)
+}
+
+fn strip_wrapping_quotes(entry: &str) -> Option<&str> {
+    let quote = entry
+        .chars()
+        .next()
+        .filter(|ch| *ch == '"' || *ch == '\'')?;
+    let inner = entry.strip_prefix(quote)?.strip_suffix(quote)?;
+    (!inner.contains(quote)).then_some(inner)
+}
+
+fn split_unquoted(line: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut quote: Option<char> = None;
+
+    for ch in line.chars() {
+        match quote {
+            Some(open) if ch == open => quote = None,
+            Some(_) => current.push(ch),
+            None if ch == '"' || ch == '\'' => quote = Some(ch),
+            None if ch.is_whitespace() => {
+                if !current.is_empty() {
+                    args.push(std::mem::take(&mut current));
+                }
+            }
+            None => current.push(ch),
+        }
+    }
+    if !current.is_empty() {
+        args.push(current);
+    }
+    args
+}
+
+fn memory_flag_prefix(arg: &str) -> Option<&'static str> {
+    MEMORY_FLAG_PREFIXES
+        .iter()
+        .copied()
+        .find(|prefix| arg.starts_with(prefix) && arg.len() > prefix.len())
+}
+
+/// Normaliza `java_args` y deja solo el último `-Xmx`/`-Xms`.
+pub fn normalize_java_args(raw: &[String]) -> Result<NormalizedJavaArgs, String> {
+    let mut args = Vec::new();
+    let mut changes = Vec::new();
+
+    for entry in raw {
+        if entry.contains(['\n', '\r', '\0']) {
+            return Err(format!(
+                "Argumento de Java inválido (contiene salto de línea o byte nulo): {:?}",
+                entry
+            ));
+        }
+        let tokens = split_java_arg_line(entry);
+        match tokens.as_slice() {
+            [] => changes.push(format!("Argumento vacío descartado: {entry:?}")),
+            [single] if single == entry => {}
+            [single] => changes.push(format!("Argumento normalizado: {entry:?} -> {single:?}")),
+            _ => changes.push(format!(
+                "Argumento separado: {entry:?} -> {}",
+                tokens
+                    .iter()
+                    .map(|token| format!("{token:?}"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )),
+        }
+        args.extend(tokens);
+    }
+
+    let mut deduped: Vec<String> = Vec::with_capacity(args.len());
+    for arg in args.into_iter().rev() {
+        if let Some(prefix) = memory_flag_prefix(&arg) {
+            if deduped
+                .iter()
+                .any(|kept| memory_flag_prefix(kept) == Some(prefix))
+            {
+                changes.push(format!("Flag de memoria duplicado descartado: {arg}"));
+                continue;
+            }
+        }
+        deduped.push(arg);
+    }
+    deduped.reverse();
+
+    Ok(NormalizedJavaArgs {
+        args: deduped,
+        changes,
+    })
+}
+
+/// Combina los flags de memoria derivados de `ram_mb` con los del usuario: si el usuario
+/// define `-Xmx` o `-Xms`, ese valor sustituye al calculado en lugar de duplicarse.
+pub fn merge_memory_args(defaults: &[String], user_args: &[String]) -> Vec<String> {
+    defaults
+        .iter()
+        .filter(|default| {
+            let Some(prefix) = memory_flag_prefix(default) else {
+                return true;
+            };
+            !user_args
+                .iter()
+                .any(|arg| memory_flag_prefix(arg) == Some(prefix))
+        })
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{merge_memory_args, normalize_java_args, split_java_arg_line};
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn splitter_handles_messy_real_world_inputs() {
+        let cases: [(&str, &[&str]); 8] = [
+            (
+                "\"-XX:+UseG1GC -Xmn128M\"",
+                &["-XX:+UseG1GC", "-Xmn128M"],
+            ),
+            ("-XX:+UseG1GC -Xmn128M", &["-XX:+UseG1GC", "-Xmn128M"]),
+            ("  -Xss2M   ", &["-Xss2M"]),
+            (
+                "-Dfml.ignoreInvalidMinecraftCertificates=true\t-Dfml.ignorePatchDiscrepancies=true",
+                &[
+                    "-Dfml.ignoreInvalidMinecraftCertificates=true",
+                    "-Dfml.ignorePatchDiscrepancies=true",
+                ],
+            ),
+            ("-Dlog.dir=\"C:\\Mis Logs\"", &["-Dlog.dir=C:\\Mis Logs"]),
+            ("'-Duser.name=Juan Pérez'", &["-Duser.name=Juan Pérez"]),
+            ("\"-XX:+UseZGC", &["-XX:+UseZGC"]),
+            ("\"\"", &[]),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(split_java_arg_line(input), strings(expected), "{input:?}");
+        }
+    }
+
+    #[test]
+    fn normalize_splits_strips_and_reports_changes() {
+        let normalized = normalize_java_args(&strings(&[
+            "\"-XX:+UseG1GC -Xmn128M\"",
+            "-XX:+UnlockExperimentalVMOptions",
+            "   ",
+        ]))
+        .expect("normalizado");
+
+        assert_eq!(
+            normalized.args,
+            strings(&[
+                "-XX:+UseG1GC",
+                "-Xmn128M",
+                "-XX:+UnlockExperimentalVMOptions"
+            ])
+        );
+        assert_eq!(normalized.changes.len(), 2);
+    }
+
+    #[test]
+    fn normalize_keeps_only_last_memory_flag() {
+        let normalized =
+            normalize_java_args(&strings(&["-Xmx2G", "-Xms1G -Xmx4G"])).expect("normalizado");
+        assert_eq!(normalized.args, strings(&["-Xms1G", "-Xmx4G"]));
+    }
+
+    #[test]
+    fn normalize_rejects_newlines_and_null_bytes() {
+        assert!(normalize_java_args(&strings(&["-Xmx2G\n-Xms1G"])).is_err());
+        assert!(normalize_java_args(&strings(&["-Dfoo=\0"])).is_err());
+    }
+
+    #[test]
+    fn user_memory_flags_override_ram_defaults() {
+        let defaults = strings(&["-Xms1024M", "-Xmx2048M"]);
+        assert_eq!(
+            merge_memory_args(&defaults, &strings(&["-Xmx6G", "-XX:+UseG1GC"])),
+            strings(&["-Xms1024M"])
+        );
+        assert_eq!(merge_memory_args(&defaults, &[]), defaults);
+    }
+}
@@ -41,7 +41,7 @@ fn resolve_java_path_from_path_env() -> Option<PathBuf> {
     }
 }
 
-fn parse_java_major(version_output: &str) -> Option<u32> {
+pub fn parse_java_major(version_output: &str) -> Option<u32> {
     let quoted = version_output.split('"').nth(1)?;
 
     if let Some(rest) = quoted.strip_prefix("1.") {
@@ -14,6 +14,16 @@ impl JavaRuntime {
         }
     }
 
+    /// Acepta el nombre de carpeta (`java17`) o el major (`17`).
+    pub fn from_name(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().trim_start_matches("java") {
+            "8" => Some(JavaRuntime::Java8),
+            "17" => Some(JavaRuntime::Java17),
+            "21" => Some(JavaRuntime::Java21),
+            _ => None,
+        }
+    }
+
     pub fn major(self) -> u8 {
         match self {
             JavaRuntime::Java8 => 8,
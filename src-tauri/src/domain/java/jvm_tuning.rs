@@ -0,0 +1,218 @@
+// Flags de JVM por defecto.
+
+use crate::domain::java::java_requirement::parse_mc_version;
+
+/// Desde esta RAM (MB) Java 21 usa ZGC generacional en lugar de G1.
+const ZGC_MIN_RAM_MB: u32 = 8 * 1024;
+/// Desde esta RAM (MB) G1 usa regiones y generación joven más grandes.
+const LARGE_HEAP_RAM_MB: u32 = 12 * 1024;
+
+const GC_SELECTORS: [&str; 6] = [
+    "UseG1GC",
+    "UseZGC",
+    "UseShenandoahGC",
+    "UseParallelGC",
+    "UseSerialGC",
+    "UseConcMarkSweepGC",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JvmTuningInput<'a> {
+    pub java_major: u32,
+    pub ram_mb: u32,
+    pub minecraft_version: &'a str,
+    pub windows: bool,
+}
+
+fn strings(values: &[&str]) -> Vec<String> {
+    values.iter().map(|value| value.to_string()).collect()
+}
+
+/// Recolector y ajustes asociados; se descartan juntos si el usuario elige otro GC.
+fn gc_flags(input: &JvmTuningInput) -> Vec<String> {
+    if input.java_major >= 21 && input.ram_mb >= ZGC_MIN_RAM_MB {
+        return strings(&[
+            "-XX:+UseZGC",
+            "-XX:+ZGenerational",
+            "-XX:+AlwaysPreTouch",
+            "-XX:+UseStringDeduplication",
+        ]);
+    }
+    if input.java_major < 17 {
+        // Los argumentos que el launcher oficial usa con Java 8.
+        return strings(&[
+            "-XX:+UseG1GC",
+            "-XX:+UnlockExperimentalVMOptions",
+            "-XX:G1NewSizePercent=20",
+            "-XX:G1ReservePercent=20",
+            "-XX:MaxGCPauseMillis=50",
+            "-XX:G1HeapRegionSize=32M",
+            "-XX:+UseStringDeduplication",
+        ]);
+    }
+    let large = input.ram_mb >= LARGE_HEAP_RAM_MB;
+    let mut flags = strings(&[
+        "-XX:+UseG1GC",
+        "-XX:+ParallelRefProcEnabled",
+        "-XX:MaxGCPauseMillis=200",
+        "-XX:+UnlockExperimentalVMOptions",
+        "-XX:+DisableExplicitGC",
+        "-XX:+AlwaysPreTouch",
+    ]);
+    flags.extend([
+        format!("-XX:G1NewSizePercent={}", if large { 40 } else { 30 }),
+        format!("-XX:G1MaxNewSizePercent={}", if large { 50 } else { 40 }),
+        format!("-XX:G1HeapRegionSize={}", if large { "16M" } else { "8M" }),
+        format!("-XX:G1ReservePercent={}", if large { 15 } else { 20 }),
+    ]);
+    flags.extend(strings(&[
+        "-XX:G1HeapWastePercent=5",
+        "-XX:G1MixedGCCountTarget=4",
+        "-XX:InitiatingHeapOccupancyPercent=15",
+        "-XX:G1MixedGCLiveThresholdPercent=90",
+        "-XX:G1RSetUpdatingPauseTimePercent=5",
+        "-XX:SurvivorRatio=32",
+        "-XX:+PerfDisableSharedMem",
+        "-XX:MaxTenuringThreshold=1",
+        "-XX:+UseStringDeduplication",
+    ]));
+    flags
+}
+
+/// Minecraft anterior a 1.13 (LWJGL 2) parpadea o se cuelga con el pipeline Direct3D de
+/// Java2D en algunas gráficas Intel/AMD de Windows.
+fn needs_d3d_workaround(input: &JvmTuningInput) -> bool {
+    input.windows
+        && parse_mc_version(input.minecraft_version)
+            .is_ok_and(|(major, minor, _)| major == 1 && minor < 13)
+}
+
+/// Nombre de la opción sin valor ni signo: `-XX:+UseG1GC` y `-XX:-UseG1GC` comparten clave,
+/// igual que `-Dfoo=1` y `-Dfoo=2`.
+pub fn jvm_flag_key(arg: &str) -> Option<String> {
+    if let Some(rest) = arg.strip_prefix("-XX:") {
+        let name = rest.trim_start_matches(['+', '-']);
+        let name = name.split('=').next().unwrap_or(name);
+        return (!name.is_empty()).then(|| format!("-XX:{name}"));
+    }
+    if let Some(rest) = arg.strip_prefix("-D") {
+        let name = rest.split('=').next().unwrap_or(rest);
+        return (!name.is_empty()).then(|| format!("-D{name}"));
+    }
+    if ["-Xmx", "-Xms", "-Xss", "-Xmn"]
+        .iter()
+        .any(|prefix| arg.starts_with(prefix))
+    {
+        return Some(arg[..4].to_string());
+    }
+    None
+}
+
+fn selects_gc(arg: &str) -> bool {
+    arg.strip_prefix("-XX:+")
+        .is_some_and(|name| GC_SELECTORS.contains(&name))
+}
+
+/// Flags curados que faltan en `user_args`, en el orden en que se añaden a la línea de
+/// comandos.
+pub fn default_jvm_flags(input: &JvmTuningInput, user_args: &[String]) -> Vec<String> {
+    let user_keys: Vec<String> = user_args
+        .iter()
+        .filter_map(|arg| jvm_flag_key(arg))
+        .collect();
+    let mut flags = if user_args.iter().any(|arg| selects_gc(arg)) {
+        Vec::new()
+    } else {
+        gc_flags(input)
+    };
+    if needs_d3d_workaround(input) {
+        flags.push("-Dsun.java2d.d3d=false".to_string());
+    }
+    flags.retain(|flag| !jvm_flag_key(flag).is_some_and(|key| user_keys.contains(&key)));
+    flags
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(java_major: u32, ram_mb: u32) -> JvmTuningInput<'static> {
+        JvmTuningInput {
+            java_major,
+            ram_mb,
+            minecraft_version: "1.20.1",
+            windows: false,
+        }
+    }
+
+    #[test]
+    fn picks_collector_by_runtime_and_ram() {
+        let cases: [(u32, u32, &str, &str); 6] = [
+            (8, 4096, "-XX:+UseG1GC", "-XX:G1HeapRegionSize=32M"),
+            (8, 16384, "-XX:+UseG1GC", "-XX:MaxGCPauseMillis=50"),
+            (17, 4096, "-XX:+UseG1GC", "-XX:G1HeapRegionSize=8M"),
+            (17, 16384, "-XX:+UseG1GC", "-XX:G1HeapRegionSize=16M"),
+            (
+                21,
+                6144,
+                "-XX:+UseG1GC",
+                "-XX:G1RSetUpdatingPauseTimePercent=5",
+            ),
+            (21, 8192, "-XX:+UseZGC", "-XX:+ZGenerational"),
+        ];
+        for (major, ram, collector, marker) in cases {
+            let flags = default_jvm_flags(&input(major, ram), &[]);
+            assert!(flags.contains(&collector.to_string()), "{major}/{ram}");
+            assert!(flags.contains(&marker.to_string()), "{major}/{ram}");
+            assert!(flags.contains(&"-XX:+UseStringDeduplication".to_string()));
+            let collectors = flags.iter().filter(|flag| selects_gc(flag)).count();
+            assert_eq!(collectors, 1, "{major}/{ram}");
+        }
+        assert!(!default_jvm_flags(&input(21, 8192), &[])
+            .iter()
+            .any(|flag| flag.contains("G1")));
+
+        let mut legacy_windows = input(8, 4096);
+        legacy_windows.minecraft_version = "1.12.2";
+        legacy_windows.windows = true;
+        assert!(
+            default_jvm_flags(&legacy_windows, &[]).contains(&"-Dsun.java2d.d3d=false".to_string())
+        );
+        legacy_windows.minecraft_version = "1.16.5";
+        assert!(!default_jvm_flags(&legacy_windows, &[])
+            .contains(&"-Dsun.java2d.d3d=false".to_string()));
+    }
+
+    #[test]
+    fn user_args_take_precedence() {
+        let user = |values: &[&str]| strings(values);
+
+        let flags = default_jvm_flags(
+            &input(17, 4096),
+            &user(&["-XX:MaxGCPauseMillis=100", "-XX:-UseStringDeduplication"]),
+        );
+        assert!(!flags
+            .iter()
+            .any(|flag| flag.starts_with("-XX:MaxGCPauseMillis")));
+        assert!(!flags.contains(&"-XX:+UseStringDeduplication".to_string()));
+        assert!(flags.contains(&"-XX:+UseG1GC".to_string()));
+
+        // Un GC elegido por el usuario descarta todo el bloque del recolector.
+        for (major, ram) in [(8, 4096), (17, 16384), (21, 8192)] {
+            let flags = default_jvm_flags(&input(major, ram), &user(&["-XX:+UseShenandoahGC"]));
+            assert!(flags.is_empty(), "{major}/{ram}: {flags:?}");
+        }
+
+        let mut legacy_windows = input(8, 2048);
+        legacy_windows.minecraft_version = "1.8.9";
+        legacy_windows.windows = true;
+        let flags = default_jvm_flags(&legacy_windows, &user(&["-Dsun.java2d.d3d=true"]));
+        assert!(!flags
+            .iter()
+            .any(|flag| flag.starts_with("-Dsun.java2d.d3d")));
+
+        assert_eq!(jvm_flag_key("-XX:+UseG1GC"), jvm_flag_key("-XX:-UseG1GC"));
+        assert_eq!(jvm_flag_key("-Xmx4G").as_deref(), Some("-Xmx"));
+        assert_eq!(jvm_flag_key("--add-opens"), None);
+    }
+}
@@ -1,5 +1,7 @@
 pub mod embedded;
+pub mod java_args;
 pub mod java_detector;
 pub mod java_requirement;
 pub mod java_validator;
 pub mod java_version;
+pub mod jvm_tuning;
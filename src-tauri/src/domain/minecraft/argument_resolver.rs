@@ -1,6 +1,6 @@
 use serde_json::Value;
 
-use super::rule_engine::{evaluate_rules, RuleContext};
+use super::rule_engine::{evaluate_rules, RuleContext, RuleFeatures};
 
 #[derive(Debug, Clone)]
 pub struct LaunchContext {
@@ -31,6 +31,19 @@ pub struct LaunchContext {
     pub quick_play_path: String,
 }
 
+impl LaunchContext {
+    /// Activa los flags de quick play según las opciones de quick play del lanzamiento.
+    pub fn apply_quick_play_features(&self, features: RuleFeatures) -> RuleFeatures {
+        RuleFeatures {
+            has_quick_plays_support: !self.quick_play_path.trim().is_empty(),
+            is_quick_play_singleplayer: !self.quick_play_singleplayer.trim().is_empty(),
+            is_quick_play_multiplayer: !self.quick_play_multiplayer.trim().is_empty(),
+            is_quick_play_realms: !self.quick_play_realms.trim().is_empty(),
+            ..features
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ResolvedLaunchArguments {
     pub main_class: String,
@@ -222,7 +235,7 @@ mod tests {
     use serde_json::json;
 
     use super::*;
-    use crate::domain::minecraft::rule_engine::OsName;
+    use crate::domain::minecraft::rule_engine::{OsName, RuleFeatures};
 
     fn sample_launch_context() -> LaunchContext {
         LaunchContext {
@@ -273,6 +286,7 @@ mod tests {
             &RuleContext {
                 os_name: OsName::Linux,
                 arch: "x86_64".to_string(),
+                os_version: String::new(),
                 features: RuleFeatures::default(),
             },
         )
@@ -303,6 +317,7 @@ mod tests {
             &RuleContext {
                 os_name: OsName::Linux,
                 arch: "x86_64".to_string(),
+                os_version: String::new(),
                 features: RuleFeatures::default(),
             },
         )
@@ -325,6 +340,7 @@ mod tests {
             &RuleContext {
                 os_name: OsName::Windows,
                 arch: "x86_64".to_string(),
+                os_version: String::new(),
                 features: RuleFeatures::default(),
             },
         )
@@ -359,6 +375,7 @@ mod tests {
             &RuleContext {
                 os_name: OsName::Linux,
                 arch: "x86_64".to_string(),
+                os_version: String::new(),
                 features: RuleFeatures::default(),
             },
         )
@@ -392,4 +409,162 @@ mod tests {
             ]
         );
     }
+
+    fn modern_game_arguments_json() -> Value {
+        json!({
+          "mainClass":"net.minecraft.client.main.Main",
+          "arguments": {
+            "jvm": [],
+            "game": [
+              "--username", "${auth_player_name}",
+              {"rules":[{"action":"allow","features":{"is_demo_user":true}}],"value":"--demo"},
+              {"rules":[{"action":"allow","features":{"has_custom_resolution":true}}],
+               "value":["--width","${resolution_width}","--height","${resolution_height}"]},
+              {"rules":[{"action":"allow","features":{"has_quick_plays_support":true}}],
+               "value":["--quickPlayPath","${quickPlayPath}"]},
+              {"rules":[{"action":"allow","features":{"is_quick_play_singleplayer":true}}],
+               "value":["--quickPlaySingleplayer","${quickPlaySingleplayer}"]},
+              {"rules":[{"action":"allow","features":{"is_quick_play_multiplayer":true}}],
+               "value":["--quickPlayMultiplayer","${quickPlayMultiplayer}"]},
+              {"rules":[{"action":"allow","features":{"is_quick_play_realms":true}}],
+               "value":["--quickPlayRealms","${quickPlayRealms}"]}
+            ]
+          }
+        })
+    }
+
+    fn resolve_game_args_with(features: RuleFeatures) -> Vec<String> {
+        resolve_launch_arguments(
+            &modern_game_arguments_json(),
+            &sample_launch_context(),
+            &RuleContext {
+                os_name: OsName::Linux,
+                arch: "x86_64".to_string(),
+                os_version: String::new(),
+                features,
+            },
+        )
+        .expect("debe resolver")
+        .game
+    }
+
+    #[test]
+    fn modern_game_feature_groups_are_dropped_by_default() {
+        assert_eq!(
+            resolve_game_args_with(RuleFeatures::default()),
+            vec!["--username", "Steve"]
+        );
+    }
+
+    #[test]
+    fn each_feature_flag_toggles_only_its_argument_group() {
+        let cases: [(RuleFeatures, &[&str]); 6] = [
+            (
+                RuleFeatures {
+                    is_demo_user: true,
+                    ..RuleFeatures::default()
+                },
+                &["--demo"],
+            ),
+            (
+                RuleFeatures {
+                    has_custom_resolution: true,
+                    ..RuleFeatures::default()
+                },
+                &["--width", "1280", "--height", "720"],
+            ),
+            (
+                RuleFeatures {
+                    has_quick_plays_support: true,
+                    ..RuleFeatures::default()
+                },
+                &["--quickPlayPath", "/game/quickPlay/log.json"],
+            ),
+            (
+                RuleFeatures {
+                    is_quick_play_singleplayer: true,
+                    ..RuleFeatures::default()
+                },
+                &["--quickPlaySingleplayer", "Mundo"],
+            ),
+            (
+                RuleFeatures {
+                    is_quick_play_multiplayer: true,
+                    ..RuleFeatures::default()
+                },
+                &["--quickPlayMultiplayer", "mc.example.net"],
+            ),
+            (
+                RuleFeatures {
+                    is_quick_play_realms: true,
+                    ..RuleFeatures::default()
+                },
+                &["--quickPlayRealms", "1234"],
+            ),
+        ];
+
+        let mut launch = sample_launch_context();
+        launch.quick_play_path = "/game/quickPlay/log.json".to_string();
+        launch.quick_play_singleplayer = "Mundo".to_string();
+        launch.quick_play_multiplayer = "mc.example.net".to_string();
+        launch.quick_play_realms = "1234".to_string();
+
+        for (features, expected_extra) in cases {
+            let game = resolve_launch_arguments(
+                &modern_game_arguments_json(),
+                &launch,
+                &RuleContext {
+                    os_name: OsName::Linux,
+                    arch: "x86_64".to_string(),
+                    os_version: String::new(),
+                    features: features.clone(),
+                },
+            )
+            .expect("debe resolver")
+            .game;
+            let mut expected = vec!["--username", "Steve"];
+            expected.extend_from_slice(expected_extra);
+            assert_eq!(game, expected, "features: {features:?}");
+        }
+    }
+
+    #[test]
+    fn quick_play_features_follow_launch_options() {
+        let mut launch = sample_launch_context();
+        launch.quick_play_multiplayer = "mc.example.net".to_string();
+
+        let features = launch.apply_quick_play_features(RuleFeatures::default());
+
+        assert!(features.is_quick_play_multiplayer);
+        assert!(!features.is_quick_play_singleplayer);
+        assert!(!features.is_quick_play_realms);
+        assert!(!features.has_quick_plays_support);
+    }
+
+    #[test]
+    fn unknown_feature_keys_never_match() {
+        let version_json = json!({
+          "mainClass":"net.minecraft.client.main.Main",
+          "arguments": {
+            "jvm": [],
+            "game": [
+              {"rules":[{"action":"allow","features":{"is_future_feature":false}}],"value":"--future"}
+            ]
+          }
+        });
+
+        let result = resolve_launch_arguments(
+            &version_json,
+            &sample_launch_context(),
+            &RuleContext {
+                os_name: OsName::Linux,
+                arch: "x86_64".to_string(),
+                os_version: String::new(),
+                features: RuleFeatures::default(),
+            },
+        )
+        .expect("debe resolver");
+
+        assert!(result.game.is_empty());
+    }
 }
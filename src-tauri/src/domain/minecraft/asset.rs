@@ -1 +1,24 @@
 // Módulo minecraft: asset.
+
+use std::{fs, path::Path};
+
+use serde_json::Value;
+
+/// Bytes de los objetos del índice de assets que faltan (o tienen otro tamaño) bajo
+/// `objects_root`: el total del índice menos lo ya presente.
+pub fn missing_asset_bytes(index_json: &Value, objects_root: &Path) -> u64 {
+    let Some(objects) = index_json.get("objects").and_then(Value::as_object) else {
+        return 0;
+    };
+    objects
+        .values()
+        .filter_map(|obj| {
+            let hash = obj.get("hash").and_then(Value::as_str)?.trim();
+            let size = obj.get("size").and_then(Value::as_u64)?;
+            let prefix = hash.get(..2)?;
+            let present = fs::metadata(objects_root.join(prefix).join(hash))
+                .is_ok_and(|meta| meta.len() == size);
+            (!present).then_some(size)
+        })
+        .sum()
+}
@@ -0,0 +1,165 @@
+use serde::{Deserialize, Serialize};
+
+/// Flags opcionales de privacidad de la instancia. Cada uno se traduce al mecanismo
+/// oficial del juego y solo se admite en las versiones que lo reconocen.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct OptionalGameFlags {
+    /// `--disableMultiplayer` (1.16+).
+    pub disable_multiplayer: bool,
+    /// `--disableChat` (1.16+).
+    pub disable_chat: bool,
+    /// Opción de telemetría de `options.txt`: `snooperEnabled` hasta 1.17 y
+    /// `telemetryOptInExtra` desde 1.19.3. 1.18–1.19.2 no tienen forma de desactivarla.
+    pub disable_telemetry: bool,
+}
+
+impl OptionalGameFlags {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+fn parse_release(minecraft_version: &str) -> Option<(u32, u32)> {
+    let mut parts = minecraft_version.trim().split('.');
+    if parts.next()? != "1" {
+        return None;
+    }
+    let minor = parts.next()?.parse().ok()?;
+    let patch = match parts.next() {
+        Some(patch) => patch.parse().ok()?,
+        None => 0,
+    };
+    Some((minor, patch))
+}
+
+/// Clave y valor de `options.txt` que desactiva la telemetría en la versión.
+pub fn telemetry_option(minecraft_version: &str) -> Option<(&'static str, &'static str)> {
+    match parse_release(minecraft_version)? {
+        (3..=17, _) => Some(("snooperEnabled", "false")),
+        (19, patch) if patch >= 3 => Some(("telemetryOptInExtra", "false")),
+        (minor, _) if minor >= 20 => Some(("telemetryOptInExtra", "false")),
+        _ => None,
+    }
+}
+
+fn supports_disable_args(minecraft_version: &str) -> bool {
+    parse_release(minecraft_version).is_some_and(|(minor, _)| minor >= 16)
+}
+
+/// Rechaza los flags que la versión no reconoce: el juego aborta con argumentos
+/// desconocidos, así que es mejor fallar al guardar que al lanzar.
+pub fn validate_optional_game_flags(
+    flags: &OptionalGameFlags,
+    minecraft_version: &str,
+) -> Result<(), String> {
+    let mut unsupported = Vec::new();
+    if !supports_disable_args(minecraft_version) {
+        if flags.disable_multiplayer {
+            unsupported.push("disableMultiplayer (requiere 1.16+)");
+        }
+        if flags.disable_chat {
+            unsupported.push("disableChat (requiere 1.16+)");
+        }
+    }
+    if flags.disable_telemetry && telemetry_option(minecraft_version).is_none() {
+        unsupported
+            .push("disableTelemetry (no disponible en 1.18–1.19.2 ni en versiones no reconocidas)");
+    }
+    if unsupported.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "Flags no soportados por Minecraft {minecraft_version}: {}",
+            unsupported.join(", ")
+        ))
+    }
+}
+
+/// Argumentos de juego de los flags activos, en orden estable.
+pub fn optional_game_args(
+    flags: &OptionalGameFlags,
+    minecraft_version: &str,
+) -> Result<Vec<String>, String> {
+    validate_optional_game_flags(flags, minecraft_version)?;
+    let mut args = Vec::new();
+    if flags.disable_multiplayer {
+        args.push("--disableMultiplayer".to_string());
+    }
+    if flags.disable_chat {
+        args.push("--disableChat".to_string());
+    }
+    Ok(args)
+}
+
+/// Fija `key:value` en el contenido de `options.txt` conservando el resto de líneas.
+pub fn upsert_option_line(options: &str, key: &str, value: &str) -> String {
+    let prefix = format!("{key}:");
+    let mut found = false;
+    let mut lines = options
+        .lines()
+        .map(|line| {
+            if line.starts_with(&prefix) {
+                found = true;
+                format!("{prefix}{value}")
+            } else {
+                line.to_string()
+            }
+        })
+        .collect::<Vec<_>>();
+    if !found {
+        lines.push(format!("{prefix}{value}"));
+    }
+    let mut updated = lines.join("\n");
+    updated.push('\n');
+    updated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn translates_flags_and_rejects_unsupported_versions() {
+        let flags = OptionalGameFlags {
+            disable_multiplayer: true,
+            disable_chat: true,
+            disable_telemetry: false,
+        };
+        assert_eq!(
+            optional_game_args(&flags, "1.20.1"),
+            Ok(vec![
+                "--disableMultiplayer".to_string(),
+                "--disableChat".to_string()
+            ])
+        );
+        assert!(optional_game_args(&flags, "1.12.2").is_err());
+        assert_eq!(
+            optional_game_args(&OptionalGameFlags::default(), "1.8.9"),
+            Ok(Vec::new())
+        );
+
+        let telemetry = OptionalGameFlags {
+            disable_telemetry: true,
+            ..OptionalGameFlags::default()
+        };
+        assert!(validate_optional_game_flags(&telemetry, "1.12.2").is_ok());
+        assert!(validate_optional_game_flags(&telemetry, "1.18.2").is_err());
+        assert_eq!(
+            telemetry_option("1.20.4"),
+            Some(("telemetryOptInExtra", "false"))
+        );
+    }
+
+    #[test]
+    fn upserts_options_txt_line() {
+        assert_eq!(
+            upsert_option_line("fov:0.0\nsnooperEnabled:true\n", "snooperEnabled", "false"),
+            "fov:0.0\nsnooperEnabled:false\n"
+        );
+        assert_eq!(
+            upsert_option_line("", "telemetryOptInExtra", "false"),
+            "telemetryOptInExtra:false\n"
+        );
+    }
+}
@@ -0,0 +1,269 @@
+use serde::Serialize;
+use serde_json::Value;
+
+/// GPU principal detectada sin crear un contexto OpenGL en el launcher.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct GpuInfo {
+    pub adapter_name: String,
+    /// intel | nvidia | amd | apple | software | unknown
+    pub vendor: String,
+    pub driver_version: String,
+    /// Sonda que produjo el dato (registry, glxinfo, drm, system_profiler) o `none`.
+    pub source: String,
+}
+
+impl GpuInfo {
+    pub fn unknown() -> Self {
+        Self {
+            adapter_name: "GPU desconocida".to_string(),
+            vendor: "unknown".to_string(),
+            driver_version: String::new(),
+            source: "none".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct GpuWarning {
+    pub code: String,
+    pub message: String,
+    pub suggestion: String,
+}
+
+/// Regla de combinación problemática GPU/driver/LWJGL.
+struct GpuRule {
+    code: &'static str,
+    os: Option<&'static str>,
+    /// Versión mínima de LWJGL (major, minor) afectada; `None` aplica a cualquiera.
+    min_lwjgl: Option<(u32, u32)>,
+    /// Versión máxima de LWJGL (major, minor) afectada, inclusive.
+    max_lwjgl: Option<(u32, u32)>,
+    matches: fn(&GpuInfo) -> bool,
+    message: &'static str,
+    suggestion: &'static str,
+}
+
+const SOFTWARE_RENDERERS: [&str; 6] = [
+    "llvmpipe",
+    "softpipe",
+    "swrast",
+    "software rasterizer",
+    "microsoft basic render",
+    "microsoft basic display",
+];
+
+/// Clasifica el fabricante a partir del nombre del adaptador o del vendor reportado.
+pub fn normalize_gpu_vendor(text: &str) -> &'static str {
+    let lower = text.to_ascii_lowercase();
+    if SOFTWARE_RENDERERS.iter().any(|name| lower.contains(name)) {
+        "software"
+    } else if lower.contains("intel") || lower.contains("0x8086") {
+        "intel"
+    } else if ["nvidia", "geforce", "nouveau", "0x10de"]
+        .iter()
+        .any(|name| lower.contains(name))
+    {
+        "nvidia"
+    } else if lower.contains("amd")
+        || lower.contains("radeon")
+        || lower.contains("ati ")
+        || lower.contains("0x1002")
+    {
+        "amd"
+    } else if lower.contains("apple") {
+        "apple"
+    } else {
+        "unknown"
+    }
+}
+
+fn is_software_renderer(gpu: &GpuInfo) -> bool {
+    gpu.vendor == "software" || normalize_gpu_vendor(&gpu.adapter_name) == "software"
+}
+
+/// Drivers de Intel para Windows anteriores a la rama 20.x (HD 2000/3000/4000 y similares).
+fn is_legacy_intel_windows_driver(gpu: &GpuInfo) -> bool {
+    gpu.vendor == "intel"
+        && gpu
+            .driver_version
+            .split('.')
+            .next()
+            .and_then(|major| major.trim().parse::<u32>().ok())
+            .is_some_and(|major| major < 20)
+}
+
+fn is_nouveau(gpu: &GpuInfo) -> bool {
+    gpu.adapter_name.to_ascii_lowercase().contains("nouveau")
+}
+
+const GPU_RULES: [GpuRule; 4] = [
+    GpuRule {
+        code: "software_rendering_lwjgl2",
+        os: None,
+        min_lwjgl: None,
+        max_lwjgl: Some((2, 99)),
+        matches: is_software_renderer,
+        message: "Se detectó renderizado por software (sin aceleración de GPU); LWJGL 2 se niega a crear la ventana.",
+        suggestion: "Instala el driver de la GPU. Como último recurso añade -Dorg.lwjgl.opengl.Display.allowSoftwareOpenGL=true a los argumentos de Java.",
+    },
+    GpuRule {
+        code: "software_rendering",
+        os: None,
+        min_lwjgl: Some((3, 0)),
+        max_lwjgl: None,
+        matches: is_software_renderer,
+        message: "Se detectó renderizado por software (llvmpipe o similar): el juego puede cerrarse al iniciar o ir a pocos FPS.",
+        suggestion: "Instala el driver de la GPU (Mesa/driver del fabricante) y comprueba que LIBGL_ALWAYS_SOFTWARE no esté definida.",
+    },
+    GpuRule {
+        code: "intel_legacy_driver_lwjgl33",
+        os: Some("windows"),
+        min_lwjgl: Some((3, 3)),
+        max_lwjgl: None,
+        matches: is_legacy_intel_windows_driver,
+        message: "El driver de Intel instalado es muy antiguo y falla con LWJGL 3.3 (\"Pixel format not accelerated\" o cierre inmediato).",
+        suggestion: "Actualiza el driver de Intel desde la web del fabricante o usa la GPU dedicada si el equipo tiene una.",
+    },
+    GpuRule {
+        code: "nouveau_driver",
+        os: Some("linux"),
+        min_lwjgl: None,
+        max_lwjgl: None,
+        matches: is_nouveau,
+        message: "La GPU NVIDIA usa el driver libre nouveau, con cierres y rendimiento bajo conocidos en Minecraft.",
+        suggestion: "Instala el driver propietario de NVIDIA.",
+    },
+];
+
+fn parse_major_minor(version: &str) -> Option<(u32, u32)> {
+    let mut parts = version.trim().split(['.', '-']);
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next().and_then(|part| part.parse().ok()).unwrap_or(0);
+    Some((major, minor))
+}
+
+/// Versión de LWJGL a partir de las librerías del version JSON resuelto
+/// (`org.lwjgl:lwjgl:3.3.1` o `org.lwjgl.lwjgl:lwjgl:2.9.4`).
+pub fn lwjgl_version_from_version_json(version_json: &Value) -> Option<String> {
+    version_json
+        .get("libraries")?
+        .as_array()?
+        .iter()
+        .filter_map(|library| library.get("name")?.as_str())
+        .find_map(|name| {
+            let mut parts = name.split(':');
+            let group = parts.next()?;
+            let artifact = parts.next()?;
+            let version = parts.next()?;
+            (matches!(group, "org.lwjgl" | "org.lwjgl.lwjgl") && artifact == "lwjgl")
+                .then(|| version.to_string())
+        })
+}
+
+/// Avisos de las reglas que coinciden con la GPU, el SO y la versión de LWJGL de la
+/// instancia. Una GPU desconocida nunca produce avisos.
+pub fn gpu_compat_warnings(
+    gpu: &GpuInfo,
+    os: &str,
+    lwjgl_version: Option<&str>,
+) -> Vec<GpuWarning> {
+    if gpu.source == "none" {
+        return Vec::new();
+    }
+    let lwjgl = lwjgl_version.and_then(parse_major_minor);
+    GPU_RULES
+        .iter()
+        .filter(|rule| rule.os.is_none() || rule.os == Some(os))
+        .filter(|rule| match (rule.min_lwjgl, lwjgl) {
+            (Some(min), Some(version)) => version >= min,
+            (Some(_), None) => false,
+            (None, _) => true,
+        })
+        .filter(|rule| match (rule.max_lwjgl, lwjgl) {
+            (Some(max), Some(version)) => version <= max,
+            (Some(_), None) => false,
+            (None, _) => true,
+        })
+        .filter(|rule| (rule.matches)(gpu))
+        .map(|rule| GpuWarning {
+            code: rule.code.to_string(),
+            message: rule.message.to_string(),
+            suggestion: rule.suggestion.to_string(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn gpu(adapter_name: &str, driver_version: &str) -> GpuInfo {
+        GpuInfo {
+            adapter_name: adapter_name.to_string(),
+            vendor: normalize_gpu_vendor(adapter_name).to_string(),
+            driver_version: driver_version.to_string(),
+            source: "test".to_string(),
+        }
+    }
+
+    #[test]
+    fn old_intel_driver_warns_only_with_lwjgl_33_on_windows() {
+        let intel = gpu("Intel(R) HD Graphics 3000", "9.17.10.4459");
+        let codes = |os: &str, lwjgl: Option<&str>| {
+            gpu_compat_warnings(&intel, os, lwjgl)
+                .into_iter()
+                .map(|warning| warning.code)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            codes("windows", Some("3.3.1")),
+            vec!["intel_legacy_driver_lwjgl33"]
+        );
+        assert!(codes("windows", Some("3.2.2")).is_empty());
+        assert!(codes("linux", Some("3.3.1")).is_empty());
+        assert!(gpu_compat_warnings(
+            &gpu("Intel(R) UHD Graphics 620", "31.0.101.2111"),
+            "windows",
+            Some("3.3.1")
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn llvmpipe_and_unknown_gpu() {
+        let llvmpipe = gpu("llvmpipe (LLVM 15.0.7, 256 bits)", "23.2.1");
+        let warnings = gpu_compat_warnings(&llvmpipe, "linux", Some("2.9.4"));
+        assert_eq!(warnings[0].code, "software_rendering_lwjgl2");
+        assert!(warnings[0].suggestion.contains("allowSoftwareOpenGL"));
+        assert_eq!(
+            gpu_compat_warnings(&llvmpipe, "linux", Some("3.3.3"))[0].code,
+            "software_rendering"
+        );
+        assert!(gpu_compat_warnings(&GpuInfo::unknown(), "linux", Some("3.3.3")).is_empty());
+    }
+
+    #[test]
+    fn reads_lwjgl_version_from_version_json() {
+        let version_json = json!({
+            "libraries": [
+                { "name": "com.mojang:logging:1.1.1" },
+                { "name": "org.lwjgl:lwjgl-glfw:3.3.1" },
+                { "name": "org.lwjgl:lwjgl:3.3.1" }
+            ]
+        });
+        assert_eq!(
+            lwjgl_version_from_version_json(&version_json).as_deref(),
+            Some("3.3.1")
+        );
+        let legacy =
+            json!({ "libraries": [{ "name": "org.lwjgl.lwjgl:lwjgl:2.9.4-nightly-20150209" }] });
+        assert_eq!(
+            lwjgl_version_from_version_json(&legacy).as_deref(),
+            Some("2.9.4-nightly-20150209")
+        );
+    }
+}
@@ -0,0 +1,168 @@
+// Rutas del sistema en argumentos de la JVM y del juego.
+
+use std::{ffi::OsStr, process::Command};
+
+/// Separador de listas de rutas de Java: `;` si el valor ya lo usa o en Windows, donde `:`
+/// aparece en la unidad (`C:/...`).
+pub fn java_path_list_separator(value: &str) -> char {
+    if value.contains(';') || cfg!(target_os = "windows") {
+        ';'
+    } else {
+        ':'
+    }
+}
+
+/// Quita las comillas de un argumento de args file. Una comilla sin pareja se conserva
+/// como carácter literal (`O'Brien`).
+fn strip_args_file_quotes(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut quote: Option<char> = None;
+    for (idx, ch) in value.char_indices() {
+        match quote {
+            Some(open) if ch == open => quote = None,
+            Some(_) => result.push(ch),
+            None if (ch == '"' || ch == '\'') && value[idx + 1..].contains(ch) => quote = Some(ch),
+            None => result.push(ch),
+        }
+    }
+    result
+}
+
+/// Parte una línea de args file en `flag valor` o en un único argumento, sin comillas.
+/// Los espacios del valor se conservan: `-Dclave=C:/Users/José García` es un solo argumento.
+pub fn split_args_file_line(line: &str) -> Vec<String> {
+    let line = line.trim();
+    match line.split_once(char::is_whitespace) {
+        Some((flag, value)) if flag.starts_with('-') && !flag.contains(['=', '"', '\'']) => {
+            vec![flag.to_string(), strip_args_file_quotes(value.trim())]
+        }
+        _ => vec![strip_args_file_quotes(line)],
+    }
+}
+
+/// Rutas que contiene una línea de args file (valor de `-p`, `-Dclave=` o la línea entera),
+/// separadas por el separador de listas de Java y sin cortar en los espacios.
+pub fn args_file_path_entries(line: &str) -> Vec<String> {
+    let value = split_args_file_line(line).pop().unwrap_or_default();
+    let value = match value.split_once('=') {
+        Some((key, path)) if key.starts_with("-D") => path.to_string(),
+        _ => value,
+    };
+    value
+        .split(java_path_list_separator(&value))
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Locale UTF-8 a fijar en Linux para que la JVM acepte rutas no ASCII.
+pub fn utf8_locale_override(
+    var: impl Fn(&str) -> Option<String>,
+) -> Option<(&'static str, &'static str)> {
+    let non_empty = |key: &str| var(key).filter(|value| !value.is_empty());
+    let effective = ["LC_ALL", "LC_CTYPE", "LANG"]
+        .into_iter()
+        .find_map(non_empty)
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    if effective.contains("utf-8") || effective.contains("utf8") {
+        return None;
+    }
+    // LC_ALL manda sobre LC_CTYPE: si está fijado hay que sustituirlo.
+    let key = if non_empty("LC_ALL").is_some() {
+        "LC_ALL"
+    } else {
+        "LC_CTYPE"
+    };
+    Some((key, "C.UTF-8"))
+}
+
+fn is_non_ascii(value: &OsStr) -> bool {
+    !value.to_str().is_some_and(str::is_ascii)
+}
+
+/// Ajusta el entorno de un comando java ya construido para que reciba intactas las rutas no
+/// ASCII de sus argumentos, variables (`CLASSPATH`) o directorio de trabajo.
+pub fn prepare_java_path_environment(command: &mut Command) {
+    if !cfg!(target_os = "linux") {
+        return;
+    }
+    let has_non_ascii = command.get_args().any(is_non_ascii)
+        || command
+            .get_envs()
+            .any(|(_, value)| value.is_some_and(is_non_ascii))
+        || command
+            .get_current_dir()
+            .is_some_and(|dir| is_non_ascii(dir.as_os_str()));
+    if !has_non_ascii {
+        return;
+    }
+    if let Some((key, value)) = utf8_locale_override(|key| std::env::var(key).ok()) {
+        log::info!("🔹 Rutas no ASCII con locale sin UTF-8: se lanza java con {key}={value}.");
+        command.env(key, value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn args_file_lines_keep_spaces_and_drop_argfile_quotes() {
+        assert_eq!(
+            split_args_file_line("-DlibraryDirectory=C:/Users/José García/libraries"),
+            vec!["-DlibraryDirectory=C:/Users/José García/libraries"]
+        );
+        assert_eq!(
+            split_args_file_line("-Dtest.path=\"/home/Ínstance Tëst Dir\""),
+            vec!["-Dtest.path=/home/Ínstance Tëst Dir"]
+        );
+        assert_eq!(
+            split_args_file_line("-p \"${library_directory}/a.jar\""),
+            vec!["-p", "${library_directory}/a.jar"]
+        );
+        assert_eq!(
+            split_args_file_line("/home/O'Brien/libraries/a.jar"),
+            vec!["/home/O'Brien/libraries/a.jar"]
+        );
+        assert_eq!(
+            args_file_path_entries("-p /a b/libraries/x.jar:/a b/libraries/y.jar"),
+            if cfg!(target_os = "windows") {
+                vec!["/a b/libraries/x.jar:/a b/libraries/y.jar"]
+            } else {
+                vec!["/a b/libraries/x.jar", "/a b/libraries/y.jar"]
+            }
+        );
+        assert_eq!(
+            args_file_path_entries("-DlibraryDirectory=\"/home/José García/libraries\""),
+            vec!["/home/José García/libraries"]
+        );
+        assert_eq!(java_path_list_separator("C:/a.jar;C:/b.jar"), ';');
+    }
+
+    #[test]
+    fn utf8_locale_is_forced_only_when_missing() {
+        let env = |pairs: &'static [(&'static str, &'static str)]| {
+            move |key: &str| {
+                pairs
+                    .iter()
+                    .find(|(name, _)| *name == key)
+                    .map(|(_, value)| value.to_string())
+            }
+        };
+        assert_eq!(
+            utf8_locale_override(env(&[])),
+            Some(("LC_CTYPE", "C.UTF-8"))
+        );
+        assert_eq!(utf8_locale_override(env(&[("LANG", "es_ES.UTF-8")])), None);
+        assert_eq!(
+            utf8_locale_override(env(&[("LANG", "es_ES.UTF-8"), ("LC_ALL", "C")])),
+            Some(("LC_ALL", "C.UTF-8"))
+        );
+        assert_eq!(
+            utf8_locale_override(env(&[("LC_ALL", ""), ("LC_CTYPE", "C.utf8")])),
+            None
+        );
+    }
+}
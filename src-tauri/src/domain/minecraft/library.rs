@@ -1 +1,296 @@
-// Módulo minecraft: library.
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Qué hacer con las librerías que coinciden con la regla.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LibraryOverrideAction {
+    /// Se quita del classpath y de los natives.
+    Exclude,
+    /// Se usa otra versión del mismo artefacto (misma disposición maven).
+    ReplaceVersion(String),
+}
+
+/// Regla por instancia para sortear metadata rota del version.json (p. ej. un lwjgl con
+/// un native de macOS defectuoso) sin editar el JSON combinado, que se regenera.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct LibraryOverride {
+    /// Prefijo de coordenada maven: `org.lwjgl`, `org.lwjgl:lwjgl-glfw`...
+    pub coordinate: String,
+    pub action: LibraryOverrideAction,
+}
+
+impl LibraryOverride {
+    pub fn describe(&self) -> String {
+        match &self.action {
+            LibraryOverrideAction::Exclude => format!("{} excluida", self.coordinate),
+            LibraryOverrideAction::ReplaceVersion(version) => {
+                format!("{} -> {version}", self.coordinate)
+            }
+        }
+    }
+}
+
+/// Jar del cliente y librerías de arranque de los loaders: sin ellas el juego no arranca,
+/// así que no se permite excluirlas ni cambiarles la versión.
+const PROTECTED_COORDINATES: [&str; 13] = [
+    "com.mojang:minecraft",
+    "net.minecraft:client",
+    "net.minecraft:launchwrapper",
+    "net.fabricmc:fabric-loader",
+    "net.fabricmc:intermediary",
+    "org.quiltmc:quilt-loader",
+    "net.minecraftforge:forge",
+    "net.minecraftforge:fmlloader",
+    "net.minecraftforge:bootstrap",
+    "cpw.mods:bootstraplauncher",
+    "cpw.mods:modlauncher",
+    "net.neoforged:neoforge",
+    "net.neoforged.fancymodloader",
+];
+
+/// `prefix` cubre `name` si coincide entero o hasta un separador de grupo o de coordenada.
+pub fn coordinate_matches(prefix: &str, name: &str) -> bool {
+    let prefix = prefix.trim();
+    !prefix.is_empty()
+        && name.starts_with(prefix)
+        && matches!(name[prefix.len()..].chars().next(), None | Some(':' | '.'))
+}
+
+/// Comprueba una regla antes de guardarla.
+pub fn validate_library_override(rule: &LibraryOverride) -> Result<(), String> {
+    let coordinate = rule.coordinate.trim();
+    if coordinate.is_empty() || coordinate.split(':').count() > 3 {
+        return Err(format!(
+            "Coordenada inválida '{}': usa grupo[:artefacto[:versión]].",
+            rule.coordinate
+        ));
+    }
+    if let Some(protected) = PROTECTED_COORDINATES.iter().find(|protected| {
+        coordinate_matches(coordinate, protected) || coordinate_matches(protected, coordinate)
+    }) {
+        return Err(format!(
+            "La regla '{coordinate}' afecta a {protected}, necesaria para arrancar el juego."
+        ));
+    }
+    if let LibraryOverrideAction::ReplaceVersion(version) = &rule.action {
+        let version = version.trim();
+        if version.is_empty() || version.contains(['/', '\\', ':', '@']) || version.contains("..") {
+            return Err(format!(
+                "Versión de reemplazo inválida para {coordinate}: '{version}'."
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Regla más específica que cubre la librería `name`, ignorando las que tocarían una
+/// librería protegida (por si la metadata se editó a mano).
+pub fn find_library_override<'a>(
+    overrides: &'a [LibraryOverride],
+    name: &str,
+) -> Option<&'a LibraryOverride> {
+    overrides
+        .iter()
+        .filter(|rule| coordinate_matches(&rule.coordinate, name))
+        .filter(|rule| validate_library_override(rule).is_ok())
+        .max_by_key(|rule| rule.coordinate.trim().len())
+}
+
+/// Ruta maven relativa de `group:artifact:version[:classifier][@ext]`.
+pub fn maven_relative_path(name: &str) -> Option<String> {
+    let (coordinate, extension) = name.split_once('@').unwrap_or((name, "jar"));
+    let mut parts = coordinate.split(':');
+    let group = parts.next()?;
+    let artifact = parts.next()?;
+    let version = parts.next()?;
+    let file_name = match parts.next() {
+        Some(classifier) => format!("{artifact}-{version}-{classifier}.{extension}"),
+        None => format!("{artifact}-{version}.{extension}"),
+    };
+    Some(format!(
+        "{}/{artifact}/{version}/{file_name}",
+        group.replace('.', "/")
+    ))
+}
+
+/// Reescribe ruta y URL de una descarga a la nueva versión. El SHA1 publicado ya no
+/// corresponde, así que se quita.
+fn rewrite_download(download: &mut Value, old_path: Option<String>, new_path: String) {
+    let Some(download) = download.as_object_mut() else {
+        return;
+    };
+    let current_path = download
+        .get("path")
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .or(old_path);
+    if let (Some(url), Some(current_path)) = (
+        download.get("url").and_then(Value::as_str),
+        current_path.as_deref(),
+    ) {
+        let base = url.strip_suffix(current_path).unwrap_or(url);
+        if base != url {
+            let new_url = format!("{base}{new_path}");
+            download.insert("url".to_string(), Value::String(new_url));
+        }
+    }
+    download.insert("path".to_string(), Value::String(new_path));
+    download.remove("sha1");
+    download.remove("size");
+}
+
+/// Copia de la librería apuntando a `version`: cambia el `name` y las rutas/URLs del
+/// artefacto y de los clasificadores siguiendo la disposición maven.
+pub fn replace_library_version(library: &Value, version: &str) -> Option<Value> {
+    let name = library.get("name")?.as_str()?;
+    let (coordinate, extension) = match name.split_once('@') {
+        Some((coordinate, extension)) => (coordinate, Some(extension)),
+        None => (name, None),
+    };
+    let mut parts = coordinate.split(':').collect::<Vec<_>>();
+    if parts.len() < 3 {
+        return None;
+    }
+    let old_version = parts[2].to_string();
+    parts[2] = version;
+    let mut new_name = parts.join(":");
+    if let Some(extension) = extension {
+        new_name = format!("{new_name}@{extension}");
+    }
+
+    let mut replaced = library.clone();
+    replaced["name"] = Value::String(new_name.clone());
+    if let Some(artifact) = replaced
+        .get_mut("downloads")
+        .and_then(|downloads| downloads.get_mut("artifact"))
+    {
+        rewrite_download(
+            artifact,
+            maven_relative_path(name),
+            maven_relative_path(&new_name)?,
+        );
+    }
+    if let Some(classifiers) = replaced
+        .get_mut("downloads")
+        .and_then(|downloads| downloads.get_mut("classifiers"))
+        .and_then(Value::as_object_mut)
+    {
+        for download in classifiers.values_mut() {
+            let Some(old_path) = download.get("path").and_then(Value::as_str) else {
+                continue;
+            };
+            let new_path = old_path
+                .rsplit_once('/')
+                .and_then(|(dir, file)| {
+                    let dir = dir.strip_suffix(&old_version)?;
+                    Some(format!(
+                        "{dir}{version}/{}",
+                        file.replacen(&old_version, version, 1)
+                    ))
+                })
+                .unwrap_or_else(|| old_path.to_string());
+            rewrite_download(download, None, new_path);
+        }
+    }
+    Some(replaced)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn rule(coordinate: &str, action: LibraryOverrideAction) -> LibraryOverride {
+        LibraryOverride {
+            coordinate: coordinate.to_string(),
+            action,
+        }
+    }
+
+    #[test]
+    fn most_specific_rule_wins_and_protected_libraries_are_refused() {
+        let overrides = vec![
+            rule("org.lwjgl", LibraryOverrideAction::Exclude),
+            rule(
+                "org.lwjgl:lwjgl-glfw",
+                LibraryOverrideAction::ReplaceVersion("3.3.2".to_string()),
+            ),
+            rule("net.fabricmc", LibraryOverrideAction::Exclude),
+        ];
+        assert_eq!(
+            find_library_override(&overrides, "org.lwjgl:lwjgl-glfw:3.3.1:natives-macos"),
+            Some(&overrides[1])
+        );
+        assert_eq!(
+            find_library_override(&overrides, "org.lwjgl:lwjgl:3.3.1"),
+            Some(&overrides[0])
+        );
+        assert_eq!(find_library_override(&overrides, "org.lwjglx:x:1"), None);
+        assert_eq!(
+            find_library_override(&overrides, "net.fabricmc:fabric-loader:0.15.11"),
+            None
+        );
+
+        assert!(validate_library_override(&overrides[2]).is_err());
+        assert!(validate_library_override(&rule(
+            "cpw.mods:modlauncher:10",
+            LibraryOverrideAction::Exclude
+        ))
+        .is_err());
+        assert!(validate_library_override(&rule(
+            "org.lwjgl:lwjgl",
+            LibraryOverrideAction::ReplaceVersion("../3.3.2".to_string())
+        ))
+        .is_err());
+        assert!(validate_library_override(&overrides[1]).is_ok());
+        assert_eq!(
+            serde_json::to_value(&overrides[1]).unwrap(),
+            json!({ "coordinate": "org.lwjgl:lwjgl-glfw", "action": { "replace_version": "3.3.2" } })
+        );
+    }
+
+    #[test]
+    fn replaced_version_rewrites_maven_paths_and_urls() {
+        let library = json!({
+            "name": "org.lwjgl:lwjgl-glfw:3.3.1",
+            "downloads": {
+                "artifact": {
+                    "path": "org/lwjgl/lwjgl-glfw/3.3.1/lwjgl-glfw-3.3.1.jar",
+                    "url": "https://libraries.minecraft.net/org/lwjgl/lwjgl-glfw/3.3.1/lwjgl-glfw-3.3.1.jar",
+                    "sha1": "abc",
+                    "size": 10
+                },
+                "classifiers": {
+                    "natives-macos": {
+                        "path": "org/lwjgl/lwjgl-glfw/3.3.1/lwjgl-glfw-3.3.1-natives-macos.jar",
+                        "url": "https://libraries.minecraft.net/org/lwjgl/lwjgl-glfw/3.3.1/lwjgl-glfw-3.3.1-natives-macos.jar",
+                        "sha1": "def"
+                    }
+                }
+            }
+        });
+        let replaced = replace_library_version(&library, "3.3.2").expect("reemplazo");
+        assert_eq!(replaced["name"], "org.lwjgl:lwjgl-glfw:3.3.2");
+        let artifact = &replaced["downloads"]["artifact"];
+        assert_eq!(
+            artifact["path"],
+            "org/lwjgl/lwjgl-glfw/3.3.2/lwjgl-glfw-3.3.2.jar"
+        );
+        assert_eq!(
+            artifact["url"],
+            "https://libraries.minecraft.net/org/lwjgl/lwjgl-glfw/3.3.2/lwjgl-glfw-3.3.2.jar"
+        );
+        assert!(artifact.get("sha1").is_none());
+        let native = &replaced["downloads"]["classifiers"]["natives-macos"];
+        assert_eq!(
+            native["url"],
+            "https://libraries.minecraft.net/org/lwjgl/lwjgl-glfw/3.3.2/lwjgl-glfw-3.3.2-natives-macos.jar"
+        );
+        assert_eq!(
+            maven_relative_path("net.sf.jopt-simple:jopt-simple:5.0.4@zip").as_deref(),
+            Some("net/sf/jopt-simple/jopt-simple/5.0.4/jopt-simple-5.0.4.zip")
+        );
+    }
+}
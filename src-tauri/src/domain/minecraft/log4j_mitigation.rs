@@ -0,0 +1,108 @@
+const NO_LOOKUPS_PROPERTY: &str = "-Dlog4j2.formatMsgNoLookups=true";
+pub const CONFIGURATION_FILE_PROPERTY: &str = "-Dlog4j.configurationFile=";
+
+/// Configuración de log4j2 publicada por Mojang para 1.7–1.11 (CVE-2021-44228).
+pub const LEGACY_LOG4J_CONFIG_FILE: &str = "log4j2_17-111.xml";
+pub const LEGACY_LOG4J_CONFIG_URL: &str =
+    "https://launcher.mojang.com/v1/objects/dd2b723346a8dcd48e7f4d245f6bf09e98db9696/log4j2_17-111.xml";
+pub const LEGACY_LOG4J_CONFIG_SHA1: &str = "dd2b723346a8dcd48e7f4d245f6bf09e98db9696";
+
+/// Mitigación de log4j que necesita una versión concreta de Minecraft.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Log4jMitigation {
+    /// 1.12–1.18.0: basta con desactivar los lookups por propiedad del sistema.
+    NoLookupsProperty,
+    /// 1.7–1.11: el log4j incluido ignora la propiedad; hay que usar el XML de Mojang.
+    LegacyConfigFile,
+}
+
+fn parse_release(minecraft_version: &str) -> Option<(u32, u32)> {
+    let mut parts = minecraft_version.trim().split('.');
+    if parts.next()? != "1" {
+        return None;
+    }
+    let minor = parts.next()?.parse().ok()?;
+    let patch = match parts.next() {
+        Some(patch) => patch.parse().ok()?,
+        None => 0,
+    };
+    Some((minor, patch))
+}
+
+/// Mitigación que corresponde a la versión. Desde 1.18.1 el juego trae log4j parcheado y
+/// las snapshots o versiones no reconocidas se dejan como están.
+pub fn log4j_mitigation_for_version(minecraft_version: &str) -> Option<Log4jMitigation> {
+    match parse_release(minecraft_version)? {
+        (7..=11, _) => Some(Log4jMitigation::LegacyConfigFile),
+        (12..=17, _) | (18, 0) => Some(Log4jMitigation::NoLookupsProperty),
+        _ => None,
+    }
+}
+
+/// Argumentos JVM de la mitigación. `config_path` es el XML ya descargado; sin él la
+/// mitigación por archivo no añade nada.
+pub fn log4j_jvm_args(mitigation: Log4jMitigation, config_path: Option<&str>) -> Vec<String> {
+    match mitigation {
+        Log4jMitigation::NoLookupsProperty => vec![NO_LOOKUPS_PROPERTY.to_string()],
+        Log4jMitigation::LegacyConfigFile => config_path
+            .map(|path| vec![format!("{CONFIGURATION_FILE_PROPERTY}{path}")])
+            .unwrap_or_default(),
+    }
+}
+
+/// Indica si los argumentos ya traen una configuración de log4j (del usuario o del
+/// version.json), en cuyo caso no se inyecta otra.
+pub fn has_log4j_override(jvm_args: &[String]) -> bool {
+    jvm_args.iter().any(|arg| {
+        arg.starts_with(CONFIGURATION_FILE_PROPERTY)
+            || arg.starts_with("-Dlog4j2.formatMsgNoLookups=")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn picks_mitigation_per_version_range() {
+        for version in ["1.7.10", "1.8.9", "1.11.2", "1.11"] {
+            assert_eq!(
+                log4j_mitigation_for_version(version),
+                Some(Log4jMitigation::LegacyConfigFile),
+                "{version}"
+            );
+        }
+        for version in ["1.12", "1.12.2", "1.16.5", "1.17.1", "1.18"] {
+            assert_eq!(
+                log4j_mitigation_for_version(version),
+                Some(Log4jMitigation::NoLookupsProperty),
+                "{version}"
+            );
+        }
+        for version in ["1.18.1", "1.18.2", "1.20.4", "1.6.4", "21w44a", "b1.7.3"] {
+            assert_eq!(log4j_mitigation_for_version(version), None, "{version}");
+        }
+    }
+
+    #[test]
+    fn builds_jvm_args_and_detects_existing_override() {
+        assert_eq!(
+            log4j_jvm_args(Log4jMitigation::NoLookupsProperty, None),
+            vec!["-Dlog4j2.formatMsgNoLookups=true".to_string()]
+        );
+        assert_eq!(
+            log4j_jvm_args(
+                Log4jMitigation::LegacyConfigFile,
+                Some("/launcher/config/log4j/log4j2_17-111.xml")
+            ),
+            vec!["-Dlog4j.configurationFile=/launcher/config/log4j/log4j2_17-111.xml".to_string()]
+        );
+        assert!(log4j_jvm_args(Log4jMitigation::LegacyConfigFile, None).is_empty());
+
+        assert!(has_log4j_override(&[
+            "-Xmx2G".to_string(),
+            "-Dlog4j.configurationFile=custom.xml".to_string()
+        ]));
+        assert!(!has_log4j_override(&["-Xmx2G".to_string()]));
+    }
+}
@@ -0,0 +1,327 @@
+// Configuración de log4j del version.json.
+
+use std::sync::OnceLock;
+
+use chrono::{Local, TimeZone};
+use regex::Regex;
+use serde_json::Value;
+
+use crate::domain::minecraft::log4j_mitigation::CONFIGURATION_FILE_PROPERTY;
+
+/// XML de configuración de log4j declarado por la versión.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientLoggingConfig {
+    /// Nombre del archivo (`client-1.12.xml`); se usa como clave de caché.
+    pub id: String,
+    pub sha1: String,
+    pub url: String,
+    /// Argumento JVM con el marcador `${path}`.
+    pub argument: String,
+}
+
+/// Qué hacer con la configuración de logging de la versión.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LoggingDecision {
+    Apply,
+    /// Desactivado en la configuración del launcher.
+    Disabled,
+    /// Ya hay un `-Dlog4j.configurationFile` (mitigación de log4j o del usuario), que manda.
+    AlreadyConfigured(String),
+}
+
+/// Lee `logging.client` del version.json; `None` si falta o está incompleto.
+pub fn client_logging_config(version_json: &Value) -> Option<ClientLoggingConfig> {
+    let client = version_json.get("logging")?.get("client")?;
+    let file = client.get("file")?;
+    let text = |value: Option<&Value>| {
+        value
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|text| !text.is_empty())
+            .map(str::to_string)
+    };
+    let id = text(file.get("id"))?;
+    // El id acaba en una ruta: no se aceptan separadores.
+    if id.contains(['/', '\\']) || id.contains("..") {
+        return None;
+    }
+    Some(ClientLoggingConfig {
+        id,
+        sha1: text(file.get("sha1"))?,
+        url: text(file.get("url"))?,
+        argument: text(client.get("argument"))
+            .unwrap_or_else(|| format!("{CONFIGURATION_FILE_PROPERTY}${{path}}")),
+    })
+}
+
+/// Decide si se añade la configuración de la versión a `jvm_args`, que ya incluye la
+/// mitigación de log4j si hacía falta.
+pub fn decide_logging(enabled: bool, jvm_args: &[String]) -> LoggingDecision {
+    if !enabled {
+        return LoggingDecision::Disabled;
+    }
+    match jvm_args
+        .iter()
+        .find(|arg| arg.starts_with(CONFIGURATION_FILE_PROPERTY))
+    {
+        Some(existing) => LoggingDecision::AlreadyConfigured(existing.clone()),
+        None => LoggingDecision::Apply,
+    }
+}
+
+/// Argumento JVM con la ruta del XML descargado.
+pub fn logging_jvm_argument(config: &ClientLoggingConfig, path: &str) -> String {
+    config.argument.replace("${path}", path)
+}
+
+/// Eventos más largos que esto se sueltan tal cual: no es un `XMLLayout` bien formado.
+const MAX_EVENT_LINES: usize = 512;
+
+struct EventPatterns {
+    attribute: Regex,
+    message: Regex,
+    throwable: Regex,
+}
+
+static EVENT_PATTERNS: OnceLock<EventPatterns> = OnceLock::new();
+
+fn event_patterns() -> &'static EventPatterns {
+    EVENT_PATTERNS.get_or_init(|| EventPatterns {
+        attribute: Regex::new(r#"(\w+)="([^"]*)""#).expect("Regex de atributos log4j inválida"),
+        message: Regex::new(r"(?s)<log4j:Message>(?:<!\[CDATA\[(.*?)\]\]>|(.*?))</log4j:Message>")
+            .expect("Regex de mensaje log4j inválida"),
+        throwable: Regex::new(
+            r"(?s)<log4j:Throwable>(?:<!\[CDATA\[(.*?)\]\]>|(.*?))</log4j:Throwable>",
+        )
+        .expect("Regex de excepción log4j inválida"),
+    })
+}
+
+fn unescape_xml(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Convierte un `<log4j:Event>` completo en líneas con el formato de `latest.log`.
+fn render_event(event: &str) -> Vec<String> {
+    let patterns = event_patterns();
+    let header = event.split('>').next().unwrap_or_default();
+    let attribute = |name: &str| {
+        patterns
+            .attribute
+            .captures_iter(header)
+            .find(|captures| &captures[1] == name)
+            .map(|captures| unescape_xml(&captures[2]))
+            .unwrap_or_default()
+    };
+    let time = attribute("timestamp")
+        .parse::<i64>()
+        .ok()
+        .and_then(|millis| Local.timestamp_millis_opt(millis).single())
+        .map(|at| at.format("%H:%M:%S").to_string())
+        .unwrap_or_else(|| "??:??:??".to_string());
+    let body = |pattern: &Regex| {
+        pattern.captures(event).map(|captures| {
+            captures
+                .get(1)
+                .map(|cdata| cdata.as_str().to_string())
+                .or_else(|| captures.get(2).map(|plain| unescape_xml(plain.as_str())))
+                .unwrap_or_default()
+        })
+    };
+    let message = body(&patterns.message).unwrap_or_default();
+    let mut lines = message
+        .trim_end_matches(['\r', '\n'])
+        .lines()
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    if lines.is_empty() {
+        lines.push(String::new());
+    }
+    lines[0] = format!(
+        "[{time}] [{}/{}]: {}",
+        attribute("thread"),
+        attribute("level"),
+        lines[0]
+    );
+    if let Some(throwable) = body(&patterns.throwable) {
+        lines.extend(
+            throwable
+                .trim_end_matches(['\r', '\n'])
+                .lines()
+                .map(str::to_string),
+        );
+    }
+    lines
+}
+
+/// Junta los `<log4j:Event>` de la salida del juego; el resto de líneas pasa sin tocar.
+#[derive(Debug, Default)]
+pub struct Log4jXmlAssembler {
+    pending: Vec<String>,
+}
+
+impl Log4jXmlAssembler {
+    /// Líneas listas para mostrar tras recibir `line` (ninguna si el evento sigue abierto).
+    pub fn push(&mut self, line: String) -> Vec<String> {
+        if self.pending.is_empty() && !line.trim_start().starts_with("<log4j:Event") {
+            return vec![line];
+        }
+        let closed = line.contains("</log4j:Event>");
+        self.pending.push(line);
+        if closed {
+            let event = std::mem::take(&mut self.pending).join("\n");
+            return render_event(&event);
+        }
+        if self.pending.len() >= MAX_EVENT_LINES {
+            return std::mem::take(&mut self.pending);
+        }
+        Vec::new()
+    }
+
+    /// Lo que quede a medias al cerrarse la salida.
+    pub fn finish(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.pending)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::minecraft::log4j_mitigation::{
+        log4j_jvm_args, log4j_mitigation_for_version,
+    };
+
+    const VERSION_1_20_1: &str = r#"{
+        "id": "1.20.1",
+        "logging": {
+            "client": {
+                "argument": "-Dlog4j.configurationFile=${path}",
+                "file": {
+                    "id": "client-1.12.xml",
+                    "sha1": "bd65e7d2e3c237be76cfbef4c2405033d7f91521",
+                    "size": 888,
+                    "url": "https://piston-data.mojang.com/v1/objects/bd65e7d2e3c237be76cfbef4c2405033d7f91521/client-1.12.xml"
+                },
+                "type": "log4j2-xml"
+            }
+        },
+        "mainClass": "net.minecraft.client.main.Main"
+    }"#;
+
+    const VERSION_1_8_9: &str = r#"{
+        "id": "1.8.9",
+        "logging": {
+            "client": {
+                "argument": "-Dlog4j.configurationFile=${path}",
+                "file": {
+                    "id": "client-1.7.xml",
+                    "sha1": "50c9cc4af6d853d9fc137c84bcd153e7bd3a9a82",
+                    "size": 966,
+                    "url": "https://launcher.mojang.com/v1/objects/50c9cc4af6d853d9fc137c84bcd153e7bd3a9a82/client-1.7.xml"
+                },
+                "type": "log4j2-xml"
+            }
+        },
+        "mainClass": "net.minecraft.client.main.Main"
+    }"#;
+
+    const FABRIC_PROFILE: &str = r#"{
+        "id": "fabric-loader-0.15.11-1.20.1",
+        "inheritsFrom": "1.20.1",
+        "mainClass": "net.fabricmc.loader.impl.launch.knot.KnotClient"
+    }"#;
+
+    fn parse(raw: &str) -> Value {
+        serde_json::from_str(raw).expect("version json")
+    }
+
+    #[test]
+    fn reads_the_client_logging_block() {
+        let config = client_logging_config(&parse(VERSION_1_20_1)).expect("logging");
+        assert_eq!(config.id, "client-1.12.xml");
+        assert_eq!(config.sha1, "bd65e7d2e3c237be76cfbef4c2405033d7f91521");
+        assert_eq!(
+            logging_jvm_argument(&config, "/launcher/assets/log_configs/client-1.12.xml"),
+            "-Dlog4j.configurationFile=/launcher/assets/log_configs/client-1.12.xml"
+        );
+        assert_eq!(
+            decide_logging(true, &["-Xmx4G".to_string()]),
+            LoggingDecision::Apply
+        );
+        assert_eq!(decide_logging(false, &[]), LoggingDecision::Disabled);
+    }
+
+    #[test]
+    fn absent_block_adds_nothing() {
+        assert_eq!(client_logging_config(&parse(FABRIC_PROFILE)), None);
+        let mut tampered = parse(VERSION_1_20_1);
+        tampered["logging"]["client"]["file"]["id"] = Value::from("../../evil.xml");
+        assert_eq!(client_logging_config(&tampered), None);
+    }
+
+    #[test]
+    fn log4j_mitigation_wins_on_vulnerable_versions() {
+        let version = parse(VERSION_1_8_9);
+        assert!(client_logging_config(&version).is_some());
+        let mitigation = log4j_mitigation_for_version("1.8.9").expect("vulnerable");
+        let jvm_args = log4j_jvm_args(mitigation, Some("/launcher/config/log4j/log4j2_17-111.xml"));
+        assert_eq!(
+            decide_logging(true, &jvm_args),
+            LoggingDecision::AlreadyConfigured(
+                "-Dlog4j.configurationFile=/launcher/config/log4j/log4j2_17-111.xml".to_string()
+            )
+        );
+
+        // 1.12–1.18.0 solo llevan la propiedad de lookups: el XML de la versión se añade.
+        let property_only = log4j_jvm_args(
+            log4j_mitigation_for_version("1.16.5").expect("vulnerable"),
+            None,
+        );
+        assert_eq!(decide_logging(true, &property_only), LoggingDecision::Apply);
+    }
+
+    #[test]
+    fn reassembles_xml_layout_events_into_log_lines() {
+        let mut assembler = Log4jXmlAssembler::default();
+        assert_eq!(
+            assembler.push("Plain line from a mod".to_string()),
+            vec!["Plain line from a mod"]
+        );
+
+        let event = [
+            r#"<log4j:Event logger="net.minecraft.client.Minecraft" timestamp="1700000000000" level="INFO" thread="Render thread">"#,
+            r#"  <log4j:Message><![CDATA[Setting user: Steve]]></log4j:Message>"#,
+            r#"</log4j:Event>"#,
+        ];
+        assert!(assembler.push(event[0].to_string()).is_empty());
+        assert!(assembler.push(event[1].to_string()).is_empty());
+        let lines = assembler.push(event[2].to_string());
+        assert_eq!(lines.len(), 1);
+        assert!(lines[0].ends_with("] [Render thread/INFO]: Setting user: Steve"));
+        assert!(lines[0].starts_with('['));
+
+        let failing = [
+            r#"<log4j:Event logger="x" timestamp="1700000000000" level="ERROR" thread="main">"#,
+            r#"  <log4j:Message>a &lt; b</log4j:Message>"#,
+            r#"  <log4j:Throwable><![CDATA[java.lang.IllegalStateException: boom"#,
+            r#"	at Foo.bar(Foo.java:1)"#,
+            r#"]]></log4j:Throwable>"#,
+            r#"</log4j:Event>"#,
+        ];
+        let mut lines = Vec::new();
+        for line in failing {
+            lines.extend(assembler.push(line.to_string()));
+        }
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].ends_with("[main/ERROR]: a < b"));
+        assert_eq!(lines[1], "java.lang.IllegalStateException: boom");
+        assert_eq!(lines[2], "\tat Foo.bar(Foo.java:1)");
+
+        assert!(assembler.push(event[0].to_string()).is_empty());
+        assert_eq!(assembler.finish(), vec![event[0].to_string()]);
+    }
+}
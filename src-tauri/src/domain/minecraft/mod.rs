@@ -1,6 +1,12 @@
 pub mod argument_resolver;
 pub mod asset;
+pub mod game_flags;
+pub mod gpu_compat;
+pub mod launch_paths;
 pub mod library;
+pub mod log4j_mitigation;
+pub mod logging_config;
 pub mod manifest;
+pub mod mods_dir;
 pub mod rule_engine;
 pub mod version_json;
@@ -0,0 +1,119 @@
+use std::path::Path;
+
+const FABRIC_MODS_FOLDER_PROPERTY: &str = "-Dfabric.modsFolder=";
+const FML_MODS_FOLDER_ARG: &str = "--fml.modsFolder";
+
+/// Argumentos con los que el loader carga los mods desde una carpeta distinta de
+/// `minecraft/mods`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ModsDirInjection {
+    pub jvm: Vec<String>,
+    pub game: Vec<String>,
+}
+
+fn minecraft_minor(minecraft_version: &str) -> Option<u32> {
+    let mut parts = minecraft_version.trim().split('.');
+    if parts.next()? != "1" {
+        return None;
+    }
+    parts.next()?.parse().ok()
+}
+
+/// Argumentos que redirigen la carpeta de mods (Fabric/Quilt y Forge 1.13–1.16); el resto
+/// de loaders devuelve error.
+pub fn mods_dir_injection(
+    loader: &str,
+    minecraft_version: &str,
+    mods_dir: &Path,
+) -> Result<ModsDirInjection, String> {
+    let path = mods_dir.display().to_string();
+    match loader.trim().to_ascii_lowercase().as_str() {
+        "fabric" | "quilt" => Ok(ModsDirInjection {
+            jvm: vec![format!("{FABRIC_MODS_FOLDER_PROPERTY}{path}")],
+            game: Vec::new(),
+        }),
+        "forge" => match minecraft_minor(minecraft_version) {
+            Some(minor) if (13..=16).contains(&minor) => Ok(ModsDirInjection {
+                jvm: Vec::new(),
+                game: vec![FML_MODS_FOLDER_ARG.to_string(), path],
+            }),
+            _ => Err(format!(
+                "Forge para Minecraft {minecraft_version} no permite redirigir la carpeta de mods (solo 1.13–1.16). Quita mods_dir_override y usa la carpeta mods de la instancia."
+            )),
+        },
+        "" | "-" | "vanilla" => Err(
+            "Las instancias vanilla no cargan mods: mods_dir_override no se puede usar."
+                .to_string(),
+        ),
+        other => Err(format!(
+            "El loader '{other}' no permite redirigir la carpeta de mods. Quita mods_dir_override y usa la carpeta mods de la instancia."
+        )),
+    }
+}
+
+/// Añade la redirección a los argumentos quitando antes cualquier aparición previa
+/// (p. ej. la que el usuario puso en java_args), de modo que quede exactamente una vez.
+pub fn apply_mods_dir_injection(
+    jvm_args: &mut Vec<String>,
+    game_args: &mut Vec<String>,
+    injection: &ModsDirInjection,
+) {
+    if !injection.jvm.is_empty() {
+        jvm_args.retain(|arg| !arg.starts_with(FABRIC_MODS_FOLDER_PROPERTY));
+        jvm_args.extend(injection.jvm.iter().cloned());
+    }
+    if !injection.game.is_empty() {
+        while let Some(index) = game_args.iter().position(|arg| arg == FML_MODS_FOLDER_ARG) {
+            let end = (index + 2).min(game_args.len());
+            game_args.drain(index..end);
+        }
+        game_args.extend(injection.game.iter().cloned());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fabric_injection_appears_exactly_once() {
+        let injection =
+            mods_dir_injection("Fabric", "1.20.1", Path::new("/shared/mods")).expect("fabric");
+        let mut jvm = vec![
+            "-Xmx4G".to_string(),
+            "-Dfabric.modsFolder=/old/mods".to_string(),
+        ];
+        let mut game = vec!["--username".to_string(), "Steve".to_string()];
+        apply_mods_dir_injection(&mut jvm, &mut game, &injection);
+        apply_mods_dir_injection(&mut jvm, &mut game, &injection);
+
+        let injected: Vec<_> = jvm
+            .iter()
+            .filter(|arg| arg.starts_with("-Dfabric.modsFolder="))
+            .collect();
+        assert_eq!(injected, vec!["-Dfabric.modsFolder=/shared/mods"]);
+        assert_eq!(game.len(), 2);
+    }
+
+    #[test]
+    fn forge_injection_uses_game_args_once_and_rejects_unsupported_versions() {
+        let injection =
+            mods_dir_injection("forge", "1.16.5", Path::new("/shared/mods")).expect("forge");
+        let mut jvm = Vec::new();
+        let mut game = vec!["--fml.modsFolder".to_string(), "/old".to_string()];
+        apply_mods_dir_injection(&mut jvm, &mut game, &injection);
+        assert_eq!(game, vec!["--fml.modsFolder", "/shared/mods"]);
+        assert!(jvm.is_empty());
+
+        assert!(mods_dir_injection("forge", "1.20.1", Path::new("/shared/mods")).is_err());
+        assert!(mods_dir_injection("neoforge", "1.20.4", Path::new("/shared/mods")).is_err());
+    }
+
+    #[test]
+    fn vanilla_rejects_override_with_clear_error() {
+        let err = mods_dir_injection("vanilla", "1.20.1", Path::new("/shared/mods"))
+            .expect_err("vanilla");
+        assert!(err.contains("vanilla"));
+        assert!(err.contains("mods_dir_override"));
+    }
+}
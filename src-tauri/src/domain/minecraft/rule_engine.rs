@@ -1,5 +1,14 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    sync::{Mutex, OnceLock},
+};
+
+use regex::Regex;
+use serde::Serialize;
 use serde_json::Value;
 
+static LOGGED_UNKNOWN_FEATURES: OnceLock<Mutex<HashSet<String>>> = OnceLock::new();
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum OsName {
     Windows,
@@ -8,17 +17,60 @@ pub enum OsName {
     Unknown,
 }
 
+/// Claves de `rules[].features` de los version JSON; las desconocidas no coinciden.
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub struct RuleFeatures {
     pub is_demo_user: bool,
     pub has_custom_resolution: bool,
-    pub is_quick_play: bool,
+    pub has_quick_plays_support: bool,
+    pub is_quick_play_singleplayer: bool,
+    pub is_quick_play_multiplayer: bool,
+    pub is_quick_play_realms: bool,
+}
+
+impl RuleFeatures {
+    pub fn value_of(&self, key: &str) -> Option<bool> {
+        match key {
+            "is_demo_user" => Some(self.is_demo_user),
+            "has_custom_resolution" => Some(self.has_custom_resolution),
+            "has_quick_plays_support" => Some(self.has_quick_plays_support),
+            "is_quick_play_singleplayer" => Some(self.is_quick_play_singleplayer),
+            "is_quick_play_multiplayer" => Some(self.is_quick_play_multiplayer),
+            "is_quick_play_realms" => Some(self.is_quick_play_realms),
+            _ => None,
+        }
+    }
+}
+
+/// Reinicia el registro de claves de features desconocidas; se llama al iniciar cada lanzamiento.
+pub fn reset_unknown_feature_log() {
+    if let Ok(mut logged) = LOGGED_UNKNOWN_FEATURES
+        .get_or_init(|| Mutex::new(HashSet::new()))
+        .lock()
+    {
+        logged.clear();
+    }
+}
+
+fn log_unknown_feature_once(key: &str) {
+    let Ok(mut logged) = LOGGED_UNKNOWN_FEATURES
+        .get_or_init(|| Mutex::new(HashSet::new()))
+        .lock()
+    else {
+        return;
+    };
+    if logged.insert(key.to_string()) {
+        log::warn!("⚠ Feature de regla desconocida '{key}': el bloque condicionado se omitirá.");
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RuleContext {
     pub os_name: OsName,
     pub arch: String,
+    /// Equivalente a `os.version` de Java (`10.0` en Windows 10/11, `14.2` en macOS, la
+    /// versión del kernel en Linux); vacío si no se conoce.
+    pub os_version: String,
     pub features: RuleFeatures,
 }
 
@@ -37,11 +89,41 @@ impl RuleContext {
         Self {
             os_name,
             arch: std::env::consts::ARCH.to_string(),
+            os_version: crate::platform::os_version::os_version().to_string(),
             features: RuleFeatures::default(),
         }
     }
 }
 
+/// Evaluación de una regla dentro de una traza.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleStep {
+    pub action: String,
+    /// Lo que exige la regla (`os` y `features` tal cual); vacío si es incondicional.
+    pub demands: Value,
+    pub matched: bool,
+    /// Primera condición que no se cumplió.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mismatch: Option<String>,
+}
+
+/// Traza de `evaluate_rules`: cada regla evaluada y el resultado final.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleTrace {
+    pub steps: Vec<RuleStep>,
+    pub allowed: bool,
+    /// Índice de la última regla coincidente, que es la que decide; `None` sin coincidencias.
+    pub decided_by: Option<usize>,
+}
+
+fn rule_action(rule: &Value) -> &str {
+    rule.get("action")
+        .and_then(Value::as_str)
+        .unwrap_or("allow")
+}
+
 pub fn evaluate_rules(rules: &[Value], context: &RuleContext) -> bool {
     if rules.is_empty() {
         return true;
@@ -49,40 +131,124 @@ pub fn evaluate_rules(rules: &[Value], context: &RuleContext) -> bool {
 
     let mut allowed = false;
     for rule in rules {
-        let action = rule
-            .get("action")
-            .and_then(Value::as_str)
-            .unwrap_or("allow");
-        if rule_matches_context(rule, context) {
-            allowed = action == "allow";
+        if rule_mismatch(rule, context).is_none() {
+            allowed = rule_action(rule) == "allow";
         }
     }
 
     allowed
 }
 
-fn rule_matches_context(rule: &Value, context: &RuleContext) -> bool {
+/// Igual que `evaluate_rules`, registrando qué pidió cada regla y por qué coincidió o no.
+pub fn evaluate_rules_traced(rules: &[Value], context: &RuleContext) -> RuleTrace {
+    let mut trace = RuleTrace {
+        steps: Vec::with_capacity(rules.len()),
+        allowed: rules.is_empty(),
+        decided_by: None,
+    };
+    for (index, rule) in rules.iter().enumerate() {
+        let mismatch = rule_mismatch(rule, context);
+        let demands: serde_json::Map<String, Value> = ["os", "features"]
+            .into_iter()
+            .filter_map(|key| Some((key.to_string(), rule.get(key)?.clone())))
+            .collect();
+        if mismatch.is_none() {
+            trace.allowed = rule_action(rule) == "allow";
+            trace.decided_by = Some(index);
+        }
+        trace.steps.push(RuleStep {
+            action: rule_action(rule).to_string(),
+            demands: Value::Object(demands),
+            matched: mismatch.is_none(),
+            mismatch,
+        });
+    }
+    trace
+}
+
+/// Trazas de todas las librerías y grupos de argumentos con reglas de un version.json.
+/// Las librerías van por nombre Maven y los argumentos como `arguments.<grupo>[<índice>]`.
+pub fn trace_version_rules(
+    version_json: &Value,
+    context: &RuleContext,
+) -> BTreeMap<String, RuleTrace> {
+    let mut traces = BTreeMap::new();
+    let libraries = version_json
+        .get("libraries")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten();
+    for (index, library) in libraries.enumerate() {
+        let Some(rules) = library.get("rules").and_then(Value::as_array) else {
+            continue;
+        };
+        let name = library
+            .get("name")
+            .and_then(Value::as_str)
+            .map(ToString::to_string)
+            .unwrap_or_else(|| format!("libraries[{index}]"));
+        let key = if traces.contains_key(&name) {
+            format!("{name}#{index}")
+        } else {
+            name
+        };
+        traces.insert(key, evaluate_rules_traced(rules, context));
+    }
+    for group in ["game", "jvm"] {
+        let arguments = version_json
+            .get("arguments")
+            .and_then(|arguments| arguments.get(group))
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten();
+        for (index, argument) in arguments.enumerate() {
+            let Some(rules) = argument.get("rules").and_then(Value::as_array) else {
+                continue;
+            };
+            traces.insert(
+                format!("arguments.{group}[{index}]"),
+                evaluate_rules_traced(rules, context),
+            );
+        }
+    }
+    traces
+}
+
+/// `None` si la regla coincide con el contexto; si no, la primera condición incumplida.
+fn rule_mismatch(rule: &Value, context: &RuleContext) -> Option<String> {
     if let Some(os_rule) = rule.get("os") {
         let Some(os_obj) = os_rule.as_object() else {
-            return false;
+            return Some("os no es un objeto".to_string());
         };
 
         if let Some(name) = os_obj.get("name").and_then(Value::as_str) {
             if !os_name_matches(name, context.os_name) {
-                return false;
+                return Some(format!(
+                    "os.name: se pide {name}, el sistema es {:?}",
+                    context.os_name
+                ));
+            }
+        }
+
+        if let Some(version) = os_obj.get("version").and_then(Value::as_str) {
+            if let Some(mismatch) = os_version_mismatch(version, &context.os_version) {
+                return Some(mismatch);
             }
         }
 
         if let Some(arch) = os_obj.get("arch").and_then(Value::as_str) {
             if !arch_matches(arch, &context.arch) {
-                return false;
+                return Some(format!(
+                    "os.arch: se pide {arch}, el sistema es {}",
+                    context.arch
+                ));
             }
         }
     }
 
     if let Some(feature_rule) = rule.get("features") {
         let Some(feature_obj) = feature_rule.as_object() else {
-            return false;
+            return Some("features no es un objeto".to_string());
         };
 
         for (key, expected) in feature_obj {
@@ -90,20 +256,36 @@ fn rule_matches_context(rule: &Value, context: &RuleContext) -> bool {
                 continue;
             };
 
-            let actual = match key.as_str() {
-                "is_demo_user" => context.features.is_demo_user,
-                "has_custom_resolution" => context.features.has_custom_resolution,
-                "is_quick_play" => context.features.is_quick_play,
-                _ => false,
+            let Some(actual) = context.features.value_of(key) else {
+                log_unknown_feature_once(key);
+                return Some(format!("feature desconocida: {key}"));
             };
 
             if actual != expected_bool {
-                return false;
+                return Some(format!(
+                    "feature {key}: se pide {expected_bool}, es {actual}"
+                ));
             }
         }
     }
 
-    true
+    None
+}
+
+/// `os.version` es una expresión regular sobre la versión del sistema (`^10\\.`).
+fn os_version_mismatch(pattern: &str, actual: &str) -> Option<String> {
+    if actual.is_empty() {
+        return Some(format!(
+            "os.version: se pide {pattern}, la versión del sistema es desconocida"
+        ));
+    }
+    match Regex::new(pattern) {
+        Ok(regex) if regex.is_match(actual) => None,
+        Ok(_) => Some(format!(
+            "os.version: se pide {pattern}, el sistema es {actual}"
+        )),
+        Err(err) => Some(format!("os.version: expresión inválida {pattern}: {err}")),
+    }
 }
 
 fn os_name_matches(expected: &str, actual: OsName) -> bool {
@@ -128,3 +310,138 @@ fn arch_matches(expected: &str, actual: &str) -> bool {
         _ => false,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn context(os_name: OsName, arch: &str, os_version: &str) -> RuleContext {
+        RuleContext {
+            os_name,
+            arch: arch.to_string(),
+            os_version: os_version.to_string(),
+            features: RuleFeatures::default(),
+        }
+    }
+
+    fn rules(value: Value) -> Vec<Value> {
+        value.as_array().cloned().unwrap_or_default()
+    }
+
+    #[test]
+    fn rule_shapes_from_vanilla_manifests() {
+        let windows_10 = context(OsName::Windows, "x86_64", "10.0");
+        let windows_7 = context(OsName::Windows, "x86", "6.1");
+        let linux = context(OsName::Linux, "x86_64", "6.5.0-14-generic");
+        let mac_arm = context(OsName::Macos, "aarch64", "14.2");
+        let old_mac = context(OsName::Macos, "x86_64", "10.5.8");
+
+        // 1.8 / 1.12: lwjgl-platform y twitch: todo menos osx.
+        let not_osx = rules(
+            json!([{ "action": "allow" }, { "action": "disallow", "os": { "name": "osx" } }]),
+        );
+        assert!(evaluate_rules(&not_osx, &linux));
+        assert!(!evaluate_rules(&not_osx, &mac_arm));
+        // 1.8: lwjgl 2.9.1 excluida en Mac OS X 10.5.
+        let not_leopard = rules(json!([
+            { "action": "allow" },
+            { "action": "disallow", "os": { "name": "osx", "version": "^10\\.5\\.\\d$" } }
+        ]));
+        assert!(!evaluate_rules(&not_leopard, &old_mac));
+        assert!(evaluate_rules(&not_leopard, &mac_arm));
+
+        // 1.16: argumentos JVM por sistema, versión de Windows y JVM de 32 bits.
+        let only_osx = rules(json!([{ "action": "allow", "os": { "name": "osx" } }]));
+        assert!(evaluate_rules(&only_osx, &mac_arm));
+        assert!(!evaluate_rules(&only_osx, &windows_10));
+        let windows_ten =
+            rules(json!([{ "action": "allow", "os": { "name": "windows", "version": "^10\\." } }]));
+        assert!(evaluate_rules(&windows_ten, &windows_10));
+        assert!(!evaluate_rules(&windows_ten, &windows_7));
+        assert!(!evaluate_rules(
+            &windows_ten,
+            &context(OsName::Windows, "x86_64", "")
+        ));
+        let x86 = rules(json!([{ "action": "allow", "os": { "arch": "x86" } }]));
+        assert!(evaluate_rules(&x86, &windows_7));
+        assert!(!evaluate_rules(&x86, &windows_10));
+        assert!(!evaluate_rules(&x86, &mac_arm));
+
+        // 1.20: natives por sistema y argumentos de juego por feature.
+        let only_linux = rules(json!([{ "action": "allow", "os": { "name": "linux" } }]));
+        assert!(evaluate_rules(&only_linux, &linux));
+        assert!(!evaluate_rules(&only_linux, &windows_10));
+        let demo = rules(json!([{ "action": "allow", "features": { "is_demo_user": true } }]));
+        assert!(!evaluate_rules(&demo, &linux));
+        let mut demo_user = linux.clone();
+        demo_user.features.is_demo_user = true;
+        assert!(evaluate_rules(&demo, &demo_user));
+        let quick_play =
+            rules(json!([{ "action": "allow", "features": { "is_quick_play_realms": true } }]));
+        assert!(!evaluate_rules(&quick_play, &linux));
+
+        let invalid = rules(json!([{ "action": "allow", "os": { "version": "^10\\.(" } }]));
+        assert!(!evaluate_rules(&invalid, &windows_10));
+    }
+
+    #[test]
+    fn trace_records_demands_and_deciding_rule() {
+        let version_json = json!({
+            "libraries": [
+                { "name": "org.lwjgl:lwjgl:3.3.1" },
+                {
+                    "name": "org.lwjgl:lwjgl:3.3.1:natives-macos-arm64",
+                    "rules": [{ "action": "allow", "os": { "name": "osx" } }]
+                },
+                {
+                    "name": "tv.twitch:twitch-platform:5.16",
+                    "rules": [{ "action": "allow" }, { "action": "disallow", "os": { "name": "osx" } }]
+                }
+            ],
+            "arguments": {
+                "jvm": [
+                    { "rules": [{ "action": "allow", "os": { "name": "windows", "version": "^10\\." } }], "value": ["-Dos.name=Windows 10"] },
+                    "-cp"
+                ]
+            }
+        });
+        let linux = context(OsName::Linux, "x86_64", "6.5.0");
+        let traces = trace_version_rules(&version_json, &linux);
+        assert_eq!(traces.len(), 3, "{traces:?}");
+
+        let natives = &traces["org.lwjgl:lwjgl:3.3.1:natives-macos-arm64"];
+        assert!(!natives.allowed);
+        assert_eq!(natives.decided_by, None);
+        assert_eq!(natives.steps[0].demands, json!({ "os": { "name": "osx" } }));
+        assert!(natives.steps[0]
+            .mismatch
+            .as_deref()
+            .is_some_and(|reason| reason.starts_with("os.name: se pide osx")));
+
+        let twitch = &traces["tv.twitch:twitch-platform:5.16"];
+        assert!(twitch.allowed);
+        assert_eq!(twitch.decided_by, Some(0));
+        assert_eq!(twitch.steps[0].demands, json!({}));
+        assert!(!twitch.steps[1].matched);
+
+        let windows_args = &traces["arguments.jvm[0]"];
+        assert!(!windows_args.allowed);
+        let windows_10 = context(OsName::Windows, "x86_64", "10.0");
+        assert!(trace_version_rules(&version_json, &windows_10)["arguments.jvm[0]"].allowed);
+
+        for (name, trace) in &traces {
+            let library_rules = version_json["libraries"]
+                .as_array()
+                .and_then(|libs| libs.iter().find(|lib| lib["name"] == name.as_str()))
+                .map(|lib| rules(lib["rules"].clone()));
+            if let Some(library_rules) = library_rules {
+                assert_eq!(
+                    trace.allowed,
+                    evaluate_rules(&library_rules, &linux),
+                    "{name}"
+                );
+            }
+        }
+    }
+}
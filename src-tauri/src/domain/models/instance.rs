@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::domain::minecraft::{game_flags::OptionalGameFlags, library::LibraryOverride};
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct LaunchAuthSession {
@@ -12,6 +14,10 @@ pub struct LaunchAuthSession {
     pub microsoft_refresh_token: Option<String>,
     #[serde(default)]
     pub premium_verified: bool,
+    /// `true` cuando el backend ya guardó esta sesión en `config/accounts.json`; el frontend
+    /// no necesita conservar los secretos.
+    #[serde(default)]
+    pub persisted: bool,
 }
 
 #[derive(Debug, Deserialize)]
@@ -23,11 +29,19 @@ pub struct CreateInstancePayload {
     pub loader: String,
     pub loader_version: String,
     pub required_java_major: Option<u32>,
+    /// 0 (o ausente) usa la RAM recomendada de la plantilla.
+    #[serde(default)]
     pub ram_mb: u32,
     pub java_args: Vec<String>,
     pub auth_session: LaunchAuthSession,
     #[serde(default)]
     pub creation_request_id: Option<String>,
+    /// Plantilla (integrada o de usuario) a aplicar tras la creación base.
+    #[serde(default)]
+    pub template: Option<String>,
+    /// Perfil de ajustes del jugador (options.txt, teclas) con el que empieza la instancia.
+    #[serde(default)]
+    pub apply_settings_profile: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -40,6 +54,12 @@ pub struct CreateInstanceResult {
     pub instance_root: String,
     pub minecraft_path: String,
     pub logs: Vec<String>,
+    /// Avisos sobre el sistema de archivos de la instancia (nube, red, FAT...).
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+    /// Mods de la plantilla que no se pudieron instalar (la instancia se crea igualmente).
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub template_failures: Vec<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -49,21 +69,85 @@ pub struct InstanceSummary {
     pub name: String,
     pub group: String,
     pub instance_root: String,
+    pub tags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub health: Option<InstanceHealth>,
+    /// `NEEDS_RECOVERY` si quedó una operación interrumpida (ver `.op-journal.json`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstanceHealthFinding {
+    pub code: String,
+    /// `warning` o `error`.
+    pub severity: String,
+    pub message: String,
+}
+
+/// Estado agregado para la tarjeta de la instancia. Solo usa señales cacheadas o
+/// baratas; las comprobaciones pesadas viven en sus propios comandos.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstanceHealth {
+    /// `ok`, `warning` o `error`.
+    pub status: String,
+    pub findings: Vec<InstanceHealthFinding>,
+}
+
+/// Capacidades del sistema de archivos donde vive la instancia, detectadas al crearla
+/// o en el primer lanzamiento y guardadas en la metadata.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct FilesystemCapabilities {
+    /// Nombre del sistema de archivos (`ntfs`, `ext4`, `exfat`, `smb2`...) o `unknown`.
+    pub filesystem: String,
+    /// `local`, `network`, `removable` o `unknown`.
+    pub drive_kind: String,
+    pub is_cloud_synced: bool,
+    #[serde(default)]
+    pub cloud_provider: Option<String>,
+    pub supports_atomic_rename: bool,
+    pub supports_exec_bit: bool,
+    #[serde(default)]
+    pub probed_at: String,
 }
 
+/// Estado de las instancias escritas antes de existir el campo `state`.
+pub fn default_state() -> String {
+    "READY".to_string()
+}
+
+fn default_loader() -> String {
+    "vanilla".to_string()
+}
+
+fn default_ram_mb() -> u32 {
+    4096
+}
+
+/// Metadata de `.instance.json`; los campos nuevos llevan valor por defecto.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct InstanceMetadata {
     pub name: String,
+    #[serde(default)]
     pub group: String,
     pub minecraft_version: String,
     #[serde(default)]
     pub version_id: String,
+    #[serde(default = "default_loader")]
     pub loader: String,
+    #[serde(default)]
     pub loader_version: String,
+    #[serde(default = "default_ram_mb")]
     pub ram_mb: u32,
+    #[serde(default)]
     pub java_args: Vec<String>,
+    #[serde(default)]
     pub java_path: String,
+    #[serde(default)]
     pub java_runtime: String,
     #[serde(default)]
     pub java_version: String,
@@ -71,8 +155,197 @@ pub struct InstanceMetadata {
     pub required_java_major: u32,
     #[serde(default)]
     pub created_at: String,
-    #[serde(default)]
+    #[serde(default = "default_state")]
     pub state: String,
+    #[serde(default)]
     pub last_used: Option<String>,
+    #[serde(default)]
     pub internal_uuid: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filesystem: Option<FilesystemCapabilities>,
+    /// Carpeta de mods compartida que el loader usa en lugar de `minecraft/mods`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mods_dir_override: Option<String>,
+    /// Metadata regenerada al adoptar una carpeta huérfana; los campos son estimaciones.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub adopted: bool,
+    /// Etiquetas libres normalizadas (minúsculas, máx. 32 caracteres, máx. 20).
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Aspectos bloqueados por el autor del pack (`mods`, `java_args`, `loader_version`...).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub locked_fields: Vec<String>,
+    /// Nota que se muestra al rechazar una edición de un campo bloqueado.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub locked_by: String,
+    /// Flags de privacidad (`--disableMultiplayer`, `--disableChat`, telemetría).
+    #[serde(default, skip_serializing_if = "OptionalGameFlags::is_empty")]
+    pub optional_game_flags: OptionalGameFlags,
+    /// Build concreta de Java (`runtime/javaNN/<build>`); sin ella se usa la más reciente.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub java_build_pin: Option<String>,
+    /// Permite escribir RAM y argumentos de Java en el `instance.cfg` del launcher de
+    /// origen (Prism/MultiMC). Solo para atajos y siempre por opt-in explícito.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub source_settings_write: bool,
+    /// Antigüedad máxima de logs, crash reports y capturas antes de la limpieza automática.
+    #[serde(default, skip_serializing_if = "RetentionSettings::is_default")]
+    pub retention: RetentionSettings,
+    /// Exclusiones y cambios de versión de librerías aplicados al resolver el classpath.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub library_overrides: Vec<LibraryOverride>,
+    /// Modpack de CurseForge/Modrinth del que se importó la instancia.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pack_origin: Option<PackOrigin>,
+    /// Añade al lanzar los flags de GC recomendados para el runtime y la RAM (ver
+    /// `jvm_tuning`). Activado en instancias nuevas; las existentes no cambian.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub auto_jvm_tuning: bool,
+    /// GPU con la que lanzar en equipos híbridos: `integrated`, `dedicated` o el nombre de un
+    /// adaptador detectado. `None` deja la elección al sistema.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preferred_gpu: Option<String>,
+    /// Qué copiar en las copias de seguridad de la carpeta de juego.
+    #[serde(default, skip_serializing_if = "BackupSettings::is_default")]
+    pub backup: BackupSettings,
+    /// Modo estricto para autores de modpacks: los avisos tolerados del lanzamiento pasan a
+    /// ser errores.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub strict_mode: bool,
+    /// Desactiva la comprobación de mods duplicados (mismo id en varios jars) al lanzar.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub skip_duplicate_mod_check: bool,
+    /// Ajustes que el launcher aplica a la ventana del juego tras lanzarlo.
+    #[serde(default, skip_serializing_if = "WindowTweaks::is_default")]
+    pub window_tweaks: WindowTweaks,
+    /// Comandos externos del lanzamiento. Nunca se ejecutan sin la aprobación guardada en
+    /// la configuración del launcher (ver `hook_approval`).
+    #[serde(default, skip_serializing_if = "LaunchHooks::is_default")]
+    pub launch_hooks: LaunchHooks,
+    /// Reglas para ocultar o agrupar en la consola el ruido de mods muy habladores.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub console_filters: Vec<ConsoleFilterRule>,
+}
+
+/// Regla de filtro de la consola. Las líneas ocultas siguen escribiéndose en el log de
+/// sesión; solo dejan de emitirse a la interfaz.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ConsoleFilterRule {
+    /// Subcadena a buscar en la línea o, con `regex`, expresión regular.
+    pub pattern: String,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub regex: bool,
+    pub action: ConsoleFilterAction,
+    /// Limita la regla a las líneas cuyo campo `source` parseado contiene este texto.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ConsoleFilterAction {
+    /// No se emite.
+    Hide,
+    /// Se emite la primera vez; las repeticiones cercanas llevan un contador.
+    Collapse,
+}
+
+/// Comando previo al lanzamiento y envoltorio de la JVM (`wrapper java ...`), con la misma
+/// sintaxis que `PreLaunchCommand`/`WrapperCommand` de Prism.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct LaunchHooks {
+    pub pre_launch_command: Option<String>,
+    pub wrapper_command: Option<String>,
+}
+
+impl LaunchHooks {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Ventana sin borde a pantalla completa y/o siempre encima, sin mods. El launcher los
+/// vuelve a aplicar si el juego recrea su ventana.
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct WindowTweaks {
+    pub borderless: bool,
+    pub always_on_top: bool,
+}
+
+impl WindowTweaks {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Proyecto y versión del modpack de origen, para buscar actualizaciones del pack.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PackOrigin {
+    /// `modrinth` o `curseforge`.
+    pub platform: String,
+    pub project_id: String,
+    /// Versión en Modrinth o id de archivo en CurseForge.
+    pub version_id: String,
+    #[serde(default)]
+    pub version_number: String,
+}
+
+/// Retención por instancia, en días. Las capturas no se borran salvo que se indique.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RetentionSettings {
+    pub log_retention_days: u32,
+    pub crash_report_retention_days: u32,
+    /// `None` = nunca; `Some(días)` = borrar capturas más antiguas.
+    pub screenshot_retention: Option<u32>,
+}
+
+impl Default for RetentionSettings {
+    fn default() -> Self {
+        Self {
+            log_retention_days: 30,
+            crash_report_retention_days: 90,
+            screenshot_retention: None,
+        }
+    }
+}
+
+impl RetentionSettings {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Copias de seguridad por instancia. `paths` son relativas a la carpeta de juego.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct BackupSettings {
+    pub enabled: bool,
+    pub paths: Vec<String>,
+    /// Copias que se conservan; las más antiguas se borran al crear una nueva.
+    pub keep_last: u32,
+}
+
+impl Default for BackupSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            paths: vec![
+                "saves".to_string(),
+                "config".to_string(),
+                "options.txt".to_string(),
+            ],
+            keep_last: 5,
+        }
+    }
+}
+
+impl BackupSettings {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
 }
@@ -1 +1,166 @@
 // Cache de manifiestos y metadatos.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::shared::result::AppResult;
+
+/// Copia local de un documento remoto junto a la fuente y la fecha de descarga.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CachedDocument<T> {
+    pub source_url: String,
+    /// RFC 3339.
+    pub fetched_at: String,
+    /// Los alias leen las cachés de actualizaciones y noticias guardadas con su formato anterior.
+    #[serde(alias = "manifest", alias = "entries")]
+    pub data: T,
+}
+
+impl<T> CachedDocument<T> {
+    /// La copia sirve si es de la misma fuente y tiene menos de `max_age`.
+    pub fn is_fresh(&self, source_url: &str, now: DateTime<Utc>, max_age: Duration) -> bool {
+        let Ok(fetched_at) = DateTime::parse_from_rfc3339(&self.fetched_at) else {
+            return false;
+        };
+        self.source_url == source_url
+            && now.signed_duration_since(fetched_at.with_timezone(&Utc)) < max_age
+    }
+}
+
+pub fn cache_file(launcher_root: &Path, dir: &str, name: &str) -> PathBuf {
+    launcher_root.join(dir).join(format!("{name}.json"))
+}
+
+pub fn read_cached<T: DeserializeOwned>(path: &Path) -> Option<CachedDocument<T>> {
+    serde_json::from_str(&fs::read_to_string(path).ok()?).ok()
+}
+
+/// `what` nombra la caché en los mensajes de error ("noticias", "actualizaciones").
+pub fn write_cached<T: Serialize>(
+    path: &Path,
+    cached: &CachedDocument<T>,
+    what: &str,
+) -> AppResult<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|err| {
+            format!(
+                "No se pudo crear la caché de {what} {}: {err}",
+                parent.display()
+            )
+        })?;
+    }
+    let raw = serde_json::to_string_pretty(cached)
+        .map_err(|err| format!("No se pudo serializar la caché de {what}: {err}"))?;
+    let tmp = path.with_extension("json.part");
+    fs::write(&tmp, raw)
+        .and_then(|_| fs::rename(&tmp, path))
+        .map_err(|err| {
+            format!(
+                "No se pudo guardar la caché de {what} {}: {err}",
+                path.display()
+            )
+        })
+}
+
+/// Copia en caché si está vigente; si no, `fetch` y, sin conexión, la copia caducada.
+/// Devuelve también si la copia está caducada.
+pub fn load_cached<T: Serialize + DeserializeOwned + Clone>(
+    path: &Path,
+    source_url: &str,
+    now: DateTime<Utc>,
+    max_age: Duration,
+    force_refresh: bool,
+    what: &str,
+    fetch: impl FnOnce() -> AppResult<T>,
+) -> AppResult<(CachedDocument<T>, bool)> {
+    let cached = read_cached::<T>(path);
+    if let Some(cached) = cached
+        .as_ref()
+        .filter(|cached| !force_refresh && cached.is_fresh(source_url, now, max_age))
+    {
+        return Ok((cached.clone(), false));
+    }
+    match fetch() {
+        Ok(data) => {
+            let fresh = CachedDocument {
+                source_url: source_url.to_string(),
+                fetched_at: now.to_rfc3339(),
+                data,
+            };
+            if let Err(err) = write_cached(path, &fresh, what) {
+                log::warn!("⚠ {err}");
+            }
+            Ok((fresh, false))
+        }
+        Err(err) => match cached {
+            Some(cached) => {
+                log::warn!("⚠ Sin conexión ({what}), se usa la copia en caché: {err}");
+                let stale = !cached.is_fresh(source_url, now, max_age);
+                Ok((cached, stale))
+            }
+            None => Err(err),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cached_copy_is_fresh_for_its_max_age_and_survives_offline_fetches() {
+        let dir = std::env::temp_dir().join(format!("cache-manager-{}", uuid::Uuid::new_v4()));
+        let path = cache_file(&dir, "cache/news", "minecraft");
+        let fetched_at = DateTime::parse_from_rfc3339("2026-10-01T12:00:00Z")
+            .expect("fecha")
+            .with_timezone(&Utc);
+        let source = "https://example.invalid/news.json";
+        let max_age = Duration::hours(6);
+        let at = |hours: i64| fetched_at + Duration::hours(hours);
+
+        let (fresh, stale) = load_cached(
+            &path,
+            source,
+            fetched_at,
+            max_age,
+            false,
+            "noticias",
+            || Ok(vec!["uno".to_string()]),
+        )
+        .expect("descarga");
+        assert!(!stale);
+        assert!(fresh.is_fresh(source, at(5), max_age));
+        assert!(!fresh.is_fresh(source, at(6), max_age));
+        assert!(!fresh.is_fresh("https://otra.fuente/news.json", at(1), max_age));
+
+        let (cached, stale) =
+            load_cached::<Vec<String>>(&path, source, at(1), max_age, false, "noticias", || {
+                panic!("la copia vigente no se vuelve a descargar")
+            })
+            .expect("caché");
+        assert_eq!((cached.data, stale), (vec!["uno".to_string()], false));
+
+        let (offline, stale) =
+            load_cached::<Vec<String>>(&path, source, at(7), max_age, false, "noticias", || {
+                Err("sin red".to_string())
+            })
+            .expect("copia caducada");
+        assert_eq!((offline.data, stale), (vec!["uno".to_string()], true));
+
+        fs::write(
+            &path,
+            r#"{"sourceUrl":"x","fetchedAt":"2026-10-01T12:00:00Z","entries":["viejo"]}"#,
+        )
+        .expect("formato anterior");
+        let legacy = read_cached::<Vec<String>>(&path).expect("alias");
+        assert_eq!(legacy.data, vec!["viejo".to_string()]);
+
+        let _ = fs::remove_dir_all(dir);
+    }
+}
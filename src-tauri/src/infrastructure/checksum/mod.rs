@@ -1 +1,2 @@
 pub mod sha1;
+pub mod verification_cache;
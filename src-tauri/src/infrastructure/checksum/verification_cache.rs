@@ -0,0 +1,192 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    time::UNIX_EPOCH,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{infrastructure::checksum::sha1::compute_file_sha1, shared::result::AppResult};
+
+/// Archivo de la caché dentro de `launcher_root/cache`.
+pub const VERIFICATION_CACHE_FILE: &str = "verification-cache.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+struct CachedHash {
+    size: u64,
+    modified_ms: u128,
+    sha1: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    manifest: Option<JarManifestInfo>,
+}
+
+/// Licencia y fabricante declarados dentro de un jar, guardados junto a su hash para no
+/// volver a abrir el archivo mientras no cambie.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct JarManifestInfo {
+    /// `Bundle-License` del manifest o la licencia reconocida en un archivo LICENSE.
+    pub license: Option<String>,
+    /// `Implementation-Vendor` (o `Bundle-Vendor`).
+    pub vendor: Option<String>,
+    /// Archivo de licencia embebido, aunque su texto no se haya reconocido.
+    pub license_file: Option<String>,
+}
+
+/// SHA1 de archivos ya verificados, indexados por ruta. Una entrada solo vale mientras el
+/// tamaño y la fecha de modificación no cambien; si cambian se vuelve a hashear.
+#[derive(Debug, Default)]
+pub struct VerificationCache {
+    path: PathBuf,
+    entries: HashMap<String, CachedHash>,
+    dirty: bool,
+}
+
+fn file_stamp(path: &Path) -> Option<(u64, u128)> {
+    let metadata = fs::metadata(path).ok()?;
+    let modified = metadata
+        .modified()
+        .ok()?
+        .duration_since(UNIX_EPOCH)
+        .ok()?
+        .as_millis();
+    Some((metadata.len(), modified))
+}
+
+impl VerificationCache {
+    pub fn load(launcher_root: &Path) -> Self {
+        let path = launcher_root.join("cache").join(VERIFICATION_CACHE_FILE);
+        let entries = fs::read_to_string(&path)
+            .ok()
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+            .unwrap_or_default();
+        Self {
+            path,
+            entries,
+            dirty: false,
+        }
+    }
+
+    /// SHA1 y tamaño del archivo, desde la caché si sigue vigente.
+    pub fn sha1(&mut self, file: &Path) -> AppResult<(String, u64)> {
+        let (size, modified_ms) = file_stamp(file)
+            .ok_or_else(|| format!("No se pudo leer metadata de {}", file.display()))?;
+        let key = file.display().to_string();
+        if let Some(cached) = self.entries.get(&key) {
+            if cached.size == size && cached.modified_ms == modified_ms {
+                return Ok((cached.sha1.clone(), size));
+            }
+        }
+        let sha1 = compute_file_sha1(file)?;
+        self.entries.insert(
+            key,
+            CachedHash {
+                size,
+                modified_ms,
+                sha1: sha1.clone(),
+                manifest: None,
+            },
+        );
+        self.dirty = true;
+        Ok((sha1, size))
+    }
+
+    /// Registra un SHA1 calculado fuera (p. ej. en la reparación completa).
+    pub fn record(&mut self, file: &Path, sha1: &str) {
+        if let Some((size, modified_ms)) = file_stamp(file) {
+            let manifest = self
+                .current_entry(file)
+                .filter(|cached| cached.sha1.eq_ignore_ascii_case(sha1))
+                .and_then(|cached| cached.manifest.clone());
+            self.entries.insert(
+                file.display().to_string(),
+                CachedHash {
+                    size,
+                    modified_ms,
+                    sha1: sha1.to_ascii_lowercase(),
+                    manifest,
+                },
+            );
+            self.dirty = true;
+        }
+    }
+
+    fn current_entry(&self, file: &Path) -> Option<&CachedHash> {
+        let (size, modified_ms) = file_stamp(file)?;
+        self.entries
+            .get(&file.display().to_string())
+            .filter(|cached| cached.size == size && cached.modified_ms == modified_ms)
+    }
+
+    /// Cabeceras ya leídas del jar; `None` si no se leyeron o el archivo cambió desde entonces.
+    pub fn jar_manifest(&self, file: &Path) -> Option<JarManifestInfo> {
+        self.current_entry(file)?.manifest.clone()
+    }
+
+    /// Guarda las cabeceras junto al hash vigente; sin hash vigente no se guarda nada.
+    pub fn record_jar_manifest(&mut self, file: &Path, manifest: JarManifestInfo) {
+        if self.current_entry(file).is_none() {
+            return;
+        }
+        if let Some(cached) = self.entries.get_mut(&file.display().to_string()) {
+            cached.manifest = Some(manifest);
+            self.dirty = true;
+        }
+    }
+
+    /// Guarda la caché si cambió, descartando entradas de archivos que ya no existen.
+    pub fn save(&mut self) {
+        if !self.dirty {
+            return;
+        }
+        self.entries.retain(|path, _| Path::new(path).exists());
+        if let Some(parent) = self.path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        match serde_json::to_string(&self.entries) {
+            Ok(raw) => {
+                if let Err(err) = fs::write(&self.path, raw) {
+                    log::warn!(
+                        "⚠ No se pudo guardar la caché de verificación {}: {err}",
+                        self.path.display()
+                    );
+                }
+            }
+            Err(err) => log::warn!("⚠ No se pudo serializar la caché de verificación: {err}"),
+        }
+        self.dirty = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reuses_hash_until_file_changes() {
+        let root = std::env::temp_dir().join(format!(
+            "interface-verification-cache-{}",
+            std::process::id()
+        ));
+        fs::create_dir_all(&root).expect("root");
+        let jar = root.join("lib.jar");
+        fs::write(&jar, b"abc").expect("jar");
+
+        let mut cache = VerificationCache::load(&root);
+        let (first, size) = cache.sha1(&jar).expect("sha1");
+        assert_eq!(first, "a9993e364706816aba3e25717850c26c9cd0d89d");
+        assert_eq!(size, 3);
+        cache.save();
+
+        let mut reloaded = VerificationCache::load(&root);
+        assert_eq!(reloaded.sha1(&jar).expect("sha1").0, first);
+        assert!(!reloaded.dirty);
+
+        fs::write(&jar, b"abcd").expect("jar");
+        assert_ne!(reloaded.sha1(&jar).expect("sha1").0, first);
+
+        let _ = fs::remove_dir_all(root);
+    }
+}
@@ -1,7 +1,10 @@
+use std::{fs, path::Path};
+
 use reqwest::blocking::Client;
 
 use crate::{
     domain::models::java::JavaRuntime,
+    infrastructure::http::tls::with_custom_roots,
     platform::{linux::current_os, windows::detect_architecture},
     shared::result::AppResult,
 };
@@ -29,20 +32,97 @@ struct AdoptiumRelease {
     binaries: Vec<AdoptiumBinary>,
 }
 
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CachedTemurinAsset {
+    download_url: String,
+    checksum: String,
+    file_name: String,
+    image_type: String,
+    cached_at: String,
+}
+
 pub fn build_http_client() -> AppResult<Client> {
-    Client::builder()
+    with_custom_roots(Client::builder())
         .user_agent("InterfaceLauncher/0.1")
         .build()
         .map_err(|err| format!("No se pudo crear cliente HTTP: {err}"))
 }
 
+/// Resuelve el binario Temurin consultando la API de Adoptium. La última respuesta válida
+/// se guarda en `cache_dir/adoptium` y se reutiliza si la API no responde.
 pub fn resolve_temurin_asset(
     client: &Client,
     runtime: JavaRuntime,
+    cache_dir: &Path,
+    logs: &mut Vec<String>,
 ) -> AppResult<(String, String, String, String)> {
     let arch = detect_architecture()?;
     let os = current_os();
+    let cache_path = cache_dir
+        .join("adoptium")
+        .join(format!("{}-{os}-{arch}.json", runtime.as_dir_name()));
+
+    cached_temurin_asset(
+        &cache_path,
+        fetch_temurin_asset(client, runtime, arch, os),
+        logs,
+    )
+}
+
+/// Guarda la respuesta del catálogo si llegó; si falló, devuelve la última guardada.
+fn cached_temurin_asset(
+    cache_path: &Path,
+    fetched: AppResult<(String, String, String, String)>,
+    logs: &mut Vec<String>,
+) -> AppResult<(String, String, String, String)> {
+    match fetched {
+        Ok(asset) => {
+            write_cached_temurin_asset(cache_path, &asset);
+            Ok(asset)
+        }
+        Err(err) => {
+            let cached = fs::read_to_string(cache_path)
+                .ok()
+                .and_then(|raw| serde_json::from_str::<CachedTemurinAsset>(&raw).ok())
+                .ok_or(err.clone())?;
+            logs.push(format!(
+                "⚠ API de Adoptium no disponible ({err}). Usando catálogo en caché del {}.",
+                cached.cached_at
+            ));
+            Ok((
+                cached.download_url,
+                cached.checksum,
+                cached.file_name,
+                cached.image_type,
+            ))
+        }
+    }
+}
+
+fn write_cached_temurin_asset(cache_path: &Path, asset: &(String, String, String, String)) {
+    let (download_url, checksum, file_name, image_type) = asset;
+    let cached = CachedTemurinAsset {
+        download_url: download_url.clone(),
+        checksum: checksum.clone(),
+        file_name: file_name.clone(),
+        image_type: image_type.clone(),
+        cached_at: chrono::Utc::now().to_rfc3339(),
+    };
+    if let Some(parent) = cache_path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    if let Ok(raw) = serde_json::to_string_pretty(&cached) {
+        let _ = fs::write(cache_path, raw);
+    }
+}
 
+fn fetch_temurin_asset(
+    client: &Client,
+    runtime: JavaRuntime,
+    arch: &str,
+    os: &str,
+) -> AppResult<(String, String, String, String)> {
     let mut last_error = String::new();
     for image_type in ["jre", "jdk"] {
         let api = format!(
@@ -81,6 +161,50 @@ pub fn resolve_temurin_asset(
     ))
 }
 
+/// Resuelve el binario de una release concreta de Temurin (`jdk-17.0.9+9`) con el
+/// endpoint `release_name` de Adoptium. Prueba JRE y, si no existe, JDK.
+pub fn resolve_temurin_release_asset(
+    client: &Client,
+    runtime: JavaRuntime,
+    release_name: &str,
+) -> AppResult<(String, String, String, String)> {
+    let arch = detect_architecture()?;
+    let os = current_os();
+    let encoded = release_name.trim().replace('+', "%2B");
+    let mut last_error = String::new();
+    for image_type in ["jre", "jdk"] {
+        let api = format!(
+            "https://api.adoptium.net/v3/assets/release_name/eclipse/{encoded}?architecture={arch}&heap_size=normal&image_type={image_type}&os={os}&project=jdk"
+        );
+        let response = client
+            .get(&api)
+            .send()
+            .map_err(|err| format!("No se pudo consultar la release {release_name}: {err}"))?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            last_error = format!("Sin binario {image_type} para {release_name} ({api}).");
+            continue;
+        }
+        let release = response
+            .error_for_status()
+            .map_err(|err| format!("No se pudo consultar la release {release_name}: {err}"))?
+            .json::<AdoptiumRelease>()
+            .map_err(|err| format!("Respuesta inválida para la release {release_name}: {err}"))?;
+        if let Some(package) = release
+            .binary
+            .or_else(|| release.binaries.into_iter().next())
+            .map(|binary| binary.package)
+        {
+            return build_asset_tuple(client, package, image_type);
+        }
+        last_error = format!("La release {release_name} no tiene binarios para {os}/{arch}.");
+    }
+
+    Err(format!(
+        "No se encontró la release {release_name} de Temurin para Java {}. {last_error}",
+        runtime.major()
+    ))
+}
+
 fn build_asset_tuple(
     client: &Client,
     package: AdoptiumBinaryPackage,
@@ -117,3 +241,36 @@ fn build_asset_tuple(
 
     Ok((download_link, checksum, file_name, image_type.to_string()))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn catalog_falls_back_to_the_last_cached_answer() {
+        let cache_dir =
+            std::env::temp_dir().join(format!("interface-adoptium-cache-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&cache_dir);
+        let cache_path = cache_dir.join("adoptium").join("java17-linux-x64.json");
+        let asset = (
+            "https://example.invalid/jre.tar.gz".to_string(),
+            "abc123".to_string(),
+            "jre.tar.gz".to_string(),
+            "jre".to_string(),
+        );
+        let mut logs = Vec::new();
+
+        let offline = cached_temurin_asset(&cache_path, Err("sin red".to_string()), &mut logs);
+        assert_eq!(offline, Err("sin red".to_string()));
+
+        let fetched = cached_temurin_asset(&cache_path, Ok(asset.clone()), &mut logs);
+        assert_eq!(fetched, Ok(asset.clone()));
+        assert!(logs.is_empty());
+
+        let offline = cached_temurin_asset(&cache_path, Err("sin red".to_string()), &mut logs);
+        assert_eq!(offline, Ok(asset));
+        assert!(logs[0].contains("sin red"));
+
+        let _ = fs::remove_dir_all(cache_dir);
+    }
+}
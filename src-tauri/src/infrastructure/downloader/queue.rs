@@ -10,7 +10,10 @@ use std::{
 
 use reqwest::blocking::Client;
 
-use crate::{infrastructure::checksum::sha1::compute_file_sha1, shared::result::AppResult};
+use crate::{
+    infrastructure::{checksum::sha1::compute_file_sha1, http::tls},
+    shared::result::AppResult,
+};
 
 const OFFICIAL_BINARY_HOSTS: [&str; 24] = [
     // Mojang / Microsoft
@@ -79,7 +82,7 @@ pub fn official_retries() -> usize {
 }
 
 pub fn build_official_client() -> AppResult<Client> {
-    Client::builder()
+    tls::with_custom_roots(Client::builder())
         .timeout(official_timeout())
         .connect_timeout(Duration::from_secs(30))
         .tcp_keepalive(Duration::from_secs(60))
@@ -266,6 +269,10 @@ fn perform_download(
 }
 
 pub fn explain_network_error(url: &str, err: &reqwest::Error) -> String {
+    if let Some(interception) = tls::interception_error(err) {
+        return interception.into();
+    }
+
     if err.is_timeout() {
         return format!("Timeout al descargar {url}. Verifica latencia/red/firewall.");
     }
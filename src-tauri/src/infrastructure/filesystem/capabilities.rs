@@ -0,0 +1,319 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::domain::models::instance::FilesystemCapabilities;
+
+const PROBE_FILE: &str = ".fs-probe.tmp";
+const PROBE_RENAMED_FILE: &str = ".fs-probe-renamed.tmp";
+
+/// Sistemas de archivos sin bits de permisos Unix.
+const NO_EXEC_FILESYSTEMS: [&str; 4] = ["vfat", "msdos", "exfat", "fat32"];
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+const NETWORK_FILESYSTEMS: [&str; 7] = ["nfs", "smb", "smb2", "cifs", "smbfs", "afpfs", "webdav"];
+
+/// Sistema de archivos de `path` (que debe existir) y si admite renombrado atómico y bit de
+/// ejecución.
+pub fn probe_filesystem_capabilities(path: &Path) -> FilesystemCapabilities {
+    let (filesystem, drive_kind) = detect_filesystem(path);
+    let cloud_provider = detect_cloud_provider(path, &cloud_sync_roots());
+    let supports_exec_bit =
+        !NO_EXEC_FILESYSTEMS.contains(&filesystem.as_str()) && probe_exec_bit(path).unwrap_or(true);
+
+    FilesystemCapabilities {
+        supports_atomic_rename: probe_atomic_rename(path).unwrap_or(false),
+        supports_exec_bit,
+        is_cloud_synced: cloud_provider.is_some(),
+        cloud_provider,
+        drive_kind: drive_kind.to_string(),
+        filesystem,
+        probed_at: chrono::Utc::now().to_rfc3339(),
+    }
+}
+
+/// Solo el tipo de unidad (`local`, `removable`, `network`, `unknown`), sin archivos de
+/// prueba.
+pub fn detect_drive_kind(path: &Path) -> &'static str {
+    detect_filesystem(path).1
+}
+
+/// Avisos legibles para mostrar en el resultado de creación y en los logs de lanzamiento.
+pub fn capability_warnings(capabilities: &FilesystemCapabilities) -> Vec<String> {
+    let mut warnings = Vec::new();
+    if let Some(provider) = &capabilities.cloud_provider {
+        warnings.push(format!(
+            "⚠ La instancia está dentro de una carpeta sincronizada con {provider}. El cliente de sincronización puede bloquear archivos y romper la extracción de natives; mueve la instancia fuera de esa carpeta."
+        ));
+    }
+    if capabilities.drive_kind == "network" {
+        warnings.push(format!(
+            "⚠ La instancia está en una unidad de red ({}). Los bloqueos y renombrados pueden fallar de forma intermitente.",
+            capabilities.filesystem
+        ));
+    }
+    if !capabilities.supports_exec_bit && !cfg!(target_os = "windows") {
+        warnings.push(format!(
+            "⚠ El sistema de archivos {} no admite permisos de ejecución; no se pueden ejecutar runtimes de Java desde esta ruta.",
+            capabilities.filesystem
+        ));
+    }
+    if !capabilities.supports_atomic_rename {
+        warnings.push(
+            "⚠ El sistema de archivos no admite renombrado atómico; la metadata se escribirá copiando y borrando un temporal."
+                .to_string(),
+        );
+    }
+    warnings
+}
+
+fn probe_atomic_rename(dir: &Path) -> std::io::Result<bool> {
+    let source = dir.join(PROBE_FILE);
+    let target = dir.join(PROBE_RENAMED_FILE);
+    fs::write(&source, b"probe")?;
+    fs::write(&target, b"old")?;
+    let renamed = fs::rename(&source, &target).is_ok()
+        && fs::read(&target)
+            .map(|raw| raw == b"probe")
+            .unwrap_or(false);
+    let _ = fs::remove_file(&source);
+    let _ = fs::remove_file(&target);
+    Ok(renamed)
+}
+
+#[cfg(unix)]
+fn probe_exec_bit(dir: &Path) -> std::io::Result<bool> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let probe = dir.join(PROBE_FILE);
+    fs::write(&probe, b"#!/bin/sh\n")?;
+    let result = fs::set_permissions(&probe, fs::Permissions::from_mode(0o755))
+        .and_then(|_| fs::metadata(&probe))
+        .map(|meta| meta.permissions().mode() & 0o111 == 0o111);
+    let _ = fs::remove_file(&probe);
+    Ok(result.unwrap_or(false))
+}
+
+#[cfg(not(unix))]
+fn probe_exec_bit(_dir: &Path) -> std::io::Result<bool> {
+    Ok(true)
+}
+
+#[cfg(target_os = "linux")]
+fn detect_filesystem(path: &Path) -> (String, &'static str) {
+    let Some(f_type) = statfs(path).map(|stat| (stat.f_type as u64) & 0xFFFF_FFFF) else {
+        return ("unknown".to_string(), "unknown");
+    };
+    let name = match f_type {
+        0xEF53 => "ext4",
+        0x9123_683E => "btrfs",
+        0x5846_5342 => "xfs",
+        0x2FC1_2FC1 => "zfs",
+        0x0102_1994 => "tmpfs",
+        0x794C_7630 => "overlay",
+        0x5346_544E => "ntfs",
+        0x4D44 => "vfat",
+        0x2011_BAB0 => "exfat",
+        0x6969 => "nfs",
+        0x517B => "smb",
+        0xFF53_4D42 => "cifs",
+        0xFE53_4D42 => "smb2",
+        0x6573_5546 => "fuse",
+        _ => "unknown",
+    };
+    (name.to_string(), drive_kind_for(name))
+}
+
+#[cfg(target_os = "macos")]
+fn detect_filesystem(path: &Path) -> (String, &'static str) {
+    let Some(stat) = statfs(path) else {
+        return ("unknown".to_string(), "unknown");
+    };
+    let name = stat
+        .f_fstypename
+        .iter()
+        .take_while(|byte| **byte != 0)
+        .map(|byte| *byte as u8 as char)
+        .collect::<String>()
+        .to_ascii_lowercase();
+    let kind = drive_kind_for(&name);
+    (name, kind)
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn statfs(path: &Path) -> Option<libc::statfs> {
+    use std::os::unix::ffi::OsStrExt;
+
+    let raw = std::ffi::CString::new(path.as_os_str().as_bytes()).ok()?;
+    let mut stat = std::mem::MaybeUninit::<libc::statfs>::zeroed();
+    // SAFETY: `raw` es una cadena C válida y `stat` apunta a memoria del tamaño correcto.
+    let result = unsafe { libc::statfs(raw.as_ptr(), stat.as_mut_ptr()) };
+    if result != 0 {
+        return None;
+    }
+    // SAFETY: statfs devolvió 0, por lo que rellenó la estructura.
+    Some(unsafe { stat.assume_init() })
+}
+
+#[cfg(target_os = "windows")]
+fn detect_filesystem(path: &Path) -> (String, &'static str) {
+    use std::os::windows::ffi::OsStrExt;
+    use windows_sys::Win32::{
+        Storage::FileSystem::{GetDriveTypeW, GetVolumeInformationW, GetVolumePathNameW},
+        System::WindowsProgramming::{DRIVE_FIXED, DRIVE_REMOTE, DRIVE_REMOVABLE},
+    };
+
+    let wide = path
+        .as_os_str()
+        .encode_wide()
+        .chain(std::iter::once(0))
+        .collect::<Vec<u16>>();
+    let mut volume = [0u16; 261];
+    // SAFETY: `wide` termina en nulo y `volume` tiene el tamaño indicado.
+    let ok = unsafe { GetVolumePathNameW(wide.as_ptr(), volume.as_mut_ptr(), volume.len() as u32) };
+    if ok == 0 {
+        return ("unknown".to_string(), "unknown");
+    }
+
+    let mut fs_name = [0u16; 64];
+    // SAFETY: `volume` es una raíz de volumen terminada en nulo; los punteros nulos se
+    // permiten para los campos que no se consultan.
+    let ok = unsafe {
+        GetVolumeInformationW(
+            volume.as_ptr(),
+            std::ptr::null_mut(),
+            0,
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+            fs_name.as_mut_ptr(),
+            fs_name.len() as u32,
+        )
+    };
+    let name = if ok == 0 {
+        "unknown".to_string()
+    } else {
+        let len = fs_name.iter().position(|ch| *ch == 0).unwrap_or(0);
+        String::from_utf16_lossy(&fs_name[..len]).to_ascii_lowercase()
+    };
+
+    // SAFETY: `volume` es una raíz de volumen terminada en nulo.
+    let kind = match unsafe { GetDriveTypeW(volume.as_ptr()) } {
+        DRIVE_REMOTE => "network",
+        DRIVE_REMOVABLE => "removable",
+        DRIVE_FIXED if NO_EXEC_FILESYSTEMS.contains(&name.as_str()) => "removable",
+        DRIVE_FIXED => "local",
+        _ => "unknown",
+    };
+    (name, kind)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn detect_filesystem(_path: &Path) -> (String, &'static str) {
+    ("unknown".to_string(), "unknown")
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn drive_kind_for(filesystem: &str) -> &'static str {
+    if NETWORK_FILESYSTEMS.contains(&filesystem) {
+        "network"
+    } else if NO_EXEC_FILESYSTEMS.contains(&filesystem) {
+        "removable"
+    } else if filesystem == "unknown" {
+        "unknown"
+    } else {
+        "local"
+    }
+}
+
+/// Raíces conocidas de clientes de sincronización, a partir de variables de entorno y
+/// rutas por defecto en la carpeta del usuario.
+fn cloud_sync_roots() -> Vec<(&'static str, PathBuf)> {
+    let mut roots = Vec::new();
+    for var in ["OneDrive", "OneDriveConsumer", "OneDriveCommercial"] {
+        if let Some(value) = std::env::var_os(var).filter(|value| !value.is_empty()) {
+            roots.push(("OneDrive", PathBuf::from(value)));
+        }
+    }
+
+    let home = std::env::var_os("USERPROFILE")
+        .or_else(|| std::env::var_os("HOME"))
+        .map(PathBuf::from);
+    if let Some(home) = home {
+        roots.push(("Dropbox", home.join("Dropbox")));
+        roots.push(("Google Drive", home.join("Google Drive")));
+        roots.push(("Google Drive", home.join("My Drive")));
+        roots.push(("iCloud", home.join("iCloudDrive")));
+        roots.push(("iCloud", home.join("Library").join("Mobile Documents")));
+        roots.push(("OneDrive", home.join("OneDrive")));
+        roots.push((
+            "macOS CloudStorage",
+            home.join("Library").join("CloudStorage"),
+        ));
+    }
+    roots
+}
+
+fn detect_cloud_provider(path: &Path, roots: &[(&'static str, PathBuf)]) -> Option<String> {
+    let path = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    roots.iter().find_map(|(provider, root)| {
+        let root = fs::canonicalize(root).unwrap_or_else(|_| root.clone());
+        path.starts_with(&root).then(|| provider.to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::test_support::test_temp_dir;
+
+    #[test]
+    fn detects_cloud_synced_paths_from_known_roots() {
+        let root = test_temp_dir("interface-fs-cloud");
+        let onedrive = root.join("OneDrive - Empresa");
+        let instance = onedrive.join("Instancias").join("pack");
+        fs::create_dir_all(&instance).expect("instancia");
+        let roots = vec![("OneDrive", onedrive.clone())];
+
+        assert_eq!(
+            detect_cloud_provider(&instance, &roots),
+            Some("OneDrive".to_string())
+        );
+        assert_eq!(detect_cloud_provider(&root, &roots), None);
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn probe_on_temp_dir_reports_local_capabilities_and_cleans_up() {
+        let root = test_temp_dir("interface-fs-probe");
+        let capabilities = probe_filesystem_capabilities(&root);
+
+        assert!(capabilities.supports_atomic_rename);
+        assert!(!root.join(PROBE_FILE).exists());
+        assert!(!root.join(PROBE_RENAMED_FILE).exists());
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn warnings_cover_each_problematic_capability() {
+        let capabilities = FilesystemCapabilities {
+            filesystem: "exfat".to_string(),
+            drive_kind: "network".to_string(),
+            is_cloud_synced: true,
+            cloud_provider: Some("Dropbox".to_string()),
+            supports_atomic_rename: false,
+            supports_exec_bit: false,
+            probed_at: String::new(),
+        };
+        let expected = if cfg!(target_os = "windows") { 3 } else { 4 };
+        assert_eq!(capability_warnings(&capabilities).len(), expected);
+        assert!(capability_warnings(&FilesystemCapabilities {
+            filesystem: "ext4".to_string(),
+            drive_kind: "local".to_string(),
+            supports_atomic_rename: true,
+            supports_exec_bit: true,
+            ..FilesystemCapabilities::default()
+        })
+        .is_empty());
+    }
+}
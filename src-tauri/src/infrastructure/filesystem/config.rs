@@ -1,4 +1,4 @@
-use std::{fs, path::PathBuf};
+use std::{collections::HashMap, fs, path::PathBuf};
 
 use tauri::{path::BaseDirectory, AppHandle, Manager};
 
@@ -9,6 +9,92 @@ use crate::shared::result::AppResult;
 pub struct LauncherConfig {
     pub launcher_root_override: Option<String>,
     pub instances_dir_override: Option<String>,
+    pub local_api_enabled: bool,
+    pub local_api_port: Option<u16>,
+    /// Presupuesto en segundos de cada fase de preparación del lanzamiento.
+    pub launch_phase_timeout_secs: Option<u64>,
+    /// Refrescar en segundo plano todas las cuentas guardadas, no solo la activa.
+    pub refresh_all_accounts_in_background: bool,
+    /// Tamaño máximo en MB de `cache/images` (por defecto 200).
+    pub image_cache_max_mb: Option<u64>,
+    /// Emitir también `instance_runtime_output` por cada línea además de los lotes
+    /// `instance_runtime_output_batch`. Compatibilidad durante una versión; por defecto activo.
+    pub legacy_runtime_output_events: Option<bool>,
+    /// Inyectar la mitigación de log4j (CVE-2021-44228) en versiones 1.7–1.18.0; por
+    /// defecto activo.
+    pub log4j_mitigation: Option<bool>,
+    /// Pasar al juego la configuración de log4j que declara el version.json
+    /// (`logging.client`), como el launcher oficial; por defecto activo.
+    pub version_logging_config: Option<bool>,
+    /// Webhooks a los que se notifica el inicio, cierre o crash de las instancias.
+    pub webhooks: Vec<WebhookConfig>,
+    /// Peticiones por minuto por host de API (p. ej. `api.modrinth.com`); sustituyen a los
+    /// límites por defecto y 0 desactiva el límite.
+    pub api_rate_limits: HashMap<String, u32>,
+    /// Mirrors de los objetos de assets, en orden de preferencia, que se prueban cuando
+    /// `resources.download.minecraft.net` falla (p. ej. `https://mirror.example/assets`).
+    pub asset_mirrors: Vec<String>,
+    /// Archivos PEM con certificados raíz adicionales (CA de proxies corporativos); se
+    /// cargan al arrancar en todos los clientes HTTP.
+    pub custom_ca_certificates: Vec<String>,
+    /// Borrar logs, crash reports y capturas antiguas según la retención de cada instancia;
+    /// por defecto activo.
+    pub instance_cleanup_enabled: Option<bool>,
+    /// Descargar noticias para la pantalla de inicio; con `false` no se hace ninguna
+    /// petición. Por defecto activo.
+    pub news_enabled: Option<bool>,
+    /// Fuente JSON o Atom de las notas de versión del launcher (por defecto, sus releases
+    /// en GitHub).
+    pub launcher_news_url: Option<String>,
+    /// Fuente JSON o Atom de las noticias de Minecraft (por defecto, las del launcher
+    /// oficial).
+    pub minecraft_news_url: Option<String>,
+    /// Quitar al cerrar el juego la preferencia de GPU escrita en el registro de Windows
+    /// para el `javaw.exe` de la instancia; por defecto se deja.
+    pub clear_gpu_preference_on_exit: bool,
+    /// Descargar el runtime Java y los archivos de la versión a la vez al crear una
+    /// instancia; por defecto activo. Con `false` se descargan uno tras otro.
+    pub concurrent_creation_downloads: Option<bool>,
+    /// No tocar la ventana del juego aunque la instancia pida sin borde o siempre encima;
+    /// para gestores de ventanas que no lo llevan bien.
+    pub disable_window_tweaks: bool,
+    /// Decisiones sobre los comandos externos de cada instancia. Se guardan aquí y no en la
+    /// instancia para que una instancia importada no pueda venir ya aprobada.
+    pub hook_approvals: Vec<HookApproval>,
+    /// Buscar actualizaciones del launcher; por defecto activo. Con `false` no se hace
+    /// ninguna petición.
+    pub update_checks_enabled: Option<bool>,
+    /// `stable` (por defecto) o `beta`; `beta` también recibe las versiones estables.
+    pub update_channel: Option<String>,
+    /// Manifiesto de versiones del launcher (por defecto, el publicado en GitHub Pages).
+    pub update_manifest_url: Option<String>,
+    /// Identificador aleatorio de esta instalación; solo decide en qué tramo de un
+    /// despliegue escalonado cae el equipo.
+    pub installation_id: Option<String>,
+}
+
+/// Aprobación (o rechazo) de los comandos externos de una instancia, válida solo mientras
+/// el comando no cambie.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct HookApproval {
+    pub instance_uuid: String,
+    /// SHA-256 de la línea de comandos aprobada.
+    pub command_hash: String,
+    pub command_line: String,
+    pub approved: bool,
+    pub decided_at: String,
+}
+
+/// Destino de los eventos de ciclo de vida de las instancias.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct WebhookConfig {
+    pub url: String,
+    /// `start`, `exit` y/o `crash`.
+    pub events: Vec<String>,
+    /// `internal_uuid` de la única instancia que dispara el webhook; `None` para todas.
+    pub instance_filter: Option<String>,
 }
 
 pub fn launcher_config_path(app: &AppHandle) -> AppResult<PathBuf> {
@@ -0,0 +1,241 @@
+// Comprobación de espacio libre antes de escribir gigabytes.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::Serialize;
+
+use crate::shared::tasks::current_task;
+
+/// Margen mínimo libre tras la operación, en porcentaje de lo requerido, para no avisar.
+const TIGHT_HEADROOM_PERCENT: u64 = 10;
+
+/// Falta espacio para la operación. Se serializa para que la UI muestre las cifras.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct InsufficientDiskSpaceError {
+    /// Siempre `INSUFFICIENT_DISK_SPACE`.
+    pub code: &'static str,
+    pub volume: String,
+    pub required_bytes: u64,
+    pub available_bytes: u64,
+    pub message: String,
+}
+
+impl InsufficientDiskSpaceError {
+    fn new(volume: &str, required_bytes: u64, available_bytes: u64) -> Self {
+        Self {
+            code: "INSUFFICIENT_DISK_SPACE",
+            volume: volume.to_string(),
+            required_bytes,
+            available_bytes,
+            message: format!(
+                "Espacio en disco insuficiente en {volume}: se necesitan {required_bytes} bytes y hay {available_bytes} bytes libres. Libera espacio o cambia la carpeta del launcher."
+            ),
+        }
+    }
+}
+
+impl From<InsufficientDiskSpaceError> for String {
+    fn from(err: InsufficientDiskSpaceError) -> Self {
+        err.message
+    }
+}
+
+/// Resultado de una comprobación que permite continuar.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DiskSpaceCheck {
+    pub volume: String,
+    pub required_bytes: u64,
+    pub available_bytes: u64,
+    /// Queda menos del 10 % de margen sobre lo requerido.
+    pub tight: bool,
+}
+
+/// Decide con cifras ya medidas; separada de la consulta al sistema para poder probarla.
+pub fn evaluate_disk_space(
+    volume: &str,
+    required_bytes: u64,
+    available_bytes: u64,
+) -> Result<DiskSpaceCheck, InsufficientDiskSpaceError> {
+    if available_bytes < required_bytes {
+        return Err(InsufficientDiskSpaceError::new(
+            volume,
+            required_bytes,
+            available_bytes,
+        ));
+    }
+    let headroom = available_bytes - required_bytes;
+    Ok(DiskSpaceCheck {
+        volume: volume.to_string(),
+        required_bytes,
+        available_bytes,
+        tight: required_bytes > 0
+            && headroom.saturating_mul(100) < required_bytes.saturating_mul(TIGHT_HEADROOM_PERCENT),
+    })
+}
+
+/// Primer ancestro existente de `path`: el destino de la operación aún no suele existir.
+fn existing_ancestor(path: &Path) -> Option<&Path> {
+    path.ancestors().find(|candidate| candidate.exists())
+}
+
+/// Raíz del volumen que contiene `path` (que debe existir), para nombrarlo en los errores.
+#[cfg(unix)]
+fn volume_root(path: &Path) -> PathBuf {
+    use std::os::unix::fs::MetadataExt;
+
+    let path = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let Ok(device) = fs::metadata(&path).map(|meta| meta.dev()) else {
+        return path;
+    };
+    let mut root = path.as_path();
+    while let Some(parent) = root.parent() {
+        if fs::metadata(parent).map(|meta| meta.dev()).ok() != Some(device) {
+            break;
+        }
+        root = parent;
+    }
+    root.to_path_buf()
+}
+
+#[cfg(not(unix))]
+fn volume_root(path: &Path) -> PathBuf {
+    use std::path::Component;
+
+    let path = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let root: PathBuf = path
+        .components()
+        .take_while(|component| matches!(component, Component::Prefix(_) | Component::RootDir))
+        .collect();
+    if root.as_os_str().is_empty() {
+        path
+    } else {
+        root
+    }
+}
+
+fn preflight_with(
+    target_path: &Path,
+    required_bytes: u64,
+    free_space: impl Fn(&Path) -> io::Result<u64>,
+) -> Result<Option<DiskSpaceCheck>, InsufficientDiskSpaceError> {
+    let Some(existing) = existing_ancestor(target_path) else {
+        return Ok(None);
+    };
+    let available = match free_space(existing) {
+        Ok(available) => available,
+        Err(err) => {
+            log::warn!(
+                "⚠ No se pudo consultar el espacio libre en {}: {err}",
+                existing.display()
+            );
+            return Ok(None);
+        }
+    };
+    let volume = volume_root(existing).display().to_string();
+    Ok(Some(evaluate_disk_space(
+        &volume,
+        required_bytes,
+        available,
+    )?))
+}
+
+/// Comprueba que el volumen de `target_path` tenga `required_bytes` libres. Si no se puede
+/// consultar el espacio se continúa sin comprobación (`None`), como antes de existir.
+pub fn preflight_disk_space(
+    target_path: &Path,
+    required_bytes: u64,
+) -> Result<Option<DiskSpaceCheck>, InsufficientDiskSpaceError> {
+    let check = preflight_with(target_path, required_bytes, |path| {
+        fs2::available_space(path)
+    })?;
+    if let Some(check) = check.as_ref().filter(|check| check.tight) {
+        log::warn!(
+            "⚠ Poco espacio en {}: se necesitan {} bytes y quedan {} bytes libres.",
+            check.volume,
+            check.required_bytes,
+            check.available_bytes
+        );
+        if let Some(task) = current_task() {
+            task.emit_event("disk_space_warning", check);
+        }
+    }
+    Ok(check)
+}
+
+/// Tamaño total de los archivos bajo `path` sin seguir enlaces; se usa como estimación
+/// del espacio que ocupará una copia.
+pub fn directory_size(path: &Path) -> u64 {
+    let Ok(meta) = fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if !meta.is_dir() {
+        return meta.len();
+    }
+    fs::read_dir(path)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| directory_size(&entry.path()))
+                .sum()
+        })
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decides_between_ample_tight_and_insufficient() {
+        let gib = 1024_u64 * 1024 * 1024;
+
+        let ample = evaluate_disk_space("/data", 2 * gib, 10 * gib).expect("ample");
+        assert!(!ample.tight);
+
+        let tight = evaluate_disk_space("/data", 10 * gib, 10 * gib + gib / 2).expect("tight");
+        assert!(tight.tight);
+        let edge = evaluate_disk_space("/data", 10 * gib, 11 * gib).expect("edge");
+        assert!(!edge.tight);
+
+        assert!(!evaluate_disk_space("/data", 0, 0).expect("zero").tight);
+
+        let err = evaluate_disk_space("/data", 3 * gib, gib).unwrap_err();
+        assert_eq!(err.code, "INSUFFICIENT_DISK_SPACE");
+        assert_eq!((err.required_bytes, err.available_bytes), (3 * gib, gib));
+    }
+
+    #[test]
+    fn preflight_measures_the_nearest_existing_ancestor() {
+        let root = std::env::temp_dir().join(format!("disk-space-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(root.join("src/nested")).expect("temp dir");
+        fs::write(root.join("src/a.bin"), vec![0u8; 300]).expect("write");
+        fs::write(root.join("src/nested/b.bin"), vec![0u8; 200]).expect("write");
+        assert_eq!(directory_size(&root.join("src")), 500);
+
+        let target = root.join("not/yet/created");
+        let queried = std::cell::RefCell::new(None);
+        let free = |path: &Path| {
+            *queried.borrow_mut() = Some(path.to_path_buf());
+            Ok(1_000)
+        };
+        let err = preflight_with(&target, 5_000, free).unwrap_err();
+        assert_eq!(queried.borrow().as_deref(), Some(root.as_path()));
+        assert_eq!((err.required_bytes, err.available_bytes), (5_000, 1_000));
+
+        let check = preflight_with(&target, 950, free)
+            .expect("ok")
+            .expect("measured");
+        assert!(check.tight);
+        let unknown = preflight_with(&target, 950, |_: &Path| {
+            Err(io::Error::other("sin soporte"))
+        });
+        assert_eq!(unknown, Ok(None));
+
+        let _ = fs::remove_dir_all(root);
+    }
+}
@@ -19,3 +19,28 @@ pub fn write_placeholder_file(path: &Path, content: &str) -> AppResult<()> {
     })?;
     Ok(())
 }
+
+/// Reemplaza `path` con un temporal renombrado encima, o copiado si no hay renombrado atómico.
+pub fn write_file_replacing(
+    path: &Path,
+    content: &[u8],
+    supports_atomic_rename: bool,
+) -> AppResult<()> {
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let temp_path = path.with_file_name(format!("{file_name}.tmp"));
+    fs::write(&temp_path, content)
+        .map_err(|err| format!("No se pudo escribir {}: {err}", temp_path.display()))?;
+
+    if supports_atomic_rename && fs::rename(&temp_path, path).is_ok() {
+        return Ok(());
+    }
+
+    let copied = fs::copy(&temp_path, path);
+    let _ = fs::remove_file(&temp_path);
+    copied
+        .map(|_| ())
+        .map_err(|err| format!("No se pudo escribir {}: {err}", path.display()))
+}
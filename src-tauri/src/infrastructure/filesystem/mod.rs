@@ -1,5 +1,9 @@
+pub mod capabilities;
 pub mod config;
+pub mod disk_space;
 pub mod directories;
 pub mod file_ops;
 pub mod lock;
 pub mod paths;
+pub mod text_encoding;
+pub mod verified_copy;
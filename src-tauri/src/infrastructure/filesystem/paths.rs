@@ -1,15 +1,116 @@
 use std::{
     fs,
     path::{Path, PathBuf},
+    sync::{Mutex, OnceLock},
 };
 
 use tauri::{path::BaseDirectory, Manager};
 
 use crate::{infrastructure::filesystem::config::load_launcher_config, shared::result::AppResult};
 
+const LAUNCHER_ROOT_POINTER_FILE: &str = "launcher_root.json";
+
+static CONFIGURED_LAUNCHER_ROOT: OnceLock<Mutex<Option<PathBuf>>> = OnceLock::new();
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LauncherRootPointer {
+    launcher_root: String,
+    updated_at: String,
+}
+
+fn configured_root_slot() -> &'static Mutex<Option<PathBuf>> {
+    CONFIGURED_LAUNCHER_ROOT.get_or_init(|| Mutex::new(None))
+}
+
+/// Última raíz del launcher resuelta en este proceso. Permite a código sin `AppHandle`
+/// (p. ej. el pipeline de lanzamiento) usar la misma fuente de verdad.
+pub fn configured_launcher_root() -> Option<PathBuf> {
+    configured_root_slot()
+        .lock()
+        .ok()
+        .and_then(|slot| slot.clone())
+}
+
+fn remember_launcher_root(root: &Path) {
+    if let Ok(mut slot) = configured_root_slot().lock() {
+        *slot = Some(root.to_path_buf());
+    }
+}
+
+/// El puntero vive siempre en la ubicación por defecto de AppData, aunque la raíz se reubique.
+pub fn launcher_root_pointer_path(app: &tauri::AppHandle) -> AppResult<PathBuf> {
+    Ok(default_launcher_root(app)?.join(LAUNCHER_ROOT_POINTER_FILE))
+}
+
+fn read_launcher_root_pointer(app: &tauri::AppHandle) -> Option<PathBuf> {
+    let raw = fs::read_to_string(launcher_root_pointer_path(app).ok()?).ok()?;
+    let pointer = serde_json::from_str::<LauncherRootPointer>(&raw).ok()?;
+    let candidate = PathBuf::from(pointer.launcher_root.trim());
+    if candidate.as_os_str().is_empty() {
+        return None;
+    }
+    if !candidate.exists() {
+        log::warn!(
+            "⚠ El puntero de raíz del launcher apunta a una ruta inexistente: {}",
+            candidate.display()
+        );
+        return None;
+    }
+    Some(candidate)
+}
+
+/// Guarda (o elimina con `None`) el puntero a la raíz del launcher.
+pub fn write_launcher_root_pointer(app: &tauri::AppHandle, root: Option<&Path>) -> AppResult<()> {
+    let pointer_path = launcher_root_pointer_path(app)?;
+    let Some(root) = root else {
+        if pointer_path.exists() {
+            fs::remove_file(&pointer_path).map_err(|err| {
+                format!(
+                    "No se pudo eliminar puntero de raíz {}: {err}",
+                    pointer_path.display()
+                )
+            })?;
+        }
+        return Ok(());
+    };
+
+    if let Some(parent) = pointer_path.parent() {
+        fs::create_dir_all(parent).map_err(|err| {
+            format!(
+                "No se pudo crear carpeta para puntero de raíz {}: {err}",
+                parent.display()
+            )
+        })?;
+    }
+    let raw = serde_json::to_string_pretty(&LauncherRootPointer {
+        launcher_root: root.display().to_string(),
+        updated_at: chrono::Utc::now().to_rfc3339(),
+    })
+    .map_err(|err| format!("No se pudo serializar puntero de raíz: {err}"))?;
+    fs::write(&pointer_path, raw).map_err(|err| {
+        format!(
+            "No se pudo guardar puntero de raíz {}: {err}",
+            pointer_path.display()
+        )
+    })?;
+    remember_launcher_root(root);
+    Ok(())
+}
+
 pub fn resolve_launcher_root(app: &tauri::AppHandle) -> AppResult<PathBuf> {
+    let root = resolve_launcher_root_uncached(app)?;
+    remember_launcher_root(&root);
+    Ok(root)
+}
+
+fn resolve_launcher_root_uncached(app: &tauri::AppHandle) -> AppResult<PathBuf> {
     let default = default_launcher_root(app)?;
 
+    if let Some(pointer) = read_launcher_root_pointer(app) {
+        return Ok(pointer);
+    }
+
     if let Ok(config) = load_launcher_config(app) {
         if let Some(override_path) = config.launcher_root_override {
             let candidate = PathBuf::from(override_path.trim());
@@ -72,26 +173,102 @@ pub fn folder_routes_settings_file(app: &tauri::AppHandle) -> AppResult<PathBuf>
     Ok(settings_root.join("config").join("folder_routes.json"))
 }
 
-pub fn sanitize_path_segment(value: &str) -> String {
-    let sanitized = value
+/// Indica si la instancia vive dentro de la raíz del launcher (comparando rutas canónicas
+/// cuando existen, para tolerar symlinks).
+pub fn is_path_within_root(path: &Path, root: &Path) -> bool {
+    let canonical_path = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let canonical_root = fs::canonicalize(root).unwrap_or_else(|_| root.to_path_buf());
+    canonical_path.starts_with(&canonical_root)
+}
+
+/// Longitud máxima (en caracteres) de un componente de ruta generado a partir de un nombre.
+pub const MAX_PATH_COMPONENT_CHARS: usize = 64;
+/// Límite en bytes de un componente en sistemas de archivos Unix (ext4, APFS).
+const MAX_PATH_COMPONENT_BYTES: usize = 255;
+
+const WINDOWS_RESERVED_NAMES: [&str; 28] = [
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "COM¹", "COM²", "COM³", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8",
+    "LPT9", "LPT¹", "LPT²", "LPT³",
+];
+
+/// Motivo por el que un nombre no puede usarse como carpeta o archivo.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NameError {
+    /// Tras sanear no queda ningún carácter alfanumérico.
+    Empty,
+    /// Coincide con un nombre reservado de Windows (CON, NUL, COM1...).
+    Reserved(String),
+}
+
+impl NameError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            NameError::Empty => "empty",
+            NameError::Reserved(_) => "reserved",
+        }
+    }
+}
+
+impl std::fmt::Display for NameError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NameError::Empty => {
+                write!(f, "El nombre debe contener al menos una letra o un número.")
+            }
+            NameError::Reserved(name) => write!(
+                f,
+                "El nombre \"{name}\" está reservado por Windows y no puede usarse."
+            ),
+        }
+    }
+}
+
+impl serde::Serialize for NameError {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+        let mut state = serializer.serialize_struct("NameError", 2)?;
+        state.serialize_field("code", self.code())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.end()
+    }
+}
+
+/// Convierte un nombre visible en un único componente de ruta válido en Windows y Unix.
+pub fn safe_path_component(name: &str) -> Result<String, NameError> {
+    let lowered = name.to_lowercase();
+    let normalized = icu_normalizer::ComposingNormalizerBorrowed::new_nfc().normalize(&lowered);
+    let filtered = normalized
         .chars()
         .map(|ch| {
-            if ch.is_ascii_alphanumeric() || ch == '-' || ch == '_' || ch == ' ' {
+            if ch.is_alphanumeric() || ch == '-' || ch == '_' || ch == '.' || ch == ' ' {
                 ch
             } else {
                 '_'
             }
         })
-        .collect::<String>()
-        .trim()
-        .replace(' ', "-")
-        .to_lowercase();
+        .collect::<String>();
+    let trimmed = filtered
+        .trim_matches(|ch: char| ch == '.' || ch == ' ')
+        .replace(' ', "-");
 
-    if sanitized.is_empty() {
-        "instance".to_string()
-    } else {
-        sanitized
+    let mut component = String::new();
+    for ch in trimmed.chars().take(MAX_PATH_COMPONENT_CHARS) {
+        if component.len() + ch.len_utf8() > MAX_PATH_COMPONENT_BYTES {
+            break;
+        }
+        component.push(ch);
+    }
+    let component = component.trim_end_matches('.').to_string();
+
+    if !component.chars().any(char::is_alphanumeric) {
+        return Err(NameError::Empty);
+    }
+    let stem = component.split('.').next().unwrap_or_default();
+    if WINDOWS_RESERVED_NAMES.contains(&stem.to_uppercase().as_str()) {
+        return Err(NameError::Reserved(component));
     }
+    Ok(component)
 }
 
 pub fn java_executable_path(runtime_root: &Path) -> PathBuf {
@@ -101,3 +278,120 @@ pub fn java_executable_path(runtime_root: &Path) -> PathBuf {
         runtime_root.join("bin").join("java")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WINDOWS_FORBIDDEN: [char; 9] = ['<', '>', ':', '"', '/', '\\', '|', '?', '*'];
+
+    const SAMPLE_CHARS: [char; 40] = [
+        'a', 'Z', '0', '9', ' ', '.', '-', '_', '/', '\\', ':', '*', '?', '"', '<', '>', '|', '\0',
+        '\n', '\t', '\u{7f}', 'é', 'ñ', 'Ö', 'İ', 'ß', '\u{301}', '\u{308}', '\u{200b}',
+        '\u{feff}', 'ﬁ', 'Ω', 'Ω', 'ж', '中', '한', 'ا', '🙂', '¹', '²',
+    ];
+
+    struct XorShift(u64);
+
+    impl XorShift {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+    }
+
+    fn random_name(rng: &mut XorShift) -> String {
+        let len = (rng.next() % 120) as usize;
+        (0..len)
+            .map(|_| SAMPLE_CHARS[(rng.next() % SAMPLE_CHARS.len() as u64) as usize])
+            .collect()
+    }
+
+    fn assert_valid_component(input: &str, component: &str) {
+        assert!(!component.is_empty(), "vacío para {input:?}");
+        assert!(component.chars().count() <= MAX_PATH_COMPONENT_CHARS);
+        assert!(component.len() <= MAX_PATH_COMPONENT_BYTES);
+        assert!(component != "." && component != "..");
+        assert!(!component.starts_with(['.', ' ']), "{component:?}");
+        assert!(!component.ends_with(['.', ' ']), "{component:?}");
+        assert!(
+            !component
+                .chars()
+                .any(|ch| ch.is_control() || WINDOWS_FORBIDDEN.contains(&ch)),
+            "{component:?}"
+        );
+        let stem = component
+            .split('.')
+            .next()
+            .unwrap_or_default()
+            .to_uppercase();
+        assert!(!WINDOWS_RESERVED_NAMES.contains(&stem.as_str()));
+        assert_eq!(Path::new(component).components().count(), 1);
+    }
+
+    #[test]
+    fn fuzzed_unicode_names_always_produce_a_single_valid_component() {
+        let mut rng = XorShift(0x9e37_79b9_7f4a_7c15);
+        for _ in 0..5000 {
+            let input = random_name(&mut rng);
+            match safe_path_component(&input) {
+                Ok(component) => {
+                    assert_valid_component(&input, &component);
+                    assert_eq!(safe_path_component(&component), Ok(component.clone()));
+                }
+                Err(NameError::Empty) | Err(NameError::Reserved(_)) => {}
+            }
+        }
+    }
+
+    #[test]
+    fn visually_identical_names_map_to_the_same_component() {
+        assert_eq!(
+            safe_path_component("Caf\u{e9} Pack"),
+            safe_path_component("Cafe\u{301} Pack")
+        );
+        assert_eq!(safe_path_component("Café Pack").as_deref(), Ok("café-pack"));
+    }
+
+    #[test]
+    fn rejects_empty_and_dot_only_names() {
+        for input in ["", "   ", ".", "..", "...", "?*<>", " . . "] {
+            assert_eq!(
+                safe_path_component(input),
+                Err(NameError::Empty),
+                "{input:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_windows_reserved_names_with_or_without_extension() {
+        for input in ["CON", "con", "Nul", "aux.txt", "COM1", "lpt9.log", "com¹"] {
+            assert!(
+                matches!(safe_path_component(input), Err(NameError::Reserved(_))),
+                "{input:?}"
+            );
+        }
+        assert_eq!(safe_path_component("console").as_deref(), Ok("console"));
+        assert_eq!(safe_path_component("COM10").as_deref(), Ok("com10"));
+    }
+
+    #[test]
+    fn trims_edges_and_limits_length() {
+        assert_eq!(
+            safe_path_component("  .My Pack.  ").as_deref(),
+            Ok("my-pack")
+        );
+        assert_eq!(safe_path_component("a/b\\c").as_deref(), Ok("a_b_c"));
+        let long = "x".repeat(200);
+        assert_eq!(
+            safe_path_component(&long).map(|value| value.len()),
+            Ok(MAX_PATH_COMPONENT_CHARS)
+        );
+        let wide = "🙂".repeat(100);
+        let component = safe_path_component(&format!("a{wide}")).expect("component");
+        assert!(component.len() <= MAX_PATH_COMPONENT_BYTES);
+    }
+}
@@ -0,0 +1,182 @@
+use std::{fs, path::Path};
+
+use crate::{
+    infrastructure::filesystem::file_ops::write_file_replacing, shared::result::AppResult,
+};
+
+const UTF8_BOM: &[u8] = &[0xEF, 0xBB, 0xBF];
+const UTF16_LE_BOM: &[u8] = &[0xFF, 0xFE];
+const UTF16_BE_BOM: &[u8] = &[0xFE, 0xFF];
+
+/// Texto ya normalizado (UTF-8 sin BOM, fines de línea LF) y las correcciones aplicadas.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NormalizedText {
+    pub text: String,
+    pub fixes: Vec<&'static str>,
+}
+
+impl NormalizedText {
+    pub fn was_fixed(&self) -> bool {
+        !self.fixes.is_empty()
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Utf16 {
+    Le,
+    Be,
+}
+
+/// UTF-16 sin BOM: el texto de estos archivos es casi todo ASCII, así que uno de cada dos
+/// bytes es nulo (los impares en LE, los pares en BE).
+fn sniff_utf16(bytes: &[u8]) -> Option<Utf16> {
+    if bytes.len() < 4 || bytes.len() % 2 != 0 {
+        return None;
+    }
+    let pairs = bytes.len() / 2;
+    let odd_nulls = bytes.iter().skip(1).step_by(2).filter(|b| **b == 0).count();
+    let even_nulls = bytes.iter().step_by(2).filter(|b| **b == 0).count();
+    if odd_nulls * 10 >= pairs * 4 && even_nulls == 0 {
+        Some(Utf16::Le)
+    } else if even_nulls * 10 >= pairs * 4 && odd_nulls == 0 {
+        Some(Utf16::Be)
+    } else {
+        None
+    }
+}
+
+fn decode_utf16(bytes: &[u8], order: Utf16) -> Result<String, String> {
+    let units = bytes
+        .chunks_exact(2)
+        .map(|pair| match order {
+            Utf16::Le => u16::from_le_bytes([pair[0], pair[1]]),
+            Utf16::Be => u16::from_be_bytes([pair[0], pair[1]]),
+        })
+        .collect::<Vec<_>>();
+    String::from_utf16(&units).map_err(|err| format!("UTF-16 inválido: {err}"))
+}
+
+/// Decodifica bytes de texto escritos por otras herramientas: quita BOM, convierte UTF-16
+/// (por BOM o por la heurística de bytes nulos) a UTF-8 y pasa CRLF a LF.
+pub fn normalize_text_bytes(bytes: &[u8]) -> Result<NormalizedText, String> {
+    let mut fixes = Vec::new();
+    let mut text = if let Some(rest) = bytes.strip_prefix(UTF8_BOM) {
+        fixes.push("BOM UTF-8");
+        String::from_utf8(rest.to_vec()).map_err(|err| format!("UTF-8 inválido: {err}"))?
+    } else if let Some(rest) = bytes.strip_prefix(UTF16_LE_BOM) {
+        fixes.push("UTF-16LE");
+        decode_utf16(rest, Utf16::Le)?
+    } else if let Some(rest) = bytes.strip_prefix(UTF16_BE_BOM) {
+        fixes.push("UTF-16BE");
+        decode_utf16(rest, Utf16::Be)?
+    } else if let Some(order) = sniff_utf16(bytes) {
+        fixes.push(match order {
+            Utf16::Le => "UTF-16LE",
+            Utf16::Be => "UTF-16BE",
+        });
+        decode_utf16(bytes, order)?
+    } else {
+        String::from_utf8(bytes.to_vec()).map_err(|err| format!("UTF-8 inválido: {err}"))?
+    };
+    if let Some(rest) = text.strip_prefix('\u{feff}') {
+        text = rest.to_string();
+    }
+    if text.contains("\r\n") {
+        fixes.push("CRLF");
+        text = text.replace("\r\n", "\n");
+    }
+    Ok(NormalizedText { text, fixes })
+}
+
+/// Lee un archivo de texto normalizándolo. No modifica el archivo.
+pub fn read_text_normalized(path: &Path) -> AppResult<NormalizedText> {
+    let bytes =
+        fs::read(path).map_err(|err| format!("No se pudo leer {}: {err}", path.display()))?;
+    normalize_text_bytes(&bytes).map_err(|err| format!("{}: {err}", path.display()))
+}
+
+/// Como [`read_text_normalized`], pero si hubo que corregir algo y el archivo es nuestro
+/// (`rewrite`) lo deja guardado en forma canónica para que la corrección sea permanente.
+pub fn read_text_repairing(path: &Path, rewrite: bool) -> AppResult<String> {
+    let normalized = read_text_normalized(path)?;
+    if normalized.was_fixed() {
+        log::warn!(
+            "⚠ {} tenía codificación no estándar ({}); normalizado a UTF-8/LF",
+            path.display(),
+            normalized.fixes.join(", ")
+        );
+        if rewrite {
+            if let Err(err) = write_file_replacing(path, normalized.text.as_bytes(), true) {
+                log::warn!("⚠ No se pudo reescribir {}: {err}", path.display());
+            }
+        }
+    }
+    Ok(normalized.text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CLEAN: &str = "{\n  \"id\": \"1.20.1-forge-47.2.0\",\n  \"mainClass\": \"cpw.mods.bootstraplauncher.BootstrapLauncher\"\n}\n";
+
+    fn utf16le(text: &str, bom: bool) -> Vec<u8> {
+        let mut bytes = if bom {
+            UTF16_LE_BOM.to_vec()
+        } else {
+            Vec::new()
+        };
+        bytes.extend(text.encode_utf16().flat_map(u16::to_le_bytes));
+        bytes
+    }
+
+    #[test]
+    fn bom_utf16_and_crlf_variants_decode_to_clean_text() {
+        let crlf = CLEAN.replace('\n', "\r\n");
+        let mut bom_crlf = UTF8_BOM.to_vec();
+        bom_crlf.extend(crlf.as_bytes());
+        let utf16be = {
+            let mut bytes = UTF16_BE_BOM.to_vec();
+            bytes.extend(CLEAN.encode_utf16().flat_map(u16::to_be_bytes));
+            bytes
+        };
+
+        for (bytes, expected_fixes) in [
+            (bom_crlf, vec!["BOM UTF-8", "CRLF"]),
+            (utf16le(CLEAN, true), vec!["UTF-16LE"]),
+            (utf16le(&crlf, false), vec!["UTF-16LE", "CRLF"]),
+            (utf16be, vec!["UTF-16BE"]),
+        ] {
+            let normalized = normalize_text_bytes(&bytes).expect("decode");
+            assert_eq!(normalized.text, CLEAN);
+            assert_eq!(normalized.fixes, expected_fixes);
+        }
+
+        let clean = normalize_text_bytes(CLEAN.as_bytes()).expect("clean");
+        assert!(!clean.was_fixed());
+    }
+
+    #[test]
+    fn repairing_rewrites_owned_files_only() {
+        let dir =
+            std::env::temp_dir().join(format!("interface-text-encoding-{}", std::process::id()));
+        fs::create_dir_all(&dir).expect("dir");
+        let owned = dir.join("owned.json");
+        let external = dir.join("external.json");
+        fs::write(&owned, utf16le(CLEAN, true)).expect("owned");
+        fs::write(&external, utf16le(CLEAN, true)).expect("external");
+
+        assert_eq!(read_text_repairing(&owned, true).expect("owned"), CLEAN);
+        assert_eq!(
+            read_text_repairing(&external, false).expect("external"),
+            CLEAN
+        );
+        assert_eq!(fs::read(&owned).expect("owned bytes"), CLEAN.as_bytes());
+        assert_eq!(
+            fs::read(&external).expect("external bytes"),
+            utf16le(CLEAN, true)
+        );
+
+        let _ = fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,269 @@
+// Copia con verificación SHA1 para copias grandes.
+
+use std::{
+    fs::{self, File},
+    io::{Read, Write},
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+use sha1::{Digest, Sha1};
+
+use super::capabilities::detect_drive_kind;
+
+const BUFFER_SIZE: usize = 1024 * 1024;
+const MAX_RETRIES: u32 = 2;
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+/// Archivos que se citan en el error antes de abreviar.
+const MAX_LISTED_FAILURES: usize = 20;
+
+#[derive(Debug, Clone, Default, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CopyProgress {
+    pub files_copied: u64,
+    pub bytes_copied: u64,
+    /// Bytes releídos del destino y comprobados; 0 si la verificación está desactivada.
+    pub bytes_verified: u64,
+    pub verified: bool,
+    pub retried_files: u64,
+}
+
+/// Verificación por defecto: solo en destinos extraíbles o de red, donde los fallos
+/// silenciosos son habituales. `requested` la fuerza en un sentido u otro.
+pub fn verified_copy_enabled(target: &Path, requested: Option<bool>) -> bool {
+    requested.unwrap_or_else(|| {
+        let probe = target
+            .ancestors()
+            .find(|path| path.exists())
+            .unwrap_or(target);
+        matches!(detect_drive_kind(probe), "removable" | "network")
+    })
+}
+
+/// Copiador de archivos que acumula progreso y fallos de toda una operación.
+pub struct VerifiedCopier<'a> {
+    verify: bool,
+    progress: CopyProgress,
+    failed: Vec<String>,
+    last_report: Option<Instant>,
+    on_progress: Box<dyn FnMut(&CopyProgress) + 'a>,
+}
+
+impl<'a> VerifiedCopier<'a> {
+    pub fn new(verify: bool, on_progress: impl FnMut(&CopyProgress) + 'a) -> Self {
+        Self {
+            verify,
+            progress: CopyProgress {
+                verified: verify,
+                ..CopyProgress::default()
+            },
+            failed: Vec::new(),
+            last_report: None,
+            on_progress: Box::new(on_progress),
+        }
+    }
+
+    /// Sin verificación equivale a `fs::copy`. Con verificación un archivo que no se
+    /// consigue copiar bien se anota y la copia sigue; el error llega en `finish`.
+    pub fn copy_file(&mut self, source: &Path, target: &Path) -> Result<(), String> {
+        if !self.verify {
+            let bytes = fs::copy(source, target).map_err(|err| {
+                format!(
+                    "No se pudo copiar {} -> {}: {err}",
+                    source.display(),
+                    target.display()
+                )
+            })?;
+            self.progress.files_copied += 1;
+            self.progress.bytes_copied += bytes;
+            self.report(false);
+            return Ok(());
+        }
+
+        let mut last_error = String::new();
+        for attempt in 0..=MAX_RETRIES {
+            if attempt > 0 {
+                self.progress.retried_files += 1;
+                log::warn!(
+                    "⚠ Reintentando copia de {} ({attempt}/{MAX_RETRIES}): {last_error}",
+                    source.display()
+                );
+            }
+            match copy_and_verify(source, target) {
+                Ok(bytes) => {
+                    self.progress.files_copied += 1;
+                    self.progress.bytes_copied += bytes;
+                    self.progress.bytes_verified += bytes;
+                    self.report(false);
+                    return Ok(());
+                }
+                Err(err) => last_error = err,
+            }
+        }
+        log::error!(
+            "❌ Copia no recuperable de {}: {last_error}",
+            source.display()
+        );
+        self.failed.push(source.display().to_string());
+        Ok(())
+    }
+
+    fn report(&mut self, force: bool) {
+        let due = !self
+            .last_report
+            .is_some_and(|last| last.elapsed() < PROGRESS_INTERVAL);
+        if force || due {
+            self.last_report = Some(Instant::now());
+            (self.on_progress)(&self.progress);
+        }
+    }
+
+    /// Cierra la operación: error con la lista de archivos que no se pudieron copiar bien.
+    pub fn finish(mut self) -> Result<CopyProgress, String> {
+        self.report(true);
+        if self.failed.is_empty() {
+            return Ok(self.progress);
+        }
+        let mut listed = self
+            .failed
+            .iter()
+            .take(MAX_LISTED_FAILURES)
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(", ");
+        if self.failed.len() > MAX_LISTED_FAILURES {
+            listed.push_str(&format!(" (+{})", self.failed.len() - MAX_LISTED_FAILURES));
+        }
+        Err(format!(
+            "La copia verificada falló en {} archivo(s) tras {MAX_RETRIES} reintentos; el disco de destino puede estar dañando datos: {listed}",
+            self.failed.len()
+        ))
+    }
+}
+
+/// Copia `source` en `target` hasheando en la misma pasada y relee el destino ya en disco.
+fn copy_and_verify(source: &Path, target: &Path) -> Result<u64, String> {
+    let mut input = File::open(source)
+        .map_err(|err| format!("No se pudo abrir {}: {err}", source.display()))?;
+    let mut output = File::create(target)
+        .map_err(|err| format!("No se pudo crear {}: {err}", target.display()))?;
+    let mut hasher = Sha1::new();
+    let mut buffer = vec![0u8; BUFFER_SIZE];
+    let mut bytes = 0u64;
+    loop {
+        let read = input
+            .read(&mut buffer)
+            .map_err(|err| format!("No se pudo leer {}: {err}", source.display()))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+        output
+            .write_all(&buffer[..read])
+            .map_err(|err| format!("No se pudo escribir {}: {err}", target.display()))?;
+        bytes += read as u64;
+    }
+    output
+        .sync_all()
+        .map_err(|err| format!("No se pudo volcar a disco {}: {err}", target.display()))?;
+    drop(output);
+    if let Ok(permissions) = fs::metadata(source).map(|meta| meta.permissions()) {
+        let _ = fs::set_permissions(target, permissions);
+    }
+
+    let expected = format!("{:x}", hasher.finalize());
+    let written = hash_file(target, &mut buffer)?;
+    if written != expected {
+        return Err(format!(
+            "SHA1 distinto tras copiar ({expected} != {written})"
+        ));
+    }
+    Ok(bytes)
+}
+
+fn hash_file(path: &Path, buffer: &mut [u8]) -> Result<String, String> {
+    let mut file =
+        File::open(path).map_err(|err| format!("No se pudo releer {}: {err}", path.display()))?;
+    let mut hasher = Sha1::new();
+    loop {
+        let read = file
+            .read(buffer)
+            .map_err(|err| format!("No se pudo releer {}: {err}", path.display()))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Copia recursiva de `source` en `destination` con el copiador indicado.
+pub fn copy_dir_with(
+    source: &Path,
+    destination: &Path,
+    copier: &mut VerifiedCopier<'_>,
+) -> Result<(), String> {
+    fs::create_dir_all(destination).map_err(|err| {
+        format!(
+            "No se pudo crear carpeta destino {}: {err}",
+            destination.display()
+        )
+    })?;
+    let entries = fs::read_dir(source)
+        .map_err(|err| format!("No se pudo leer carpeta origen {}: {err}", source.display()))?;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let target: PathBuf = destination.join(entry.file_name());
+        if path.is_dir() {
+            copy_dir_with(&path, &target, copier)?;
+        } else {
+            copier.copy_file(&path, &target)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::test_support::test_temp_dir;
+
+    #[test]
+    fn verified_copy_counts_verified_bytes() {
+        let dir = test_temp_dir("verified-copy-ok");
+        let source = dir.join("source");
+        fs::create_dir_all(source.join("mods")).expect("mods");
+        fs::write(source.join("mods/a.jar"), vec![7u8; 3 * BUFFER_SIZE + 11]).expect("jar");
+        fs::write(source.join("options.txt"), "fov:0.5\n").expect("options");
+
+        let mut reports = 0;
+        let mut copier = VerifiedCopier::new(true, |_| reports += 1);
+        copy_dir_with(&source, &dir.join("target"), &mut copier).expect("copy");
+        let progress = copier.finish().expect("verified");
+        assert_eq!(progress.files_copied, 2);
+        assert_eq!(progress.bytes_verified, progress.bytes_copied);
+        assert_eq!(progress.bytes_copied, 3 * BUFFER_SIZE as u64 + 11 + 8);
+        assert!(reports >= 1);
+        assert_eq!(
+            fs::read(dir.join("target/mods/a.jar"))
+                .expect("copied")
+                .len(),
+            3 * BUFFER_SIZE + 11
+        );
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn unreadable_files_fail_the_operation_with_their_names() {
+        let dir = test_temp_dir("verified-copy-fail");
+        let mut copier = VerifiedCopier::new(true, |_| {});
+        copier
+            .copy_file(&dir.join("missing.jar"), &dir.join("copy.jar"))
+            .expect("recorded, not aborted");
+        let err = copier.finish().expect_err("must fail");
+        assert!(err.contains("missing.jar"));
+        assert!(err.contains("1 archivo(s)"));
+        let _ = fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,233 @@
+// Mirrors de los objetos de assets.
+
+use std::{
+    collections::BTreeMap,
+    sync::{OnceLock, RwLock},
+};
+
+use crate::infrastructure::checksum::sha1::sha1_hex;
+
+pub const OFFICIAL_ASSETS_RESOURCES_URL: &str = "https://resources.download.minecraft.net";
+/// Fallos consecutivos del host oficial tras los que se deja de intentar en esta operación.
+const STICKY_FAILOVER_THRESHOLD: u32 = 10;
+
+static CONFIGURED_MIRRORS: OnceLock<RwLock<Vec<String>>> = OnceLock::new();
+
+fn configured_mirrors() -> &'static RwLock<Vec<String>> {
+    CONFIGURED_MIRRORS.get_or_init(|| RwLock::new(Vec::new()))
+}
+
+/// Sustituye los mirrors de assets por los de la configuración. Se ignoran las URLs que no
+/// sean http(s) y las repetidas; el orden se conserva.
+pub fn configure_asset_mirrors(mirrors: &[String]) {
+    let mut accepted = Vec::<String>::new();
+    for mirror in mirrors {
+        let base = mirror.trim().trim_end_matches('/').to_string();
+        if base.is_empty() {
+            continue;
+        }
+        let valid = reqwest::Url::parse(&base)
+            .is_ok_and(|url| matches!(url.scheme(), "http" | "https") && url.has_host());
+        if !valid {
+            log::warn!("⚠ Mirror de assets ignorado, URL no válida: {mirror}");
+            continue;
+        }
+        if base != OFFICIAL_ASSETS_RESOURCES_URL && !accepted.contains(&base) {
+            accepted.push(base);
+        }
+    }
+    if let Ok(mut current) = configured_mirrors().write() {
+        *current = accepted;
+    }
+}
+
+/// Host oficial seguido de los mirrors configurados.
+pub fn asset_base_urls() -> Vec<String> {
+    let mut hosts = vec![OFFICIAL_ASSETS_RESOURCES_URL.to_string()];
+    if let Ok(mirrors) = configured_mirrors().read() {
+        hosts.extend(mirrors.iter().cloned());
+    }
+    hosts
+}
+
+/// Fallo al pedir un objeto a un host.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FetchError {
+    /// Error de conexión o de HTTP: se prueba el siguiente host.
+    Retryable(String),
+    /// Cancelación o timeout de la operación: no se prueba nada más.
+    Abort(String),
+}
+
+/// Estado de los orígenes durante una sincronización de assets.
+pub struct AssetSources {
+    hosts: Vec<String>,
+    /// Primer host que se prueba para cada objeto; avanza con el failover.
+    active: usize,
+    primary_failures: u32,
+    served: BTreeMap<usize, u64>,
+}
+
+impl AssetSources {
+    pub fn new(hosts: Vec<String>) -> Self {
+        Self {
+            hosts,
+            active: 0,
+            primary_failures: 0,
+            served: BTreeMap::new(),
+        }
+    }
+
+    /// Descarga el objeto `hash` desde el primer host que lo sirva íntegro. Devuelve los
+    /// bytes y el host que los sirvió.
+    pub fn fetch(
+        &mut self,
+        hash: &str,
+        mut get: impl FnMut(&str) -> Result<Vec<u8>, FetchError>,
+    ) -> Result<(Vec<u8>, String), String> {
+        let hash = hash.trim().to_ascii_lowercase();
+        let prefix = hash.get(..2).unwrap_or_default();
+        let mut errors = Vec::new();
+        for index in self.active..self.hosts.len() {
+            let host = &self.hosts[index];
+            let url = format!("{host}/{prefix}/{hash}");
+            let failure = match get(&url) {
+                Ok(bytes) => {
+                    let actual = sha1_hex(&bytes);
+                    if actual == hash {
+                        if index == 0 {
+                            self.primary_failures = 0;
+                        }
+                        *self.served.entry(index).or_default() += 1;
+                        log::debug!("🔹 Asset {hash} servido por {host}");
+                        return Ok((bytes, host.clone()));
+                    }
+                    format!("SHA1 distinto ({actual})")
+                }
+                Err(FetchError::Retryable(err)) => err,
+                Err(FetchError::Abort(err)) => return Err(err),
+            };
+            log::warn!("⚠ Asset {hash} falló en {host}: {failure}");
+            errors.push(format!("{host}: {failure}"));
+            if index == 0 {
+                self.record_primary_failure();
+            }
+        }
+        Err(format!(
+            "Asset {hash}: ningún origen lo sirvió íntegro ({})",
+            errors.join("; ")
+        ))
+    }
+
+    fn record_primary_failure(&mut self) {
+        self.primary_failures += 1;
+        if self.active == 0
+            && self.primary_failures >= STICKY_FAILOVER_THRESHOLD
+            && self.hosts.len() > 1
+        {
+            self.active = 1;
+            log::warn!(
+                "⚠ {} falló {} veces seguidas; el resto de assets se descarga desde {}",
+                self.hosts[0],
+                self.primary_failures,
+                self.hosts[1]
+            );
+        }
+    }
+
+    /// Host con el que empieza cada objeto ahora mismo.
+    pub fn active_host(&self) -> &str {
+        self.hosts
+            .get(self.active)
+            .map(String::as_str)
+            .unwrap_or_default()
+    }
+
+    /// Resumen para los logs de descarga (`host (n), ...`); `None` si no se descargó nada.
+    pub fn summary(&self) -> Option<String> {
+        if self.served.is_empty() {
+            return None;
+        }
+        Some(
+            self.served
+                .iter()
+                .map(|(index, count)| format!("{} ({count})", self.hosts[*index]))
+                .collect::<Vec<_>>()
+                .join(", "),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PRIMARY: &str = "https://primary.invalid";
+    const MIRROR: &str = "https://mirror.invalid";
+
+    fn sources() -> AssetSources {
+        AssetSources::new(vec![PRIMARY.to_string(), MIRROR.to_string()])
+    }
+
+    #[test]
+    fn failing_primary_falls_back_per_object_and_then_sticks_to_the_mirror() {
+        let mut sources = sources();
+        let mut requests = Vec::<String>::new();
+        for n in 0..12u32 {
+            let body = format!("objeto {n}").into_bytes();
+            let hash = sha1_hex(&body);
+            let (bytes, host) = sources
+                .fetch(&hash, |url| {
+                    requests.push(url.to_string());
+                    if url.starts_with(PRIMARY) {
+                        Err(FetchError::Retryable("connection reset".to_string()))
+                    } else {
+                        Ok(body.clone())
+                    }
+                })
+                .expect("mirror sirve el objeto");
+            assert_eq!(bytes, body);
+            assert_eq!(host, MIRROR);
+        }
+        // 10 objetos pasan por el primario; los 2 últimos van directos al mirror.
+        let primary_requests = requests
+            .iter()
+            .filter(|url| url.starts_with(PRIMARY))
+            .count();
+        assert_eq!(primary_requests, 10);
+        assert_eq!(requests.len(), 22);
+        assert_eq!(sources.active_host(), MIRROR);
+        assert_eq!(
+            sources.summary().as_deref(),
+            Some("https://mirror.invalid (12)")
+        );
+    }
+
+    #[test]
+    fn corrupt_bytes_are_rejected_and_fetched_from_the_next_host() {
+        let mut sources = sources();
+        let body = b"sonido".to_vec();
+        let hash = sha1_hex(&body);
+        let (_, host) = sources
+            .fetch(&hash, |url| {
+                if url.starts_with(PRIMARY) {
+                    Ok(b"sonid0".to_vec())
+                } else {
+                    Ok(body.clone())
+                }
+            })
+            .expect("mirror íntegro");
+        assert_eq!(host, MIRROR);
+        // Un primario que vuelve a funcionar reinicia la cuenta y no hay failover.
+        assert_eq!(sources.active_host(), PRIMARY);
+
+        let err = sources
+            .fetch(&hash, |_| Ok(b"basura".to_vec()))
+            .expect_err("ningún host íntegro");
+        assert!(err.contains("SHA1 distinto"), "{err}");
+        assert_eq!(
+            sources.fetch(&hash, |_| Err(FetchError::Abort("cancelado".to_string()))),
+            Err("cancelado".to_string())
+        );
+    }
+}
@@ -14,6 +14,8 @@ use tokio::{
     time::sleep,
 };
 
+use crate::infrastructure::http::tls;
+
 #[derive(Debug, Clone)]
 pub struct DownloadTask {
     pub url: String,
@@ -75,7 +77,7 @@ pub fn build_download_client() -> Result<Arc<Client>, DownloadError> {
         header::HeaderValue::from_static("identity"),
     );
 
-    Client::builder()
+    tls::with_custom_roots_async(Client::builder())
         .no_gzip()
         .no_brotli()
         .no_deflate()
@@ -107,10 +109,10 @@ pub async fn download_file(
 
     let tmp_path = dest.with_extension("tmp");
     let response = client.get(url).send().await.map_err(|err| {
-        DownloadError::Network(format!(
-            "request failed for {url} -> {}: {err}",
-            dest.display()
-        ))
+        DownloadError::Network(match tls::interception_error(&err) {
+            Some(interception) => interception.into(),
+            None => format!("request failed for {url} -> {}: {err}", dest.display()),
+        })
     })?;
 
     let status = response.status();
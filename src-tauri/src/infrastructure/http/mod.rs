@@ -1,2 +1,5 @@
+pub mod asset_mirrors;
 pub mod client;
 pub mod downloader;
+pub mod rate_limit;
+pub mod tls;
@@ -0,0 +1,370 @@
+use std::{
+    collections::HashMap,
+    sync::{Mutex, OnceLock},
+    thread,
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+
+use crate::infrastructure::http::tls;
+
+/// Límites por defecto (peticiones por minuto) según la documentación de cada API.
+pub const DEFAULT_RATE_LIMITS: [(&str, u32); 2] =
+    [("api.minecraftservices.com", 60), ("api.modrinth.com", 300)];
+/// Espera máxima en cola antes de devolver `RATE_LIMITED`.
+const MAX_QUEUE_WAIT: Duration = Duration::from_secs(5);
+/// Un 429 con `Retry-After` mayor que esto no se reintenta: se informa al usuario.
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(30);
+const DEFAULT_RETRY_AFTER: Duration = Duration::from_secs(20);
+
+/// Presupuesto agotado para un host. Se serializa para que la UI muestre la espera.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RateLimitedError {
+    /// Siempre `RATE_LIMITED`.
+    pub code: &'static str,
+    pub host: String,
+    pub retry_after_seconds: u64,
+    pub message: String,
+}
+
+impl RateLimitedError {
+    fn new(host: &str, retry_after: Duration) -> Self {
+        let retry_after_seconds = retry_after.as_secs_f64().ceil().max(1.0) as u64;
+        Self {
+            code: "RATE_LIMITED",
+            host: host.to_string(),
+            retry_after_seconds,
+            message: format!(
+                "Demasiadas solicitudes a {host}; reintentando en {retry_after_seconds} s."
+            ),
+        }
+    }
+}
+
+impl From<RateLimitedError> for String {
+    fn from(err: RateLimitedError) -> Self {
+        err.message
+    }
+}
+
+/// Error de una petición enviada a través del limitador.
+#[derive(Debug)]
+pub enum LimitedRequestError {
+    RateLimited(RateLimitedError),
+    Request(reqwest::Error),
+}
+
+impl LimitedRequestError {
+    /// Error para quien lo propaga: el de límite y el de inspección TLS quedan estructurados
+    /// y el de red lleva el contexto de quien llama.
+    pub fn with_context(self, context: &str) -> ApiRequestError {
+        match self {
+            LimitedRequestError::RateLimited(err) => ApiRequestError::RateLimited(err),
+            LimitedRequestError::Request(err) => match tls::interception_error(&err) {
+                Some(interception) => ApiRequestError::TlsInterceptionSuspected(interception),
+                None => ApiRequestError::Other(format!("{context}: {err}")),
+            },
+        }
+    }
+
+    /// Mensaje para las rutas con errores `String`.
+    pub fn describe(self, context: &str) -> String {
+        self.with_context(context).into()
+    }
+}
+
+/// Error de una función que consulta una API limitada y además puede fallar por otros
+/// motivos (estado HTTP, JSON). Quien llama puede conservar el límite sin parsear textos.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApiRequestError {
+    RateLimited(RateLimitedError),
+    TlsInterceptionSuspected(tls::TlsInterceptionSuspectedError),
+    Other(String),
+}
+
+impl From<String> for ApiRequestError {
+    fn from(err: String) -> Self {
+        ApiRequestError::Other(err)
+    }
+}
+
+impl From<ApiRequestError> for String {
+    fn from(err: ApiRequestError) -> Self {
+        match err {
+            ApiRequestError::RateLimited(limited) => limited.message,
+            ApiRequestError::TlsInterceptionSuspected(interception) => interception.message,
+            ApiRequestError::Other(message) => message,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    per_minute: u32,
+    tokens: f64,
+    last_refill: Instant,
+    blocked_until: Option<Instant>,
+    rejected: u64,
+    throttled: u64,
+}
+
+impl TokenBucket {
+    fn new(per_minute: u32, now: Instant) -> Self {
+        Self {
+            per_minute,
+            tokens: f64::from(per_minute),
+            last_refill: now,
+            blocked_until: None,
+            rejected: 0,
+            throttled: 0,
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now
+            .saturating_duration_since(self.last_refill)
+            .as_secs_f64();
+        let capacity = f64::from(self.per_minute);
+        self.tokens = (self.tokens + elapsed * capacity / 60.0).min(capacity);
+        self.last_refill = now;
+    }
+
+    /// Toma un token o devuelve cuánto falta para el siguiente.
+    fn try_take(&mut self, now: Instant) -> Result<(), Duration> {
+        if let Some(until) = self.blocked_until {
+            if until > now {
+                return Err(until - now);
+            }
+            self.blocked_until = None;
+        }
+        self.refill(now);
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            return Ok(());
+        }
+        let missing = 1.0 - self.tokens;
+        Err(Duration::from_secs_f64(
+            missing * 60.0 / f64::from(self.per_minute.max(1)),
+        ))
+    }
+
+    /// El servidor respondió 429: nadie más sale hacia el host hasta `Retry-After`.
+    fn block_for(&mut self, now: Instant, retry_after: Duration) {
+        self.blocked_until = Some(now + retry_after);
+        self.tokens = 0.0;
+        self.throttled += 1;
+    }
+}
+
+/// Estado del limitador para un host (comando de depuración).
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RateLimiterHostStatus {
+    pub host: String,
+    pub per_minute: u32,
+    pub available_tokens: u32,
+    pub blocked_for_seconds: u64,
+    pub rejected: u64,
+    pub throttled_by_server: u64,
+}
+
+static BUCKETS: OnceLock<Mutex<HashMap<String, TokenBucket>>> = OnceLock::new();
+
+fn buckets() -> &'static Mutex<HashMap<String, TokenBucket>> {
+    BUCKETS.get_or_init(|| {
+        let now = Instant::now();
+        Mutex::new(
+            DEFAULT_RATE_LIMITS
+                .iter()
+                .map(|(host, per_minute)| (host.to_string(), TokenBucket::new(*per_minute, now)))
+                .collect(),
+        )
+    })
+}
+
+/// Sustituye los límites por defecto con los de la configuración (host → peticiones por
+/// minuto). Un valor 0 desactiva el límite de ese host.
+pub fn configure_rate_limits(overrides: &HashMap<String, u32>) {
+    let Ok(mut buckets) = buckets().lock() else {
+        return;
+    };
+    let now = Instant::now();
+    for (host, per_minute) in overrides {
+        let host = host.trim().to_ascii_lowercase();
+        if *per_minute == 0 {
+            buckets.remove(&host);
+        } else {
+            buckets.insert(host, TokenBucket::new(*per_minute, now));
+        }
+    }
+}
+
+fn host_of(url: &str) -> Option<String> {
+    reqwest::Url::parse(url)
+        .ok()?
+        .host_str()
+        .map(str::to_ascii_lowercase)
+}
+
+/// Espera necesaria antes de poder enviar a `host`, o `RATE_LIMITED` si supera la cola.
+fn reserve(host: &str) -> Result<Option<Duration>, RateLimitedError> {
+    let Ok(mut buckets) = buckets().lock() else {
+        return Ok(None);
+    };
+    let Some(bucket) = buckets.get_mut(host) else {
+        return Ok(None);
+    };
+    match bucket.try_take(Instant::now()) {
+        Ok(()) => Ok(None),
+        Err(wait) if wait <= MAX_QUEUE_WAIT && bucket.blocked_until.is_none() => {
+            // El token se consume ya: quien espera tiene su hueco reservado.
+            bucket.tokens -= 1.0;
+            Ok(Some(wait))
+        }
+        Err(wait) => {
+            bucket.rejected += 1;
+            Err(RateLimitedError::new(host, wait))
+        }
+    }
+}
+
+fn register_429(host: &str, retry_after: Duration) {
+    if let Ok(mut buckets) = buckets().lock() {
+        if let Some(bucket) = buckets.get_mut(host) {
+            bucket.block_for(Instant::now(), retry_after);
+        }
+    }
+}
+
+fn parse_retry_after(value: Option<&reqwest::header::HeaderValue>) -> Duration {
+    value
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_RETRY_AFTER)
+}
+
+/// Envía una petición bloqueante respetando el límite del host. Ante un 429 espera el
+/// `Retry-After` y reintenta una vez; si vuelve a fallar devuelve `RATE_LIMITED`.
+pub fn send_blocking(
+    url: &str,
+    build: impl Fn() -> reqwest::blocking::RequestBuilder,
+) -> Result<reqwest::blocking::Response, LimitedRequestError> {
+    let host = host_of(url).unwrap_or_default();
+    for attempt in 1..=2 {
+        if let Some(wait) = reserve(&host).map_err(LimitedRequestError::RateLimited)? {
+            thread::sleep(wait);
+        }
+        let response = build().send().map_err(LimitedRequestError::Request)?;
+        if response.status() != reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Ok(response);
+        }
+        let retry_after = parse_retry_after(response.headers().get(reqwest::header::RETRY_AFTER));
+        register_429(&host, retry_after);
+        log::warn!(
+            "⚠ HTTP 429 de {host}; Retry-After {} s",
+            retry_after.as_secs()
+        );
+        if attempt == 2 || retry_after > MAX_RETRY_AFTER {
+            return Err(LimitedRequestError::RateLimited(RateLimitedError::new(
+                &host,
+                retry_after,
+            )));
+        }
+        thread::sleep(retry_after);
+    }
+    unreachable!("el bucle siempre devuelve en el segundo intento")
+}
+
+/// Variante asíncrona de [`send_blocking`].
+pub async fn send(
+    url: &str,
+    build: impl Fn() -> reqwest::RequestBuilder,
+) -> Result<reqwest::Response, LimitedRequestError> {
+    let host = host_of(url).unwrap_or_default();
+    for attempt in 1..=2 {
+        if let Some(wait) = reserve(&host).map_err(LimitedRequestError::RateLimited)? {
+            tokio::time::sleep(wait).await;
+        }
+        let response = build().send().await.map_err(LimitedRequestError::Request)?;
+        if response.status() != reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Ok(response);
+        }
+        let retry_after = parse_retry_after(response.headers().get(reqwest::header::RETRY_AFTER));
+        register_429(&host, retry_after);
+        log::warn!(
+            "⚠ HTTP 429 de {host}; Retry-After {} s",
+            retry_after.as_secs()
+        );
+        if attempt == 2 || retry_after > MAX_RETRY_AFTER {
+            return Err(LimitedRequestError::RateLimited(RateLimitedError::new(
+                &host,
+                retry_after,
+            )));
+        }
+        tokio::time::sleep(retry_after).await;
+    }
+    unreachable!("el bucle siempre devuelve en el segundo intento")
+}
+
+pub fn rate_limiter_status() -> Vec<RateLimiterHostStatus> {
+    let Ok(mut buckets) = buckets().lock() else {
+        return Vec::new();
+    };
+    let now = Instant::now();
+    let mut status = buckets
+        .iter_mut()
+        .map(|(host, bucket)| {
+            bucket.refill(now);
+            RateLimiterHostStatus {
+                host: host.clone(),
+                per_minute: bucket.per_minute,
+                available_tokens: bucket.tokens.max(0.0).floor() as u32,
+                blocked_for_seconds: bucket
+                    .blocked_until
+                    .map(|until| until.saturating_duration_since(now).as_secs())
+                    .unwrap_or(0),
+                rejected: bucket.rejected,
+                throttled_by_server: bucket.throttled,
+            }
+        })
+        .collect::<Vec<_>>();
+    status.sort_by(|a, b| a.host.cmp(&b.host));
+    status
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bucket_refills_over_time_and_honors_server_block() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(60, start);
+        for _ in 0..60 {
+            assert!(bucket.try_take(start).is_ok());
+        }
+        let wait = bucket.try_take(start).expect_err("sin tokens");
+        assert!(wait <= Duration::from_millis(1001), "{wait:?}");
+        assert!(bucket.try_take(start + Duration::from_secs(2)).is_ok());
+
+        bucket.block_for(start + Duration::from_secs(2), Duration::from_secs(20));
+        let blocked = bucket
+            .try_take(start + Duration::from_secs(12))
+            .expect_err("bloqueado por 429");
+        assert_eq!(blocked, Duration::from_secs(10));
+        assert!(bucket.try_take(start + Duration::from_secs(30)).is_ok());
+    }
+
+    #[test]
+    fn rate_limited_error_rounds_retry_after_up() {
+        let err = RateLimitedError::new("api.minecraftservices.com", Duration::from_millis(19_200));
+        assert_eq!(err.retry_after_seconds, 20);
+        assert_eq!(
+            err.message,
+            "Demasiadas solicitudes a api.minecraftservices.com; reintentando en 20 s."
+        );
+    }
+}
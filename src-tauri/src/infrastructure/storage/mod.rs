@@ -1 +1,3 @@
 // Persistencia de configuración y cuentas.
+
+pub mod secret_store;
@@ -0,0 +1,76 @@
+// Secretos del launcher en el llavero del sistema.
+
+use crate::shared::result::AppResult;
+
+const KEYRING_SERVICE: &str = "InterfaceLauncher";
+
+#[cfg(not(test))]
+pub fn load_secret(key: &str) -> AppResult<Option<String>> {
+    match keyring_entry(key)?.get_password() {
+        Ok(secret) => Ok(Some(secret)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(err) => Err(format!(
+            "No se pudo leer '{key}' del llavero del sistema: {err}"
+        )),
+    }
+}
+
+#[cfg(not(test))]
+pub fn store_secret(key: &str, secret: &str) -> AppResult<()> {
+    keyring_entry(key)?
+        .set_password(secret)
+        .map_err(|err| format!("No se pudo guardar '{key}' en el llavero del sistema: {err}"))
+}
+
+#[cfg(not(test))]
+pub fn delete_secret(key: &str) -> AppResult<()> {
+    match keyring_entry(key)?.delete_credential() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(err) => Err(format!(
+            "No se pudo borrar '{key}' del llavero del sistema: {err}"
+        )),
+    }
+}
+
+#[cfg(not(test))]
+fn keyring_entry(key: &str) -> AppResult<keyring::Entry> {
+    keyring::Entry::new(KEYRING_SERVICE, key)
+        .map_err(|err| format!("Llavero del sistema no disponible: {err}"))
+}
+
+// En tests el llavero es un mapa por hilo, para no tocar el del sistema.
+#[cfg(test)]
+thread_local! {
+    static TEST_SECRETS: std::cell::RefCell<std::collections::HashMap<String, String>> =
+        std::cell::RefCell::new(std::collections::HashMap::new());
+    static TEST_KEYRING_UNAVAILABLE: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+}
+
+/// Simula un llavero que rechaza las escrituras en el hilo del test.
+#[cfg(test)]
+pub fn set_test_keyring_unavailable(unavailable: bool) {
+    TEST_KEYRING_UNAVAILABLE.with(|flag| flag.set(unavailable));
+}
+
+#[cfg(test)]
+pub fn load_secret(key: &str) -> AppResult<Option<String>> {
+    let key = format!("{KEYRING_SERVICE}/{key}");
+    Ok(TEST_SECRETS.with(|secrets| secrets.borrow().get(&key).cloned()))
+}
+
+#[cfg(test)]
+pub fn store_secret(key: &str, secret: &str) -> AppResult<()> {
+    if TEST_KEYRING_UNAVAILABLE.with(|flag| flag.get()) {
+        return Err(format!("Llavero del sistema no disponible para '{key}'"));
+    }
+    let key = format!("{KEYRING_SERVICE}/{key}");
+    TEST_SECRETS.with(|secrets| secrets.borrow_mut().insert(key, secret.to_string()));
+    Ok(())
+}
+
+#[cfg(test)]
+pub fn delete_secret(key: &str) -> AppResult<()> {
+    let key = format!("{KEYRING_SERVICE}/{key}");
+    TEST_SECRETS.with(|secrets| secrets.borrow_mut().remove(&key));
+    Ok(())
+}
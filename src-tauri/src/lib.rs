@@ -1,3 +1,7 @@
+// La sustitución de los endpoints de autenticación nunca puede llegar a un build de release.
+#[cfg(all(feature = "dev-auth", not(debug_assertions)))]
+compile_error!("La feature `dev-auth` es solo para desarrollo y no se puede compilar en release.");
+
 pub mod app;
 pub mod commands;
 pub mod domain;
@@ -16,32 +20,140 @@ pub fn run() {
                 .level(log::LevelFilter::Info)
                 .build(),
         )
+        .manage(shared::clock::AppClock::default())
+        .manage(shared::tasks::TaskRegistry::default())
         .invoke_handler(tauri::generate_handler![
             app::launcher_service::create_instance,
+            app::launcher_service::validate_instance_name,
             app::launcher_service::list_instances,
             app::launcher_service::delete_instance,
             app::launcher_service::fetch_remote_update_manifest,
+            app::launcher_update::check_launcher_update,
+            app::launcher_update::download_launcher_update,
             app::auth_service::list_available_browsers,
             app::auth_service::open_url_in_browser,
             app::auth_service::authorize_microsoft_in_launcher,
             app::auth_service::start_microsoft_auth,
             app::auth_service::complete_microsoft_auth,
             app::auth_service::refresh_microsoft_auth,
+            app::token_maintenance::register_account_session,
+            app::token_maintenance::remove_account_session,
+            app::token_maintenance::get_account_token_health,
             app::auth_service::start_microsoft_device_auth,
             app::auth_service::complete_microsoft_device_auth,
+            app::java_service::install_java_from_archive,
+            app::java_service::list_installed_java_builds,
+            app::java_service::install_specific_java_build,
+            app::java_service::reinstall_embedded_runtime,
             app::instance_service::open_instance_folder,
             app::instance_service::open_redirect_origin_folder,
             app::instance_service::get_instance_metadata,
+            app::instance_service::update_instance_java_args,
+            app::instance_service::set_instance_mods_dir_override,
+            app::instance_service::set_instance_optional_game_flags,
+            app::instance_service::set_instance_library_overrides,
+            app::instance_service::set_instance_auto_jvm_tuning,
+            app::instance_service::set_instance_preferred_gpu,
+            app::instance_service::set_instance_java_build_pin,
+            app::source_instance_settings::read_source_instance_settings,
+            app::source_instance_settings::write_source_instance_settings,
+            app::source_instance_settings::set_source_settings_write_enabled,
+            app::instance_reset::reset_instance,
+            app::instance_cleanup::run_instance_cleanup_now,
+            app::instance_cleanup::set_instance_retention,
+            app::instance_cleanup::clean_stale_loader_files,
+            app::instance_backup::set_instance_backup_settings,
+            app::batch_operations::run_batch_operation,
+            app::maintenance::get_maintenance_status,
+            app::op_journal::recover_interrupted_operation,
+            app::instance_dedup::deduplicate_instance_files,
+            app::launcher_problems::get_launcher_problems,
+            app::launcher_snapshot::get_launcher_snapshot,
+            commands::tasks::list_active_tasks,
+            commands::tasks::get_download_status,
+            commands::tasks::cancel_task,
+            app::launch_prewarm::prewarm_instance,
+            app::startup_profile::get_startup_profile,
+            app::strict_mode::set_instance_strict_mode,
+            app::mod_duplicates::find_duplicate_mods,
+            app::mod_duplicates::resolve_duplicate_mods,
+            app::mod_duplicates::set_instance_duplicate_mod_check,
+            app::window_tweaks::set_instance_window_tweaks,
+            app::console_filters::get_console_filters,
+            app::console_filters::set_console_filters,
+            app::console_filters::suggest_console_filters,
+            app::playtime::get_playtime_by_account,
+            app::playtime::get_account_playtime,
+            app::runtime_metrics::get_runtime_metrics,
+            app::performance_report::generate_performance_report,
+            app::strict_mode::check_strict_launch,
+            app::pack_update::check_pack_update,
+            app::pack_update::update_pack,
+            app::pack_update::install_optional_pack_files,
+            app::jvm_memory::get_instance_heap_summary,
+            app::crash_index::list_instance_crashes,
+            app::crash_index::get_crash_details,
+            app::screenshots::list_instance_screenshots,
+            app::screenshots::get_screenshot_thumbnail,
+            app::screenshots::delete_screenshot,
+            app::screenshots::reveal_screenshot,
+            app::version_inspect::get_effective_version_json,
+            app::version_inspect::diff_version_json,
+            app::library_provenance::get_instance_provenance,
+            app::news_feed::get_news_feed,
+            app::tls_diagnostics::test_tls,
             app::instance_service::get_instance_card_stats,
+            app::instance_service::get_instance_health,
+            app::instance_service::list_instance_versions,
+            app::instance_service::prune_instance_versions,
+            app::instance_upgrade::plan_instance_upgrade,
+            app::instance_upgrade::apply_instance_upgrade,
+            app::instance_templates::list_instance_templates,
+            app::instance_templates::save_instance_template,
+            app::instance_templates::delete_instance_template,
+            app::orphan_adoption::analyze_orphan_instance,
+            app::orphan_adoption::adopt_orphan_instance,
             app::instance_service::validate_and_prepare_launch,
             app::instance_service::start_instance,
+            app::instance_service::get_launch_preparation_status,
+            app::instance_service::pause_launch_preparation,
+            app::instance_service::resume_launch_preparation,
             app::instance_service::get_runtime_status,
+            app::event_journal::replay_instance_events,
             app::instance_service::force_close_instance,
+            app::instance_search::search_instances,
+            app::instance_search::rebuild_instance_search_index,
+            app::instance_shutdown::close_instance,
             app::redirect_launch::validate_redirect_instance,
             app::redirect_launch::get_redirect_cache_info,
             app::redirect_launch::force_cleanup_redirect_cache,
+            app::saves_sync::sync_saves_to_source,
+            app::redirect_launch::preview_redirect_cache_cleanup,
+            app::redirect_launch::set_redirect_cache_pinned,
             app::redirect_launch::repair_instance,
             app::redirect_launch::repair_all_instances,
+            platform::gpu::get_gpu_info,
+            platform::gpu::get_gpu_adapters,
+            app::instance_tags::add_instance_tag,
+            app::instance_tags::remove_instance_tag,
+            app::instance_tags::list_all_tags,
+            app::instance_locks::set_instance_locks,
+            app::image_cache::fetch_remote_image,
+            app::image_cache::clear_image_cache,
+            app::quarantine::list_quarantined_files,
+            app::quarantine::purge_quarantine,
+            app::launch_lock::get_launch_lock,
+            app::launch_lock::diff_launch_locks,
+            app::launch_changes::what_changed_since_last_launch,
+            app::hook_approval::approve_instance_hooks,
+            app::hook_approval::list_hook_approvals,
+            app::hook_approval::revoke_hook_approval,
+            app::webhooks::get_webhooks,
+            app::webhooks::set_webhooks,
+            app::webhooks::test_webhook,
+            app::local_api::get_local_api_settings,
+            app::local_api::set_local_api_settings,
+            app::local_api::get_local_api_token,
             app::settings_service::pick_folder,
             app::settings_service::load_folder_routes,
             app::settings_service::save_folder_routes,
@@ -49,7 +161,14 @@ pub fn run() {
             app::settings_service::open_folder_route,
             app::settings_service::migrate_instances_folder,
             commands::settings::get_launcher_folders,
+            commands::settings::get_rate_limiter_status,
+            app::service_status::get_service_status,
+            app::settings_profiles::save_settings_profile,
+            app::settings_profiles::list_settings_profiles,
+            app::settings_profiles::apply_settings_profile,
+            app::settings_profiles::delete_settings_profile,
             commands::settings::migrate_launcher_root,
+            commands::settings::relocate_launcher_root,
             commands::settings::change_instances_folder,
             commands::settings::get_instances_count,
             commands::import::detect_external_instances,
@@ -64,6 +183,10 @@ pub fn run() {
             commands::mods::set_instance_mod_enabled,
             commands::mods::replace_instance_mod_file,
             commands::mods::install_catalog_mod_file,
+            commands::mods::delete_instance_mod,
+            app::mod_list_install::install_mods_from_list,
+            app::mod_list_install::install_mods_from_list_file,
+            app::mod_list_install::cancel_mod_list_install,
             commands::exports::export_instance_package,
             commands::skin_processor::optimize_skin_png,
             commands::file_manager::list_skins,
@@ -77,8 +200,32 @@ pub fn run() {
             commands::visual_meta::read_visual_media_as_data_url
         ])
         .setup(|app| {
-            let _ = app::redirect_launch::cleanup_redirect_cache_on_startup(app.handle());
+            let _ = infrastructure::filesystem::paths::resolve_launcher_root(app.handle());
+            if let Ok(config) =
+                infrastructure::filesystem::config::load_launcher_config(app.handle())
+            {
+                infrastructure::http::tls::configure_custom_ca_certificates(
+                    &config.custom_ca_certificates,
+                );
+                infrastructure::http::rate_limit::configure_rate_limits(&config.api_rate_limits);
+                infrastructure::http::asset_mirrors::configure_asset_mirrors(
+                    &config.asset_mirrors,
+                );
+            }
+            let cleanup_handle = app.handle().clone();
+            tauri::async_runtime::spawn_blocking(move || {
+                let _ = app::redirect_launch::cleanup_redirect_cache_on_startup(&cleanup_handle);
+                app::instance_cleanup::cleanup_idle_instances_on_startup(&cleanup_handle);
+                app::instance_service::migrate_instance_metadata_on_startup(&cleanup_handle);
+                app::instance_uuid::repair_duplicate_instance_uuids(&cleanup_handle);
+                app::op_journal::scan_interrupted_operations(&cleanup_handle);
+            });
             services::discord_presence::initialize_discord_rpc();
+            app::local_api::initialize_local_api(app.handle());
+            app::launcher_snapshot::initialize_launcher_snapshot(app.handle());
+            app::token_maintenance::start_token_maintenance(app.handle().clone());
+            app::power_events::start_power_listener(app.handle().clone());
+            app::instances_watcher::start_instances_watcher(app.handle().clone());
             Ok(())
         })
         .run(tauri::generate_context!())
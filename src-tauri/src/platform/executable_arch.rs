@@ -0,0 +1,171 @@
+// Arquitectura de un ejecutable leída de su cabecera.
+
+use std::{fs, io::Read, path::Path};
+
+/// Bytes que se leen del ejecutable; la cabecera PE suele estar en los primeros cientos.
+const HEADER_BYTES: u64 = 4096;
+
+const MACHO_CPU_X86: u32 = 7;
+const MACHO_CPU_ARM: u32 = 12;
+const MACHO_ABI64: u32 = 0x0100_0000;
+/// Un universal binary real trae pocas arquitecturas; más indica otro formato con el
+/// mismo magic (un `.class` empieza también por `CAFEBABE`).
+const MAX_FAT_ARCHS: u32 = 8;
+
+fn read_u16(bytes: &[u8], offset: usize, little_endian: bool) -> Option<u16> {
+    let raw: [u8; 2] = bytes.get(offset..offset + 2)?.try_into().ok()?;
+    Some(if little_endian {
+        u16::from_le_bytes(raw)
+    } else {
+        u16::from_be_bytes(raw)
+    })
+}
+
+fn read_u32(bytes: &[u8], offset: usize, little_endian: bool) -> Option<u32> {
+    let raw: [u8; 4] = bytes.get(offset..offset + 4)?.try_into().ok()?;
+    Some(if little_endian {
+        u32::from_le_bytes(raw)
+    } else {
+        u32::from_be_bytes(raw)
+    })
+}
+
+/// Nombres como los de `std::env::consts::ARCH`.
+fn elf_arch(machine: u16) -> Option<&'static str> {
+    match machine {
+        0x03 => Some("x86"),
+        0x28 => Some("arm"),
+        0x3E => Some("x86_64"),
+        0xB7 => Some("aarch64"),
+        0xF3 => Some("riscv64"),
+        _ => None,
+    }
+}
+
+fn macho_arch(cpu_type: u32) -> Option<&'static str> {
+    match cpu_type {
+        MACHO_CPU_X86 => Some("x86"),
+        cpu if cpu == MACHO_CPU_X86 | MACHO_ABI64 => Some("x86_64"),
+        MACHO_CPU_ARM => Some("arm"),
+        cpu if cpu == MACHO_CPU_ARM | MACHO_ABI64 => Some("aarch64"),
+        _ => None,
+    }
+}
+
+fn pe_arch(machine: u16) -> Option<&'static str> {
+    match machine {
+        0x014C => Some("x86"),
+        0x01C4 => Some("arm"),
+        0x8664 => Some("x86_64"),
+        0xAA64 => Some("aarch64"),
+        _ => None,
+    }
+}
+
+/// Arquitecturas para las que está compilado el ejecutable (varias en un universal binary
+/// de macOS). Vacío si el formato o la máquina no se reconocen.
+pub fn executable_architectures(header: &[u8]) -> Vec<&'static str> {
+    match header.get(..4) {
+        Some([0x7F, b'E', b'L', b'F']) => {
+            let little_endian = header.get(5) == Some(&1);
+            read_u16(header, 18, little_endian)
+                .and_then(elf_arch)
+                .into_iter()
+                .collect()
+        }
+        Some([0xCE | 0xCF, 0xFA, 0xED, 0xFE]) => read_u32(header, 4, true)
+            .and_then(macho_arch)
+            .into_iter()
+            .collect(),
+        Some([0xFE, 0xED, 0xFA, 0xCE | 0xCF]) => read_u32(header, 4, false)
+            .and_then(macho_arch)
+            .into_iter()
+            .collect(),
+        Some([0xCA, 0xFE, 0xBA, magic @ (0xBE | 0xBF)]) => {
+            let entry_size = if *magic == 0xBE { 20 } else { 32 };
+            let count = read_u32(header, 4, false).unwrap_or(0);
+            if count > MAX_FAT_ARCHS {
+                return Vec::new();
+            }
+            (0..count as usize)
+                .filter_map(|index| read_u32(header, 8 + index * entry_size, false))
+                .filter_map(macho_arch)
+                .collect()
+        }
+        Some([b'M', b'Z', _, _]) => read_u32(header, 0x3C, true)
+            .map(|offset| offset as usize)
+            .filter(|offset| header.get(*offset..*offset + 4) == Some(b"PE\0\0"))
+            .and_then(|offset| read_u16(header, offset + 4, true))
+            .and_then(pe_arch)
+            .into_iter()
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Lee la cabecera de `path`; vacío si no se puede leer o no se reconoce.
+pub fn read_executable_architectures(path: &Path) -> Vec<&'static str> {
+    let mut header = Vec::new();
+    let read =
+        fs::File::open(path).and_then(|file| file.take(HEADER_BYTES).read_to_end(&mut header));
+    if read.is_err() {
+        return Vec::new();
+    }
+    executable_architectures(&header)
+}
+
+/// `Some(arquitecturas)` si el ejecutable no puede correr de forma nativa en este proceso;
+/// `None` si coincide o no se pudo determinar.
+pub fn foreign_executable_architectures(path: &Path) -> Option<Vec<&'static str>> {
+    let arches = read_executable_architectures(path);
+    (!arches.is_empty() && !arches.contains(&std::env::consts::ARCH)).then_some(arches)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_machine_from_elf_and_pe_headers() {
+        let mut elf = vec![0x7F, b'E', b'L', b'F', 2, 1, 1, 0];
+        elf.resize(18, 0);
+        elf.extend_from_slice(&0xB7_u16.to_le_bytes());
+        assert_eq!(executable_architectures(&elf), vec!["aarch64"]);
+
+        let mut big_endian_elf = vec![0x7F, b'E', b'L', b'F', 2, 2, 1, 0];
+        big_endian_elf.resize(18, 0);
+        big_endian_elf.extend_from_slice(&0x3E_u16.to_be_bytes());
+        assert_eq!(executable_architectures(&big_endian_elf), vec!["x86_64"]);
+
+        let mut pe = vec![0_u8; 0x80];
+        pe[..2].copy_from_slice(b"MZ");
+        pe[0x3C..0x40].copy_from_slice(&0x40_u32.to_le_bytes());
+        pe[0x40..0x44].copy_from_slice(b"PE\0\0");
+        pe[0x44..0x46].copy_from_slice(&0x8664_u16.to_le_bytes());
+        assert_eq!(executable_architectures(&pe), vec!["x86_64"]);
+
+        pe[0x40..0x44].copy_from_slice(b"XX\0\0");
+        assert!(executable_architectures(&pe).is_empty());
+        assert!(executable_architectures(b"#!/bin/sh\n").is_empty());
+    }
+
+    #[test]
+    fn reads_thin_and_universal_mach_o_headers() {
+        let mut thin = vec![0xCF, 0xFA, 0xED, 0xFE];
+        thin.extend_from_slice(&(MACHO_CPU_X86 | MACHO_ABI64).to_le_bytes());
+        assert_eq!(executable_architectures(&thin), vec!["x86_64"]);
+
+        let mut fat = vec![0xCA, 0xFE, 0xBA, 0xBE];
+        fat.extend_from_slice(&2_u32.to_be_bytes());
+        for cpu in [MACHO_CPU_X86 | MACHO_ABI64, MACHO_CPU_ARM | MACHO_ABI64] {
+            let mut entry = cpu.to_be_bytes().to_vec();
+            entry.resize(20, 0);
+            fat.extend_from_slice(&entry);
+        }
+        assert_eq!(executable_architectures(&fat), vec!["x86_64", "aarch64"]);
+
+        // Un .class de Java (CAFEBABE + versión 52) no es un universal binary.
+        let class_file = [0xCA, 0xFE, 0xBA, 0xBE, 0x00, 0x00, 0x00, 0x34];
+        assert!(executable_architectures(&class_file).is_empty());
+    }
+}
@@ -0,0 +1,237 @@
+// Ventana del juego localizada por PID.
+
+use crate::domain::models::instance::WindowTweaks;
+
+/// Identificador de la ventana en el sistema (`HWND` en Windows, id de X11 en Linux). Si
+/// cambia para el mismo proceso, el juego recreó la ventana.
+pub type WindowId = u64;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WindowTweakError {
+    /// El sistema o la sesión no lo permiten; no tiene sentido reintentar.
+    Unsupported(String),
+    Failed(String),
+}
+
+impl std::fmt::Display for WindowTweakError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WindowTweakError::Unsupported(reason) => write!(f, "no soportado: {reason}"),
+            WindowTweakError::Failed(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+/// Ventana principal visible de `pid`, o `None` si todavía no tiene ninguna.
+#[cfg(target_os = "windows")]
+pub fn find_process_window(pid: u32) -> Result<Option<WindowId>, WindowTweakError> {
+    Ok(crate::platform::processes::visible_top_level_windows(pid)
+        .first()
+        .map(|window| *window as usize as WindowId))
+}
+
+#[cfg(target_os = "windows")]
+pub fn apply_window_tweaks(window: WindowId, tweaks: WindowTweaks) -> Result<(), WindowTweakError> {
+    use windows_sys::Win32::{
+        Foundation::HWND,
+        Graphics::Gdi::{
+            GetMonitorInfoW, MonitorFromWindow, MONITORINFO, MONITOR_DEFAULTTONEAREST,
+        },
+        UI::WindowsAndMessaging::{
+            GetWindowLongPtrW, IsWindow, SetWindowLongPtrW, SetWindowPos, GWL_STYLE, HWND_TOPMOST,
+            SWP_FRAMECHANGED, SWP_NOMOVE, SWP_NOOWNERZORDER, SWP_NOSIZE, SWP_NOZORDER, WS_CAPTION,
+            WS_THICKFRAME,
+        },
+    };
+
+    let window = window as usize as HWND;
+    // SAFETY: se comprueba que el handle siga siendo una ventana antes de usarlo; las
+    // llamadas solo leen y escriben el estilo y la posición de esa ventana.
+    unsafe {
+        if IsWindow(window) == 0 {
+            return Err(WindowTweakError::Failed(
+                "la ventana ya no existe".to_string(),
+            ));
+        }
+        if tweaks.borderless {
+            let style = GetWindowLongPtrW(window, GWL_STYLE);
+            let borderless = style & !((WS_CAPTION | WS_THICKFRAME) as isize);
+            SetWindowLongPtrW(window, GWL_STYLE, borderless);
+
+            let monitor = MonitorFromWindow(window, MONITOR_DEFAULTTONEAREST);
+            let mut info: MONITORINFO = std::mem::zeroed();
+            info.cbSize = std::mem::size_of::<MONITORINFO>() as u32;
+            if GetMonitorInfoW(monitor, &mut info) == 0 {
+                return Err(WindowTweakError::Failed(
+                    "no se pudieron leer los límites del monitor".to_string(),
+                ));
+            }
+            let bounds = info.rcMonitor;
+            if SetWindowPos(
+                window,
+                std::ptr::null_mut(),
+                bounds.left,
+                bounds.top,
+                bounds.right - bounds.left,
+                bounds.bottom - bounds.top,
+                SWP_FRAMECHANGED | SWP_NOZORDER | SWP_NOOWNERZORDER,
+            ) == 0
+            {
+                return Err(WindowTweakError::Failed(format!(
+                    "SetWindowPos falló: {}",
+                    std::io::Error::last_os_error()
+                )));
+            }
+        }
+        if tweaks.always_on_top
+            && SetWindowPos(window, HWND_TOPMOST, 0, 0, 0, 0, SWP_NOMOVE | SWP_NOSIZE) == 0
+        {
+            return Err(WindowTweakError::Failed(format!(
+                "no se pudo poner la ventana siempre encima: {}",
+                std::io::Error::last_os_error()
+            )));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+fn ensure_x11() -> Result<(), WindowTweakError> {
+    let wayland = std::env::var("XDG_SESSION_TYPE")
+        .is_ok_and(|session| session.eq_ignore_ascii_case("wayland"))
+        || std::env::var_os("WAYLAND_DISPLAY").is_some();
+    if wayland {
+        return Err(WindowTweakError::Unsupported(
+            "Wayland no permite ajustar ventanas de otro proceso".to_string(),
+        ));
+    }
+    if std::env::var_os("DISPLAY").is_none() {
+        return Err(WindowTweakError::Unsupported(
+            "no hay sesión gráfica X11 (DISPLAY vacío)".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+fn x11_error(err: impl std::fmt::Display) -> WindowTweakError {
+    WindowTweakError::Failed(format!("X11: {err}"))
+}
+
+#[cfg(target_os = "linux")]
+fn intern_atom(
+    conn: &impl x11rb::protocol::xproto::ConnectionExt,
+    name: &[u8],
+) -> Result<u32, WindowTweakError> {
+    Ok(conn
+        .intern_atom(false, name)
+        .map_err(x11_error)?
+        .reply()
+        .map_err(x11_error)?
+        .atom)
+}
+
+#[cfg(target_os = "linux")]
+pub fn find_process_window(pid: u32) -> Result<Option<WindowId>, WindowTweakError> {
+    use x11rb::{
+        connection::Connection,
+        protocol::xproto::{AtomEnum, ConnectionExt},
+    };
+
+    ensure_x11()?;
+    let (conn, screen) = x11rb::connect(None).map_err(x11_error)?;
+    let root = conn.setup().roots[screen].root;
+    let client_list = intern_atom(&conn, b"_NET_CLIENT_LIST")?;
+    let wm_pid = intern_atom(&conn, b"_NET_WM_PID")?;
+    let clients = conn
+        .get_property(false, root, client_list, AtomEnum::WINDOW, 0, u32::MAX)
+        .map_err(x11_error)?
+        .reply()
+        .map_err(x11_error)?;
+    let Some(windows) = clients.value32() else {
+        return Ok(None);
+    };
+    for window in windows {
+        let owner = conn
+            .get_property(false, window, wm_pid, AtomEnum::CARDINAL, 0, 1)
+            .map_err(x11_error)?
+            .reply()
+            .map_err(x11_error)?;
+        if owner.value32().and_then(|mut value| value.next()) == Some(pid) {
+            return Ok(Some(WindowId::from(window)));
+        }
+    }
+    Ok(None)
+}
+
+#[cfg(target_os = "linux")]
+pub fn apply_window_tweaks(window: WindowId, tweaks: WindowTweaks) -> Result<(), WindowTweakError> {
+    use x11rb::{
+        connection::Connection,
+        protocol::xproto::{ClientMessageEvent, ConnectionExt, EventMask, PropMode},
+        wrapper::ConnectionExt as _,
+    };
+
+    /// `_NET_WM_STATE_ADD` con origen "aplicación normal", según EWMH.
+    const NET_WM_STATE_ADD: u32 = 1;
+    const SOURCE_APPLICATION: u32 = 1;
+    /// `MWM_HINTS_DECORATIONS` en `flags`; `decorations = 0` quita marco y título.
+    const MWM_HINTS_DECORATIONS: u32 = 1 << 1;
+
+    ensure_x11()?;
+    let window = u32::try_from(window)
+        .map_err(|_| WindowTweakError::Failed(format!("id de ventana X11 inválido: {window}")))?;
+    let (conn, screen) = x11rb::connect(None).map_err(x11_error)?;
+    let root = conn.setup().roots[screen].root;
+    let wm_state = intern_atom(&conn, b"_NET_WM_STATE")?;
+    let mut states = Vec::new();
+    if tweaks.borderless {
+        let motif_hints = intern_atom(&conn, b"_MOTIF_WM_HINTS")?;
+        conn.change_property32(
+            PropMode::REPLACE,
+            window,
+            motif_hints,
+            motif_hints,
+            &[MWM_HINTS_DECORATIONS, 0, 0, 0, 0],
+        )
+        .map_err(x11_error)?;
+        states.push(intern_atom(&conn, b"_NET_WM_STATE_FULLSCREEN")?);
+    }
+    if tweaks.always_on_top {
+        states.push(intern_atom(&conn, b"_NET_WM_STATE_ABOVE")?);
+    }
+    for state in states {
+        let event = ClientMessageEvent::new(
+            32,
+            window,
+            wm_state,
+            [NET_WM_STATE_ADD, state, 0, SOURCE_APPLICATION, 0],
+        );
+        conn.send_event(
+            false,
+            root,
+            EventMask::SUBSTRUCTURE_REDIRECT | EventMask::SUBSTRUCTURE_NOTIFY,
+            event,
+        )
+        .map_err(x11_error)?;
+    }
+    conn.flush().map_err(x11_error)?;
+    Ok(())
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
+pub fn find_process_window(_pid: u32) -> Result<Option<WindowId>, WindowTweakError> {
+    Err(WindowTweakError::Unsupported(
+        "este sistema no permite ajustar la ventana del juego".to_string(),
+    ))
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
+pub fn apply_window_tweaks(
+    _window: WindowId,
+    _tweaks: WindowTweaks,
+) -> Result<(), WindowTweakError> {
+    Err(WindowTweakError::Unsupported(
+        "este sistema no permite ajustar la ventana del juego".to_string(),
+    ))
+}
@@ -0,0 +1,216 @@
+use std::{sync::OnceLock, time::Duration};
+
+use crate::{
+    domain::minecraft::gpu_compat::{normalize_gpu_vendor, GpuInfo},
+    platform::processes::run_command_with_timeout,
+};
+
+const PROBE_TIMEOUT: Duration = Duration::from_secs(4);
+
+static GPU_INFO: OnceLock<GpuInfo> = OnceLock::new();
+static GPU_ADAPTERS: OnceLock<Vec<GpuInfo>> = OnceLock::new();
+
+/// Ejecuta una sonda externa con límite de tiempo; cualquier fallo devuelve `None`.
+fn run_probe(program: &str, args: &[&str]) -> Option<String> {
+    run_command_with_timeout(program, args, PROBE_TIMEOUT)
+}
+
+fn value_after<'a>(text: &'a str, label: &str) -> Option<&'a str> {
+    text.lines()
+        .find_map(|line| line.trim().strip_prefix(label))
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
+
+fn gpu_info(adapter_name: &str, vendor_hint: &str, driver_version: &str, source: &str) -> GpuInfo {
+    let vendor = match normalize_gpu_vendor(adapter_name) {
+        "unknown" => normalize_gpu_vendor(vendor_hint),
+        vendor => vendor,
+    };
+    GpuInfo {
+        adapter_name: adapter_name.to_string(),
+        vendor: vendor.to_string(),
+        driver_version: driver_version.to_string(),
+        source: source.to_string(),
+    }
+}
+
+/// Interpreta `glxinfo -B`. La versión del driver es el último token de la versión de
+/// OpenGL (`4.6 (Compatibility Profile) Mesa 23.2.1` → `23.2.1`).
+pub fn parse_glxinfo(output: &str) -> Option<GpuInfo> {
+    let renderer = value_after(output, "OpenGL renderer string:")?;
+    let vendor = value_after(output, "OpenGL vendor string:").unwrap_or_default();
+    let driver_version = value_after(output, "OpenGL version string:")
+        .and_then(|version| version.split_whitespace().last())
+        .unwrap_or_default();
+    // nouveau se identifica por el vendor ("nouveau"), no por el renderer ("NV117").
+    let renderer = if vendor.eq_ignore_ascii_case("nouveau") {
+        format!("{renderer} (nouveau)")
+    } else {
+        renderer.to_string()
+    };
+    Some(gpu_info(&renderer, vendor, driver_version, "glxinfo"))
+}
+
+/// Interpreta `reg query` de la clave de un adaptador de vídeo (DriverDesc, ProviderName,
+/// DriverVersion).
+pub fn parse_windows_registry(output: &str) -> Option<GpuInfo> {
+    let value = |name: &str| {
+        output.lines().find_map(|line| {
+            let mut parts = line.split_whitespace();
+            (parts.next()? == name && parts.next()?.starts_with("REG_"))
+                .then(|| parts.collect::<Vec<_>>().join(" "))
+        })
+    };
+    let adapter_name = value("DriverDesc").filter(|name| !name.is_empty())?;
+    Some(gpu_info(
+        &adapter_name,
+        &value("ProviderName").unwrap_or_default(),
+        &value("DriverVersion").unwrap_or_default(),
+        "registry",
+    ))
+}
+
+#[cfg(target_os = "windows")]
+fn probe_adapters() -> Vec<GpuInfo> {
+    const DISPLAY_CLASS: &str =
+        r"HKLM\SYSTEM\CurrentControlSet\Control\Class\{4d36e968-e325-11ce-bfc1-08002be10318}";
+    (0..4)
+        .filter_map(|index| {
+            let key = format!(r"{DISPLAY_CLASS}\{index:04}");
+            run_probe("reg", &["query", &key]).and_then(|output| parse_windows_registry(&output))
+        })
+        .collect()
+}
+
+#[cfg(target_os = "windows")]
+fn probe_platform() -> Option<GpuInfo> {
+    let adapters = detect_gpu_adapters();
+    // El adaptador genérico de Microsoft solo cuenta si no hay otro (sin driver instalado).
+    adapters
+        .iter()
+        .find(|gpu| gpu.vendor != "software")
+        .or_else(|| adapters.first())
+        .cloned()
+}
+
+#[cfg(target_os = "linux")]
+fn probe_platform() -> Option<GpuInfo> {
+    run_probe("glxinfo", &["-B"])
+        .and_then(|output| parse_glxinfo(&output))
+        .or_else(|| probe_drm_sysfs().into_iter().next())
+}
+
+#[cfg(target_os = "linux")]
+fn probe_adapters() -> Vec<GpuInfo> {
+    probe_drm_sysfs()
+}
+
+/// Alternativa sin X11: fabricante y driver de cada nodo DRM (`/sys/class/drm/cardN/device`).
+#[cfg(target_os = "linux")]
+fn probe_drm_sysfs() -> Vec<GpuInfo> {
+    use std::fs;
+
+    (0..4)
+        .filter_map(|index| {
+            let device = std::path::PathBuf::from(format!("/sys/class/drm/card{index}/device"));
+            let vendor_id = fs::read_to_string(device.join("vendor")).ok()?;
+            let device_id = fs::read_to_string(device.join("device")).unwrap_or_default();
+            let driver = fs::read_link(device.join("driver"))
+                .ok()
+                .and_then(|link| {
+                    link.file_name()
+                        .map(|name| name.to_string_lossy().to_string())
+                })
+                .unwrap_or_default();
+            let driver_version = fs::read_to_string(format!("/sys/module/{driver}/version"))
+                .map(|version| version.trim().to_string())
+                .unwrap_or_else(|_| driver.clone());
+            let adapter_name = format!("{} {} ({driver})", vendor_id.trim(), device_id.trim());
+            Some(gpu_info(
+                &adapter_name,
+                vendor_id.trim(),
+                &driver_version,
+                "drm",
+            ))
+        })
+        .collect()
+}
+
+#[cfg(target_os = "macos")]
+fn probe_platform() -> Option<GpuInfo> {
+    let output = run_probe("system_profiler", &["SPDisplaysDataType"])?;
+    let adapter_name = value_after(&output, "Chipset Model:")?;
+    let vendor = value_after(&output, "Vendor:").unwrap_or(adapter_name);
+    Some(gpu_info(adapter_name, vendor, "", "system_profiler"))
+}
+
+#[cfg(target_os = "macos")]
+fn probe_adapters() -> Vec<GpuInfo> {
+    probe_platform().into_iter().collect()
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux", target_os = "macos")))]
+fn probe_platform() -> Option<GpuInfo> {
+    None
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux", target_os = "macos")))]
+fn probe_adapters() -> Vec<GpuInfo> {
+    Vec::new()
+}
+
+/// GPU principal del equipo. Se sondea una sola vez por sesión; si las sondas fallan
+/// devuelve "GPU desconocida" en lugar de un error.
+pub fn detect_gpu_info() -> GpuInfo {
+    GPU_INFO
+        .get_or_init(|| probe_platform().unwrap_or_else(GpuInfo::unknown))
+        .clone()
+}
+
+/// Todos los adaptadores de vídeo del equipo (integrada y dedicada en portátiles híbridos).
+/// Se sondean una sola vez por sesión.
+pub fn detect_gpu_adapters() -> Vec<GpuInfo> {
+    GPU_ADAPTERS.get_or_init(probe_adapters).clone()
+}
+
+#[tauri::command]
+pub async fn get_gpu_adapters() -> Result<Vec<GpuInfo>, String> {
+    tauri::async_runtime::spawn_blocking(detect_gpu_adapters)
+        .await
+        .map_err(|err| format!("Falló la detección de GPU: {err}"))
+}
+
+#[tauri::command]
+pub async fn get_gpu_info() -> Result<GpuInfo, String> {
+    tauri::async_runtime::spawn_blocking(detect_gpu_info)
+        .await
+        .map_err(|err| format!("Falló la detección de GPU: {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_glxinfo_and_registry_output() {
+        let glxinfo = "name of display: :0\n\
+            OpenGL vendor string: Mesa\n\
+            OpenGL renderer string: llvmpipe (LLVM 15.0.7, 256 bits)\n\
+            OpenGL version string: 4.5 (Compatibility Profile) Mesa 23.2.1\n";
+        let gpu = parse_glxinfo(glxinfo).expect("glxinfo");
+        assert_eq!(gpu.vendor, "software");
+        assert_eq!(gpu.driver_version, "23.2.1");
+
+        let registry = "HKEY_LOCAL_MACHINE\\...\\0000\n    \
+            DriverDesc    REG_SZ    Intel(R) HD Graphics 3000\n    \
+            ProviderName    REG_SZ    Intel Corporation\n    \
+            DriverVersion    REG_SZ    9.17.10.4459\n";
+        let gpu = parse_windows_registry(registry).expect("registry");
+        assert_eq!(gpu.adapter_name, "Intel(R) HD Graphics 3000");
+        assert_eq!(gpu.vendor, "intel");
+        assert_eq!(gpu.driver_version, "9.17.10.4459");
+
+        assert!(parse_glxinfo("Error: unable to open display").is_none());
+    }
+}
@@ -0,0 +1,270 @@
+// GPU preferida por instancia en gráficos híbridos.
+
+use std::{path::Path, process::Command};
+
+use crate::{domain::minecraft::gpu_compat::GpuInfo, platform::gpu::detect_gpu_adapters};
+
+#[cfg(target_os = "windows")]
+const USER_GPU_PREFERENCES_KEY: &str = r"HKCU\Software\Microsoft\DirectX\UserGpuPreferences";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GpuPreference {
+    Integrated,
+    Dedicated,
+    /// Nombre (o parte del nombre) de un adaptador detectado.
+    Adapter(String),
+}
+
+impl GpuPreference {
+    /// `None` para un valor vacío: sin preferencia, decide el sistema.
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        if value.is_empty() {
+            return None;
+        }
+        Some(match value.to_ascii_lowercase().as_str() {
+            "integrated" => Self::Integrated,
+            "dedicated" => Self::Dedicated,
+            _ => Self::Adapter(value.to_string()),
+        })
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            Self::Integrated => "integrated".to_string(),
+            Self::Dedicated => "dedicated".to_string(),
+            Self::Adapter(name) => name.clone(),
+        }
+    }
+}
+
+/// `true` si la preferencia pide la GPU dedicada; `None` si el adaptador no se detectó.
+pub fn wants_dedicated(preference: &GpuPreference, adapters: &[GpuInfo]) -> Option<bool> {
+    match preference {
+        GpuPreference::Integrated => Some(false),
+        GpuPreference::Dedicated => Some(true),
+        GpuPreference::Adapter(name) => {
+            let name = name.to_ascii_lowercase();
+            adapters
+                .iter()
+                .find(|gpu| gpu.adapter_name.to_ascii_lowercase().contains(&name))
+                .map(|gpu| !matches!(gpu.vendor.as_str(), "intel" | "software"))
+        }
+    }
+}
+
+/// Dato `REG_SZ` de `UserGpuPreferences`: 1 = ahorro de energía, 2 = alto rendimiento.
+pub fn windows_registry_data(dedicated: bool) -> &'static str {
+    if dedicated {
+        "GpuPreference=2;"
+    } else {
+        "GpuPreference=1;"
+    }
+}
+
+/// Variables de PRIME para el proceso del juego. Con NVIDIA se usa el offload del driver
+/// propietario; con Mesa, `DRI_PRIME`.
+pub fn linux_prime_env(dedicated: bool, adapters: &[GpuInfo]) -> Vec<(&'static str, &'static str)> {
+    if !dedicated {
+        return vec![("DRI_PRIME", "0")];
+    }
+    if adapters.iter().any(|gpu| gpu.vendor == "nvidia") {
+        vec![
+            ("__NV_PRIME_RENDER_OFFLOAD", "1"),
+            ("__GLX_VENDOR_LIBRARY_NAME", "nvidia"),
+        ]
+    } else {
+        vec![("DRI_PRIME", "1")]
+    }
+}
+
+/// Resultado de aplicar la preferencia, para el log del lanzamiento.
+#[derive(Debug, Clone)]
+pub struct AppliedGpuPreference {
+    pub log_line: String,
+    /// Ejecutable cuya entrada del registro se escribió; se usa para limpiarla al salir.
+    pub registry_executable: Option<String>,
+}
+
+#[cfg(target_os = "windows")]
+fn apply_platform(
+    _command: &mut Command,
+    java_launch_path: &Path,
+    label: &str,
+    dedicated: bool,
+    _adapters: &[GpuInfo],
+) -> AppliedGpuPreference {
+    let executable = java_launch_path.display().to_string();
+    let data = windows_registry_data(dedicated);
+    let written = crate::platform::processes::run_command_with_timeout(
+        "reg",
+        &[
+            "add",
+            USER_GPU_PREFERENCES_KEY,
+            "/v",
+            executable.as_str(),
+            "/t",
+            "REG_SZ",
+            "/d",
+            data,
+            "/f",
+        ],
+        std::time::Duration::from_secs(5),
+    )
+    .is_some();
+    if written {
+        AppliedGpuPreference {
+            log_line: format!(
+                "GPU preferida '{label}' aplicada con el registro de Windows ({data} para {executable})."
+            ),
+            registry_executable: Some(executable),
+        }
+    } else {
+        AppliedGpuPreference {
+            log_line: format!(
+                "⚠ No se pudo escribir la preferencia de GPU '{label}' en el registro para {executable}; se lanza con la GPU que elija el sistema."
+            ),
+            registry_executable: None,
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn apply_platform(
+    command: &mut Command,
+    _java_launch_path: &Path,
+    label: &str,
+    dedicated: bool,
+    adapters: &[GpuInfo],
+) -> AppliedGpuPreference {
+    let env = linux_prime_env(dedicated, adapters);
+    let vars = env
+        .iter()
+        .map(|(key, value)| format!("{key}={value}"))
+        .collect::<Vec<_>>()
+        .join(" ");
+    command.envs(env);
+    AppliedGpuPreference {
+        log_line: format!("GPU preferida '{label}' aplicada con variables de entorno ({vars})."),
+        registry_executable: None,
+    }
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
+fn apply_platform(
+    _command: &mut Command,
+    _java_launch_path: &Path,
+    label: &str,
+    _dedicated: bool,
+    _adapters: &[GpuInfo],
+) -> AppliedGpuPreference {
+    AppliedGpuPreference {
+        log_line: format!(
+            "⚠ La preferencia de GPU '{label}' no está soportada en este sistema; se ignora."
+        ),
+        registry_executable: None,
+    }
+}
+
+/// Aplica la GPU preferida de la instancia antes de lanzar `java_launch_path`. Nunca falla:
+/// si no se puede aplicar, la línea de log es un aviso.
+pub fn apply_gpu_preference(
+    command: &mut Command,
+    java_launch_path: &Path,
+    preferred_gpu: Option<&str>,
+) -> Option<AppliedGpuPreference> {
+    let preference = GpuPreference::parse(preferred_gpu?)?;
+    let label = preference.label();
+    let adapters = detect_gpu_adapters();
+    let applied = match wants_dedicated(&preference, &adapters) {
+        Some(dedicated) => apply_platform(command, java_launch_path, &label, dedicated, &adapters),
+        None => AppliedGpuPreference {
+            log_line: format!(
+                "⚠ No se encontró el adaptador '{label}' entre las GPU detectadas; se lanza con la GPU que elija el sistema."
+            ),
+            registry_executable: None,
+        },
+    };
+    if applied.log_line.starts_with('⚠') {
+        log::warn!("{}", applied.log_line);
+    } else {
+        log::info!("🔹 {}", applied.log_line);
+    }
+    Some(applied)
+}
+
+/// Quita la entrada escrita por `apply_gpu_preference` (ajuste
+/// `clear_gpu_preference_on_exit`).
+#[cfg(target_os = "windows")]
+pub fn clear_gpu_preference(executable: &str) {
+    let removed = crate::platform::processes::run_command_with_timeout(
+        "reg",
+        &["delete", USER_GPU_PREFERENCES_KEY, "/v", executable, "/f"],
+        std::time::Duration::from_secs(5),
+    )
+    .is_some();
+    if removed {
+        log::info!("✔ Preferencia de GPU eliminada del registro para {executable}");
+    } else {
+        log::warn!("⚠ No se pudo eliminar la preferencia de GPU del registro para {executable}");
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn clear_gpu_preference(_executable: &str) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gpu(adapter_name: &str, vendor: &str) -> GpuInfo {
+        GpuInfo {
+            adapter_name: adapter_name.to_string(),
+            vendor: vendor.to_string(),
+            driver_version: String::new(),
+            source: "registry".to_string(),
+        }
+    }
+
+    #[test]
+    fn resolves_named_adapters_by_vendor() {
+        let adapters = [
+            gpu("Intel(R) UHD Graphics 620", "intel"),
+            gpu("NVIDIA GeForce MX150", "nvidia"),
+        ];
+        assert_eq!(GpuPreference::parse("  "), None);
+        assert_eq!(
+            GpuPreference::parse("Dedicated"),
+            Some(GpuPreference::Dedicated)
+        );
+        let named = GpuPreference::parse("geforce mx150").expect("adapter");
+        assert_eq!(wants_dedicated(&named, &adapters), Some(true));
+        let intel = GpuPreference::parse("UHD Graphics").expect("adapter");
+        assert_eq!(wants_dedicated(&intel, &adapters), Some(false));
+        let missing = GpuPreference::parse("Radeon RX 6600").expect("adapter");
+        assert_eq!(wants_dedicated(&missing, &adapters), None);
+        assert_eq!(windows_registry_data(true), "GpuPreference=2;");
+        assert_eq!(windows_registry_data(false), "GpuPreference=1;");
+    }
+
+    #[test]
+    fn picks_prime_variables_for_the_driver() {
+        let nvidia = [
+            gpu("0x8086 0x5917 (i915)", "intel"),
+            gpu("0x10de 0x1d10 (nvidia)", "nvidia"),
+        ];
+        assert_eq!(
+            linux_prime_env(true, &nvidia),
+            vec![
+                ("__NV_PRIME_RENDER_OFFLOAD", "1"),
+                ("__GLX_VENDOR_LIBRARY_NAME", "nvidia")
+            ]
+        );
+        let mesa = [
+            gpu("0x8086 0x5917 (i915)", "intel"),
+            gpu("0x1002 0x699f (amdgpu)", "amd"),
+        ];
+        assert_eq!(linux_prime_env(true, &mesa), vec![("DRI_PRIME", "1")]);
+        assert_eq!(linux_prime_env(false, &nvidia), vec![("DRI_PRIME", "0")]);
+    }
+}
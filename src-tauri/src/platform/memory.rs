@@ -0,0 +1,67 @@
+use std::sync::OnceLock;
+
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+use crate::platform::processes::run_command_with_timeout;
+
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+const PROBE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(4);
+
+static TOTAL_MEMORY_MB: OnceLock<Option<u64>> = OnceLock::new();
+
+/// Interpreta la línea `MemTotal:` de `/proc/meminfo` (en kB).
+#[cfg(target_os = "linux")]
+fn parse_meminfo_total_mb(meminfo: &str) -> Option<u64> {
+    meminfo
+        .lines()
+        .find_map(|line| line.strip_prefix("MemTotal:"))
+        .and_then(|rest| rest.split_whitespace().next())
+        .and_then(|kb| kb.parse::<u64>().ok())
+        .map(|kb| kb / 1024)
+}
+
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+fn bytes_to_mb(output: &str) -> Option<u64> {
+    output
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .map(|bytes| bytes / (1024 * 1024))
+}
+
+#[cfg(target_os = "linux")]
+fn probe_platform() -> Option<u64> {
+    parse_meminfo_total_mb(&std::fs::read_to_string("/proc/meminfo").ok()?)
+}
+
+#[cfg(target_os = "macos")]
+fn probe_platform() -> Option<u64> {
+    bytes_to_mb(&run_command_with_timeout(
+        "sysctl",
+        &["-n", "hw.memsize"],
+        PROBE_TIMEOUT,
+    )?)
+}
+
+#[cfg(target_os = "windows")]
+fn probe_platform() -> Option<u64> {
+    let output = run_command_with_timeout(
+        "powershell",
+        &[
+            "-NoProfile",
+            "-Command",
+            "(Get-CimInstance Win32_ComputerSystem).TotalPhysicalMemory",
+        ],
+        PROBE_TIMEOUT,
+    )?;
+    bytes_to_mb(&output)
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux", target_os = "macos")))]
+fn probe_platform() -> Option<u64> {
+    None
+}
+
+/// Memoria física total en MB, sondeada una vez por sesión; `None` si no se pudo leer.
+pub fn total_memory_mb() -> Option<u64> {
+    *TOTAL_MEMORY_MB.get_or_init(probe_platform)
+}
@@ -1,3 +1,10 @@
+pub mod executable_arch;
+pub mod game_window;
+pub mod gpu;
+pub mod gpu_preference;
 pub mod linux;
 pub mod macos;
+pub mod memory;
+pub mod os_version;
+pub mod processes;
 pub mod windows;
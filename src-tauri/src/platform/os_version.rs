@@ -0,0 +1,82 @@
+use std::sync::OnceLock;
+
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+use crate::platform::processes::run_command_with_timeout;
+
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+const PROBE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(4);
+
+static OS_VERSION: OnceLock<String> = OnceLock::new();
+
+/// `Microsoft Windows [Version 10.0.19045.3803]` -> `10.0`, como lo informa Java. La
+/// palabra `Version` cambia con el idioma del sistema; se toma el número entre corchetes.
+#[cfg(any(target_os = "windows", test))]
+fn parse_windows_ver(output: &str) -> Option<String> {
+    let inside = output.split('[').nth(1)?.split(']').next()?;
+    let version = inside.split_whitespace().last()?;
+    let mut parts = version.split('.');
+    let major = parts.next().filter(|part| part.parse::<u32>().is_ok())?;
+    let minor = parts.next().filter(|part| part.parse::<u32>().is_ok())?;
+    Some(format!("{major}.{minor}"))
+}
+
+#[cfg(target_os = "linux")]
+fn probe_platform() -> Option<String> {
+    Some(
+        std::fs::read_to_string("/proc/sys/kernel/osrelease")
+            .ok()?
+            .trim()
+            .to_string(),
+    )
+}
+
+#[cfg(target_os = "macos")]
+fn probe_platform() -> Option<String> {
+    Some(
+        run_command_with_timeout("sw_vers", &["-productVersion"], PROBE_TIMEOUT)?
+            .trim()
+            .to_string(),
+    )
+}
+
+#[cfg(target_os = "windows")]
+fn probe_platform() -> Option<String> {
+    parse_windows_ver(&run_command_with_timeout(
+        "cmd",
+        &["/C", "ver"],
+        PROBE_TIMEOUT,
+    )?)
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux", target_os = "macos")))]
+fn probe_platform() -> Option<String> {
+    None
+}
+
+/// Versión del sistema con el formato de `os.version` de Java, sondeada una vez por
+/// sesión; vacía si no se pudo leer.
+pub fn os_version() -> &'static str {
+    OS_VERSION.get_or_init(|| probe_platform().unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn windows_ver_output_maps_to_java_os_version() {
+        assert_eq!(
+            parse_windows_ver("\r\nMicrosoft Windows [Version 10.0.19045.3803]\r\n").as_deref(),
+            Some("10.0")
+        );
+        assert_eq!(
+            parse_windows_ver("Microsoft Windows [Versión 6.1.7601]").as_deref(),
+            Some("6.1")
+        );
+        assert_eq!(
+            parse_windows_ver("Microsoft Windows [Version]").as_deref(),
+            None
+        );
+        assert_eq!(parse_windows_ver("").as_deref(), None);
+    }
+}
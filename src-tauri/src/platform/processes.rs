@@ -0,0 +1,387 @@
+use std::{
+    path::PathBuf,
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Proceso Java en ejecución visto por el sistema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JavaProcess {
+    pub pid: u32,
+    /// Línea de comandos completa con los argumentos separados por espacios.
+    pub command_line: String,
+    /// Directorio de trabajo, solo donde el sistema lo expone sin privilegios (Linux).
+    pub cwd: Option<PathBuf>,
+}
+
+/// Ejecuta un comando externo con límite de tiempo; cualquier fallo devuelve `None`.
+pub(crate) fn run_command_with_timeout(
+    program: &str,
+    args: &[&str],
+    timeout: Duration,
+) -> Option<String> {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+    let started = Instant::now();
+    loop {
+        match child.try_wait().ok()? {
+            Some(_) => break,
+            None if started.elapsed() > timeout => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return None;
+            }
+            None => thread::sleep(Duration::from_millis(20)),
+        }
+    }
+    let output = child.wait_with_output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+fn is_java_executable(program: &str) -> bool {
+    let name = program
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    matches!(name.as_str(), "java" | "javaw" | "java.exe" | "javaw.exe")
+}
+
+#[cfg(target_os = "linux")]
+fn list_platform_java_processes() -> Vec<JavaProcess> {
+    use std::fs;
+
+    let Ok(entries) = fs::read_dir("/proc") else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let pid = entry.file_name().to_str()?.parse::<u32>().ok()?;
+            let raw = fs::read(entry.path().join("cmdline")).ok()?;
+            let args = raw
+                .split(|byte| *byte == 0)
+                .filter(|arg| !arg.is_empty())
+                .map(|arg| String::from_utf8_lossy(arg).to_string())
+                .collect::<Vec<_>>();
+            if !is_java_executable(args.first()?) {
+                return None;
+            }
+            Some(JavaProcess {
+                pid,
+                command_line: args.join(" "),
+                cwd: fs::read_link(entry.path().join("cwd")).ok(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(target_os = "macos")]
+fn list_platform_java_processes() -> Vec<JavaProcess> {
+    let Some(output) = run_command_with_timeout(
+        "ps",
+        &["-axww", "-o", "pid=,command="],
+        Duration::from_millis(500),
+    ) else {
+        return Vec::new();
+    };
+    output
+        .lines()
+        .filter_map(|line| {
+            let (pid, command_line) = line.trim().split_once(' ')?;
+            let command_line = command_line.trim();
+            if !is_java_executable(command_line.split_whitespace().next()?) {
+                return None;
+            }
+            Some(JavaProcess {
+                pid: pid.parse().ok()?,
+                command_line: command_line.to_string(),
+                cwd: None,
+            })
+        })
+        .collect()
+}
+
+/// `wmic` responde en ~100 ms; en sistemas donde ya no existe la búsqueda queda vacía
+/// y solo cuentan los `session.lock`.
+#[cfg(target_os = "windows")]
+fn list_platform_java_processes() -> Vec<JavaProcess> {
+    let Some(output) = run_command_with_timeout(
+        "wmic",
+        &[
+            "process",
+            "where",
+            "name='java.exe' or name='javaw.exe'",
+            "get",
+            "ProcessId,CommandLine",
+            "/format:list",
+        ],
+        Duration::from_millis(1500),
+    ) else {
+        return Vec::new();
+    };
+    parse_wmic_list(&output)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn list_platform_java_processes() -> Vec<JavaProcess> {
+    Vec::new()
+}
+
+/// Interpreta `wmic ... /format:list`: bloques `CommandLine=...` / `ProcessId=...`.
+#[cfg_attr(not(any(target_os = "windows", test)), allow(dead_code))]
+fn parse_wmic_list(output: &str) -> Vec<JavaProcess> {
+    let mut processes = Vec::new();
+    let mut command_line = None;
+    for line in output.lines().map(str::trim) {
+        if let Some(value) = line.strip_prefix("CommandLine=") {
+            command_line = Some(value.to_string());
+        } else if let Some(value) = line.strip_prefix("ProcessId=") {
+            if let (Ok(pid), Some(command_line)) = (value.parse(), command_line.take()) {
+                processes.push(JavaProcess {
+                    pid,
+                    command_line,
+                    cwd: None,
+                });
+            }
+        }
+    }
+    processes
+}
+
+/// Si el proceso `pid` sigue vivo.
+#[cfg(unix)]
+pub fn is_process_alive(pid: u32) -> bool {
+    let Ok(pid) = libc::pid_t::try_from(pid) else {
+        return false;
+    };
+    // La señal 0 solo comprueba que el proceso exista; EPERM indica que existe pero es ajeno.
+    if unsafe { libc::kill(pid, 0) } == 0 {
+        return true;
+    }
+    std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
+
+#[cfg(windows)]
+pub fn is_process_alive(pid: u32) -> bool {
+    run_command_with_timeout(
+        "tasklist",
+        &["/FI", &format!("PID eq {pid}"), "/NH", "/FO", "CSV"],
+        Duration::from_millis(1500),
+    )
+    .is_some_and(|output| output.contains(&format!("\"{pid}\"")))
+}
+
+/// Pide al proceso que se cierre por su cuenta: SIGTERM solo a `pid` (no al grupo) en
+/// Unix. Devuelve `false` si no se pudo entregar la petición.
+#[cfg(unix)]
+pub fn request_process_close(pid: u32) -> bool {
+    let Ok(pid) = libc::pid_t::try_from(pid) else {
+        return false;
+    };
+    unsafe { libc::kill(pid, libc::SIGTERM) == 0 }
+}
+
+/// Pide al proceso que se cierre por su cuenta: `WM_CLOSE` a cada ventana de primer nivel
+/// de `pid`. Sin ventanas visibles (todavía cargando, o sin escritorio) devuelve `false`.
+#[cfg(windows)]
+pub fn request_process_close(pid: u32) -> bool {
+    use windows_sys::Win32::UI::WindowsAndMessaging::{PostMessageW, WM_CLOSE};
+
+    visible_top_level_windows(pid)
+        .into_iter()
+        .filter(|window| unsafe { PostMessageW(*window, WM_CLOSE, 0, 0) } != 0)
+        .count()
+        > 0
+}
+
+/// Ventanas de primer nivel visibles de `pid`, en el orden de `EnumWindows` (de arriba
+/// abajo en el orden Z).
+#[cfg(windows)]
+pub(crate) fn visible_top_level_windows(pid: u32) -> Vec<windows_sys::Win32::Foundation::HWND> {
+    use windows_sys::Win32::{
+        Foundation::{BOOL, HWND, LPARAM},
+        UI::WindowsAndMessaging::{EnumWindows, GetWindowThreadProcessId, IsWindowVisible},
+    };
+
+    struct Search {
+        pid: u32,
+        windows: Vec<HWND>,
+    }
+
+    unsafe extern "system" fn visit(window: HWND, lparam: LPARAM) -> BOOL {
+        let search = &mut *(lparam as *mut Search);
+        let mut owner = 0;
+        GetWindowThreadProcessId(window, &mut owner);
+        if owner == search.pid && IsWindowVisible(window) != 0 {
+            search.windows.push(window);
+        }
+        1
+    }
+
+    let mut search = Search {
+        pid,
+        windows: Vec::new(),
+    };
+    // SAFETY: `search` vive durante toda la enumeración, que es síncrona.
+    unsafe {
+        EnumWindows(Some(visit), &mut search as *mut Search as LPARAM);
+    }
+    search.windows
+}
+
+/// Memoria residente y tiempo de CPU acumulado (usuario + sistema) de un proceso.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProcessUsage {
+    pub rss_bytes: u64,
+    pub cpu_time_ms: u64,
+}
+
+/// Uso actual de `pid`; `None` si ya no existe o no se puede leer.
+#[cfg(target_os = "linux")]
+pub fn process_usage(pid: u32) -> Option<ProcessUsage> {
+    use std::fs;
+
+    let stat = fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+    // El nombre del ejecutable va entre paréntesis y puede contener espacios; tras él, el
+    // campo 3 (`state`) queda en la posición 0, así que utime (14) y stime (15) son 11 y 12.
+    let fields = stat
+        .get(stat.rfind(')')? + 1..)?
+        .split_whitespace()
+        .collect::<Vec<_>>();
+    let utime = fields.get(11)?.parse::<u64>().ok()?;
+    let stime = fields.get(12)?.parse::<u64>().ok()?;
+    let statm = fs::read_to_string(format!("/proc/{pid}/statm")).ok()?;
+    let resident_pages = statm.split_whitespace().nth(1)?.parse::<u64>().ok()?;
+    // SAFETY: `sysconf` solo consulta constantes del sistema.
+    let (ticks, page_size) = unsafe {
+        (
+            libc::sysconf(libc::_SC_CLK_TCK),
+            libc::sysconf(libc::_SC_PAGESIZE),
+        )
+    };
+    Some(ProcessUsage {
+        rss_bytes: resident_pages * u64::try_from(page_size).ok()?.max(1),
+        cpu_time_ms: (utime + stime) * 1000 / u64::try_from(ticks).ok()?.max(1),
+    })
+}
+
+#[cfg(target_os = "macos")]
+pub fn process_usage(pid: u32) -> Option<ProcessUsage> {
+    let output = run_command_with_timeout(
+        "ps",
+        &["-o", "rss=,time=", "-p", &pid.to_string()],
+        Duration::from_millis(1500),
+    )?;
+    let mut fields = output.split_whitespace();
+    let rss_kb = fields.next()?.parse::<u64>().ok()?;
+    Some(ProcessUsage {
+        rss_bytes: rss_kb * 1024,
+        cpu_time_ms: parse_ps_cpu_time(fields.next()?)?,
+    })
+}
+
+#[cfg(target_os = "windows")]
+pub fn process_usage(pid: u32) -> Option<ProcessUsage> {
+    use windows_sys::Win32::{
+        Foundation::{CloseHandle, FILETIME},
+        System::{
+            ProcessStatus::{K32GetProcessMemoryInfo, PROCESS_MEMORY_COUNTERS},
+            Threading::{GetProcessTimes, OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION},
+        },
+    };
+
+    fn hundred_ns(time: FILETIME) -> u64 {
+        (u64::from(time.dwHighDateTime) << 32) | u64::from(time.dwLowDateTime)
+    }
+
+    // SAFETY: el handle se comprueba antes de usarlo y se cierra en todos los caminos; las
+    // estructuras de salida viven en esta función.
+    unsafe {
+        let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+        if handle.is_null() {
+            return None;
+        }
+        let mut creation: FILETIME = std::mem::zeroed();
+        let mut exit: FILETIME = std::mem::zeroed();
+        let mut kernel: FILETIME = std::mem::zeroed();
+        let mut user: FILETIME = std::mem::zeroed();
+        let times = GetProcessTimes(handle, &mut creation, &mut exit, &mut kernel, &mut user);
+        let mut counters: PROCESS_MEMORY_COUNTERS = std::mem::zeroed();
+        counters.cb = std::mem::size_of::<PROCESS_MEMORY_COUNTERS>() as u32;
+        let memory = K32GetProcessMemoryInfo(handle, &mut counters, counters.cb);
+        CloseHandle(handle);
+        // Un proceso terminado conserva sus tiempos mientras haya handles abiertos.
+        if times == 0 || memory == 0 || hundred_ns(exit) != 0 {
+            return None;
+        }
+        Some(ProcessUsage {
+            rss_bytes: counters.WorkingSetSize as u64,
+            cpu_time_ms: (hundred_ns(kernel) + hundred_ns(user)) / 10_000,
+        })
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+pub fn process_usage(_pid: u32) -> Option<ProcessUsage> {
+    None
+}
+
+/// Tiempo de CPU de `ps -o time=`: `[[dd-]hh:]mm:ss[.cc]`.
+#[cfg_attr(not(any(target_os = "macos", test)), allow(dead_code))]
+fn parse_ps_cpu_time(value: &str) -> Option<u64> {
+    let (days, clock) = match value.split_once('-') {
+        Some((days, clock)) => (days.parse::<u64>().ok()?, clock),
+        None => (0, value),
+    };
+    let mut seconds = 0.0;
+    for part in clock.split(':') {
+        seconds = seconds * 60.0 + part.parse::<f64>().ok()?;
+    }
+    Some(((days * 86_400) as f64 * 1000.0 + seconds * 1000.0).round() as u64)
+}
+
+/// Procesos Java del sistema (excluido el propio launcher).
+pub fn list_java_processes() -> Vec<JavaProcess> {
+    let own_pid = std::process::id();
+    list_platform_java_processes()
+        .into_iter()
+        .filter(|process| process.pid != own_pid)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_wmic_output_and_detects_java_executables() {
+        let output = "\r\n\r\nCommandLine=\"C:\\Program Files\\Java\\bin\\javaw.exe\" -Xmx4G net.minecraft.client.main.Main --gameDir C:\\mc\r\nProcessId=4242\r\n\r\nCommandLine=\r\nProcessId=17\r\n";
+        let processes = parse_wmic_list(output);
+        assert_eq!(processes.len(), 2);
+        assert_eq!(processes[0].pid, 4242);
+        assert!(processes[0].command_line.contains("--gameDir C:\\mc"));
+
+        assert!(is_java_executable("/usr/lib/jvm/java-17/bin/java"));
+        assert!(is_java_executable("C:\\Java\\bin\\javaw.exe"));
+        assert!(!is_java_executable("/usr/bin/javac"));
+    }
+
+    #[test]
+    fn parses_ps_cpu_times() {
+        assert_eq!(parse_ps_cpu_time("0:01.50"), Some(1_500));
+        assert_eq!(parse_ps_cpu_time("12:34.00"), Some(754_000));
+        assert_eq!(parse_ps_cpu_time("01:00:00"), Some(3_600_000));
+        assert_eq!(parse_ps_cpu_time("2-00:00:01"), Some(172_801_000));
+        assert_eq!(parse_ps_cpu_time("abc"), None);
+    }
+}
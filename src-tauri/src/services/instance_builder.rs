@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::{
-    collections::VecDeque,
+    collections::{HashSet, VecDeque},
     fs,
     path::Path,
     sync::{Arc, Mutex},
@@ -20,9 +20,10 @@ use crate::{
     infrastructure::{
         checksum::sha1::compute_file_sha1,
         downloader::queue::{build_official_client, download_with_retry, DownloadJob},
+        filesystem::{disk_space::preflight_disk_space, file_ops::write_file_replacing},
     },
     services::loader_installer::install_loader_if_needed,
-    shared::result::AppResult,
+    shared::{result::AppResult, tasks::current_task},
 };
 
 const MOJANG_MANIFEST_URL: &str =
@@ -154,6 +155,35 @@ pub fn build_instance_structure(
     java_exec: &Path,
     logs: &mut Vec<String>,
     on_progress: &mut dyn FnMut(InstanceBuildProgress),
+) -> AppResult<String> {
+    let normalized_minecraft_version = download_vanilla_files(
+        instance_root,
+        minecraft_root,
+        minecraft_version,
+        on_progress,
+    )?;
+    install_instance_loader(
+        minecraft_root,
+        &normalized_minecraft_version,
+        loader,
+        loader_version,
+        java_exec,
+        logs,
+        on_progress,
+    )
+}
+
+/// Punto seguro entre pasos: corta si la tarea del hilo se canceló.
+fn check_task_cancelled() -> AppResult<()> {
+    current_task().map_or(Ok(()), |task| task.check_cancelled())
+}
+
+/// Pasos 1–6 (archivos vanilla); no necesita Java. Devuelve la versión normalizada.
+pub fn download_vanilla_files(
+    instance_root: &Path,
+    minecraft_root: &Path,
+    minecraft_version: &str,
+    on_progress: &mut dyn FnMut(InstanceBuildProgress),
 ) -> AppResult<String> {
     let launcher_root = instance_root
         .parent()
@@ -191,6 +221,7 @@ pub fn build_instance_structure(
     let normalized_minecraft_version = normalize_minecraft_version_id(minecraft_version);
     let version_entry = load_manifest_entry(launcher_root, &normalized_minecraft_version)?;
 
+    check_task_cancelled()?;
     on_progress(InstanceBuildProgress {
         step: "downloading_version_json".to_string(),
         step_index: 2,
@@ -201,6 +232,7 @@ pub fn build_instance_structure(
     });
     let version_json = download_version_json(minecraft_root, &version_entry)?;
 
+    check_task_cancelled()?;
     on_progress(InstanceBuildProgress {
         step: "downloading_client_jar".to_string(),
         step_index: 3,
@@ -211,6 +243,7 @@ pub fn build_instance_structure(
     });
     download_client_jar(minecraft_root, &version_entry.id, &version_json)?;
 
+    check_task_cancelled()?;
     on_progress(InstanceBuildProgress {
         step: "downloading_libraries".to_string(),
         step_index: 4,
@@ -221,6 +254,7 @@ pub fn build_instance_structure(
     });
     download_libraries(&version_json, &shared_libraries, on_progress)?;
 
+    check_task_cancelled()?;
     on_progress(InstanceBuildProgress {
         step: "downloading_assets_index".to_string(),
         step_index: 5,
@@ -231,6 +265,7 @@ pub fn build_instance_structure(
     });
     let assets_index = download_assets_index(&version_json, &shared_assets)?;
 
+    check_task_cancelled()?;
     on_progress(InstanceBuildProgress {
         step: "downloading_assets".to_string(),
         step_index: 6,
@@ -240,7 +275,22 @@ pub fn build_instance_structure(
         total: 1,
     });
     download_assets_objects(&assets_index, &shared_assets, on_progress)?;
+    check_task_cancelled()?;
 
+    Ok(normalized_minecraft_version)
+}
+
+/// Pasos 7–8: instala el loader. Forge/NeoForge ejecutan su instalador con `java_exec`, así
+/// que solo se llama con el runtime y los archivos vanilla ya en disco.
+pub fn install_instance_loader(
+    minecraft_root: &Path,
+    normalized_minecraft_version: &str,
+    loader: &str,
+    loader_version: &str,
+    java_exec: &Path,
+    logs: &mut Vec<String>,
+    on_progress: &mut dyn FnMut(InstanceBuildProgress),
+) -> AppResult<String> {
     on_progress(InstanceBuildProgress {
         step: "installing_loader".to_string(),
         step_index: 7,
@@ -251,7 +301,7 @@ pub fn build_instance_structure(
     });
     let effective_version_id = prepare_loader(
         minecraft_root,
-        &normalized_minecraft_version,
+        normalized_minecraft_version,
         loader,
         loader_version,
         java_exec,
@@ -353,6 +403,22 @@ fn load_manifest_entry(
         })
 }
 
+/// Ids del manifest cacheado, sin descargarlo; `None` si no hay caché legible.
+pub fn cached_manifest_version_ids(launcher_root: &Path) -> Option<(HashSet<String>, bool)> {
+    let cache_path = launcher_root.join("cache").join("version_manifest_v2.json");
+    let raw = fs::read_to_string(&cache_path).ok()?;
+    let manifest = serde_json::from_str::<VersionManifest>(&raw).ok()?;
+    let fresh = !must_refresh_manifest(&cache_path).unwrap_or(true);
+    Some((
+        manifest
+            .versions
+            .into_iter()
+            .map(|entry| entry.id)
+            .collect(),
+        fresh,
+    ))
+}
+
 fn must_refresh_manifest(cache_path: &Path) -> AppResult<bool> {
     if !cache_path.exists() {
         return Ok(true);
@@ -645,6 +711,10 @@ fn download_assets_objects(
     if total == 0 {
         return Ok(());
     }
+    preflight_disk_space(
+        &shared_assets_root.join("objects"),
+        jobs.iter().map(|(_, size)| size).sum(),
+    )?;
 
     run_download_jobs_limited(jobs.into_iter().map(|(job, _)| job).collect(), 16)?;
     on_progress(InstanceBuildProgress {
@@ -663,12 +733,14 @@ fn run_download_jobs_limited(jobs: Vec<DownloadJob>, max_concurrency: usize) ->
     let queue = Arc::new(Mutex::new(VecDeque::from(jobs)));
     let progress = Arc::new(Mutex::new(0_u64));
     let errors = Arc::new(Mutex::new(Vec::<String>::new()));
+    let task = current_task();
 
     thread::scope(|scope| {
         for _ in 0..workers {
             let queue = Arc::clone(&queue);
             let progress = Arc::clone(&progress);
             let errors = Arc::clone(&errors);
+            let task = task.clone();
             scope.spawn(move || {
                 let client = match build_official_client() {
                     Ok(client) => client,
@@ -681,6 +753,10 @@ fn run_download_jobs_limited(jobs: Vec<DownloadJob>, max_concurrency: usize) ->
                 };
 
                 loop {
+                    // Punto seguro: cada archivo se escribe completo antes de pasar al siguiente.
+                    if task.as_ref().is_some_and(|task| task.is_cancelled()) {
+                        break;
+                    }
                     let next = queue.lock().ok().and_then(|mut q| q.pop_front());
                     let Some(job) = next else { break };
 
@@ -705,6 +781,7 @@ fn run_download_jobs_limited(jobs: Vec<DownloadJob>, max_concurrency: usize) ->
         }
     });
 
+    check_task_cancelled()?;
     let errors = errors
         .lock()
         .map_err(|_| "No se pudo bloquear colección de errores de descarga".to_string())?;
@@ -768,12 +845,17 @@ pub fn persist_instance_metadata(
 ) -> AppResult<()> {
     let metadata_path = instance_root.join(".instance.json");
     let metadata_content = serde_json::to_string_pretty(metadata).map_err(|err| err.to_string())?;
-    fs::write(&metadata_path, metadata_content).map_err(|err| {
-        format!(
-            "No se pudo guardar la metadata de la instancia en {}: {err}",
-            metadata_path.display()
-        )
-    })?;
+    let supports_atomic_rename = metadata
+        .filesystem
+        .as_ref()
+        .map(|capabilities| capabilities.supports_atomic_rename)
+        .unwrap_or(true);
+    write_file_replacing(
+        &metadata_path,
+        metadata_content.as_bytes(),
+        supports_atomic_rename,
+    )
+    .map_err(|err| format!("No se pudo guardar la metadata de la instancia: {err}"))?;
 
     let instance_json_path = instance_root.join("instance.json");
     let state_file = InstanceStateFile {
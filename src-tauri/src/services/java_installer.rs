@@ -1,6 +1,17 @@
-use std::{ffi::OsStr, fs, io::Cursor, path::Path, path::PathBuf, process::Command};
+use std::{
+    collections::HashMap,
+    ffi::OsStr,
+    fs,
+    io::{Cursor, Read},
+    path::Path,
+    path::PathBuf,
+    process::Command,
+    sync::{Mutex, OnceLock},
+    time::SystemTime,
+};
 
 use flate2::read::GzDecoder;
+use serde::Serialize;
 use tar::Archive;
 use zip::ZipArchive;
 
@@ -9,69 +20,432 @@ use crate::{
     infrastructure::{
         checksum::sha1::sha256_hex,
         downloader::{
-            client::{build_http_client, resolve_temurin_asset},
+            client::{build_http_client, resolve_temurin_asset, resolve_temurin_release_asset},
             integrity::validate_checksum,
         },
-        filesystem::paths::java_executable_path,
+        filesystem::{
+            capabilities::probe_filesystem_capabilities, disk_space::preflight_disk_space,
+            paths::java_executable_path,
+        },
     },
-    shared::result::AppResult,
+    platform::executable_arch::foreign_executable_architectures,
+    shared::{result::AppResult, tasks::current_task},
 };
 
-pub fn ensure_embedded_java(
+/// Build a la que pasan los runtimes instalados antes de admitir varias por major.
+pub const DEFAULT_JAVA_BUILD: &str = "default";
+const INSTALLING_DIR: &str = ".installing";
+/// Sufijo de la build sustituida mientras se publica la nueva en su lugar.
+const REPLACED_SUFFIX: &str = ".replaced";
+const MIGRATING_DIR: &str = ".migrating-default";
+/// Sufijo de las builds apartadas por estar compiladas para otra arquitectura.
+const WRONG_ARCH_SUFFIX: &str = ".wrong-arch";
+
+/// Build de Java instalada en `runtime/javaNN/<build>/`.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct InstalledJavaBuild {
+    pub name: String,
+    pub java_path: String,
+    /// `JAVA_RUNTIME_VERSION` del archivo `release` del runtime.
+    pub runtime_version: String,
+    /// `adoptium`, `local`, `migrated` o `recovered` (marcador regenerado).
+    pub source: String,
+}
+
+pub fn runtime_major_root(root: &Path, runtime: JavaRuntime) -> PathBuf {
+    root.join("runtime").join(runtime.as_dir_name())
+}
+
+/// Los runtimes anteriores vivían directamente en `runtime/javaNN`; se mueven a la build
+/// `default` sin volver a descargarlos.
+pub fn migrate_flat_runtime(major_root: &Path) -> AppResult<()> {
+    if !java_executable_path(major_root).exists() {
+        return Ok(());
+    }
+    let staging = major_root.join(MIGRATING_DIR);
+    fs::create_dir_all(&staging).map_err(|err| {
+        format!(
+            "No se pudo preparar la migración del runtime {}: {err}",
+            major_root.display()
+        )
+    })?;
+    let entries = fs::read_dir(major_root)
+        .map_err(|err| format!("No se pudo leer runtime {}: {err}", major_root.display()))?
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_name() != MIGRATING_DIR)
+        .collect::<Vec<_>>();
+    for entry in entries {
+        let to = staging.join(entry.file_name());
+        fs::rename(entry.path(), &to).map_err(|err| {
+            format!(
+                "No se pudo mover {} a {} al migrar el runtime: {err}",
+                entry.path().display(),
+                to.display()
+            )
+        })?;
+    }
+    let target = major_root.join(DEFAULT_JAVA_BUILD);
+    fs::rename(&staging, &target).map_err(|err| {
+        format!(
+            "No se pudo renombrar {} a {}: {err}",
+            staging.display(),
+            target.display()
+        )
+    })?;
+    log::info!(
+        "✔ Runtime {} migrado a la build '{DEFAULT_JAVA_BUILD}'",
+        major_root.display()
+    );
+    Ok(())
+}
+
+fn read_runtime_version(build_root: &Path) -> String {
+    let Ok(raw) = fs::read_to_string(build_root.join("release")) else {
+        return String::new();
+    };
+    let value = |key: &str| {
+        raw.lines().find_map(|line| {
+            line.strip_prefix(key)
+                .and_then(|rest| rest.strip_prefix('='))
+                .map(|value| value.trim().trim_matches('"').to_string())
+        })
+    };
+    value("JAVA_RUNTIME_VERSION")
+        .or_else(|| value("JAVA_VERSION"))
+        .unwrap_or_default()
+}
+
+/// Números de una versión (`17.0.9+9` -> `[17, 0, 9, 9]`) para ordenar builds.
+fn version_key(version: &str) -> Vec<u64> {
+    version
+        .split(|ch: char| !ch.is_ascii_digit())
+        .filter(|part| !part.is_empty())
+        .filter_map(|part| part.parse().ok())
+        .collect()
+}
+
+fn build_name_for(build_root: &Path) -> String {
+    let version = read_runtime_version(build_root);
+    let sanitized = version
+        .chars()
+        .map(|ch| {
+            if ch.is_ascii_alphanumeric() || matches!(ch, '.' | '+' | '-' | '_') {
+                ch
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+    if sanitized.is_empty() {
+        DEFAULT_JAVA_BUILD.to_string()
+    } else {
+        sanitized
+    }
+}
+
+fn read_install_marker(build_root: &Path) -> Option<serde_json::Value> {
+    fs::read_to_string(build_root.join(".installed.json"))
+        .ok()
+        .and_then(|raw| serde_json::from_str::<serde_json::Value>(&raw).ok())
+        .filter(serde_json::Value::is_object)
+}
+
+fn build_source(build_root: &Path) -> String {
+    read_install_marker(build_root)
+        .and_then(|marker| marker.get("source")?.as_str().map(str::to_string))
+        .unwrap_or_else(|| "migrated".to_string())
+}
+
+/// Builds instaladas para el major, de la más reciente a la más antigua.
+pub fn list_java_builds(root: &Path, runtime: JavaRuntime) -> AppResult<Vec<InstalledJavaBuild>> {
+    let major_root = runtime_major_root(root, runtime);
+    migrate_flat_runtime(&major_root)?;
+    let Ok(entries) = fs::read_dir(&major_root) else {
+        return Ok(Vec::new());
+    };
+    let mut builds = entries
+        .filter_map(Result::ok)
+        .filter(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            !name.starts_with('.')
+                && !name.ends_with(WRONG_ARCH_SUFFIX)
+                && !name.ends_with(REPLACED_SUFFIX)
+        })
+        .filter_map(|entry| {
+            let build_root = entry.path();
+            let java_exec = java_executable_path(&build_root);
+            java_exec.is_file().then(|| InstalledJavaBuild {
+                name: entry.file_name().to_string_lossy().to_string(),
+                java_path: java_exec.display().to_string(),
+                runtime_version: read_runtime_version(&build_root),
+                source: build_source(&build_root),
+            })
+        })
+        .collect::<Vec<_>>();
+    builds.sort_by(|a, b| {
+        version_key(&b.runtime_version)
+            .cmp(&version_key(&a.runtime_version))
+            .then_with(|| b.name.cmp(&a.name))
+    });
+    Ok(builds)
+}
+
+/// Aparta una build compilada para otra arquitectura como `<build>.wrong-arch` (sustituye
+/// a un apartado anterior con el mismo nombre) para que no se vuelva a elegir.
+fn quarantine_wrong_arch(build_root: &Path) -> AppResult<PathBuf> {
+    let mut target = build_root.as_os_str().to_owned();
+    target.push(WRONG_ARCH_SUFFIX);
+    let target = PathBuf::from(target);
+    if target.exists() {
+        fs::remove_dir_all(&target).map_err(|err| {
+            format!(
+                "No se pudo limpiar el runtime apartado {}: {err}",
+                target.display()
+            )
+        })?;
+    }
+    fs::rename(build_root, &target).map_err(|err| {
+        format!(
+            "No se pudo apartar el runtime de otra arquitectura {}: {err}",
+            build_root.display()
+        )
+    })?;
+    Ok(target)
+}
+
+/// Estado de una build instalada antes de reutilizarla.
+#[derive(Debug, PartialEq, Eq)]
+enum BuildCheck {
+    Ready,
+    /// Compilada para estas arquitecturas y no para la del proceso.
+    WrongArch(Vec<&'static str>),
+    Broken,
+}
+
+/// Comprueba arquitectura, `java -version` y el marcador `.installed.json`.
+fn check_installed_build(
+    build_root: &Path,
+    java_exec: &Path,
+    runtime: JavaRuntime,
+    logs: &mut Vec<String>,
+) -> BuildCheck {
+    if let Some(arches) = foreign_executable_architectures(java_exec) {
+        return BuildCheck::WrongArch(arches);
+    }
+    if !cached_runtime_health(java_exec) {
+        return BuildCheck::Broken;
+    }
+    if read_install_marker(build_root).is_none() {
+        let marker = serde_json::json!({
+            "runtime": runtime.as_dir_name(),
+            "javaMajor": runtime.major(),
+            "build": build_root.file_name().map(|name| name.to_string_lossy().to_string()),
+            "source": "recovered",
+            "status": "installed"
+        });
+        let message = match fs::write(build_root.join(".installed.json"), marker.to_string()) {
+            Ok(()) => format!(
+                "⚠ Marcador .installed.json ausente o incompleto en {}; el runtime responde a java -version y se regeneró.",
+                build_root.display()
+            ),
+            Err(err) => format!(
+                "⚠ Marcador .installed.json ausente o incompleto en {} y no se pudo regenerar: {err}",
+                build_root.display()
+            ),
+        };
+        log::warn!("{message}");
+        logs.push(message);
+    }
+    BuildCheck::Ready
+}
+
+fn wrong_arch_message(
+    build_root: &Path,
+    quarantined: &Path,
+    runtime: JavaRuntime,
+    arches: &[&str],
+) -> String {
+    format!(
+        "⚠ El runtime de Java {} en {} está compilado para {} y este proceso es {}; se apartó en {}.",
+        runtime.major(),
+        build_root.display(),
+        arches.join("/"),
+        std::env::consts::ARCH,
+        quarantined.display()
+    )
+}
+
+/// Java de la instancia: la build fijada si la hay (con error claro si se borró) o la más
+/// reciente instalada del major, descargándola si no existe ninguna.
+pub fn ensure_java_build(
     root: &Path,
     runtime: JavaRuntime,
+    pin: Option<&str>,
     logs: &mut Vec<String>,
 ) -> AppResult<PathBuf> {
-    let arch = crate::platform::windows::detect_architecture()?;
-    logs.push(format!("Arquitectura detectada: {arch}."));
+    ensure_java_build_with(root, runtime, pin, logs, &|| Ok(()))
+}
 
-    let runtime_root = root.join("runtime").join(runtime.as_dir_name());
-    let java_exec = java_executable_path(&runtime_root);
-    if java_exec.exists() {
-        if !is_runtime_healthy(&java_exec) {
-            logs.push(format!(
-                "⚠ Runtime existente parece corrupto/no ejecutable: {}. Se reinstalará.",
-                java_exec.display()
+/// Igual que `ensure_java_build`; `cancel` se consulta antes y durante la descarga para que
+/// quien supervisa (el watchdog del lanzamiento) pueda detenerla.
+pub fn ensure_java_build_with(
+    root: &Path,
+    runtime: JavaRuntime,
+    pin: Option<&str>,
+    logs: &mut Vec<String>,
+    cancel: &dyn Fn() -> AppResult<()>,
+) -> AppResult<PathBuf> {
+    let Some(pin) = pin.map(str::trim).filter(|pin| !pin.is_empty()) else {
+        return ensure_embedded_java_with(root, runtime, logs, cancel);
+    };
+    let major_root = runtime_major_root(root, runtime);
+    migrate_flat_runtime(&major_root)?;
+    let java_exec = java_executable_path(&major_root.join(pin));
+    if !java_exec.is_file() {
+        return Err(format!(
+            "La build de Java fijada '{pin}' ya no está instalada en {}. Quita la fijación de la instancia para usar la build más reciente de Java {} o vuelve a instalarla.",
+            major_root.display(),
+            runtime.major()
+        ));
+    }
+    let build_root = major_root.join(pin);
+    match check_installed_build(&build_root, &java_exec, runtime, logs) {
+        BuildCheck::Ready => {}
+        BuildCheck::WrongArch(arches) => {
+            let quarantined = quarantine_wrong_arch(&build_root)?;
+            let message = wrong_arch_message(&build_root, &quarantined, runtime, &arches);
+            log::warn!("{message}");
+            return Err(format!(
+                "{message} Vuelve a instalar la build fijada '{pin}' o quita la fijación."
             ));
-            fs::remove_dir_all(&runtime_root).map_err(|err| {
-                format!(
-                    "No se pudo limpiar runtime posiblemente corrupto {}: {err}",
-                    runtime_root.display()
-                )
-            })?;
-            fs::create_dir_all(&runtime_root).map_err(|err| {
-                format!(
-                    "No se pudo recrear directorio runtime {}: {err}",
-                    runtime_root.display()
-                )
-            })?;
-        } else {
-            logs.push(format!(
-                "Java {} ya instalado: {}",
-                runtime.major(),
+        }
+        BuildCheck::Broken => {
+            return Err(format!(
+                "La build de Java fijada '{pin}' no se puede ejecutar ({}). Reinstálala o quita la fijación.",
                 java_exec.display()
             ));
-            return Ok(java_exec);
         }
     }
+    logs.push(format!(
+        "Java {} (build fijada '{pin}'): {}",
+        runtime.major(),
+        java_exec.display()
+    ));
+    Ok(java_exec)
+}
+
+pub fn ensure_embedded_java(
+    root: &Path,
+    runtime: JavaRuntime,
+    logs: &mut Vec<String>,
+) -> AppResult<PathBuf> {
+    ensure_embedded_java_with(root, runtime, logs, &|| Ok(()))
+}
+
+fn ensure_embedded_java_with(
+    root: &Path,
+    runtime: JavaRuntime,
+    logs: &mut Vec<String>,
+    cancel: &dyn Fn() -> AppResult<()>,
+) -> AppResult<PathBuf> {
+    let arch = crate::platform::windows::detect_architecture()?;
+    logs.push(format!("Arquitectura detectada: {arch}."));
 
-    if !runtime_root.exists() {
-        fs::create_dir_all(&runtime_root).map_err(|err| {
+    let major_root = runtime_major_root(root, runtime);
+    for build in list_java_builds(root, runtime)? {
+        let java_exec = PathBuf::from(&build.java_path);
+        let build_root = major_root.join(&build.name);
+        match check_installed_build(&build_root, &java_exec, runtime, logs) {
+            BuildCheck::Ready => {
+                logs.push(format!(
+                    "Java {} ya instalado (build '{}'): {}",
+                    runtime.major(),
+                    build.name,
+                    java_exec.display()
+                ));
+                return Ok(java_exec);
+            }
+            BuildCheck::WrongArch(arches) => {
+                let quarantined = quarantine_wrong_arch(&build_root)?;
+                let message = format!(
+                    "{} Se instalará la build para {}.",
+                    wrong_arch_message(&build_root, &quarantined, runtime, &arches),
+                    std::env::consts::ARCH
+                );
+                log::warn!("{message}");
+                logs.push(message);
+                continue;
+            }
+            BuildCheck::Broken => {}
+        }
+        logs.push(format!(
+            "⚠ Runtime existente parece corrupto/no ejecutable: {}. Se reinstalará.",
+            java_exec.display()
+        ));
+        fs::remove_dir_all(&build_root).map_err(|err| {
             format!(
-                "Error creando directorio runtime {}: {err}",
-                runtime_root.display()
+                "No se pudo limpiar runtime posiblemente corrupto {}: {err}",
+                build_root.display()
             )
         })?;
     }
+
     logs.push(format!(
         "Java {} no encontrado. Iniciando descarga de runtime embebido oficial (Temurin).",
         runtime.major()
     ));
+    cancel()?;
+    let client = build_http_client()?;
+    let asset = resolve_temurin_asset(&client, runtime, &root.join("cache"), logs)?;
+    download_and_install_build(&client, &major_root, runtime, asset, None, logs, cancel)
+}
 
+/// Instala una release concreta de Temurin (`jdk-17.0.9+9`) como build adicional.
+pub fn install_specific_java_build(
+    root: &Path,
+    runtime: JavaRuntime,
+    release_name: &str,
+    logs: &mut Vec<String>,
+) -> AppResult<PathBuf> {
+    let release_name = release_name.trim();
+    if release_name.is_empty() {
+        return Err("Indica el nombre de la release (p. ej. jdk-17.0.9+9).".to_string());
+    }
+    let wanted = version_key(release_name);
+    if let Some(build) = list_java_builds(root, runtime)?
+        .into_iter()
+        .find(|build| build.name == release_name || version_key(&build.runtime_version) == wanted)
+    {
+        logs.push(format!(
+            "La build {release_name} ya está instalada como '{}'.",
+            build.name
+        ));
+        return Ok(PathBuf::from(build.java_path));
+    }
     let client = build_http_client()?;
-    let (download_url, expected_checksum, file_name, selected_image_type) =
-        resolve_temurin_asset(&client, runtime)?;
+    let asset = resolve_temurin_release_asset(&client, runtime, release_name)?;
+    download_and_install_build(
+        &client,
+        &runtime_major_root(root, runtime),
+        runtime,
+        asset,
+        Some(release_name),
+        logs,
+        &|| Ok(()),
+    )
+}
 
+fn download_and_install_build(
+    client: &reqwest::blocking::Client,
+    major_root: &Path,
+    runtime: JavaRuntime,
+    asset: (String, String, String, String),
+    release_name: Option<&str>,
+    logs: &mut Vec<String>,
+    cancel: &dyn Fn() -> AppResult<()>,
+) -> AppResult<PathBuf> {
+    let (download_url, expected_checksum, file_name, selected_image_type) = asset;
     if selected_image_type == "jdk" {
         logs.push(
             "⚠ No se encontró binario JRE para esta arquitectura/runtime. Se aplicó fallback a JDK."
@@ -80,14 +454,34 @@ pub fn ensure_embedded_java(
     }
 
     logs.push(format!("Descargando: {download_url}"));
-    let archive_bytes = client
+    let mut response = client
         .get(&download_url)
         .send()
         .and_then(|resp| resp.error_for_status())
-        .map_err(|err| format!("Fallo la descarga del JDK: {err}"))?
-        .bytes()
-        .map_err(|err| format!("No se pudo leer el binario descargado: {err}"))?
-        .to_vec();
+        .map_err(|err| format!("Fallo la descarga del JDK: {err}"))?;
+    let total = response.content_length();
+    if let Some(package_size) = total {
+        // El paquete se descarga en memoria; en disco ocupa lo extraído (~2,5× el paquete).
+        preflight_disk_space(major_root, package_size.saturating_mul(5) / 2)?;
+    }
+    let task = current_task();
+    let mut archive_bytes = Vec::with_capacity(total.unwrap_or(0) as usize);
+    let mut chunk = vec![0u8; 256 * 1024];
+    loop {
+        // Punto seguro: el archivo solo está en memoria; cancelar no deja nada en disco.
+        if let Some(task) = &task {
+            task.check_cancelled()?;
+            task.progress(archive_bytes.len() as u64, total, "bytes");
+        }
+        cancel()?;
+        let read = response
+            .read(&mut chunk)
+            .map_err(|err| format!("No se pudo leer el binario descargado: {err}"))?;
+        if read == 0 {
+            break;
+        }
+        archive_bytes.extend_from_slice(&chunk[..read]);
+    }
 
     let archive_sha = sha256_hex(&archive_bytes);
     validate_checksum(&expected_checksum, &archive_sha, runtime.major())?;
@@ -98,58 +492,265 @@ pub fn ensure_embedded_java(
     ));
     logs.push(format!("Hash SHA-256 runtime descargado: {archive_sha}"));
 
-    extract_archive(&archive_bytes, &file_name, &runtime_root)?;
+    let java_exec = install_build_from_bytes(
+        major_root,
+        runtime,
+        &archive_bytes,
+        &file_name,
+        serde_json::json!({
+            "runtime": runtime.as_dir_name(),
+            "javaMajor": runtime.major(),
+            "releaseName": release_name,
+            "downloadUrl": download_url,
+            "checksum": expected_checksum,
+            "downloadedSha256": archive_sha,
+            "archive": file_name,
+            "imageType": selected_image_type,
+            "source": "adoptium",
+            "status": "installed"
+        }),
+        &|_| Ok(()),
+    )?;
+    logs.push(format!(
+        "Java {} instalado y marcado como listo en {}.",
+        runtime.major(),
+        java_exec.display()
+    ));
+    Ok(java_exec)
+}
+
+/// Extrae el archivo en una carpeta temporal y, si `verify` acepta su ejecutable, la publica
+/// como `runtime/javaNN/<JAVA_RUNTIME_VERSION>/`, sustituyendo la build con el mismo nombre.
+fn install_build_from_bytes(
+    major_root: &Path,
+    runtime: JavaRuntime,
+    archive_bytes: &[u8],
+    file_name: &str,
+    mut marker: serde_json::Value,
+    verify: &dyn Fn(&Path) -> AppResult<()>,
+) -> AppResult<PathBuf> {
+    migrate_flat_runtime(major_root)?;
+    let staging = major_root.join(INSTALLING_DIR);
+    if staging.exists() {
+        fs::remove_dir_all(&staging).map_err(|err| {
+            format!(
+                "No se pudo limpiar instalación interrumpida {}: {err}",
+                staging.display()
+            )
+        })?;
+    }
+    fs::create_dir_all(&staging).map_err(|err| {
+        format!(
+            "Error creando directorio runtime {}: {err}",
+            staging.display()
+        )
+    })?;
+    if !cfg!(target_os = "windows") {
+        let capabilities = probe_filesystem_capabilities(&staging);
+        if !capabilities.supports_exec_bit {
+            let _ = fs::remove_dir_all(&staging);
+            return Err(format!(
+                "No se puede instalar Java en {}: el sistema de archivos ({}) no admite permisos de ejecución. Mueve la carpeta del launcher a un disco local (ext4, APFS...) desde la configuración de rutas.",
+                major_root.display(),
+                capabilities.filesystem
+            ));
+        }
+    }
 
-    if !java_exec.exists() {
+    if let Err(err) = extract_archive(archive_bytes, file_name, &staging) {
+        let _ = fs::remove_dir_all(&staging);
+        return Err(err);
+    }
+    let staged_java = java_executable_path(&staging);
+    if !staged_java.exists() {
+        let _ = fs::remove_dir_all(&staging);
         return Err(format!(
-            "Se extrajo el runtime de Java {}, pero no se encontró ejecutable en {}",
-            runtime.major(),
-            java_exec.display()
+            "Se extrajo el runtime de Java {} ({file_name}), pero no se encontró ejecutable.",
+            runtime.major()
         ));
     }
+    if let Err(err) = verify(&staged_java) {
+        let _ = fs::remove_dir_all(&staging);
+        return Err(err);
+    }
 
-    let marker = runtime_root.join(".installed.json");
-    if marker.exists() {
-        logs.push(format!(
-            "⚠ Metadata existente preservada en {} (no sobrescrita automáticamente).",
-            marker.display()
+    let build_name = build_name_for(&staging);
+    marker["build"] = serde_json::Value::String(build_name.clone());
+    fs::write(staging.join(".installed.json"), marker.to_string())
+        .map_err(|err| format!("Error escribiendo marcador de instalación: {err}"))?;
+
+    let build_root = major_root.join(&build_name);
+    publish_staged_build(&staging, &build_root)?;
+    Ok(java_executable_path(&build_root))
+}
+
+/// Pone `staging` en el lugar de `build_root`. La build anterior se aparta con un rename y
+/// solo se borra cuando la nueva ya está publicada; si algo falla, se restaura.
+fn publish_staged_build(staging: &Path, build_root: &Path) -> AppResult<()> {
+    let mut replaced = build_root.as_os_str().to_owned();
+    replaced.push(REPLACED_SUFFIX);
+    let replaced = PathBuf::from(replaced);
+    if replaced.exists() {
+        fs::remove_dir_all(&replaced).map_err(|err| {
+            format!(
+                "No se pudo limpiar la build sustituida {}: {err}",
+                replaced.display()
+            )
+        })?;
+    }
+    let had_previous = build_root.exists();
+    if had_previous {
+        fs::rename(build_root, &replaced).map_err(|err| {
+            format!(
+                "No se pudo apartar la build existente {}: {err}",
+                build_root.display()
+            )
+        })?;
+    }
+    if let Err(err) = fs::rename(staging, build_root) {
+        if had_previous {
+            let _ = fs::rename(&replaced, build_root);
+        }
+        return Err(format!(
+            "No se pudo publicar la build {}: {err}",
+            build_root.display()
         ));
-        return Ok(java_exec);
     }
+    if had_previous {
+        if let Err(err) = fs::remove_dir_all(&replaced) {
+            log::warn!(
+                "⚠ No se pudo borrar la build sustituida {}: {err}",
+                replaced.display()
+            );
+        }
+    }
+    Ok(())
+}
 
-    fs::write(
-        &marker,
+/// Instala un runtime a partir de un archivo local (zip/tar.gz) sin consultar Adoptium.
+/// Se añade como una build más del major.
+pub fn install_java_from_archive(
+    root: &Path,
+    runtime: JavaRuntime,
+    archive_path: &Path,
+    expected_sha256: Option<&str>,
+    logs: &mut Vec<String>,
+) -> AppResult<PathBuf> {
+    let file_name = archive_path
+        .file_name()
+        .and_then(OsStr::to_str)
+        .ok_or_else(|| format!("Ruta de archivo inválida: {}", archive_path.display()))?
+        .to_string();
+    let archive_bytes = fs::read(archive_path).map_err(|err| {
+        format!(
+            "No se pudo leer el archivo de runtime {}: {err}",
+            archive_path.display()
+        )
+    })?;
+
+    let major_root = runtime_major_root(root, runtime);
+    preflight_disk_space(
+        &major_root,
+        (archive_bytes.len() as u64).saturating_mul(5) / 2,
+    )?;
+
+    let archive_sha = sha256_hex(&archive_bytes);
+    match expected_sha256.map(str::trim).filter(|value| !value.is_empty()) {
+        Some(expected) => {
+            validate_checksum(expected, &archive_sha, runtime.major())?;
+            logs.push(format!(
+                "Checksum SHA-256 validado para Java {}.",
+                runtime.major()
+            ));
+        }
+        None => logs.push(format!(
+            "⚠ No se indicó checksum para {file_name}; no se pudo verificar la integridad (SHA-256: {archive_sha})."
+        )),
+    }
+
+    let java_exec = install_build_from_bytes(
+        &major_root,
+        runtime,
+        &archive_bytes,
+        &file_name,
         serde_json::json!({
             "runtime": runtime.as_dir_name(),
             "javaMajor": runtime.major(),
-            "downloadUrl": download_url,
-            "checksum": expected_checksum,
+            "archivePath": archive_path.display().to_string(),
+            "checksum": expected_sha256.unwrap_or_default(),
             "downloadedSha256": archive_sha,
             "archive": file_name,
-            "imageType": selected_image_type,
+            "source": "local",
             "status": "installed"
-        })
-        .to_string(),
-    )
-    .map_err(|err| format!("Error escribiendo marcador de instalación: {err}"))?;
+        }),
+        &|java_exec| {
+            if let Some(arches) = foreign_executable_architectures(java_exec) {
+                return Err(format!(
+                    "El runtime de {file_name} está compilado para {} y este proceso es {}; no se instaló.",
+                    arches.join("/"),
+                    std::env::consts::ARCH
+                ));
+            }
+            if !is_runtime_healthy(java_exec) {
+                return Err(format!(
+                    "El runtime extraído de {file_name} no se pudo ejecutar ({}); no se instaló.",
+                    java_exec.display()
+                ));
+            }
+            Ok(())
+        },
+    )?;
 
     logs.push(format!(
-        "Java {} instalado y marcado como listo en {}.",
+        "Java {} instalado desde archivo local en {}.",
         runtime.major(),
-        marker.display()
+        java_exec.display()
     ));
 
     Ok(java_exec)
 }
 
+/// Último resultado de `java -version` por ejecutable, válido mientras no cambie su fecha.
+type RuntimeHealthCache = HashMap<PathBuf, (Option<SystemTime>, bool)>;
+static RUNTIME_HEALTH: OnceLock<Mutex<RuntimeHealthCache>> = OnceLock::new();
+
+fn executable_modified(java_exec: &Path) -> Option<SystemTime> {
+    fs::metadata(java_exec)
+        .and_then(|meta| meta.modified())
+        .ok()
+}
+
 fn is_runtime_healthy(java_exec: &Path) -> bool {
-    Command::new(java_exec)
+    let healthy = Command::new(java_exec)
         .arg("-version")
         .stdout(std::process::Stdio::null())
         .stderr(std::process::Stdio::null())
         .status()
         .map(|status| status.success())
-        .unwrap_or(false)
+        .unwrap_or(false);
+    if let Ok(mut cache) = RUNTIME_HEALTH.get_or_init(Default::default).lock() {
+        cache.insert(
+            java_exec.to_path_buf(),
+            (executable_modified(java_exec), healthy),
+        );
+    }
+    healthy
+}
+
+/// Como `is_runtime_healthy`, pero reutiliza la última comprobación si el ejecutable no
+/// ha cambiado; pensado para diagnósticos que recorren todos los runtimes.
+pub fn cached_runtime_health(java_exec: &Path) -> bool {
+    let modified = executable_modified(java_exec);
+    let cached = RUNTIME_HEALTH
+        .get_or_init(Default::default)
+        .lock()
+        .ok()
+        .and_then(|cache| cache.get(java_exec).copied())
+        .filter(|(cached_modified, _)| *cached_modified == modified);
+    match cached {
+        Some((_, healthy)) => healthy,
+        None => is_runtime_healthy(java_exec),
+    }
 }
 
 fn extract_archive(archive: &[u8], file_name: &str, destination: &Path) -> AppResult<()> {
@@ -262,3 +863,162 @@ fn flatten_single_top_level_dir(destination: &Path) -> AppResult<()> {
     })?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_root(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!(
+            "interface-java-builds-{name}-{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&root);
+        root
+    }
+
+    fn fake_runtime(build_root: &Path, version: &str) {
+        let java = java_executable_path(build_root);
+        fs::create_dir_all(java.parent().expect("bin")).expect("bin");
+        fs::write(&java, b"").expect("java");
+        fs::write(
+            build_root.join("release"),
+            format!("JAVA_VERSION=\"17\"\nJAVA_RUNTIME_VERSION=\"{version}\"\n"),
+        )
+        .expect("release");
+    }
+
+    #[test]
+    fn flat_runtime_becomes_default_build() {
+        let root = temp_root("migrate");
+        let major_root = runtime_major_root(&root, JavaRuntime::Java17);
+        fake_runtime(&major_root, "17.0.8+7");
+
+        let builds = list_java_builds(&root, JavaRuntime::Java17).expect("builds");
+        assert_eq!(builds.len(), 1);
+        assert_eq!(builds[0].name, DEFAULT_JAVA_BUILD);
+        assert_eq!(builds[0].runtime_version, "17.0.8+7");
+        assert!(!java_executable_path(&major_root).exists());
+        assert!(java_executable_path(&major_root.join(DEFAULT_JAVA_BUILD)).exists());
+
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn builds_sort_newest_first_and_missing_pin_errors() {
+        let root = temp_root("sort");
+        let major_root = runtime_major_root(&root, JavaRuntime::Java17);
+        fake_runtime(&major_root.join("17.0.8+7"), "17.0.8+7");
+        fake_runtime(&major_root.join("17.0.10+7"), "17.0.10+7");
+        fs::create_dir_all(major_root.join(INSTALLING_DIR)).expect("staging");
+
+        let names = list_java_builds(&root, JavaRuntime::Java17)
+            .expect("builds")
+            .into_iter()
+            .map(|build| build.name)
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["17.0.10+7", "17.0.8+7"]);
+
+        let err = ensure_java_build(
+            &root,
+            JavaRuntime::Java17,
+            Some("17.0.1+12"),
+            &mut Vec::new(),
+        )
+        .expect_err("build fijada ausente");
+        assert!(err.contains("ya no está instalada"));
+
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn foreign_arch_build_is_set_aside_and_not_listed() {
+        let root = temp_root("wrong-arch");
+        let build_root = runtime_major_root(&root, JavaRuntime::Java17).join("17.0.8+7");
+        fake_runtime(&build_root, "17.0.8+7");
+        let foreign_machine: u16 = if std::env::consts::ARCH == "aarch64" {
+            0x3E
+        } else {
+            0xB7
+        };
+        let mut elf = vec![0x7F, b'E', b'L', b'F', 2, 1, 1, 0];
+        elf.resize(18, 0);
+        elf.extend_from_slice(&foreign_machine.to_le_bytes());
+        let java = java_executable_path(&build_root);
+        fs::write(&java, elf).expect("elf");
+
+        let check = check_installed_build(&build_root, &java, JavaRuntime::Java17, &mut Vec::new());
+        assert!(matches!(check, BuildCheck::WrongArch(_)));
+        let quarantined = quarantine_wrong_arch(&build_root).expect("quarantine");
+        assert!(quarantined.ends_with("17.0.8+7.wrong-arch"));
+        assert!(!build_root.exists());
+        assert!(list_java_builds(&root, JavaRuntime::Java17)
+            .expect("builds")
+            .is_empty());
+
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[cfg(unix)]
+    fn runtime_archive(version: &str, exit_code: i32) -> Vec<u8> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let source = temp_root(&format!("archive-{exit_code}"));
+        let build_root = source.join("jdk");
+        fake_runtime(&build_root, version);
+        let java = java_executable_path(&build_root);
+        fs::write(&java, format!("#!/bin/sh\nexit {exit_code}\n")).expect("java");
+        fs::set_permissions(&java, fs::Permissions::from_mode(0o755)).expect("chmod");
+
+        let mut tar = tar::Builder::new(flate2::write::GzEncoder::new(
+            Vec::new(),
+            flate2::Compression::fast(),
+        ));
+        tar.append_dir_all("jdk", &build_root).expect("tar");
+        let bytes = tar.into_inner().and_then(|gz| gz.finish()).expect("gz");
+        let _ = fs::remove_dir_all(source);
+        bytes
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn local_archive_replaces_a_build_only_once_the_new_one_works() {
+        let root = temp_root("local-archive");
+        let build_root = runtime_major_root(&root, JavaRuntime::Java17).join("17.0.8+7");
+        fake_runtime(&build_root, "17.0.8+7");
+        fs::write(build_root.join("previous"), b"").expect("previous");
+        fs::create_dir_all(&root).expect("root");
+
+        let broken = root.join("broken.tar.gz");
+        fs::write(&broken, runtime_archive("17.0.8+7", 1)).expect("broken");
+        let err =
+            install_java_from_archive(&root, JavaRuntime::Java17, &broken, None, &mut Vec::new())
+                .expect_err("runtime roto");
+        assert!(err.contains("no se instaló"));
+        assert!(build_root.join("previous").exists());
+        assert!(!build_root.with_file_name(INSTALLING_DIR).exists());
+
+        let working = root.join("working.tar.gz");
+        let bytes = runtime_archive("17.0.8+7", 0);
+        fs::write(&working, &bytes).expect("working");
+        let java = install_java_from_archive(
+            &root,
+            JavaRuntime::Java17,
+            &working,
+            Some(&sha256_hex(&bytes)),
+            &mut Vec::new(),
+        )
+        .expect("install");
+        assert_eq!(java, java_executable_path(&build_root));
+        assert!(!build_root.join("previous").exists());
+        assert_eq!(build_source(&build_root), "local");
+        let names = list_java_builds(&root, JavaRuntime::Java17)
+            .expect("builds")
+            .into_iter()
+            .map(|build| build.name)
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["17.0.8+7"]);
+
+        let _ = fs::remove_dir_all(root);
+    }
+}
@@ -0,0 +1,271 @@
+// Manifiesto de archivos generados por el instalador de Forge/NeoForge.
+
+use std::{
+    collections::{BTreeSet, HashMap},
+    fs,
+    path::{Path, PathBuf},
+    time::UNIX_EPOCH,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    infrastructure::filesystem::file_ops::write_file_replacing, shared::result::AppResult,
+};
+
+/// Archivo del manifiesto en la carpeta de la instancia, junto a `.instance.json`.
+pub const LOADER_FILES_MANIFEST: &str = ".loader-files.json";
+/// Carpetas (relativas a `minecraft/`) donde escriben los instaladores.
+const WATCHED_DIRS: [&str; 3] = [
+    "versions",
+    "libraries/net/minecraftforge",
+    "libraries/net/neoforged",
+];
+/// Carpetas de librerías con una subcarpeta por build del loader.
+const LOADER_LIBRARY_DIRS: [&str; 3] = [
+    "libraries/net/minecraftforge/forge",
+    "libraries/net/neoforged/neoforge",
+    "libraries/net/neoforged/forge",
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct LoaderFilesManifest {
+    pub loader: String,
+    pub loader_version: String,
+    pub version_id: String,
+    /// Rutas relativas a `minecraft/`, con `/`, creadas o modificadas por la instalación.
+    pub files: Vec<String>,
+}
+
+impl LoaderFilesManifest {
+    pub fn contains(&self, relative: &str) -> bool {
+        self.files
+            .binary_search_by(|file| file.as_str().cmp(relative))
+            .is_ok()
+    }
+
+    /// Es del loader indicado; un `loader_version` vacío acepta cualquier build.
+    pub fn matches(&self, loader: &str, loader_version: &str) -> bool {
+        let loader_version = loader_version.trim();
+        self.loader.eq_ignore_ascii_case(loader.trim())
+            && (loader_version.is_empty()
+                || self.loader_version.eq_ignore_ascii_case(loader_version))
+    }
+}
+
+/// Tamaño y fecha de modificación de cada archivo de las carpetas vigiladas.
+pub type LoaderFilesSnapshot = HashMap<String, (u64, u128)>;
+
+fn relative_path(minecraft_root: &Path, path: &Path) -> String {
+    path.strip_prefix(minecraft_root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+fn collect_files(minecraft_root: &Path, dir: &Path, snapshot: &mut LoaderFilesSnapshot) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(meta) = fs::symlink_metadata(&path) else {
+            continue;
+        };
+        if meta.is_dir() {
+            collect_files(minecraft_root, &path, snapshot);
+        } else if meta.is_file() {
+            let modified = meta
+                .modified()
+                .ok()
+                .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                .map(|elapsed| elapsed.as_millis())
+                .unwrap_or_default();
+            snapshot.insert(relative_path(minecraft_root, &path), (meta.len(), modified));
+        }
+    }
+}
+
+/// Foto de las carpetas vigiladas; `minecraft/libraries` puede ser un enlace a las
+/// librerías compartidas y se sigue.
+pub fn snapshot_loader_files(minecraft_root: &Path) -> LoaderFilesSnapshot {
+    let mut snapshot = LoaderFilesSnapshot::new();
+    for dir in WATCHED_DIRS {
+        collect_files(minecraft_root, &minecraft_root.join(dir), &mut snapshot);
+    }
+    snapshot
+}
+
+pub fn manifest_path(minecraft_root: &Path) -> Option<PathBuf> {
+    minecraft_root
+        .parent()
+        .map(|instance_root| instance_root.join(LOADER_FILES_MANIFEST))
+}
+
+pub fn read_loader_files_manifest(minecraft_root: &Path) -> Option<LoaderFilesManifest> {
+    let raw = fs::read_to_string(manifest_path(minecraft_root)?).ok()?;
+    serde_json::from_str(&raw).ok()
+}
+
+/// Guarda lo que cambió respecto a `before` más todo `versions/<version_id>/` (si el
+/// build ya estaba instalado no cambia nada, pero sigue siendo el activo).
+pub fn record_loader_files(
+    minecraft_root: &Path,
+    loader: &str,
+    loader_version: &str,
+    version_id: &str,
+    before: &LoaderFilesSnapshot,
+) -> AppResult<LoaderFilesManifest> {
+    let version_prefix = format!("versions/{version_id}/");
+    let files = snapshot_loader_files(minecraft_root)
+        .into_iter()
+        .filter(|(path, stamp)| {
+            path.starts_with(&version_prefix) || before.get(path) != Some(stamp)
+        })
+        .map(|(path, _)| path)
+        .collect::<BTreeSet<_>>();
+    let manifest = LoaderFilesManifest {
+        loader: loader.trim().to_ascii_lowercase(),
+        loader_version: loader_version.trim().to_string(),
+        version_id: version_id.to_string(),
+        files: files.into_iter().collect(),
+    };
+    let path = manifest_path(minecraft_root).ok_or_else(|| {
+        format!(
+            "No se pudo resolver la carpeta de instancia desde {}",
+            minecraft_root.display()
+        )
+    })?;
+    let raw = serde_json::to_vec_pretty(&manifest)
+        .map_err(|err| format!("No se pudo serializar {LOADER_FILES_MANIFEST}: {err}"))?;
+    write_file_replacing(&path, &raw, true)?;
+    Ok(manifest)
+}
+
+fn is_loader_profile(version_dir: &Path, id: &str) -> bool {
+    fs::read_to_string(version_dir.join(format!("{id}.json")))
+        .ok()
+        .and_then(|raw| serde_json::from_str::<serde_json::Value>(&raw).ok())
+        .is_some_and(|json| json.get("inheritsFrom").is_some())
+}
+
+/// Restos de instalaciones anteriores del loader; las librerías compartidas no se tocan.
+pub fn stale_loader_paths(minecraft_root: &Path, manifest: &LoaderFilesManifest) -> Vec<PathBuf> {
+    let mut stale = Vec::new();
+    if let Ok(entries) = fs::read_dir(minecraft_root.join("versions")) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let id = entry.file_name().to_string_lossy().to_string();
+            if path.is_dir() && id != manifest.version_id && is_loader_profile(&path, &id) {
+                stale.push(path);
+            }
+        }
+    }
+
+    let libraries = minecraft_root.join("libraries");
+    let shared = !fs::symlink_metadata(&libraries).is_ok_and(|meta| !meta.file_type().is_symlink());
+    if !shared {
+        for dir in LOADER_LIBRARY_DIRS {
+            let Ok(entries) = fs::read_dir(minecraft_root.join(dir)) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let path = entry.path();
+                let build = entry.file_name().to_string_lossy().to_string();
+                let prefix = format!("{}/", relative_path(minecraft_root, &path));
+                let in_use = build.contains(&manifest.loader_version)
+                    || manifest.files.iter().any(|file| file.starts_with(&prefix));
+                if path.is_dir() && !in_use {
+                    stale.push(path);
+                }
+            }
+        }
+    }
+    stale.sort();
+    stale
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn install(minecraft_root: &Path, version_id: &str, build: &str) -> LoaderFilesManifest {
+        let before = snapshot_loader_files(minecraft_root);
+        let version_dir = minecraft_root.join("versions").join(version_id);
+        fs::create_dir_all(&version_dir).expect("version");
+        fs::write(
+            version_dir.join(format!("{version_id}.json")),
+            r#"{"inheritsFrom":"1.20.1"}"#,
+        )
+        .expect("json");
+        fs::write(version_dir.join("unix_args.txt"), build).expect("args");
+        let library = minecraft_root
+            .join("libraries/net/minecraftforge/forge")
+            .join(format!("1.20.1-{build}"));
+        fs::create_dir_all(&library).expect("library");
+        fs::write(library.join(format!("forge-1.20.1-{build}.jar")), build).expect("jar");
+        record_loader_files(minecraft_root, "Forge", build, version_id, &before).expect("record")
+    }
+
+    #[test]
+    fn manifest_lists_only_the_files_of_the_latest_install() {
+        let instance = std::env::temp_dir().join(format!(
+            "interface-loader-files-record-{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&instance);
+        let minecraft_root = instance.join("minecraft");
+        fs::create_dir_all(minecraft_root.join("versions/1.20.1")).expect("vanilla");
+
+        install(&minecraft_root, "1.20.1-forge-47.2.0", "47.2.0");
+        let second = install(&minecraft_root, "1.20.1-forge-47.1.0", "47.1.0");
+
+        assert_eq!(
+            read_loader_files_manifest(&minecraft_root),
+            Some(second.clone())
+        );
+        assert!(second.matches("forge", ""));
+        assert!(!second.matches("forge", "47.2.0"));
+        assert!(second.contains("versions/1.20.1-forge-47.1.0/unix_args.txt"));
+        assert!(second
+            .contains("libraries/net/minecraftforge/forge/1.20.1-47.1.0/forge-1.20.1-47.1.0.jar"));
+        assert!(!second.contains("versions/1.20.1-forge-47.2.0/unix_args.txt"));
+
+        // Reinstalar un build que ya estaba no cambia archivos, pero vuelve a ser el activo.
+        let again = install(&minecraft_root, "1.20.1-forge-47.2.0", "47.2.0");
+        assert!(again.contains("versions/1.20.1-forge-47.2.0/unix_args.txt"));
+        assert!(!again.contains("versions/1.20.1-forge-47.1.0/unix_args.txt"));
+
+        let _ = fs::remove_dir_all(instance);
+    }
+
+    #[test]
+    fn stale_paths_keep_active_build_and_vanilla() {
+        let instance = std::env::temp_dir().join(format!(
+            "interface-loader-files-stale-{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&instance);
+        let minecraft_root = instance.join("minecraft");
+        fs::create_dir_all(minecraft_root.join("versions/1.20.1")).expect("vanilla");
+        fs::write(minecraft_root.join("versions/1.20.1/1.20.1.json"), "{}").expect("json");
+
+        install(&minecraft_root, "1.20.1-forge-47.1.0", "47.1.0");
+        let active = install(&minecraft_root, "1.20.1-forge-47.2.0", "47.2.0");
+
+        let stale = stale_loader_paths(&minecraft_root, &active)
+            .into_iter()
+            .map(|path| relative_path(&minecraft_root, &path))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            stale,
+            vec![
+                "libraries/net/minecraftforge/forge/1.20.1-47.1.0",
+                "versions/1.20.1-forge-47.1.0",
+            ]
+        );
+
+        let _ = fs::remove_dir_all(instance);
+    }
+}
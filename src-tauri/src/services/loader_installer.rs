@@ -18,6 +18,8 @@ use crate::domain::loaders::{
     neoforge::installer::{ensure_neoforge_java, neoforge_installer_args},
     quilt::installer::quilt_profile_url,
 };
+use crate::infrastructure::http::tls::with_custom_roots;
+use crate::services::loader_files::{record_loader_files, snapshot_loader_files};
 use crate::shared::result::AppResult;
 
 pub fn install_loader_if_needed(
@@ -41,7 +43,7 @@ pub fn install_loader_if_needed(
         ));
     }
 
-    let client = Client::builder()
+    let client = with_custom_roots(Client::builder())
         .timeout(std::time::Duration::from_secs(300))
         .connect_timeout(std::time::Duration::from_secs(60))
         .user_agent("InterfaceLauncher/0.2")
@@ -53,7 +55,12 @@ pub fn install_loader_if_needed(
         java_exec.display()
     ));
 
-    match normalized_loader.as_str() {
+    // Forge/NeoForge dejan carpetas y librerías por build; se registra qué produjo esta
+    // instalación para no mezclarla con restos de otra.
+    let installer_files = matches!(normalized_loader.as_str(), "forge" | "neoforge")
+        .then(|| snapshot_loader_files(minecraft_root));
+
+    let version_id = match normalized_loader.as_str() {
         "fabric" => install_fabric_like(
             &client,
             minecraft_root,
@@ -113,7 +120,26 @@ pub fn install_loader_if_needed(
             logs,
         ),
         _ => Err(format!("Loader no soportado todavía: {loader}")),
+    }?;
+
+    if let Some(before) = installer_files {
+        match record_loader_files(
+            minecraft_root,
+            &normalized_loader,
+            loader_version,
+            &version_id,
+            &before,
+        ) {
+            Ok(manifest) => logs.push(format!(
+                "Manifiesto del loader guardado: {} archivos de {version_id}.",
+                manifest.files.len()
+            )),
+            Err(err) => logs.push(format!(
+                "⚠ No se pudo guardar el manifiesto de archivos del loader: {err}"
+            )),
+        }
     }
+    Ok(version_id)
 }
 
 fn expected_main_class_for_loader(loader: &str) -> Option<&'static str> {
@@ -401,7 +427,7 @@ fn download_neoforge_installer(
         return Ok(target);
     }
 
-    let client = Client::builder()
+    let client = with_custom_roots(Client::builder())
         .timeout(std::time::Duration::from_secs(300))
         .connect_timeout(std::time::Duration::from_secs(60))
         .user_agent("InterfaceLauncher/0.2")
@@ -2,5 +2,6 @@ pub mod discord_presence;
 pub mod game_launcher;
 pub mod instance_builder;
 pub mod java_installer;
+pub mod loader_files;
 pub mod loader_installer;
 pub mod minecraft_downloader;
@@ -0,0 +1,115 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use tauri::{AppHandle, Manager};
+
+/// Fuente de la hora actual. Las funciones con lógica dependiente del tiempo (caducidad de
+/// caché, tiempo de juego, last_used) la reciben en lugar de llamar a `Utc::now()`.
+pub trait Clock: Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+
+    fn now_rfc3339(&self) -> String {
+        self.now().to_rfc3339()
+    }
+
+    fn now_millis(&self) -> u64 {
+        self.now().timestamp_millis().max(0) as u64
+    }
+}
+
+/// Generador de identificadores (`internal_uuid`, nombres únicos).
+pub trait IdGenerator: Send + Sync {
+    fn new_id(&self) -> String;
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct UuidV4Generator;
+
+impl IdGenerator for UuidV4Generator {
+    fn new_id(&self) -> String {
+        uuid::Uuid::new_v4().to_string()
+    }
+}
+
+/// Reloj e identificadores del launcher, guardados como estado de Tauri.
+#[derive(Clone)]
+pub struct AppClock {
+    pub clock: Arc<dyn Clock>,
+    pub ids: Arc<dyn IdGenerator>,
+}
+
+impl Default for AppClock {
+    fn default() -> Self {
+        Self {
+            clock: Arc::new(SystemClock),
+            ids: Arc::new(UuidV4Generator),
+        }
+    }
+}
+
+/// Estado `AppClock` registrado en la app, o el reloj real si no se registró ninguno.
+pub fn app_clock(app: &AppHandle) -> AppClock {
+    app.try_state::<AppClock>()
+        .map(|state| state.inner().clone())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+pub mod mock {
+    use std::sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    };
+
+    use super::*;
+
+    /// Reloj controlable para tests: solo avanza con `advance`.
+    #[derive(Debug)]
+    pub struct MockClock {
+        now: Mutex<DateTime<Utc>>,
+    }
+
+    impl MockClock {
+        pub fn at(rfc3339: &str) -> Self {
+            Self {
+                now: Mutex::new(
+                    DateTime::parse_from_rfc3339(rfc3339)
+                        .expect("fecha de test")
+                        .with_timezone(&Utc),
+                ),
+            }
+        }
+
+        pub fn advance(&self, duration: chrono::Duration) {
+            let mut now = self.now.lock().expect("mock clock");
+            *now += duration;
+        }
+    }
+
+    impl Clock for MockClock {
+        fn now(&self) -> DateTime<Utc> {
+            *self.now.lock().expect("mock clock")
+        }
+    }
+
+    /// Identificadores `id-1`, `id-2`, ... en orden.
+    #[derive(Debug, Default)]
+    pub struct SequentialIds {
+        next: AtomicU64,
+    }
+
+    impl IdGenerator for SequentialIds {
+        fn new_id(&self) -> String {
+            format!("id-{}", self.next.fetch_add(1, Ordering::Relaxed) + 1)
+        }
+    }
+}
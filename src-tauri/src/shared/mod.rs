@@ -1,5 +1,10 @@
+pub mod clock;
 pub mod constants;
 pub mod errors;
 pub mod json;
 pub mod logger;
 pub mod result;
+pub mod tasks;
+#[cfg(test)]
+pub mod test_support;
+pub mod throughput;
@@ -0,0 +1,445 @@
+// Registro común de tareas largas con progreso y cancelación.
+
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, MutexGuard,
+    },
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+use serde_json::json;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::shared::{clock::app_clock, result::AppResult, throughput::ThroughputEstimator};
+
+/// Prefijo del error de una tarea cancelada, como `TIMEOUT` en el watchdog de lanzamiento.
+pub const TASK_CANCELLED_PREFIX: &str = "CANCELLED";
+/// Intervalo mínimo entre eventos `task_progress` de una misma tarea.
+const PROGRESS_EMIT_INTERVAL: Duration = Duration::from_millis(250);
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskProgress {
+    pub completed: u64,
+    pub total: Option<u64>,
+    /// `items`, `bytes` o `percent`.
+    pub unit: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// Velocidad de los últimos segundos; solo con unidad `bytes`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bytes_per_second: Option<u64>,
+    /// Segundos restantes estimados; sin velocidad conocida o detenida no hay estimación.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eta_seconds: Option<u64>,
+    /// Más de 10 s sin avanzar.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub stalled: bool,
+}
+
+/// Velocidad global para la barra de estado y el detalle de cada tarea con progreso.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadStatus {
+    /// Suma de las tareas en `bytes` que no están detenidas.
+    pub bytes_per_second: u64,
+    pub stalled_tasks: usize,
+    pub tasks: Vec<ActiveTask>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActiveTask {
+    pub task_id: String,
+    /// Tipo de operación (`assets_download`, `redirect_cache_copy`, `java_runtime_download`...).
+    pub kind: String,
+    pub label: String,
+    pub started_at: String,
+    pub cancel_requested: bool,
+    pub progress: Option<TaskProgress>,
+}
+
+struct TaskEntry {
+    info: ActiveTask,
+    cancelled: Arc<AtomicBool>,
+    throughput: Arc<Mutex<ThroughputEstimator>>,
+}
+
+fn lock_throughput(throughput: &Mutex<ThroughputEstimator>) -> MutexGuard<'_, ThroughputEstimator> {
+    throughput
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Añade velocidad, ETA y estado detenido según el estimador en `now`.
+fn with_throughput(
+    mut progress: TaskProgress,
+    throughput: &ThroughputEstimator,
+    now: Instant,
+) -> TaskProgress {
+    let snapshot = throughput.snapshot(now, progress.total);
+    progress.bytes_per_second =
+        (progress.unit == "bytes").then(|| snapshot.per_second.round() as u64);
+    progress.eta_seconds = snapshot.eta_seconds;
+    progress.stalled = snapshot.stalled;
+    progress
+}
+
+/// Tareas en curso; vive como estado de Tauri.
+#[derive(Clone, Default)]
+pub struct TaskRegistry {
+    tasks: Arc<Mutex<HashMap<String, TaskEntry>>>,
+}
+
+impl TaskRegistry {
+    fn tasks(&self) -> MutexGuard<'_, HashMap<String, TaskEntry>> {
+        self.tasks
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    pub fn list(&self) -> Vec<ActiveTask> {
+        let mut tasks = self
+            .tasks()
+            .values()
+            .map(|entry| entry.info.clone())
+            .collect::<Vec<_>>();
+        tasks.sort_by(|a, b| a.started_at.cmp(&b.started_at));
+        tasks
+    }
+
+    /// Marca la tarea para que se detenga en su siguiente punto seguro.
+    pub fn cancel(&self, task_id: &str) -> bool {
+        let mut tasks = self.tasks();
+        let Some(entry) = tasks.get_mut(task_id) else {
+            return false;
+        };
+        entry.cancelled.store(true, Ordering::SeqCst);
+        entry.info.cancel_requested = true;
+        true
+    }
+
+    /// Tareas con progreso y su velocidad en este momento (una descarga colgada no emite
+    /// eventos, así que la detención solo se ve consultando).
+    pub fn download_status(&self) -> DownloadStatus {
+        let now = Instant::now();
+        let mut tasks = self
+            .tasks()
+            .values()
+            .filter_map(|entry| {
+                let progress = entry.info.progress.clone()?;
+                let progress = with_throughput(progress, &lock_throughput(&entry.throughput), now);
+                Some(ActiveTask {
+                    progress: Some(progress),
+                    ..entry.info.clone()
+                })
+            })
+            .collect::<Vec<_>>();
+        tasks.sort_by(|a, b| a.started_at.cmp(&b.started_at));
+        let progress = || tasks.iter().filter_map(|task| task.progress.as_ref());
+        DownloadStatus {
+            bytes_per_second: progress()
+                .filter(|progress| !progress.stalled)
+                .filter_map(|progress| progress.bytes_per_second)
+                .sum(),
+            stalled_tasks: progress().filter(|progress| progress.stalled).count(),
+            tasks,
+        }
+    }
+
+    fn set_progress(&self, task_id: &str, progress: TaskProgress) {
+        if let Some(entry) = self.tasks().get_mut(task_id) {
+            entry.info.progress = Some(progress);
+        }
+    }
+}
+
+/// Estado de Tauri, o un registro aislado si no hay ninguno (pruebas).
+pub fn task_registry(app: &AppHandle) -> TaskRegistry {
+    app.try_state::<TaskRegistry>()
+        .map(|state| state.inner().clone())
+        .unwrap_or_default()
+}
+
+pub fn cancelled_error(kind: &str) -> String {
+    format!("{TASK_CANCELLED_PREFIX}: operación '{kind}' cancelada por el usuario.")
+}
+
+pub fn is_cancelled_error(err: &str) -> bool {
+    err.starts_with(TASK_CANCELLED_PREFIX)
+}
+
+/// Vista de una tarea para comprobar la cancelación e informar progreso; se puede clonar
+/// y pasar a hilos o funciones internas sin transferir la propiedad de la tarea.
+#[derive(Clone)]
+pub struct TaskProbe {
+    registry: TaskRegistry,
+    app: Option<AppHandle>,
+    task_id: String,
+    kind: String,
+    cancelled: Arc<AtomicBool>,
+    last_emit: Arc<Mutex<Option<Instant>>>,
+    throughput: Arc<Mutex<ThroughputEstimator>>,
+}
+
+impl TaskProbe {
+    pub fn task_id(&self) -> &str {
+        &self.task_id
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Punto seguro: devuelve el error `CANCELLED` si se pidió cancelar.
+    pub fn check_cancelled(&self) -> AppResult<()> {
+        if self.is_cancelled() {
+            Err(cancelled_error(&self.kind))
+        } else {
+            Ok(())
+        }
+    }
+
+    pub fn progress(&self, completed: u64, total: Option<u64>, unit: &str) {
+        self.progress_with_message(completed, total, unit, None);
+    }
+
+    pub fn progress_with_message(
+        &self,
+        completed: u64,
+        total: Option<u64>,
+        unit: &str,
+        message: Option<String>,
+    ) {
+        let now = Instant::now();
+        let progress = {
+            let mut throughput = lock_throughput(&self.throughput);
+            throughput.record(now, completed);
+            with_throughput(
+                TaskProgress {
+                    completed,
+                    total,
+                    unit: unit.to_string(),
+                    message,
+                    bytes_per_second: None,
+                    eta_seconds: None,
+                    stalled: false,
+                },
+                &throughput,
+                now,
+            )
+        };
+        self.registry.set_progress(&self.task_id, progress.clone());
+        let Some(app) = &self.app else {
+            return;
+        };
+        let finished = total.is_some_and(|total| completed >= total);
+        {
+            let mut last_emit = self
+                .last_emit
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            let due = !last_emit.is_some_and(|last| last.elapsed() < PROGRESS_EMIT_INTERVAL);
+            if !due && !finished {
+                return;
+            }
+            *last_emit = Some(Instant::now());
+        }
+        let _ = app.emit(
+            "task_progress",
+            json!({ "taskId": self.task_id, "kind": self.kind, "progress": progress }),
+        );
+    }
+
+    /// Evento auxiliar de la tarea (avisos que no son progreso); lleva `taskId` y `kind`.
+    pub fn emit_event<S: Serialize>(&self, event: &str, payload: S) {
+        let Some(app) = &self.app else {
+            return;
+        };
+        let _ = app.emit(
+            event,
+            json!({ "taskId": self.task_id, "kind": self.kind, "payload": payload }),
+        );
+    }
+}
+
+/// Propietario de una tarea registrada; al soltarlo la tarea sale del registro.
+pub struct TaskHandle {
+    probe: TaskProbe,
+    finished: bool,
+}
+
+impl std::ops::Deref for TaskHandle {
+    type Target = TaskProbe;
+
+    fn deref(&self) -> &TaskProbe {
+        &self.probe
+    }
+}
+
+impl TaskHandle {
+    pub fn begin(app: &AppHandle, kind: &str, label: impl Into<String>) -> Self {
+        let clock = app_clock(app);
+        let label = label.into();
+        let handle = Self::register(
+            task_registry(app),
+            Some(app.clone()),
+            clock.ids.new_id(),
+            kind,
+            label.clone(),
+            clock.clock.now_rfc3339(),
+        );
+        let _ = app.emit(
+            "task_started",
+            json!({ "taskId": handle.probe.task_id, "kind": kind, "label": label }),
+        );
+        handle
+    }
+
+    /// Tarea sin app (pruebas o llamadas internas): se puede cancelar pero no emite eventos.
+    pub fn detached(kind: &str) -> Self {
+        Self::register(
+            TaskRegistry::default(),
+            None,
+            format!("{kind}-detached"),
+            kind,
+            kind.to_string(),
+            String::new(),
+        )
+    }
+
+    fn register(
+        registry: TaskRegistry,
+        app: Option<AppHandle>,
+        task_id: String,
+        kind: &str,
+        label: String,
+        started_at: String,
+    ) -> Self {
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let throughput = Arc::new(Mutex::new(ThroughputEstimator::default()));
+        registry.tasks().insert(
+            task_id.clone(),
+            TaskEntry {
+                info: ActiveTask {
+                    task_id: task_id.clone(),
+                    kind: kind.to_string(),
+                    label,
+                    started_at,
+                    cancel_requested: false,
+                    progress: None,
+                },
+                cancelled: cancelled.clone(),
+                throughput: throughput.clone(),
+            },
+        );
+        Self {
+            probe: TaskProbe {
+                registry,
+                app,
+                task_id,
+                kind: kind.to_string(),
+                cancelled,
+                last_emit: Arc::new(Mutex::new(None)),
+                throughput,
+            },
+            finished: false,
+        }
+    }
+
+    pub fn probe(&self) -> TaskProbe {
+        self.probe.clone()
+    }
+
+    /// Cierra la tarea según el resultado: `task_completed` o, si terminó por cancelación,
+    /// `task_cancelled`.
+    pub fn finish<T, E: std::fmt::Display>(mut self, result: &Result<T, E>) {
+        self.finished = true;
+        let error = result.as_ref().err().map(ToString::to_string);
+        self.emit_end(error.as_deref());
+    }
+
+    fn emit_end(&self, error: Option<&str>) {
+        self.probe.registry.tasks().remove(&self.probe.task_id);
+        let Some(app) = &self.probe.app else {
+            return;
+        };
+        let (event, payload) = match error {
+            Some(err) if is_cancelled_error(err) || self.probe.is_cancelled() => (
+                "task_cancelled",
+                json!({ "taskId": self.probe.task_id, "kind": self.probe.kind }),
+            ),
+            _ => (
+                "task_completed",
+                json!({
+                    "taskId": self.probe.task_id,
+                    "kind": self.probe.kind,
+                    "success": error.is_none(),
+                    "error": error,
+                }),
+            ),
+        };
+        let _ = app.emit(event, payload);
+    }
+}
+
+impl Drop for TaskHandle {
+    fn drop(&mut self) {
+        if !self.finished {
+            self.emit_end(Some("La tarea terminó sin informar resultado."));
+        }
+    }
+}
+
+thread_local! {
+    static CURRENT_TASK: RefCell<Option<TaskProbe>> = const { RefCell::new(None) };
+}
+
+/// Ejecuta `f` con `probe` como tarea del hilo actual, para código bloqueante que no recibe
+/// la tarea por parámetro (como `run_with_watchdog`).
+pub fn run_with_task<T>(probe: TaskProbe, f: impl FnOnce() -> T) -> T {
+    let previous = CURRENT_TASK.with(|current| current.borrow_mut().replace(probe));
+    let result = f();
+    CURRENT_TASK.with(|current| *current.borrow_mut() = previous);
+    result
+}
+
+pub fn current_task() -> Option<TaskProbe> {
+    CURRENT_TASK.with(|current| current.borrow().clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cancel_is_seen_at_the_next_safe_point_and_task_leaves_registry() {
+        let task = TaskHandle::detached("assets_download");
+        let registry = task.probe.registry.clone();
+        task.progress(3, Some(10), "items");
+        assert_eq!(registry.list()[0].progress.as_ref().unwrap().completed, 3);
+        assert!(task.check_cancelled().is_ok());
+
+        assert!(registry.cancel(task.task_id()));
+        assert!(registry.list()[0].cancel_requested);
+        let err = task.check_cancelled().unwrap_err();
+        assert!(is_cancelled_error(&err));
+
+        task.finish(&Err::<(), _>(err));
+        assert!(registry.list().is_empty());
+        assert!(!registry.cancel("assets_download-detached"));
+    }
+
+    #[test]
+    fn current_task_is_scoped_to_the_closure() {
+        let task = TaskHandle::detached("java_runtime_download");
+        assert!(current_task().is_none());
+        let seen = run_with_task(task.probe(), || current_task().map(|probe| probe.task_id));
+        assert_eq!(seen.as_deref(), Some("java_runtime_download-detached"));
+        assert!(current_task().is_none());
+    }
+}
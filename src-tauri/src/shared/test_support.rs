@@ -0,0 +1,18 @@
+// Utilidades compartidas por los tests.
+
+use std::{
+    fs,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Carpeta temporal nueva con `prefix` en el nombre; cada llamada devuelve una distinta.
+pub fn test_temp_dir(prefix: &str) -> PathBuf {
+    let nonce = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("clock")
+        .as_nanos();
+    let dir = std::env::temp_dir().join(format!("{prefix}-{nonce}"));
+    fs::create_dir_all(&dir).expect("temp dir");
+    dir
+}
@@ -0,0 +1,183 @@
+// Velocidad y tiempo restante de una tarea.
+
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+
+pub const THROUGHPUT_WINDOW: Duration = Duration::from_secs(15);
+/// Sin avance durante más de esto la tarea se considera detenida.
+pub const STALL_AFTER: Duration = Duration::from_secs(10);
+/// Un hueco así entre muestras es una pausa: la ventana se reinicia.
+const PAUSE_GAP: Duration = Duration::from_secs(3);
+/// Intervalo mínimo de la ventana para calcular una velocidad.
+const MIN_SPAN: Duration = Duration::from_millis(500);
+/// Constante de tiempo (s) de la media exponencial de la velocidad.
+const SMOOTHING_SECS: f64 = 4.0;
+
+#[derive(Debug, Clone, Copy, Default, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ThroughputSnapshot {
+    /// Unidades (bytes, ítems...) por segundo, suavizadas.
+    pub per_second: f64,
+    pub eta_seconds: Option<u64>,
+    pub stalled: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct ThroughputEstimator {
+    samples: VecDeque<(Instant, u64)>,
+    smoothed: Option<f64>,
+    last_advance: Option<Instant>,
+}
+
+impl ThroughputEstimator {
+    /// Registra el total acumulado en `now`.
+    pub fn record(&mut self, now: Instant, completed: u64) {
+        let previous = self.samples.back().copied();
+        if let Some((at, done)) = previous {
+            let restarted = completed < done;
+            let paused = now.saturating_duration_since(at) > PAUSE_GAP;
+            if restarted || paused {
+                self.samples.clear();
+            }
+            if restarted {
+                self.smoothed = None;
+            }
+        }
+        if !previous.is_some_and(|(_, done)| completed <= done) {
+            self.last_advance = Some(now);
+        }
+        self.samples.push_back((now, completed));
+        while self.samples.len() > 2
+            && self
+                .samples
+                .get(1)
+                .is_some_and(|(at, _)| now.saturating_duration_since(*at) >= THROUGHPUT_WINDOW)
+        {
+            self.samples.pop_front();
+        }
+
+        let (Some(&(first_at, first_done)), Some(&(last_at, last_done))) =
+            (self.samples.front(), self.samples.back())
+        else {
+            return;
+        };
+        let span = last_at.saturating_duration_since(first_at);
+        if span < MIN_SPAN {
+            return;
+        }
+        let raw = last_done.saturating_sub(first_done) as f64 / span.as_secs_f64();
+        let elapsed = previous
+            .map(|(at, _)| now.saturating_duration_since(at).as_secs_f64())
+            .unwrap_or_default();
+        let alpha = 1.0 - (-elapsed / SMOOTHING_SECS).exp();
+        // Antes del primer avance no hay nada que suavizar: se parte de la primera medida real.
+        self.smoothed = Some(match self.smoothed.filter(|smoothed| *smoothed > 0.0) {
+            Some(smoothed) => smoothed + alpha * (raw - smoothed),
+            None => raw,
+        });
+    }
+
+    /// Estado en `now`; `total` permite calcular el ETA.
+    pub fn snapshot(&self, now: Instant, total: Option<u64>) -> ThroughputSnapshot {
+        let stalled = self
+            .last_advance
+            .is_some_and(|at| now.saturating_duration_since(at) > STALL_AFTER);
+        let per_second = if stalled {
+            0.0
+        } else {
+            self.smoothed.unwrap_or_default()
+        };
+        let completed = self.samples.back().map_or(0, |(_, done)| *done);
+        let eta_seconds = total
+            .filter(|_| per_second > 0.0)
+            .map(|total| (total.saturating_sub(completed) as f64 / per_second).ceil() as u64);
+        ThroughputSnapshot {
+            per_second,
+            eta_seconds,
+            stalled,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MB: u64 = 1024 * 1024;
+
+    fn feed(
+        estimator: &mut ThroughputEstimator,
+        start: Instant,
+        timeline: impl IntoIterator<Item = (u64, u64)>,
+    ) {
+        for (millis, completed) in timeline {
+            estimator.record(start + Duration::from_millis(millis), completed);
+        }
+    }
+
+    #[test]
+    fn steady_and_bursty_transfers_give_stable_eta() {
+        let start = Instant::now();
+        let total = 100 * MB;
+
+        let mut steady = ThroughputEstimator::default();
+        feed(
+            &mut steady,
+            start,
+            (0..=40).map(|tick| (tick * 250, tick * MB / 4)),
+        );
+        let snapshot = steady.snapshot(start + Duration::from_secs(10), Some(total));
+        assert!((snapshot.per_second - MB as f64).abs() < MB as f64 * 0.05);
+        assert!((85..=95).contains(&snapshot.eta_seconds.unwrap()));
+        assert!(!snapshot.stalled);
+
+        // 4 MB de golpe cada 2 s: misma media de 2 MB/s aunque llegue a ráfagas.
+        let mut bursty = ThroughputEstimator::default();
+        let mut etas = Vec::new();
+        for tick in 0..=40_u64 {
+            let completed = (tick / 8) * 4 * MB;
+            bursty.record(start + Duration::from_millis(tick * 250), completed);
+            if tick >= 16 {
+                let now = start + Duration::from_millis(tick * 250);
+                etas.push(bursty.snapshot(now, Some(total)).eta_seconds.unwrap());
+            }
+        }
+        let (min, max) = (etas.iter().min().unwrap(), etas.iter().max().unwrap());
+        assert!(*min >= 30 && *max <= 75, "ETA fuera de rango: {etas:?}");
+    }
+
+    #[test]
+    fn stall_is_reported_and_resume_starts_a_fresh_window() {
+        let start = Instant::now();
+        let mut estimator = ThroughputEstimator::default();
+        feed(
+            &mut estimator,
+            start,
+            (0..=20).map(|tick| (tick * 500, tick * MB)),
+        );
+
+        let paused = estimator.snapshot(start + Duration::from_secs(15), Some(100 * MB));
+        assert!(!paused.stalled);
+        let stalled = estimator.snapshot(start + Duration::from_secs(21), Some(100 * MB));
+        assert!(stalled.stalled);
+        assert_eq!(stalled.per_second, 0.0);
+        assert_eq!(stalled.eta_seconds, None);
+
+        // Tras 30 s en pausa vuelve a 2 MB/s: la velocidad no arrastra el hueco.
+        let resume = 40_000;
+        feed(
+            &mut estimator,
+            start,
+            (0..=4).map(|tick| (resume + tick * 500, 20 * MB + tick * MB)),
+        );
+        let resumed =
+            estimator.snapshot(start + Duration::from_millis(resume + 2000), Some(100 * MB));
+        assert!(!resumed.stalled);
+        assert!(resumed.per_second > MB as f64);
+        assert!(resumed.eta_seconds.unwrap() < 60);
+    }
+}
@@ -133,6 +133,7 @@ type LaunchValidationResult = {
     minecraftAccessTokenExpiresAt?: number | null
     microsoftRefreshToken?: string | null
     premiumVerified: boolean
+    persisted?: boolean
   }
 }
 
@@ -2505,6 +2506,7 @@ function App() {
           microsoftRefreshToken: authSession.microsoftRefreshToken,
           premiumVerified: authSession.premiumVerified,
         },
+        persistRefreshedSession: true,
       })
 
       const refreshedSession: AuthSession = {